@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+pragma solidity ^0.7.0;
+
+pragma experimental ABIEncoderV2;
+
+import "./IERC20.sol";
+import "./ReentrancyGuard.sol";
+import "./SafeMath.sol";
+
+/// @notice Part of the zkSync contract interface used by the router
+interface IZkSyncDeposits {
+    function firstPriorityRequestId() external view returns (uint64);
+
+    function totalOpenPriorityRequests() external view returns (uint64);
+
+    function depositETH(address _zkSyncAddress) external payable;
+
+    function depositERC20(
+        IERC20 _token,
+        uint104 _amount,
+        address _zkSyncAddress
+    ) external;
+}
+
+/// @title Deposits carrying the call executed on L2 once the deposit is credited
+/// @notice Funds are always deposited to the sender of the transaction. The call is a list of the L2 transactions
+/// of the sender signed in advance (e.g. forwarding the funds to another address), which the server executes
+/// right after the deposit.
+contract DepositRouter is ReentrancyGuard {
+    using SafeMath for uint256;
+
+    IZkSyncDeposits public immutable zkSync;
+
+    /// @notice Event emitted right after the deposit with the given priority request serial id
+    event DepositCall(uint64 indexed serialId, address indexed owner, bytes call);
+
+    constructor(IZkSyncDeposits _zkSync) {
+        initializeReentrancyGuard();
+        zkSync = _zkSync;
+    }
+
+    /// @notice Deposits ETH to the sender's L2 account and registers the call
+    /// @param _call Encoded L2 transactions executed after the deposit
+    function depositETHWithCall(bytes calldata _call) external payable nonReentrant {
+        uint64 serialId = nextSerialId();
+        zkSync.depositETH{value: msg.value}(msg.sender);
+        emit DepositCall(serialId, msg.sender, _call);
+    }
+
+    /// @notice Deposits ERC20 token to the sender's L2 account and registers the call
+    /// @param _token Token address, the router has to be approved to spend the amount
+    /// @param _amount Token amount
+    /// @param _call Encoded L2 transactions executed after the deposit
+    function depositERC20WithCall(
+        IERC20 _token,
+        uint104 _amount,
+        bytes calldata _call
+    ) external nonReentrant {
+        // Tokens may charge the transfer fees, so only the received amount is deposited
+        uint256 balanceBefore = _token.balanceOf(address(this));
+        _token.transferFrom(msg.sender, address(this), _amount);
+        uint256 received = _token.balanceOf(address(this)).sub(balanceBefore);
+        require(received <= type(uint104).max, "C"); // deposit amount is too big
+        require(_token.approve(address(zkSync), received), "a"); // token approval failed
+
+        uint64 serialId = nextSerialId();
+        zkSync.depositERC20(_token, uint104(received), msg.sender);
+        emit DepositCall(serialId, msg.sender, _call);
+    }
+
+    function nextSerialId() internal view returns (uint64) {
+        return zkSync.firstPriorityRequestId() + zkSync.totalOpenPriorityRequests();
+    }
+}
@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+pragma solidity ^0.7.0;
+
+pragma experimental ABIEncoderV2;
+
+import "./Ownable.sol";
+import "./ReentrancyGuard.sol";
+
+/// @notice Wrapped ETH token interface
+interface IWETH {
+    function deposit() external payable;
+
+    function withdraw(uint256 _amount) external;
+
+    function balanceOf(address _owner) external view returns (uint256);
+
+    function transfer(address _to, uint256 _amount) external returns (bool);
+}
+
+/// @notice Part of the zkSync contract interface used by the unwrapper
+interface IZkSyncPendingBalances {
+    function withdrawPendingBalance(
+        address payable _owner,
+        address _token,
+        uint128 _amount
+    ) external;
+}
+
+/// @title Receiver of the WETH withdrawals delivered to their owners as native ETH
+/// @notice Withdrawals with the `unwrapWeth` flag are sent to this contract. Once the block is executed, the server
+/// unwraps the withdrawn amount and sends the ETH to the L2 account owner. If the owner can't receive ETH, the WETH
+/// is sent instead.
+contract WethUnwrapper is Ownable, ReentrancyGuard {
+    IWETH public immutable weth;
+
+    IZkSyncPendingBalances public immutable zkSync;
+
+    // This is the role of the zkSync server
+    // that will be able to unwrap the withdrawals
+    address public operator;
+
+    /// @notice Hashes of the L2 withdrawals already delivered
+    mapping(bytes32 => bool) public unwrapped;
+
+    /// @notice Event emitted once the withdrawal is delivered, `native` is false if the WETH was sent instead of ETH
+    event Unwrapped(bytes32 indexed txHash, address indexed recipient, uint128 amount, bool native);
+
+    constructor(
+        address _master,
+        address _operator,
+        IWETH _weth,
+        IZkSyncPendingBalances _zkSync
+    ) Ownable(_master) {
+        initializeReentrancyGuard();
+
+        operator = _operator;
+        weth = _weth;
+        zkSync = _zkSync;
+    }
+
+    function setOperator(address _newOperator) external {
+        requireMaster(msg.sender);
+
+        operator = _newOperator;
+    }
+
+    /// @notice Unwraps the withdrawn WETH and sends ETH to the recipient
+    /// @param _txHash Hash of the L2 withdrawal, each withdrawal is delivered once
+    /// @param _recipient Owner of the L2 account the funds were withdrawn from
+    /// @param _amount Withdrawn amount
+    function unwrap(
+        bytes32 _txHash,
+        address payable _recipient,
+        uint128 _amount
+    ) external nonReentrant {
+        require(msg.sender == operator || msg.sender == getMaster(), "u1"); // only the operator can unwrap
+        require(!unwrapped[_txHash], "u2"); // withdrawal is already delivered
+        unwrapped[_txHash] = true;
+
+        // Withdrawals are kept as the pending balance if the block was executed without completing them.
+        uint256 balance = weth.balanceOf(address(this));
+        if (balance < _amount) {
+            zkSync.withdrawPendingBalance(payable(address(this)), address(weth), uint128(_amount - balance));
+        }
+        weth.withdraw(_amount);
+
+        (bool success, ) = _recipient.call{value: _amount}("");
+        if (!success) {
+            weth.deposit{value: _amount}();
+            require(weth.transfer(_recipient, _amount), "u3"); // WETH transfer failed
+        }
+        emit Unwrapped(_txHash, _recipient, _amount, success);
+    }
+
+    // We have to use fallback instead of `receive` since the ethabi
+    // library can't decode the receive function:
+    // https://github.com/rust-ethereum/ethabi/issues/185
+    fallback() external payable {
+        require(msg.sender == address(weth), "u4"); // ETH is only received from WETH
+    }
+}
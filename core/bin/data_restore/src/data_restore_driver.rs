@@ -395,6 +395,12 @@ impl<T: Transport> DataRestoreDriver<T> {
                     if self.finite_mode && *last_verified_block == total_verified_blocks {
                         // Check if the final hash was found and panic otherwise.
                         if self.final_hash.is_some() && !final_hash_was_found {
+                            // The state restored from the contract diverged from the expected one.
+                            interactor
+                                .trip_circuit_breaker(
+                                    "Final hash was not met during the state restoring process",
+                                )
+                                .await;
                             panic!("Final hash was not met during the state restoring process");
                         }
                         break;
@@ -436,4 +436,12 @@ impl<'a> DatabaseStorageInteractor<'a> {
             .expect("Failed to retrieve maximum priority op serial id")
             .unwrap_or(0)
     }
+
+    pub async fn trip_circuit_breaker(&mut self, reason: &str) {
+        self.storage
+            .circuit_breaker_schema()
+            .trip("data_restore", reason)
+            .await
+            .expect("Failed to trip the circuit breaker")
+    }
 }
@@ -331,4 +331,9 @@ impl InMemoryStorageInteractor {
             .count();
         number_of_priority_ops as SerialId
     }
+
+    pub async fn trip_circuit_breaker(&mut self, reason: &str) {
+        // There is no server to be halted for the in-memory storage.
+        vlog::error!("Circuit breaker is tripped: {}", reason);
+    }
 }
@@ -211,6 +211,16 @@ impl StorageInteractor<'_> {
     pub async fn get_max_priority_op_serial_id(&mut self) -> SerialId {
         storage_interact!(self.get_max_priority_op_serial_id())
     }
+
+    /// Trips the circuit breaker of the server using the restored storage.
+    ///
+    /// # Arguments
+    ///
+    /// * `reason` - Description of the detected divergence
+    ///
+    pub async fn trip_circuit_breaker(&mut self, reason: &str) {
+        storage_interact!(self.trip_circuit_breaker(reason))
+    }
 }
 
 /// Returns Rollup contract event from its stored representation
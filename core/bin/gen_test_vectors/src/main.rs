@@ -0,0 +1,32 @@
+//! Generator of the test vectors for the SDKs written in other languages.
+//!
+//! Vectors are built with the encoding code of `zksync_types`, so regenerating them after
+//! the changes of the transactions or the operations keeps the SDKs in sync with the server.
+
+use std::path::PathBuf;
+
+use structopt::StructOpt;
+
+mod vectors;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zkSync test vectors generator", author = "Matter Labs")]
+#[structopt(about = "Tool for generating the test vectors of the transactions and operations")]
+struct Opt {
+    /// File to write the vectors to, they're printed if omitted.
+    #[structopt(long, short)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> std::io::Result<()> {
+    let opt = Opt::from_args();
+
+    let vectors = serde_json::to_string_pretty(&vectors::generate())?;
+    match opt.output {
+        Some(path) => std::fs::write(path, vectors + "\n"),
+        None => {
+            println!("{}", vectors);
+            Ok(())
+        }
+    }
+}
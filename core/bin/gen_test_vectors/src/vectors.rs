@@ -0,0 +1,536 @@
+//! Test vectors built from the fixed inputs with the encoding code of `zksync_types`.
+
+// External uses
+use num::BigUint;
+use serde::Serialize;
+use serde_json::Value;
+// Workspace uses
+use zksync_crypto::{
+    bellman::{pairing::ff::PrimeField, PrimeFieldRepr},
+    franklin_crypto::alt_babyjubjub::fs::FsRepr,
+    params::MIN_NFT_TOKEN_ID,
+    priv_key_from_fs, Fs, PrivateKey,
+};
+use zksync_types::{
+    block::commitment::{block_commitment_from_public_data, onchain_op_commitment},
+    operations::{
+        ChangePubKeyOp, DepositOp, ForcedExitOp, FullExitOp, MintNFTOp, NoopOp, SwapOp, TransferOp,
+        TransferToNewOp, WithdrawNFTOp, WithdrawOp,
+    },
+    tx::TimeRange,
+    AccountId, Address, BlockNumber, ChangePubKey, Deposit, ForcedExit, FullExit, MintNFT, Nonce,
+    Order, PubKeyHash, Swap, Token, TokenId, TokenKind, Transfer, Withdraw, WithdrawNFT, ZkSyncOp,
+    ZkSyncTx, H256,
+};
+
+/// Private key derived from the seed `0x000102...1f`, the same as in the SDK test vectors.
+const PRIVATE_KEY: &str = "0552a69519d1f3043611126c13489ff4a2a867a1c667b1d9d9031cd27fdcff5a";
+
+const ACCOUNT_ID: AccountId = AccountId(44);
+const RECIPIENT_ID: AccountId = AccountId(45);
+const NEW_ACCOUNT_ID: AccountId = AccountId(46);
+const NONCE: Nonce = Nonce(12);
+const NFT_TOKEN_ID: TokenId = TokenId(MIN_NFT_TOKEN_ID + 1);
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestVectors {
+    pub tx_test: TestSet<TxInput, TxOutput>,
+    pub op_test: TestSet<OpInput, OpOutput>,
+    pub block_test: TestSet<BlockInput, BlockOutput>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TestSet<I, O> {
+    pub description: String,
+    pub items: Vec<TestEntry<I, O>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TestEntry<I, O> {
+    pub inputs: I,
+    pub outputs: O,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxInput {
+    pub description: String,
+    pub private_key: String,
+    pub tx: Value,
+    pub token_symbol: String,
+    pub token_decimals: u8,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxOutput {
+    pub sign_bytes: String,
+    pub signature: Value,
+    pub tx_hash: String,
+    pub eth_sign_message: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OpInput {
+    pub description: String,
+    pub operation: Value,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpOutput {
+    pub chunks: usize,
+    pub public_data: String,
+    pub eth_witness: Option<String>,
+    pub withdrawal_data: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockInput {
+    pub description: String,
+    pub block_number: BlockNumber,
+    pub fee_account: AccountId,
+    pub old_state_hash: H256,
+    pub new_state_hash: H256,
+    pub timestamp: u64,
+    pub public_data: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockOutput {
+    pub onchain_op_commitment: String,
+    pub commitment: H256,
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn private_key() -> PrivateKey {
+    let mut fs_repr = FsRepr::default();
+    fs_repr
+        .read_be(&hex::decode(PRIVATE_KEY).unwrap()[..])
+        .unwrap();
+    priv_key_from_fs(Fs::from_repr(fs_repr).unwrap())
+}
+
+fn address(byte: u8) -> Address {
+    Address::repeat_byte(byte)
+}
+
+fn amount() -> BigUint {
+    BigUint::from(1_000_000_000_000u64)
+}
+
+fn fee() -> BigUint {
+    BigUint::from(1_000_000u32)
+}
+
+/// Time range limited on both ends, most vectors use the unbounded one.
+fn bounded_time_range() -> TimeRange {
+    TimeRange::new(1_600_000_000, 1_700_000_000)
+}
+
+/// Returns the token the Ethereum message of the transaction is formatted with.
+fn token_for(tx: &ZkSyncTx) -> Token {
+    let token_id = tx.token_id();
+    if *token_id >= MIN_NFT_TOKEN_ID {
+        Token::new_nft(token_id, &format!("NFT-{}", token_id.0))
+    } else {
+        Token::new(token_id, Address::zero(), "ETH", 18, TokenKind::ERC20)
+    }
+}
+
+/// Returns the message signed by the zkSync key, the swaps commit to the hash of the orders.
+fn sign_bytes(tx: &ZkSyncTx) -> Vec<u8> {
+    match tx {
+        ZkSyncTx::Swap(tx) => tx.get_sign_bytes(),
+        tx => tx.get_bytes(),
+    }
+}
+
+fn tx_entry(description: &str, tx: ZkSyncTx) -> TestEntry<TxInput, TxOutput> {
+    let token = token_for(&tx);
+    TestEntry {
+        inputs: TxInput {
+            description: description.to_string(),
+            private_key: format!("0x{}", PRIVATE_KEY),
+            tx: serde_json::to_value(&tx).unwrap(),
+            token_symbol: token.symbol.clone(),
+            token_decimals: token.decimals,
+        },
+        outputs: TxOutput {
+            sign_bytes: encode_hex(&sign_bytes(&tx)),
+            signature: serde_json::to_value(tx.signature()).unwrap(),
+            tx_hash: encode_hex(tx.hash().as_ref()),
+            eth_sign_message: tx.get_ethereum_sign_message(token),
+        },
+    }
+}
+
+fn op_entry(description: &str, op: ZkSyncOp) -> TestEntry<OpInput, OpOutput> {
+    TestEntry {
+        inputs: OpInput {
+            description: description.to_string(),
+            operation: serde_json::to_value(&op).unwrap(),
+        },
+        outputs: OpOutput {
+            chunks: op.chunks(),
+            public_data: encode_hex(&op.public_data()),
+            eth_witness: op.eth_witness().map(|witness| encode_hex(&witness)),
+            withdrawal_data: op.withdrawal_data().map(|data| encode_hex(&data)),
+        },
+    }
+}
+
+fn transfer(to: Address, fee: BigUint, time_range: TimeRange) -> Transfer {
+    Transfer::new_signed(
+        ACCOUNT_ID,
+        address(1),
+        to,
+        TokenId(0),
+        amount(),
+        fee,
+        NONCE,
+        time_range,
+        &private_key(),
+    )
+    .unwrap()
+}
+
+fn order(account_id: AccountId, recipient: Address, tokens: (u32, u32)) -> Order {
+    Order::new_signed(
+        account_id,
+        recipient,
+        NONCE,
+        TokenId(tokens.0),
+        TokenId(tokens.1),
+        (BigUint::from(1u32), BigUint::from(2u32)),
+        amount(),
+        TimeRange::default(),
+        &private_key(),
+    )
+    .unwrap()
+}
+
+/// Returns the signed transactions along with the operations executing them.
+fn operations() -> Vec<(&'static str, Option<ZkSyncTx>, ZkSyncOp)> {
+    let private_key = private_key();
+    let mut operations = Vec::new();
+
+    let tx = transfer(address(2), fee(), TimeRange::default());
+    operations.push((
+        "Transfer to the existing account",
+        Some(tx.clone().into()),
+        TransferOp {
+            tx,
+            from: ACCOUNT_ID,
+            to: RECIPIENT_ID,
+        }
+        .into(),
+    ));
+    let tx = transfer(address(3), fee(), TimeRange::default());
+    operations.push((
+        "Transfer creating the recipient account",
+        Some(tx.clone().into()),
+        TransferToNewOp {
+            tx,
+            from: ACCOUNT_ID,
+            to: NEW_ACCOUNT_ID,
+        }
+        .into(),
+    ));
+    let tx = transfer(address(2), BigUint::from(0u32), bounded_time_range());
+    operations.push((
+        "Transfer without the fee valid in the bounded time range",
+        Some(tx.clone().into()),
+        TransferOp {
+            tx,
+            from: ACCOUNT_ID,
+            to: RECIPIENT_ID,
+        }
+        .into(),
+    ));
+
+    let tx = Withdraw::new_signed(
+        ACCOUNT_ID,
+        address(1),
+        address(2),
+        TokenId(0),
+        amount(),
+        fee(),
+        NONCE,
+        TimeRange::default(),
+        &private_key,
+    )
+    .unwrap();
+    operations.push((
+        "Withdrawal to the L1 address",
+        Some(tx.clone().into()),
+        WithdrawOp {
+            tx,
+            account_id: ACCOUNT_ID,
+        }
+        .into(),
+    ));
+
+    let tx = ForcedExit::new_signed(
+        ACCOUNT_ID,
+        address(2),
+        TokenId(0),
+        fee(),
+        NONCE,
+        TimeRange::default(),
+        &private_key,
+    )
+    .unwrap();
+    operations.push((
+        "Forced exit of the account without the signing key",
+        Some(tx.clone().into()),
+        ForcedExitOp {
+            tx,
+            target_account_id: RECIPIENT_ID,
+            withdraw_amount: Some(amount().into()),
+        }
+        .into(),
+    ));
+
+    let tx = ChangePubKey::new_signed(
+        ACCOUNT_ID,
+        address(1),
+        PubKeyHash::from_privkey(&private_key),
+        TokenId(0),
+        fee(),
+        NONCE,
+        TimeRange::default(),
+        None,
+        &private_key,
+    )
+    .unwrap();
+    operations.push((
+        "Change of the signing key authorized onchain",
+        Some(tx.clone().into()),
+        ChangePubKeyOp {
+            tx,
+            account_id: ACCOUNT_ID,
+        }
+        .into(),
+    ));
+
+    let tx = MintNFT::new_signed(
+        ACCOUNT_ID,
+        address(1),
+        H256::repeat_byte(0xaa),
+        address(2),
+        fee(),
+        TokenId(0),
+        NONCE,
+        &private_key,
+    )
+    .unwrap();
+    operations.push((
+        "Mint of the NFT to the existing account",
+        Some(tx.clone().into()),
+        MintNFTOp {
+            tx,
+            creator_account_id: ACCOUNT_ID,
+            recipient_account_id: RECIPIENT_ID,
+        }
+        .into(),
+    ));
+
+    let tx = WithdrawNFT::new_signed(
+        ACCOUNT_ID,
+        address(1),
+        address(2),
+        NFT_TOKEN_ID,
+        TokenId(0),
+        fee(),
+        NONCE,
+        TimeRange::default(),
+        &private_key,
+    )
+    .unwrap();
+    operations.push((
+        "Withdrawal of the NFT",
+        Some(tx.clone().into()),
+        WithdrawNFTOp {
+            tx,
+            creator_id: RECIPIENT_ID,
+            creator_address: address(2),
+            serial_id: 1,
+            content_hash: H256::repeat_byte(0xaa),
+        }
+        .into(),
+    ));
+
+    let tx = Swap::new_signed(
+        ACCOUNT_ID,
+        address(1),
+        NONCE,
+        (
+            order(ACCOUNT_ID, address(1), (0, 1)),
+            order(RECIPIENT_ID, address(2), (1, 0)),
+        ),
+        (amount(), amount()),
+        fee(),
+        TokenId(0),
+        &private_key,
+    )
+    .unwrap();
+    operations.push((
+        "Swap of the orders submitted by the first party",
+        Some(tx.clone().into()),
+        SwapOp {
+            tx,
+            submitter: ACCOUNT_ID,
+            accounts: (ACCOUNT_ID, RECIPIENT_ID),
+            recipients: (ACCOUNT_ID, RECIPIENT_ID),
+        }
+        .into(),
+    ));
+
+    operations.push((
+        "Deposit to the existing account",
+        None,
+        DepositOp {
+            priority_op: Deposit {
+                from: address(1),
+                token: TokenId(0),
+                amount: amount(),
+                to: address(2),
+            },
+            account_id: RECIPIENT_ID,
+        }
+        .into(),
+    ));
+    operations.push((
+        "Full exit of the fungible token",
+        None,
+        FullExitOp {
+            priority_op: FullExit {
+                account_id: ACCOUNT_ID,
+                eth_address: address(1),
+                token: TokenId(0),
+                is_legacy: false,
+            },
+            withdraw_amount: Some(amount().into()),
+            creator_account_id: None,
+            creator_address: None,
+            serial_id: None,
+            content_hash: None,
+        }
+        .into(),
+    ));
+    operations.push((
+        "Full exit of the NFT",
+        None,
+        FullExitOp {
+            priority_op: FullExit {
+                account_id: ACCOUNT_ID,
+                eth_address: address(1),
+                token: NFT_TOKEN_ID,
+                is_legacy: false,
+            },
+            withdraw_amount: Some(BigUint::from(1u32).into()),
+            creator_account_id: Some(RECIPIENT_ID),
+            creator_address: Some(address(2)),
+            serial_id: Some(1),
+            content_hash: Some(H256::repeat_byte(0xaa)),
+        }
+        .into(),
+    ));
+    operations.push(("Operation filling the block", None, NoopOp {}.into()));
+
+    operations
+}
+
+pub fn generate() -> TestVectors {
+    let operations = operations();
+
+    let tx_items = operations
+        .iter()
+        .filter_map(|(description, tx, _)| Some(tx_entry(description, tx.clone()?)))
+        .collect();
+    let op_items = operations
+        .iter()
+        .map(|(description, _, op)| op_entry(description, op.clone()))
+        .collect();
+
+    let public_data: Vec<u8> = operations
+        .iter()
+        .flat_map(|(_, _, op)| op.public_data())
+        .collect();
+    let block = BlockInput {
+        description: "Block with all the operations above".to_string(),
+        block_number: BlockNumber(1),
+        fee_account: AccountId(0),
+        old_state_hash: H256::repeat_byte(0x01),
+        new_state_hash: H256::repeat_byte(0x02),
+        timestamp: 1_600_000_000,
+        public_data: encode_hex(&public_data),
+    };
+    let outputs = BlockOutput {
+        onchain_op_commitment: encode_hex(&onchain_op_commitment(&public_data).unwrap()),
+        commitment: block_commitment_from_public_data(
+            block.block_number,
+            block.fee_account,
+            block.old_state_hash,
+            block.new_state_hash,
+            block.timestamp,
+            &public_data,
+        )
+        .unwrap(),
+    };
+
+    TestVectors {
+        tx_test: TestSet {
+            description: "Contains the signed transactions with their messages to sign and hashes"
+                .to_string(),
+            items: tx_items,
+        },
+        op_test: TestSet {
+            description: "Contains the operations with their public data".to_string(),
+            items: op_items,
+        },
+        block_test: TestSet {
+            description: "Contains the block with its commitment".to_string(),
+            items: vec![TestEntry {
+                inputs: block,
+                outputs,
+            }],
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that the vectors are consistent with the decoding code, so they aren't generated
+    /// from the invalid inputs.
+    #[test]
+    fn vectors_are_decodable() {
+        for (description, tx, op) in operations() {
+            let public_data = op.public_data();
+            assert_eq!(
+                public_data.len(),
+                op.chunks() * zksync_crypto::params::CHUNK_BYTES
+            );
+            let decoded = ZkSyncOp::from_public_data(&public_data).unwrap();
+            assert_eq!(decoded.public_data(), public_data, "{}", description);
+
+            if let Some(tx) = tx {
+                assert!(
+                    tx.signature().verify_musig(&sign_bytes(&tx)).is_some(),
+                    "{}",
+                    description
+                );
+            }
+        }
+        generate();
+    }
+}
@@ -12,6 +12,7 @@
 
 mod recursive_keys;
 mod sample_proofs;
+mod sealing_key;
 mod verifier_contract_generator;
 mod zksync_key;
 
@@ -21,6 +22,7 @@ use crate::recursive_keys::{
     count_gates_recursive_verification_keys, make_recursive_verification_keys,
 };
 use crate::sample_proofs::make_sample_proofs;
+use crate::sealing_key::make_sealing_key_shares;
 use crate::verifier_contract_generator::create_verifier_contract;
 use crate::zksync_key::{
     calculate_and_print_max_zksync_main_circuit_size, make_plonk_blocks_verify_keys,
@@ -36,6 +38,15 @@ enum Command {
     Contract,
     /// Counts available sizes (chunks and aggregated proof size) for available setups
     CircuitSize,
+    /// Generate the sealing key of the encrypted mempool and split it between the keyholders
+    SealingKey {
+        /// Minimum number of the keyholders required to decrypt a blob
+        #[structopt(long)]
+        threshold: usize,
+        /// Number of the keyholders
+        #[structopt(long)]
+        keyholders: usize,
+    },
 }
 
 #[derive(StructOpt)]
@@ -65,5 +76,11 @@ fn main() {
             calculate_and_print_max_zksync_main_circuit_size();
             count_gates_recursive_verification_keys();
         }
+        Command::SealingKey {
+            threshold,
+            keyholders,
+        } => {
+            make_sealing_key_shares(threshold, keyholders);
+        }
     }
 }
@@ -0,0 +1,19 @@
+//! Generates the sealing key of the encrypted mempool split between the keyholders.
+//! The key itself is never printed, only its public key and the shares.
+
+use zksync_types::{
+    encrypted_tx::{sealing_public_key, split_sealing_key},
+    H256,
+};
+
+pub fn make_sealing_key_shares(threshold: usize, keyholders: usize) {
+    let sealing_key = H256::random();
+    let public_key = sealing_public_key(&sealing_key).expect("Failed to generate the sealing key");
+    let shares = split_sealing_key(&sealing_key, threshold, keyholders)
+        .expect("Failed to split the sealing key");
+
+    println!("Sealing public key: {:#x}", public_key);
+    for share in shares {
+        println!("Key share {}: {:#x}", share.index, share.key);
+    }
+}
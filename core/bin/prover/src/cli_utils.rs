@@ -14,7 +14,9 @@ fn api_client_from_env() -> client::ApiClient {
     let server_api_url = parse_env("API_PROVER_URL");
     let request_timout = Duration::from_secs(parse_env::<u64>("PROVER_PROVER_REQUEST_TIMEOUT"));
     let secret = get_env("API_PROVER_SECRET_AUTH");
+    let payload_compression = parse_env("PROVER_PROVER_PAYLOAD_COMPRESSION");
     client::ApiClient::new(&server_api_url, request_timout, &secret)
+        .with_payload_compression(payload_compression)
 }
 
 #[derive(StructOpt)]
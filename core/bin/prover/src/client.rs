@@ -12,6 +12,10 @@ use crate::auth_utils::AuthTokenGenerator;
 use zksync_prover_utils::api::{
     ProverInputRequest, ProverInputResponse, ProverOutputRequest, ProverStopped, WorkingOn,
 };
+use zksync_prover_utils::payload::{
+    decode_payload, EncodedPayload, PayloadCompression, CONTENT_HASH_HEADER,
+    PAYLOAD_COMPRESSION_HEADER,
+};
 
 #[derive(Debug, Clone)]
 pub struct ApiClient {
@@ -23,6 +27,8 @@ pub struct ApiClient {
     http_client: reqwest::Client,
     // A generator that create the authentication token upon request to any endpoint.
     auth_token_generator: AuthTokenGenerator,
+    // Compression of the jobs requested from the server and of the published proofs.
+    payload_compression: PayloadCompression,
 }
 
 impl ApiClient {
@@ -43,9 +49,16 @@ impl ApiClient {
             stopped_url: base_url.join("/stopped").unwrap(),
             http_client,
             auth_token_generator,
+            payload_compression: PayloadCompression::None,
         }
     }
 
+    /// Sets the compression of the payloads exchanged with the server.
+    pub fn with_payload_compression(mut self, payload_compression: PayloadCompression) -> Self {
+        self.payload_compression = payload_compression;
+        self
+    }
+
     /// Repeats the function execution on the exponential backoff principle.
     async fn with_retries<I, E, Fn, Fut>(&self, operation: Fn) -> anyhow::Result<I>
     where
@@ -101,6 +114,10 @@ impl crate::ApiClient for ApiClient {
                 .http_client
                 .get(self.get_job_url.clone())
                 .bearer_auth(&self.get_encoded_token()?)
+                .header(
+                    PAYLOAD_COMPRESSION_HEADER,
+                    self.payload_compression.as_str(),
+                )
                 .json(&req)
                 .send()
                 .await
@@ -110,10 +127,26 @@ impl crate::ApiClient for ApiClient {
                 return Err(Permanent(format_err!("authorization error")));
             }
 
-            response
-                .json()
+            // Servers not supporting the compression send plain JSON without the headers.
+            let header = |name: &str| {
+                response
+                    .headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string)
+            };
+            let compression =
+                PayloadCompression::from_header(header(PAYLOAD_COMPRESSION_HEADER).as_deref())
+                    .map_err(|e| Permanent(e.into()))?;
+            let content_hash = header(CONTENT_HASH_HEADER);
+            let body = response
+                .bytes()
                 .await
-                .map_err(|e| Transient(format_err!("failed parse json on get job request: {}", e)))
+                .map_err(|e| Transient(format_err!("failed to receive the job: {}", e)))?;
+            // Corrupted payload is requested again.
+            decode_payload(&body, compression, content_hash.as_deref())
+                .map(|(job, _)| job)
+                .map_err(|e| Transient(format_err!("failed to decode the job: {}", e)))
         };
 
         self.with_retries(operation).await
@@ -150,14 +183,23 @@ impl crate::ApiClient for ApiClient {
     }
 
     async fn publish(&self, data: ProverOutputRequest) -> anyhow::Result<()> {
+        let payload = EncodedPayload::encode(&data, self.payload_compression)?;
         let operation = || async {
             log::trace!("Trying publish proof: {:?}", data);
 
-            let response = self
+            let mut request = self
                 .http_client
                 .post(self.publish_url.clone())
-                .bearer_auth(&self.get_encoded_token()?)
-                .json(&data)
+                .bearer_auth(&self.get_encoded_token()?);
+            if self.payload_compression == PayloadCompression::None {
+                // Servers not supporting the compression accept JSON only.
+                request = request.header(reqwest::header::CONTENT_TYPE, "application/json");
+            }
+            for (name, value) in payload.headers() {
+                request = request.header(name, value);
+            }
+            let response = request
+                .body(payload.body.clone())
                 .send()
                 .await
                 .map_err(|e| Transient(format_err!("failed to send publish request: {}", e)))?;
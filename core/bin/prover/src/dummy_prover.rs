@@ -1,9 +1,10 @@
-use crate::{ProverConfig, ProverImpl};
+use crate::{ProofSystem, ProverConfig, ProverImpl};
 use anyhow::Error;
 use zksync_config::ZkSyncConfig;
 use zksync_crypto::proof::PrecomputedSampleProofs;
 use zksync_prover_utils::api::{JobRequestData, JobResultData};
 use zksync_prover_utils::fs_utils::load_precomputed_proofs;
+use zksync_types::prover::ProofSystemVersion;
 
 #[derive(Debug)]
 pub struct DummyProverConfig {
@@ -34,6 +35,13 @@ impl ProverImpl for DummyProver {
                 .expect("Failed to load precomputed proofs"),
         }
     }
+}
+
+/// Creates the fake proofs accepted by the dummy verifier of the PLONK contract.
+impl ProofSystem for DummyProver {
+    fn version(&self) -> ProofSystemVersion {
+        ProofSystemVersion::PlonkV1
+    }
 
     fn create_proof(&self, data: JobRequestData) -> Result<JobResultData, Error> {
         let empty_proof = match data {
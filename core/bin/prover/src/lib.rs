@@ -24,6 +24,7 @@ use zksync_prover_utils::api::{
     JobRequestData, JobResultData, ProverInputRequest, ProverInputRequestAuxData,
     ProverInputResponse, ProverOutputRequest,
 };
+use zksync_types::prover::ProofSystemVersion;
 
 const ABSENT_PROVER_ID: i32 = -1;
 
@@ -71,10 +72,20 @@ pub trait ProverConfig {
     fn from_env() -> Self;
 }
 
+/// Proof system backend creating the block proofs. Backends for the experimental proof systems
+/// are developed side-by-side with the production one, and their proofs are tagged with the
+/// backend version, so the server stores them apart and sends them to the matching verifier.
+pub trait ProofSystem {
+    /// Version the created proofs are tagged with.
+    fn version(&self) -> ProofSystemVersion;
+    /// Resource heavy operation
+    fn create_proof(&self, data: JobRequestData) -> anyhow::Result<JobResultData>;
+}
+
 /// Trait that tries to separate prover from networking (API)
 /// It is still assumed that prover will use ApiClient methods to fetch data from server, but it
 /// allows to use common code for all provers (like sending heartbeats, registering prover, etc.)
-pub trait ProverImpl {
+pub trait ProverImpl: ProofSystem {
     /// Config concrete type used by current prover.
     type Config: ProverConfig;
     /// Creates prover from config and API client.
@@ -83,8 +94,6 @@ pub trait ProverImpl {
         Default::default()
         // TODO: Add the ability to define different config (ZKS-283).
     }
-    /// Resource heavy operation
-    fn create_proof(&self, data: JobRequestData) -> anyhow::Result<JobResultData>;
 }
 #[async_trait::async_trait]
 pub trait ApiClient: Debug {
@@ -222,6 +231,7 @@ pub async fn prover_work_cycle<PROVER, CLIENT>(
                 first_block,
                 last_block,
                 data: proof,
+                proof_system: prover.version(),
             })
             .await
             .map_err(|e| vlog::warn!("Failed to publish proof: {}", e))
@@ -9,9 +9,10 @@ use zksync_prover_utils::api::{JobRequestData, JobResultData};
 use zksync_prover_utils::{PlonkVerificationKey, SetupForStepByStepProver};
 use zksync_utils::parse_env;
 // Local deps
-use crate::{ProverConfig, ProverImpl};
+use crate::{ProofSystem, ProverConfig, ProverImpl};
 use tokio::time::Instant;
 use zksync_prover_utils::fs_utils::load_precomputed_proofs;
+use zksync_types::prover::ProofSystemVersion;
 
 /// We prepare some data before making proof for each block size, so we cache it in case next block
 /// would be of our size
@@ -135,8 +136,10 @@ impl PlonkStepByStepProver {
     }
 }
 
-impl ProverImpl for PlonkStepByStepProver {
-    type Config = PlonkStepByStepProverConfig;
+impl ProofSystem for PlonkStepByStepProver {
+    fn version(&self) -> ProofSystemVersion {
+        ProofSystemVersion::PlonkV1
+    }
 
     fn create_proof(&self, data: JobRequestData) -> Result<JobResultData, anyhow::Error> {
         let proof = match data {
@@ -172,6 +175,10 @@ impl ProverImpl for PlonkStepByStepProver {
 
         Ok(proof)
     }
+}
+
+impl ProverImpl for PlonkStepByStepProver {
+    type Config = PlonkStepByStepProverConfig;
 
     fn create_from_config(config: PlonkStepByStepProverConfig) -> Self {
         assert!(!config.block_sizes.is_empty());
@@ -57,10 +57,12 @@ impl Default for MockProverConfigs {
                 cycle_wait: 500,
                 request_timeout: 1,
                 die_after_proof: false,
+                payload_compression: "none".into(),
             },
             core: zksync_config::configs::prover::Core {
                 gone_timeout: 2,
                 idle_provers: 1,
+                backlog_drain_time: 3600,
             },
             witness_generator: zksync_config::configs::prover::WitnessGenerator {
                 prepare_data_interval: 5000,
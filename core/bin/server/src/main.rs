@@ -1,6 +1,12 @@
-use futures::{channel::mpsc, executor::block_on, SinkExt, StreamExt};
+use futures::{
+    channel::mpsc,
+    executor::block_on,
+    future::{self, Either},
+    SinkExt, StreamExt,
+};
 use std::cell::RefCell;
 use std::str::FromStr;
+use std::time::Duration;
 
 use structopt::StructOpt;
 
@@ -16,14 +22,24 @@ use zksync_witness_generator::run_prover_server;
 use tokio::task::JoinHandle;
 use zksync_config::configs::api::{PrivateApiConfig, PrometheusConfig, TokenConfig};
 use zksync_config::{
-    configs::api::{CommonApiConfig, JsonRpcConfig, ProverApiConfig, RestApiConfig, Web3Config},
+    configs::api::{
+        CommonApiConfig, GrpcConfig, JsonRpcConfig, ProverApiConfig, RestApiConfig, Web3Config,
+    },
     ChainConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
-    ForcedExitRequestsConfig, GatewayWatcherConfig, ProverConfig, TickerConfig, ZkSyncConfig,
+    ForcedExitRequestsConfig, GatewayWatcherConfig, ProverConfig, TickerConfig, TreasuryConfig,
+    ZkSyncConfig,
+};
+use zksync_core::{
+    leadership::{acquire_leadership, run_leadership_watchdog, Leadership},
+    rejected_tx_cleaner::run_rejected_tx_cleaner,
+    treasury::run_treasury_task,
 };
-use zksync_core::rejected_tx_cleaner::run_rejected_tx_cleaner;
-use zksync_mempool::run_mempool_tx_handler;
+use zksync_crypto::merkle_tree::set_hash_backend;
+use zksync_mempool::{run_mempool_tx_handler, MempoolTransactionRequest};
 use zksync_prometheus_exporter::{run_operation_counter, run_prometheus_exporter};
 use zksync_storage::ConnectionPool;
+use zksync_types::Address;
+use zksync_utils::shutdown::{Shutdown, ShutdownParticipant};
 
 const DEFAULT_CHANNEL_CAPACITY: usize = 32_768;
 
@@ -40,6 +56,7 @@ pub enum Component {
     Web3Api,
     RpcApi,
     RpcWebSocketApi,
+    GrpcApi,
 
     // Core components
     Fetchers,
@@ -52,6 +69,24 @@ pub enum Component {
     Prometheus,
     PrometheusPeriodicMetrics,
     RejectedTaskCleaner,
+    Treasury,
+}
+
+impl Component {
+    /// Whether the component writes to the database or interacts with L1 on its own.
+    /// Such components can't be launched on the read-only nodes.
+    fn is_writer(&self) -> bool {
+        !matches!(
+            self,
+            Component::RestApi
+                | Component::Web3Api
+                | Component::RpcApi
+                | Component::RpcWebSocketApi
+                | Component::GrpcApi
+                | Component::Prometheus
+                | Component::PrometheusPeriodicMetrics
+        )
+    }
 }
 
 impl FromStr for Component {
@@ -63,6 +98,7 @@ impl FromStr for Component {
             "web3-api" => Ok(Component::Web3Api),
             "rpc-api" => Ok(Component::RpcApi),
             "rpc-websocket-api" => Ok(Component::RpcWebSocketApi),
+            "grpc-api" => Ok(Component::GrpcApi),
             "eth-sender" => Ok(Component::EthSender),
             "witness-generator" => Ok(Component::WitnessGenerator),
             "forced-exit" => Ok(Component::ForcedExit),
@@ -71,6 +107,7 @@ impl FromStr for Component {
             "core" => Ok(Component::Core),
             "rejected-task-cleaner" => Ok(Component::RejectedTaskCleaner),
             "prometheus-periodic-metrics" => Ok(Component::PrometheusPeriodicMetrics),
+            "treasury" => Ok(Component::Treasury),
             other => Err(format!("{} is not a valid component name", other)),
         }
     }
@@ -134,6 +171,7 @@ async fn main() -> anyhow::Result<()> {
         _vlog_guard = Some(vlog::init());
         ServerCommand::Launch
     };
+    select_hash_backend();
 
     if let ServerCommand::Genesis = server_mode {
         vlog::info!("Performing the server genesis initialization",);
@@ -150,36 +188,66 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Selects the configured hash backend for the state trees of the process.
+fn select_hash_backend() {
+    let requested = ChainConfig::from_env().circuit.hash_backend;
+    let selected = set_hash_backend(requested);
+    if selected == requested {
+        vlog::info!("Using the {:?} hash backend", selected);
+    } else {
+        vlog::warn!(
+            "{:?} hash backend is not supported by this build or CPU, using the {:?} one",
+            requested,
+            selected
+        );
+    }
+}
+
 async fn run_server(components: &ComponentsToRun) {
+    let read_only = CommonApiConfig::from_env().read_only;
+    if read_only {
+        let writers: Vec<_> = components.0.iter().filter(|c| c.is_writer()).collect();
+        assert!(
+            writers.is_empty(),
+            "Components {:?} can't be launched on the read-only node",
+            writers
+        );
+        vlog::info!("Running the read-only node, transactions are rejected by the API");
+    }
+
     let connection_pool = ConnectionPool::new(None);
     let read_only_connection_pool = ConnectionPool::new_readonly_pool(None);
+    // API servers and auxiliary components take the connections of the shared pool under their
+    // own quotas, so the core and the eth sender aren't starved by the bursts of their queries.
+    let db_config = DBConfig::from_env();
+    let api_connection_pool = connection_pool.with_quota("api", db_config.api_pool_quota);
     let (stop_signal_sender, mut stop_signal_receiver) = mpsc::channel(256);
+    // On the stop signal, the API servers stop accepting the transactions, and the server waits for
+    // the participants to finish their in-flight work.
+    let mut shutdown = Shutdown::new();
 
     let mut tasks = vec![];
 
     if components.0.contains(&Component::Web3Api) {
         // Run web3 api
         tasks.push(zksync_api::api_server::web3::start_rpc_server(
-            connection_pool.clone(),
+            api_connection_pool.clone(),
             &Web3Config::from_env(),
             &TokenConfig::from_env(),
         ));
     }
 
-    if components.0.contains(&Component::Fetchers) {
-        // Run price fetchers
-        let mut price_tasks = run_price_updaters(connection_pool.clone());
-        tasks.append(&mut price_tasks);
-    }
-
     if components.0.iter().any(|c| {
         matches!(
             c,
-            Component::RpcWebSocketApi | Component::RpcApi | Component::RestApi
+            Component::RpcWebSocketApi
+                | Component::RpcApi
+                | Component::RestApi
+                | Component::GrpcApi
         )
     }) {
         // Create gateway
-        let eth_gateway = create_eth_gateway();
+        let eth_gateway = create_eth_gateway(None);
 
         let eth_watch_config = ETHWatchConfig::from_env();
         let gateway_watcher_config = GatewayWatcherConfig::from_env();
@@ -191,19 +259,23 @@ async fn run_server(components: &ComponentsToRun) {
             tasks.push(task);
         }
 
+        let common_config = CommonApiConfig::from_env();
+
         // Run signer
         let (sign_check_sender, sign_check_receiver) = mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
         tasks.push(zksync_api::signature_checker::start_sign_checker(
-            eth_gateway,
+            eth_gateway.clone(),
             sign_check_receiver,
+            &common_config,
         ));
 
         let contracts_config = ContractsConfig::from_env();
-        let common_config = CommonApiConfig::from_env();
         let token_config = TokenConfig::from_env();
         let chain_config = ChainConfig::from_env();
         let fee_ticker_config = TickerConfig::from_env();
-        let ticker_info = Box::new(TickerInfo::new(read_only_connection_pool.clone()));
+        let ticker_info = Box::new(
+            TickerInfo::new(read_only_connection_pool.clone()).with_eth_client(eth_gateway),
+        );
 
         let ticker = FeeTicker::new_with_default_validator(
             ticker_info,
@@ -213,34 +285,34 @@ async fn run_server(components: &ComponentsToRun) {
         );
 
         if components.0.contains(&Component::RpcWebSocketApi) {
-            let (mempool_tx_request_sender, mempool_tx_request_receiver) =
-                mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
-            tasks.push(run_mempool_tx_handler(
-                connection_pool.clone(),
-                mempool_tx_request_receiver,
+            let mempool_tx_request_sender = run_api_mempool_tx_handler(
+                read_only,
+                api_connection_pool.clone(),
                 chain_config.state_keeper.block_chunk_sizes.clone(),
-            ));
+                &mut tasks,
+            );
             tasks.push(zksync_api::api_server::rpc_subscriptions::start_ws_server(
                 read_only_connection_pool.clone(),
                 sign_check_sender.clone(),
                 ticker.clone(),
                 &common_config,
                 &token_config,
+                contracts_config.contract_addr,
                 &JsonRpcConfig::from_env(),
                 chain_config.state_keeper.miniblock_iteration_interval(),
                 mempool_tx_request_sender,
                 eth_watch_config.confirmations_for_eth_event,
+                shutdown.signal(),
             ));
         }
 
         if components.0.contains(&Component::RpcApi) {
-            let (mempool_tx_request_sender, mempool_tx_request_receiver) =
-                mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
-            tasks.push(run_mempool_tx_handler(
-                connection_pool.clone(),
-                mempool_tx_request_receiver,
+            let mempool_tx_request_sender = run_api_mempool_tx_handler(
+                read_only,
+                api_connection_pool.clone(),
                 chain_config.state_keeper.block_chunk_sizes.clone(),
-            ));
+                &mut tasks,
+            );
             tasks.push(zksync_api::api_server::rpc_server::start_rpc_server(
                 read_only_connection_pool.clone(),
                 sign_check_sender.clone(),
@@ -248,39 +320,95 @@ async fn run_server(components: &ComponentsToRun) {
                 &JsonRpcConfig::from_env(),
                 &common_config,
                 &token_config,
+                contracts_config.contract_addr,
                 mempool_tx_request_sender,
                 eth_watch_config.confirmations_for_eth_event,
+                shutdown.signal(),
+            ));
+        }
+
+        if components.0.contains(&Component::GrpcApi) {
+            let mempool_tx_request_sender = run_api_mempool_tx_handler(
+                read_only,
+                api_connection_pool.clone(),
+                chain_config.state_keeper.block_chunk_sizes.clone(),
+                &mut tasks,
+            );
+            tasks.push(zksync_api::api_server::grpc::start_grpc_server(
+                read_only_connection_pool.clone(),
+                sign_check_sender.clone(),
+                ticker.clone(),
+                &GrpcConfig::from_env(),
+                &common_config,
+                &token_config,
+                contracts_config.contract_addr,
+                mempool_tx_request_sender,
+                eth_watch_config.confirmations_for_eth_event,
+                shutdown.signal(),
             ));
         }
 
         if components.0.contains(&Component::RestApi) {
-            let (mempool_tx_request_sender, mempool_tx_request_receiver) =
-                mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
-            tasks.push(run_mempool_tx_handler(
-                connection_pool.clone(),
-                mempool_tx_request_receiver,
+            let mempool_tx_request_sender = run_api_mempool_tx_handler(
+                read_only,
+                api_connection_pool.clone(),
                 chain_config.state_keeper.block_chunk_sizes,
-            ));
+                &mut tasks,
+            );
             let private_config = PrivateApiConfig::from_env();
             tasks.push(zksync_api::api_server::rest::start_server_thread_detached(
                 read_only_connection_pool.clone(),
-                connection_pool.clone(),
+                api_connection_pool.clone(),
                 RestApiConfig::from_env().bind_addr(),
                 contracts_config.contract_addr,
                 ticker,
                 sign_check_sender,
                 mempool_tx_request_sender,
                 private_config.url,
+                shutdown.signal(),
             ));
         }
     }
 
+    if components.0.contains(&Component::Prometheus) {
+        // Run prometheus data exporter.
+        let config = PrometheusConfig::from_env();
+        let prometheus_task_handle = run_prometheus_exporter(config.port);
+        tasks.push(prometheus_task_handle);
+        // We can run them only with active prometheus
+        if components.0.contains(&Component::PrometheusPeriodicMetrics) {
+            let counter_task_handle = run_operation_counter(read_only_connection_pool.clone());
+            tasks.push(counter_task_handle);
+        }
+    }
+
+    // Standby instances serve the API, and start the rest of the components once they become the
+    // leader. The stop signal isn't handled yet at this point, so the standby exits right away.
+    if db_config.leader_election && components.0.iter().any(|c| c.is_writer()) {
+        let poll_interval = db_config.leader_election_poll_interval();
+        let (leadership, running_tasks) = become_leader(tasks, poll_interval).await;
+        tasks = running_tasks;
+        tasks.push(run_leadership_watchdog(leadership, poll_interval));
+    }
+
+    if components.0.contains(&Component::Fetchers) {
+        // Run price fetchers
+        let mut price_tasks = run_price_updaters(
+            connection_pool.with_quota("fetchers", db_config.fetchers_pool_quota),
+        );
+        tasks.append(&mut price_tasks);
+    }
+
     if components.0.contains(&Component::EthSender) {
-        tasks.push(run_eth_sender(connection_pool.clone()))
+        tasks.push(run_eth_sender(
+            connection_pool.clone(),
+            shutdown.participant("eth_sender"),
+        ))
     }
 
     if components.0.contains(&Component::Core) {
-        let eth_gateway = create_eth_gateway();
+        // The core delivers the unwrapped WETH withdrawals with the operator key.
+        let eth_gateway = create_eth_gateway(CommonApiConfig::from_env().weth_unwrapper_addr);
 
         tasks.append(
             &mut run_core(
@@ -288,6 +416,7 @@ async fn run_server(components: &ComponentsToRun) {
                 read_only_connection_pool.clone(),
                 &ZkSyncConfig::from_env(),
                 eth_gateway.clone(),
+                shutdown.participant("state_keeper"),
             )
             .await
             .unwrap(),
@@ -295,28 +424,32 @@ async fn run_server(components: &ComponentsToRun) {
     }
 
     if components.0.contains(&Component::WitnessGenerator) {
-        tasks.push(run_witness_generator(connection_pool.clone()))
+        tasks.push(run_witness_generator(connection_pool.with_quota(
+            "witness_generator",
+            db_config.witness_generator_pool_quota,
+        )))
     }
 
-    if components.0.contains(&Component::Prometheus) {
-        // Run prometheus data exporter.
-        let config = PrometheusConfig::from_env();
-        let prometheus_task_handle = run_prometheus_exporter(config.port);
-        tasks.push(prometheus_task_handle);
-        // We can run them only with active prometheus
-        if components.0.contains(&Component::PrometheusPeriodicMetrics) {
-            let counter_task_handle = run_operation_counter(read_only_connection_pool.clone());
-            tasks.push(counter_task_handle);
-        }
+    if components.0.contains(&Component::ForcedExit) {
+        tasks.append(&mut run_forced_exit(
+            connection_pool.with_quota("forced_exit", db_config.forced_exit_pool_quota),
+        ));
     }
 
-    if components.0.contains(&Component::ForcedExit) {
-        tasks.append(&mut run_forced_exit(connection_pool.clone()));
+    if components.0.contains(&Component::Treasury) {
+        tasks.append(&mut run_treasury(
+            connection_pool.with_quota("treasury", db_config.treasury_pool_quota),
+        ));
     }
 
     if components.0.contains(&Component::RejectedTaskCleaner) {
-        let config = DBConfig::from_env();
-        tasks.push(run_rejected_tx_cleaner(&config, connection_pool));
+        tasks.push(run_rejected_tx_cleaner(
+            &db_config,
+            connection_pool.with_quota(
+                "rejected_tx_cleaner",
+                db_config.rejected_tx_cleaner_pool_quota,
+            ),
+        ));
     }
 
     {
@@ -336,6 +469,66 @@ async fn run_server(components: &ComponentsToRun) {
             vlog::warn!("Stop signal received, shutting down");
         }
     };
+
+    let timeout = ChainConfig::from_env().state_keeper.shutdown_timeout();
+    tokio::select! {
+        incomplete = shutdown.run(timeout) => {
+            if incomplete.is_empty() {
+                vlog::info!("Actors have finished their work, exiting");
+            } else {
+                vlog::warn!(
+                    "Actors {:?} haven't finished their work in {:?}, exiting anyway",
+                    incomplete,
+                    timeout
+                );
+            }
+        },
+        _ = async { stop_signal_receiver.next().await } => {
+            vlog::warn!("Second stop signal received, exiting without waiting for the actors");
+        }
+    };
+}
+
+/// Waits for the leadership of the instance, watching the already running components meanwhile.
+async fn become_leader(
+    tasks: Vec<JoinHandle<()>>,
+    poll_interval: Duration,
+) -> (Leadership, Vec<JoinHandle<()>>) {
+    vlog::info!("Waiting for the leadership to start the rest of the components");
+    if tasks.is_empty() {
+        return (acquire_leadership(poll_interval).await, tasks);
+    }
+
+    let leadership = Box::pin(acquire_leadership(poll_interval));
+    match future::select(leadership, future::select_all(tasks)).await {
+        Either::Left((leadership, running)) => (leadership, running.into_inner()),
+        Either::Right(((Err(err), _, _), _)) if err.is_panic() => {
+            std::panic::resume_unwind(err.into_panic())
+        }
+        Either::Right(_) => {
+            panic!("One of the actors finished its run, while it wasn't expected to do it")
+        }
+    }
+}
+
+/// Runs the mempool handler for the API component. Read-only nodes don't need it, since the
+/// transactions are rejected before reaching the mempool.
+fn run_api_mempool_tx_handler(
+    read_only: bool,
+    connection_pool: ConnectionPool,
+    block_chunk_sizes: Vec<usize>,
+    tasks: &mut Vec<JoinHandle<()>>,
+) -> mpsc::Sender<MempoolTransactionRequest> {
+    let (mempool_tx_request_sender, mempool_tx_request_receiver) =
+        mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
+    if !read_only {
+        tasks.push(run_mempool_tx_handler(
+            connection_pool,
+            mempool_tx_request_receiver,
+            block_chunk_sizes,
+        ));
+    }
+    mempool_tx_request_sender
 }
 
 pub fn run_forced_exit(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
@@ -345,6 +538,7 @@ pub fn run_forced_exit(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
     let contract_config = ContractsConfig::from_env();
     let eth_client_config = ETHClientConfig::from_env();
     let chain_config = ChainConfig::from_env();
+    let eth_gateway = EthereumGateway::read_only(&eth_client_config, contract_config.contract_addr);
 
     let (mempool_tx_request_sender, mempool_tx_request_receiver) =
         mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
@@ -359,11 +553,32 @@ pub fn run_forced_exit(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
         config,
         common_config,
         contract_config,
-        eth_client_config.web3_url(),
+        eth_gateway,
     );
     vec![mempool_task, forced_exit_task]
 }
 
+pub fn run_treasury(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
+    vlog::info!("Starting the treasury actors");
+    let config = TreasuryConfig::from_env();
+    let chain_config = ChainConfig::from_env();
+
+    let (mempool_tx_request_sender, mempool_tx_request_receiver) =
+        mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
+    let mempool_task = run_mempool_tx_handler(
+        connection_pool.clone(),
+        mempool_tx_request_receiver,
+        chain_config.state_keeper.block_chunk_sizes,
+    );
+    let treasury_task = run_treasury_task(
+        connection_pool,
+        mempool_tx_request_sender,
+        config,
+        chain_config.state_keeper.fee_account_addr,
+    );
+    vec![mempool_task, treasury_task]
+}
+
 pub fn run_witness_generator(connection_pool: ConnectionPool) -> JoinHandle<()> {
     vlog::info!("Starting the Prover server actors");
     let prover_api_config = ProverApiConfig::from_env();
@@ -372,7 +587,10 @@ pub fn run_witness_generator(connection_pool: ConnectionPool) -> JoinHandle<()>
     run_prover_server(database, prover_api_config, prover_config)
 }
 
-pub fn run_eth_sender(connection_pool: ConnectionPool) -> JoinHandle<()> {
+pub fn run_eth_sender(
+    connection_pool: ConnectionPool,
+    shutdown: ShutdownParticipant,
+) -> JoinHandle<()> {
     vlog::info!("Starting the Ethereum sender actors");
     let eth_client_config = ETHClientConfig::from_env();
     let eth_sender_config = ETHSenderConfig::from_env();
@@ -383,7 +601,7 @@ pub fn run_eth_sender(connection_pool: ConnectionPool) -> JoinHandle<()> {
         contracts.contract_addr,
     );
 
-    zksync_eth_sender::run_eth_sender(connection_pool, eth_gateway, eth_sender_config)
+    zksync_eth_sender::run_eth_sender(connection_pool, eth_gateway, eth_sender_config, shutdown)
 }
 
 pub fn run_price_updaters(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
@@ -391,13 +609,14 @@ pub fn run_price_updaters(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>
     run_updaters(connection_pool, &ticker_config)
 }
 
-pub fn create_eth_gateway() -> EthereumGateway {
+pub fn create_eth_gateway(weth_unwrapper: Option<Address>) -> EthereumGateway {
     let eth_client_config = ETHClientConfig::from_env();
     let eth_sender_config = ETHSenderConfig::from_env();
     let contracts = ContractsConfig::from_env();
-    EthereumGateway::from_config(
+    EthereumGateway::with_weth_unwrapper(
         &eth_client_config,
         &eth_sender_config,
         contracts.contract_addr,
+        weth_unwrapper,
     )
 }
@@ -0,0 +1,99 @@
+//! Offline migration of the account tree to a deeper depth.
+//!
+//! The tool re-roots the account tree of the last executed block, verifies that the deeper tree
+//! holds the same accounts and balances, and writes the report with the calldata of the contract
+//! upgrade setting the new state root. The database is not modified. The server must be stopped,
+//! with every committed block executed, so the migrated state is the one stored on the contract.
+//! The circuit and the verification keys for the new depth are not produced by the tool.
+
+use anyhow::{ensure, format_err};
+use serde_json::json;
+use structopt::StructOpt;
+use zksync_config::DBConfig;
+use zksync_storage::StorageProcessor;
+use zksync_types::block::Block;
+
+mod migration;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "tree_depth_migration",
+    about = "Tool to re-root the account tree to a deeper depth."
+)]
+struct Opt {
+    /// Depth of the migrated account tree.
+    #[structopt(long)]
+    new_depth: usize,
+    /// File to write the migration report to.
+    #[structopt(long, default_value = "tree_depth_migration.json")]
+    output: String,
+    /// Amount of the contracts managed by the upgrade gatekeeper.
+    #[structopt(long, default_value = "3")]
+    upgrade_targets: usize,
+    /// Index of the zkSync contract among the upgrade targets.
+    #[structopt(long, default_value = "2")]
+    zksync_target: usize,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+
+    println!("Database URL is {}", DBConfig::from_env().url);
+    let mut storage = StorageProcessor::establish_connection().await?;
+
+    let last_committed_block = storage
+        .chain()
+        .block_schema()
+        .get_last_committed_block()
+        .await?;
+    let (last_block, accounts) = storage.chain().state_schema().load_verified_state().await?;
+    ensure!(
+        last_committed_block == last_block,
+        "Blocks up to {} are committed, but only up to {} are executed. \
+         Stop the server and wait for every block to be executed",
+        last_committed_block,
+        last_block
+    );
+    let last_block = storage
+        .chain()
+        .block_schema()
+        .get_block(last_block)
+        .await?
+        .ok_or_else(|| format_err!("No executed blocks to migrate"))?;
+
+    println!(
+        "Migrating {} accounts of the block {} to the tree of depth {}",
+        accounts.len(),
+        last_block.block_number,
+        opt.new_depth
+    );
+    let report = migration::migrate(&accounts, opt.new_depth)?;
+    ensure!(
+        report.old_root == last_block.new_root_hash,
+        "Root of the loaded state doesn't match the root of the block {}",
+        last_block.block_number
+    );
+    let calldata =
+        migration::upgrade_calldata(&last_block, &report, opt.upgrade_targets, opt.zksync_target)?;
+
+    let total_balances: serde_json::Map<_, _> = report
+        .total_balances
+        .iter()
+        .map(|(token, balance)| (token.to_string(), json!(balance.to_string())))
+        .collect();
+    let output = json!({
+        "blockNumber": last_block.block_number,
+        "accounts": report.accounts,
+        "oldDepth": report.old_depth,
+        "newDepth": report.new_depth,
+        "oldRoot": Block::encode_fr_for_eth(report.old_root),
+        "newRoot": Block::encode_fr_for_eth(report.new_root),
+        "totalBalances": total_balances,
+        "upgradeCalldata": format!("0x{}", hex::encode(calldata)),
+    });
+    std::fs::write(&opt.output, serde_json::to_string_pretty(&output)?)?;
+
+    println!("Done, the report is written to {}", opt.output);
+    Ok(())
+}
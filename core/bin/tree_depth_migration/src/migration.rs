@@ -0,0 +1,186 @@
+//! Re-rooting of the account tree to a deeper depth.
+//!
+//! Accounts keep their IDs, so the old tree becomes the leftmost subtree of the deeper one, and
+//! the new root is the old root hashed with the roots of the empty subtrees up to the new depth.
+//! The migration builds the deeper tree from scratch and checks its root against this derivation,
+//! then checks that every account and the total balances of every token are the same in both trees.
+
+// Built-in uses
+use std::collections::BTreeMap;
+// External uses
+use anyhow::{ensure, format_err};
+use ethabi::Token;
+use num::BigUint;
+// Workspace uses
+use zksync_crypto::{
+    merkle_tree::hasher::Hasher, params::account_tree_depth, primitives::GetBits, Fr,
+};
+use zksync_types::{
+    aggregated_operations::stored_block_info, block::Block, Account, AccountMap, AccountTree,
+    Engine, RescueHasher, TokenId, U256,
+};
+
+/// Deepest tree supported by the tree implementation, which indexes the nodes with `u64`.
+pub const MAX_TREE_DEPTH: usize = 62;
+
+#[derive(Debug)]
+pub struct MigrationReport {
+    pub old_depth: usize,
+    pub new_depth: usize,
+    pub accounts: usize,
+    pub old_root: Fr,
+    pub new_root: Fr,
+    pub total_balances: BTreeMap<TokenId, BigUint>,
+}
+
+pub fn build_tree(accounts: &AccountMap, depth: usize) -> AccountTree {
+    let mut tree = AccountTree::new(depth);
+    for (id, account) in accounts {
+        tree.insert(**id, account.clone());
+    }
+    tree
+}
+
+/// Returns the root of the tree of `to_depth` having the tree with the given root as its leftmost
+/// subtree, and no other accounts.
+pub fn extend_root(root: Fr, from_depth: usize, to_depth: usize) -> Fr {
+    let hasher = RescueHasher::<Engine>::default();
+    let mut empty_subtree = hasher.hash_bits(Account::default().get_bits_le());
+    for level in 0..from_depth {
+        empty_subtree = hasher.compress(&empty_subtree, &empty_subtree, level);
+    }
+
+    let mut root = root;
+    for level in from_depth..to_depth {
+        root = hasher.compress(&root, &empty_subtree, level);
+        empty_subtree = hasher.compress(&empty_subtree, &empty_subtree, level);
+    }
+    root
+}
+
+/// Re-roots the tree of the given accounts to `new_depth`, verifying the equivalence of the trees.
+pub fn migrate(accounts: &AccountMap, new_depth: usize) -> anyhow::Result<MigrationReport> {
+    let old_depth = account_tree_depth();
+    ensure!(
+        new_depth > old_depth && new_depth <= MAX_TREE_DEPTH,
+        "New tree depth must be greater than {} and not greater than {}",
+        old_depth,
+        MAX_TREE_DEPTH
+    );
+
+    let old_tree = build_tree(accounts, old_depth);
+    let new_tree = build_tree(accounts, new_depth);
+    let old_root = old_tree.root_hash();
+    let new_root = new_tree.root_hash();
+    ensure!(
+        new_root == extend_root(old_root, old_depth, new_depth),
+        "Root of the deeper tree doesn't match the one derived from the current root"
+    );
+
+    for (id, account) in accounts {
+        let migrated = new_tree
+            .get(**id)
+            .ok_or_else(|| format_err!("Account {} is missing in the deeper tree", id))?;
+        ensure!(
+            migrated.get_bits_le() == account.get_bits_le()
+                && migrated.get_nonzero_balances() == account.get_nonzero_balances(),
+            "Account {} differs in the deeper tree",
+            id
+        );
+    }
+    let total_balances = total_balances(accounts.values().map(Some));
+    ensure!(
+        total_balances == total_balances(accounts.keys().map(|id| new_tree.get(**id))),
+        "Total balances differ in the deeper tree"
+    );
+
+    Ok(MigrationReport {
+        old_depth,
+        new_depth,
+        accounts: accounts.len(),
+        old_root,
+        new_root,
+        total_balances,
+    })
+}
+
+fn total_balances<'a>(
+    accounts: impl Iterator<Item = Option<&'a Account>>,
+) -> BTreeMap<TokenId, BigUint> {
+    let mut totals = BTreeMap::new();
+    for account in accounts.flatten() {
+        for (token, balance) in account.get_nonzero_balances() {
+            *totals.entry(token).or_insert_with(BigUint::default) += balance.0;
+        }
+    }
+    totals
+}
+
+/// Encodes the `finishUpgrade` call of the upgrade gatekeeper. The upgrade parameters of the zkSync
+/// contract are the info of the last executed block, which the contract checks against the stored
+/// block hash, the new tree depth and the new state root; the rest of the targets get no parameters.
+pub fn upgrade_calldata(
+    last_block: &Block,
+    report: &MigrationReport,
+    upgrade_targets: usize,
+    zksync_target: usize,
+) -> anyhow::Result<Vec<u8>> {
+    ensure!(
+        zksync_target < upgrade_targets,
+        "zkSync target index {} is out of the {} upgrade targets",
+        zksync_target,
+        upgrade_targets
+    );
+    let zksync_parameters = ethabi::encode(&[
+        stored_block_info(last_block),
+        Token::Uint(U256::from(report.new_depth)),
+        Token::FixedBytes(
+            Block::encode_fr_for_eth(report.new_root)
+                .as_bytes()
+                .to_vec(),
+        ),
+    ]);
+    let mut parameters = vec![Token::Bytes(Vec::new()); upgrade_targets];
+    parameters[zksync_target] = Token::Bytes(zksync_parameters);
+
+    let calldata = zksync_contracts::upgrade_gatekeeper()
+        .function("finishUpgrade")?
+        .encode_input(&[Token::Array(parameters)])?;
+    Ok(calldata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{AccountId, Address};
+
+    /// Checks that the root of the deeper tree built from scratch matches the derived one.
+    #[test]
+    fn deeper_tree_root() {
+        let mut accounts = AccountMap::default();
+        for id in [0u32, 1, 17, 1000] {
+            let mut account = Account::default_with_address(&Address::from_low_u64_be(id as u64));
+            account.set_balance(TokenId(0), 100u32.into());
+            account.set_balance(TokenId(id + 1), (id + 1).into());
+            accounts.insert(AccountId(id), account);
+        }
+
+        let new_depth = account_tree_depth() + 2;
+        let report = migrate(&accounts, new_depth).unwrap();
+        assert_eq!(report.accounts, 4);
+        assert_eq!(report.total_balances[&TokenId(0)], 400u32.into());
+        assert_eq!(report.total_balances[&TokenId(18)], 18u32.into());
+        assert_eq!(
+            report.new_root,
+            build_tree(&accounts, new_depth).root_hash()
+        );
+        assert_ne!(report.new_root, report.old_root);
+        assert_eq!(
+            extend_root(report.old_root, report.old_depth, report.old_depth),
+            report.old_root
+        );
+
+        assert!(migrate(&accounts, account_tree_depth()).is_err());
+        assert!(migrate(&accounts, MAX_TREE_DEPTH + 1).is_err());
+    }
+}
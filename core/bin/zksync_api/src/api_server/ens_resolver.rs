@@ -0,0 +1,59 @@
+//! Cached resolution of the ENS names accepted by the API instead of the addresses.
+
+// Built-in uses
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+// External uses
+use tokio::sync::RwLock;
+
+// Workspace uses
+use zksync_eth_client::{ens::resolve_ens_name, EthereumGateway};
+use zksync_types::Address;
+
+/// Time the resolved name is kept in the cache.
+const ENS_CACHE_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Default)]
+pub struct EnsResolver {
+    eth_client: Option<EthereumGateway>,
+    cache: Arc<RwLock<HashMap<String, (Option<Address>, Instant)>>>,
+}
+
+impl EnsResolver {
+    pub fn new(eth_client: EthereumGateway) -> Self {
+        Self {
+            eth_client: Some(eth_client),
+            cache: Default::default(),
+        }
+    }
+
+    /// Returns the address the name points to, `None` if the name is not resolved.
+    pub async fn resolve(&self, name: &str) -> anyhow::Result<Option<Address>> {
+        let eth_client = self
+            .eth_client
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("ENS resolution is not available"))?;
+        let name = name.to_lowercase();
+
+        if let Some((address, resolved_at)) = self.cache.read().await.get(&name) {
+            if resolved_at.elapsed() < ENS_CACHE_TTL {
+                metrics::increment_counter!("api.ens_resolver.cache_hit");
+                return Ok(*address);
+            }
+        }
+
+        let start = Instant::now();
+        let address = resolve_ens_name(eth_client, &name).await?;
+        metrics::histogram!("api.ens_resolver.resolve", start.elapsed());
+
+        self.cache
+            .write()
+            .await
+            .insert(name, (address, Instant::now()));
+        Ok(address)
+    }
+}
@@ -5,6 +5,7 @@ use jsonrpc_pubsub::{
     SubscriptionId,
 };
 use std::time::Duration;
+use zksync_api_types::v02::account::AccountsDiff;
 use zksync_config::configs::api::TokenConfig;
 use zksync_storage::ConnectionPool;
 use zksync_types::tx::TxHash;
@@ -42,6 +43,11 @@ pub enum EventSubscribeRequest {
         action: ActionType,
         subscriber: Subscriber<ResponseAccountState>,
     },
+    AccountsDiff {
+        addresses: Vec<Address>,
+        from_block: BlockNumber,
+        subscriber: Subscriber<AccountsDiff>,
+    },
 }
 
 pub enum EventNotifierRequest {
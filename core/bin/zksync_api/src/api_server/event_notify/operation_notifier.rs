@@ -1,8 +1,11 @@
-use crate::api_server::rpc_server::types::{
-    BlockInfo, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
+use crate::api_server::{
+    helpers::MAX_ACCOUNTS_DIFF_BLOCKS,
+    rpc_server::types::{BlockInfo, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp},
 };
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
+use std::collections::BTreeSet;
 use std::time::{Duration, Instant};
+use zksync_api_types::v02::{account::AccountsDiff, pagination::MAX_LIMIT};
 use zksync_storage::ConnectionPool;
 use zksync_types::aggregated_operations::AggregatedOperation;
 use zksync_types::tx::TxHash;
@@ -10,8 +13,9 @@ use zksync_types::BlockNumber;
 use zksync_types::{block::ExecutedOperations, AccountId, ActionType, Address, PriorityOpId};
 
 use super::{
-    state::NotifierState, sub_store::SubStorage, EventNotifierRequest, EventSubscribeRequest,
-    ExecutedOps,
+    state::NotifierState,
+    sub_store::{DiffSubStorage, SubStorage},
+    EventNotifierRequest, EventSubscribeRequest, ExecutedOps,
 };
 
 pub struct OperationNotifier {
@@ -20,6 +24,7 @@ pub struct OperationNotifier {
     tx_subs: SubStorage<TxHash, TransactionInfoResp>,
     prior_op_subs: SubStorage<PriorityOpId, ETHOpInfoResp>,
     account_subs: SubStorage<AccountId, ResponseAccountState>,
+    accounts_diff_subs: DiffSubStorage,
 }
 
 impl OperationNotifier {
@@ -33,6 +38,7 @@ impl OperationNotifier {
             tx_subs: SubStorage::new(),
             prior_op_subs: SubStorage::new(),
             account_subs: SubStorage::new(),
+            accounts_diff_subs: DiffSubStorage::new(),
         }
     }
 
@@ -64,6 +70,14 @@ impl OperationNotifier {
                     self.add_account_update_sub(address, action, subscriber)
                         .await
                 }
+                EventSubscribeRequest::AccountsDiff {
+                    addresses,
+                    from_block,
+                    subscriber,
+                } => {
+                    self.add_accounts_diff_sub(addresses, from_block, subscriber)
+                        .await
+                }
             }
             .map_err(|e| anyhow::format_err!("Failed to add sub: {}", e)),
             EventNotifierRequest::Unsub(sub_id) => self
@@ -99,6 +113,11 @@ impl OperationNotifier {
                 .flatten()
                 .collect();
 
+            if action == ActionType::COMMIT {
+                self.notify_accounts_diff_subs(&updated_accounts, block.block_number)
+                    .await?;
+            }
+
             for id in updated_accounts {
                 if self.account_subs.subscriber_exists(id, action) {
                     let account_state = match self.state.get_account_state(id, action).await? {
@@ -163,6 +182,33 @@ impl OperationNotifier {
         metrics::histogram!("api.notifier.handle_executed_operations", start.elapsed());
     }
 
+    /// Sends the changes made in the committed block to the subscribers of the updated accounts.
+    async fn notify_accounts_diff_subs(
+        &mut self,
+        updated_accounts: &[AccountId],
+        block_number: BlockNumber,
+    ) -> anyhow::Result<()> {
+        let updated_accounts: BTreeSet<_> = updated_accounts.iter().copied().collect();
+        for sub in self.accounts_diff_subs.subs_mut() {
+            if sub.has_unknown_addresses() {
+                // The account may be created in this block.
+                sub.account_ids = self.state.get_account_ids(&sub.addresses).await?;
+            }
+            if sub.account_ids.is_disjoint(&updated_accounts) {
+                continue;
+            }
+
+            let diff = self
+                .state
+                .get_accounts_diff(&sub.addresses, block_number - 1, block_number)
+                .await?;
+            if !diff.accounts.is_empty() {
+                DiffSubStorage::notify(sub, diff);
+            }
+        }
+        Ok(())
+    }
+
     /// More convenient alias for `handle_executed_operations`.
     pub fn handle_new_executed_batch(
         &mut self,
@@ -180,7 +226,8 @@ impl OperationNotifier {
     fn handle_unsub(&mut self, sub_id: SubscriptionId) -> Result<(), anyhow::Error> {
         self.prior_op_subs.remove(sub_id.clone())?;
         self.tx_subs.remove(sub_id.clone())?;
-        self.account_subs.remove(sub_id)?;
+        self.account_subs.remove(sub_id.clone())?;
+        self.accounts_diff_subs.remove(sub_id);
         Ok(())
     }
 
@@ -295,4 +342,55 @@ impl OperationNotifier {
         metrics::histogram!("api.notifier.add_account_update_sub", start.elapsed());
         Ok(())
     }
+
+    /// Add accounts diff subscription. The changes made after the `from_block` are sent
+    /// right away, so the client doesn't miss the blocks committed before the subscription.
+    async fn add_accounts_diff_sub(
+        &mut self,
+        addresses: Vec<Address>,
+        from_block: BlockNumber,
+        sub: Subscriber<AccountsDiff>,
+    ) -> Result<(), anyhow::Error> {
+        let start = Instant::now();
+        if addresses.len() > MAX_LIMIT as usize {
+            sub.reject(jsonrpc_core::Error::invalid_params(format!(
+                "Number of addresses should be less than or equal to {}",
+                MAX_LIMIT
+            )))
+            .map_err(|_| anyhow::format_err!("SubReject"))?;
+            return Ok(());
+        }
+
+        let last_committed = self.state.get_last_committed_block().await?;
+        let mut from_block = from_block.min(last_committed);
+        let mut diffs = Vec::new();
+        loop {
+            let to_block = last_committed.min(from_block + MAX_ACCOUNTS_DIFF_BLOCKS);
+            let diff = self
+                .state
+                .get_accounts_diff(&addresses, from_block, to_block)
+                .await?;
+            // The last diff is sent even if it's empty, so the client knows the current block.
+            if !diff.accounts.is_empty() || to_block == last_committed {
+                diffs.push(diff);
+            }
+            if to_block == last_committed {
+                break;
+            }
+            from_block = to_block;
+        }
+
+        let account_ids = self.state.get_account_ids(&addresses).await?;
+        let sub_id = self.accounts_diff_subs.generate_sub_id();
+        if let Some(sub) =
+            self.accounts_diff_subs
+                .insert_new(sub_id, sub, addresses, account_ids)?
+        {
+            for diff in diffs {
+                DiffSubStorage::notify(sub, diff);
+            }
+        }
+        metrics::histogram!("api.notifier.add_accounts_diff_sub", start.elapsed());
+        Ok(())
+    }
 }
@@ -1,6 +1,11 @@
-use crate::api_server::rpc_server::types::{BlockInfo, ResponseAccountState};
+use crate::api_server::{
+    helpers::load_accounts_diff,
+    rpc_server::types::{BlockInfo, ResponseAccountState},
+};
 use lru_cache::LruCache;
+use std::collections::BTreeSet;
 use std::time::{Duration, Instant};
+use zksync_api_types::v02::account::AccountsDiff;
 use zksync_storage::chain::operations::records::StoredExecutedPriorityOperation;
 use zksync_storage::chain::operations_ext::records::TxReceiptResponse;
 use zksync_storage::ConnectionPool;
@@ -228,4 +233,56 @@ impl NotifierState {
         metrics::histogram!("api.notifier.get_account_state", start.elapsed());
         Ok(account)
     }
+
+    pub async fn get_last_committed_block(&self) -> anyhow::Result<BlockNumber> {
+        let mut storage = self.db_pool.access_storage().await?;
+        let block_number = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await?;
+        Ok(block_number)
+    }
+
+    /// Returns the ids of the accounts of the addresses that exist in the network.
+    pub async fn get_account_ids(
+        &self,
+        addresses: &[Address],
+    ) -> anyhow::Result<BTreeSet<AccountId>> {
+        let mut storage = self.db_pool.access_storage().await?;
+        let mut account_ids = BTreeSet::new();
+        for address in addresses {
+            if let Some(account_id) = storage
+                .chain()
+                .account_schema()
+                .account_id_by_address(*address)
+                .await?
+            {
+                account_ids.insert(account_id);
+            }
+        }
+        Ok(account_ids)
+    }
+
+    pub async fn get_accounts_diff(
+        &self,
+        addresses: &[Address],
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> anyhow::Result<AccountsDiff> {
+        let start = Instant::now();
+        let mut storage = self.db_pool.access_storage().await?;
+        let diff = load_accounts_diff(
+            &mut storage,
+            &self.tokens_cache,
+            addresses,
+            from_block,
+            to_block,
+        )
+        .await
+        .map_err(|err| anyhow::format_err!("{}", err.message))?;
+
+        metrics::histogram!("api.notifier.get_accounts_diff", start.elapsed());
+        Ok(diff)
+    }
 }
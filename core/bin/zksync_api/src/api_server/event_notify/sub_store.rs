@@ -1,7 +1,12 @@
 //! Storage for subscription objects.
 use super::SubscriptionSender;
-use std::{cmp::Ord, collections::BTreeMap, str::FromStr};
-use zksync_types::{tx::TxHash, AccountId, ActionType, PriorityOpId};
+use std::{
+    cmp::Ord,
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+};
+use zksync_api_types::v02::account::AccountsDiff;
+use zksync_types::{tx::TxHash, AccountId, ActionType, Address, PriorityOpId};
 
 use jsonrpc_pubsub::{
     typed::{Sink, Subscriber},
@@ -12,6 +17,7 @@ const MAX_LISTENERS_PER_ENTITY: usize = 2048;
 const TX_SUB_PREFIX: &str = "txsub";
 const ETHOP_SUB_PREFIX: &str = "eosub";
 const ACCOUNT_SUB_PREFIX: &str = "acsub";
+const ACCOUNTS_DIFF_SUB_PREFIX: &str = "adsub";
 
 pub trait ActionId {
     fn sub_type() -> &'static str;
@@ -158,3 +164,78 @@ where
         Ok(())
     }
 }
+
+/// Subscription to the changes of the set of accounts.
+#[derive(Debug)]
+pub struct AccountsDiffSub {
+    pub addresses: Vec<Address>,
+    /// Accounts of the addresses that exist in the network.
+    pub account_ids: BTreeSet<AccountId>,
+    sender: SubscriptionSender<AccountsDiff>,
+}
+
+impl AccountsDiffSub {
+    /// Whether some of the addresses don't have an account yet.
+    pub fn has_unknown_addresses(&self) -> bool {
+        self.account_ids.len() < self.addresses.len()
+    }
+}
+
+/// Storage for the accounts diff subscriptions. Unlike the other subscriptions, these
+/// ones are notified on every block that changes the accounts until the client unsubscribes.
+#[derive(Debug, Default)]
+pub struct DiffSubStorage {
+    subs: Vec<AccountsDiffSub>,
+}
+
+impl DiffSubStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn generate_sub_id(&self) -> SubscriptionId {
+        // The id has the same number of segments as the other subscription ids,
+        // so that the other storages can recognize that it's not their subscription.
+        SubscriptionId::String(format!(
+            "{}/accounts/{}/{}",
+            ACCOUNTS_DIFF_SUB_PREFIX,
+            ActionType::COMMIT.to_string(),
+            zksync_crypto::rand::random::<u64>()
+        ))
+    }
+
+    pub fn insert_new(
+        &mut self,
+        sub_id: SubscriptionId,
+        sub: Subscriber<AccountsDiff>,
+        addresses: Vec<Address>,
+        account_ids: BTreeSet<AccountId>,
+    ) -> anyhow::Result<Option<&AccountsDiffSub>> {
+        if self.subs.len() >= MAX_LISTENERS_PER_ENTITY {
+            return Ok(None);
+        }
+        let sink = sub
+            .assign_id(sub_id.clone())
+            .map_err(|_| anyhow::format_err!("SubIdAssign"))?;
+        self.subs.push(AccountsDiffSub {
+            addresses,
+            account_ids,
+            sender: SubscriptionSender { id: sub_id, sink },
+        });
+        Ok(self.subs.last())
+    }
+
+    pub fn remove(&mut self, sub_id: SubscriptionId) {
+        self.subs.retain(|sub| sub.sender.id != sub_id);
+    }
+
+    pub fn subs_mut(&mut self) -> impl Iterator<Item = &mut AccountsDiffSub> {
+        self.subs.iter_mut()
+    }
+
+    pub fn notify(sub: &AccountsDiffSub, diff: AccountsDiff) {
+        if let Err(e) = sub.sender.sink.notify(Ok(diff)) {
+            vlog::warn!("{}", e.to_string())
+        }
+    }
+}
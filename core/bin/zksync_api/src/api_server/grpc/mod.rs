@@ -0,0 +1,383 @@
+//! gRPC interface of the server.
+//!
+//! The protobuf schema is versioned in the `proto` directory of the crate, the package name
+//! contains the version of the interface, so breaking changes must be done in a new package.
+//! The service is a thin layer over the JSON RPC implementation: transactions and account
+//! states are passed in the same JSON representation as for the other APIs.
+
+// Built-in uses
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+// External uses
+use futures::{channel::mpsc, Future};
+use jsonrpc_core::ErrorCode;
+use tokio::{sync::mpsc as tokio_mpsc, task::JoinHandle, time};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{metadata::MetadataValue, transport::Server, Code, Request, Response, Status};
+
+// Workspace uses
+use zksync_config::configs::api::{CommonApiConfig, GrpcConfig, TokenConfig};
+use zksync_mempool::MempoolTransactionRequest;
+use zksync_storage::ConnectionPool;
+use zksync_types::{
+    tx::{TxEthSignatureVariant, TxHash},
+    Address, ZkSyncTx,
+};
+use zksync_utils::shutdown::ShutdownSignal;
+
+// Local uses
+use crate::{
+    api_server::rpc_server::{
+        types::{RequestMetadata, ResponseAccountState, TransactionInfoResp},
+        RpcApp,
+    },
+    fee_ticker::FeeTicker,
+    signature_checker::VerifySignatureRequest,
+};
+use proto::{
+    tx_status,
+    zk_sync_server::{ZkSync, ZkSyncServer},
+};
+
+pub mod proto {
+    tonic::include_proto!("zksync.api.v1");
+}
+
+/// Name of the metadata entry containing the JSON RPC code of the error.
+const ERROR_CODE_METADATA: &str = "x-zksync-error-code";
+/// Time after which the transaction status stream is closed, even if the status is not final.
+const MAX_TX_STATUS_STREAM_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
+struct GrpcService {
+    rpc_app: RpcApp,
+    tx_status_poll_interval: Duration,
+}
+
+fn rpc_error(err: jsonrpc_core::Error) -> Status {
+    let code = match err.code {
+        ErrorCode::InternalError => Code::Internal,
+        _ => Code::InvalidArgument,
+    };
+    let mut status = Status::new(code, err.message);
+    if let Ok(value) = MetadataValue::from_str(&err.code.code().to_string()) {
+        status.metadata_mut().insert(ERROR_CODE_METADATA, value);
+    }
+    status
+}
+
+fn parse_tx_hash(tx_hash: &str) -> Result<TxHash, Status> {
+    TxHash::from_str(tx_hash).map_err(|err| Status::invalid_argument(err.to_string()))
+}
+
+fn tx_status_from_info(tx_hash: String, info: TransactionInfoResp) -> proto::TxStatus {
+    let status = match (&info.block, info.success) {
+        (_, Some(false)) => tx_status::Status::Rejected,
+        (Some(block), _) if block.verified => tx_status::Status::Finalized,
+        (Some(_), _) => tx_status::Status::Committed,
+        (None, _) => tx_status::Status::Queued,
+    };
+    proto::TxStatus {
+        tx_hash,
+        status: status as i32,
+        block: info.block.map(|block| proto::BlockNumber {
+            number: block.block_number as u32,
+        }),
+        fail_reason: info.fail_reason.unwrap_or_default(),
+    }
+}
+
+fn is_final(status: &proto::TxStatus) -> bool {
+    status.status == tx_status::Status::Finalized as i32
+        || status.status == tx_status::Status::Rejected as i32
+}
+
+fn account_balances(state: ResponseAccountState) -> proto::AccountBalances {
+    proto::AccountBalances {
+        nonce: *state.nonce,
+        pub_key_hash: state.pub_key_hash.as_hex(),
+        balances: state
+            .balances
+            .into_iter()
+            .map(|(token, balance)| (token, balance.0.to_string()))
+            .collect(),
+    }
+}
+
+async fn tx_status(rpc_app: RpcApp, tx_hash: String) -> Result<proto::TxStatus, Status> {
+    let hash = parse_tx_hash(&tx_hash)?;
+    let info = rpc_app._impl_tx_info(hash).await.map_err(rpc_error)?;
+    Ok(tx_status_from_info(tx_hash, info))
+}
+
+/// Polls the status of the transaction and sends it to the stream every time it changes.
+/// Polling stops once the status is final, the client has closed the stream,
+/// or the stream has been open for `max_lifetime`.
+async fn watch_tx_status<F, Fut>(
+    mut fetch_status: F,
+    sender: tokio_mpsc::Sender<Result<proto::TxStatus, Status>>,
+    poll_interval: Duration,
+    max_lifetime: Duration,
+) where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<proto::TxStatus, Status>>,
+{
+    let deadline = Instant::now() + max_lifetime;
+    let mut timer = time::interval(poll_interval);
+    let mut last_status = None;
+    loop {
+        timer.tick().await;
+        if sender.is_closed() {
+            // The client has closed the stream.
+            break;
+        }
+        if Instant::now() >= deadline {
+            let err = Status::deadline_exceeded("Transaction status stream has expired");
+            sender.send(Err(err)).await.ok();
+            break;
+        }
+        match fetch_status().await {
+            Ok(status) => {
+                let done = is_final(&status);
+                if last_status.as_ref() != Some(&status) {
+                    last_status = Some(status.clone());
+                    if sender.send(Ok(status)).await.is_err() {
+                        break;
+                    }
+                }
+                if done {
+                    break;
+                }
+            }
+            Err(err) => {
+                sender.send(Err(err)).await.ok();
+                break;
+            }
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl ZkSync for GrpcService {
+    type StreamTxStatusStream = ReceiverStream<Result<proto::TxStatus, Status>>;
+
+    async fn submit_tx(
+        &self,
+        request: Request<proto::SubmitTxRequest>,
+    ) -> Result<Response<proto::SubmitTxResponse>, Status> {
+        let start = Instant::now();
+        let metadata = request.remote_addr().map(|addr| RequestMetadata {
+            ip: addr.ip().to_string(),
+        });
+        let request = request.into_inner();
+
+        let tx: ZkSyncTx = serde_json::from_str(&request.tx)
+            .map_err(|err| Status::invalid_argument(format!("Invalid transaction: {}", err)))?;
+        let signature: TxEthSignatureVariant = if request.signature.is_empty() {
+            Default::default()
+        } else {
+            serde_json::from_str(&request.signature)
+                .map_err(|err| Status::invalid_argument(format!("Invalid signature: {}", err)))?
+        };
+
+        let tx_hash = self
+            .rpc_app
+            .clone()
+            ._impl_tx_submit(
+                Box::new(tx),
+                Box::new(signature),
+                Some(request.fast_processing),
+                Some(request.idempotency_key).filter(|key| !key.is_empty()),
+                metadata,
+            )
+            .await
+            .map_err(rpc_error)?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "grpc", "endpoint_name" => "submit_tx");
+        Ok(Response::new(proto::SubmitTxResponse {
+            tx_hash: tx_hash.to_string(),
+        }))
+    }
+
+    async fn get_tx_status(
+        &self,
+        request: Request<proto::TxStatusRequest>,
+    ) -> Result<Response<proto::TxStatus>, Status> {
+        let start = Instant::now();
+        let status = tx_status(self.rpc_app.clone(), request.into_inner().tx_hash).await?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "grpc", "endpoint_name" => "get_tx_status");
+        Ok(Response::new(status))
+    }
+
+    async fn stream_tx_status(
+        &self,
+        request: Request<proto::TxStatusRequest>,
+    ) -> Result<Response<Self::StreamTxStatusStream>, Status> {
+        let tx_hash = request.into_inner().tx_hash;
+        // Check the hash before opening the stream.
+        parse_tx_hash(&tx_hash)?;
+
+        let (sender, receiver) = tokio_mpsc::channel(4);
+        let rpc_app = self.rpc_app.clone();
+        tokio::spawn(watch_tx_status(
+            move || tx_status(rpc_app.clone(), tx_hash.clone()),
+            sender,
+            self.tx_status_poll_interval,
+            MAX_TX_STATUS_STREAM_LIFETIME,
+        ));
+
+        metrics::increment_counter!("api.grpc.tx_status_streams");
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+
+    async fn get_account_state(
+        &self,
+        request: Request<proto::AccountStateRequest>,
+    ) -> Result<Response<proto::AccountState>, Status> {
+        let start = Instant::now();
+        let address = Address::from_str(request.get_ref().address.trim_start_matches("0x"))
+            .map_err(|err| Status::invalid_argument(format!("Invalid address: {}", err)))?;
+
+        let info = self
+            .rpc_app
+            .clone()
+            ._impl_account_info(address)
+            .await
+            .map_err(rpc_error)?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "grpc", "endpoint_name" => "get_account_state");
+        Ok(Response::new(proto::AccountState {
+            address: format!("{:?}", info.address),
+            account_id: info.id.map(|id| proto::AccountId { id: *id }),
+            committed: Some(account_balances(info.committed)),
+            finalized: Some(account_balances(info.verified)),
+        }))
+    }
+}
+
+/// Start the gRPC server.
+#[allow(clippy::too_many_arguments)]
+#[must_use]
+pub fn start_grpc_server(
+    connection_pool: ConnectionPool,
+    sign_verify_request_sender: mpsc::Sender<VerifySignatureRequest>,
+    ticker: FeeTicker,
+    config: &GrpcConfig,
+    common_api_config: &CommonApiConfig,
+    token_config: &TokenConfig,
+    contract_addr: Address,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    confirmations_for_eth_event: u64,
+    shutdown: ShutdownSignal,
+) -> JoinHandle<()> {
+    let addr = config.bind_addr();
+    let service = GrpcService {
+        rpc_app: RpcApp::new(
+            connection_pool,
+            sign_verify_request_sender,
+            ticker,
+            common_api_config,
+            token_config,
+            contract_addr,
+            confirmations_for_eth_event,
+            mempool_tx_sender,
+        )
+        .with_shutdown_signal(shutdown),
+        tx_status_poll_interval: config.tx_status_poll_interval(),
+    };
+
+    tokio::spawn(async move {
+        Server::builder()
+            .add_service(ZkSyncServer::new(service))
+            .serve(addr)
+            .await
+            .expect("gRPC server has crashed");
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rpc_server::types::BlockInfo;
+
+    fn info(success: Option<bool>, block: Option<(i64, bool)>) -> TransactionInfoResp {
+        TransactionInfoResp {
+            executed: success.is_some(),
+            success,
+            fail_reason: success
+                .filter(|success| !success)
+                .map(|_| "Nonce mismatch".to_string()),
+            block: block.map(|(block_number, verified)| BlockInfo {
+                block_number,
+                committed: true,
+                verified,
+            }),
+        }
+    }
+
+    #[test]
+    fn tx_status_mapping() {
+        let status = |info| tx_status_from_info("hash".to_string(), info);
+
+        let queued = status(info(None, None));
+        assert_eq!(queued.status, tx_status::Status::Queued as i32);
+        assert!(queued.block.is_none());
+        assert!(!is_final(&queued));
+
+        let committed = status(info(Some(true), Some((5, false))));
+        assert_eq!(committed.status, tx_status::Status::Committed as i32);
+        assert_eq!(committed.block, Some(proto::BlockNumber { number: 5 }));
+        assert!(!is_final(&committed));
+
+        let finalized = status(info(Some(true), Some((5, true))));
+        assert_eq!(finalized.status, tx_status::Status::Finalized as i32);
+        assert!(is_final(&finalized));
+
+        let rejected = status(info(Some(false), Some((5, false))));
+        assert_eq!(rejected.status, tx_status::Status::Rejected as i32);
+        assert_eq!(rejected.fail_reason, "Nonce mismatch");
+        assert!(is_final(&rejected));
+    }
+
+    /// Checks that the stream of the transaction which never gets final doesn't poll forever.
+    #[tokio::test]
+    async fn tx_status_stream_is_bounded() {
+        let queued = || async { Ok(tx_status_from_info("hash".to_string(), info(None, None))) };
+        let poll_interval = Duration::from_millis(1);
+
+        // The client disconnects.
+        let (sender, receiver) = tokio_mpsc::channel(4);
+        drop(receiver);
+        let watch = watch_tx_status(queued, sender, poll_interval, Duration::from_secs(60));
+        time::timeout(Duration::from_secs(5), watch)
+            .await
+            .expect("Stream of the closed client is still polled");
+
+        // The stream expires.
+        let (sender, mut receiver) = tokio_mpsc::channel(4);
+        let watch = watch_tx_status(queued, sender, poll_interval, Duration::from_millis(20));
+        time::timeout(Duration::from_secs(5), watch)
+            .await
+            .expect("Expired stream is still polled");
+        let status = receiver.recv().await.unwrap().unwrap();
+        assert_eq!(status.status, tx_status::Status::Queued as i32);
+        let err = receiver.recv().await.unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::DeadlineExceeded);
+        assert!(receiver.recv().await.is_none());
+    }
+
+    #[test]
+    fn error_mapping() {
+        let status = rpc_error(jsonrpc_core::Error::internal_error());
+        assert_eq!(status.code(), Code::Internal);
+
+        let status = rpc_error(jsonrpc_core::Error::invalid_params("Wrong nonce"));
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(status.message(), "Wrong nonce");
+        assert_eq!(
+            status.metadata().get(ERROR_CODE_METADATA).unwrap(),
+            "-32602"
+        );
+    }
+}
@@ -1,16 +1,22 @@
 //! Helpers collection shared between the different API implementations.
 
 // Built-in uses
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 // External uses
-use num::BigUint;
+use num::{BigUint, Zero};
 
 // Workspace uses
-use zksync_api_types::v02::account::{DepositingAccountBalances, DepositingFunds, OngoingDeposit};
+use zksync_api_types::v02::account::{
+    AccountDiff, AccountsDiff, DepositingAccountBalances, DepositingFunds, OngoingDeposit,
+};
+use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL};
 use zksync_storage::StorageProcessor;
 use zksync_token_db_cache::TokenDBCache;
-use zksync_types::{Address, H256};
+use zksync_types::{
+    AccountId, AccountUpdate, AccountUpdates, Address, BlockNumber, Nonce, PubKeyHash, TokenId,
+    H256, NFT,
+};
 use zksync_utils::remove_prefix;
 
 // Local uses
@@ -84,3 +90,190 @@ pub async fn get_depositing(
         .collect();
     depositing_from_pending_ops(storage, tokens, pending_ops, confirmations_for_eth_event).await
 }
+
+/// Maximum number of blocks covered by a single accounts diff.
+pub const MAX_ACCOUNTS_DIFF_BLOCKS: u32 = 100;
+
+/// Net result of the updates of the account.
+#[derive(Debug, Default, PartialEq)]
+struct AccountChanges {
+    nonce: Nonce,
+    pub_key_hash: Option<PubKeyHash>,
+    balances: BTreeMap<TokenId, BigUint>,
+    minted_nfts: BTreeMap<TokenId, NFT>,
+}
+
+/// Folds the ordered list of the updates into the final values of the changed fields.
+fn fold_account_updates(updates: AccountUpdates) -> BTreeMap<AccountId, AccountChanges> {
+    let mut changes: BTreeMap<AccountId, AccountChanges> = BTreeMap::new();
+    for (account_id, update) in updates {
+        let account = changes.entry(account_id).or_default();
+        match update {
+            AccountUpdate::Create { nonce, .. } | AccountUpdate::Delete { nonce, .. } => {
+                account.nonce = nonce;
+            }
+            AccountUpdate::UpdateBalance {
+                new_nonce,
+                balance_update: (token_id, _, new_balance),
+                ..
+            } => {
+                account.nonce = new_nonce;
+                account.balances.insert(token_id, new_balance);
+            }
+            AccountUpdate::ChangePubKeyHash {
+                new_pub_key_hash,
+                new_nonce,
+                ..
+            } => {
+                account.nonce = new_nonce;
+                account.pub_key_hash = Some(new_pub_key_hash);
+            }
+            AccountUpdate::MintNFT { token, .. } => {
+                account.minted_nfts.insert(token.id, token);
+            }
+            AccountUpdate::RemoveNFT { token, .. } => {
+                account.minted_nfts.remove(&token.id);
+            }
+        }
+    }
+    changes
+}
+
+/// Loads the changes of the accounts made in the blocks `(from_block, to_block]`.
+/// Addresses without an account in the network are ignored.
+pub async fn load_accounts_diff(
+    storage: &mut StorageProcessor<'_>,
+    tokens: &TokenDBCache,
+    addresses: &[Address],
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<AccountsDiff, Error> {
+    let mut accounts = BTreeMap::new();
+    for address in addresses {
+        let account_id = storage
+            .chain()
+            .account_schema()
+            .account_id_by_address(*address)
+            .await
+            .map_err(Error::storage)?;
+        if let Some(account_id) = account_id {
+            accounts.insert(account_id, *address);
+        }
+    }
+    let account_ids: Vec<_> = accounts.keys().copied().collect();
+    let updates = storage
+        .chain()
+        .state_schema()
+        .load_accounts_diff(&account_ids, from_block, to_block)
+        .await
+        .map_err(Error::storage)?;
+
+    let mut diffs = Vec::new();
+    for (account_id, changes) in fold_account_updates(updates) {
+        let mut diff = AccountDiff {
+            account_id,
+            address: accounts[&account_id],
+            nonce: changes.nonce,
+            pub_key_hash: changes.pub_key_hash,
+            balances: BTreeMap::new(),
+            nfts: BTreeMap::new(),
+            removed_nfts: Vec::new(),
+            minted_nfts: changes
+                .minted_nfts
+                .into_iter()
+                .map(|(id, nft)| (id, nft.into()))
+                .collect(),
+        };
+        for (token_id, balance) in changes.balances {
+            match token_id.0 {
+                NFT_TOKEN_ID_VAL => {
+                    // Don't include special token to balances or nfts
+                }
+                MIN_NFT_TOKEN_ID..=NFT_TOKEN_ID_VAL if balance.is_zero() => {
+                    diff.removed_nfts.push(token_id);
+                }
+                MIN_NFT_TOKEN_ID..=NFT_TOKEN_ID_VAL => {
+                    let nft = tokens
+                        .get_nft_by_id(storage, token_id)
+                        .await
+                        .map_err(Error::storage)?
+                        .ok_or_else(|| Error::from(PriceError::token_not_found(token_id)))?;
+                    diff.nfts.insert(token_id, nft.into());
+                }
+                _ => {
+                    let token_symbol = tokens
+                        .token_symbol(storage, token_id)
+                        .await
+                        .map_err(Error::storage)?
+                        .ok_or_else(|| Error::from(PriceError::token_not_found(token_id)))?;
+                    diff.balances.insert(token_symbol, balance.into());
+                }
+            }
+        }
+        diffs.push(diff);
+    }
+    Ok(AccountsDiff {
+        from_block,
+        to_block,
+        accounts: diffs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_updates_folding() {
+        let (first, second) = (AccountId(1), AccountId(2));
+        let pub_key_hash =
+            PubKeyHash::from_hex("sync:0102030405060708091011121314151617181920").unwrap();
+        let updates = vec![
+            (
+                first,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: Nonce(0),
+                    new_nonce: Nonce(1),
+                    balance_update: (TokenId(0), 10u32.into(), 7u32.into()),
+                },
+            ),
+            (
+                second,
+                AccountUpdate::Create {
+                    address: Address::repeat_byte(2),
+                    nonce: Nonce(0),
+                },
+            ),
+            (
+                first,
+                AccountUpdate::ChangePubKeyHash {
+                    old_pub_key_hash: PubKeyHash::default(),
+                    new_pub_key_hash: pub_key_hash,
+                    old_nonce: Nonce(1),
+                    new_nonce: Nonce(2),
+                },
+            ),
+            (
+                first,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: Nonce(2),
+                    new_nonce: Nonce(2),
+                    balance_update: (TokenId(0), 7u32.into(), 5u32.into()),
+                },
+            ),
+        ];
+
+        let changes = fold_account_updates(updates);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(
+            changes[&first],
+            AccountChanges {
+                nonce: Nonce(2),
+                pub_key_hash: Some(pub_key_hash),
+                balances: vec![(TokenId(0), 5u32.into())].into_iter().collect(),
+                minted_nfts: BTreeMap::new(),
+            }
+        );
+        assert_eq!(changes[&second], AccountChanges::default());
+    }
+}
@@ -3,15 +3,21 @@
 //! `mod rest` - api is used for block explorer.
 //! `mod rpc_server` - JSON rpc via HTTP (for request reply functions)
 //! `mod rpc_subscriptions` - JSON rpc via WebSocket (for request reply functions and subscriptions)
+//! `mod grpc` - gRPC interface for transaction submission, status streaming and account states
 
+pub mod ens_resolver;
 mod event_notify;
 pub mod forced_exit_checker;
+pub mod grpc;
 mod helpers;
 pub mod rest;
 pub mod rpc_server;
 pub mod rpc_subscriptions;
 mod tx_sender;
 pub mod web3;
+pub mod weth_unwrap;
+mod withdrawal_screening;
+mod withdrawal_timelock;
 
 /// Amount of threads used by each server to serve requests.
 const THREADS_PER_SERVER: usize = 128;
@@ -0,0 +1,14 @@
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_config::configs::chain::EncryptedMempool as EncryptedMempoolConfig;
+use zksync_storage::ConnectionPool;
+
+// Local uses
+
+mod v01;
+
+pub(crate) fn api_scope(connection_pool: ConnectionPool, config: &EncryptedMempoolConfig) -> Scope {
+    web::scope("/api/encrypted_txs").service(v01::api_scope(connection_pool, config))
+}
@@ -0,0 +1,72 @@
+//! Encrypted mempool part of API implementation.
+//!
+//! The server can't decrypt the blobs, so they are only checked for the size and stored until
+//! the block formation.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+
+// Workspace uses
+use zksync_api_client::rest::encrypted_txs::EncryptedTx;
+use zksync_config::configs::chain::EncryptedMempool as EncryptedMempoolConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::encrypted_tx::MAX_ENCRYPTED_TX_SIZE;
+
+// Local uses
+use crate::api_server::rest::forced_exit_requests::{error::ApiError, JsonResult};
+
+/// Shared data between `/api/encrypted_txs/v0.1/` endpoints.
+struct ApiEncryptedTxsData {
+    connection_pool: ConnectionPool,
+    max_pending_txs: usize,
+}
+
+async fn submit_tx(
+    data: web::Data<ApiEncryptedTxsData>,
+    tx: web::Json<EncryptedTx>,
+) -> JsonResult<i64> {
+    let start = Instant::now();
+    if tx.blob.is_empty() || tx.blob.len() > MAX_ENCRYPTED_TX_SIZE {
+        return Err(ApiError::bad_request(format!(
+            "Encrypted transaction must be from 1 to {} bytes long",
+            MAX_ENCRYPTED_TX_SIZE
+        )));
+    }
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    let id = storage
+        .encrypted_mempool_schema()
+        .insert_tx(&tx.blob, data.max_pending_txs)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| {
+            ApiError::too_many_requests("Too many encrypted transactions are waiting, try later")
+        })?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "submit_encrypted_tx");
+    Ok(Json(id))
+}
+
+pub fn api_scope(connection_pool: ConnectionPool, config: &EncryptedMempoolConfig) -> Scope {
+    let data = ApiEncryptedTxsData {
+        connection_pool,
+        max_pending_txs: config.max_pending_txs,
+    };
+
+    let scope = web::scope("v0.1").app_data(web::Data::new(data));
+    if config.enabled {
+        scope.route("/submit", web::post().to(submit_tx))
+    } else {
+        scope
+    }
+}
@@ -35,6 +35,11 @@ impl ApiError {
         Self::with_code(StatusCode::NOT_FOUND, title)
     }
 
+    /// Creates a new Error with the TOO_MANY_REQUESTS (429) status code.
+    pub fn too_many_requests(title: impl Display) -> Self {
+        Self::with_code(StatusCode::TOO_MANY_REQUESTS, title)
+    }
+
     fn with_code(http_code: StatusCode, title: impl Display) -> Self {
         Self {
             http_code,
@@ -100,7 +105,10 @@ impl SumbitErrorCode {
             SubmitError::UnsupportedFastProcessing => Self::UnsupportedFastProcessing,
             SubmitError::IncorrectTx(_) => Self::IncorrectTx,
             SubmitError::TxAdd(_) => Self::TxAdd,
+            SubmitError::InvalidBatchTx { error, .. } => Self::from_err(error),
             SubmitError::InappropriateFeeToken => Self::InappropriateFeeToken,
+            SubmitError::AccountLocked(_) => Self::IncorrectTx,
+            SubmitError::AmbiguousTokenSymbol(_) => Self::InvalidParams,
             SubmitError::MempoolCommunication(_) => Self::CommunicationCoreServer,
             SubmitError::Internal(_) => Self::Internal,
             SubmitError::Other(_) => Self::Other,
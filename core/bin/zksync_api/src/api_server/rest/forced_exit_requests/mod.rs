@@ -11,7 +11,7 @@ use crate::api_server::forced_exit_checker::ForcedExitChecker;
 use error::ApiError;
 use ethabi::Address;
 
-mod error;
+pub(super) mod error;
 mod v01;
 
 pub type JsonResult<T> = std::result::Result<web::Json<T>, ApiError>;
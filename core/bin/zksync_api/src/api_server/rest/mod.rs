@@ -5,24 +5,42 @@ use std::net::SocketAddr;
 use zksync_storage::ConnectionPool;
 use zksync_types::{SequentialTxId, H160};
 
-use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
+use zksync_utils::{
+    panic_notify::{spawn_panic_handler, ThreadPanicNotify},
+    shutdown::ShutdownSignal,
+};
 
 use self::v01::api_decl::ApiV01;
-use crate::signature_checker::VerifySignatureRequest;
+use crate::{
+    api_server::ens_resolver::EnsResolver, eth_checker::EthereumChecker,
+    signature_checker::VerifySignatureRequest,
+};
 
-use super::tx_sender::TxSender;
+use super::{tx_sender::TxSender, withdrawal_timelock};
 
 use crate::api_server::rest::network_status::SharedNetworkStatus;
 use crate::fee_ticker::FeeTicker;
 use tokio::task::JoinHandle;
 use zksync_config::ZkSyncConfig;
+use zksync_eth_client::EthereumGateway;
 use zksync_mempool::MempoolTransactionRequest;
 
+mod encrypted_txs;
 mod forced_exit_requests;
 mod helpers;
+mod names;
 pub mod network_status;
+mod nft_factory;
+mod onchain_auth;
+mod openapi;
+mod private_txs;
+mod read_tokens;
+mod response_signer;
+mod stats_aggregator;
+mod travel_rule;
 mod v01;
 pub mod v02;
+pub mod v03;
 
 async fn start_server(
     api_v01: ApiV01,
@@ -30,7 +48,15 @@ async fn start_server(
     sign_verifier: mpsc::Sender<VerifySignatureRequest>,
     bind_to: SocketAddr,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    eth_client: EthereumGateway,
+    shutdown: ShutdownSignal,
 ) {
+    // Limits of the private channel are shared by all the workers of the server.
+    let private_txs_rate_limiter = private_txs::BatchRateLimiter::default();
+    // Cached responses are shared by the workers as well.
+    let response_caches = v02::ResponseCaches::new(&api_v01.config);
+    // Cached responses are invalidated by the notifications about the new blocks.
+    tokio::spawn(response_caches.blocks_version().run_updater());
     HttpServer::new(move || {
         let api_v01 = api_v01.clone();
         // This api stores forced exit requests, it's necessary to use main database connection
@@ -44,19 +70,78 @@ async fn start_server(
             &api_v01.config.forced_exit_requests,
             api_v01.config.contracts.forced_exit_addr,
         );
+        // This api stores the queued transactions, so the main database connection is used as well.
+        let onchain_auth_api_scope = onchain_auth::api_scope(
+            api_v01.main_database_connection_pool.clone(),
+            api_v01.config.contracts.contract_addr,
+        );
+        // This api stores the encrypted transactions, so the main database connection is used as well.
+        let encrypted_txs_api_scope = encrypted_txs::api_scope(
+            api_v01.main_database_connection_pool.clone(),
+            &api_v01.config.chain.encrypted_mempool,
+        );
+        // This api stores the issued read tokens, so the main database connection is used as well.
+        let read_tokens_api_scope =
+            read_tokens::api_scope(api_v01.main_database_connection_pool.clone());
+        // This api stores the registered factories, so the main database connection is used as well.
+        let nft_factory_api_scope = nft_factory::api_scope(
+            api_v01.main_database_connection_pool.clone(),
+            eth_client.clone(),
+            &api_v01.config,
+        );
 
-        let api_v02_scope = {
+        let (
+            api_v02_scope,
+            api_v03_scope,
+            names_api_scope,
+            travel_rule_api_scope,
+            private_txs_api_scope,
+        ) = {
             let tx_sender = TxSender::new(
                 api_v01.connection_pool.clone(),
                 sign_verifier.clone(),
                 fee_ticker.clone(),
                 &api_v01.config.api.common,
                 &api_v01.config.api.token_config,
+                api_v01.config.contracts.contract_addr,
                 mempool_tx_sender.clone(),
-            );
-            v02::api_scope(tx_sender, &api_v01.config, api_v01.network_status.clone())
+            )
+            .with_ens_resolver(EnsResolver::new(eth_client.clone()))
+            .with_eth_checker(EthereumChecker::new(eth_client.clone()))
+            .with_shutdown_signal(shutdown.clone());
+            (
+                v02::api_scope(
+                    tx_sender.clone(),
+                    &api_v01.config,
+                    api_v01.network_status.clone(),
+                    response_caches.clone(),
+                ),
+                v03::api_scope(
+                    tx_sender.clone(),
+                    &api_v01.config,
+                    api_v01.network_status.clone(),
+                    response_caches.clone(),
+                ),
+                // This api stores the registered names, so the main database connection is used.
+                names::api_scope(
+                    api_v01.main_database_connection_pool.clone(),
+                    tx_sender.clone(),
+                ),
+                // This api stores the travel rule metadata, so the main database connection is used.
+                travel_rule::api_scope(
+                    api_v01.main_database_connection_pool.clone(),
+                    tx_sender.clone(),
+                ),
+                // Clients of the private channel are registered in the main database.
+                private_txs::api_scope(
+                    api_v01.main_database_connection_pool.clone(),
+                    tx_sender,
+                    private_txs_rate_limiter.clone(),
+                ),
+            )
         };
-        App::new()
+        let read_only = api_v01.config.api.common.read_only;
+        let app = App::new()
             .wrap(
                 Cors::default()
                     .send_wildcard()
@@ -66,13 +151,26 @@ async fn start_server(
                     .allow_any_method(),
             )
             .service(api_v01.into_scope())
-            .service(forced_exit_requests_api_scope)
             .service(api_v02_scope)
+            .service(api_v03_scope)
             // Endpoint needed for js isReachable
             .route(
                 "/favicon.ico",
                 web::get().to(|| HttpResponse::Ok().finish()),
-            )
+            );
+        // These APIs store the requests in the database, so they are served by the main nodes only.
+        if read_only {
+            app
+        } else {
+            app.service(forced_exit_requests_api_scope)
+                .service(onchain_auth_api_scope)
+                .service(nft_factory_api_scope)
+                .service(names_api_scope)
+                .service(travel_rule_api_scope)
+                .service(read_tokens_api_scope)
+                .service(private_txs_api_scope)
+                .service(encrypted_txs_api_scope)
+        }
     })
     .workers(super::THREADS_PER_SERVER)
     .bind(bind_to)
@@ -97,6 +195,7 @@ pub fn start_server_thread_detached(
     sign_verifier: mpsc::Sender<VerifySignatureRequest>,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     core_address: String,
+    shutdown: ShutdownSignal,
 ) -> JoinHandle<()> {
     let (handler, panic_sender) = spawn_panic_handler();
 
@@ -108,7 +207,14 @@ pub fn start_server_thread_detached(
                 // TODO remove this config ZKS-815
                 let config = ZkSyncConfig::from_env();
 
-                let mut network_status = SharedNetworkStatus::new(core_address);
+                let eth_client = EthereumGateway::from_config(
+                    &config.eth_client,
+                    &config.eth_sender,
+                    config.contracts.contract_addr,
+                );
+
+                let mut network_status =
+                    SharedNetworkStatus::new(core_address).with_eth_client(eth_client.clone());
                 // We want to update the network status, as soon as possible, otherwise we can catch the situation,
                 // when the node is started and receiving the request, but the status is still `null` and
                 // monitoring tools spawn the notification that our node is down, though it's just a default status
@@ -117,6 +223,37 @@ pub fn start_server_thread_detached(
                     .await
                     .unwrap();
 
+                // Read-only nodes rely on the main nodes to run the background tasks writing to the database.
+                if !config.api.common.read_only {
+                    // The aggregator stores the statistics, so the main database has to be used.
+                    stats_aggregator::start_stats_aggregator_detached(
+                        panic_sender.clone(),
+                        main_database_connection_pool.clone(),
+                        config.api.rest.stats_aggregation_interval(),
+                    );
+                    onchain_auth::start_onchain_auth_releaser_detached(
+                        panic_sender.clone(),
+                        main_database_connection_pool.clone(),
+                        EthereumChecker::new(eth_client.clone()),
+                        TxSender::new(
+                            read_only_connection_pool.clone(),
+                            sign_verifier.clone(),
+                            fee_ticker.clone(),
+                            &config.api.common,
+                            &config.api.token_config,
+                            config.contracts.contract_addr,
+                            mempool_tx_sender.clone(),
+                        ),
+                        config.api.rest.onchain_auth_poll_interval(),
+                    );
+                    withdrawal_timelock::start_withdrawal_timelock_releaser_detached(
+                        panic_sender.clone(),
+                        main_database_connection_pool.clone(),
+                        mempool_tx_sender.clone(),
+                        &config.api.rest,
+                    );
+                }
+
                 let api_v01 = ApiV01::new(
                     read_only_connection_pool,
                     main_database_connection_pool,
@@ -133,6 +270,8 @@ pub fn start_server_thread_detached(
                     sign_verifier,
                     listen_addr,
                     mempool_tx_sender.clone(),
+                    eth_client,
+                    shutdown,
                 )
                 .await;
             });
@@ -0,0 +1,14 @@
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_storage::ConnectionPool;
+
+// Local uses
+use crate::api_server::tx_sender::TxSender;
+
+mod v01;
+
+pub(crate) fn api_scope(connection_pool: ConnectionPool, tx_sender: TxSender) -> Scope {
+    web::scope("/api/names").service(v01::api_scope(connection_pool, tx_sender))
+}
@@ -0,0 +1,236 @@
+//! Account names part of API implementation.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+
+// Workspace uses
+use zksync_api_client::rest::names::{
+    AccountName, AccountNameFeeRequest, AccountNameRegistration, AccountNameStatus,
+};
+use zksync_api_types::v02::fee::ApiFee;
+use zksync_storage::{account_names::records::StorageAccountName, ConnectionPool};
+use zksync_types::{
+    account_name::{register_account_name_message, validate_account_name},
+    tx::TxHash,
+    Address, ZkSyncTx,
+};
+
+// Local uses
+use crate::api_server::{
+    rest::forced_exit_requests::{error::ApiError, JsonResult},
+    tx_sender::TxSender,
+};
+
+/// Shared data between `/api/names/v0.1/` endpoints.
+struct ApiNamesData {
+    connection_pool: ConnectionPool,
+    tx_sender: TxSender,
+}
+
+fn account_name(record: StorageAccountName) -> AccountName {
+    let status = match record.fee_tx_success {
+        Some(true) => AccountNameStatus::Active,
+        Some(false) => AccountNameStatus::Failed,
+        None => AccountNameStatus::Pending,
+    };
+    AccountName {
+        name: record.name,
+        address: Address::from_slice(&record.address),
+        fee_tx_hash: TxHash::from_slice(&record.fee_tx_hash)
+            .expect("Incorrect tx hash in the database"),
+        status,
+        created_at: record.created_at,
+    }
+}
+
+/// Checks the registration and returns the address the name is registered for.
+/// The fee has to be paid by the owner's transfer to themselves and the owner has to
+/// sign the registration message.
+fn registration_owner(registration: &AccountNameRegistration) -> Result<Address, ApiError> {
+    validate_account_name(&registration.name).map_err(ApiError::bad_request)?;
+
+    let owner =
+        match &registration.fee_tx.tx {
+            ZkSyncTx::Transfer(transfer) if transfer.from == transfer.to => transfer.from,
+            _ => return Err(ApiError::bad_request(
+                "Registration fee has to be paid by the transfer to the registered address itself",
+            )),
+        };
+
+    let message =
+        register_account_name_message(&registration.name, owner, registration.fee_tx.tx.hash());
+    let signer = registration
+        .signature
+        .signature_recover_signer(message.as_bytes())
+        .map_err(ApiError::bad_request)?;
+    if signer != owner {
+        return Err(ApiError::bad_request(
+            "Registration is not signed by the owner of the address",
+        ));
+    }
+    Ok(owner)
+}
+
+async fn register_account_name(
+    data: web::Data<ApiNamesData>,
+    registration: web::Json<AccountNameRegistration>,
+) -> JsonResult<AccountName> {
+    let start = Instant::now();
+    let registration = registration.into_inner();
+    let owner = registration_owner(&registration)?;
+    let fee_tx_hash = registration.fee_tx.tx.hash();
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    // The name stays reserved until the fee transaction is accepted by the mempool,
+    // so the concurrent registrations of the same name don't pay the fee in vain.
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(ApiError::internal)?;
+    let reserved = transaction
+        .account_names_schema()
+        .reserve_account_name(&registration.name, owner, fee_tx_hash)
+        .await
+        .map_err(ApiError::internal)?;
+    if !reserved {
+        return Err(ApiError::bad_request("Name is already taken"));
+    }
+    data.tx_sender
+        .submit_account_name_fee(registration.fee_tx.tx, registration.fee_tx.signature)
+        .await?;
+    let record = transaction
+        .account_names_schema()
+        .load_account_name(&registration.name)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::internal("Registered name is not found"))?;
+    transaction.commit().await.map_err(ApiError::internal)?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "register_account_name");
+    Ok(Json(account_name(record)))
+}
+
+async fn account_name_fee(
+    data: web::Data<ApiNamesData>,
+    request: web::Json<AccountNameFeeRequest>,
+) -> JsonResult<ApiFee> {
+    let start = Instant::now();
+    let request = request.into_inner();
+    let fee = data
+        .tx_sender
+        .account_name_fee(request.token, request.address)
+        .await?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "account_name_fee");
+    Ok(Json(fee.into()))
+}
+
+async fn resolve_account_name(
+    data: web::Data<ApiNamesData>,
+    name: web::Path<String>,
+) -> JsonResult<AccountName> {
+    let start = Instant::now();
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    let record = storage
+        .account_names_schema()
+        .load_account_name(&name)
+        .await
+        .map_err(ApiError::internal)?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "resolve_account_name");
+    match record {
+        Some(record) => Ok(Json(account_name(record))),
+        None => Err(ApiError::not_found("Name is not registered")),
+    }
+}
+
+pub fn api_scope(connection_pool: ConnectionPool, tx_sender: TxSender) -> Scope {
+    let data = ApiNamesData {
+        connection_pool,
+        tx_sender,
+    };
+
+    web::scope("v0.1")
+        .app_data(web::Data::new(data))
+        .route("/register", web::post().to(register_account_name))
+        .route("/fee", web::post().to(account_name_fee))
+        .route("/resolve/{name}", web::get().to(resolve_account_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::BigUint;
+    use zksync_api_types::TxWithSignature;
+    use zksync_types::{
+        tx::{PackedEthSignature, TxEthSignatureVariant},
+        AccountId, Nonce, TokenId, Transfer, H256,
+    };
+
+    fn registration(
+        name: &str,
+        private_key: &H256,
+        to: Option<Address>,
+    ) -> AccountNameRegistration {
+        let owner = PackedEthSignature::address_from_private_key(private_key).unwrap();
+        let transfer = Transfer::new(
+            AccountId(1),
+            owner,
+            to.unwrap_or(owner),
+            TokenId(0),
+            BigUint::from(0u32),
+            BigUint::from(100u32),
+            Nonce(0),
+            Default::default(),
+            None,
+        );
+        let fee_tx = ZkSyncTx::from(transfer);
+        let message = register_account_name_message(name, owner, fee_tx.hash());
+        AccountNameRegistration {
+            name: name.to_string(),
+            fee_tx: TxWithSignature {
+                tx: fee_tx,
+                signature: TxEthSignatureVariant::Single(None),
+            },
+            signature: PackedEthSignature::sign(private_key, message.as_bytes()).unwrap(),
+        }
+    }
+
+    #[test]
+    fn registration_checks() {
+        let private_key = H256::repeat_byte(7);
+        let owner = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+
+        assert_eq!(
+            registration_owner(&registration("alice", &private_key, None)).unwrap(),
+            owner
+        );
+        // Invalid name.
+        assert!(registration_owner(&registration("Alice", &private_key, None)).is_err());
+        // The fee has to be paid by the transfer to the owner.
+        assert!(registration_owner(&registration(
+            "alice",
+            &private_key,
+            Some(Address::repeat_byte(1))
+        ))
+        .is_err());
+        // The signature has to cover the registered name.
+        let mut wrong_name = registration("alice", &private_key, None);
+        wrong_name.name = "bob".to_string();
+        assert!(registration_owner(&wrong_name).is_err());
+    }
+}
@@ -5,6 +5,7 @@ use std::time::Duration;
 use tokio::sync::RwLock;
 use tokio::{runtime::Runtime, time};
 use zksync_api_types::CoreStatus;
+use zksync_eth_client::EthereumGateway;
 use zksync_storage::ConnectionPool;
 use zksync_types::{BlockNumber, SequentialTxId};
 use zksync_utils::panic_notify::ThreadPanicNotify;
@@ -18,6 +19,8 @@ pub struct NetworkStatus {
     pub outstanding_txs: u32,
     pub mempool_size: u32,
     pub core_status: Option<CoreStatus>,
+    /// The latest Ethereum block, `None` if the Ethereum node is not available.
+    pub last_eth_block: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -25,6 +28,7 @@ pub struct SharedNetworkStatus {
     status: Arc<RwLock<NetworkStatus>>,
     core_status_address: String,
     core_client: reqwest::Client,
+    eth_client: Option<EthereumGateway>,
 }
 
 impl SharedNetworkStatus {
@@ -35,9 +39,17 @@ impl SharedNetworkStatus {
             status: Default::default(),
             core_status_address,
             core_client,
+            eth_client: None,
         }
     }
 
+    /// Enables tracking of the latest Ethereum block, required to report the confirmations
+    /// of the deposits.
+    pub fn with_eth_client(mut self, eth_client: EthereumGateway) -> Self {
+        self.eth_client = Some(eth_client);
+        self
+    }
+
     pub async fn read(&self) -> NetworkStatus {
         (*self.status.as_ref().read().await).clone()
     }
@@ -104,6 +116,14 @@ impl SharedNetworkStatus {
         transaction.commit().await.unwrap_or_default();
 
         let core_status = self.get_core_status().await.ok();
+        let last_eth_block = match &self.eth_client {
+            Some(eth_client) => eth_client
+                .block_number()
+                .await
+                .ok()
+                .map(|block| block.as_u64()),
+            None => None,
+        };
         let status = NetworkStatus {
             next_block_at_max: None,
             last_committed,
@@ -112,6 +132,7 @@ impl SharedNetworkStatus {
             outstanding_txs,
             mempool_size,
             core_status,
+            last_eth_block,
         };
 
         // save status to state
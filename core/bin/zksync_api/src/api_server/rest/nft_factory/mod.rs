@@ -0,0 +1,22 @@
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_config::ZkSyncConfig;
+use zksync_eth_client::EthereumGateway;
+use zksync_storage::ConnectionPool;
+
+mod v01;
+
+pub(crate) fn api_scope(
+    connection_pool: ConnectionPool,
+    eth_client: EthereumGateway,
+    config: &ZkSyncConfig,
+) -> Scope {
+    web::scope("/api/nft_factory").service(v01::api_scope(
+        connection_pool,
+        eth_client,
+        config.contracts.governance_addr,
+        config.eth_watch.confirmations_for_eth_event,
+    ))
+}
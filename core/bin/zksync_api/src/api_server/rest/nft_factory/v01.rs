@@ -0,0 +1,327 @@
+//! NFT factory registration part of API implementation.
+
+// Built-in uses
+use std::convert::TryFrom;
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+
+// Workspace uses
+use zksync_api_client::rest::nft_factory::{NFTFactoryRegistration, RegisteredNFTFactory};
+use zksync_contracts::governance_contract;
+use zksync_eth_client::EthereumGateway;
+use zksync_storage::{tokens::records::StorageNFTFactory, ConnectionPool};
+use zksync_types::{
+    register_factory::register_nft_factory_message, AccountId, Address, Log,
+    RegisterNFTFactoryEvent, H256,
+};
+
+// Local uses
+use crate::api_server::rest::forced_exit_requests::{error::ApiError, JsonResult};
+
+/// Shared data between `/api/nft_factory/v0.1/` endpoints.
+struct ApiNFTFactoryData {
+    connection_pool: ConnectionPool,
+    eth_client: EthereumGateway,
+    governance_address: Address,
+    confirmations: u64,
+    factory_registered_topic: H256,
+}
+
+fn registered_nft_factory(record: StorageNFTFactory) -> RegisteredNFTFactory {
+    RegisteredNFTFactory {
+        creator_id: AccountId(record.creator_id as u32),
+        creator_address: record.creator_address(),
+        factory_address: record.factory_address(),
+        created_at: record.created_at,
+    }
+}
+
+/// Returns `true` if one of the logs is the `NFTFactoryRegisteredCreator` event of the
+/// Governance contract describing the requested registration.
+fn contains_registration_event(
+    logs: Vec<Log>,
+    governance_address: Address,
+    factory_registered_topic: H256,
+    registration: &NFTFactoryRegistration,
+) -> bool {
+    logs.into_iter()
+        .filter(|log| {
+            log.address == governance_address
+                && log.topics.first() == Some(&factory_registered_topic)
+        })
+        .filter_map(|log| RegisterNFTFactoryEvent::try_from(log).ok())
+        .any(|event| {
+            event.creator_id == registration.creator_id
+                && event.creator_address == registration.creator_address
+                && event.factory_address == registration.factory_address
+        })
+}
+
+impl ApiNFTFactoryData {
+    /// Checks that the registration transaction is successful, confirmed and emitted
+    /// the event of the requested registration.
+    async fn verify_registration_tx(
+        &self,
+        registration: &NFTFactoryRegistration,
+    ) -> Result<(), ApiError> {
+        let receipt = self
+            .eth_client
+            .tx_receipt(registration.eth_tx_hash)
+            .await
+            .map_err(ApiError::internal)?
+            .ok_or_else(|| ApiError::bad_request("Transaction is not found"))?;
+        if receipt.status != Some(1.into()) {
+            return Err(ApiError::bad_request("Transaction has failed"));
+        }
+
+        let tx_block = receipt
+            .block_number
+            .ok_or_else(|| ApiError::bad_request("Transaction is not mined yet"))?
+            .as_u64();
+        let last_block = self
+            .eth_client
+            .block_number()
+            .await
+            .map_err(ApiError::internal)?
+            .as_u64();
+        if last_block < tx_block + self.confirmations {
+            return Err(ApiError::bad_request("Transaction is not confirmed yet"));
+        }
+
+        if !contains_registration_event(
+            receipt.logs,
+            self.governance_address,
+            self.factory_registered_topic,
+            registration,
+        ) {
+            return Err(ApiError::bad_request(
+                "Transaction doesn't register the factory for the creator",
+            ));
+        }
+        Ok(())
+    }
+}
+
+async fn register_nft_factory(
+    data: web::Data<ApiNFTFactoryData>,
+    registration: web::Json<NFTFactoryRegistration>,
+) -> JsonResult<RegisteredNFTFactory> {
+    let start = Instant::now();
+    let registration = registration.into_inner();
+
+    let message = register_nft_factory_message(
+        registration.creator_id,
+        registration.creator_address,
+        registration.factory_address,
+    );
+    let signer = registration
+        .signature
+        .signature_recover_signer(&message)
+        .map_err(ApiError::bad_request)?;
+    if signer != registration.creator_address {
+        return Err(ApiError::bad_request(
+            "Registration is not signed by the creator",
+        ));
+    }
+
+    data.verify_registration_tx(&registration).await?;
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(ApiError::internal)?;
+    let account_id = transaction
+        .chain()
+        .account_schema()
+        .account_id_by_address(registration.creator_address)
+        .await
+        .map_err(ApiError::internal)?;
+    if account_id != Some(registration.creator_id) {
+        return Err(ApiError::bad_request(
+            "Creator account ID doesn't match the creator address",
+        ));
+    }
+    transaction
+        .tokens_schema()
+        .store_nft_factory(
+            registration.creator_id,
+            registration.creator_address,
+            registration.factory_address,
+        )
+        .await
+        .map_err(ApiError::internal)?;
+    let record = transaction
+        .tokens_schema()
+        .load_nft_factory(registration.creator_id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::internal("Registered factory is not found"))?;
+    transaction.commit().await.map_err(ApiError::internal)?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "register_nft_factory");
+    Ok(Json(registered_nft_factory(record)))
+}
+
+async fn get_nft_factory(
+    data: web::Data<ApiNFTFactoryData>,
+    creator_id: web::Path<AccountId>,
+) -> JsonResult<RegisteredNFTFactory> {
+    let start = Instant::now();
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    let record = storage
+        .tokens_schema()
+        .load_nft_factory(*creator_id)
+        .await
+        .map_err(ApiError::internal)?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "get_nft_factory");
+    match record {
+        Some(record) => Ok(Json(registered_nft_factory(record))),
+        None => Err(ApiError::not_found("Creator has no registered factory")),
+    }
+}
+
+pub fn api_scope(
+    connection_pool: ConnectionPool,
+    eth_client: EthereumGateway,
+    governance_address: Address,
+    confirmations: u64,
+) -> Scope {
+    let factory_registered_topic = governance_contract()
+        .event("NFTFactoryRegisteredCreator")
+        .expect("Governance contract abi error")
+        .signature();
+    let data = ApiNFTFactoryData {
+        connection_pool,
+        eth_client,
+        governance_address,
+        confirmations,
+        factory_registered_topic,
+    };
+
+    web::scope("v0.1")
+        .app_data(web::Data::new(data))
+        .route("/register", web::post().to(register_nft_factory))
+        .route("/creators/{creator_id}", web::get().to(get_nft_factory))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use web3::types::Bytes;
+    use zksync_types::tx::PackedEthSignature;
+
+    fn registration_log(
+        governance_address: Address,
+        topic: H256,
+        creator_id: AccountId,
+        creator_address: Address,
+        factory_address: Address,
+    ) -> Log {
+        Log {
+            address: governance_address,
+            topics: vec![
+                topic,
+                H256::from_low_u64_be(u64::from(*creator_id)),
+                creator_address.into(),
+            ],
+            data: Bytes(H256::from(factory_address).as_bytes().to_vec()),
+            block_hash: None,
+            block_number: Some(10.into()),
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        }
+    }
+
+    #[test]
+    fn registration_event_lookup() {
+        let governance_address = Address::repeat_byte(1);
+        let topic = governance_contract()
+            .event("NFTFactoryRegisteredCreator")
+            .unwrap()
+            .signature();
+        let creator_private_key = H256::repeat_byte(5);
+        let creator_address =
+            PackedEthSignature::address_from_private_key(&creator_private_key).unwrap();
+        let factory_address = Address::repeat_byte(3);
+        let message = register_nft_factory_message(AccountId(7), creator_address, factory_address);
+        let registration = NFTFactoryRegistration {
+            eth_tx_hash: H256::zero(),
+            creator_id: AccountId(7),
+            creator_address,
+            factory_address,
+            signature: PackedEthSignature::sign(&creator_private_key, &message).unwrap(),
+        };
+        assert_eq!(
+            registration
+                .signature
+                .signature_recover_signer(&message)
+                .unwrap(),
+            creator_address
+        );
+
+        let log = registration_log(
+            governance_address,
+            topic,
+            AccountId(7),
+            creator_address,
+            factory_address,
+        );
+        assert!(contains_registration_event(
+            vec![log.clone()],
+            governance_address,
+            topic,
+            &registration
+        ));
+
+        // The event has to be emitted by the Governance contract.
+        let mut foreign_log = log.clone();
+        foreign_log.address = Address::repeat_byte(2);
+        assert!(!contains_registration_event(
+            vec![foreign_log],
+            governance_address,
+            topic,
+            &registration
+        ));
+
+        // The event has to describe the same registration.
+        let other_factory_log = registration_log(
+            governance_address,
+            topic,
+            AccountId(7),
+            creator_address,
+            Address::repeat_byte(4),
+        );
+        let other_creator_log = registration_log(
+            governance_address,
+            topic,
+            AccountId(8),
+            creator_address,
+            factory_address,
+        );
+        assert!(!contains_registration_event(
+            vec![other_factory_log, other_creator_log],
+            governance_address,
+            topic,
+            &registration
+        ));
+    }
+}
@@ -0,0 +1,15 @@
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_storage::ConnectionPool;
+use zksync_types::Address;
+
+mod releaser;
+mod v01;
+
+pub use releaser::start_onchain_auth_releaser_detached;
+
+pub(crate) fn api_scope(connection_pool: ConnectionPool, contract: Address) -> Scope {
+    web::scope("/api/onchain_auth").service(v01::api_scope(connection_pool, contract))
+}
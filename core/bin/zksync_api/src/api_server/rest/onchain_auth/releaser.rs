@@ -0,0 +1,116 @@
+//! Background task submitting the queued onchain `ChangePubKey` transactions.
+//!
+//! The auth facts of the queued transactions are periodically checked on L1, and once the fact
+//! matching the new public key hash appears, the transaction is submitted as if it was sent
+//! by the user.
+
+// Built-in uses
+use std::time::Duration;
+
+// External uses
+use futures::channel::mpsc;
+use tokio::{runtime::Runtime, time};
+
+// Workspace uses
+use zksync_storage::{
+    chain::mempool::{ONCHAIN_AUTH_FAILED, ONCHAIN_AUTH_RELEASED},
+    ConnectionPool,
+};
+use zksync_types::{
+    tx::{ChangePubKey, TxEthSignatureVariant, TxHash},
+    Address, Nonce, ZkSyncTx,
+};
+use zksync_utils::panic_notify::ThreadPanicNotify;
+
+// Local uses
+use crate::{api_server::tx_sender::TxSender, eth_checker::EthereumChecker};
+
+async fn release_authorized_txs(
+    connection_pool: &ConnectionPool,
+    eth_checker: &EthereumChecker,
+    tx_sender: &TxSender,
+) -> anyhow::Result<()> {
+    let waiting = connection_pool
+        .access_storage()
+        .await?
+        .chain()
+        .mempool_schema()
+        .load_waiting_onchain_auth_txs()
+        .await?;
+
+    for record in waiting {
+        let tx_hash = TxHash::from_slice(&record.tx_hash)
+            .ok_or_else(|| anyhow::anyhow!("Incorrect tx hash in the database"))?;
+        let tx: ChangePubKey = serde_json::from_value(record.tx)?;
+        let is_authorized = eth_checker
+            .is_new_pubkey_hash_authorized(
+                Address::from_slice(&record.account_address),
+                Nonce(record.nonce as u32),
+                &tx.new_pk_hash,
+            )
+            .await?;
+        if !is_authorized {
+            continue;
+        }
+
+        let (status, fail_reason) = match tx_sender
+            .submit_tx(
+                ZkSyncTx::ChangePubKey(Box::new(tx)),
+                TxEthSignatureVariant::Single(None),
+                None,
+            )
+            .await
+        {
+            Ok(_) => {
+                metrics::increment_counter!("api.onchain_auth.released_txs");
+                (ONCHAIN_AUTH_RELEASED, None)
+            }
+            Err(err) => {
+                vlog::warn!("Queued ChangePubKey {} was rejected: {}", tx_hash, err);
+                metrics::increment_counter!("api.onchain_auth.failed_txs");
+                (ONCHAIN_AUTH_FAILED, Some(err.to_string()))
+            }
+        };
+        connection_pool
+            .access_storage()
+            .await?
+            .chain()
+            .mempool_schema()
+            .update_onchain_auth_tx_status(tx_hash, status, fail_reason)
+            .await?;
+    }
+    Ok(())
+}
+
+pub fn start_onchain_auth_releaser_detached(
+    panic_notify: mpsc::Sender<bool>,
+    connection_pool: ConnectionPool,
+    eth_checker: EthereumChecker,
+    tx_sender: TxSender,
+    interval: Duration,
+) {
+    std::thread::Builder::new()
+        .name("rest-onchain-auth-releaser".to_string())
+        .spawn(move || {
+            let _panic_sentinel = ThreadPanicNotify(panic_notify.clone());
+
+            let runtime = Runtime::new().expect("tokio runtime creation");
+
+            let releaser_task = async move {
+                let mut timer = time::interval(interval);
+                loop {
+                    timer.tick().await;
+                    if let Err(err) =
+                        release_authorized_txs(&connection_pool, &eth_checker, &tx_sender).await
+                    {
+                        vlog::error!(
+                            "Can't release the queued ChangePubKey transactions: {}",
+                            err
+                        );
+                    }
+                }
+            };
+            runtime.block_on(releaser_task);
+        })
+        .expect("Onchain auth releaser thread");
+}
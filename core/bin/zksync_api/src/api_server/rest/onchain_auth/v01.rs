@@ -0,0 +1,176 @@
+//! Onchain `ChangePubKey` authorization part of API implementation.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+use ethabi::Token;
+
+// Workspace uses
+use zksync_api_client::rest::onchain_auth::{
+    AuthCalldata, AuthCalldataRequest, OnchainAuthStatus, QueuedChangePubKey,
+};
+use zksync_contracts::zksync_contract;
+use zksync_storage::{
+    chain::mempool::{records::OnchainAuthTx, ONCHAIN_AUTH_FAILED, ONCHAIN_AUTH_RELEASED},
+    ConnectionPool,
+};
+use zksync_types::{
+    tx::{ChangePubKey, TxHash},
+    Address, Nonce, PubKeyHash, ZkSyncTx, U256,
+};
+
+// Local uses
+use crate::api_server::rest::forced_exit_requests::{error::ApiError, JsonResult};
+
+/// Shared data between `/api/onchain_auth/v0.1/` endpoints.
+struct ApiOnchainAuthData {
+    connection_pool: ConnectionPool,
+    contract_address: Address,
+}
+
+/// Encodes the call of `setAuthPubkeyHash` authorizing the public key hash for the nonce.
+fn set_auth_pubkey_hash_calldata(pub_key_hash: &PubKeyHash, nonce: Nonce) -> Vec<u8> {
+    zksync_contract()
+        .function("setAuthPubkeyHash")
+        .expect("setAuthPubkeyHash function is not found in the contract ABI")
+        .encode_input(&[
+            Token::Bytes(pub_key_hash.data.to_vec()),
+            Token::Uint(U256::from(*nonce)),
+        ])
+        .expect("Failed to encode setAuthPubkeyHash call")
+}
+
+pub(super) fn queued_change_pubkey(record: OnchainAuthTx) -> QueuedChangePubKey {
+    let status = match record.status.as_str() {
+        ONCHAIN_AUTH_RELEASED => OnchainAuthStatus::Released,
+        ONCHAIN_AUTH_FAILED => OnchainAuthStatus::Failed,
+        _ => OnchainAuthStatus::Waiting,
+    };
+    QueuedChangePubKey {
+        tx_hash: TxHash::from_slice(&record.tx_hash).expect("Incorrect tx hash in the database"),
+        status,
+        fail_reason: record.fail_reason,
+        created_at: record.created_at,
+        updated_at: record.updated_at,
+    }
+}
+
+async fn auth_calldata(
+    data: web::Data<ApiOnchainAuthData>,
+    params: web::Json<AuthCalldataRequest>,
+) -> JsonResult<AuthCalldata> {
+    let start = Instant::now();
+    let calldata = set_auth_pubkey_hash_calldata(&params.pub_key_hash, params.nonce);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "onchain_auth_calldata");
+    Ok(Json(AuthCalldata {
+        contract_address: data.contract_address,
+        calldata: format!("0x{}", hex::encode(calldata)),
+    }))
+}
+
+async fn queue_change_pubkey(
+    data: web::Data<ApiOnchainAuthData>,
+    tx: web::Json<ChangePubKey>,
+) -> JsonResult<QueuedChangePubKey> {
+    let start = Instant::now();
+    let mut tx = tx.into_inner();
+    if !tx.is_onchain() {
+        return Err(ApiError::bad_request(
+            "Only ChangePubKey transactions authorized onchain can be queued",
+        ));
+    }
+    tx.check_correctness().map_err(ApiError::bad_request)?;
+    let tx_hash = ZkSyncTx::ChangePubKey(Box::new(tx.clone())).hash();
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(ApiError::internal)?;
+    transaction
+        .chain()
+        .mempool_schema()
+        .enqueue_onchain_auth_tx(tx_hash, &tx)
+        .await
+        .map_err(ApiError::internal)?;
+    let record = transaction
+        .chain()
+        .mempool_schema()
+        .get_onchain_auth_tx(tx_hash)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::internal("Queued transaction is not found"))?;
+    transaction.commit().await.map_err(ApiError::internal)?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "queue_onchain_change_pubkey");
+    Ok(Json(queued_change_pubkey(record)))
+}
+
+async fn get_queued_change_pubkey(
+    data: web::Data<ApiOnchainAuthData>,
+    tx_hash: web::Path<TxHash>,
+) -> JsonResult<QueuedChangePubKey> {
+    let start = Instant::now();
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    let record = storage
+        .chain()
+        .mempool_schema()
+        .get_onchain_auth_tx(*tx_hash)
+        .await
+        .map_err(ApiError::internal)?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "get_queued_change_pubkey");
+    match record {
+        Some(record) => Ok(Json(queued_change_pubkey(record))),
+        None => Err(ApiError::not_found(
+            "Transaction with such hash is not queued",
+        )),
+    }
+}
+
+pub fn api_scope(connection_pool: ConnectionPool, contract_address: Address) -> Scope {
+    let data = ApiOnchainAuthData {
+        connection_pool,
+        contract_address,
+    };
+
+    web::scope("v0.1")
+        .app_data(web::Data::new(data))
+        .route("/calldata", web::post().to(auth_calldata))
+        .route("/queue", web::post().to(queue_change_pubkey))
+        .route("/queue/{tx_hash}", web::get().to(get_queued_change_pubkey))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calldata_encoding() {
+        let pub_key_hash =
+            PubKeyHash::from_hex("sync:1234567890123456789012345678901234567890").unwrap();
+        let calldata = set_auth_pubkey_hash_calldata(&pub_key_hash, Nonce(3));
+
+        let selector = tiny_keccak::keccak256(b"setAuthPubkeyHash(bytes,uint32)");
+        assert_eq!(calldata[..4], selector[..4]);
+        // Offset of the bytes argument, nonce, length of the bytes and the padded bytes.
+        assert_eq!(calldata.len(), 4 + 32 * 4);
+        assert_eq!(U256::from_big_endian(&calldata[36..68]), U256::from(3));
+        assert_eq!(U256::from_big_endian(&calldata[68..100]), U256::from(20));
+        assert_eq!(&calldata[100..120], &pub_key_hash.data[..]);
+    }
+}
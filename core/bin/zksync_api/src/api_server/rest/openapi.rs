@@ -0,0 +1,288 @@
+//! Generation of the OpenAPI specification of the REST API.
+//!
+//! Every API module declares the documentation of its endpoints next to the routes in the
+//! `api_docs` function, request and response schemas are derived from the API types via
+//! `JsonSchema`, so the document can't drift from the actual structures. Path parameters are
+//! derived from the path templates, the list endpoints get the pagination query parameters and
+//! every response is described with the common v0.3 envelope.
+
+// External uses
+use schemars::{
+    gen::{SchemaGenerator, SchemaSettings},
+    schema::Schema,
+    JsonSchema,
+};
+use serde_json::{json, Map, Value};
+
+// Workspace uses
+use zksync_api_types::v03::Response;
+
+type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+fn schema_of<T: JsonSchema>(gen: &mut SchemaGenerator) -> Schema {
+    gen.subschema_for::<T>()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Method {
+    Get,
+    Post,
+}
+
+impl Method {
+    fn as_str(self) -> &'static str {
+        match self {
+            Method::Get => "get",
+            Method::Post => "post",
+        }
+    }
+}
+
+/// Documentation of the API endpoint.
+pub struct Endpoint {
+    method: Method,
+    /// Path relative to the API prefix, parameters are written as `{name}`.
+    path: &'static str,
+    tag: &'static str,
+    summary: &'static str,
+    /// Whether the endpoint returns a paginated list of `result` items.
+    paginated: bool,
+    /// Optional query parameters besides the pagination ones.
+    query: &'static [&'static str],
+    body: Option<SchemaFn>,
+    result: SchemaFn,
+}
+
+impl Endpoint {
+    pub fn get<R: JsonSchema>(
+        path: &'static str,
+        tag: &'static str,
+        summary: &'static str,
+    ) -> Self {
+        Self {
+            method: Method::Get,
+            path,
+            tag,
+            summary,
+            paginated: false,
+            query: &[],
+            body: None,
+            result: schema_of::<R>,
+        }
+    }
+
+    pub fn post<B: JsonSchema, R: JsonSchema>(
+        path: &'static str,
+        tag: &'static str,
+        summary: &'static str,
+    ) -> Self {
+        Self {
+            body: Some(schema_of::<B>),
+            method: Method::Post,
+            ..Self::get::<R>(path, tag, summary)
+        }
+    }
+
+    pub fn paginated(mut self) -> Self {
+        self.paginated = true;
+        self
+    }
+
+    pub fn with_query(mut self, query: &'static [&'static str]) -> Self {
+        self.query = query;
+        self
+    }
+
+    fn path_params(&self) -> impl Iterator<Item = &'static str> {
+        self.path
+            .split('/')
+            .filter(|segment| segment.starts_with('{') && segment.ends_with('}'))
+            .map(|segment| &segment[1..segment.len() - 1])
+    }
+
+    fn operation(&self, gen: &mut SchemaGenerator, envelope: &Schema) -> Value {
+        let mut parameters: Vec<Value> = self
+            .path_params()
+            .map(|name| parameter(name, "path", true))
+            .collect();
+        if self.paginated {
+            parameters.push(parameter("from", "query", true));
+            parameters.push(json!({
+                "name": "limit",
+                "in": "query",
+                "required": true,
+                "schema": { "type": "integer", "minimum": 1, "maximum": 100 },
+            }));
+            parameters.push(json!({
+                "name": "direction",
+                "in": "query",
+                "required": true,
+                "schema": { "type": "string", "enum": ["newer", "older"] },
+            }));
+        }
+        parameters.extend(
+            self.query
+                .iter()
+                .map(|name| parameter(name, "query", false)),
+        );
+
+        let result = (self.result)(gen);
+        let result = if self.paginated {
+            json!({ "type": "array", "items": result })
+        } else {
+            json!(result)
+        };
+        let mut operation = json!({
+            "tags": [self.tag],
+            "summary": self.summary,
+            "parameters": parameters,
+            "responses": {
+                "default": {
+                    "description": "Response envelope",
+                    "content": {
+                        "application/json": {
+                            "schema": {
+                                "allOf": [
+                                    envelope,
+                                    { "type": "object", "properties": { "result": result } }
+                                ]
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        if let Some(body) = self.body {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": {
+                    "application/json": { "schema": body(gen) }
+                }
+            });
+        }
+        operation
+    }
+}
+
+fn parameter(name: &str, location: &str, required: bool) -> Value {
+    json!({
+        "name": name,
+        "in": location,
+        "required": required,
+        "schema": { "type": "string" },
+    })
+}
+
+/// Generates the OpenAPI document for the endpoints served under the `prefix`.
+pub fn openapi_spec(prefix: &str, version: &str, endpoints: &[Endpoint]) -> Value {
+    let mut gen = SchemaSettings::openapi3().into_generator();
+    let envelope = gen.subschema_for::<Response>();
+
+    let mut paths = Map::new();
+    for endpoint in endpoints {
+        let operation = endpoint.operation(&mut gen, &envelope);
+        let path = paths
+            .entry(format!("{}{}", prefix, endpoint.path))
+            .or_insert_with(|| json!({}));
+        path[endpoint.method.as_str()] = operation;
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "zkSync API",
+            "version": version,
+        },
+        "paths": paths,
+        "components": {
+            "schemas": gen.take_definitions(),
+        },
+    })
+}
+
+/// Page of Swagger UI rendering the specification served at `spec_url`.
+pub fn swagger_ui(title: &str, spec_url: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="utf-8">
+    <title>{title}</title>
+    <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@4/swagger-ui.css">
+</head>
+<body>
+    <div id="swagger-ui"></div>
+    <script src="https://unpkg.com/swagger-ui-dist@4/swagger-ui-bundle.js"></script>
+    <script>
+        window.onload = () => SwaggerUIBundle({{ url: "{spec_url}", dom_id: "#swagger-ui" }});
+    </script>
+</body>
+</html>"#,
+        title = title,
+        spec_url = spec_url
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Collects all the `$ref` links of the document.
+    fn collect_refs<'a>(value: &'a Value, refs: &mut Vec<&'a str>) {
+        match value {
+            Value::Object(object) => {
+                for (key, value) in object {
+                    match value {
+                        Value::String(link) if key == "$ref" => refs.push(link),
+                        value => collect_refs(value, refs),
+                    }
+                }
+            }
+            Value::Array(array) => array.iter().for_each(|value| collect_refs(value, refs)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn spec_consistency() {
+        let endpoints = crate::api_server::rest::v02::api_docs();
+        let spec = openapi_spec("/api/v0.3", "0.3", &endpoints);
+        let paths = spec["paths"].as_object().unwrap();
+
+        let mut operations = HashSet::new();
+        for endpoint in &endpoints {
+            assert!(
+                operations.insert((endpoint.method, endpoint.path)),
+                "Duplicate endpoint {}",
+                endpoint.path
+            );
+            let operation =
+                &paths[&format!("/api/v0.3{}", endpoint.path)][endpoint.method.as_str()];
+            let params: Vec<_> = operation["parameters"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|param| param["in"] == "path")
+                .map(|param| param["name"].as_str().unwrap())
+                .collect();
+            assert_eq!(params, endpoint.path_params().collect::<Vec<_>>());
+            assert_eq!(
+                endpoint.body.is_some(),
+                operation.get("requestBody").is_some()
+            );
+        }
+
+        // Every referenced schema must be defined.
+        let schemas = spec["components"]["schemas"].as_object().unwrap();
+        let mut refs = Vec::new();
+        collect_refs(&spec, &mut refs);
+        assert!(!refs.is_empty());
+        for link in refs {
+            let name = link.trim_start_matches("#/components/schemas/");
+            assert!(schemas.contains_key(name), "Schema {} is not defined", name);
+        }
+        assert!(schemas.contains_key("Response"));
+        assert!(schemas.contains_key("Transaction"));
+    }
+}
@@ -0,0 +1,76 @@
+// Built-in uses
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_storage::ConnectionPool;
+
+// Local uses
+use crate::api_server::tx_sender::TxSender;
+
+mod v01;
+
+/// Window over which the submitted batches of the client are counted.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Sliding window limit of the batches submitted by the clients of the channel.
+///
+/// The limiter is shared by the workers of the server, but every API server tracks the limits
+/// separately, so the effective limit is multiplied by the number of the servers.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BatchRateLimiter(Arc<Mutex<HashMap<i64, VecDeque<Instant>>>>);
+
+impl BatchRateLimiter {
+    /// Records the batch of the client, returns `false` if the client has already submitted
+    /// `limit` batches within the window.
+    fn try_acquire(&self, client_id: i64, limit: u32, now: Instant) -> bool {
+        let mut clients = self.0.lock().unwrap();
+        let submitted = clients.entry(client_id).or_default();
+        while let Some(submitted_at) = submitted.front() {
+            if now.duration_since(*submitted_at) < RATE_LIMIT_WINDOW {
+                break;
+            }
+            submitted.pop_front();
+        }
+
+        if submitted.len() >= limit as usize {
+            return false;
+        }
+        submitted.push_back(now);
+        true
+    }
+}
+
+pub(crate) fn api_scope(
+    connection_pool: ConnectionPool,
+    tx_sender: TxSender,
+    rate_limiter: BatchRateLimiter,
+) -> Scope {
+    web::scope("/api/private_txs").service(v01::api_scope(connection_pool, tx_sender, rate_limiter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limiter() {
+        let limiter = BatchRateLimiter::default();
+        let now = Instant::now();
+
+        assert!(limiter.try_acquire(1, 2, now));
+        assert!(limiter.try_acquire(1, 2, now + Duration::from_secs(10)));
+        assert!(!limiter.try_acquire(1, 2, now + Duration::from_secs(20)));
+        // Limits of the clients are independent.
+        assert!(limiter.try_acquire(2, 2, now + Duration::from_secs(20)));
+        // Rejected batches are not counted, and the window slides.
+        assert!(limiter.try_acquire(1, 2, now + RATE_LIMIT_WINDOW));
+        assert!(!limiter.try_acquire(1, 2, now + RATE_LIMIT_WINDOW));
+        // Clients with zero limit can't submit anything.
+        assert!(!limiter.try_acquire(3, 0, now));
+    }
+}
@@ -0,0 +1,121 @@
+//! Private transactions part of API implementation.
+//!
+//! Requests are authorized by the bearer token of the client registered by the operator, which
+//! is resolved to the client by the authentication middleware.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    dev::ServiceRequest,
+    web::{self, Json},
+    HttpMessage, Scope,
+};
+use actix_web_httpauth::{
+    extractors::{
+        bearer::{BearerAuth, Config},
+        AuthenticationError,
+    },
+    middleware::HttpAuthentication,
+};
+
+// Workspace uses
+use zksync_api_types::v02::transaction::{IncomingTxBatch, SubmitBatchResponse};
+use zksync_storage::ConnectionPool;
+use zksync_types::private_tx::private_tx_token_hash;
+
+// Local uses
+use super::BatchRateLimiter;
+use crate::api_server::{
+    rest::forced_exit_requests::{error::ApiError, JsonResult},
+    tx_sender::TxSender,
+};
+
+/// Shared data between `/api/private_txs/v0.1/` endpoints.
+struct ApiPrivateTxsData {
+    tx_sender: TxSender,
+    rate_limiter: BatchRateLimiter,
+}
+
+/// Client of the channel authorizing the request, set by the authentication middleware.
+#[derive(Debug, Clone, Copy)]
+struct PrivateTxClient {
+    id: i64,
+    max_batches_per_minute: u32,
+}
+
+/// Resolves the bearer token to the client and attaches it to the request.
+async fn authorize(
+    connection_pool: ConnectionPool,
+    req: ServiceRequest,
+    credentials: BearerAuth,
+) -> actix_web::Result<ServiceRequest> {
+    let mut storage = connection_pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    let client = storage
+        .private_txs_schema()
+        .get_client_by_token(&private_tx_token_hash(credentials.token()))
+        .await
+        .map_err(ApiError::internal)?;
+    let client = match client {
+        Some(client) => client,
+        None => {
+            let config = req.app_data::<Config>().cloned().unwrap_or_default();
+            return Err(AuthenticationError::from(config).into());
+        }
+    };
+
+    req.extensions_mut().insert(PrivateTxClient {
+        id: client.id,
+        max_batches_per_minute: client.max_batches_per_minute as u32,
+    });
+    Ok(req)
+}
+
+async fn submit_batch(
+    data: web::Data<ApiPrivateTxsData>,
+    client: web::ReqData<PrivateTxClient>,
+    batch: web::Json<IncomingTxBatch>,
+) -> JsonResult<SubmitBatchResponse> {
+    let start = Instant::now();
+    if !data
+        .rate_limiter
+        .try_acquire(client.id, client.max_batches_per_minute, start)
+    {
+        return Err(ApiError::too_many_requests(format!(
+            "At most {} batches can be submitted per minute",
+            client.max_batches_per_minute
+        )));
+    }
+
+    let batch = batch.into_inner();
+    let response = data
+        .tx_sender
+        .submit_private_txs_batch(client.id, batch.txs, batch.signature)
+        .await?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "submit_private_txs_batch");
+    Ok(Json(response))
+}
+
+pub fn api_scope(
+    connection_pool: ConnectionPool,
+    tx_sender: TxSender,
+    rate_limiter: BatchRateLimiter,
+) -> Scope {
+    let auth = HttpAuthentication::bearer(move |req, credentials| {
+        authorize(connection_pool.clone(), req, credentials)
+    });
+    let data = ApiPrivateTxsData {
+        tx_sender,
+        rate_limiter,
+    };
+
+    web::scope("v0.1")
+        .app_data(web::Data::new(data))
+        .wrap(auth)
+        .route("/batches", web::post().to(submit_batch))
+}
@@ -0,0 +1,13 @@
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_storage::ConnectionPool;
+
+// Local uses
+
+mod v01;
+
+pub(crate) fn api_scope(connection_pool: ConnectionPool) -> Scope {
+    web::scope("/api/read_tokens").service(v01::api_scope(connection_pool))
+}
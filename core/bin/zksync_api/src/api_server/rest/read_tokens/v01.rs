@@ -0,0 +1,374 @@
+//! Read tokens part of API implementation.
+//!
+//! Tokens are issued for the grants signed by the owners of the accounts. Endpoints of the
+//! `account` scope are authorized by the bearer token, which is resolved to the grant by the
+//! authentication middleware.
+
+// Built-in uses
+use std::{collections::HashMap, time::Instant};
+
+// External uses
+use actix_web::{
+    dev::ServiceRequest,
+    web::{self, Json},
+    HttpMessage, Scope,
+};
+use actix_web_httpauth::{
+    extractors::{
+        bearer::{BearerAuth, Config},
+        AuthenticationError,
+    },
+    middleware::HttpAuthentication,
+};
+use chrono::{DateTime, SubsecRound, Utc};
+
+// Workspace uses
+use zksync_api_client::rest::{
+    read_tokens::{
+        ReadToken, ReadTokenGrant, ReadTokenHistoryEntry, ReadTokenHistoryQuery, ReadTokenInfo,
+        ReadTokenRevocation,
+    },
+    travel_rule::TravelRuleMetadata,
+};
+use zksync_api_types::{
+    v02::pagination::{
+        AccountTxsRequest, ApiEither, Latest, PaginationDirection, PaginationQuery, MAX_LIMIT,
+    },
+    Either,
+};
+use zksync_storage::ConnectionPool;
+use zksync_types::{
+    read_token::{read_token_grant_message, read_token_revoke_message, validate_read_token_grant},
+    tx::{PackedEthSignature, TxHash},
+    Address, H256,
+};
+
+// Local uses
+use crate::api_server::rest::{
+    forced_exit_requests::{error::ApiError, JsonResult},
+    travel_rule::metadata_from_record,
+};
+
+/// Shared data between `/api/read_tokens/v0.1/` endpoints.
+struct ApiReadTokensData {
+    connection_pool: ConnectionPool,
+}
+
+/// Grant of the read token authorizing the request, set by the authentication middleware.
+#[derive(Debug, Clone)]
+struct ReadGrant {
+    id: i64,
+    address: Address,
+    grantee: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Only the hashes of the tokens are stored, so the leaked database doesn't give access
+/// to the accounts.
+fn read_token_hash(token: &str) -> [u8; 32] {
+    tiny_keccak::keccak256(token.as_bytes())
+}
+
+/// Checks that the message is signed by the owner of the address.
+fn check_owner_signature(
+    address: Address,
+    signature: &PackedEthSignature,
+    message: &str,
+) -> Result<(), ApiError> {
+    let signer = signature
+        .signature_recover_signer(message.as_bytes())
+        .map_err(ApiError::bad_request)?;
+    if signer != address {
+        return Err(ApiError::bad_request(
+            "Request is not signed by the owner of the address",
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the limit of the page, which is `MAX_LIMIT` unless set by the query.
+fn page_limit(limit: Option<u32>) -> Result<u32, ApiError> {
+    let limit = limit.unwrap_or(MAX_LIMIT);
+    if limit > MAX_LIMIT {
+        return Err(ApiError::bad_request(format!(
+            "Limit can't be greater than {}",
+            MAX_LIMIT
+        )));
+    }
+    Ok(limit)
+}
+
+/// Checks the grant and returns its expiration time, the fractions of a second are not
+/// covered by the signature and are dropped.
+fn grant_expiration(grant: &ReadTokenGrant, now: DateTime<Utc>) -> Result<DateTime<Utc>, ApiError> {
+    let expires_at = grant.expires_at.trunc_subsecs(0);
+    validate_read_token_grant(&grant.grantee, expires_at, now).map_err(ApiError::bad_request)?;
+    check_owner_signature(
+        grant.address,
+        &grant.signature,
+        &read_token_grant_message(&grant.grantee, expires_at),
+    )?;
+    Ok(expires_at)
+}
+
+async fn issue_token(
+    data: web::Data<ApiReadTokensData>,
+    grant: web::Json<ReadTokenGrant>,
+) -> JsonResult<ReadToken> {
+    let start = Instant::now();
+    let grant = grant.into_inner();
+    let expires_at = grant_expiration(&grant, Utc::now())?;
+
+    let token = hex::encode(H256::random().as_bytes());
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    let id = storage
+        .read_tokens_schema()
+        .store_token(
+            grant.address,
+            &read_token_hash(&token),
+            &grant.grantee,
+            expires_at,
+        )
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::bad_request("Token is already issued for this grant"))?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "issue_read_token");
+    Ok(Json(ReadToken {
+        id,
+        token,
+        address: grant.address,
+        grantee: grant.grantee,
+        expires_at,
+    }))
+}
+
+async fn revoke_token(
+    data: web::Data<ApiReadTokensData>,
+    revocation: web::Json<ReadTokenRevocation>,
+) -> JsonResult<()> {
+    let start = Instant::now();
+    check_owner_signature(
+        revocation.address,
+        &revocation.signature,
+        &read_token_revoke_message(revocation.id),
+    )?;
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    let revoked = storage
+        .read_tokens_schema()
+        .revoke_token(revocation.id, revocation.address)
+        .await
+        .map_err(ApiError::internal)?;
+    if !revoked {
+        return Err(ApiError::not_found("Token is not found or already revoked"));
+    }
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "revoke_read_token");
+    Ok(Json(()))
+}
+
+/// Resolves the bearer token to the active grant and attaches it to the request.
+async fn authorize(
+    connection_pool: ConnectionPool,
+    req: ServiceRequest,
+    credentials: BearerAuth,
+) -> actix_web::Result<ServiceRequest> {
+    let mut storage = connection_pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    let token = storage
+        .read_tokens_schema()
+        .get_active_token(&read_token_hash(credentials.token()))
+        .await
+        .map_err(ApiError::internal)?;
+    let token = match token {
+        Some(token) => token,
+        None => {
+            let config = req.app_data::<Config>().cloned().unwrap_or_default();
+            return Err(AuthenticationError::from(config).into());
+        }
+    };
+
+    req.extensions_mut().insert(ReadGrant {
+        id: token.id,
+        address: Address::from_slice(&token.account_address),
+        grantee: token.grantee,
+        expires_at: token.expires_at,
+    });
+    Ok(req)
+}
+
+async fn token_info(grant: web::ReqData<ReadGrant>) -> JsonResult<ReadTokenInfo> {
+    let grant = grant.into_inner();
+    Ok(Json(ReadTokenInfo {
+        id: grant.id,
+        address: grant.address,
+        grantee: grant.grantee,
+        expires_at: grant.expires_at,
+    }))
+}
+
+/// Pages the transactions of the account, the transfers are returned with their travel rule
+/// metadata.
+async fn account_history(
+    data: web::Data<ApiReadTokensData>,
+    grant: web::ReqData<ReadGrant>,
+    web::Query(query): web::Query<ReadTokenHistoryQuery>,
+) -> JsonResult<Vec<ReadTokenHistoryEntry>> {
+    let start = Instant::now();
+    let limit = page_limit(query.limit)?;
+
+    // The page of the account transactions includes the one it starts from, so the transaction
+    // `before` is loaded as well and skipped.
+    let (from, skip) = match query.before {
+        Some(tx_hash) => (ApiEither::from(tx_hash), 1),
+        None => (
+            ApiEither {
+                inner: Either::Right(Latest),
+            },
+            0,
+        ),
+    };
+    let pagination = PaginationQuery {
+        from: AccountTxsRequest {
+            address: grant.address,
+            tx_hash: from,
+            token: None,
+            second_address: None,
+        },
+        limit: limit + skip as u32,
+        direction: PaginationDirection::Older,
+    };
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    let txs: Vec<_> = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions(&pagination)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::not_found("Transaction is not found"))?
+        .into_iter()
+        .skip(skip)
+        .collect();
+
+    let tx_hashes: Vec<TxHash> = txs.iter().map(|tx| tx.tx_hash).collect();
+    let mut metadata: HashMap<_, _> = storage
+        .travel_rule_schema()
+        .load_metadata_records(&tx_hashes)
+        .await
+        .map_err(ApiError::internal)?
+        .into_iter()
+        .map(|record| metadata_from_record(record).map(|metadata| (metadata.tx_hash, metadata)))
+        .collect::<Result<_, _>>()?;
+    let history = txs
+        .into_iter()
+        .map(|tx| ReadTokenHistoryEntry {
+            travel_rule: metadata.remove(&tx.tx_hash),
+            tx,
+        })
+        .collect();
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "read_token_account_history");
+    Ok(Json(history))
+}
+
+async fn account_travel_rule_metadata(
+    data: web::Data<ApiReadTokensData>,
+    grant: web::ReqData<ReadGrant>,
+    web::Query(query): web::Query<ReadTokenHistoryQuery>,
+) -> JsonResult<Vec<TravelRuleMetadata>> {
+    let start = Instant::now();
+    let limit = page_limit(query.limit)?;
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    let records = storage
+        .travel_rule_schema()
+        .load_account_records(grant.address, query.before, limit)
+        .await
+        .map_err(ApiError::internal)?;
+    let metadata = records
+        .into_iter()
+        .map(metadata_from_record)
+        .collect::<Result<_, _>>()?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "read_token_travel_rule_metadata");
+    Ok(Json(metadata))
+}
+
+pub fn api_scope(connection_pool: ConnectionPool) -> Scope {
+    let auth_pool = connection_pool.clone();
+    let auth = HttpAuthentication::bearer(move |req, credentials| {
+        authorize(auth_pool.clone(), req, credentials)
+    });
+    let data = ApiReadTokensData { connection_pool };
+
+    web::scope("v0.1")
+        .app_data(web::Data::new(data))
+        .route("/issue", web::post().to(issue_token))
+        .route("/revoke", web::post().to(revoke_token))
+        .service(
+            web::scope("/account")
+                .wrap(auth)
+                .route("", web::get().to(token_info))
+                .route("/history", web::get().to(account_history))
+                .route("/travel_rule", web::get().to(account_travel_rule_metadata)),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn grant(private_key: &H256, grantee: &str, expires_at: DateTime<Utc>) -> ReadTokenGrant {
+        let message = read_token_grant_message(grantee, expires_at);
+        ReadTokenGrant {
+            address: PackedEthSignature::address_from_private_key(private_key).unwrap(),
+            grantee: grantee.to_string(),
+            expires_at,
+            signature: PackedEthSignature::sign(private_key, message.as_bytes()).unwrap(),
+        }
+    }
+
+    #[test]
+    fn grant_checks() {
+        let private_key = H256::repeat_byte(7);
+        let now = Utc::now();
+        let expires_at = now + Duration::days(30);
+
+        let mut signed = grant(&private_key, "Auditor", expires_at);
+        assert_eq!(
+            grant_expiration(&signed, now).unwrap(),
+            expires_at.trunc_subsecs(0)
+        );
+        // The grantee is covered by the signature.
+        signed.grantee = "Someone else".to_string();
+        assert!(grant_expiration(&signed, now).is_err());
+        // Only the owner of the address can grant the access.
+        let mut signed = grant(&private_key, "Auditor", expires_at);
+        signed.address = Address::repeat_byte(1);
+        assert!(grant_expiration(&signed, now).is_err());
+        // Expired grants are rejected.
+        let expired = grant(&private_key, "Auditor", now - Duration::days(1));
+        assert!(grant_expiration(&expired, now).is_err());
+    }
+}
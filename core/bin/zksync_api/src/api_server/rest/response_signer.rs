@@ -0,0 +1,89 @@
+//! Operator signatures of the API responses.
+//!
+//! See `zksync_api_types::v02::signature` for the signing scheme the clients verify against.
+
+// External uses
+use serde_json::Value;
+
+// Workspace uses
+use zksync_api_types::v02::signature::{
+    canonical_json, RESPONSE_SIGNATURE_HEADER, RESPONSE_SIGNER_HEADER,
+};
+use zksync_config::configs::api::CommonApiConfig;
+use zksync_types::{tx::PackedEthSignature, Address, H256};
+
+/// Signs the responses of the endpoints requiring the non-repudiable evidence.
+#[derive(Debug, Clone)]
+pub struct ResponseSigner {
+    private_key: H256,
+    address: Address,
+}
+
+impl ResponseSigner {
+    pub fn new(private_key: H256) -> Self {
+        let address = PackedEthSignature::address_from_private_key(&private_key)
+            .expect("Invalid response signing key");
+        Self {
+            private_key,
+            address,
+        }
+    }
+
+    /// Creates the signer if the response signing key is configured.
+    pub fn from_config(config: &CommonApiConfig) -> Option<Self> {
+        config.response_signing_key().map(Self::new)
+    }
+
+    /// Returns the headers with the detached signature of the response body.
+    pub fn signature_headers(&self, body: &str) -> Vec<(&'static str, String)> {
+        let body: Value = serde_json::from_str(body).expect("Response body is a valid JSON");
+        let signature =
+            PackedEthSignature::sign(&self.private_key, canonical_json(&body).as_bytes())
+                .expect("Failed to sign the response");
+        vec![
+            (
+                RESPONSE_SIGNATURE_HEADER,
+                format!("0x{}", hex::encode(signature.serialize_packed())),
+            ),
+            (RESPONSE_SIGNER_HEADER, format!("{:?}", self.address)),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn canonical_json_sorts_keys() {
+        let value = json!({ "b": [1, { "d": null, "c": "x" }], "a": true });
+        assert_eq!(
+            canonical_json(&value),
+            r#"{"a":true,"b":[1,{"c":"x","d":null}]}"#
+        );
+    }
+
+    #[test]
+    fn signature_is_recoverable() {
+        let signer = ResponseSigner::new(H256::repeat_byte(0x11));
+        let body = r#"{ "status": "success", "result": { "nonce": 1 } }"#;
+        let headers = signer.signature_headers(body);
+        assert_eq!(headers[1].1, format!("{:?}", signer.address));
+
+        // Formatting of the body doesn't affect the signature.
+        let message = canonical_json(&serde_json::from_str(body).unwrap());
+        let signature = hex::decode(&headers[0].1[2..]).unwrap();
+        let signature = PackedEthSignature::deserialize_packed(&signature).unwrap();
+        assert_eq!(
+            signature
+                .signature_recover_signer(message.as_bytes())
+                .unwrap(),
+            signer.address
+        );
+        assert_eq!(
+            signer.signature_headers(r#"{"result":{"nonce":1},"status":"success"}"#),
+            headers
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! Background task filling the summary tables of the network statistics.
+//!
+//! Computing the statistics requires the full scans of the executed transactions, so instead
+//! of doing it on every request the values are periodically recomputed and stored into the
+//! database, and the API only reads the stored values.
+
+use chrono::{Duration as ChronoDuration, Utc};
+use futures::channel::mpsc;
+use std::time::Duration;
+use tokio::{runtime::Runtime, time};
+use zksync_storage::{ConnectionPool, QueryResult};
+use zksync_utils::panic_notify::ThreadPanicNotify;
+
+/// Recomputes the statistics of the current and the previous days, the latter is required
+/// to include the transactions executed after the last run of the previous day.
+async fn aggregate_stats(connection_pool: &ConnectionPool) -> QueryResult<()> {
+    let mut storage = connection_pool.access_storage().await?;
+    let today = Utc::today().naive_utc();
+    let yesterday = today - ChronoDuration::days(1);
+
+    let mut stats_schema = storage.chain().stats_schema();
+    stats_schema.aggregate_daily_stats(yesterday).await?;
+    stats_schema.aggregate_daily_stats(today).await?;
+    stats_schema.update_tvl().await?;
+    Ok(())
+}
+
+pub fn start_stats_aggregator_detached(
+    panic_notify: mpsc::Sender<bool>,
+    connection_pool: ConnectionPool,
+    interval: Duration,
+) {
+    std::thread::Builder::new()
+        .name("rest-stats-aggregator".to_string())
+        .spawn(move || {
+            let _panic_sentinel = ThreadPanicNotify(panic_notify.clone());
+
+            let runtime = Runtime::new().expect("tokio runtime creation");
+
+            let aggregation_task = async move {
+                let mut timer = time::interval(interval);
+                loop {
+                    timer.tick().await;
+                    if let Err(err) = aggregate_stats(&connection_pool).await {
+                        vlog::error!("Can't aggregate network statistics: {}", err);
+                    }
+                }
+            };
+            runtime.block_on(aggregation_task);
+        })
+        .expect("Stats aggregator thread");
+}
@@ -0,0 +1,41 @@
+// External uses
+use actix_web::{web, Scope};
+use num::bigint::ToBigInt;
+
+// Workspace uses
+use zksync_api_client::rest::travel_rule::TravelRuleMetadata;
+use zksync_storage::{travel_rule::records::StorageTravelRuleRecord, ConnectionPool};
+use zksync_types::{tx::TxHash, Address, TokenId};
+
+// Local uses
+use crate::api_server::{rest::forced_exit_requests::error::ApiError, tx_sender::TxSender};
+
+mod v01;
+
+pub(crate) fn api_scope(connection_pool: ConnectionPool, tx_sender: TxSender) -> Scope {
+    web::scope("/api/travel_rule").service(v01::api_scope(connection_pool, tx_sender))
+}
+
+/// Returns the metadata of the recorded transfer. Records of the non-compliant transfers have
+/// no metadata.
+pub(super) fn metadata_from_record(
+    record: StorageTravelRuleRecord,
+) -> Result<TravelRuleMetadata, ApiError> {
+    let metadata = record
+        .encrypted_metadata
+        .ok_or_else(|| ApiError::not_found("Transfer has no travel rule metadata"))?;
+
+    Ok(TravelRuleMetadata {
+        tx_hash: TxHash::from_slice(&record.tx_hash).expect("Incorrect tx hash in the database"),
+        from: Address::from_slice(&record.from_address),
+        to: Address::from_slice(&record.to_address),
+        token: TokenId(record.token_id as u32),
+        amount: record
+            .amount
+            .to_bigint()
+            .and_then(|amount| amount.to_biguint())
+            .expect("Incorrect amount in the database"),
+        metadata,
+        created_at: record.created_at,
+    })
+}
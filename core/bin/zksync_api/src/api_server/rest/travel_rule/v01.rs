@@ -0,0 +1,150 @@
+//! Travel rule part of API implementation.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+
+// Workspace uses
+use zksync_api_client::rest::travel_rule::{
+    TravelRuleMetadata, TravelRuleMetadataRequest, TravelRuleTransfer,
+};
+use zksync_storage::{travel_rule::records::StorageTravelRuleRecord, ConnectionPool};
+use zksync_types::{travel_rule::travel_rule_metadata_message, tx::TxHash, Address};
+
+// Local uses
+use super::metadata_from_record;
+use crate::api_server::{
+    rest::forced_exit_requests::{error::ApiError, JsonResult},
+    tx_sender::TxSender,
+};
+
+/// Shared data between `/api/travel_rule/v0.1/` endpoints.
+struct ApiTravelRuleData {
+    connection_pool: ConnectionPool,
+    tx_sender: TxSender,
+}
+
+/// Checks that the request is signed by a party of the transfer and returns its metadata.
+/// Records of the non-compliant transfers have no metadata and are available to the operator only.
+fn travel_rule_metadata(
+    record: StorageTravelRuleRecord,
+    request: &TravelRuleMetadataRequest,
+) -> Result<TravelRuleMetadata, ApiError> {
+    let tx_hash = TxHash::from_slice(&record.tx_hash).expect("Incorrect tx hash in the database");
+    let from = Address::from_slice(&record.from_address);
+    let to = Address::from_slice(&record.to_address);
+
+    let message = travel_rule_metadata_message(tx_hash);
+    let signer = request
+        .signature
+        .signature_recover_signer(message.as_bytes())
+        .map_err(ApiError::bad_request)?;
+    if signer != from && signer != to {
+        return Err(ApiError::bad_request(
+            "Request is not signed by a party of the transfer",
+        ));
+    }
+    metadata_from_record(record)
+}
+
+async fn submit_transfer(
+    data: web::Data<ApiTravelRuleData>,
+    transfer: web::Json<TravelRuleTransfer>,
+) -> JsonResult<TxHash> {
+    let start = Instant::now();
+    let transfer = transfer.into_inner();
+    let tx_hash = data
+        .tx_sender
+        .submit_transfer_with_travel_rule(transfer.tx.tx, transfer.tx.signature, transfer.metadata)
+        .await?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "submit_travel_rule_transfer");
+    Ok(Json(tx_hash))
+}
+
+async fn get_metadata(
+    data: web::Data<ApiTravelRuleData>,
+    tx_hash: web::Path<TxHash>,
+    request: web::Json<TravelRuleMetadataRequest>,
+) -> JsonResult<TravelRuleMetadata> {
+    let start = Instant::now();
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    let record = storage
+        .travel_rule_schema()
+        .get_record(*tx_hash)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::not_found("Transfer has no travel rule metadata"))?;
+    let metadata = travel_rule_metadata(record, &request)?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "travel_rule_metadata");
+    Ok(Json(metadata))
+}
+
+pub fn api_scope(connection_pool: ConnectionPool, tx_sender: TxSender) -> Scope {
+    let data = ApiTravelRuleData {
+        connection_pool,
+        tx_sender,
+    };
+
+    web::scope("v0.1")
+        .app_data(web::Data::new(data))
+        .route("/transfer", web::post().to(submit_transfer))
+        .route("/metadata/{tx_hash}", web::post().to(get_metadata))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use zksync_types::{tx::PackedEthSignature, H256};
+
+    fn record(from: Address, encrypted_metadata: Option<Vec<u8>>) -> StorageTravelRuleRecord {
+        StorageTravelRuleRecord {
+            tx_hash: vec![1; 32],
+            from_address: from.as_bytes().to_vec(),
+            to_address: Address::repeat_byte(2).as_bytes().to_vec(),
+            token_id: 0,
+            amount: 100.into(),
+            amount_usd_scaled: None,
+            encrypted_metadata,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn request(private_key: &H256) -> TravelRuleMetadataRequest {
+        let message = travel_rule_metadata_message(TxHash::from_slice(&[1; 32]).unwrap());
+        TravelRuleMetadataRequest {
+            signature: PackedEthSignature::sign(private_key, message.as_bytes()).unwrap(),
+        }
+    }
+
+    #[test]
+    fn metadata_access() {
+        let private_key = H256::repeat_byte(7);
+        let sender = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+
+        let metadata =
+            travel_rule_metadata(record(sender, Some(vec![0xab; 4])), &request(&private_key))
+                .unwrap();
+        assert_eq!(metadata.from, sender);
+        assert_eq!(metadata.metadata, vec![0xab; 4]);
+        // Only the parties of the transfer can access the metadata.
+        assert!(travel_rule_metadata(
+            record(Address::repeat_byte(3), Some(vec![0xab; 4])),
+            &request(&private_key)
+        )
+        .is_err());
+        // Flagged transfers have no metadata.
+        assert!(travel_rule_metadata(record(sender, None), &request(&private_key)).is_err());
+    }
+}
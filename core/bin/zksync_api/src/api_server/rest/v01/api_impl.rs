@@ -61,8 +61,8 @@ impl ApiV01 {
         let start = Instant::now();
 
         let liquidity_volume = Ratio::from(
-            BigUint::from_f64(self_.config.ticker.liquidity_volume)
-                .expect("TickerConfig::liquidity_volume must be positive"),
+            BigUint::from_f64(self_.config.ticker.min_market_volume())
+                .expect("TickerConfig::min_market_volume must be positive"),
         );
 
         let mut storage = self_.access_storage().await?;
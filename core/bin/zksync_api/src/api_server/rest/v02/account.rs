@@ -7,27 +7,59 @@ use std::time::Instant;
 
 // External uses
 use actix_web::{web, Scope};
+use num::bigint::ToBigInt;
 
 // Workspace uses
 use zksync_api_types::v02::{
-    account::{Account, AccountAddressOrId, AccountState, IncomingAccountTxsQuery},
+    account::{
+        Account, AccountActivation, AccountAddressOrId, AccountState, AccountStateQuery,
+        AccountsBulkRequest, AccountsDiff, AccountsDiffRequest, BulkAccountState, EthAccountType,
+        IncomingAccountTxsQuery, ProvisionalDeposit, RefundableDeposit,
+    },
     pagination::{
-        parse_query, AccountTxsRequest, ApiEither, Paginated, PaginationQuery, PendingOpsRequest,
+        parse_query, AccountNFTsRequest, AccountTxsRequest, ApiEither, NFTCollectionRequest,
+        Paginated, PaginationQuery, PendingOpsRequest, MAX_LIMIT,
     },
+    token::NFT,
     transaction::{Transaction, TxHashSerializeWrapper},
 };
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL};
+use zksync_eth_client::ens::is_ens_name;
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_token_db_cache::TokenDBCache;
-use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, SerialId, TokenLike};
+use zksync_types::{
+    tx::TxHash, AccountId, Address, BlockNumber, PubKeyHash, SerialId, TokenId, TokenLike,
+    ZkSyncPriorityOp, H256,
+};
 
 // Local uses
 use super::{
     error::{Error, InvalidDataError},
     paginate_trait::Paginate,
-    response::ApiResult,
+    response::{ApiResult, Signed},
 };
-use crate::{api_server::helpers::get_depositing, api_try, fee_ticker::PriceError};
+use crate::{
+    api_server::{
+        ens_resolver::EnsResolver,
+        helpers::{get_depositing, load_accounts_diff, MAX_ACCOUNTS_DIFF_BLOCKS},
+        rest::{network_status::SharedNetworkStatus, openapi::Endpoint},
+    },
+    api_try,
+    eth_checker::EthereumChecker,
+    fee_ticker::PriceError,
+};
+
+/// Number of confirmations of the Ethereum block, the block is accepted by the network
+/// once it gets `required_confirmations` confirmations.
+fn eth_block_confirmations(
+    eth_block: u64,
+    last_eth_block: u64,
+    required_confirmations: u64,
+) -> u64 {
+    last_eth_block
+        .saturating_sub(eth_block)
+        .min(required_confirmations)
+}
 
 /// Shared data between `api/v02/accounts` endpoints.
 #[derive(Clone)]
@@ -35,14 +67,27 @@ struct ApiAccountData {
     pool: ConnectionPool,
     tokens: TokenDBCache,
     confirmations_for_eth_event: u64,
+    network_status: SharedNetworkStatus,
+    ens_resolver: EnsResolver,
+    eth_checker: Option<EthereumChecker>,
 }
 
 impl ApiAccountData {
-    fn new(pool: ConnectionPool, tokens: TokenDBCache, confirmations_for_eth_event: u64) -> Self {
+    fn new(
+        pool: ConnectionPool,
+        tokens: TokenDBCache,
+        confirmations_for_eth_event: u64,
+        network_status: SharedNetworkStatus,
+        ens_resolver: EnsResolver,
+        eth_checker: Option<EthereumChecker>,
+    ) -> Self {
         Self {
             pool,
             tokens,
             confirmations_for_eth_event,
+            network_status,
+            ens_resolver,
+            eth_checker,
         }
     }
 
@@ -84,12 +129,21 @@ impl ApiAccountData {
         }
     }
 
-    fn parse_account_id_or_address(
+    /// Parses the account ID or address, the ENS names are resolved to the addresses.
+    async fn parse_account_id_or_address(
         &self,
         account_address_or_id: &str,
     ) -> Result<AccountAddressOrId, Error> {
         if let Ok(account_id) = u32::from_str(account_address_or_id) {
             Ok(AccountAddressOrId::Id(AccountId(account_id)))
+        } else if is_ens_name(account_address_or_id) {
+            let address = self
+                .ens_resolver
+                .resolve(account_address_or_id)
+                .await
+                .map_err(Error::from)?
+                .ok_or_else(|| Error::from(InvalidDataError::EnsNameNotResolved))?;
+            Ok(AccountAddressOrId::Address(address))
         } else {
             let address_str = if let Some(address_str) = account_address_or_id.strip_prefix("0x") {
                 address_str
@@ -111,6 +165,31 @@ impl ApiAccountData {
         account_id: AccountId,
         last_update_in_block: BlockNumber,
         storage: &mut StorageProcessor<'_>,
+    ) -> Result<Account, Error> {
+        let account_type = storage
+            .chain()
+            .account_schema()
+            .account_type_by_id(account_id)
+            .await
+            .map_err(Error::storage)?
+            .map(|t| t.into());
+        self.api_account_with_type(
+            account,
+            account_id,
+            last_update_in_block,
+            account_type,
+            storage,
+        )
+        .await
+    }
+
+    async fn api_account_with_type(
+        &self,
+        account: zksync_types::Account,
+        account_id: AccountId,
+        last_update_in_block: BlockNumber,
+        account_type: Option<EthAccountType>,
+        storage: &mut StorageProcessor<'_>,
     ) -> Result<Account, Error> {
         let mut balances = BTreeMap::new();
         let mut nfts = BTreeMap::new();
@@ -149,13 +228,6 @@ impl ApiAccountData {
             .map(|(id, nft)| (*id, nft.clone().into()))
             .collect();
 
-        let account_type = storage
-            .chain()
-            .account_schema()
-            .account_type_by_id(account_id)
-            .await
-            .map_err(Error::storage)?
-            .map(|t| t.into());
         Ok(Account {
             account_id,
             address: account.address,
@@ -247,6 +319,17 @@ impl ApiAccountData {
         )
         .await?;
 
+        let freeze = if let Some(account_id) = account_id {
+            transaction
+                .account_freezes_schema()
+                .active_freeze(account_id)
+                .await
+                .map_err(Error::storage)?
+                .map(Into::into)
+        } else {
+            None
+        };
+
         let (committed, finalized) = if let Some(account_id) = account_id {
             let (finalized_state, committed_state) = transaction
                 .chain()
@@ -284,9 +367,119 @@ impl ApiAccountData {
         };
         transaction.commit().await.map_err(Error::storage)?;
         Ok(AccountState {
+            address,
             depositing,
             committed,
             finalized,
+            freeze,
+        })
+    }
+
+    /// Returns the state of the account after the finalized block. Only the `finalized` state
+    /// is set, since neither the committed state nor the deposits make sense for the past block.
+    async fn account_state_at_block(
+        &self,
+        address: Address,
+        account_id: Option<AccountId>,
+        block_number: BlockNumber,
+    ) -> Result<AccountState, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let is_finalized = storage
+            .chain()
+            .block_schema()
+            .is_block_finalized(block_number)
+            .await
+            .map_err(Error::storage)?;
+        if !is_finalized {
+            return Err(Error::from(InvalidDataError::BlockNotFinalized));
+        }
+
+        let finalized = if let Some(account_id) = account_id {
+            let (last_update_in_block, account) = storage
+                .chain()
+                .account_schema()
+                .account_state_at_block(account_id, block_number)
+                .await
+                .map_err(Error::storage)?;
+            match account {
+                Some(account) => Some(
+                    self.api_account(account, account_id, last_update_in_block, &mut storage)
+                        .await?,
+                ),
+                None => None,
+            }
+        } else {
+            None
+        };
+        Ok(AccountState {
+            address,
+            finalized,
+            ..Default::default()
+        })
+    }
+
+    async fn account_activation(
+        &self,
+        address: Address,
+        account_id: Option<AccountId>,
+    ) -> Result<AccountActivation, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let (account, account_type) = if let Some(account_id) = account_id {
+            let (_, account) = storage
+                .chain()
+                .account_schema()
+                .last_committed_state_for_account(account_id)
+                .await
+                .map_err(Error::storage)?;
+            let account_type: Option<EthAccountType> = storage
+                .chain()
+                .account_schema()
+                .account_type_by_id(account_id)
+                .await
+                .map_err(Error::storage)?
+                .map(|t| t.into());
+            (account, account_type)
+        } else {
+            (None, None)
+        };
+        let (nonce, pub_key_hash) = account
+            .map(|account| (account.nonce, account.pub_key_hash))
+            .unwrap_or_default();
+
+        let queued = storage
+            .chain()
+            .mempool_schema()
+            .has_waiting_onchain_auth_tx(address, nonce)
+            .await
+            .map_err(Error::storage)?;
+        let (auth_fact_set, auth_fact_reset_time) = match &self.eth_checker {
+            Some(eth_checker) => {
+                let auth_fact = eth_checker
+                    .get_auth_fact(address, nonce)
+                    .await
+                    .map_err(Error::from)?;
+                let reset_time = eth_checker
+                    .get_auth_fact_reset_time(address, nonce)
+                    .await
+                    .map_err(Error::from)?;
+                (
+                    Some(auth_fact.iter().any(|byte| *byte != 0)),
+                    Some(reset_time),
+                )
+            }
+            None => (None, None),
+        };
+
+        Ok(AccountActivation {
+            address,
+            account_id,
+            nonce,
+            pub_key_set: pub_key_hash != PubKeyHash::default(),
+            pub_key_hash,
+            two_factor_auth: account_type.into(),
+            onchain_auth_pending: queued || auth_fact_reset_time.unwrap_or_default() != 0,
+            auth_fact_set,
+            auth_fact_reset_time,
         })
     }
 
@@ -345,53 +538,295 @@ impl ApiAccountData {
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
         storage.paginate_checked(&new_query).await
     }
+
+    async fn account_provisional_deposits(
+        &self,
+        address: Address,
+    ) -> Result<Vec<ProvisionalDeposit>, Error> {
+        let last_eth_block = self.network_status.read().await.last_eth_block;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let deposits = storage
+            .chain()
+            .mempool_schema()
+            .get_pending_deposits(address)
+            .await
+            .map_err(Error::storage)?
+            .into_iter()
+            .filter_map(|op| match op.data {
+                ZkSyncPriorityOp::Deposit(deposit) => Some(ProvisionalDeposit {
+                    serial_id: op.serial_id,
+                    eth_tx_hash: op.eth_hash,
+                    token_id: deposit.token,
+                    amount: deposit.amount,
+                    eth_block: op.eth_block,
+                    confirmations: last_eth_block.map(|last_eth_block| {
+                        eth_block_confirmations(
+                            op.eth_block,
+                            last_eth_block,
+                            self.confirmations_for_eth_event,
+                        )
+                    }),
+                    required_confirmations: self.confirmations_for_eth_event,
+                }),
+                _ => None,
+            })
+            .collect();
+        Ok(deposits)
+    }
+
+    async fn account_refundable_deposits(
+        &self,
+        address: Address,
+    ) -> Result<Vec<RefundableDeposit>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let deposits = storage
+            .deposit_refunds_schema()
+            .load_refundable_deposits(address)
+            .await
+            .map_err(Error::storage)?
+            .into_iter()
+            .map(|deposit| RefundableDeposit {
+                serial_id: deposit.serial_id as SerialId,
+                eth_tx_hash: H256::from_slice(&deposit.eth_hash),
+                from: Address::from_slice(&deposit.from_address),
+                to: Address::from_slice(&deposit.to_address),
+                token_id: TokenId(deposit.token_id as u32),
+                amount: deposit
+                    .amount
+                    .to_bigint()
+                    .and_then(|amount| amount.to_biguint())
+                    .unwrap_or_default(),
+                reason: deposit.reason,
+                created_at: deposit.created_at,
+            })
+            .collect();
+        Ok(deposits)
+    }
+
+    async fn account_nfts(
+        &self,
+        query: PaginationQuery<ApiEither<TokenId>>,
+        account_id: Option<AccountId>,
+    ) -> Result<Paginated<NFT, TokenId>, Error> {
+        let account_id = match account_id {
+            Some(account_id) => account_id,
+            None => {
+                return Ok(Paginated::new(
+                    Vec::new(),
+                    Default::default(),
+                    query.limit,
+                    query.direction,
+                    0,
+                ))
+            }
+        };
+        let new_query = PaginationQuery {
+            from: AccountNFTsRequest {
+                account_id,
+                token_id: query.from,
+            },
+            limit: query.limit,
+            direction: query.direction,
+        };
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage.paginate_checked(&new_query).await
+    }
+
+    async fn account_minted_nfts(
+        &self,
+        query: PaginationQuery<ApiEither<u32>>,
+        account_id: Option<AccountId>,
+    ) -> Result<Paginated<NFT, u32>, Error> {
+        let creator_id = match account_id {
+            Some(account_id) => account_id,
+            None => {
+                return Ok(Paginated::new(
+                    Vec::new(),
+                    Default::default(),
+                    query.limit,
+                    query.direction,
+                    0,
+                ))
+            }
+        };
+        let new_query = PaginationQuery {
+            from: NFTCollectionRequest {
+                creator_id,
+                serial_id: query.from,
+            },
+            limit: query.limit,
+            direction: query.direction,
+        };
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage.paginate_checked(&new_query).await
+    }
+
+    async fn accounts_bulk(
+        &self,
+        request: AccountsBulkRequest,
+    ) -> Result<Vec<BulkAccountState>, Error> {
+        if request.addresses.len() > MAX_LIMIT as usize {
+            return Err(Error::from(InvalidDataError::TooManyAddresses));
+        }
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let states = storage
+            .chain()
+            .account_schema()
+            .account_states_by_addresses(&request.addresses)
+            .await
+            .map_err(Error::storage)?;
+
+        let mut result = Vec::with_capacity(states.len());
+        for (address, state) in request.addresses.into_iter().zip(states) {
+            let account_type: Option<EthAccountType> = state.account_type.map(|t| t.into());
+            let committed = match state.state.committed {
+                Some((account_id, account)) => Some(
+                    self.api_account_with_type(
+                        account,
+                        account_id,
+                        state.last_committed_block,
+                        account_type.clone(),
+                        &mut storage,
+                    )
+                    .await?,
+                ),
+                None => None,
+            };
+            let finalized = match state.state.verified {
+                Some((account_id, account)) => Some(
+                    self.api_account_with_type(
+                        account,
+                        account_id,
+                        state.last_verified_block,
+                        account_type,
+                        &mut storage,
+                    )
+                    .await?,
+                ),
+                None => None,
+            };
+            result.push(BulkAccountState {
+                address,
+                committed,
+                finalized,
+            });
+        }
+        Ok(result)
+    }
+
+    async fn accounts_diff(&self, request: AccountsDiffRequest) -> Result<AccountsDiff, Error> {
+        if request.addresses.len() > MAX_LIMIT as usize {
+            return Err(Error::from(InvalidDataError::TooManyAddresses));
+        }
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let last_committed = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await
+            .map_err(Error::storage)?;
+        let from_block = request.from_block.min(last_committed);
+        let to_block = last_committed.min(from_block + MAX_ACCOUNTS_DIFF_BLOCKS);
+        load_accounts_diff(
+            &mut storage,
+            &self.tokens,
+            &request.addresses,
+            from_block,
+            to_block,
+        )
+        .await
+    }
 }
 
 async fn account_committed_info(
     data: web::Data<ApiAccountData>,
     account_id_or_address: web::Path<String>,
-) -> ApiResult<Option<Account>> {
+) -> Signed<Option<Account>> {
     let start = Instant::now();
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
-    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
-    let res = if let Some(account_id) = account_id {
-        data.account_committed_info(account_id).await.into()
-    } else {
-        ApiResult::Ok(None)
-    };
+    let res: ApiResult<Option<Account>> = async {
+        let address_or_id = api_try!(
+            data.parse_account_id_or_address(&account_id_or_address)
+                .await
+        );
+        let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+        if let Some(account_id) = account_id {
+            data.account_committed_info(account_id).await.into()
+        } else {
+            ApiResult::Ok(None)
+        }
+    }
+    .await;
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_committed_info");
-    res
+    Signed(res)
 }
 
 async fn account_finalized_info(
     data: web::Data<ApiAccountData>,
     account_id_or_address: web::Path<String>,
-) -> ApiResult<Option<Account>> {
+) -> Signed<Option<Account>> {
     let start = Instant::now();
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
-    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
-    let res = if let Some(account_id) = account_id {
-        data.account_finalized_info(account_id).await.into()
-    } else {
-        ApiResult::Ok(None)
-    };
+    let res: ApiResult<Option<Account>> = async {
+        let address_or_id = api_try!(
+            data.parse_account_id_or_address(&account_id_or_address)
+                .await
+        );
+        let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+        if let Some(account_id) = account_id {
+            data.account_finalized_info(account_id).await.into()
+        } else {
+            ApiResult::Ok(None)
+        }
+    }
+    .await;
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_finalized_info");
-    res
+    Signed(res)
 }
 
 async fn account_full_info(
     data: web::Data<ApiAccountData>,
     account_id_or_address: web::Path<String>,
-) -> ApiResult<AccountState> {
+    web::Query(query): web::Query<AccountStateQuery>,
+) -> Signed<AccountState> {
     let start = Instant::now();
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let res: ApiResult<AccountState> = async {
+        let address_or_id = api_try!(
+            data.parse_account_id_or_address(&account_id_or_address)
+                .await
+        );
+        let address = api_try!(
+            data.get_address_by_address_or_id(address_or_id.clone())
+                .await
+        );
+        let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+        match query.at_block {
+            Some(block_number) => data
+                .account_state_at_block(address, account_id, block_number)
+                .await
+                .into(),
+            None => data.account_full_info(address, account_id).await.into(),
+        }
+    }
+    .await;
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_full_info");
+    Signed(res)
+}
+
+async fn account_activation(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<AccountActivation> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
     let address = api_try!(
         data.get_address_by_address_or_id(address_or_id.clone())
             .await
     );
     let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
-    let res = data.account_full_info(address, account_id).await.into();
-    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_full_info");
+    let res = data.account_activation(address, account_id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_activation");
     res
 }
 
@@ -408,11 +843,14 @@ async fn account_txs(
     })
     .map_err(Error::from));
 
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
     let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
 
     let second_address = if let Some(second_account) = query.second_account {
-        let address_or_id = api_try!(data.parse_account_id_or_address(&second_account));
+        let address_or_id = api_try!(data.parse_account_id_or_address(&second_account).await);
         Some(api_try!(
             data.get_address_by_address_or_id(address_or_id).await
         ))
@@ -437,7 +875,10 @@ async fn account_pending_txs(
 ) -> ApiResult<Paginated<Transaction, SerialId>> {
     let start = Instant::now();
     let query = api_try!(parse_query(query).map_err(Error::from));
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
     let address = api_try!(
         data.get_address_by_address_or_id(address_or_id.clone())
             .await
@@ -451,15 +892,111 @@ async fn account_pending_txs(
     res
 }
 
+async fn account_provisional_deposits(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<Vec<ProvisionalDeposit>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
+    let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
+    let res = data.account_provisional_deposits(address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_provisional_deposits");
+    res
+}
+
+async fn account_refundable_deposits(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<Vec<RefundableDeposit>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
+    let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
+    let res = data.account_refundable_deposits(address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_refundable_deposits");
+    res
+}
+
+async fn account_nfts(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+    web::Query(query): web::Query<PaginationQuery<String>>,
+) -> ApiResult<Paginated<NFT, TokenId>> {
+    let start = Instant::now();
+    let query = api_try!(parse_query(query).map_err(Error::from));
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = data.account_nfts(query, account_id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_nfts");
+    res
+}
+
+async fn account_minted_nfts(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+    web::Query(query): web::Query<PaginationQuery<String>>,
+) -> ApiResult<Paginated<NFT, u32>> {
+    let start = Instant::now();
+    let query = api_try!(parse_query(query).map_err(Error::from));
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = data.account_minted_nfts(query, account_id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_minted_nfts");
+    res
+}
+
+async fn accounts_bulk(
+    data: web::Data<ApiAccountData>,
+    web::Json(request): web::Json<AccountsBulkRequest>,
+) -> ApiResult<Vec<BulkAccountState>> {
+    let start = Instant::now();
+    let res = data.accounts_bulk(request).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "accounts_bulk");
+    res
+}
+
+async fn accounts_diff(
+    data: web::Data<ApiAccountData>,
+    web::Json(request): web::Json<AccountsDiffRequest>,
+) -> ApiResult<AccountsDiff> {
+    let start = Instant::now();
+    let res = data.accounts_diff(request).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "accounts_diff");
+    res
+}
+
 pub fn api_scope(
     pool: ConnectionPool,
     tokens: TokenDBCache,
     confirmations_for_eth_event: u64,
+    network_status: SharedNetworkStatus,
+    ens_resolver: EnsResolver,
+    eth_checker: Option<EthereumChecker>,
 ) -> Scope {
-    let data = ApiAccountData::new(pool, tokens, confirmations_for_eth_event);
+    let data = ApiAccountData::new(
+        pool,
+        tokens,
+        confirmations_for_eth_event,
+        network_status,
+        ens_resolver,
+        eth_checker,
+    );
 
     web::scope("accounts")
         .app_data(web::Data::new(data))
+        .route("bulk", web::post().to(accounts_bulk))
+        .route("diff", web::post().to(accounts_diff))
         .route(
             "{account_id_or_address}/committed",
             web::get().to(account_committed_info),
@@ -469,6 +1006,10 @@ pub fn api_scope(
             web::get().to(account_finalized_info),
         )
         .route("{account_id_or_address}", web::get().to(account_full_info))
+        .route(
+            "{account_id_or_address}/activation",
+            web::get().to(account_activation),
+        )
         .route(
             "{account_id_or_address}/transactions",
             web::get().to(account_txs),
@@ -477,6 +1018,90 @@ pub fn api_scope(
             "{account_id_or_address}/transactions/pending",
             web::get().to(account_pending_txs),
         )
+        .route(
+            "{account_id_or_address}/deposits/pending",
+            web::get().to(account_provisional_deposits),
+        )
+        .route(
+            "{account_id_or_address}/deposits/refundable",
+            web::get().to(account_refundable_deposits),
+        )
+        .route("{account_id_or_address}/nfts", web::get().to(account_nfts))
+        .route(
+            "{account_id_or_address}/nfts/minted",
+            web::get().to(account_minted_nfts),
+        )
+}
+
+pub fn api_docs() -> Vec<Endpoint> {
+    vec![
+        Endpoint::get::<AccountState>(
+            "/accounts/{accountIdOrAddress}",
+            "accounts",
+            "Committed and finalized state of the account, or its state after the finalized block",
+        )
+        .with_query(&["atBlock"]),
+        Endpoint::get::<Option<Account>>(
+            "/accounts/{accountIdOrAddress}/committed",
+            "accounts",
+            "Committed state of the account",
+        ),
+        Endpoint::get::<Option<Account>>(
+            "/accounts/{accountIdOrAddress}/finalized",
+            "accounts",
+            "Finalized state of the account",
+        ),
+        Endpoint::get::<AccountActivation>(
+            "/accounts/{accountIdOrAddress}/activation",
+            "accounts",
+            "Signing key, pending onchain auth and 2FA policy of the account for the onboarding",
+        ),
+        Endpoint::get::<Transaction>(
+            "/accounts/{accountIdOrAddress}/transactions",
+            "accounts",
+            "Transactions of the account",
+        )
+        .paginated()
+        .with_query(&["token", "secondAccount"]),
+        Endpoint::get::<Transaction>(
+            "/accounts/{accountIdOrAddress}/transactions/pending",
+            "accounts",
+            "Pending priority operations of the account",
+        )
+        .paginated(),
+        Endpoint::get::<Vec<ProvisionalDeposit>>(
+            "/accounts/{accountIdOrAddress}/deposits/pending",
+            "accounts",
+            "Deposits of the account awaiting confirmations, with the confirmation progress",
+        ),
+        Endpoint::get::<Vec<RefundableDeposit>>(
+            "/accounts/{accountIdOrAddress}/deposits/refundable",
+            "accounts",
+            "Deposits sent from or to the account that have to be refunded on Ethereum",
+        ),
+        Endpoint::get::<NFT>(
+            "/accounts/{accountIdOrAddress}/nfts",
+            "accounts",
+            "Finalized NFTs owned by the account, paginated by the token ID",
+        )
+        .paginated(),
+        Endpoint::get::<NFT>(
+            "/accounts/{accountIdOrAddress}/nfts/minted",
+            "accounts",
+            "Finalized NFTs minted by the account, paginated by the serial ID",
+        )
+        .paginated(),
+        Endpoint::post::<AccountsBulkRequest, Vec<BulkAccountState>>(
+            "/accounts/bulk",
+            "accounts",
+            "Committed and finalized states of several accounts",
+        ),
+        Endpoint::post::<AccountsDiffRequest, AccountsDiff>(
+            "/accounts/diff",
+            "accounts",
+            "Changes of the accounts made after the given block",
+        ),
+    ]
 }
 
 #[cfg(test)]
@@ -490,7 +1115,7 @@ mod tests {
     use serde::Deserialize;
     use zksync_api_client::rest::client::Client;
     use zksync_api_types::v02::{
-        account::{DepositingAccountBalances, DepositingFunds},
+        account::{DepositingAccountBalances, DepositingFunds, TwoFactorAuthPolicy},
         pagination::{PaginationDirection, PaginationQuery},
         transaction::{L1Transaction, TransactionData},
         ApiVersion,
@@ -535,6 +1160,9 @@ mod tests {
                             cfg.config.api.token_config.invalidate_token_cache_period(),
                         ),
                         cfg.config.eth_watch.confirmations_for_eth_event,
+                        SharedNetworkStatus::new("0.0.0.0".to_string()),
+                        EnsResolver::default(),
+                        None,
                     )
                 },
                 Some(shared_data),
@@ -576,6 +1204,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn deposit_confirmations() {
+        assert_eq!(eth_block_confirmations(100, 100, 10), 0);
+        assert_eq!(eth_block_confirmations(100, 103, 10), 3);
+        assert_eq!(eth_block_confirmations(100, 150, 10), 10);
+        // The latest block may lag behind the block of the deposit.
+        assert_eq!(eth_block_confirmations(100, 99, 10), 0);
+    }
+
     #[actix_rt::test]
     #[cfg_attr(
         not(feature = "api_test"),
@@ -681,6 +1318,56 @@ mod tests {
         );
         assert_eq!(account_full_info.finalized, account_finalized_info);
         assert_eq!(account_full_info.depositing, expected_depositing);
+        assert!(account_full_info.freeze.is_none());
+
+        // Freeze of the account is reported in its status.
+        server
+            .pool
+            .access_storage()
+            .await?
+            .account_freezes_schema()
+            .freeze(account_id, "compliance hold")
+            .await?;
+        let response = client.account_full_info(&format!("{:?}", address)).await?;
+        let account_full_info: AccountState = deserialize_response_result(response)?;
+        assert_eq!(account_full_info.freeze.unwrap().reason, "compliance hold");
+
+        // The latest Ethereum block is unknown, so only the required confirmations are reported.
+        let response = client
+            .account_provisional_deposits(&format!("{:?}", address))
+            .await?;
+        let deposits: Vec<ProvisionalDeposit> = deserialize_response_result(response)?;
+        assert_eq!(deposits.len(), 3);
+        assert_eq!(deposits[0].serial_id, 10);
+        assert_eq!(deposits[0].confirmations, None);
+        assert_eq!(
+            deposits[0].required_confirmations,
+            server.confirmations_for_eth_event
+        );
+
+        let response = client
+            .account_refundable_deposits(&format!("{:?}", address))
+            .await?;
+        let deposits: Vec<RefundableDeposit> = deserialize_response_result(response)?;
+        assert!(deposits.is_empty());
+
+        // Onchain state is not checked without the Ethereum node.
+        let committed = account_full_info.committed.unwrap();
+        let response = client.account_activation(&format!("{:?}", address)).await?;
+        let activation: AccountActivation = deserialize_response_result(response)?;
+        assert_eq!(activation.account_id, Some(account_id));
+        assert_eq!(activation.nonce, committed.nonce);
+        assert_eq!(
+            activation.pub_key_set,
+            committed.pub_key_hash != PubKeyHash::default()
+        );
+        assert_eq!(
+            activation.two_factor_auth,
+            TwoFactorAuthPolicy::from(committed.account_type)
+        );
+        assert!(!activation.onchain_auth_pending);
+        assert_eq!(activation.auth_fact_set, None);
+        assert_eq!(activation.auth_fact_reset_time, None);
 
         let query = PaginationQuery {
             from: ApiEither::from(tx_hash),
@@ -711,6 +1398,58 @@ mod tests {
             _ => panic!("account_pending_txs returned L2 tx"),
         }
 
+        let query = PaginationQuery {
+            from: ApiEither::from_str("latest").unwrap(),
+            limit: 10,
+            direction: PaginationDirection::Older,
+        };
+        let response = client.account_nfts(&query, &account_id.to_string()).await?;
+        let nfts: Paginated<NFT, TokenId> = deserialize_response_result(response)?;
+        assert!(nfts.list.len() <= nfts.pagination.count as usize);
+        let response = client
+            .account_nfts(&query, &format!("{:?}", Address::repeat_byte(0xff)))
+            .await?;
+        let nfts: Paginated<NFT, TokenId> = deserialize_response_result(response)?;
+        assert!(nfts.list.is_empty());
+        assert_eq!(nfts.pagination.count, 0);
+
+        let query = PaginationQuery {
+            from: ApiEither::from(0),
+            limit: 10,
+            direction: PaginationDirection::Newer,
+        };
+        let response = client
+            .account_minted_nfts(&query, &account_id.to_string())
+            .await?;
+        let nfts: Paginated<NFT, u32> = deserialize_response_result(response)?;
+        for (serial_id, nft) in nfts.list.iter().enumerate() {
+            assert_eq!(nft.serial_id, serial_id as u32);
+            assert_eq!(nft.creator_id, account_id);
+        }
+
+        let response = client
+            .accounts_bulk(vec![address, Address::repeat_byte(0xff)])
+            .await?;
+        let states: Vec<BulkAccountState> = deserialize_response_result(response)?;
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[0].address, address);
+        assert_eq!(states[0].committed, account_full_info.committed);
+        assert_eq!(states[0].finalized, account_full_info.finalized);
+        assert!(states[1].committed.is_none());
+
+        let response = client
+            .accounts_diff(vec![address, Address::repeat_byte(0xff)], BlockNumber(0))
+            .await?;
+        let diff: AccountsDiff = deserialize_response_result(response)?;
+        assert_eq!(diff.from_block, BlockNumber(0));
+        assert_eq!(diff.accounts.len(), 1);
+        assert_eq!(diff.accounts[0].account_id, account_id);
+        assert_eq!(diff.accounts[0].address, address);
+
+        let response = client.accounts_diff(vec![address], diff.to_block).await?;
+        let diff: AccountsDiff = deserialize_response_result(response)?;
+        assert!(diff.accounts.is_empty());
+
         server.stop().await;
         Ok(())
     }
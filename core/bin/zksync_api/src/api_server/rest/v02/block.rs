@@ -9,13 +9,15 @@ use actix_web::{web, Scope};
 
 // Workspace uses
 use zksync_api_types::v02::{
-    block::{BlockInfo, BlockStatus},
+    block::{
+        BlockAccountUpdate, BlockAccountUpdates, BlockInfo, BlockOpStats, BlockPubdata, BlockStatus,
+    },
     pagination::{parse_query, ApiEither, BlockAndTxHash, Paginated, PaginationQuery},
     transaction::{Transaction, TxData, TxHashSerializeWrapper},
 };
 use zksync_crypto::{convert::FeConvert, Fr};
 use zksync_storage::{chain::block::records::StorageBlockDetails, ConnectionPool, QueryResult};
-use zksync_types::{tx::TxHash, BlockNumber, H256};
+use zksync_types::{operations::PUBDATA_VERSION, tx::TxHash, BlockNumber, H256};
 
 // Local uses
 use super::{
@@ -23,7 +25,18 @@ use super::{
     paginate_trait::Paginate,
     response::ApiResult,
 };
-use crate::{api_try, utils::block_details_cache::BlockDetailsCache};
+use crate::{
+    api_server::rest::openapi::Endpoint,
+    api_try,
+    utils::{
+        block_details_cache::BlockDetailsCache,
+        response_cache::{BlocksVersionCache, ResponseCache},
+    },
+};
+
+/// Cache of the blocks list pages, keyed by the raw pagination query.
+pub type BlockPagesCache =
+    ResponseCache<PaginationQuery<String>, Paginated<BlockInfo, BlockNumber>>;
 
 pub fn block_info_from_details(details: StorageBlockDetails) -> BlockInfo {
     let status = if details.is_verified() {
@@ -53,13 +66,22 @@ pub fn block_info_from_details(details: StorageBlockDetails) -> BlockInfo {
 struct ApiBlockData {
     pool: ConnectionPool,
     verified_blocks_cache: BlockDetailsCache,
+    block_pages_cache: BlockPagesCache,
+    blocks_version: BlocksVersionCache,
 }
 
 impl ApiBlockData {
-    fn new(pool: ConnectionPool, verified_blocks_cache: BlockDetailsCache) -> Self {
+    fn new(
+        pool: ConnectionPool,
+        verified_blocks_cache: BlockDetailsCache,
+        block_pages_cache: BlockPagesCache,
+        blocks_version: BlocksVersionCache,
+    ) -> Self {
         Self {
             pool,
             verified_blocks_cache,
+            block_pages_cache,
+            blocks_version,
         }
     }
 
@@ -79,6 +101,77 @@ impl ApiBlockData {
         }
     }
 
+    /// Returns the public data of the block, which is generated from the stored operations.
+    async fn block_pubdata(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Option<BlockPubdata>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let block = storage
+            .chain()
+            .block_schema()
+            .get_block(block_number)
+            .await
+            .map_err(Error::storage)?;
+        Ok(block.map(|block| BlockPubdata {
+            block_number,
+            version: PUBDATA_VERSION,
+            pubdata: block.get_eth_public_data(),
+            commitment: block.block_commitment,
+        }))
+    }
+
+    /// Returns the resources consumed by every type of the operations in the block.
+    async fn block_op_stats(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Option<BlockOpStats>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let stats = storage
+            .chain()
+            .block_schema()
+            .load_block_op_type_stats(block_number)
+            .await
+            .map_err(Error::storage)?;
+        Ok(stats.map(|stats| BlockOpStats {
+            block_number,
+            // Unused chunks are accounted as noops, so the chunks add up to the block size.
+            block_size: stats.iter().map(|stats| stats.chunks).sum(),
+            op_types: stats.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    /// Returns the account updates applied in the block, `None` if the block isn't committed yet.
+    async fn block_account_updates(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Option<BlockAccountUpdates>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let last_committed_block = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await
+            .map_err(Error::storage)?;
+        if block_number == BlockNumber(0) || block_number > last_committed_block {
+            return Ok(None);
+        }
+
+        let updates = storage
+            .chain()
+            .state_schema()
+            .load_state_diff_for_block(block_number)
+            .await
+            .map_err(Error::storage)?;
+        Ok(Some(BlockAccountUpdates {
+            block_number,
+            updates: updates
+                .into_iter()
+                .map(|(account_id, update)| BlockAccountUpdate { account_id, update })
+                .collect(),
+        }))
+    }
+
     async fn get_block_number_by_position(
         &self,
         block_position: &str,
@@ -100,12 +193,23 @@ impl ApiBlockData {
         }
     }
 
+    /// Returns the page of the blocks list.
+    ///
+    /// The page only changes with the new sealed, committed or finalized blocks, so it's cached
+    /// until then.
     async fn block_page(
         &self,
-        query: PaginationQuery<ApiEither<BlockNumber>>,
+        raw_query: PaginationQuery<String>,
     ) -> Result<Paginated<BlockInfo, BlockNumber>, Error> {
-        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
-        storage.paginate_checked(&query).await
+        let query: PaginationQuery<ApiEither<BlockNumber>> =
+            parse_query(raw_query.clone()).map_err(Error::from)?;
+        let version = self.blocks_version.get();
+        self.block_pages_cache
+            .get_or_load(raw_query, version, || async {
+                let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+                storage.paginate_checked(&query).await
+            })
+            .await
     }
 
     async fn transaction_page(
@@ -167,7 +271,6 @@ async fn block_pagination(
     web::Query(query): web::Query<PaginationQuery<String>>,
 ) -> ApiResult<Paginated<BlockInfo, BlockNumber>> {
     let start = Instant::now();
-    let query = api_try!(parse_query(query).map_err(Error::from));
     let res = data.block_page(query).await.into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_pagination");
     res
@@ -186,6 +289,39 @@ async fn block_by_position(
     res
 }
 
+async fn block_pubdata(
+    data: web::Data<ApiBlockData>,
+    block_position: web::Path<String>,
+) -> ApiResult<Option<BlockPubdata>> {
+    let start = Instant::now();
+    let block_number = api_try!(data.get_block_number_by_position(&block_position).await);
+    let res = data.block_pubdata(block_number).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_pubdata");
+    res
+}
+
+async fn block_op_stats(
+    data: web::Data<ApiBlockData>,
+    block_position: web::Path<String>,
+) -> ApiResult<Option<BlockOpStats>> {
+    let start = Instant::now();
+    let block_number = api_try!(data.get_block_number_by_position(&block_position).await);
+    let res = data.block_op_stats(block_number).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_op_stats");
+    res
+}
+
+async fn block_account_updates(
+    data: web::Data<ApiBlockData>,
+    block_position: web::Path<String>,
+) -> ApiResult<Option<BlockAccountUpdates>> {
+    let start = Instant::now();
+    let block_number = api_try!(data.get_block_number_by_position(&block_position).await);
+    let res = data.block_account_updates(block_number).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_account_updates");
+    res
+}
+
 async fn block_transactions(
     data: web::Data<ApiBlockData>,
     block_position: web::Path<String>,
@@ -210,13 +346,24 @@ async fn transaction_in_block(
     ApiResult::Ok(res)
 }
 
-pub fn api_scope(pool: ConnectionPool, cache: BlockDetailsCache) -> Scope {
-    let data = ApiBlockData::new(pool, cache);
+pub fn api_scope(
+    pool: ConnectionPool,
+    cache: BlockDetailsCache,
+    block_pages_cache: BlockPagesCache,
+    blocks_version: BlocksVersionCache,
+) -> Scope {
+    let data = ApiBlockData::new(pool, cache, block_pages_cache, blocks_version);
 
     web::scope("blocks")
         .app_data(web::Data::new(data))
         .route("", web::get().to(block_pagination))
         .route("{block_position}", web::get().to(block_by_position))
+        .route("{block_position}/pubdata", web::get().to(block_pubdata))
+        .route(
+            "{block_position}/account_updates",
+            web::get().to(block_account_updates),
+        )
+        .route("{block_position}/op_stats", web::get().to(block_op_stats))
         .route(
             "{block_position}/transactions",
             web::get().to(block_transactions),
@@ -227,6 +374,39 @@ pub fn api_scope(pool: ConnectionPool, cache: BlockDetailsCache) -> Scope {
         )
 }
 
+pub fn api_docs() -> Vec<Endpoint> {
+    vec![
+        Endpoint::get::<BlockInfo>("/blocks", "blocks", "Blocks list").paginated(),
+        Endpoint::get::<Option<BlockInfo>>("/blocks/{blockPosition}", "blocks", "Block details"),
+        Endpoint::get::<Option<BlockPubdata>>(
+            "/blocks/{blockPosition}/pubdata",
+            "blocks",
+            "Public data of the block",
+        ),
+        Endpoint::get::<Option<BlockAccountUpdates>>(
+            "/blocks/{blockPosition}/account_updates",
+            "blocks",
+            "Account updates applied in the block",
+        ),
+        Endpoint::get::<Option<BlockOpStats>>(
+            "/blocks/{blockPosition}/op_stats",
+            "blocks",
+            "Chunks, public data and estimated gas consumed by every operation type in the block",
+        ),
+        Endpoint::get::<Transaction>(
+            "/blocks/{blockPosition}/transactions",
+            "blocks",
+            "Transactions of the block",
+        )
+        .paginated(),
+        Endpoint::get::<Option<TxData>>(
+            "/blocks/{blockPosition}/transactions/{blockIndex}",
+            "blocks",
+            "Transaction of the block by its index",
+        ),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -235,7 +415,8 @@ mod tests {
         SharedData,
     };
     use zksync_api_types::v02::{
-        pagination::PaginationDirection, transaction::TransactionData, ApiVersion,
+        block::BlockOpTypeStats, pagination::PaginationDirection, transaction::TransactionData,
+        ApiVersion,
     };
 
     #[actix_rt::test]
@@ -252,7 +433,14 @@ mod tests {
             api_version: ApiVersion::V02,
         };
         let (client, server) = cfg.start_server(
-            |cfg: &TestServerConfig| api_scope(cfg.pool.clone(), BlockDetailsCache::new(10)),
+            |cfg: &TestServerConfig| {
+                api_scope(
+                    cfg.pool.clone(),
+                    BlockDetailsCache::new(10),
+                    BlockPagesCache::new("block_pages", 10),
+                    BlocksVersionCache::default(),
+                )
+            },
             Some(shared_data),
         );
 
@@ -273,6 +461,51 @@ mod tests {
         let block: BlockInfo = deserialize_response_result(response)?;
         assert_eq!(block, expected_blocks.list[1]);
 
+        let response = client.block_pubdata("2").await?;
+        let pubdata: Option<BlockPubdata> = deserialize_response_result(response)?;
+        let expected_block = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .chain()
+                .block_schema()
+                .get_block(BlockNumber(2))
+                .await?
+                .unwrap()
+        };
+        let pubdata = pubdata.unwrap();
+        assert_eq!(pubdata.version, PUBDATA_VERSION);
+        assert_eq!(pubdata.pubdata, expected_block.get_eth_public_data());
+        assert_eq!(pubdata.commitment, expected_block.block_commitment);
+
+        let response = client.block_op_stats("2").await?;
+        let op_stats: Option<BlockOpStats> = deserialize_response_result(response)?;
+        let op_stats = op_stats.unwrap();
+        assert_eq!(op_stats.block_size, expected_block.block_chunks_size as u64);
+        let expected_op_stats: Vec<BlockOpTypeStats> = expected_block
+            .op_type_stats()
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        assert_eq!(op_stats.op_types, expected_op_stats);
+
+        let response = client.block_account_updates("2").await?;
+        let account_updates: Option<BlockAccountUpdates> = deserialize_response_result(response)?;
+        let expected_updates = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .chain()
+                .state_schema()
+                .load_state_diff_for_block(BlockNumber(2))
+                .await?
+        };
+        let account_updates: Vec<_> = account_updates
+            .unwrap()
+            .updates
+            .into_iter()
+            .map(|update| (update.account_id, update.update))
+            .collect();
+        assert_eq!(account_updates, expected_updates);
+
         let response = client.block_pagination(&query).await?;
         let paginated: Paginated<BlockInfo, BlockNumber> = deserialize_response_result(response)?;
         assert_eq!(paginated, expected_blocks);
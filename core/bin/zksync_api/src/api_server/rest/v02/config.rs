@@ -5,6 +5,7 @@
 use std::time::Instant;
 // External uses
 use actix_web::{web, Scope};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
@@ -14,13 +15,17 @@ use zksync_types::{network::Network, Address};
 
 // Local uses
 use super::response::ApiResult;
+use crate::api_server::rest::openapi::Endpoint;
 
 /// Shared data between `api/v0.2/config` endpoints.
-#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiConfigData {
+    #[schemars(with = "String")]
     network: Network,
+    #[schemars(with = "String")]
     contract: Address,
+    #[schemars(with = "String")]
     gov_contract: Address,
     deposit_confirmations: u64,
     zksync_version: ZksyncVersion,
@@ -56,6 +61,14 @@ pub fn api_scope(config: &ZkSyncConfig) -> Scope {
         .route("", web::get().to(config_endpoint))
 }
 
+pub fn api_docs() -> Vec<Endpoint> {
+    vec![Endpoint::get::<ApiConfigData>(
+        "/config",
+        "config",
+        "Network configuration",
+    )]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -7,11 +7,17 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 use thiserror::Error;
 
 // Workspace uses
-use zksync_api_types::v02::pagination::{UnknownFromParameter, MAX_LIMIT};
+use zksync_api_types::{
+    submit_error::SubmitErrorDetails,
+    v02::pagination::{UnknownFromParameter, MAX_LIMIT},
+};
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
 
 // Local uses
-use crate::{api_server::tx_sender::SubmitError, fee_ticker::PriceError};
+use crate::{
+    api_server::tx_sender::{AmbiguousTokenSymbol, SubmitError},
+    fee_ticker::PriceError,
+};
 
 #[derive(Serialize_repr, Debug, Deserialize_repr, Clone, PartialEq)]
 #[repr(u16)]
@@ -27,9 +33,14 @@ pub enum ErrorCode {
     PaginationLimitTooBig = 206,
     QueryDeserializationError = 207,
     InvalidNFTTokenId = 208,
+    TooManyAddresses = 209,
+    EnsNameNotResolved = 210,
+    BlockNotFinalized = 211,
+    AmbiguousTokenSymbol = 212,
     StorageError = 300,
     TokenNotFound = 500,
     ExternalApiError = 501,
+    StaleTokenPrice = 502,
     InternalError = 600,
     AccountCloseDisabled = 601,
     InvalidParams = 602,
@@ -39,6 +50,7 @@ pub enum ErrorCode {
     InappropriateFeeToken = 606,
     CommunicationCoreServer = 607,
     Toggle2FAError = 608,
+    AccountLocked = 609,
     Other = 60_000,
 }
 
@@ -49,6 +61,9 @@ pub struct Error {
     pub error_type: String,
     pub code: ErrorCode,
     pub message: String,
+    /// Stable code of the transaction rejection, only set for the submission errors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<SubmitErrorDetails>,
 }
 
 /// Trait that can be used to map custom errors to the object.
@@ -60,6 +75,10 @@ pub trait ApiError: std::fmt::Display {
     fn message(&self) -> String {
         self.to_string()
     }
+
+    fn details(&self) -> Option<SubmitErrorDetails> {
+        None
+    }
 }
 
 impl<T> From<T> for Error
@@ -71,6 +90,7 @@ where
             error_type: t.error_type(),
             code: t.code(),
             message: t.message(),
+            details: t.details(),
         }
     }
 }
@@ -103,6 +123,12 @@ pub enum InvalidDataError {
     PaginationLimitTooBig,
     #[error("NFT token ID should be greater than or equal to {}", MIN_NFT_TOKEN_ID)]
     InvalidNFTTokenId,
+    #[error("Number of addresses should be less than or equal to {}", MAX_LIMIT)]
+    TooManyAddresses,
+    #[error("ENS name doesn't resolve to an address")]
+    EnsNameNotResolved,
+    #[error("Block is not finalized yet")]
+    BlockNotFinalized,
 }
 
 impl ApiError for InvalidDataError {
@@ -120,10 +146,23 @@ impl ApiError for InvalidDataError {
             Self::TransactionNotFound => ErrorCode::TransactionNotFound,
             Self::PaginationLimitTooBig => ErrorCode::PaginationLimitTooBig,
             Self::InvalidNFTTokenId => ErrorCode::InvalidNFTTokenId,
+            Self::TooManyAddresses => ErrorCode::TooManyAddresses,
+            Self::EnsNameNotResolved => ErrorCode::EnsNameNotResolved,
+            Self::BlockNotFinalized => ErrorCode::BlockNotFinalized,
         }
     }
 }
 
+impl ApiError for AmbiguousTokenSymbol {
+    fn error_type(&self) -> String {
+        String::from("invalidDataError")
+    }
+
+    fn code(&self) -> ErrorCode {
+        ErrorCode::AmbiguousTokenSymbol
+    }
+}
+
 #[derive(Debug)]
 pub struct StorageError(String);
 
@@ -176,11 +215,18 @@ impl ApiError for CoreApiError {
 
 impl ApiError for anyhow::Error {
     fn error_type(&self) -> String {
-        "internalError".to_string()
+        // Fee ticker reports the price errors wrapped into `anyhow::Error`.
+        match self.downcast_ref::<PriceError>() {
+            Some(err) => err.error_type(),
+            None => "internalError".to_string(),
+        }
     }
 
     fn code(&self) -> ErrorCode {
-        ErrorCode::Other
+        match self.downcast_ref::<PriceError>() {
+            Some(err) => err.code(),
+            None => ErrorCode::Other,
+        }
     }
 }
 
@@ -196,14 +242,22 @@ impl ApiError for SubmitError {
             Self::UnsupportedFastProcessing => ErrorCode::UnsupportedFastProcessing,
             Self::IncorrectTx(_) => ErrorCode::IncorrectTx,
             Self::TxAdd(_) => ErrorCode::TxAddError,
+            Self::InvalidBatchTx { error, .. } => error.code(),
             Self::InappropriateFeeToken => ErrorCode::InappropriateFeeToken,
+            Self::AccountLocked(_) => ErrorCode::AccountLocked,
+            Self::AmbiguousTokenSymbol(_) => ErrorCode::AmbiguousTokenSymbol,
             Self::MempoolCommunication(_) => ErrorCode::CommunicationCoreServer,
             Self::Internal(_) => ErrorCode::InternalError,
             Self::Toggle2FA(_) => ErrorCode::Toggle2FAError,
             Self::Other(_) => ErrorCode::Other,
+            Self::PriceError(PriceError::StalePrice(_)) => ErrorCode::StaleTokenPrice,
             Self::PriceError(_) => ErrorCode::InternalError,
         }
     }
+
+    fn details(&self) -> Option<SubmitErrorDetails> {
+        Some(SubmitError::details(self))
+    }
 }
 
 impl ApiError for PriceError {
@@ -216,6 +270,7 @@ impl ApiError for PriceError {
             Self::TokenNotFound(_) => ErrorCode::TokenNotFound,
             Self::ApiError(_) => ErrorCode::ExternalApiError,
             Self::DBError(_) => ErrorCode::StorageError,
+            Self::StalePrice(_) => ErrorCode::StaleTokenPrice,
         }
     }
 }
@@ -8,14 +8,22 @@ use actix_web::{
     web::{self, Json},
     Scope,
 };
+use num::{BigUint, Zero};
 
 // Workspace uses
-use zksync_api_types::v02::fee::{ApiFee, BatchFeeRequest, TxFeeRequest};
+use zksync_api_types::v02::fee::{
+    ApiFee, ApiFeeScaling, ApiFeeScalingLevel, BatchFeeRequest, TxFeeRequest,
+};
+use zksync_types::TxFeeTypes;
 
 // Local uses
 use super::{error::Error, response::ApiResult};
 use crate::{
-    api_server::tx_sender::{SubmitError, TxSender},
+    api_server::{
+        rest::openapi::Endpoint,
+        tx_sender::{SubmitError, TxSender},
+        weth_unwrap::WETH_UNWRAP_GAS,
+    },
     api_try,
 };
 
@@ -36,6 +44,11 @@ async fn get_tx_fee(
     Json(body): Json<TxFeeRequest>,
 ) -> ApiResult<ApiFee> {
     let start = Instant::now();
+    api_try!(data
+        .tx_sender
+        .check_token_symbol(&body.token_like)
+        .await
+        .map_err(Error::from));
     let token_allowed = api_try!(data
         .tx_sender
         .ticker
@@ -45,11 +58,33 @@ async fn get_tx_fee(
     if !token_allowed {
         return Error::from(SubmitError::InappropriateFeeToken).into();
     }
+    let tx_type = body.tx_type.into();
+    let extra_gas = match body.nft {
+        Some(nft)
+            if matches!(
+                tx_type,
+                TxFeeTypes::WithdrawNFT | TxFeeTypes::FastWithdrawNFT
+            ) =>
+        {
+            api_try!(data
+                .tx_sender
+                .ticker
+                .withdraw_nft_extra_gas(nft, body.address)
+                .await
+                .map_err(Error::from))
+        }
+        _ if body.unwrap_weth
+            && matches!(tx_type, TxFeeTypes::Withdraw | TxFeeTypes::FastWithdraw) =>
+        {
+            BigUint::from(WETH_UNWRAP_GAS)
+        }
+        _ => BigUint::zero(),
+    };
     // TODO implement subsidies for v02 api ZKS-888
     let res = data
         .tx_sender
         .ticker
-        .get_fee_from_ticker_in_wei(body.tx_type.into(), body.token_like, body.address)
+        .get_fee_with_extra_gas(tx_type, body.token_like, body.address, extra_gas)
         .await
         .map(|fee| fee.normal_fee.into())
         .map_err(Error::from)
@@ -63,6 +98,11 @@ async fn get_batch_fee(
     Json(body): Json<BatchFeeRequest>,
 ) -> ApiResult<ApiFee> {
     let start = Instant::now();
+    api_try!(data
+        .tx_sender
+        .check_token_symbol(&body.token_like)
+        .await
+        .map_err(Error::from));
     let token_allowed = api_try!(data
         .tx_sender
         .ticker
@@ -89,6 +129,46 @@ async fn get_batch_fee(
     res
 }
 
+async fn get_fee_scaling(data: web::Data<ApiFeeData>) -> ApiResult<ApiFeeScaling> {
+    let start = Instant::now();
+    let ticker = &data.tx_sender.ticker;
+    let res = match ticker.fee_scaling_schedule() {
+        Some(schedule) => {
+            let load = api_try!(ticker.pipeline_load().await.map_err(Error::from));
+            let load_percent = schedule.load_percent(load);
+            ApiFeeScaling {
+                enabled: true,
+                mempool_size: load.mempool_size,
+                prover_backlog: load.prover_backlog,
+                load_percent,
+                fee_percent: schedule.fee_percent(load_percent),
+                mempool_capacity: schedule.mempool_capacity,
+                prover_capacity: schedule.prover_capacity,
+                schedule: schedule
+                    .levels
+                    .iter()
+                    .map(|level| ApiFeeScalingLevel {
+                        load_percent: level.load_percent,
+                        fee_percent: level.fee_percent,
+                    })
+                    .collect(),
+            }
+        }
+        None => ApiFeeScaling {
+            enabled: false,
+            mempool_size: 0,
+            prover_backlog: 0,
+            load_percent: 0,
+            fee_percent: 100,
+            mempool_capacity: 0,
+            prover_capacity: 0,
+            schedule: Vec::new(),
+        },
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_fee_scaling");
+    Ok(res).into()
+}
+
 pub fn api_scope(tx_sender: TxSender) -> Scope {
     let data = ApiFeeData::new(tx_sender);
 
@@ -96,6 +176,23 @@ pub fn api_scope(tx_sender: TxSender) -> Scope {
         .app_data(web::Data::new(data))
         .route("", web::post().to(get_tx_fee))
         .route("/batch", web::post().to(get_batch_fee))
+        .route("/scaling", web::get().to(get_fee_scaling))
+}
+
+pub fn api_docs() -> Vec<Endpoint> {
+    vec![
+        Endpoint::post::<TxFeeRequest, ApiFee>("/fee", "fee", "Fee for the transaction"),
+        Endpoint::post::<BatchFeeRequest, ApiFee>(
+            "/fee/batch",
+            "fee",
+            "Fee for the batch of transactions",
+        ),
+        Endpoint::get::<ApiFeeScaling>(
+            "/fee/scaling",
+            "fee",
+            "Fee scaling schedule and the current load of the pipeline",
+        ),
+    ]
 }
 
 #[cfg(test)]
@@ -171,6 +268,7 @@ mod tests {
                     dummy_fee_ticker(&prices, Some(cache.clone())),
                     &cfg.config.api.common,
                     &cfg.config.api.token_config,
+                    cfg.config.contracts.contract_addr,
                     mempool_tx_request_sender.clone(),
                 ))
             },
@@ -210,6 +308,12 @@ mod tests {
         assert_eq!(api_batch_fee.zkp_fee, BigUint::from(1u32));
         assert_eq!(api_batch_fee.total_fee, BigUint::from(2u32));
 
+        // The dummy ticker doesn't scale the fees.
+        let response = client.get_fee_scaling().await?;
+        let fee_scaling: ApiFeeScaling = deserialize_response_result(response)?;
+        assert!(!fee_scaling.enabled);
+        assert_eq!(fee_scaling.fee_percent, 100);
+
         server.stop().await;
         Ok(())
     }
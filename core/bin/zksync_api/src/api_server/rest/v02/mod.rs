@@ -10,7 +10,13 @@ use zksync_config::ZkSyncConfig;
 use zksync_types::network::Network;
 
 // Local uses
-use crate::api_server::tx_sender::TxSender;
+use crate::{
+    api_server::{
+        rest::{openapi::Endpoint, response_signer::ResponseSigner},
+        tx_sender::TxSender,
+    },
+    utils::response_cache::BlocksVersionCache,
+};
 
 mod account;
 mod block;
@@ -20,6 +26,8 @@ mod fee;
 mod paginate_impl;
 mod paginate_trait;
 mod response;
+mod search;
+mod stats;
 mod status;
 #[cfg(test)]
 pub mod test_utils;
@@ -32,34 +40,119 @@ pub struct SharedData {
     pub api_version: ApiVersion,
 }
 
+/// Caches of the expensive read endpoints, shared by all the workers of the server and by
+/// the API v0.2 and v0.3, since the cached values don't depend on the response envelope.
+#[derive(Debug, Clone)]
+pub struct ResponseCaches {
+    block_pages: block::BlockPagesCache,
+    token_pages: token::TokenPagesCache,
+    stats: stats::StatsCache,
+    blocks_version: BlocksVersionCache,
+}
+
+impl ResponseCaches {
+    pub fn new(zk_config: &ZkSyncConfig) -> Self {
+        let capacity = zk_config.api.rest.response_cache_size;
+        Self {
+            block_pages: block::BlockPagesCache::new("block_pages", capacity),
+            token_pages: token::TokenPagesCache::new("token_pages", capacity)
+                .with_max_age(zk_config.api.token_config.invalidate_token_cache_period()),
+            stats: stats::StatsCache::new(
+                capacity,
+                zk_config.api.rest.stats_aggregation_interval(),
+            ),
+            blocks_version: BlocksVersionCache::default(),
+        }
+    }
+
+    /// Returns the blocks version the cached responses are invalidated by.
+    pub fn blocks_version(&self) -> BlocksVersionCache {
+        self.blocks_version.clone()
+    }
+}
+
 pub(crate) fn api_scope(
     tx_sender: TxSender,
     zk_config: &ZkSyncConfig,
     network_status: SharedNetworkStatus,
+    response_caches: ResponseCaches,
 ) -> Scope {
     let data = SharedData {
         net: zk_config.chain.eth.network,
         api_version: ApiVersion::V02,
     };
-    web::scope("/api/v0.2")
-        .app_data(web::Data::new(data))
+    register_scopes(
+        web::scope("/api/v0.2").app_data(web::Data::new(data)),
+        tx_sender,
+        zk_config,
+        network_status,
+        response_caches,
+    )
+}
+
+/// Adds the endpoints shared by the API v0.2 and v0.3 to the scope.
+pub(crate) fn register_scopes(
+    scope: Scope,
+    tx_sender: TxSender,
+    zk_config: &ZkSyncConfig,
+    network_status: SharedNetworkStatus,
+    response_caches: ResponseCaches,
+) -> Scope {
+    // Responses of the signed endpoints are sent without the signature unless the key is configured.
+    let scope = match ResponseSigner::from_config(&zk_config.api.common) {
+        Some(signer) => scope.app_data(web::Data::new(signer)),
+        None => scope,
+    };
+    scope
         .service(account::api_scope(
             tx_sender.pool.clone(),
             tx_sender.tokens.clone(),
             zk_config.eth_watch.confirmations_for_eth_event,
+            network_status.clone(),
+            tx_sender.ens_resolver.clone(),
+            tx_sender.eth_checker.clone(),
         ))
         .service(block::api_scope(
             tx_sender.pool.clone(),
             tx_sender.blocks.clone(),
+            response_caches.block_pages,
+            response_caches.blocks_version.clone(),
         ))
         .service(config::api_scope(zk_config))
         .service(fee::api_scope(tx_sender.clone()))
+        .service(search::api_scope(
+            zk_config,
+            tx_sender.pool.clone(),
+            tx_sender.tokens.clone(),
+            tx_sender.blocks.clone(),
+        ))
+        .service(stats::api_scope(
+            tx_sender.pool.clone(),
+            response_caches.stats,
+            response_caches.blocks_version.clone(),
+        ))
         .service(status::api_scope(network_status))
         .service(token::api_scope(
             zk_config,
             tx_sender.pool.clone(),
             tx_sender.tokens.clone(),
+            response_caches.token_pages,
+            response_caches.blocks_version,
             tx_sender.ticker.clone(),
         ))
         .service(transaction::api_scope(tx_sender))
 }
+
+/// Documentation of the endpoints shared by the API v0.2 and v0.3.
+pub(crate) fn api_docs() -> Vec<Endpoint> {
+    let mut endpoints = account::api_docs();
+    endpoints.extend(block::api_docs());
+    endpoints.extend(config::api_docs());
+    endpoints.extend(fee::api_docs());
+    endpoints.extend(search::api_docs());
+    endpoints.extend(stats::api_docs());
+    endpoints.extend(status::api_docs());
+    endpoints.extend(token::api_docs());
+    endpoints.extend(transaction::api_docs());
+    endpoints
+}
@@ -7,9 +7,10 @@ use zksync_api_types::{
     v02::{
         block::BlockInfo,
         pagination::{
-            AccountTxsRequest, ApiEither, BlockAndTxHash, Paginated, PaginationQuery,
-            PendingOpsRequest,
+            AccountNFTsRequest, AccountTxsRequest, ApiEither, BlockAndTxHash, NFTCollectionRequest,
+            Paginated, PaginationQuery, PendingOpsRequest,
         },
+        token::NFT,
         transaction::{Transaction, TxHashSerializeWrapper},
     },
     Either,
@@ -338,3 +339,108 @@ impl Paginate<PendingOpsRequest> for StorageProcessor<'_> {
         ))
     }
 }
+
+#[async_trait::async_trait]
+impl Paginate<AccountNFTsRequest> for StorageProcessor<'_> {
+    type OutputObj = NFT;
+    type OutputId = TokenId;
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<AccountNFTsRequest>,
+    ) -> Result<Paginated<NFT, TokenId>, Error> {
+        let mut transaction = self.start_transaction().await.map_err(Error::storage)?;
+
+        let (count, max_token_id) = transaction
+            .tokens_schema()
+            .get_owned_nfts_stats(query.from.account_id)
+            .await
+            .map_err(Error::storage)?;
+        let token_id = match (&query.from.token_id.inner, max_token_id) {
+            (Either::Left(token_id), _) => *token_id,
+            (Either::Right(_), Some(max_token_id)) => max_token_id,
+            (Either::Right(_), None) => {
+                return Ok(Paginated::new(
+                    Vec::new(),
+                    Default::default(),
+                    query.limit,
+                    query.direction,
+                    0,
+                ));
+            }
+        };
+
+        let page = PaginationQuery {
+            from: token_id,
+            limit: query.limit,
+            direction: query.direction,
+        };
+        let nfts = transaction
+            .tokens_schema()
+            .load_owned_nfts_page(query.from.account_id, &page)
+            .await
+            .map_err(Error::storage)?;
+        transaction.commit().await.map_err(Error::storage)?;
+
+        Ok(Paginated::new(
+            nfts.into_iter().map(NFT::from).collect(),
+            token_id,
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl Paginate<NFTCollectionRequest> for StorageProcessor<'_> {
+    type OutputObj = NFT;
+    type OutputId = u32;
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<NFTCollectionRequest>,
+    ) -> Result<Paginated<NFT, u32>, Error> {
+        let mut transaction = self.start_transaction().await.map_err(Error::storage)?;
+
+        let count = transaction
+            .tokens_schema()
+            .get_minted_nfts_count(query.from.creator_id)
+            .await
+            .map_err(Error::storage)?;
+        let serial_id = match query.from.serial_id.inner {
+            Either::Left(serial_id) => serial_id,
+            // Serial IDs are sequential, so the latest one is one less than the count.
+            Either::Right(_) if count > 0 => count - 1,
+            Either::Right(_) => {
+                return Ok(Paginated::new(
+                    Vec::new(),
+                    Default::default(),
+                    query.limit,
+                    query.direction,
+                    0,
+                ));
+            }
+        };
+
+        let page = PaginationQuery {
+            from: serial_id,
+            limit: query.limit,
+            direction: query.direction,
+        };
+        let nfts = transaction
+            .tokens_schema()
+            .load_minted_nfts_page(query.from.creator_id, &page)
+            .await
+            .map_err(Error::storage)?;
+        transaction.commit().await.map_err(Error::storage)?;
+
+        Ok(Paginated::new(
+            nfts.into_iter().map(NFT::from).collect(),
+            serial_id,
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
@@ -7,12 +7,14 @@ use actix_web::{web::Data, HttpRequest, HttpResponse, Responder};
 use chrono::Utc;
 use qstring::QString;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 // Workspace uses
-use zksync_api_types::v02::{Request, Response, ResultStatus};
+use zksync_api_types::v02::{ApiVersion, Request, Response, ResultStatus};
 
 // Local uses
 use super::{error::Error, SharedData};
+use crate::api_server::rest::{response_signer::ResponseSigner, v03};
 
 // This struct is needed to wrap all api responses is `Response` struct by implementing `Responder` trait for it.
 // We can't use simple `Result`, because `actix-web` has already `Responder` implementation for it.
@@ -25,47 +27,86 @@ pub enum ApiResult<R: Serialize> {
 
 impl<R: Serialize> Responder for ApiResult<R> {
     fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        self.respond(req, None)
+    }
+}
+
+impl<R: Serialize> ApiResult<R> {
+    fn respond(self, req: &HttpRequest, signer: Option<&ResponseSigner>) -> HttpResponse {
         let data = req
             .app_data::<Data<SharedData>>()
             .expect("Wrong app data type");
-        let mut args = HashMap::new();
-        for (name, value) in req.match_info().iter() {
-            args.insert(name.to_string(), value.to_string());
-        }
-        let query_string = QString::from(req.query_string());
-        for (name, value) in query_string {
-            args.insert(name, value);
+        let result = match self {
+            ApiResult::Ok(res) => Ok(serde_json::to_value(res).unwrap()),
+            ApiResult::Error(err) => Err(err),
+        };
+
+        match data.api_version {
+            ApiVersion::V02 => respond_v02(data, req, result, signer),
+            ApiVersion::V03 => v03::response::respond(data, req, result, signer),
         }
+    }
+}
 
-        let request = Request {
-            network: data.net,
-            api_version: data.api_version,
-            resource: String::from(req.path()),
-            args,
-            timestamp: Utc::now(),
-        };
+/// Result of the endpoint whose responses are signed by the operator, if the response signing
+/// key is configured.
+#[derive(Debug)]
+pub struct Signed<R: Serialize>(pub ApiResult<R>);
 
-        let response = match self {
-            ApiResult::Ok(res) => Response {
-                request,
-                status: ResultStatus::Success,
-                result: Some(serde_json::to_value(res).unwrap()),
-                error: None,
-            },
-            ApiResult::Error(err) => Response {
-                request,
-                status: ResultStatus::Error,
-                result: None,
-                error: Some(serde_json::to_value(err).unwrap()),
-            },
-        };
+impl<R: Serialize> Responder for Signed<R> {
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        let signer = req.app_data::<Data<ResponseSigner>>();
+        self.0.respond(req, signer.map(|signer| signer.get_ref()))
+    }
+}
+
+fn respond_v02(
+    data: &SharedData,
+    req: &HttpRequest,
+    result: Result<Value, Error>,
+    signer: Option<&ResponseSigner>,
+) -> HttpResponse {
+    let mut args = HashMap::new();
+    for (name, value) in req.match_info().iter() {
+        args.insert(name.to_string(), value.to_string());
+    }
+    let query_string = QString::from(req.query_string());
+    for (name, value) in query_string {
+        args.insert(name, value);
+    }
 
-        let body = serde_json::to_string(&response).expect("Should be correct serializable");
+    let request = Request {
+        network: data.net,
+        api_version: data.api_version,
+        resource: String::from(req.path()),
+        args,
+        timestamp: Utc::now(),
+    };
+
+    let response = match result {
+        Ok(res) => Response {
+            request,
+            status: ResultStatus::Success,
+            result: Some(res),
+            error: None,
+        },
+        Err(err) => Response {
+            request,
+            status: ResultStatus::Error,
+            result: None,
+            error: Some(serde_json::to_value(err).unwrap()),
+        },
+    };
 
-        HttpResponse::Ok()
-            .content_type("application/json")
-            .body(body)
+    let body = serde_json::to_string(&response).expect("Should be correct serializable");
+
+    let mut builder = HttpResponse::Ok();
+    if let Some(signer) = signer {
+        for header in signer.signature_headers(&body) {
+            builder.insert_header(header);
+        }
     }
+    builder.content_type("application/json").body(body)
 }
 
 impl<R: Serialize> From<Error> for ApiResult<R> {
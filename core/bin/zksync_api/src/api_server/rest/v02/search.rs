@@ -0,0 +1,300 @@
+//! Search part of API implementation.
+
+// Built-in uses
+use std::str::FromStr;
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, Scope};
+use num::{rational::Ratio, BigUint, FromPrimitive};
+
+// Workspace uses
+use zksync_api_types::v02::{
+    search::{AccountSearchResult, SearchQuery, SearchResult},
+    token::ApiToken,
+    transaction::TransactionData,
+};
+use zksync_config::ZkSyncConfig;
+use zksync_storage::ConnectionPool;
+use zksync_token_db_cache::TokenDBCache;
+use zksync_types::{tx::TxHash, Address, BlockNumber, TokenLike};
+use zksync_utils::remove_prefix;
+
+// Local uses
+use super::{
+    block::block_info_from_details, error::Error, response::ApiResult, transaction::load_tx_data,
+};
+use crate::{api_server::rest::openapi::Endpoint, utils::block_details_cache::BlockDetailsCache};
+
+/// Interpretation of the search query.
+#[derive(Debug, Clone, PartialEq)]
+enum SearchTarget {
+    Block(BlockNumber),
+    Address(Address),
+    /// Either L2 transaction hash or the hash of the L1 transaction of the priority operation.
+    Hash(TxHash),
+    TokenSymbol(String),
+}
+
+/// Detects the type of the query by its format.
+fn parse_search_query(query: &str) -> Option<SearchTarget> {
+    let query = query.trim();
+    if query.is_empty() {
+        return None;
+    }
+    if let Ok(block_number) = u32::from_str(query) {
+        return Some(SearchTarget::Block(BlockNumber(block_number)));
+    }
+
+    let hex = remove_prefix(query);
+    if hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        match hex.len() {
+            40 => {
+                if let Ok(address) = Address::from_str(hex) {
+                    return Some(SearchTarget::Address(address));
+                }
+            }
+            64 => {
+                if let Some(hash) = hex::decode(hex)
+                    .ok()
+                    .and_then(|bytes| TxHash::from_slice(&bytes))
+                {
+                    return Some(SearchTarget::Hash(hash));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if query
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+    {
+        Some(SearchTarget::TokenSymbol(query.to_string()))
+    } else {
+        None
+    }
+}
+
+/// Shared data between `api/v0.2/search` endpoints.
+#[derive(Clone)]
+struct ApiSearchData {
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    blocks: BlockDetailsCache,
+    min_market_volume: Ratio<BigUint>,
+}
+
+impl ApiSearchData {
+    async fn search(&self, target: SearchTarget) -> Result<Option<SearchResult>, Error> {
+        // The block details cache takes its own connection, so it's queried before the storage
+        // is accessed.
+        if let SearchTarget::Block(block_number) = target {
+            let details = self
+                .blocks
+                .get(&self.pool, block_number)
+                .await
+                .map_err(Error::storage)?;
+            return Ok(details.map(|details| SearchResult::Block(block_info_from_details(details))));
+        }
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let result = match target {
+            SearchTarget::Block(_) => unreachable!("Blocks are searched above"),
+            SearchTarget::Address(address) => {
+                let account_id = storage
+                    .chain()
+                    .account_schema()
+                    .account_id_by_address(address)
+                    .await
+                    .map_err(Error::storage)?;
+                Some(SearchResult::Account(AccountSearchResult {
+                    address,
+                    account_id,
+                }))
+            }
+            SearchTarget::Hash(hash) => {
+                load_tx_data(&mut storage, hash)
+                    .await?
+                    .map(|data| match data.tx.op {
+                        TransactionData::L1(_) => SearchResult::PriorityOperation(data.tx),
+                        TransactionData::L2(_) => SearchResult::Transaction(data.tx),
+                    })
+            }
+            SearchTarget::TokenSymbol(symbol) => {
+                let token = self
+                    .tokens
+                    .get_token(&mut storage, TokenLike::Symbol(symbol))
+                    .await
+                    .map_err(Error::storage)?;
+                match token {
+                    Some(token) => {
+                        let enabled_for_fees = !storage
+                            .tokens_schema()
+                            .filter_tokens_by_market_volume(vec![token.id], &self.min_market_volume)
+                            .await
+                            .map_err(Error::storage)?
+                            .is_empty();
+                        Some(SearchResult::Token(ApiToken::from_token_and_eligibility(
+                            token,
+                            enabled_for_fees,
+                        )))
+                    }
+                    None => None,
+                }
+            }
+        };
+        Ok(result)
+    }
+}
+
+// Server implementation
+
+async fn search(
+    data: web::Data<ApiSearchData>,
+    web::Query(query): web::Query<SearchQuery>,
+) -> ApiResult<Option<SearchResult>> {
+    let start = Instant::now();
+    let res = match parse_search_query(&query.q) {
+        Some(target) => data.search(target).await.into(),
+        None => ApiResult::Ok(None),
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "search");
+    res
+}
+
+pub fn api_scope(
+    config: &ZkSyncConfig,
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    blocks: BlockDetailsCache,
+) -> Scope {
+    let data = ApiSearchData {
+        pool,
+        tokens,
+        blocks,
+        min_market_volume: Ratio::from(
+            BigUint::from_f64(config.ticker.min_market_volume())
+                .expect("TickerConfig::min_market_volume must be positive"),
+        ),
+    };
+
+    web::scope("search")
+        .app_data(web::Data::new(data))
+        .route("", web::get().to(search))
+}
+
+pub fn api_docs() -> Vec<Endpoint> {
+    vec![Endpoint::get::<Option<SearchResult>>(
+        "/search",
+        "search",
+        "Search of the transaction, priority operation, account, block or token",
+    )
+    .with_query(&["q"])]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::{block::BlockInfo, ApiVersion};
+
+    #[test]
+    fn search_query_detection() {
+        let hash = "0x1f0e9bc88e8f4bcc2dc0c28d5bac7f3ac1a4a0b21b72e5b4f4e5f1c7f6d1e2a3";
+        let address = "0x7a16ff8270133f063aab6c9977183d9e72835428";
+
+        assert_eq!(
+            parse_search_query("42"),
+            Some(SearchTarget::Block(BlockNumber(42)))
+        );
+        assert_eq!(
+            parse_search_query(address),
+            Some(SearchTarget::Address(
+                Address::from_str(&address[2..]).unwrap()
+            ))
+        );
+        let expected_hash = TxHash::from_slice(&hex::decode(&hash[2..]).unwrap()).unwrap();
+        assert_eq!(
+            parse_search_query(hash),
+            Some(SearchTarget::Hash(expected_hash))
+        );
+        assert_eq!(
+            parse_search_query(&format!("sync-tx:{}", &hash[2..])),
+            Some(SearchTarget::Hash(expected_hash))
+        );
+        assert_eq!(
+            parse_search_query(" ETH "),
+            Some(SearchTarget::TokenSymbol("ETH".to_string()))
+        );
+        assert_eq!(parse_search_query(""), None);
+        assert_eq!(parse_search_query("not a symbol"), None);
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn search_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            |cfg: &TestServerConfig| {
+                api_scope(
+                    &cfg.config,
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                    BlockDetailsCache::new(10),
+                )
+            },
+            Some(shared_data),
+        );
+
+        let response = client.search("1").await?;
+        let result: Option<SearchResult> = deserialize_response_result(response)?;
+        match result {
+            Some(SearchResult::Block(BlockInfo { block_number, .. })) => {
+                assert_eq!(block_number, BlockNumber(1))
+            }
+            result => panic!("Block is expected, found {:?}", result),
+        }
+
+        let tx_hash = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let txs = storage
+                .chain()
+                .block_schema()
+                .get_block_transactions(BlockNumber(1))
+                .await?;
+            TxHash::from_str(&txs[0].tx_hash).unwrap()
+        };
+        let response = client.search(&tx_hash.to_string()).await?;
+        let result: Option<SearchResult> = deserialize_response_result(response)?;
+        match result {
+            Some(SearchResult::Transaction(tx)) | Some(SearchResult::PriorityOperation(tx)) => {
+                assert_eq!(tx.tx_hash, tx_hash)
+            }
+            result => panic!("Transaction is expected, found {:?}", result),
+        }
+
+        let response = client.search("ETH").await?;
+        let result: Option<SearchResult> = deserialize_response_result(response)?;
+        assert!(matches!(result, Some(SearchResult::Token(token)) if token.symbol == "ETH"));
+
+        let response = client.search("UNKNOWN").await?;
+        let result: Option<SearchResult> = deserialize_response_result(response)?;
+        assert!(result.is_none());
+
+        server.stop().await;
+        Ok(())
+    }
+}
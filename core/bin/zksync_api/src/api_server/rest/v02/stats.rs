@@ -0,0 +1,301 @@
+//! Network statistics part of API implementation.
+//!
+//! The statistics are computed by the background aggregator, the endpoints only read
+//! the summary tables.
+
+// Built-in uses
+use std::collections::BTreeMap;
+use std::time::{Duration as StdDuration, Instant};
+
+// External uses
+use actix_web::{web, Scope};
+use chrono::{Duration, Utc};
+use num::{bigint::ToBigInt, BigUint};
+
+// Workspace uses
+use zksync_api_types::v02::stats::{DailyStats, DailyStatsQuery, TokenAmount, TokenTvl};
+use zksync_storage::{BigDecimal, ConnectionPool};
+use zksync_types::TokenId;
+
+// Local uses
+use super::{error::Error, response::ApiResult};
+use crate::{
+    api_server::rest::openapi::Endpoint,
+    utils::response_cache::{BlocksVersionCache, ResponseCache},
+};
+
+/// Number of days returned by default.
+const DEFAULT_DAYS: u32 = 7;
+/// Maximum number of days the statistics can be requested for at once.
+const MAX_DAYS: u32 = 90;
+
+fn to_biguint(amount: BigDecimal) -> BigUint {
+    amount
+        .to_bigint()
+        .and_then(|amount| amount.to_biguint())
+        .unwrap_or_default()
+}
+
+/// Caches of the `api/v0.2/stats` responses.
+///
+/// The statistics are recomputed by the aggregator regardless of the blocks, so the responses
+/// also get stale after the aggregation interval.
+#[derive(Debug, Clone)]
+pub struct StatsCache {
+    daily_stats: ResponseCache<u32, Vec<DailyStats>>,
+    tvl: ResponseCache<(), Vec<TokenTvl>>,
+    total_fees: ResponseCache<(), Vec<TokenAmount>>,
+}
+
+impl StatsCache {
+    pub fn new(capacity: usize, aggregation_interval: StdDuration) -> Self {
+        Self {
+            daily_stats: ResponseCache::new("daily_stats", capacity)
+                .with_max_age(aggregation_interval),
+            // There is a single response of these endpoints.
+            tvl: ResponseCache::new("tvl", 1).with_max_age(aggregation_interval),
+            total_fees: ResponseCache::new("total_fees", 1).with_max_age(aggregation_interval),
+        }
+    }
+}
+
+/// Shared data between `api/v0.2/stats` endpoints.
+#[derive(Debug, Clone)]
+struct ApiStatsData {
+    pool: ConnectionPool,
+    cache: StatsCache,
+    blocks_version: BlocksVersionCache,
+}
+
+impl ApiStatsData {
+    fn new(pool: ConnectionPool, cache: StatsCache, blocks_version: BlocksVersionCache) -> Self {
+        Self {
+            pool,
+            cache,
+            blocks_version,
+        }
+    }
+
+    async fn daily_stats(&self, days: u32) -> Result<Vec<DailyStats>, Error> {
+        let days = days.clamp(1, MAX_DAYS);
+        let version = self.blocks_version.get();
+        self.cache
+            .daily_stats
+            .get_or_load(days, version, || self.load_daily_stats(days))
+            .await
+    }
+
+    async fn tvl(&self) -> Result<Vec<TokenTvl>, Error> {
+        let version = self.blocks_version.get();
+        self.cache
+            .tvl
+            .get_or_load((), version, || self.load_tvl())
+            .await
+    }
+
+    async fn total_fees(&self) -> Result<Vec<TokenAmount>, Error> {
+        let version = self.blocks_version.get();
+        self.cache
+            .total_fees
+            .get_or_load((), version, || self.load_total_fees())
+            .await
+    }
+
+    async fn load_daily_stats(&self, days: u32) -> Result<Vec<DailyStats>, Error> {
+        let from_day = Utc::today().naive_utc() - Duration::days(i64::from(days) - 1);
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
+        let mut stats_schema = transaction.chain().stats_schema();
+        let daily_stats = stats_schema
+            .load_daily_stats(from_day)
+            .await
+            .map_err(Error::storage)?;
+        let tx_counts = stats_schema
+            .load_daily_tx_counts(from_day)
+            .await
+            .map_err(Error::storage)?;
+        let fees = stats_schema
+            .load_daily_fees(from_day)
+            .await
+            .map_err(Error::storage)?;
+        transaction.commit().await.map_err(Error::storage)?;
+
+        let mut stats: Vec<_> = daily_stats
+            .into_iter()
+            .map(|stats| DailyStats {
+                day: stats.day,
+                tx_counts: BTreeMap::new(),
+                active_accounts: stats.active_accounts as u64,
+                fees: Vec::new(),
+                avg_finality_secs: stats.avg_finality_secs,
+            })
+            .collect();
+        for count in tx_counts {
+            if let Some(stats) = stats.iter_mut().find(|stats| stats.day == count.day) {
+                stats.tx_counts.insert(count.tx_type, count.tx_count as u64);
+            }
+        }
+        for fee in fees {
+            if let Some(stats) = stats.iter_mut().find(|stats| stats.day == fee.day) {
+                stats.fees.push(TokenAmount {
+                    token_id: TokenId(fee.token_id as u32),
+                    amount: to_biguint(fee.amount),
+                });
+            }
+        }
+        Ok(stats)
+    }
+
+    async fn load_tvl(&self) -> Result<Vec<TokenTvl>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let tvl = storage
+            .chain()
+            .stats_schema()
+            .load_tvl()
+            .await
+            .map_err(Error::storage)?
+            .into_iter()
+            .map(|tvl| TokenTvl {
+                token_id: TokenId(tvl.token_id as u32),
+                amount: to_biguint(tvl.amount),
+                updated_at: tvl.updated_at,
+            })
+            .collect();
+        Ok(tvl)
+    }
+
+    async fn load_total_fees(&self) -> Result<Vec<TokenAmount>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let fees = storage
+            .chain()
+            .stats_schema()
+            .load_total_fees()
+            .await
+            .map_err(Error::storage)?
+            .into_iter()
+            .map(|(token_id, amount)| TokenAmount {
+                token_id,
+                amount: to_biguint(amount),
+            })
+            .collect();
+        Ok(fees)
+    }
+}
+
+// Server implementation
+
+async fn daily_stats(
+    data: web::Data<ApiStatsData>,
+    web::Query(query): web::Query<DailyStatsQuery>,
+) -> ApiResult<Vec<DailyStats>> {
+    let start = Instant::now();
+    let res = data
+        .daily_stats(query.days.unwrap_or(DEFAULT_DAYS))
+        .await
+        .into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "daily_stats");
+    res
+}
+
+async fn tvl(data: web::Data<ApiStatsData>) -> ApiResult<Vec<TokenTvl>> {
+    let start = Instant::now();
+    let res = data.tvl().await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tvl");
+    res
+}
+
+async fn total_fees(data: web::Data<ApiStatsData>) -> ApiResult<Vec<TokenAmount>> {
+    let start = Instant::now();
+    let res = data.total_fees().await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "total_fees");
+    res
+}
+
+pub fn api_scope(
+    pool: ConnectionPool,
+    cache: StatsCache,
+    blocks_version: BlocksVersionCache,
+) -> Scope {
+    let data = ApiStatsData::new(pool, cache, blocks_version);
+
+    web::scope("stats")
+        .app_data(web::Data::new(data))
+        .route("daily", web::get().to(daily_stats))
+        .route("tvl", web::get().to(tvl))
+        .route("fees", web::get().to(total_fees))
+}
+
+pub fn api_docs() -> Vec<Endpoint> {
+    vec![
+        Endpoint::get::<Vec<DailyStats>>(
+            "/stats/daily",
+            "stats",
+            "Aggregated statistics of the latest days",
+        )
+        .with_query(&["days"]),
+        Endpoint::get::<Vec<TokenTvl>>(
+            "/stats/tvl",
+            "stats",
+            "Total value locked in the network per token",
+        ),
+        Endpoint::get::<Vec<TokenAmount>>("/stats/fees", "stats", "Total fees collected per token"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::ApiVersion;
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn stats_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            let mut stats_schema = storage.chain().stats_schema();
+            stats_schema
+                .aggregate_daily_stats(Utc::today().naive_utc())
+                .await?;
+            stats_schema.update_tvl().await?;
+        }
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            |cfg: &TestServerConfig| {
+                api_scope(
+                    cfg.pool.clone(),
+                    StatsCache::new(10, cfg.config.api.rest.stats_aggregation_interval()),
+                    BlocksVersionCache::default(),
+                )
+            },
+            Some(shared_data),
+        );
+
+        let response = client.daily_stats(Some(1)).await?;
+        let stats: Vec<DailyStats> = deserialize_response_result(response)?;
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].day, Utc::today().naive_utc());
+
+        let response = client.tvl().await?;
+        let _tvl: Vec<TokenTvl> = deserialize_response_result(response)?;
+
+        let response = client.total_fees().await?;
+        let _fees: Vec<TokenAmount> = deserialize_response_result(response)?;
+
+        server.stop().await;
+        Ok(())
+    }
+}
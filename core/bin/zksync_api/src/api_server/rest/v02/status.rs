@@ -11,7 +11,7 @@ use zksync_api_types::v02::status::NetworkStatus;
 
 // Local uses
 use super::response::ApiResult;
-use crate::api_server::rest::network_status::SharedNetworkStatus;
+use crate::api_server::rest::{network_status::SharedNetworkStatus, openapi::Endpoint};
 
 /// Shared data between `api/v0.2/networkStatus` endpoints.
 #[derive(Debug, Clone)]
@@ -50,6 +50,14 @@ pub fn api_scope(shared_status: SharedNetworkStatus) -> Scope {
         .route("", web::get().to(get_status))
 }
 
+pub fn api_docs() -> Vec<Endpoint> {
+    vec![Endpoint::get::<NetworkStatus>(
+        "/networkStatus",
+        "status",
+        "Network status",
+    )]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
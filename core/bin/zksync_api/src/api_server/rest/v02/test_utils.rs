@@ -36,7 +36,7 @@ use zksync_types::{
     aggregated_operations::AggregatedActionType,
     helpers::{apply_updates, closest_packable_fee_amount, closest_packable_token_amount},
     operations::{ChangePubKeyOp, TransferToNewOp},
-    prover::ProverJobType,
+    prover::{ProofSystemVersion, ProverJobType},
     tx::ChangePubKeyType,
     AccountId, AccountMap, AccountUpdate, Address, BlockNumber, Deposit, DepositOp,
     ExecutedOperations, ExecutedPriorityOp, ExecutedTx, FullExit, FullExitOp, MintNFTOp, Nonce,
@@ -47,6 +47,7 @@ use zksync_utils::{big_decimal_to_ratio, scaled_u64_to_ratio, UnsignedRatioSeria
 
 // Local uses
 use crate::fee_ticker::{
+    scaling::PipelineLoad,
     tests::TestToken,
     ticker_info::BlocksInFutureAggregatedOperations,
     validator::{cache::TokenInMemoryCache, FeeTokenValidator},
@@ -550,7 +551,12 @@ impl TestServerConfig {
                 let proof = get_sample_single_proof();
                 let aggregated_proof = get_sample_aggregated_proof();
                 ProverSchema(&mut storage)
-                    .store_proof(stored_job_id, block_number, &proof)
+                    .store_proof(
+                        stored_job_id,
+                        block_number,
+                        &proof,
+                        ProofSystemVersion::PlonkV1,
+                    )
                     .await?;
                 ProverSchema(&mut storage)
                     .store_aggregated_proof(
@@ -558,6 +564,7 @@ impl TestServerConfig {
                         block_number,
                         block_number,
                         &aggregated_proof,
+                        ProofSystemVersion::PlonkV1,
                     )
                     .await?;
 
@@ -889,6 +896,18 @@ impl FeeTickerInfo for DummyFeeTickerInfo {
         })
     }
 
+    async fn nft_factory_mint_gas(
+        &self,
+        _token: TokenId,
+        _recipient: Address,
+    ) -> anyhow::Result<Option<BigUint>> {
+        Ok(None)
+    }
+
+    async fn pipeline_load(&self) -> anyhow::Result<PipelineLoad> {
+        Ok(PipelineLoad::default())
+    }
+
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
@@ -912,6 +931,9 @@ pub fn get_test_ticker_config() -> TickerConfig {
         scale_fee_coefficient: Ratio::new(BigUint::from(150u32), BigUint::from(100u32)),
         max_blocks_to_aggregate: 5,
         subsidy_cpk_price_usd: scaled_u64_to_ratio(SUBSIDY_CPK_PRICE_USD_SCALED),
+        stale_price_timeout: None,
+        stale_price_multiplier: None,
+        fee_scaling: None,
     }
 }
 pub fn dummy_fee_ticker(
@@ -30,16 +30,23 @@ use super::{
     response::ApiResult,
 };
 use crate::{
+    api_server::{rest::openapi::Endpoint, tx_sender::AmbiguousTokenSymbol},
     api_try,
     fee_ticker::{FeeTicker, PriceError, TokenPriceRequestType},
+    utils::response_cache::{BlocksVersionCache, ResponseCache},
 };
 
+/// Cache of the tokens list pages, keyed by the raw pagination query.
+pub type TokenPagesCache = ResponseCache<PaginationQuery<String>, Paginated<ApiToken, TokenId>>;
+
 /// Shared data between `api/v0.2/tokens` endpoints.
 #[derive(Clone)]
 struct ApiTokenData {
     min_market_volume: Ratio<BigUint>,
     fee_ticker: FeeTicker,
     tokens: TokenDBCache,
+    token_pages_cache: TokenPagesCache,
+    blocks_version: BlocksVersionCache,
     pool: ConnectionPool,
 }
 
@@ -48,15 +55,19 @@ impl ApiTokenData {
         config: &ZkSyncConfig,
         pool: ConnectionPool,
         tokens: TokenDBCache,
+        token_pages_cache: TokenPagesCache,
+        blocks_version: BlocksVersionCache,
         fee_ticker: FeeTicker,
     ) -> Self {
         Self {
             min_market_volume: Ratio::from(
-                BigUint::from_f64(config.ticker.liquidity_volume)
-                    .expect("TickerConfig::liquidity_volume must be positive"),
+                BigUint::from_f64(config.ticker.min_market_volume())
+                    .expect("TickerConfig::min_market_volume must be positive"),
             ),
             pool,
             tokens,
+            token_pages_cache,
+            blocks_version,
             fee_ticker,
         }
     }
@@ -76,7 +87,23 @@ impl ApiTokenData {
         Ok(!result.is_empty())
     }
 
+    /// Returns the page of the tokens list.
+    ///
+    /// Tokens are added and enabled for fees regardless of the blocks, so the cached pages also
+    /// get stale after the token cache invalidation period.
     async fn token_page(
+        &self,
+        raw_query: PaginationQuery<String>,
+    ) -> Result<Paginated<ApiToken, TokenId>, Error> {
+        let query: PaginationQuery<ApiEither<TokenId>> =
+            parse_query(raw_query.clone()).map_err(Error::from)?;
+        let version = self.blocks_version.get();
+        self.token_pages_cache
+            .get_or_load(raw_query, version, || self.load_token_page(query))
+            .await
+    }
+
+    async fn load_token_page(
         &self,
         query: PaginationQuery<ApiEither<TokenId>>,
     ) -> Result<Paginated<ApiToken, TokenId>, Error> {
@@ -128,16 +155,27 @@ impl ApiTokenData {
 
         let token = self
             .tokens
-            .get_token(&mut storage, token_like)
+            .get_token(&mut storage, token_like.clone())
             .await
             .map_err(Error::storage)?;
         if let Some(token) = token {
-            Ok(token)
-        } else {
-            Err(Error::from(PriceError::token_not_found(
-                "Token not found in storage",
-            )))
+            return Ok(token);
         }
+
+        let candidates = self
+            .tokens
+            .symbol_collisions(&mut storage, &token_like)
+            .await
+            .map_err(Error::storage)?;
+        if !candidates.is_empty() {
+            return Err(Error::from(AmbiguousTokenSymbol {
+                symbol: token_like.to_string(),
+                candidates,
+            }));
+        }
+        Err(Error::from(PriceError::token_not_found(
+            "Token not found in storage",
+        )))
     }
 
     async fn api_token(&self, token_like: TokenLike) -> Result<ApiToken, Error> {
@@ -195,7 +233,6 @@ async fn token_pagination(
     web::Query(query): web::Query<PaginationQuery<String>>,
 ) -> ApiResult<Paginated<ApiToken, TokenId>> {
     let start = Instant::now();
-    let query = api_try!(parse_query(query).map_err(Error::from));
     let res = data.token_page(query).await.into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "token_pagination");
     res
@@ -222,8 +259,9 @@ async fn token_price(
     let (token_like_string, currency) = path.into_inner();
     let first_token = TokenLike::parse(&token_like_string);
 
-    let price = api_try!(data.token_price_in(first_token.clone(), &currency).await);
-    let token = api_try!(data.token(first_token).await);
+    // The token is resolved first, so the ambiguous symbols are reported as such.
+    let token = api_try!(data.token(first_token.clone()).await);
+    let price = api_try!(data.token_price_in(first_token, &currency).await);
 
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_token_price");
     ApiResult::Ok(TokenPrice {
@@ -292,9 +330,18 @@ pub fn api_scope(
     config: &ZkSyncConfig,
     pool: ConnectionPool,
     tokens_db: TokenDBCache,
+    token_pages_cache: TokenPagesCache,
+    blocks_version: BlocksVersionCache,
     fee_ticker: FeeTicker,
 ) -> Scope {
-    let data = ApiTokenData::new(config, pool, tokens_db, fee_ticker);
+    let data = ApiTokenData::new(
+        config,
+        pool,
+        tokens_db,
+        token_pages_cache,
+        blocks_version,
+        fee_ticker,
+    );
 
     web::scope("tokens")
         .app_data(web::Data::new(data))
@@ -312,6 +359,26 @@ pub fn api_scope(
         )
 }
 
+pub fn api_docs() -> Vec<Endpoint> {
+    vec![
+        Endpoint::get::<ApiToken>("/tokens", "tokens", "Tokens list").paginated(),
+        Endpoint::get::<ApiToken>("/tokens/{tokenLike}", "tokens", "Token details"),
+        Endpoint::get::<TokenPrice>(
+            "/tokens/{tokenLike}/priceIn/{currency}",
+            "tokens",
+            "Token price in the currency",
+        ),
+        Endpoint::get::<Option<ApiNFT>>("/tokens/nft/{id}", "tokens", "NFT details"),
+        // Account ID of the owner.
+        Endpoint::get::<Option<u32>>("/tokens/nft/{id}/owner", "tokens", "Owner of the NFT"),
+        Endpoint::get::<Option<u32>>(
+            "/tokens/nft_id_by_tx_hash/{txHash}",
+            "tokens",
+            "ID of the NFT minted by the transaction",
+        ),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -328,8 +395,8 @@ mod tests {
         config: &ZkSyncConfig,
     ) -> anyhow::Result<bool> {
         let min_market_volume = Ratio::from(
-            BigUint::from_f64(config.ticker.liquidity_volume)
-                .expect("TickerConfig::liquidity_volume must be positive"),
+            BigUint::from_f64(config.ticker.min_market_volume())
+                .expect("TickerConfig::min_market_volume must be positive"),
         );
         let filtered = storage
             .tokens_schema()
@@ -366,6 +433,8 @@ mod tests {
                     &cfg.config,
                     cfg.pool.clone(),
                     TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                    TokenPagesCache::new("token_pages", 10),
+                    BlocksVersionCache::default(),
                     fee_ticker.clone(),
                 )
             },
@@ -1,27 +1,88 @@
 //! Transactions part of API implementation.
 
 // Built-in uses
-use std::time::Instant;
+use std::time::{Duration, Instant};
 // External uses
 use actix_web::{
     web::{self, Json},
-    Scope,
+    HttpRequest, Scope,
 };
 
 // Workspace uses
 use zksync_api_types::{
     v02::transaction::{
-        ApiTxBatch, IncomingTxBatch, L1Receipt, L1Transaction, Receipt, SubmitBatchResponse,
+        AccountWithdrawalTimelock, ApiTxBatch, CancelWithdrawalRequest, IncomingNFTTransferBatch,
+        IncomingTxBatch, L1Receipt, L1Transaction, PendingWithdrawal, Receipt, SubmitBatchResponse,
         Toggle2FA, Toggle2FAResponse, Transaction, TransactionData, TxData, TxHashSerializeWrapper,
-        TxInBlockStatus,
+        TxInBlockStatus, TxWaitQuery, WithdrawalTimelockRequest, WithdrawalTimelockSettings,
+        IDEMPOTENCY_KEY_HEADER, RECIPIENT_NAME_HEADER,
     },
     TxWithSignature,
 };
-use zksync_types::{tx::TxHash, EthBlockId};
+use zksync_storage::StorageProcessor;
+use zksync_types::{tx::TxHash, AccountId, EthBlockId};
 
 // Local uses
-use super::{error::Error, response::ApiResult};
-use crate::api_server::tx_sender::{SubmitError, TxSender};
+use super::{
+    error::Error,
+    response::{ApiResult, Signed},
+};
+use crate::api_server::{
+    rest::openapi::Endpoint,
+    tx_sender::{SubmitError, TxSender},
+};
+
+/// Interval of re-checking the status of the awaited transaction.
+const TX_WAIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Time the transaction is awaited for, unless the request sets a shorter one.
+const MAX_TX_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Loads the executed transaction by its hash or the priority operation by the hash of
+/// the L1 transaction, including the priority operations not yet executed.
+pub(super) async fn load_tx_data(
+    storage: &mut StorageProcessor<'_>,
+    tx_hash: TxHash,
+) -> Result<Option<TxData>, Error> {
+    if let Some(data) = storage
+        .chain()
+        .operations_ext_schema()
+        .tx_data_api_v02(tx_hash.as_ref())
+        .await
+        .map_err(Error::storage)?
+    {
+        Ok(Some(data))
+    } else if let Some(op) = storage
+        .chain()
+        .mempool_schema()
+        .get_pending_operation_by_hash(tx_hash.into())
+        .await
+        .map_err(Error::core_api)?
+    {
+        let tx_hash = op.tx_hash();
+        let tx = Transaction {
+            tx_hash,
+            block_index: None,
+            block_number: None,
+            op: TransactionData::L1(L1Transaction::from_pending_op(
+                op.data,
+                op.eth_hash,
+                op.serial_id,
+                tx_hash,
+            )),
+            status: TxInBlockStatus::Queued,
+            fail_reason: None,
+            created_at: None,
+            batch_id: None,
+        };
+
+        Ok(Some(TxData {
+            tx,
+            eth_signature: None,
+        }))
+    } else {
+        Ok(None)
+    }
+}
 
 /// Shared data between `api/v0.2/transactions` endpoints.
 #[derive(Clone)]
@@ -74,6 +135,28 @@ impl ApiTransactionData {
         }
     }
 
+    /// Re-checks the status of the transaction until it reaches the requested level or the
+    /// timeout expires, returning the latest receipt.
+    async fn wait_tx(&self, tx_hash: TxHash, query: TxWaitQuery) -> Result<Option<Receipt>, Error> {
+        let level = query.level.unwrap_or_default();
+        let timeout = query.timeout.map_or(MAX_TX_WAIT_TIMEOUT, |secs| {
+            Duration::from_secs(secs).min(MAX_TX_WAIT_TIMEOUT)
+        });
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let receipt = self.tx_status(tx_hash).await?;
+            let reached = receipt
+                .as_ref()
+                .map_or(false, |receipt| level.is_reached(receipt.status()));
+            let now = Instant::now();
+            if reached || now >= deadline {
+                return Ok(receipt);
+            }
+            tokio::time::sleep(TX_WAIT_POLL_INTERVAL.min(deadline - now)).await;
+        }
+    }
+
     async fn tx_data(&self, tx_hash: TxHash) -> Result<Option<TxData>, Error> {
         let mut storage = self
             .tx_sender
@@ -81,45 +164,7 @@ impl ApiTransactionData {
             .access_storage()
             .await
             .map_err(Error::storage)?;
-        if let Some(data) = storage
-            .chain()
-            .operations_ext_schema()
-            .tx_data_api_v02(tx_hash.as_ref())
-            .await
-            .map_err(Error::storage)?
-        {
-            Ok(Some(data))
-        } else if let Some(op) = storage
-            .chain()
-            .mempool_schema()
-            .get_pending_operation_by_hash(tx_hash.into())
-            .await
-            .map_err(Error::core_api)?
-        {
-            let tx_hash = op.tx_hash();
-            let tx = Transaction {
-                tx_hash,
-                block_index: None,
-                block_number: None,
-                op: TransactionData::L1(L1Transaction::from_pending_op(
-                    op.data,
-                    op.eth_hash,
-                    op.serial_id,
-                    tx_hash,
-                )),
-                status: TxInBlockStatus::Queued,
-                fail_reason: None,
-                created_at: None,
-                batch_id: None,
-            };
-
-            Ok(Some(TxData {
-                tx,
-                eth_signature: None,
-            }))
-        } else {
-            Ok(None)
-        }
+        load_tx_data(&mut storage, tx_hash).await
     }
 
     async fn get_batch(&self, batch_hash: TxHash) -> Result<Option<ApiTxBatch>, Error> {
@@ -143,10 +188,21 @@ impl ApiTransactionData {
 async fn tx_status(
     data: web::Data<ApiTransactionData>,
     tx_hash: web::Path<TxHash>,
-) -> ApiResult<Option<Receipt>> {
+) -> Signed<Option<Receipt>> {
     let start = Instant::now();
     let res = data.tx_status(*tx_hash).await.into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tx_status");
+    Signed(res)
+}
+
+async fn wait_tx(
+    data: web::Data<ApiTransactionData>,
+    tx_hash: web::Path<TxHash>,
+    web::Query(query): web::Query<TxWaitQuery>,
+) -> ApiResult<Option<Receipt>> {
+    let start = Instant::now();
+    let res = data.wait_tx(*tx_hash, query).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "wait_tx");
     res
 }
 
@@ -162,16 +218,41 @@ async fn tx_data(
 
 async fn submit_tx(
     data: web::Data<ApiTransactionData>,
+    req: HttpRequest,
     Json(body): Json<TxWithSignature>,
 ) -> ApiResult<TxHashSerializeWrapper> {
     let start = Instant::now();
-    let tx_hash = data
-        .tx_sender
-        .submit_tx(body.tx, body.signature, None)
-        .await;
+    let idempotency_key = req
+        .headers()
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .map(|value| value.to_str().map(String::from));
+    let idempotency_key = match idempotency_key.transpose() {
+        Ok(idempotency_key) => idempotency_key,
+        Err(err) => return Error::from(SubmitError::invalid_params(err)).into(),
+    };
+    let recipient_name = req
+        .headers()
+        .get(RECIPIENT_NAME_HEADER)
+        .map(|value| value.to_str().map(String::from));
+    let recipient_name = match recipient_name.transpose() {
+        Ok(recipient_name) => recipient_name,
+        Err(err) => return Error::from(SubmitError::invalid_params(err)).into(),
+    };
+    let recipient_check = match &recipient_name {
+        Some(name) => data.tx_sender.check_recipient_name(&body.tx, name).await,
+        None => Ok(()),
+    };
+    let tx_hash = match recipient_check {
+        Ok(()) => {
+            data.tx_sender
+                .submit_tx_with_idempotency_key(body.tx, body.signature, idempotency_key, None)
+                .await
+        }
+        Err(err) => Err(err),
+    };
 
     if let Err(err) = &tx_hash {
-        let err_label = match err {
+        let err_label = match err.cause() {
             SubmitError::IncorrectTx(err) => err.clone(),
             SubmitError::TxAdd(err) => err.to_string(),
             _ => "other".to_string(),
@@ -196,7 +277,7 @@ async fn submit_batch(
         .await;
 
     if let Err(err) = &response {
-        let err_label = match err {
+        let err_label = match err.cause() {
             SubmitError::IncorrectTx(err) => err.clone(),
             SubmitError::TxAdd(err) => err.to_string(),
             _ => "other".to_string(),
@@ -210,6 +291,28 @@ async fn submit_batch(
     response.into()
 }
 
+async fn submit_nft_transfer_batch(
+    data: web::Data<ApiTransactionData>,
+    Json(body): Json<IncomingNFTTransferBatch>,
+) -> ApiResult<SubmitBatchResponse> {
+    let start = Instant::now();
+    let response = data.tx_sender.submit_nft_transfer_batch(body, None).await;
+
+    if let Err(err) = &response {
+        let err_label = match err.cause() {
+            SubmitError::IncorrectTx(err) => err.clone(),
+            SubmitError::TxAdd(err) => err.to_string(),
+            _ => "other".to_string(),
+        };
+        let labels = vec![("stage", "api".to_string()), ("error", err_label)];
+        metrics::increment_counter!("rejected_txs", &labels);
+    }
+
+    let response = response.map_err(Error::from);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "submit_nft_transfer_batch");
+    response.into()
+}
+
 async fn toggle_2fa(
     data: web::Data<ApiTransactionData>,
     Json(toggle_2fa): Json<Toggle2FA>,
@@ -225,6 +328,51 @@ async fn toggle_2fa(
     response.into()
 }
 
+async fn set_withdrawal_timelock(
+    data: web::Data<ApiTransactionData>,
+    Json(request): Json<WithdrawalTimelockRequest>,
+) -> ApiResult<Option<WithdrawalTimelockSettings>> {
+    let start = Instant::now();
+    let response = data
+        .tx_sender
+        .set_withdrawal_timelock(request)
+        .await
+        .map_err(Error::from);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "set_withdrawal_timelock");
+    response.into()
+}
+
+async fn withdrawal_timelock(
+    data: web::Data<ApiTransactionData>,
+    account_id: web::Path<AccountId>,
+) -> ApiResult<AccountWithdrawalTimelock> {
+    let start = Instant::now();
+    let response = data
+        .tx_sender
+        .account_withdrawal_timelock(*account_id)
+        .await
+        .map_err(Error::from);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "withdrawal_timelock");
+    response.into()
+}
+
+async fn cancel_withdrawal(
+    data: web::Data<ApiTransactionData>,
+    Json(request): Json<CancelWithdrawalRequest>,
+) -> ApiResult<PendingWithdrawal> {
+    let start = Instant::now();
+    let response = data
+        .tx_sender
+        .cancel_withdrawal(request)
+        .await
+        .map_err(Error::from);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "cancel_withdrawal");
+    response.into()
+}
+
 async fn get_batch(
     data: web::Data<ApiTransactionData>,
     batch_hash: web::Path<TxHash>,
@@ -243,9 +391,87 @@ pub fn api_scope(tx_sender: TxSender) -> Scope {
         .route("", web::post().to(submit_tx))
         .route("{tx_hash}", web::get().to(tx_status))
         .route("{tx_hash}/data", web::get().to(tx_data))
+        .route("{tx_hash}/wait", web::get().to(wait_tx))
         .route("/batches", web::post().to(submit_batch))
+        .route("/batches/nft", web::post().to(submit_nft_transfer_batch))
         .route("/batches/{batch_hash}", web::get().to(get_batch))
         .route("/toggle2FA", web::post().to(toggle_2fa))
+        .route(
+            "/withdrawalTimelock",
+            web::post().to(set_withdrawal_timelock),
+        )
+        .route(
+            "/withdrawalTimelock/cancel",
+            web::post().to(cancel_withdrawal),
+        )
+        .route(
+            "/withdrawalTimelock/{account_id}",
+            web::get().to(withdrawal_timelock),
+        )
+}
+
+pub fn api_docs() -> Vec<Endpoint> {
+    vec![
+        Endpoint::post::<TxWithSignature, TxHashSerializeWrapper>(
+            "/transactions",
+            "transactions",
+            "Submit the transaction, retries with the same `Idempotency-Key` header return the original hash, \
+            the `Recipient-Name` header makes the transfer rejected unless the name resolves to the recipient",
+        ),
+        Endpoint::get::<Option<Receipt>>(
+            "/transactions/{txHash}",
+            "transactions",
+            "Receipt of the transaction",
+        ),
+        Endpoint::get::<Option<TxData>>(
+            "/transactions/{txHash}/data",
+            "transactions",
+            "Transaction data",
+        ),
+        Endpoint::get::<Option<Receipt>>(
+            "/transactions/{txHash}/wait",
+            "transactions",
+            "Receipt of the transaction, returned once it is committed, finalized or rejected \
+            depending on the `level`, or once the `timeout` in seconds expires, 60 at most",
+        )
+        .with_query(&["level", "timeout"]),
+        Endpoint::post::<IncomingTxBatch, SubmitBatchResponse>(
+            "/transactions/batches",
+            "transactions",
+            "Submit the batch of transactions",
+        ),
+        Endpoint::post::<IncomingNFTTransferBatch, SubmitBatchResponse>(
+            "/transactions/batches/nft",
+            "transactions",
+            "Submit the bundle of NFT transfers to a single recipient as an atomic batch",
+        ),
+        Endpoint::get::<Option<ApiTxBatch>>(
+            "/transactions/batches/{batchHash}",
+            "transactions",
+            "Status of the batch",
+        ),
+        Endpoint::post::<Toggle2FA, Toggle2FAResponse>(
+            "/transactions/toggle2FA",
+            "transactions",
+            "Enable or disable 2FA for the account",
+        ),
+        Endpoint::post::<WithdrawalTimelockRequest, Option<WithdrawalTimelockSettings>>(
+            "/transactions/withdrawalTimelock",
+            "transactions",
+            "Set or remove the withdrawal timelock of the account, withdrawals above its threshold \
+            are held for the given number of blocks and can be cancelled meanwhile",
+        ),
+        Endpoint::post::<CancelWithdrawalRequest, PendingWithdrawal>(
+            "/transactions/withdrawalTimelock/cancel",
+            "transactions",
+            "Cancel the withdrawal held by the timelock of the account",
+        ),
+        Endpoint::get::<AccountWithdrawalTimelock>(
+            "/transactions/withdrawalTimelock/{accountId}",
+            "transactions",
+            "Withdrawal timelock of the account and the withdrawals held by it",
+        ),
+    ]
 }
 
 #[cfg(test)]
@@ -266,7 +492,7 @@ mod tests {
     use std::str::FromStr;
     use tokio::task::JoinHandle;
     use zksync_api_types::v02::{
-        transaction::{L2Receipt, TxHashSerializeWrapper},
+        transaction::{L2Receipt, TxHashSerializeWrapper, TxWaitLevel},
         ApiVersion,
     };
     use zksync_mempool::MempoolTransactionRequest;
@@ -294,6 +520,9 @@ mod tests {
                     MempoolTransactionRequest::NewTxsBatch(_, _, resp) => {
                         resp.send(Ok(())).unwrap_or_default()
                     }
+                    MempoolTransactionRequest::NewPrivateTxsBatch(_, _, _, resp) => {
+                        resp.send(Ok(())).unwrap_or_default()
+                    }
                 }
             }
         });
@@ -352,6 +581,7 @@ mod tests {
                     dummy_fee_ticker(&prices, Some(cache.clone())),
                     &cfg.config.api.common,
                     &cfg.config.api.token_config,
+                    cfg.config.contracts.contract_addr,
                     sender.clone(),
                 ))
             },
@@ -455,9 +685,16 @@ mod tests {
             rollup_block: Some(BlockNumber(1)),
             status: TxInBlockStatus::Finalized,
             fail_reason: None,
+            eth_tx_hash: None,
         });
         assert_eq!(tx_status, expected_tx_status);
 
+        let response = client
+            .wait_tx(tx_hash, Some(TxWaitLevel::Finalized), Some(1))
+            .await?;
+        let tx_status: Receipt = deserialize_response_result(response)?;
+        assert_eq!(tx_status, expected_tx_status);
+
         let response = client.tx_data(tx_hash).await?;
         let tx_data: Option<TxData> = deserialize_response_result(response)?;
         assert_eq!(tx_data.unwrap().tx.tx_hash, tx_hash);
@@ -486,9 +723,17 @@ mod tests {
             rollup_block: None,
             status: TxInBlockStatus::Queued,
             fail_reason: None,
+            eth_tx_hash: None,
         });
         assert_eq!(tx_status, expected_tx_status);
 
+        // The queued transaction is returned as is once the timeout expires.
+        let response = client
+            .wait_tx(pending_tx_hash, Some(TxWaitLevel::Committed), Some(1))
+            .await?;
+        let tx_status: Receipt = deserialize_response_result(response)?;
+        assert_eq!(tx_status, expected_tx_status);
+
         let response = client.tx_data(pending_tx_hash).await?;
         let tx_data: Option<TxData> = deserialize_response_result(response)?;
         assert_eq!(tx_data.unwrap().tx.tx_hash, pending_tx_hash);
@@ -0,0 +1,51 @@
+//! API v0.3.
+//!
+//! Routes are shared with the API v0.2 and are served under the `/api/v0.3` prefix, the only
+//! difference is the response envelope (see `zksync_api_types::v03`). Errors of the request
+//! parsing are reported within the envelope too. OpenAPI specification of the API is served at
+//! `/api/v0.3/spec` and rendered with Swagger UI at `/api/v0.3/docs`.
+
+// External uses
+use actix_web::{web, Scope};
+// Workspace uses
+use zksync_api_types::v02::ApiVersion;
+use zksync_config::ZkSyncConfig;
+
+// Local uses
+use super::{
+    network_status::SharedNetworkStatus,
+    v02::{self, error::ErrorCode, ResponseCaches, SharedData},
+};
+use crate::api_server::tx_sender::TxSender;
+
+pub mod response;
+mod spec;
+
+pub use spec::openapi_spec;
+
+pub(crate) fn api_scope(
+    tx_sender: TxSender,
+    zk_config: &ZkSyncConfig,
+    network_status: SharedNetworkStatus,
+    response_caches: ResponseCaches,
+) -> Scope {
+    let data = SharedData {
+        net: zk_config.chain.eth.network,
+        api_version: ApiVersion::V03,
+    };
+    let scope =
+        web::scope("/api/v0.3")
+            .app_data(web::Data::new(data))
+            .app_data(web::PathConfig::default().error_handler(|err, req| {
+                response::request_error(err, req, ErrorCode::QueryDeserializationError)
+            }))
+            .app_data(web::QueryConfig::default().error_handler(|err, req| {
+                response::request_error(err, req, ErrorCode::QueryDeserializationError)
+            }))
+            .app_data(web::JsonConfig::default().error_handler(|err, req| {
+                response::request_error(err, req, ErrorCode::InvalidParams)
+            }));
+    let scope = spec::register_resources(scope);
+
+    v02::register_scopes(scope, tx_sender, zk_config, network_status, response_caches)
+}
@@ -0,0 +1,246 @@
+//! Envelope of the API v0.3 responses.
+
+// Built-in uses
+use std::fmt::{Debug, Display};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// External uses
+use actix_web::{error::InternalError, http::StatusCode, web::Data, HttpRequest, HttpResponse};
+use chrono::Utc;
+use serde_json::Value;
+
+// Workspace uses
+use zksync_api_types::v02::{pagination::PaginationDetails, ResultStatus};
+use zksync_api_types::v03::{self, Response, REQUEST_ID_HEADER};
+
+// Local uses
+use crate::api_server::rest::{
+    response_signer::ResponseSigner,
+    v02::{
+        error::{Error, ErrorCode},
+        SharedData,
+    },
+};
+
+/// Counter used to generate identifiers of the requests that don't have one.
+static REQUEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Maximum length of the request identifier passed by the client.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Wraps the result of the endpoint into the v0.3 envelope.
+/// The response is signed if the signer is provided.
+pub fn respond(
+    data: &SharedData,
+    req: &HttpRequest,
+    result: Result<Value, Error>,
+    signer: Option<&ResponseSigner>,
+) -> HttpResponse {
+    let request_id = request_id(req);
+    let (status, response) = envelope(data, request_id.clone(), result);
+    let body = serde_json::to_string(&response).expect("Should be correct serializable");
+
+    let mut builder = HttpResponse::build(status);
+    builder.insert_header((REQUEST_ID_HEADER, request_id));
+    if let Some(signer) = signer {
+        for header in signer.signature_headers(&body) {
+            builder.insert_header(header);
+        }
+    }
+    builder.content_type("application/json").body(body)
+}
+
+/// Error handler of the request extractors, so that malformed requests are answered with
+/// the envelope as well.
+pub fn request_error<E>(err: E, req: &HttpRequest, code: ErrorCode) -> actix_web::Error
+where
+    E: Debug + Display + 'static,
+{
+    let data = req
+        .app_data::<Data<SharedData>>()
+        .expect("Wrong app data type");
+    let error = Error {
+        error_type: String::from("invalidRequestError"),
+        code,
+        message: err.to_string(),
+        details: None,
+    };
+    let response = respond(data, req, Err(error), None);
+    InternalError::from_response(err, response).into()
+}
+
+/// Returns the HTTP status corresponding to the error code.
+pub fn status_code(code: &ErrorCode) -> StatusCode {
+    match code {
+        ErrorCode::AccountNotFound
+        | ErrorCode::TransactionNotFound
+        | ErrorCode::TokenNotFound
+        | ErrorCode::EnsNameNotResolved => StatusCode::NOT_FOUND,
+        ErrorCode::TokenZeroPriceError
+        | ErrorCode::InvalidCurrency
+        | ErrorCode::InvalidBlockPosition
+        | ErrorCode::InvalidAccountIdOrAddress
+        | ErrorCode::PaginationLimitTooBig
+        | ErrorCode::QueryDeserializationError
+        | ErrorCode::InvalidNFTTokenId
+        | ErrorCode::TooManyAddresses
+        | ErrorCode::BlockNotFinalized
+        | ErrorCode::AmbiguousTokenSymbol
+        | ErrorCode::AccountCloseDisabled
+        | ErrorCode::InvalidParams
+        | ErrorCode::UnsupportedFastProcessing
+        | ErrorCode::IncorrectTx
+        | ErrorCode::TxAddError
+        | ErrorCode::InappropriateFeeToken
+        | ErrorCode::Toggle2FAError
+        | ErrorCode::AccountLocked => StatusCode::BAD_REQUEST,
+        ErrorCode::StaleTokenPrice => StatusCode::SERVICE_UNAVAILABLE,
+        ErrorCode::CoreApiError
+        | ErrorCode::ExternalApiError
+        | ErrorCode::CommunicationCoreServer => StatusCode::BAD_GATEWAY,
+        ErrorCode::UnreacheableError
+        | ErrorCode::StorageError
+        | ErrorCode::InternalError
+        | ErrorCode::Other => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Returns the identifier of the request passed by the client or generates a new one.
+fn request_id(req: &HttpRequest) -> String {
+    req.headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN)
+        .map(String::from)
+        .unwrap_or_else(|| {
+            format!(
+                "{:x}-{:x}",
+                Utc::now().timestamp_millis(),
+                REQUEST_COUNTER.fetch_add(1, Ordering::Relaxed)
+            )
+        })
+}
+
+fn envelope(
+    data: &SharedData,
+    request_id: String,
+    result: Result<Value, Error>,
+) -> (StatusCode, Response) {
+    let mut response = Response {
+        request_id,
+        api_version: data.api_version,
+        network: data.net,
+        timestamp: Utc::now(),
+        status: ResultStatus::Success,
+        result: None,
+        error: None,
+        pagination: None,
+    };
+
+    match result {
+        Ok(result) => {
+            let (result, pagination) = split_pagination(result);
+            response.result = Some(result);
+            response.pagination = pagination;
+            (StatusCode::OK, response)
+        }
+        Err(err) => {
+            let status = status_code(&err.code);
+            response.status = ResultStatus::Error;
+            response.error = Some(v03::Error {
+                code: err.code as u16,
+                error_type: err.error_type,
+                message: err.message,
+                details: err.details,
+            });
+            (status, response)
+        }
+    }
+}
+
+/// Moves the pagination details of the `Paginated` result to the envelope.
+fn split_pagination(result: Value) -> (Value, Option<PaginationDetails<Value>>) {
+    match result {
+        Value::Object(mut object) if object.len() == 2 && object.contains_key("list") => {
+            match object
+                .get("pagination")
+                .cloned()
+                .map(serde_json::from_value)
+            {
+                Some(Ok(pagination)) => (object.remove("list").unwrap(), Some(pagination)),
+                _ => (Value::Object(object), None),
+            }
+        }
+        result => (result, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use zksync_api_types::v02::{
+        pagination::{Paginated, PaginationDirection},
+        ApiVersion,
+    };
+    use zksync_types::network::Network;
+
+    fn shared_data() -> SharedData {
+        SharedData {
+            net: Network::Localhost,
+            api_version: ApiVersion::V03,
+        }
+    }
+
+    #[test]
+    fn paginated_result() {
+        let paginated = Paginated::new(vec![1, 2], 5u32, 2, PaginationDirection::Older, 10);
+        let (status, response) = envelope(
+            &shared_data(),
+            "id".to_string(),
+            Ok(serde_json::to_value(paginated).unwrap()),
+        );
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response.request_id, "id");
+        assert_eq!(response.result, Some(json!([1, 2])));
+        let pagination = response.pagination.unwrap();
+        assert_eq!(pagination.from, json!(5));
+        assert_eq!(pagination.count, 10);
+        assert!(response.error.is_none());
+    }
+
+    #[test]
+    fn plain_result() {
+        // Objects that only look like paginated ones are left intact.
+        let result = json!({ "list": [1], "pagination": "none" });
+        let (status, response) = envelope(&shared_data(), "id".to_string(), Ok(result.clone()));
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response.result, Some(result));
+        assert!(response.pagination.is_none());
+    }
+
+    #[test]
+    fn error_result() {
+        let error = Error {
+            error_type: "invalidDataError".to_string(),
+            code: ErrorCode::AccountNotFound,
+            message: "Account is not found".to_string(),
+            details: None,
+        };
+        let (status, response) = envelope(&shared_data(), "id".to_string(), Err(error));
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(response.result.is_none());
+        assert_eq!(
+            response.error,
+            Some(v03::Error {
+                code: 204,
+                error_type: "invalidDataError".to_string(),
+                message: "Account is not found".to_string(),
+                details: None,
+            })
+        );
+    }
+}
@@ -0,0 +1,39 @@
+//! OpenAPI specification of the API v0.3 and Swagger UI rendering it.
+
+// External uses
+use actix_web::{web, HttpResponse, Scope};
+use serde_json::Value;
+
+// Local uses
+use crate::api_server::rest::{openapi, v02};
+
+const API_PREFIX: &str = "/api/v0.3";
+
+/// Generates the OpenAPI document of the API v0.3.
+pub fn openapi_spec() -> Value {
+    openapi::openapi_spec(API_PREFIX, "0.3", &v02::api_docs())
+}
+
+async fn spec(data: web::Data<Value>) -> HttpResponse {
+    HttpResponse::Ok().json(data.get_ref())
+}
+
+async fn swagger_ui() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(openapi::swagger_ui(
+            "zkSync API v0.3",
+            &format!("{}/spec", API_PREFIX),
+        ))
+}
+
+/// Adds the `/spec` and `/docs` resources to the API scope.
+pub fn register_resources(scope: Scope) -> Scope {
+    scope
+        .service(
+            web::resource("/spec")
+                .app_data(web::Data::new(openapi_spec()))
+                .route(web::get().to(spec)),
+        )
+        .service(web::resource("/docs").route(web::get().to(swagger_ui)))
+}
@@ -1,9 +1,10 @@
 // External uses
-use jsonrpc_core::ErrorCode;
+use jsonrpc_core::{Error, ErrorCode};
+use serde_json::{json, Value};
 use zksync_types::tx::error::TxAddError;
 // Workspace uses
 // Local uses
-use crate::api_server::tx_sender::SubmitError;
+use crate::{api_server::tx_sender::SubmitError, fee_ticker::PriceError};
 
 #[derive(Debug, Clone, Copy)]
 pub enum RpcErrorCodes {
@@ -11,6 +12,8 @@ pub enum RpcErrorCodes {
     IncorrectTx = 103,
     FeeTooLow = 104,
     InappropriateFeeToken = 105,
+    AmbiguousTokenSymbol = 106,
+    StaleTokenPrice = 107,
 
     MissingEthSignature = 200,
     EIP1271SignatureVerificationFail = 201,
@@ -22,6 +25,7 @@ pub enum RpcErrorCodes {
     OperationsLimitReached = 302,
     UnsupportedFastProcessing = 303,
     Toggle2FA = 304,
+    AccountLocked = 305,
 }
 
 impl From<TxAddError> for RpcErrorCodes {
@@ -41,6 +45,7 @@ impl From<TxAddError> for RpcErrorCodes {
             TxAddError::BatchTooBig => Self::Other,
             TxAddError::BatchWithdrawalsOverload => Self::Other,
             TxAddError::EthSignaturesLimitExceeded => Self::Other,
+            TxAddError::SponsorGasBudgetExceeded => Self::OperationsLimitReached,
         }
     }
 }
@@ -51,62 +56,116 @@ impl From<RpcErrorCodes> for ErrorCode {
     }
 }
 
-impl From<SubmitError> for jsonrpc_core::Error {
+/// Errors of the submission carry the structured details of the rejection in the `data` field,
+/// along with the data reported by the error itself, if any.
+impl From<SubmitError> for Error {
     fn from(inner: SubmitError) -> Self {
-        match inner {
-            SubmitError::AccountCloseDisabled => Self {
-                code: RpcErrorCodes::AccountCloseDisabled.into(),
-                message: "Account close tx is disabled.".to_string(),
-                data: None,
-            },
+        let mut details =
+            serde_json::to_value(inner.details()).expect("Should be correct serializable");
+        let mut error = submit_error(inner);
+        match error.data.take() {
+            Some(Value::Object(data)) => {
+                for (key, value) in data {
+                    details[key.as_str()] = value;
+                }
+            }
+            Some(reason) => details["reason"] = reason,
+            None => {}
+        }
+        error.data = Some(details);
+        error
+    }
+}
+
+fn submit_error(inner: SubmitError) -> Error {
+    match inner {
+        SubmitError::AccountCloseDisabled => Error {
+            code: RpcErrorCodes::AccountCloseDisabled.into(),
+            message: "Account close tx is disabled.".to_string(),
+            data: None,
+        },
 
-            SubmitError::InvalidParams(msg) => Self::invalid_params(msg),
-            SubmitError::UnsupportedFastProcessing => Self {
-                code: RpcErrorCodes::UnsupportedFastProcessing.into(),
-                message: "Fast processing available only for 'withdraw' operation type."
-                    .to_string(),
-                data: None,
-            },
-            SubmitError::IncorrectTx(message) => Self {
-                code: RpcErrorCodes::IncorrectTx.into(),
+        SubmitError::InvalidParams(msg) => Error::invalid_params(msg),
+        SubmitError::UnsupportedFastProcessing => Error {
+            code: RpcErrorCodes::UnsupportedFastProcessing.into(),
+            message: "Fast processing available only for 'withdraw' operation type.".to_string(),
+            data: None,
+        },
+        SubmitError::IncorrectTx(message) => Error {
+            code: RpcErrorCodes::IncorrectTx.into(),
+            message,
+            data: None,
+        },
+        SubmitError::TxAdd(inner) => Error {
+            code: RpcErrorCodes::from(inner).into(),
+            message: inner.to_string(),
+            data: None,
+        },
+        SubmitError::InvalidBatchTx { index, error } => {
+            let message = format!("Transaction {} of the batch is invalid: {}", index, error);
+            Error {
                 message,
-                data: None,
-            },
-            SubmitError::TxAdd(inner) => Self {
-                code: RpcErrorCodes::from(inner).into(),
-                message: inner.to_string(),
-                data: None,
-            },
-            SubmitError::Toggle2FA(inner) => Self {
-                code: RpcErrorCodes::Toggle2FA.into(),
-                message: inner.to_string(),
-                data: None,
-            },
-            SubmitError::InappropriateFeeToken => Self {
-                code: RpcErrorCodes::InappropriateFeeToken.into(),
+                ..submit_error(*error)
+            }
+        }
+        SubmitError::Toggle2FA(inner) => Error {
+            code: RpcErrorCodes::Toggle2FA.into(),
+            message: inner.to_string(),
+            data: None,
+        },
+        SubmitError::InappropriateFeeToken => Error {
+            code: RpcErrorCodes::InappropriateFeeToken.into(),
+            message: inner.to_string(),
+            data: None,
+        },
+        SubmitError::AmbiguousTokenSymbol(ref ambiguous) => {
+            let suggestions: Vec<_> = ambiguous
+                .candidates
+                .iter()
+                .map(|token| {
+                    json!({
+                        "id": token.id,
+                        "address": token.address,
+                        "symbol": token.symbol,
+                        "qualifiedSymbol": token.qualified_symbol(),
+                    })
+                })
+                .collect();
+            Error {
+                code: RpcErrorCodes::AmbiguousTokenSymbol.into(),
                 message: inner.to_string(),
-                data: None,
-            },
-            SubmitError::MempoolCommunication(reason) => Self {
-                code: RpcErrorCodes::Other.into(),
-                message: "Error communicating core server".to_string(),
-                data: Some(reason.into()),
-            },
-            SubmitError::Internal(msg) => Self {
-                code: ErrorCode::InternalError,
-                message: msg.to_string(),
-                data: None,
-            },
-            SubmitError::Other(message) => Self {
-                code: ErrorCode::InternalError,
-                message,
-                data: None,
-            },
-            SubmitError::PriceError(error) => Self {
-                code: ErrorCode::InternalError,
-                message: error.to_string(),
-                data: None,
-            },
+                data: Some(json!({ "suggestions": suggestions })),
+            }
         }
+        SubmitError::AccountLocked(_) => Error {
+            code: RpcErrorCodes::AccountLocked.into(),
+            message: inner.to_string(),
+            data: None,
+        },
+        SubmitError::MempoolCommunication(reason) => Error {
+            code: RpcErrorCodes::Other.into(),
+            message: "Error communicating core server".to_string(),
+            data: Some(reason.into()),
+        },
+        SubmitError::Internal(msg) => Error {
+            code: ErrorCode::InternalError,
+            message: msg.to_string(),
+            data: None,
+        },
+        SubmitError::Other(message) => Error {
+            code: ErrorCode::InternalError,
+            message,
+            data: None,
+        },
+        SubmitError::PriceError(error @ PriceError::StalePrice(_)) => Error {
+            code: RpcErrorCodes::StaleTokenPrice.into(),
+            message: error.to_string(),
+            data: None,
+        },
+        SubmitError::PriceError(error) => Error {
+            code: ErrorCode::InternalError,
+            message: error.to_string(),
+            data: None,
+        },
     }
 }
@@ -3,7 +3,7 @@ use std::{collections::HashMap, iter::FromIterator};
 
 // External uses
 use futures::{FutureExt, StreamExt};
-use jsonrpc_core::Params;
+use jsonrpc_core::{Call, Params, Request};
 use jsonrpc_http_server::{RequestMiddleware, RequestMiddlewareAction};
 
 use super::types::RequestMetadata;
@@ -47,7 +47,7 @@ fn get_call_with_ip_if_needed(
 ) -> jsonrpc_core::MethodCall {
     // Methods, which should have the information about the ip appended to them
     let methods_with_ip: HashMap<&'static str, MethodWithIpDescription> = HashMap::from_iter([
-        ("tx_submit", MethodWithIpDescription::new(1, 4)),
+        ("tx_submit", MethodWithIpDescription::new(1, 5)),
         ("submit_txs_batch", MethodWithIpDescription::new(1, 3)),
         ("get_tx_fee", MethodWithIpDescription::new(3, 4)),
         (
@@ -108,6 +108,24 @@ fn get_call_with_ip_if_needed(
     }
 }
 
+/// Inserts the information about the IP into every call of the request, including the calls of the batch.
+fn get_request_with_ip_if_needed(request: Request, ip: Option<String>) -> Request {
+    let insert_ip = |call: Call, ip: Option<String>| match call {
+        Call::MethodCall(call) => Call::MethodCall(get_call_with_ip_if_needed(call, ip)),
+        call => call,
+    };
+
+    match request {
+        Request::Single(call) => Request::Single(insert_ip(call, ip)),
+        Request::Batch(calls) => Request::Batch(
+            calls
+                .into_iter()
+                .map(|call| insert_ip(call, ip.clone()))
+                .collect(),
+        ),
+    }
+}
+
 /// Given the HTTP body of the JSON-RPC request and the IP of the user, inserts the information about it
 /// in the call (if needed) and returns the bytes of the new body.
 /// If the IP supplied is None, the method makes sure that the user could not pass the IP
@@ -119,12 +137,11 @@ async fn insert_ip_if_needed(body: hyper::Body, ip: Option<String>) -> hyper::Re
         body_bytes.extend(bytes?.into_iter());
     }
 
-    let call: std::result::Result<jsonrpc_core::MethodCall, _> =
-        serde_json::from_slice(&body_bytes);
+    let request: std::result::Result<Request, _> = serde_json::from_slice(&body_bytes);
 
-    if let Ok(call) = call {
-        let new_call = get_call_with_ip_if_needed(call, ip);
-        let new_body_bytes = serde_json::to_vec(&new_call);
+    if let Ok(request) = request {
+        let new_request = get_request_with_ip_if_needed(request, ip);
+        let new_body_bytes = serde_json::to_vec(&new_request);
         if let Ok(s) = new_body_bytes {
             body_bytes = s;
         }
@@ -213,6 +230,7 @@ mod tests {
             Value::String("serialized_transfer".to_owned()),
             Value::String("some_signature".to_owned()),
             Value::Null,
+            Value::Null,
             json!({ "ip": IP }),
         ]);
         test_call_ip_insertion(
@@ -229,12 +247,14 @@ mod tests {
             Value::String("serialized_transfer".to_owned()),
             Value::String("some_signature".to_owned()),
             Value::Bool(true),
+            Value::String("idempotency_key".to_owned()),
             Value::String("override_ip".to_owned()),
         ]);
         let expected_result_params = Params::Array(vec![
             Value::String("serialized_transfer".to_owned()),
             Value::String("some_signature".to_owned()),
             Value::Bool(true),
+            Value::String("idempotency_key".to_owned()),
             json!({ "ip": IP }),
         ]);
         test_call_ip_insertion(
@@ -248,12 +268,14 @@ mod tests {
             Value::String("serialized_transfer".to_owned()),
             Value::String("some_signature".to_owned()),
             Value::Bool(true),
+            Value::String("idempotency_key".to_owned()),
             Value::String("override_ip".to_owned()),
         ]);
         let expected_result_params = Params::Array(vec![
             Value::String("serialized_transfer".to_owned()),
             Value::String("some_signature".to_owned()),
             Value::Bool(true),
+            Value::String("idempotency_key".to_owned()),
         ]);
         test_call_ip_insertion(
             "tx_submit".to_string(),
@@ -320,6 +342,7 @@ mod tests {
                 Value::String("param2".to_owned()),
                 Value::String("param4".to_owned()),
                 Value::String("param5".to_owned()),
+                Value::String("param6".to_owned()),
             ]),
             Some(IP.to_owned()),
         );
@@ -331,4 +354,46 @@ mod tests {
             Some(IP.to_owned()),
         );
     }
+
+    #[test]
+    fn insert_ip_into_batch_test() {
+        let submit = get_method_call(
+            "tx_submit".to_owned(),
+            Params::Array(vec![
+                Value::String("serialized_transfer".to_owned()),
+                Value::String("some_signature".to_owned()),
+                Value::Null,
+                Value::Null,
+                // The user tries to pass the metadata in the batch.
+                json!({ "ip": "1.1.1.1" }),
+            ]),
+        );
+        let other = get_method_call(
+            "some_different_method".to_owned(),
+            Params::Array(vec![Value::String("param".to_owned())]),
+        );
+        let request = Request::Batch(vec![
+            Call::MethodCall(submit),
+            Call::MethodCall(other.clone()),
+        ]);
+
+        let calls = match get_request_with_ip_if_needed(request, Some(IP.to_owned())) {
+            Request::Batch(calls) => calls,
+            request => panic!("Unexpected request: {:?}", request),
+        };
+        match &calls[0] {
+            Call::MethodCall(call) => assert_eq!(
+                call.params,
+                Params::Array(vec![
+                    Value::String("serialized_transfer".to_owned()),
+                    Value::String("some_signature".to_owned()),
+                    Value::Null,
+                    Value::Null,
+                    json!({ "ip": IP }),
+                ])
+            ),
+            call => panic!("Unexpected call: {:?}", call),
+        }
+        assert_eq!(calls[1], Call::MethodCall(other));
+    }
 }
@@ -3,7 +3,7 @@ use std::time::Instant;
 
 // External uses
 use futures::channel::mpsc;
-use jsonrpc_core::{Error, IoHandler, MetaIoHandler, Metadata, Middleware, Result};
+use jsonrpc_core::{Error, MetaIoHandler, Metadata, Middleware, Result};
 use jsonrpc_http_server::ServerBuilder;
 use tokio::task::JoinHandle;
 
@@ -17,7 +17,10 @@ use zksync_storage::{
     ConnectionPool, StorageProcessor,
 };
 use zksync_types::{tx::TxHash, Address, BlockNumber};
-use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
+use zksync_utils::{
+    panic_notify::{spawn_panic_handler, ThreadPanicNotify},
+    shutdown::ShutdownSignal,
+};
 
 // Local uses
 use crate::{signature_checker::VerifySignatureRequest, utils::shared_lru_cache::AsyncLruCache};
@@ -25,6 +28,7 @@ use crate::{signature_checker::VerifySignatureRequest, utils::shared_lru_cache::
 pub mod error;
 mod ip_insert_middleware;
 mod rpc_impl;
+mod rpc_middleware;
 mod rpc_trait;
 pub mod types;
 
@@ -33,6 +37,7 @@ use self::types::*;
 use super::tx_sender::TxSender;
 use crate::fee_ticker::FeeTicker;
 use ip_insert_middleware::IpInsertMiddleWare;
+use rpc_middleware::RpcMiddleware;
 use zksync_mempool::MempoolTransactionRequest;
 
 #[derive(Clone)]
@@ -47,12 +52,14 @@ pub struct RpcApp {
 }
 
 impl RpcApp {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         connection_pool: ConnectionPool,
         sign_verify_request_sender: mpsc::Sender<VerifySignatureRequest>,
         ticker: FeeTicker,
         config: &CommonApiConfig,
         token_config: &TokenConfig,
+        contract_addr: Address,
         confirmations_for_eth_event: u64,
         mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     ) -> Self {
@@ -64,6 +71,7 @@ impl RpcApp {
             ticker,
             config,
             token_config,
+            contract_addr,
             mempool_tx_sender,
         );
 
@@ -78,6 +86,12 @@ impl RpcApp {
         }
     }
 
+    /// Makes the transactions rejected once the shutdown is requested.
+    pub fn with_shutdown_signal(mut self, shutdown: ShutdownSignal) -> Self {
+        self.tx_sender = self.tx_sender.with_shutdown_signal(shutdown);
+        self
+    }
+
     pub fn extend<T: Metadata, S: Middleware<T>>(self, io: &mut MetaIoHandler<T, S>) {
         io.extend_with(self.to_delegate())
     }
@@ -264,24 +278,29 @@ pub fn start_rpc_server(
     config: &JsonRpcConfig,
     common_api_config: &CommonApiConfig,
     token_config: &TokenConfig,
+    contract_addr: Address,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     confirmations_for_eth_event: u64,
+    shutdown: ShutdownSignal,
 ) -> JoinHandle<()> {
     let addr = config.http_bind_addr();
+    let max_batch_concurrency = config.max_batch_concurrency;
     let rpc_app = RpcApp::new(
         connection_pool,
         sign_verify_request_sender,
         ticker,
         common_api_config,
         token_config,
+        contract_addr,
         confirmations_for_eth_event,
         mempool_tx_sender,
-    );
+    )
+    .with_shutdown_signal(shutdown);
 
     let (handler, panic_sender) = spawn_panic_handler();
     std::thread::spawn(move || {
         let _panic_sentinel = ThreadPanicNotify(panic_sender);
-        let mut io = IoHandler::new();
+        let mut io = MetaIoHandler::with_middleware(RpcMiddleware::new(max_batch_concurrency));
         rpc_app.extend(&mut io);
 
         let server = ServerBuilder::new(io)
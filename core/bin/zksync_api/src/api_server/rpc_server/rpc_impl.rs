@@ -142,6 +142,7 @@ impl RpcApp {
         tx: Box<ZkSyncTx>,
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
+        idempotency_key: Option<String>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> Result<TxHash> {
         let start = Instant::now();
@@ -152,11 +153,12 @@ impl RpcApp {
                 *tx,
                 *signature,
                 fast_processing,
+                idempotency_key,
                 extracted_request_metadata,
             )
             .await;
         if let Err(err) = &result {
-            let err_label = match err {
+            let err_label = match err.cause() {
                 SubmitError::IncorrectTx(err) => err.clone(),
                 SubmitError::TxAdd(err) => err.to_string(),
                 _ => "other".to_string(),
@@ -190,7 +192,7 @@ impl RpcApp {
             });
 
         if let Err(err) = &result {
-            let err_label = match err {
+            let err_label = match err.cause() {
                 SubmitError::IncorrectTx(err) => err.clone(),
                 SubmitError::TxAdd(err) => err.to_string(),
                 _ => "other".to_string(),
@@ -257,11 +259,20 @@ impl RpcApp {
             Error::internal_error()
         })?;
 
+        // Tokens sharing the symbol are keyed by the symbol qualified with the ID.
+        let mut symbol_counts = HashMap::new();
+        for token in tokens.values() {
+            *symbol_counts
+                .entry(token.symbol.to_lowercase())
+                .or_insert(0usize) += 1;
+        }
         let result: HashMap<_, _> = tokens
             .drain()
             .map(|(id, token)| {
                 if *id == 0 {
                     ("ETH".to_string(), token)
+                } else if symbol_counts[&token.symbol.to_lowercase()] > 1 {
+                    (token.qualified_symbol(), token)
                 } else {
                     (token.symbol.clone(), token)
                 }
@@ -280,6 +291,7 @@ impl RpcApp {
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> Result<Fee> {
         let start = Instant::now();
+        self.tx_sender.check_token_symbol(&token).await?;
         let token_allowed = self
             .tx_sender
             .ticker
@@ -295,7 +307,7 @@ impl RpcApp {
             .ticker
             .get_fee_from_ticker_in_wei(tx_type.into(), token.clone(), address)
             .await
-            .map_err(SubmitError::Internal)?;
+            .map_err(SubmitError::fee_error)?;
 
         let should_subsidize_cpk = self
             .tx_sender
@@ -333,6 +345,7 @@ impl RpcApp {
             });
         }
 
+        self.tx_sender.check_token_symbol(&token).await?;
         let token_allowed = self
             .tx_sender
             .ticker
@@ -355,7 +368,7 @@ impl RpcApp {
             .ticker
             .get_batch_from_ticker_in_wei(token.clone(), transactions)
             .await
-            .map_err(SubmitError::Internal)?;
+            .map_err(SubmitError::fee_error)?;
 
         let should_subsidize_cpk = self
             .tx_sender
@@ -381,6 +394,7 @@ impl RpcApp {
 
     pub async fn _impl_get_token_price(self, token: TokenLike) -> Result<BigDecimal> {
         let start = Instant::now();
+        self.tx_sender.check_token_symbol(&token).await?;
         let result = self
             .tx_sender
             .ticker
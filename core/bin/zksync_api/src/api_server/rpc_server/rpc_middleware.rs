@@ -0,0 +1,165 @@
+// Built-in uses
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// External uses
+use futures::future::Either;
+use jsonrpc_core::{
+    Call, ErrorCode, FutureOutput, FutureResponse, Metadata, Middleware, Output, Request,
+};
+use tokio::sync::Semaphore;
+
+/// Label used for the calls of the methods that don't exist, so that arbitrary method names
+/// passed by the clients don't end up in the metrics.
+const UNKNOWN_METHOD: &str = "unknown";
+
+/// Metadata of the JSON RPC request.
+#[derive(Debug, Clone, Default)]
+pub struct RequestMeta {
+    /// Limits the number of the calls of the same batch executed at once.
+    batch_limiter: Option<Arc<Semaphore>>,
+}
+
+impl Metadata for RequestMeta {}
+
+/// Middleware of the JSON RPC server.
+///
+/// The calls of the batch requests are executed concurrently by the `jsonrpc_core`, so the
+/// middleware bounds the number of calls of every batch being executed at the same moment.
+/// Besides, it reports the latency and the errors of the calls for every method.
+pub struct RpcMiddleware {
+    max_batch_concurrency: usize,
+}
+
+impl RpcMiddleware {
+    pub fn new(max_batch_concurrency: usize) -> Self {
+        Self {
+            max_batch_concurrency: max_batch_concurrency.max(1),
+        }
+    }
+}
+
+fn report_call(method: &str, elapsed: Duration, output: &Option<Output>) {
+    let error_code = match output {
+        Some(Output::Failure(failure)) => Some(failure.error.code.clone()),
+        _ => None,
+    };
+    let method = if error_code == Some(ErrorCode::MethodNotFound) {
+        UNKNOWN_METHOD.to_string()
+    } else {
+        method.to_string()
+    };
+
+    metrics::histogram!("api.rpc.method", elapsed, "method" => method.clone());
+    if let Some(code) = error_code {
+        metrics::increment_counter!("api.rpc.method_errors", "method" => method, "code" => code.code().to_string());
+    }
+}
+
+impl Middleware<RequestMeta> for RpcMiddleware {
+    type Future = FutureResponse;
+    type CallFuture = FutureOutput;
+
+    fn on_request<F, X>(
+        &self,
+        request: Request,
+        mut meta: RequestMeta,
+        next: F,
+    ) -> Either<Self::Future, X>
+    where
+        F: Fn(Request, RequestMeta) -> X + Send + Sync,
+        X: futures::Future<Output = Option<jsonrpc_core::Response>> + Send + 'static,
+    {
+        if let Request::Batch(calls) = &request {
+            metrics::histogram!("api.rpc.batch_size", calls.len() as f64);
+            if calls.len() > self.max_batch_concurrency {
+                meta.batch_limiter = Some(Arc::new(Semaphore::new(self.max_batch_concurrency)));
+            }
+        }
+        Either::Right(next(request, meta))
+    }
+
+    fn on_call<F, X>(&self, call: Call, meta: RequestMeta, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, RequestMeta) -> X + Send + Sync,
+        X: futures::Future<Output = Option<Output>> + Send + 'static,
+    {
+        let method = match &call {
+            Call::MethodCall(call) => call.method.clone(),
+            Call::Notification(notification) => notification.method.clone(),
+            Call::Invalid { .. } => return Either::Right(next(call, meta)),
+        };
+        let batch_limiter = meta.batch_limiter.clone();
+        // The handler future doesn't do anything until it is polled, so the call is executed
+        // only after the permit is acquired.
+        let call_future = next(call, meta);
+
+        Either::Left(Box::pin(async move {
+            let _permit = match batch_limiter {
+                Some(limiter) => Some(
+                    limiter
+                        .acquire_owned()
+                        .await
+                        .expect("Batch limiter is never closed"),
+                ),
+                None => None,
+            };
+
+            let start = Instant::now();
+            let output = call_future.await;
+            report_call(&method, start.elapsed(), &output);
+            output
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpc_core::{MetaIoHandler, Value};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn batch_concurrency_limit() {
+        const LIMIT: usize = 2;
+
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        let mut io = MetaIoHandler::with_middleware(RpcMiddleware::new(LIMIT));
+        {
+            let running = running.clone();
+            let max_running = max_running.clone();
+            io.add_method_with_meta("sleep", move |_params, _meta: RequestMeta| {
+                let running = running.clone();
+                let max_running = max_running.clone();
+                async move {
+                    let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now_running, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok(Value::Bool(true))
+                }
+            });
+        }
+
+        let batch: Vec<_> = (0..8)
+            .map(|id| {
+                format!(
+                    r#"{{"jsonrpc":"2.0","method":"sleep","params":[],"id":{}}}"#,
+                    id
+                )
+            })
+            .collect();
+        let request = format!("[{}]", batch.join(","));
+        let response = io
+            .handle_request(&request, RequestMeta::default())
+            .await
+            .unwrap();
+
+        let response: Vec<Value> = serde_json::from_str(&response).unwrap();
+        assert_eq!(response.len(), 8);
+        assert!(response.iter().all(|output| output["result"] == true));
+        assert_eq!(max_running.load(Ordering::SeqCst), LIMIT);
+    }
+}
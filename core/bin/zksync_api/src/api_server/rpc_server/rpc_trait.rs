@@ -48,6 +48,7 @@ pub trait Rpc {
         tx: Box<ZkSyncTx>,
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
+        idempotency_key: Option<String>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> BoxFutureResult<TxHash>;
 
@@ -131,9 +132,10 @@ impl Rpc for RpcApp {
         tx: Box<ZkSyncTx>,
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
+        idempotency_key: Option<String>,
         meta: Option<RequestMetadata>,
     ) -> BoxFutureResult<TxHash> {
-        spawn!(self._impl_tx_submit(tx, signature, fast_processing, meta))
+        spawn!(self._impl_tx_submit(tx, signature, fast_processing, idempotency_key, meta))
     }
 
     // Important: the last parameter should have name `meta` and be of type `RequestMetadata`
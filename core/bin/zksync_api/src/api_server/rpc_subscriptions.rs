@@ -11,11 +11,15 @@ use jsonrpc_pubsub::{typed::Subscriber, PubSubHandler, Session, SubscriptionId};
 use jsonrpc_ws_server::RequestContext;
 use tokio::task::JoinHandle;
 // Workspace uses
+use zksync_api_types::v02::account::AccountsDiff;
 use zksync_config::configs::api::{CommonApiConfig, JsonRpcConfig, TokenConfig};
 use zksync_mempool::MempoolTransactionRequest;
 use zksync_storage::ConnectionPool;
-use zksync_types::{tx::TxHash, ActionType, Address};
-use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
+use zksync_types::{tx::TxHash, ActionType, Address, BlockNumber};
+use zksync_utils::{
+    panic_notify::{spawn_panic_handler, ThreadPanicNotify},
+    shutdown::ShutdownSignal,
+};
 // Local uses
 use crate::fee_ticker::FeeTicker;
 use crate::{
@@ -82,6 +86,30 @@ pub trait RpcPubSub {
         meta: Option<Self::Metadata>,
         subscription: SubscriptionId,
     ) -> Result<bool>;
+
+    #[pubsub(
+        subscription = "account_diff",
+        subscribe,
+        name = "account_diff_subscribe",
+        alias("account_diff_sub")
+    )]
+    fn subscribe_accounts_diff(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<AccountsDiff>,
+        addresses: Vec<Address>,
+        from_block: BlockNumber,
+    );
+    #[pubsub(
+        subscription = "account_diff",
+        unsubscribe,
+        name = "account_diff_unsubscribe"
+    )]
+    fn unsubscribe_accounts_diff(
+        &self,
+        meta: Option<Self::Metadata>,
+        subscription: SubscriptionId,
+    ) -> Result<bool>;
 }
 
 impl RpcPubSub for RpcSubApp {
@@ -170,6 +198,37 @@ impl RpcPubSub for RpcSubApp {
             .unwrap_or_default();
         Ok(true)
     }
+
+    fn subscribe_accounts_diff(
+        &self,
+        _meta: Self::Metadata,
+        subscriber: Subscriber<AccountsDiff>,
+        addresses: Vec<Address>,
+        from_block: BlockNumber,
+    ) {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Sub(
+                EventSubscribeRequest::AccountsDiff {
+                    addresses,
+                    from_block,
+                    subscriber,
+                },
+            ))
+            .unwrap_or_default();
+    }
+
+    fn unsubscribe_accounts_diff(
+        &self,
+        _meta: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool> {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Unsub(id))
+            .unwrap_or_default();
+        Ok(true)
+    }
 }
 
 struct RpcSubApp {
@@ -184,10 +243,12 @@ pub fn start_ws_server(
     ticker: FeeTicker,
     common_config: &CommonApiConfig,
     token_config: &TokenConfig,
+    contract_addr: Address,
     config: &JsonRpcConfig,
     miniblock_iteration_interval: Duration,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     confirmations_for_eth_event: u64,
+    shutdown: ShutdownSignal,
 ) -> JoinHandle<()> {
     let addr = config.ws_bind_addr();
 
@@ -207,9 +268,11 @@ pub fn start_ws_server(
         ticker,
         common_config,
         token_config,
+        contract_addr,
         confirmations_for_eth_event,
         mempool_tx_sender,
-    );
+    )
+    .with_shutdown_signal(shutdown);
 
     let (handler, panic_sender) = spawn_panic_handler();
 
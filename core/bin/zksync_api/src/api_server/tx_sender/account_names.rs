@@ -0,0 +1,85 @@
+//! Fees of the account names registration and the transfers to the named recipients.
+
+// External uses
+use num::BigUint;
+
+// Workspace uses
+use zksync_types::{
+    tx::{TxEthSignatureVariant, TxHash},
+    Address, Fee, TokenLike, TxFeeTypes, ZkSyncTx,
+};
+
+// Local uses
+use super::{SubmitError, TxSender};
+
+/// Amount of gas the account name registration fee is equivalent to, it's charged on top
+/// of the fee of the transfer paying for the registration.
+const ACCOUNT_NAME_REGISTRATION_GAS: u64 = 100_000;
+
+impl TxSender {
+    /// Submits the transfer paying for the account name registration.
+    pub async fn submit_account_name_fee(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+    ) -> Result<TxHash, SubmitError> {
+        self.submit_new_tx(
+            tx,
+            signature,
+            None,
+            BigUint::from(ACCOUNT_NAME_REGISTRATION_GAS),
+            None,
+        )
+        .await
+    }
+
+    /// Returns the fee of the transfer paying for the account name registration.
+    pub async fn account_name_fee(
+        &self,
+        token: TokenLike,
+        address: Address,
+    ) -> Result<Fee, SubmitError> {
+        let fee = self
+            .ticker
+            .get_fee_with_extra_gas(
+                TxFeeTypes::Transfer,
+                token,
+                address,
+                BigUint::from(ACCOUNT_NAME_REGISTRATION_GAS),
+            )
+            .await
+            .map_err(SubmitError::fee_error)?;
+        Ok(fee.normal_fee)
+    }
+
+    /// Checks that the name resolves to the recipient of the transfer.
+    pub async fn check_recipient_name(&self, tx: &ZkSyncTx, name: &str) -> Result<(), SubmitError> {
+        let recipient = match tx {
+            ZkSyncTx::Transfer(transfer) => transfer.to,
+            _ => {
+                return Err(SubmitError::invalid_params(
+                    "recipient name can be specified only for transfers",
+                ))
+            }
+        };
+        let address = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .account_names_schema()
+            .resolve_account_name(name)
+            .await
+            .map_err(SubmitError::internal)?;
+
+        match address {
+            Some(address) if address == recipient => Ok(()),
+            Some(_) => Err(SubmitError::invalid_params(
+                "recipient name doesn't match the recipient address",
+            )),
+            None => Err(SubmitError::invalid_params(
+                "recipient name is not registered",
+            )),
+        }
+    }
+}
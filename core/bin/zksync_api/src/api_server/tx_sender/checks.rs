@@ -0,0 +1,247 @@
+//! Checks of the transactions made before they are sent to the mempool, on top of the ones
+//! made by the mempool and the state keeper.
+
+// Built-in uses
+use std::collections::HashSet;
+
+// External uses
+use num::{BigUint, Zero};
+
+// Workspace uses
+use zksync_api_types::TxWithSignature;
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_types::{fee_sponsor::sponsorship_key, tx::error::TxAddError, TokenLike, ZkSyncTx};
+
+// Local uses
+use super::{AmbiguousTokenSymbol, SubmitError, TxSender};
+
+/// Rejection reason of the transactions submitted to the read-only node.
+pub(super) const READ_ONLY_NODE: &str =
+    "Node is read-only, transactions are accepted by the main nodes";
+/// Rejection reason of the transactions submitted while the server is shutting down.
+pub(super) const SHUTTING_DOWN: &str = "Server is shutting down, transactions are not accepted";
+
+impl TxSender {
+    pub(super) fn check_writable(&self) -> Result<(), SubmitError> {
+        if self.read_only {
+            return Err(SubmitError::Other(READ_ONLY_NODE.to_string()));
+        }
+        if self.shutdown.is_requested() {
+            return Err(SubmitError::Other(SHUTTING_DOWN.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Checks whether the transaction is a zero-fee transfer of the fee sponsor.
+    /// Gas budget of the sponsor is charged by the mempool.
+    pub(super) async fn is_sponsored(&self, tx: &ZkSyncTx) -> Result<bool, SubmitError> {
+        let (address, token) = match sponsorship_key(tx) {
+            Some(key) => key,
+            None => return Ok(false),
+        };
+        let sponsor = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .fee_sponsors_schema()
+            .load_sponsor(address, token)
+            .await
+            .map_err(SubmitError::internal)?;
+        Ok(sponsor.is_some())
+    }
+
+    /// Rejects the symbol-only token queries matching several tokens, suggesting the
+    /// unambiguous queries instead.
+    pub async fn check_token_symbol(&self, token: &TokenLike) -> Result<(), SubmitError> {
+        if !matches!(token, TokenLike::Symbol(_))
+            || self.tokens.try_get_token_from_cache(token).await.is_some()
+        {
+            return Ok(());
+        }
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let candidates = self
+            .tokens
+            .symbol_collisions(&mut storage, token)
+            .await
+            .map_err(SubmitError::internal)?;
+        if candidates.is_empty() {
+            return Ok(());
+        }
+        Err(AmbiguousTokenSymbol {
+            symbol: token.to_string(),
+            candidates,
+        }
+        .into())
+    }
+
+    /// Checks the withdrawals delivered as native ETH, see `WethUnwrap`.
+    pub(super) async fn check_weth_unwrap(&self, tx: &ZkSyncTx) -> Result<(), SubmitError> {
+        if let ZkSyncTx::Withdraw(withdraw) = tx {
+            if self.weth_unwrap.applies_to(withdraw) {
+                let token = self.token_info_from_id(withdraw.token).await?;
+                self.weth_unwrap.check(withdraw, token.address)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects the transactions of the accounts frozen by the operator, which would be rejected
+    /// by the state keeper anyway. Exits of the frozen accounts are never blocked.
+    pub(super) async fn check_account_freeze(&self, tx: &ZkSyncTx) -> Result<(), SubmitError> {
+        let accounts = match tx {
+            ZkSyncTx::Transfer(tx) => vec![tx.account_id],
+            ZkSyncTx::Swap(tx) => vec![tx.orders.0.account_id, tx.orders.1.account_id],
+            _ => return Ok(()),
+        };
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        for account_id in accounts {
+            let freeze = storage
+                .account_freezes_schema()
+                .active_freeze(account_id)
+                .await
+                .map_err(SubmitError::internal)?;
+            if let Some(freeze) = freeze {
+                return Err(SubmitError::AccountLocked(freeze.reason));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks that the bundle consists of the zero-fee transfers of distinct NFTs between the same
+/// accounts and the fee transfer paid by the sender in a fungible token.
+pub(super) fn check_nft_transfer_batch(
+    transfers: &[TxWithSignature],
+    fee: &TxWithSignature,
+) -> Result<(), SubmitError> {
+    let fee = match &fee.tx {
+        ZkSyncTx::Transfer(fee) => fee,
+        _ => {
+            return Err(SubmitError::invalid_params(
+                "fee of the NFT bundle has to be paid with a transfer",
+            ))
+        }
+    };
+    if fee.token.0 >= MIN_NFT_TOKEN_ID || !fee.amount.is_zero() {
+        return Err(SubmitError::invalid_params(
+            "fee transfer has to be of zero amount in a fungible token",
+        ));
+    }
+    if transfers.is_empty() {
+        return Err(SubmitError::TxAdd(TxAddError::EmptyBatch));
+    }
+
+    let mut recipient = None;
+    let mut tokens = HashSet::with_capacity(transfers.len());
+    for tx in transfers {
+        let transfer = match &tx.tx {
+            ZkSyncTx::Transfer(transfer) => transfer,
+            _ => {
+                return Err(SubmitError::invalid_params(
+                    "NFT bundle can contain only transfers",
+                ))
+            }
+        };
+        if transfer.token.0 < MIN_NFT_TOKEN_ID || transfer.amount != BigUint::from(1u32) {
+            return Err(SubmitError::invalid_params(
+                "every transfer of the bundle has to move a single NFT",
+            ));
+        }
+        if !transfer.fee.is_zero() {
+            return Err(SubmitError::invalid_params(
+                "fee has to be paid by the fee transfer only",
+            ));
+        }
+        if transfer.account_id != fee.account_id || transfer.from != fee.from {
+            return Err(SubmitError::invalid_params(
+                "all transfers of the bundle have to be sent by the fee payer",
+            ));
+        }
+        if *recipient.get_or_insert(transfer.to) != transfer.to {
+            return Err(SubmitError::invalid_params(
+                "all NFTs of the bundle have to be sent to the same recipient",
+            ));
+        }
+        if !tokens.insert(transfer.token) {
+            return Err(SubmitError::invalid_params(format!(
+                "NFT {} is transferred more than once",
+                transfer.token
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{
+        tx::{Transfer, TxEthSignatureVariant},
+        AccountId, Address, Nonce, TokenId,
+    };
+
+    fn transfer(to: Address, token: u32, amount: u32, fee: u32) -> TxWithSignature {
+        let tx = Transfer::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            to,
+            TokenId(token),
+            BigUint::from(amount),
+            BigUint::from(fee),
+            Nonce(0),
+            Default::default(),
+            None,
+        );
+        TxWithSignature {
+            tx: ZkSyncTx::Transfer(Box::new(tx)),
+            signature: TxEthSignatureVariant::Single(None),
+        }
+    }
+
+    #[test]
+    fn test_nft_transfer_batch_check() {
+        let recipient = Address::repeat_byte(2);
+        let nft = MIN_NFT_TOKEN_ID;
+        let fee = transfer(Address::repeat_byte(3), 0, 0, 100);
+
+        let transfers = vec![
+            transfer(recipient, nft, 1, 0),
+            transfer(recipient, nft + 1, 1, 0),
+        ];
+        assert!(check_nft_transfer_batch(&transfers, &fee).is_ok());
+
+        // Empty bundle.
+        assert!(check_nft_transfer_batch(&[], &fee).is_err());
+        // Fungible token transfer.
+        let transfers = vec![transfer(recipient, 0, 1, 0)];
+        assert!(check_nft_transfer_batch(&transfers, &fee).is_err());
+        // Fee paid by the NFT transfer.
+        let transfers = vec![transfer(recipient, nft, 1, 10)];
+        assert!(check_nft_transfer_batch(&transfers, &fee).is_err());
+        // Different recipients.
+        let transfers = vec![
+            transfer(recipient, nft, 1, 0),
+            transfer(Address::repeat_byte(4), nft + 1, 1, 0),
+        ];
+        assert!(check_nft_transfer_batch(&transfers, &fee).is_err());
+        // The same NFT twice.
+        let transfers = vec![
+            transfer(recipient, nft, 1, 0),
+            transfer(recipient, nft, 1, 0),
+        ];
+        assert!(check_nft_transfer_batch(&transfers, &fee).is_err());
+        // Fee paid in NFT.
+        let transfers = vec![transfer(recipient, nft, 1, 0)];
+        let nft_fee = transfer(Address::repeat_byte(3), nft + 1, 0, 1);
+        assert!(check_nft_transfer_batch(&transfers, &nft_fee).is_err());
+    }
+}
@@ -0,0 +1,352 @@
+//! Errors of the transactions submission.
+
+// Built-in uses
+use std::fmt::Display;
+
+// External uses
+use thiserror::Error;
+
+// Workspace uses
+use zksync_api_types::submit_error::{SubmitErrorCode, SubmitErrorDetails};
+use zksync_types::{
+    tx::error::{TxAddError, WRONG_SIGNATURE, WRONG_TOKEN, WRONG_TOKEN_FOR_PAYING_FEE},
+    Token,
+};
+
+// Local uses
+use super::checks::{READ_ONLY_NODE, SHUTTING_DOWN};
+use crate::{fee_ticker::PriceError, tx_error::Toggle2FAError};
+
+#[derive(Debug, Error)]
+pub enum SubmitError {
+    #[error("Account close tx is disabled.")]
+    AccountCloseDisabled,
+    #[error("Invalid params: {0}.")]
+    InvalidParams(String),
+    #[error("Fast processing available only for 'withdraw' operation type.")]
+    UnsupportedFastProcessing,
+    #[error("Incorrect transaction: {0}.")]
+    IncorrectTx(String),
+    #[error("Transaction adding error: {0}.")]
+    TxAdd(#[from] TxAddError),
+    #[error("Transaction {index} of the batch is invalid: {error}")]
+    InvalidBatchTx {
+        index: usize,
+        error: Box<SubmitError>,
+    },
+    #[error("Chosen token is not suitable for paying fees.")]
+    InappropriateFeeToken,
+    #[error("Account is frozen by the operator: {0}.")]
+    AccountLocked(String),
+    #[error("{0}.")]
+    AmbiguousTokenSymbol(#[from] AmbiguousTokenSymbol),
+    // Not all TxAddErrors would apply to Toggle2FA, but
+    // it is helpful to re-use IncorrectEthSignature and DbError
+    #[error("Failed to toggle 2FA: {0}.")]
+    Toggle2FA(#[from] Toggle2FAError),
+
+    #[error("Communication error with the mempool: {0}.")]
+    MempoolCommunication(String),
+    #[error("Price error {0}")]
+    PriceError(#[from] PriceError),
+    #[error("Internal error.")]
+    Internal(#[from] anyhow::Error),
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Symbol-only query of the token matching several tokens.
+#[derive(Debug)]
+pub struct AmbiguousTokenSymbol {
+    pub symbol: String,
+    pub candidates: Vec<Token>,
+}
+
+impl Display for AmbiguousTokenSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let suggestions: Vec<_> = self
+            .candidates
+            .iter()
+            .map(|token| {
+                format!(
+                    "{} (ID {}, address {:#x})",
+                    token.qualified_symbol(),
+                    token.id,
+                    token.address
+                )
+            })
+            .collect();
+        write!(
+            f,
+            "Symbol {} is shared by several tokens, use one of: {}",
+            self.symbol,
+            suggestions.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for AmbiguousTokenSymbol {}
+
+impl SubmitError {
+    pub fn internal(inner: impl Into<anyhow::Error>) -> Self {
+        Self::Internal(inner.into())
+    }
+
+    /// Converts the error of the fee calculation, keeping the price errors distinguishable.
+    pub fn fee_error(err: anyhow::Error) -> Self {
+        match err.downcast::<PriceError>() {
+            Ok(err) => Self::PriceError(err),
+            Err(err) => Self::Internal(err),
+        }
+    }
+
+    pub fn other(msg: impl Display) -> Self {
+        Self::Other(msg.to_string())
+    }
+
+    pub fn mempool_communication(msg: impl Display) -> Self {
+        Self::MempoolCommunication(msg.to_string())
+    }
+
+    pub fn invalid_params(msg: impl Display) -> Self {
+        Self::InvalidParams(msg.to_string())
+    }
+
+    pub fn invalid_batch_tx(index: usize, error: SubmitError) -> Self {
+        Self::InvalidBatchTx {
+            index,
+            error: Box::new(error),
+        }
+    }
+
+    /// Returns the error of the transaction itself, unwrapping the batch context.
+    pub fn cause(&self) -> &SubmitError {
+        match self {
+            Self::InvalidBatchTx { error, .. } => error.cause(),
+            err => err,
+        }
+    }
+
+    /// Returns the stable code of the rejection reported to the clients.
+    pub fn error_code(&self) -> SubmitErrorCode {
+        match self.cause() {
+            Self::AccountCloseDisabled | Self::UnsupportedFastProcessing => {
+                SubmitErrorCode::UnsupportedTx
+            }
+            Self::InvalidParams(_) | Self::IncorrectTx(_) | Self::Toggle2FA(_) => {
+                SubmitErrorCode::InvalidTx
+            }
+            Self::TxAdd(err) => tx_add_error_code(err),
+            Self::InappropriateFeeToken | Self::PriceError(PriceError::TokenNotFound(_)) => {
+                SubmitErrorCode::TokenDisabled
+            }
+            Self::AmbiguousTokenSymbol(_) => SubmitErrorCode::InvalidTx,
+            Self::AccountLocked(_) => SubmitErrorCode::AccountLocked,
+            Self::MempoolCommunication(_) | Self::PriceError(_) => SubmitErrorCode::Unavailable,
+            Self::Other(msg) if msg == READ_ONLY_NODE || msg == SHUTTING_DOWN => {
+                SubmitErrorCode::Unavailable
+            }
+            Self::Internal(_) | Self::Other(_) | Self::InvalidBatchTx { .. } => {
+                SubmitErrorCode::Internal
+            }
+        }
+    }
+
+    /// Returns the structured details of the rejection, enclosed into the error responses.
+    pub fn details(&self) -> SubmitErrorDetails {
+        let index = match self {
+            Self::InvalidBatchTx { index, .. } => Some(*index),
+            _ => None,
+        };
+        SubmitErrorDetails::new(self.error_code(), index)
+    }
+}
+
+impl SubmitError {
+    pub fn internal(inner: impl Into<anyhow::Error>) -> Self {
+        Self::Internal(inner.into())
+    }
+
+    /// Converts the error of the fee calculation, keeping the price errors distinguishable.
+    pub fn fee_error(err: anyhow::Error) -> Self {
+        match err.downcast::<PriceError>() {
+            Ok(err) => Self::PriceError(err),
+            Err(err) => Self::Internal(err),
+        }
+    }
+
+    pub fn other(msg: impl Display) -> Self {
+        Self::Other(msg.to_string())
+    }
+
+    pub fn mempool_communication(msg: impl Display) -> Self {
+        Self::MempoolCommunication(msg.to_string())
+    }
+
+    pub fn invalid_params(msg: impl Display) -> Self {
+        Self::InvalidParams(msg.to_string())
+    }
+
+    pub fn invalid_batch_tx(index: usize, error: SubmitError) -> Self {
+        Self::InvalidBatchTx {
+            index,
+            error: Box::new(error),
+        }
+    }
+
+    /// Returns the error of the transaction itself, unwrapping the batch context.
+    pub fn cause(&self) -> &SubmitError {
+        match self {
+            Self::InvalidBatchTx { error, .. } => error.cause(),
+            err => err,
+        }
+    }
+
+    /// Returns the stable code of the rejection reported to the clients.
+    pub fn error_code(&self) -> SubmitErrorCode {
+        match self.cause() {
+            Self::AccountCloseDisabled | Self::UnsupportedFastProcessing => {
+                SubmitErrorCode::UnsupportedTx
+            }
+            Self::InvalidParams(_) | Self::IncorrectTx(_) | Self::Toggle2FA(_) => {
+                SubmitErrorCode::InvalidTx
+            }
+            Self::TxAdd(err) => tx_add_error_code(err),
+            Self::InappropriateFeeToken | Self::PriceError(PriceError::TokenNotFound(_)) => {
+                SubmitErrorCode::TokenDisabled
+            }
+            Self::AmbiguousTokenSymbol(_) => SubmitErrorCode::InvalidTx,
+            Self::AccountLocked(_) => SubmitErrorCode::AccountLocked,
+            Self::MempoolCommunication(_) | Self::PriceError(_) => SubmitErrorCode::Unavailable,
+            Self::Other(msg) if msg == READ_ONLY_NODE || msg == SHUTTING_DOWN => {
+                SubmitErrorCode::Unavailable
+            }
+            Self::Internal(_) | Self::Other(_) | Self::InvalidBatchTx { .. } => {
+                SubmitErrorCode::Internal
+            }
+        }
+    }
+
+    /// Returns the structured details of the rejection, enclosed into the error responses.
+    pub fn details(&self) -> SubmitErrorDetails {
+        let index = match self {
+            Self::InvalidBatchTx { index, .. } => Some(*index),
+            _ => None,
+        };
+        SubmitErrorDetails::new(self.error_code(), index)
+    }
+}
+
+fn tx_add_error_code(err: &TxAddError) -> SubmitErrorCode {
+    match err {
+        TxAddError::NonceMismatch => SubmitErrorCode::WrongNonce,
+        TxAddError::TxFeeTooLow | TxAddError::TxBatchFeeTooLow => SubmitErrorCode::InsufficientFee,
+        TxAddError::MissingEthSignature
+        | TxAddError::EIP1271SignatureVerificationFail
+        | TxAddError::IncorrectEthSignature
+        | TxAddError::ChangePkNotAuthorized => SubmitErrorCode::InvalidSignature,
+        // Every transaction type reports these checks with the same messages.
+        TxAddError::IncorrectTx(err) => match err.to_string().as_str() {
+            WRONG_SIGNATURE => SubmitErrorCode::InvalidSignature,
+            WRONG_TOKEN | WRONG_TOKEN_FOR_PAYING_FEE => SubmitErrorCode::TokenDisabled,
+            _ => SubmitErrorCode::InvalidTx,
+        },
+        TxAddError::EmptyBatch => SubmitErrorCode::InvalidTx,
+        TxAddError::BatchTooBig
+        | TxAddError::BatchWithdrawalsOverload
+        | TxAddError::EthSignaturesLimitExceeded
+        | TxAddError::SponsorGasBudgetExceeded => SubmitErrorCode::LimitExceeded,
+        TxAddError::DbError => SubmitErrorCode::Unavailable,
+        TxAddError::Other => SubmitErrorCode::Internal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rpc_server::error::RpcErrorCodes;
+    use zksync_types::{tx::Transfer, AccountId, Address, Nonce, TokenId};
+
+    #[test]
+    fn invalid_batch_tx_error() {
+        let err = SubmitError::invalid_batch_tx(3, TxAddError::NonceMismatch.into());
+        assert!(matches!(
+            err.cause(),
+            SubmitError::TxAdd(TxAddError::NonceMismatch)
+        ));
+
+        let rpc_error = jsonrpc_core::Error::from(err);
+        assert_eq!(rpc_error.code, RpcErrorCodes::NonceMismatch.into());
+        assert_eq!(
+            rpc_error.data,
+            Some(serde_json::json!({ "code": 1002, "kind": "wrongNonce", "index": 3 }))
+        );
+    }
+
+    #[test]
+    fn submit_error_codes() {
+        let incorrect_transfer = |to: Address, token: u32| -> SubmitError {
+            let mut tx = Transfer::new(
+                AccountId(1),
+                Address::repeat_byte(1),
+                to,
+                TokenId(token),
+                1u32.into(),
+                0u32.into(),
+                Nonce(0),
+                Default::default(),
+                None,
+            );
+            TxAddError::IncorrectTx(tx.check_correctness().unwrap_err().into()).into()
+        };
+
+        let cases = vec![
+            (
+                SubmitError::TxAdd(TxAddError::TxBatchFeeTooLow),
+                SubmitErrorCode::InsufficientFee,
+            ),
+            (
+                SubmitError::TxAdd(TxAddError::IncorrectEthSignature),
+                SubmitErrorCode::InvalidSignature,
+            ),
+            (
+                incorrect_transfer(Address::repeat_byte(2), 0),
+                SubmitErrorCode::InvalidSignature,
+            ),
+            (
+                incorrect_transfer(Address::repeat_byte(2), u32::MAX),
+                SubmitErrorCode::TokenDisabled,
+            ),
+            (
+                incorrect_transfer(Address::zero(), 0),
+                SubmitErrorCode::InvalidTx,
+            ),
+            (
+                SubmitError::InappropriateFeeToken,
+                SubmitErrorCode::TokenDisabled,
+            ),
+            (
+                SubmitError::AccountLocked("compliance hold".to_string()),
+                SubmitErrorCode::AccountLocked,
+            ),
+            (
+                SubmitError::Other(READ_ONLY_NODE.to_string()),
+                SubmitErrorCode::Unavailable,
+            ),
+            (
+                SubmitError::Other(SHUTTING_DOWN.to_string()),
+                SubmitErrorCode::Unavailable,
+            ),
+        ];
+        for (err, code) in cases {
+            assert_eq!(err.error_code(), code, "{}", err);
+        }
+
+        // The reason of the error is kept along with the details.
+        let rpc_error = jsonrpc_core::Error::from(SubmitError::mempool_communication("closed"));
+        assert_eq!(
+            rpc_error.data,
+            Some(serde_json::json!({ "code": 1009, "kind": "unavailable", "reason": "closed" }))
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Idempotent submission of the transactions, so the clients can safely retry it.
+
+// External uses
+use num::{BigUint, Zero};
+
+// Workspace uses
+use zksync_types::{
+    tx::{TxEthSignatureVariant, TxHash},
+    AccountId, ZkSyncTx,
+};
+
+// Local uses
+use super::{SubmitError, TxSender};
+use crate::api_server::rpc_server::types::RequestMetadata;
+
+/// Maximum length of the idempotency key passed by the client.
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 128;
+
+impl TxSender {
+    /// Submits the transaction. If the account has already submitted a transaction with the same
+    /// idempotency key, the hash of that transaction is returned instead, so the clients can safely
+    /// retry the submission. Reusing the key for another transaction is an error.
+    pub async fn submit_tx_with_idempotency_key(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        idempotency_key: Option<String>,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<TxHash, SubmitError> {
+        let idempotency_key = match idempotency_key {
+            Some(idempotency_key) => idempotency_key,
+            None => {
+                return self
+                    .submit_new_tx(
+                        tx,
+                        signature,
+                        extracted_request_metadata,
+                        BigUint::zero(),
+                        None,
+                    )
+                    .await
+            }
+        };
+        if idempotency_key.is_empty() || idempotency_key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+            return Err(SubmitError::invalid_params(format!(
+                "idempotency key must be from 1 to {} characters long",
+                MAX_IDEMPOTENCY_KEY_LEN
+            )));
+        }
+        let account_id = tx
+            .account_id()
+            .map_err(|_| SubmitError::AccountCloseDisabled)?;
+        let tx_hash = tx.hash();
+
+        if let Some(tx_hash) = self
+            .load_idempotent_tx(account_id, &idempotency_key, tx_hash)
+            .await?
+        {
+            metrics::increment_counter!("tx_sender.submit_tx.idempotent_retry");
+            return Ok(tx_hash);
+        }
+
+        match self
+            .submit_new_tx(
+                tx,
+                signature,
+                extracted_request_metadata,
+                BigUint::zero(),
+                None,
+            )
+            .await
+        {
+            Ok(tx_hash) => {
+                // The transaction is already sent to the mempool, so the error is not returned to the user.
+                if let Err(err) = self
+                    .store_idempotency_key(account_id, &idempotency_key, tx_hash)
+                    .await
+                {
+                    vlog::warn!("Failed to store the idempotency key: {}", err);
+                }
+                Ok(tx_hash)
+            }
+            Err(err) => {
+                // The concurrent retry with the same key could have been accepted meanwhile.
+                match self
+                    .load_idempotent_tx(account_id, &idempotency_key, tx_hash)
+                    .await?
+                {
+                    Some(tx_hash) => Ok(tx_hash),
+                    None => Err(err),
+                }
+            }
+        }
+    }
+
+    /// Returns the hash of the transaction submitted with the idempotency key, if it matches the hash
+    /// of the transaction being submitted.
+    async fn load_idempotent_tx(
+        &self,
+        account_id: AccountId,
+        idempotency_key: &str,
+        tx_hash: TxHash,
+    ) -> Result<Option<TxHash>, SubmitError> {
+        let stored_hash = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .chain()
+            .mempool_schema()
+            .get_tx_by_idempotency_key(account_id, idempotency_key)
+            .await
+            .map_err(SubmitError::internal)?;
+
+        match stored_hash {
+            Some(stored_hash) if stored_hash != tx_hash => Err(SubmitError::invalid_params(
+                "idempotency key is already used for another transaction",
+            )),
+            stored_hash => Ok(stored_hash),
+        }
+    }
+
+    async fn store_idempotency_key(
+        &self,
+        account_id: AccountId,
+        idempotency_key: &str,
+        tx_hash: TxHash,
+    ) -> Result<(), anyhow::Error> {
+        self.pool
+            .access_storage()
+            .await?
+            .chain()
+            .mempool_schema()
+            .store_idempotency_key(account_id, idempotency_key, tx_hash)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,16 +1,18 @@
 //! Helper module to submit transactions into the zkSync Network.
+//!
+//! The core submission flow lives here, while the checks and the features built on top of it
+//! are implemented in the submodules.
 
 // Built-in uses
 use std::iter::FromIterator;
 use std::{
     collections::{HashMap, HashSet},
-    fmt::Display,
     str::FromStr,
 };
 
 // External uses
 use bigdecimal::BigDecimal;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use futures::{
     channel::{mpsc, oneshot},
     prelude::*,
@@ -18,30 +20,41 @@ use futures::{
 use itertools::izip;
 use num::rational::Ratio;
 use num::{bigint::ToBigInt, BigUint, Zero};
-use thiserror::Error;
 
 // Workspace uses
 use zksync_api_types::{
-    v02::transaction::{SubmitBatchResponse, Toggle2FA, Toggle2FAResponse, TxHashSerializeWrapper},
+    v02::transaction::{
+        IncomingNFTTransferBatch, SubmitBatchResponse, Toggle2FA, Toggle2FAResponse,
+        TxHashSerializeWrapper,
+    },
     TxWithSignature,
 };
+use zksync_shared_cache::SharedCache;
 use zksync_storage::misc::records::Subsidy;
 use zksync_storage::{chain::account::records::EthAccountType, ConnectionPool};
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{
     tx::{
-        EthBatchSignData, EthBatchSignatures, EthSignData, Order, SignedZkSyncTx, TxEthSignature,
-        TxEthSignatureVariant, TxHash,
+        Eip712Domain, EthBatchSignData, EthBatchSignatures, EthSignData, Order, SignedZkSyncTx,
+        TxEthSignature, TxEthSignatureVariant, TxHash,
     },
     AccountId, Address, PubKeyHash, Token, TokenId, TokenLike, TxFeeTypes, ZkSyncTx, H160,
 };
 use zksync_utils::{
     big_decimal_to_ratio, biguint_to_big_decimal, ratio_to_scaled_u64, scaled_big_decimal_to_ratio,
+    shutdown::ShutdownSignal,
 };
 
 // Local uses
+use self::checks::check_nft_transfer_batch;
 use crate::{
-    api_server::forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
+    api_server::{
+        ens_resolver::EnsResolver,
+        forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
+        weth_unwrap::{WethUnwrap, WETH_UNWRAP_GAS},
+        withdrawal_screening::WithdrawalScreening,
+    },
+    eth_checker::EthereumChecker,
     fee_ticker::{ResponseBatchFee, ResponseFee, TokenPriceRequestType},
     signature_checker::{
         BatchRequest, OrderRequest, RequestData, Toggle2FARequest, TxRequest, VerifiedTx,
@@ -55,9 +68,26 @@ use zksync_mempool::MempoolTransactionRequest;
 use zksync_types::tx::error::TxAddError;
 
 use super::rpc_server::types::RequestMetadata;
-use crate::fee_ticker::{FeeTicker, PriceError};
+use crate::fee_ticker::FeeTicker;
+
+pub use self::error::{AmbiguousTokenSymbol, SubmitError};
+
+mod account_names;
+mod checks;
+mod error;
+mod idempotency;
+mod timelocks;
+mod travel_rule;
 
 const VALIDNESS_INTERVAL_MINUTES: i64 = 40;
+/// Rejection reason of the `ChangePubKey` transactions changing the 2FA policy with the typed data signature.
+const NO_2FA_TYPED_DATA: &str = "2FA policy can only be changed with the text message signature";
+/// Rejection reason of the transactions signed with the typed data they don't have.
+const TYPED_DATA_NOT_SUPPORTED: &str =
+    "Typed data signature is not supported by this transaction type";
+/// Rejection reason of the batches with the withdrawals delayed by the timelock of the account.
+const TIMELOCKED_WITHDRAWAL_IN_BATCH: &str =
+    "Withdrawals delayed by the timelock can't be submitted in batches";
 
 #[derive(Clone)]
 pub struct TxSender {
@@ -80,53 +110,22 @@ pub struct TxSender {
     pub current_subsidy_type: String,
     pub max_subsidy_usd: Ratio<BigUint>,
     pub subsidized_ips: HashSet<String>,
-}
-
-#[derive(Debug, Error)]
-pub enum SubmitError {
-    #[error("Account close tx is disabled.")]
-    AccountCloseDisabled,
-    #[error("Invalid params: {0}.")]
-    InvalidParams(String),
-    #[error("Fast processing available only for 'withdraw' operation type.")]
-    UnsupportedFastProcessing,
-    #[error("Incorrect transaction: {0}.")]
-    IncorrectTx(String),
-    #[error("Transaction adding error: {0}.")]
-    TxAdd(#[from] TxAddError),
-    #[error("Chosen token is not suitable for paying fees.")]
-    InappropriateFeeToken,
-    // Not all TxAddErrors would apply to Toggle2FA, but
-    // it is helpful to re-use IncorrectEthSignature and DbError
-    #[error("Failed to toggle 2FA: {0}.")]
-    Toggle2FA(#[from] Toggle2FAError),
-
-    #[error("Communication error with the mempool: {0}.")]
-    MempoolCommunication(String),
-    #[error("Price error {0}")]
-    PriceError(#[from] PriceError),
-    #[error("Internal error.")]
-    Internal(#[from] anyhow::Error),
-    #[error("{0}")]
-    Other(String),
-}
-
-impl SubmitError {
-    pub fn internal(inner: impl Into<anyhow::Error>) -> Self {
-        Self::Internal(inner.into())
-    }
-
-    pub fn other(msg: impl Display) -> Self {
-        Self::Other(msg.to_string())
-    }
-
-    pub fn mempool_communication(msg: impl Display) -> Self {
-        Self::MempoolCommunication(msg.to_string())
-    }
-
-    pub fn invalid_params(msg: impl Display) -> Self {
-        Self::InvalidParams(msg.to_string())
-    }
+    /// Domain of the EIP-712 typed data signatures.
+    pub eip712_domain: Eip712Domain,
+    /// Resolver of the ENS names accepted instead of the addresses.
+    pub ens_resolver: EnsResolver,
+    /// Checker of the onchain state used by the queries, `None` if the Ethereum node is not available.
+    pub eth_checker: Option<EthereumChecker>,
+    /// Minimum USD value of the transfer requiring the travel rule metadata.
+    pub travel_rule_threshold_usd: Option<Ratio<BigUint>>,
+    /// Read-only nodes serve the queries only and reject the transactions.
+    pub read_only: bool,
+    pub withdrawal_screening: WithdrawalScreening,
+    pub weth_unwrap: WethUnwrap,
+    /// Maximum number of blocks the withdrawal timelocks can delay the withdrawals for.
+    pub max_withdrawal_delay_blocks: u32,
+    /// Transactions are rejected once the shutdown of the server is requested.
+    pub shutdown: ShutdownSignal,
 }
 
 #[macro_export]
@@ -148,6 +147,7 @@ impl TxSender {
         ticker: FeeTicker,
         config: &CommonApiConfig,
         token_config: &TokenConfig,
+        contract_addr: Address,
         mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     ) -> Self {
         let max_number_of_transactions_per_batch =
@@ -159,7 +159,10 @@ impl TxSender {
             pool: connection_pool,
             sign_verify_requests: sign_verify_request_sender,
             ticker,
-            tokens: TokenDBCache::new(token_config.invalidate_token_cache_period()),
+            tokens: TokenDBCache::new(token_config.invalidate_token_cache_period())
+                .with_shared_cache(
+                    SharedCache::from_config(config).expect("Invalid shared cache config"),
+                ),
             forced_exit_checker: ForcedExitChecker::new(
                 config.forced_exit_minimum_account_age_secs,
             ),
@@ -172,9 +175,36 @@ impl TxSender {
             current_subsidy_type: config.subsidy_name.clone(),
             max_subsidy_usd: config.max_subsidy_usd(),
             subsidized_ips: config.subsidized_ips.clone().into_iter().collect(),
+            eip712_domain: Eip712Domain::new(config.eip712_chain_id, contract_addr),
+            ens_resolver: EnsResolver::default(),
+            eth_checker: None,
+            travel_rule_threshold_usd: config.travel_rule_threshold_usd(),
+            read_only: config.read_only,
+            withdrawal_screening: WithdrawalScreening::from_config(config),
+            weth_unwrap: WethUnwrap::from_config(config),
+            max_withdrawal_delay_blocks: config.max_withdrawal_delay_blocks,
+            shutdown: ShutdownSignal::never(),
         }
     }
 
+    /// Enables the resolution of the ENS names.
+    pub fn with_ens_resolver(mut self, ens_resolver: EnsResolver) -> Self {
+        self.ens_resolver = ens_resolver;
+        self
+    }
+
+    /// Enables the queries of the onchain state.
+    pub fn with_eth_checker(mut self, eth_checker: EthereumChecker) -> Self {
+        self.eth_checker = Some(eth_checker);
+        self
+    }
+
+    /// Makes the transactions rejected once the shutdown is requested.
+    pub fn with_shutdown_signal(mut self, shutdown: ShutdownSignal) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
     /// If `ForcedExit` has Ethereum siganture (e.g. it's a part of a batch), an actual signer
     /// is initiator, not the target, thus, this function will perform a database query to acquire
     /// the corresponding address.
@@ -217,6 +247,7 @@ impl TxSender {
         &self,
         toggle_2fa: Toggle2FA,
     ) -> Result<Toggle2FAResponse, SubmitError> {
+        self.check_writable()?;
         let account_id = toggle_2fa.account_id;
         let current_type = self
             .get_sender_type(toggle_2fa.account_id)
@@ -258,9 +289,27 @@ impl TxSender {
     async fn verify_toggle_2fa_request_eth_signature(
         &self,
         toggle_2fa: Toggle2FA,
+    ) -> Result<(), SubmitError> {
+        let message = toggle_2fa.get_ethereum_sign_message();
+        self.verify_owner_request_eth_signature(
+            toggle_2fa.account_id,
+            toggle_2fa.timestamp,
+            message,
+            toggle_2fa.signature,
+        )
+        .await
+    }
+
+    /// Verifies the signature of the text message signed by the owner of the account, the message
+    /// is valid for `VALIDNESS_INTERVAL_MINUTES` around its timestamp.
+    async fn verify_owner_request_eth_signature(
+        &self,
+        account_id: AccountId,
+        request_time: DateTime<Utc>,
+        message: String,
+        signature: TxEthSignature,
     ) -> Result<(), SubmitError> {
         let current_time = Utc::now();
-        let request_time = toggle_2fa.timestamp;
         let validness_interval = Duration::minutes(VALIDNESS_INTERVAL_MINUTES);
 
         if current_time - validness_interval > request_time
@@ -272,11 +321,9 @@ impl TxSender {
             )));
         }
 
-        let message = toggle_2fa.get_ethereum_sign_message().into_bytes();
-
-        let signature = toggle_2fa.signature;
+        let message = message.into_bytes();
         let signer = self
-            .get_address_by_id(toggle_2fa.account_id)
+            .get_address_by_id(account_id)
             .await
             .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
 
@@ -361,6 +408,7 @@ impl TxSender {
         mut tx: ZkSyncTx,
         signature: TxEthSignatureVariant,
         fast_processing: Option<bool>,
+        idempotency_key: Option<String>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> Result<TxHash, SubmitError> {
         let fast_processing = fast_processing.unwrap_or(false);
@@ -380,7 +428,12 @@ impl TxSender {
         }
 
         let result = self
-            .submit_tx(tx, signature, extracted_request_metadata)
+            .submit_tx_with_idempotency_key(
+                tx,
+                signature,
+                idempotency_key,
+                extracted_request_metadata,
+            )
             .await;
 
         if let Err(err) = &result {
@@ -486,6 +539,20 @@ impl TxSender {
         tx: ZkSyncTx,
         signature: TxEthSignatureVariant,
         extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<TxHash, SubmitError> {
+        self.submit_tx_with_idempotency_key(tx, signature, None, extracted_request_metadata)
+            .await
+    }
+
+    /// Submits the transaction, `extra_gas` is charged on top of the transaction fee.
+    /// The travel rule metadata is stored once the transfer is accepted by the mempool.
+    async fn submit_new_tx(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        extracted_request_metadata: Option<RequestMetadata>,
+        extra_gas: BigUint,
+        travel_rule_metadata: Option<Vec<u8>>,
     ) -> Result<TxHash, SubmitError> {
         let labels = vec![
             ("stage", "api".to_string()),
@@ -494,6 +561,7 @@ impl TxSender {
         ];
         // The initial state of processing tx
         metrics::increment_counter!("process_tx_count", &labels);
+        self.check_writable()?;
 
         if tx.is_close() {
             return Err(SubmitError::AccountCloseDisabled);
@@ -502,17 +570,24 @@ impl TxSender {
         if let ZkSyncTx::ForcedExit(forced_exit) = &tx {
             self.check_forced_exit(forced_exit).await?;
         }
+        self.withdrawal_screening.check(&tx)?;
+        self.check_weth_unwrap(&tx).await?;
+        self.check_account_freeze(&tx).await?;
 
         // Resolve the token.
         let token = self.token_info_from_id(tx.token_id()).await?;
-        let msg_to_sign = tx
-            .get_ethereum_sign_message(token.clone())
-            .map(String::into_bytes);
+        let msg_to_sign = eth_message_to_sign(
+            &tx,
+            signature.tx_signature().as_ref(),
+            &token,
+            &self.eip712_domain,
+        )?;
 
         let is_whitelisted_initiator = tx
             .account_id()
             .map(|account_id| self.fee_free_accounts.contains(&account_id))
-            .unwrap_or(false);
+            .unwrap_or(false)
+            || self.is_sponsored(&tx).await?;
 
         let tx_fee_info = if !is_whitelisted_initiator {
             tx.get_fee_info()
@@ -534,10 +609,24 @@ impl TxSender {
                 return Err(SubmitError::InappropriateFeeToken);
             }
 
+            let extra_gas = match &tx {
+                ZkSyncTx::WithdrawNFT(withdraw) => {
+                    extra_gas
+                        + self
+                            .ticker
+                            .withdraw_nft_extra_gas(withdraw.token, withdraw.to)
+                            .await?
+                }
+                ZkSyncTx::Withdraw(withdraw) if withdraw.unwrap_weth => {
+                    extra_gas + BigUint::from(WETH_UNWRAP_GAS)
+                }
+                _ => extra_gas,
+            };
             let required_fee_data = self
                 .ticker
-                .get_fee_from_ticker_in_wei(tx_type, token.clone(), address)
-                .await?;
+                .get_fee_with_extra_gas(tx_type, token.clone(), address, extra_gas)
+                .await
+                .map_err(SubmitError::fee_error)?;
 
             let required_fee_data = if self
                 .should_subsidize_cpk(
@@ -592,6 +681,23 @@ impl TxSender {
                 .await?;
         }
 
+        if let Some(release_block) = self.withdrawal_release_block(&tx).await? {
+            self.pool
+                .access_storage()
+                .await
+                .map_err(SubmitError::internal)?
+                .withdrawal_timelocks_schema()
+                .store_pending_withdrawal(
+                    tx.account_id().or(Err(SubmitError::AccountCloseDisabled))?,
+                    &verified_tx,
+                    release_block,
+                )
+                .await
+                .map_err(SubmitError::internal)?;
+            metrics::increment_counter!("tx_sender.submit_tx.held_withdrawals");
+            return Ok(tx.hash());
+        }
+
         let (sender, receiver) = oneshot::channel();
         let item = MempoolTransactionRequest::NewTx(Box::new(verified_tx), sender);
         let mut mempool_sender = self.mempool_tx_sender.clone();
@@ -626,6 +732,16 @@ impl TxSender {
             })?;
         }
 
+        self.store_travel_rule_record(&tx, travel_rule_metadata)
+            .await
+            .map_err(|e| {
+                metrics::increment_counter!("tx_sender.submit_tx.store_travel_rule_record_fail");
+                SubmitError::Other(format!(
+                    "Failed to store the travel rule record to database. Reason: {}",
+                    e
+                ))
+            })?;
+
         // if everything is OK, return the transactions hashes.
         Ok(tx.hash())
     }
@@ -636,6 +752,30 @@ impl TxSender {
         eth_signatures: Option<EthBatchSignatures>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> Result<SubmitBatchResponse, SubmitError> {
+        self.submit_batch(txs, eth_signatures, extracted_request_metadata, None)
+            .await
+    }
+
+    /// Submits the batch of the client of the private channel with the given ID. The batch is
+    /// checked the same way as the public ones, but is hidden from the public API until executed.
+    pub async fn submit_private_txs_batch(
+        &self,
+        client_id: i64,
+        txs: Vec<TxWithSignature>,
+        eth_signatures: Option<EthBatchSignatures>,
+    ) -> Result<SubmitBatchResponse, SubmitError> {
+        self.submit_batch(txs, eth_signatures, None, Some(client_id))
+            .await
+    }
+
+    async fn submit_batch(
+        &self,
+        txs: Vec<TxWithSignature>,
+        eth_signatures: Option<EthBatchSignatures>,
+        extracted_request_metadata: Option<RequestMetadata>,
+        private_client: Option<i64>,
+    ) -> Result<SubmitBatchResponse, SubmitError> {
+        self.check_writable()?;
         // Bring the received signatures into a vector for simplified work.
         let eth_signatures = EthBatchSignatures::api_arg_to_vec(eth_signatures);
 
@@ -649,78 +789,44 @@ impl TxSender {
             return Err(SubmitError::TxAdd(TxAddError::BatchTooBig));
         }
 
-        for tx in &txs {
+        // Same check but in terms of signatures.
+        if eth_signatures.len() > self.max_number_of_authors_per_batch {
+            return Err(SubmitError::TxAdd(TxAddError::EthSignaturesLimitExceeded));
+        }
+
+        // Transactions are validated one by one, so the batch is rejected on the first
+        // invalid transaction, and the fee is accumulated along the way.
+        let mut fee = BatchFeeAccumulator::default();
+        let mut messages_to_sign = Vec::with_capacity(txs.len());
+        let mut tx_senders = Vec::with_capacity(txs.len());
+        let mut tx_sender_types = Vec::with_capacity(txs.len());
+        let mut tokens = Vec::with_capacity(txs.len());
+        for (index, tx) in txs.iter().enumerate() {
             let labels = vec![
                 ("stage", "api".to_string()),
                 ("name", tx.tx.variance_name()),
                 ("token", tx.tx.token_id().to_string()),
             ];
             metrics::increment_counter!("process_tx_count", &labels);
-        }
 
-        // Same check but in terms of signatures.
-        if eth_signatures.len() > self.max_number_of_authors_per_batch {
-            return Err(SubmitError::TxAdd(TxAddError::EthSignaturesLimitExceeded));
-        }
-
-        if txs.iter().any(|tx| tx.tx.is_close()) {
-            return Err(SubmitError::AccountCloseDisabled);
+            let (token, message, sender, sender_type) = self
+                .validate_batch_tx(tx, &mut fee)
+                .await
+                .map_err(|err| SubmitError::invalid_batch_tx(index, err))?;
+            tokens.push(token);
+            messages_to_sign.push(message);
+            tx_senders.push(sender);
+            tx_sender_types.push(sender_type);
         }
-
-        // Checking fees data
-        let mut provided_total_usd_fee = BigDecimal::from(0);
-        let mut transaction_types = vec![];
-
+        let BatchFeeAccumulator {
+            provided_total_usd_fee,
+            transaction_types,
+            extra_gas,
+            token_fees,
+            token_fees_ids,
+        } = fee;
         let eth_token = TokenLike::Id(TokenId(0));
 
-        let mut token_fees = HashMap::<Address, BigUint>::new();
-        let mut token_fees_ids = vec![];
-
-        for tx in &txs {
-            let tx_fee_info = tx.tx.get_fee_info();
-
-            if let Some((tx_type, token, address, provided_fee)) = tx_fee_info {
-                // Save the transaction type before moving on to the next one, otherwise
-                // the total fee won't get affected by it.
-                transaction_types.push((tx_type, address));
-
-                if provided_fee == BigUint::zero() {
-                    continue;
-                }
-                let fee_allowed = self.ticker.token_allowed_for_fees(token.clone()).await?;
-
-                // In batches, transactions with non-popular token are allowed to be included, but should not
-                // used to pay fees. Fees must be covered by some more common token.
-                if !fee_allowed && provided_fee != 0u64.into() {
-                    return Err(SubmitError::InappropriateFeeToken);
-                }
-
-                let check_token = if fee_allowed {
-                    // For allowed tokens, we perform check in the transaction token (as expected).
-                    token.clone()
-                } else {
-                    // For non-popular tokens we've already checked that the provided fee is 0,
-                    // and the USD price will be checked in ETH.
-                    eth_token.clone()
-                };
-
-                let token_price_in_usd = self
-                    .ticker
-                    .get_token_price(check_token.clone(), TokenPriceRequestType::USDForOneWei)
-                    .await?;
-
-                let token_data = self.token_info_from_id(token).await?;
-                token_fees_ids.push(token_data.id);
-                let mut token_fee = token_fees.remove(&token_data.address).unwrap_or_default();
-                token_fee += &provided_fee;
-                token_fees.insert(token_data.address, token_fee);
-
-                provided_total_usd_fee +=
-                    BigDecimal::from(provided_fee.clone().to_bigint().unwrap())
-                        * &token_price_in_usd;
-            }
-        }
-
         let mut fee_data_for_subsidy: Option<ResponseBatchFee> = None;
 
         // Only one token in batch
@@ -728,8 +834,13 @@ impl TxSender {
             let (batch_token, fee_paid) = token_fees.into_iter().next().unwrap();
             let batch_token_fee = self
                 .ticker
-                .get_batch_from_ticker_in_wei(batch_token.into(), transaction_types.clone())
-                .await?;
+                .get_batch_fee_with_extra_gas(
+                    batch_token.into(),
+                    transaction_types.clone(),
+                    extra_gas,
+                )
+                .await
+                .map_err(SubmitError::fee_error)?;
 
             let required_fee = if self
                 .should_subsidize_cpk(
@@ -763,8 +874,9 @@ impl TxSender {
             // Calculate required fee for ethereum token
             let required_eth_fee = self
                 .ticker
-                .get_batch_from_ticker_in_wei(eth_token.clone(), transaction_types)
-                .await?;
+                .get_batch_fee_with_extra_gas(eth_token.clone(), transaction_types, extra_gas)
+                .await
+                .map_err(SubmitError::fee_error)?;
 
             let required_fee = if self
                 .should_subsidize_cpk(
@@ -803,49 +915,18 @@ impl TxSender {
             }
         }
 
-        for tx in txs.iter() {
-            if let ZkSyncTx::Swap(swap) = &tx.tx {
-                if tx.signature.is_single() {
-                    return Err(SubmitError::TxAdd(TxAddError::MissingEthSignature));
-                }
-                let signatures = tx.signature.orders_signatures();
-                self.verify_order_eth_signature(&swap.orders.0, signatures.0.clone())
-                    .await?;
-                self.verify_order_eth_signature(&swap.orders.1, signatures.1.clone())
-                    .await?;
-            }
-        }
-
-        let mut verified_txs = Vec::with_capacity(txs.len());
-        let mut verified_signatures = Vec::new();
-
-        let mut messages_to_sign = Vec::with_capacity(txs.len());
-        let mut tx_senders = Vec::with_capacity(txs.len());
-        let mut tx_sender_types = Vec::with_capacity(txs.len());
-        let mut tokens = Vec::with_capacity(txs.len());
-        for tx in txs.iter().map(|tx| &tx.tx) {
-            // Resolve the token and save it for constructing the batch message.
-            let token = self.token_info_from_id(tx.token_id()).await?;
-            tokens.push(token.clone());
-
-            messages_to_sign.push(tx.get_ethereum_sign_message(token).map(String::into_bytes));
-            tx_senders.push(
-                self.get_tx_sender(tx)
-                    .await
-                    .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?,
-            );
-            tx_sender_types.push(self.get_tx_sender_type(tx).await?);
-        }
-
         let batch_sign_data = if !eth_signatures.is_empty() {
             // User provided at least one signature for the whole batch.
             // In this case each sender cannot be CREATE2.
-            if tx_sender_types
+            if let Some(index) = tx_sender_types
                 .iter()
-                .any(|_type| matches!(_type, EthAccountType::CREATE2))
+                .position(|_type| matches!(_type, EthAccountType::CREATE2))
             {
-                return Err(SubmitError::IncorrectTx(
-                    "Eth signature from CREATE2 account not expected".to_string(),
+                return Err(SubmitError::invalid_batch_tx(
+                    index,
+                    SubmitError::IncorrectTx(
+                        "Eth signature from CREATE2 account not expected".to_string(),
+                    ),
                 ));
             }
             let _txs = txs
@@ -859,6 +940,8 @@ impl TxSender {
         } else {
             None
         };
+        let mut verified_txs = Vec::with_capacity(txs.len());
+        let mut verified_signatures = Vec::new();
         let (verified_batch, sign_data) = verify_txs_batch_signature(
             txs,
             tx_senders,
@@ -876,10 +959,24 @@ impl TxSender {
         verified_txs.extend(verified_batch.into_iter());
 
         let tx_hashes: Vec<TxHash> = verified_txs.iter().map(|tx| tx.tx.hash()).collect();
+        let transfers: Vec<ZkSyncTx> = verified_txs
+            .iter()
+            .filter(|tx| matches!(tx.tx, ZkSyncTx::Transfer(_)))
+            .map(|tx| tx.tx.clone())
+            .collect();
 
         let (sender, receiver) = oneshot::channel();
-        let item =
-            MempoolTransactionRequest::NewTxsBatch(verified_txs, verified_signatures, sender);
+        let item = match private_client {
+            Some(client_id) => MempoolTransactionRequest::NewPrivateTxsBatch(
+                client_id,
+                verified_txs,
+                verified_signatures,
+                sender,
+            ),
+            None => {
+                MempoolTransactionRequest::NewTxsBatch(verified_txs, verified_signatures, sender)
+            }
+        };
         let mut mempool_sender = self.mempool_tx_sender.clone();
         mempool_sender
             .send(item)
@@ -925,12 +1022,135 @@ impl TxSender {
             })?;
         }
 
+        // The metadata can't be attached to the batch transfers, so the large ones are flagged.
+        for transfer in &transfers {
+            self.store_travel_rule_record(transfer, None)
+                .await
+                .map_err(|e| {
+                    metrics::increment_counter!(
+                        "tx_sender.submit_txs_batch.store_travel_rule_record_fail"
+                    );
+                    SubmitError::Other(format!(
+                        "Failed to store the travel rule record to database. Reason: {}",
+                        e
+                    ))
+                })?;
+        }
+
         Ok(SubmitBatchResponse {
             transaction_hashes: tx_hashes.into_iter().map(TxHashSerializeWrapper).collect(),
             batch_hash,
         })
     }
 
+    /// Validates the transaction of the batch on its own, accumulating its fee. Returns the data
+    /// required to verify the Ethereum signatures of the batch.
+    async fn validate_batch_tx(
+        &self,
+        tx: &TxWithSignature,
+        fee: &mut BatchFeeAccumulator,
+    ) -> Result<(Token, Option<Vec<u8>>, Address, EthAccountType), SubmitError> {
+        if tx.tx.is_close() {
+            return Err(SubmitError::AccountCloseDisabled);
+        }
+        self.withdrawal_screening.check(&tx.tx)?;
+        self.check_weth_unwrap(&tx.tx).await?;
+        self.check_account_freeze(&tx.tx).await?;
+        if self.withdrawal_release_block(&tx.tx).await?.is_some() {
+            return Err(SubmitError::IncorrectTx(
+                TIMELOCKED_WITHDRAWAL_IN_BATCH.to_string(),
+            ));
+        }
+
+        if let Some((tx_type, token, address, provided_fee)) = tx.tx.get_fee_info() {
+            // Save the transaction type even if it pays no fee, otherwise
+            // the total fee won't get affected by it.
+            fee.transaction_types.push((tx_type, address));
+            if let ZkSyncTx::WithdrawNFT(withdraw) = &tx.tx {
+                fee.extra_gas += self
+                    .ticker
+                    .withdraw_nft_extra_gas(withdraw.token, withdraw.to)
+                    .await?;
+            }
+            if let ZkSyncTx::Withdraw(withdraw) = &tx.tx {
+                if withdraw.unwrap_weth {
+                    fee.extra_gas += BigUint::from(WETH_UNWRAP_GAS);
+                }
+            }
+            if !provided_fee.is_zero() {
+                self.accumulate_batch_fee(fee, token, provided_fee).await?;
+            }
+        }
+
+        if let ZkSyncTx::Swap(swap) = &tx.tx {
+            if tx.signature.is_single() {
+                return Err(SubmitError::TxAdd(TxAddError::MissingEthSignature));
+            }
+            let signatures = tx.signature.orders_signatures();
+            self.verify_order_eth_signature(&swap.orders.0, signatures.0.clone())
+                .await?;
+            self.verify_order_eth_signature(&swap.orders.1, signatures.1.clone())
+                .await?;
+        }
+
+        // Resolve the token and save it for constructing the batch message.
+        let token = self.token_info_from_id(tx.tx.token_id()).await?;
+        let message = eth_message_to_sign(
+            &tx.tx,
+            tx.signature.tx_signature().as_ref(),
+            &token,
+            &self.eip712_domain,
+        )?;
+        let sender = self
+            .get_tx_sender(&tx.tx)
+            .await
+            .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
+        let sender_type = self.get_tx_sender_type(&tx.tx).await?;
+        Ok((token, message, sender, sender_type))
+    }
+
+    async fn accumulate_batch_fee(
+        &self,
+        fee: &mut BatchFeeAccumulator,
+        token: TokenLike,
+        provided_fee: BigUint,
+    ) -> Result<(), SubmitError> {
+        // In batches, transactions with non-popular token are allowed to be included, but should not
+        // used to pay fees. Fees must be covered by some more common token.
+        let fee_allowed = self.ticker.token_allowed_for_fees(token.clone()).await?;
+        if !fee_allowed {
+            return Err(SubmitError::InappropriateFeeToken);
+        }
+
+        let token_price_in_usd = self
+            .ticker
+            .get_token_price(token.clone(), TokenPriceRequestType::USDForOneWei)
+            .await?;
+
+        let token_data = self.token_info_from_id(token).await?;
+        fee.token_fees_ids.push(token_data.id);
+        *fee.token_fees.entry(token_data.address).or_default() += &provided_fee;
+
+        fee.provided_total_usd_fee +=
+            BigDecimal::from(provided_fee.to_bigint().unwrap()) * &token_price_in_usd;
+        Ok(())
+    }
+
+    /// Expands the bundle of the NFT transfers into the batch with the fee transfer at the end
+    /// and submits it, so the bundle is executed atomically and tracked as a single batch.
+    pub async fn submit_nft_transfer_batch(
+        &self,
+        bundle: IncomingNFTTransferBatch,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<SubmitBatchResponse, SubmitError> {
+        check_nft_transfer_batch(&bundle.transfers, &bundle.fee)?;
+
+        let mut txs = bundle.transfers;
+        txs.push(bundle.fee);
+        self.submit_txs_batch(txs, bundle.signature, extracted_request_metadata)
+            .await
+    }
+
     /// For forced exits, we must check that target account exists for more
     /// than 24 hours in order to give new account owners give an opportunity
     /// to set the signing key. While `ForcedExit` operation doesn't do anything
@@ -1031,6 +1251,32 @@ async fn send_verify_request_and_recv(
         .map_err(SubmitError::TxAdd)
 }
 
+/// Returns the message the Ethereum signature of the transaction is expected to sign.
+/// Typed data signature is negotiated by its type, so the text message is expected
+/// for every other signature. Typed data signature of the transaction without
+/// the EIP-712 structure is rejected, otherwise its 2FA check would be skipped.
+fn eth_message_to_sign(
+    tx: &ZkSyncTx,
+    signature: Option<&TxEthSignature>,
+    token: &Token,
+    domain: &Eip712Domain,
+) -> Result<Option<Vec<u8>>, SubmitError> {
+    let is_typed_data = signature
+        .map(TxEthSignature::is_typed_data)
+        .unwrap_or(false);
+    if !is_typed_data {
+        return Ok(tx
+            .get_ethereum_sign_message(token.clone())
+            .map(String::into_bytes));
+    }
+    if matches!(tx, ZkSyncTx::ChangePubKey(tx) if tx.no_2fa.is_some()) {
+        return Err(SubmitError::IncorrectTx(NO_2FA_TYPED_DATA.to_string()));
+    }
+    tx.get_eip712_signing_message(token, domain)
+        .map(Some)
+        .ok_or_else(|| SubmitError::IncorrectTx(TYPED_DATA_NOT_SUPPORTED.to_string()))
+}
+
 /// Send a request for Ethereum signature verification and wait for the response.
 /// If `msg_to_sign` is not `None`, then the signature must be present.
 async fn verify_tx_info_message_signature(
@@ -1093,6 +1339,17 @@ async fn verify_tx_info_message_signature(
     send_verify_request_and_recv(request, req_channel, receiever).await
 }
 
+/// Fee of the batch accumulated while its transactions are validated.
+#[derive(Debug, Default)]
+struct BatchFeeAccumulator {
+    provided_total_usd_fee: BigDecimal,
+    transaction_types: Vec<(TxFeeTypes, Address)>,
+    extra_gas: BigUint,
+    /// Fees provided in every token, by the token address.
+    token_fees: HashMap<Address, BigUint>,
+    token_fees_ids: Vec<TokenId>,
+}
+
 /// Send a request for Ethereum signature verification and wait for the response.
 /// Unlike in case of `verify_tx_info_message_signature`, we do not require
 /// every transaction from the batch to be signed. The signature must be obtained
@@ -1111,12 +1368,18 @@ async fn verify_txs_batch_signature(
     // from this batch. We save the account type to the db later.
     let mut create2_senders = HashSet::<H160>::new();
     let mut txs = Vec::with_capacity(batch.len());
-    for (tx, message, sender, mut sender_type) in
-        izip!(batch, msgs_to_sign, senders.iter(), sender_types)
+    for (index, (tx, message, sender, mut sender_type)) in
+        izip!(batch, msgs_to_sign, senders.iter(), sender_types).enumerate()
     {
         if create2_senders.contains(sender) {
             sender_type = EthAccountType::CREATE2;
         }
+        // Change of the 2FA policy always requires the Ethereum signature.
+        if matches!(&tx.tx, ZkSyncTx::ChangePubKey(tx) if tx.no_2fa.is_some())
+            && matches!(sender_type, EthAccountType::No2FA(_))
+        {
+            sender_type = EthAccountType::Owned;
+        }
         if let ZkSyncTx::ChangePubKey(tx) = &tx.tx {
             if let Some(auth_data) = &tx.eth_auth_data {
                 if auth_data.is_create2() {
@@ -1130,15 +1393,21 @@ async fn verify_txs_batch_signature(
             match sender_type {
                 EthAccountType::CREATE2 => {
                     if tx.signature.exists() {
-                        return Err(SubmitError::IncorrectTx(
-                            "Eth signature from CREATE2 account not expected".to_string(),
+                        return Err(SubmitError::invalid_batch_tx(
+                            index,
+                            SubmitError::IncorrectTx(
+                                "Eth signature from CREATE2 account not expected".to_string(),
+                            ),
                         ));
                     }
                     None
                 }
                 EthAccountType::Owned => {
                     if batch_sign_data.is_none() && !tx.signature.exists() {
-                        return Err(SubmitError::TxAdd(TxAddError::MissingEthSignature));
+                        return Err(SubmitError::invalid_batch_tx(
+                            index,
+                            SubmitError::TxAdd(TxAddError::MissingEthSignature),
+                        ));
                     }
                     tx.signature
                         .tx_signature()
@@ -1149,7 +1418,10 @@ async fn verify_txs_batch_signature(
                     let tx_pub_key_hash = PubKeyHash::from_pubkey(&tx.tx.signature().pub_key.0);
                     if tx_pub_key_hash != unchecked_hash {
                         if batch_sign_data.is_none() && !tx.signature.exists() {
-                            return Err(SubmitError::TxAdd(TxAddError::MissingEthSignature));
+                            return Err(SubmitError::invalid_batch_tx(
+                                index,
+                                SubmitError::TxAdd(TxAddError::MissingEthSignature),
+                            ));
                         }
 
                         tx.signature
@@ -1225,6 +1497,10 @@ fn scale_user_fee_up(provided_total_usd_fee: BigDecimal) -> BigDecimal {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use zksync_types::{
+        tx::{MintNFT, PackedEthSignature, Swap, TimeRange, Transfer, TxSignature},
+        Nonce, TokenKind, H256,
+    };
 
     #[test]
     fn test_scaling_user_fee_by_two() {
@@ -1255,4 +1531,75 @@ mod tests {
 
         assert_eq!(provided_fee_scaled_by_five_percent, scaled_fee);
     }
+
+    #[test]
+    fn typed_data_signature_requires_eip712_structure() {
+        let token = Token::new(TokenId(0), Address::zero(), "ETH", 18, TokenKind::ERC20);
+        let domain = Eip712Domain::new(9, Address::repeat_byte(9));
+        let eth_signature =
+            PackedEthSignature::sign(&H256::repeat_byte(1), b"zksync").expect("Should sign");
+        let typed = TxEthSignature::EIP712Signature(eth_signature.clone());
+        let text = TxEthSignature::EthereumSignature(eth_signature);
+
+        let mint_nft = ZkSyncTx::MintNFT(Box::new(MintNFT::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            H256::repeat_byte(2),
+            Address::repeat_byte(3),
+            0u32.into(),
+            TokenId(0),
+            Nonce(0),
+            None,
+        )));
+        let order = |account_id: u32| Order {
+            account_id: AccountId(account_id),
+            recipient_address: Address::repeat_byte(account_id as u8),
+            nonce: Nonce(0),
+            token_buy: TokenId(1),
+            token_sell: TokenId(0),
+            price: (1u32.into(), 1u32.into()),
+            amount: 1u32.into(),
+            time_range: TimeRange::default(),
+            signature: TxSignature::default(),
+        };
+        let swap = ZkSyncTx::Swap(Box::new(Swap::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Nonce(0),
+            (order(1), order(2)),
+            (1u32.into(), 1u32.into()),
+            0u32.into(),
+            TokenId(0),
+            None,
+        )));
+        // Typed data signature of these transactions has nothing to sign, so it must not
+        // make the Ethereum signature optional.
+        for tx in [&mint_nft, &swap] {
+            let err = eth_message_to_sign(tx, Some(&typed), &token, &domain).unwrap_err();
+            assert!(
+                matches!(&err, SubmitError::IncorrectTx(reason) if reason == TYPED_DATA_NOT_SUPPORTED),
+                "{}",
+                err
+            );
+            let message = eth_message_to_sign(tx, Some(&text), &token, &domain).unwrap();
+            assert!(message.is_some());
+        }
+
+        let transfer = ZkSyncTx::Transfer(Box::new(Transfer::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(0),
+            1u32.into(),
+            0u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        )));
+        let message = eth_message_to_sign(&transfer, Some(&typed), &token, &domain).unwrap();
+        assert_eq!(
+            message,
+            transfer.get_eip712_signing_message(&token, &domain)
+        );
+    }
 }
@@ -0,0 +1,212 @@
+//! Withdrawal timelocks of the accounts, see `withdrawal_timelock`.
+
+// External uses
+use num::{rational::Ratio, BigUint};
+
+// Workspace uses
+use zksync_api_types::v02::transaction::{
+    AccountWithdrawalTimelock, CancelWithdrawalRequest, PendingWithdrawal,
+    WithdrawalTimelockRequest, WithdrawalTimelockSettings,
+};
+use zksync_storage::withdrawal_timelocks::{WITHDRAWAL_CANCELLED, WITHDRAWAL_PENDING};
+use zksync_types::{AccountId, BlockNumber, TokenLike, ZkSyncTx};
+use zksync_utils::big_decimal_to_ratio;
+
+// Local uses
+use super::{SubmitError, TxSender};
+use crate::{
+    api_server::withdrawal_timelock::{
+        pending_withdrawal_from_record, timelock_settings_from_record,
+    },
+    fee_ticker::TokenPriceRequestType,
+};
+
+impl TxSender {
+    /// Sets or removes the withdrawal timelock of the account, see `withdrawal_timelock`.
+    pub async fn set_withdrawal_timelock(
+        &self,
+        request: WithdrawalTimelockRequest,
+    ) -> Result<Option<WithdrawalTimelockSettings>, SubmitError> {
+        self.check_writable()?;
+        if let Some(timelock) = request.timelock {
+            if timelock.delay_blocks == 0
+                || timelock.delay_blocks > self.max_withdrawal_delay_blocks
+            {
+                return Err(SubmitError::invalid_params(format!(
+                    "withdrawal delay must be from 1 to {} blocks",
+                    self.max_withdrawal_delay_blocks
+                )));
+            }
+            if timelock.threshold_usd > i64::MAX as u64 {
+                return Err(SubmitError::invalid_params(
+                    "withdrawal threshold is too big",
+                ));
+            }
+        }
+
+        let message = request.get_ethereum_sign_message();
+        self.verify_owner_request_eth_signature(
+            request.account_id,
+            request.timestamp,
+            message,
+            request.signature,
+        )
+        .await?;
+
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let mut schema = storage.withdrawal_timelocks_schema();
+        match request.timelock {
+            Some(timelock) => schema
+                .set_timelock(
+                    request.account_id,
+                    timelock.threshold_usd,
+                    timelock.delay_blocks,
+                )
+                .await
+                .map_err(SubmitError::internal)?,
+            None => schema
+                .remove_timelock(request.account_id)
+                .await
+                .map_err(SubmitError::internal)?,
+        }
+        Ok(request.timelock)
+    }
+
+    /// Cancels the withdrawal held by the timelock of the account.
+    pub async fn cancel_withdrawal(
+        &self,
+        request: CancelWithdrawalRequest,
+    ) -> Result<PendingWithdrawal, SubmitError> {
+        self.check_writable()?;
+        let message = request.get_ethereum_sign_message();
+        self.verify_owner_request_eth_signature(
+            request.account_id,
+            request.timestamp,
+            message,
+            request.signature,
+        )
+        .await?;
+
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let withdrawal = storage
+            .withdrawal_timelocks_schema()
+            .get_pending_withdrawal(&request.tx_hash)
+            .await
+            .map_err(SubmitError::internal)?
+            .filter(|withdrawal| withdrawal.account_id == i64::from(*request.account_id))
+            .ok_or_else(|| SubmitError::invalid_params("withdrawal is not held by the timelock"))?;
+        let is_cancelled = storage
+            .withdrawal_timelocks_schema()
+            .update_withdrawal_status(
+                &request.tx_hash,
+                WITHDRAWAL_PENDING,
+                WITHDRAWAL_CANCELLED,
+                None,
+            )
+            .await
+            .map_err(SubmitError::internal)?;
+        if !is_cancelled {
+            return Err(SubmitError::invalid_params(format!(
+                "withdrawal is already {}",
+                withdrawal.status
+            )));
+        }
+
+        let withdrawal = storage
+            .withdrawal_timelocks_schema()
+            .get_pending_withdrawal(&request.tx_hash)
+            .await
+            .map_err(SubmitError::internal)?
+            .ok_or_else(|| SubmitError::other("cancelled withdrawal is not found"))?;
+        pending_withdrawal_from_record(withdrawal).map_err(SubmitError::internal)
+    }
+
+    /// Returns the withdrawal timelock of the account along with the withdrawals held by it.
+    pub async fn account_withdrawal_timelock(
+        &self,
+        account_id: AccountId,
+    ) -> Result<AccountWithdrawalTimelock, SubmitError> {
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let timelock = storage
+            .withdrawal_timelocks_schema()
+            .get_timelock(account_id)
+            .await
+            .map_err(SubmitError::internal)?
+            .map(timelock_settings_from_record);
+        let withdrawals = storage
+            .withdrawal_timelocks_schema()
+            .load_account_withdrawals(account_id)
+            .await
+            .map_err(SubmitError::internal)?
+            .into_iter()
+            .map(pending_withdrawal_from_record)
+            .collect::<Result<_, _>>()
+            .map_err(SubmitError::internal)?;
+        Ok(AccountWithdrawalTimelock {
+            timelock,
+            withdrawals,
+        })
+    }
+
+    /// Returns the block the withdrawal is held until, if it's delayed by the timelock of the
+    /// account. Withdrawals of the tokens without the known price are delayed regardless of
+    /// their amount.
+    pub(super) async fn withdrawal_release_block(
+        &self,
+        tx: &ZkSyncTx,
+    ) -> Result<Option<BlockNumber>, SubmitError> {
+        let withdraw = match tx {
+            ZkSyncTx::Withdraw(withdraw) => withdraw,
+            _ => return Ok(None),
+        };
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let timelock = match storage
+            .withdrawal_timelocks_schema()
+            .get_timelock(withdraw.account_id)
+            .await
+            .map_err(SubmitError::internal)?
+        {
+            Some(timelock) => timelock_settings_from_record(timelock),
+            None => return Ok(None),
+        };
+
+        if let Ok(price) = self
+            .ticker
+            .get_token_price(
+                TokenLike::Id(withdraw.token),
+                TokenPriceRequestType::USDForOneWei,
+            )
+            .await
+        {
+            let amount_usd =
+                big_decimal_to_ratio(&price).map_err(SubmitError::internal)? * &withdraw.amount;
+            if amount_usd < Ratio::from_integer(BigUint::from(timelock.threshold_usd)) {
+                return Ok(None);
+            }
+        }
+
+        let last_block = storage
+            .chain()
+            .block_schema()
+            .get_last_saved_block()
+            .await
+            .map_err(SubmitError::internal)?;
+        Ok(Some(BlockNumber(*last_block + timelock.delay_blocks)))
+    }
+}
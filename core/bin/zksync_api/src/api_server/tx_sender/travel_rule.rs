@@ -0,0 +1,95 @@
+//! Travel rule metadata of the transfers, see `TravelRuleRecord`.
+
+// External uses
+use num::{BigUint, Zero};
+
+// Workspace uses
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_storage::travel_rule::records::TravelRuleRecord;
+use zksync_types::{
+    travel_rule::MAX_TRAVEL_RULE_METADATA_LEN,
+    tx::{TxEthSignatureVariant, TxHash},
+    TokenLike, ZkSyncTx,
+};
+use zksync_utils::big_decimal_to_ratio;
+
+// Local uses
+use super::{SubmitError, TxSender};
+use crate::fee_ticker::TokenPriceRequestType;
+
+impl TxSender {
+    /// Submits the transfer along with its travel rule metadata encrypted for the recipient.
+    pub async fn submit_transfer_with_travel_rule(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        metadata: Vec<u8>,
+    ) -> Result<TxHash, SubmitError> {
+        if !matches!(&tx, ZkSyncTx::Transfer(transfer) if transfer.token.0 < MIN_NFT_TOKEN_ID) {
+            return Err(SubmitError::invalid_params(
+                "travel rule metadata can be attached only to the fungible token transfers",
+            ));
+        }
+        if metadata.is_empty() || metadata.len() > MAX_TRAVEL_RULE_METADATA_LEN {
+            return Err(SubmitError::invalid_params(format!(
+                "travel rule metadata must be from 1 to {} bytes long",
+                MAX_TRAVEL_RULE_METADATA_LEN
+            )));
+        }
+        self.submit_new_tx(tx, signature, None, BigUint::zero(), Some(metadata))
+            .await
+    }
+
+    /// Stores the travel rule record of the transfer accepted by the mempool, see
+    /// `TravelRuleRecord::for_transfer`.
+    pub(super) async fn store_travel_rule_record(
+        &self,
+        tx: &ZkSyncTx,
+        metadata: Option<Vec<u8>>,
+    ) -> Result<(), anyhow::Error> {
+        let transfer = match tx {
+            ZkSyncTx::Transfer(transfer) if transfer.token.0 < MIN_NFT_TOKEN_ID => transfer,
+            _ => return Ok(()),
+        };
+        if metadata.is_none() && self.travel_rule_threshold_usd.is_none() {
+            return Ok(());
+        }
+
+        let amount_usd = match self
+            .ticker
+            .get_token_price(
+                TokenLike::Id(transfer.token),
+                TokenPriceRequestType::USDForOneWei,
+            )
+            .await
+        {
+            Ok(price) => Some(big_decimal_to_ratio(&price)? * &transfer.amount),
+            Err(_) => None,
+        };
+        let record = match TravelRuleRecord::for_transfer(
+            tx.hash(),
+            transfer,
+            amount_usd,
+            self.travel_rule_threshold_usd.as_ref(),
+            metadata,
+        ) {
+            Some(record) => record,
+            None => return Ok(()),
+        };
+        if record.is_flagged() {
+            metrics::increment_counter!("tx_sender.travel_rule.flagged");
+            vlog::warn!(
+                "Transfer {} exceeds the travel rule threshold without the metadata",
+                tx.hash()
+            );
+        }
+
+        self.pool
+            .access_storage()
+            .await?
+            .travel_rule_schema()
+            .save_record(record)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+//! Validation of the withdrawals delivered as native ETH.
+//!
+//! Withdrawals with the `unwrapWeth` flag send the WETH to the unwrapper contract, and the server
+//! forwards the unwrapped ETH to the owner of the L2 account once the block is executed. The
+//! unwrapper can't tell the owner of the funds received without the flag, so such withdrawals are
+//! rejected as well as the flagged withdrawals of the other tokens. The unwrapping transaction is
+//! paid by the server, so its gas is charged on top of the withdrawal fee.
+
+// Workspace uses
+use zksync_config::configs::api::CommonApiConfig;
+use zksync_types::{Address, Withdraw};
+// Local uses
+use crate::api_server::tx_sender::SubmitError;
+
+/// L1 gas spent by the server on the unwrapping, charged on top of the withdrawal fee.
+pub const WETH_UNWRAP_GAS: u64 = 80_000;
+
+#[derive(Debug, Clone, Default)]
+pub struct WethUnwrap {
+    unwrapper: Option<Address>,
+    weth: Option<Address>,
+}
+
+impl WethUnwrap {
+    pub fn from_config(config: &CommonApiConfig) -> Self {
+        Self {
+            unwrapper: config.weth_unwrapper_addr,
+            weth: config.weth_token_addr,
+        }
+    }
+
+    /// Returns whether the withdrawal is subject to the check, i.e. it's either flagged or sent to
+    /// the unwrapper.
+    pub fn applies_to(&self, tx: &Withdraw) -> bool {
+        tx.unwrap_weth || Some(tx.to) == self.unwrapper
+    }
+
+    /// Checks the withdrawal of the token with the given L1 address.
+    pub fn check(&self, tx: &Withdraw, token_address: Address) -> Result<(), SubmitError> {
+        let (unwrapper, weth) = match (self.unwrapper, self.weth) {
+            (Some(unwrapper), Some(weth)) => (unwrapper, weth),
+            _ => {
+                return Err(SubmitError::IncorrectTx(
+                    "WETH unwrapping is not supported".to_string(),
+                ))
+            }
+        };
+        if !tx.unwrap_weth {
+            return Err(SubmitError::IncorrectTx(
+                "Withdrawal to the WETH unwrapper must have the 'unwrapWeth' flag set".to_string(),
+            ));
+        }
+        if tx.to != unwrapper {
+            return Err(SubmitError::IncorrectTx(format!(
+                "Withdrawal with the 'unwrapWeth' flag must be sent to the WETH unwrapper {:?}",
+                unwrapper
+            )));
+        }
+        if token_address != weth {
+            return Err(SubmitError::IncorrectTx(
+                "Only WETH can be unwrapped on withdrawal".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{AccountId, Nonce, TokenId};
+
+    fn withdraw(to: Address, unwrap_weth: bool) -> Withdraw {
+        let mut tx = Withdraw::new(
+            AccountId(1),
+            Address::repeat_byte(0x11),
+            to,
+            TokenId(1),
+            1u32.into(),
+            0u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        );
+        tx.unwrap_weth = unwrap_weth;
+        tx
+    }
+
+    #[test]
+    fn unwrapped_withdrawals() {
+        let unwrapper = Address::repeat_byte(0x22);
+        let weth = Address::repeat_byte(0x33);
+        let unwrap = WethUnwrap {
+            unwrapper: Some(unwrapper),
+            weth: Some(weth),
+        };
+
+        let regular = withdraw(Address::repeat_byte(0x44), false);
+        assert!(!unwrap.applies_to(&regular));
+        assert!(unwrap.check(&withdraw(unwrapper, true), weth).is_ok());
+        // The funds would get stuck on the unwrapper and the contract can't unwrap other tokens.
+        assert!(unwrap.applies_to(&withdraw(unwrapper, false)));
+        assert!(unwrap.check(&withdraw(unwrapper, false), weth).is_err());
+        assert!(unwrap
+            .check(&withdraw(unwrapper, true), Address::repeat_byte(0x55))
+            .is_err());
+        assert!(unwrap
+            .check(&withdraw(Address::repeat_byte(0x44), true), weth)
+            .is_err());
+        assert!(WethUnwrap::default()
+            .check(&withdraw(unwrapper, true), weth)
+            .is_err());
+    }
+}
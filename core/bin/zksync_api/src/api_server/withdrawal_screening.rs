@@ -0,0 +1,104 @@
+//! Screening of the L1 recipients of the withdrawals.
+//!
+//! Funds withdrawn to a contract which can't receive ETH, or to the deposit contract of another
+//! rollup, are usually lost for good. Operator configures the list of such addresses, and the
+//! withdrawals to them are logged and counted, or rejected if the blocking is enabled.
+
+// Built-in uses
+use std::collections::HashSet;
+// Workspace uses
+use zksync_config::configs::api::CommonApiConfig;
+use zksync_types::{Address, ZkSyncTx};
+// Local uses
+use crate::api_server::tx_sender::SubmitError;
+
+#[derive(Debug, Clone, Default)]
+pub struct WithdrawalScreening {
+    addresses: HashSet<Address>,
+    block: bool,
+}
+
+impl WithdrawalScreening {
+    pub fn from_config(config: &CommonApiConfig) -> Self {
+        Self {
+            addresses: config
+                .screened_withdrawal_addresses
+                .iter()
+                .copied()
+                .collect(),
+            block: config.block_screened_withdrawals,
+        }
+    }
+
+    /// Checks the L1 recipient of the withdrawn funds, returning an error only if the recipient
+    /// is screened and the blocking is enabled.
+    pub fn check(&self, tx: &ZkSyncTx) -> Result<(), SubmitError> {
+        let recipient = match tx {
+            ZkSyncTx::Withdraw(tx) => tx.to,
+            ZkSyncTx::WithdrawNFT(tx) => tx.to,
+            ZkSyncTx::ForcedExit(tx) => tx.target,
+            _ => return Ok(()),
+        };
+        if !self.addresses.contains(&recipient) {
+            return Ok(());
+        }
+
+        metrics::increment_counter!(
+            "api.withdrawal_screening.hits",
+            "name" => tx.variance_name(),
+            "blocked" => self.block.to_string()
+        );
+        if self.block {
+            return Err(SubmitError::IncorrectTx(format!(
+                "Withdrawal recipient {:?} is known to be unable to receive the funds",
+                recipient
+            )));
+        }
+        vlog::warn!(
+            "Withdrawal {} is sent to the screened address {:?}",
+            tx.hash(),
+            recipient
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{AccountId, Nonce, TokenId, Withdraw};
+
+    fn withdraw(to: Address) -> ZkSyncTx {
+        Withdraw::new(
+            AccountId(1),
+            Address::repeat_byte(0x11),
+            to,
+            TokenId(0),
+            1u32.into(),
+            0u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        )
+        .into()
+    }
+
+    #[test]
+    fn screened_recipients() {
+        let screened = Address::repeat_byte(0x22);
+        let mut screening = WithdrawalScreening {
+            addresses: vec![screened].into_iter().collect(),
+            block: false,
+        };
+        assert!(screening.check(&withdraw(screened)).is_ok());
+
+        screening.block = true;
+        assert!(screening.check(&withdraw(screened)).is_err());
+        assert!(screening
+            .check(&withdraw(Address::repeat_byte(0x33)))
+            .is_ok());
+        assert!(WithdrawalScreening::default()
+            .check(&withdraw(screened))
+            .is_ok());
+    }
+}
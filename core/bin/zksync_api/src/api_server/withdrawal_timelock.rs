@@ -0,0 +1,206 @@
+//! Withdrawal timelocks of the accounts.
+//!
+//! The owner of the account can opt into the timelock, so the withdrawals worth at least the
+//! threshold are not sent to the mempool right away. Such withdrawals are verified and held in the
+//! database for the given number of blocks, during which the owner can cancel them with the
+//! Ethereum signature. Once the timelock expires, the background task sends the withdrawals to the
+//! mempool, so they're included into the blocks as usual. Every change of the status of the held
+//! withdrawal is posted to the configured webhook.
+
+// Built-in uses
+use std::time::Duration;
+
+// External uses
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
+use tokio::{runtime::Runtime, time};
+
+// Workspace uses
+use zksync_api_types::v02::transaction::{
+    PendingWithdrawal, PendingWithdrawalStatus, WithdrawalTimelockSettings,
+};
+use zksync_config::configs::api::RestApiConfig;
+use zksync_mempool::MempoolTransactionRequest;
+use zksync_storage::{
+    withdrawal_timelocks::{
+        records::{StoragePendingWithdrawal, StorageWithdrawalTimelock},
+        WITHDRAWAL_CANCELLED, WITHDRAWAL_PENDING, WITHDRAWAL_REJECTED, WITHDRAWAL_RELEASED,
+    },
+    ConnectionPool,
+};
+use zksync_types::{
+    tx::{SignedZkSyncTx, TxHash},
+    AccountId, BlockNumber,
+};
+use zksync_utils::panic_notify::ThreadPanicNotify;
+
+/// Timeout of the webhook requests.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn timelock_settings_from_record(
+    record: StorageWithdrawalTimelock,
+) -> WithdrawalTimelockSettings {
+    WithdrawalTimelockSettings {
+        threshold_usd: record.threshold_usd as u64,
+        delay_blocks: record.delay_blocks as u32,
+    }
+}
+
+pub fn pending_withdrawal_from_record(
+    record: StoragePendingWithdrawal,
+) -> anyhow::Result<PendingWithdrawal> {
+    let tx_hash = TxHash::from_slice(&record.tx_hash)
+        .ok_or_else(|| anyhow::anyhow!("Incorrect tx hash in the database"))?;
+    let status = match record.status.as_str() {
+        WITHDRAWAL_PENDING => PendingWithdrawalStatus::Pending,
+        WITHDRAWAL_RELEASED => PendingWithdrawalStatus::Released,
+        WITHDRAWAL_CANCELLED => PendingWithdrawalStatus::Cancelled,
+        WITHDRAWAL_REJECTED => PendingWithdrawalStatus::Rejected,
+        status => anyhow::bail!("Unknown status of the held withdrawal: {}", status),
+    };
+    Ok(PendingWithdrawal {
+        tx_hash,
+        account_id: AccountId(record.account_id as u32),
+        release_block: BlockNumber(record.release_block as u32),
+        status,
+        fail_reason: record.fail_reason,
+        created_at: record.created_at,
+    })
+}
+
+/// Sends the withdrawals which timelocks have expired to the mempool.
+async fn release_withdrawals(
+    connection_pool: &ConnectionPool,
+    mempool_tx_sender: &mpsc::Sender<MempoolTransactionRequest>,
+) -> anyhow::Result<()> {
+    // The connection is not held while the withdrawals are sent, since the mempool handler
+    // takes its connections from the same quota.
+    let releasable = {
+        let mut storage = connection_pool.access_storage().await?;
+        let last_block = storage
+            .chain()
+            .block_schema()
+            .get_last_saved_block()
+            .await?;
+        storage
+            .withdrawal_timelocks_schema()
+            .load_releasable_withdrawals(last_block)
+            .await?
+    };
+
+    for record in releasable {
+        let tx_hash = TxHash::from_slice(&record.tx_hash)
+            .ok_or_else(|| anyhow::anyhow!("Incorrect tx hash in the database"))?;
+        let tx: SignedZkSyncTx = serde_json::from_value(record.tx)?;
+        // The status is changed first, so the withdrawal cancelled meanwhile is never released.
+        let is_released = connection_pool
+            .access_storage()
+            .await?
+            .withdrawal_timelocks_schema()
+            .update_withdrawal_status(&tx_hash, WITHDRAWAL_PENDING, WITHDRAWAL_RELEASED, None)
+            .await?;
+        if !is_released {
+            continue;
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        mempool_tx_sender
+            .clone()
+            .send(MempoolTransactionRequest::NewTx(Box::new(tx), sender))
+            .await?;
+        match receiver.await? {
+            Ok(()) => {
+                metrics::increment_counter!("api.withdrawal_timelock.released_txs");
+            }
+            Err(err) => {
+                vlog::warn!("Held withdrawal {} was rejected: {}", tx_hash, err);
+                metrics::increment_counter!("api.withdrawal_timelock.rejected_txs");
+                connection_pool
+                    .access_storage()
+                    .await?
+                    .withdrawal_timelocks_schema()
+                    .update_withdrawal_status(
+                        &tx_hash,
+                        WITHDRAWAL_RELEASED,
+                        WITHDRAWAL_REJECTED,
+                        Some(err.to_string()),
+                    )
+                    .await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Posts the status changes of the held withdrawals to the webhook. The changes are posted until
+/// the webhook accepts them, so the hook may receive the same change more than once.
+async fn notify_withdrawals(
+    connection_pool: &ConnectionPool,
+    client: &reqwest::Client,
+    webhook_url: &reqwest::Url,
+) -> anyhow::Result<()> {
+    let mut storage = connection_pool.access_storage().await?;
+    let unnotified = storage
+        .withdrawal_timelocks_schema()
+        .load_unnotified_withdrawals()
+        .await?;
+
+    for record in unnotified {
+        let status = record.status.clone();
+        let withdrawal = pending_withdrawal_from_record(record)?;
+        client
+            .post(webhook_url.clone())
+            .timeout(WEBHOOK_TIMEOUT)
+            .json(&withdrawal)
+            .send()
+            .await?
+            .error_for_status()?;
+        metrics::increment_counter!("api.withdrawal_timelock.notifications");
+        storage
+            .withdrawal_timelocks_schema()
+            .mark_withdrawal_notified(&withdrawal.tx_hash, &status)
+            .await?;
+    }
+    Ok(())
+}
+
+pub fn start_withdrawal_timelock_releaser_detached(
+    panic_notify: mpsc::Sender<bool>,
+    connection_pool: ConnectionPool,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    config: &RestApiConfig,
+) {
+    let interval = config.withdrawal_timelock_poll_interval();
+    let webhook_url = reqwest::Url::parse(&config.withdrawal_timelock_webhook_url).ok();
+    std::thread::Builder::new()
+        .name("rest-withdrawal-timelock-releaser".to_string())
+        .spawn(move || {
+            let _panic_sentinel = ThreadPanicNotify(panic_notify.clone());
+
+            let runtime = Runtime::new().expect("tokio runtime creation");
+
+            let releaser_task = async move {
+                let client = reqwest::Client::new();
+                let mut timer = time::interval(interval);
+                loop {
+                    timer.tick().await;
+                    if let Err(err) =
+                        release_withdrawals(&connection_pool, &mempool_tx_sender).await
+                    {
+                        vlog::error!("Can't release the held withdrawals: {}", err);
+                    }
+                    if let Some(webhook_url) = &webhook_url {
+                        if let Err(err) =
+                            notify_withdrawals(&connection_pool, &client, webhook_url).await
+                        {
+                            vlog::warn!("Can't notify about the held withdrawals: {}", err);
+                        }
+                    }
+                }
+            };
+            runtime.block_on(releaser_task);
+        })
+        .expect("Withdrawal timelock releaser thread");
+}
@@ -0,0 +1,27 @@
+//! Generates the OpenAPI specification of the REST API v0.3.
+//!
+//! The specification is derived from the API types, so it's always in sync with the server.
+//! It's written to stdout unless the output file is specified.
+
+use std::{fs, path::PathBuf};
+
+use structopt::StructOpt;
+use zksync_api::api_server::rest::v03::openapi_spec;
+
+#[derive(Debug, StructOpt)]
+struct Opts {
+    /// File to write the specification to.
+    #[structopt(long, short)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let opts = Opts::from_args();
+    let spec = serde_json::to_string_pretty(&openapi_spec())?;
+
+    match opts.output {
+        Some(path) => fs::write(path, spec)?,
+        None => println!("{}", spec),
+    }
+    Ok(())
+}
@@ -2,7 +2,13 @@
 //! onchain `ChangePubKey` authorization or EIP1271 signature
 //! verification.
 
-use web3::{contract::Options, types::Address};
+use std::time::Duration;
+
+use tokio::time::timeout;
+use web3::{
+    contract::Options,
+    types::{Address, U256},
+};
 use zksync_contracts::eip1271_contract;
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
 use zksync_types::{
@@ -14,6 +20,10 @@ use zksync_types::{
 /// bytes4(keccak256("isValidSignature(bytes32,bytes)")
 pub const EIP1271_SUCCESS_RETURN_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
 
+/// Time given to the contract to check the EIP1271 signature, the signature is considered
+/// incorrect if the call takes longer.
+const EIP1271_CALL_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Clone)]
 pub struct EthereumChecker {
     client: EthereumGateway,
@@ -42,18 +52,22 @@ impl EthereumChecker {
     ) -> Result<bool, anyhow::Error> {
         let sign_message = Self::get_sign_message(message);
 
-        let call_result = self
-            .client
-            .call_contract_function(
-                "isValidSignature",
-                (sign_message, signature.0),
-                Some(address),
-                Options::default(),
-                None,
-                address,
-                eip1271_contract(),
-            )
-            .await;
+        let call = self.client.call_contract_function(
+            "isValidSignature",
+            (sign_message, signature.0),
+            Some(address),
+            Options::default(),
+            None,
+            address,
+            eip1271_contract(),
+        );
+        let call_result = match timeout(EIP1271_CALL_TIMEOUT, call).await {
+            Ok(call_result) => call_result,
+            Err(_) => {
+                vlog::warn!("EIP1271 signature check of {:?} has timed out", address);
+                return Ok(false);
+            }
+        };
 
         let received: [u8; 4] = match call_result {
             Ok(val) => val,
@@ -68,14 +82,13 @@ impl EthereumChecker {
         Ok(received == EIP1271_SUCCESS_RETURN_VALUE)
     }
 
-    pub async fn is_new_pubkey_hash_authorized(
+    /// Returns the auth fact set by the account for the `ChangePubKey` operation with the given nonce.
+    pub async fn get_auth_fact(
         &self,
         address: Address,
         nonce: Nonce,
-        pub_key_hash: &PubKeyHash,
-    ) -> Result<bool, anyhow::Error> {
-        let auth_fact: Vec<u8> = self
-            .client
+    ) -> Result<Vec<u8>, anyhow::Error> {
+        self.client
             .call_main_contract_function(
                 "authFacts",
                 (address, u64::from(*nonce)),
@@ -84,7 +97,36 @@ impl EthereumChecker {
                 None,
             )
             .await
-            .map_err(|e| anyhow::format_err!("Failed to query contract authFacts: {}", e))?;
+            .map_err(|e| anyhow::format_err!("Failed to query contract authFacts: {}", e))
+    }
+
+    /// Returns the time the reset of the auth fact with the given nonce was requested at,
+    /// `0` if the reset is not requested.
+    pub async fn get_auth_fact_reset_time(
+        &self,
+        address: Address,
+        nonce: Nonce,
+    ) -> Result<u64, anyhow::Error> {
+        self.client
+            .call_main_contract_function(
+                "authFactsResetTimer",
+                (address, u64::from(*nonce)),
+                None,
+                Options::default(),
+                None,
+            )
+            .await
+            .map(|res: U256| res.as_u64())
+            .map_err(|e| anyhow::format_err!("Failed to query contract authFactsResetTimer: {}", e))
+    }
+
+    pub async fn is_new_pubkey_hash_authorized(
+        &self,
+        address: Address,
+        nonce: Nonce,
+        pub_key_hash: &PubKeyHash,
+    ) -> Result<bool, anyhow::Error> {
+        let auth_fact = self.get_auth_fact(address, nonce).await?;
         Ok(auth_fact.as_slice() == tiny_keccak::keccak256(&pub_key_hash.data[..]))
     }
 }
@@ -124,7 +166,7 @@ mod tests {
             transport,
             zksync_contract(),
             Default::default(),
-            PrivateKeySigner::new(Default::default()),
+            PrivateKeySigner::new(Default::default()).into(),
             Default::default(),
             0,
             1.0,
@@ -41,3 +41,8 @@ pub(crate) const BASE_MINT_NFT_COST: u64 = VerifyCost::MINT_NFT_COST
 pub(crate) const BASE_SWAP_COST: u64 = CommitCost::SWAP_COST
     + VerifyCost::SWAP_COST
     + AMORTIZED_COST_PER_CHUNK * (SwapOp::CHUNKS as u64);
+/// Maximum gas the contract spends on minting the withdrawn NFT in the factory, if the minting
+/// requires more, the NFT is left pending to be withdrawn by the user.
+pub(crate) const WITHDRAWAL_NFT_GAS_LIMIT: u64 = 300_000;
+/// Part of the `WithdrawNFT` cost covering the minting in the factory.
+pub(crate) const BASE_WITHDRAW_NFT_MINT_COST: u64 = VerifyCost::PENDING_WITHDRAW_NFT_COST;
@@ -12,6 +12,7 @@ use std::time::Duration;
 
 // External deps
 use bigdecimal::BigDecimal;
+use chrono::Utc;
 use num::{
     rational::Ratio,
     traits::{Inv, Pow},
@@ -24,18 +25,19 @@ use tokio::time::Instant;
 
 // Workspace deps
 
-use zksync_config::configs::ticker::TokenPriceSource;
+use zksync_config::configs::ticker::{TokenLiquiditySource, TokenPriceSource};
 use zksync_storage::ConnectionPool;
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{
     gas_counter::GasCounter, tokens::ChangePubKeyFeeTypeArg, tx::ChangePubKeyType, Address,
     BatchFee, ChangePubKeyOp, Fee, MintNFTOp, OutputFeeType, SwapOp, Token, TokenId, TokenLike,
-    TransferOp, TransferToNewOp, TxFeeTypes, WithdrawNFTOp, WithdrawOp,
+    TokenPrice, TransferOp, TransferToNewOp, TxFeeTypes, WithdrawNFTOp, WithdrawOp,
 };
 use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 
 // Local deps
 use crate::fee_ticker::constants::AMORTIZED_COST_PER_CHUNK;
+use crate::fee_ticker::scaling::{FeeScalingSchedule, PipelineLoad, PipelineLoadCache};
 pub use crate::fee_ticker::ticker_info::{FeeTickerInfo, TickerInfo};
 use crate::fee_ticker::validator::FeeTokenValidator;
 use crate::fee_ticker::{
@@ -47,6 +49,7 @@ use crate::fee_ticker::{
 };
 
 mod constants;
+pub mod scaling;
 mod ticker_api;
 pub(crate) mod ticker_info;
 pub mod validator;
@@ -143,6 +146,12 @@ pub struct TickerConfig {
     pub scale_fee_coefficient: Ratio<BigUint>,
     pub max_blocks_to_aggregate: u32,
     pub subsidy_cpk_price_usd: Ratio<BigUint>,
+    /// Age after which the token price is considered stale, `None` disables the check.
+    pub stale_price_timeout: Option<Duration>,
+    /// Multiplier making the stale prices conservative, `None` rejects the fee quotes instead.
+    pub stale_price_multiplier: Option<Ratio<BigUint>>,
+    /// Schedule scaling the fees of L2 operations with the pipeline load, `None` disables it.
+    pub fee_scaling: Option<FeeScalingSchedule>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -173,6 +182,8 @@ pub enum PriceError {
     ApiError(String),
     #[error("Database error: {0}")]
     DBError(String),
+    #[error("Token price is stale: {0}")]
+    StalePrice(String),
 }
 
 impl PriceError {
@@ -187,6 +198,10 @@ impl PriceError {
     pub fn db_error(msg: impl Display) -> Self {
         Self::DBError(msg.to_string())
     }
+
+    pub fn stale_price(msg: impl Display) -> Self {
+        Self::StalePrice(msg.to_string())
+    }
 }
 
 #[derive(Clone)]
@@ -194,6 +209,7 @@ pub struct FeeTicker {
     info: Box<dyn FeeTickerInfo>,
     config: TickerConfig,
     validator: FeeTokenValidator,
+    pipeline_load: PipelineLoadCache,
 }
 
 const CPK_CREATE2_FEE_TYPE: OutputFeeType = OutputFeeType::ChangePubKey(
@@ -209,19 +225,44 @@ pub fn run_updaters(
     config: &zksync_config::TickerConfig,
 ) -> Vec<JoinHandle<()>> {
     let cache = (db_pool.clone(), TokenDBCache::new(TOKEN_INVALIDATE_CACHE));
-
-    let watcher = UniswapTokenWatcher::new(config.uniswap_url.clone());
-
-    let updater = MarketUpdater::new(cache, watcher);
-    let mut tasks = vec![tokio::spawn(
-        updater.keep_updated(config.token_market_update_time),
-    )];
     let client = reqwest::ClientBuilder::new()
         .timeout(CONNECTION_TIMEOUT)
         .connect_timeout(CONNECTION_TIMEOUT)
         .build()
         .expect("Failed to build reqwest::Client");
     let (price_source, base_url) = config.price_source();
+    let token_market_update_time = config.token_market_update_time;
+
+    // Tokens are acceptable for paying fees or not depending on the liquidity
+    // observed by the market updater, so illiquid tokens are disabled automatically.
+    let market_updater = match (config.liquidity_source, price_source) {
+        (TokenLiquiditySource::Uniswap, _) => {
+            let watcher = UniswapTokenWatcher::new(config.uniswap_url.clone());
+            let updater = MarketUpdater::new(cache, watcher);
+            tokio::spawn(updater.keep_updated(token_market_update_time))
+        }
+        (TokenLiquiditySource::PriceSource, TokenPriceSource::CoinMarketCap) => {
+            let watcher = CoinMarketCapAPI::new(
+                client.clone(),
+                base_url.parse().expect("Correct CoinMarketCap url"),
+            );
+            let updater = MarketUpdater::new(cache, watcher);
+            tokio::spawn(updater.keep_updated(token_market_update_time))
+        }
+        (TokenLiquiditySource::PriceSource, TokenPriceSource::CoinGecko) => {
+            let client = client.clone();
+            let base_url = base_url.clone();
+            tokio::spawn(async move {
+                let watcher =
+                    CoinGeckoAPI::new(client, base_url.parse().expect("Correct CoinGecko url"))
+                        .await
+                        .expect("failed to init CoinGecko client");
+                let updater = MarketUpdater::new(cache, watcher);
+                updater.keep_updated(token_market_update_time).await;
+            })
+        }
+    };
+    let mut tasks = vec![market_updater];
     let price_updater = match price_source {
         TokenPriceSource::CoinMarketCap => {
             let token_price_api =
@@ -255,6 +296,7 @@ impl FeeTicker {
             info,
             config,
             validator,
+            pipeline_load: PipelineLoadCache::default(),
         }
     }
 
@@ -275,11 +317,21 @@ impl FeeTicker {
             ),
             max_blocks_to_aggregate,
             subsidy_cpk_price_usd: config.subsidy_cpk_price_usd(),
+            stale_price_timeout: config.stale_price_timeout(),
+            stale_price_multiplier: if config.stale_price_multiplier_percent == 0 {
+                None
+            } else {
+                Some(Ratio::new(
+                    BigUint::from(config.stale_price_multiplier_percent),
+                    BigUint::from(100u32),
+                ))
+            },
+            fee_scaling: FeeScalingSchedule::from_config(&config),
         };
         let validator = FeeTokenValidator::new(
             cache,
             chrono::Duration::seconds(config.available_liquidity_seconds as i64),
-            BigDecimal::try_from(config.liquidity_volume).expect("Valid f64 for decimal"),
+            BigDecimal::try_from(config.min_market_volume()).expect("Valid f64 for decimal"),
             HashSet::from_iter(config.unconditionally_valid_tokens),
         );
         Self::new(info, ticker_config, validator)
@@ -294,6 +346,48 @@ impl FeeTicker {
         gas_price * BigUint::from(130u32) / BigUint::from(100u32)
     }
 
+    /// Returns the coefficient scaling the fees of L2 operations, including the multiplier
+    /// of the fee scaling schedule for the current load of the pipeline.
+    async fn scale_fee_coefficient(&self) -> anyhow::Result<Ratio<BigUint>> {
+        let schedule = match &self.config.fee_scaling {
+            Some(schedule) => schedule,
+            None => return Ok(self.config.scale_fee_coefficient.clone()),
+        };
+        let load = self.pipeline_load().await?;
+        Ok(&self.config.scale_fee_coefficient * schedule.multiplier(load))
+    }
+
+    /// Returns the fee scaling schedule, `None` if the fee scaling is disabled.
+    pub fn fee_scaling_schedule(&self) -> Option<&FeeScalingSchedule> {
+        self.config.fee_scaling.as_ref()
+    }
+
+    /// Returns the load of the pipeline, which is checked once per update interval of
+    /// the fee scaling schedule.
+    pub async fn pipeline_load(&self) -> anyhow::Result<PipelineLoad> {
+        let update_interval = self
+            .config
+            .fee_scaling
+            .as_ref()
+            .map(|schedule| schedule.update_interval)
+            .unwrap_or_default();
+        if let Some(load) = self.pipeline_load.get(update_interval).await {
+            return Ok(load);
+        }
+
+        let load = self.info.pipeline_load().await?;
+        self.pipeline_load.set(load).await;
+        metrics::gauge!(
+            "ticker.pipeline_load.mempool_size",
+            load.mempool_size as f64
+        );
+        metrics::gauge!(
+            "ticker.pipeline_load.prover_backlog",
+            load.prover_backlog as f64
+        );
+        Ok(load)
+    }
+
     pub async fn get_token_price(
         &self,
         token: TokenLike,
@@ -327,6 +421,20 @@ impl FeeTicker {
         tx_type: TxFeeTypes,
         token: TokenLike,
         recipient: Address,
+    ) -> Result<ResponseFee, anyhow::Error> {
+        self.get_fee_with_extra_gas(tx_type, token, recipient, BigUint::zero())
+            .await
+    }
+
+    /// Calculates the fee of the transaction which requires `extra_gas` to be spent on L1
+    /// in addition to the base cost of the operation, e.g. for minting the NFT withdrawn
+    /// to the custom factory.
+    pub async fn get_fee_with_extra_gas(
+        &self,
+        tx_type: TxFeeTypes,
+        token: TokenLike,
+        recipient: Address,
+        extra_gas: BigUint,
     ) -> Result<ResponseFee, anyhow::Error> {
         let start = Instant::now();
         let zkp_cost_chunk = self.config.zkp_cost_chunk_usd.clone();
@@ -338,6 +446,9 @@ impl FeeTicker {
         let token_usd_risk = self.token_usd_risk(&token).await?;
 
         let (fee_type, gas_tx_amount, op_chunks) = self.gas_tx_amount(tx_type, recipient).await?;
+        let gas_tx_amount = gas_tx_amount + extra_gas;
+
+        let scale_fee_coefficient = self.scale_fee_coefficient().await?;
 
         let zkp_fee = (zkp_cost_chunk * op_chunks) * &token_usd_risk;
         let mut normal_gas_fee =
@@ -351,7 +462,7 @@ impl FeeTicker {
                 | OutputFeeType::MintNFT
                 | OutputFeeType::Swap
         ) {
-            normal_gas_fee *= self.config.scale_fee_coefficient.clone();
+            normal_gas_fee *= scale_fee_coefficient;
         }
 
         let normal_fee = Fee::new(
@@ -409,6 +520,18 @@ impl FeeTicker {
         &self,
         token: TokenLike,
         txs: Vec<(TxFeeTypes, Address)>,
+    ) -> anyhow::Result<ResponseBatchFee> {
+        self.get_batch_fee_with_extra_gas(token, txs, BigUint::zero())
+            .await
+    }
+
+    /// Calculates the fee of the batch which transactions require `extra_gas` to be spent
+    /// on L1 in total in addition to the base cost of the operations.
+    pub async fn get_batch_fee_with_extra_gas(
+        &self,
+        token: TokenLike,
+        txs: Vec<(TxFeeTypes, Address)>,
+        extra_gas: BigUint,
     ) -> anyhow::Result<ResponseBatchFee> {
         let start = Instant::now();
         let zkp_cost_chunk = self.config.zkp_cost_chunk_usd.clone();
@@ -419,6 +542,7 @@ impl FeeTicker {
         let scale_gas_price = Self::risk_gas_price_estimate(gas_price_wei.clone());
         let wei_price_usd = self.wei_price_usd().await?;
         let token_usd_risk = self.token_usd_risk(&token).await?;
+        let scale_fee_coefficient = self.scale_fee_coefficient().await?;
 
         let mut total_normal_gas_tx_amount = Ratio::from(BigUint::zero());
         let mut total_op_chunks = Ratio::from(BigUint::zero());
@@ -471,7 +595,7 @@ impl FeeTicker {
                     | OutputFeeType::Swap
                     | OutputFeeType::MintNFT
             ) {
-                self.config.scale_fee_coefficient.clone() * gas_tx_amount
+                scale_fee_coefficient.clone() * gas_tx_amount
             } else {
                 gas_tx_amount.into()
             };
@@ -489,6 +613,11 @@ impl FeeTicker {
             }
         }
 
+        // The extra gas is spent on L1, so it's neither scaled nor subsidized.
+        let extra_gas = Ratio::from(extra_gas);
+        total_normal_gas_tx_amount += &extra_gas;
+        total_subsidized_gas_tx_amount += extra_gas;
+
         let normal_fee = {
             let total_zkp_fee = (&zkp_cost_chunk * total_op_chunks) * &token_usd_risk;
             let total_gas_fee =
@@ -520,12 +649,16 @@ impl FeeTicker {
 
     pub async fn wei_price_usd(&self) -> anyhow::Result<Ratio<BigUint>> {
         let start = Instant::now();
-        let res = self
+        let price = self
             .info
             .get_last_token_price(TokenLike::Id(TokenId(0)))
-            .await?
-            .usd_price
-            / BigUint::from(10u32).pow(18u32);
+            .await?;
+        // The higher price of ETH makes the gas part of the fee bigger.
+        let usd_price = match self.stale_price_multiplier(TokenId(0), &price)? {
+            Some(multiplier) => price.usd_price * multiplier,
+            None => price.usd_price,
+        };
+        let res = usd_price / BigUint::from(10u32).pow(18u32);
         metrics::histogram!("ticker.wei_price_usd", start.elapsed());
         Ok(res)
     }
@@ -539,12 +672,16 @@ impl FeeTicker {
             .cloned()
             .unwrap_or_else(|| Ratio::from_integer(1u32.into()));
 
-        let token_price_usd = self
+        let price = self
             .info
             .get_last_token_price(TokenLike::Id(token.id))
-            .await?
-            .usd_price
-            / BigUint::from(10u32).pow(u32::from(token.decimals));
+            .await?;
+        // The lower price of the token makes the fee in this token bigger.
+        let usd_price = match self.stale_price_multiplier(token.id, &price)? {
+            Some(multiplier) => price.usd_price / multiplier,
+            None => price.usd_price,
+        };
+        let token_price_usd = usd_price / BigUint::from(10u32).pow(u32::from(token.decimals));
         // TODO Check tokens fee allowance by non-zero price (ZKS-580)
         metrics::histogram!("ticker.token_usd_risk", start.elapsed());
         token_risk_factor
@@ -552,6 +689,65 @@ impl FeeTicker {
             .ok_or_else(|| anyhow::format_err!("Token is not acceptable for fee"))
     }
 
+    /// Checks the age of the token price used for the fee calculation. Returns the safety multiplier
+    /// if the price is stale, or the error if the stale prices are not accepted.
+    fn stale_price_multiplier(
+        &self,
+        token: TokenId,
+        price: &TokenPrice,
+    ) -> Result<Option<Ratio<BigUint>>, PriceError> {
+        let timeout = match self.config.stale_price_timeout {
+            Some(timeout) => timeout,
+            None => return Ok(None),
+        };
+        let age = Utc::now()
+            .signed_duration_since(price.last_updated)
+            .to_std()
+            .unwrap_or_default();
+        if age <= timeout {
+            return Ok(None);
+        }
+
+        metrics::increment_counter!("ticker.stale_price", "token" => token.to_string());
+        match &self.config.stale_price_multiplier {
+            Some(multiplier) => {
+                vlog::warn!(
+                    "Price of the token {} was updated {}s ago, using the safety multiplier",
+                    token,
+                    age.as_secs()
+                );
+                Ok(Some(multiplier.clone()))
+            }
+            None => Err(PriceError::stale_price(format!(
+                "price of the token {} was updated {}s ago",
+                token,
+                age.as_secs()
+            ))),
+        }
+    }
+
+    /// Returns the gas spent on L1 for minting the withdrawn NFT in addition to the base cost of
+    /// `WithdrawNFT`. The base cost covers the default factory, the custom factories may have
+    /// more expensive minting, which is limited by the contract though.
+    pub async fn withdraw_nft_extra_gas(
+        &self,
+        token: TokenId,
+        recipient: Address,
+    ) -> anyhow::Result<BigUint> {
+        let mint_gas = self
+            .info
+            .nft_factory_mint_gas(token, recipient)
+            .await?
+            .unwrap_or_default()
+            .min(BigUint::from(constants::WITHDRAWAL_NFT_GAS_LIMIT));
+        let base_gas = BigUint::from(constants::BASE_WITHDRAW_NFT_MINT_COST);
+        Ok(if mint_gas > base_gas {
+            mint_gas - base_gas
+        } else {
+            BigUint::zero()
+        })
+    }
+
     /// Returns `true` if account does not yet exist in the zkSync network.
     pub async fn is_account_new(&self, address: Address) -> anyhow::Result<bool> {
         self.info.is_account_new(address).await
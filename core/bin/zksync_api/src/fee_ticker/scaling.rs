@@ -0,0 +1,120 @@
+//! Fee scaling schedule adjusting the fees of L2 operations to the load of the pipeline.
+//!
+//! The load is the biggest of the mempool size and the prover backlog relatively to their
+//! configured capacities. The schedule is a list of load levels, each with the fee multiplier
+//! applied starting from this level, so the fees rise under congestion and fall when the
+//! pipeline is idle.
+
+// Built-in deps
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+// External deps
+use num::{rational::Ratio, BigUint};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+/// Load of the pipeline observed by the fee ticker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PipelineLoad {
+    /// Amount of the transactions in the mempool.
+    pub mempool_size: u32,
+    /// Amount of the pending prover jobs.
+    pub prover_backlog: u32,
+}
+
+/// Level of the fee scaling schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeScalingLevel {
+    /// Load of the pipeline in percent of the full load starting from which the level applies.
+    pub load_percent: u32,
+    /// Fee multiplier in percent.
+    pub fee_percent: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeScalingSchedule {
+    /// Mempool size corresponding to the full load.
+    pub mempool_capacity: u32,
+    /// Prover backlog corresponding to the full load.
+    pub prover_capacity: u32,
+    /// Levels sorted by the load.
+    pub levels: Vec<FeeScalingLevel>,
+    /// Interval between the checks of the pipeline load.
+    pub update_interval: Duration,
+}
+
+impl FeeScalingSchedule {
+    /// Creates the schedule from the config, returns `None` if the fee scaling is disabled.
+    pub fn from_config(config: &zksync_config::TickerConfig) -> Option<Self> {
+        let update_interval = config.fee_scaling_update_interval()?;
+        assert_eq!(
+            config.fee_scaling_load_levels.len(),
+            config.fee_scaling_fee_percents.len(),
+            "Each load level of the fee scaling must have the fee multiplier"
+        );
+
+        let mut levels: Vec<_> = config
+            .fee_scaling_load_levels
+            .iter()
+            .zip(&config.fee_scaling_fee_percents)
+            .map(|(&load_percent, &fee_percent)| FeeScalingLevel {
+                load_percent,
+                fee_percent,
+            })
+            .collect();
+        levels.sort_by_key(|level| level.load_percent);
+
+        Some(Self {
+            mempool_capacity: config.fee_scaling_mempool_capacity,
+            prover_capacity: config.fee_scaling_prover_capacity,
+            levels,
+            update_interval,
+        })
+    }
+
+    /// Returns the load of the pipeline in percent of the full load.
+    pub fn load_percent(&self, load: PipelineLoad) -> u32 {
+        let percent = |value: u32, capacity: u32| {
+            (u64::from(value) * 100 / u64::from(capacity.max(1))).min(u64::from(u32::MAX)) as u32
+        };
+        percent(load.mempool_size, self.mempool_capacity)
+            .max(percent(load.prover_backlog, self.prover_capacity))
+    }
+
+    /// Returns the fee multiplier in percent for the given load.
+    /// Fees are not scaled below the first level of the schedule.
+    pub fn fee_percent(&self, load_percent: u32) -> u32 {
+        self.levels
+            .iter()
+            .rev()
+            .find(|level| level.load_percent <= load_percent)
+            .map(|level| level.fee_percent)
+            .unwrap_or(100)
+    }
+
+    /// Returns the fee multiplier for the given load.
+    pub fn multiplier(&self, load: PipelineLoad) -> Ratio<BigUint> {
+        Ratio::new(
+            BigUint::from(self.fee_percent(self.load_percent(load))),
+            BigUint::from(100u32),
+        )
+    }
+}
+
+/// Last observed load of the pipeline, shared between the clones of the fee ticker.
+#[derive(Debug, Clone, Default)]
+pub struct PipelineLoadCache(Arc<RwLock<Option<(Instant, PipelineLoad)>>>);
+
+impl PipelineLoadCache {
+    /// Returns the cached load if it was observed less than `max_age` ago.
+    pub async fn get(&self, max_age: Duration) -> Option<PipelineLoad> {
+        match *self.0.read().await {
+            Some((observed_at, load)) if observed_at.elapsed() < max_age => Some(load),
+            _ => None,
+        }
+    }
+
+    pub async fn set(&self, load: PipelineLoad) {
+        *self.0.write().await = Some((Instant::now(), load));
+    }
+}
@@ -11,6 +11,7 @@ use zksync_utils::{
 };
 
 use crate::fee_ticker::{
+    scaling::{FeeScalingLevel, FeeScalingSchedule, PipelineLoad},
     ticker_api::TokenPriceAPI,
     validator::{cache::TokenInMemoryCache, FeeTokenValidator},
 };
@@ -114,6 +115,9 @@ pub fn get_test_ticker_config() -> TickerConfig {
         scale_fee_coefficient: Ratio::new(BigUint::from(150u32), BigUint::from(100u32)),
         max_blocks_to_aggregate: 5,
         subsidy_cpk_price_usd: scaled_u64_to_ratio(SUBSIDY_CPK_PRICE_USD_SCALED),
+        stale_price_timeout: None,
+        stale_price_multiplier: None,
+        fee_scaling: None,
     }
 }
 
@@ -130,6 +134,9 @@ impl FeeTickerAPI for MockApiProvider {
 struct MockTickerInfo {
     pub future_blocks: BlocksInFutureAggregatedOperations,
     pub remaining_chunks: Option<usize>,
+    pub nft_mint_gas: Option<u64>,
+    pub price_age: chrono::Duration,
+    pub pipeline_load: PipelineLoad,
 }
 
 impl Default for MockTickerInfo {
@@ -141,6 +148,9 @@ impl Default for MockTickerInfo {
                 blocks_to_execute: 0,
             },
             remaining_chunks: None,
+            nft_mint_gas: None,
+            price_age: chrono::Duration::zero(),
+            pipeline_load: PipelineLoad::default(),
         }
     }
 }
@@ -167,7 +177,7 @@ impl FeeTickerInfo for MockTickerInfo {
             if TokenLike::Id(test_token.id) == token {
                 let token_price = TokenPrice {
                     usd_price: test_token.price_usd,
-                    last_updated: Utc::now(),
+                    last_updated: Utc::now() - self.price_age,
                 };
                 return Ok(token_price);
             }
@@ -195,6 +205,18 @@ impl FeeTickerInfo for MockTickerInfo {
         unreachable!("incorrect token input")
     }
 
+    async fn nft_factory_mint_gas(
+        &self,
+        _token: TokenId,
+        _recipient: Address,
+    ) -> anyhow::Result<Option<BigUint>> {
+        Ok(self.nft_mint_gas.map(BigUint::from))
+    }
+
+    async fn pipeline_load(&self) -> anyhow::Result<PipelineLoad> {
+        Ok(self.pipeline_load)
+    }
+
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
@@ -708,3 +730,206 @@ fn test_zero_price_token_fee() {
     ))
     .unwrap_err();
 }
+
+#[test]
+fn test_withdraw_nft_custom_factory_fee() {
+    let ticker_with_mint_gas = |nft_mint_gas| {
+        let validator = FeeTokenValidator::new(
+            TokenInMemoryCache::new(),
+            chrono::Duration::seconds(100),
+            BigDecimal::from(100),
+            Default::default(),
+        );
+        let info = MockTickerInfo {
+            nft_mint_gas,
+            ..Default::default()
+        };
+        FeeTicker::new(Box::new(info), get_test_ticker_config(), validator)
+    };
+    let nft = TokenId(zksync_crypto::params::MIN_NFT_TOKEN_ID);
+    let extra_gas = |ticker: &FeeTicker| {
+        block_on(ticker.withdraw_nft_extra_gas(nft, Address::default())).unwrap()
+    };
+
+    // The default factory and the cheap factories are covered by the base cost.
+    assert!(extra_gas(&ticker_with_mint_gas(None)).is_zero());
+    assert!(extra_gas(&ticker_with_mint_gas(Some(100_000))).is_zero());
+    // The minting can't cost more than the contract allows to spend.
+    let ticker = ticker_with_mint_gas(Some(1_000_000));
+    let extra = extra_gas(&ticker);
+    assert_eq!(
+        extra,
+        BigUint::from(constants::WITHDRAWAL_NFT_GAS_LIMIT - constants::BASE_WITHDRAW_NFT_MINT_COST)
+    );
+
+    let token = TokenLike::Id(TestToken::eth().id);
+    let default_fee = block_on(ticker.get_fee_from_ticker_in_wei(
+        TxFeeTypes::WithdrawNFT,
+        token.clone(),
+        Address::default(),
+    ))
+    .unwrap();
+    let custom_fee = block_on(ticker.get_fee_with_extra_gas(
+        TxFeeTypes::WithdrawNFT,
+        token.clone(),
+        Address::default(),
+        extra.clone(),
+    ))
+    .unwrap();
+    assert!(custom_fee.normal_fee.total_fee > default_fee.normal_fee.total_fee);
+
+    let batch_fee = block_on(ticker.get_batch_fee_with_extra_gas(
+        token,
+        vec![(TxFeeTypes::WithdrawNFT, Address::default())],
+        extra,
+    ))
+    .unwrap();
+    assert_eq!(
+        custom_fee.normal_fee.total_fee,
+        batch_fee.normal_fee.total_fee
+    );
+}
+
+#[test]
+fn test_stale_price_fee() {
+    let ticker_with_stale_prices = |stale_price_multiplier| {
+        let validator = FeeTokenValidator::new(
+            TokenInMemoryCache::new(),
+            chrono::Duration::seconds(100),
+            BigDecimal::from(100),
+            Default::default(),
+        );
+        let info = MockTickerInfo {
+            price_age: chrono::Duration::hours(2),
+            ..Default::default()
+        };
+        let config = TickerConfig {
+            stale_price_timeout: Some(Duration::from_secs(3600)),
+            stale_price_multiplier,
+            ..get_test_ticker_config()
+        };
+        FeeTicker::new(Box::new(info), config, validator)
+    };
+    let validator = FeeTokenValidator::new(
+        TokenInMemoryCache::new(),
+        chrono::Duration::seconds(100),
+        BigDecimal::from(100),
+        Default::default(),
+    );
+    let fresh_ticker = FeeTicker::new(
+        Box::new(MockTickerInfo::default()),
+        get_test_ticker_config(),
+        validator,
+    );
+    let token = TokenLike::Id(TestToken::hex().id);
+    let fee = |ticker: &FeeTicker| {
+        block_on(ticker.get_fee_from_ticker_in_wei(
+            TxFeeTypes::Transfer,
+            token.clone(),
+            Address::default(),
+        ))
+    };
+
+    // Stale prices are rejected without the safety multiplier.
+    let err = fee(&ticker_with_stale_prices(None)).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<PriceError>(),
+        Some(PriceError::StalePrice(_))
+    ));
+    let err = block_on(ticker_with_stale_prices(None).get_batch_from_ticker_in_wei(
+        token.clone(),
+        vec![(TxFeeTypes::Transfer, Address::default())],
+    ))
+    .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<PriceError>(),
+        Some(PriceError::StalePrice(_))
+    ));
+
+    // Otherwise the fee is calculated with the conservative prices.
+    let multiplier = Ratio::new(BigUint::from(120u32), BigUint::from(100u32));
+    let fresh_fee = fee(&fresh_ticker).unwrap();
+    let stale_fee = fee(&ticker_with_stale_prices(Some(multiplier))).unwrap();
+    assert!(stale_fee.normal_fee.total_fee > fresh_fee.normal_fee.total_fee);
+}
+
+#[test]
+fn test_fee_scaling_schedule() {
+    let schedule = FeeScalingSchedule {
+        mempool_capacity: 1000,
+        prover_capacity: 10,
+        levels: vec![
+            FeeScalingLevel {
+                load_percent: 0,
+                fee_percent: 90,
+            },
+            FeeScalingLevel {
+                load_percent: 10,
+                fee_percent: 100,
+            },
+            FeeScalingLevel {
+                load_percent: 100,
+                fee_percent: 150,
+            },
+        ],
+        update_interval: Duration::from_secs(60),
+    };
+    let ticker_with_load = |pipeline_load| {
+        let validator = FeeTokenValidator::new(
+            TokenInMemoryCache::new(),
+            chrono::Duration::seconds(100),
+            BigDecimal::from(100),
+            Default::default(),
+        );
+        let info = MockTickerInfo {
+            pipeline_load,
+            ..Default::default()
+        };
+        let config = TickerConfig {
+            fee_scaling: Some(schedule.clone()),
+            ..get_test_ticker_config()
+        };
+        FeeTicker::new(Box::new(info), config, validator)
+    };
+    let load = |mempool_size, prover_backlog| PipelineLoad {
+        mempool_size,
+        prover_backlog,
+    };
+
+    // The load is the biggest of the mempool and the prover loads.
+    assert_eq!(schedule.load_percent(load(0, 0)), 0);
+    assert_eq!(schedule.load_percent(load(500, 2)), 50);
+    assert_eq!(schedule.load_percent(load(100, 20)), 200);
+    assert_eq!(schedule.fee_percent(5), 90);
+    assert_eq!(schedule.fee_percent(50), 100);
+    assert_eq!(schedule.fee_percent(200), 150);
+
+    let token = TokenLike::Id(TestToken::eth().id);
+    let fee = |ticker: &FeeTicker, tx_type| {
+        block_on(ticker.get_fee_from_ticker_in_wei(tx_type, token.clone(), Address::default()))
+            .unwrap()
+            .normal_fee
+            .total_fee
+    };
+    let idle = ticker_with_load(load(0, 0));
+    let normal = ticker_with_load(load(500, 0));
+    let congested = ticker_with_load(load(0, 15));
+    assert!(fee(&idle, TxFeeTypes::Transfer) < fee(&normal, TxFeeTypes::Transfer));
+    assert!(fee(&normal, TxFeeTypes::Transfer) < fee(&congested, TxFeeTypes::Transfer));
+    // Only the fees of L2 operations are scaled.
+    assert_eq!(
+        fee(&idle, TxFeeTypes::Withdraw),
+        fee(&congested, TxFeeTypes::Withdraw)
+    );
+
+    let batch_fee = |ticker: &FeeTicker| {
+        block_on(ticker.get_batch_from_ticker_in_wei(
+            token.clone(),
+            vec![(TxFeeTypes::Transfer, Address::default())],
+        ))
+        .unwrap()
+        .normal_fee
+        .total_fee
+    };
+    assert_eq!(batch_fee(&congested), fee(&congested, TxFeeTypes::Transfer));
+}
@@ -1,6 +1,7 @@
-use super::{TokenPriceAPI, REQUEST_TIMEOUT};
-use crate::fee_ticker::ticker_api::PriceError;
+use super::{TokenPriceAPI, MARKET_VOLUME_PRECISION, REQUEST_TIMEOUT};
+use crate::fee_ticker::{ticker_api::PriceError, validator::watcher::TokenWatcher};
 use async_trait::async_trait;
+use bigdecimal::{BigDecimal, Zero};
 use chrono::{DateTime, NaiveDateTime, Utc};
 use num::rational::Ratio;
 use num::BigUint;
@@ -10,7 +11,7 @@ use std::collections::HashMap;
 use std::str::FromStr;
 use std::time::Instant;
 use zksync_types::{Address, Token, TokenPrice};
-use zksync_utils::{remove_prefix, UnsignedRatioSerializeAsDecimal};
+use zksync_utils::{ratio_to_big_decimal, remove_prefix, UnsignedRatioSerializeAsDecimal};
 
 #[derive(Debug, Clone)]
 pub struct CoinGeckoAPI {
@@ -52,12 +53,8 @@ impl CoinGeckoAPI {
             token_ids,
         })
     }
-}
 
-#[async_trait]
-impl TokenPriceAPI for CoinGeckoAPI {
-    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
-        let start = Instant::now();
+    async fn get_market_chart(&self, token: &Token) -> Result<CoinGeckoMarketChart, PriceError> {
         let token_id = self.token_ids.get(&token.address).ok_or_else(|| {
             PriceError::token_not_found(format!(
                 "Token '{}, {:?}' is not listed on CoinGecko",
@@ -85,6 +82,15 @@ impl TokenPriceAPI for CoinGeckoAPI {
             .json::<CoinGeckoMarketChart>()
             .await
             .map_err(PriceError::api_error)?;
+        Ok(market_chart)
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for CoinGeckoAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let start = Instant::now();
+        let market_chart = self.get_market_chart(token).await?;
 
         let last_updated_timestamp_ms = market_chart
             .prices
@@ -132,6 +138,27 @@ impl TokenPriceAPI for CoinGeckoAPI {
     }
 }
 
+/// Liquidity of the token is its trading volume in USD over the last 24 hours.
+#[async_trait]
+impl TokenWatcher for CoinGeckoAPI {
+    async fn get_token_market_volume(&mut self, token: &Token) -> anyhow::Result<BigDecimal> {
+        let start = Instant::now();
+        let market_chart = match self.get_market_chart(token).await {
+            Ok(market_chart) => market_chart,
+            // Tokens not listed on CoinGecko have no confirmed liquidity.
+            Err(PriceError::TokenNotFound(_)) => return Ok(BigDecimal::zero()),
+            Err(err) => return Err(err.into()),
+        };
+        let volume = market_chart
+            .total_volumes
+            .last()
+            .map(|volume| ratio_to_big_decimal(&volume.1, MARKET_VOLUME_PRECISION))
+            .unwrap_or_default();
+        metrics::histogram!("ticker.coingecko.market_volume", start.elapsed());
+        Ok(volume)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoinGeckoTokenInfo {
     pub(crate) id: String,
@@ -150,6 +177,9 @@ pub struct CoinGeckoTokenPrice(
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoinGeckoMarketChart {
     pub(crate) prices: Vec<CoinGeckoTokenPrice>,
+    /// Trading volumes in USD over the last 24 hours.
+    #[serde(default)]
+    pub(crate) total_volumes: Vec<CoinGeckoTokenPrice>,
 }
 
 #[cfg(test)]
@@ -168,4 +198,24 @@ mod tests {
             .await
             .expect("Failed to get data from ticker");
     }
+
+    #[test]
+    fn parse_market_chart() {
+        let example = r#"{
+            "prices": [[1635292800000, 4157.62], [1635296400000, 4190.27]],
+            "market_caps": [[1635292800000, 490783684646.13], [1635296400000, 494105107908.66]],
+            "total_volumes": [[1635292800000, 24592426845.19], [1635296400000, 24995553495.54]]
+        }"#;
+        let market_chart = serde_json::from_str::<CoinGeckoMarketChart>(example).unwrap();
+        assert_eq!(market_chart.prices.len(), 2);
+        assert_eq!(
+            ratio_to_big_decimal(&market_chart.total_volumes[1].1, MARKET_VOLUME_PRECISION),
+            BigDecimal::from_str("24995553495.54").unwrap()
+        );
+
+        // Volumes are optional in the responses of the dev ticker.
+        let example = r#"{"prices": [[1635292800000, 4157.62]]}"#;
+        let market_chart = serde_json::from_str::<CoinGeckoMarketChart>(example).unwrap();
+        assert!(market_chart.total_volumes.is_empty());
+    }
 }
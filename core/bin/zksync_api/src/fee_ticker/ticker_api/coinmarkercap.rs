@@ -2,15 +2,16 @@
 use std::collections::HashMap;
 // External deps
 use async_trait::async_trait;
+use bigdecimal::{BigDecimal, Zero};
 use chrono::{DateTime, Utc};
 use num::{rational::Ratio, BigUint};
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 // Workspace deps
-use super::{TokenPriceAPI, REQUEST_TIMEOUT};
-use crate::fee_ticker::PriceError;
+use super::{TokenPriceAPI, MARKET_VOLUME_PRECISION, REQUEST_TIMEOUT};
+use crate::fee_ticker::{validator::watcher::TokenWatcher, PriceError};
 use zksync_types::{Token, TokenLike, TokenPrice};
-use zksync_utils::UnsignedRatioSerializeAsDecimal;
+use zksync_utils::{ratio_to_big_decimal, UnsignedRatioSerializeAsDecimal};
 
 #[derive(Debug, Clone)]
 pub struct CoinMarketCapAPI {
@@ -24,9 +25,8 @@ impl CoinMarketCapAPI {
     }
 }
 
-#[async_trait]
-impl TokenPriceAPI for CoinMarketCapAPI {
-    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+impl CoinMarketCapAPI {
+    async fn get_usd_quote(&self, token: &Token) -> Result<CoinmarketcapQuote, PriceError> {
         let token_symbol = token.symbol.as_str();
         let request_url = self
             .base_url
@@ -57,6 +57,14 @@ impl TokenPriceAPI for CoinMarketCapAPI {
             .quote
             .remove(&TokenLike::Symbol("USD".to_string()))
             .ok_or_else(|| PriceError::api_error("Could not found usd quote in response"))?;
+        Ok(usd_quote)
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for CoinMarketCapAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let usd_quote = self.get_usd_quote(token).await?;
         Ok(TokenPrice {
             usd_price: usd_quote.price,
             last_updated: usd_quote.last_updated,
@@ -64,10 +72,28 @@ impl TokenPriceAPI for CoinMarketCapAPI {
     }
 }
 
+/// Liquidity of the token is its trading volume in USD over the last 24 hours.
+#[async_trait]
+impl TokenWatcher for CoinMarketCapAPI {
+    async fn get_token_market_volume(&mut self, token: &Token) -> anyhow::Result<BigDecimal> {
+        match self.get_usd_quote(token).await {
+            Ok(usd_quote) => Ok(ratio_to_big_decimal(
+                &usd_quote.volume_24h,
+                MARKET_VOLUME_PRECISION,
+            )),
+            // Tokens not listed on CoinMarketCap have no confirmed liquidity.
+            Err(PriceError::TokenNotFound(_)) => Ok(BigDecimal::zero()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub(super) struct CoinmarketcapQuote {
     #[serde(with = "UnsignedRatioSerializeAsDecimal")]
     pub price: Ratio<BigUint>,
+    #[serde(default, with = "UnsignedRatioSerializeAsDecimal")]
+    pub volume_24h: Ratio<BigUint>,
     pub last_updated: DateTime<Utc>,
 }
 
@@ -161,6 +187,11 @@ mod test {
             UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot("170.692214992")
                 .unwrap()
         );
+        assert_eq!(
+            quote.volume_24h,
+            UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot("22515583743.3856")
+                .unwrap()
+        );
         assert_eq!(
             quote.last_updated,
             DateTime::<Utc>::from_str("2020-04-17T04:50:41.000Z").unwrap()
@@ -18,6 +18,8 @@ const UPDATE_PRICE_INTERVAL_SECS: u64 = 10 * 60;
 pub const REQUEST_TIMEOUT: Duration = Duration::from_millis(700);
 /// Configuration parameter of the reqwest Client
 pub const CONNECTION_TIMEOUT: Duration = Duration::from_millis(700);
+/// Precision of the token market volumes in USD reported by the price sources.
+pub const MARKET_VOLUME_PRECISION: usize = 2;
 
 #[async_trait]
 pub trait TokenPriceAPI {
@@ -5,20 +5,28 @@
 #[cfg(test)]
 use std::any::Any;
 
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Instant;
 // External deps
 use anyhow::format_err;
 use async_trait::async_trait;
 use chrono::Utc;
+use ethabi::Token as AbiToken;
 use num::rational::Ratio;
 use num::BigUint;
+use tokio::sync::RwLock;
+use web3::types::{Bytes, CallRequest};
 // Workspace deps
+use zksync_api_types::v02::token::ApiNFT;
+use zksync_eth_client::EthereumGateway;
 use zksync_storage::ConnectionPool;
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::aggregated_operations::AggregatedActionType;
-use zksync_types::{Address, Token, TokenId, TokenLike, TokenPrice};
+use zksync_types::{Address, Token, TokenId, TokenLike, TokenPrice, U256};
 // Local deps
-use crate::fee_ticker::PriceError;
+use crate::fee_ticker::{scaling::PipelineLoad, PriceError};
 
 pub trait FeeTickerClone {
     fn clone_box(&self) -> Box<dyn FeeTickerInfo>;
@@ -60,15 +68,33 @@ pub trait FeeTickerInfo: FeeTickerClone + Send + Sync + 'static {
 
     async fn get_token(&self, token: TokenLike) -> Result<Token, anyhow::Error>;
 
+    /// Get the L1 gas spent for minting the NFT withdrawn to `recipient` in its factory.
+    /// Returns `None` if the NFT is minted by the default factory.
+    async fn nft_factory_mint_gas(
+        &self,
+        token: TokenId,
+        recipient: Address,
+    ) -> anyhow::Result<Option<BigUint>>;
+
+    /// Get the current load of the pipeline used for the fee scaling.
+    async fn pipeline_load(&self) -> anyhow::Result<PipelineLoad>;
+
     /// Make boxed value to any. Helpful for downcasting in tests
     #[cfg(test)]
     fn into_any(self: Box<Self>) -> Box<dyn Any>;
 }
 
+/// Intrinsic gas of the Ethereum transaction, the minting is called internally by the contract
+/// and doesn't pay it.
+const TX_INTRINSIC_GAS: u64 = 21_000;
+
 #[derive(Clone)]
 pub struct TickerInfo {
     db: ConnectionPool,
     token_db_cache: TokenDBCache,
+    eth_client: Option<EthereumGateway>,
+    /// Estimated minting gas of the custom NFT factories.
+    factories_mint_gas: Arc<RwLock<HashMap<Address, BigUint>>>,
 }
 
 impl TickerInfo {
@@ -76,8 +102,34 @@ impl TickerInfo {
         Self {
             db,
             token_db_cache: Default::default(),
+            eth_client: None,
+            factories_mint_gas: Default::default(),
         }
     }
+
+    /// Enables estimating the minting gas of the custom NFT factories.
+    /// Without Ethereum client all the factories are priced as the default one.
+    pub fn with_eth_client(mut self, eth_client: EthereumGateway) -> Self {
+        self.eth_client = Some(eth_client);
+        self
+    }
+}
+
+/// Encodes the call of `mintNFTFromZkSync` of the factory minting the NFT to `recipient`.
+fn mint_nft_calldata(nft: &ApiNFT, recipient: Address) -> Vec<u8> {
+    let selector =
+        tiny_keccak::keccak256(b"mintNFTFromZkSync(address,address,uint32,uint32,bytes32,uint256)");
+    let params = ethabi::encode(&[
+        AbiToken::Address(nft.creator_address),
+        AbiToken::Address(recipient),
+        AbiToken::Uint(U256::from(*nft.creator_id)),
+        AbiToken::Uint(U256::from(nft.serial_id)),
+        AbiToken::FixedBytes(nft.content_hash.as_bytes().to_vec()),
+        AbiToken::Uint(U256::from(*nft.id)),
+    ]);
+    let mut calldata = selector[..4].to_vec();
+    calldata.extend(params);
+    calldata
 }
 
 #[derive(Debug, Clone)]
@@ -258,6 +310,75 @@ impl FeeTickerInfo for TickerInfo {
         result
     }
 
+    async fn nft_factory_mint_gas(
+        &self,
+        token: TokenId,
+        recipient: Address,
+    ) -> anyhow::Result<Option<BigUint>> {
+        let start = Instant::now();
+        let eth_client = match &self.eth_client {
+            Some(eth_client) => eth_client,
+            None => return Ok(None),
+        };
+
+        let mut storage = self.db.access_storage().await?;
+        let nft = match storage
+            .tokens_schema()
+            .get_nft_with_factories(token)
+            .await?
+        {
+            Some(nft) => nft,
+            None => return Ok(None),
+        };
+        let server_config = storage.config_schema().load_config().await?;
+        let parse_address = |address: Option<String>| {
+            address.and_then(|address| Address::from_str(address.trim_start_matches("0x")).ok())
+        };
+        let default_factory = parse_address(server_config.nft_factory_addr);
+        if default_factory == Some(nft.current_factory) {
+            return Ok(None);
+        }
+
+        let factory = nft.current_factory;
+        if let Some(gas) = self.factories_mint_gas.read().await.get(&factory) {
+            return Ok(Some(gas.clone()));
+        }
+
+        let zksync_contract = parse_address(server_config.contract_addr)
+            .ok_or_else(|| format_err!("zkSync contract address is not set in the config"))?;
+        let call = CallRequest {
+            from: Some(zksync_contract),
+            to: Some(factory),
+            data: Some(Bytes(mint_nft_calldata(&nft, recipient))),
+            ..Default::default()
+        };
+        let gas = eth_client
+            .estimate_gas(call)
+            .await?
+            .saturating_sub(U256::from(TX_INTRINSIC_GAS));
+        let gas = BigUint::from(gas.as_u64());
+        self.factories_mint_gas
+            .write()
+            .await
+            .insert(factory, gas.clone());
+
+        metrics::histogram!("ticker_info.nft_factory_mint_gas", start.elapsed());
+        Ok(Some(gas))
+    }
+
+    async fn pipeline_load(&self) -> anyhow::Result<PipelineLoad> {
+        let start = Instant::now();
+        let mut storage = self.db.access_storage().await?;
+        let mempool_size = storage.chain().mempool_schema().get_mempool_size().await?;
+        let prover_backlog = storage.prover_schema().pending_jobs_count().await?;
+
+        metrics::histogram!("ticker_info.pipeline_load", start.elapsed());
+        Ok(PipelineLoad {
+            mempool_size,
+            prover_backlog,
+        })
+    }
+
     #[cfg(test)]
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
         self
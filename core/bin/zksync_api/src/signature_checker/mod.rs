@@ -3,6 +3,11 @@
 //! Main routine of this module operates a multithreaded event loop,
 //! which is used to spawn concurrent tasks to efficiently check the
 //! transactions signatures.
+//!
+//! Requests which are available at the same time are grouped into batches:
+//! Ethereum signatures of the batch are checked concurrently, and then the zkSync
+//! signatures of the requests which Ethereum checks have finished are verified on
+//! the dedicated thread pool. Every request is answered on its own.
 
 // Built-in uses
 use std::collections::HashSet;
@@ -11,11 +16,13 @@ use std::time::Instant;
 // External uses
 use futures::{
     channel::{mpsc, oneshot},
-    StreamExt,
+    stream::FuturesUnordered,
+    FutureExt, StreamExt,
 };
 use tokio::task::JoinHandle;
 
 // Workspace uses
+use zksync_config::configs::api::CommonApiConfig;
 use zksync_eth_client::EthereumGateway;
 use zksync_types::{
     tx::{error::TxAddError, EthBatchSignData, EthSignData, TxEthSignature},
@@ -25,6 +32,10 @@ use zksync_types::{
 use crate::eth_checker::EthereumChecker;
 use zksync_types::tx::TransactionError;
 
+pub use self::verification_pool::VerificationPool;
+
+mod verification_pool;
+
 /// `TxVariant` is used to form a verify request. It is possible to wrap
 /// either a single transaction, or the transaction batch.
 #[derive(Debug, Clone)]
@@ -160,6 +171,9 @@ async fn verify_ethereum_signature(
         TxEthSignature::EthereumSignature(packed_signature) => {
             packed_signature.signature_recover_signer(message)
         }
+        TxEthSignature::EIP712Signature(packed_signature) => {
+            packed_signature.typed_data_recover_signer(message)
+        }
         TxEthSignature::EIP1271Signature(signature) => {
             return eth_checker
                 .is_eip1271_signature_correct(sender_address, message, signature.clone())
@@ -340,6 +354,8 @@ pub struct OrderRequest {
     pub sender: Address,
 }
 
+/// Text message signed by the owner of the account, e.g. the 2FA toggle or the change
+/// of the withdrawal timelock.
 #[derive(Debug)]
 pub struct Toggle2FARequest {
     pub sign_data: EthSignData,
@@ -375,28 +391,102 @@ impl RequestData {
     }
 }
 
+/// Verifies the batch of requests, notifying the request senders about the check results.
+/// Every request is answered as soon as its own checks are done, so a slow Ethereum
+/// signature check (e.g. the EIP-1271 contract call) doesn't delay the other requests.
+async fn verify_requests_batch(
+    requests: Vec<VerifySignatureRequest>,
+    eth_checker: EthereumChecker,
+    verification_pool: VerificationPool,
+) {
+    let start = Instant::now();
+    // Ethereum signatures may require a call to the Ethereum node (e.g. for EIP-1271),
+    // so they're checked concurrently and not on the verification pool.
+    let mut eth_checks: FuturesUnordered<_> = requests
+        .into_iter()
+        .map(|request| {
+            let eth_checker = &eth_checker;
+            async move {
+                let result = verify_eth_signature(&request.data, eth_checker).await;
+                (request, result)
+            }
+        })
+        .collect();
+
+    while let Some(checked) = eth_checks.next().await {
+        // Requests which Ethereum signatures are checked by now are verified together.
+        let mut checked = vec![checked];
+        while let Some(Some(next)) = eth_checks.next().now_or_never() {
+            checked.push(next);
+        }
+
+        let mut txs = Vec::with_capacity(checked.len());
+        let mut responses = Vec::with_capacity(checked.len());
+        for (request, eth_result) in checked {
+            match eth_result {
+                Ok(()) => {
+                    txs.push(request.data.get_tx_variant());
+                    responses.push(request.response);
+                }
+                Err(err) => request.response.send(Err(err)).unwrap_or_default(),
+            }
+        }
+        if txs.is_empty() {
+            continue;
+        }
+
+        let verification_pool = verification_pool.clone();
+        tokio::spawn(async move {
+            let results = verification_pool.verify_batch(txs).await;
+            for (response, result) in responses.into_iter().zip(results) {
+                response.send(result.map(VerifiedTx)).unwrap_or_default();
+            }
+        });
+    }
+    metrics::histogram!("signature_checker.verify_requests_batch", start.elapsed());
+}
+
 /// Main routine of the concurrent signature checker.
 /// See the module documentation for details.
 pub fn start_sign_checker(
     client: EthereumGateway,
     input: mpsc::Receiver<VerifySignatureRequest>,
+    config: &CommonApiConfig,
 ) -> JoinHandle<()> {
     let eth_checker = EthereumChecker::new(client);
+    let verification_pool = VerificationPool::new(config.signature_verification_threads);
+    let max_batch_size = std::cmp::max(config.signature_verification_batch_size, 1);
 
     /// Basically it receives the requests through the channel and verifies signatures,
     /// notifying the request sender about the check result.
     async fn checker_routine(
         mut input: mpsc::Receiver<VerifySignatureRequest>,
         eth_checker: EthereumChecker,
+        verification_pool: VerificationPool,
+        max_batch_size: usize,
     ) {
-        while let Some(VerifySignatureRequest { data, response }) = input.next().await {
-            let eth_checker = eth_checker.clone();
-            tokio::spawn(async move {
-                let resp = VerifiedTx::verify(data, &eth_checker).await;
+        while let Some(request) = input.next().await {
+            // Take all the requests that are already available without waiting for new ones,
+            // so batching doesn't increase the latency.
+            let mut batch = vec![request];
+            while batch.len() < max_batch_size {
+                match input.try_next() {
+                    Ok(Some(request)) => batch.push(request),
+                    _ => break,
+                }
+            }
 
-                response.send(resp).unwrap_or_default();
-            });
+            tokio::spawn(verify_requests_batch(
+                batch,
+                eth_checker.clone(),
+                verification_pool.clone(),
+            ));
         }
     }
-    tokio::spawn(checker_routine(input, eth_checker))
+    tokio::spawn(checker_routine(
+        input,
+        eth_checker,
+        verification_pool,
+        max_batch_size,
+    ))
 }
@@ -0,0 +1,119 @@
+//! Dedicated thread pool for the zkSync signatures verification.
+//!
+//! Verification of the musig signatures is CPU-bound, so doing it inline in the async tasks
+//! blocks the executor threads used by API servers. Instead, verification requests are grouped
+//! into batches and the whole batch is verified on a separate thread pool.
+
+// Built-in uses
+use std::sync::Arc;
+use std::time::Instant;
+
+// External uses
+use futures::channel::oneshot;
+use rayon::prelude::*;
+
+// Workspace uses
+use zksync_types::tx::error::TxAddError;
+
+// Local uses
+use super::{verify_tx_correctness, TxVariant};
+
+#[derive(Debug, Clone)]
+pub struct VerificationPool {
+    pool: Arc<rayon::ThreadPool>,
+}
+
+impl VerificationPool {
+    pub fn new(threads: usize) -> Self {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|idx| format!("signature-verifier-{}", idx))
+            .build()
+            .expect("Unable to create signature verification pool");
+
+        Self {
+            pool: Arc::new(pool),
+        }
+    }
+
+    /// Verifies the correctness (including zkSync signatures) of every transaction from the batch.
+    /// Results are returned in the same order as the transactions were provided.
+    pub async fn verify_batch(
+        &self,
+        mut txs: Vec<TxVariant>,
+    ) -> Vec<Result<TxVariant, TxAddError>> {
+        if txs.is_empty() {
+            return Vec::new();
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        let batch_size = txs.len();
+        self.pool.spawn(move || {
+            let start = Instant::now();
+            let checks: Vec<_> = txs.par_iter_mut().map(verify_tx_correctness).collect();
+            let results: Vec<_> = checks
+                .into_iter()
+                .zip(txs)
+                .map(|(check, tx)| check.map(|_| tx))
+                .collect();
+            metrics::histogram!(
+                "signature_checker.verification_pool.verify_batch",
+                start.elapsed()
+            );
+            sender.send(results).unwrap_or_default();
+        });
+        metrics::histogram!(
+            "signature_checker.verification_pool.batch_size",
+            batch_size as f64
+        );
+
+        receiver
+            .await
+            .expect("Signature verification pool dropped the request")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use zksync_types::{AccountId, Address, Nonce, SignedZkSyncTx, TokenId, Transfer, ZkSyncTx};
+
+    use super::*;
+
+    /// Creates a transfer to the zero address, which is never correct.
+    fn incorrect_tx() -> TxVariant {
+        let transfer = Transfer::new(
+            AccountId(1),
+            Address::random(),
+            Address::zero(),
+            TokenId(0),
+            100u32.into(),
+            10u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        );
+        TxVariant::Tx(SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Checks that results of the batch verification are returned in the order of the requests.
+    #[tokio::test]
+    async fn verify_batch_preserves_order() {
+        let pool = VerificationPool::new(2);
+        assert!(pool.verify_batch(Vec::new()).await.is_empty());
+
+        let results = pool
+            .verify_batch(vec![
+                TxVariant::Toggle2FA,
+                incorrect_tx(),
+                TxVariant::Toggle2FA,
+            ])
+            .await;
+        let results: Vec<_> = results.iter().map(Result::is_ok).collect();
+        assert_eq!(results, vec![true, false, true]);
+    }
+}
@@ -1,2 +1,3 @@
 pub mod block_details_cache;
+pub mod response_cache;
 pub mod shared_lru_cache;
@@ -0,0 +1,192 @@
+// Built-in uses
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+
+// Workspace uses
+use zksync_storage::{
+    listener::{StorageListener, BLOCKS_CHANNEL},
+    QueryResult,
+};
+
+// Local uses
+use super::shared_lru_cache::AsyncLruCache;
+
+/// Interval between the attempts to reconnect the listener of the blocks channel.
+const LISTENER_RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Version of the blocks the API shows, bumped whenever a block is sealed, committed, finalized
+/// or reverted. The explorer data can't change while the version is the same, so it's used to
+/// invalidate the cached responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlocksVersion(u64);
+
+/// Current `BlocksVersion`, shared by the response caches. It's bumped by the notifications of
+/// the blocks channel (see `run_updater`), so the version is never loaded by the requests.
+#[derive(Debug, Clone, Default)]
+pub struct BlocksVersionCache {
+    version: Arc<AtomicU64>,
+}
+
+impl BlocksVersionCache {
+    pub fn get(&self) -> BlocksVersion {
+        BlocksVersion(self.version.load(Ordering::SeqCst))
+    }
+
+    pub fn bump(&self) {
+        self.version.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Bumps the version on every notification of the blocks channel. Notifications are lost
+    /// while the listener is disconnected, so the version is also bumped once it's reconnected.
+    pub async fn run_updater(self) {
+        loop {
+            if let Err(err) = self.listen().await {
+                vlog::warn!("Listener of the blocks channel has failed: {}", err);
+            }
+            self.bump();
+            tokio::time::sleep(LISTENER_RECONNECT_INTERVAL).await;
+        }
+    }
+
+    /// Listens to the blocks channel until the connection is lost.
+    async fn listen(&self) -> QueryResult<()> {
+        let mut listener = StorageListener::connect().await?;
+        listener.listen(BLOCKS_CHANNEL).await?;
+        // Blocks may have changed before the listener was connected.
+        self.bump();
+        while listener.try_recv().await?.is_some() {
+            self.bump();
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedResponse<V> {
+    version: BlocksVersion,
+    created_at: Instant,
+    value: V,
+}
+
+/// Cache of the responses of the expensive read endpoints.
+///
+/// Responses are served from the cache until the blocks version is bumped. Data which is updated
+/// regardless of the blocks (e.g. the aggregated statistics) additionally gets stale after
+/// `max_age`.
+#[derive(Debug, Clone)]
+pub struct ResponseCache<K: Eq + Hash, V: Clone> {
+    name: &'static str,
+    responses: AsyncLruCache<K, CachedResponse<V>>,
+    max_age: Option<Duration>,
+}
+
+impl<K: Eq + Hash, V: Clone> ResponseCache<K, V> {
+    /// Creates the cache, `name` is used as the label of its metrics.
+    pub fn new(name: &'static str, capacity: usize) -> Self {
+        Self {
+            name,
+            responses: AsyncLruCache::new(capacity),
+            max_age: None,
+        }
+    }
+
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Returns the response computed for the same blocks, otherwise loads and caches it.
+    /// Errors are not cached.
+    pub async fn get_or_load<F, Fut, E>(
+        &self,
+        key: K,
+        version: BlocksVersion,
+        load: F,
+    ) -> Result<V, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        if let Some(response) = self.responses.get(&key).await {
+            if self.is_fresh(&response, version) {
+                metrics::increment_counter!("api.response_cache", "cache" => self.name, "result" => "hit");
+                return Ok(response.value);
+            }
+        }
+        metrics::increment_counter!("api.response_cache", "cache" => self.name, "result" => "miss");
+
+        // The version is loaded before the response, so the response can only be newer than
+        // the version it's cached for, and it's never served after the next block.
+        let value = load().await?;
+        let response = CachedResponse {
+            version,
+            created_at: Instant::now(),
+            value: value.clone(),
+        };
+        self.responses.insert(key, response).await;
+        Ok(value)
+    }
+
+    fn is_fresh(&self, response: &CachedResponse<V>, version: BlocksVersion) -> bool {
+        response.version == version
+            && self
+                .max_age
+                .map(|max_age| response.created_at.elapsed() < max_age)
+                .unwrap_or(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn load(cache: &ResponseCache<u32, u32>, version: BlocksVersion, value: u32) -> u32 {
+        cache
+            .get_or_load(1, version, || async move { Ok::<_, ()>(value) })
+            .await
+            .unwrap()
+    }
+
+    /// Checks that the responses are invalidated by the bumped blocks version.
+    #[tokio::test]
+    async fn invalidation_by_blocks() {
+        let cache = ResponseCache::new("test", 10);
+        let versions = BlocksVersionCache::default();
+
+        assert_eq!(load(&cache, versions.get(), 1).await, 1);
+        assert_eq!(load(&cache, versions.get(), 2).await, 1);
+        versions.bump();
+        assert_eq!(load(&cache, versions.get(), 3).await, 3);
+        assert_eq!(load(&cache, versions.get(), 4).await, 3);
+        // The version is shared by the clones.
+        versions.clone().bump();
+        assert_eq!(load(&cache, versions.get(), 5).await, 5);
+
+        // Errors are not cached.
+        let result = cache
+            .get_or_load(2, versions.get(), || async { Err(()) })
+            .await;
+        assert_eq!(result, Err(()));
+        let result = cache
+            .get_or_load(2, versions.get(), || async { Ok::<_, ()>(6) })
+            .await;
+        assert_eq!(result, Ok(6));
+    }
+
+    /// Checks that the responses get stale after the max age.
+    #[tokio::test]
+    async fn invalidation_by_age() {
+        let cache = ResponseCache::new("test", 10).with_max_age(Duration::from_millis(50));
+        let version = BlocksVersionCache::default().get();
+
+        assert_eq!(load(&cache, version, 1).await, 1);
+        assert_eq!(load(&cache, version, 2).await, 1);
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(load(&cache, version, 3).await, 3);
+    }
+}
@@ -0,0 +1,68 @@
+//! HTTP client of the admin interface of the server.
+
+use anyhow::Context;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Deserialize)]
+pub struct EventsResponse {
+    pub last_event_id: Option<u64>,
+    pub events: Vec<Value>,
+}
+
+/// Client of the private core API.
+pub struct AdminClient {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl AdminClient {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> anyhow::Result<T> {
+        let response = self
+            .client
+            .get(format!("{}/{}", self.url, path))
+            .send()
+            .await?;
+        Self::parse(response).await
+    }
+
+    pub async fn post<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: impl Serialize,
+    ) -> anyhow::Result<T> {
+        let response = self
+            .client
+            .post(format!("{}/{}", self.url, path))
+            .json(&body)
+            .send()
+            .await?;
+        Self::parse(response).await
+    }
+
+    async fn parse<T: DeserializeOwned>(response: reqwest::Response) -> anyhow::Result<T> {
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            anyhow::bail!("Request failed with {}: {}", status, text);
+        }
+        response
+            .json()
+            .await
+            .context("Failed to parse the response")
+    }
+
+    pub async fn events(&self, after: Option<u64>) -> anyhow::Result<EventsResponse> {
+        match after {
+            Some(after) => self.get(&format!("events?after={}", after)).await,
+            None => self.get("events").await,
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Terminal dashboard of the server.
+//!
+//! The dashboard polls the private core API and redraws the screen with the state of the block
+//! pipeline, the sizes of the queues and the status of the operator account on L1. Failed
+//! requests are shown in place of the section, so the dashboard survives the server restarts.
+
+use std::{fmt::Write, time::Duration};
+
+use serde::Deserialize;
+use tokio::time;
+
+use crate::client::AdminClient;
+
+/// Interval of refreshing the dashboard.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+/// Clears the terminal and moves the cursor to the top left corner.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const WEI_IN_GWEI: f64 = 1_000_000_000.0;
+
+#[derive(Debug, Deserialize)]
+struct Pipeline {
+    sealed: u32,
+    committed: u32,
+    proofs_generated: u32,
+    proven: u32,
+    executed: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct Queues {
+    mempool_txs: u64,
+    prover_jobs: u64,
+    unconfirmed_eth_operations: u64,
+}
+
+/// L1 values are serialized as hex strings by the server.
+#[derive(Debug, Deserialize)]
+struct EthStatus {
+    mined_nonce: String,
+    pending_nonce: String,
+    gas_price: String,
+    average_gas_price: Option<String>,
+    gas_price_limit: String,
+}
+
+fn parse_hex(value: &str) -> u128 {
+    u128::from_str_radix(value.trim_start_matches("0x"), 16).unwrap_or_default()
+}
+
+fn gwei(value: &str) -> String {
+    format!("{:.2} gwei", parse_hex(value) as f64 / WEI_IN_GWEI)
+}
+
+fn render_pipeline(out: &mut String, pipeline: &Pipeline) {
+    let stages = [
+        ("sealed", pipeline.sealed),
+        ("committed", pipeline.committed),
+        ("proofs generated", pipeline.proofs_generated),
+        ("proven", pipeline.proven),
+        ("executed", pipeline.executed),
+    ];
+    for (name, block) in stages.iter() {
+        let behind = pipeline.sealed.saturating_sub(*block);
+        if behind > 0 {
+            writeln!(out, "  {:<20}{:>10}  ({} behind)", name, block, behind).unwrap();
+        } else {
+            writeln!(out, "  {:<20}{:>10}", name, block).unwrap();
+        }
+    }
+}
+
+fn render_queues(out: &mut String, queues: &Queues) {
+    writeln!(out, "  {:<20}{:>10}", "mempool txs", queues.mempool_txs).unwrap();
+    writeln!(out, "  {:<20}{:>10}", "prover jobs", queues.prover_jobs).unwrap();
+    writeln!(
+        out,
+        "  {:<20}{:>10}",
+        "unconfirmed L1 ops", queues.unconfirmed_eth_operations
+    )
+    .unwrap();
+}
+
+fn render_eth_status(out: &mut String, status: &EthStatus) {
+    let mined_nonce = parse_hex(&status.mined_nonce);
+    let pending_nonce = parse_hex(&status.pending_nonce);
+    writeln!(
+        out,
+        "  {:<20}mined {}, pending {} ({} not mined)",
+        "operator nonce",
+        mined_nonce,
+        pending_nonce,
+        pending_nonce.saturating_sub(mined_nonce)
+    )
+    .unwrap();
+    writeln!(out, "  {:<20}{}", "gas price", gwei(&status.gas_price)).unwrap();
+    if let Some(average_gas_price) = &status.average_gas_price {
+        writeln!(
+            out,
+            "  {:<20}{}",
+            "average gas price",
+            gwei(average_gas_price)
+        )
+        .unwrap();
+    }
+    writeln!(
+        out,
+        "  {:<20}{}",
+        "gas price limit",
+        gwei(&status.gas_price_limit)
+    )
+    .unwrap();
+}
+
+fn render_section<T>(
+    out: &mut String,
+    title: &str,
+    value: &anyhow::Result<T>,
+    render: impl FnOnce(&mut String, &T),
+) {
+    writeln!(out, "{}", title).unwrap();
+    match value {
+        Ok(value) => render(out, value),
+        Err(err) => writeln!(out, "  unavailable: {}", err).unwrap(),
+    }
+    writeln!(out).unwrap();
+}
+
+/// Redraws the dashboard until the process is interrupted.
+pub async fn run_dashboard(client: &AdminClient, url: &str) -> anyhow::Result<()> {
+    let mut timer = time::interval(REFRESH_INTERVAL);
+    loop {
+        timer.tick().await;
+        let pipeline = client.get::<Pipeline>("pipeline").await;
+        let queues = client.get::<Queues>("queues").await;
+        let eth_status = client.get::<EthStatus>("eth/status").await;
+
+        let mut out = String::from(CLEAR_SCREEN);
+        writeln!(
+            out,
+            "zkSync dashboard: {} (refreshed every {}s, press Ctrl+C to exit)\n",
+            url,
+            REFRESH_INTERVAL.as_secs()
+        )
+        .unwrap();
+        render_section(&mut out, "Blocks", &pipeline, render_pipeline);
+        render_section(&mut out, "Queues", &queues, render_queues);
+        render_section(&mut out, "L1", &eth_status, render_eth_status);
+        print!("{}", out);
+    }
+}
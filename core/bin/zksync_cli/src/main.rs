@@ -0,0 +1,180 @@
+//! Administration tool of the zkSync server.
+//!
+//! The tool talks to the private API of the core server, so it must be run from within
+//! the cluster. Responses are printed as JSON, so the output can be processed by `jq`.
+
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use structopt::StructOpt;
+use tokio::time;
+
+use crate::client::AdminClient;
+
+mod client;
+mod dashboard;
+
+/// Interval of polling the new events.
+const EVENTS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zkSync CLI", author = "Matter Labs")]
+#[structopt(about = "Administration tool of the zkSync server")]
+struct Opt {
+    /// URL of the private core API.
+    #[structopt(long, env = "API_PRIVATE_URL", default_value = "http://127.0.0.1:8090")]
+    url: String,
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Shows the availability of the databases and the Ethereum node.
+    Status,
+    /// Shows the sizes of the mempool, prover and L1 operations queues.
+    Queues,
+    /// Shows the last blocks at each stage of the block pipeline.
+    Pipeline,
+    /// Shows the nonces of the operator account and the gas prices.
+    EthStatus,
+    /// Lists the L1 operations not mined before their deadline blocks.
+    StuckEthTxs,
+    /// Requests the state keeper to seal the pending block.
+    SealBlock,
+    /// Lists the tokens known to the server.
+    Tokens,
+    /// Manages the aliases of the token symbols.
+    TokenAliases(TokenAliasesCommand),
+    /// Manages the circuit breaker of the state keeper.
+    CircuitBreaker(CircuitBreakerCommand),
+    /// Freezes the account, so it can't send funds within the network.
+    Freeze {
+        account_id: u32,
+        /// Reason of the freeze, e.g. the reference of the compliance case.
+        reason: String,
+    },
+    /// Lifts the active freeze of the account.
+    Unfreeze { account_id: u32, reason: String },
+    /// Shows the block pipeline, the queues and the L1 status, refreshed periodically.
+    Dashboard,
+    /// Prints the new events as they are stored, one JSON per line.
+    Events {
+        /// ID of the event to start after, the latest stored event by default.
+        #[structopt(long)]
+        after: Option<u64>,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+enum CircuitBreakerCommand {
+    /// Shows the active trip of the circuit breaker.
+    Status,
+    /// Stops the block production.
+    Trip { reason: String },
+    /// Resumes the block production.
+    Reset,
+}
+
+#[derive(Debug, StructOpt)]
+enum TokenAliasesCommand {
+    /// Lists the aliases of the token symbols.
+    List,
+    /// Points the alias to the token, e.g. the canonical one of the tokens sharing a symbol.
+    Set { alias: String, token_id: u32 },
+    /// Removes the alias.
+    Remove { alias: String },
+}
+
+fn print_json(value: &Value) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).expect("Failed to serialize the response")
+    );
+}
+
+/// Follows the events stored after the given one. The events are fetched in pages,
+/// so the next page is requested right away if the previous one isn't empty.
+async fn tail_events(client: &AdminClient, after: Option<u64>) -> anyhow::Result<()> {
+    let mut after = match after {
+        Some(after) => after,
+        None => client.events(None).await?.last_event_id.unwrap_or_default(),
+    };
+    let mut timer = time::interval(EVENTS_POLL_INTERVAL);
+    loop {
+        let response = client.events(Some(after)).await?;
+        if response.events.is_empty() {
+            timer.tick().await;
+            continue;
+        }
+        for event in response.events {
+            if let Some(id) = event["id"].as_u64() {
+                after = id;
+            }
+            println!("{}", event);
+        }
+    }
+}
+
+async fn run(opt: Opt) -> anyhow::Result<()> {
+    let client = AdminClient::new(opt.url.clone());
+    let response: Value = match opt.command {
+        Command::Status => client.get("status").await?,
+        Command::Queues => client.get("queues").await?,
+        Command::Pipeline => client.get("pipeline").await?,
+        Command::EthStatus => client.get("eth/status").await?,
+        Command::StuckEthTxs => client.get("eth/stuck").await?,
+        Command::SealBlock => client.post("seal_block", ()).await?,
+        Command::Tokens => client.get("tokens").await?,
+        Command::TokenAliases(TokenAliasesCommand::List) => client.get("tokens/aliases").await?,
+        Command::TokenAliases(TokenAliasesCommand::Set { alias, token_id }) => {
+            client
+                .post(
+                    "tokens/aliases",
+                    json!({ "alias": alias, "token_id": token_id }),
+                )
+                .await?
+        }
+        Command::TokenAliases(TokenAliasesCommand::Remove { alias }) => {
+            client
+                .post("tokens/aliases/remove", json!({ "alias": alias }))
+                .await?
+        }
+        Command::CircuitBreaker(CircuitBreakerCommand::Status) => {
+            client.get("circuit_breaker").await?
+        }
+        Command::CircuitBreaker(CircuitBreakerCommand::Trip { reason }) => {
+            client
+                .post("circuit_breaker/trip", json!({ "reason": reason }))
+                .await?
+        }
+        Command::CircuitBreaker(CircuitBreakerCommand::Reset) => {
+            client.post("circuit_breaker/reset", ()).await?
+        }
+        Command::Freeze { account_id, reason } => {
+            client
+                .post(
+                    "accounts/freeze",
+                    json!({ "account_id": account_id, "reason": reason }),
+                )
+                .await?
+        }
+        Command::Unfreeze { account_id, reason } => {
+            client
+                .post(
+                    "accounts/unfreeze",
+                    json!({ "account_id": account_id, "reason": reason }),
+                )
+                .await?
+        }
+        Command::Dashboard => return dashboard::run_dashboard(&client, &opt.url).await,
+        Command::Events { after } => return tail_events(&client, after).await,
+    };
+    print_json(&response);
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    run(Opt::from_args()).await
+}
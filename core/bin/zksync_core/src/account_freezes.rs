@@ -0,0 +1,86 @@
+//! Compliance holds of the accounts made by the operator.
+//!
+//! Freezes are stored in the database by the private core API. This module polls the database
+//! and keeps the shared `FrozenAccounts` set up to date, so the state keeper may pass it to the
+//! state without accessing the database. Frozen accounts can't send funds within the network,
+//! but their exits are never blocked.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use tokio::{task::JoinHandle, time};
+
+use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_types::{AccountFreeze, AccountId};
+
+/// Interval of polling the database for the changed freezes.
+const FREEZES_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Shared set of the active account freezes.
+#[derive(Debug, Clone, Default)]
+pub struct FrozenAccounts(Arc<RwLock<HashMap<AccountId, AccountFreeze>>>);
+
+impl FrozenAccounts {
+    /// Loads the active freezes from the database.
+    pub async fn load(storage: &mut StorageProcessor<'_>) -> anyhow::Result<Self> {
+        let frozen_accounts = Self::default();
+        frozen_accounts.update(storage).await?;
+        Ok(frozen_accounts)
+    }
+
+    /// Returns the copy of the active freezes.
+    pub fn get(&self) -> HashMap<AccountId, AccountFreeze> {
+        self.0.read().unwrap().clone()
+    }
+
+    async fn update(&self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+        let freezes = storage
+            .account_freezes_schema()
+            .load_active_freezes()
+            .await?;
+        let freezes: HashMap<_, _> = freezes
+            .into_iter()
+            .map(|freeze| (freeze.account_id, freeze))
+            .collect();
+
+        let mut frozen_accounts = self.0.write().unwrap();
+        if freezes.len() != frozen_accounts.len()
+            || freezes.keys().any(|id| !frozen_accounts.contains_key(id))
+        {
+            vlog::info!(
+                "Active account freezes are updated: {} accounts",
+                freezes.len()
+            );
+        }
+        *frozen_accounts = freezes;
+        Ok(())
+    }
+}
+
+#[must_use]
+pub fn run_account_freezes_watcher(
+    connection_pool: ConnectionPool,
+    frozen_accounts: FrozenAccounts,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut timer = time::interval(FREEZES_POLL_INTERVAL);
+        loop {
+            timer.tick().await;
+
+            let result = match connection_pool.access_storage().await {
+                Ok(mut storage) => frozen_accounts.update(&mut storage).await,
+                Err(err) => Err(err.into()),
+            };
+            if let Err(err) = result {
+                vlog::warn!("Failed to load the account freezes: {}", err);
+            }
+            metrics::gauge!(
+                "account_freezes.active",
+                frozen_accounts.0.read().unwrap().len() as f64
+            );
+        }
+    })
+}
@@ -0,0 +1,158 @@
+//! Circuit breaker halting the block production once a divergence of the state is detected.
+//!
+//! Trips are stored in the database, so the breaker can be tripped by any component of the
+//! server (the witness generator, the prover server, data restore, the state root check) or
+//! manually via the private core API. This module polls the database and keeps the shared
+//! `CircuitBreaker` flag up to date, so the state keeper may check it without accessing the
+//! database. `eth_sender` checks the database on its own and stops sending new operations while
+//! the breaker is tripped.
+//!
+//! Optionally, once the breaker is tripped, the blocks that are committed but not yet verified
+//! are reverted on the contract, so they can't be executed. The storage has to be reverted
+//! with the `block_revert` tool afterwards.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use anyhow::format_err;
+use ethabi::Token;
+use tokio::{task::JoinHandle, time};
+use web3::{contract::Options, types::U256};
+
+use zksync_config::configs::chain::CircuitBreaker as CircuitBreakerConfig;
+use zksync_eth_client::EthereumGateway;
+use zksync_storage::ConnectionPool;
+use zksync_types::{aggregated_operations::stored_block_info, BlockNumber};
+
+/// Shared flag set while the circuit breaker is tripped.
+#[derive(Debug, Clone, Default)]
+pub struct CircuitBreaker(Arc<AtomicBool>);
+
+impl CircuitBreaker {
+    pub fn is_tripped(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn set_tripped(&self, tripped: bool) {
+        self.0.store(tripped, Ordering::Relaxed);
+    }
+}
+
+#[must_use]
+pub fn run_circuit_breaker(
+    connection_pool: ConnectionPool,
+    eth_gateway: EthereumGateway,
+    config: CircuitBreakerConfig,
+    circuit_breaker: CircuitBreaker,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut timer = time::interval(config.check_interval());
+        // ID of the trip the blocks were already reverted for.
+        let mut reverted_for = None;
+        loop {
+            timer.tick().await;
+
+            let trip = match connection_pool.access_storage().await {
+                Ok(mut storage) => storage.circuit_breaker_schema().load_active_trip().await,
+                Err(err) => Err(err.into()),
+            };
+            let trip = match trip {
+                Ok(trip) => trip,
+                Err(err) => {
+                    vlog::warn!("Failed to load the circuit breaker state: {}", err);
+                    continue;
+                }
+            };
+
+            match trip {
+                Some(trip) => {
+                    if !circuit_breaker.is_tripped() {
+                        vlog::error!(
+                            "Circuit breaker is tripped by {} at {}: {}. Block production is halted",
+                            trip.source,
+                            trip.tripped_at,
+                            trip.reason
+                        );
+                        circuit_breaker.set_tripped(true);
+                    }
+                    if config.revert_unverified_blocks && reverted_for != Some(trip.id) {
+                        match revert_unverified_blocks(&connection_pool, &eth_gateway).await {
+                            Ok(()) => reverted_for = Some(trip.id),
+                            Err(err) => vlog::error!("Failed to revert unverified blocks: {}", err),
+                        }
+                    }
+                }
+                None => {
+                    if circuit_breaker.is_tripped() {
+                        vlog::info!("Circuit breaker is reset. Block production is resumed");
+                        circuit_breaker.set_tripped(false);
+                    }
+                }
+            }
+            metrics::gauge!(
+                "circuit_breaker.tripped",
+                circuit_breaker.is_tripped() as u8 as f64
+            );
+        }
+    })
+}
+
+/// Reverts the blocks that are committed on the contract, but not verified yet.
+async fn revert_unverified_blocks(
+    connection_pool: &ConnectionPool,
+    eth_gateway: &EthereumGateway,
+) -> anyhow::Result<()> {
+    let mut storage = connection_pool.access_storage().await?;
+    let last_committed_block = storage
+        .chain()
+        .block_schema()
+        .get_last_committed_confirmed_block()
+        .await?;
+    let last_verified_block = storage
+        .chain()
+        .block_schema()
+        .get_last_verified_confirmed_block()
+        .await?;
+    if last_committed_block <= last_verified_block {
+        return Ok(());
+    }
+
+    // Blocks are reverted starting from the latest one.
+    let mut blocks = Vec::new();
+    for block_number in (*last_verified_block + 1..=*last_committed_block).rev() {
+        let block = storage
+            .chain()
+            .block_schema()
+            .get_block(BlockNumber(block_number))
+            .await?
+            .ok_or_else(|| format_err!("Block {} is not found in storage", block_number))?;
+        blocks.push(block);
+    }
+
+    let tx_arg = Token::Array(blocks.iter().map(stored_block_info).collect());
+    let data = eth_gateway.encode_tx_data("revertBlocks", tx_arg);
+    let gas_limit = 200000 + 15000 * blocks.len();
+    // The transaction is signed with the operator key, so its nonce is reserved in the counter of
+    // `eth_sender` before signing, otherwise both may use the same nonce.
+    let nonce = storage.ethereum_schema().get_next_nonce().await?;
+    let signed_tx = eth_gateway
+        .sign_prepared_tx(
+            data,
+            Options::with(|f| {
+                f.gas = Some(U256::from(gas_limit));
+                f.nonce = Some(U256::from(nonce));
+            }),
+        )
+        .await?;
+    let tx_hash = eth_gateway.send_raw_tx(signed_tx.raw_tx).await?;
+
+    vlog::warn!(
+        "Sent the transaction {:#x} reverting blocks {}-{} on the contract",
+        tx_hash,
+        *last_verified_block + 1,
+        last_committed_block
+    );
+    Ok(())
+}
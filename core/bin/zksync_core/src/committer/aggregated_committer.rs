@@ -14,8 +14,10 @@ use zksync_types::{
     },
     block::Block,
     gas_counter::GasCounter,
+    prover::ProofSystemVersion,
     BlockNumber, U256,
 };
+use zksync_utils::chaos::{self, FaultPoint};
 
 fn create_new_commit_operation(
     last_committed_block: &Block,
@@ -140,10 +142,12 @@ fn create_new_create_proof_operation(
 fn create_publish_proof_operation(
     unpublished_create_proof_op: &BlocksCreateProofOperation,
     aggregated_proof: &AggregatedProof,
+    proof_system: ProofSystemVersion,
 ) -> BlocksProofOperation {
     BlocksProofOperation {
         blocks: unpublished_create_proof_op.blocks.clone(),
         proof: aggregated_proof.serialize_aggregated_proof(),
+        proof_system,
     }
 }
 
@@ -384,9 +388,12 @@ async fn create_aggregated_publish_proof_operation_storage(
             .await?
     };
 
-    let result = if let Some(proof) = aggregated_proof {
-        let operation =
-            create_publish_proof_operation(&last_unpublished_create_proof_operation, &proof);
+    let result = if let Some((proof, proof_system)) = aggregated_proof {
+        let operation = create_publish_proof_operation(
+            &last_unpublished_create_proof_operation,
+            &proof,
+            proof_system,
+        );
         let aggregated_op = operation.into();
         log_aggregated_op_creation(&aggregated_op);
         OperationsSchema(&mut transaction)
@@ -458,6 +465,7 @@ pub async fn create_aggregated_operations_storage(
     storage: &mut StorageProcessor<'_>,
     config: &ChainConfig,
 ) -> anyhow::Result<()> {
+    chaos::inject_error(FaultPoint::Committer)?;
     while create_aggregated_commits_storage(storage, config).await? {}
     while create_aggregated_prover_task_storage(storage, config).await? {}
     while create_aggregated_publish_proof_operation_storage(storage).await? {}
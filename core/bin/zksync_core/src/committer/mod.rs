@@ -1,19 +1,24 @@
 // Built-in uses
 use std::time::{Duration, Instant};
 // External uses
-use futures::{channel::mpsc::Receiver, StreamExt};
+use futures::{
+    channel::{mpsc::Receiver, oneshot},
+    StreamExt,
+};
 use serde::{Deserialize, Serialize};
 use tokio::{task::JoinHandle, time};
 
 // Workspace uses
 use zksync_config::ChainConfig;
 use zksync_crypto::Fr;
+use zksync_shared_cache::SharedCache;
 use zksync_storage::ConnectionPool;
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{
     block::{Block, BlockMetadata, ExecutedOperations, IncompleteBlock, PendingBlock},
     AccountUpdates, BlockNumber,
 };
+use zksync_utils::chaos::{self, FaultPoint};
 
 mod aggregated_committer;
 
@@ -27,6 +32,8 @@ pub enum CommitRequest {
     RemoveRevertedBlock(BlockNumber),
     SealIncompleteBlock((BlockCommitRequest, AppliedUpdatesRequest)),
     FinishBlock(BlockFinishRequest),
+    /// Responds once every request received before this one is processed.
+    Flush(oneshot::Sender<()>),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -54,13 +61,19 @@ pub struct ExecutedOpsNotify {
 
 const PROOF_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
-async fn handle_new_commit_task(mut rx_for_ops: Receiver<CommitRequest>, pool: ConnectionPool) {
+async fn handle_new_commit_task(
+    mut rx_for_ops: Receiver<CommitRequest>,
+    pool: ConnectionPool,
+    shared_cache: Option<SharedCache>,
+) {
     vlog::info!("Run committer");
     let mut token_db_cache = TokenDBCache::new(TOKEN_INVALIDATE_CACHE);
     token_db_cache
         .fill_token_cache(&mut pool.access_storage().await.unwrap())
         .await;
     while let Some(request) = rx_for_ops.next().await {
+        // Requests are never dropped, the blocks would be lost otherwise.
+        chaos::inject_delay(FaultPoint::Committer).await;
         match request {
             CommitRequest::SealIncompleteBlock((block_commit_request, applied_updates_req)) => {
                 seal_incomplete_block(
@@ -68,6 +81,7 @@ async fn handle_new_commit_task(mut rx_for_ops: Receiver<CommitRequest>, pool: C
                     applied_updates_req,
                     &pool,
                     &mut token_db_cache,
+                    shared_cache.as_ref(),
                 )
                 .await;
             }
@@ -80,6 +94,9 @@ async fn handle_new_commit_task(mut rx_for_ops: Receiver<CommitRequest>, pool: C
             CommitRequest::RemoveRevertedBlock(block_number) => {
                 remove_reverted_block(block_number, &pool).await;
             }
+            CommitRequest::Flush(sender) => {
+                sender.send(()).unwrap_or_default();
+            }
         }
     }
 }
@@ -150,6 +167,7 @@ async fn seal_incomplete_block(
     applied_updates_request: AppliedUpdatesRequest,
     pool: &ConnectionPool,
     token_db_cache: &mut TokenDBCache,
+    shared_cache: Option<&SharedCache>,
 ) {
     let start = Instant::now();
     let BlockCommitRequest {
@@ -213,6 +231,13 @@ async fn seal_incomplete_block(
         .await
         .expect("Unable to commit DB transaction");
 
+    // The API replicas drop the cached data once the block is stored.
+    if let Some(shared_cache) = shared_cache {
+        if let Err(err) = shared_cache.publish_block_sealed(block_number).await {
+            vlog::warn!("Failed to invalidate the shared cache: {}", err);
+        }
+    }
+
     // We do this outside of a transaction,
     // because we want the incomplete block data to be available as soon as possible.
     // If something happened to the metric count, it won't affect the block data
@@ -273,6 +298,12 @@ async fn finish_block(request: BlockFinishRequest, pool: &ConnectionPool) {
         .await
         .expect("committer must commit the op into db");
 
+    transaction
+        .accounting_schema()
+        .record_block_fees(block_number)
+        .await
+        .expect("committer must record the block fees");
+
     transaction
         .commit()
         .await
@@ -303,7 +334,12 @@ pub fn run_committer(
     rx_for_ops: Receiver<CommitRequest>,
     pool: ConnectionPool,
     config: ChainConfig,
+    shared_cache: Option<SharedCache>,
 ) -> JoinHandle<()> {
-    tokio::spawn(handle_new_commit_task(rx_for_ops, pool.clone()));
+    tokio::spawn(handle_new_commit_task(
+        rx_for_ops,
+        pool.clone(),
+        shared_cache,
+    ));
     tokio::spawn(poll_for_new_proofs_task(pool, config))
 }
@@ -0,0 +1,153 @@
+//! Submits the L2 calls carried by the deposits made via the deposit router.
+//!
+//! Calls observed by the Ethereum watcher are stored as pending, and once the deposit is executed
+//! by the network, the transactions of the call are sent to the mempool as a single batch. The
+//! call is submitted at most once: if it's invalid or rejected by the mempool, the reason is stored
+//! and the deposited funds simply stay on the owner's account.
+
+// Built-in deps
+use std::time::Duration;
+// External uses
+use chrono::Utc;
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
+use tokio::task::JoinHandle;
+// Workspace uses
+use zksync_mempool::MempoolTransactionRequest;
+use zksync_storage::{
+    deposit_calls::{DEPOSIT_CALL_REJECTED, DEPOSIT_CALL_SUBMITTED},
+    ConnectionPool, StorageProcessor,
+};
+use zksync_types::{Address, DepositCallEvent, SignedZkSyncTx};
+// Local uses
+use crate::eth_watch::EthWatchRequest;
+
+#[derive(Debug)]
+struct DepositCallHandler {
+    connection_pool: ConnectionPool,
+    eth_watch_req: mpsc::Sender<EthWatchRequest>,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    poll_interval: Duration,
+    last_eth_block: Option<u64>,
+}
+
+impl DepositCallHandler {
+    async fn load_deposit_calls(&self) -> Vec<DepositCallEvent> {
+        let (sender, receiver) = oneshot::channel();
+        self.eth_watch_req
+            .clone()
+            .send(EthWatchRequest::GetDepositCalls {
+                last_eth_block: self.last_eth_block,
+                resp: sender,
+            })
+            .await
+            .expect("ETH watch req receiver dropped");
+
+        receiver.await.expect("Err response from eth watch")
+    }
+
+    /// Sends the transactions of the call to the mempool, returns the reason of the rejection.
+    async fn submit_call(&mut self, event: &DepositCallEvent) -> anyhow::Result<Option<String>> {
+        let txs = match event.decode_call() {
+            Ok(txs) => txs,
+            Err(err) => return Ok(Some(err.to_string())),
+        };
+        // The deposit transaction on L1 already authorizes the call.
+        let txs = txs
+            .into_iter()
+            .map(|tx| SignedZkSyncTx {
+                tx,
+                eth_sign_data: None,
+                created_at: Utc::now(),
+            })
+            .collect();
+
+        let (sender, receiver) = oneshot::channel();
+        self.mempool_tx_sender
+            .send(MempoolTransactionRequest::NewTxsBatch(txs, vec![], sender))
+            .await?;
+        Ok(receiver.await?.err().map(|err| err.to_string()))
+    }
+
+    async fn submit_executed_calls(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<()> {
+        let calls = storage
+            .deposit_calls_schema()
+            .load_executed_deposit_calls()
+            .await?;
+        for call in calls {
+            let event = DepositCallEvent {
+                serial_id: call.serial_id as u64,
+                owner: Address::from_slice(&call.owner_address),
+                call: call.call,
+                eth_block: call.eth_block as u64,
+            };
+            let (status, error) = match self.submit_call(&event).await? {
+                None => (DEPOSIT_CALL_SUBMITTED, None),
+                Some(error) => {
+                    vlog::warn!(
+                        "Call of the deposit {} is rejected: {}",
+                        event.serial_id,
+                        error
+                    );
+                    (DEPOSIT_CALL_REJECTED, Some(error))
+                }
+            };
+            metrics::increment_counter!("deposit_calls.processed", "status" => status);
+            storage
+                .deposit_calls_schema()
+                .set_deposit_call_status(event.serial_id, status, error)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn run_iteration(&mut self) -> anyhow::Result<()> {
+        let events = self.load_deposit_calls().await;
+        let mut storage = self.connection_pool.access_storage().await?;
+        for event in &events {
+            storage
+                .deposit_calls_schema()
+                .store_deposit_call(event)
+                .await?;
+        }
+        self.last_eth_block = events
+            .iter()
+            .map(|event| event.eth_block)
+            .max()
+            .or(self.last_eth_block);
+
+        self.submit_executed_calls(&mut storage).await
+    }
+
+    async fn run(&mut self) {
+        let mut timer = tokio::time::interval(self.poll_interval);
+        loop {
+            timer.tick().await;
+            if let Err(err) = self.run_iteration().await {
+                vlog::error!("Deposit call handler iteration has failed: {}", err);
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn run_deposit_call_handler(
+    connection_pool: ConnectionPool,
+    eth_watch_req: mpsc::Sender<EthWatchRequest>,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    poll_interval: Duration,
+) -> JoinHandle<()> {
+    let mut handler = DepositCallHandler {
+        connection_pool,
+        eth_watch_req,
+        mempool_tx_sender,
+        poll_interval,
+        last_eth_block: None,
+    };
+    tokio::spawn(async move { handler.run().await })
+}
@@ -0,0 +1,131 @@
+//! The tracker of the deposits which can't be credited by the network.
+//!
+//! The priority operation has to be processed before its deadline block, otherwise the contract
+//! allows to activate the exodus mode and the deposit won't ever be credited on L2. In this case
+//! the funds are returned on L1: the deposit has to be canceled via
+//! `cancelOutstandingDepositsForExodusMode`, after that the recipient can take the funds out via
+//! `withdrawPendingBalance`.
+//!
+//! The tracker records such deposits as refundable, so users can find them through the API instead
+//! of funds silently sitting in limbo. Records of the deposits which were eventually executed are
+//! removed.
+
+// Built-in deps
+use std::time::Duration;
+// External uses
+use tokio::{task::JoinHandle, time};
+// Workspace deps
+use zksync_storage::{deposit_refunds::REFUND_REASON_EXPIRED, ConnectionPool};
+use zksync_types::{PriorityOp, ZkSyncPriorityOp};
+// Local deps
+use crate::eth_watch::LatestEthBlock;
+
+/// Returns the deposits which deadline has passed.
+fn expired_deposits(ops: &[PriorityOp], latest_eth_block: u64) -> Vec<&PriorityOp> {
+    ops.iter()
+        .filter(|op| {
+            matches!(op.data, ZkSyncPriorityOp::Deposit(_)) && op.deadline_block < latest_eth_block
+        })
+        .collect()
+}
+
+async fn update_refundable_deposits(
+    pool: &ConnectionPool,
+    latest_eth_block: u64,
+) -> anyhow::Result<()> {
+    let mut storage = pool.access_storage().await?;
+    let mut transaction = storage.start_transaction().await?;
+
+    let pending_ops: Vec<_> = transaction
+        .chain()
+        .mempool_schema()
+        .get_confirmed_priority_ops()
+        .await?
+        .into_iter()
+        .collect();
+    for op in expired_deposits(&pending_ops, latest_eth_block) {
+        vlog::warn!(
+            "Deposit with serial id {} has expired and has to be refunded on L1",
+            op.serial_id
+        );
+        transaction
+            .deposit_refunds_schema()
+            .store_refundable_deposit(op, REFUND_REASON_EXPIRED)
+            .await?;
+    }
+    transaction
+        .deposit_refunds_schema()
+        .remove_executed_deposits()
+        .await?;
+
+    transaction.commit().await?;
+    Ok(())
+}
+
+#[must_use]
+pub fn run_deposit_refunds_tracker(
+    pool: ConnectionPool,
+    latest_eth_block: LatestEthBlock,
+    interval: Duration,
+) -> JoinHandle<()> {
+    let mut timer = time::interval(interval);
+
+    tokio::spawn(async move {
+        loop {
+            timer.tick().await;
+            // The watcher hasn't observed any Ethereum block yet.
+            let latest_eth_block = latest_eth_block.get();
+            if latest_eth_block == 0 {
+                continue;
+            }
+            if let Err(err) = update_refundable_deposits(&pool, latest_eth_block).await {
+                vlog::error!("Failed to update the refundable deposits: {}", err);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::BigUint;
+    use zksync_types::{AccountId, Address, Deposit, FullExit, TokenId, H256};
+
+    fn priority_op(serial_id: u64, data: ZkSyncPriorityOp, deadline_block: u64) -> PriorityOp {
+        PriorityOp {
+            serial_id,
+            data,
+            deadline_block,
+            eth_hash: H256::zero(),
+            eth_block: 1,
+            eth_block_index: Some(1),
+        }
+    }
+
+    #[test]
+    fn expired_deposits_detection() {
+        let deposit = ZkSyncPriorityOp::Deposit(Deposit {
+            from: Address::zero(),
+            token: TokenId(0),
+            amount: BigUint::from(100u32),
+            to: Address::zero(),
+        });
+        let full_exit = ZkSyncPriorityOp::FullExit(FullExit {
+            account_id: AccountId(1),
+            eth_address: Address::zero(),
+            token: TokenId(0),
+            is_legacy: false,
+        });
+        let ops = vec![
+            priority_op(1, deposit.clone(), 10),
+            priority_op(2, deposit, 20),
+            priority_op(3, full_exit, 10),
+        ];
+
+        assert!(expired_deposits(&ops, 10).is_empty());
+        let expired = expired_deposits(&ops, 15);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].serial_id, 1);
+        assert_eq!(expired_deposits(&ops, 21).len(), 2);
+    }
+}
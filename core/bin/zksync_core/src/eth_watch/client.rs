@@ -1,21 +1,23 @@
-use std::{convert::TryFrom, time::Instant};
+use std::{collections::HashMap, convert::TryFrom, time::Instant};
 
 use anyhow::format_err;
 use ethabi::Hash;
 use std::fmt::Debug;
 use web3::{
     contract::Options,
-    transports::http,
     types::{BlockNumber, FilterBuilder, Log},
-    Web3,
 };
 
-use zksync_contracts::{governance_contract, zksync_contract};
+use itertools::Itertools;
+use zksync_contracts::{governance_contract, upgrade_gatekeeper, zksync_contract_for_version};
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
 use zksync_types::{
-    Address, NewTokenEvent, Nonce, PriorityOp, RegisterNFTFactoryEvent, H160, U256,
+    Address, DepositCallEvent, NewTokenEvent, Nonce, PriorityOp, RegisterNFTFactoryEvent, H160,
+    U256,
 };
 
+use super::upgrades::{ContractVersions, UpgradeEvent, UpgradeTopics};
+
 struct ContractTopics {
     new_priority_request: Hash,
     new_token: Hash,
@@ -53,22 +55,50 @@ pub trait EthClient {
         from: BlockNumber,
         to: BlockNumber,
     ) -> anyhow::Result<Vec<RegisterNFTFactoryEvent>>;
+    /// Returns the events of the deposits carrying the L2 call, if the deposit router is set.
+    async fn get_deposit_call_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> anyhow::Result<Vec<DepositCallEvent>>;
     async fn get_new_tokens_events(
         &self,
         from: BlockNumber,
         to: BlockNumber,
     ) -> anyhow::Result<Vec<NewTokenEvent>>;
+    async fn get_upgrade_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> anyhow::Result<Vec<UpgradeEvent>>;
+    /// Returns the current version of the contracts reported by the Upgrade Gatekeeper.
+    async fn get_contract_version(&self) -> anyhow::Result<u32>;
+    /// Makes the client parse events and encode calls according to the versions of the contract
+    /// active at the corresponding blocks.
+    fn set_contract_versions(&mut self, versions: ContractVersions);
     async fn block_number(&self) -> anyhow::Result<u64>;
     async fn get_auth_fact(&self, address: Address, nonce: Nonce) -> anyhow::Result<Vec<u8>>;
     async fn get_auth_fact_reset_time(&self, address: Address, nonce: Nonce)
         -> anyhow::Result<u64>;
 }
 
+/// ABI of the main contract of a certain version.
+struct VersionedContract {
+    abi: ethabi::Contract,
+    topics: ContractTopics,
+}
+
 pub struct EthHttpClient {
     client: EthereumGateway,
-    topics: ContractTopics,
+    contracts: HashMap<u32, VersionedContract>,
+    contract_versions: ContractVersions,
+    /// Version of the main contract deployed at genesis, i.e. with the gatekeeper version `0`.
+    init_contract_version: u32,
+    upgrade_topics: UpgradeTopics,
     zksync_contract_addr: H160,
     governance_contract_addr: H160,
+    upgrade_gatekeeper_addr: H160,
+    deposit_router_addr: Option<H160>,
 }
 
 impl EthHttpClient {
@@ -76,31 +106,45 @@ impl EthHttpClient {
         client: EthereumGateway,
         zksync_contract_addr: H160,
         governance_contract_addr: H160,
+        upgrade_gatekeeper_addr: H160,
+        deposit_router_addr: Option<H160>,
+        init_contract_version: u32,
     ) -> Self {
-        let topics = ContractTopics::new(&zksync_contract(), &governance_contract());
-        Self {
+        let mut client = Self {
             client,
-            topics,
+            contracts: HashMap::new(),
+            contract_versions: ContractVersions::new(0),
+            init_contract_version,
+            upgrade_topics: UpgradeTopics::new(&upgrade_gatekeeper()),
             zksync_contract_addr,
             governance_contract_addr,
-        }
+            upgrade_gatekeeper_addr,
+            deposit_router_addr,
+        };
+        client.set_contract_versions(ContractVersions::new(0));
+        client
     }
 
-    async fn get_events<T>(
+    fn contract(&self, version: u32) -> &VersionedContract {
+        self.contracts
+            .get(&version)
+            .expect("ABI of every known contract version is loaded")
+    }
+
+    /// Returns the ABI of the latest version of the main contract.
+    fn latest_contract(&self) -> &VersionedContract {
+        self.contract(self.contract_versions.latest())
+    }
+
+    async fn get_logs(
         &self,
         from: BlockNumber,
         to: BlockNumber,
+        addresses: Vec<H160>,
         topics: Vec<Hash>,
-    ) -> anyhow::Result<Vec<T>>
-    where
-        T: TryFrom<Log>,
-        T::Error: Debug,
-    {
+    ) -> anyhow::Result<Vec<Log>> {
         let filter = FilterBuilder::default()
-            .address(vec![
-                self.zksync_contract_addr,
-                self.governance_contract_addr,
-            ])
+            .address(addresses)
             .from_block(from)
             .to_block(to)
             .topics(Some(topics), None, None, None)
@@ -116,6 +160,64 @@ impl EthHttpClient {
         } else {
             vlog::warn!("Some of the log entries does not have log_index, we rely on the provided logs order");
         }
+        Ok(logs)
+    }
+
+    /// Loads the priority operations, every log is matched against the event signature of the
+    /// contract version that was active at the log's block.
+    async fn get_versioned_priority_op_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> anyhow::Result<Vec<PriorityOp>> {
+        let topics = self
+            .contract_versions
+            .versions()
+            .map(|version| self.contract(version).topics.new_priority_request)
+            .unique()
+            .collect();
+        let logs = self
+            .get_logs(
+                from,
+                to,
+                vec![self.zksync_contract_addr, self.governance_contract_addr],
+                topics,
+            )
+            .await?;
+
+        logs.into_iter()
+            .filter(|log| match log.block_number {
+                Some(block) => {
+                    let version = self.contract_versions.version_at(block.as_u64());
+                    log.topics.first() == Some(&self.contract(version).topics.new_priority_request)
+                }
+                None => true,
+            })
+            .map(|event| {
+                PriorityOp::try_from(event)
+                    .map_err(|e| format_err!("Failed to parse event log from ETH: {:?}", e))
+            })
+            .collect()
+    }
+
+    async fn get_events<T>(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        topics: Vec<Hash>,
+    ) -> anyhow::Result<Vec<T>>
+    where
+        T: TryFrom<Log>,
+        T::Error: Debug,
+    {
+        let logs = self
+            .get_logs(
+                from,
+                to,
+                vec![self.zksync_contract_addr, self.governance_contract_addr],
+                topics,
+            )
+            .await?;
 
         logs.into_iter()
             .map(|event| {
@@ -135,9 +237,7 @@ impl EthClient for EthHttpClient {
     ) -> anyhow::Result<Vec<PriorityOp>> {
         let start = Instant::now();
 
-        let result = self
-            .get_events(from, to, vec![self.topics.new_priority_request])
-            .await;
+        let result = self.get_versioned_priority_op_events(from, to).await;
 
         if let Err(err) = &result {
             // Check whether the error is related to way too many results being returned.
@@ -199,7 +299,11 @@ impl EthClient for EthHttpClient {
         let start = Instant::now();
 
         let result = self
-            .get_events(from, to, vec![self.topics.factory_registered])
+            .get_events(
+                from,
+                to,
+                vec![self.latest_contract().topics.factory_registered],
+            )
             .await;
         metrics::histogram!(
             "eth_watcher.get_new_register_nft_factory_events",
@@ -208,6 +312,37 @@ impl EthClient for EthHttpClient {
         result
     }
 
+    async fn get_deposit_call_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> anyhow::Result<Vec<DepositCallEvent>> {
+        let deposit_router_addr = match self.deposit_router_addr {
+            Some(address) => address,
+            None => return Ok(Vec::new()),
+        };
+        let start = Instant::now();
+
+        let result = self
+            .get_logs(
+                from,
+                to,
+                vec![deposit_router_addr],
+                vec![DepositCallEvent::topic()],
+            )
+            .await
+            .and_then(|logs| {
+                logs.into_iter()
+                    .map(|event| {
+                        DepositCallEvent::try_from(event)
+                            .map_err(|e| format_err!("Failed to parse event log from ETH: {:?}", e))
+                    })
+                    .collect()
+            });
+        metrics::histogram!("eth_watcher.get_deposit_call_events", start.elapsed());
+        result
+    }
+
     async fn get_new_tokens_events(
         &self,
         from: BlockNumber,
@@ -215,23 +350,79 @@ impl EthClient for EthHttpClient {
     ) -> anyhow::Result<Vec<NewTokenEvent>> {
         let start = Instant::now();
 
-        let result = self.get_events(from, to, vec![self.topics.new_token]).await;
+        let result = self
+            .get_events(from, to, vec![self.latest_contract().topics.new_token])
+            .await;
         metrics::histogram!("eth_watcher.get_new_tokens_event", start.elapsed());
         result
     }
 
+    async fn get_upgrade_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> anyhow::Result<Vec<UpgradeEvent>> {
+        let start = Instant::now();
+
+        let result = self
+            .get_logs(
+                from,
+                to,
+                vec![self.upgrade_gatekeeper_addr],
+                self.upgrade_topics.all(),
+            )
+            .await
+            .and_then(|logs| {
+                logs.iter()
+                    .map(|log| UpgradeEvent::from_log(log, &self.upgrade_topics))
+                    .collect()
+            });
+        metrics::histogram!("eth_watcher.get_upgrade_events", start.elapsed());
+        result
+    }
+
+    async fn get_contract_version(&self) -> anyhow::Result<u32> {
+        self.client
+            .call_contract_function(
+                "versionId",
+                (),
+                None,
+                Options::default(),
+                None,
+                self.upgrade_gatekeeper_addr,
+                upgrade_gatekeeper(),
+            )
+            .await
+            .map_err(|e| format_err!("Failed to query gatekeeper versionId: {}", e))
+            .map(|version: U256| version.as_u32())
+    }
+
+    fn set_contract_versions(&mut self, versions: ContractVersions) {
+        for version in versions.versions() {
+            let init_contract_version = self.init_contract_version;
+            self.contracts.entry(version).or_insert_with(|| {
+                let abi = zksync_contract_for_version(init_contract_version + version);
+                let topics = ContractTopics::new(&abi, &governance_contract());
+                VersionedContract { abi, topics }
+            });
+        }
+        self.contract_versions = versions;
+    }
+
     async fn block_number(&self) -> anyhow::Result<u64> {
         Ok(self.client.block_number().await?.as_u64())
     }
 
     async fn get_auth_fact(&self, address: Address, nonce: Nonce) -> anyhow::Result<Vec<u8>> {
         self.client
-            .call_main_contract_function(
+            .call_contract_function(
                 "authFacts",
                 (address, u64::from(*nonce)),
                 None,
                 Options::default(),
                 None,
+                self.zksync_contract_addr,
+                self.latest_contract().abi.clone(),
             )
             .await
             .map_err(|e| format_err!("Failed to query contract authFacts: {}", e))
@@ -243,21 +434,17 @@ impl EthClient for EthHttpClient {
         nonce: Nonce,
     ) -> anyhow::Result<u64> {
         self.client
-            .call_main_contract_function(
+            .call_contract_function(
                 "authFactsResetTimer",
                 (address, u64::from(*nonce)),
                 None,
                 Options::default(),
                 None,
+                self.zksync_contract_addr,
+                self.latest_contract().abi.clone(),
             )
             .await
             .map_err(|e| format_err!("Failed to query contract authFacts: {}", e))
             .map(|res: U256| res.as_u64())
     }
 }
-
-pub async fn get_web3_block_number(web3: &Web3<http::Http>) -> anyhow::Result<u64> {
-    let block_number = web3.eth().block_number().await?.as_u64();
-
-    Ok(block_number)
-}
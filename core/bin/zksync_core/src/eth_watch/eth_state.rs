@@ -2,7 +2,9 @@
 use std::collections::HashMap;
 // External uses
 // Workspace deps
-use zksync_types::{NewTokenEvent, PriorityOp, RegisterNFTFactoryEvent, SerialId};
+use zksync_types::{
+    DepositCallEvent, NewTokenEvent, PriorityOp, RegisterNFTFactoryEvent, SerialId,
+};
 // Local deps
 use super::received_ops::ReceivedPriorityOp;
 
@@ -39,6 +41,8 @@ pub struct ETHState {
     new_tokens: Vec<NewTokenEvent>,
     /// List of events denoting registered factories for NFT withdrawing
     register_nft_factory_events: Vec<RegisterNFTFactoryEvent>,
+    /// List of the deposits carrying the L2 call.
+    deposit_call_events: Vec<DepositCallEvent>,
 }
 
 impl ETHState {
@@ -49,6 +53,7 @@ impl ETHState {
         priority_queue: HashMap<SerialId, ReceivedPriorityOp>,
         new_tokens: Vec<NewTokenEvent>,
         register_nft_factory_events: Vec<RegisterNFTFactoryEvent>,
+        deposit_call_events: Vec<DepositCallEvent>,
     ) -> Self {
         assert!(
             last_ethereum_block_backup <= last_ethereum_block,
@@ -67,6 +72,7 @@ impl ETHState {
             priority_queue,
             new_tokens,
             register_nft_factory_events,
+            deposit_call_events,
         }
     }
 
@@ -86,6 +92,10 @@ impl ETHState {
         &self.register_nft_factory_events
     }
 
+    pub fn deposit_call_events(&self) -> &[DepositCallEvent] {
+        &self.deposit_call_events
+    }
+
     pub fn new_tokens(&self) -> &[NewTokenEvent] {
         &self.new_tokens
     }
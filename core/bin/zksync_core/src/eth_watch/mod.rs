@@ -1,12 +1,26 @@
 //! Ethereum watcher polls the Ethereum node for new events
 //! such as PriorityQueue events or NewToken events.
 //! New events are accepted to the zkSync network once they have the sufficient amount of confirmations.
+//! Priority operations from the blocks up to the latest one (i.e. with zero confirmations) are also
+//! passed to the mempool as unconfirmed, so the API can report the provisional deposits.
 //!
-//! Poll interval is configured using the `ETH_POLL_INTERVAL` constant.
+//! Gaps in the serial ids of the priority operations (e.g. if the node has pruned the logs or
+//! has been misconfigured) are backfilled by re-fetching the block range where the missing
+//! operations are expected to be.
+//!
+//! Upgrades of the main contract are tracked via the Upgrade Gatekeeper events, so that the events of
+//! every block are parsed according to the contract version active at that block.
+//!
+//! Poll interval is adaptive: the node is polled more often after new priority operations were
+//! observed and less often during idle periods, see `ETH_WATCH_ETH_NODE_*` environment variables.
 //! Number of confirmations is configured using the `CONFIRMATIONS_FOR_ETH_EVENT` environment variable.
 
 // Built-in deps
 use std::collections::HashMap;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 use std::time::{Duration, Instant};
 
 // External uses
@@ -16,7 +30,7 @@ use futures::{
 };
 use thiserror::Error;
 
-pub use client::{get_web3_block_number, EthHttpClient};
+pub use client::EthHttpClient;
 use itertools::Itertools;
 use tokio::{task::JoinHandle, time};
 use web3::types::BlockNumber;
@@ -25,14 +39,25 @@ use zksync_config::{ContractsConfig, ETHWatchConfig};
 use zksync_crypto::params::PRIORITY_EXPIRATION;
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
 use zksync_mempool::MempoolTransactionRequest;
-use zksync_types::{NewTokenEvent, PriorityOp, RegisterNFTFactoryEvent, SerialId};
+use zksync_types::{
+    DepositCallEvent, NewTokenEvent, PriorityOp, RegisterNFTFactoryEvent, SerialId,
+};
+use zksync_utils::chaos::{self, FaultPoint};
 
 // Local deps
-use self::{client::EthClient, eth_state::ETHState, received_ops::sift_outdated_ops};
+use self::{
+    client::EthClient,
+    eth_state::ETHState,
+    poll_scheduler::{ObservedPriorityOps, PollScheduler},
+    received_ops::sift_outdated_ops,
+    upgrades::ContractVersions,
+};
 
 mod client;
 mod eth_state;
+mod poll_scheduler;
 mod received_ops;
+mod upgrades;
 
 #[cfg(test)]
 mod tests;
@@ -67,6 +92,10 @@ pub enum EthWatchRequest {
         last_eth_block: Option<u64>,
         resp: oneshot::Sender<Vec<RegisterNFTFactoryEvent>>,
     },
+    GetDepositCalls {
+        last_eth_block: Option<u64>,
+        resp: oneshot::Sender<Vec<DepositCallEvent>>,
+    },
 }
 
 #[derive(Debug, Error)]
@@ -77,10 +106,44 @@ fn is_missing_priority_op_error(error: &anyhow::Error) -> bool {
     error.is::<MissingPriorityOpError>()
 }
 
+/// Returns the first missing serial id starting from `next_serial_id` along with the closest
+/// present serial id following it.
+fn find_priority_ops_gap<'a>(
+    next_serial_id: SerialId,
+    serial_ids: impl Iterator<Item = &'a SerialId>,
+) -> Option<(SerialId, SerialId)> {
+    let mut expected = next_serial_id;
+    for serial_id in serial_ids.sorted() {
+        if *serial_id > expected {
+            return Some((expected, *serial_id));
+        }
+        expected = expected.max(*serial_id + 1);
+    }
+    None
+}
+
+/// Shared number of the last Ethereum block observed by the watcher.
+/// Allows other actors to reason about L1 deadlines without querying the Ethereum node.
+#[derive(Debug, Clone, Default)]
+pub struct LatestEthBlock(Arc<AtomicU64>);
+
+impl LatestEthBlock {
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn set(&self, block: u64) {
+        self.0.store(block, Ordering::Relaxed);
+    }
+}
+
 pub struct EthWatch<W: EthClient> {
     client: W,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     eth_state: ETHState,
+    latest_eth_block: LatestEthBlock,
+    observed_priority_ops: ObservedPriorityOps,
+    contract_versions: ContractVersions,
     /// All ethereum events are accepted after sufficient confirmations to eliminate risk of block reorg.
     number_of_confirmations_for_event: u64,
     mode: WatcherMode,
@@ -96,6 +159,9 @@ impl<W: EthClient> EthWatch<W> {
             client,
             mempool_tx_sender,
             eth_state: ETHState::default(),
+            latest_eth_block: LatestEthBlock::default(),
+            observed_priority_ops: ObservedPriorityOps::default(),
+            contract_versions: ContractVersions::new(0),
             mode: WatcherMode::Working,
             number_of_confirmations_for_event,
         }
@@ -103,9 +169,86 @@ impl<W: EthClient> EthWatch<W> {
 
     /// Atomically replaces the stored Ethereum state.
     fn set_new_state(&mut self, new_state: ETHState) {
+        self.latest_eth_block.set(new_state.last_ethereum_block());
+        let next_unconfirmed_op_id = new_state
+            .unconfirmed_queue()
+            .iter()
+            .map(|op| op.serial_id + 1)
+            .max()
+            .unwrap_or_default();
+        self.observed_priority_ops
+            .set(new_state.next_priority_op_id().max(next_unconfirmed_op_id));
         self.eth_state = new_state;
     }
 
+    /// Restores the versions of the main contract active during the blocks the state is restored from.
+    async fn restore_contract_versions(&mut self, last_ethereum_block: u64) -> anyhow::Result<()> {
+        let current_version = self.client.get_contract_version().await?;
+        let events = self
+            .client
+            .get_upgrade_events(
+                BlockNumber::Number(
+                    last_ethereum_block
+                        .saturating_sub(PRIORITY_EXPIRATION)
+                        .into(),
+                ),
+                BlockNumber::Number(last_ethereum_block.into()),
+            )
+            .await?;
+
+        self.contract_versions = ContractVersions::restore(current_version, &events);
+        self.client
+            .set_contract_versions(self.contract_versions.clone());
+        metrics::gauge!(
+            "eth_watcher.contract_version",
+            self.contract_versions.latest() as f64
+        );
+        Ok(())
+    }
+
+    /// Applies the Upgrade Gatekeeper events of the block range. Once the upgrade is complete,
+    /// the events starting from the upgrade block are parsed according to the new contract version.
+    async fn update_contract_versions(
+        &mut self,
+        from_block: u64,
+        to_block: u64,
+    ) -> anyhow::Result<()> {
+        let events = self
+            .client
+            .get_upgrade_events(
+                BlockNumber::Number(from_block.into()),
+                BlockNumber::Number(to_block.into()),
+            )
+            .await?;
+
+        let pending_upgrade = self.contract_versions.pending_upgrade();
+        let mut upgraded = false;
+        for event in &events {
+            upgraded |= self.contract_versions.apply(event);
+        }
+
+        if upgraded {
+            vlog::info!(
+                "Contracts were upgraded to version {}",
+                self.contract_versions.latest()
+            );
+            self.client
+                .set_contract_versions(self.contract_versions.clone());
+            metrics::gauge!(
+                "eth_watcher.contract_version",
+                self.contract_versions.latest() as f64
+            );
+        } else if self.contract_versions.pending_upgrade() != pending_upgrade {
+            match self.contract_versions.pending_upgrade() {
+                Some(version) => {
+                    vlog::warn!("Upgrade of the contracts to version {} is pending", version)
+                }
+                None => vlog::warn!("Upgrade of the contracts was canceled"),
+            }
+        }
+        Ok(())
+    }
+
     async fn get_unconfirmed_ops(
         &mut self,
         current_ethereum_block: u64,
@@ -141,6 +284,25 @@ impl<W: EthClient> EthWatch<W> {
         let updated_state = self
             .update_eth_state(last_ethereum_block, block_difference)
             .await?;
+        let mut updated_priority_queue = updated_state.priority_queue().clone();
+        if let Some((missing_serial_id, next_serial_id)) =
+            find_priority_ops_gap(next_priority_op_id, updated_priority_queue.keys())
+        {
+            let next_op = updated_priority_queue[&next_serial_id].as_ref().clone();
+            // The operation preceding the gap is either in the new or in the previous state.
+            let preceding_op_block = missing_serial_id.checked_sub(1).and_then(|serial_id| {
+                updated_priority_queue
+                    .get(&serial_id)
+                    .or_else(|| self.eth_state.priority_queue().get(&serial_id))
+                    .map(|op| op.as_ref().eth_block)
+            });
+            for op in self
+                .backfill_priority_ops(missing_serial_id, &next_op, preceding_op_block)
+                .await?
+            {
+                updated_priority_queue.insert(op.serial_id, op.into());
+            }
+        }
 
         // It's assumed that for the current state all priority operations have consecutive ids,
         // i.e. there're no gaps. Thus, there're two opportunities for missing them: either
@@ -151,11 +313,7 @@ impl<W: EthClient> EthWatch<W> {
         let mut priority_queue = sift_outdated_ops(self.eth_state.priority_queue());
 
         // Iterate through new priority operations sorted by their serial id.
-        for (serial_id, op) in updated_state
-            .priority_queue()
-            .iter()
-            .sorted_by_key(|(id, _)| **id)
-        {
+        for (serial_id, op) in updated_priority_queue.iter().sorted_by_key(|(id, _)| **id) {
             if *serial_id > next_priority_op_id {
                 // Updated state misses some logs for new priority operations.
                 // We have to revert the block range back. This will only move the watcher
@@ -191,6 +349,14 @@ impl<W: EthClient> EthWatch<W> {
         register_nft_factory_events.sort_by_key(|factory_event| factory_event.creator_address);
         register_nft_factory_events.dedup_by_key(|factory_event| factory_event.creator_address);
 
+        let mut deposit_call_events = self.eth_state.deposit_call_events().to_vec();
+        for event in updated_state.deposit_call_events() {
+            deposit_call_events.push(event.clone());
+        }
+        // Remove duplicates.
+        deposit_call_events.sort_by_key(|call_event| call_event.serial_id);
+        deposit_call_events.dedup_by_key(|call_event| call_event.serial_id);
+
         let new_state = ETHState::new(
             last_ethereum_block,
             previous_ethereum_block,
@@ -198,12 +364,87 @@ impl<W: EthClient> EthWatch<W> {
             priority_queue,
             new_tokens,
             register_nft_factory_events,
+            deposit_call_events,
         );
         self.set_new_state(new_state);
         Ok(())
     }
 
+    /// Re-fetches the priority operations from the block range between the last known operation
+    /// preceding the gap and the operation following it.
+    async fn backfill_priority_ops(
+        &mut self,
+        missing_serial_id: SerialId,
+        next_op: &PriorityOp,
+        preceding_op_block: Option<u64>,
+    ) -> anyhow::Result<Vec<PriorityOp>> {
+        // If the preceding operation is unknown, the missing one still can't be older
+        // than the expiration period of the next one.
+        let from_block = preceding_op_block
+            .unwrap_or_else(|| next_op.eth_block.saturating_sub(PRIORITY_EXPIRATION));
+        let to_block = next_op.eth_block;
+
+        let backfilled_ops: Vec<_> = self
+            .client
+            .get_priority_op_events(
+                BlockNumber::Number(from_block.into()),
+                BlockNumber::Number(to_block.into()),
+            )
+            .await?
+            .into_iter()
+            .filter(|op| op.serial_id >= missing_serial_id && op.serial_id < next_op.serial_id)
+            .collect();
+
+        let missing_count = next_op.serial_id - missing_serial_id;
+        if backfilled_ops.len() as u64 == missing_count {
+            vlog::warn!(
+                "Backfilled priority operations with serial ids {}..{} from blocks {}-{}",
+                missing_serial_id,
+                next_op.serial_id,
+                from_block,
+                to_block
+            );
+            metrics::counter!("eth_watcher.backfilled_priority_ops", missing_count);
+        } else {
+            vlog::error!(
+                "Failed to backfill priority operations with serial ids {}..{} from blocks {}-{}, \
+                 found {} of {}",
+                missing_serial_id,
+                next_op.serial_id,
+                from_block,
+                to_block,
+                backfilled_ops.len(),
+                missing_count
+            );
+            metrics::increment_counter!("eth_watcher.priority_ops_gap_unfilled");
+        }
+
+        if !backfilled_ops.is_empty() {
+            self.send_priority_ops_to_mempool(backfilled_ops.clone(), true)
+                .await?;
+        }
+        Ok(backfilled_ops)
+    }
+
+    async fn send_priority_ops_to_mempool(
+        &mut self,
+        ops: Vec<PriorityOp>,
+        confirmed: bool,
+    ) -> anyhow::Result<()> {
+        let (sender, receiver) = oneshot::channel();
+        self.mempool_tx_sender
+            .send(MempoolTransactionRequest::NewPriorityOps(
+                ops, confirmed, sender,
+            ))
+            .await?;
+
+        // TODO maybe retry? It can be the only problem is database
+        receiver.await.expect("Mempool actor was dropped")?;
+        Ok(())
+    }
+
     async fn restore_state_from_eth(&mut self, last_ethereum_block: u64) -> anyhow::Result<()> {
+        self.restore_contract_versions(last_ethereum_block).await?;
         let new_state = self
             .update_eth_state(last_ethereum_block, PRIORITY_EXPIRATION)
             .await?;
@@ -224,6 +465,9 @@ impl<W: EthClient> EthWatch<W> {
         let previous_block_with_accepted_events =
             new_block_with_accepted_events.saturating_sub(unprocessed_blocks_amount);
 
+        // The upgrade may affect both the confirmed and the unconfirmed events.
+        self.update_contract_versions(previous_block_with_accepted_events, current_ethereum_block)
+            .await?;
         let unconfirmed_queue = self.get_unconfirmed_ops(current_ethereum_block).await?;
         let priority_queue = self
             .client
@@ -254,6 +498,14 @@ impl<W: EthClient> EthWatch<W> {
             )
             .await?;
 
+        let deposit_call_events = self
+            .client
+            .get_deposit_call_events(
+                BlockNumber::Number(previous_block_with_accepted_events.into()),
+                BlockNumber::Number(new_block_with_accepted_events.into()),
+            )
+            .await?;
+
         let mut new_priority_op_ids: Vec<_> = priority_queue_map.keys().cloned().collect();
         new_priority_op_ids.sort_unstable();
         vlog::debug!(
@@ -264,29 +516,11 @@ impl<W: EthClient> EthWatch<W> {
         );
 
         // Add unconfirmed priority ops to queue
-        let (sender, receiver) = oneshot::channel();
-        self.mempool_tx_sender
-            .send(MempoolTransactionRequest::NewPriorityOps(
-                unconfirmed_queue.clone(),
-                false,
-                sender,
-            ))
+        self.send_priority_ops_to_mempool(unconfirmed_queue.clone(), false)
             .await?;
-
-        // TODO maybe retry? It can be the only problem is database
-        receiver.await.expect("Mempool actor was dropped")?;
         // Add confirmed priority ops to queue
-        let (sender, receiver) = oneshot::channel();
-        self.mempool_tx_sender
-            .send(MempoolTransactionRequest::NewPriorityOps(
-                priority_queue,
-                true,
-                sender,
-            ))
+        self.send_priority_ops_to_mempool(priority_queue, true)
             .await?;
-
-        // TODO maybe retry? It can be the only problem is database
-        receiver.await.expect("Mempool actor was dropped")?;
         // The backup block number is not used.
         let state = ETHState::new(
             current_ethereum_block,
@@ -295,6 +529,7 @@ impl<W: EthClient> EthWatch<W> {
             priority_queue_map,
             new_tokens,
             new_register_nft_factory_events,
+            deposit_call_events,
         );
         Ok(state)
     }
@@ -315,6 +550,15 @@ impl<W: EthClient> EthWatch<W> {
 
         events
     }
+    fn get_deposit_calls(&self, last_block_number: Option<u64>) -> Vec<DepositCallEvent> {
+        self.eth_state
+            .deposit_call_events()
+            .iter()
+            .filter(|event| Some(event.eth_block) > last_block_number)
+            .cloned()
+            .collect()
+    }
+
     fn get_new_tokens(&self, last_block_number: Option<u64>) -> Vec<NewTokenEvent> {
         let mut new_tokens = self.eth_state.new_tokens().to_vec();
 
@@ -331,6 +575,7 @@ impl<W: EthClient> EthWatch<W> {
 
     async fn poll_eth_node(&mut self) -> anyhow::Result<()> {
         let start = Instant::now();
+        chaos::inject_error(FaultPoint::EthWatch)?;
         let last_block_number = self.client.block_number().await?;
 
         if last_block_number > self.eth_state.last_ethereum_block() {
@@ -404,6 +649,14 @@ impl<W: EthClient> EthWatch<W> {
 
     pub async fn run(mut self, mut eth_watch_req: mpsc::Receiver<EthWatchRequest>) {
         while let Some(request) = eth_watch_req.next().await {
+            chaos::inject_delay(FaultPoint::EthWatch).await;
+            // Only polls may be dropped: other requests are awaited by the senders which expect
+            // the response.
+            if matches!(request, EthWatchRequest::PollETHNode)
+                && chaos::inject_drop(FaultPoint::EthWatch)
+            {
+                continue;
+            }
             match request {
                 EthWatchRequest::PollETHNode => {
                     if !self.polling_allowed() {
@@ -444,6 +697,12 @@ impl<W: EthClient> EthWatch<W> {
                     resp.send(self.get_register_factory_event(last_eth_block))
                         .ok();
                 }
+                EthWatchRequest::GetDepositCalls {
+                    last_eth_block,
+                    resp,
+                } => {
+                    resp.send(self.get_deposit_calls(last_eth_block)).ok();
+                }
             }
         }
     }
@@ -456,11 +715,15 @@ pub async fn start_eth_watch(
     contract_config: &ContractsConfig,
     eth_watcher_config: &ETHWatchConfig,
     mempool_req_sender: mpsc::Sender<MempoolTransactionRequest>,
+    latest_eth_block: LatestEthBlock,
 ) -> JoinHandle<()> {
     let eth_client = EthHttpClient::new(
         eth_gateway,
         contract_config.contract_addr,
         contract_config.governance_addr,
+        contract_config.upgrade_gatekeeper_addr,
+        contract_config.deposit_router_addr,
+        contract_config.init_contract_version,
     );
 
     let mut eth_watch = EthWatch::new(
@@ -468,17 +731,20 @@ pub async fn start_eth_watch(
         mempool_req_sender,
         eth_watcher_config.confirmations_for_eth_event,
     );
+    eth_watch.latest_eth_block = latest_eth_block;
+    let observed_priority_ops = eth_watch.observed_priority_ops.clone();
 
     eth_watch.restore_from_eth_using_latest_block_number().await;
 
     tokio::spawn(eth_watch.run(eth_req_receiver));
 
-    let poll_interval = eth_watcher_config.poll_interval();
+    let mut poll_scheduler = PollScheduler::new(eth_watcher_config);
     tokio::spawn(async move {
-        let mut timer = time::interval(poll_interval);
-
         loop {
-            timer.tick().await;
+            let poll_interval =
+                poll_scheduler.next_interval(observed_priority_ops.get(), Instant::now());
+            metrics::histogram!("eth_watcher.poll_interval", poll_interval);
+            time::sleep(poll_interval).await;
             eth_req_sender
                 .clone()
                 .send(EthWatchRequest::PollETHNode)
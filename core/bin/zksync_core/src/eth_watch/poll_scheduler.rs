@@ -0,0 +1,158 @@
+//! Adaptive scheduling of the Ethereum node polls.
+//!
+//! Priority operations tend to come in bursts, so after a new operation is observed the node is
+//! polled more often for a while. During idle periods the interval grows exponentially up to the
+//! configured maximum. Regardless of the activity the interval never gets below the one allowed
+//! by the requests limit of the node provider.
+
+// Built-in deps
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+// Workspace deps
+use zksync_config::ETHWatchConfig;
+
+/// Amount of requests to the Ethereum node made during a single poll: the block number,
+/// the Upgrade Gatekeeper events, the unconfirmed and confirmed priority operations,
+/// new tokens and NFT factories.
+const REQUESTS_PER_POLL: u64 = 6;
+
+/// Shared number of the priority operations observed by the watcher, including the unconfirmed ones.
+/// Allows the poll scheduler to detect the activity on L1.
+#[derive(Debug, Clone, Default)]
+pub struct ObservedPriorityOps(Arc<AtomicU64>);
+
+impl ObservedPriorityOps {
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn set(&self, count: u64) {
+        self.0.store(count, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PollScheduler {
+    poll_interval: Duration,
+    fast_poll_interval: Duration,
+    fast_poll_period: Duration,
+    max_poll_interval: Duration,
+    /// The shortest interval allowed by the requests limit of the node provider.
+    min_poll_interval: Duration,
+
+    current_interval: Duration,
+    /// Amount of the observed priority operations at the previous poll.
+    observed_priority_ops: Option<u64>,
+    last_activity: Option<Instant>,
+}
+
+impl PollScheduler {
+    pub fn new(config: &ETHWatchConfig) -> Self {
+        let min_poll_interval = config
+            .requests_per_second_limit()
+            .map(|limit| Duration::from_secs(REQUESTS_PER_POLL) / limit as u32)
+            .unwrap_or_default();
+
+        Self {
+            poll_interval: config.poll_interval(),
+            fast_poll_interval: config.fast_poll_interval(),
+            fast_poll_period: config.fast_poll_period(),
+            max_poll_interval: config.max_poll_interval().max(config.poll_interval()),
+            min_poll_interval,
+            current_interval: config.poll_interval(),
+            observed_priority_ops: None,
+            last_activity: None,
+        }
+    }
+
+    /// Returns the delay before the next poll given the amount of priority operations
+    /// observed by the watcher so far.
+    pub fn next_interval(&mut self, observed_priority_ops: u64, now: Instant) -> Duration {
+        // The operations restored at the watcher start are not considered as the new ones.
+        if matches!(self.observed_priority_ops, Some(observed) if observed_priority_ops > observed)
+        {
+            self.last_activity = Some(now);
+        }
+        self.observed_priority_ops = Some(observed_priority_ops);
+
+        let is_active = self.last_activity.map_or(false, |last_activity| {
+            now.duration_since(last_activity) < self.fast_poll_period
+        });
+        self.current_interval = if is_active {
+            self.fast_poll_interval
+        } else if self.current_interval < self.poll_interval {
+            self.poll_interval
+        } else {
+            (self.current_interval * 2).min(self.max_poll_interval)
+        };
+
+        self.current_interval.max(self.min_poll_interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(requests_per_second_limit: u64) -> ETHWatchConfig {
+        ETHWatchConfig {
+            confirmations_for_eth_event: 0,
+            eth_node_poll_interval: 1000,
+            eth_node_fast_poll_interval: 100,
+            eth_node_fast_poll_period: 10,
+            eth_node_max_poll_interval: 5000,
+            eth_node_requests_per_second_limit: requests_per_second_limit,
+            deposit_refunds_check_interval: 60,
+        }
+    }
+
+    #[test]
+    fn adaptive_interval() {
+        let mut scheduler = PollScheduler::new(&config(0));
+        let start = Instant::now();
+
+        // Idle watcher backs off up to the maximum interval.
+        let intervals: Vec<_> = (0..5)
+            .map(|_| scheduler.next_interval(0, start).as_millis())
+            .collect();
+        assert_eq!(intervals, vec![2000, 4000, 5000, 5000, 5000]);
+
+        // New priority operations speed the polling up for the configured period.
+        assert_eq!(
+            scheduler.next_interval(2, start),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            scheduler.next_interval(2, start + Duration::from_secs(9)),
+            Duration::from_millis(100)
+        );
+        let idle = start + Duration::from_secs(10);
+        assert_eq!(
+            scheduler.next_interval(2, idle),
+            Duration::from_millis(1000)
+        );
+        assert_eq!(
+            scheduler.next_interval(2, idle),
+            Duration::from_millis(2000)
+        );
+    }
+
+    #[test]
+    fn requests_limit() {
+        // 6 requests per poll with the limit of 10 requests per second.
+        let mut scheduler = PollScheduler::new(&config(10));
+        let start = Instant::now();
+        scheduler.next_interval(0, start);
+        assert_eq!(
+            scheduler.next_interval(1, start),
+            Duration::from_millis(600)
+        );
+        assert_eq!(
+            scheduler.next_interval(1, start + Duration::from_secs(20)),
+            Duration::from_millis(1000)
+        );
+    }
+}
@@ -5,8 +5,8 @@ use std::sync::Arc;
 use web3::types::{Address, BlockNumber};
 
 use zksync_types::{
-    AccountId, Deposit, FullExit, NewTokenEvent, Nonce, PriorityOp, RegisterNFTFactoryEvent,
-    SerialId, TokenId, ZkSyncPriorityOp, H256,
+    AccountId, Deposit, DepositCallEvent, FullExit, NewTokenEvent, Nonce, PriorityOp,
+    RegisterNFTFactoryEvent, SerialId, TokenId, ZkSyncPriorityOp, H256,
 };
 
 use futures::channel::mpsc;
@@ -14,11 +14,17 @@ use futures::StreamExt;
 use tokio::sync::RwLock;
 use zksync_mempool::MempoolTransactionRequest;
 
-use super::is_missing_priority_op_error;
-use crate::eth_watch::{client::EthClient, EthWatch};
+use super::{find_priority_ops_gap, is_missing_priority_op_error};
+use crate::eth_watch::{
+    client::EthClient,
+    upgrades::{ContractVersions, UpgradeEvent, UpgradeEventKind},
+    EthWatch,
+};
 
 struct FakeEthClientData {
     priority_ops: HashMap<u64, Vec<PriorityOp>>,
+    upgrade_events: Vec<UpgradeEvent>,
+    contract_versions: Option<ContractVersions>,
     last_block_number: u64,
 }
 
@@ -26,6 +32,8 @@ impl FakeEthClientData {
     fn new() -> Self {
         Self {
             priority_ops: Default::default(),
+            upgrade_events: Vec::new(),
+            contract_versions: None,
             last_block_number: 0,
         }
     }
@@ -66,6 +74,18 @@ impl FakeEthClient {
         }
     }
 
+    async fn add_upgrade_events(&mut self, events: &[UpgradeEvent]) {
+        self.inner
+            .write()
+            .await
+            .upgrade_events
+            .extend_from_slice(events);
+    }
+
+    async fn contract_versions(&self) -> Option<ContractVersions> {
+        self.inner.read().await.contract_versions.clone()
+    }
+
     async fn set_last_block_number(&mut self, block_number: u64) {
         let mut inner = self.inner.write().await;
         inner.last_block_number = block_number;
@@ -98,6 +118,14 @@ impl EthClient for FakeEthClient {
         Ok(Vec::new())
     }
 
+    async fn get_deposit_call_events(
+        &self,
+        _from: BlockNumber,
+        _to: BlockNumber,
+    ) -> anyhow::Result<Vec<DepositCallEvent>> {
+        Ok(Vec::new())
+    }
+
     async fn get_new_tokens_events(
         &self,
         _from: BlockNumber,
@@ -107,6 +135,44 @@ impl EthClient for FakeEthClient {
         Ok(Vec::new())
     }
 
+    async fn get_upgrade_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> anyhow::Result<Vec<UpgradeEvent>> {
+        let from = self.block_to_number(&from).await;
+        let to = self.block_to_number(&to).await;
+        Ok(self
+            .inner
+            .read()
+            .await
+            .upgrade_events
+            .iter()
+            .filter(|event| event.eth_block >= from && event.eth_block <= to)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_contract_version(&self) -> anyhow::Result<u32> {
+        Ok(self
+            .inner
+            .read()
+            .await
+            .upgrade_events
+            .iter()
+            .filter(|event| event.kind == UpgradeEventKind::UpgradeComplete)
+            .map(|event| event.version_id)
+            .max()
+            .unwrap_or_default())
+    }
+
+    fn set_contract_versions(&mut self, versions: ContractVersions) {
+        self.inner
+            .try_write()
+            .expect("fake client is not shared during the update")
+            .contract_versions = Some(versions);
+    }
+
     async fn block_number(&self) -> Result<u64, anyhow::Error> {
         Ok(self.inner.read().await.last_block_number)
     }
@@ -152,6 +218,7 @@ async fn fake_mempool(
                 channel.send(Ok(())).unwrap_or_default()
             }
             MempoolTransactionRequest::NewTxsBatch(_, _, _) => unreachable!(),
+            MempoolTransactionRequest::NewPrivateTxsBatch(_, _, _, _) => unreachable!(),
         }
     }
 }
@@ -546,3 +613,130 @@ async fn test_serial_id_gaps() {
     assert_eq!(watcher.eth_state.last_ethereum_block_backup(), 0);
     assert_eq!(watcher.eth_state.last_ethereum_block(), 3);
 }
+
+#[test]
+fn priority_ops_gap_detection() {
+    assert_eq!(find_priority_ops_gap(0, [0, 1, 2].iter()), None);
+    assert_eq!(find_priority_ops_gap(3, [].iter()), None);
+    // Already processed operations are ignored.
+    assert_eq!(find_priority_ops_gap(2, [1, 2, 3].iter()), None);
+    assert_eq!(find_priority_ops_gap(2, [5, 2, 6].iter()), Some((3, 5)));
+    assert_eq!(find_priority_ops_gap(0, [2, 3].iter()), Some((0, 2)));
+}
+
+/// Checks that the operations missed by the watcher are re-fetched from the block range
+/// between the known operations.
+#[tokio::test]
+async fn test_priority_ops_backfill() {
+    let (sender, receiver) = mpsc::channel(10);
+    let deposit = ZkSyncPriorityOp::Deposit(Deposit {
+        from: Default::default(),
+        token: TokenId(0),
+        amount: Default::default(),
+        to: [2u8; 20].into(),
+    });
+    let priority_op = |serial_id, eth_block| PriorityOp {
+        serial_id,
+        data: deposit.clone(),
+        deadline_block: 0,
+        eth_hash: [serial_id as u8; 32].into(),
+        eth_block,
+        eth_block_index: Some(1),
+    };
+
+    let data = Arc::new(RwLock::new(HashMap::new()));
+    tokio::spawn(fake_mempool(receiver, data.clone()));
+    let mut client = FakeEthClient::new();
+    client
+        .add_operations(&[priority_op(0, 1), priority_op(1, 1)])
+        .await;
+
+    let mut watcher = create_watcher(client.clone(), sender);
+    watcher.restore_state_from_eth(0).await.unwrap();
+    client.set_last_block_number(10).await;
+    watcher.poll_eth_node().await.unwrap();
+    assert_eq!(watcher.eth_state.next_priority_op_id(), 2);
+
+    // The operation in the already processed block range was missed by the node.
+    client
+        .add_operations(&[priority_op(2, 5), priority_op(3, 11)])
+        .await;
+    client.set_last_block_number(12).await;
+    watcher.poll_eth_node().await.unwrap();
+
+    assert_eq!(watcher.eth_state.next_priority_op_id(), 4);
+    assert!(watcher.eth_state.priority_queue().contains_key(&2));
+    let mempool_data = data.read().await;
+    assert_eq!(
+        mempool_data.get(&2).map(|(_, confirmed)| *confirmed),
+        Some(true)
+    );
+}
+
+fn upgrade_event(kind: UpgradeEventKind, version_id: u32, eth_block: u64) -> UpgradeEvent {
+    UpgradeEvent {
+        kind,
+        version_id,
+        eth_block,
+    }
+}
+
+#[test]
+fn contract_versions() {
+    let mut versions = ContractVersions::new(1);
+    assert_eq!(versions.version_at(100), 1);
+
+    assert!(!versions.apply(&upgrade_event(UpgradeEventKind::NoticePeriodStart, 1, 10)));
+    assert_eq!(versions.pending_upgrade(), Some(2));
+    assert!(versions.apply(&upgrade_event(UpgradeEventKind::UpgradeComplete, 2, 20)));
+    assert_eq!(versions.pending_upgrade(), None);
+    // Already applied events are ignored.
+    assert!(!versions.apply(&upgrade_event(UpgradeEventKind::UpgradeComplete, 2, 20)));
+
+    assert_eq!(versions.latest(), 2);
+    assert_eq!(versions.version_at(19), 1);
+    assert_eq!(versions.version_at(20), 2);
+    assert_eq!(versions.versions().collect::<Vec<_>>(), vec![1, 2]);
+
+    let restored = ContractVersions::restore(
+        2,
+        &[
+            upgrade_event(UpgradeEventKind::NoticePeriodStart, 1, 10),
+            upgrade_event(UpgradeEventKind::UpgradeComplete, 2, 20),
+        ],
+    );
+    assert_eq!(restored, versions);
+    assert_eq!(ContractVersions::restore(2, &[]), ContractVersions::new(2));
+}
+
+/// Checks that the watcher passes the versions of the contract to the client
+/// once the upgrade is complete.
+#[tokio::test]
+async fn test_contract_upgrade() {
+    let mut client = FakeEthClient::new();
+    let (sender, receiver) = mpsc::channel(10);
+    let data = Arc::new(RwLock::new(HashMap::new()));
+    tokio::spawn(fake_mempool(receiver, data.clone()));
+
+    let mut watcher = create_watcher(client.clone(), sender);
+    client.set_last_block_number(10).await;
+    watcher.restore_state_from_eth(10).await.unwrap();
+    assert_eq!(
+        client.contract_versions().await,
+        Some(ContractVersions::new(0))
+    );
+
+    client
+        .add_upgrade_events(&[
+            upgrade_event(UpgradeEventKind::NoticePeriodStart, 0, 12),
+            upgrade_event(UpgradeEventKind::UpgradeComplete, 1, 15),
+        ])
+        .await;
+    client.set_last_block_number(20).await;
+    watcher.poll_eth_node().await.unwrap();
+
+    let versions = client.contract_versions().await.unwrap();
+    assert_eq!(versions.latest(), 1);
+    assert_eq!(versions.version_at(14), 0);
+    assert_eq!(versions.version_at(15), 1);
+}
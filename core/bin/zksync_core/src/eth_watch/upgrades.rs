@@ -0,0 +1,189 @@
+//! Tracking of the main contract upgrades performed through the Upgrade Gatekeeper.
+//!
+//! The ABI of the main contract may change with an upgrade, so the watcher has to know which
+//! contract version was active at the block of every event it parses. The gatekeeper announces
+//! the upgrade with the `NoticePeriodStart` event and increments its `versionId` once the upgrade
+//! is complete.
+
+// External uses
+use anyhow::format_err;
+use ethabi::Hash;
+use web3::types::Log;
+
+// Workspace uses
+use zksync_types::U256;
+
+/// Kind of the Upgrade Gatekeeper event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradeEventKind {
+    NoticePeriodStart,
+    UpgradeCancel,
+    PreparationStart,
+    UpgradeComplete,
+}
+
+/// Event of the Upgrade Gatekeeper.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpgradeEvent {
+    pub kind: UpgradeEventKind,
+    /// Version of the gatekeeper at the moment of the event.
+    /// For `UpgradeComplete` this is the version the contracts were upgraded to.
+    pub version_id: u32,
+    pub eth_block: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpgradeTopics {
+    notice_period_start: Hash,
+    upgrade_cancel: Hash,
+    preparation_start: Hash,
+    upgrade_complete: Hash,
+}
+
+impl UpgradeTopics {
+    pub fn new(upgrade_gatekeeper: &ethabi::Contract) -> Self {
+        let signature = |name: &str| {
+            upgrade_gatekeeper
+                .event(name)
+                .expect("upgrade gatekeeper abi error")
+                .signature()
+        };
+        Self {
+            notice_period_start: signature("NoticePeriodStart"),
+            upgrade_cancel: signature("UpgradeCancel"),
+            preparation_start: signature("PreparationStart"),
+            upgrade_complete: signature("UpgradeComplete"),
+        }
+    }
+
+    pub fn all(&self) -> Vec<Hash> {
+        vec![
+            self.notice_period_start,
+            self.upgrade_cancel,
+            self.preparation_start,
+            self.upgrade_complete,
+        ]
+    }
+
+    fn kind(&self, topic: &Hash) -> Option<UpgradeEventKind> {
+        if topic == &self.notice_period_start {
+            Some(UpgradeEventKind::NoticePeriodStart)
+        } else if topic == &self.upgrade_cancel {
+            Some(UpgradeEventKind::UpgradeCancel)
+        } else if topic == &self.preparation_start {
+            Some(UpgradeEventKind::PreparationStart)
+        } else if topic == &self.upgrade_complete {
+            Some(UpgradeEventKind::UpgradeComplete)
+        } else {
+            None
+        }
+    }
+}
+
+impl UpgradeEvent {
+    pub fn from_log(log: &Log, topics: &UpgradeTopics) -> anyhow::Result<Self> {
+        let kind = log
+            .topics
+            .first()
+            .and_then(|topic| topics.kind(topic))
+            .ok_or_else(|| format_err!("Unknown upgrade gatekeeper event: {:?}", log.topics))?;
+        // `versionId` is the indexed parameter of every gatekeeper event.
+        let version_id = log
+            .topics
+            .get(1)
+            .map(|topic| U256::from_big_endian(topic.as_bytes()).as_u32())
+            .ok_or_else(|| format_err!("Upgrade gatekeeper event without version id"))?;
+        let eth_block = log
+            .block_number
+            .ok_or_else(|| format_err!("Upgrade gatekeeper event without block number"))?
+            .as_u64();
+
+        Ok(Self {
+            kind,
+            version_id,
+            eth_block,
+        })
+    }
+}
+
+/// Versions of the main contract and the Ethereum blocks they were activated at.
+///
+/// The versions are the ones of the Upgrade Gatekeeper, the ABI version is obtained by adding
+/// the version of the contract deployed at genesis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractVersions {
+    /// First Ethereum block of the version and the version itself, sorted by the block.
+    versions: Vec<(u64, u32)>,
+    /// Version the contract is going to be upgraded to, if the notice period has been started.
+    pending_upgrade: Option<u32>,
+}
+
+impl ContractVersions {
+    pub fn new(version: u32) -> Self {
+        Self {
+            versions: vec![(0, version)],
+            pending_upgrade: None,
+        }
+    }
+
+    /// Restores the versions from the current one and the gatekeeper events of the recent blocks.
+    pub fn restore(current_version: u32, events: &[UpgradeEvent]) -> Self {
+        let initial_version = events
+            .iter()
+            .find(|event| event.kind == UpgradeEventKind::UpgradeComplete)
+            .map(|event| event.version_id.saturating_sub(1))
+            .unwrap_or(current_version);
+
+        let mut versions = Self::new(initial_version);
+        for event in events {
+            versions.apply(event);
+        }
+        versions
+    }
+
+    /// Returns the latest known version of the contract.
+    pub fn latest(&self) -> u32 {
+        self.versions.last().expect("at least one version").1
+    }
+
+    /// Returns the version of the contract active at the given Ethereum block.
+    /// The new version is considered active starting from the block with the completed upgrade.
+    pub fn version_at(&self, eth_block: u64) -> u32 {
+        self.versions
+            .iter()
+            .rev()
+            .find(|(from_block, _)| *from_block <= eth_block)
+            .map(|(_, version)| *version)
+            .unwrap_or_else(|| self.versions[0].1)
+    }
+
+    /// Returns all the known versions of the contract.
+    pub fn versions(&self) -> impl Iterator<Item = u32> + '_ {
+        self.versions.iter().map(|(_, version)| *version)
+    }
+
+    pub fn pending_upgrade(&self) -> Option<u32> {
+        self.pending_upgrade
+    }
+
+    /// Applies the gatekeeper event, returns `true` if the new version was activated.
+    /// Events that were already applied are ignored.
+    pub fn apply(&mut self, event: &UpgradeEvent) -> bool {
+        let latest = self.latest();
+        match event.kind {
+            UpgradeEventKind::NoticePeriodStart if event.version_id == latest => {
+                self.pending_upgrade = Some(latest + 1);
+            }
+            UpgradeEventKind::UpgradeCancel if event.version_id == latest => {
+                self.pending_upgrade = None;
+            }
+            UpgradeEventKind::UpgradeComplete if event.version_id > latest => {
+                self.versions.push((event.eth_block, event.version_id));
+                self.pending_upgrade = None;
+                return true;
+            }
+            _ => {}
+        }
+        false
+    }
+}
@@ -0,0 +1,101 @@
+//! Leader election of the server instances deployed as active/standby.
+//!
+//! Instances compete for the Postgres advisory lock, held by a dedicated connection of the leader.
+//! Only the leader runs the components producing the blocks and sending the L1 transactions, while
+//! the standby instances serve the API and keep trying to take the lock. The lock is released by
+//! Postgres once the leader's connection is closed, e.g. when the leader crashes, so one of the
+//! standby instances takes over without the operator.
+//!
+//! The previous leader may still be running for a while after its connection is lost, until its
+//! watchdog notices it. To not have two instances producing the blocks, the new leader waits for
+//! `TAKEOVER_GRACE_INTERVALS` watchdog intervals before starting the components, while the watchdog
+//! stops the previous leader within two intervals: the check is made every interval, and fails if
+//! it doesn't complete within an interval.
+
+// Built-in uses
+use std::time::Duration;
+// External uses
+use tokio::{task::JoinHandle, time};
+// Workspace uses
+pub use zksync_storage::misc::LEADER_LOCK_KEY;
+use zksync_storage::{QueryResult, StorageProcessor};
+
+/// Number of the watchdog intervals the new leader waits for before starting the components.
+const TAKEOVER_GRACE_INTERVALS: u32 = 3;
+
+/// Leadership of the instance, valid as long as the connection holding the lock is open.
+pub struct Leadership {
+    storage: StorageProcessor<'static>,
+}
+
+impl std::fmt::Debug for Leadership {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Leadership").finish()
+    }
+}
+
+async fn try_acquire_leadership() -> QueryResult<Option<Leadership>> {
+    let mut storage = StorageProcessor::establish_connection().await?;
+    let acquired = storage
+        .misc_schema()
+        .try_acquire_advisory_lock(LEADER_LOCK_KEY)
+        .await?;
+    if acquired {
+        Ok(Some(Leadership { storage }))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Waits until the instance becomes the leader. `poll_interval` must be the interval of the
+/// leadership watchdog.
+pub async fn acquire_leadership(poll_interval: Duration) -> Leadership {
+    let mut standby_reported = false;
+    loop {
+        match try_acquire_leadership().await {
+            Ok(Some(leadership)) => {
+                // The lock may have been just released by the connection of the previous leader
+                // which is still running, so wait until its watchdog stops it.
+                let grace_period = poll_interval * TAKEOVER_GRACE_INTERVALS;
+                vlog::info!(
+                    "Leadership lock is taken, waiting {:?} for the previous leader to stop",
+                    grace_period
+                );
+                time::sleep(grace_period).await;
+                vlog::info!("Server instance became the leader");
+                metrics::gauge!("leadership.is_leader", 1.0);
+                return leadership;
+            }
+            Ok(None) if !standby_reported => {
+                vlog::info!("Another server instance is the leader, running as the standby");
+                metrics::gauge!("leadership.is_leader", 0.0);
+                standby_reported = true;
+            }
+            Ok(None) => {}
+            Err(err) => vlog::warn!("Failed to acquire the leadership: {}", err),
+        }
+        time::sleep(poll_interval).await;
+    }
+}
+
+/// Checks that the lock is still held, panicking otherwise: once the connection holding the lock
+/// is lost, another instance may already be the leader, and this one must stop producing blocks.
+#[must_use]
+pub fn run_leadership_watchdog(mut leadership: Leadership, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut timer = time::interval(interval);
+        loop {
+            timer.tick().await;
+            let check = leadership
+                .storage
+                .misc_schema()
+                .holds_advisory_lock(LEADER_LOCK_KEY);
+            match time::timeout(interval, check).await {
+                Ok(Ok(true)) => {}
+                Ok(Ok(false)) => panic!("Leadership lock is lost"),
+                Ok(Err(err)) => panic!("Unable to check the leadership lock: {}", err),
+                Err(_) => panic!("Leadership lock check has timed out"),
+            }
+        }
+    })
+}
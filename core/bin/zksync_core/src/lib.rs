@@ -1,28 +1,62 @@
 use crate::register_factory_handler::run_register_factory_handler;
 use crate::state_keeper::ZkSyncStateInitParams;
 use crate::{
+    account_freezes::{run_account_freezes_watcher, FrozenAccounts},
+    circuit_breaker::{run_circuit_breaker, CircuitBreaker},
     committer::run_committer,
-    eth_watch::start_eth_watch,
-    state_keeper::{start_root_hash_calculator, start_state_keeper, ZkSyncStateKeeper},
+    deposit_call_handler::run_deposit_call_handler,
+    deposit_refunds::run_deposit_refunds_tracker,
+    eth_watch::{start_eth_watch, LatestEthBlock},
+    state_keeper::{
+        run_prover_queue_monitor, start_root_hash_calculator, start_state_keeper,
+        AccountReclaimPolicy, BlockSizePolicy, ChunkPacking, DeadManSwitch, EarlySealTriggers,
+        IntakePause, PaddingTransfers, ProverQueueDepth, SealRequest, ZkSyncStateKeeper,
+    },
+    state_root_check::run_state_root_check,
     token_handler::run_token_handler,
+    watch_list_notifier::run_watch_list_notifier,
+    weth_unwrapper::run_weth_unwrapper,
+    withdrawal_tracker::run_withdrawal_tracker,
 };
 use futures::{channel::mpsc, future};
+use num::{rational::Ratio, BigUint, FromPrimitive};
 use tokio::task::JoinHandle;
-use zksync_config::{ChainConfig, ZkSyncConfig};
+use zksync_config::{
+    configs::chain::{
+        EncryptedMempool as EncryptedMempoolConfig, StateKeeper as StateKeeperConfig,
+        TxOrderingPolicy,
+    },
+    ChainConfig, ZkSyncConfig,
+};
 use zksync_eth_client::EthereumGateway;
-use zksync_mempool::{run_mempool_block_handler, run_mempool_tx_handler};
+use zksync_mempool::{
+    run_mempool_block_handler, run_mempool_tx_handler, EncryptedMempool, FeePriorityOrdering,
+    FifoOrdering, Keyholders, QosOrdering, QosWeights, RoundRobinOrdering, TxOrdering,
+};
+use zksync_shared_cache::SharedCache;
 use zksync_storage::ConnectionPool;
-use zksync_types::{tokens::get_genesis_token_list, Token, TokenId, TokenKind};
+use zksync_types::{network::Network, tokens::get_genesis_token_list, Token, TokenId, TokenKind};
+use zksync_utils::shutdown::ShutdownParticipant;
 
 const DEFAULT_CHANNEL_CAPACITY: usize = 32_768;
 
+pub mod account_freezes;
+pub mod circuit_breaker;
 pub mod committer;
+pub mod deposit_call_handler;
+pub mod deposit_refunds;
 pub mod eth_watch;
+pub mod leadership;
 pub mod register_factory_handler;
 pub mod rejected_tx_cleaner;
 pub mod state_keeper;
+pub mod state_root_check;
 pub mod token_handler;
+pub mod treasury;
 pub mod tx_event_emitter;
+pub mod watch_list_notifier;
+pub mod weth_unwrapper;
+pub mod withdrawal_tracker;
 
 mod genesis;
 mod private_api;
@@ -46,6 +80,70 @@ pub async fn wait_for_tasks(task_futures: Vec<JoinHandle<()>>) {
     }
 }
 
+/// Checks that the configured L1 network corresponds to the chain ID the Ethereum client uses,
+/// so that the server can't be started against the wrong L1 by mistake.
+fn check_l1_network(config: &ZkSyncConfig) -> anyhow::Result<()> {
+    let network = config.chain.eth.network;
+    if matches!(network, Network::Unknown | Network::Test) {
+        return Ok(());
+    }
+    anyhow::ensure!(
+        network.chain_id() == config.eth_client.chain_id,
+        "Chain ID of the {} network is {}, but the Ethereum client is configured with {}",
+        network,
+        network.chain_id(),
+        config.eth_client.chain_id
+    );
+    Ok(())
+}
+
+/// Creates the policy of the order in which mempool drains the ready L2 transactions.
+fn tx_ordering(config: &StateKeeperConfig) -> Box<dyn TxOrdering> {
+    match config.tx_ordering {
+        TxOrderingPolicy::Qos => Box::new(QosOrdering(QosWeights {
+            forced_exit: config.forced_exit_qos_weight,
+            fee_paying: config.fee_paying_qos_weight,
+            zero_fee: config.zero_fee_qos_weight,
+        })),
+        TxOrderingPolicy::Fifo => Box::new(FifoOrdering),
+        TxOrderingPolicy::FeePriority => Box::new(FeePriorityOrdering),
+        TxOrderingPolicy::RoundRobin => Box::new(RoundRobinOrdering),
+    }
+}
+
+/// Creates the settings of the encrypted mempool, `None` if it's disabled.
+fn encrypted_mempool(
+    config: &EncryptedMempoolConfig,
+    travel_rule_threshold_usd: Option<Ratio<BigUint>>,
+) -> Option<EncryptedMempool> {
+    if !config.enabled {
+        return None;
+    }
+    let keyholders = config
+        .keyholders()
+        .and_then(|keyholders| {
+            Keyholders::new(
+                keyholders,
+                config.threshold,
+                config.secret_auth.clone(),
+                config.request_timeout(),
+            )
+            .map_err(|err| err.to_string())
+        })
+        .expect("Keyholders of the encrypted mempool are misconfigured");
+    // Minimal fee is applied with the precision of the cent.
+    let min_fee = Ratio::new(
+        BigUint::from_f64((config.min_fee_usd * 100.0).round()).unwrap_or_default(),
+        BigUint::from(100u32),
+    );
+    Some(EncryptedMempool {
+        keyholders,
+        max_txs_per_block: config.max_txs_per_block,
+        min_fee,
+        travel_rule_threshold_usd,
+    })
+}
+
 /// Inserts the initial information about zkSync tokens into the database.
 pub async fn genesis_init(config: &ChainConfig) {
     let pool = ConnectionPool::new(Some(1));
@@ -87,12 +185,18 @@ pub async fn genesis_init(config: &ChainConfig) {
 /// - block proposer, module to create block proposals for state keeper.
 /// - committer, module to store pending and completed blocks into the database.
 /// - private Core API server.
+///
+/// On the shutdown, the state keeper seals the pending block and waits for the committer to store
+/// the sealed blocks before reporting the completion.
 pub async fn run_core(
     connection_pool: ConnectionPool,
     read_only_connection_pool: ConnectionPool,
     config: &ZkSyncConfig,
     eth_gateway: EthereumGateway,
+    shutdown: ShutdownParticipant,
 ) -> anyhow::Result<Vec<JoinHandle<()>>> {
+    check_l1_network(config)?;
+
     let (proposed_blocks_sender, proposed_blocks_receiver) =
         mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
     let (eth_watch_req_sender, eth_watch_req_receiver) = mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
@@ -110,14 +214,43 @@ pub async fn run_core(
         config.chain.state_keeper.block_chunk_sizes.clone(),
     );
 
+    // Shared state used to decide whether the pending block should be sealed early.
+    let latest_eth_block = LatestEthBlock::default();
+    let seal_request = SealRequest::default();
+    let intake_pause = IntakePause::default();
+    let circuit_breaker = CircuitBreaker::default();
+
     // Run health check api for core
+    let committee_member = private_api::CommitteeMember::from_config(&config.eth_sender.committee);
+    let keyholder = private_api::Keyholder::from_config(&config.chain.encrypted_mempool);
     let private_api_task = private_api::start_private_core_api(
         connection_pool.clone(),
         read_only_connection_pool,
         eth_gateway.clone(),
         config.api.private.clone(),
+        seal_request.clone(),
+        intake_pause.clone(),
+        committee_member,
+        keyholder,
     );
 
+    // Start circuit breaker, so the state keeper is halted if the breaker is already tripped.
+    let circuit_breaker_task = run_circuit_breaker(
+        connection_pool.clone(),
+        eth_gateway.clone(),
+        config.chain.circuit_breaker.clone(),
+        circuit_breaker.clone(),
+    );
+
+    // Cross-check the last executed block with the contract, tripping the breaker on mismatch.
+    let state_root_check_task = config
+        .chain
+        .circuit_breaker
+        .state_root_check_interval()
+        .map(|interval| {
+            run_state_root_check(connection_pool.clone(), eth_gateway.clone(), interval)
+        });
+
     // Start Ethereum Watcher.
     let eth_watch_task = start_eth_watch(
         eth_watch_req_sender.clone(),
@@ -126,9 +259,17 @@ pub async fn run_core(
         &config.contracts,
         &config.eth_watch,
         mempool_tx_request_sender.clone(),
+        latest_eth_block.clone(),
     )
     .await;
 
+    // Record the deposits which can't be credited anymore.
+    let deposit_refunds_task = run_deposit_refunds_tracker(
+        connection_pool.clone(),
+        latest_eth_block.clone(),
+        config.eth_watch.deposit_refunds_check_interval(),
+    );
+
     // Insert pending withdrawals into database (if required)
     let mut storage_processor = connection_pool.access_storage().await?;
 
@@ -151,6 +292,34 @@ pub async fn run_core(
         processed_tx_events_sender,
     );
 
+    let prover_queue_depth = ProverQueueDepth::default();
+    state_keeper.set_block_size_policy(BlockSizePolicy::from_config(
+        &config.chain.state_keeper,
+        prover_queue_depth.clone(),
+    ));
+    state_keeper.set_early_seal_triggers(EarlySealTriggers::from_config(
+        &config.chain.state_keeper,
+        latest_eth_block.clone(),
+        seal_request,
+    ));
+    state_keeper.set_dead_man_switch(DeadManSwitch::from_config(
+        &config.chain.state_keeper,
+        latest_eth_block,
+        intake_pause,
+    ));
+    state_keeper.set_circuit_breaker(circuit_breaker);
+    state_keeper.set_account_reclaim_policy(AccountReclaimPolicy::from_config(
+        &config.chain.state_keeper,
+    ));
+    state_keeper.set_chunk_packing(ChunkPacking::from_config(&config.chain.state_keeper));
+    state_keeper.set_padding_transfers(PaddingTransfers::from_config(&config.chain.state_keeper)?);
+    // Freezes are loaded before the start, so no transfer from the frozen account is executed.
+    let frozen_accounts = FrozenAccounts::load(&mut storage_processor).await?;
+    state_keeper.set_frozen_accounts(frozen_accounts.clone());
+    state_keeper.set_shutdown_participant(shutdown);
+    let account_freezes_task =
+        run_account_freezes_watcher(connection_pool.clone(), frozen_accounts);
+
     // Execute reverted blocks before start
     state_keeper.execute_reverted_blocks().await;
 
@@ -159,12 +328,15 @@ pub async fn run_core(
         config.chain.state_keeper.miniblock_iteration_interval(),
     );
     let root_hash_calculator_task = start_root_hash_calculator(root_hash_calculator);
+    let prover_queue_monitor_task =
+        run_prover_queue_monitor(connection_pool.clone(), prover_queue_depth);
 
     // Start committer.
     let committer_task = run_committer(
         proposed_blocks_receiver,
         connection_pool.clone(),
         config.chain.clone(),
+        SharedCache::from_config(&config.api.common).expect("Invalid shared cache config"),
     );
 
     // Start mempool.
@@ -172,6 +344,11 @@ pub async fn run_core(
         connection_pool.clone(),
         mempool_block_request_receiver,
         config.chain.state_keeper.block_chunk_sizes.clone(),
+        tx_ordering(&config.chain.state_keeper),
+        encrypted_mempool(
+            &config.chain.encrypted_mempool,
+            config.api.common.travel_rule_threshold_usd(),
+        ),
     );
 
     // Start token handler.
@@ -189,15 +366,44 @@ pub async fn run_core(
         config.token_handler.clone(),
     );
 
+    // Submit the calls of the deposits made via the deposit router.
+    let deposit_call_task = config.contracts.deposit_router_addr.map(|_| {
+        run_deposit_call_handler(
+            connection_pool.clone(),
+            eth_watch_req_sender.clone(),
+            mempool_tx_request_sender.clone(),
+            config.eth_watch.poll_interval(),
+        )
+    });
+
+    // Deliver the withdrawals with the `unwrapWeth` flag as native ETH.
+    let weth_unwrapper_task = config.api.common.weth_unwrapper_addr.map(|unwrapper_addr| {
+        run_weth_unwrapper(
+            connection_pool.clone(),
+            eth_gateway.clone(),
+            unwrapper_addr,
+            config.eth_watch.poll_interval(),
+        )
+    });
+
+    // Report the incoming operations of the watched addresses to the webhooks.
+    let watch_list_notifier_task =
+        run_watch_list_notifier(connection_pool.clone(), config.eth_watch.poll_interval());
+
+    // Record the L1 transactions of the withdrawals of the executed blocks.
+    let withdrawal_tracker_task =
+        run_withdrawal_tracker(connection_pool.clone(), config.eth_watch.poll_interval());
+
     let tx_event_emitter_task = tx_event_emitter::run_tx_event_emitter_task(
         connection_pool.clone(),
         processed_tx_events_receiver,
     );
 
-    let task_futures = vec![
+    let mut task_futures = vec![
         eth_watch_task,
         state_keeper_task,
         root_hash_calculator_task,
+        prover_queue_monitor_task,
         committer_task,
         token_handler_task,
         register_factory_task,
@@ -205,7 +411,15 @@ pub async fn run_core(
         mempool_block_handler_task,
         mempool_tx_handler_task,
         private_api_task,
+        circuit_breaker_task,
+        deposit_refunds_task,
+        account_freezes_task,
+        watch_list_notifier_task,
+        withdrawal_tracker_task,
     ];
+    task_futures.extend(state_root_check_task);
+    task_futures.extend(deposit_call_task);
+    task_futures.extend(weth_unwrapper_task);
 
     Ok(task_futures)
 }
@@ -0,0 +1,227 @@
+//! Profitability of the blocks, treasury conversions and travel rule reports.
+
+use actix_web::{web, HttpResponse};
+use chrono::{Duration as ChronoDuration, Utc};
+use serde::Deserialize;
+
+use zksync_types::BlockNumber;
+
+use super::AppState;
+
+/// Maximum number of blocks the accounting can be requested for at once.
+const MAX_ACCOUNTING_BLOCKS: u32 = 1000;
+
+/// Maximum number of days the accounting can be requested for at once.
+const MAX_ACCOUNTING_DAYS: u32 = 365;
+
+/// Maximum number of treasury conversions returned at once.
+const MAX_TREASURY_CONVERSIONS: u32 = 100;
+
+/// Maximum number of flagged travel rule transfers returned at once.
+const MAX_FLAGGED_TRANSFERS: u32 = 100;
+
+#[derive(Debug, Deserialize)]
+struct BlocksAccountingQuery {
+    from_block: u32,
+    to_block: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct DailyAccountingQuery {
+    days: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TreasuryConversionsQuery {
+    limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FlaggedTransfersQuery {
+    limit: Option<u32>,
+}
+
+/// Returns the collected fees, the L1 costs and the profitability of the blocks of the range.
+#[actix_web::get("/accounting/blocks")]
+async fn accounting_blocks(
+    data: web::Data<AppState>,
+    query: web::Query<BlocksAccountingQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let BlocksAccountingQuery {
+        from_block,
+        to_block,
+    } = query.into_inner();
+    if from_block > to_block || to_block - from_block >= MAX_ACCOUNTING_BLOCKS {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "Invalid block range, at most {} blocks can be requested",
+            MAX_ACCOUNTING_BLOCKS
+        )));
+    }
+
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let fees = storage
+        .accounting_schema()
+        .load_block_fees(BlockNumber(from_block), BlockNumber(to_block))
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let blocks = storage
+        .accounting_schema()
+        .load_blocks_profitability(BlockNumber(from_block), BlockNumber(to_block))
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let response: Vec<_> = blocks
+        .into_iter()
+        .map(|block| {
+            let block_fees: Vec<_> = fees
+                .iter()
+                .filter(|fee| fee.block_number == block.block_number)
+                .map(|fee| {
+                    serde_json::json!({
+                        "token_id": fee.token_id,
+                        "amount": fee.amount.to_string(),
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "block_number": block.block_number,
+                "fees": block_fees,
+                "revenue_usd": block.revenue_usd.to_string(),
+                "l1_cost_wei": block.l1_cost_wei.with_scale(0).to_string(),
+                "l1_cost_usd": block.l1_cost_usd.to_string(),
+                "profit_usd": block.profit_usd().to_string(),
+            })
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Returns the profitability of the blocks created during the latest days, including the current one.
+#[actix_web::get("/accounting/daily")]
+async fn accounting_daily(
+    data: web::Data<AppState>,
+    query: web::Query<DailyAccountingQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let days = query.days.unwrap_or(30).clamp(1, MAX_ACCOUNTING_DAYS);
+    let from_day = Utc::today().naive_utc() - ChronoDuration::days(i64::from(days) - 1);
+
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let days = storage
+        .accounting_schema()
+        .load_daily_profitability(from_day)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let response: Vec<_> = days
+        .into_iter()
+        .map(|day| {
+            serde_json::json!({
+                "day": day.day,
+                "blocks_count": day.blocks_count,
+                "revenue_usd": day.revenue_usd.to_string(),
+                "l1_cost_wei": day.l1_cost_wei.with_scale(0).to_string(),
+                "l1_cost_usd": day.l1_cost_usd.to_string(),
+                "profit_usd": day.profit_usd().to_string(),
+            })
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Returns the audit records of the latest fee conversions made by the treasury task.
+#[actix_web::get("/treasury/conversions")]
+async fn treasury_conversions(
+    data: web::Data<AppState>,
+    query: web::Query<TreasuryConversionsQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let limit = query
+        .limit
+        .unwrap_or(MAX_TREASURY_CONVERSIONS)
+        .clamp(1, MAX_TREASURY_CONVERSIONS);
+
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let conversions = storage
+        .treasury_schema()
+        .load_conversions(limit)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let response: Vec<_> = conversions
+        .into_iter()
+        .map(|conversion| {
+            serde_json::json!({
+                "id": conversion.id,
+                "token_id": conversion.token_id,
+                "amount": conversion.amount.to_string(),
+                "target_token_id": conversion.target_token_id,
+                "expected_amount": conversion.expected_amount.to_string(),
+                "route": conversion.route,
+                "tx_hash": format!("sync-tx:{}", hex::encode(&conversion.tx_hash)),
+                "status": conversion.status,
+                "fail_reason": conversion.fail_reason,
+                "details": conversion.details,
+                "created_at": conversion.created_at,
+                "updated_at": conversion.updated_at,
+            })
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Returns the latest transfers above the travel rule threshold submitted without the metadata.
+#[actix_web::get("/travel_rule/flagged")]
+async fn travel_rule_flagged(
+    data: web::Data<AppState>,
+    query: web::Query<FlaggedTransfersQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let limit = query
+        .limit
+        .unwrap_or(MAX_FLAGGED_TRANSFERS)
+        .clamp(1, MAX_FLAGGED_TRANSFERS);
+
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let records = storage
+        .travel_rule_schema()
+        .load_flagged(limit)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let response: Vec<_> = records
+        .into_iter()
+        .map(|record| {
+            serde_json::json!({
+                "tx_hash": format!("sync-tx:{}", hex::encode(&record.tx_hash)),
+                "from": format!("0x{}", hex::encode(&record.from_address)),
+                "to": format!("0x{}", hex::encode(&record.to_address)),
+                "token_id": record.token_id,
+                "amount": record.amount.to_string(),
+                "amount_usd_scaled": record.amount_usd_scaled,
+                "created_at": record.created_at,
+            })
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(accounting_blocks)
+        .service(accounting_daily)
+        .service(treasury_conversions)
+        .service(travel_rule_flagged);
+}
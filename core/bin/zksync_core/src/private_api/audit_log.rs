@@ -0,0 +1,112 @@
+//! Access to the audit log of the admin actions.
+
+use actix_web::{web, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use zksync_storage::admin_audit::records::StorageAuditEntry;
+
+use super::AppState;
+
+/// Maximum number of audit log entries returned at once.
+const MAX_AUDIT_ENTRIES: u32 = 1000;
+
+#[derive(Debug, Deserialize)]
+struct AuditLogQuery {
+    after: Option<i64>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    action: Option<String>,
+    limit: Option<u32>,
+}
+
+/// Returns the audit log entries following the `after` one in the order they were recorded.
+#[actix_web::get("/audit_log")]
+async fn audit_log(
+    data: web::Data<AppState>,
+    query: web::Query<AuditLogQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(MAX_AUDIT_ENTRIES);
+    if limit > MAX_AUDIT_ENTRIES {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "Limit must not exceed {}",
+            MAX_AUDIT_ENTRIES
+        )));
+    }
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let entries = storage
+        .admin_audit_schema()
+        .load_entries(
+            query.after.unwrap_or(0),
+            query.since,
+            query.until,
+            query.action.as_deref(),
+            limit,
+        )
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let response: Vec<_> = entries.into_iter().map(audit_entry_json).collect();
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Exports all the audit log entries of the time range as JSON lines.
+#[actix_web::get("/audit_log/export")]
+async fn audit_log_export(
+    data: web::Data<AppState>,
+    query: web::Query<AuditLogQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let mut body = String::new();
+    let mut after = query.after.unwrap_or(0);
+    loop {
+        let entries = storage
+            .admin_audit_schema()
+            .load_entries(
+                after,
+                query.since,
+                query.until,
+                query.action.as_deref(),
+                MAX_AUDIT_ENTRIES,
+            )
+            .await
+            .map_err(actix_web::error::ErrorInternalServerError)?;
+        let is_last_page = entries.len() < MAX_AUDIT_ENTRIES as usize;
+        for entry in entries {
+            after = entry.id;
+            body.push_str(&audit_entry_json(entry).to_string());
+            body.push('\n');
+        }
+        if is_last_page {
+            break;
+        }
+    }
+    Ok(HttpResponse::Ok()
+        .content_type("application/x-ndjson")
+        .body(body))
+}
+
+fn audit_entry_json(entry: StorageAuditEntry) -> serde_json::Value {
+    serde_json::json!({
+        "id": entry.id,
+        "actor": entry.actor,
+        "action": entry.action,
+        "target": entry.target,
+        "before": entry.before_value,
+        "after": entry.after_value,
+        "created_at": entry.created_at,
+    })
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(audit_log).service(audit_log_export);
+}
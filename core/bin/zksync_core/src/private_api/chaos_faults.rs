@@ -0,0 +1,93 @@
+//! Faults injected into the core actors, see `zksync_utils::chaos`.
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+
+use zksync_utils::chaos::{self, FaultConfig, FaultPoint};
+
+use super::{audit, AppState};
+
+#[derive(Debug, Deserialize)]
+struct ChaosFaultsRequest {
+    point: FaultPoint,
+    #[serde(flatten)]
+    faults: FaultConfig,
+}
+
+/// Returns the faults injected into the core actors, see `zksync_utils::chaos`.
+#[actix_web::get("/chaos")]
+async fn chaos_faults() -> actix_web::Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "enabled": chaos::is_enabled(),
+        "faults": chaos::configured_faults(),
+    })))
+}
+
+/// Sets the faults injected at the point. Only available in the builds with the `chaos` feature,
+/// which must never be deployed to production.
+#[actix_web::post("/chaos")]
+async fn chaos_faults_set(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<ChaosFaultsRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let request = request.into_inner();
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let before = chaos::configured_faults()
+        .get(&request.point)
+        .map(|faults| serde_json::to_value(faults).expect("fault config is serializable"));
+    chaos::set_faults(request.point, request.faults).map_err(actix_web::error::ErrorBadRequest)?;
+    let after = serde_json::to_value(&request.faults).expect("fault config is serializable");
+    audit(
+        &mut storage,
+        &req,
+        "chaos_faults_set",
+        Some(format!("{:?}", request.point)),
+        before,
+        Some(after),
+    )
+    .await?;
+    vlog::warn!(
+        "Chaos: faults of {:?} are set to {:?}",
+        request.point,
+        request.faults
+    );
+    Ok(HttpResponse::Ok().json(()))
+}
+
+/// Stops injecting the faults into every actor.
+#[actix_web::post("/chaos/reset")]
+async fn chaos_faults_reset(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let before = serde_json::to_value(chaos::configured_faults())
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    chaos::clear_faults();
+    audit(
+        &mut storage,
+        &req,
+        "chaos_faults_reset",
+        None,
+        Some(before),
+        None,
+    )
+    .await?;
+    vlog::info!("Chaos: faults injection is stopped");
+    Ok(HttpResponse::Ok().json(()))
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(chaos_faults)
+        .service(chaos_faults_set)
+        .service(chaos_faults_reset);
+}
@@ -0,0 +1,131 @@
+//! Manual trips and resets of the circuit breaker.
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+
+use super::{audit, AppState};
+
+#[derive(Debug, Deserialize)]
+struct TripRequest {
+    reason: String,
+}
+
+/// Returns the active circuit breaker trip, `null` if the breaker is not tripped.
+#[actix_web::get("/circuit_breaker")]
+async fn circuit_breaker_status(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let trip = storage
+        .circuit_breaker_schema()
+        .load_active_trip()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok().json(trip.map(|trip| {
+        serde_json::json!({
+            "source": trip.source,
+            "reason": trip.reason,
+            "tripped_at": trip.tripped_at,
+        })
+    })))
+}
+
+/// Trips the circuit breaker manually, halting the block production and L1 transactions.
+#[actix_web::post("/circuit_breaker/trip")]
+async fn circuit_breaker_trip(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<TripRequest>,
+) -> actix_web::Result<HttpResponse> {
+    vlog::warn!(
+        "Received a request to trip the circuit breaker: {}",
+        request.reason
+    );
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    transaction
+        .circuit_breaker_schema()
+        .trip("admin", &request.reason)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let after = serde_json::json!({ "reason": request.reason });
+    audit(
+        &mut transaction,
+        &req,
+        "circuit_breaker_trip",
+        None,
+        None,
+        Some(after),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok().json(()))
+}
+
+/// Resets the circuit breaker. Must only be called once the cause of the trip is eliminated.
+#[actix_web::post("/circuit_breaker/reset")]
+async fn circuit_breaker_reset(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let trip = transaction
+        .circuit_breaker_schema()
+        .load_active_trip()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let resolved = transaction
+        .circuit_breaker_schema()
+        .reset()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let before = trip.map(|trip| {
+        serde_json::json!({
+            "source": trip.source,
+            "reason": trip.reason,
+            "tripped_at": trip.tripped_at,
+        })
+    });
+    let after = serde_json::json!({ "resolved": resolved });
+    audit(
+        &mut transaction,
+        &req,
+        "circuit_breaker_reset",
+        None,
+        before,
+        Some(after),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::warn!("Circuit breaker is reset, {} trips resolved", resolved);
+    Ok(HttpResponse::Ok().json(resolved))
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(circuit_breaker_status)
+        .service(circuit_breaker_trip)
+        .service(circuit_breaker_reset);
+}
@@ -0,0 +1,142 @@
+//! Approvals of the L1 operations by the operators committee members.
+
+use actix_web::{http::header, web, HttpRequest, HttpResponse};
+use jsonwebtoken::{decode, DecodingKey, Validation};
+use serde::Deserialize;
+
+use zksync_config::configs::eth_sender::Committee;
+use zksync_types::{
+    aggregated_operations::{committee_approval_message, AggregatedActionType},
+    tx::PackedEthSignature,
+    BlockNumber, H256,
+};
+
+use super::{audit, AppState};
+
+/// Credentials of the node as a member of the operators committee.
+#[derive(Debug, Clone)]
+pub struct CommitteeMember {
+    /// Key used to sign the approvals.
+    private_key: H256,
+    /// Secret used to validate the access tokens (JWT) of the approval requests.
+    secret_auth: String,
+}
+
+impl CommitteeMember {
+    /// Returns `None` if the node is not a member of the committee.
+    pub fn from_config(config: &Committee) -> Option<Self> {
+        if config.member_private_key.is_zero() {
+            return None;
+        }
+        assert!(
+            !config.secret_auth.is_empty(),
+            "Secret of the operators committee must be set for its members"
+        );
+        Some(Self {
+            private_key: config.member_private_key,
+            secret_auth: config.secret_auth.clone(),
+        })
+    }
+
+    /// Checks that the approval request is made by the committee coordinator, i.e. its bearer
+    /// token is signed with the shared secret.
+    fn authorize(&self, req: &HttpRequest) -> actix_web::Result<()> {
+        let token = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| actix_web::error::ErrorUnauthorized("Missing access token"))?;
+        decode::<CommitteeAuthToken>(
+            token,
+            &DecodingKey::from_secret(self.secret_auth.as_ref()),
+            &Validation::default(),
+        )
+        .map_err(|_| actix_web::error::ErrorUnauthorized("Invalid access token"))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitteeAuthToken {
+    /// Subject (whom auth token refers to).
+    sub: String,
+    /// Expiration time (as UTC timestamp).
+    exp: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApprovalRequest {
+    action_type: AggregatedActionType,
+    first_block: BlockNumber,
+    last_block: BlockNumber,
+}
+
+/// Approves the L1 operation as a member of the operators committee.
+///
+/// The approval message is built from the blocks stored by this node, so the approval
+/// is only valid if the coordinator sends the same blocks to L1.
+#[actix_web::post("/committee/approve")]
+async fn committee_approve(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<ApprovalRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let member = data.committee_member.as_ref().ok_or_else(|| {
+        actix_web::error::ErrorForbidden("Node is not a member of the operators committee")
+    })?;
+    member.authorize(&req)?;
+    let ApprovalRequest {
+        action_type,
+        first_block,
+        last_block,
+    } = request.into_inner();
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut blocks = Vec::new();
+    for block_number in *first_block..=*last_block {
+        let block = storage
+            .chain()
+            .block_schema()
+            .get_block(BlockNumber(block_number))
+            .await
+            .map_err(actix_web::error::ErrorInternalServerError)?
+            .ok_or_else(|| {
+                actix_web::error::ErrorNotFound(format!("Block {} not found", block_number))
+            })?;
+        blocks.push(block);
+    }
+
+    let message = committee_approval_message(action_type, &blocks);
+    let signature = PackedEthSignature::sign(&member.private_key, message.as_bytes())
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let after = serde_json::json!({
+        "action_type": action_type.to_string(),
+        "first_block": first_block,
+        "last_block": last_block,
+    });
+    audit(
+        &mut storage,
+        &req,
+        "committee_approve",
+        None,
+        None,
+        Some(after),
+    )
+    .await?;
+    vlog::info!(
+        "Approved {} for blocks {}-{}",
+        action_type.to_string(),
+        first_block,
+        last_block
+    );
+    Ok(HttpResponse::Ok().json(signature))
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(committee_approve);
+}
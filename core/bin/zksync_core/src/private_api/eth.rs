@@ -0,0 +1,103 @@
+//! State of the operator account and of the L1 operations.
+
+use actix_web::{web, HttpResponse};
+
+use super::AppState;
+
+/// Returns the nonces of the operator account and the gas prices. The difference between
+/// the pending and the mined nonces is the number of the operator transactions not mined yet.
+#[actix_web::get("/eth/status")]
+async fn eth_status(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mined_nonce = data
+        .eth_client
+        .current_nonce()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let pending_nonce = data
+        .eth_client
+        .pending_nonce()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let gas_price = data
+        .eth_client
+        .get_gas_price()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let gas_price_limit = storage
+        .ethereum_schema()
+        .load_gas_price_limit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let average_gas_price = storage
+        .ethereum_schema()
+        .load_average_gas_price()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "mined_nonce": mined_nonce,
+        "pending_nonce": pending_nonce,
+        "gas_price": gas_price,
+        "average_gas_price": average_gas_price,
+        "gas_price_limit": gas_price_limit,
+    })))
+}
+
+/// Returns the L1 operations whose last transaction is not mined before its deadline block.
+/// Such operations are resent by the Ethereum sender with the increased gas price.
+#[actix_web::get("/eth/stuck")]
+async fn eth_stuck_operations(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let current_block = data
+        .eth_client
+        .block_number()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .as_u64();
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let operations = storage
+        .ethereum_schema()
+        .load_unconfirmed_operations()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let response: Vec<_> = operations
+        .into_iter()
+        .filter(|operation| operation.is_stuck(current_block))
+        .map(|operation| {
+            serde_json::json!({
+                "id": operation.id,
+                "op_type": operation.op_type.to_string(),
+                "nonce": operation.nonce,
+                "last_deadline_block": operation.last_deadline_block,
+                "last_used_gas_price": operation.last_used_gas_price,
+                "used_tx_hashes": operation.used_tx_hashes,
+            })
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Returns the latest calls to the Ethereum nodes, if the recording is enabled.
+#[actix_web::get("/eth_calls")]
+async fn eth_calls(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let recorder = data.eth_client.call_recorder().ok_or_else(|| {
+        actix_web::error::ErrorNotFound("Recording of the Ethereum calls is disabled")
+    })?;
+    Ok(HttpResponse::Ok().json(recorder.calls()))
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(eth_status)
+        .service(eth_stuck_operations)
+        .service(eth_calls);
+}
@@ -0,0 +1,144 @@
+//! Sponsors of the zero-fee transfers.
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+
+use zksync_types::{Address, FeeSponsor, TokenId};
+
+use super::{audit, AppState};
+
+#[derive(Debug, Deserialize)]
+struct RemoveFeeSponsorRequest {
+    address: Address,
+    token: TokenId,
+}
+
+/// Adds the sponsor of the zero-fee transfers of the token or updates its daily gas budget.
+#[actix_web::post("/fee_sponsors")]
+async fn fee_sponsor_store(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    sponsor: web::Json<FeeSponsor>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let before = transaction
+        .fee_sponsors_schema()
+        .load_sponsor(sponsor.address, sponsor.token)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    transaction
+        .fee_sponsors_schema()
+        .store_sponsor(&sponsor)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    audit(
+        &mut transaction,
+        &req,
+        "fee_sponsor_store",
+        Some(format!("{:?}:{}", sponsor.address, sponsor.token)),
+        before.map(|before| serde_json::json!(before)),
+        Some(serde_json::json!(sponsor.0)),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Fee sponsor {:?} of the token {} is stored with the daily gas budget {}",
+        sponsor.address,
+        sponsor.token,
+        sponsor.daily_gas_budget
+    );
+    Ok(HttpResponse::Ok().json(()))
+}
+
+/// Removes the sponsor of the zero-fee transfers of the token.
+#[actix_web::post("/fee_sponsors/remove")]
+async fn fee_sponsor_remove(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<RemoveFeeSponsorRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let before = transaction
+        .fee_sponsors_schema()
+        .load_sponsor(request.address, request.token)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .ok_or_else(|| actix_web::error::ErrorNotFound("Fee sponsor is not found"))?;
+    transaction
+        .fee_sponsors_schema()
+        .remove_sponsor(request.address, request.token)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    audit(
+        &mut transaction,
+        &req,
+        "fee_sponsor_remove",
+        Some(format!("{:?}:{}", request.address, request.token)),
+        Some(serde_json::json!(before)),
+        None,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Fee sponsor {:?} of the token {} is removed",
+        request.address,
+        request.token
+    );
+    Ok(HttpResponse::Ok().json(()))
+}
+
+/// Returns the fee sponsors along with the gas taken by their transfers today.
+#[actix_web::get("/fee_sponsors")]
+async fn fee_sponsors(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let sponsors = storage
+        .fee_sponsors_schema()
+        .load_sponsors_usage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let response: Vec<_> = sponsors
+        .into_iter()
+        .map(|sponsor| {
+            serde_json::json!({
+                "address": Address::from_slice(&sponsor.address),
+                "token": sponsor.token_id,
+                "daily_gas_budget": sponsor.daily_gas_budget,
+                "gas_used_today": sponsor.gas_used_today,
+            })
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(fee_sponsor_store)
+        .service(fee_sponsor_remove)
+        .service(fee_sponsors);
+}
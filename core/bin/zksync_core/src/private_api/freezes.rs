@@ -0,0 +1,163 @@
+//! Freezes of the accounts and their audit trail.
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+
+use zksync_types::AccountId;
+
+use super::{audit, AppState};
+
+#[derive(Debug, Deserialize)]
+struct AccountFreezeRequest {
+    account_id: AccountId,
+    reason: String,
+}
+
+/// Freezes the account, so it can't send funds within the network. The exits of the account
+/// stay available. The freeze is applied by the state keeper within a few seconds.
+#[actix_web::post("/accounts/freeze")]
+async fn account_freeze(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<AccountFreezeRequest>,
+) -> actix_web::Result<HttpResponse> {
+    if request.reason.trim().is_empty() {
+        return Err(actix_web::error::ErrorBadRequest(
+            "Reason of the freeze must be provided",
+        ));
+    }
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let frozen = transaction
+        .account_freezes_schema()
+        .freeze(request.account_id, &request.reason)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !frozen {
+        return Err(actix_web::error::ErrorConflict("Account is already frozen"));
+    }
+    let after = serde_json::json!({ "reason": request.reason });
+    audit(
+        &mut transaction,
+        &req,
+        "account_freeze",
+        Some(request.account_id.to_string()),
+        None,
+        Some(after),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::warn!(
+        "Account {} is frozen: {}",
+        request.account_id,
+        request.reason
+    );
+    Ok(HttpResponse::Ok().json(()))
+}
+
+/// Lifts the active freeze of the account.
+#[actix_web::post("/accounts/unfreeze")]
+async fn account_unfreeze(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<AccountFreezeRequest>,
+) -> actix_web::Result<HttpResponse> {
+    if request.reason.trim().is_empty() {
+        return Err(actix_web::error::ErrorBadRequest(
+            "Reason of the unfreeze must be provided",
+        ));
+    }
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let freeze = transaction
+        .account_freezes_schema()
+        .active_freeze(request.account_id)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .ok_or_else(|| actix_web::error::ErrorNotFound("Account is not frozen"))?;
+    transaction
+        .account_freezes_schema()
+        .unfreeze(request.account_id, &request.reason)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let before = serde_json::json!({
+        "reason": freeze.reason,
+        "frozen_at": freeze.frozen_at,
+    });
+    let after = serde_json::json!({ "unfreeze_reason": request.reason });
+    audit(
+        &mut transaction,
+        &req,
+        "account_unfreeze",
+        Some(request.account_id.to_string()),
+        Some(before),
+        Some(after),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::warn!(
+        "Account {} is unfrozen: {}",
+        request.account_id,
+        request.reason
+    );
+    Ok(HttpResponse::Ok().json(()))
+}
+
+/// Returns the audit trail of the account freezes, starting from the latest one.
+#[actix_web::get("/accounts/{account_id}/freezes")]
+async fn account_freezes(
+    data: web::Data<AppState>,
+    account_id: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let freezes = storage
+        .account_freezes_schema()
+        .load_history(AccountId(account_id.into_inner()))
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let response: Vec<_> = freezes
+        .into_iter()
+        .map(|freeze| {
+            serde_json::json!({
+                "id": freeze.id,
+                "account_id": freeze.account_id,
+                "reason": freeze.reason,
+                "frozen_at": freeze.frozen_at,
+                "unfreeze_reason": freeze.unfreeze_reason,
+                "unfrozen_at": freeze.unfrozen_at,
+            })
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(account_freeze)
+        .service(account_unfreeze)
+        .service(account_freezes);
+}
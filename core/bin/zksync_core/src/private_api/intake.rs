@@ -0,0 +1,91 @@
+//! Control of the block production by the state keeper.
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+
+use super::{audit, AppState};
+
+#[derive(Debug, Deserialize)]
+struct IntakePauseRequest {
+    reason: String,
+}
+
+/// Requests the state keeper to seal the current pending block without waiting for it to be
+/// full or timed out. The block is sealed on the next miniblock iteration, unless it's empty.
+#[actix_web::post("/seal_block")]
+async fn seal_block(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+) -> actix_web::Result<HttpResponse> {
+    vlog::info!("Received a request to seal the pending block");
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    audit(&mut storage, &req, "seal_block", None, None, None).await?;
+    data.seal_request.request();
+    Ok(HttpResponse::Ok().json(()))
+}
+
+/// Pauses the execution of the L2 transactions. Priority operations approaching their deadlines
+/// are still processed. The pause isn't persisted and is lifted once the server is restarted.
+#[actix_web::post("/intake/pause")]
+async fn pause_intake(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<IntakePauseRequest>,
+) -> actix_web::Result<HttpResponse> {
+    if request.reason.trim().is_empty() {
+        return Err(actix_web::error::ErrorBadRequest(
+            "Reason of the pause must be provided",
+        ));
+    }
+    vlog::warn!("Received a request to pause the intake: {}", request.reason);
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let after = serde_json::json!({ "reason": request.reason });
+    audit(&mut storage, &req, "intake_pause", None, None, Some(after)).await?;
+    data.intake_pause.set_paused(true);
+    Ok(HttpResponse::Ok().json(()))
+}
+
+#[actix_web::post("/intake/resume")]
+async fn resume_intake(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+) -> actix_web::Result<HttpResponse> {
+    vlog::info!("Received a request to resume the intake");
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let before = serde_json::json!({ "paused": data.intake_pause.is_paused() });
+    audit(
+        &mut storage,
+        &req,
+        "intake_resume",
+        None,
+        Some(before),
+        None,
+    )
+    .await?;
+    let was_paused = data.intake_pause.set_paused(false);
+    Ok(HttpResponse::Ok().json(was_paused))
+}
+
+#[actix_web::get("/intake")]
+async fn intake_status(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "paused": data.intake_pause.is_paused() })))
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(seal_block)
+        .service(pause_intake)
+        .service(resume_intake)
+        .service(intake_status);
+}
@@ -0,0 +1,114 @@
+//! Decryption shares of the encrypted mempool blobs computed by the keyholders.
+
+use actix_web::{http::header, web, HttpRequest, HttpResponse};
+use jsonwebtoken::{decode, DecodingKey, Validation};
+use serde::Deserialize;
+
+use zksync_config::configs::chain::EncryptedMempool;
+use zksync_types::{
+    encrypted_tx::{decryption_share, Public},
+    H256,
+};
+
+use super::{audit, AppState};
+
+/// Credentials of the node as a keyholder of the encrypted mempool.
+#[derive(Debug, Clone)]
+pub struct Keyholder {
+    /// Share of the sealing key.
+    key_share: H256,
+    /// Secret used to validate the access tokens (JWT) of the decryption shares requests.
+    secret_auth: String,
+}
+
+impl Keyholder {
+    /// Returns `None` if the encrypted mempool is disabled or the node is not a keyholder.
+    pub fn from_config(config: &EncryptedMempool) -> Option<Self> {
+        if !config.enabled || config.key_share.is_zero() {
+            return None;
+        }
+        assert!(
+            !config.secret_auth.is_empty(),
+            "Secret of the keyholders must be set for the keyholders"
+        );
+        Some(Self {
+            key_share: config.key_share,
+            secret_auth: config.secret_auth.clone(),
+        })
+    }
+
+    /// Checks that the request is made by the mempool, i.e. its bearer token is signed with the
+    /// shared secret.
+    fn authorize(&self, req: &HttpRequest) -> actix_web::Result<()> {
+        let token = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| actix_web::error::ErrorUnauthorized("Missing access token"))?;
+        decode::<KeyholdersAuthToken>(
+            token,
+            &DecodingKey::from_secret(self.secret_auth.as_ref()),
+            &Validation::default(),
+        )
+        .map_err(|_| actix_web::error::ErrorUnauthorized("Invalid access token"))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct KeyholdersAuthToken {
+    /// Subject (whom auth token refers to).
+    sub: String,
+    /// Expiration time (as UTC timestamp).
+    exp: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct SharesRequest {
+    ephemeral_keys: Vec<Public>,
+}
+
+/// Computes the decryption shares of the blobs with the given ephemeral keys as a keyholder.
+///
+/// Shares of the key never leave the keyholder, and every request is recorded in the audit log,
+/// so the blobs revealed outside of the block formation can be traced.
+#[actix_web::post("/encrypted_mempool/shares")]
+async fn decryption_shares(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<SharesRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let keyholder = data.keyholder.as_ref().ok_or_else(|| {
+        actix_web::error::ErrorForbidden("Node is not a keyholder of the encrypted mempool")
+    })?;
+    keyholder.authorize(&req)?;
+    let SharesRequest { ephemeral_keys } = request.into_inner();
+
+    let shares = ephemeral_keys
+        .iter()
+        .map(|ephemeral_key| decryption_share(&keyholder.key_share, ephemeral_key))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(actix_web::error::ErrorBadRequest)?;
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let after = serde_json::json!({ "blobs": ephemeral_keys.len() });
+    audit(
+        &mut storage,
+        &req,
+        "encrypted_mempool_shares",
+        None,
+        None,
+        Some(after),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(shares))
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(decryption_shares);
+}
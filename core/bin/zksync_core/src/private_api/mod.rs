@@ -6,11 +6,14 @@
 //!
 //! All the incoming data is assumed to be correct and not double-checked
 //! for correctness.
+//!
+//! Endpoints are grouped by the feature into the submodules, each of them registering
+//! its services via `configure`.
 
 use std::thread;
 use std::time::{Duration, Instant};
 
-use actix_web::{web, App, HttpResponse, HttpServer};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
 use futures::{channel::mpsc, StreamExt};
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
@@ -18,17 +21,88 @@ use zksync_api_types::CoreStatus;
 
 use zksync_config::configs::api::PrivateApiConfig;
 use zksync_eth_client::EthereumGateway;
-use zksync_storage::ConnectionPool;
+use zksync_storage::{admin_audit::records::NewAuditEntry, ConnectionPool, StorageProcessor};
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
+use crate::state_keeper::{IntakePause, SealRequest};
+
+pub use self::committee::CommitteeMember;
+pub use self::keyholder::Keyholder;
+
+mod accounting;
+mod audit_log;
+mod chaos_faults;
+mod circuit_breaker;
+mod committee;
+mod eth;
+mod fee_sponsors;
+mod freezes;
+mod intake;
+mod keyholder;
+mod monitoring;
+mod private_tx_clients;
+mod token_aliases;
+mod watch_lists;
+
 const STATUS_INVALIDATION_PERIOD: Duration = Duration::from_secs(60);
 
+/// Header with the identity of the operator making the admin action, recorded in the audit log.
+const ACTOR_HEADER: &str = "X-Admin-Actor";
+
 #[derive(Debug)]
 struct AppState {
     connection_pool: ConnectionPool,
     read_only_connection_pool: ConnectionPool,
     eth_client: EthereumGateway,
     status_cache: RwLock<Option<(CoreStatus, Instant)>>,
+    seal_request: SealRequest,
+    intake_pause: IntakePause,
+    /// Credentials used to approve L1 operations as an operators committee member.
+    committee_member: Option<CommitteeMember>,
+    /// Share of the sealing key used to reveal the encrypted transfers as a keyholder.
+    keyholder: Option<Keyholder>,
+}
+
+/// Returns the identity of the operator making the request. Requests without the actor header
+/// are attributed to the address they came from.
+fn request_actor(req: &HttpRequest) -> String {
+    req.headers()
+        .get(ACTOR_HEADER)
+        .and_then(|actor| actor.to_str().ok())
+        .filter(|actor| !actor.trim().is_empty())
+        .map(String::from)
+        .unwrap_or_else(|| {
+            let peer = req
+                .peer_addr()
+                .map(|addr| addr.ip().to_string())
+                .unwrap_or_default();
+            format!("unknown@{}", peer)
+        })
+}
+
+/// Appends the admin action to the audit log. Must be called within the database transaction
+/// of the audited change.
+async fn audit(
+    storage: &mut StorageProcessor<'_>,
+    req: &HttpRequest,
+    action: &str,
+    target: Option<String>,
+    before_value: Option<serde_json::Value>,
+    after_value: Option<serde_json::Value>,
+) -> actix_web::Result<()> {
+    let entry = NewAuditEntry {
+        actor: request_actor(req),
+        action: action.to_string(),
+        target,
+        before_value,
+        after_value,
+    };
+    storage
+        .admin_audit_schema()
+        .record(&entry)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok(())
 }
 
 /// Health check.
@@ -68,6 +142,10 @@ pub fn start_private_core_api(
     read_only_connection_pool: ConnectionPool,
     eth_client: EthereumGateway,
     config: PrivateApiConfig,
+    seal_request: SealRequest,
+    intake_pause: IntakePause,
+    committee_member: Option<CommitteeMember>,
+    keyholder: Option<Keyholder>,
 ) -> JoinHandle<()> {
     let (panic_sender, mut panic_receiver) = mpsc::channel(1);
 
@@ -85,6 +163,10 @@ pub fn start_private_core_api(
                         read_only_connection_pool: read_only_connection_pool.clone(),
                         eth_client: eth_client.clone(),
                         status_cache: Default::default(),
+                        seal_request: seal_request.clone(),
+                        intake_pause: intake_pause.clone(),
+                        committee_member: committee_member.clone(),
+                        keyholder: keyholder.clone(),
                     };
 
                     // By calling `register_data` instead of `data` we're avoiding double
@@ -94,6 +176,20 @@ pub fn start_private_core_api(
                         .app_data(web::Data::new(app_state))
                         .app_data(web::JsonConfig::default().limit(2usize.pow(32)))
                         .service(status)
+                        .configure(intake::configure)
+                        .configure(monitoring::configure)
+                        .configure(eth::configure)
+                        .configure(committee::configure)
+                        .configure(keyholder::configure)
+                        .configure(circuit_breaker::configure)
+                        .configure(chaos_faults::configure)
+                        .configure(freezes::configure)
+                        .configure(fee_sponsors::configure)
+                        .configure(token_aliases::configure)
+                        .configure(audit_log::configure)
+                        .configure(accounting::configure)
+                        .configure(watch_lists::configure)
+                        .configure(private_tx_clients::configure)
                 })
                 .bind(&config.bind_addr())
                 .expect("failed to bind")
@@ -0,0 +1,162 @@
+//! Sizes of the server queues, progress of the blocks pipeline, known tokens and events.
+
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+
+use zksync_types::event::EventId;
+
+use super::AppState;
+
+/// Maximum number of events returned at once.
+const MAX_EVENTS: usize = 100;
+
+#[derive(Debug, Deserialize)]
+struct EventsQuery {
+    after: Option<u64>,
+}
+
+/// Returns the sizes of the queues of the server: the transactions in the mempool, the blocks
+/// waiting for the proofs and the L1 operations waiting for the confirmation.
+#[actix_web::get("/queues")]
+async fn queues(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mempool_txs = storage
+        .chain()
+        .mempool_schema()
+        .get_mempool_size()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let prover_jobs = storage
+        .prover_schema()
+        .pending_jobs_count()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let unconfirmed_eth_operations = storage
+        .ethereum_schema()
+        .load_unconfirmed_operations()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .len();
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "mempool_txs": mempool_txs,
+        "prover_jobs": prover_jobs,
+        "unconfirmed_eth_operations": unconfirmed_eth_operations,
+    })))
+}
+
+/// Returns the last blocks at each stage of the pipeline: sealed by the state keeper, committed,
+/// proven and executed on L1. The numbers of the L1 stages are the ones confirmed on Ethereum.
+#[actix_web::get("/pipeline")]
+async fn pipeline(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut block_schema = storage.chain().block_schema();
+    let sealed = block_schema
+        .get_last_saved_block()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let committed = block_schema
+        .get_last_committed_confirmed_block()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let proofs_generated = block_schema
+        .get_last_verified_block()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let proven = block_schema
+        .get_last_proven_confirmed_block()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let executed = block_schema
+        .get_last_verified_confirmed_block()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "sealed": sealed,
+        "committed": committed,
+        "proofs_generated": proofs_generated,
+        "proven": proven,
+        "executed": executed,
+    })))
+}
+
+/// Returns the tokens known to the server.
+#[actix_web::get("/tokens")]
+async fn tokens(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut tokens: Vec<_> = storage
+        .tokens_schema()
+        .load_tokens()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|(_, token)| token)
+        .collect();
+    tokens.sort_by_key(|token| token.id);
+    Ok(HttpResponse::Ok().json(tokens))
+}
+
+/// Returns the events stored after the given one, and the ID of the latest stored event.
+/// Without the `after` parameter only the ID of the latest event is returned, so the clients
+/// may start following the events from it.
+#[actix_web::get("/events")]
+async fn events(
+    data: web::Data<AppState>,
+    query: web::Query<EventsQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let last_event_id = storage
+        .event_schema()
+        .get_last_event_id()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut events = match query.after {
+        Some(after) => storage
+            .event_schema()
+            .fetch_new_events(EventId(after))
+            .await
+            .map_err(actix_web::error::ErrorInternalServerError)?,
+        None => Vec::new(),
+    };
+    events.truncate(MAX_EVENTS);
+
+    let events: Vec<_> = events
+        .into_iter()
+        .map(|event| {
+            serde_json::json!({
+                "id": event.id,
+                "block_number": event.block_number,
+                "event_type": event.event_type,
+                "event_data": event.event_data,
+            })
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "last_event_id": last_event_id,
+        "events": events,
+    })))
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(queues)
+        .service(pipeline)
+        .service(tokens)
+        .service(events);
+}
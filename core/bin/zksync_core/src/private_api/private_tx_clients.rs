@@ -0,0 +1,158 @@
+//! Clients of the private transactions channel.
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+
+use zksync_types::{
+    private_tx::{private_tx_token_hash, MAX_PRIVATE_TX_CLIENT_NAME_LEN},
+    H256,
+};
+
+use super::{audit, AppState};
+
+#[derive(Debug, Deserialize)]
+struct PrivateTxClientRequest {
+    name: String,
+    max_batches_per_minute: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemovePrivateTxClientRequest {
+    name: String,
+}
+
+/// Registers the client of the private transactions channel and returns its bearer token.
+/// The token is not stored and can't be retrieved again.
+#[actix_web::post("/private_txs/clients")]
+async fn private_tx_client_add(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<PrivateTxClientRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let name = request.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_PRIVATE_TX_CLIENT_NAME_LEN {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "Client name must be from 1 to {} characters long",
+            MAX_PRIVATE_TX_CLIENT_NAME_LEN
+        )));
+    }
+    if request.max_batches_per_minute == 0 || request.max_batches_per_minute > i32::MAX as u32 {
+        return Err(actix_web::error::ErrorBadRequest(
+            "Incorrect limit of batches per minute",
+        ));
+    }
+
+    let token = hex::encode(H256::random().as_bytes());
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let id = transaction
+        .private_txs_schema()
+        .create_client(
+            name,
+            &private_tx_token_hash(&token),
+            request.max_batches_per_minute,
+        )
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .ok_or_else(|| actix_web::error::ErrorBadRequest("Client already exists"))?;
+    audit(
+        &mut transaction,
+        &req,
+        "private_tx_client_add",
+        Some(name.to_string()),
+        None,
+        Some(serde_json::json!({
+            "id": id,
+            "max_batches_per_minute": request.max_batches_per_minute,
+        })),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!("Private transactions client {} is registered", name);
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "id": id, "token": token })))
+}
+
+/// Removes the client of the private transactions channel, its token stops being accepted.
+#[actix_web::post("/private_txs/clients/remove")]
+async fn private_tx_client_remove(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<RemovePrivateTxClientRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let removed = transaction
+        .private_txs_schema()
+        .remove_client(&request.name)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !removed {
+        return Err(actix_web::error::ErrorNotFound("Client is not found"));
+    }
+    audit(
+        &mut transaction,
+        &req,
+        "private_tx_client_remove",
+        Some(request.name.clone()),
+        None,
+        None,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!("Private transactions client {} is removed", request.name);
+    Ok(HttpResponse::Ok().json(()))
+}
+
+/// Returns the clients of the private transactions channel.
+#[actix_web::get("/private_txs/clients")]
+async fn private_tx_clients(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let clients = storage
+        .private_txs_schema()
+        .load_clients()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let response: Vec<_> = clients
+        .into_iter()
+        .map(|client| {
+            serde_json::json!({
+                "id": client.id,
+                "name": client.name,
+                "max_batches_per_minute": client.max_batches_per_minute,
+                "created_at": client.created_at,
+            })
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(private_tx_client_add)
+        .service(private_tx_client_remove)
+        .service(private_tx_clients);
+}
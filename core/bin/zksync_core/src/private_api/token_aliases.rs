@@ -0,0 +1,165 @@
+//! Aliases of the token symbols.
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+
+use zksync_types::{TokenId, TokenLike};
+
+use super::{audit, AppState};
+
+#[derive(Debug, Deserialize)]
+struct TokenSymbolAliasRequest {
+    alias: String,
+    token_id: TokenId,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoveTokenSymbolAliasRequest {
+    alias: String,
+}
+
+/// Returns the aliases of the token symbols.
+#[actix_web::get("/tokens/aliases")]
+async fn token_symbol_aliases(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let aliases = storage
+        .tokens_schema()
+        .load_symbol_aliases()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok().json(aliases))
+}
+
+/// Adds the alias of the token symbol or points the existing one to another token.
+///
+/// Alias can't be the symbol of other tokens only: it may be equal to the symbol shared by several
+/// tokens to select the canonical one of them.
+#[actix_web::post("/tokens/aliases")]
+async fn token_symbol_alias_store(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<TokenSymbolAliasRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let alias = request.alias.trim();
+    if alias.is_empty() || !matches!(TokenLike::parse(alias), TokenLike::Symbol(_)) {
+        return Err(actix_web::error::ErrorBadRequest(
+            "Alias must be a non-empty token symbol",
+        ));
+    }
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let token = transaction
+        .tokens_schema()
+        .get_token(TokenLike::Id(request.token_id))
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .ok_or_else(|| actix_web::error::ErrorNotFound("Token is not found"))?;
+    let same_symbol = transaction
+        .tokens_schema()
+        .get_tokens_by_symbol(alias)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !same_symbol.is_empty() && same_symbol.iter().all(|other| other.id != token.id) {
+        return Err(actix_web::error::ErrorConflict(
+            "Alias is the symbol of other tokens",
+        ));
+    }
+
+    let before = transaction
+        .tokens_schema()
+        .load_symbol_aliases()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .find(|stored| stored.alias == alias.to_lowercase());
+    transaction
+        .tokens_schema()
+        .store_symbol_alias(alias, token.id)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    audit(
+        &mut transaction,
+        &req,
+        "token_symbol_alias_store",
+        Some(alias.to_lowercase()),
+        before.map(|before| serde_json::json!(before)),
+        Some(serde_json::json!({ "token_id": token.id })),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Symbol alias {} is stored for the token {} ({})",
+        alias,
+        token.id,
+        token.qualified_symbol()
+    );
+    Ok(HttpResponse::Ok().json(()))
+}
+
+/// Removes the alias of the token symbol.
+#[actix_web::post("/tokens/aliases/remove")]
+async fn token_symbol_alias_remove(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<RemoveTokenSymbolAliasRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let alias = request.alias.trim().to_lowercase();
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let before = transaction
+        .tokens_schema()
+        .load_symbol_aliases()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .find(|stored| stored.alias == alias)
+        .ok_or_else(|| actix_web::error::ErrorNotFound("Alias is not found"))?;
+    transaction
+        .tokens_schema()
+        .remove_symbol_alias(&alias)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    audit(
+        &mut transaction,
+        &req,
+        "token_symbol_alias_remove",
+        Some(alias.clone()),
+        Some(serde_json::json!(before)),
+        None,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!("Symbol alias {} is removed", alias);
+    Ok(HttpResponse::Ok().json(()))
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(token_symbol_aliases)
+        .service(token_symbol_alias_store)
+        .service(token_symbol_alias_remove);
+}
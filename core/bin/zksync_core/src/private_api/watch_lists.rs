@@ -0,0 +1,262 @@
+//! Watch lists of the addresses whose incoming funds are posted to the webhooks.
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+
+use zksync_types::Address;
+
+use super::{audit, AppState};
+
+/// Maximum number of addresses added to or removed from the watch list at once.
+const MAX_WATCH_ADDRESSES_PER_REQUEST: usize = 100_000;
+
+#[derive(Debug, Deserialize)]
+struct WatchAddressesRequest {
+    name: String,
+    webhook_url: String,
+    addresses: Vec<Address>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoveWatchAddressesRequest {
+    name: String,
+    addresses: Vec<Address>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoveWatchListRequest {
+    name: String,
+}
+
+/// Adds the addresses to the watch list, creating the list if it doesn't exist yet.
+/// The incoming transfers and deposits of the addresses are posted to the webhook of the list
+/// starting from the next block.
+#[actix_web::post("/watch_lists")]
+async fn watch_addresses_add(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<WatchAddressesRequest>,
+) -> actix_web::Result<HttpResponse> {
+    if request.name.trim().is_empty() {
+        return Err(actix_web::error::ErrorBadRequest(
+            "Watch list name can't be empty",
+        ));
+    }
+    if reqwest::Url::parse(&request.webhook_url).is_err() {
+        return Err(actix_web::error::ErrorBadRequest("Incorrect webhook URL"));
+    }
+    if request.addresses.len() > MAX_WATCH_ADDRESSES_PER_REQUEST {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "At most {} addresses can be added at once",
+            MAX_WATCH_ADDRESSES_PER_REQUEST
+        )));
+    }
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let watch_list = transaction
+        .watch_lists_schema()
+        .get_watch_list(&request.name)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let id = match watch_list {
+        Some(watch_list) if watch_list.webhook_url != request.webhook_url => {
+            return Err(actix_web::error::ErrorBadRequest(
+                "Watch list already exists with another webhook",
+            ));
+        }
+        Some(watch_list) => watch_list.id,
+        None => {
+            let last_block = transaction
+                .chain()
+                .block_schema()
+                .get_last_saved_block()
+                .await
+                .map_err(actix_web::error::ErrorInternalServerError)?;
+            transaction
+                .watch_lists_schema()
+                .create_watch_list(&request.name, &request.webhook_url, last_block)
+                .await
+                .map_err(actix_web::error::ErrorInternalServerError)?
+        }
+    };
+    let added = transaction
+        .watch_lists_schema()
+        .add_watch_addresses(id, &request.addresses)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    audit(
+        &mut transaction,
+        &req,
+        "watch_addresses_add",
+        Some(request.name.clone()),
+        None,
+        Some(serde_json::json!({
+            "webhook_url": request.webhook_url,
+            "added": added,
+        })),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "{} addresses are added to the watch list {}",
+        added,
+        request.name
+    );
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "id": id, "added": added })))
+}
+
+/// Removes the addresses from the watch list.
+#[actix_web::post("/watch_lists/addresses/remove")]
+async fn watch_addresses_remove(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<RemoveWatchAddressesRequest>,
+) -> actix_web::Result<HttpResponse> {
+    if request.addresses.len() > MAX_WATCH_ADDRESSES_PER_REQUEST {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "At most {} addresses can be removed at once",
+            MAX_WATCH_ADDRESSES_PER_REQUEST
+        )));
+    }
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let watch_list = transaction
+        .watch_lists_schema()
+        .get_watch_list(&request.name)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .ok_or_else(|| actix_web::error::ErrorNotFound("Watch list is not found"))?;
+    let removed = transaction
+        .watch_lists_schema()
+        .remove_watch_addresses(watch_list.id, &request.addresses)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    audit(
+        &mut transaction,
+        &req,
+        "watch_addresses_remove",
+        Some(request.name.clone()),
+        None,
+        Some(serde_json::json!({ "removed": removed })),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "{} addresses are removed from the watch list {}",
+        removed,
+        request.name
+    );
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "removed": removed })))
+}
+
+/// Removes the watch list along with all its addresses.
+#[actix_web::post("/watch_lists/remove")]
+async fn watch_list_remove(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    request: web::Json<RemoveWatchListRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let watch_list = transaction
+        .watch_lists_schema()
+        .get_watch_list(&request.name)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .ok_or_else(|| actix_web::error::ErrorNotFound("Watch list is not found"))?;
+    let addresses = transaction
+        .watch_lists_schema()
+        .count_watch_addresses(watch_list.id)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    transaction
+        .watch_lists_schema()
+        .remove_watch_list(watch_list.id)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    audit(
+        &mut transaction,
+        &req,
+        "watch_list_remove",
+        Some(request.name.clone()),
+        Some(serde_json::json!({
+            "webhook_url": watch_list.webhook_url,
+            "addresses": addresses,
+        })),
+        None,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!("Watch list {} is removed", request.name);
+    Ok(HttpResponse::Ok().json(()))
+}
+
+/// Returns the watch lists along with the number of their addresses.
+#[actix_web::get("/watch_lists")]
+async fn watch_lists(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .read_only_connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let records = storage
+        .watch_lists_schema()
+        .load_watch_lists()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let mut response = Vec::with_capacity(records.len());
+    for record in records {
+        let addresses = storage
+            .watch_lists_schema()
+            .count_watch_addresses(record.id)
+            .await
+            .map_err(actix_web::error::ErrorInternalServerError)?;
+        response.push(serde_json::json!({
+            "id": record.id,
+            "name": record.name,
+            "webhook_url": record.webhook_url,
+            "last_block": record.last_block,
+            "addresses": addresses,
+            "created_at": record.created_at,
+        }));
+    }
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(watch_addresses_add)
+        .service(watch_addresses_remove)
+        .service(watch_list_remove)
+        .service(watch_lists);
+}
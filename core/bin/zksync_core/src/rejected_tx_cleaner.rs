@@ -5,6 +5,9 @@
 //! which is useful only for a short period of time. Since such transactions are not actually
 //! included in the block and don't affect the state hash, there is no much sense to keep
 //! them forever.
+//!
+//! The idempotency keys of the submitted transactions are removed by the cleaner as well, once
+//! they are older than the configured age.
 
 // External uses
 use tokio::{task::JoinHandle, time};
@@ -16,6 +19,8 @@ use zksync_storage::ConnectionPool;
 #[must_use]
 pub fn run_rejected_tx_cleaner(config: &DBConfig, db_pool: ConnectionPool) -> JoinHandle<()> {
     let max_age = chrono::Duration::from_std(config.rejected_transactions_max_age()).unwrap();
+    let idempotency_keys_max_age =
+        chrono::Duration::from_std(config.idempotency_keys_max_age()).unwrap();
     let interval = config.rejected_transactions_cleaner_interval();
     let mut timer = time::interval(interval);
 
@@ -33,6 +38,14 @@ pub fn run_rejected_tx_cleaner(config: &DBConfig, db_pool: ConnectionPool) -> Jo
             {
                 vlog::error!("Can't delete rejected transactions {:?}", e);
             }
+            if let Err(e) = storage
+                .chain()
+                .mempool_schema()
+                .remove_old_idempotency_keys(idempotency_keys_max_age)
+                .await
+            {
+                vlog::error!("Can't delete old idempotency keys {:?}", e);
+            }
             timer.tick().await;
         }
     })
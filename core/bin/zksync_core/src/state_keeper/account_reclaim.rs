@@ -0,0 +1,185 @@
+//! Reclaims of the empty accounts left by the forced exits.
+//!
+//! Forced exit withdraws the whole balance of the account which never set its signing key, and
+//! the drained account keeps occupying a leaf of the account tree. Once the share of the occupied
+//! leaves reaches the configured threshold, the state keeper appends the operator-initiated
+//! `Close` operations for such accounts to the block being sealed, and their leaves are reused
+//! by the new accounts.
+//!
+//! Candidates are tracked in memory only, so the accounts drained before the restart of the
+//! server are never reclaimed.
+
+// Built-in uses
+use std::collections::BTreeSet;
+// Workspace uses
+use zksync_config::configs::chain::StateKeeper as StateKeeperConfig;
+use zksync_crypto::params::max_account_id;
+use zksync_state::state::ZkSyncState;
+use zksync_types::{AccountId, ZkSyncOp};
+
+#[derive(Debug, Clone)]
+pub struct AccountReclaimPolicy {
+    /// Percentage of the occupied leaves starting from which the accounts are reclaimed,
+    /// zero means that reclaims are disabled.
+    leaf_pressure_threshold: u64,
+    max_reclaims_per_block: usize,
+    /// Amount of the account tree leaves available for the accounts.
+    capacity: usize,
+    candidates: BTreeSet<AccountId>,
+}
+
+impl AccountReclaimPolicy {
+    /// Creates the policy which never reclaims the accounts.
+    pub fn disabled() -> Self {
+        Self {
+            leaf_pressure_threshold: 0,
+            max_reclaims_per_block: 0,
+            capacity: *max_account_id() as usize + 1,
+            candidates: BTreeSet::new(),
+        }
+    }
+
+    /// Panics if the reclaims are enabled: the close account operation is not supported by
+    /// the circuit yet, so the blocks with the reclaims couldn't be proven.
+    pub fn from_config(config: &StateKeeperConfig) -> Self {
+        assert_eq!(
+            config.reclaim_leaf_pressure_threshold, 0,
+            "Account reclaims are not supported by the circuit yet, \
+             CHAIN_STATE_KEEPER_RECLAIM_LEAF_PRESSURE_THRESHOLD must be zero"
+        );
+        Self {
+            leaf_pressure_threshold: config.reclaim_leaf_pressure_threshold,
+            max_reclaims_per_block: config.max_reclaims_per_block,
+            ..Self::disabled()
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.leaf_pressure_threshold > 0 && self.max_reclaims_per_block > 0
+    }
+
+    /// Remembers the target of the executed forced exit if the account was drained by it.
+    pub(super) fn track(&mut self, op: &ZkSyncOp, state: &ZkSyncState) {
+        if !self.is_enabled() {
+            return;
+        }
+        if let ZkSyncOp::ForcedExit(op) = op {
+            let is_drained = state
+                .get_account(op.target_account_id)
+                .map(|account| account.get_nonzero_balances().is_empty())
+                .unwrap_or(false);
+            if is_drained {
+                self.candidates.insert(op.target_account_id);
+            }
+        }
+    }
+
+    /// Returns the accounts to be reclaimed in the block being sealed, removing them from
+    /// the candidates. Nothing is returned while the leaf pressure is below the threshold.
+    pub(super) fn take(&mut self, state: &ZkSyncState) -> Vec<AccountId> {
+        if !self.is_enabled() {
+            return Vec::new();
+        }
+        let occupied_leaves = state.occupied_leaves() as u64;
+        if occupied_leaves * 100 < self.leaf_pressure_threshold * self.capacity as u64 {
+            return Vec::new();
+        }
+
+        let accounts: Vec<_> = self
+            .candidates
+            .iter()
+            .take(self.max_reclaims_per_block)
+            .copied()
+            .collect();
+        for account_id in &accounts {
+            self.candidates.remove(account_id);
+        }
+        accounts
+    }
+
+    /// Returns the account to the candidates, e.g. if it didn't fit into the block.
+    pub(super) fn defer(&mut self, account_id: AccountId) {
+        self.candidates.insert(account_id);
+    }
+}
+
+impl Default for AccountReclaimPolicy {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{Account, Address, ForcedExit, ForcedExitOp, TokenId};
+
+    fn policy(leaf_pressure_threshold: u64, capacity: usize) -> AccountReclaimPolicy {
+        AccountReclaimPolicy {
+            leaf_pressure_threshold,
+            max_reclaims_per_block: 2,
+            capacity,
+            candidates: BTreeSet::new(),
+        }
+    }
+
+    fn forced_exit(target_account_id: AccountId) -> ZkSyncOp {
+        let tx = ForcedExit::new(
+            AccountId(0),
+            Address::zero(),
+            TokenId(0),
+            0u32.into(),
+            Default::default(),
+            Default::default(),
+            None,
+        );
+        ZkSyncOp::ForcedExit(Box::new(ForcedExitOp {
+            tx,
+            target_account_id,
+            withdraw_amount: None,
+        }))
+    }
+
+    fn state(accounts: u32) -> ZkSyncState {
+        let mut state = ZkSyncState::empty();
+        for id in 0..accounts {
+            let account = Account::default_with_address(&Address::from_low_u64_be(id as u64 + 1));
+            state.insert_account(AccountId(id), account);
+        }
+        state
+    }
+
+    /// Checks that only the drained accounts are reclaimed, and only under the leaf pressure.
+    #[test]
+    fn reclaims_under_pressure() {
+        let mut state = state(4);
+        let mut account = state.get_account(AccountId(3)).unwrap();
+        account.set_balance(TokenId(0), 1u32.into());
+        state.insert_account(AccountId(3), account);
+
+        let mut policy = policy(50, 10);
+        for account_id in 1..4 {
+            policy.track(&forced_exit(AccountId(account_id)), &state);
+        }
+        assert_eq!(policy.candidates.len(), 2);
+        // 4 of 10 leaves are occupied.
+        assert!(policy.take(&state).is_empty());
+
+        let state = self::state(5);
+        assert_eq!(policy.take(&state), vec![AccountId(1), AccountId(2)]);
+        assert!(policy.take(&state).is_empty());
+
+        policy.defer(AccountId(2));
+        assert_eq!(policy.take(&state), vec![AccountId(2)]);
+    }
+
+    /// Checks that disabled policy doesn't track the accounts.
+    #[test]
+    fn disabled_policy() {
+        let state = state(10);
+        let mut policy = policy(0, 10);
+        policy.track(&forced_exit(AccountId(1)), &state);
+        assert!(policy.candidates.is_empty());
+        assert!(policy.take(&state).is_empty());
+    }
+}
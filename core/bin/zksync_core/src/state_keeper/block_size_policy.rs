@@ -0,0 +1,334 @@
+//! Block size policy decides how many chunks the next pending block is allowed to fill
+//! before it gets sealed.
+//!
+//! By default the state keeper always targets the biggest supported block size, which means
+//! that under light load blocks are sealed by timeout only, and under a withdrawal-heavy load
+//! blocks are sealed by the gas limit long before the chunks are filled (so the prover has to
+//! prove a big block which is mostly padded with noops).
+//!
+//! The dynamic policy picks one of the supported block sizes based on:
+//! - the chunk demand observed while the previous block was formed;
+//! - the observed L1 gas per chunk, so that the block is not bigger than what the target L1
+//!   cost per block allows to fill;
+//! - the depth of the prover job queue: if provers can't keep up, bigger blocks are preferred,
+//!   since every block requires a separate proof.
+
+// Built-in uses
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use std::time::Duration;
+// External uses
+use tokio::{task::JoinHandle, time};
+// Workspace uses
+use zksync_config::configs::chain::StateKeeper as StateKeeperConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::gas_counter::GasCounter;
+
+/// Interval between two consecutive checks of the prover job queue.
+const PROVER_QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Weight of the latest observation in the exponential moving average of gas per chunk.
+const GAS_PER_CHUNK_EMA_WEIGHT: f64 = 0.3;
+
+/// Shared value of the prover job queue depth, updated by the prover queue monitor task.
+#[derive(Debug, Clone, Default)]
+pub struct ProverQueueDepth(Arc<AtomicUsize>);
+
+impl ProverQueueDepth {
+    pub fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn set(&self, depth: usize) {
+        self.0.store(depth, Ordering::Relaxed);
+    }
+}
+
+/// Parameters of the dynamic block sizing.
+#[derive(Debug, Clone)]
+pub struct DynamicBlockSizing {
+    /// Amount of pending prover jobs after which the biggest block size is always chosen.
+    pub prover_queue_threshold: usize,
+    /// Target amount of L1 gas (commit + verify) to be spent on one block.
+    pub target_block_gas: u64,
+    /// Depth of the prover job queue.
+    pub prover_queue_depth: ProverQueueDepth,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlockSizePolicy {
+    available_block_chunk_sizes: Vec<usize>,
+    dynamic: Option<DynamicBlockSizing>,
+
+    /// Chunks requested by the operations proposed since the current pending block was created.
+    demand_chunks: usize,
+    /// Chunks requested by the biggest proposed element (transaction, batch or priority operation).
+    largest_element_chunks: usize,
+    /// Moving average of L1 gas spent per used chunk, `None` until the first block is observed.
+    gas_per_chunk: Option<f64>,
+}
+
+impl BlockSizePolicy {
+    /// Creates a policy which always chooses the biggest available block size.
+    pub fn fixed(available_block_chunk_sizes: Vec<usize>) -> Self {
+        Self {
+            available_block_chunk_sizes,
+            dynamic: None,
+            demand_chunks: 0,
+            largest_element_chunks: 0,
+            gas_per_chunk: None,
+        }
+    }
+
+    pub fn dynamic(available_block_chunk_sizes: Vec<usize>, params: DynamicBlockSizing) -> Self {
+        Self {
+            dynamic: Some(params),
+            ..Self::fixed(available_block_chunk_sizes)
+        }
+    }
+
+    /// Creates a policy according to the state keeper configuration.
+    pub fn from_config(config: &StateKeeperConfig, prover_queue_depth: ProverQueueDepth) -> Self {
+        if config.dynamic_block_sizing {
+            Self::dynamic(
+                config.block_chunk_sizes.clone(),
+                DynamicBlockSizing {
+                    prover_queue_threshold: config.dynamic_block_sizing_prover_queue_threshold,
+                    target_block_gas: config.dynamic_block_sizing_target_block_gas,
+                    prover_queue_depth,
+                },
+            )
+        } else {
+            Self::fixed(config.block_chunk_sizes.clone())
+        }
+    }
+
+    fn max_block_size(&self) -> usize {
+        *self
+            .available_block_chunk_sizes
+            .last()
+            .expect("Block chunk sizes are empty")
+    }
+
+    /// Records the amount of chunks requested by an element proposed by mempool.
+    pub fn record_demand(&mut self, chunks: usize) {
+        self.demand_chunks += chunks;
+        self.largest_element_chunks = std::cmp::max(self.largest_element_chunks, chunks);
+    }
+
+    /// Records the sealed block and returns the capacity for the next pending block.
+    /// `block_gas` is the total gas limit of commit and verify operations for the sealed block.
+    pub fn on_block_sealed(
+        &mut self,
+        chunks_used: usize,
+        block_size: usize,
+        block_gas: u64,
+    ) -> usize {
+        if block_size > 0 {
+            metrics::histogram!(
+                "state_keeper.block_utilization",
+                chunks_used as f64 / block_size as f64
+            );
+        }
+
+        if chunks_used > 0 {
+            // Base costs are paid per block no matter its content, so they are not a part of
+            // the per-chunk cost.
+            let gas_per_chunk =
+                block_gas.saturating_sub(empty_block_gas()) as f64 / chunks_used as f64;
+            self.gas_per_chunk = Some(match self.gas_per_chunk {
+                Some(avg) => {
+                    avg * (1.0 - GAS_PER_CHUNK_EMA_WEIGHT)
+                        + gas_per_chunk * GAS_PER_CHUNK_EMA_WEIGHT
+                }
+                None => gas_per_chunk,
+            });
+        }
+
+        // Operations which didn't fit into the sealed block are still waiting to be included.
+        let demand = std::mem::take(&mut self.demand_chunks);
+        let largest_element = std::mem::take(&mut self.largest_element_chunks);
+        let next_size = self.select_block_size(demand, largest_element);
+        metrics::gauge!(
+            "state_keeper.block_size_policy.selected_size",
+            next_size as f64
+        );
+        next_size
+    }
+
+    fn select_block_size(&self, demand: usize, largest_element: usize) -> usize {
+        let params = match &self.dynamic {
+            Some(params) => params,
+            None => return self.max_block_size(),
+        };
+
+        if params.prover_queue_depth.get() >= params.prover_queue_threshold {
+            // Provers are the bottleneck, so we want to have as few blocks as possible.
+            return self.max_block_size();
+        }
+
+        // Smallest block that fits all the observed demand.
+        let demanded_size = self.smallest_block_size_for(demand);
+
+        // Biggest block that can be filled without exceeding the target L1 cost. If the current
+        // tx mix is gas-heavy, a bigger block would be sealed by the gas limit anyway.
+        let cost_bound_size = match self.gas_per_chunk {
+            Some(gas_per_chunk) if gas_per_chunk > 0.0 => {
+                let affordable_chunks = params.target_block_gas.saturating_sub(empty_block_gas())
+                    as f64
+                    / gas_per_chunk;
+                self.available_block_chunk_sizes
+                    .iter()
+                    .copied()
+                    .take_while(|&size| size as f64 <= affordable_chunks)
+                    .last()
+                    .unwrap_or(self.available_block_chunk_sizes[0])
+            }
+            _ => self.max_block_size(),
+        };
+
+        // Whatever the cost is, the next block must be able to fit the biggest pending element,
+        // otherwise this element would never be included.
+        std::cmp::max(
+            std::cmp::min(demanded_size, cost_bound_size),
+            self.smallest_block_size_for(largest_element),
+        )
+    }
+
+    fn smallest_block_size_for(&self, chunks: usize) -> usize {
+        self.available_block_chunk_sizes
+            .iter()
+            .copied()
+            .find(|&size| size >= chunks)
+            .unwrap_or_else(|| self.max_block_size())
+    }
+}
+
+/// Estimated L1 gas (commit + verify) required for a block without any operations.
+fn empty_block_gas() -> u64 {
+    let gas_counter = GasCounter::new();
+    (gas_counter.commit_gas_limit() + gas_counter.verify_gas_limit()).as_u64()
+}
+
+/// Periodically loads the amount of pending prover jobs, so it can be used by the block size policy
+/// without giving the state keeper access to the database.
+#[must_use]
+pub fn run_prover_queue_monitor(
+    db_pool: ConnectionPool,
+    prover_queue_depth: ProverQueueDepth,
+) -> JoinHandle<()> {
+    let mut timer = time::interval(PROVER_QUEUE_POLL_INTERVAL);
+
+    tokio::spawn(async move {
+        loop {
+            timer.tick().await;
+
+            let mut storage = match db_pool.access_storage().await {
+                Ok(storage) => storage,
+                Err(err) => {
+                    vlog::warn!("Prover queue monitor couldn't access the database: {}", err);
+                    continue;
+                }
+            };
+            match storage.prover_schema().pending_jobs_count().await {
+                Ok(count) => {
+                    prover_queue_depth.set(count as usize);
+                    metrics::gauge!(
+                        "state_keeper.block_size_policy.prover_queue_depth",
+                        count as f64
+                    );
+                }
+                Err(err) => vlog::warn!("Can't load the amount of pending prover jobs: {}", err),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZES: [usize; 3] = [10, 50, 100];
+
+    fn dynamic_policy(prover_queue_threshold: usize, target_block_gas: u64) -> BlockSizePolicy {
+        BlockSizePolicy::dynamic(
+            SIZES.to_vec(),
+            DynamicBlockSizing {
+                prover_queue_threshold,
+                target_block_gas,
+                prover_queue_depth: ProverQueueDepth::default(),
+            },
+        )
+    }
+
+    fn base_gas() -> u64 {
+        empty_block_gas()
+    }
+
+    /// Checks that fixed policy always chooses the biggest block.
+    #[test]
+    fn fixed_policy() {
+        let mut policy = BlockSizePolicy::fixed(SIZES.to_vec());
+        policy.record_demand(1);
+        assert_eq!(policy.on_block_sealed(1, 10, base_gas()), 100);
+    }
+
+    /// Checks that dynamic policy chooses the smallest block fitting the observed demand.
+    #[test]
+    fn block_size_follows_demand() {
+        let mut policy = dynamic_policy(10, u64::MAX);
+
+        policy.record_demand(5);
+        assert_eq!(policy.on_block_sealed(5, 10, base_gas()), 10);
+
+        policy.record_demand(20);
+        policy.record_demand(20);
+        assert_eq!(policy.on_block_sealed(10, 10, base_gas()), 50);
+
+        policy.record_demand(500);
+        assert_eq!(policy.on_block_sealed(50, 50, base_gas()), 100);
+
+        // Demand is reset after each block.
+        assert_eq!(policy.on_block_sealed(0, 10, base_gas()), 10);
+    }
+
+    /// Checks that the block size is bounded by the target L1 cost.
+    #[test]
+    fn block_size_bounded_by_gas() {
+        let mut policy = dynamic_policy(10, base_gas() + 60_000);
+
+        // 1000 gas per chunk, so 60 chunks are affordable.
+        policy.record_demand(500);
+        assert_eq!(policy.on_block_sealed(10, 10, base_gas() + 10_000), 50);
+
+        // Target is too low for any block size, the smallest one should be used.
+        let mut policy = dynamic_policy(10, base_gas());
+        policy.record_demand(5);
+        policy.record_demand(5);
+        assert_eq!(policy.on_block_sealed(10, 10, base_gas() + 10_000), 10);
+    }
+
+    /// Checks that the next block is always able to fit the biggest proposed element.
+    #[test]
+    fn biggest_element_fits() {
+        let mut policy = dynamic_policy(10, base_gas());
+
+        policy.record_demand(5);
+        policy.on_block_sealed(10, 10, base_gas() + 10_000);
+
+        policy.record_demand(70);
+        assert_eq!(policy.on_block_sealed(0, 10, base_gas()), 100);
+    }
+
+    /// Checks that the biggest block is chosen once the prover queue is too deep.
+    #[test]
+    fn deep_prover_queue() {
+        let mut policy = dynamic_policy(3, base_gas());
+        policy.dynamic.as_ref().unwrap().prover_queue_depth.set(3);
+
+        policy.record_demand(1);
+        assert_eq!(policy.on_block_sealed(10, 10, base_gas() + 10_000), 100);
+    }
+}
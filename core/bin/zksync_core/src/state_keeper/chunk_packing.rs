@@ -0,0 +1,154 @@
+//! Packing of the spare chunks of the block being sealed.
+//!
+//! Transactions are executed in the order proposed by the mempool, so once the next one doesn't
+//! fit into the pending block, the block is sealed and the chunks left are padded with noops. With
+//! the packing enabled, the state keeper looks ahead in the queue for the transactions which still
+//! fit and moves them ahead of the deferred ones before the block is sealed.
+//!
+//! Transaction may only be moved if it doesn't touch any account touched by the transactions it
+//! overtakes, which keeps the nonces in order and the results of the execution the same. If the
+//! accounts touched by a deferred transaction can't be determined (e.g. for the swaps or the
+//! forced exits), nothing after it is moved. Batches are never moved, only overtaken.
+
+// Built-in uses
+use std::collections::HashSet;
+// Workspace uses
+use zksync_config::configs::chain::StateKeeper as StateKeeperConfig;
+use zksync_types::{mempool::SignedTxVariant, Address, ZkSyncTx};
+
+#[derive(Debug, Clone, Default)]
+pub struct ChunkPacking {
+    /// Amount of the queued transactions checked for each deferred one, zero means that
+    /// the packing is disabled.
+    pub(super) lookahead: usize,
+}
+
+impl ChunkPacking {
+    /// Creates the policy which never reorders the transactions.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    pub fn from_config(config: &StateKeeperConfig) -> Self {
+        Self {
+            lookahead: config.chunk_packing_lookahead,
+        }
+    }
+
+    /// Returns the accounts the packed transactions must not touch, or `None` if nothing may be
+    /// moved ahead of the deferred transaction.
+    pub(super) fn dependencies(&self, deferred: &SignedTxVariant) -> Option<PackingDependencies> {
+        if self.lookahead == 0 {
+            return None;
+        }
+        let mut dependencies = PackingDependencies::default();
+        if dependencies.defer(deferred) {
+            Some(dependencies)
+        } else {
+            None
+        }
+    }
+}
+
+/// Accounts touched by the transactions left in the queue.
+#[derive(Debug, Default)]
+pub(super) struct PackingDependencies {
+    accounts: HashSet<Address>,
+}
+
+impl PackingDependencies {
+    /// Remembers the accounts of the transaction left in the queue. Returns `false` if they can't
+    /// be determined, so no transaction after it may be moved.
+    pub(super) fn defer(&mut self, variant: &SignedTxVariant) -> bool {
+        let txs = match variant {
+            SignedTxVariant::Tx(tx) => std::slice::from_ref(tx),
+            SignedTxVariant::Batch(batch) => batch.txs.as_slice(),
+        };
+        for tx in txs {
+            match touched_accounts(&tx.tx) {
+                Some(accounts) => self.accounts.extend(accounts),
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// Checks whether the transaction may be executed ahead of the deferred ones.
+    pub(super) fn is_independent(&self, tx: &ZkSyncTx) -> bool {
+        touched_accounts(tx).map_or(false, |accounts| {
+            accounts
+                .iter()
+                .all(|account| !self.accounts.contains(account))
+        })
+    }
+}
+
+/// Returns the accounts whose state the transaction depends on or changes, `None` if they are not
+/// known from the transaction itself.
+fn touched_accounts(tx: &ZkSyncTx) -> Option<Vec<Address>> {
+    let accounts = match tx {
+        ZkSyncTx::Transfer(tx) => vec![tx.from, tx.to],
+        ZkSyncTx::Withdraw(tx) => vec![tx.from],
+        ZkSyncTx::Close(tx) => vec![tx.account],
+        ZkSyncTx::ChangePubKey(tx) => vec![tx.account],
+        ZkSyncTx::WithdrawNFT(tx) => vec![tx.from],
+        // Forced exit is paid by the initiator known by its ID only, accounts of the swap orders
+        // are not listed either, and the minted tokens get their IDs in the order of execution.
+        ZkSyncTx::ForcedExit(_) | ZkSyncTx::Swap(_) | ZkSyncTx::MintNFT(_) => return None,
+    };
+    Some(accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{AccountId, Nonce, SignedZkSyncTx, TokenId, Transfer};
+
+    fn transfer(from: u64, to: u64) -> SignedTxVariant {
+        let tx = Transfer::new(
+            AccountId(from as u32),
+            Address::from_low_u64_be(from),
+            Address::from_low_u64_be(to),
+            TokenId(0),
+            1u32.into(),
+            0u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        );
+        SignedZkSyncTx::from(ZkSyncTx::from(tx)).into()
+    }
+
+    fn tx(variant: &SignedTxVariant) -> &ZkSyncTx {
+        match variant {
+            SignedTxVariant::Tx(tx) => &tx.tx,
+            SignedTxVariant::Batch(_) => unreachable!(),
+        }
+    }
+
+    /// Checks that the transactions touching the accounts of the deferred ones are not moved.
+    #[test]
+    fn dependencies() {
+        let packing = ChunkPacking { lookahead: 8 };
+        let mut dependencies = packing.dependencies(&transfer(1, 2)).unwrap();
+        assert!(!dependencies.is_independent(tx(&transfer(1, 3))));
+        assert!(!dependencies.is_independent(tx(&transfer(3, 2))));
+        assert!(dependencies.is_independent(tx(&transfer(3, 4))));
+
+        let batch = SignedTxVariant::batch(
+            vec![
+                SignedZkSyncTx::from(tx(&transfer(5, 6)).clone()),
+                SignedZkSyncTx::from(tx(&transfer(7, 8)).clone()),
+            ],
+            1,
+            Vec::new(),
+        );
+        assert!(dependencies.defer(&batch));
+        assert!(!dependencies.is_independent(tx(&transfer(8, 3))));
+        assert!(dependencies.is_independent(tx(&transfer(3, 4))));
+
+        assert!(ChunkPacking::disabled()
+            .dependencies(&transfer(1, 2))
+            .is_none());
+    }
+}
@@ -0,0 +1,162 @@
+//! Processing of the expiring priority operations while the intake of the L2 transactions is paused.
+//!
+//! The operator may pause the intake via the private core API, e.g. during an incident. If the pause
+//! lasts long enough, the queued priority operations expire and anyone can activate the exodus mode
+//! on the contract. To prevent this, the state keeper keeps asking the mempool for the priority
+//! operations only, and once the earliest of them comes within the configured margin of its deadline,
+//! the operations are executed in a block which is sealed right away.
+//!
+//! The switch doesn't override the circuit breaker: while the state may be diverged, no blocks are
+//! produced at all.
+
+// Built-in uses
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+// Workspace uses
+use zksync_config::configs::chain::StateKeeper as StateKeeperConfig;
+use zksync_types::PriorityOp;
+// Local uses
+use crate::eth_watch::LatestEthBlock;
+
+/// Shared flag set while the intake of the L2 transactions is paused by the operator.
+#[derive(Debug, Clone, Default)]
+pub struct IntakePause(Arc<AtomicBool>);
+
+impl IntakePause {
+    pub fn is_paused(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Sets the pause, returning its previous state.
+    pub fn set_paused(&self, paused: bool) -> bool {
+        self.0.swap(paused, Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DeadManSwitch {
+    /// Priority operations expiring in less than this amount of Ethereum blocks are processed
+    /// during the pause, zero means that they are never processed.
+    deadline_margin: u64,
+    latest_eth_block: LatestEthBlock,
+    intake_pause: IntakePause,
+}
+
+impl DeadManSwitch {
+    /// Creates the switch for the intake which is never paused.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    pub fn from_config(
+        config: &StateKeeperConfig,
+        latest_eth_block: LatestEthBlock,
+        intake_pause: IntakePause,
+    ) -> Self {
+        Self {
+            deadline_margin: config.dead_man_switch_margin,
+            latest_eth_block,
+            intake_pause,
+        }
+    }
+
+    pub(super) fn is_paused(&self) -> bool {
+        self.intake_pause.is_paused()
+    }
+
+    /// Returns the operations to be executed during the pause: all the operations up to the last
+    /// one expiring within the margin, since they must be processed in order. The rest are left in
+    /// the mempool and proposed again on the next iteration.
+    pub(super) fn select_expiring(&self, mut priority_ops: Vec<PriorityOp>) -> Vec<PriorityOp> {
+        // Until the first Ethereum block is observed we can't reason about deadlines.
+        let latest_eth_block = self.latest_eth_block.get();
+        if self.deadline_margin == 0 || latest_eth_block == 0 {
+            return Vec::new();
+        }
+
+        let expiring = priority_ops.iter().rposition(|op| {
+            op.deadline_block.saturating_sub(latest_eth_block) < self.deadline_margin
+        });
+        match expiring {
+            Some(index) => {
+                priority_ops.truncate(index + 1);
+                priority_ops
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{AccountId, Address, FullExit, TokenId, ZkSyncPriorityOp, H256};
+
+    fn priority_op(serial_id: u64, deadline_block: u64) -> PriorityOp {
+        PriorityOp {
+            serial_id,
+            data: ZkSyncPriorityOp::FullExit(FullExit {
+                account_id: AccountId(0),
+                eth_address: Address::zero(),
+                token: TokenId(0),
+                is_legacy: false,
+            }),
+            deadline_block,
+            eth_hash: H256::zero(),
+            eth_block: 0,
+            eth_block_index: None,
+        }
+    }
+
+    fn serial_ids(ops: &[PriorityOp]) -> Vec<u64> {
+        ops.iter().map(|op| op.serial_id).collect()
+    }
+
+    /// Checks that the operations are selected in order up to the last expiring one.
+    #[test]
+    fn select_expiring() {
+        let switch = DeadManSwitch {
+            deadline_margin: 10,
+            ..Default::default()
+        };
+        let ops = vec![
+            priority_op(0, 100),
+            priority_op(1, 150),
+            priority_op(2, 105),
+            priority_op(3, 200),
+        ];
+
+        // Ethereum block is not known yet.
+        assert!(switch.select_expiring(ops.clone()).is_empty());
+
+        switch.latest_eth_block.set(80);
+        assert!(switch.select_expiring(ops.clone()).is_empty());
+
+        switch.latest_eth_block.set(91);
+        assert_eq!(serial_ids(&switch.select_expiring(ops.clone())), vec![0]);
+
+        switch.latest_eth_block.set(96);
+        assert_eq!(
+            serial_ids(&switch.select_expiring(ops.clone())),
+            vec![0, 1, 2]
+        );
+
+        // Disabled switch.
+        let switch = DeadManSwitch {
+            latest_eth_block: switch.latest_eth_block.clone(),
+            ..Default::default()
+        };
+        assert!(switch.select_expiring(ops).is_empty());
+    }
+
+    #[test]
+    fn intake_pause() {
+        let pause = IntakePause::default();
+        assert!(!pause.set_paused(true));
+        assert!(pause.is_paused());
+        assert!(pause.set_paused(false));
+        assert!(!pause.is_paused());
+    }
+}
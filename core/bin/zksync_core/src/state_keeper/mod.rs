@@ -2,8 +2,10 @@ use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
 // External uses
+use chrono::Utc;
 use futures::{
     channel::{mpsc, oneshot},
+    future,
     stream::StreamExt,
     SinkExt,
 };
@@ -19,9 +21,11 @@ use zksync_types::{
     },
     gas_counter::GasCounter,
     mempool::SignedTxVariant,
-    tx::ZkSyncTx,
-    Address, PriorityOp, SignedZkSyncTx,
+    operations::CloseOp,
+    tx::{Close, TimeRange, TxSignature, ZkSyncTx},
+    Address, PriorityOp, SignedZkSyncTx, ZkSyncOp,
 };
+use zksync_utils::shutdown::ShutdownParticipant;
 // Local uses
 use self::{
     pending_block::PendingBlock,
@@ -30,19 +34,33 @@ use self::{
     utils::system_time_timestamp,
 };
 use crate::{
+    account_freezes::FrozenAccounts,
+    circuit_breaker::CircuitBreaker,
     committer::{BlockCommitRequest, CommitRequest},
     tx_event_emitter::ProcessedOperations,
 };
 
 pub use self::{
+    account_reclaim::AccountReclaimPolicy,
+    block_size_policy::{run_prover_queue_monitor, BlockSizePolicy, ProverQueueDepth},
+    chunk_packing::ChunkPacking,
+    dead_man_switch::{DeadManSwitch, IntakePause},
     init_params::ZkSyncStateInitParams,
+    padding_transfers::PaddingTransfers,
     root_hash_calculator::{start_root_hash_calculator, BlockRootHashJobQueue},
+    seal_triggers::{EarlySealTriggers, SealRequest},
     types::StateKeeperTestkitRequest,
 };
 
+mod account_reclaim;
+mod block_size_policy;
+mod chunk_packing;
+mod dead_man_switch;
 mod init_params;
+mod padding_transfers;
 mod pending_block;
 mod root_hash_calculator;
+mod seal_triggers;
 mod state_restore;
 mod types;
 mod utils;
@@ -56,6 +74,24 @@ pub struct ZkSyncStateKeeper {
     state: ZkSyncState,
     pending_block: PendingBlock,
     config: StateKeeperConfig,
+    /// Policy choosing the capacity of the next pending block.
+    block_size_policy: BlockSizePolicy,
+    /// Conditions to seal the pending block before it's full or timed out.
+    early_seal_triggers: EarlySealTriggers,
+    /// New miniblocks are not executed while the circuit breaker is tripped.
+    circuit_breaker: CircuitBreaker,
+    /// Processes the expiring priority operations while the intake of the L2 transactions is paused.
+    dead_man_switch: DeadManSwitch,
+    /// Accounts frozen by the operator, passed to the state before each miniblock.
+    frozen_accounts: FrozenAccounts,
+    /// Empty accounts to be removed from the tree once it's filled enough.
+    account_reclaim: AccountReclaimPolicy,
+    /// Fills the spare chunks of the block being sealed with the independent queued transactions.
+    chunk_packing: ChunkPacking,
+    /// Operator self-transfers filling the spare chunks of the block being sealed instead of the noops.
+    padding_transfers: PaddingTransfers,
+    /// Once the shutdown is requested, the pending block is sealed and the committer is flushed.
+    shutdown: Option<ShutdownParticipant>,
 
     tx_for_commitments: mpsc::Sender<CommitRequest>,
     tx_for_mempool: mpsc::Sender<MempoolBlocksRequest>,
@@ -128,6 +164,7 @@ impl ZkSyncStateKeeper {
             .get_account_by_address(&fee_account_address)
             .expect("Fee account should be present in the account tree");
 
+        let block_size_policy = BlockSizePolicy::fixed(available_block_chunk_sizes.clone());
         let config = StateKeeperConfig::new(
             fee_account_id,
             available_block_chunk_sizes,
@@ -151,6 +188,15 @@ impl ZkSyncStateKeeper {
             state: sk_state,
             pending_block,
             config,
+            block_size_policy,
+            early_seal_triggers: EarlySealTriggers::disabled(),
+            circuit_breaker: CircuitBreaker::default(),
+            dead_man_switch: DeadManSwitch::disabled(),
+            frozen_accounts: FrozenAccounts::default(),
+            account_reclaim: AccountReclaimPolicy::disabled(),
+            chunk_packing: ChunkPacking::disabled(),
+            padding_transfers: PaddingTransfers::disabled(),
+            shutdown: None,
 
             tx_for_commitments,
             tx_for_mempool,
@@ -201,7 +247,7 @@ impl ZkSyncStateKeeper {
             for operation in pending_block.success_operations.clone() {
                 match operation {
                     ExecutedOperations::Tx(tx) => {
-                        self.apply_tx(&tx.signed_tx)
+                        self.reapply_tx(&tx.signed_tx)
                             .assert_included("Tx from the restored pending block was not executed");
                         txs_count += 1;
                     }
@@ -243,6 +289,52 @@ impl ZkSyncStateKeeper {
         metrics::histogram!("state_keeper.initialize", start.elapsed());
     }
 
+    /// Replaces the policy used to choose the capacity of the blocks.
+    /// The new policy will be used starting from the next pending block.
+    pub fn set_block_size_policy(&mut self, block_size_policy: BlockSizePolicy) {
+        self.block_size_policy = block_size_policy;
+    }
+
+    /// Replaces the conditions under which the pending block is sealed early.
+    pub fn set_early_seal_triggers(&mut self, early_seal_triggers: EarlySealTriggers) {
+        self.early_seal_triggers = early_seal_triggers;
+    }
+
+    /// Sets the circuit breaker that halts the block production once tripped.
+    pub fn set_circuit_breaker(&mut self, circuit_breaker: CircuitBreaker) {
+        self.circuit_breaker = circuit_breaker;
+    }
+
+    /// Sets the switch processing the expiring priority operations while the intake is paused.
+    pub fn set_dead_man_switch(&mut self, dead_man_switch: DeadManSwitch) {
+        self.dead_man_switch = dead_man_switch;
+    }
+
+    /// Sets the accounts frozen by the operator, transfers from them are rejected.
+    pub fn set_frozen_accounts(&mut self, frozen_accounts: FrozenAccounts) {
+        self.frozen_accounts = frozen_accounts;
+    }
+
+    /// Sets the policy of reclaiming the empty accounts left by the forced exits.
+    pub fn set_account_reclaim_policy(&mut self, account_reclaim: AccountReclaimPolicy) {
+        self.account_reclaim = account_reclaim;
+    }
+
+    /// Sets the policy of packing the spare chunks of the block being sealed.
+    pub fn set_chunk_packing(&mut self, chunk_packing: ChunkPacking) {
+        self.chunk_packing = chunk_packing;
+    }
+
+    /// Sets the operator self-transfers used to pad the blocks being sealed.
+    pub fn set_padding_transfers(&mut self, padding_transfers: PaddingTransfers) {
+        self.padding_transfers = padding_transfers;
+    }
+
+    /// Registers the state keeper as a participant of the server shutdown.
+    pub fn set_shutdown_participant(&mut self, shutdown: ShutdownParticipant) {
+        self.shutdown = Some(shutdown);
+    }
+
     pub async fn execute_reverted_blocks(&mut self) {
         while let Some(block) = self.reverted_blocks.pop_front() {
             self.execute_incomplete_block(block).await;
@@ -285,21 +377,106 @@ impl ZkSyncStateKeeper {
             // spend more time in the loop iteration than this interval.
             metrics::histogram!("state_keeper.miniblock_interval", start.elapsed());
 
+            if self
+                .shutdown
+                .as_ref()
+                .map_or(false, ShutdownParticipant::is_requested)
+            {
+                self.shut_down().await;
+                // Other actors may keep sending the requests to the state keeper, so it's parked
+                // instead of finishing the run.
+                future::pending::<()>().await;
+            }
+
+            // Neither new transactions are executed nor the pending block is sealed while the state
+            // may be diverged. Pending block is kept as is and will be processed once the breaker is reset.
+            if self.circuit_breaker.is_tripped() {
+                metrics::increment_counter!("state_keeper.halted_miniblocks");
+                continue;
+            }
+
             let start = Instant::now();
             // `.throttle()` method will postpone the next miniblock iteration if currently we have too
             // many blocks for which root hash is not yet calculated.
             self.root_hash_queue.throttle().await;
             metrics::histogram!("state_keeper.throttle", start.elapsed());
 
+            if self.dead_man_switch.is_paused() {
+                self.execute_expiring_priority_ops().await;
+                continue;
+            }
+
             let block_timestamp = self.pending_block.timestamp;
-            let proposed_block = self.propose_new_block(block_timestamp).await;
+            let proposed_block = self.propose_new_block(block_timestamp, false).await;
             metrics::histogram!("miniblock_size", proposed_block.size() as f64);
 
             self.execute_proposed_block(proposed_block).await;
         }
     }
 
-    async fn propose_new_block(&mut self, block_timestamp: u64) -> ProposedBlock {
+    /// Seals the pending block and waits until the committer stores everything sent to it,
+    /// including the root hashes of the sealed blocks, so no block is left half-sealed.
+    /// The pending block is kept as is if it's empty or the circuit breaker is tripped.
+    async fn shut_down(&mut self) {
+        let start = Instant::now();
+        if !self.circuit_breaker.is_tripped() && !self.pending_block.success_operations.is_empty() {
+            vlog::info!(
+                "Sealing block {} before the shutdown",
+                *self.pending_block.number
+            );
+            self.reclaim_empty_accounts();
+            self.seal_pending_block().await;
+        }
+
+        self.root_hash_queue.wait_finished().await;
+        let (sender, receiver) = oneshot::channel();
+        self.tx_for_commitments
+            .send(CommitRequest::Flush(sender))
+            .await
+            .expect("committer receiver dropped");
+        receiver.await.expect("committer dropped the flush request");
+
+        vlog::info!("State keeper is stopped, the committer is flushed");
+        metrics::histogram!("state_keeper.shut_down", start.elapsed());
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.complete();
+        }
+    }
+
+    /// Executes the priority operations approaching their deadlines while the intake is paused,
+    /// sealing the block right away. Pending block is kept as is if there are no such operations.
+    async fn execute_expiring_priority_ops(&mut self) {
+        let block_timestamp = self.pending_block.timestamp;
+        let proposed_block = self.propose_new_block(block_timestamp, true).await;
+        let priority_ops = self
+            .dead_man_switch
+            .select_expiring(proposed_block.priority_ops);
+        if priority_ops.is_empty() {
+            metrics::increment_counter!("state_keeper.paused_miniblocks");
+            return;
+        }
+
+        vlog::warn!(
+            "Intake is paused, but {} priority operations are close to expiration. Processing them in block {}",
+            priority_ops.len(),
+            *self.pending_block.number
+        );
+        metrics::increment_counter!("state_keeper.dead_man_switch_blocks");
+        self.execute_proposed_block(ProposedBlock {
+            priority_ops,
+            txs: Vec::new(),
+        })
+        .await;
+        if !self.pending_block.success_operations.is_empty() {
+            self.seal_pending_block().await;
+        }
+    }
+
+    async fn propose_new_block(
+        &mut self,
+        block_timestamp: u64,
+        priority_ops_only: bool,
+    ) -> ProposedBlock {
         let start = Instant::now();
 
         let (response_sender, receiver) = oneshot::channel();
@@ -328,6 +505,7 @@ impl ZkSyncStateKeeper {
             block_timestamp,
             response_sender,
             executed_txs,
+            priority_ops_only,
         });
 
         self.tx_for_mempool
@@ -352,7 +530,7 @@ impl ZkSyncStateKeeper {
         for tx in block.block_transactions {
             match tx {
                 ExecutedOperations::Tx(tx) => {
-                    if let ApplyOutcome::NotIncluded = self.apply_tx(&tx.signed_tx) {
+                    if let ApplyOutcome::NotIncluded = self.reapply_tx(&tx.signed_tx) {
                         // It's not necessary to include all txs because we can change the rules, cause of some bugs.
                         // However, it's not a common situation in most of the cases it should be applied as is
                         vlog::error!("The transaction was not included in a block when a block recovery was performed: {:?}", &tx.signed_tx)
@@ -383,8 +561,12 @@ impl ZkSyncStateKeeper {
             self.pending_block.timestamp = system_time_timestamp();
         }
 
+        // Freezes are applied starting from the next miniblock after the change.
+        self.state.frozen_accounts = self.frozen_accounts.get();
+
         // We want to store this variable before moving anything from the pending block.
         let empty_proposed_block = proposed_block.is_empty();
+        self.record_proposed_demand(&proposed_block);
 
         let mut priority_op_queue = proposed_block
             .priority_ops
@@ -415,6 +597,7 @@ impl ZkSyncStateKeeper {
                             // We could not execute the tx due to either of block size limit
                             // or the withdraw operations limit, so we seal this block and
                             // the last transaction will go to the next block instead.
+                            self.pack_spare_chunks(&variant, &mut tx_queue, &mut executed_ops);
                             self.seal_pending_block().await;
 
                             tx_queue.push_front(variant);
@@ -430,6 +613,7 @@ impl ZkSyncStateKeeper {
                             // We could not execute the batch tx due to either of block size limit
                             // or the withdraw operations limit, so we seal this block and
                             // the last transaction will go to the next block instead.
+                            self.pack_spare_chunks(&variant, &mut tx_queue, &mut executed_ops);
                             self.seal_pending_block().await;
 
                             tx_queue.push_front(variant);
@@ -461,9 +645,25 @@ impl ZkSyncStateKeeper {
             self.config.max_miniblock_iterations
         };
 
+        // Early seal triggers are checked on every iteration, so that a pending operator request is
+        // consumed even if the block is sealed for another reason.
+        let early_seal_reason = self
+            .early_seal_triggers
+            .check(&self.pending_block, Utc::now());
+
         // Check whether we should seal this block and start processing the next one, or we just need
         // to persist the pending block.
         if self.pending_block.should_seal(max_miniblock_iterations) {
+            self.reclaim_empty_accounts();
+            self.seal_pending_block().await;
+        } else if let Some(reason) = early_seal_reason {
+            vlog::info!(
+                "Sealing block {} early, reason: {}",
+                *self.pending_block.number,
+                reason.as_str()
+            );
+            metrics::increment_counter!("state_keeper.early_seal", "reason" => reason.as_str());
+            self.reclaim_empty_accounts();
             self.seal_pending_block().await;
         } else {
             // State keeper may process empty blocks (or blocks containing rejected transactions only), and it's an
@@ -478,6 +678,21 @@ impl ZkSyncStateKeeper {
         metrics::histogram!("state_keeper.execute_proposed_block", start.elapsed());
     }
 
+    /// Reports the amount of chunks requested by the proposed block to the block size policy.
+    fn record_proposed_demand(&mut self, proposed_block: &ProposedBlock) {
+        for priority_op in &proposed_block.priority_ops {
+            self.block_size_policy
+                .record_demand(priority_op.data.chunks());
+        }
+        for variant in &proposed_block.txs {
+            let chunks = match variant {
+                SignedTxVariant::Tx(tx) => self.state.chunks_for_tx(tx),
+                SignedTxVariant::Batch(batch) => self.state.chunks_for_batch(&batch.txs),
+            };
+            self.block_size_policy.record_demand(chunks);
+        }
+    }
+
     // Err if there is no space in current block
     fn apply_priority_op(&mut self, priority_op: &PriorityOp) -> ApplyOutcome<ExecutedOperations> {
         let start = Instant::now();
@@ -595,6 +810,7 @@ impl ZkSyncStateKeeper {
                         .gas_counter
                         .add_op(&executed_op)
                         .expect("We have already checked that we can include this tx");
+                    self.account_reclaim.track(&executed_op, &self.state);
                     let chunks_used = executed_op.chunks();
 
                     let block_index = self.pending_block.pending_op_block_index;
@@ -694,6 +910,7 @@ impl ZkSyncStateKeeper {
                     .gas_counter
                     .add_op(&executed_op)
                     .expect("We have already checked that we can include this tx");
+                self.account_reclaim.track(&executed_op, &self.state);
 
                 let block_index = self.pending_block.pending_op_block_index;
                 let exec_result = ExecutedOperations::Tx(Box::new(ExecutedTx {
@@ -737,10 +954,165 @@ impl ZkSyncStateKeeper {
         ApplyOutcome::Included(exec_result)
     }
 
+    /// Executes the transaction of the restored block. Reclaims of the accounts are stored
+    /// as the `Close` transactions, but can't be executed as the transactions of the users.
+    fn reapply_tx(&mut self, tx: &SignedZkSyncTx) -> ApplyOutcome<ExecutedOperations> {
+        match &tx.tx {
+            ZkSyncTx::Close(close) => self.apply_reclaim(*close.clone()),
+            _ => self.apply_tx(tx),
+        }
+    }
+
+    /// Appends the reclaims of the empty accounts to the pending block if the tree is filled enough.
+    fn reclaim_empty_accounts(&mut self) {
+        let block_timestamp = self.pending_block.timestamp;
+        for account_id in self.account_reclaim.take(&self.state) {
+            let account = match self.state.get_account(account_id) {
+                Some(account) => account,
+                None => continue,
+            };
+            let tx = Close {
+                account: account.address,
+                nonce: account.nonce,
+                signature: TxSignature::default(),
+                // Hashes of the reclaims of the same account must differ
+                // if the leaf is taken by the same address again.
+                time_range: TimeRange::new(block_timestamp, block_timestamp),
+            };
+            if let ApplyOutcome::NotIncluded = self.apply_reclaim(tx) {
+                self.account_reclaim.defer(account_id);
+            }
+        }
+    }
+
+    fn apply_reclaim(&mut self, tx: Close) -> ApplyOutcome<ExecutedOperations> {
+        if self.pending_block.chunks_left < CloseOp::CHUNKS {
+            return ApplyOutcome::NotIncluded;
+        }
+        if let Some((account_id, _)) = self.state.get_account_by_address(&tx.account) {
+            let op = ZkSyncOp::Close(Box::new(CloseOp {
+                tx: tx.clone(),
+                account_id,
+            }));
+            if !self.pending_block.gas_counter.can_include(&[op]) {
+                return ApplyOutcome::NotIncluded;
+            }
+        }
+
+        let signed_tx = SignedZkSyncTx::from(ZkSyncTx::Close(Box::new(tx.clone())));
+        let exec_result = match self.state.reclaim_account(tx) {
+            Ok(OpSuccess {
+                fee,
+                updates,
+                executed_op,
+            }) => {
+                self.pending_block
+                    .gas_counter
+                    .add_op(&executed_op)
+                    .expect("We have already checked that we can include this tx");
+                metrics::increment_counter!("state_keeper.reclaimed_accounts");
+
+                let block_index = self.pending_block.pending_op_block_index;
+                let exec_result = ExecutedOperations::Tx(Box::new(ExecutedTx {
+                    signed_tx,
+                    success: true,
+                    op: Some(executed_op),
+                    fail_reason: None,
+                    block_index: Some(block_index),
+                    created_at: chrono::Utc::now(),
+                    batch_id: None,
+                }));
+                self.pending_block.add_successful_execution(
+                    CloseOp::CHUNKS,
+                    updates,
+                    fee,
+                    exec_result.clone(),
+                );
+                exec_result
+            }
+            Err(e) => {
+                // The account is not reclaimed, e.g. it received the funds after the forced exit.
+                // Reclaims are not requested by the users, so they are not stored as failed.
+                vlog::debug!("Failed to reclaim account {:?}: {}", signed_tx.tx, e);
+                ExecutedOperations::Tx(Box::new(ExecutedTx {
+                    signed_tx,
+                    success: false,
+                    op: None,
+                    fail_reason: Some(e.to_string()),
+                    block_index: None,
+                    created_at: chrono::Utc::now(),
+                    batch_id: None,
+                }))
+            }
+        };
+        ApplyOutcome::Included(exec_result)
+    }
+
+    /// Executes the queued transactions which still fit into the pending block and don't depend on
+    /// the deferred one, removing them from the queue. See `ChunkPacking` for the details.
+    fn pack_spare_chunks(
+        &mut self,
+        deferred: &SignedTxVariant,
+        tx_queue: &mut VecDeque<SignedTxVariant>,
+        executed_ops: &mut Vec<ExecutedOperations>,
+    ) {
+        let mut dependencies = match self.chunk_packing.dependencies(deferred) {
+            Some(dependencies) => dependencies,
+            None => return,
+        };
+
+        let mut index = 0;
+        let mut packed_txs = 0u64;
+        for _ in 0..self.chunk_packing.lookahead {
+            if index >= tx_queue.len() || self.pending_block.chunks_left == 0 {
+                break;
+            }
+            if let SignedTxVariant::Tx(tx) = &tx_queue[index] {
+                if dependencies.is_independent(&tx.tx) {
+                    if let ApplyOutcome::Included(exec_op) = self.apply_tx(tx) {
+                        executed_ops.push(exec_op);
+                        tx_queue.remove(index);
+                        packed_txs += 1;
+                        continue;
+                    }
+                }
+            }
+            if !dependencies.defer(&tx_queue[index]) {
+                break;
+            }
+            index += 1;
+        }
+
+        if packed_txs > 0 {
+            metrics::counter!("state_keeper.packed_txs", packed_txs);
+        }
+    }
+
+    /// Fills the chunks left in the pending block with the operator self-transfers,
+    /// see `PaddingTransfers` for the details.
+    fn pad_with_transfers(&mut self) {
+        let block_timestamp = self.pending_block.timestamp;
+        while let Some(fee_account) = self.state.get_account(self.config.fee_account_id) {
+            let tx = match self.padding_transfers.next(&fee_account, block_timestamp) {
+                Some(tx) => SignedZkSyncTx::from(ZkSyncTx::Transfer(Box::new(tx))),
+                None => break,
+            };
+            match self.apply_tx(&tx) {
+                ApplyOutcome::Included(ExecutedOperations::Tx(tx)) if tx.success => {
+                    metrics::increment_counter!("state_keeper.padding_transfers");
+                }
+                ApplyOutcome::Included(_) => self.padding_transfers.skip(),
+                ApplyOutcome::NotIncluded => break,
+            }
+        }
+    }
+
     /// Finalizes the pending block, transforming it into a full block.
     async fn seal_pending_block(&mut self) {
         let start = Instant::now();
 
+        self.pad_with_transfers();
+
         // Before sealing the block, we need to store the pending block in order to
         // save all the new transactions to the database.
         // Even though it will be removed once the seal request is processed, having a consistent
@@ -764,12 +1136,6 @@ impl ZkSyncStateKeeper {
         // should be spearated.
         let current_block = self.pending_block.number;
         let next_unprocessed_priority_op = self.pending_block.unprocessed_priority_op_current;
-        let new_pending_block = PendingBlock::new(
-            self.pending_block.number + 1,
-            next_unprocessed_priority_op,
-            self.config.max_block_size(),
-            system_time_timestamp(),
-        );
 
         let mut block_transactions = self.pending_block.success_operations.clone(); // TODO (ZKS-821): Avoid cloning.
         block_transactions.extend(
@@ -797,6 +1163,22 @@ impl ZkSyncStateKeeper {
             self.pending_block.timestamp,
         );
 
+        metrics::histogram!(
+            "state_keeper.block_padding_chunks",
+            (block.block_chunks_size - block.chunks_used()) as f64
+        );
+        let next_block_capacity = self.block_size_policy.on_block_sealed(
+            block.chunks_used(),
+            block.block_chunks_size,
+            (commit_gas_limit + verify_gas_limit).as_u64(),
+        );
+        let new_pending_block = PendingBlock::new(
+            self.pending_block.number + 1,
+            next_unprocessed_priority_op,
+            next_block_capacity,
+            system_time_timestamp(),
+        );
+
         // Update the fields of the new pending block.
         let block_metadata = BlockMetadata {
             fast_processing: self.pending_block.fast_processing_required,
@@ -0,0 +1,154 @@
+//! Padding of the spare chunks of the block being sealed with the operator self-transfers.
+//!
+//! By default, the chunks left in the sealed block are filled with noops. Operator may provide
+//! a file with the transfers from the fee account to itself signed beforehand, e.g. zero-amount
+//! transfers used to check the accounting of the collected fees or to exercise the transfer
+//! circuit on a testnet, and the state keeper pads the blocks with them while they last. Padding
+//! transfers are ordinary operations, so the witness generator and the contract process them as
+//! any other transfer.
+//!
+//! Transfers are taken in the order of nonces starting from the current nonce of the fee account,
+//! the ones with the lower nonces are skipped, so the same file may be used after the restart.
+
+// Built-in uses
+use std::collections::VecDeque;
+// External uses
+use anyhow::Context;
+// Workspace uses
+use zksync_config::configs::chain::StateKeeper as StateKeeperConfig;
+use zksync_types::{Account, Transfer};
+
+#[derive(Debug, Clone, Default)]
+pub struct PaddingTransfers {
+    transfers: VecDeque<Transfer>,
+}
+
+impl PaddingTransfers {
+    /// Creates the padding which leaves the spare chunks to the noops.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    pub fn new(mut transfers: Vec<Transfer>) -> Self {
+        transfers.sort_by_key(|tx| tx.nonce);
+        Self {
+            transfers: transfers.into(),
+        }
+    }
+
+    /// Loads the transfers from the JSON file set in the config, if any.
+    pub fn from_config(config: &StateKeeperConfig) -> anyhow::Result<Self> {
+        if config.padding_transfers_file.is_empty() {
+            return Ok(Self::disabled());
+        }
+        let file = std::fs::read_to_string(&config.padding_transfers_file).with_context(|| {
+            format!(
+                "Failed to read the padding transfers from {}",
+                config.padding_transfers_file
+            )
+        })?;
+        let transfers = serde_json::from_str(&file).context("Malformed padding transfers")?;
+        Ok(Self::new(transfers))
+    }
+
+    /// Returns the transfer to be applied next, if it's valid for the current state of the fee
+    /// account. Transfers which can't be applied anymore are dropped.
+    pub(super) fn next(&mut self, fee_account: &Account, block_timestamp: u64) -> Option<Transfer> {
+        while let Some(tx) = self.transfers.front() {
+            if tx.nonce > fee_account.nonce {
+                return None;
+            }
+            if tx.nonce == fee_account.nonce {
+                match check_transfer(tx, fee_account, block_timestamp) {
+                    Ok(()) => return Some(tx.clone()),
+                    Err(reason) => vlog::warn!("Padding transfer {:?} is dropped: {}", tx, reason),
+                }
+            }
+            self.transfers.pop_front();
+        }
+        None
+    }
+
+    /// Drops the transfer returned by `next`, e.g. if its execution failed.
+    pub(super) fn skip(&mut self) {
+        self.transfers.pop_front();
+    }
+}
+
+fn check_transfer(
+    tx: &Transfer,
+    fee_account: &Account,
+    block_timestamp: u64,
+) -> Result<(), &'static str> {
+    if tx.from != fee_account.address || tx.to != fee_account.address {
+        return Err("not a self-transfer of the fee account");
+    }
+    if tx.verify_signature().map(|(pub_key_hash, _)| pub_key_hash) != Some(fee_account.pub_key_hash)
+    {
+        return Err("not signed by the fee account");
+    }
+    if !tx.time_range.unwrap_or_default().is_valid(block_timestamp) {
+        return Err("out of the valid time range");
+    }
+    if fee_account.get_balance(tx.token) < &tx.amount + &tx.fee {
+        return Err("not enough balance");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_crypto::{
+        priv_key_from_fs,
+        rand::{Rng, SeedableRng, XorShiftRng},
+        PrivateKey,
+    };
+    use zksync_types::{AccountId, Address, Nonce, PubKeyHash, TokenId};
+
+    fn transfer(account: &Account, sk: &PrivateKey, nonce: u32, to: Address) -> Transfer {
+        Transfer::new_signed(
+            AccountId(0),
+            account.address,
+            to,
+            TokenId(0),
+            0u32.into(),
+            1u32.into(),
+            Nonce(nonce),
+            Default::default(),
+            sk,
+        )
+        .unwrap()
+    }
+
+    /// Checks that the transfers are taken in order of nonces, and the invalid ones are dropped.
+    #[test]
+    fn next_transfer() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let sk = priv_key_from_fs(rng.gen());
+        let mut account = Account::default_with_address(&Address::repeat_byte(0x11));
+        account.pub_key_hash = PubKeyHash::from_privkey(&sk);
+        account.set_balance(TokenId(0), 2u32.into());
+        account.nonce = Nonce(1);
+
+        let mut padding = PaddingTransfers::new(vec![
+            transfer(&account, &sk, 3, account.address),
+            transfer(&account, &sk, 2, Address::repeat_byte(0x22)),
+            transfer(&account, &sk, 1, account.address),
+            transfer(&account, &sk, 0, account.address),
+        ]);
+        assert_eq!(padding.next(&account, 0).unwrap().nonce, Nonce(1));
+        // Transfer is returned until it's applied.
+        assert_eq!(padding.next(&account, 0).unwrap().nonce, Nonce(1));
+
+        // Transfer to the other account is dropped, the next one can't be applied yet.
+        account.nonce = Nonce(2);
+        assert!(padding.next(&account, 0).is_none());
+        account.nonce = Nonce(3);
+        assert_eq!(padding.next(&account, 0).unwrap().nonce, Nonce(3));
+
+        account.set_balance(TokenId(0), 0u32.into());
+        assert!(padding.next(&account, 0).is_none());
+        assert!(padding.transfers.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 // External uses
+use chrono::{DateTime, Utc};
 // Workspace uses
 use zksync_state::state::CollectedFee;
 use zksync_types::{
@@ -30,6 +31,10 @@ pub(super) struct PendingBlock {
     /// Number of stored account updates in the db (from `account_updates` field)
     pub(super) stored_account_updates: usize,
     pub(super) timestamp: u64,
+    /// The closest deadline (Ethereum block number) among the priority operations in the block.
+    pub(super) earliest_priority_op_deadline: Option<u64>,
+    /// Time when the oldest transaction in the block was received by the server.
+    pub(super) oldest_tx_received_at: Option<DateTime<Utc>>,
 
     // Two fields below are for optimization: we don't want to overwrite all the block contents over and over.
     // With these fields we'll be able save the diff between two pending block states only.
@@ -61,6 +66,8 @@ impl PendingBlock {
             collected_fees: Vec::new(),
             stored_account_updates: 0,
             timestamp,
+            earliest_priority_op_deadline: None,
+            oldest_tx_received_at: None,
 
             success_txs_pending_len: 0,
             failed_txs_pending_len: 0,
@@ -107,8 +114,22 @@ impl PendingBlock {
         }
         self.pending_op_block_index += 1;
 
-        if exec_result.is_priority() {
-            self.unprocessed_priority_op_current += 1;
+        match &exec_result {
+            ExecutedOperations::PriorityOp(op) => {
+                self.unprocessed_priority_op_current += 1;
+                let deadline = op.priority_op.deadline_block;
+                self.earliest_priority_op_deadline = Some(
+                    self.earliest_priority_op_deadline
+                        .map_or(deadline, |current| current.min(deadline)),
+                );
+            }
+            ExecutedOperations::Tx(tx) => {
+                let received_at = tx.signed_tx.created_at;
+                self.oldest_tx_received_at = Some(
+                    self.oldest_tx_received_at
+                        .map_or(received_at, |current| current.min(received_at)),
+                );
+            }
         }
 
         self.success_operations.push(exec_result);
@@ -91,6 +91,7 @@ impl RootHashCalculator {
 
         // Increment block number to expect the next one.
         self.last_block_number = self.last_block_number + 1;
+        self.job_queue.finish_job();
 
         metrics::histogram!("root_hash_calculator.process_job", start.elapsed());
         metrics::gauge!(
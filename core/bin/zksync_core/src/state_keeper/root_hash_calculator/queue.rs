@@ -11,6 +11,10 @@ use tokio::sync::{Mutex, Notify};
 
 use zksync_types::{AccountUpdates, BlockNumber};
 
+/// Interval of checking the queue by the waiters. Duration interval should be small enough compared
+/// to the root hash calculation time, so that we don't "overthrottle".
+const POLL_INTERVAL: Duration = Duration::from_millis(25);
+
 /// Description of a single block root hash job.
 ///
 /// Contains data required to calculate the root hash of a block, given that root hashes
@@ -35,6 +39,8 @@ pub struct BlockRootHashJobQueue {
     queue: Arc<Mutex<VecDeque<BlockRootHashJob>>>,
     /// Queue size.
     size: Arc<AtomicUsize>,
+    /// Amount of the jobs pushed to the queue, but not yet finished by the calculator.
+    unfinished: Arc<AtomicUsize>,
     /// New jobs notification channel. Used to wake up waiters when the new job was pushed to the queue.
     notify: Arc<Notify>,
 }
@@ -47,6 +53,7 @@ impl BlockRootHashJobQueue {
         Self {
             queue: Arc::new(Mutex::new(queue)),
             size: Arc::new(AtomicUsize::from(size)),
+            unfinished: Arc::new(AtomicUsize::from(size)),
             notify: Arc::new(Notify::new()),
         }
     }
@@ -56,6 +63,7 @@ impl BlockRootHashJobQueue {
         self.queue.lock().await.push_back(job);
         // Here and below: `Relaxed` is enough as don't rely on the value for any critical sections.
         self.size.fetch_add(1, Ordering::Relaxed);
+        self.unfinished.fetch_add(1, Ordering::Relaxed);
         self.notify.notify_one();
         metrics::increment_gauge!("block_root_hash_job_queue.size", 1.0);
     }
@@ -80,6 +88,22 @@ impl BlockRootHashJobQueue {
         }
     }
 
+    /// Marks the popped job as finished, i.e. sent to the committer.
+    pub(crate) fn finish_job(&self) {
+        let old_value = self.unfinished.fetch_sub(1, Ordering::Relaxed);
+        assert!(
+            old_value != 0,
+            "Underflow on unfinished jobs count in state keeper"
+        );
+    }
+
+    /// Blocks until every job pushed to the queue is finished.
+    pub(crate) async fn wait_finished(&self) {
+        while self.unfinished.load(Ordering::Relaxed) != 0 {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
     /// Returns the current size of the queue.
     pub(crate) fn size(&self) -> usize {
         self.size.load(Ordering::Relaxed)
@@ -104,15 +128,11 @@ impl BlockRootHashJobQueue {
 
     /// Blocks until the job queue is small enough to proceed with the block generation.
     pub(crate) async fn throttle(&self) {
-        // Duration interval should be small enough compared to the root hash calculation time, so that we
-        // don't "overthrottle".
-        const THROTTLE_ITERATION_INTERVAL: Duration = Duration::from_millis(25);
-
         // Note: since block proposer is already timeout-based, it is more or less OK to sleep here too.
         // If it will become a bottleneck (which is unlikely), we can implement a `Future` that resolves
         // when we the queue has enough elements.
         while self.should_throttle() {
-            tokio::time::sleep(THROTTLE_ITERATION_INTERVAL).await;
+            tokio::time::sleep(POLL_INTERVAL).await;
         }
     }
 }
@@ -155,6 +175,13 @@ mod tests {
         assert_eq!(second_job.block, BlockNumber(2));
         assert_eq!(queue.size(), 0);
         assert!(!queue.should_throttle());
+
+        // Popped jobs are still awaited until they are finished.
+        queue.finish_job();
+        let wait = tokio::time::timeout(Duration::from_millis(100), queue.wait_finished());
+        assert!(wait.await.is_err());
+        queue.finish_job();
+        queue.wait_finished().await;
     }
 
     /// Checks notifier logic.
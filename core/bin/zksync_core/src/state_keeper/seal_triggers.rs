@@ -0,0 +1,310 @@
+//! Early seal triggers define conditions under which the pending block is sealed right away,
+//! even if it's neither full nor timed out.
+//!
+//! Currently supported triggers are:
+//! - a priority operation in the block is close to its expiration deadline;
+//! - a transaction in the block has been waiting for too long since it was received;
+//! - an operator requested the block to be sealed via the private API.
+
+// Built-in uses
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::Duration;
+// External uses
+use chrono::{DateTime, Utc};
+// Workspace uses
+use zksync_config::configs::chain::StateKeeper as StateKeeperConfig;
+// Local uses
+use super::pending_block::PendingBlock;
+use crate::eth_watch::LatestEthBlock;
+
+/// Shared flag set when an operator requests the pending block to be sealed.
+#[derive(Debug, Clone, Default)]
+pub struct SealRequest(Arc<AtomicBool>);
+
+impl SealRequest {
+    pub fn request(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the seal was requested, resetting the request.
+    fn take(&self) -> bool {
+        self.0.swap(false, Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum EarlySealReason {
+    PriorityOpDeadline,
+    MaxTxAge,
+    OperatorRequest,
+}
+
+impl EarlySealReason {
+    pub(super) fn as_str(self) -> &'static str {
+        match self {
+            Self::PriorityOpDeadline => "priority_op_deadline",
+            Self::MaxTxAge => "max_tx_age",
+            Self::OperatorRequest => "operator_request",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EarlySealTriggers {
+    /// Priority operations expiring in less than this amount of Ethereum blocks trigger the seal,
+    /// zero means that trigger is disabled.
+    priority_op_deadline_margin: u64,
+    /// Transactions waiting for longer than this trigger the seal.
+    max_tx_age: Option<Duration>,
+    latest_eth_block: LatestEthBlock,
+    seal_request: SealRequest,
+}
+
+impl EarlySealTriggers {
+    /// Creates triggers which never fire.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    pub fn from_config(
+        config: &StateKeeperConfig,
+        latest_eth_block: LatestEthBlock,
+        seal_request: SealRequest,
+    ) -> Self {
+        Self {
+            priority_op_deadline_margin: config.priority_op_deadline_seal_margin,
+            max_tx_age: config.max_tx_age_before_seal(),
+            latest_eth_block,
+            seal_request,
+        }
+    }
+
+    /// Checks whether the pending block must be sealed right now.
+    /// Empty blocks are never sealed early, and the operator request is discarded for them.
+    pub(super) fn check(
+        &self,
+        pending_block: &PendingBlock,
+        now: DateTime<Utc>,
+    ) -> Option<EarlySealReason> {
+        let seal_requested = self.seal_request.take();
+        if pending_block.success_operations.is_empty() {
+            if seal_requested {
+                vlog::info!(
+                    "Seal of the block {} was requested, but it contains no operations",
+                    *pending_block.number
+                );
+            }
+            return None;
+        }
+
+        if seal_requested {
+            return Some(EarlySealReason::OperatorRequest);
+        }
+
+        if let Some(deadline) = pending_block.earliest_priority_op_deadline {
+            // Until the first Ethereum block is observed we can't reason about deadlines.
+            let latest_eth_block = self.latest_eth_block.get();
+            if self.priority_op_deadline_margin > 0
+                && latest_eth_block > 0
+                && deadline.saturating_sub(latest_eth_block) < self.priority_op_deadline_margin
+            {
+                return Some(EarlySealReason::PriorityOpDeadline);
+            }
+        }
+
+        if let (Some(max_tx_age), Some(received_at)) =
+            (self.max_tx_age, pending_block.oldest_tx_received_at)
+        {
+            let tx_age = now
+                .signed_duration_since(received_at)
+                .to_std()
+                .unwrap_or_default();
+            if tx_age >= max_tx_age {
+                return Some(EarlySealReason::MaxTxAge);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{
+        AccountId, Address, BlockNumber, ExecutedOperations, ExecutedPriorityOp, ExecutedTx,
+        FullExit, FullExitOp, Nonce, PriorityOp, SignedZkSyncTx, TokenId, Transfer, ZkSyncOp,
+        ZkSyncPriorityOp, ZkSyncTx, H256,
+    };
+
+    fn triggers(
+        priority_op_deadline_margin: u64,
+        max_tx_age: Option<Duration>,
+    ) -> EarlySealTriggers {
+        EarlySealTriggers {
+            priority_op_deadline_margin,
+            max_tx_age,
+            ..Default::default()
+        }
+    }
+
+    fn pending_block() -> PendingBlock {
+        PendingBlock::new(BlockNumber(1), 0, 100, 0)
+    }
+
+    /// Creates a mock priority operation with the provided deadline.
+    fn mock_priority_op(deadline_block: u64) -> ExecutedOperations {
+        let full_exit = FullExit {
+            account_id: AccountId(0),
+            eth_address: Address::zero(),
+            token: TokenId(0),
+            is_legacy: false,
+        };
+        ExecutedOperations::PriorityOp(Box::new(ExecutedPriorityOp {
+            priority_op: PriorityOp {
+                serial_id: 0,
+                data: ZkSyncPriorityOp::FullExit(full_exit.clone()),
+                deadline_block,
+                eth_hash: H256::zero(),
+                eth_block: 0,
+                eth_block_index: None,
+            },
+            op: ZkSyncOp::FullExit(Box::new(FullExitOp {
+                priority_op: full_exit,
+                withdraw_amount: None,
+                creator_account_id: None,
+                creator_address: None,
+                serial_id: None,
+                content_hash: None,
+            })),
+            block_index: 0,
+            created_at: Utc::now(),
+        }))
+    }
+
+    /// Creates a mock transaction received at the provided time.
+    fn mock_tx(received_at: DateTime<Utc>) -> ExecutedOperations {
+        let tx = ZkSyncTx::Transfer(Box::new(Transfer::new(
+            AccountId(0),
+            Address::default(),
+            Address::default(),
+            TokenId(0),
+            100u64.into(),
+            100u64.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        )));
+        ExecutedOperations::Tx(Box::new(ExecutedTx {
+            signed_tx: SignedZkSyncTx {
+                tx,
+                eth_sign_data: None,
+                created_at: received_at,
+            },
+            success: true,
+            op: None,
+            fail_reason: None,
+            block_index: None,
+            created_at: Utc::now(),
+            batch_id: None,
+        }))
+    }
+
+    fn add_operation(block: &mut PendingBlock, operation: ExecutedOperations) {
+        block.add_successful_execution(1, Vec::new(), None, operation);
+    }
+
+    /// Checks that empty blocks are never sealed early.
+    #[test]
+    fn empty_block_is_not_sealed() {
+        let triggers = triggers(10, Some(Duration::from_secs(1)));
+        triggers.latest_eth_block.set(100);
+        triggers.seal_request.request();
+
+        let mut block = pending_block();
+        block.earliest_priority_op_deadline = Some(100);
+        block.oldest_tx_received_at = Some(Utc::now() - chrono::Duration::hours(1));
+
+        assert_eq!(triggers.check(&block, Utc::now()), None);
+        // Request is discarded.
+        assert!(!triggers.seal_request.take());
+    }
+
+    /// Checks that operator request seals the block only once.
+    #[test]
+    fn operator_request() {
+        let triggers = triggers(0, None);
+        let mut block = pending_block();
+        add_operation(&mut block, mock_tx(Utc::now()));
+
+        assert_eq!(triggers.check(&block, Utc::now()), None);
+        triggers.seal_request.request();
+        assert_eq!(
+            triggers.check(&block, Utc::now()),
+            Some(EarlySealReason::OperatorRequest)
+        );
+        assert_eq!(triggers.check(&block, Utc::now()), None);
+    }
+
+    /// Checks that the block is sealed once a priority operation approaches its deadline.
+    #[test]
+    fn priority_op_deadline() {
+        let triggers = triggers(10, None);
+        let mut block = pending_block();
+        add_operation(&mut block, mock_priority_op(200));
+        add_operation(&mut block, mock_priority_op(100));
+        assert_eq!(block.earliest_priority_op_deadline, Some(100));
+
+        // Ethereum block is not known yet.
+        assert_eq!(triggers.check(&block, Utc::now()), None);
+
+        triggers.latest_eth_block.set(90);
+        assert_eq!(triggers.check(&block, Utc::now()), None);
+
+        triggers.latest_eth_block.set(91);
+        assert_eq!(
+            triggers.check(&block, Utc::now()),
+            Some(EarlySealReason::PriorityOpDeadline)
+        );
+
+        // Disabled trigger.
+        let triggers = EarlySealTriggers {
+            latest_eth_block: triggers.latest_eth_block.clone(),
+            ..self::triggers(0, None)
+        };
+        assert_eq!(triggers.check(&block, Utc::now()), None);
+    }
+
+    /// Checks that the block is sealed once the oldest transaction is too old.
+    #[test]
+    fn max_tx_age() {
+        let triggers = triggers(0, Some(Duration::from_secs(60)));
+        let mut block = pending_block();
+        let received_at = Utc::now();
+        add_operation(
+            &mut block,
+            mock_tx(received_at + chrono::Duration::seconds(30)),
+        );
+        add_operation(&mut block, mock_tx(received_at));
+        assert_eq!(block.oldest_tx_received_at, Some(received_at));
+
+        assert_eq!(
+            triggers.check(&block, received_at + chrono::Duration::seconds(59)),
+            None
+        );
+        assert_eq!(
+            triggers.check(&block, received_at + chrono::Duration::seconds(60)),
+            Some(EarlySealReason::MaxTxAge)
+        );
+
+        // Disabled trigger.
+        let triggers = self::triggers(0, None);
+        assert_eq!(
+            triggers.check(&block, received_at + chrono::Duration::hours(1)),
+            None
+        );
+    }
+}
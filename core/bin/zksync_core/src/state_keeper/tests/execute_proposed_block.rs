@@ -1,4 +1,5 @@
 use chrono::Utc;
+use futures::StreamExt;
 use zksync_mempool::ProposedBlock;
 use zksync_types::{
     mempool::SignedTxVariant, AccountId, BlockNumber, Nonce, SignedZkSyncTx, TokenId, Transfer,
@@ -6,6 +7,7 @@ use zksync_types::{
 };
 
 use super::utils::*;
+use crate::state_keeper::{ChunkPacking, CommitRequest};
 
 /// Checks if executing a proposed_block with just enough chunks is done correctly
 /// and checks if number of chunks left is correct after each operation.
@@ -168,6 +170,60 @@ async fn few_chunks() {
     tester.assert_pending().await;
 }
 
+/// Checks that the transaction fitting into the spare chunks is moved ahead of the deferred one
+/// once the packing is enabled.
+#[tokio::test]
+async fn chunk_packing() {
+    for lookahead in [0, 1] {
+        let mut tester = StateKeeperTester::new(8, 3, 3);
+        tester
+            .state_keeper
+            .set_chunk_packing(ChunkPacking { lookahead });
+        let first_withdraw = create_account_and_withdrawal(
+            &mut tester,
+            TokenId(0),
+            AccountId(1),
+            200u32,
+            145u32,
+            Default::default(),
+        );
+        let second_withdraw = create_account_and_withdrawal(
+            &mut tester,
+            TokenId(0),
+            AccountId(2),
+            200u32,
+            145u32,
+            Default::default(),
+        );
+        let transfer =
+            create_account_and_transfer(&mut tester, TokenId(0), AccountId(3), 200u32, 145u32);
+        let proposed_block = ProposedBlock {
+            txs: vec![
+                SignedTxVariant::Tx(first_withdraw),
+                SignedTxVariant::Tx(second_withdraw),
+                SignedTxVariant::Tx(transfer),
+            ],
+            priority_ops: Vec::new(),
+        };
+        tester
+            .state_keeper
+            .execute_proposed_block(proposed_block)
+            .await;
+
+        let expected_txs = if lookahead == 0 { 1 } else { 2 };
+        tester
+            .assert_sealed_with(|block| {
+                assert_eq!(block.block_transactions.len(), expected_txs);
+            })
+            .await;
+        tester
+            .assert_pending_with(|block| {
+                assert_eq!(block.success_operations.len(), 3 - expected_txs);
+            })
+            .await;
+    }
+}
+
 /// Checks if executing a proposed_block is done correctly
 /// max_iterations == 0, so the block should get sealed, not stored.
 #[tokio::test]
@@ -565,3 +621,43 @@ async fn transfers_with_different_timestamps() {
         })
         .await;
 }
+
+/// Checks that the pending block is sealed and the committer is flushed on the shutdown.
+#[tokio::test]
+async fn shut_down() {
+    let mut tester = StateKeeperTester::new(6, 3, 3);
+    apply_single_transfer(&mut tester).await;
+    tester.assert_pending().await;
+
+    // Root hashes are "calculated" in place of the root hash calculator.
+    let mut root_hash_queue = tester.state_keeper.root_hash_queue.clone();
+    tokio::spawn(async move {
+        loop {
+            root_hash_queue.pop().await;
+            root_hash_queue.finish_job();
+        }
+    });
+
+    let StateKeeperTester {
+        state_keeper,
+        response_rx,
+        ..
+    } = &mut tester;
+    let committer = async {
+        assert!(matches!(
+            response_rx.next().await,
+            Some(CommitRequest::PendingBlock(_))
+        ));
+        assert!(matches!(
+            response_rx.next().await,
+            Some(CommitRequest::SealIncompleteBlock(_))
+        ));
+        match response_rx.next().await {
+            Some(CommitRequest::Flush(sender)) => sender.send(()).unwrap(),
+            _ => panic!("Expected the committer to be flushed"),
+        }
+    };
+    futures::join!(state_keeper.shut_down(), committer);
+    assert_eq!(tester.state_keeper.pending_block.number, BlockNumber(2));
+    tester.assert_empty().await;
+}
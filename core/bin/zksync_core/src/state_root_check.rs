@@ -0,0 +1,153 @@
+//! Periodic cross-check of the last executed block with the contract.
+//!
+//! The contract stores the hash of the info of every block, which includes its state root.
+//! The task compares the hash of the last block executed on the contract with the hash of the
+//! same block computed from the local data. A mismatch means a divergence of the state somewhere
+//! in the pipeline, so the circuit breaker is tripped to halt the block production.
+
+// Built-in deps
+use std::time::Duration;
+// External uses
+use anyhow::format_err;
+use tokio::{task::JoinHandle, time};
+use web3::{contract::Options, types::U256};
+// Workspace deps
+use zksync_eth_client::EthereumGateway;
+use zksync_storage::ConnectionPool;
+use zksync_types::{aggregated_operations::stored_block_info_hash, BlockNumber, H256};
+
+/// Source of the circuit breaker trips made by the check.
+const TRIP_SOURCE: &str = "state_root_check";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckResult {
+    Matched,
+    /// The block is not stored locally yet.
+    Skipped,
+    Mismatched {
+        local: H256,
+        onchain: H256,
+    },
+}
+
+async fn check_last_executed_block(
+    pool: &ConnectionPool,
+    eth_gateway: &EthereumGateway,
+) -> anyhow::Result<(BlockNumber, CheckResult)> {
+    let last_executed_block: U256 = eth_gateway
+        .call_main_contract_function("totalBlocksExecuted", (), None, Options::default(), None)
+        .await
+        .map_err(|e| format_err!("Failed to query contract totalBlocksExecuted: {}", e))?;
+    let block_number = BlockNumber(last_executed_block.as_u32());
+    let onchain: H256 = eth_gateway
+        .call_main_contract_function(
+            "storedBlockHashes",
+            U256::from(*block_number),
+            None,
+            Options::default(),
+            None,
+        )
+        .await
+        .map_err(|e| format_err!("Failed to query contract storedBlockHashes: {}", e))?;
+
+    let mut storage = pool.access_storage().await?;
+    let block = storage
+        .chain()
+        .block_schema()
+        .get_block(block_number)
+        .await?;
+    let result = match block {
+        Some(block) => compare_hashes(stored_block_info_hash(&block), onchain),
+        None => CheckResult::Skipped,
+    };
+    Ok((block_number, result))
+}
+
+fn compare_hashes(local: H256, onchain: H256) -> CheckResult {
+    if local == onchain {
+        CheckResult::Matched
+    } else {
+        CheckResult::Mismatched { local, onchain }
+    }
+}
+
+#[must_use]
+pub fn run_state_root_check(
+    pool: ConnectionPool,
+    eth_gateway: EthereumGateway,
+    interval: Duration,
+) -> JoinHandle<()> {
+    let mut timer = time::interval(interval);
+
+    tokio::spawn(async move {
+        // Number of the block the breaker was already tripped for.
+        let mut tripped_for = None;
+        loop {
+            timer.tick().await;
+
+            let (block_number, result) = match check_last_executed_block(&pool, &eth_gateway).await
+            {
+                Ok(result) => result,
+                Err(err) => {
+                    vlog::warn!("Failed to cross-check the state root with L1: {}", err);
+                    continue;
+                }
+            };
+            metrics::gauge!(
+                "state_root_check.mismatch",
+                matches!(result, CheckResult::Mismatched { .. }) as u8 as f64
+            );
+
+            match result {
+                CheckResult::Matched => {
+                    vlog::debug!("State of block {} matches the contract", block_number);
+                }
+                CheckResult::Skipped => {
+                    vlog::warn!(
+                        "Block {} executed on the contract is not found in storage",
+                        block_number
+                    );
+                }
+                CheckResult::Mismatched { .. } if tripped_for == Some(block_number) => {}
+                CheckResult::Mismatched { local, onchain } => {
+                    let reason = format!(
+                        "Hash of block {} stored on the contract {:#x} doesn't match the local one {:#x}",
+                        block_number, onchain, local
+                    );
+                    vlog::error!("{}", reason);
+                    let trip = match pool.access_storage().await {
+                        Ok(mut storage) => {
+                            storage
+                                .circuit_breaker_schema()
+                                .trip(TRIP_SOURCE, &reason)
+                                .await
+                        }
+                        Err(err) => Err(err.into()),
+                    };
+                    match trip {
+                        Ok(()) => tripped_for = Some(block_number),
+                        Err(err) => vlog::error!("Failed to trip the circuit breaker: {}", err),
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_comparison() {
+        let hash = H256::repeat_byte(1);
+        assert_eq!(compare_hashes(hash, hash), CheckResult::Matched);
+        assert_eq!(
+            compare_hashes(hash, H256::zero()),
+            CheckResult::Mismatched {
+                local: hash,
+                onchain: H256::zero()
+            }
+        );
+    }
+}
@@ -0,0 +1,579 @@
+//! The treasury task converts the fees collected in the fee account into the target token.
+//!
+//! Once the value of the fee account balance in some token exceeds the configured threshold,
+//! the task either swaps it with the counter-order provided by the market maker (`swap` route)
+//! or withdraws it to the L1 router contract that exchanges it on the DEX (`l1` route).
+//! Every conversion is recorded in the database together with the expected result before its
+//! transaction is sent, and the record is updated once the block with the transaction is sealed.
+
+// Built-in uses
+use std::str::FromStr;
+
+// External uses
+use chrono::Utc;
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
+use num::{rational::Ratio, BigUint, FromPrimitive, Zero};
+use serde::Serialize;
+use tokio::{task::JoinHandle, time};
+
+// Workspace uses
+use zksync_config::configs::treasury::{ConversionRoute, TreasuryConfig};
+use zksync_crypto::{
+    ff::PrimeField,
+    franklin_crypto::{alt_babyjubjub::fs::FsRepr, bellman::PrimeFieldRepr},
+    params::MIN_NFT_TOKEN_ID,
+    priv_key_from_fs, Fs, PrivateKey,
+};
+use zksync_mempool::MempoolTransactionRequest;
+use zksync_storage::{
+    treasury::{records::NewTreasuryConversion, CONVERSION_COMMITTED, CONVERSION_FAILED},
+    BigDecimal, ConnectionPool, StorageProcessor,
+};
+use zksync_types::{
+    helpers::{closest_packable_token_amount, is_token_amount_packable},
+    tx::{Order, Swap, TimeRange, TxHash, Withdraw},
+    AccountId, Address, Nonce, PubKeyHash, SignedZkSyncTx, Token, TokenId, TokenLike, ZkSyncTx,
+};
+
+/// Name of the swap route in the audit records.
+const SWAP_ROUTE: &str = "swap";
+/// Name of the L1 route in the audit records.
+const L1_ROUTE: &str = "l1";
+/// Fail reason of the conversions which transactions were not accepted by the mempool.
+const NOT_SENT_REASON: &str = "Transaction was not sent to the mempool";
+
+/// Request of the counter-order sent to the market maker.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QuoteRequest {
+    /// Token the market maker has to buy.
+    token_buy: TokenId,
+    /// Token the market maker has to sell.
+    token_sell: TokenId,
+    /// Amount of tokens the market maker has to buy.
+    #[serde(with = "zksync_utils::BigUintSerdeAsRadix10Str")]
+    amount: BigUint,
+    /// Address receiving the tokens sold by the market maker.
+    recipient: Address,
+}
+
+fn read_private_key(private_key: &str) -> anyhow::Result<PrivateKey> {
+    let bytes = hex::decode(private_key.trim_start_matches("0x"))?;
+    let mut fs_repr = FsRepr::default();
+    fs_repr.read_be(bytes.as_slice())?;
+    let fs = Fs::from_repr(fs_repr)?;
+    Ok(priv_key_from_fs(fs))
+}
+
+/// Value of the token amount in USD.
+fn amount_usd(amount: &BigUint, token: &Token, usd_price: &Ratio<BigUint>) -> Ratio<BigUint> {
+    Ratio::from(amount.clone()) * usd_price / BigUint::from(10u32).pow(u32::from(token.decimals))
+}
+
+/// Amount of the target token equivalent to the amount of the sold token at the market price,
+/// reduced by the maximum allowed slippage.
+fn min_expected_amount(
+    amount: &BigUint,
+    token: &Token,
+    token_price: &Ratio<BigUint>,
+    target: &Token,
+    target_price: &Ratio<BigUint>,
+    max_slippage_percent: f64,
+) -> BigUint {
+    if target_price.is_zero() {
+        return BigUint::zero();
+    }
+    // Slippage is applied with the precision of the hundredth of the percent.
+    let slippage_bps = (max_slippage_percent * 100.0).round().clamp(0.0, 10_000.0) as u32;
+    let value = amount_usd(amount, token, token_price)
+        * BigUint::from(10u32).pow(u32::from(target.decimals))
+        / target_price
+        * Ratio::new(
+            BigUint::from(10_000 - slippage_bps),
+            BigUint::from(10_000u32),
+        );
+    value.to_integer()
+}
+
+/// Checks that the counter-order provided by the market maker matches the requested conversion.
+fn validate_counter_order(
+    order: &Order,
+    token_sell: TokenId,
+    token_buy: TokenId,
+    amount: &BigUint,
+    min_amount: &BigUint,
+) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        order.token_sell == token_buy && order.token_buy == token_sell,
+        "Counter-order has the wrong tokens"
+    );
+    anyhow::ensure!(
+        &order.amount >= min_amount,
+        "Counter-order amount {} is less than the minimal expected {}",
+        order.amount,
+        min_amount
+    );
+    anyhow::ensure!(
+        is_token_amount_packable(&order.amount),
+        "Counter-order amount is not packable"
+    );
+    // The market maker must accept the full amount for the price it offers.
+    let sold = &order.amount * &order.price.1;
+    let bought = amount * &order.price.0;
+    anyhow::ensure!(
+        sold <= bought,
+        "Counter-order price doesn't allow to buy the whole amount"
+    );
+    anyhow::ensure!(
+        order.time_range.check_correctness()
+            && order.time_range.is_valid(Utc::now().timestamp() as u64),
+        "Counter-order is expired"
+    );
+    Ok(())
+}
+
+fn to_decimal(amount: &BigUint) -> BigDecimal {
+    BigDecimal::from_str(&amount.to_string()).unwrap()
+}
+
+struct Treasury {
+    pool: ConnectionPool,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    config: TreasuryConfig,
+    fee_account_address: Address,
+    private_key: PrivateKey,
+    http_client: reqwest::Client,
+}
+
+impl Treasury {
+    /// Updates the status of the conversions which transactions were executed in the sealed blocks.
+    /// Transactions of the pending block are not taken into account, since the state of the fee
+    /// account is loaded from the sealed blocks only.
+    async fn update_pending_conversions(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<usize> {
+        let pending = storage.treasury_schema().load_pending_conversions().await?;
+        let last_sealed_block = storage
+            .chain()
+            .block_schema()
+            .get_last_saved_block()
+            .await?;
+        let mut still_pending = 0;
+        for conversion in pending {
+            let receipt = storage
+                .chain()
+                .operations_ext_schema()
+                .tx_receipt(&conversion.tx_hash)
+                .await?;
+            match receipt {
+                Some(receipt) if receipt.block_number > i64::from(*last_sealed_block) => {
+                    still_pending += 1;
+                }
+                Some(receipt) if receipt.success => {
+                    storage
+                        .treasury_schema()
+                        .update_conversion_status(conversion.id, CONVERSION_COMMITTED, None)
+                        .await?;
+                    vlog::info!("Treasury conversion {} is committed", conversion.id);
+                }
+                Some(receipt) => {
+                    storage
+                        .treasury_schema()
+                        .update_conversion_status(
+                            conversion.id,
+                            CONVERSION_FAILED,
+                            receipt.fail_reason.clone(),
+                        )
+                        .await?;
+                    metrics::increment_counter!("treasury.failed_conversions");
+                    vlog::warn!(
+                        "Treasury conversion {} has failed: {:?}",
+                        conversion.id,
+                        receipt.fail_reason
+                    );
+                }
+                None => {
+                    let tx_hash = TxHash::from_slice(&conversion.tx_hash)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid conversion tx hash"))?;
+                    let is_queued = storage
+                        .chain()
+                        .mempool_schema()
+                        .contains_tx(tx_hash)
+                        .await?;
+                    if is_queued {
+                        still_pending += 1;
+                        continue;
+                    }
+                    // The server has stopped after the conversion was stored, but before
+                    // its transaction was sent.
+                    storage
+                        .treasury_schema()
+                        .update_conversion_status(
+                            conversion.id,
+                            CONVERSION_FAILED,
+                            Some(NOT_SENT_REASON.to_string()),
+                        )
+                        .await?;
+                    vlog::warn!(
+                        "Treasury conversion {} was never sent to the mempool",
+                        conversion.id
+                    );
+                }
+            }
+        }
+        Ok(still_pending)
+    }
+
+    async fn request_counter_order(&self, request: &QuoteRequest) -> anyhow::Result<Order> {
+        let order = self
+            .http_client
+            .post(&self.config.swap_quote_url)
+            .json(request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(order)
+    }
+
+    /// Builds the transaction converting the amount of the token, returns it together with
+    /// the route-specific details of the conversion.
+    #[allow(clippy::too_many_arguments)]
+    async fn build_conversion_tx(
+        &self,
+        account_id: AccountId,
+        nonce: Nonce,
+        token: &Token,
+        amount: &BigUint,
+        target: &Token,
+        min_amount: &BigUint,
+    ) -> anyhow::Result<(ZkSyncTx, serde_json::Value)> {
+        match self.config.route {
+            ConversionRoute::Swap => {
+                let request = QuoteRequest {
+                    token_buy: token.id,
+                    token_sell: target.id,
+                    amount: amount.clone(),
+                    recipient: self.fee_account_address,
+                };
+                let counter_order = self.request_counter_order(&request).await?;
+                validate_counter_order(&counter_order, token.id, target.id, amount, min_amount)?;
+
+                let order = Order::new_signed(
+                    account_id,
+                    self.fee_account_address,
+                    nonce,
+                    token.id,
+                    target.id,
+                    (amount.clone(), min_amount.clone()),
+                    amount.clone(),
+                    TimeRange::default(),
+                    &self.private_key,
+                )?;
+                let amounts = (amount.clone(), counter_order.amount.clone());
+                let details = serde_json::json!({ "counterOrder": counter_order });
+                let swap = Swap::new_signed(
+                    account_id,
+                    self.fee_account_address,
+                    nonce,
+                    (order, counter_order),
+                    amounts,
+                    BigUint::zero(),
+                    token.id,
+                    &self.private_key,
+                )?;
+                Ok((ZkSyncTx::Swap(Box::new(swap)), details))
+            }
+            ConversionRoute::L1 => {
+                let withdraw = Withdraw::new_signed(
+                    account_id,
+                    self.fee_account_address,
+                    self.config.l1_router_address,
+                    token.id,
+                    amount.clone(),
+                    BigUint::zero(),
+                    nonce,
+                    TimeRange::default(),
+                    &self.private_key,
+                )?;
+                let details = serde_json::json!({ "router": self.config.l1_router_address });
+                Ok((ZkSyncTx::Withdraw(Box::new(withdraw)), details))
+            }
+        }
+    }
+
+    async fn send_tx(&mut self, tx: SignedZkSyncTx) -> anyhow::Result<()> {
+        let (sender, receiver) = oneshot::channel();
+        let item = MempoolTransactionRequest::NewTxsBatch(vec![tx], vec![], sender);
+        self.mempool_tx_sender.send(item).await?;
+        receiver.await??;
+        Ok(())
+    }
+
+    async fn run_iteration(&mut self) -> anyhow::Result<()> {
+        let mut storage = self.pool.access_storage().await?;
+
+        // New conversions are created only when the previous ones are finished,
+        // so the nonce of the fee account is always known.
+        if self.update_pending_conversions(&mut storage).await? > 0 {
+            return Ok(());
+        }
+
+        let account_id = storage
+            .chain()
+            .account_schema()
+            .account_id_by_address(self.fee_account_address)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Fee account is not created yet"))?;
+        let account = storage
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(account_id)
+            .await?
+            .1
+            .ok_or_else(|| anyhow::anyhow!("Fee account state is not found"))?;
+        anyhow::ensure!(
+            account.pub_key_hash == PubKeyHash::from_privkey(&self.private_key),
+            "Signing key of the fee account doesn't match the treasury private key"
+        );
+
+        let target = storage
+            .tokens_schema()
+            .get_token(TokenLike::parse(&self.config.target_token))
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Target token is not found"))?;
+        let target_price = storage
+            .tokens_schema()
+            .get_historical_ticker_price(target.id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Price of the target token is unknown"))?
+            .usd_price;
+        // Threshold is applied with the precision of the cent.
+        let threshold = Ratio::new(
+            BigUint::from_f64((self.config.min_conversion_usd * 100.0).round()).unwrap_or_default(),
+            BigUint::from(100u32),
+        );
+
+        let mut conversions = Vec::new();
+        for (token_id, balance) in account.get_nonzero_balances() {
+            if token_id == target.id
+                || self.config.excluded_tokens.contains(&token_id)
+                || *token_id >= MIN_NFT_TOKEN_ID
+            {
+                continue;
+            }
+            let token = match storage.tokens_schema().get_token(token_id.into()).await? {
+                Some(token) => token,
+                None => continue,
+            };
+            let price = match storage
+                .tokens_schema()
+                .get_historical_ticker_price(token_id)
+                .await?
+            {
+                Some(price) => price.usd_price,
+                None => continue,
+            };
+            let amount = closest_packable_token_amount(&balance.0);
+            if amount.is_zero() || amount_usd(&amount, &token, &price) < threshold {
+                continue;
+            }
+
+            let min_amount = min_expected_amount(
+                &amount,
+                &token,
+                &price,
+                &target,
+                &target_price,
+                self.config.max_slippage_percent,
+            );
+            conversions.push((token, amount, min_amount));
+        }
+        // The transactions are sent by the mempool handler using the same connection quota,
+        // so the connection is released before sending them.
+        drop(storage);
+
+        let mut nonce = account.nonce;
+        for (token, amount, min_amount) in conversions {
+            let (tx, details) = match self
+                .build_conversion_tx(account_id, nonce, &token, &amount, &target, &min_amount)
+                .await
+            {
+                Ok(result) => result,
+                Err(err) => {
+                    vlog::warn!("Can't build the conversion of {}: {}", token.symbol, err);
+                    continue;
+                }
+            };
+            let tx = SignedZkSyncTx {
+                tx,
+                eth_sign_data: None,
+                created_at: Utc::now(),
+            };
+            let tx_hash = tx.hash();
+
+            // Conversion is stored before its transaction is sent, so that it's never lost.
+            let route = match self.config.route {
+                ConversionRoute::Swap => SWAP_ROUTE,
+                ConversionRoute::L1 => L1_ROUTE,
+            };
+            let id = self
+                .pool
+                .access_storage()
+                .await?
+                .treasury_schema()
+                .store_conversion(NewTreasuryConversion {
+                    token_id: *token.id as i32,
+                    amount: to_decimal(&amount),
+                    target_token_id: *target.id as i32,
+                    expected_amount: to_decimal(&min_amount),
+                    route: route.to_string(),
+                    tx_hash: tx_hash.as_ref().to_vec(),
+                    details,
+                })
+                .await?;
+            if let Err(err) = self.send_tx(tx).await {
+                self.pool
+                    .access_storage()
+                    .await?
+                    .treasury_schema()
+                    .update_conversion_status(id, CONVERSION_FAILED, Some(err.to_string()))
+                    .await?;
+                return Err(err);
+            }
+            *nonce += 1;
+            metrics::increment_counter!("treasury.conversions", "route" => route);
+            vlog::info!(
+                "Treasury conversion {}: {} {} to {} via {}, tx {}",
+                id,
+                amount,
+                token.symbol,
+                target.symbol,
+                route,
+                tx_hash
+            );
+        }
+        Ok(())
+    }
+}
+
+#[must_use]
+pub fn run_treasury_task(
+    pool: ConnectionPool,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    config: TreasuryConfig,
+    fee_account_address: Address,
+) -> JoinHandle<()> {
+    let private_key = read_private_key(&config.fee_account_private_key)
+        .expect("TREASURY_FEE_ACCOUNT_PRIVATE_KEY is not a valid private key");
+    let mut timer = time::interval(config.check_interval());
+    let mut treasury = Treasury {
+        pool,
+        mempool_tx_sender,
+        config,
+        fee_account_address,
+        private_key,
+        http_client: reqwest::Client::new(),
+    };
+
+    tokio::spawn(async move {
+        loop {
+            timer.tick().await;
+            if let Err(err) = treasury.run_iteration().await {
+                vlog::error!("Treasury iteration has failed: {}", err);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::TokenKind;
+
+    fn token(id: u32, decimals: u8) -> Token {
+        Token::new(
+            TokenId(id),
+            Address::zero(),
+            &format!("TOKEN{}", id),
+            decimals,
+            TokenKind::ERC20,
+        )
+    }
+
+    fn usd(value: u32) -> Ratio<BigUint> {
+        Ratio::from(BigUint::from(value))
+    }
+
+    #[test]
+    fn expected_amount() {
+        let token = token(1, 6);
+        let target = token_eth();
+        // 3000 tokens with 6 decimals for 1 USD to ETH for 1500 USD is 2 ETH.
+        let amount = BigUint::from(3_000_000_000u64);
+        let two_eth = BigUint::from(2_000_000_000_000_000_000u128);
+        assert_eq!(
+            amount_usd(&amount, &token, &usd(1)).to_integer(),
+            BigUint::from(3000u32)
+        );
+        assert_eq!(
+            min_expected_amount(&amount, &token, &usd(1), &target, &usd(1500), 0.0),
+            two_eth
+        );
+        assert_eq!(
+            min_expected_amount(&amount, &token, &usd(1), &target, &usd(1500), 1.5),
+            BigUint::from(1_970_000_000_000_000_000u128)
+        );
+        assert!(min_expected_amount(&amount, &token, &usd(1), &target, &usd(0), 1.0).is_zero());
+    }
+
+    fn token_eth() -> Token {
+        token(0, 18)
+    }
+
+    fn counter_order(amount: u64, price: (u64, u64)) -> Order {
+        let private_key = priv_key_from_fs(Fs::from_str("42").unwrap());
+        Order::new_signed(
+            AccountId(2),
+            Address::zero(),
+            Nonce(0),
+            TokenId(1),
+            TokenId(0),
+            (BigUint::from(price.0), BigUint::from(price.1)),
+            BigUint::from(amount),
+            TimeRange::default(),
+            &private_key,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn counter_order_validation() {
+        let amount = BigUint::from(1000u32);
+        let min_amount = BigUint::from(500u32);
+        let check = |order: &Order| {
+            validate_counter_order(order, TokenId(1), TokenId(0), &amount, &min_amount)
+        };
+
+        // Sells 600 of the target token for 1000 of the fee token.
+        assert!(check(&counter_order(600, (600, 1000))).is_ok());
+        // Amount is less than expected.
+        assert!(check(&counter_order(400, (400, 1000))).is_err());
+        // Price doesn't allow to buy the whole amount.
+        assert!(check(&counter_order(600, (600, 900))).is_err());
+        // Wrong tokens.
+        assert!(validate_counter_order(
+            &counter_order(600, (600, 1000)),
+            TokenId(2),
+            TokenId(0),
+            &amount,
+            &min_amount
+        )
+        .is_err());
+    }
+}
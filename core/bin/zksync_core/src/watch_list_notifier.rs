@@ -0,0 +1,169 @@
+//! Reports the incoming transfers and deposits of the watched addresses to the webhooks.
+//!
+//! Exchanges register their deposit addresses in the watch lists via the private API, up to
+//! the hundreds of thousands per list. Once new blocks are created, the operations of all the
+//! addresses of the list are loaded with a single query and posted to the webhook of the list
+//! in one notification per range of blocks. The range is marked as reported only once the hook
+//! accepts it, so the hook may receive the same operations more than once.
+
+// Built-in deps
+use std::time::Duration;
+// External uses
+use anyhow::format_err;
+use num::BigUint;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+// Workspace uses
+use zksync_storage::{
+    watch_lists::records::{StorageWatchList, StorageWatchedOperation},
+    ConnectionPool,
+};
+use zksync_types::{tx::TxHash, Address, BlockNumber, TokenId};
+use zksync_utils::big_decimal_to_ratio;
+
+/// Maximum number of blocks reported in a single notification.
+const MAX_BLOCKS_PER_NOTIFICATION: u32 = 100;
+/// Timeout of the webhook requests.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WatchedOperation {
+    kind: String,
+    tx_hash: TxHash,
+    block_number: BlockNumber,
+    from: Address,
+    to: Address,
+    token: TokenId,
+    #[serde(with = "zksync_utils::BigUintSerdeAsRadix10Str")]
+    amount: BigUint,
+}
+
+impl WatchedOperation {
+    fn from_record(record: StorageWatchedOperation) -> anyhow::Result<Self> {
+        let tx_hash = TxHash::from_slice(&record.tx_hash)
+            .ok_or_else(|| format_err!("Incorrect tx hash in the database"))?;
+        let amount = big_decimal_to_ratio(&record.amount)?.to_integer();
+        Ok(Self {
+            kind: record.kind,
+            tx_hash,
+            block_number: BlockNumber(record.block_number as u32),
+            from: Address::from_slice(&record.from_account),
+            to: Address::from_slice(&record.to_account),
+            token: TokenId(record.token_id as u32),
+            amount,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WatchListNotification {
+    watch_list: String,
+    /// Operations of the blocks after this one are reported.
+    from_block: BlockNumber,
+    /// Last reported block.
+    to_block: BlockNumber,
+    operations: Vec<WatchedOperation>,
+}
+
+struct WatchListNotifier {
+    connection_pool: ConnectionPool,
+    client: reqwest::Client,
+    poll_interval: Duration,
+}
+
+impl WatchListNotifier {
+    async fn notify(
+        &self,
+        watch_list: &StorageWatchList,
+        notification: &WatchListNotification,
+    ) -> anyhow::Result<()> {
+        let webhook_url = reqwest::Url::parse(&watch_list.webhook_url)?;
+        self.client
+            .post(webhook_url)
+            .timeout(WEBHOOK_TIMEOUT)
+            .json(notification)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    async fn run_iteration(&mut self) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let last_block = storage
+            .chain()
+            .block_schema()
+            .get_last_saved_block()
+            .await?;
+        let watch_lists = storage.watch_lists_schema().load_watch_lists().await?;
+
+        for watch_list in watch_lists {
+            let from_block = BlockNumber(watch_list.last_block as u32);
+            if from_block >= last_block {
+                continue;
+            }
+            let to_block = BlockNumber(
+                (*last_block).min(from_block.saturating_add(MAX_BLOCKS_PER_NOTIFICATION)),
+            );
+            let operations = storage
+                .watch_lists_schema()
+                .load_watched_operations(watch_list.id, from_block, to_block)
+                .await?
+                .into_iter()
+                .map(WatchedOperation::from_record)
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            if !operations.is_empty() {
+                let notification = WatchListNotification {
+                    watch_list: watch_list.name.clone(),
+                    from_block,
+                    to_block,
+                    operations,
+                };
+                // The failed hook is retried on the next iteration, other lists are not delayed.
+                if let Err(err) = self.notify(&watch_list, &notification).await {
+                    vlog::warn!(
+                        "Can't notify the watch list {} about the blocks {}..={}: {}",
+                        watch_list.name,
+                        *from_block + 1,
+                        to_block,
+                        err
+                    );
+                    metrics::increment_counter!("watch_list_notifier.failed_notifications");
+                    continue;
+                }
+                metrics::increment_counter!("watch_list_notifier.notifications");
+            }
+            storage
+                .watch_lists_schema()
+                .set_last_block(watch_list.id, to_block)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn run(&mut self) {
+        let mut timer = tokio::time::interval(self.poll_interval);
+        loop {
+            timer.tick().await;
+            if let Err(err) = self.run_iteration().await {
+                vlog::error!("Watch list notifier iteration has failed: {}", err);
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn run_watch_list_notifier(
+    connection_pool: ConnectionPool,
+    poll_interval: Duration,
+) -> JoinHandle<()> {
+    let mut notifier = WatchListNotifier {
+        connection_pool,
+        client: reqwest::Client::new(),
+        poll_interval,
+    };
+    tokio::spawn(async move { notifier.run().await })
+}
@@ -0,0 +1,195 @@
+//! Delivers the withdrawals with the `unwrapWeth` flag as native ETH.
+//!
+//! Such withdrawals send the WETH to the unwrapper contract. Once the block is executed on L1,
+//! the withdrawal is recorded as pending and the server calls the unwrapper, which converts the
+//! withdrawn amount and sends the ETH to the owner of the L2 account. The contract delivers each
+//! withdrawal at most once. The signed transaction is stored along with the operator nonce reserved
+//! for it, and is resent with the same nonce until it's mined, so that the reserved nonce never
+//! becomes a gap blocking `eth_sender`.
+
+// Built-in deps
+use std::time::Duration;
+// External uses
+use anyhow::format_err;
+use ethabi::{Contract, Token};
+use num::{bigint::ToBigInt, ToPrimitive};
+use tokio::task::JoinHandle;
+use web3::{contract::Options, types::U256};
+// Workspace uses
+use zksync_contracts::weth_unwrapper_contract;
+use zksync_eth_client::{EthereumGateway, SignedCallResult};
+use zksync_storage::{weth_unwraps::records::StorageWethUnwrap, ConnectionPool, StorageProcessor};
+use zksync_types::{tx::TxHash, Address, BlockNumber, H256};
+
+/// Gas limit of the unwrapping, including the withdrawal of the pending balance.
+const UNWRAP_GAS_LIMIT: u64 = 200_000;
+
+struct WethUnwrapper {
+    connection_pool: ConnectionPool,
+    eth_gateway: EthereumGateway,
+    contract: Contract,
+    unwrapper_addr: Address,
+    poll_interval: Duration,
+    /// Last executed block which withdrawals are recorded.
+    last_block: BlockNumber,
+}
+
+impl WethUnwrapper {
+    async fn sign_unwrap(
+        &self,
+        unwrap: &StorageWethUnwrap,
+        nonce: i64,
+    ) -> anyhow::Result<SignedCallResult> {
+        let amount = unwrap
+            .amount
+            .to_bigint()
+            .and_then(|amount| amount.to_u128())
+            .ok_or_else(|| format_err!("Withdrawal amount {} overflows", unwrap.amount))?;
+        let data = self.contract.function("unwrap")?.encode_input(&[
+            Token::FixedBytes(unwrap.tx_hash.clone()),
+            Token::Address(Address::from_slice(&unwrap.recipient)),
+            Token::Uint(U256::from(amount)),
+        ])?;
+        self.eth_gateway
+            .sign_prepared_tx_for_addr(
+                data,
+                self.unwrapper_addr,
+                Options::with(|f| {
+                    f.gas = Some(U256::from(UNWRAP_GAS_LIMIT));
+                    f.nonce = Some(U256::from(nonce));
+                }),
+            )
+            .await
+    }
+
+    /// Checks the signed unwrapping transactions, the ones which aren't mined yet are sent again.
+    /// Since the nonces are reserved, each of them must be mined, or `eth_sender` gets stuck.
+    async fn check_unconfirmed_unwraps(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<()> {
+        let unwraps = storage
+            .weth_unwraps_schema()
+            .load_unconfirmed_unwraps()
+            .await?;
+        for unwrap in unwraps {
+            let tx_hash = TxHash::from_slice(&unwrap.tx_hash)
+                .ok_or_else(|| format_err!("Invalid withdrawal hash"))?;
+            let (eth_tx_hash, raw_tx) = match (unwrap.eth_tx_hash, unwrap.raw_tx) {
+                (Some(eth_tx_hash), Some(raw_tx)) => (H256::from_slice(&eth_tx_hash), raw_tx),
+                _ => return Err(format_err!("Unwrapping of {} isn't stored", tx_hash)),
+            };
+
+            match self.eth_gateway.get_tx_status(eth_tx_hash, None).await? {
+                Some(status) => {
+                    if !status.success {
+                        vlog::error!(
+                            "Transaction {:#x} unwrapping the withdrawal {} has failed",
+                            eth_tx_hash,
+                            tx_hash.to_string()
+                        );
+                        metrics::increment_counter!("weth_unwrapper.failed");
+                    }
+                    storage
+                        .weth_unwraps_schema()
+                        .confirm_unwrap(&tx_hash)
+                        .await?;
+                }
+                None => {
+                    // The transaction may have been dropped from the mempool, so it's sent again
+                    // with the same nonce. An error for the transaction that is already known
+                    // to the node is expected here.
+                    if let Err(err) = self.eth_gateway.send_raw_tx(raw_tx).await {
+                        vlog::warn!(
+                            "Failed to resend the transaction {:#x} unwrapping the withdrawal {}: {}",
+                            eth_tx_hash,
+                            tx_hash.to_string(),
+                            err
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn run_iteration(&mut self) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let last_executed_block = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+        if last_executed_block > self.last_block {
+            storage
+                .weth_unwraps_schema()
+                .store_executed_unwraps(self.last_block, last_executed_block)
+                .await?;
+            self.last_block = last_executed_block;
+        }
+
+        self.check_unconfirmed_unwraps(&mut storage).await?;
+
+        let unwraps = storage.weth_unwraps_schema().load_pending_unwraps().await?;
+        for unwrap in unwraps {
+            let tx_hash = TxHash::from_slice(&unwrap.tx_hash)
+                .ok_or_else(|| format_err!("Invalid withdrawal hash"))?;
+            // The transaction is signed with the operator key, so its nonce is reserved in the
+            // counter of `eth_sender` before signing, otherwise both may use the same nonce.
+            // The nonce is only reserved along with the signed transaction, so it can be resent.
+            let mut transaction = storage.start_transaction().await?;
+            let nonce = transaction.ethereum_schema().get_next_nonce().await?;
+            let signed_tx = self.sign_unwrap(&unwrap, nonce).await?;
+            transaction
+                .weth_unwraps_schema()
+                .save_unwrap_tx(&tx_hash, nonce, signed_tx.hash, &signed_tx.raw_tx)
+                .await?;
+            transaction.commit().await?;
+
+            if let Err(err) = self.eth_gateway.send_raw_tx(signed_tx.raw_tx).await {
+                vlog::warn!(
+                    "Failed to send the transaction {:#x} unwrapping the withdrawal {}: {}",
+                    signed_tx.hash,
+                    tx_hash.to_string(),
+                    err
+                );
+                continue;
+            }
+            vlog::info!(
+                "Sent the transaction {:#x} unwrapping the withdrawal {}",
+                signed_tx.hash,
+                tx_hash.to_string()
+            );
+            metrics::increment_counter!("weth_unwrapper.sent");
+        }
+        Ok(())
+    }
+
+    async fn run(&mut self) {
+        let mut timer = tokio::time::interval(self.poll_interval);
+        loop {
+            timer.tick().await;
+            if let Err(err) = self.run_iteration().await {
+                vlog::error!("WETH unwrapper iteration has failed: {}", err);
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn run_weth_unwrapper(
+    connection_pool: ConnectionPool,
+    eth_gateway: EthereumGateway,
+    unwrapper_addr: Address,
+    poll_interval: Duration,
+) -> JoinHandle<()> {
+    let mut unwrapper = WethUnwrapper {
+        connection_pool,
+        eth_gateway,
+        contract: weth_unwrapper_contract(),
+        unwrapper_addr,
+        poll_interval,
+        last_block: BlockNumber(0),
+    };
+    tokio::spawn(async move { unwrapper.run().await })
+}
@@ -0,0 +1,83 @@
+//! Records the L1 transactions which delivered the funds of the withdrawals.
+//!
+//! Withdrawals are completed by the `ExecuteBlocks` transaction of their block. Once the block
+//! is executed on L1, the hash of this transaction is recorded for every successful withdrawal
+//! of the block, so it's returned by the receipts of the withdrawals. On start, the blocks
+//! executed before the last recorded withdrawal are skipped, and the rest of the history is
+//! recorded in chunks.
+
+// Built-in deps
+use std::time::Duration;
+// External uses
+use tokio::task::JoinHandle;
+// Workspace uses
+use zksync_storage::ConnectionPool;
+use zksync_types::BlockNumber;
+
+/// Maximum number of blocks which withdrawals are recorded in a single iteration.
+const MAX_BLOCKS_PER_ITERATION: u32 = 1000;
+
+struct WithdrawalTracker {
+    connection_pool: ConnectionPool,
+    poll_interval: Duration,
+    /// Last executed block which withdrawals are recorded.
+    last_block: Option<BlockNumber>,
+}
+
+impl WithdrawalTracker {
+    async fn run_iteration(&mut self) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let last_block = match self.last_block {
+            Some(last_block) => last_block,
+            None => {
+                storage
+                    .withdrawal_eth_txs_schema()
+                    .get_last_recorded_block()
+                    .await?
+            }
+        };
+        let last_executed_block = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+        if last_executed_block <= last_block {
+            self.last_block = Some(last_block);
+            return Ok(());
+        }
+
+        let to_block = BlockNumber(
+            (*last_executed_block).min(last_block.saturating_add(MAX_BLOCKS_PER_ITERATION)),
+        );
+        let stored = storage
+            .withdrawal_eth_txs_schema()
+            .store_executed_withdrawals(last_block, to_block)
+            .await?;
+        metrics::counter!("withdrawal_tracker.recorded", stored);
+        self.last_block = Some(to_block);
+        Ok(())
+    }
+
+    async fn run(&mut self) {
+        let mut timer = tokio::time::interval(self.poll_interval);
+        loop {
+            timer.tick().await;
+            if let Err(err) = self.run_iteration().await {
+                vlog::error!("Withdrawal tracker iteration has failed: {}", err);
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn run_withdrawal_tracker(
+    connection_pool: ConnectionPool,
+    poll_interval: Duration,
+) -> JoinHandle<()> {
+    let mut tracker = WithdrawalTracker {
+        connection_pool,
+        poll_interval,
+        last_block: None,
+    };
+    tokio::spawn(async move { tracker.run().await })
+}
@@ -0,0 +1,275 @@
+//! Approval of L1 operations by the committee of operator nodes.
+//!
+//! Before sending an operation to L1, `ETHSender` requests an approval from every member of the
+//! committee via its private core API (`POST /committee/approve`). Each member loads the blocks
+//! of the operation from its own storage and signs the `committee_approval_message`, so an
+//! approval means that the member agrees on the exact content of the blocks. The operation is
+//! only sent once the number of valid approvals reaches the threshold, otherwise it's returned
+//! to the queue and approvals are requested again on the next iteration.
+//!
+//! Requests are authorized by the access tokens (JWT) signed with the secret shared by the
+//! committee. The collected approvals are appended to the calldata of the L1 transaction (see
+//! `encode_approvals`), so they are recorded on-chain along with the operation.
+
+// Built-in deps
+use std::time::{Duration, Instant, UNIX_EPOCH};
+// External uses
+use anyhow::{ensure, format_err};
+use futures::future::join_all;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde::Serialize;
+use serde_json::json;
+// Workspace uses
+use zksync_config::configs::eth_sender::Committee as CommitteeConfig;
+use zksync_types::{
+    aggregated_operations::AggregatedOperation, tx::PackedEthSignature, Address, H256,
+};
+
+/// Lifetime of the access token of the approval requests.
+const AUTH_TOKEN_LIFETIME: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize)]
+struct CommitteeAuthToken {
+    /// Subject (whom auth token refers to).
+    sub: String,
+    /// Expiration time (as UTC timestamp).
+    exp: usize,
+}
+
+/// Client of the operators committee.
+#[derive(Debug)]
+pub(crate) struct Committee {
+    client: reqwest::Client,
+    /// Addresses of the members' approval keys and URLs of their private APIs.
+    members: Vec<(Address, String)>,
+    threshold: usize,
+    secret_auth: String,
+}
+
+impl Committee {
+    /// Creates the committee client, returns `None` if the committee mode is disabled.
+    /// Returns an error if the committee config is malformed.
+    pub fn from_config(config: &CommitteeConfig) -> anyhow::Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let members = config.members().map_err(|err| format_err!(err))?;
+        ensure!(
+            config.threshold > 0 && config.threshold <= members.len(),
+            "Committee threshold must be in range 1..={}",
+            members.len()
+        );
+        ensure!(
+            !config.secret_auth.is_empty(),
+            "Secret of the operators committee is not set"
+        );
+
+        let client = reqwest::Client::builder()
+            .timeout(config.request_timeout())
+            .build()?;
+        Ok(Some(Self {
+            client,
+            members,
+            threshold: config.threshold,
+            secret_auth: config.secret_auth.clone(),
+        }))
+    }
+
+    /// Creates the access token of the approval requests.
+    fn auth_token(&self) -> anyhow::Result<String> {
+        let exp = UNIX_EPOCH.elapsed()? + AUTH_TOKEN_LIFETIME;
+        let token = encode(
+            &Header::default(),
+            &CommitteeAuthToken {
+                sub: "committee".to_string(),
+                exp: exp.as_secs() as usize,
+            },
+            &EncodingKey::from_secret(self.secret_auth.as_ref()),
+        )?;
+        Ok(token)
+    }
+
+    /// Collects approvals of the operation from the committee members.
+    /// Returns an error if the number of valid approvals is below the threshold.
+    pub async fn collect_approvals(
+        &self,
+        operation: &AggregatedOperation,
+    ) -> anyhow::Result<Vec<(Address, PackedEthSignature)>> {
+        let start = Instant::now();
+        let message = operation.committee_approval_message();
+        let (first_block, last_block) = operation.get_block_range();
+        let body = json!({
+            "action_type": operation.get_action_type(),
+            "first_block": first_block,
+            "last_block": last_block,
+        });
+        let token = self.auth_token()?;
+
+        let requests = self
+            .members
+            .iter()
+            .map(|(address, url)| self.request_approval(*address, url, &token, &body));
+        let responses = join_all(requests).await;
+
+        let mut approvals = Vec::with_capacity(responses.len());
+        for ((address, url), response) in self.members.iter().zip(responses) {
+            match response {
+                Ok(signature) => approvals.push((*address, signature)),
+                Err(err) => vlog::warn!("Committee member {} didn't approve: {}", url, err),
+            }
+        }
+        let approvals = valid_approvals(approvals, message);
+
+        metrics::histogram!("eth_sender.committee.collect_approvals", start.elapsed());
+        metrics::gauge!("eth_sender.committee.approvals", approvals.len() as f64);
+        if approvals.len() < self.threshold {
+            metrics::increment_counter!("eth_sender.committee.quorum_not_reached");
+            return Err(format_err!(
+                "Committee quorum is not reached for {} of blocks {}-{}: {} of {} approvals",
+                operation.get_action_type().to_string(),
+                first_block,
+                last_block,
+                approvals.len(),
+                self.threshold
+            ));
+        }
+
+        vlog::info!(
+            "Committee approved {} of blocks {}-{} by {:?}",
+            operation.get_action_type().to_string(),
+            first_block,
+            last_block,
+            approvals
+                .iter()
+                .map(|(address, _)| *address)
+                .collect::<Vec<_>>()
+        );
+        Ok(approvals)
+    }
+
+    async fn request_approval(
+        &self,
+        address: Address,
+        url: &str,
+        token: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<PackedEthSignature> {
+        let response = self
+            .client
+            .post(format!("{}/committee/approve", url.trim_end_matches('/')))
+            .bearer_auth(token)
+            .json(body)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format_err!(
+                "Member {:?} responded with {}: {}",
+                address,
+                status,
+                body
+            ));
+        }
+        Ok(response.json().await?)
+    }
+}
+
+/// Encodes the approvals to be appended to the calldata of the L1 transaction:
+/// `signature_1 || ... || signature_n || uint256(n)`, where each signature is 65 bytes long.
+///
+/// The contract ignores the data after the ABI-encoded arguments, while the approvals can be read
+/// from the end of the calldata and checked against `committee_approval_message` of the blocks.
+pub(crate) fn encode_approvals(approvals: &[(Address, PackedEthSignature)]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(approvals.len() * 65 + 32);
+    for (_, signature) in approvals {
+        bytes.extend_from_slice(&signature.serialize_packed());
+    }
+    let mut count = [0u8; 32];
+    count[24..].copy_from_slice(&(approvals.len() as u64).to_be_bytes());
+    bytes.extend_from_slice(&count);
+    bytes
+}
+
+/// Filters out approvals that are not signed by the corresponding member, as well as
+/// duplicate approvals of the same member.
+fn valid_approvals(
+    approvals: Vec<(Address, PackedEthSignature)>,
+    message: H256,
+) -> Vec<(Address, PackedEthSignature)> {
+    let mut valid: Vec<(Address, PackedEthSignature)> = Vec::with_capacity(approvals.len());
+    for (address, signature) in approvals {
+        match signature.signature_recover_signer(message.as_bytes()) {
+            Ok(signer) if signer == address => {
+                if valid.iter().all(|(member, _)| *member != address) {
+                    valid.push((address, signature));
+                }
+            }
+            _ => vlog::warn!(
+                "Invalid approval signature of committee member {:?}",
+                address
+            ),
+        }
+    }
+    valid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approval(private_key: H256, message: H256) -> (Address, PackedEthSignature) {
+        (
+            PackedEthSignature::address_from_private_key(&private_key).unwrap(),
+            PackedEthSignature::sign(&private_key, message.as_bytes()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn approvals_validation() {
+        let message = H256::repeat_byte(1);
+        let first = approval(H256::repeat_byte(2), message);
+        let second = approval(H256::repeat_byte(3), message);
+        // Signature of another message.
+        let (address, _) = approval(H256::repeat_byte(4), message);
+        let wrong_message = (address, approval(H256::repeat_byte(4), H256::zero()).1);
+        // Signature made by another member.
+        let impostor = (Address::repeat_byte(5), first.1.clone());
+
+        let approvals = valid_approvals(
+            vec![
+                first.clone(),
+                second.clone(),
+                wrong_message,
+                impostor,
+                first.clone(),
+            ],
+            message,
+        );
+        assert_eq!(approvals, vec![first, second]);
+    }
+
+    #[test]
+    fn approvals_encoding() {
+        let message = H256::repeat_byte(1);
+        let approvals = vec![
+            approval(H256::repeat_byte(2), message),
+            approval(H256::repeat_byte(3), message),
+        ];
+
+        let bytes = encode_approvals(&approvals);
+        assert_eq!(bytes.len(), 2 * 65 + 32);
+        assert_eq!(bytes[bytes.len() - 1], 2);
+        for (i, (address, _)) in approvals.iter().enumerate() {
+            let signature =
+                PackedEthSignature::deserialize_packed(&bytes[i * 65..(i + 1) * 65]).unwrap();
+            assert_eq!(
+                signature
+                    .signature_recover_signer(message.as_bytes())
+                    .unwrap(),
+                *address
+            );
+        }
+        assert_eq!(encode_approvals(&[]), vec![0u8; 32]);
+    }
+}
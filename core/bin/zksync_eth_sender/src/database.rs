@@ -83,6 +83,16 @@ pub(super) trait DatabaseInterface {
         op: &ETHOperation,
     ) -> anyhow::Result<()>;
 
+    /// Records the L1 gas spent by the transaction confirming the operation.
+    async fn record_operation_cost(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        op: &ETHOperation,
+        hash: &H256,
+        gas_used: U256,
+        gas_price: U256,
+    ) -> anyhow::Result<()>;
+
     /// Loads the stored Ethereum operations stats.
     async fn load_stats(&self, connection: &mut StorageProcessor<'_>) -> anyhow::Result<ETHStats>;
 
@@ -105,6 +115,12 @@ pub(super) trait DatabaseInterface {
         connection: &mut StorageProcessor<'_>,
         op: &ETHOperation,
     ) -> anyhow::Result<bool>;
+
+    /// Checks whether the circuit breaker is tripped.
+    async fn is_circuit_breaker_tripped(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<bool>;
 }
 
 /// The actual database wrapper.
@@ -318,6 +334,31 @@ impl DatabaseInterface for Database {
         Ok(())
     }
 
+    async fn record_operation_cost(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        op: &ETHOperation,
+        hash: &H256,
+        gas_used: U256,
+        gas_price: U256,
+    ) -> anyhow::Result<()> {
+        let op_id = match &op.op {
+            Some((op_id, _)) => *op_id,
+            None => return Ok(()),
+        };
+        let op_type = op.op_type.to_string();
+        metrics::histogram!("eth_sender.l1_gas_used", gas_used.low_u64() as f64, "type" => op_type.clone());
+        // Gwei are used to stay within the `f64` precision.
+        let cost_gwei = (gas_used * gas_price) / U256::exp10(9);
+        metrics::histogram!("eth_sender.l1_cost_gwei", cost_gwei.low_u64() as f64, "type" => op_type);
+
+        connection
+            .accounting_schema()
+            .record_operation_cost(op_id, *hash, gas_used, gas_price)
+            .await?;
+        Ok(())
+    }
+
     async fn load_stats(&self, connection: &mut StorageProcessor<'_>) -> anyhow::Result<ETHStats> {
         let stats = connection.ethereum_schema().load_stats().await?;
         Ok(stats.into())
@@ -343,6 +384,17 @@ impl DatabaseInterface for Database {
             .await?;
         Ok(())
     }
+
+    async fn is_circuit_breaker_tripped(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<bool> {
+        let trip = connection
+            .circuit_breaker_schema()
+            .load_active_trip()
+            .await?;
+        Ok(trip.is_some())
+    }
 }
 
 impl Database {
@@ -18,8 +18,13 @@ use zksync_config::ETHSenderConfig;
 use zksync_eth_client::{EthereumGateway, SignedCallResult};
 use zksync_storage::ConnectionPool;
 use zksync_types::ethereum::ETHOperation;
+use zksync_utils::{
+    chaos::{self, FaultPoint},
+    shutdown::ShutdownParticipant,
+};
 // Local uses
 use self::{
+    committee::Committee,
     database::{Database, DatabaseInterface},
     gas_adjuster::GasAdjuster,
     transactions::*,
@@ -28,8 +33,10 @@ use self::{
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     gas_counter::GasCounter,
+    prover::ProofSystemVersion,
 };
 
+mod committee;
 mod database;
 mod gas_adjuster;
 mod transactions;
@@ -121,6 +128,8 @@ struct ETHSender<DB: DatabaseInterface> {
     gas_adjuster: GasAdjuster<DB>,
     /// Settings for the `ETHSender`.
     options: ETHSenderConfig,
+    /// Committee that must approve operations before they are sent, if enabled.
+    committee: Option<Committee>,
 }
 
 impl<DB: DatabaseInterface> ETHSender<DB> {
@@ -172,6 +181,9 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             .expect("Failed commit database transaction");
         drop(connection);
 
+        let committee =
+            Committee::from_config(&options.committee).expect("Invalid operators committee config");
+
         Self {
             ongoing_ops,
             db,
@@ -179,17 +191,30 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             tx_queue,
             gas_adjuster,
             options,
+            committee,
         }
     }
 
     /// Main routine of `ETHSender`.
-    pub async fn run(mut self) {
+    ///
+    /// Once the shutdown is requested, the sender stops between the iterations, so no operation
+    /// is left half-processed: the sent transactions are stored and are tracked again after the restart.
+    pub async fn run(mut self, mut shutdown: ShutdownParticipant) {
         // `eth_sender` must perform some of the activities only once per block change.
         // Having `0` as an initial value is to ensure that on the first iteration we will run all the activities.
         let mut last_used_block = 0;
         loop {
             // We perform a loading routine every X seconds.
-            tokio::time::sleep(self.options.sender.tx_poll_period()).await;
+            tokio::select! {
+                _ = tokio::time::sleep(self.options.sender.tx_poll_period()) => {},
+                _ = shutdown.requested() => {
+                    vlog::info!("Ethereum sender is stopped");
+                    shutdown.complete();
+                    // Parked instead of finishing the run, as the rest of the actors are still running.
+                    return futures::future::pending().await;
+                }
+            }
+            chaos::inject_delay(FaultPoint::EthSender).await;
             // If we received an error when loading a new operation, we can't do anything about it and should panic.
             if let Err(error) = self.load_new_operations().await {
                 vlog::error!("Unable to restore operations from the database: {}", error);
@@ -258,16 +283,23 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             }
         };
 
-        while let Some(tx) = self.tx_queue.pop_front() {
-            if let Err(e) = self.initialize_operation(tx.clone(), current_block).await {
-                Self::process_error(e).await;
-                // Return the unperformed operation to the queue, since failing the
-                // operation initialization means that it was not stored in the database.
-                if let Err(err_message) = self.tx_queue.return_popped(tx) {
-                    panic!(
-                        "Failed return previous sent operation to the queue: {}",
-                        err_message
-                    );
+        // New operations are not sent while the circuit breaker is tripped,
+        // but the already sent ones are still processed.
+        if !self.is_circuit_breaker_tripped().await {
+            while let Some(tx) = self.tx_queue.pop_front() {
+                if let Err(e) = self.initialize_operation(tx.clone(), current_block).await {
+                    Self::process_error(e).await;
+                    // Return the unperformed operation to the queue, since failing the
+                    // operation initialization means that it was not stored in the database.
+                    if let Err(err_message) = self.tx_queue.return_popped(tx) {
+                        panic!(
+                            "Failed return previous sent operation to the queue: {}",
+                            err_message
+                        );
+                    }
+                    // The returned operation would be popped again right away, so it's retried
+                    // on the next iteration instead (e.g. once the committee quorum is reached).
+                    break;
                 }
             }
         }
@@ -321,6 +353,27 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         current_block
     }
 
+    /// Checks whether the circuit breaker is tripped. If the state can't be loaded,
+    /// the breaker is considered tripped, since the operations can't be stored anyway.
+    async fn is_circuit_breaker_tripped(&self) -> bool {
+        let tripped = match self.db.acquire_connection().await {
+            Ok(mut connection) => self.db.is_circuit_breaker_tripped(&mut connection).await,
+            Err(err) => Err(err),
+        };
+        match tripped {
+            Ok(tripped) => {
+                if tripped {
+                    vlog::warn!("Circuit breaker is tripped, new operations are not sent");
+                }
+                tripped
+            }
+            Err(err) => {
+                vlog::warn!("Failed to load the circuit breaker state: {}", err);
+                true
+            }
+        }
+    }
+
     async fn process_error(err: anyhow::Error) {
         vlog::warn!("Error while trying to complete uncommitted op: {}", err);
         if err.to_string().contains(RATE_LIMIT_HTTP_CODE) {
@@ -336,7 +389,18 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     }
 
     /// Stores the new operation in the database and sends the corresponding transaction.
-    async fn initialize_operation(&mut self, tx: TxData, current_block: u64) -> anyhow::Result<()> {
+    async fn initialize_operation(
+        &mut self,
+        mut tx: TxData,
+        current_block: u64,
+    ) -> anyhow::Result<()> {
+        // Operation is not stored until it's approved, so it will be retried if quorum is not reached.
+        if let Some(committee) = &self.committee {
+            let approvals = committee.collect_approvals(&tx.operation.1).await?;
+            // Approvals are stored and resent as a part of the transaction data.
+            tx.raw.extend(committee::encode_approvals(&approvals));
+        }
+
         let deadline_block = self.get_deadline_block(current_block);
         let gas_price = self
             .gas_adjuster
@@ -449,6 +513,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         current_block: u64,
     ) -> anyhow::Result<OperationCommitment> {
         let start = Instant::now();
+        chaos::inject_error(FaultPoint::EthSender)?;
         assert!(
             !op.used_tx_hashes.is_empty(),
             "OperationETHState should have at least one transaction"
@@ -472,7 +537,10 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     // Transaction is pending, nothing to do yet.
                     return Ok(OperationCommitment::Pending);
                 }
-                TxCheckOutcome::Committed => {
+                TxCheckOutcome::Committed {
+                    gas_used,
+                    effective_gas_price,
+                } => {
                     let mut connection = self.db.acquire_connection().await?;
                     let mut transaction = connection.start_transaction().await?;
 
@@ -508,6 +576,20 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     self.db
                         .confirm_operation(&mut transaction, tx_hash, op)
                         .await?;
+                    if let Some(gas_used) = gas_used {
+                        // Nodes without EIP-1559 support don't report the effective price,
+                        // but then the price of the transaction is exactly the one we've set.
+                        let gas_price = effective_gas_price.unwrap_or(op.last_used_gas_price);
+                        self.db
+                            .record_operation_cost(
+                                &mut transaction,
+                                op,
+                                tx_hash,
+                                gas_used,
+                                gas_price,
+                            )
+                            .await?;
+                    }
                     transaction.commit().await?;
                     return Ok(OperationCommitment::Committed);
                 }
@@ -602,7 +684,10 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             Some(status) if status.success => {
                 // Check if transaction has enough confirmations.
                 if status.confirmations >= self.options.sender.wait_confirmations {
-                    TxCheckOutcome::Committed
+                    TxCheckOutcome::Committed {
+                        gas_used: status.gas_used,
+                        effective_gas_price: status.effective_gas_price,
+                    }
                 } else {
                     TxCheckOutcome::Pending
                 }
@@ -764,7 +849,8 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             } // not for eth sender
             AggregatedOperation::PublishProofBlocksOnchain(operation) => {
                 let args = operation.get_eth_tx_args();
-                self.ethereum.encode_tx_data("proveBlocks", args.as_slice())
+                self.ethereum
+                    .encode_tx_data(verifier_method(operation.proof_system), args.as_slice())
             }
             AggregatedOperation::ExecuteBlocks(operation) => {
                 let args = operation.get_eth_tx_args();
@@ -796,17 +882,25 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     }
 }
 
+/// Returns the contract method verifying the proofs created with the proof system.
+fn verifier_method(proof_system: ProofSystemVersion) -> &'static str {
+    match proof_system {
+        ProofSystemVersion::PlonkV1 => "proveBlocks",
+    }
+}
+
 #[must_use]
 pub fn run_eth_sender(
     pool: ConnectionPool,
     eth_gateway: EthereumGateway,
     options: ETHSenderConfig,
+    shutdown: ShutdownParticipant,
 ) -> JoinHandle<()> {
     let db = Database::new(pool);
 
     tokio::spawn(async move {
         let eth_sender = ETHSender::new(options, db, eth_gateway).await;
 
-        eth_sender.run().await
+        eth_sender.run(shutdown).await
     })
 }
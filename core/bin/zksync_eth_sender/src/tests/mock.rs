@@ -8,7 +8,9 @@ use tokio::sync::RwLock;
 use web3::contract::Options;
 use zksync_basic_types::{BlockNumber, H256, U256};
 // Workspace uses
-use zksync_config::configs::eth_sender::{ETHSenderConfig, GasLimit, Sender};
+use zksync_config::configs::eth_sender::{
+    Committee, ETHSenderConfig, GasLimit, Sender, SignerBackend,
+};
 use zksync_eth_client::EthereumGateway;
 use zksync_storage::{ethereum::records::ETHParams, StorageProcessor};
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
@@ -26,6 +28,7 @@ pub(crate) struct MockDatabase {
     aggregated_operations: RwLock<Vec<(i64, AggregatedOperation)>>,
     unprocessed_operations: RwLock<Vec<(i64, AggregatedOperation)>>,
     eth_parameters: RwLock<ETHParams>,
+    circuit_breaker_tripped: RwLock<bool>,
 }
 
 impl MockDatabase {
@@ -41,9 +44,15 @@ impl MockDatabase {
             aggregated_operations: RwLock::new(aggregated_operations),
             unprocessed_operations: RwLock::new(unprocessed_operations),
             eth_parameters: RwLock::new(eth_parameters),
+            circuit_breaker_tripped: RwLock::new(false),
         }
     }
 
+    /// Simulates tripping (or resetting) the circuit breaker by another component.
+    pub async fn set_circuit_breaker_tripped(&self, tripped: bool) {
+        *self.circuit_breaker_tripped.write().await = tripped;
+    }
+
     pub async fn update_gas_price_limit(&self, value: i64) -> anyhow::Result<()> {
         let mut eth_parameters = self.eth_parameters.write().await;
         eth_parameters.gas_price_limit = value;
@@ -302,6 +311,17 @@ impl DatabaseInterface for MockDatabase {
         Ok(())
     }
 
+    async fn record_operation_cost(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        _op: &ETHOperation,
+        _hash: &H256,
+        _gas_used: U256,
+        _gas_price: U256,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     async fn load_gas_price_limit(
         &self,
         _connection: &mut StorageProcessor<'_>,
@@ -355,6 +375,13 @@ impl DatabaseInterface for MockDatabase {
 
         Ok(confirmed)
     }
+
+    async fn is_circuit_breaker_tripped(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<bool> {
+        Ok(*self.circuit_breaker_tripped.read().await)
+    }
 }
 
 /// Creates a default `ETHParams` for use by mock `ETHSender` .
@@ -441,7 +468,14 @@ async fn build_eth_sender(
             tx_poll_period: 0,
             is_enabled: true,
             operator_commit_eth_addr: Default::default(),
-            operator_private_key: Default::default(),
+            operator_private_key: None,
+            signer_backend: SignerBackend::PrivateKey,
+            kms_key_id: Default::default(),
+            kms_region: Default::default(),
+            remote_signer_url: Default::default(),
+            remote_signer_ca_cert_path: Default::default(),
+            remote_signer_identity_path: Default::default(),
+            remote_signer_identity_password: Default::default(),
         },
         gas_price_limit: GasLimit {
             default: 1000,
@@ -449,6 +483,14 @@ async fn build_eth_sender(
             update_interval: 15,
             scale_factor: 1.0f64,
         },
+        committee: Committee {
+            enabled: false,
+            members: Vec::new(),
+            threshold: 0,
+            request_timeout: 0,
+            member_private_key: Default::default(),
+            secret_auth: String::new(),
+        },
     };
 
     ETHSender::new(options, db, ethereum).await
@@ -78,6 +78,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: true,
         receipt: None,
+        gas_used: None,
+        effective_gas_price: None,
     };
     eth_sender
         .ethereum
@@ -91,6 +93,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: true,
         receipt: None,
+        gas_used: None,
+        effective_gas_price: None,
     };
     eth_sender
         .ethereum
@@ -104,6 +108,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
+        effective_gas_price: None,
     };
     eth_sender
         .ethereum
@@ -117,6 +123,8 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
+        effective_gas_price: None,
     };
     eth_sender
         .ethereum
@@ -139,7 +147,10 @@ async fn transaction_state() {
             )
             .await
             .unwrap(),
-        TxCheckOutcome::Committed
+        TxCheckOutcome::Committed {
+            gas_used: None,
+            effective_gas_price: None,
+        }
     );
 
     // Pending operation (no enough confirmations).
@@ -306,6 +317,32 @@ async fn operation_commitment_workflow() {
     }
 }
 
+/// Checks that new operations are not sent while the circuit breaker is tripped
+/// and are sent once it's reset.
+#[tokio::test]
+async fn circuit_breaker_halts_sending() {
+    let mut eth_sender = default_eth_sender().await;
+    let operation = test_data::commit_blocks_operation(0);
+
+    eth_sender.db.set_circuit_breaker_tripped(true).await;
+    eth_sender
+        .db
+        .send_aggregated_operation(operation.clone())
+        .await
+        .unwrap();
+    eth_sender.load_new_operations().await.unwrap();
+    eth_sender.proceed_next_operations(0).await;
+    assert!(eth_sender.ongoing_ops.is_empty());
+
+    eth_sender.db.set_circuit_breaker_tripped(false).await;
+    eth_sender.proceed_next_operations(0).await;
+    assert_eq!(eth_sender.ongoing_ops.len(), 1);
+    assert_eq!(
+        eth_sender.ongoing_ops[0].op.as_ref().unwrap().0,
+        operation.0
+    );
+}
+
 /// A simple scenario for a stuck transaction:
 /// - A transaction is sent to the Ethereum.
 /// - It is not processed after some blocks.
@@ -78,6 +78,7 @@ fn gen_aggregated_operation(
             AggregatedOperation::PublishProofBlocksOnchain(BlocksProofOperation {
                 blocks: vec![block],
                 proof: Default::default(),
+                proof_system: Default::default(),
             })
         }
         AggregatedActionType::ExecuteBlocks => {
@@ -5,7 +5,7 @@
 
 // Built-in deps
 // External uses
-use zksync_basic_types::TransactionReceipt;
+use zksync_basic_types::{TransactionReceipt, U256};
 // Workspace uses
 use zksync_storage::ethereum::records::ETHStats as StorageETHStats;
 
@@ -37,7 +37,10 @@ impl From<StorageETHStats> for ETHStats {
 #[derive(Debug, PartialEq)]
 pub enum TxCheckOutcome {
     /// Transaction was committed and confirmed.
-    Committed,
+    Committed {
+        gas_used: Option<U256>,
+        effective_gas_price: Option<U256>,
+    },
     /// Transaction is pending yet.
     Pending,
     /// Transaction is considered stuck, a replacement should be made.
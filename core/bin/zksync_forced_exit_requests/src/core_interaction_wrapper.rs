@@ -140,15 +140,17 @@ impl CoreInteractionWrapper for MempoolCoreInteractionWrapper {
         request: &ForcedExitRequest,
         txs: Vec<SignedZkSyncTx>,
     ) -> anyhow::Result<Vec<TxHash>> {
-        let mut storage = self.connection_pool.access_storage().await?;
-        let mut schema = storage.forced_exit_requests_schema();
-
         let hashes: Vec<TxHash> = txs.iter().map(|tx| tx.hash()).collect();
 
         let (sender, receiver) = oneshot::channel();
         let item = MempoolTransactionRequest::NewTxsBatch(txs, vec![], sender);
         self.mempool_tx_sender.send(item).await?;
         receiver.await??;
+
+        // The mempool handler shares the connection quota, so the connection is taken only
+        // once the batch is added.
+        let mut storage = self.connection_pool.access_storage().await?;
+        let mut schema = storage.forced_exit_requests_schema();
         schema
             .set_fulfilled_by(request.id, Some(hashes.clone()))
             .await?;
@@ -9,19 +9,15 @@ use std::{
 use std::{convert::TryInto, fmt::Debug};
 use tokio::task::JoinHandle;
 use tokio::time;
-use web3::{
-    contract::Contract,
-    transports::Http,
-    types::{BlockNumber, FilterBuilder, Log},
-    Web3,
-};
+use web3::types::{BlockNumber, FilterBuilder, Log};
 use zksync_config::ForcedExitRequestsConfig;
+use zksync_eth_client::EthereumGateway;
 use zksync_storage::ConnectionPool;
 
 use zksync_contracts::forced_exit_contract;
 use zksync_types::H160;
 
-use zksync_core::eth_watch::{get_web3_block_number, WatcherMode};
+use zksync_core::eth_watch::WatcherMode;
 use zksync_mempool::MempoolTransactionRequest;
 use zksync_types::forced_exit_requests::FundsReceivedEvent;
 
@@ -63,20 +59,17 @@ pub trait EthClient {
 }
 
 pub struct EthHttpClient {
-    web3: Web3<Http>,
-    forced_exit_contract: Contract<Http>,
+    eth_gateway: EthereumGateway,
+    forced_exit_contract_addr: H160,
     topics: ContractTopics,
 }
 
 impl EthHttpClient {
-    pub fn new(web3: Web3<Http>, zksync_contract_addr: H160) -> Self {
-        let forced_exit_contract =
-            Contract::new(web3.eth(), zksync_contract_addr, forced_exit_contract());
-
-        let topics = ContractTopics::new(forced_exit_contract.abi());
+    pub fn new(eth_gateway: EthereumGateway, forced_exit_contract_addr: H160) -> Self {
+        let topics = ContractTopics::new(&forced_exit_contract());
         Self {
-            web3,
-            forced_exit_contract,
+            eth_gateway,
+            forced_exit_contract_addr,
             topics,
         }
     }
@@ -89,8 +82,8 @@ impl EthHttpClient {
         let from = BlockNumber::from(from);
         let to = BlockNumber::from(to);
         get_contract_events(
-            &self.web3,
-            self.forced_exit_contract.address(),
+            &self.eth_gateway,
+            self.forced_exit_contract_addr,
             from,
             to,
             topics,
@@ -119,7 +112,7 @@ impl EthClient for EthHttpClient {
     }
 
     async fn block_number(&self) -> anyhow::Result<u64> {
-        get_web3_block_number(&self.web3).await
+        Ok(self.eth_gateway.block_number().await?.as_u64())
     }
 }
 
@@ -389,11 +382,9 @@ pub fn run_forced_exit_contract_watcher(
     config: ForcedExitRequestsConfig,
     forced_exit_minimum_account_age_secs: u64,
     contract: Address,
-    web3_url: String,
+    eth_gateway: EthereumGateway,
 ) -> JoinHandle<()> {
-    let transport = web3::transports::Http::new(&web3_url).unwrap();
-    let web3 = web3::Web3::new(transport);
-    let eth_client = EthHttpClient::new(web3, contract);
+    let eth_client = EthHttpClient::new(eth_gateway, contract);
 
     tokio::spawn(async move {
         // We should not proceed if the feature is disabled
@@ -437,7 +428,7 @@ pub fn run_forced_exit_contract_watcher(
 }
 
 pub async fn get_contract_events<T>(
-    web3: &Web3<Http>,
+    eth_gateway: &EthereumGateway,
     contract_address: Address,
     from: BlockNumber,
     to: BlockNumber,
@@ -454,7 +445,7 @@ where
         .topics(Some(topics), None, None, None)
         .build();
 
-    web3.eth()
+    eth_gateway
         .logs(filter)
         .await?
         .into_iter()
@@ -5,6 +5,7 @@ use zksync_storage::ConnectionPool;
 
 use forced_exit_sender::ForcedExitSender;
 use zksync_config::configs::api::CommonApiConfig;
+use zksync_eth_client::EthereumGateway;
 use zksync_mempool::MempoolTransactionRequest;
 
 mod core_interaction_wrapper;
@@ -23,7 +24,7 @@ pub fn run_forced_exit_requests_actors(
     config: ForcedExitRequestsConfig,
     common: CommonApiConfig,
     contracts: ContractsConfig,
-    web3_url: String,
+    eth_gateway: EthereumGateway,
 ) -> JoinHandle<()> {
     eth_watch::run_forced_exit_contract_watcher(
         sender,
@@ -31,6 +32,6 @@ pub fn run_forced_exit_requests_actors(
         config,
         common.forced_exit_minimum_account_age_secs,
         contracts.forced_exit_addr,
-        web3_url,
+        eth_gateway,
     )
 }
@@ -56,8 +56,10 @@ pub async fn prepare_forced_exit_sender_account(
         .await
         .expect("Failed to get account id for forced exit sender");
 
+    // Mempool handler takes its connection from the same quota as ours.
+    drop(storage);
     register_signing_key(
-        &mut storage,
+        &connection_pool,
         id,
         mempool_tx_sender,
         sender_address,
@@ -170,7 +172,7 @@ pub async fn wait_for_change_pub_key_tx(
 }
 
 pub async fn register_signing_key(
-    storage: &mut StorageProcessor<'_>,
+    connection_pool: &ConnectionPool,
     sender_id: AccountId,
     mut mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     sender_address: Address,
@@ -214,7 +216,8 @@ pub async fn register_signing_key(
         .expect("Failed to receive result")
         .expect("Failed to change pub key");
 
-    wait_for_change_pub_key_tx(storage, tx_hash)
+    let mut storage = connection_pool.access_storage().await?;
+    wait_for_change_pub_key_tx(&mut storage, tx_hash)
         .await
         .expect("Failed to wait for ChangePubKey tx");
 
@@ -5,14 +5,16 @@
 
 // Built-in
 use std::clone::Clone;
+// External uses
+use chrono::{DateTime, Utc};
 // Workspace uses
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
-use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_storage::{prover::records::ProverBacklog, ConnectionPool, StorageProcessor};
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
-    prover::{ProverJob, ProverJobType},
-    AccountMap, AccountUpdates, BlockNumber,
+    prover::{ProofSystemVersion, ProverJob, ProverJobType},
+    AccountMap, AccountUpdates, BlockNumber, H256,
 };
 // Local uses
 use crate::DatabaseInterface;
@@ -100,7 +102,7 @@ impl DatabaseInterface for Database {
         &self,
         connection: &mut StorageProcessor<'_>,
         block_number: BlockNumber,
-    ) -> anyhow::Result<Option<SingleProof>> {
+    ) -> anyhow::Result<Option<(SingleProof, ProofSystemVersion)>> {
         let proof = connection.prover_schema().load_proof(block_number).await?;
 
         Ok(proof)
@@ -183,10 +185,54 @@ impl DatabaseInterface for Database {
         job_id: i32,
         block_number: BlockNumber,
         proof: &SingleProof,
+        proof_system: ProofSystemVersion,
     ) -> anyhow::Result<()> {
         connection
             .prover_schema()
-            .store_proof(job_id, block_number, proof)
+            .store_proof(job_id, block_number, proof, proof_system)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn store_cached_proof(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block_commitment: H256,
+        proof: &SingleProof,
+        proof_system: ProofSystemVersion,
+    ) -> anyhow::Result<()> {
+        connection
+            .prover_schema()
+            .store_cached_proof(block_commitment, proof, proof_system)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn load_cached_proof(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block_commitment: H256,
+    ) -> anyhow::Result<Option<(SingleProof, ProofSystemVersion)>> {
+        let proof = connection
+            .prover_schema()
+            .load_cached_proof(block_commitment)
+            .await?;
+
+        Ok(proof)
+    }
+
+    async fn store_proof_from_cache(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block_number: BlockNumber,
+        proof: &SingleProof,
+        proof_system: ProofSystemVersion,
+    ) -> anyhow::Result<()> {
+        connection
+            .prover_schema()
+            .store_proof_from_cache(block_number, proof, proof_system)
             .await?;
 
         Ok(())
@@ -199,10 +245,27 @@ impl DatabaseInterface for Database {
         first_block: BlockNumber,
         last_block: BlockNumber,
         proof: &AggregatedProof,
+        proof_system: ProofSystemVersion,
     ) -> anyhow::Result<()> {
         connection
             .prover_schema()
-            .store_aggregated_proof(job_id, first_block, last_block, proof)
+            .store_aggregated_proof(job_id, first_block, last_block, proof, proof_system)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn store_job_result_hash(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        job_id: i32,
+        content_hash: H256,
+        compression: &str,
+        payload_size: usize,
+    ) -> anyhow::Result<()> {
+        connection
+            .prover_schema()
+            .store_job_result_hash(job_id, content_hash, compression, payload_size)
             .await?;
 
         Ok(())
@@ -293,4 +356,31 @@ impl DatabaseInterface for Database {
 
         Ok(count)
     }
+
+    async fn load_prover_backlog(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        done_since: DateTime<Utc>,
+    ) -> anyhow::Result<ProverBacklog> {
+        let backlog = connection
+            .prover_schema()
+            .prover_backlog(done_since)
+            .await?;
+
+        Ok(backlog)
+    }
+
+    async fn trip_circuit_breaker(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        source: &str,
+        reason: &str,
+    ) -> anyhow::Result<()> {
+        connection
+            .circuit_breaker_schema()
+            .trip(source, reason)
+            .await?;
+
+        Ok(())
+    }
 }
@@ -6,14 +6,16 @@
 // Built-in
 use std::clone::Clone;
 use std::marker::{Send, Sync};
+// External uses
+use chrono::{DateTime, Utc};
 // Workspace uses
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
-use zksync_storage::StorageProcessor;
+use zksync_storage::{prover::records::ProverBacklog, StorageProcessor};
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 use zksync_types::{
     block::Block,
-    prover::{ProverJob, ProverJobType},
-    AccountMap, AccountUpdates, BlockNumber,
+    prover::{ProofSystemVersion, ProverJob, ProverJobType},
+    AccountMap, AccountUpdates, BlockNumber, H256,
 };
 
 /// Abstract database access trait.
@@ -57,7 +59,7 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         &self,
         connection: &mut StorageProcessor<'_>,
         block_number: BlockNumber,
-    ) -> anyhow::Result<Option<SingleProof>>;
+    ) -> anyhow::Result<Option<(SingleProof, ProofSystemVersion)>>;
 
     async fn mark_stale_jobs_as_idle(
         &self,
@@ -98,6 +100,31 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         job_id: i32,
         block_number: BlockNumber,
         proof: &SingleProof,
+        proof_system: ProofSystemVersion,
+    ) -> anyhow::Result<()>;
+
+    /// Caches the single block proof by the block commitment.
+    async fn store_cached_proof(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block_commitment: H256,
+        proof: &SingleProof,
+        proof_system: ProofSystemVersion,
+    ) -> anyhow::Result<()>;
+
+    async fn load_cached_proof(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block_commitment: H256,
+    ) -> anyhow::Result<Option<(SingleProof, ProofSystemVersion)>>;
+
+    /// Stores the cached proof for a block instead of queueing the job for it.
+    async fn store_proof_from_cache(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block_number: BlockNumber,
+        proof: &SingleProof,
+        proof_system: ProofSystemVersion,
     ) -> anyhow::Result<()>;
 
     async fn store_aggregated_proof(
@@ -107,6 +134,17 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         first_block: BlockNumber,
         last_block: BlockNumber,
         proof: &AggregatedProof,
+        proof_system: ProofSystemVersion,
+    ) -> anyhow::Result<()>;
+
+    /// Records the content hash of the result published for the job.
+    async fn store_job_result_hash(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        job_id: i32,
+        content_hash: H256,
+        compression: &str,
+        payload_size: usize,
     ) -> anyhow::Result<()>;
 
     async fn record_prover_stop(
@@ -146,4 +184,20 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         &self,
         connection: &mut StorageProcessor<'_>,
     ) -> anyhow::Result<u32>;
+
+    /// Returns the amounts of the prover jobs by their state, the finished ones are counted
+    /// starting from `done_since`.
+    async fn load_prover_backlog(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        done_since: DateTime<Utc>,
+    ) -> anyhow::Result<ProverBacklog>;
+
+    /// Trips the circuit breaker, halting the block production.
+    async fn trip_circuit_breaker(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        source: &str,
+        reason: &str,
+    ) -> anyhow::Result<()>;
 }
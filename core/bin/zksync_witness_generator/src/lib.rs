@@ -4,7 +4,7 @@ use std::thread;
 use std::time::Duration;
 // External
 use actix_web::dev::ServiceRequest;
-use actix_web::{web, App, HttpResponse, HttpServer};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
 use actix_web_httpauth::extractors::{
     bearer::{BearerAuth, Config},
     AuthenticationError,
@@ -23,17 +23,22 @@ use self::scaler::ScalerOracle;
 use tokio::task::JoinHandle;
 use zksync_circuit::serialization::ProverData;
 use zksync_config::configs::api::ProverApiConfig;
+use zksync_crypto::proof::{EncodedAggregatedProof, SingleProof};
 use zksync_prover_utils::api::{
     JobRequestData, JobResultData, ProverInputRequest, ProverInputResponse, ProverOutputRequest,
     WorkingOn,
 };
+use zksync_prover_utils::payload::{
+    decode_payload, EncodedPayload, PayloadCompression, CONTENT_HASH_HEADER,
+    PAYLOAD_COMPRESSION_HEADER,
+};
 use zksync_types::aggregated_operations::{
     AggregatedActionType, AggregatedOperation, BlocksCreateProofOperation,
 };
 use zksync_types::prover::{
-    ProverJobType, AGGREGATED_PROOF_JOB_PRIORITY, SINGLE_PROOF_JOB_PRIORITY,
+    ProofSystemVersion, ProverJobType, AGGREGATED_PROOF_JOB_PRIORITY, SINGLE_PROOF_JOB_PRIORITY,
 };
-use zksync_types::BlockNumber;
+use zksync_types::{block::Block, BlockNumber, U256};
 use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 
 #[cfg(test)]
@@ -44,6 +49,9 @@ mod database_interface;
 mod scaler;
 mod witness_generator;
 
+/// Maximum size of the published proof payload in bytes.
+const MAX_PUBLISH_PAYLOAD_SIZE: usize = 16 * 1024 * 1024;
+
 #[derive(Debug, Serialize, Deserialize)]
 struct PayloadAuthToken {
     /// Subject (whom auth token refers to).
@@ -60,10 +68,16 @@ struct AppState<DB: DatabaseInterface> {
 }
 
 impl<DB: DatabaseInterface> AppState<DB> {
-    pub fn new(secret_auth: String, database: DB, idle_provers: u32) -> Self {
+    pub fn new(
+        secret_auth: String,
+        database: DB,
+        idle_provers: u32,
+        backlog_drain_time: Duration,
+    ) -> Self {
         let scaler_oracle = Arc::new(RwLock::new(ScalerOracle::new(
             database.clone(),
             idle_provers,
+            backlog_drain_time,
         )));
 
         Self {
@@ -118,8 +132,41 @@ async fn status() -> actix_web::Result<String> {
     Ok("alive".into())
 }
 
+fn header_value<'a>(req: &'a HttpRequest, name: &str) -> actix_web::Result<Option<&'a str>> {
+    req.headers()
+        .get(name)
+        .map(|value| value.to_str().map_err(actix_web::error::ErrorBadRequest))
+        .transpose()
+}
+
+/// Sends the value with the compression requested by the prover, see `zksync_prover_utils::payload`.
+fn payload_response<T: Serialize>(req: &HttpRequest, value: &T) -> actix_web::Result<HttpResponse> {
+    let compression =
+        PayloadCompression::from_header(header_value(req, PAYLOAD_COMPRESSION_HEADER)?)
+            .map_err(actix_web::error::ErrorBadRequest)?;
+    let payload = EncodedPayload::encode(value, compression)
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    metrics::histogram!(
+        "prover_server.payload_size",
+        payload.body.len() as f64,
+        "direction" => "sent",
+        "compression" => compression.as_str()
+    );
+
+    let mut response = HttpResponse::Ok();
+    for header in payload.headers() {
+        response.insert_header(header);
+    }
+    let content_type = match compression {
+        PayloadCompression::None => "application/json",
+        PayloadCompression::Gzip | PayloadCompression::Zstd => "application/octet-stream",
+    };
+    Ok(response.content_type(content_type).body(payload.body))
+}
+
 async fn get_job<DB: DatabaseInterface>(
     data: web::Data<AppState<DB>>,
+    req: HttpRequest,
     r: web::Json<ProverInputRequest>,
 ) -> actix_web::Result<HttpResponse> {
     vlog::trace!("request block to prove from worker: {}", r.prover_name);
@@ -137,25 +184,81 @@ async fn get_job<DB: DatabaseInterface>(
         })?;
     if let Some(prover_job) = ret {
         vlog::info!("satisfied request to prove from worker");
-        Ok(HttpResponse::Ok().json(ProverInputResponse {
-            job_id: prover_job.job_id,
-            first_block: prover_job.first_block,
-            last_block: prover_job.last_block,
-            data: Some(
-                serde_json::from_value(prover_job.job_data)
-                    .expect("Failed to parse prover job from db"),
-            ),
-        }))
+        payload_response(
+            &req,
+            &ProverInputResponse {
+                job_id: prover_job.job_id,
+                first_block: prover_job.first_block,
+                last_block: prover_job.last_block,
+                data: Some(
+                    serde_json::from_value(prover_job.job_data)
+                        .expect("Failed to parse prover job from db"),
+                ),
+            },
+        )
     } else {
-        Ok(HttpResponse::Ok().json(ProverInputResponse {
-            job_id: 0,
-            first_block: BlockNumber(0),
-            last_block: BlockNumber(0),
-            data: None,
-        }))
+        payload_response(
+            &req,
+            &ProverInputResponse {
+                job_id: 0,
+                first_block: BlockNumber(0),
+                last_block: BlockNumber(0),
+                data: None,
+            },
+        )
     }
 }
 
+/// Serves the witness of the block to the external proving services. Witness is built from
+/// the stored state if the witness generators haven't prepared it or it was already removed.
+async fn block_witness<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+    req: HttpRequest,
+    block_number: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let block_number = BlockNumber(block_number.into_inner());
+    let mut storage = data.access_storage().await?;
+    let block = data
+        .database
+        .load_block(&mut storage, block_number)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to load block {}: {}", block_number, e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?
+        .ok_or_else(|| {
+            actix_web::error::ErrorNotFound(format!("block {} not found", block_number))
+        })?;
+    let stored_witness = data
+        .database
+        .load_witness(&mut storage, block_number)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to load witness for block {}: {}", block_number, e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    drop(storage);
+
+    let witness = match stored_witness {
+        Some(witness) => {
+            metrics::increment_counter!("prover_server.witness_requests", "source" => "stored");
+            witness
+        }
+        None => {
+            metrics::increment_counter!("prover_server.witness_requests", "source" => "built");
+            let witness = witness_generator::build_witness_on_demand(&data.database, block)
+                .await
+                .map_err(|e| {
+                    vlog::error!("failed to build witness for block {}: {}", block_number, e);
+                    actix_web::error::ErrorInternalServerError("failed to build witness")
+                })?;
+            serde_json::to_value(witness).expect("Witness serialize to json")
+        }
+    };
+
+    payload_response(&req, &witness)
+}
+
 async fn working_on<DB: DatabaseInterface>(
     data: web::Data<AppState<DB>>,
     r: web::Json<WorkingOn>,
@@ -180,8 +283,27 @@ async fn working_on<DB: DatabaseInterface>(
 
 async fn publish<DB: DatabaseInterface>(
     data: web::Data<AppState<DB>>,
-    r: web::Json<ProverOutputRequest>,
+    req: HttpRequest,
+    body: web::Bytes,
 ) -> actix_web::Result<HttpResponse> {
+    let compression =
+        PayloadCompression::from_header(header_value(&req, PAYLOAD_COMPRESSION_HEADER)?)
+            .map_err(actix_web::error::ErrorBadRequest)?;
+    metrics::histogram!(
+        "prover_server.payload_size",
+        body.len() as f64,
+        "direction" => "received",
+        "compression" => compression.as_str()
+    );
+    let (r, content_hash): (ProverOutputRequest, _) =
+        decode_payload(&body, compression, header_value(&req, CONTENT_HASH_HEADER)?).map_err(
+            |e| {
+                vlog::warn!("Received a malformed proof payload: {}", e);
+                metrics::increment_counter!("prover_server.malformed_payloads");
+                actix_web::error::ErrorBadRequest(e)
+            },
+        )?;
+
     let mut storage = data
         .access_storage()
         .await
@@ -194,7 +316,13 @@ async fn publish<DB: DatabaseInterface>(
                 r.first_block
             );
             data.database
-                .store_proof(&mut storage, r.job_id, r.first_block, single_proof)
+                .store_proof(
+                    &mut storage,
+                    r.job_id,
+                    r.first_block,
+                    single_proof,
+                    r.proof_system,
+                )
                 .await
         }
         JobResultData::AggregatedBlockProof(aggregated_proof) => {
@@ -204,6 +332,31 @@ async fn publish<DB: DatabaseInterface>(
                 r.first_block,
                 r.last_block
             );
+            let mut blocks = Vec::new();
+            for block_number in *r.first_block..=*r.last_block {
+                let block = data
+                    .database
+                    .load_block(&mut storage, BlockNumber(block_number))
+                    .await
+                    .map_err(actix_web::error::ErrorInternalServerError)?
+                    .ok_or_else(|| {
+                        actix_web::error::ErrorBadRequest(format!(
+                            "block {} not found",
+                            block_number
+                        ))
+                    })?;
+                blocks.push(block);
+            }
+            if let Err(reason) =
+                check_proof_commitments(&blocks, &aggregated_proof.serialize_aggregated_proof())
+            {
+                vlog::error!("Received an invalid proof for job {}: {}", r.job_id, reason);
+                data.database
+                    .trip_circuit_breaker(&mut storage, "prover_server", &reason)
+                    .await
+                    .map_err(actix_web::error::ErrorInternalServerError)?;
+                return Err(actix_web::error::ErrorBadRequest(reason));
+            }
             data.database
                 .store_aggregated_proof(
                     &mut storage,
@@ -211,6 +364,7 @@ async fn publish<DB: DatabaseInterface>(
                     r.first_block,
                     r.last_block,
                     aggregated_proof,
+                    r.proof_system,
                 )
                 .await
         }
@@ -224,10 +378,85 @@ async fn publish<DB: DatabaseInterface>(
         };
         return Err(actix_web::error::ErrorInternalServerError(message));
     }
+    // Failure to cache the proof doesn't affect its publication.
+    if let JobResultData::BlockProof(single_proof) = &r.data {
+        if let Err(e) = cache_proof(
+            &data.database,
+            &mut storage,
+            r.first_block,
+            single_proof,
+            r.proof_system,
+        )
+        .await
+        {
+            vlog::warn!(
+                "Failed to cache the proof for block {}: {}",
+                r.first_block,
+                e
+            );
+        }
+    }
+    data.database
+        .store_job_result_hash(
+            &mut storage,
+            r.job_id,
+            content_hash,
+            compression.as_str(),
+            body.len(),
+        )
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to store the content hash of the proof: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
 
     Ok(HttpResponse::Ok().finish())
 }
 
+/// Caches the single block proof by the block commitment, so the block is not proven again if
+/// it's re-queued.
+async fn cache_proof<DB: DatabaseInterface>(
+    database: &DB,
+    storage: &mut zksync_storage::StorageProcessor<'_>,
+    block_number: BlockNumber,
+    proof: &SingleProof,
+    proof_system: ProofSystemVersion,
+) -> anyhow::Result<()> {
+    let block = database
+        .load_block(storage, block_number)
+        .await?
+        .ok_or_else(|| anyhow::format_err!("block not found"))?;
+    database
+        .store_cached_proof(storage, block.block_commitment, proof, proof_system)
+        .await
+}
+
+/// Checks that the aggregated proof is created for the blocks stored by the server,
+/// the same way as the contract does it when the proof is published: the public input
+/// of every block proof must be equal to the block commitment with the highest 3 bits cleared.
+///
+/// Mismatch means that the state used by provers diverged from the one committed to the contract.
+fn check_proof_commitments(blocks: &[Block], proof: &EncodedAggregatedProof) -> Result<(), String> {
+    let input_mask = U256::max_value() >> 3;
+    if proof.individual_vk_inputs.len() < blocks.len() {
+        return Err(format!(
+            "proof contains {} block inputs, {} expected",
+            proof.individual_vk_inputs.len(),
+            blocks.len()
+        ));
+    }
+    for (block, input) in blocks.iter().zip(&proof.individual_vk_inputs) {
+        let commitment = U256::from_big_endian(block.block_commitment.as_bytes());
+        if *input & input_mask != commitment & input_mask {
+            return Err(format!(
+                "proof input for block {} doesn't match the block commitment {:?}",
+                block.block_number, block.block_commitment
+            ));
+        }
+    }
+    Ok(())
+}
+
 async fn stopped<DB: DatabaseInterface>(
     data: web::Data<AppState<DB>>,
     prover_name: web::Json<String>,
@@ -284,6 +513,34 @@ async fn required_replicas<DB: DatabaseInterface>(
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// Query of the `/api/internal/prover/backlog` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvingBacklogQuery {
+    /// Amount of currently running prover entities, if known to the autoscaler.
+    current_count: Option<u32>,
+}
+
+async fn proving_backlog<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+    query: web::Query<ProvingBacklogQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let mut oracle = data.scaler_oracle.write().await;
+
+    let backlog = oracle.backlog(query.current_count).await.map_err(|e| {
+        vlog::warn!("Failed to load the proving backlog: {}", e);
+        actix_web::error::ErrorInternalServerError("storage layer error")
+    })?;
+
+    metrics::gauge!("prover.backlog.pending_jobs", backlog.pending_jobs as f64);
+    metrics::gauge!("prover.backlog.jobs_per_hour", backlog.jobs_per_hour as f64);
+    metrics::gauge!(
+        "prover.backlog.recommended_provers",
+        backlog.recommended_provers as f64
+    );
+
+    Ok(HttpResponse::Ok().json(backlog))
+}
+
 async fn update_prover_job_queue_loop<DB: DatabaseInterface>(
     database: DB,
     prepare_data_interval: Duration,
@@ -313,7 +570,27 @@ async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow:
         let witness_for_next_single_block = database
             .load_witness(&mut connection, next_single_block_to_add)
             .await?;
-        if let Some(witness) = witness_for_next_single_block {
+        let cached_proof = match witness_for_next_single_block {
+            Some(_) => {
+                load_cached_proof(&database, &mut connection, next_single_block_to_add).await?
+            }
+            None => None,
+        };
+        if let Some((proof, proof_system)) = cached_proof {
+            vlog::info!(
+                "Proof for block {} is taken from the cache",
+                next_single_block_to_add
+            );
+            metrics::increment_counter!("prover_server.proof_cache_hits");
+            database
+                .store_proof_from_cache(
+                    &mut connection,
+                    next_single_block_to_add,
+                    &proof,
+                    proof_system,
+                )
+                .await?;
+        } else if let Some(witness) = witness_for_next_single_block {
             let prover_data: ProverData =
                 serde_json::from_value(witness).expect("incorrect single block witness");
             let block_size = prover_data.operations.len();
@@ -386,6 +663,21 @@ async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow:
     Ok(())
 }
 
+/// Returns the cached proof of the block with the same commitment, if it was proven before.
+async fn load_cached_proof<DB: DatabaseInterface>(
+    database: &DB,
+    connection: &mut zksync_storage::StorageProcessor<'_>,
+    block_number: BlockNumber,
+) -> anyhow::Result<Option<(SingleProof, ProofSystemVersion)>> {
+    let block = match database.load_block(connection, block_number).await? {
+        Some(block) => block,
+        None => return Ok(None),
+    };
+    database
+        .load_cached_proof(connection, block.block_commitment)
+        .await
+}
+
 pub fn run_prover_server<DB: DatabaseInterface>(
     database: DB,
     prover_api_opts: ProverApiConfig,
@@ -440,9 +732,14 @@ pub fn run_prover_server<DB: DatabaseInterface>(
                 // Start HTTP server.
                 let secret_auth = prover_api_opts.secret_auth.clone();
                 let idle_provers = core_opts.idle_provers;
+                let backlog_drain_time = core_opts.backlog_drain_time();
                 HttpServer::new(move || {
-                    let app_state =
-                        AppState::new(secret_auth.clone(), database.clone(), idle_provers);
+                    let app_state = AppState::new(
+                        secret_auth.clone(),
+                        database.clone(),
+                        idle_provers,
+                        backlog_drain_time,
+                    );
 
                     let auth = HttpAuthentication::bearer(move |req, credentials| async {
                         let secret_auth = req
@@ -460,8 +757,13 @@ pub fn run_prover_server<DB: DatabaseInterface>(
                     App::new()
                         .wrap(auth)
                         .app_data(web::Data::new(app_state))
+                        .app_data(web::PayloadConfig::new(MAX_PUBLISH_PAYLOAD_SIZE))
                         .route("/status", web::get().to(status))
                         .route("/get_job", web::get().to(get_job::<DB>))
+                        .route(
+                            "/block_witness/{block_number}",
+                            web::get().to(block_witness::<DB>),
+                        )
                         .route("/working_on", web::post().to(working_on::<DB>))
                         .route("/publish", web::post().to(publish::<DB>))
                         .route("/stopped", web::post().to(stopped::<DB>))
@@ -469,6 +771,10 @@ pub fn run_prover_server<DB: DatabaseInterface>(
                             "/api/internal/prover/replicas",
                             web::post().to(required_replicas::<DB>),
                         )
+                        .route(
+                            "/api/internal/prover/backlog",
+                            web::get().to(proving_backlog::<DB>),
+                        )
                 })
                 .bind(&prover_api_opts.bind_addr())
                 .expect("failed to bind")
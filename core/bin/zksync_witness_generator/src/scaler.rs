@@ -1,7 +1,44 @@
 //! Module with utilities for prover scaler service.
 
+// Built-in deps
+use std::time::Duration;
+// External deps
+use serde::{Deserialize, Serialize};
 // Workspace deps
+use zksync_storage::prover::records::ProverBacklog;
+// Local deps
 use crate::database_interface::DatabaseInterface;
+
+/// Period over which the throughput of the provers is measured.
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// Change of the amount of provers recommended to the autoscaler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScalingRecommendation {
+    ScaleUp,
+    ScaleDown,
+    Hold,
+}
+
+/// State of the proving backlog along with the amount of provers required to prove it in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProvingBacklog {
+    /// Jobs not taken by any prover yet.
+    pub pending_jobs: u32,
+    pub in_progress_jobs: u32,
+    /// Provers working on the jobs in progress.
+    pub active_provers: u32,
+    /// Jobs finished during the last hour.
+    pub jobs_per_hour: u32,
+    /// Hours required to prove the pending and in progress jobs at the current throughput,
+    /// `None` if no jobs were finished during the last hour.
+    pub estimated_hours: Option<f64>,
+    pub recommended_provers: u32,
+    /// Recommendation relative to the amount of the currently running provers.
+    pub recommendation: ScalingRecommendation,
+}
+
 /// Scaler oracle provides information for prover scaler
 /// service about required amount of provers for server
 /// to operate optimally.
@@ -12,11 +49,18 @@ pub struct ScalerOracle<DB: DatabaseInterface> {
 
     /// Number of idle provers running for faster up-scaling.
     idle_provers: u32,
+
+    /// Time in which the provers are expected to prove the backlog.
+    backlog_drain_time: Duration,
 }
 
 impl<DB: DatabaseInterface> ScalerOracle<DB> {
-    pub fn new(db: DB, idle_provers: u32) -> Self {
-        Self { db, idle_provers }
+    pub fn new(db: DB, idle_provers: u32, backlog_drain_time: Duration) -> Self {
+        Self {
+            db,
+            idle_provers,
+            backlog_drain_time,
+        }
     }
 
     /// Decides how many prover entities should be created depending on the amount of pending blocks.
@@ -31,4 +75,113 @@ impl<DB: DatabaseInterface> ScalerOracle<DB> {
 
         Ok(provers_required)
     }
+
+    /// Returns the proving backlog and the amount of provers required to prove it within
+    /// the configured drain time at the current throughput. Recommendation is made against
+    /// `current_provers`, or the amount of the active provers if it's not known.
+    pub async fn backlog(
+        &mut self,
+        current_provers: Option<u32>,
+    ) -> anyhow::Result<ProvingBacklog> {
+        let mut storage = self.db.acquire_connection().await?;
+        let done_since = chrono::Utc::now() - chrono::Duration::from_std(THROUGHPUT_WINDOW)?;
+        let backlog = self
+            .db
+            .load_prover_backlog(&mut storage, done_since)
+            .await?;
+
+        Ok(proving_backlog(
+            backlog,
+            current_provers,
+            self.idle_provers,
+            self.backlog_drain_time,
+        ))
+    }
+}
+
+fn proving_backlog(
+    backlog: ProverBacklog,
+    current_provers: Option<u32>,
+    idle_provers: u32,
+    drain_time: Duration,
+) -> ProvingBacklog {
+    let window_hours = THROUGHPUT_WINDOW.as_secs_f64() / 3600.0;
+    let jobs_per_hour = backlog.done_jobs as f64 / window_hours;
+    let remaining_jobs = (backlog.idle_jobs + backlog.in_progress_jobs) as f64;
+
+    let estimated_hours = if jobs_per_hour > 0.0 {
+        Some(remaining_jobs / jobs_per_hour)
+    } else {
+        None
+    };
+    // Without the throughput data we fall back to a prover per job.
+    let required_provers = match estimated_hours {
+        Some(hours) if backlog.active_provers > 0 => {
+            let drain_hours = drain_time.as_secs_f64() / 3600.0;
+            (hours * backlog.active_provers as f64 / drain_hours).ceil() as u32
+        }
+        _ => remaining_jobs as u32,
+    };
+    let recommended_provers = std::cmp::max(required_provers, idle_provers);
+
+    let current_provers = current_provers.unwrap_or(backlog.active_provers);
+    let recommendation = match recommended_provers.cmp(&current_provers) {
+        std::cmp::Ordering::Greater => ScalingRecommendation::ScaleUp,
+        std::cmp::Ordering::Less => ScalingRecommendation::ScaleDown,
+        std::cmp::Ordering::Equal => ScalingRecommendation::Hold,
+    };
+
+    ProvingBacklog {
+        pending_jobs: backlog.idle_jobs,
+        in_progress_jobs: backlog.in_progress_jobs,
+        active_provers: backlog.active_provers,
+        jobs_per_hour: jobs_per_hour.round() as u32,
+        estimated_hours,
+        recommended_provers,
+        recommendation,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backlog(idle_jobs: u32, in_progress_jobs: u32, done_jobs: u32) -> ProverBacklog {
+        ProverBacklog {
+            idle_jobs,
+            in_progress_jobs,
+            done_jobs,
+            active_provers: in_progress_jobs,
+        }
+    }
+
+    /// Checks that the provers are recommended based on the current throughput.
+    #[test]
+    fn scaling_recommendation() {
+        let drain_time = Duration::from_secs(3600);
+
+        // 2 provers finish 4 jobs per hour, 10 more jobs need 5 provers to be finished in an hour.
+        let output = proving_backlog(backlog(8, 2, 4), None, 1, drain_time);
+        assert_eq!(output.jobs_per_hour, 4);
+        assert_eq!(output.estimated_hours, Some(2.5));
+        assert_eq!(output.recommended_provers, 5);
+        assert_eq!(output.recommendation, ScalingRecommendation::ScaleUp);
+
+        // Same backlog may be proven in 3 hours by 2 provers.
+        let output = proving_backlog(backlog(8, 2, 4), Some(2), 1, drain_time * 3);
+        assert_eq!(output.recommended_provers, 2);
+        assert_eq!(output.recommendation, ScalingRecommendation::Hold);
+
+        // No throughput data, a prover per job is recommended.
+        let output = proving_backlog(backlog(3, 0, 0), Some(1), 1, drain_time);
+        assert_eq!(output.estimated_hours, None);
+        assert_eq!(output.recommended_provers, 3);
+        assert_eq!(output.recommendation, ScalingRecommendation::ScaleUp);
+
+        // Idle provers are kept running when there is no backlog.
+        let output = proving_backlog(backlog(0, 0, 10), Some(4), 1, drain_time);
+        assert_eq!(output.estimated_hours, Some(0.0));
+        assert_eq!(output.recommended_provers, 1);
+        assert_eq!(output.recommendation, ScalingRecommendation::ScaleDown);
+    }
 }
@@ -1,23 +1,28 @@
 // Built-in
 use std::clone::Clone;
+use std::collections::HashSet;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 // External uses
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use tokio::sync::RwLock;
 use tokio::time::sleep;
 // Workspace uses
 use zksync_crypto::params::account_tree_depth;
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
 use zksync_storage::chain::tree_cache::records::AccountTreeCache;
-use zksync_storage::prover::records::{StorageBlockWitness, StorageProverJobQueue, StoredProof};
+use zksync_storage::prover::records::{
+    ProverBacklog, StorageBlockWitness, StorageProverJobQueue, StoredProof,
+};
 use zksync_storage::StorageProcessor;
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
-    prover::{ProverJob, ProverJobStatus, ProverJobType},
-    AccountId, AccountMap, AccountTree, AccountUpdates, Address, BlockNumber,
+    prover::{
+        ProofSystemVersion, ProverJob, ProverJobStatus, ProverJobType, SINGLE_PROOF_JOB_PRIORITY,
+    },
+    AccountId, AccountMap, AccountTree, AccountUpdates, Address, BlockNumber, H256,
 };
 // Local uses
 use crate::DatabaseInterface;
@@ -31,6 +36,12 @@ pub struct MockDatabase {
     blocks: Arc<RwLock<Vec<Block>>>,
     account_tree_cache: Arc<RwLock<AccountTreeCache>>,
     accounts_state: Arc<RwLock<(u32, AccountMap)>>,
+    /// Sources and reasons of the circuit breaker trips.
+    circuit_breaker_trips: Arc<RwLock<Vec<(String, String)>>>,
+    /// Content hashes of the published results by the job ID.
+    job_result_hashes: Arc<RwLock<Vec<(i32, H256)>>>,
+    /// Single block proofs by the block commitment.
+    proof_cache: Arc<RwLock<Vec<(H256, serde_json::Value, ProofSystemVersion)>>>,
 }
 
 impl MockDatabase {
@@ -49,6 +60,9 @@ impl MockDatabase {
                 tree_cache_binary,
             })),
             accounts_state: Arc::new(RwLock::new((0, accounts))),
+            circuit_breaker_trips: Arc::new(RwLock::new(Vec::new())),
+            job_result_hashes: Arc::new(RwLock::new(Vec::new())),
+            proof_cache: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
@@ -75,6 +89,14 @@ impl MockDatabase {
     pub async fn add_block(&self, block: Block) {
         self.blocks.write().await.push(block);
     }
+
+    pub async fn circuit_breaker_trips(&self) -> Vec<(String, String)> {
+        self.circuit_breaker_trips.read().await.clone()
+    }
+
+    pub async fn job_result_hashes(&self) -> Vec<(i32, H256)> {
+        self.job_result_hashes.read().await.clone()
+    }
 }
 
 #[async_trait::async_trait]
@@ -148,6 +170,30 @@ impl DatabaseInterface for MockDatabase {
         Ok(count as u32)
     }
 
+    async fn load_prover_backlog(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        done_since: DateTime<Utc>,
+    ) -> anyhow::Result<ProverBacklog> {
+        let jobs = &self.prover_job_queue.read().await.1;
+        let mut backlog = ProverBacklog::default();
+        let mut active_provers = HashSet::new();
+        for job in jobs {
+            match ProverJobStatus::from_number(job.job_status)? {
+                ProverJobStatus::Idle => backlog.idle_jobs += 1,
+                ProverJobStatus::InProgress => {
+                    backlog.in_progress_jobs += 1;
+                    active_provers.insert(job.updated_by.as_str());
+                }
+                ProverJobStatus::Done if job.updated_at >= done_since => backlog.done_jobs += 1,
+                ProverJobStatus::Done => {}
+            }
+        }
+        backlog.active_provers = active_provers.len() as u32;
+
+        Ok(backlog)
+    }
+
     async fn load_aggregated_op_that_affects_block(
         &self,
         _: &mut StorageProcessor<'_>,
@@ -161,7 +207,7 @@ impl DatabaseInterface for MockDatabase {
         &self,
         _: &mut StorageProcessor<'_>,
         block_number: BlockNumber,
-    ) -> anyhow::Result<Option<SingleProof>> {
+    ) -> anyhow::Result<Option<(SingleProof, ProofSystemVersion)>> {
         let proofs = self.proofs.read().await;
         let single_proof = proofs
             .iter()
@@ -272,6 +318,7 @@ impl DatabaseInterface for MockDatabase {
         job_id: i32,
         block_number: BlockNumber,
         proof: &SingleProof,
+        proof_system: ProofSystemVersion,
     ) -> anyhow::Result<()> {
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
         let prover_job = prover_job_queue.iter_mut().find(|job| job.id == job_id);
@@ -285,12 +332,89 @@ impl DatabaseInterface for MockDatabase {
             block_number: i64::from(*block_number),
             created_at: Utc::now(),
             proof: serde_json::to_value(proof).unwrap(),
+            proof_system: proof_system.to_string(),
         };
         self.proofs.write().await.push(proof);
 
         Ok(())
     }
 
+    async fn store_cached_proof(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        block_commitment: H256,
+        proof: &SingleProof,
+        proof_system: ProofSystemVersion,
+    ) -> anyhow::Result<()> {
+        let mut proof_cache = self.proof_cache.write().await;
+        if !proof_cache
+            .iter()
+            .any(|(commitment, ..)| *commitment == block_commitment)
+        {
+            proof_cache.push((
+                block_commitment,
+                serde_json::to_value(proof).unwrap(),
+                proof_system,
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn load_cached_proof(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        block_commitment: H256,
+    ) -> anyhow::Result<Option<(SingleProof, ProofSystemVersion)>> {
+        let proof = self
+            .proof_cache
+            .read()
+            .await
+            .iter()
+            .find(|(commitment, ..)| *commitment == block_commitment)
+            .map(|(_, proof, proof_system)| {
+                (
+                    serde_json::from_value(proof.clone()).unwrap(),
+                    *proof_system,
+                )
+            });
+
+        Ok(proof)
+    }
+
+    async fn store_proof_from_cache(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        block_number: BlockNumber,
+        proof: &SingleProof,
+        proof_system: ProofSystemVersion,
+    ) -> anyhow::Result<()> {
+        let mut prover_job_queue = self.prover_job_queue.write().await;
+        let id = prover_job_queue.0;
+        prover_job_queue.0 += 1;
+        prover_job_queue.1.push(StorageProverJobQueue {
+            id,
+            job_status: ProverJobStatus::Done.to_number(),
+            job_priority: SINGLE_PROOF_JOB_PRIORITY,
+            job_type: ProverJobType::SingleProof.to_string(),
+            created_at: Utc::now(),
+            updated_by: "server_proof_cache".to_string(),
+            updated_at: Utc::now(),
+            first_block: i64::from(*block_number),
+            last_block: i64::from(*block_number),
+            job_data: serde_json::Value::Null,
+        });
+
+        self.proofs.write().await.push(StoredProof {
+            block_number: i64::from(*block_number),
+            created_at: Utc::now(),
+            proof: serde_json::to_value(proof).unwrap(),
+            proof_system: proof_system.to_string(),
+        });
+
+        Ok(())
+    }
+
     async fn store_aggregated_proof(
         &self,
         _: &mut StorageProcessor<'_>,
@@ -298,10 +422,27 @@ impl DatabaseInterface for MockDatabase {
         _first_block: BlockNumber,
         _last_block: BlockNumber,
         _proof: &AggregatedProof,
+        _proof_system: ProofSystemVersion,
     ) -> anyhow::Result<()> {
         unreachable!();
     }
 
+    async fn store_job_result_hash(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        job_id: i32,
+        content_hash: H256,
+        _compression: &str,
+        _payload_size: usize,
+    ) -> anyhow::Result<()> {
+        self.job_result_hashes
+            .write()
+            .await
+            .push((job_id, content_hash));
+
+        Ok(())
+    }
+
     async fn record_prover_stop(
         &self,
         _: &mut StorageProcessor<'_>,
@@ -394,4 +535,18 @@ impl DatabaseInterface for MockDatabase {
 
         Ok(())
     }
+
+    async fn trip_circuit_breaker(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        source: &str,
+        reason: &str,
+    ) -> anyhow::Result<()> {
+        self.circuit_breaker_trips
+            .write()
+            .await
+            .push((source.to_string(), reason.to_string()));
+
+        Ok(())
+    }
 }
@@ -12,12 +12,13 @@ use zksync_config::{
     ProverConfig,
 };
 use zksync_crypto::franklin_crypto::bellman::pairing::ff::{PrimeField, PrimeFieldRepr};
+use zksync_crypto::proof::EncodedAggregatedProof;
 use zksync_prover::{client, ApiClient};
 use zksync_prover_utils::api::ProverInputRequest;
-use zksync_types::{block::Block, AccountId, BlockNumber, TokenId, H256};
+use zksync_types::{block::Block, AccountId, BlockNumber, TokenId, H256, U256};
 // Local deps
 use super::mock::MockDatabase;
-use crate::{run_prover_server, DatabaseInterface};
+use crate::{check_proof_commitments, run_prover_server, DatabaseInterface};
 
 const CORRECT_PROVER_SECRET_AUTH: &str = "42";
 const INCORRECT_PROVER_SECRET_AUTH: &str = "123";
@@ -39,10 +40,12 @@ impl Default for MockProverOptions {
                 cycle_wait: 500,
                 request_timeout: 10,
                 die_after_proof: false,
+                payload_compression: "zstd".into(),
             },
             core: Core {
                 gone_timeout: 60000,
                 idle_provers: 1,
+                backlog_drain_time: 3600,
             },
             witness_generator: WitnessGenerator {
                 prepare_data_interval: 500,
@@ -161,6 +164,25 @@ async fn test_api_client_simple_simulation(prover_name: &str, database: MockData
     assert!(witness.is_some());
 }
 
+/// Checks that the aggregated proof is only accepted if its inputs match the block commitments.
+#[tokio::test]
+async fn test_check_proof_commitments() {
+    let block = get_test_block().await;
+    let commitment = U256::from_big_endian(block.block_commitment.as_bytes());
+    let proof = |inputs: Vec<U256>| EncodedAggregatedProof {
+        individual_vk_inputs: inputs,
+        ..Default::default()
+    };
+
+    // Inputs are compared without the highest 3 bits, and padding inputs are ignored.
+    let masked = commitment & (U256::max_value() >> 3);
+    assert!(check_proof_commitments(&[block.clone()], &proof(vec![masked])).is_ok());
+    assert!(check_proof_commitments(&[block.clone()], &proof(vec![masked, U256::zero()])).is_ok());
+
+    assert!(check_proof_commitments(&[block.clone()], &proof(vec![masked + 1])).is_err());
+    assert!(check_proof_commitments(&[block.clone()], &proof(vec![])).is_err());
+}
+
 pub async fn get_test_block() -> Block {
     let (circuit_tree, accounts) = MockDatabase::get_default_tree_and_accounts();
     let validator_account_id = AccountId(0);
@@ -204,11 +204,19 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
                 .load_block(&mut storage, block)
                 .await?
                 .expect("Block for witness generator must exist");
-            assert_eq!(
-                storage_block.new_root_hash,
-                circuit_account_tree.root_hash(),
-                "account tree root hash restored incorrectly"
-            );
+            let root_hash = circuit_account_tree.root_hash();
+            if storage_block.new_root_hash != root_hash {
+                // Restored state doesn't match the state the block was created for,
+                // so the block production must be halted until the divergence is investigated.
+                let reason = format!(
+                    "account tree root hash restored incorrectly for block {}: expected {}, restored {}",
+                    block, storage_block.new_root_hash, root_hash
+                );
+                self.database
+                    .trip_circuit_breaker(&mut storage, "witness_generator", &reason)
+                    .await?;
+                panic!("{}", reason);
+            }
         }
         metrics::histogram!("witness_generator", start.elapsed(), "stage" => "ensure_root_hash");
 
@@ -303,6 +311,49 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
     }
 }
 
+/// Builds the witness for the block from the committed state of the previous block, without
+/// touching the account tree cache used by the witness generators. Used to serve the witnesses
+/// on demand, e.g. for the blocks whose stored witnesses were already removed.
+pub async fn build_witness_on_demand<DB: DatabaseInterface>(
+    database: &DB,
+    block: Block,
+) -> anyhow::Result<ProverData> {
+    let start = Instant::now();
+    let mut storage = database.acquire_connection().await?;
+    let previous_block = block.block_number - 1;
+    let (_, accounts) = database
+        .load_committed_state(&mut storage, Some(previous_block))
+        .await?;
+    let expected_root_hash = database
+        .load_block(&mut storage, previous_block)
+        .await?
+        .map(|block| block.new_root_hash);
+    drop(storage);
+
+    // Restoring the tree takes a while for the large states, so it's done off the async runtime.
+    let witness = tokio::task::spawn_blocking(move || -> anyhow::Result<ProverData> {
+        let mut circuit_account_tree = CircuitAccountTree::new(account_tree_depth());
+        for (id, account) in accounts {
+            circuit_account_tree.insert(*id, account.into());
+        }
+        let root_hash = circuit_account_tree.root_hash();
+        if let Some(expected_root_hash) = expected_root_hash {
+            anyhow::ensure!(
+                root_hash == expected_root_hash,
+                "account tree root hash restored incorrectly for block {}: expected {}, restored {}",
+                previous_block,
+                expected_root_hash,
+                root_hash
+            );
+        }
+        Ok(build_block_witness(&mut circuit_account_tree, &block)?.into())
+    })
+    .await??;
+
+    metrics::histogram!("witness_generator", start.elapsed(), "stage" => "build_witness_on_demand");
+    Ok(witness)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,6 +109,18 @@ impl ClientRequestBuilder {
         }
     }
 
+    /// Enable the bearer authentication.
+    ///
+    /// See [reqwest] documentation for details
+    ///
+    /// [reqwest]: https://docs.rs/reqwest/latest/reqwest/struct.RequestBuilder.html#method.bearer_auth
+    pub fn bearer_auth(self, token: &str) -> Self {
+        Self {
+            inner: self.inner.bearer_auth(token),
+            url: self.url,
+        }
+    }
+
     /// Constructs the Request and sends it to the target URL, returning a future Response.
     ///
     /// This method takes account of the responses structure and the error handling specific.
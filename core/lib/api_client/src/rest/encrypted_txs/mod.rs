@@ -0,0 +1,36 @@
+//! Encrypted mempool part of API implementation.
+//!
+//! Users submit their transfers encrypted to the sealing key of the operator, see
+//! `zksync_types::encrypted_tx`. The server stores the blobs as is until the block formation.
+
+// Built-in uses
+
+// External uses
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_utils::ZeroPrefixHexSerde;
+
+// Local uses
+use crate::rest::client::{Client, Result as ClientResult};
+
+// Data transfer objects.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedTx {
+    /// Signed transfer encrypted to the sealing key of the operator.
+    #[serde(with = "ZeroPrefixHexSerde")]
+    pub blob: Vec<u8>,
+}
+
+const ENCRYPTED_TXS_SCOPE: &str = "/api/encrypted_txs/v0.1/";
+
+impl Client {
+    /// Submits the encrypted transfer and returns the identifier of the stored blob.
+    pub async fn submit_encrypted_tx(&self, blob: Vec<u8>) -> ClientResult<i64> {
+        self.post_with_scope(ENCRYPTED_TXS_SCOPE, "submit")
+            .body(&EncryptedTx { blob })
+            .send()
+            .await
+    }
+}
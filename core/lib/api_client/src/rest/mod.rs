@@ -1,4 +1,11 @@
 pub mod client;
+pub mod encrypted_txs;
 pub mod error;
 pub mod forced_exit_requests;
+pub mod names;
+pub mod nft_factory;
+pub mod onchain_auth;
+pub mod private_txs;
+pub mod read_tokens;
+pub mod travel_rule;
 pub mod v02;
@@ -0,0 +1,91 @@
+//! Account names part of API implementation.
+//!
+//! The owner of the address registers a human-readable name for it. The registration fee is
+//! paid by the transfer of the owner to themselves, which is submitted along with the
+//! registration. The name resolves to the address once the transfer is executed.
+
+// Built-in uses
+
+// External uses
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_api_types::{v02::fee::ApiFee, TxWithSignature};
+use zksync_types::{
+    tx::{PackedEthSignature, TxHash},
+    Address, TokenLike,
+};
+
+// Local uses
+use crate::rest::client::{Client, Result as ClientResult};
+
+// Data transfer objects.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountNameRegistration {
+    pub name: String,
+    /// Transfer of the owner to themselves paying for the registration.
+    pub fee_tx: TxWithSignature,
+    /// Signature of the owner of the address over the registration message.
+    pub signature: PackedEthSignature,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountNameFeeRequest {
+    pub address: Address,
+    pub token: TokenLike,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum AccountNameStatus {
+    /// The fee transaction is not executed yet.
+    Pending,
+    /// The name resolves to the address.
+    Active,
+    /// The fee transaction has failed, the name can be registered again.
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountName {
+    pub name: String,
+    pub address: Address,
+    pub fee_tx_hash: TxHash,
+    pub status: AccountNameStatus,
+    pub created_at: DateTime<Utc>,
+}
+
+const NAMES_SCOPE: &str = "/api/names/v0.1/";
+
+impl Client {
+    pub async fn register_account_name(
+        &self,
+        registration: AccountNameRegistration,
+    ) -> ClientResult<AccountName> {
+        self.post_with_scope(NAMES_SCOPE, "register")
+            .body(&registration)
+            .send()
+            .await
+    }
+
+    pub async fn account_name_fee(
+        &self,
+        address: Address,
+        token: TokenLike,
+    ) -> ClientResult<ApiFee> {
+        self.post_with_scope(NAMES_SCOPE, "fee")
+            .body(&AccountNameFeeRequest { address, token })
+            .send()
+            .await
+    }
+
+    pub async fn account_name(&self, name: &str) -> ClientResult<AccountName> {
+        self.get_with_scope(NAMES_SCOPE, &format!("resolve/{}", name))
+            .send()
+            .await
+    }
+}
@@ -0,0 +1,61 @@
+//! NFT factory registration part of API implementation.
+//!
+//! The creator registers their factory on L1 by letting the factory call
+//! `registerNFTFactoryCreator` of the Governance contract. The service checks the transaction
+//! and the signature, so the factory is used for the withdrawals of the creator's NFTs as soon
+//! as the registration is confirmed.
+
+// Built-in uses
+
+// External uses
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_types::{tx::PackedEthSignature, AccountId, Address, H256};
+
+// Local uses
+use crate::rest::client::{Client, Result as ClientResult};
+
+// Data transfer objects.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NFTFactoryRegistration {
+    /// Hash of the L1 transaction that called `registerNFTFactoryCreator`.
+    pub eth_tx_hash: H256,
+    pub creator_id: AccountId,
+    pub creator_address: Address,
+    pub factory_address: Address,
+    /// Signature of the creator passed to `registerNFTFactoryCreator`.
+    pub signature: PackedEthSignature,
+}
+
+/// Factory used for the withdrawals of the creator's NFTs.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisteredNFTFactory {
+    pub creator_id: AccountId,
+    pub creator_address: Address,
+    pub factory_address: Address,
+    pub created_at: DateTime<Utc>,
+}
+
+const NFT_FACTORY_SCOPE: &str = "/api/nft_factory/v0.1/";
+
+impl Client {
+    pub async fn register_nft_factory(
+        &self,
+        registration: NFTFactoryRegistration,
+    ) -> ClientResult<RegisteredNFTFactory> {
+        self.post_with_scope(NFT_FACTORY_SCOPE, "register")
+            .body(&registration)
+            .send()
+            .await
+    }
+
+    pub async fn nft_factory(&self, creator_id: AccountId) -> ClientResult<RegisteredNFTFactory> {
+        self.get_with_scope(NFT_FACTORY_SCOPE, &format!("creators/{}", creator_id))
+            .send()
+            .await
+    }
+}
@@ -0,0 +1,98 @@
+//! Onchain `ChangePubKey` authorization part of API implementation.
+//!
+//! Contract wallets can't sign the `ChangePubKey` message, so they authorize the new public
+//! key hash by calling `setAuthPubkeyHash` of the zkSync contract. The service prepares the
+//! calldata of that call and keeps the `ChangePubKey` transaction until the auth fact appears
+//! on L1, then submits it automatically.
+
+// Built-in uses
+
+// External uses
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_types::{
+    tx::{ChangePubKey, TxHash},
+    Address, Nonce, PubKeyHash,
+};
+
+// Local uses
+use crate::rest::client::{Client, Result as ClientResult};
+
+// Data transfer objects.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthCalldataRequest {
+    pub pub_key_hash: PubKeyHash,
+    /// Nonce of the account the `ChangePubKey` transaction will be sent with.
+    pub nonce: Nonce,
+}
+
+/// Transaction the account has to send to authorize the new public key hash.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthCalldata {
+    pub contract_address: Address,
+    /// Hex-encoded calldata of the `setAuthPubkeyHash` call.
+    pub calldata: String,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum OnchainAuthStatus {
+    /// The auth fact is not set on L1 yet.
+    Waiting,
+    /// The auth fact was found and the transaction was submitted.
+    Released,
+    /// The transaction was rejected on submission.
+    Failed,
+}
+
+/// State of the `ChangePubKey` transaction queued until its auth fact appears on L1.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedChangePubKey {
+    pub tx_hash: TxHash,
+    pub status: OnchainAuthStatus,
+    pub fail_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+const ONCHAIN_AUTH_SCOPE: &str = "/api/onchain_auth/v0.1/";
+
+impl Client {
+    pub async fn onchain_auth_calldata(
+        &self,
+        pub_key_hash: PubKeyHash,
+        nonce: Nonce,
+    ) -> ClientResult<AuthCalldata> {
+        self.post_with_scope(ONCHAIN_AUTH_SCOPE, "calldata")
+            .body(&AuthCalldataRequest {
+                pub_key_hash,
+                nonce,
+            })
+            .send()
+            .await
+    }
+
+    pub async fn queue_onchain_change_pubkey(
+        &self,
+        tx: ChangePubKey,
+    ) -> ClientResult<QueuedChangePubKey> {
+        self.post_with_scope(ONCHAIN_AUTH_SCOPE, "queue")
+            .body(&tx)
+            .send()
+            .await
+    }
+
+    pub async fn queued_change_pubkey(&self, tx_hash: TxHash) -> ClientResult<QueuedChangePubKey> {
+        self.get_with_scope(
+            ONCHAIN_AUTH_SCOPE,
+            &format!("queue/{}", tx_hash.to_string()),
+        )
+        .send()
+        .await
+    }
+}
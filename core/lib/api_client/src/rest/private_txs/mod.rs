@@ -0,0 +1,35 @@
+//! Private transactions part of API implementation.
+//!
+//! Clients registered by the operator submit their batches with the bearer tokens. Batches are
+//! validated as the public ones, but are not shown by the public API until they are executed.
+
+// Built-in uses
+
+// External uses
+
+// Workspace uses
+use zksync_api_types::{
+    v02::transaction::{IncomingTxBatch, SubmitBatchResponse},
+    TxWithSignature,
+};
+use zksync_types::tx::EthBatchSignatures;
+
+// Local uses
+use crate::rest::client::{Client, Result as ClientResult};
+
+const PRIVATE_TXS_SCOPE: &str = "/api/private_txs/v0.1/";
+
+impl Client {
+    pub async fn submit_private_txs_batch(
+        &self,
+        token: &str,
+        txs: Vec<TxWithSignature>,
+        signature: Option<EthBatchSignatures>,
+    ) -> ClientResult<SubmitBatchResponse> {
+        self.post_with_scope(PRIVATE_TXS_SCOPE, "batches")
+            .bearer_auth(token)
+            .body(&IncomingTxBatch { txs, signature })
+            .send()
+            .await
+    }
+}
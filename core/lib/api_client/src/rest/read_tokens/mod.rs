@@ -0,0 +1,136 @@
+//! Read tokens part of API implementation.
+//!
+//! The owner of the address signs the grant naming the third party and the expiration time, and
+//! receives the bearer token for it. The token gives the third party access to the full history of
+//! the account including the travel rule metadata of its transfers, until it expires or is revoked
+//! by the owner.
+
+// Built-in uses
+
+// External uses
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_api_types::v02::transaction::Transaction;
+use zksync_types::{
+    tx::{PackedEthSignature, TxHash},
+    Address,
+};
+
+// Local uses
+use crate::rest::{
+    client::{Client, Result as ClientResult},
+    travel_rule::TravelRuleMetadata,
+};
+
+// Data transfer objects.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadTokenGrant {
+    pub address: Address,
+    /// Name of the third party the access is granted to.
+    pub grantee: String,
+    /// Expiration time of the token, the fractions of a second are ignored.
+    pub expires_at: DateTime<Utc>,
+    /// Signature of the owner of the address over the grant message.
+    pub signature: PackedEthSignature,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadToken {
+    pub id: i64,
+    /// Bearer token, it's not stored by the server and can't be retrieved again.
+    pub token: String,
+    pub address: Address,
+    pub grantee: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadTokenRevocation {
+    pub address: Address,
+    pub id: i64,
+    /// Signature of the owner of the address over the revocation message.
+    pub signature: PackedEthSignature,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadTokenHistoryQuery {
+    /// Hash of the last transaction of the previous page.
+    pub before: Option<TxHash>,
+    pub limit: Option<u32>,
+}
+
+/// Transaction of the account history, along with the travel rule metadata if it's a transfer
+/// the metadata was attached to.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadTokenHistoryEntry {
+    pub tx: Transaction,
+    pub travel_rule: Option<TravelRuleMetadata>,
+}
+
+/// Account the read token gives access to, along with the grant details.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadTokenInfo {
+    pub id: i64,
+    pub address: Address,
+    pub grantee: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+const READ_TOKENS_SCOPE: &str = "/api/read_tokens/v0.1/";
+
+impl Client {
+    pub async fn issue_read_token(&self, grant: ReadTokenGrant) -> ClientResult<ReadToken> {
+        self.post_with_scope(READ_TOKENS_SCOPE, "issue")
+            .body(&grant)
+            .send()
+            .await
+    }
+
+    pub async fn revoke_read_token(&self, revocation: ReadTokenRevocation) -> ClientResult<()> {
+        self.post_with_scope(READ_TOKENS_SCOPE, "revoke")
+            .body(&revocation)
+            .send()
+            .await
+    }
+
+    pub async fn read_token_info(&self, token: &str) -> ClientResult<ReadTokenInfo> {
+        self.get_with_scope(READ_TOKENS_SCOPE, "account")
+            .bearer_auth(token)
+            .send()
+            .await
+    }
+
+    /// Loads the transactions of the account from the latest to the earliest.
+    pub async fn read_token_account_history(
+        &self,
+        token: &str,
+        query: &ReadTokenHistoryQuery,
+    ) -> ClientResult<Vec<ReadTokenHistoryEntry>> {
+        self.get_with_scope(READ_TOKENS_SCOPE, "account/history")
+            .bearer_auth(token)
+            .query(query)
+            .send()
+            .await
+    }
+
+    /// Loads the transfers of the account having the travel rule metadata.
+    pub async fn read_token_travel_rule_metadata(
+        &self,
+        token: &str,
+        query: &ReadTokenHistoryQuery,
+    ) -> ClientResult<Vec<TravelRuleMetadata>> {
+        self.get_with_scope(READ_TOKENS_SCOPE, "account/travel_rule")
+            .bearer_auth(token)
+            .query(query)
+            .send()
+            .await
+    }
+}
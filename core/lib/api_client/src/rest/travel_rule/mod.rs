@@ -0,0 +1,79 @@
+//! Travel rule part of API implementation.
+//!
+//! The sender of the large transfer submits it along with the travel rule metadata encrypted
+//! for the recipient. Both parties of the transfer can retrieve the metadata by signing the
+//! access message with their Ethereum keys.
+
+// Built-in uses
+
+// External uses
+use chrono::{DateTime, Utc};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_api_types::TxWithSignature;
+use zksync_types::{
+    tx::{PackedEthSignature, TxHash},
+    Address, TokenId,
+};
+use zksync_utils::{BigUintSerdeAsRadix10Str, ZeroPrefixHexSerde};
+
+// Local uses
+use crate::rest::client::{Client, Result as ClientResult};
+
+// Data transfer objects.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TravelRuleTransfer {
+    pub tx: TxWithSignature,
+    /// Metadata encrypted by the sender for the recipient.
+    #[serde(with = "ZeroPrefixHexSerde")]
+    pub metadata: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TravelRuleMetadataRequest {
+    /// Signature of the party of the transfer over the access message.
+    pub signature: PackedEthSignature,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TravelRuleMetadata {
+    pub tx_hash: TxHash,
+    pub from: Address,
+    pub to: Address,
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    #[serde(with = "ZeroPrefixHexSerde")]
+    pub metadata: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+}
+
+const TRAVEL_RULE_SCOPE: &str = "/api/travel_rule/v0.1/";
+
+impl Client {
+    pub async fn submit_travel_rule_transfer(
+        &self,
+        transfer: TravelRuleTransfer,
+    ) -> ClientResult<TxHash> {
+        self.post_with_scope(TRAVEL_RULE_SCOPE, "transfer")
+            .body(&transfer)
+            .send()
+            .await
+    }
+
+    pub async fn travel_rule_metadata(
+        &self,
+        tx_hash: TxHash,
+        signature: PackedEthSignature,
+    ) -> ClientResult<TravelRuleMetadata> {
+        self.post_with_scope(TRAVEL_RULE_SCOPE, &format!("metadata/{}", tx_hash))
+            .body(&TravelRuleMetadataRequest { signature })
+            .send()
+            .await
+    }
+}
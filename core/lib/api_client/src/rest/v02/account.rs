@@ -1,10 +1,11 @@
 use crate::rest::client::{Client, Result};
 
 use zksync_api_types::v02::{
+    account::{AccountStateQuery, AccountsBulkRequest, AccountsDiffRequest},
     pagination::{ApiEither, PaginationQuery},
     Response,
 };
-use zksync_types::{tx::TxHash, SerialId};
+use zksync_types::{tx::TxHash, Address, BlockNumber, SerialId, TokenId};
 
 impl Client {
     pub async fn account_info(
@@ -29,6 +30,33 @@ impl Client {
         .await
     }
 
+    /// Loads the state of the account after the finalized block.
+    pub async fn account_info_at_block(
+        &self,
+        account_id_or_address: &str,
+        block_number: BlockNumber,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}", account_id_or_address),
+        )
+        .query(&AccountStateQuery {
+            at_block: Some(block_number),
+        })
+        .send()
+        .await
+    }
+
+    /// Loads the signing key, the pending onchain auth and the 2FA policy of the account.
+    pub async fn account_activation(&self, account_id_or_address: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/activation", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
     pub async fn account_txs(
         &self,
         pagination_query: &PaginationQuery<ApiEither<TxHash>>,
@@ -56,4 +84,77 @@ impl Client {
         .send()
         .await
     }
+
+    pub async fn account_provisional_deposits(
+        &self,
+        account_id_or_address: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/deposits/pending", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn account_refundable_deposits(
+        &self,
+        account_id_or_address: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/deposits/refundable", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn account_nfts(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TokenId>>,
+        account_id_or_address: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/nfts", account_id_or_address),
+        )
+        .query(pagination_query)
+        .send()
+        .await
+    }
+
+    pub async fn account_minted_nfts(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<u32>>,
+        account_id_or_address: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/nfts/minted", account_id_or_address),
+        )
+        .query(pagination_query)
+        .send()
+        .await
+    }
+
+    pub async fn accounts_bulk(&self, addresses: Vec<Address>) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "accounts/bulk")
+            .body(&AccountsBulkRequest { addresses })
+            .send()
+            .await
+    }
+
+    pub async fn accounts_diff(
+        &self,
+        addresses: Vec<Address>,
+        from_block: BlockNumber,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "accounts/diff")
+            .body(&AccountsDiffRequest {
+                addresses,
+                from_block,
+            })
+            .send()
+            .await
+    }
 }
@@ -13,6 +13,33 @@ impl Client {
             .await
     }
 
+    pub async fn block_pubdata(&self, block_position: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("blocks/{}/pubdata", block_position),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn block_account_updates(&self, block_position: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("blocks/{}/account_updates", block_position),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn block_op_stats(&self, block_position: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("blocks/{}/op_stats", block_position),
+        )
+        .send()
+        .await
+    }
+
     pub async fn transaction_in_block(&self, block_number: u32, tx_index: u32) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
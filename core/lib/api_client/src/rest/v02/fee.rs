@@ -4,7 +4,7 @@ use zksync_api_types::v02::{
     fee::{ApiTxFeeTypes, BatchFeeRequest, TxFeeRequest, TxInBatchFeeRequest},
     Response,
 };
-use zksync_types::{Address, TokenLike};
+use zksync_types::{Address, TokenId, TokenLike};
 
 impl Client {
     pub async fn get_txs_fee(
@@ -18,6 +18,27 @@ impl Client {
                 tx_type,
                 address,
                 token_like,
+                nft: None,
+                unwrap_weth: false,
+            })
+            .send()
+            .await
+    }
+
+    pub async fn get_withdraw_nft_fee(
+        &self,
+        tx_type: ApiTxFeeTypes,
+        address: Address,
+        token_like: TokenLike,
+        nft: TokenId,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "fee")
+            .body(&TxFeeRequest {
+                tx_type,
+                address,
+                token_like,
+                nft: Some(nft),
+                unwrap_weth: false,
             })
             .send()
             .await
@@ -36,4 +57,10 @@ impl Client {
             .send()
             .await
     }
+
+    pub async fn get_fee_scaling(&self) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "fee/scaling")
+            .send()
+            .await
+    }
 }
@@ -0,0 +1,23 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{stats::DailyStatsQuery, Response};
+
+impl Client {
+    pub async fn daily_stats(&self, days: Option<u32>) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "stats/daily")
+            .query(&DailyStatsQuery { days })
+            .send()
+            .await
+    }
+
+    pub async fn tvl(&self) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "stats/tvl")
+            .send()
+            .await
+    }
+
+    pub async fn total_fees(&self) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "stats/fees")
+            .send()
+            .await
+    }
+}
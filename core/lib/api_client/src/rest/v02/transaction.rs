@@ -1,9 +1,18 @@
 use crate::rest::client::{Client, Result};
 use zksync_api_types::{
-    v02::{transaction::IncomingTxBatch, Response},
+    v02::{
+        transaction::{
+            CancelWithdrawalRequest, IncomingNFTTransferBatch, IncomingTxBatch, TxWaitLevel,
+            TxWaitQuery, WithdrawalTimelockRequest,
+        },
+        Response,
+    },
     TxWithSignature,
 };
-use zksync_types::tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash, ZkSyncTx};
+use zksync_types::{
+    tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash, ZkSyncTx},
+    AccountId,
+};
 
 impl Client {
     pub async fn submit_tx(
@@ -28,6 +37,22 @@ impl Client {
             .await
     }
 
+    pub async fn submit_nft_transfer_batch(
+        &self,
+        transfers: Vec<TxWithSignature>,
+        fee: TxWithSignature,
+        signature: Option<EthBatchSignatures>,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions/batches/nft")
+            .body(&IncomingNFTTransferBatch {
+                transfers,
+                fee,
+                signature,
+            })
+            .send()
+            .await
+    }
+
     pub async fn tx_status(&self, tx_hash: TxHash) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
@@ -37,6 +62,22 @@ impl Client {
         .await
     }
 
+    /// Waits until the transaction reaches the given level, or the timeout in seconds expires.
+    pub async fn wait_tx(
+        &self,
+        tx_hash: TxHash,
+        level: Option<TxWaitLevel>,
+        timeout: Option<u64>,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/{}/wait", tx_hash.to_string()),
+        )
+        .query(&TxWaitQuery { level, timeout })
+        .send()
+        .await
+    }
+
     pub async fn tx_data(&self, tx_hash: TxHash) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
@@ -54,4 +95,33 @@ impl Client {
         .send()
         .await
     }
+
+    pub async fn set_withdrawal_timelock(
+        &self,
+        request: &WithdrawalTimelockRequest,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions/withdrawalTimelock")
+            .body(request)
+            .send()
+            .await
+    }
+
+    pub async fn cancel_withdrawal(&self, request: &CancelWithdrawalRequest) -> Result<Response> {
+        self.post_with_scope(
+            super::API_V02_SCOPE,
+            "transactions/withdrawalTimelock/cancel",
+        )
+        .body(request)
+        .send()
+        .await
+    }
+
+    pub async fn withdrawal_timelock(&self, account_id: AccountId) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/withdrawalTimelock/{}", account_id),
+        )
+        .send()
+        .await
+    }
 }
@@ -1,35 +1,40 @@
 pub use either::Either;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use zksync_types::{
     tx::{TxEthSignatureVariant, TxHash},
     ZkSyncTx, H256,
 };
 
+pub mod submit_error;
 pub mod v02;
+pub mod v03;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TxWithSignature {
+    #[schemars(with = "serde_json::Value")]
     pub tx: ZkSyncTx,
     #[serde(default)]
+    #[schemars(with = "serde_json::Value")]
     pub signature: TxEthSignatureVariant,
 }
 
 /// Combined identifier of the priority operations for the lookup.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub enum PriorityOpLookupQuery {
     /// Query priority operation using zkSync hash, which is calculated based on the priority operation metadata.
-    BySyncHash(TxHash),
+    BySyncHash(#[schemars(with = "String")] TxHash),
     /// Query priority operation using the corresponding Ethereum transaction hash.
-    ByEthHash(H256),
+    ByEthHash(#[schemars(with = "String")] H256),
     /// Query priority operation using any of both hashes.
-    ByAnyHash(TxHash),
+    ByAnyHash(#[schemars(with = "String")] TxHash),
 }
 
 /// Status of core server.
 /// Server should have stable connection to the database (main and replica)
 /// and connection to the ethereum node
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 pub struct CoreStatus {
     pub main_database_available: bool,
     pub replica_database_available: bool,
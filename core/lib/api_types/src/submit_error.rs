@@ -0,0 +1,62 @@
+//! Stable codes of the transaction rejections.
+//!
+//! Errors of the transaction submission are reported with the same codes by the JSON-RPC and
+//! REST APIs, so the clients may handle the rejections without matching the error messages.
+//! Codes are never reused: a new rejection reason gets a new code.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Reason of the transaction rejection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SubmitErrorCode {
+    /// Fee of the transaction or of the batch is lower than the required one.
+    InsufficientFee = 1001,
+    /// Nonce of the transaction is already used by the account.
+    WrongNonce = 1002,
+    /// L2 or Ethereum signature is missing or incorrect.
+    InvalidSignature = 1003,
+    /// Token is not supported, or can't be used for paying fees.
+    TokenDisabled = 1004,
+    /// Account can't send the funds, e.g. it is frozen by the operator.
+    AccountLocked = 1005,
+    /// Transaction is malformed or has the wrong parameters.
+    InvalidTx = 1006,
+    /// Transaction type or option is not accepted by the server.
+    UnsupportedTx = 1007,
+    /// Batch size, signatures or sponsor limits are exceeded.
+    LimitExceeded = 1008,
+    /// Server can't accept the transactions at the moment, the submission may be retried.
+    Unavailable = 1009,
+    Internal = 1010,
+}
+
+impl SubmitErrorCode {
+    /// Numeric value of the code.
+    pub fn code(self) -> u16 {
+        self as u16
+    }
+}
+
+/// Structured details of the transaction rejection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitErrorDetails {
+    /// Numeric value of the `kind`.
+    pub code: u16,
+    pub kind: SubmitErrorCode,
+    /// Index of the rejected transaction in the batch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+}
+
+impl SubmitErrorDetails {
+    pub fn new(kind: SubmitErrorCode, index: Option<usize>) -> Self {
+        Self {
+            code: kind.code(),
+            kind,
+            index,
+        }
+    }
+}
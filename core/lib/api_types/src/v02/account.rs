@@ -1,50 +1,210 @@
 use std::collections::{BTreeMap, HashMap};
 
+use chrono::{DateTime, Utc};
 use num::{BigUint, ToPrimitive};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use zksync_types::{
-    AccountId, Address, BlockNumber, Nonce, PriorityOp, PubKeyHash, TokenId, ZkSyncPriorityOp,
+    AccountFreeze, AccountId, Address, BlockNumber, Nonce, PriorityOp, PubKeyHash, SerialId,
+    TokenId, ZkSyncPriorityOp, H256,
 };
 use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper};
 
 use super::pagination::PaginationDirection;
 use super::token::NFT;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountState {
+    /// Address of the account, it's the resolved address if the account was requested
+    /// by the ENS name.
+    #[serde(default)]
+    #[schemars(with = "String")]
+    pub address: Address,
     pub depositing: DepositingAccountBalances,
     pub committed: Option<Account>,
     pub finalized: Option<Account>,
+    /// Set while the account is frozen by the operator.
+    #[serde(default)]
+    pub freeze: Option<AccountFreezeStatus>,
+}
+
+/// Compliance hold of the account. The frozen account can't send funds to other accounts,
+/// but it can still withdraw them.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountFreezeStatus {
+    pub reason: String,
+    pub frozen_at: DateTime<Utc>,
+}
+
+impl From<AccountFreeze> for AccountFreezeStatus {
+    fn from(freeze: AccountFreeze) -> Self {
+        Self {
+            reason: freeze.reason,
+            frozen_at: freeze.frozen_at,
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Account {
+    #[schemars(with = "u32")]
     pub account_id: AccountId,
+    #[schemars(with = "String")]
     pub address: Address,
+    #[schemars(with = "u32")]
     pub nonce: Nonce,
+    #[schemars(with = "String")]
     pub pub_key_hash: PubKeyHash,
+    #[schemars(with = "u32")]
     pub last_update_in_block: BlockNumber,
+    #[schemars(with = "BTreeMap<String, String>")]
     pub balances: BTreeMap<String, BigUintSerdeWrapper>,
+    #[schemars(with = "BTreeMap<String, NFT>")]
     pub nfts: BTreeMap<TokenId, NFT>,
+    #[schemars(with = "BTreeMap<String, NFT>")]
     pub minted_nfts: BTreeMap<TokenId, NFT>,
     pub account_type: Option<EthAccountType>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+/// Request of the states of several accounts at once.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsBulkRequest {
+    #[schemars(with = "Vec<String>")]
+    pub addresses: Vec<Address>,
+}
+
+/// Committed and finalized states of the account from the bulk request.
+/// The states are `None` if the account doesn't exist.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkAccountState {
+    #[schemars(with = "String")]
+    pub address: Address,
+    pub committed: Option<Account>,
+    pub finalized: Option<Account>,
+}
+
+/// Request of the changes of the accounts made after the block known to the client.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsDiffRequest {
+    #[schemars(with = "Vec<String>")]
+    pub addresses: Vec<Address>,
+    #[schemars(with = "u32")]
+    pub from_block: BlockNumber,
+}
+
+/// Changes of the committed account state. Only the changed balances are listed,
+/// the public key hash is set only if it was changed.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDiff {
+    #[schemars(with = "u32")]
+    pub account_id: AccountId,
+    #[schemars(with = "String")]
+    pub address: Address,
+    #[schemars(with = "u32")]
+    pub nonce: Nonce,
+    #[schemars(with = "Option<String>")]
+    pub pub_key_hash: Option<PubKeyHash>,
+    #[schemars(with = "BTreeMap<String, String>")]
+    pub balances: BTreeMap<String, BigUintSerdeWrapper>,
+    /// NFTs received by the account.
+    #[schemars(with = "BTreeMap<String, NFT>")]
+    pub nfts: BTreeMap<TokenId, NFT>,
+    /// NFTs that no longer belong to the account.
+    #[schemars(with = "Vec<u32>")]
+    pub removed_nfts: Vec<TokenId>,
+    #[schemars(with = "BTreeMap<String, NFT>")]
+    pub minted_nfts: BTreeMap<TokenId, NFT>,
+}
+
+/// Changes of the accounts made in the blocks `(fromBlock, toBlock]`. The accounts without
+/// changes are omitted. The `toBlock` should be used as `fromBlock` of the next request.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsDiff {
+    #[schemars(with = "u32")]
+    pub from_block: BlockNumber,
+    #[schemars(with = "u32")]
+    pub to_block: BlockNumber,
+    pub accounts: Vec<AccountDiff>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum AccountAddressOrId {
-    Address(Address),
-    Id(AccountId),
+    Address(#[schemars(with = "String")] Address),
+    Id(#[schemars(with = "u32")] AccountId),
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
 pub enum EthAccountType {
     Owned,
     CREATE2,
-    No2FA(Option<PubKeyHash>),
+    No2FA(#[schemars(with = "Option<String>")] Option<PubKeyHash>),
+}
+
+/// 2FA policy applied to the L2 transactions of the account.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum TwoFactorAuthPolicy {
+    /// Transactions have to be signed with the Ethereum key in addition to the L2 key.
+    Required,
+    /// Transactions are signed with the L2 key only.
+    Disabled,
+    /// Ethereum signature is not required for the transactions signed with the given L2 key.
+    DisabledForPubKey(#[schemars(with = "String")] PubKeyHash),
+    /// CREATE2 accounts have no Ethereum key, so the transactions are signed with the L2 key only.
+    Create2,
+}
+
+impl From<Option<EthAccountType>> for TwoFactorAuthPolicy {
+    fn from(account_type: Option<EthAccountType>) -> Self {
+        match account_type {
+            None | Some(EthAccountType::Owned) => Self::Required,
+            Some(EthAccountType::CREATE2) => Self::Create2,
+            Some(EthAccountType::No2FA(None)) => Self::Disabled,
+            Some(EthAccountType::No2FA(Some(pub_key_hash))) => {
+                Self::DisabledForPubKey(pub_key_hash)
+            }
+        }
+    }
+}
+
+/// Activation status of the account used by the wallets to drive the onboarding.
+///
+/// The account is activated once its signing key is set by the `ChangePubKey` transaction.
+/// Transactions authorized onchain are pending until the auth fact is set on L1, the L1
+/// fields are `null` if the Ethereum node is not available to the server.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountActivation {
+    #[schemars(with = "String")]
+    pub address: Address,
+    /// `null` if the account doesn't exist yet.
+    #[schemars(with = "Option<u32>")]
+    pub account_id: Option<AccountId>,
+    /// Committed nonce, i.e. the nonce of the next `ChangePubKey` transaction.
+    #[schemars(with = "u32")]
+    pub nonce: Nonce,
+    pub pub_key_set: bool,
+    #[schemars(with = "String")]
+    pub pub_key_hash: PubKeyHash,
+    pub two_factor_auth: TwoFactorAuthPolicy,
+    /// Set if the `ChangePubKey` transaction with the onchain auth is queued until the auth
+    /// fact is set on L1, or the auth fact for the current nonce is being reset.
+    pub onchain_auth_pending: bool,
+    /// Set if the auth fact for the current nonce is set on L1.
+    pub auth_fact_set: Option<bool>,
+    /// Unix timestamp of the requested reset of the auth fact for the current nonce, `0` if
+    /// the reset is not requested. The new auth fact can be set 24 hours after the request.
+    pub auth_fact_reset_time: Option<u64>,
 }
 
 /// Information about ongoing deposits for certain recipient address.
@@ -53,7 +213,7 @@ pub enum EthAccountType {
 /// currently awaiting confirmations, this information is approximate:
 /// blocks on Ethereum can be reverted, and final list of executed deposits
 /// can differ from this estimation.
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OngoingDepositsResp {
     pub deposits: Vec<OngoingDeposit>,
@@ -61,10 +221,11 @@ pub struct OngoingDepositsResp {
 
 /// Flattened `PriorityOp` object representing a deposit operation.
 /// Used in the `OngoingDepositsResp`.
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OngoingDeposit {
     pub received_on_block: u64,
+    #[schemars(with = "u32")]
     pub token_id: TokenId,
     pub amount: u128,
 }
@@ -92,21 +253,79 @@ impl OngoingDeposit {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DepositingFunds {
     #[serde(with = "BigUintSerdeAsRadix10Str")]
+    #[schemars(with = "String")]
     pub amount: BigUint,
     pub expected_accept_block: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DepositingAccountBalances {
     pub balances: HashMap<String, DepositingFunds>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Deposit observed on Ethereum that is not yet executed by the network.
+///
+/// Deposits are reported starting from zero confirmations, thus the information is
+/// provisional: the Ethereum block containing the deposit can still be reverted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvisionalDeposit {
+    pub serial_id: SerialId,
+    #[schemars(with = "String")]
+    pub eth_tx_hash: H256,
+    #[schemars(with = "u32")]
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    #[schemars(with = "String")]
+    pub amount: BigUint,
+    pub eth_block: u64,
+    /// Number of confirmations received so far, `null` if the latest Ethereum block is unknown.
+    pub confirmations: Option<u64>,
+    /// Number of confirmations required for the deposit to be accepted by the network.
+    pub required_confirmations: u64,
+}
+
+/// Deposit that can't be credited by the network and has to be refunded on Ethereum.
+///
+/// The funds are returned to the recipient through the exodus mode: the deposit has to be
+/// canceled via `cancelOutstandingDepositsForExodusMode`, after that the funds can be taken out
+/// via `withdrawPendingBalance`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RefundableDeposit {
+    pub serial_id: SerialId,
+    #[schemars(with = "String")]
+    pub eth_tx_hash: H256,
+    #[schemars(with = "String")]
+    pub from: Address,
+    #[schemars(with = "String")]
+    pub to: Address,
+    #[schemars(with = "u32")]
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    #[schemars(with = "String")]
+    pub amount: BigUint,
+    /// Reason why the deposit can't be credited, e.g. `expired`.
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query of the account state. If `atBlock` is set, the state after this finalized block
+/// is returned instead of the current one.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStateQuery {
+    #[serde(alias = "at_block")]
+    #[schemars(with = "Option<u32>")]
+    pub at_block: Option<BlockNumber>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IncomingAccountTxsQuery {
     pub from: String,
@@ -1,25 +1,110 @@
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use zksync_crypto::{serialization::FrSerde, Fr};
-use zksync_types::{BlockNumber, H256};
+use zksync_types::{block::OpTypeStats, AccountId, AccountUpdate, BlockNumber, H256};
+use zksync_utils::ZeroPrefixHexSerde;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum BlockStatus {
     Committed,
     Finalized,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockInfo {
+    #[schemars(with = "u32")]
     pub block_number: BlockNumber,
     #[serde(with = "FrSerde")]
+    #[schemars(with = "String")]
     pub new_state_root: Fr,
     pub block_size: u64,
+    #[schemars(with = "Option<String>")]
     pub commit_tx_hash: Option<H256>,
+    #[schemars(with = "Option<String>")]
     pub verify_tx_hash: Option<H256>,
     pub committed_at: DateTime<Utc>,
     pub finalized_at: Option<DateTime<Utc>>,
     pub status: BlockStatus,
 }
+
+/// Public data of the block as it is sent to the smart contract.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockPubdata {
+    #[schemars(with = "u32")]
+    pub block_number: BlockNumber,
+    /// Version of the public data encoding.
+    pub version: u32,
+    /// Public data of the operations padded with the noops up to the block size.
+    #[serde(with = "ZeroPrefixHexSerde")]
+    #[schemars(with = "String")]
+    pub pubdata: Vec<u8>,
+    /// Commitment of the block, can be recomputed from the public data and the block header.
+    #[schemars(with = "String")]
+    pub commitment: H256,
+}
+
+/// Updates of the account tree applied in the block, in the order they were applied.
+/// Applying them to the state after the previous block gives the state after this one.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockAccountUpdates {
+    #[schemars(with = "u32")]
+    pub block_number: BlockNumber,
+    pub updates: Vec<BlockAccountUpdate>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockAccountUpdate {
+    #[schemars(with = "u32")]
+    pub account_id: AccountId,
+    #[schemars(with = "serde_json::Value")]
+    pub update: AccountUpdate,
+}
+
+/// Resources consumed by every type of the operations executed in the block, the chunks left
+/// unused are accounted as the `Noop` operations.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockOpStats {
+    #[schemars(with = "u32")]
+    pub block_number: BlockNumber,
+    pub block_size: u64,
+    pub op_types: Vec<BlockOpTypeStats>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockOpTypeStats {
+    pub op_type: String,
+    pub count: u32,
+    pub chunks: u64,
+    pub pubdata_bytes: u64,
+    /// Estimated gas of processing the operations when the block is committed.
+    pub commit_gas: u64,
+    /// Estimated gas of processing the operations when the block is executed.
+    pub verify_gas: u64,
+    /// Gas of the public data of the operations in the commit calldata.
+    pub pubdata_gas: u64,
+    /// Total estimated L1 gas consumed by the operations.
+    pub estimated_gas: u64,
+}
+
+impl From<OpTypeStats> for BlockOpTypeStats {
+    fn from(stats: OpTypeStats) -> Self {
+        Self {
+            estimated_gas: stats.estimated_gas(),
+            op_type: stats.op_type,
+            count: stats.count,
+            chunks: stats.chunks,
+            pubdata_bytes: stats.pubdata_bytes,
+            commit_gas: stats.commit_gas,
+            verify_gas: stats.verify_gas,
+            pubdata_gas: stats.pubdata_gas,
+        }
+    }
+}
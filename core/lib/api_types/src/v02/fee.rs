@@ -1,16 +1,22 @@
 use num::BigUint;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use zksync_types::{tokens::ChangePubKeyFeeTypeArg, Address, BatchFee, Fee, TokenLike, TxFeeTypes};
+use zksync_types::{
+    tokens::ChangePubKeyFeeTypeArg, Address, BatchFee, Fee, TokenId, TokenLike, TxFeeTypes,
+};
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiFee {
     #[serde(with = "BigUintSerdeAsRadix10Str")]
+    #[schemars(with = "String")]
     pub gas_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
+    #[schemars(with = "String")]
     pub zkp_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
+    #[schemars(with = "String")]
     pub total_fee: BigUint,
 }
 
@@ -34,7 +40,7 @@ impl From<BatchFee> for ApiFee {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub enum ApiTxFeeTypes {
     /// Fee for the `Withdraw` transaction.
     Withdraw,
@@ -43,7 +49,7 @@ pub enum ApiTxFeeTypes {
     /// Fee for the `Transfer` operation.
     Transfer,
     /// Fee for the `ChangePubKey` operation.
-    ChangePubKey(ChangePubKeyFeeTypeArg),
+    ChangePubKey(#[schemars(with = "serde_json::Value")] ChangePubKeyFeeTypeArg),
     /// Fee for the `ForcedExit` transaction.
     ForcedExit,
     /// Fee for the `MintNFT` transaction.
@@ -71,24 +77,62 @@ impl From<ApiTxFeeTypes> for TxFeeTypes {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TxFeeRequest {
     pub tx_type: ApiTxFeeTypes,
+    #[schemars(with = "String")]
     pub address: Address,
+    #[schemars(with = "serde_json::Value")]
     pub token_like: TokenLike,
+    /// Withdrawn NFT, its factory affects the fee of `WithdrawNFT`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<u32>")]
+    pub nft: Option<TokenId>,
+    /// Whether the withdrawn WETH is delivered as native ETH, the unwrapping is charged
+    /// on top of the `Withdraw` fee.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unwrap_weth: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TxInBatchFeeRequest {
     pub tx_type: ApiTxFeeTypes,
+    #[schemars(with = "String")]
     pub address: Address,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BatchFeeRequest {
     pub transactions: Vec<TxInBatchFeeRequest>,
+    #[schemars(with = "serde_json::Value")]
     pub token_like: TokenLike,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiFeeScalingLevel {
+    /// Load of the pipeline in percent of the full load starting from which the level applies.
+    pub load_percent: u32,
+    /// Multiplier of the fees of L2 operations in percent.
+    pub fee_percent: u32,
+}
+
+/// Fee scaling schedule and the current load of the pipeline.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiFeeScaling {
+    /// Whether the fees are scaled with the pipeline load.
+    pub enabled: bool,
+    pub mempool_size: u32,
+    pub prover_backlog: u32,
+    /// Current load of the pipeline in percent of the full load.
+    pub load_percent: u32,
+    /// Current multiplier of the fees of L2 operations in percent.
+    pub fee_percent: u32,
+    pub mempool_capacity: u32,
+    pub prover_capacity: u32,
+    pub schedule: Vec<ApiFeeScalingLevel>,
+}
@@ -1,4 +1,5 @@
 use either::Either;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize, Serializer};
 use std::str::FromStr;
 use thiserror::Error;
@@ -6,7 +7,7 @@ use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, SerialId, TokenI
 
 pub const MAX_LIMIT: u32 = 100;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum PaginationDirection {
     Newer,
@@ -75,7 +76,7 @@ pub fn parse_query<T: FromStr + Serialize>(
     })
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PaginationQuery<Id> {
     pub from: Id,
@@ -83,7 +84,7 @@ pub struct PaginationQuery<Id> {
     pub direction: PaginationDirection,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PaginationDetails<F: Serialize> {
     pub from: F,
@@ -92,7 +93,7 @@ pub struct PaginationDetails<F: Serialize> {
     pub count: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Paginated<T: Sized + Serialize, F: Serialize> {
     pub list: Vec<T>,
@@ -121,21 +122,39 @@ impl<T: Sized + Serialize, F: Serialize> Paginated<T, F> {
 
 #[derive(Debug, Serialize)]
 pub struct BlockAndTxHash {
+    #[schemars(with = "u32")]
     pub block_number: BlockNumber,
     pub tx_hash: ApiEither<TxHash>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct PendingOpsRequest {
+    #[schemars(with = "String")]
     pub address: Address,
+    #[schemars(with = "Option<u32>")]
     pub account_id: Option<AccountId>,
     pub serial_id: ApiEither<SerialId>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct AccountTxsRequest {
+    #[schemars(with = "String")]
     pub address: Address,
     pub tx_hash: ApiEither<TxHash>,
+    #[schemars(with = "Option<u32>")]
     pub token: Option<TokenId>,
+    #[schemars(with = "Option<String>")]
     pub second_address: Option<Address>,
 }
+
+#[derive(Debug, Serialize)]
+pub struct AccountNFTsRequest {
+    pub account_id: AccountId,
+    pub token_id: ApiEither<TokenId>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NFTCollectionRequest {
+    pub creator_id: AccountId,
+    pub serial_id: ApiEither<u32>,
+}
@@ -0,0 +1,35 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use zksync_types::{AccountId, Address};
+
+use super::{block::BlockInfo, token::ApiToken, transaction::Transaction};
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchQuery {
+    pub q: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSearchResult {
+    #[schemars(with = "String")]
+    pub address: Address,
+    /// `None` if the account is not created in the network yet.
+    #[schemars(with = "Option<u32>")]
+    pub account_id: Option<AccountId>,
+}
+
+/// Entity found by the search query, the `type` field tells how the query was interpreted.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", content = "result", rename_all = "camelCase")]
+pub enum SearchResult {
+    /// L2 transaction found by its hash.
+    Transaction(Transaction),
+    /// Priority operation found by its hash or the hash of the L1 transaction.
+    PriorityOperation(Transaction),
+    Account(AccountSearchResult),
+    Block(BlockInfo),
+    Token(ApiToken),
+}
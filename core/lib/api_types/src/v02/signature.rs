@@ -0,0 +1,52 @@
+//! Detached signatures of the API responses.
+//!
+//! If the server is configured with the response signing key, the responses of the account state
+//! and transaction receipt endpoints carry the operator signature in the headers, so the clients
+//! may keep them as the evidence of what the server reported. The signature is the Ethereum signed
+//! message (as created by `eth_sign`) over the canonical JSON of the whole response body.
+
+use serde_json::Value;
+
+/// Header with the signature of the response, a hex-encoded 65 bytes signature prefixed by `0x`.
+pub const RESPONSE_SIGNATURE_HEADER: &str = "X-Response-Signature";
+/// Header with the address of the key used to sign the response.
+pub const RESPONSE_SIGNER_HEADER: &str = "X-Response-Signer";
+
+/// Serializes the value into the canonical JSON: without whitespaces, with the keys of the objects
+/// sorted lexicographically, scalars encoded as `serde_json` does. Canonical JSON of the parsed
+/// response body is the message the response signature is created for.
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical_json(value, &mut out);
+    out
+}
+
+fn write_canonical_json(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<_> = object.iter().collect();
+            entries.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical_json(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(values) => {
+            out.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(value, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
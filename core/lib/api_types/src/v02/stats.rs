@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use num::BigUint;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use zksync_types::TokenId;
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyStatsQuery {
+    /// Number of the latest days to return the statistics for, including the current one.
+    pub days: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAmount {
+    #[schemars(with = "u32")]
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    #[schemars(with = "String")]
+    pub amount: BigUint,
+}
+
+/// Network statistics aggregated over the single day (in UTC).
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyStats {
+    pub day: NaiveDate,
+    /// Numbers of the executed transactions and priority operations by their types.
+    pub tx_counts: BTreeMap<String, u64>,
+    /// Number of accounts that have sent at least one successful transaction.
+    pub active_accounts: u64,
+    /// Fees collected in every token.
+    pub fees: Vec<TokenAmount>,
+    /// Average time between the execution of the transaction and the finalization of its block.
+    /// `None` if none of the transactions of the day is finalized yet.
+    pub avg_finality_secs: Option<f64>,
+}
+
+/// Total value locked in the network in the certain token.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTvl {
+    #[schemars(with = "u32")]
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    #[schemars(with = "String")]
+    pub amount: BigUint,
+    pub updated_at: DateTime<Utc>,
+}
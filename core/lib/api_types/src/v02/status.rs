@@ -1,11 +1,14 @@
 use crate::CoreStatus;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use zksync_types::BlockNumber;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NetworkStatus {
+    #[schemars(with = "u32")]
     pub last_committed: BlockNumber,
+    #[schemars(with = "u32")]
     pub finalized: BlockNumber,
     pub total_transactions: u32,
     pub mempool_size: u32,
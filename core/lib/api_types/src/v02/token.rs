@@ -1,24 +1,29 @@
 use bigdecimal::BigDecimal;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use zksync_types::{AccountId, Address, Token, TokenId, H256};
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiToken {
+    #[schemars(with = "u32")]
     pub id: TokenId,
+    #[schemars(with = "String")]
     pub address: Address,
     pub symbol: String,
     pub decimals: u8,
     pub enabled_for_fees: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TokenPrice {
+    #[schemars(with = "u32")]
     pub token_id: TokenId,
     pub token_symbol: String,
     pub price_in: String,
     pub decimals: u8,
+    #[schemars(with = "String")]
     pub price: BigDecimal,
 }
 
@@ -34,14 +39,19 @@ impl ApiToken {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NFT {
+    #[schemars(with = "u32")]
     pub id: TokenId,
+    #[schemars(with = "String")]
     pub content_hash: H256,
+    #[schemars(with = "u32")]
     pub creator_id: AccountId,
+    #[schemars(with = "String")]
     pub creator_address: Address,
     pub serial_id: u32,
+    #[schemars(with = "String")]
     pub address: Address,
     pub symbol: String,
 }
@@ -60,16 +70,23 @@ impl From<zksync_types::NFT> for NFT {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiNFT {
+    #[schemars(with = "u32")]
     pub id: TokenId,
+    #[schemars(with = "String")]
     pub content_hash: H256,
+    #[schemars(with = "u32")]
     pub creator_id: AccountId,
+    #[schemars(with = "String")]
     pub creator_address: Address,
     pub serial_id: u32,
+    #[schemars(with = "String")]
     pub address: Address,
     pub symbol: String,
+    #[schemars(with = "String")]
     pub current_factory: Address,
+    #[schemars(with = "Option<String>")]
     pub withdrawn_factory: Option<Address>,
 }
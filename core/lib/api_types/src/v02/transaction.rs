@@ -2,6 +2,7 @@ use crate::{v02::block::BlockStatus, TxWithSignature};
 use chrono::serde::ts_milliseconds;
 use chrono::{DateTime, Utc};
 use num::BigUint;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use zksync_types::{
     tx::{
@@ -13,14 +14,37 @@ use zksync_types::{
 };
 use zksync_utils::{BigUintSerdeAsRadix10Str, ZeroPrefixHexSerde};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Header with the key making the transaction submission idempotent: the retries with the same
+/// key return the hash of the originally submitted transaction.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+/// Header with the name of the transfer recipient: the transaction is rejected if the name
+/// doesn't resolve to the recipient address.
+pub const RECIPIENT_NAME_HEADER: &str = "Recipient-Name";
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IncomingTxBatch {
     pub txs: Vec<TxWithSignature>,
+    #[schemars(with = "Option<serde_json::Value>")]
+    pub signature: Option<EthBatchSignatures>,
+}
+
+/// Bundle of the NFT transfers to a single recipient.
+///
+/// The server expands it into the atomic batch of the transfers followed by the fee transfer,
+/// so the bundle is processed and tracked as a single batch.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingNFTTransferBatch {
+    /// Transfers of the NFTs, each of them has to have zero fee.
+    pub transfers: Vec<TxWithSignature>,
+    /// Transfer paying the fee for the whole bundle.
+    pub fee: TxWithSignature,
+    #[schemars(with = "Option<serde_json::Value>")]
     pub signature: Option<EthBatchSignatures>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum TxInBlockStatus {
     Queued,
@@ -38,45 +62,98 @@ impl From<BlockStatus> for TxInBlockStatus {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TxData {
     pub tx: Transaction,
     pub eth_signature: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct L1Receipt {
     pub status: TxInBlockStatus,
+    #[schemars(with = "u64")]
     pub eth_block: EthBlockId,
+    #[schemars(with = "Option<u32>")]
     pub rollup_block: Option<BlockNumber>,
     pub id: SerialId,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct L2Receipt {
     #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    #[schemars(with = "String")]
     pub tx_hash: TxHash,
+    #[schemars(with = "Option<u32>")]
     pub rollup_block: Option<BlockNumber>,
     pub status: TxInBlockStatus,
     pub fail_reason: Option<String>,
+    /// Hash of the L1 transaction which delivered the funds of the executed withdrawal.
+    #[schemars(with = "Option<String>")]
+    pub eth_tx_hash: Option<H256>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(untagged)]
 pub enum Receipt {
     L1(L1Receipt),
     L2(L2Receipt),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Receipt {
+    pub fn status(&self) -> TxInBlockStatus {
+        match self {
+            Receipt::L1(receipt) => receipt.status,
+            Receipt::L2(receipt) => receipt.status,
+        }
+    }
+}
+
+/// Status the transaction is awaited for by the `transactions/{txHash}/wait` endpoint.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum TxWaitLevel {
+    Committed,
+    Finalized,
+}
+
+impl Default for TxWaitLevel {
+    fn default() -> Self {
+        Self::Committed
+    }
+}
+
+impl TxWaitLevel {
+    /// Checks whether the transaction with the given status needs no more waiting: it has
+    /// reached the level, or it is rejected and will never reach it.
+    pub fn is_reached(self, status: TxInBlockStatus) -> bool {
+        match status {
+            TxInBlockStatus::Queued => false,
+            TxInBlockStatus::Committed => self == TxWaitLevel::Committed,
+            TxInBlockStatus::Finalized | TxInBlockStatus::Rejected => true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TxWaitQuery {
+    /// Status to wait for, `committed` by default.
+    pub level: Option<TxWaitLevel>,
+    /// Maximum time to hold the request for, in seconds.
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
     #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    #[schemars(with = "String")]
     pub tx_hash: TxHash,
     pub block_index: Option<u32>,
+    #[schemars(with = "Option<u32>")]
     pub block_number: Option<BlockNumber>,
     pub op: TransactionData,
     pub status: TxInBlockStatus,
@@ -85,52 +162,58 @@ pub struct Transaction {
     pub batch_id: Option<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum TransactionData {
     L1(L1Transaction),
     L2(L2Transaction),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "type")]
 pub enum L2Transaction {
-    Transfer(Box<Transfer>),
-    Withdraw(Box<WithdrawData>),
+    Transfer(#[schemars(with = "serde_json::Value")] Box<Transfer>),
+    Withdraw(#[schemars(with = "serde_json::Value")] Box<WithdrawData>),
     #[doc(hidden)]
-    Close(Box<Close>),
-    ChangePubKey(Box<ChangePubKey>),
-    ForcedExit(Box<ForcedExitData>),
-    MintNFT(Box<MintNFT>),
-    Swap(Box<Swap>),
-    WithdrawNFT(Box<WithdrawNFTData>),
+    Close(#[schemars(with = "serde_json::Value")] Box<Close>),
+    ChangePubKey(#[schemars(with = "serde_json::Value")] Box<ChangePubKey>),
+    ForcedExit(#[schemars(with = "serde_json::Value")] Box<ForcedExitData>),
+    MintNFT(#[schemars(with = "serde_json::Value")] Box<MintNFT>),
+    Swap(#[schemars(with = "serde_json::Value")] Box<Swap>),
+    WithdrawNFT(#[schemars(with = "serde_json::Value")] Box<WithdrawNFTData>),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ForcedExitData {
     #[serde(flatten)]
+    #[schemars(with = "serde_json::Value")]
     pub tx: ForcedExit,
+    #[schemars(with = "Option<String>")]
     pub eth_tx_hash: Option<H256>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct WithdrawData {
     #[serde(flatten)]
+    #[schemars(with = "serde_json::Value")]
     pub tx: Withdraw,
+    #[schemars(with = "Option<String>")]
     pub eth_tx_hash: Option<H256>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct WithdrawNFTData {
     #[serde(flatten)]
+    #[schemars(with = "serde_json::Value")]
     pub tx: WithdrawNFT,
+    #[schemars(with = "Option<String>")]
     pub eth_tx_hash: Option<H256>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(tag = "type")]
 pub enum L1Transaction {
     Deposit(ApiDeposit),
@@ -194,72 +277,93 @@ impl L1Transaction {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiDeposit {
+    #[schemars(with = "String")]
     pub from: Address,
+    #[schemars(with = "u32")]
     pub token_id: TokenId,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
+    #[schemars(with = "String")]
     pub amount: BigUint,
+    #[schemars(with = "String")]
     pub to: Address,
+    #[schemars(with = "Option<u32>")]
     pub account_id: Option<AccountId>,
+    #[schemars(with = "String")]
     pub eth_hash: H256,
     pub id: SerialId,
     #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    #[schemars(with = "String")]
     pub tx_hash: TxHash,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiFullExit {
+    #[schemars(with = "u32")]
     pub account_id: AccountId,
+    #[schemars(with = "u32")]
     pub token_id: TokenId,
+    #[schemars(with = "String")]
     pub eth_hash: H256,
     pub id: SerialId,
     #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    #[schemars(with = "String")]
     pub tx_hash: TxHash,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[derive(
+    Clone, Debug, Serialize, Deserialize, Default, Ord, PartialOrd, Eq, PartialEq, Hash, JsonSchema,
+)]
 pub struct TxHashSerializeWrapper(
-    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")] pub TxHash,
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    #[schemars(with = "String")]
+    pub TxHash,
 );
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmitBatchResponse {
     pub transaction_hashes: Vec<TxHashSerializeWrapper>,
     #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    #[schemars(with = "String")]
     pub batch_hash: TxHash,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiTxBatch {
     #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    #[schemars(with = "String")]
     pub batch_hash: TxHash,
     pub transaction_hashes: Vec<TxHashSerializeWrapper>,
     pub created_at: DateTime<Utc>,
     pub batch_status: BatchStatus,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BatchStatus {
     pub updated_at: DateTime<Utc>,
     pub last_state: TxInBlockStatus,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Toggle2FA {
     pub enable: bool,
     #[serde(with = "ts_milliseconds")]
+    #[schemars(with = "i64")]
     pub timestamp: DateTime<Utc>,
+    #[schemars(with = "u32")]
     pub account_id: AccountId,
+    #[schemars(with = "serde_json::Value")]
     pub signature: TxEthSignature,
     // If supplied, only transaction signed with this pubkey hash will not
     // have their Ethereum signature checked
+    #[schemars(with = "Option<String>")]
     pub pub_key_hash: Option<PubKeyHash>,
 }
 
@@ -292,7 +396,120 @@ impl Toggle2FA {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Toggle2FAResponse {
     pub success: bool,
 }
+
+/// Limits of the withdrawal timelock of the account.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalTimelockSettings {
+    /// Withdrawals worth at least this amount of USD are delayed. Withdrawals of the tokens
+    /// without the known price are always delayed.
+    pub threshold_usd: u64,
+    /// Number of blocks the withdrawals are held for, during which they can be cancelled.
+    pub delay_blocks: u32,
+}
+
+/// Request of the account owner to set or remove the withdrawal timelock.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalTimelockRequest {
+    /// The timelock is removed if not set.
+    pub timelock: Option<WithdrawalTimelockSettings>,
+    #[serde(with = "ts_milliseconds")]
+    #[schemars(with = "i64")]
+    pub timestamp: DateTime<Utc>,
+    #[schemars(with = "u32")]
+    pub account_id: AccountId,
+    #[schemars(with = "serde_json::Value")]
+    pub signature: TxEthSignature,
+}
+
+impl WithdrawalTimelockRequest {
+    pub fn get_ethereum_sign_message(&self) -> String {
+        match self.timelock {
+            Some(timelock) => format!(
+                "By signing this message, you are requesting the zkSync Server to delay your withdrawals.\n\
+                Withdrawals worth at least {} USD will be held for {} blocks and can be cancelled meanwhile.\n\
+                Timestamp: {}",
+                timelock.threshold_usd,
+                timelock.delay_blocks,
+                self.timestamp.timestamp_millis()
+            ),
+            None => format!(
+                "You are removing the withdrawal timelock of your account.\n\
+                Withdrawals will be processed without delay.\n\
+                Timestamp: {}",
+                self.timestamp.timestamp_millis()
+            ),
+        }
+    }
+}
+
+/// Request of the account owner to cancel the withdrawal held by the timelock.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelWithdrawalRequest {
+    #[schemars(with = "String")]
+    pub tx_hash: TxHash,
+    #[serde(with = "ts_milliseconds")]
+    #[schemars(with = "i64")]
+    pub timestamp: DateTime<Utc>,
+    #[schemars(with = "u32")]
+    pub account_id: AccountId,
+    #[schemars(with = "serde_json::Value")]
+    pub signature: TxEthSignature,
+}
+
+impl CancelWithdrawalRequest {
+    pub fn get_ethereum_sign_message(&self) -> String {
+        format!(
+            "Cancel withdrawal {}\nTimestamp: {}",
+            self.tx_hash.to_string(),
+            self.timestamp.timestamp_millis()
+        )
+    }
+}
+
+/// Status of the withdrawal held by the timelock.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum PendingWithdrawalStatus {
+    /// Waiting for the timelock to expire.
+    Pending,
+    /// Sent to the mempool after the timelock expired.
+    Released,
+    /// Cancelled by the owner of the account.
+    Cancelled,
+    /// Rejected by the mempool on release, e.g. the nonce was used meanwhile.
+    Rejected,
+}
+
+/// Withdrawal held by the timelock of the account.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingWithdrawal {
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    #[schemars(with = "String")]
+    pub tx_hash: TxHash,
+    #[schemars(with = "u32")]
+    pub account_id: AccountId,
+    /// The withdrawal is released once this block is created.
+    #[schemars(with = "u32")]
+    pub release_block: BlockNumber,
+    pub status: PendingWithdrawalStatus,
+    pub fail_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Withdrawal timelock of the account along with the withdrawals held by it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountWithdrawalTimelock {
+    /// Not set if the account has no timelock.
+    pub timelock: Option<WithdrawalTimelockSettings>,
+    /// Held withdrawals, the latest ones first.
+    pub withdrawals: Vec<PendingWithdrawal>,
+}
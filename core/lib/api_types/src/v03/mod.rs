@@ -0,0 +1,49 @@
+//! Types of the API v0.3.
+//!
+//! API v0.3 shares the endpoints with v0.2, but every response is wrapped into the same
+//! `Response` envelope: request arguments are not echoed back, errors are reported with the
+//! HTTP status corresponding to the error code and pagination details of the list endpoints are
+//! moved from the result to the envelope.
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use zksync_types::network::Network;
+
+use crate::{
+    submit_error::SubmitErrorDetails,
+    v02::{pagination::PaginationDetails, ApiVersion, ResultStatus},
+};
+
+/// Name of the header with the request identifier.
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Error object in a response.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Error {
+    /// Numeric error code, stable across the API versions.
+    pub code: u16,
+    pub error_type: String,
+    pub message: String,
+    /// Stable code of the transaction rejection, only set for the submission errors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<SubmitErrorDetails>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    /// Identifier of the request, equal to the `X-Request-Id` header if provided by the client.
+    pub request_id: String,
+    pub api_version: ApiVersion,
+    #[schemars(with = "String")]
+    pub network: Network,
+    pub timestamp: DateTime<Utc>,
+    pub status: ResultStatus,
+    pub result: Option<Value>,
+    pub error: Option<Error>,
+    /// Pagination details, only set for the list endpoints.
+    pub pagination: Option<PaginationDetails<Value>>,
+}
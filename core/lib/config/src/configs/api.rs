@@ -6,7 +6,7 @@ use std::net::SocketAddr;
 use std::time::Duration;
 use zksync_utils::scaled_u64_to_ratio;
 // Workspace uses
-use zksync_types::AccountId;
+use zksync_types::{AccountId, Address, H256};
 // Local uses
 use crate::envy_load;
 
@@ -21,6 +21,8 @@ pub struct ApiConfig {
     pub rest: RestApiConfig,
     /// Configuration options for the JSON RPC servers.
     pub json_rpc: JsonRpcConfig,
+    /// Configuration options for the gRPC server.
+    pub grpc: GrpcConfig,
     /// Configuration options for the web3 JSON RPC server.
     pub web3: Web3Config,
     /// Configuration options for the private core API.
@@ -39,6 +41,7 @@ impl ApiConfig {
             admin: envy_load!("admin", "API_ADMIN_"),
             rest: envy_load!("rest", "API_REST_"),
             json_rpc: envy_load!("json_rpc", "API_JSON_RPC_"),
+            grpc: envy_load!("grpc", "API_GRPC_"),
             web3: envy_load!("web3", "API_WEB3_"),
             private: envy_load!("private", "API_PRIVATE_"),
             prover: envy_load!("prover", "API_PROVER_"),
@@ -53,6 +56,23 @@ impl CommonApiConfig {
         scaled_u64_to_ratio(self.max_subsidy_usd_scaled)
     }
 
+    /// Returns the USD value of the transfers subject to the travel rule, `None` if the rule is disabled.
+    pub fn travel_rule_threshold_usd(&self) -> Option<Ratio<BigUint>> {
+        if self.travel_rule_threshold_usd_scaled == 0 {
+            return None;
+        }
+        Some(scaled_u64_to_ratio(self.travel_rule_threshold_usd_scaled))
+    }
+
+    pub fn shared_cache_ttl(&self) -> Duration {
+        Duration::from_secs(self.shared_cache_ttl_sec)
+    }
+
+    /// Returns the key the API responses are signed with, `None` if the signing is disabled.
+    pub fn response_signing_key(&self) -> Option<H256> {
+        Some(self.response_signing_key).filter(|key| !key.is_zero())
+    }
+
     pub fn from_env() -> Self {
         envy_load!("common", "API_COMMON_")
     }
@@ -76,6 +96,12 @@ impl JsonRpcConfig {
     }
 }
 
+impl GrpcConfig {
+    pub fn from_env() -> Self {
+        envy_load!("grpc", "API_GRPC_")
+    }
+}
+
 impl Web3Config {
     pub fn from_env() -> Self {
         envy_load!("web3", "API_WEB3_")
@@ -122,6 +148,50 @@ pub struct CommonApiConfig {
 
     /// The name of current subsidy. It is needed to conveniently fetch historical data regarding subsidies for different partners
     pub subsidy_name: String,
+
+    /// Amount of threads in the pool dedicated to the zkSync signatures verification.
+    pub signature_verification_threads: usize,
+    /// Maximum amount of requests verified by the signature checker at once.
+    pub signature_verification_batch_size: usize,
+    /// ID of the L1 chain used in the EIP-712 signing domain of the typed data signatures.
+    pub eip712_chain_id: u64,
+    /// Minimum USD value of the transfer requiring the travel rule metadata, scaled by 10^6.
+    /// The travel rule checks are disabled if the value is zero.
+    pub travel_rule_threshold_usd_scaled: u64,
+    /// Read-only nodes serve the API queries only. The transactions are rejected, and the
+    /// components writing to the database or sending L1 transactions can't be run.
+    pub read_only: bool,
+    /// Backend of the caches shared between the API replicas.
+    pub cache_backend: CacheBackend,
+    /// URL of the Redis server used by the `redis` cache backend.
+    pub redis_url: String,
+    /// Lifetime of the entries stored in the shared cache, in seconds.
+    pub shared_cache_ttl_sec: u64,
+    /// Private key used to sign the responses of the account state and the transaction receipt
+    /// endpoints, zero if the responses are not signed.
+    pub response_signing_key: H256,
+    /// L1 addresses known to lose the withdrawn funds, e.g. the contracts which can't receive ETH
+    /// or the deposit contracts of the other rollups. Withdrawals to them are flagged.
+    pub screened_withdrawal_addresses: Vec<Address>,
+    /// Whether the withdrawals to the screened addresses are rejected instead of being flagged.
+    pub block_screened_withdrawals: bool,
+    /// Contract delivering the withdrawn WETH as native ETH. Withdrawals with the `unwrapWeth`
+    /// flag are rejected if not set.
+    pub weth_unwrapper_addr: Option<Address>,
+    /// L1 address of the WETH token unwrapped on withdrawal.
+    pub weth_token_addr: Option<Address>,
+    /// Maximum number of blocks the withdrawal timelocks of the accounts can delay the withdrawals for.
+    pub max_withdrawal_delay_blocks: u32,
+}
+
+/// Backend of the caches shared between the API replicas.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheBackend {
+    /// Every process keeps its own caches.
+    Local,
+    /// Caches are stored in Redis and invalidated by the core on every sealed block.
+    Redis,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -192,12 +262,34 @@ pub struct RestApiConfig {
     pub port: u16,
     /// URL to access API server.
     pub url: String,
+    /// Interval of recomputing the aggregated network statistics, in seconds.
+    pub stats_aggregation_interval: u64,
+    /// Interval of checking the L1 auth facts of the queued onchain `ChangePubKey` transactions, in seconds.
+    pub onchain_auth_poll_interval: u64,
+    /// Interval of releasing the withdrawals held by the timelocks and calling the notification hooks, in seconds.
+    pub withdrawal_timelock_poll_interval: u64,
+    /// URL receiving the status changes of the withdrawals held by the timelocks, hooks are disabled if empty.
+    pub withdrawal_timelock_webhook_url: String,
+    /// Maximum number of the responses kept by each cache of the expensive read endpoints.
+    pub response_cache_size: usize,
 }
 
 impl RestApiConfig {
     pub fn bind_addr(&self) -> SocketAddr {
         SocketAddr::new("0.0.0.0".parse().unwrap(), self.port)
     }
+
+    pub fn stats_aggregation_interval(&self) -> Duration {
+        Duration::from_secs(self.stats_aggregation_interval)
+    }
+
+    pub fn onchain_auth_poll_interval(&self) -> Duration {
+        Duration::from_secs(self.onchain_auth_poll_interval)
+    }
+
+    pub fn withdrawal_timelock_poll_interval(&self) -> Duration {
+        Duration::from_secs(self.withdrawal_timelock_poll_interval)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -210,6 +302,8 @@ pub struct JsonRpcConfig {
     pub ws_port: u16,
     /// URL to access WebSocket RPC server.
     pub ws_url: String,
+    /// Maximum number of calls of a single batch request executed concurrently.
+    pub max_batch_concurrency: usize,
 }
 
 impl JsonRpcConfig {
@@ -222,6 +316,26 @@ impl JsonRpcConfig {
     }
 }
 
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct GrpcConfig {
+    /// Port to which the gRPC server is listening.
+    pub port: u16,
+    /// URL to access gRPC server.
+    pub url: String,
+    /// Interval of polling the transaction status for the status streams, in ms.
+    pub tx_status_poll_interval: u64,
+}
+
+impl GrpcConfig {
+    pub fn bind_addr(&self) -> SocketAddr {
+        SocketAddr::new("0.0.0.0".parse().unwrap(), self.port)
+    }
+
+    pub fn tx_status_poll_interval(&self) -> Duration {
+        Duration::from_millis(self.tx_status_poll_interval)
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Web3Config {
     /// Port to which the web3 JSON RPC server is listening.
@@ -263,6 +377,23 @@ mod tests {
                 subsidized_ips: vec!["127.0.0.1".to_owned()],
                 max_subsidy_usd_scaled: 20000,
                 subsidy_name: String::from("PartnerName"),
+                signature_verification_threads: 4,
+                signature_verification_batch_size: 64,
+                eip712_chain_id: 9,
+                travel_rule_threshold_usd_scaled: 1_000_000_000,
+                read_only: false,
+                cache_backend: CacheBackend::Local,
+                redis_url: "redis://127.0.0.1:6379".into(),
+                shared_cache_ttl_sec: 3600,
+                response_signing_key: H256::repeat_byte(0x11),
+                screened_withdrawal_addresses: vec![
+                    Address::repeat_byte(0x22),
+                    Address::repeat_byte(0x33),
+                ],
+                block_screened_withdrawals: true,
+                weth_unwrapper_addr: Some(Address::repeat_byte(0x44)),
+                weth_token_addr: Some(Address::repeat_byte(0x55)),
+                max_withdrawal_delay_blocks: 1000,
             },
             admin: AdminApiConfig {
                 port: 8080,
@@ -272,12 +403,23 @@ mod tests {
             rest: RestApiConfig {
                 port: 3001,
                 url: "http://127.0.0.1:3001".into(),
+                stats_aggregation_interval: 600,
+                onchain_auth_poll_interval: 15,
+                withdrawal_timelock_poll_interval: 10,
+                withdrawal_timelock_webhook_url: "http://127.0.0.1:4000".into(),
+                response_cache_size: 1000,
             },
             json_rpc: JsonRpcConfig {
                 http_port: 3030,
                 http_url: "http://127.0.0.1:3030".into(),
                 ws_port: 3031,
                 ws_url: "ws://127.0.0.1:3031".into(),
+                max_batch_concurrency: 16,
+            },
+            grpc: GrpcConfig {
+                port: 3040,
+                url: "http://127.0.0.1:3040".into(),
+                tx_status_poll_interval: 1000,
             },
             web3: Web3Config {
                 port: 3002,
@@ -313,16 +455,39 @@ API_COMMON_MAX_SUBSIDY_USD_SCALED=20000
 API_COMMON_SUBSIDY_NAME=PartnerName
 API_COMMON_MAX_NUMBER_OF_TRANSACTIONS_PER_BATCH=200
 API_COMMON_MAX_NUMBER_OF_AUTHORS_PER_BATCH=10
+API_COMMON_SIGNATURE_VERIFICATION_THREADS=4
+API_COMMON_SIGNATURE_VERIFICATION_BATCH_SIZE=64
+API_COMMON_EIP712_CHAIN_ID=9
+API_COMMON_TRAVEL_RULE_THRESHOLD_USD_SCALED=1000000000
+API_COMMON_READ_ONLY=false
+API_COMMON_CACHE_BACKEND="local"
+API_COMMON_REDIS_URL="redis://127.0.0.1:6379"
+API_COMMON_SHARED_CACHE_TTL_SEC=3600
+API_COMMON_RESPONSE_SIGNING_KEY="0x1111111111111111111111111111111111111111111111111111111111111111"
+API_COMMON_SCREENED_WITHDRAWAL_ADDRESSES="0x2222222222222222222222222222222222222222,0x3333333333333333333333333333333333333333"
+API_COMMON_BLOCK_SCREENED_WITHDRAWALS=true
+API_COMMON_WETH_UNWRAPPER_ADDR="0x4444444444444444444444444444444444444444"
+API_COMMON_WETH_TOKEN_ADDR="0x5555555555555555555555555555555555555555"
+API_COMMON_MAX_WITHDRAWAL_DELAY_BLOCKS=1000
 API_TOKEN_INVALIDATE_TOKEN_CACHE_PERIOD_SEC="10"
 API_ADMIN_PORT="8080"
 API_ADMIN_URL="http://127.0.0.1:8080"
 API_ADMIN_SECRET_AUTH="sample"
 API_REST_PORT="3001"
 API_REST_URL="http://127.0.0.1:3001"
+API_REST_STATS_AGGREGATION_INTERVAL="600"
+API_REST_ONCHAIN_AUTH_POLL_INTERVAL="15"
+API_REST_WITHDRAWAL_TIMELOCK_POLL_INTERVAL="10"
+API_REST_WITHDRAWAL_TIMELOCK_WEBHOOK_URL="http://127.0.0.1:4000"
+API_REST_RESPONSE_CACHE_SIZE="1000"
 API_JSON_RPC_HTTP_PORT="3030"
 API_JSON_RPC_HTTP_URL="http://127.0.0.1:3030"
 API_JSON_RPC_WS_PORT="3031"
 API_JSON_RPC_WS_URL="ws://127.0.0.1:3031"
+API_JSON_RPC_MAX_BATCH_CONCURRENCY="16"
+API_GRPC_PORT="3040"
+API_GRPC_URL="http://127.0.0.1:3040"
+API_GRPC_TX_STATUS_POLL_INTERVAL="1000"
 API_WEB3_PORT="3002"
 API_WEB3_URL="http://127.0.0.1:3002"
 API_WEB3_CHAIN_ID="240"
@@ -370,5 +535,13 @@ API_PROMETHEUS_PORT="3312"
             config.web3.bind_addr(),
             SocketAddr::new(bind_broadcast_addr, config.web3.port)
         );
+        assert_eq!(
+            config.grpc.bind_addr(),
+            SocketAddr::new(bind_broadcast_addr, config.grpc.port)
+        );
+        assert_eq!(
+            config.grpc.tx_status_poll_interval(),
+            Duration::from_millis(config.grpc.tx_status_poll_interval)
+        );
     }
 }
@@ -18,6 +18,8 @@ pub struct ContractsConfig {
     pub verifier_addr: Address,
     pub deploy_factory_addr: Address,
     pub forced_exit_addr: Address,
+    /// Router of the deposits carrying the L2 calls, the calls aren't tracked if not set.
+    pub deposit_router_addr: Option<Address>,
     pub genesis_tx_hash: H256,
     pub init_contract_version: u32,
     pub upgrade_eth_blocks: Vec<u64>,
@@ -46,6 +48,7 @@ mod tests {
             verifier_addr: addr("DAbb67b676F5b01FcC8997Cc8439846D0d8078ca"),
             deploy_factory_addr: addr("FC073319977e314F251EAE6ae6bE76B0B3BAeeCF"),
             forced_exit_addr: addr("9c7AeE886D6FcFc14e37784f143a6dAccEf50Db7"),
+            deposit_router_addr: Some(addr("3B0e6F1a5D38E4A5D1C4b1c5D7aC0E4b6F4b2C1a")),
             genesis_tx_hash: hash(
                 "b99ebfea46cbe05a21cd80fe5597d97b204befc52a16303f579c607dc1ac2e2e",
             ),
@@ -67,6 +70,7 @@ CONTRACTS_GOVERNANCE_ADDR="0x5E6D086F5eC079ADFF4FB3774CDf3e8D6a34F7E9"
 CONTRACTS_VERIFIER_ADDR="0xDAbb67b676F5b01FcC8997Cc8439846D0d8078ca"
 CONTRACTS_DEPLOY_FACTORY_ADDR="0xFC073319977e314F251EAE6ae6bE76B0B3BAeeCF"
 CONTRACTS_FORCED_EXIT_ADDR="0x9c7AeE886D6FcFc14e37784f143a6dAccEf50Db7"
+CONTRACTS_DEPOSIT_ROUTER_ADDR="0x3B0e6F1a5D38E4A5D1C4b1c5D7aC0E4b6F4b2C1a"
 CONTRACTS_GENESIS_TX_HASH="0xb99ebfea46cbe05a21cd80fe5597d97b204befc52a16303f579c607dc1ac2e2e"
 CONTRACTS_INIT_CONTRACT_VERSION=4
 CONTRACTS_UPGRADE_ETH_BLOCKS="1,4294967296,1152921504606846976"
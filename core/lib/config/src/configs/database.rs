@@ -12,12 +12,32 @@ use crate::envy_load;
 pub struct DBConfig {
     /// Amount of open connections to the database held by server in the pool.
     pub pool_size: usize,
+    /// Maximum amount of the pool connections held by the API servers at once, zero means no limit.
+    pub api_pool_quota: usize,
+    /// Same for the price fetchers.
+    pub fetchers_pool_quota: usize,
+    /// Same for the witness generator.
+    pub witness_generator_pool_quota: usize,
+    /// Same for the forced exit requests actor and its mempool handler.
+    pub forced_exit_pool_quota: usize,
+    /// Same for the treasury and its mempool handler.
+    pub treasury_pool_quota: usize,
+    /// Same for the rejected transactions cleaner.
+    pub rejected_tx_cleaner_pool_quota: usize,
     /// Database URL.
     pub url: String,
     /// Rejected transactions will be stored in the database for this amount of hours.
     pub rejected_transactions_max_age: u64,
     /// Sleep time (in hours) of the actor responsible for deleting failed transactions from the database.
     pub rejected_transactions_cleaner_interval: u64,
+    /// Idempotency keys of the submitted transactions will be stored in the database for this amount of hours.
+    pub idempotency_keys_max_age: u64,
+    /// Whether the server instances compete for the leadership, so only one of them produces
+    /// the blocks and sends the L1 transactions, while the others serve the API as the standby.
+    pub leader_election: bool,
+    /// Interval (in seconds) of the attempts to become the leader, and of the checks that the
+    /// leadership is still held.
+    pub leader_election_poll_interval: u64,
 }
 
 impl DBConfig {
@@ -34,6 +54,10 @@ impl DBConfig {
     pub fn rejected_transactions_cleaner_interval(&self) -> time::Duration {
         time::Duration::from_secs(self.rejected_transactions_cleaner_interval)
     }
+
+    pub fn idempotency_keys_max_age(&self) -> time::Duration {
+        time::Duration::from_secs(self.idempotency_keys_max_age * Self::SECS_PER_HOUR)
+    }
 }
 
 #[cfg(test)]
@@ -44,9 +68,18 @@ mod tests {
     fn expected_config() -> DBConfig {
         DBConfig {
             pool_size: 10,
+            api_pool_quota: 6,
+            fetchers_pool_quota: 2,
+            witness_generator_pool_quota: 2,
+            forced_exit_pool_quota: 2,
+            treasury_pool_quota: 2,
+            rejected_tx_cleaner_pool_quota: 1,
             url: "postgres://postgres@localhost/plasma".into(),
             rejected_transactions_max_age: 336,
             rejected_transactions_cleaner_interval: 24,
+            idempotency_keys_max_age: 24,
+            leader_election: true,
+            leader_election_poll_interval: 5,
         }
     }
 
@@ -54,13 +87,36 @@ mod tests {
     fn from_env() {
         let config = r#"
 DATABASE_POOL_SIZE="10"
+DATABASE_API_POOL_QUOTA="6"
+DATABASE_FETCHERS_POOL_QUOTA="2"
+DATABASE_WITNESS_GENERATOR_POOL_QUOTA="2"
+DATABASE_FORCED_EXIT_POOL_QUOTA="2"
+DATABASE_TREASURY_POOL_QUOTA="2"
+DATABASE_REJECTED_TX_CLEANER_POOL_QUOTA="1"
 DATABASE_URL="postgres://postgres@localhost/plasma"
 DATABASE_REJECTED_TRANSACTIONS_MAX_AGE="336"
 DATABASE_REJECTED_TRANSACTIONS_CLEANER_INTERVAL="24"
+DATABASE_IDEMPOTENCY_KEYS_MAX_AGE="24"
+DATABASE_LEADER_ELECTION="true"
+DATABASE_LEADER_ELECTION_POLL_INTERVAL="5"
         "#;
         set_env(config);
 
         let actual = DBConfig::from_env();
         assert_eq!(actual, expected_config());
     }
+
+    #[test]
+    fn methods() {
+        let config = expected_config();
+
+        assert_eq!(
+            config.idempotency_keys_max_age(),
+            time::Duration::from_secs(24 * 3600)
+        );
+        assert_eq!(
+            config.leader_election_poll_interval(),
+            time::Duration::from_secs(5)
+        );
+    }
 }
@@ -1,5 +1,7 @@
 // External uses
 use serde::Deserialize;
+// Workspace uses
+use zksync_types::U256;
 // Local uses
 use crate::envy_load;
 
@@ -12,8 +14,14 @@ pub struct ETHClientConfig {
     /// Normally it's 1, we use the network-provided price (and limit it with the gas adjuster in eth sender).
     /// However, it can be increased to speed up the transaction mining time.
     pub gas_price_factor: f64,
+    /// Gas price (in wei) to be used instead of the one reported by the node.
+    /// Should be set for the L1 networks without a reliable gas price oracle, e.g. private chains.
+    pub fixed_gas_price: Option<u64>,
     /// Address of the Ethereum node API.
     pub web3_url: Vec<String>,
+    /// Amount of the latest calls to the Ethereum nodes kept for debugging and exposed via
+    /// the private API. Calls are not recorded if not set.
+    pub call_recorder_capacity: Option<usize>,
 }
 
 impl ETHClientConfig {
@@ -28,6 +36,11 @@ impl ETHClientConfig {
             .cloned()
             .expect("Should be at least one")
     }
+
+    /// Gas price to be used instead of the one reported by the node, if any.
+    pub fn fixed_gas_price(&self) -> Option<U256> {
+        self.fixed_gas_price.map(U256::from)
+    }
 }
 
 #[cfg(test)]
@@ -39,10 +52,12 @@ mod tests {
         ETHClientConfig {
             chain_id: 9,
             gas_price_factor: 1.0f64,
+            fixed_gas_price: Some(1_000_000_000),
             web3_url: vec![
                 "http://127.0.0.1:8545".into(),
                 "http://127.0.0.1:8546".into(),
             ],
+            call_recorder_capacity: Some(1000),
         }
     }
 
@@ -51,12 +66,15 @@ mod tests {
         let config = r#"
 ETH_CLIENT_CHAIN_ID="9"
 ETH_CLIENT_GAS_PRICE_FACTOR="1"
+ETH_CLIENT_FIXED_GAS_PRICE="1000000000"
 ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545,http://127.0.0.1:8546"
+ETH_CLIENT_CALL_RECORDER_CAPACITY="1000"
         "#;
         set_env(config);
 
         let actual = ETHClientConfig::from_env();
         assert_eq!(actual, expected_config());
         assert_eq!(actual.web3_url(), "http://127.0.0.1:8545");
+        assert_eq!(actual.fixed_gas_price(), Some(U256::from(1_000_000_000)));
     }
 }
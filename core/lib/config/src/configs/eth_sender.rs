@@ -14,6 +14,8 @@ pub struct ETHSenderConfig {
     pub sender: Sender,
     /// Options related to the `gas_adjuster` submodule.
     pub gas_price_limit: GasLimit,
+    /// Options related to the operators committee.
+    pub committee: Committee,
 }
 
 impl ETHSenderConfig {
@@ -24,14 +26,28 @@ impl ETHSenderConfig {
                 "eth_sender.gas_price_limit",
                 "ETH_SENDER_GAS_PRICE_LIMIT_"
             ),
+            committee: envy_load!("eth_sender.committee", "ETH_SENDER_COMMITTEE_"),
         }
     }
 }
 
+/// Backend holding the private key of the operator account.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerBackend {
+    /// Key is provided directly via `operator_private_key`.
+    PrivateKey,
+    AwsKms,
+    GcpKms,
+    /// Key is held by the remote signer service.
+    Remote,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Sender {
     /// Private key of the operator account.
-    pub operator_private_key: H256,
+    /// Required by the `private_key` signer backend and not used by the others.
+    pub operator_private_key: Option<H256>,
     /// Address of the operator account.
     pub operator_commit_eth_addr: Address,
     /// mount of confirmations required to consider L1 transaction committed.
@@ -44,6 +60,22 @@ pub struct Sender {
     pub max_txs_in_flight: u64,
     /// Whether sender should interact with L1 or not.
     pub is_enabled: bool,
+    /// Backend used to sign the operator transactions.
+    pub signer_backend: SignerBackend,
+    /// Identifier of the operator key in KMS: key ID or ARN for AWS KMS,
+    /// resource name of the key version for GCP Cloud KMS.
+    pub kms_key_id: String,
+    /// Region of the AWS KMS key.
+    pub kms_region: String,
+    /// URL of the remote signer service.
+    pub remote_signer_url: String,
+    /// Path to the PEM-encoded certificate of the CA that issued the remote signer certificate.
+    pub remote_signer_ca_cert_path: String,
+    /// Path to the PKCS #12 archive with the client certificate and the private key
+    /// used to authenticate in the remote signer.
+    pub remote_signer_identity_path: String,
+    /// Password of the PKCS #12 archive.
+    pub remote_signer_identity_password: String,
 }
 
 impl Sender {
@@ -77,6 +109,53 @@ impl GasLimit {
     }
 }
 
+/// Committee of operator nodes which must approve every L1 operation before it's sent.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Committee {
+    /// Whether operations should be approved by the committee before being sent.
+    pub enabled: bool,
+    /// Committee members in the `<address>@<private API URL>` format.
+    pub members: Vec<String>,
+    /// Minimum number of member approvals required to send an operation.
+    pub threshold: usize,
+    /// Timeout of the approval request to a single member in milliseconds.
+    pub request_timeout: u64,
+    /// Private key used by this node to sign approvals as a committee member.
+    /// Zero key means that the node is not a member of the committee.
+    pub member_private_key: H256,
+    /// Secret shared by the committee, used to generate the access tokens (JWT) of the approval
+    /// requests.
+    pub secret_auth: String,
+}
+
+impl Committee {
+    /// Parses `self.members` into pairs of the member address and its URL.
+    pub fn members(&self) -> Result<Vec<(Address, String)>, String> {
+        self.members
+            .iter()
+            .filter(|member| !member.is_empty())
+            .map(|member| {
+                let mut parts = member.splitn(2, '@');
+                let address = parts.next().unwrap_or_default();
+                let url = parts
+                    .next()
+                    .filter(|url| !url.is_empty())
+                    .ok_or_else(|| format!("Invalid committee member: {}", member))?;
+                let address = address
+                    .trim_start_matches("0x")
+                    .parse()
+                    .map_err(|_| format!("Invalid committee member address: {}", member))?;
+                Ok((address, url.to_string()))
+            })
+            .collect()
+    }
+
+    /// Converts `self.request_timeout` into `Duration`.
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,10 +169,17 @@ mod tests {
                 tx_poll_period: 3,
                 max_txs_in_flight: 3,
                 is_enabled: true,
-                operator_private_key: hash(
+                operator_private_key: Some(hash(
                     "27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be",
-                ),
+                )),
                 operator_commit_eth_addr: addr("de03a0B5963f75f1C8485B355fF6D30f3093BDE7"),
+                signer_backend: SignerBackend::AwsKms,
+                kms_key_id: "alias/zksync-operator".to_string(),
+                kms_region: "us-east-1".to_string(),
+                remote_signer_url: "https://127.0.0.1:8443".to_string(),
+                remote_signer_ca_cert_path: "etc/tls/ca.pem".to_string(),
+                remote_signer_identity_path: "etc/tls/client.p12".to_string(),
+                remote_signer_identity_password: "sample".to_string(),
             },
             gas_price_limit: GasLimit {
                 default: 400000000000,
@@ -101,6 +187,18 @@ mod tests {
                 sample_interval: 15,
                 scale_factor: 1.0f64,
             },
+            committee: Committee {
+                enabled: true,
+                members: vec![
+                    "0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7@http://127.0.0.1:8090".to_string(),
+                ],
+                threshold: 1,
+                request_timeout: 3000,
+                member_private_key: hash(
+                    "27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be",
+                ),
+                secret_auth: "sample".into(),
+            },
         }
     }
 
@@ -114,10 +212,23 @@ ETH_SENDER_SENDER_MAX_TXS_IN_FLIGHT="3"
 ETH_SENDER_SENDER_IS_ENABLED="true"
 ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY="0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
 ETH_SENDER_SENDER_OPERATOR_COMMIT_ETH_ADDR="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
+ETH_SENDER_SENDER_SIGNER_BACKEND="aws_kms"
+ETH_SENDER_SENDER_KMS_KEY_ID="alias/zksync-operator"
+ETH_SENDER_SENDER_KMS_REGION="us-east-1"
+ETH_SENDER_SENDER_REMOTE_SIGNER_URL="https://127.0.0.1:8443"
+ETH_SENDER_SENDER_REMOTE_SIGNER_CA_CERT_PATH="etc/tls/ca.pem"
+ETH_SENDER_SENDER_REMOTE_SIGNER_IDENTITY_PATH="etc/tls/client.p12"
+ETH_SENDER_SENDER_REMOTE_SIGNER_IDENTITY_PASSWORD="sample"
 ETH_SENDER_GAS_PRICE_LIMIT_DEFAULT="400000000000"
 ETH_SENDER_GAS_PRICE_LIMIT_UPDATE_INTERVAL="150"
 ETH_SENDER_GAS_PRICE_LIMIT_SAMPLE_INTERVAL="15"
 ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
+ETH_SENDER_COMMITTEE_ENABLED="true"
+ETH_SENDER_COMMITTEE_MEMBERS="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7@http://127.0.0.1:8090"
+ETH_SENDER_COMMITTEE_THRESHOLD="1"
+ETH_SENDER_COMMITTEE_REQUEST_TIMEOUT="3000"
+ETH_SENDER_COMMITTEE_MEMBER_PRIVATE_KEY="0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
+ETH_SENDER_COMMITTEE_SECRET_AUTH="sample"
         "#;
         set_env(config);
 
@@ -125,6 +236,50 @@ ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
         assert_eq!(actual, expected_config());
     }
 
+    /// Checks that the operator private key may be omitted, e.g. with the KMS backends.
+    #[test]
+    fn without_operator_private_key() {
+        let mut vars: Vec<(String, String)> = vec![
+            ("WAIT_CONFIRMATIONS", "1"),
+            ("EXPECTED_WAIT_TIME_BLOCK", "30"),
+            ("TX_POLL_PERIOD", "3"),
+            ("MAX_TXS_IN_FLIGHT", "3"),
+            ("IS_ENABLED", "true"),
+            (
+                "OPERATOR_COMMIT_ETH_ADDR",
+                "0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7",
+            ),
+            ("SIGNER_BACKEND", "aws_kms"),
+            ("KMS_KEY_ID", "alias/zksync-operator"),
+            ("KMS_REGION", "us-east-1"),
+            ("REMOTE_SIGNER_URL", "https://127.0.0.1:8443"),
+            ("REMOTE_SIGNER_CA_CERT_PATH", "etc/tls/ca.pem"),
+            ("REMOTE_SIGNER_IDENTITY_PATH", "etc/tls/client.p12"),
+            ("REMOTE_SIGNER_IDENTITY_PASSWORD", "sample"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (format!("ETH_SENDER_SENDER_{}", name), value.to_string()))
+        .collect();
+
+        let sender: Sender = envy::prefixed("ETH_SENDER_SENDER_")
+            .from_iter(vars.clone())
+            .unwrap();
+        let expected = Sender {
+            operator_private_key: None,
+            ..expected_config().sender
+        };
+        assert_eq!(sender, expected);
+
+        vars.push((
+            "ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY".to_string(),
+            "0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be".to_string(),
+        ));
+        let sender: Sender = envy::prefixed("ETH_SENDER_SENDER_")
+            .from_iter(vars)
+            .unwrap();
+        assert_eq!(sender, expected_config().sender);
+    }
+
     /// Checks the correctness of the config helper methods.
     #[test]
     fn methods() {
@@ -143,5 +298,23 @@ ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
             config.gas_price_limit.sample_interval(),
             Duration::from_secs(config.gas_price_limit.sample_interval)
         );
+
+        assert_eq!(
+            config.committee.request_timeout(),
+            Duration::from_millis(config.committee.request_timeout)
+        );
+        assert_eq!(
+            config.committee.members(),
+            Ok(vec![(
+                addr("de03a0B5963f75f1C8485B355fF6D30f3093BDE7"),
+                "http://127.0.0.1:8090".to_string()
+            )])
+        );
+
+        let mut committee = config.committee;
+        committee.members = vec!["0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7".to_string()];
+        assert!(committee.members().is_err());
+        committee.members = vec!["not_an_address@http://127.0.0.1:8090".to_string()];
+        assert!(committee.members().is_err());
     }
 }
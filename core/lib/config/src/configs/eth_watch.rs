@@ -14,6 +14,21 @@ pub struct ETHWatchConfig {
     /// How often we want to poll the Ethereum node.
     /// Value in milliseconds.
     pub eth_node_poll_interval: u64,
+    /// How often the Ethereum node is polled after new priority operations were observed.
+    /// Value in milliseconds.
+    pub eth_node_fast_poll_interval: u64,
+    /// For how long the fast polling is kept after the last observed priority operation.
+    /// Value in seconds.
+    pub eth_node_fast_poll_period: u64,
+    /// Upper bound of the poll interval which grows during idle periods.
+    /// Value in milliseconds.
+    pub eth_node_max_poll_interval: u64,
+    /// Maximum amount of requests per second the Ethereum node provider accepts.
+    /// Zero value means there is no limit.
+    pub eth_node_requests_per_second_limit: u64,
+    /// How often the expired deposits are checked to be recorded as refundable.
+    /// Value in seconds.
+    pub deposit_refunds_check_interval: u64,
 }
 
 impl ETHWatchConfig {
@@ -25,6 +40,31 @@ impl ETHWatchConfig {
     pub fn poll_interval(&self) -> Duration {
         Duration::from_millis(self.eth_node_poll_interval)
     }
+
+    /// Converts `self.eth_node_fast_poll_interval` into `Duration`.
+    pub fn fast_poll_interval(&self) -> Duration {
+        Duration::from_millis(self.eth_node_fast_poll_interval)
+    }
+
+    /// Converts `self.eth_node_fast_poll_period` into `Duration`.
+    pub fn fast_poll_period(&self) -> Duration {
+        Duration::from_secs(self.eth_node_fast_poll_period)
+    }
+
+    /// Converts `self.eth_node_max_poll_interval` into `Duration`.
+    pub fn max_poll_interval(&self) -> Duration {
+        Duration::from_millis(self.eth_node_max_poll_interval)
+    }
+
+    /// Returns the requests per second limit of the Ethereum node provider, `None` if there is no limit.
+    pub fn requests_per_second_limit(&self) -> Option<u64> {
+        Some(self.eth_node_requests_per_second_limit).filter(|limit| *limit > 0)
+    }
+
+    /// Converts `self.deposit_refunds_check_interval` into `Duration`.
+    pub fn deposit_refunds_check_interval(&self) -> Duration {
+        Duration::from_secs(self.deposit_refunds_check_interval)
+    }
 }
 
 #[cfg(test)]
@@ -36,6 +76,11 @@ mod tests {
         ETHWatchConfig {
             confirmations_for_eth_event: 0,
             eth_node_poll_interval: 300,
+            eth_node_fast_poll_interval: 100,
+            eth_node_fast_poll_period: 60,
+            eth_node_max_poll_interval: 5000,
+            eth_node_requests_per_second_limit: 10,
+            deposit_refunds_check_interval: 60,
         }
     }
 
@@ -44,6 +89,11 @@ mod tests {
         let config = r#"
 ETH_WATCH_CONFIRMATIONS_FOR_ETH_EVENT="0"
 ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
+ETH_WATCH_ETH_NODE_FAST_POLL_INTERVAL="100"
+ETH_WATCH_ETH_NODE_FAST_POLL_PERIOD="60"
+ETH_WATCH_ETH_NODE_MAX_POLL_INTERVAL="5000"
+ETH_WATCH_ETH_NODE_REQUESTS_PER_SECOND_LIMIT="10"
+ETH_WATCH_DEPOSIT_REFUNDS_CHECK_INTERVAL="60"
         "#;
         set_env(config);
 
@@ -60,5 +110,22 @@ ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
             config.poll_interval(),
             Duration::from_millis(config.eth_node_poll_interval)
         );
+        assert_eq!(
+            config.fast_poll_interval(),
+            Duration::from_millis(config.eth_node_fast_poll_interval)
+        );
+        assert_eq!(
+            config.fast_poll_period(),
+            Duration::from_secs(config.eth_node_fast_poll_period)
+        );
+        assert_eq!(
+            config.max_poll_interval(),
+            Duration::from_millis(config.eth_node_max_poll_interval)
+        );
+        assert_eq!(config.requests_per_second_limit(), Some(10));
+        assert_eq!(
+            config.deposit_refunds_check_interval(),
+            Duration::from_secs(config.deposit_refunds_check_interval)
+        );
     }
 }
@@ -5,7 +5,7 @@ pub use self::{
     eth_sender::ETHSenderConfig, eth_watch::ETHWatchConfig, event_listener::EventListenerConfig,
     forced_exit_requests::ForcedExitRequestsConfig, gateway_watcher::GatewayWatcherConfig,
     misc::MiscConfig, prover::ProverConfig, ticker::TickerConfig,
-    token_handler::TokenHandlerConfig,
+    token_handler::TokenHandlerConfig, treasury::TreasuryConfig,
 };
 
 pub mod api;
@@ -23,6 +23,7 @@ pub mod misc;
 pub mod prover;
 pub mod ticker;
 pub mod token_handler;
+pub mod treasury;
 
 #[cfg(test)]
 pub(crate) mod test_utils;
@@ -34,6 +34,8 @@ pub struct Prover {
     pub request_timeout: u64,
     /// Flag for dying after proving cycle
     pub die_after_proof: bool,
+    /// Compression of the jobs and proofs exchanged with the prover server: `none`, `gzip` or `zstd`.
+    pub payload_compression: String,
 }
 
 impl Prover {
@@ -60,6 +62,9 @@ pub struct Core {
     pub gone_timeout: u64,
     /// Amount of provers in the cluser if there is no pending jobs.
     pub idle_provers: u32,
+    /// Time in which the provers are expected to prove the pending jobs, used to
+    /// recommend the amount of provers in seconds.
+    pub backlog_drain_time: u64,
 }
 
 impl Core {
@@ -67,6 +72,11 @@ impl Core {
     pub fn gone_timeout(&self) -> Duration {
         Duration::from_millis(self.gone_timeout)
     }
+
+    /// Converts `self.backlog_drain_time` into `Duration`.
+    pub fn backlog_drain_time(&self) -> Duration {
+        Duration::from_secs(self.backlog_drain_time)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -96,10 +106,12 @@ mod tests {
                 cycle_wait: 500,
                 request_timeout: 10,
                 die_after_proof: false,
+                payload_compression: "zstd".into(),
             },
             core: Core {
                 gone_timeout: 60000,
                 idle_provers: 1,
+                backlog_drain_time: 3600,
             },
             witness_generator: WitnessGenerator {
                 prepare_data_interval: 500,
@@ -115,8 +127,10 @@ PROVER_PROVER_HEARTBEAT_INTERVAL="1000"
 PROVER_PROVER_CYCLE_WAIT="500"
 PROVER_PROVER_REQUEST_TIMEOUT="10"
 PROVER_PROVER_DIE_AFTER_PROOF=false
+PROVER_PROVER_PAYLOAD_COMPRESSION="zstd"
 PROVER_CORE_GONE_TIMEOUT="60000"
 PROVER_CORE_IDLE_PROVERS="1"
+PROVER_CORE_BACKLOG_DRAIN_TIME="3600"
 PROVER_WITNESS_GENERATOR_PREPARE_DATA_INTERVAL="500"
 PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
         "#;
@@ -1,4 +1,5 @@
 use num::{rational::Ratio, BigUint};
+use std::time::Duration;
 // External uses
 use serde::Deserialize;
 // Workspace uses
@@ -13,6 +14,15 @@ pub enum TokenPriceSource {
     CoinMarketCap,
 }
 
+/// Source of the token liquidity data used to decide whether the token is acceptable for paying fees.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum TokenLiquiditySource {
+    /// Liquidity of the token on Uniswap, in ETH.
+    Uniswap,
+    /// Trading volume of the token over the last 24 hours reported by the price source, in USD.
+    PriceSource,
+}
+
 /// Configuration for the fee ticker.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct TickerConfig {
@@ -30,6 +40,10 @@ pub struct TickerConfig {
     pub uniswap_url: String,
     /// The volume of tokens to confirm their liquidity
     pub liquidity_volume: f64,
+    /// Source of the token liquidity data.
+    pub liquidity_source: TokenLiquiditySource,
+    /// Minimal trading volume in USD reported by the price source to confirm the token liquidity.
+    pub min_liquidity_usd: f64,
     /// Time when liquidity check results are valid
     pub available_liquidity_seconds: u64,
     /// List of the tokens that are unconditionally acceptable for paying fee in.
@@ -40,6 +54,22 @@ pub struct TickerConfig {
     pub number_of_ticker_actors: u8,
     /// Subsidized price for ChangePubKey in cents scaled by SUBSIDY_USD_AMOUNTS_SCALE
     pub subsidy_cpk_price_usd_scaled: u64,
+    /// Age of the token price in seconds after which it's considered stale. `0` disables the check.
+    pub stale_price_timeout_sec: u64,
+    /// Multiplier applied to the stale prices in the fee calculation in percent.
+    /// `0` means that the fee quotes for the tokens with the stale prices are rejected.
+    pub stale_price_multiplier_percent: u32,
+    /// Interval in seconds between the checks of the pipeline load for the fee scaling.
+    /// `0` disables the fee scaling.
+    pub fee_scaling_update_interval_sec: u64,
+    /// Mempool size corresponding to the full load of the pipeline.
+    pub fee_scaling_mempool_capacity: u32,
+    /// Amount of the pending prover jobs corresponding to the full load of the pipeline.
+    pub fee_scaling_prover_capacity: u32,
+    /// Load levels of the fee scaling schedule in percent of the full load.
+    pub fee_scaling_load_levels: Vec<u32>,
+    /// Fee multipliers in percent, applied starting from the corresponding load level.
+    pub fee_scaling_fee_percents: Vec<u32>,
 }
 
 impl TickerConfig {
@@ -47,6 +77,33 @@ impl TickerConfig {
         scaled_u64_to_ratio(self.subsidy_cpk_price_usd_scaled)
     }
 
+    /// Returns the market volume required for the token to be acceptable for paying fees,
+    /// in the units of the configured liquidity source.
+    pub fn min_market_volume(&self) -> f64 {
+        match self.liquidity_source {
+            TokenLiquiditySource::Uniswap => self.liquidity_volume,
+            TokenLiquiditySource::PriceSource => self.min_liquidity_usd,
+        }
+    }
+
+    /// Returns the age after which the token price is considered stale, if the check is enabled.
+    pub fn stale_price_timeout(&self) -> Option<Duration> {
+        if self.stale_price_timeout_sec == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(self.stale_price_timeout_sec))
+        }
+    }
+
+    /// Returns the interval between the checks of the pipeline load, if the fee scaling is enabled.
+    pub fn fee_scaling_update_interval(&self) -> Option<Duration> {
+        if self.fee_scaling_update_interval_sec == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(self.fee_scaling_update_interval_sec))
+        }
+    }
+
     pub fn from_env() -> Self {
         envy_load!("fee_ticker", "FEE_TICKER_")
     }
@@ -75,11 +132,20 @@ mod tests {
             fast_processing_coeff: 10.0f64,
             uniswap_url: "http://127.0.0.1:9975/graphql".to_string(),
             liquidity_volume: 100.0,
+            liquidity_source: TokenLiquiditySource::PriceSource,
+            min_liquidity_usd: 50000.0,
             available_liquidity_seconds: 1000,
             unconditionally_valid_tokens: vec![addr("0000000000000000000000000000000000000000")],
             token_market_update_time: 120,
             number_of_ticker_actors: 4,
             subsidy_cpk_price_usd_scaled: 100,
+            stale_price_timeout_sec: 3600,
+            stale_price_multiplier_percent: 120,
+            fee_scaling_update_interval_sec: 10,
+            fee_scaling_mempool_capacity: 5000,
+            fee_scaling_prover_capacity: 20,
+            fee_scaling_load_levels: vec![0, 10, 50, 100],
+            fee_scaling_fee_percents: vec![90, 100, 120, 150],
         }
     }
 
@@ -95,10 +161,19 @@ FEE_TICKER_AVAILABLE_LIQUIDITY_SECONDS=1000
 FEE_TICKER_TOKEN_MARKET_UPDATE_TIME=120
 FEE_TICKER_UNCONDITIONALLY_VALID_TOKENS="0x0000000000000000000000000000000000000000"
 FEE_TICKER_LIQUIDITY_VOLUME=100
+FEE_TICKER_LIQUIDITY_SOURCE="PriceSource"
+FEE_TICKER_MIN_LIQUIDITY_USD=50000
 FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
 FEE_TICKER_SUBSIDIZED_TOKENS_LIMITS=156
 FEE_TICKER_SCALE_FEE_PERCENT=100
 FEE_TICKER_SUBSIDY_CPK_PRICE_USD_SCALED=100
+FEE_TICKER_STALE_PRICE_TIMEOUT_SEC=3600
+FEE_TICKER_STALE_PRICE_MULTIPLIER_PERCENT=120
+FEE_TICKER_FEE_SCALING_UPDATE_INTERVAL_SEC=10
+FEE_TICKER_FEE_SCALING_MEMPOOL_CAPACITY=5000
+FEE_TICKER_FEE_SCALING_PROVER_CAPACITY=20
+FEE_TICKER_FEE_SCALING_LOAD_LEVELS="0,10,50,100"
+FEE_TICKER_FEE_SCALING_FEE_PERCENTS="90,100,120,150"
         "#;
         set_env(config);
 
@@ -128,5 +203,23 @@ FEE_TICKER_SUBSIDY_CPK_PRICE_USD_SCALED=100
             config.price_source(),
             (TokenPriceSource::CoinMarketCap, COINMARKETCAP_URL.into())
         );
+
+        assert_eq!(config.min_market_volume(), 50000.0);
+        config.liquidity_source = TokenLiquiditySource::Uniswap;
+        assert_eq!(config.min_market_volume(), 100.0);
+
+        assert_eq!(
+            config.stale_price_timeout(),
+            Some(Duration::from_secs(3600))
+        );
+        config.stale_price_timeout_sec = 0;
+        assert_eq!(config.stale_price_timeout(), None);
+
+        assert_eq!(
+            config.fee_scaling_update_interval(),
+            Some(Duration::from_secs(10))
+        );
+        config.fee_scaling_update_interval_sec = 0;
+        assert_eq!(config.fee_scaling_update_interval(), None);
     }
 }
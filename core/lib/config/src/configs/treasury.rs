@@ -0,0 +1,91 @@
+// Built-in uses
+use std::time::Duration;
+// External uses
+use serde::Deserialize;
+// Workspace uses
+use zksync_types::{Address, TokenId};
+// Local uses
+use crate::envy_load;
+
+/// Route used to convert the collected fees.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConversionRoute {
+    /// Swap of the fee account order with the order provided by the market maker.
+    Swap,
+    /// Withdrawal of the tokens to the L1 router contract swapping them on the DEX.
+    L1,
+}
+
+/// Configuration of the treasury task converting the fees collected in the fee account.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct TreasuryConfig {
+    /// Interval of checking the fee account balances, in seconds.
+    pub check_interval: u64,
+    /// L2 private key of the fee account.
+    pub fee_account_private_key: String,
+    /// Symbol of the token the fees are converted into.
+    pub target_token: String,
+    /// Minimal value of the token balance in USD that triggers the conversion.
+    pub min_conversion_usd: f64,
+    /// Maximum allowed deviation of the conversion result from the market price, in percent.
+    pub max_slippage_percent: f64,
+    /// Route used for the conversions.
+    pub route: ConversionRoute,
+    /// URL of the market maker providing the counter-orders for the `swap` route.
+    pub swap_quote_url: String,
+    /// Address of the L1 router contract for the `l1` route.
+    pub l1_router_address: Address,
+    /// Tokens that are never converted.
+    pub excluded_tokens: Vec<TokenId>,
+}
+
+impl TreasuryConfig {
+    pub fn from_env() -> Self {
+        envy_load!("treasury", "TREASURY_")
+    }
+
+    pub fn check_interval(&self) -> Duration {
+        Duration::from_secs(self.check_interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::test_utils::{addr, set_env};
+
+    fn expected_config() -> TreasuryConfig {
+        TreasuryConfig {
+            check_interval: 3600,
+            fee_account_private_key:
+                "0x0038b1d9a7e9ab3f5c41e6a0ab5d7b9ac1e9df58f4b3972d3ec5e2dd9cda1f17".to_string(),
+            target_token: "ETH".to_string(),
+            min_conversion_usd: 1000.0,
+            max_slippage_percent: 1.0,
+            route: ConversionRoute::Swap,
+            swap_quote_url: "http://127.0.0.1:8050/quote".to_string(),
+            l1_router_address: addr("0000000000000000000000000000000000000000"),
+            excluded_tokens: vec![TokenId(1), TokenId(2)],
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+TREASURY_CHECK_INTERVAL="3600"
+TREASURY_FEE_ACCOUNT_PRIVATE_KEY="0x0038b1d9a7e9ab3f5c41e6a0ab5d7b9ac1e9df58f4b3972d3ec5e2dd9cda1f17"
+TREASURY_TARGET_TOKEN="ETH"
+TREASURY_MIN_CONVERSION_USD="1000"
+TREASURY_MAX_SLIPPAGE_PERCENT="1"
+TREASURY_ROUTE="swap"
+TREASURY_SWAP_QUOTE_URL="http://127.0.0.1:8050/quote"
+TREASURY_L1_ROUTER_ADDRESS="0x0000000000000000000000000000000000000000"
+TREASURY_EXCLUDED_TOKENS=1,2
+        "#;
+        set_env(config);
+
+        let actual = TreasuryConfig::from_env();
+        assert_eq!(actual, expected_config());
+    }
+}
@@ -19,6 +19,8 @@ const UPGRADE_GATEKEEPER_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/UpgradeGatekeeper.sol/UpgradeGatekeeper.json";
 const FORCED_EXIT_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/ForcedExit.sol/ForcedExit.json";
+const WETH_UNWRAPPER_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/WethUnwrapper.sol/WethUnwrapper.json";
 
 fn read_file_to_json_value(path: &str) -> io::Result<serde_json::Value> {
     let zksync_home = std::env::var("ZKSYNC_HOME").unwrap_or_else(|_| ".".into());
@@ -73,6 +75,18 @@ pub fn zksync_contract() -> Contract {
     Contract::load(abi_string.as_bytes()).expect("zksync contract abi")
 }
 
+/// Returns the ABI of the main contract with the given version.
+/// Versions starting from `4` share the ABI of the latest contract.
+pub fn zksync_contract_for_version(version: u32) -> Contract {
+    match version {
+        0 => zksync_contract_v0(),
+        1 => zksync_contract_v1(),
+        2 => zksync_contract_v2(),
+        3 => zksync_contract_v3(),
+        _ => zksync_contract(),
+    }
+}
+
 pub fn governance_contract() -> Contract {
     let abi_string = read_file_to_json_value(GOVERNANCE_CONTRACT_FILE)
         .expect("couldn't read GOVERNANCE_CONTRACT_FILE")
@@ -116,3 +130,12 @@ pub fn forced_exit_contract() -> Contract {
         .to_string();
     Contract::load(abi_string.as_bytes()).expect("forced_exit contract abi")
 }
+
+pub fn weth_unwrapper_contract() -> Contract {
+    let abi_string = read_file_to_json_value(WETH_UNWRAPPER_CONTRACT_FILE)
+        .expect("couldn't read WETH_UNWRAPPER_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from WETH_UNWRAPPER_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("weth_unwrapper contract abi")
+}
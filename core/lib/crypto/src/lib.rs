@@ -1,4 +1,8 @@
 //! `zksync_crypto` is a crate containing essential zkSync cryptographic primitives, such as private keys and hashers.
+//!
+//! With the default features disabled the crate only contains the keys, hashers and serialization
+//! used to sign the transactions, and compiles to `wasm32-unknown-unknown`, so the browser wallets
+//! share this code with the server.
 
 use crate::franklin_crypto::{
     bellman::{pairing::bn256, plonk::better_cs::cs::PlonkCsWidth4WithNextStepParams},
@@ -13,6 +17,7 @@ mod crypto_exports {
     };
     pub use franklin_crypto;
     pub use rand;
+    #[cfg(feature = "proof")]
     pub use recursive_aggregation_circuit;
     pub use rescue_poseidon;
 }
@@ -25,6 +30,7 @@ pub mod error;
 pub mod merkle_tree;
 pub mod params;
 pub mod primitives;
+#[cfg(feature = "proof")]
 pub mod proof;
 pub mod serialization;
 
@@ -0,0 +1,218 @@
+//! Selection of the Rescue hash implementation used for the state hash calculations.
+//!
+//! The reference backend is the sponge of `franklin_crypto`. The AVX2 backend is the same
+//! permutation specialized for the state width of the 2-into-1 parameters: the state is kept on
+//! the stack instead of the per-round vectors, and the field arithmetic is compiled with the AVX2
+//! instructions enabled. It is only available with the `avx2` feature on `x86_64` CPUs supporting
+//! AVX2, and produces the same hashes as the reference one.
+//!
+//! Backend is selected once for the whole process with `set_hash_backend`, and is used by every
+//! `RescueHasher` afterwards.
+
+// Built-in deps
+use std::sync::atomic::{AtomicU8, Ordering};
+// External deps
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use crate::franklin_crypto::{
+    bellman::pairing::ff::{Field, PrimeField},
+    rescue::{rescue_hash, RescueEngine, RescueHashParams, SBox},
+};
+
+/// Width of the state the optimized permutation is specialized for.
+const STATE_WIDTH: usize = 3;
+
+static HASH_BACKEND: AtomicU8 = AtomicU8::new(HashBackend::Reference as u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashBackend {
+    Reference = 0,
+    Avx2 = 1,
+}
+
+impl Default for HashBackend {
+    fn default() -> Self {
+        Self::Reference
+    }
+}
+
+impl HashBackend {
+    /// Checks whether the backend can be used on this machine.
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::Reference => true,
+            Self::Avx2 => avx2::is_supported(),
+        }
+    }
+}
+
+/// Selects the hash backend for the process, falling back to the reference one if the requested
+/// backend is not supported. Returns the selected backend.
+pub fn set_hash_backend(backend: HashBackend) -> HashBackend {
+    let backend = if backend.is_supported() {
+        backend
+    } else {
+        HashBackend::Reference
+    };
+    HASH_BACKEND.store(backend as u8, Ordering::Relaxed);
+    backend
+}
+
+/// Returns the hash backend selected for the process.
+pub fn hash_backend() -> HashBackend {
+    match HASH_BACKEND.load(Ordering::Relaxed) {
+        0 => HashBackend::Reference,
+        _ => HashBackend::Avx2,
+    }
+}
+
+/// Calculates the Rescue hash of the input with the given backend. Parameters the optimized
+/// permutation isn't specialized for are hashed by the reference backend.
+pub fn backend_rescue_hash<E: RescueEngine>(
+    backend: HashBackend,
+    params: &E::Params,
+    input: &[E::Fr],
+) -> E::Fr {
+    let specialized = params.state_width() as usize == STATE_WIDTH && params.capacity() == 1;
+    match backend {
+        HashBackend::Avx2 if specialized && avx2::is_supported() => {
+            // Safety: the CPU support of AVX2 is checked above.
+            unsafe { avx2::rescue_hash::<E>(params, input) }
+        }
+        _ => {
+            let sponge_output = rescue_hash::<E>(params, input);
+            assert_eq!(sponge_output.len(), 1);
+            sponge_output[0]
+        }
+    }
+}
+
+/// Fixed length sponge equivalent to the reference one: the last state element is initialized
+/// with the input length, and the last absorbed chunk is padded with ones.
+#[inline(always)]
+fn sponge<E: RescueEngine>(params: &E::Params, input: &[E::Fr]) -> E::Fr {
+    assert!(!input.is_empty() && input.len() < 256);
+    let rate = params.rate() as usize;
+
+    let mut state = [E::Fr::zero(); STATE_WIDTH];
+    let mut len_repr = <E::Fr as PrimeField>::Repr::default();
+    len_repr.as_mut()[0] = input.len() as u64;
+    state[STATE_WIDTH - 1] =
+        E::Fr::from_repr(len_repr).expect("Input length is a valid field element");
+
+    for chunk in input.chunks(rate) {
+        for (element, value) in state.iter_mut().zip(chunk) {
+            element.add_assign(value);
+        }
+        for element in &mut state[chunk.len()..rate] {
+            element.add_assign(&E::Fr::one());
+        }
+        permute::<E>(params, &mut state);
+    }
+    state[0]
+}
+
+#[inline(always)]
+fn permute<E: RescueEngine>(params: &E::Params, state: &mut [E::Fr; STATE_WIDTH]) {
+    for (element, constant) in state.iter_mut().zip(params.round_constants(0)) {
+        element.add_assign(constant);
+    }
+    // Number of rounds is the number of applications of each S-box.
+    for round in 0..2 * params.num_rounds() {
+        if round & 1 == 0 {
+            params.sbox_0().apply(&mut state[..]);
+        } else {
+            params.sbox_1().apply(&mut state[..]);
+        }
+
+        let mut next_state = [E::Fr::zero(); STATE_WIDTH];
+        next_state.copy_from_slice(params.round_constants(round + 1));
+        for (row, next_element) in next_state.iter_mut().enumerate() {
+            for (element, mds) in state.iter().zip(params.mds_matrix_row(row as u32)) {
+                let mut product = *element;
+                product.mul_assign(mds);
+                next_element.add_assign(&product);
+            }
+        }
+        *state = next_state;
+    }
+}
+
+#[cfg(all(feature = "avx2", target_arch = "x86_64"))]
+mod avx2 {
+    use super::{sponge, RescueEngine};
+
+    pub fn is_supported() -> bool {
+        is_x86_feature_detected!("avx2")
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn rescue_hash<E: RescueEngine>(params: &E::Params, input: &[E::Fr]) -> E::Fr {
+        sponge::<E>(params, input)
+    }
+}
+
+#[cfg(not(all(feature = "avx2", target_arch = "x86_64")))]
+mod avx2 {
+    use super::RescueEngine;
+
+    pub fn is_supported() -> bool {
+        false
+    }
+
+    pub unsafe fn rescue_hash<E: RescueEngine>(_params: &E::Params, _input: &[E::Fr]) -> E::Fr {
+        unreachable!("AVX2 hash backend is not compiled in")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        franklin_crypto::bellman::pairing::bn256::{Bn256, Fr},
+        params::RESCUE_PARAMS,
+    };
+
+    fn inputs() -> Vec<Vec<Fr>> {
+        (1..=5u64)
+            .map(|len| {
+                (0..len)
+                    .map(|i| Fr::from_str(&(i * 1_000_003 + len).to_string()).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Checks that the optimized permutation yields the same hashes as the reference one,
+    /// whether or not the AVX2 backend is supported on the machine running the tests.
+    #[test]
+    fn backends_consistency() {
+        for input in inputs() {
+            let reference =
+                backend_rescue_hash::<Bn256>(HashBackend::Reference, &RESCUE_PARAMS, &input);
+            assert_eq!(sponge::<Bn256>(&RESCUE_PARAMS, &input), reference);
+            assert_eq!(
+                backend_rescue_hash::<Bn256>(HashBackend::Avx2, &RESCUE_PARAMS, &input),
+                reference
+            );
+        }
+    }
+
+    #[test]
+    fn backend_selection() {
+        let selected = set_hash_backend(HashBackend::Avx2);
+        assert!(selected.is_supported());
+        assert_eq!(
+            selected == HashBackend::Avx2,
+            HashBackend::Avx2.is_supported()
+        );
+        assert_eq!(hash_backend(), selected);
+
+        assert_eq!(
+            set_hash_backend(HashBackend::Reference),
+            HashBackend::Reference
+        );
+        assert_eq!(hash_backend(), HashBackend::Reference);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod hash_backend;
 pub mod hasher;
 pub mod parallel_smt;
 pub mod rescue_hasher;
@@ -8,6 +9,7 @@ mod tests;
 pub type SparseMerkleTree<T, H, HH> = parallel_smt::SparseMerkleTree<T, H, HH>;
 /// Default hasher used in the zkSync network for state hash calculations.
 pub type RescueHasher<T> = rescue_hasher::RescueHasher<T>;
+pub use hash_backend::{hash_backend, set_hash_backend, HashBackend};
 
 /// Represents the amount of RAM consumed by the tree.
 /// Only data allocated on the heap is counted.
@@ -56,7 +56,11 @@ type NodeRef = usize;
 ///
 /// Since this means that basically the tree is "full" all the time (all the empty indices
 /// are taken by the "default" element), the tree has fixed capacity and cannot be extended
-/// above that. The root hash is calculated for the full tree every time.
+/// above that.
+///
+/// Hashes of the nodes are cached once calculated, and the insertion invalidates the hashes on
+/// the path from the root to the inserted element only. Thus the root hash calculation after
+/// a block rehashes the paths of the updated elements, and the rest of the tree is not visited.
 ///
 /// [Merkle tree]: https://en.wikipedia.org/wiki/Merkle_tree
 #[derive(Debug)]
@@ -264,6 +268,7 @@ where
     H: Hasher<Hash> + Sync,
 {
     const ROOT_ITEM_IDX: NodeRef = 0;
+    const ROOT_NODE_INDEX: NodeIndex = NodeIndex(1);
 
     /// Obtains the element for a certain index.
     pub fn get(&self, index: u32) -> Option<&T> {
@@ -283,9 +288,9 @@ where
 
         // Invalidate the root cache.
         self.cache
-            .write()
+            .get_mut()
             .expect("write lock")
-            .remove(&NodeIndex(1));
+            .remove(&Self::ROOT_NODE_INDEX);
 
         // Traverse the tree, starting from the root.
         // Since our tree is "sparse", it can have gaps.
@@ -434,15 +439,21 @@ where
     /// if this method was not called. The intermediate calculation results are caches though,
     /// thus follow-up invocations will cost less.
     pub fn root_hash(&self) -> Hash {
-        let (root_hash, intermediate_hashes) = self.get_hash(Self::ROOT_ITEM_IDX);
+        let (root_hash, intermediate_hashes) = {
+            // The lock is held for the whole calculation, which only reads the cache.
+            let cache = self.cache.read().expect("Read lock");
+            if let Some(root_hash) = cache.get(&Self::ROOT_NODE_INDEX) {
+                // Nothing was inserted since the last calculation.
+                return root_hash.clone();
+            }
+            self.get_hash(&cache, Self::ROOT_ITEM_IDX)
+        };
 
         // Store all the intermediate hashes in the cache.
-        for (item_idx, hash) in intermediate_hashes {
-            self.cache
-                .write()
-                .expect("write lock")
-                .insert(item_idx, hash);
-        }
+        self.cache
+            .write()
+            .expect("write lock")
+            .extend(intermediate_hashes);
         root_hash
     }
 
@@ -540,7 +551,7 @@ where
     /// Removes the entry with provided index from the hashes cache, as well
     /// as its parent entries, limited by the `parent` index.
     fn wipe_cache(&mut self, child: NodeIndex, parent: NodeIndex) {
-        let mut cache = self.cache.write().expect("write lock");
+        let cache = self.cache.get_mut().expect("write lock");
         if cache.remove(&child).is_some() {
             // Item existed in cache, now we should go up the tree
             // and remove parent hashes, until we reach the provided
@@ -580,51 +591,54 @@ where
         }
     }
 
-    /// Finds the hash of the node's child, using one of the following strategy:
+    /// Returns the hash of the node's child if it doesn't have to be calculated:
     /// - If the hash exists in cache, the cached value is returned;
     /// - If the element with the child's index absents in the tree, the precomputed hash
     ///   for the corresponding layer is returned.
-    /// - Otherwise, the hash for the child is actually calculated using `calculate_child_hash`
-    ///   method.
-    fn get_child_hash(&self, parent: &Node, dir: NodeDirection) -> (Hash, Vec<(NodeIndex, Hash)>) {
+    ///
+    /// Otherwise, the hash for the child has to be calculated using `calculate_child_hash`.
+    fn known_child_hash(
+        &self,
+        cache: &FnvHashMap<NodeIndex, Hash>,
+        parent: &Node,
+        dir: NodeDirection,
+    ) -> Option<Hash> {
         let child_ref = match dir {
             NodeDirection::Left => parent.left,
             NodeDirection::Right => parent.right,
         };
-
         let child_index = dir.child_index(parent.index);
 
-        // Check if the child data exists in the cache.
-        if let Some(cached) = self.cache.read().expect("Read lock").get(&child_index) {
+        if let Some(cached) = cache.get(&child_index) {
             // Cache hit, no calculations required.
-            let updates = vec![];
+            return Some(cached.clone());
+        }
+        match child_ref {
+            Some(_) => None,
+            None => Some(self.prehashed[parent.depth + 1].clone()),
+        }
+    }
 
-            (cached.clone(), updates)
-        } else {
-            match child_ref {
-                Some(child_ref) => {
-                    // Child exists in the tree, we must calculate the underlying hashes.
-                    self.calculate_child_hash(child_ref, parent)
-                }
-                None => {
-                    let default_hash_for_layer = self.prehashed[parent.depth + 1].clone();
-                    let updates = vec![];
-                    (default_hash_for_layer, updates)
-                }
-            }
+    /// Returns the reference to the node's child, which must exist in the tree.
+    fn child_ref(parent: &Node, dir: NodeDirection) -> NodeRef {
+        match dir {
+            NodeDirection::Left => parent.left,
+            NodeDirection::Right => parent.right,
         }
+        .expect("Absent child has a precomputed hash")
     }
 
     /// Calculates the hash of the node's child given the parent node and the child direction.
     fn calculate_child_hash(
         &self,
+        cache: &FnvHashMap<NodeIndex, Hash>,
         child_ref: NodeRef,
         parent: &Node,
     ) -> (Hash, Vec<(NodeIndex, Hash)>) {
         let child = &self.nodes[child_ref];
 
         // Get the hash of the child itself.
-        let (mut cur_hash, mut updates) = self.get_hash(child_ref);
+        let (mut cur_hash, mut updates) = self.get_hash(cache, child_ref);
 
         // Now, we should fill the layer "gaps" between child and parent.
         // This means that we should go through layers of the child and parent,
@@ -656,7 +670,11 @@ where
     /// Calculates the tree hash for the element given its position.
     /// Returns the calculates hash and the list of updated underlying
     /// hashes together with their positions.
-    fn get_hash(&self, node_ref: NodeRef) -> (Hash, Vec<(NodeIndex, Hash)>) {
+    fn get_hash(
+        &self,
+        cache: &FnvHashMap<NodeIndex, Hash>,
+        node_ref: NodeRef,
+    ) -> (Hash, Vec<(NodeIndex, Hash)>) {
         let node = &self.nodes[node_ref].clone();
 
         // Calculate the hash of this node, and collect the underlying updates.
@@ -675,21 +693,41 @@ where
                 (item_hash, updates)
             } else {
                 // Not a leaf node: recursively calculate the hashes up to this node.
-
-                // Use `rayon` to calculate hashes in parallel.
-                let (left_hashes, right_hashes) = rayon::join(
-                    || self.get_child_hash(node, NodeDirection::Left),
-                    || self.get_child_hash(node, NodeDirection::Right),
-                );
+                let left_known = self.known_child_hash(cache, node, NodeDirection::Left);
+                let right_known = self.known_child_hash(cache, node, NodeDirection::Right);
+                let calculate =
+                    |dir| self.calculate_child_hash(cache, Self::child_ref(node, dir), node);
+
+                let (left_hashes, right_hashes) = match (left_known, right_known) {
+                    (Some(lhs_hash), Some(rhs_hash)) => ((lhs_hash, vec![]), (rhs_hash, vec![])),
+                    // Only one of the subtrees was updated, no need to spawn a task for it.
+                    (Some(lhs_hash), None) => ((lhs_hash, vec![]), calculate(NodeDirection::Right)),
+                    (None, Some(rhs_hash)) => (calculate(NodeDirection::Left), (rhs_hash, vec![])),
+                    // Use `rayon` to calculate hashes in parallel.
+                    #[cfg(feature = "multicore")]
+                    (None, None) => rayon::join(
+                        || calculate(NodeDirection::Left),
+                        || calculate(NodeDirection::Right),
+                    ),
+                    #[cfg(not(feature = "multicore"))]
+                    (None, None) => (
+                        calculate(NodeDirection::Left),
+                        calculate(NodeDirection::Right),
+                    ),
+                };
 
                 let (lhs_hash, lhs_updates) = left_hashes;
                 let (rhs_hash, rhs_updates) = right_hashes;
 
                 let hash = self.calculate_hash(node.depth, &lhs_hash, &rhs_hash);
 
-                // Merge left and right updates.
-                let mut updates = lhs_updates;
-                updates.extend(rhs_updates);
+                // Merge left and right updates, moving the shorter list into the longer one.
+                let (mut updates, rest) = if lhs_updates.len() >= rhs_updates.len() {
+                    (lhs_updates, rhs_updates)
+                } else {
+                    (rhs_updates, lhs_updates)
+                };
+                updates.extend(rest);
                 (hash, updates)
             }
         };
@@ -862,6 +900,32 @@ mod tests {
         assert_eq!(tree.root_hash(), 697_516_875);
     }
 
+    /// Checks that the root recalculated after the updates of the hashed tree is the same
+    /// as the root of the tree built from scratch.
+    #[test]
+    fn incremental_root_hash() {
+        let mut tree = TestSMT::new(8);
+        for idx in 0..256 {
+            tree.insert(idx, TestLeaf(idx as u64));
+        }
+        tree.root_hash();
+
+        for round in 1..4u64 {
+            for idx in (0..256).step_by(round as usize * 3) {
+                tree.insert(idx, TestLeaf(idx as u64 * round));
+            }
+            tree.remove(round as u32);
+
+            let mut fresh_tree = TestSMT::new(8);
+            for (idx, leaf) in tree.items.iter() {
+                fresh_tree.insert(*idx as u32, TestLeaf(leaf.0));
+            }
+            let root = tree.root_hash();
+            assert_eq!(root, fresh_tree.root_hash());
+            assert_eq!(root, tree.root_hash());
+        }
+    }
+
     /// Checks the correctness of the built Merkle proofs
     #[test]
     fn merkle_path_test() {
@@ -1,15 +1,15 @@
 // Pedersen hash implementation of the Hasher trait
 
 use crate::franklin_crypto::{
-    bellman::pairing::bn256::Bn256,
-    circuit::multipack,
-    rescue::{rescue_hash, RescueEngine},
+    bellman::pairing::bn256::Bn256, circuit::multipack, rescue::RescueEngine,
 };
 
+use super::hash_backend::{backend_rescue_hash, hash_backend};
 use super::hasher::Hasher;
 use core::fmt;
 
 /// Default hasher for the zkSync state hash calculation.
+/// Hashes are calculated with the backend selected for the process.
 pub struct RescueHasher<E: RescueEngine> {
     params: &'static E::Params,
 }
@@ -36,24 +36,22 @@ impl<E: RescueEngine> Hasher<E::Fr> for RescueHasher<E> {
     fn hash_bits<I: IntoIterator<Item = bool>>(&self, input: I) -> E::Fr {
         let bits: Vec<bool> = input.into_iter().collect();
         let packed = multipack::compute_multipacking::<E>(&bits);
-        let sponge_output = rescue_hash::<E>(self.params, &packed);
-        assert_eq!(sponge_output.len(), 1);
-        sponge_output[0]
+        self.rescue_hash(&packed)
     }
 
     fn hash_elements<I: IntoIterator<Item = E::Fr>>(&self, elements: I) -> E::Fr {
         let packed: Vec<_> = elements.into_iter().collect();
-        let sponge_output = rescue_hash::<E>(self.params, &packed);
-
-        assert_eq!(sponge_output.len(), 1);
-        sponge_output[0]
+        self.rescue_hash(&packed)
     }
 
     fn compress(&self, lhs: &E::Fr, rhs: &E::Fr, _i: usize) -> E::Fr {
-        let sponge_output = rescue_hash::<E>(self.params, &[*lhs, *rhs]);
+        self.rescue_hash(&[*lhs, *rhs])
+    }
+}
 
-        assert_eq!(sponge_output.len(), 1);
-        sponge_output[0]
+impl<E: RescueEngine> RescueHasher<E> {
+    fn rescue_hash(&self, input: &[E::Fr]) -> E::Fr {
+        backend_rescue_hash::<E>(hash_backend(), self.params, input)
     }
 }
 
@@ -3,18 +3,22 @@
 //! This module provides building blocks for serializing and deserializing
 //! common `zksync` types.
 
+#[cfg(feature = "proof")]
 use crate::{
     bellman::plonk::{
         better_better_cs::{cs::Circuit as NewCircuit, proof::Proof as NewProof},
         better_cs::{cs::PlonkCsWidth4WithNextStepParams, keys::Proof as OldProof},
     },
-    convert::FeConvert,
     primitives::EthereumSerializer,
     proof::EncodedSingleProof,
     recursive_aggregation_circuit::circuit::RecursiveAggregationCircuitBn256,
-    Engine, Fr,
+    Engine,
 };
-use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use crate::{convert::FeConvert, Fr};
+#[cfg(feature = "proof")]
+use serde::ser;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "proof")]
 use zksync_basic_types::U256;
 
 /// Blanket structure implementing serializing/deserializing methods for `Fr`.
@@ -195,8 +199,10 @@ impl VecFrSerde {
     }
 }
 
+#[cfg(feature = "proof")]
 pub struct SingleProofSerde;
 
+#[cfg(feature = "proof")]
 impl SingleProofSerde {
     pub fn serialize<S>(
         value: &OldProof<Engine, PlonkCsWidth4WithNextStepParams>,
@@ -230,8 +236,10 @@ impl SingleProofSerde {
     }
 }
 
+#[cfg(feature = "proof")]
 pub struct AggregatedProofSerde;
 
+#[cfg(feature = "proof")]
 impl AggregatedProofSerde {
     pub fn serialize<S>(
         value: &NewProof<Engine, RecursiveAggregationCircuitBn256<'static>>,
@@ -265,6 +273,7 @@ impl AggregatedProofSerde {
     }
 }
 
+#[cfg(feature = "proof")]
 pub fn serialize_new_proof<C: NewCircuit<Engine>>(
     proof: &NewProof<Engine, C>,
 ) -> (Vec<U256>, Vec<U256>) {
@@ -329,6 +338,7 @@ pub fn serialize_new_proof<C: NewCircuit<Engine>>(
     (inputs, serialized_proof)
 }
 
+#[cfg(feature = "proof")]
 pub fn serialize_single_proof(
     proof: &OldProof<Engine, PlonkCsWidth4WithNextStepParams>,
 ) -> EncodedSingleProof {
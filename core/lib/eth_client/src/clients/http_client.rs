@@ -13,7 +13,7 @@ use web3::{
     },
     transports::Http,
     types::{
-        Address, BlockId, BlockNumber, Bytes, Filter, Log, Transaction, TransactionId,
+        Address, BlockId, BlockNumber, Bytes, CallRequest, Filter, Log, Transaction, TransactionId,
         TransactionReceipt, H160, H256, U256, U64,
     },
     Web3,
@@ -22,6 +22,7 @@ use web3::{
 // Workspace uses
 use zksync_eth_signer::{raw_ethereum_tx::RawTransaction, EthereumSigner};
 
+use super::recorder::{CallRecorder, RecordingTransport};
 use crate::ethereum_gateway::{ExecutedTxStatus, FailureInfo, SignedCallResult};
 /// Gas limit value to be used in transaction if for some reason
 /// gas limit was not set for it.
@@ -36,7 +37,8 @@ struct ETHDirectClientInner<S: EthereumSigner> {
     contract: ethabi::Contract,
     chain_id: u64,
     gas_price_factor: f64,
-    web3: Web3<Http>,
+    fixed_gas_price: Option<U256>,
+    web3: Web3<RecordingTransport>,
 }
 
 #[derive(Clone)]
@@ -53,6 +55,7 @@ impl<S: EthereumSigner> fmt::Debug for ETHDirectClient<S> {
             .field("contract_addr", &self.inner.contract_addr)
             .field("chain_id", &self.inner.chain_id)
             .field("gas_price_factor", &self.inner.gas_price_factor)
+            .field("fixed_gas_price", &self.inner.fixed_gas_price)
             .finish()
     }
 }
@@ -75,20 +78,47 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                 chain_id,
                 contract,
                 gas_price_factor,
-                web3: Web3::new(transport),
+                fixed_gas_price: None,
+                web3: Web3::new(RecordingTransport::new(transport)),
             }),
         }
     }
 
-    pub fn main_contract_with_address(&self, address: Address) -> Contract<Http> {
+    /// Makes the client use the provided gas price instead of the one reported by the node.
+    /// Useful for the L1 networks without a reliable gas price oracle, e.g. private chains.
+    ///
+    /// Must be called right after the client is created, before it is cloned.
+    pub fn with_fixed_gas_price(mut self, gas_price: Option<U256>) -> Self {
+        Arc::get_mut(&mut self.inner)
+            .expect("Fixed gas price must be set before the client is cloned")
+            .fixed_gas_price = gas_price;
+        self
+    }
+
+    /// Makes the client report every call to the node to the recorder.
+    ///
+    /// Must be called right after the client is created, before it is cloned.
+    pub fn with_call_recorder(mut self, web3_url: &str, recorder: CallRecorder) -> Self {
+        let inner = Arc::get_mut(&mut self.inner)
+            .expect("Call recorder must be set before the client is cloned");
+        let transport = inner.web3.transport().clone();
+        inner.web3 = Web3::new(transport.with_recorder(web3_url, recorder));
+        self
+    }
+
+    pub fn main_contract_with_address(&self, address: Address) -> Contract<RecordingTransport> {
         Contract::new(self.inner.web3.eth(), address, self.inner.contract.clone())
     }
 
-    pub fn main_contract(&self) -> Contract<Http> {
+    pub fn main_contract(&self) -> Contract<RecordingTransport> {
         self.main_contract_with_address(self.inner.contract_addr)
     }
 
-    pub fn create_contract(&self, address: Address, contract: ethabi::Contract) -> Contract<Http> {
+    pub fn create_contract(
+        &self,
+        address: Address,
+        contract: ethabi::Contract,
+    ) -> Contract<RecordingTransport> {
         Contract::new(self.inner.web3.eth(), address, contract)
     }
 
@@ -144,7 +174,10 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
     pub async fn get_gas_price(&self) -> Result<U256, anyhow::Error> {
         #[cfg(feature = "with-metrics")]
         let start = Instant::now();
-        let mut network_gas_price = self.inner.web3.eth().gas_price().await?;
+        let mut network_gas_price = match self.inner.fixed_gas_price {
+            Some(gas_price) => gas_price,
+            None => self.inner.web3.eth().gas_price().await?,
+        };
         let percent_gas_price_factor =
             U256::from((self.inner.gas_price_factor * 100.0).round() as u64);
         network_gas_price = (network_gas_price * percent_gas_price_factor) / U256::from(100);
@@ -342,6 +375,15 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(balance)
     }
 
+    pub async fn estimate_gas(&self, call: CallRequest) -> Result<U256, anyhow::Error> {
+        #[cfg(feature = "with-metrics")]
+        let start = Instant::now();
+        let gas = self.inner.web3.eth().estimate_gas(call, None).await?;
+        #[cfg(feature = "with-metrics")]
+        metrics::histogram!("eth_client.direct.estimate_gas", start.elapsed());
+        Ok(gas)
+    }
+
     pub async fn sender_eth_balance(&self) -> Result<U256, anyhow::Error> {
         self.eth_balance(self.inner.sender_account).await
     }
@@ -443,17 +485,18 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                 let confirmations = current_block.saturating_sub(tx_block_number.as_u64());
                 let success = status.as_u64() == 1;
 
+                let receipt = receipt.unwrap();
+                let gas_used = receipt.gas_used;
+                let effective_gas_price = receipt.effective_gas_price;
                 // Set the receipt only for failures.
-                let receipt = if success {
-                    None
-                } else {
-                    Some(receipt.unwrap())
-                };
+                let receipt = if success { None } else { Some(receipt) };
 
                 Ok(Some(ExecutedTxStatus {
                     confirmations,
                     success,
                     receipt,
+                    gas_used,
+                    effective_gas_price,
                 }))
             }
             _ => Ok(None),
@@ -498,8 +541,12 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
             .expect("failed to encode parameters")
     }
 
+    pub fn call_recorder(&self) -> Option<&CallRecorder> {
+        self.inner.web3.transport().recorder()
+    }
+
     pub fn get_web3_transport(&self) -> &Http {
-        self.inner.web3.transport()
+        self.inner.web3.transport().http()
     }
 
     pub async fn get_tx(&self, hash: H256) -> Result<Option<Transaction>, anyhow::Error> {
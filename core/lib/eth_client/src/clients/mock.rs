@@ -6,11 +6,11 @@ use std::collections::{HashMap, HashSet};
 use tokio::sync::RwLock;
 use web3::contract::tokens::{Detokenize, Tokenize};
 use web3::contract::Options;
-use web3::transports::Http;
-use web3::types::{BlockId, Filter, Log, Transaction, U64};
+use web3::types::{BlockId, CallRequest, Filter, Log, Transaction, U64};
 
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
+use super::recorder::RecordingTransport;
 use crate::{
     ethereum_gateway::{ExecutedTxStatus, FailureInfo},
     SignedCallResult,
@@ -83,6 +83,8 @@ impl MockEthereum {
             confirmations,
             success: true,
             receipt: None,
+            gas_used: None,
+            effective_gas_price: None,
         };
         self.inner.tx_statuses.write().await.insert(tx_hash, status);
     }
@@ -95,6 +97,8 @@ impl MockEthereum {
             confirmations,
             success: false,
             receipt: Some(Default::default()),
+            gas_used: None,
+            effective_gas_price: None,
         };
         self.inner.tx_statuses.write().await.insert(*hash, status);
     }
@@ -193,6 +197,10 @@ impl MockEthereum {
         unreachable!()
     }
 
+    pub async fn estimate_gas(&self, _call: CallRequest) -> Result<U256, Error> {
+        unreachable!()
+    }
+
     pub async fn contract_balance(
         &self,
         _token_address: Address,
@@ -262,7 +270,7 @@ impl MockEthereum {
         &self,
         _address: Address,
         _contract: ethabi::Contract,
-    ) -> web3::contract::Contract<Http> {
+    ) -> web3::contract::Contract<RecordingTransport> {
         unreachable!()
     }
 
@@ -1,3 +1,4 @@
 pub mod http_client;
 pub mod mock;
 pub mod multiplexer;
+pub mod recorder;
@@ -4,18 +4,18 @@ use std::sync::Arc;
 use web3::{
     contract::tokens::{Detokenize, Tokenize},
     contract::Options,
-    transports::Http,
-    types::{Address, BlockId, Filter, Log, Transaction, U64},
+    types::{Address, BlockId, CallRequest, Filter, Log, Transaction, U64},
 };
-use zksync_eth_signer::PrivateKeySigner;
+use zksync_eth_signer::OperatorSigner;
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
+use super::recorder::RecordingTransport;
 use crate::ethereum_gateway::{ExecutedTxStatus, FailureInfo, SignedCallResult};
 use crate::ETHDirectClient;
 
 #[derive(Debug, Default)]
 struct MultiplexerEthereumClientInner {
-    clients: Vec<(String, ETHDirectClient<PrivateKeySigner>)>,
+    clients: Vec<(String, ETHDirectClient<OperatorSigner>)>,
     preferred: AtomicUsize,
 }
 
@@ -44,7 +44,7 @@ impl MultiplexerEthereumClient {
     pub fn add_client(
         &mut self,
         name: String,
-        client: ETHDirectClient<PrivateKeySigner>,
+        client: ETHDirectClient<OperatorSigner>,
     ) -> &mut Self {
         Arc::get_mut(&mut self.inner)
             .unwrap()
@@ -61,7 +61,7 @@ impl MultiplexerEthereumClient {
         }
     }
 
-    pub fn clients(&self) -> impl Iterator<Item = (&str, &ETHDirectClient<PrivateKeySigner>)> {
+    pub fn clients(&self) -> impl Iterator<Item = (&str, &ETHDirectClient<OperatorSigner>)> {
         let preferred = self.inner.preferred.load(Ordering::Relaxed);
         self.inner
             .clients
@@ -82,7 +82,7 @@ impl MultiplexerEthereumClient {
         &self,
         address: Address,
         contract: ethabi::Contract,
-    ) -> web3::contract::Contract<Http> {
+    ) -> web3::contract::Contract<RecordingTransport> {
         let client = self
             .clients()
             .next()
@@ -160,6 +160,10 @@ impl MultiplexerEthereumClient {
         multiple_call!(self, allowance(token_address, erc20_abi));
     }
 
+    pub async fn estimate_gas(&self, call: CallRequest) -> Result<U256, anyhow::Error> {
+        multiple_call!(self, estimate_gas(call));
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn call_contract_function<R, A, B, P>(
         &self,
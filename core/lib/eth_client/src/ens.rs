@@ -0,0 +1,120 @@
+//! Resolution of the ENS names.
+//!
+//! The name is resolved in two steps: the ENS registry returns the resolver of the name,
+//! then the resolver returns the address the name points to.
+
+// External uses
+use ethabi::Contract;
+use web3::{contract::Options, signing::keccak256};
+// Workspace uses
+use zksync_types::{Address, H256};
+// Local uses
+use crate::EthereumGateway;
+
+/// Address of the ENS registry, it's the same on the mainnet and the public testnets.
+const ENS_REGISTRY_ADDRESS: &str = "00000000000C2E074eC69A0dFb2997BA6C7d2e1e";
+
+const ENS_REGISTRY_ABI: &str = r#"[{"constant":true,"inputs":[{"name":"node","type":"bytes32"}],"name":"resolver","outputs":[{"name":"","type":"address"}],"payable":false,"stateMutability":"view","type":"function"}]"#;
+const ENS_RESOLVER_ABI: &str = r#"[{"constant":true,"inputs":[{"name":"node","type":"bytes32"}],"name":"addr","outputs":[{"name":"","type":"address"}],"payable":false,"stateMutability":"view","type":"function"}]"#;
+
+pub fn ens_registry_address() -> Address {
+    ENS_REGISTRY_ADDRESS.parse().unwrap()
+}
+
+pub fn ens_registry_contract() -> Contract {
+    Contract::load(ENS_REGISTRY_ABI.as_bytes()).expect("ENS registry abi error")
+}
+
+pub fn ens_resolver_contract() -> Contract {
+    Contract::load(ENS_RESOLVER_ABI.as_bytes()).expect("ENS resolver abi error")
+}
+
+/// Returns `true` if the string looks like the ENS name rather than the address.
+pub fn is_ens_name(name: &str) -> bool {
+    name.contains('.')
+        && !name.starts_with('.')
+        && !name.ends_with('.')
+        && !name.chars().any(char::is_whitespace)
+}
+
+/// Computes the ENS `namehash` of the name, the name is expected to be normalized.
+pub fn namehash(name: &str) -> H256 {
+    let mut node = [0u8; 32];
+    if name.is_empty() {
+        return H256(node);
+    }
+    for label in name.rsplit('.') {
+        let label_hash = keccak256(label.as_bytes());
+        node = keccak256(&[node, label_hash].concat());
+    }
+    H256(node)
+}
+
+/// Returns the address the ENS name points to, `None` if the name has no resolver
+/// or the resolver has no address for the name.
+pub async fn resolve_ens_name(
+    client: &EthereumGateway,
+    name: &str,
+) -> anyhow::Result<Option<Address>> {
+    let node = namehash(&name.to_lowercase());
+    let resolver: Address = client
+        .call_contract_function(
+            "resolver",
+            node,
+            None,
+            Options::default(),
+            None,
+            ens_registry_address(),
+            ens_registry_contract(),
+        )
+        .await?;
+    if resolver.is_zero() {
+        return Ok(None);
+    }
+
+    let address: Address = client
+        .call_contract_function(
+            "addr",
+            node,
+            None,
+            Options::default(),
+            None,
+            resolver,
+            ens_resolver_contract(),
+        )
+        .await?;
+    Ok(Some(address).filter(|address| !address.is_zero()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn namehash_vectors() {
+        // Test vectors from EIP-137.
+        assert_eq!(namehash(""), H256::zero());
+        assert_eq!(
+            namehash("eth"),
+            "93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae"
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(
+            namehash("foo.eth"),
+            "de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f"
+                .parse()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn ens_name_detection() {
+        assert!(is_ens_name("vitalik.eth"));
+        assert!(is_ens_name("pay.vitalik.eth"));
+        assert!(!is_ens_name("0x8cf1d9e4b3b6b3e4c8e4b4a2c6d3f0a9b8c7d6e5"));
+        assert!(!is_ens_name("vitalik"));
+        assert!(!is_ens_name(".eth"));
+        assert!(!is_ens_name("vitalik .eth"));
+    }
+}
@@ -1,16 +1,19 @@
 use web3::contract::tokens::{Detokenize, Tokenize};
 use web3::contract::{Contract, Options};
-use web3::transports::Http;
-use web3::types::{Address, BlockId, Filter, Log, Transaction, U64};
+use web3::types::{Address, BlockId, CallRequest, Filter, Log, Transaction, U64};
 
 use std::fmt::Debug;
+use zksync_config::configs::eth_sender::{Sender, SignerBackend};
 use zksync_config::{ETHClientConfig, ETHSenderConfig};
-use zksync_contracts::zksync_contract;
-use zksync_eth_signer::PrivateKeySigner;
+use zksync_contracts::{weth_unwrapper_contract, zksync_contract};
+use zksync_eth_signer::kms_signer::{AwsKmsClient, GcpKmsClient, KmsClient};
+use zksync_eth_signer::remote_signer::{RemoteSignerTls, SigningPolicy};
+use zksync_eth_signer::{KmsSigner, OperatorSigner, PrivateKeySigner, RemoteSigner};
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
 use crate::clients::mock::MockEthereum;
 use crate::clients::multiplexer::MultiplexerEthereumClient;
+use crate::clients::recorder::{CallRecorder, RecordingTransport};
 use crate::ETHDirectClient;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -31,6 +34,10 @@ pub struct ExecutedTxStatus {
     /// Receipt for a transaction. Will be set to `Some` only if the transaction
     /// failed during execution.
     pub receipt: Option<TransactionReceipt>,
+    /// Amount of gas used by the transaction.
+    pub gas_used: Option<U256>,
+    /// Price paid per unit of gas. Not reported by the nodes that don't support EIP-1559.
+    pub effective_gas_price: Option<U256>,
 }
 /// Information about transaction failure.
 #[derive(Debug, Clone)]
@@ -43,7 +50,7 @@ pub struct FailureInfo {
 
 #[derive(Debug, Clone)]
 pub enum EthereumGateway {
-    Direct(ETHDirectClient<PrivateKeySigner>),
+    Direct(ETHDirectClient<OperatorSigner>),
     Multiplexed(MultiplexerEthereumClient),
     Mock(MockEthereum),
 }
@@ -54,42 +61,157 @@ impl EthereumGateway {
         eth_sender_config: &ETHSenderConfig,
         main_contract: Address,
     ) -> Self {
-        if eth_client_config.web3_url.len() == 1 {
-            let transport = web3::transports::Http::new(&eth_client_config.web3_url()).unwrap();
+        Self::with_weth_unwrapper(eth_client_config, eth_sender_config, main_contract, None)
+    }
+
+    /// Creates the gateway which is additionally allowed to call the WETH unwrapper contract,
+    /// if it's set, to deliver the withdrawals as native ETH.
+    pub fn with_weth_unwrapper(
+        eth_client_config: &ETHClientConfig,
+        eth_sender_config: &ETHSenderConfig,
+        main_contract: Address,
+        weth_unwrapper: Option<Address>,
+    ) -> Self {
+        Self::build(
+            eth_client_config,
+            main_contract,
+            eth_sender_config.sender.operator_commit_eth_addr,
+            || operator_signer(&eth_sender_config.sender, main_contract, weth_unwrapper),
+        )
+    }
 
-            EthereumGateway::Direct(ETHDirectClient::new(
+    /// Creates the gateway for the components that only read the data from Ethereum
+    /// (e.g. watchers), so no operator account is required.
+    /// Transactions signed by such gateway are never valid.
+    pub fn read_only(eth_client_config: &ETHClientConfig, main_contract: Address) -> Self {
+        Self::build(eth_client_config, main_contract, Address::zero(), || {
+            PrivateKeySigner::new(H256::zero()).into()
+        })
+    }
+
+    fn build(
+        eth_client_config: &ETHClientConfig,
+        main_contract: Address,
+        operator_eth_addr: Address,
+        signer: impl Fn() -> OperatorSigner,
+    ) -> Self {
+        let recorder = eth_client_config
+            .call_recorder_capacity
+            .filter(|capacity| *capacity > 0)
+            .map(CallRecorder::new);
+        let direct_client = |web3_url: &str| {
+            let transport = web3::transports::Http::new(web3_url).unwrap();
+            let client = ETHDirectClient::new(
                 transport,
                 zksync_contract(),
-                eth_sender_config.sender.operator_commit_eth_addr,
-                PrivateKeySigner::new(eth_sender_config.sender.operator_private_key),
+                operator_eth_addr,
+                signer(),
                 main_contract,
                 eth_client_config.chain_id,
                 eth_client_config.gas_price_factor,
-            ))
+            )
+            .with_fixed_gas_price(eth_client_config.fixed_gas_price());
+            match &recorder {
+                Some(recorder) => client.with_call_recorder(web3_url, recorder.clone()),
+                None => client,
+            }
+        };
+
+        if eth_client_config.web3_url.len() == 1 {
+            EthereumGateway::Direct(direct_client(&eth_client_config.web3_url()))
         } else {
             let mut client = MultiplexerEthereumClient::new();
-
-            let contract = zksync_contract();
             for web3_url in eth_client_config.web3_url.iter().cloned() {
-                let transport = web3::transports::Http::new(&web3_url).unwrap();
-                client.add_client(
-                    web3_url,
-                    ETHDirectClient::new(
-                        transport,
-                        contract.clone(),
-                        eth_sender_config.sender.operator_commit_eth_addr,
-                        PrivateKeySigner::new(eth_sender_config.sender.operator_private_key),
-                        main_contract,
-                        eth_client_config.chain_id,
-                        eth_client_config.gas_price_factor,
-                    ),
-                );
+                let direct_client = direct_client(&web3_url);
+                client.add_client(web3_url, direct_client);
             }
             EthereumGateway::Multiplexed(client)
         }
     }
 }
 
+/// Contract methods that the operator is allowed to call via remote signer.
+const OPERATOR_METHODS: [&str; 4] = [
+    "commitBlocks",
+    "proveBlocks",
+    "executeBlocks",
+    "revertBlocks",
+];
+
+/// Methods of the WETH unwrapper contract that the operator is allowed to call via remote signer.
+const WETH_UNWRAPPER_METHODS: [&str; 1] = ["unwrap"];
+
+/// Creates the signer of the operator transactions according to the configured backend.
+fn operator_signer(
+    config: &Sender,
+    main_contract: Address,
+    weth_unwrapper: Option<Address>,
+) -> OperatorSigner {
+    let kms_client = match config.signer_backend {
+        SignerBackend::PrivateKey => {
+            let private_key = config
+                .operator_private_key
+                .expect("Operator private key is required by the `private_key` signer backend");
+            return PrivateKeySigner::new(private_key).into();
+        }
+        SignerBackend::Remote => {
+            return remote_signer(config, main_contract, weth_unwrapper).into()
+        }
+        SignerBackend::AwsKms => KmsClient::Aws(AwsKmsClient::new(
+            config.kms_key_id.clone(),
+            config.kms_region.clone(),
+        )),
+        SignerBackend::GcpKms => KmsClient::Gcp(GcpKmsClient::new(config.kms_key_id.clone())),
+    };
+    KmsSigner::new(kms_client, config.operator_commit_eth_addr).into()
+}
+
+fn selectors<'a>(
+    contract: &'a ethabi::Contract,
+    methods: &'a [&str],
+) -> impl Iterator<Item = [u8; 4]> + 'a {
+    methods.iter().map(move |method| {
+        contract
+            .function(method)
+            .expect("Operator method is missing in the contract ABI")
+            .short_signature()
+    })
+}
+
+fn remote_signer(
+    config: &Sender,
+    main_contract: Address,
+    weth_unwrapper: Option<Address>,
+) -> RemoteSigner {
+    let contract = zksync_contract();
+    let mut policy =
+        SigningPolicy::default().allow(main_contract, selectors(&contract, &OPERATOR_METHODS));
+    if let Some(weth_unwrapper) = weth_unwrapper {
+        let contract = weth_unwrapper_contract();
+        policy = policy.allow(
+            weth_unwrapper,
+            selectors(&contract, &WETH_UNWRAPPER_METHODS),
+        );
+    }
+    let read = |path: &str| {
+        std::fs::read(path)
+            .unwrap_or_else(|err| panic!("Unable to read remote signer TLS file {}: {}", path, err))
+    };
+    let tls = RemoteSignerTls {
+        ca_certificate: read(&config.remote_signer_ca_cert_path),
+        identity: read(&config.remote_signer_identity_path),
+        identity_password: config.remote_signer_identity_password.clone(),
+    };
+
+    RemoteSigner::new(
+        config.remote_signer_url.clone(),
+        config.operator_commit_eth_addr,
+        policy,
+        tls,
+    )
+    .expect("Unable to create remote signer")
+}
+
 macro_rules! delegate_call {
     ($self:ident.$method:ident($($args:ident),*)) => {
         match $self {
@@ -186,6 +308,11 @@ impl EthereumGateway {
         delegate_call!(self.allowance(token_address, erc20_abi))
     }
 
+    /// Estimates the gas required to execute the call.
+    pub async fn estimate_gas(&self, call: CallRequest) -> Result<U256, anyhow::Error> {
+        delegate_call!(self.estimate_gas(call))
+    }
+
     pub async fn get_tx_status(
         &self,
         hash: H256,
@@ -253,7 +380,11 @@ impl EthereumGateway {
         }
     }
 
-    pub fn create_contract(&self, address: Address, contract: ethabi::Contract) -> Contract<Http> {
+    pub fn create_contract(
+        &self,
+        address: Address,
+        contract: ethabi::Contract,
+    ) -> Contract<RecordingTransport> {
         match self {
             EthereumGateway::Multiplexed(c) => c.create_contract(address, contract),
             EthereumGateway::Direct(c) => c.create_contract(address, contract),
@@ -265,6 +396,18 @@ impl EthereumGateway {
         delegate_call!(self.get_tx(hash))
     }
 
+    /// Returns the recorder of the calls to the Ethereum nodes, if the recording is enabled.
+    /// The recorder is shared by all the nodes of the multiplexed gateway.
+    pub fn call_recorder(&self) -> Option<CallRecorder> {
+        match self {
+            EthereumGateway::Direct(c) => c.call_recorder().cloned(),
+            EthereumGateway::Multiplexed(c) => c
+                .clients()
+                .find_map(|(_, client)| client.call_recorder().cloned()),
+            EthereumGateway::Mock(_) => None,
+        }
+    }
+
     pub fn is_multiplexed(&self) -> bool {
         matches!(self, EthereumGateway::Multiplexed(_))
     }
@@ -1,4 +1,5 @@
 pub mod clients;
+pub mod ens;
 pub mod ethereum_gateway;
 pub use clients::http_client::ETHDirectClient;
 pub use clients::multiplexer::MultiplexerEthereumClient;
@@ -0,0 +1,191 @@
+//! Client of the AWS Key Management Service.
+//!
+//! Requests are authorized with the Signature Version 4 using the credentials from the standard
+//! `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and (optionally) `AWS_SESSION_TOKEN` variables.
+
+use chrono::{DateTime, Utc};
+use parity_crypto::{digest, hmac};
+use serde_json::json;
+
+use zksync_types::H256;
+
+use super::{response_body, signing_failed};
+use crate::SignerError;
+
+#[derive(Debug, Clone)]
+pub struct AwsKmsClient {
+    client: reqwest::Client,
+    /// ID, ARN or alias of the `ECC_SECG_P256K1` key.
+    key_id: String,
+    region: String,
+}
+
+#[derive(Clone)]
+struct AwsCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    fn from_env() -> Result<Self, SignerError> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .map_err(|_| SignerError::CustomError(format!("{} is not set", name)))
+        };
+        Ok(Self {
+            access_key_id: var("AWS_ACCESS_KEY_ID")?,
+            secret_access_key: var("AWS_SECRET_ACCESS_KEY")?,
+            session_token: std::env::var("AWS_SESSION_TOKEN").ok(),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SignResponse {
+    signature: String,
+}
+
+impl AwsKmsClient {
+    pub fn new(key_id: String, region: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            key_id,
+            region,
+        }
+    }
+
+    /// Signs the digest and returns the DER-encoded signature.
+    pub(super) async fn sign_digest(&self, digest: H256) -> Result<Vec<u8>, SignerError> {
+        let body = json!({
+            "KeyId": self.key_id,
+            "Message": base64::encode(digest.as_bytes()),
+            "MessageType": "DIGEST",
+            "SigningAlgorithm": "ECDSA_SHA_256",
+        })
+        .to_string();
+
+        let host = format!("kms.{}.amazonaws.com", self.region);
+        let headers = sign_request(
+            &AwsCredentials::from_env()?,
+            &self.region,
+            &host,
+            "TrentService.Sign",
+            &body,
+            Utc::now(),
+        );
+
+        let mut request = self.client.post(format!("https://{}/", host)).body(body);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        let response = request.send().await.map_err(signing_failed)?;
+        let response: SignResponse = response_body(response).await?;
+
+        base64::decode(&response.signature).map_err(signing_failed)
+    }
+}
+
+/// Returns headers of the KMS request, including the SigV4 `Authorization` header.
+fn sign_request(
+    credentials: &AwsCredentials,
+    region: &str,
+    host: &str,
+    target: &str,
+    body: &str,
+    now: DateTime<Utc>,
+) -> Vec<(&'static str, String)> {
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+
+    // Canonical request requires headers to be sorted by name.
+    let mut headers = vec![
+        ("content-type", "application/x-amz-json-1.1".to_string()),
+        ("host", host.to_string()),
+        ("x-amz-date", amz_date.clone()),
+    ];
+    if let Some(token) = &credentials.session_token {
+        headers.push(("x-amz-security-token", token.clone()));
+    }
+    headers.push(("x-amz-target", target.to_string()));
+
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value))
+        .collect();
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_request = format!(
+        "POST\n/\n\n{}\n{}\n{}",
+        canonical_headers,
+        signed_headers,
+        hex::encode(&*digest::sha256(body.as_bytes()))
+    );
+
+    let scope = format!("{}/{}/kms/aws4_request", date, region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex::encode(&*digest::sha256(canonical_request.as_bytes()))
+    );
+    let signing_key = [date.as_str(), region, "kms", "aws4_request"].iter().fold(
+        format!("AWS4{}", credentials.secret_access_key).into_bytes(),
+        |key, data| hmac_sha256(&key, data.as_bytes()),
+    );
+    let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    // `Host` header is set by the HTTP client itself.
+    headers.retain(|(name, _)| *name != "host");
+    headers.push((
+        "authorization",
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            credentials.access_key_id, scope, signed_headers, signature
+        ),
+    ));
+    headers
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    hmac::sign(&hmac::SigKey::sha256(key), data).to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn request_signature() {
+        let credentials = AwsCredentials {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: None,
+        };
+        let headers = sign_request(
+            &credentials,
+            "us-east-1",
+            "kms.us-east-1.amazonaws.com",
+            "TrentService.Sign",
+            r#"{"KeyId":"test-key"}"#,
+            Utc.ymd(2021, 8, 1).and_hms(12, 0, 0),
+        );
+
+        let (_, authorization) = headers
+            .iter()
+            .find(|(name, _)| *name == "authorization")
+            .unwrap();
+        assert_eq!(
+            authorization,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20210801/us-east-1/kms/aws4_request, \
+             SignedHeaders=content-type;host;x-amz-date;x-amz-target, \
+             Signature=894e47f2129bcb2ee38a0dada8b99a0b3f46545b6e932a91c0ea5fafa1117d67"
+        );
+        assert!(headers.iter().all(|(name, _)| *name != "host"));
+    }
+}
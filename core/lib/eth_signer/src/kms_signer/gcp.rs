@@ -0,0 +1,96 @@
+//! Client of the Google Cloud Key Management Service.
+//!
+//! Access tokens are obtained from the metadata server of the instance, so the service
+//! account credentials don't have to be provided to the server at all.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+
+use zksync_types::H256;
+
+use super::{response_body, signing_failed};
+use crate::SignerError;
+
+const METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+/// Tokens are refreshed a bit earlier than they actually expire.
+const TOKEN_EXPIRATION_MARGIN: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub struct GcpKmsClient {
+    client: reqwest::Client,
+    /// Resource name of the `EC_SIGN_SECP256K1_SHA256` key version, i.e.
+    /// `projects/*/locations/*/keyRings/*/cryptoKeys/*/cryptoKeyVersions/*`.
+    key_name: String,
+    token: Arc<Mutex<Option<(String, Instant)>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct SignResponse {
+    signature: String,
+}
+
+impl GcpKmsClient {
+    pub fn new(key_name: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            key_name,
+            token: Default::default(),
+        }
+    }
+
+    async fn access_token(&self) -> Result<String, SignerError> {
+        let cached = self.token.lock().unwrap().clone();
+        if let Some((token, expires_at)) = cached {
+            if Instant::now() < expires_at {
+                return Ok(token);
+            }
+        }
+
+        let response = self
+            .client
+            .get(METADATA_TOKEN_URL)
+            .header("Metadata-Flavor", "Google")
+            .send()
+            .await
+            .map_err(signing_failed)?;
+        let response: TokenResponse = response_body(response).await?;
+
+        let expires_at = Instant::now()
+            + Duration::from_secs(response.expires_in).saturating_sub(TOKEN_EXPIRATION_MARGIN);
+        *self.token.lock().unwrap() = Some((response.access_token.clone(), expires_at));
+        Ok(response.access_token)
+    }
+
+    /// Signs the digest and returns the DER-encoded signature.
+    pub(super) async fn sign_digest(&self, digest: H256) -> Result<Vec<u8>, SignerError> {
+        let url = format!(
+            "https://cloudkms.googleapis.com/v1/{}:asymmetricSign",
+            self.key_name
+        );
+        // KMS doesn't check how the digest was obtained, so the keccak256 hash is passed as is.
+        let body = json!({
+            "digest": { "sha256": base64::encode(digest.as_bytes()) },
+        });
+
+        let response = self
+            .client
+            .post(url)
+            .bearer_auth(self.access_token().await?)
+            .json(&body)
+            .send()
+            .await
+            .map_err(signing_failed)?;
+        let response: SignResponse = response_body(response).await?;
+
+        base64::decode(&response.signature).map_err(signing_failed)
+    }
+}
@@ -0,0 +1,259 @@
+//! Signer backed by a cloud key management service (AWS KMS or GCP Cloud KMS).
+//!
+//! The private key never leaves the KMS: the signer only sends 32-byte digests to be signed
+//! and receives DER-encoded ECDSA signatures back. KMS doesn't return the recovery ID required
+//! by Ethereum, so it's restored by checking which candidate recovers the expected address.
+
+use parity_crypto::{
+    publickey::{public_to_address, recover, Signature},
+    Keccak256,
+};
+use serde::de::DeserializeOwned;
+
+use zksync_types::tx::{PackedEthSignature, TxEthSignature};
+use zksync_types::{Address, H256, U256};
+
+pub use aws::AwsKmsClient;
+pub use gcp::GcpKmsClient;
+
+use crate::raw_ethereum_tx::{RawTransaction, Transaction};
+use crate::{EthereumSigner, SignerError};
+
+mod aws;
+mod gcp;
+
+/// Order of the secp256k1 curve.
+const SECP256K1_N: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+
+#[derive(Debug, Clone)]
+pub enum KmsClient {
+    Aws(AwsKmsClient),
+    Gcp(GcpKmsClient),
+}
+
+impl KmsClient {
+    async fn sign_digest(&self, digest: H256) -> Result<Vec<u8>, SignerError> {
+        match self {
+            Self::Aws(client) => client.sign_digest(digest).await,
+            Self::Gcp(client) => client.sign_digest(digest).await,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KmsSigner {
+    client: KmsClient,
+    /// Address corresponding to the KMS key.
+    address: Address,
+}
+
+impl KmsSigner {
+    /// Creates a signer for the KMS key that corresponds to the provided address.
+    pub fn new(client: KmsClient, address: Address) -> Self {
+        Self { client, address }
+    }
+
+    async fn sign_digest(&self, digest: H256) -> Result<(u8, H256, H256), SignerError> {
+        let der_signature = self.client.sign_digest(digest).await?;
+        recoverable_signature(&der_signature, digest, self.address)
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for KmsSigner {
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+
+    /// The sign method calculates an Ethereum specific signature with:
+    /// sign(keccak256("\x19Ethereum Signed Message:\n" + len(message) + message))).
+    async fn sign_message(&self, message: &[u8]) -> Result<TxEthSignature, SignerError> {
+        let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
+        let mut bytes = Vec::with_capacity(prefix.len() + message.len());
+        bytes.extend_from_slice(prefix.as_bytes());
+        bytes.extend_from_slice(message);
+
+        let (recovery_id, r, s) = self.sign_digest(bytes.keccak256().into()).await?;
+        let mut packed = [0u8; 65];
+        packed[..32].copy_from_slice(r.as_bytes());
+        packed[32..64].copy_from_slice(s.as_bytes());
+        packed[64] = recovery_id;
+        let signature = PackedEthSignature::deserialize_packed(&packed).map_err(signing_failed)?;
+        Ok(TxEthSignature::EthereumSignature(signature))
+    }
+
+    /// Signs and returns the RLP-encoded transaction.
+    async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
+        let chain_id = raw_tx.chain_id;
+        let tx = Transaction::from(raw_tx);
+
+        let (recovery_id, r, s) = self.sign_digest(tx.signing_hash(chain_id)).await?;
+        Ok(tx.encode_signed(chain_id, recovery_id, r, s))
+    }
+}
+
+fn signing_failed(err: impl std::fmt::Display) -> SignerError {
+    SignerError::SigningFailed(err.to_string())
+}
+
+/// Deserializes the KMS response, failing if the KMS responded with an error.
+async fn response_body<T: DeserializeOwned>(response: reqwest::Response) -> Result<T, SignerError> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(SignerError::SigningFailed(format!(
+            "KMS responded with {}: {}",
+            status, body
+        )));
+    }
+    response.json().await.map_err(signing_failed)
+}
+
+/// Converts the DER-encoded signature into the `(recovery_id, r, s)` triple.
+fn recoverable_signature(
+    der_signature: &[u8],
+    digest: H256,
+    address: Address,
+) -> Result<(u8, H256, H256), SignerError> {
+    let (r, s) = parse_der_signature(der_signature)
+        .ok_or_else(|| SignerError::SigningFailed("Malformed DER signature".to_string()))?;
+
+    // Ethereum only accepts signatures with `s` in the lower half of the curve order (EIP-2).
+    let n = U256::from_str_radix(SECP256K1_N, 16).unwrap();
+    let s_value = U256::from_big_endian(s.as_bytes());
+    let s = if s_value > n / 2 {
+        let mut bytes = [0u8; 32];
+        (n - s_value).to_big_endian(&mut bytes);
+        H256(bytes)
+    } else {
+        s
+    };
+
+    for recovery_id in 0..2 {
+        let signature = Signature::from_rsv(&r, &s, recovery_id);
+        match recover(&signature, &digest) {
+            Ok(public_key) if public_to_address(&public_key) == address => {
+                return Ok((recovery_id, r, s));
+            }
+            _ => continue,
+        }
+    }
+    Err(SignerError::SigningFailed(format!(
+        "KMS key doesn't correspond to the address {:?}",
+        address
+    )))
+}
+
+/// Parses `ECDSA-Sig-Value ::= SEQUENCE { r INTEGER, s INTEGER }`.
+fn parse_der_signature(bytes: &[u8]) -> Option<(H256, H256)> {
+    let (tag, sequence, rest) = der_element(bytes)?;
+    if tag != 0x30 || !rest.is_empty() {
+        return None;
+    }
+    let (r_tag, r, sequence) = der_element(sequence)?;
+    let (s_tag, s, sequence) = der_element(sequence)?;
+    if r_tag != 0x02 || s_tag != 0x02 || !sequence.is_empty() {
+        return None;
+    }
+    Some((der_integer(r)?, der_integer(s)?))
+}
+
+/// Splits the DER element into its tag, contents and the remaining bytes.
+fn der_element(bytes: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, bytes) = bytes.split_first()?;
+    let (&len, bytes) = bytes.split_first()?;
+    let (len, bytes) = match len {
+        0..=0x7f => (len as usize, bytes),
+        // Signatures are short enough to never need more than one length byte.
+        0x81 => {
+            let (&len, bytes) = bytes.split_first()?;
+            (len as usize, bytes)
+        }
+        _ => return None,
+    };
+    if bytes.len() < len {
+        return None;
+    }
+    Some((tag, &bytes[..len], &bytes[len..]))
+}
+
+fn der_integer(bytes: &[u8]) -> Option<H256> {
+    // Positive integers are prefixed with a zero byte if their highest bit is set.
+    let start = bytes
+        .iter()
+        .position(|&byte| byte != 0)
+        .unwrap_or(bytes.len());
+    let bytes = &bytes[start..];
+    if bytes.len() > 32 {
+        return None;
+    }
+    let mut word = [0u8; 32];
+    word[32 - bytes.len()..].copy_from_slice(bytes);
+    Some(H256(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parity_crypto::publickey::{sign, KeyPair, Secret};
+
+    fn der_integer_bytes(value: &H256) -> Vec<u8> {
+        let mut bytes: Vec<u8> = value
+            .as_bytes()
+            .iter()
+            .copied()
+            .skip_while(|&byte| byte == 0)
+            .collect();
+        if bytes[0] & 0x80 != 0 {
+            bytes.insert(0, 0);
+        }
+        let mut encoded = vec![0x02, bytes.len() as u8];
+        encoded.extend(bytes);
+        encoded
+    }
+
+    fn der_signature(r: &H256, s: &H256) -> Vec<u8> {
+        let mut contents = der_integer_bytes(r);
+        contents.extend(der_integer_bytes(s));
+        let mut encoded = vec![0x30, contents.len() as u8];
+        encoded.extend(contents);
+        encoded
+    }
+
+    /// Checks that the DER signature is converted into the Ethereum one that recovers the signer,
+    /// regardless of whether KMS returned the signature with high or low `s`.
+    #[test]
+    fn recover_der_signature() {
+        let secret = Secret::copy_from_slice(&[5; 32]).unwrap();
+        let address = KeyPair::from_secret(secret.clone()).unwrap().address();
+        let digest = H256::from(b"zksync".keccak256());
+
+        let signature = sign(&secret, &digest).unwrap();
+        let (r, s) = (
+            H256::from_slice(signature.r()),
+            H256::from_slice(signature.s()),
+        );
+        let expected = (signature.v(), r, s);
+
+        let low_s = der_signature(&r, &s);
+        assert_eq!(
+            recoverable_signature(&low_s, digest, address).unwrap(),
+            expected
+        );
+
+        let n = U256::from_str_radix(SECP256K1_N, 16).unwrap();
+        let mut high_s = [0u8; 32];
+        (n - U256::from_big_endian(s.as_bytes())).to_big_endian(&mut high_s);
+        let high_s = der_signature(&r, &H256(high_s));
+        assert_eq!(
+            recoverable_signature(&high_s, digest, address).unwrap(),
+            expected
+        );
+
+        // Signature of another key.
+        assert!(recoverable_signature(&low_s, digest, Address::repeat_byte(1)).is_err());
+        // Malformed signatures.
+        assert!(recoverable_signature(&low_s[1..], digest, address).is_err());
+        assert!(recoverable_signature(&[0x30, 0x00], digest, address).is_err());
+    }
+}
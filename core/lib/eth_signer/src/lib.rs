@@ -7,13 +7,19 @@ use zksync_types::tx::TxEthSignature;
 use zksync_types::Address;
 
 pub use json_rpc_signer::JsonRpcSigner;
+pub use kms_signer::KmsSigner;
+pub use operator_signer::OperatorSigner;
 pub use pk_signer::PrivateKeySigner;
 pub use raw_ethereum_tx::RawTransaction;
+pub use remote_signer::RemoteSigner;
 
 pub mod error;
 pub mod json_rpc_signer;
+pub mod kms_signer;
+pub mod operator_signer;
 pub mod pk_signer;
 pub mod raw_ethereum_tx;
+pub mod remote_signer;
 
 #[async_trait]
 pub trait EthereumSigner: Send + Sync + Clone {
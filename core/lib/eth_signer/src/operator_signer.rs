@@ -0,0 +1,60 @@
+use crate::raw_ethereum_tx::RawTransaction;
+use crate::{EthereumSigner, KmsSigner, PrivateKeySigner, RemoteSigner, SignerError};
+
+use zksync_types::tx::TxEthSignature;
+use zksync_types::Address;
+
+/// Signer of the operator transactions.
+/// The backend is chosen in the configuration, e.g. the key may be stored in a KMS
+/// instead of being provided to the server directly.
+#[derive(Debug, Clone)]
+pub enum OperatorSigner {
+    PrivateKey(PrivateKeySigner),
+    Kms(KmsSigner),
+    Remote(RemoteSigner),
+}
+
+impl From<PrivateKeySigner> for OperatorSigner {
+    fn from(signer: PrivateKeySigner) -> Self {
+        Self::PrivateKey(signer)
+    }
+}
+
+impl From<KmsSigner> for OperatorSigner {
+    fn from(signer: KmsSigner) -> Self {
+        Self::Kms(signer)
+    }
+}
+
+impl From<RemoteSigner> for OperatorSigner {
+    fn from(signer: RemoteSigner) -> Self {
+        Self::Remote(signer)
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for OperatorSigner {
+    async fn sign_message(&self, message: &[u8]) -> Result<TxEthSignature, SignerError> {
+        match self {
+            Self::PrivateKey(signer) => signer.sign_message(message).await,
+            Self::Kms(signer) => signer.sign_message(message).await,
+            Self::Remote(signer) => signer.sign_message(message).await,
+        }
+    }
+
+    async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
+        match self {
+            Self::PrivateKey(signer) => signer.sign_transaction(raw_tx).await,
+            Self::Kms(signer) => signer.sign_transaction(raw_tx).await,
+            Self::Remote(signer) => signer.sign_transaction(raw_tx).await,
+        }
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        match self {
+            Self::PrivateKey(signer) => signer.get_address().await,
+            Self::Kms(signer) => signer.get_address().await,
+            Self::Remote(signer) => signer.get_address().await,
+        }
+    }
+}
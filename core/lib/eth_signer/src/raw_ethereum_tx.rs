@@ -11,7 +11,7 @@
 use rlp::RlpStream;
 use web3::{
     signing::{self, Signature},
-    types::{AccessList, Address, SignedTransaction, U256, U64},
+    types::{AccessList, Address, SignedTransaction, H256, U256, U64},
 };
 
 const LEGACY_TX_ID: u64 = 0;
@@ -58,6 +58,26 @@ pub struct Transaction {
     pub max_priority_fee_per_gas: U256,
 }
 
+impl From<RawTransaction> for Transaction {
+    fn from(raw_tx: RawTransaction) -> Self {
+        let gas_price = match raw_tx.max_fee_per_gas {
+            Some(val) => val,
+            None => raw_tx.gas_price,
+        };
+        Self {
+            to: raw_tx.to,
+            nonce: raw_tx.nonce,
+            gas: raw_tx.gas,
+            gas_price,
+            value: raw_tx.value,
+            data: raw_tx.data,
+            transaction_type: raw_tx.transaction_type,
+            access_list: raw_tx.access_list.unwrap_or_default(),
+            max_priority_fee_per_gas: raw_tx.max_priority_fee_per_gas.unwrap_or_default(),
+        }
+    }
+}
+
 impl Transaction {
     fn rlp_append_legacy(&self, stream: &mut RlpStream) {
         stream.append(&self.nonce);
@@ -185,12 +205,38 @@ impl Transaction {
         }
     }
 
-    /// Sign and return a raw signed transaction.
-    pub fn sign(self, sign: impl signing::Key, chain_id: u64) -> SignedTransaction {
-        let adjust_v_value = matches!(
+    fn is_legacy(&self) -> bool {
+        matches!(
             self.transaction_type.map(|t| t.as_u64()),
             Some(LEGACY_TX_ID) | None
-        );
+        )
+    }
+
+    /// Returns the transaction encoded without signature.
+    pub fn encode_unsigned(&self, chain_id: u64) -> Vec<u8> {
+        self.encode(chain_id, None)
+    }
+
+    /// Returns the hash that has to be signed for the transaction.
+    /// Used by signers that can't provide `signing::Key`, e.g. the remote ones.
+    pub fn signing_hash(&self, chain_id: u64) -> H256 {
+        signing::keccak256(self.encode_unsigned(chain_id).as_ref()).into()
+    }
+
+    /// Returns a raw transaction signed with the provided signature of the `signing_hash`.
+    pub fn encode_signed(&self, chain_id: u64, recovery_id: u8, r: H256, s: H256) -> Vec<u8> {
+        // Legacy transactions are protected from replays according to EIP-155.
+        let v = if self.is_legacy() {
+            recovery_id as u64 + 35 + chain_id * 2
+        } else {
+            recovery_id as u64
+        };
+        self.encode(chain_id, Some(&Signature { v, r, s }))
+    }
+
+    /// Sign and return a raw signed transaction.
+    pub fn sign(self, sign: impl signing::Key, chain_id: u64) -> SignedTransaction {
+        let adjust_v_value = self.is_legacy();
 
         let encoded = self.encode(chain_id, None);
 
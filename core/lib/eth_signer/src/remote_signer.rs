@@ -0,0 +1,291 @@
+//! Client of the remote signer service, which keeps the operator key isolated from the server.
+//!
+//! The protocol is a plain JSON over HTTPS with mutual TLS authentication:
+//!
+//! - `POST /sign_transaction` with `{ "chain_id", "tx", "hash" }`, where `tx` is the RLP-encoded
+//!   unsigned transaction and `hash` is the hash to be signed, returns `{ "signature" }`;
+//! - `POST /sign_message` with `{ "message" }` returns `{ "signature" }` calculated as in `eth_sign`.
+//!
+//! Signatures are 65-byte `r || s || v` hex strings. Every request carries the `X-Request-Id`
+//! header equal to the signed hash, so the requests may be matched in the audit logs of both sides.
+//! Before sending a transaction to the signer, the client checks it against the `SigningPolicy`.
+
+use std::collections::HashMap;
+
+use parity_crypto::{
+    publickey::{public_to_address, recover, Signature},
+    Keccak256,
+};
+use serde_json::{json, Value};
+
+use zksync_types::tx::{PackedEthSignature, TxEthSignature};
+use zksync_types::{Address, H256};
+
+use crate::raw_ethereum_tx::{RawTransaction, Transaction};
+use crate::{EthereumSigner, SignerError};
+
+/// Restricts transactions that can be sent to the remote signer.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SigningPolicy {
+    /// Contracts that transactions may be sent to, along with the selectors of their methods
+    /// that may be called.
+    pub allowed_methods: HashMap<Address, Vec<[u8; 4]>>,
+}
+
+impl SigningPolicy {
+    /// Allows calling the `methods` of the `contract`.
+    pub fn allow(mut self, contract: Address, methods: impl IntoIterator<Item = [u8; 4]>) -> Self {
+        self.allowed_methods
+            .entry(contract)
+            .or_default()
+            .extend(methods);
+        self
+    }
+
+    pub fn check(&self, raw_tx: &RawTransaction) -> Result<(), SignerError> {
+        let allowed_methods = raw_tx
+            .to
+            .and_then(|contract| self.allowed_methods.get(&contract))
+            .ok_or_else(|| {
+                SignerError::CustomError(format!(
+                    "Signing policy violation: transaction recipient {:?} is not allowed",
+                    raw_tx.to
+                ))
+            })?;
+        let selector = raw_tx.data.get(..4).unwrap_or_default();
+        if !allowed_methods.iter().any(|method| &method[..] == selector) {
+            return Err(SignerError::CustomError(format!(
+                "Signing policy violation: method 0x{} is not allowed",
+                hex::encode(selector)
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// TLS settings of the remote signer client.
+#[derive(Debug, Clone)]
+pub struct RemoteSignerTls {
+    /// PEM-encoded certificate of the CA that issued the signer certificate.
+    pub ca_certificate: Vec<u8>,
+    /// DER-encoded PKCS #12 archive with the client certificate and its private key.
+    pub identity: Vec<u8>,
+    pub identity_password: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    client: reqwest::Client,
+    url: String,
+    /// Address of the operator key held by the signer.
+    address: Address,
+    policy: SigningPolicy,
+}
+
+impl RemoteSigner {
+    pub fn new(
+        url: impl Into<String>,
+        address: Address,
+        policy: SigningPolicy,
+        tls: RemoteSignerTls,
+    ) -> Result<Self, SignerError> {
+        let url = url.into();
+        if !url.starts_with("https://") {
+            return Err(SignerError::CustomError(
+                "Remote signer must be accessed via HTTPS".to_string(),
+            ));
+        }
+        let ca_certificate =
+            reqwest::Certificate::from_pem(&tls.ca_certificate).map_err(custom_error)?;
+        let identity = reqwest::Identity::from_pkcs12_der(&tls.identity, &tls.identity_password)
+            .map_err(custom_error)?;
+        let client = reqwest::Client::builder()
+            .add_root_certificate(ca_certificate)
+            .identity(identity)
+            .build()
+            .map_err(custom_error)?;
+
+        Ok(Self {
+            client,
+            url,
+            address,
+            policy,
+        })
+    }
+
+    async fn request_signature(
+        &self,
+        method: &str,
+        request_id: H256,
+        body: Value,
+    ) -> Result<RemoteSignature, SignerError> {
+        let response = self
+            .client
+            .post(format!("{}/{}", self.url.trim_end_matches('/'), method))
+            .header("X-Request-Id", format!("{:x}", request_id))
+            .json(&body)
+            .send()
+            .await
+            .map_err(signing_failed)?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(SignerError::SigningFailed(format!(
+                "Remote signer responded with {}: {}",
+                status, body
+            )));
+        }
+        let response: SignatureResponse = response.json().await.map_err(signing_failed)?;
+        RemoteSignature::parse(&response.signature)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SignatureResponse {
+    signature: String,
+}
+
+/// Signature returned by the signer, `v` is normalized to the recovery ID.
+struct RemoteSignature {
+    r: H256,
+    s: H256,
+    recovery_id: u8,
+}
+
+impl RemoteSignature {
+    fn parse(signature: &str) -> Result<Self, SignerError> {
+        let bytes = hex::decode(signature.trim_start_matches("0x")).map_err(signing_failed)?;
+        if bytes.len() != 65 {
+            return Err(SignerError::SigningFailed(
+                "Signature length mismatch".to_string(),
+            ));
+        }
+        // Signers may return either `v = 27 + recovery_id` or just the recovery ID.
+        let v = bytes[64];
+        Ok(Self {
+            r: H256::from_slice(&bytes[..32]),
+            s: H256::from_slice(&bytes[32..64]),
+            recovery_id: if v >= 27 { v - 27 } else { v },
+        })
+    }
+
+    /// Returns the address of the signer of the hash.
+    fn recover(&self, hash: H256) -> Result<Address, SignerError> {
+        let signature = Signature::from_rsv(&self.r, &self.s, self.recovery_id);
+        recover(&signature, &hash)
+            .map(|public_key| public_to_address(&public_key))
+            .map_err(|err| SignerError::RecoverAddress(err.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for RemoteSigner {
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<TxEthSignature, SignerError> {
+        let request_id: H256 = message.keccak256().into();
+        vlog::info!(
+            "Remote signer request {:x}: sign message of {} bytes",
+            request_id,
+            message.len()
+        );
+        let body = json!({ "message": format!("0x{}", hex::encode(message)) });
+        let signature = self
+            .request_signature("sign_message", request_id, body)
+            .await?;
+
+        let mut packed = [0u8; 65];
+        packed[..32].copy_from_slice(signature.r.as_bytes());
+        packed[32..64].copy_from_slice(signature.s.as_bytes());
+        packed[64] = signature.recovery_id;
+        let signature = PackedEthSignature::deserialize_packed(&packed).map_err(signing_failed)?;
+
+        let signer = signature
+            .signature_recover_signer(message)
+            .map_err(|err| SignerError::RecoverAddress(err.to_string()))?;
+        if signer != self.address {
+            return Err(SignerError::SigningFailed(
+                "Invalid signature from RemoteSigner".to_string(),
+            ));
+        }
+        Ok(TxEthSignature::EthereumSignature(signature))
+    }
+
+    async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
+        self.policy.check(&raw_tx)?;
+
+        let chain_id = raw_tx.chain_id;
+        let tx = Transaction::from(raw_tx);
+        let hash = tx.signing_hash(chain_id);
+        vlog::info!(
+            "Remote signer request {:x}: sign transaction with nonce {} calling method 0x{}",
+            hash,
+            tx.nonce,
+            hex::encode(&tx.data[..4])
+        );
+        let body = json!({
+            "chain_id": chain_id,
+            "tx": format!("0x{}", hex::encode(tx.encode_unsigned(chain_id))),
+            "hash": format!("{:?}", hash),
+        });
+        let signature = self
+            .request_signature("sign_transaction", hash, body)
+            .await?;
+
+        if signature.recover(hash)? != self.address {
+            return Err(SignerError::SigningFailed(
+                "Invalid signature from RemoteSigner".to_string(),
+            ));
+        }
+        Ok(tx.encode_signed(chain_id, signature.recovery_id, signature.r, signature.s))
+    }
+}
+
+fn signing_failed(err: impl std::fmt::Display) -> SignerError {
+    SignerError::SigningFailed(err.to_string())
+}
+
+fn custom_error(err: impl std::fmt::Display) -> SignerError {
+    SignerError::CustomError(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> SigningPolicy {
+        SigningPolicy::default()
+            .allow(Address::repeat_byte(1), vec![[1, 2, 3, 4]])
+            .allow(Address::repeat_byte(3), vec![[4, 3, 2, 1]])
+    }
+
+    fn raw_tx(to: Option<Address>, data: Vec<u8>) -> RawTransaction {
+        RawTransaction {
+            to,
+            data,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn signing_policy() {
+        let policy = policy();
+        let contract = Some(Address::repeat_byte(1));
+
+        assert!(policy.check(&raw_tx(contract, vec![1, 2, 3, 4, 5])).is_ok());
+        // Unknown method.
+        assert!(policy.check(&raw_tx(contract, vec![1, 2, 3, 5])).is_err());
+        // Data is too short to contain the selector.
+        assert!(policy.check(&raw_tx(contract, vec![1, 2, 3])).is_err());
+        // Wrong recipient.
+        let other = Some(Address::repeat_byte(2));
+        assert!(policy.check(&raw_tx(other, vec![1, 2, 3, 4])).is_err());
+        assert!(policy.check(&raw_tx(None, vec![1, 2, 3, 4])).is_err());
+        // Methods are allowed per contract.
+        let unwrapper = Some(Address::repeat_byte(3));
+        assert!(policy.check(&raw_tx(unwrapper, vec![4, 3, 2, 1])).is_ok());
+        assert!(policy.check(&raw_tx(unwrapper, vec![1, 2, 3, 4])).is_err());
+    }
+}
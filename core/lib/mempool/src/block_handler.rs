@@ -7,6 +7,8 @@ use zksync_types::{
     PriorityOp,
 };
 
+use crate::encrypted::EncryptedMempool;
+use crate::ordering::TxOrdering;
 use crate::state::MempoolState;
 
 #[derive(Clone, Debug, Default)]
@@ -34,6 +36,8 @@ pub struct GetBlockRequest {
     pub last_priority_op_number: u64,
     pub block_timestamp: u64,
     pub executed_txs: Vec<TxHash>,
+    /// Only priority operations are proposed, e.g. while the intake of the L2 transactions is paused.
+    pub priority_ops_only: bool,
     pub response_sender: oneshot::Sender<ProposedBlock>,
 }
 
@@ -47,6 +51,8 @@ pub(crate) struct MempoolBlocksHandler {
     pub mempool_state: MempoolState,
     pub requests: mpsc::Receiver<MempoolBlocksRequest>,
     pub max_block_size_chunks: usize,
+    pub tx_ordering: Box<dyn TxOrdering>,
+    pub encrypted_mempool: Option<EncryptedMempool>,
 }
 
 impl MempoolBlocksHandler {
@@ -55,8 +61,24 @@ impl MempoolBlocksHandler {
         current_unprocessed_priority_op: u64,
         block_timestamp: u64,
         executed_txs: &[TxHash],
+        priority_ops_only: bool,
     ) -> Result<ProposedBlock, TxAddError> {
         let start = std::time::Instant::now();
+        // Encrypted transfers are decrypted only now, so they are proposed before anyone sees them.
+        // The block is proposed without them if they can't be revealed.
+        if let Some(encrypted_mempool) = self
+            .encrypted_mempool
+            .as_ref()
+            .filter(|_| !priority_ops_only)
+        {
+            if let Err(err) = self
+                .mempool_state
+                .reveal_encrypted_txs(encrypted_mempool)
+                .await
+            {
+                vlog::error!("Failed to reveal the encrypted transactions: {}", err);
+            }
+        }
         // Try to exhaust the reverted transactions queue. Most of the time it
         // will be empty unless the server is restarted after reverting blocks.
         let mut tx_queue = self
@@ -64,14 +86,23 @@ impl MempoolBlocksHandler {
             .get_transaction_queue(executed_txs)
             .await?;
 
-        let (txs, priority_ops, chunks_left) = tx_queue
-            .select_transactions(
+        let (txs, priority_ops, chunks_left) = if priority_ops_only {
+            let (chunks_left, priority_ops) = tx_queue.select_l1_transactions(
                 self.max_block_size_chunks,
                 current_unprocessed_priority_op,
-                block_timestamp,
-                &self.mempool_state,
-            )
-            .await?;
+            );
+            (Vec::new(), priority_ops, chunks_left)
+        } else {
+            tx_queue
+                .select_transactions(
+                    self.max_block_size_chunks,
+                    current_unprocessed_priority_op,
+                    block_timestamp,
+                    &self.mempool_state,
+                    self.tx_ordering.as_ref(),
+                )
+                .await?
+        };
 
         if !priority_ops.is_empty() || !txs.is_empty() {
             vlog::debug!(
@@ -102,6 +133,11 @@ impl MempoolBlocksHandler {
                     ("token", tx.tx.token_id().to_string()),
                 ];
                 metrics::histogram!("process_tx", tx.elapsed(), &labels);
+                metrics::histogram!(
+                    "mempool.inclusion_latency",
+                    tx.elapsed(),
+                    "policy" => self.tx_ordering.name()
+                );
             }
         }
         Ok(ProposedBlock { priority_ops, txs })
@@ -122,6 +158,7 @@ impl MempoolBlocksHandler {
                             block.last_priority_op_number,
                             block.block_timestamp,
                             &block.executed_txs,
+                            block.priority_ops_only,
                         )
                         .await
                         .expect("Unable to propose the new miniblock");
@@ -0,0 +1,112 @@
+//! Encrypted mempool, see `zksync_types::encrypted_tx` for the details.
+//!
+//! Blobs are decrypted right before the next miniblock is proposed, so the decrypted transfers
+//! are included into it unless the block is already full. Besides the signatures and the nonce,
+//! the transfers are subject to the same rules as the ones submitted via API: the minimal fee,
+//! the account freezes and the travel rule.
+//!
+//! The sealing key is split between the keyholders, so the blobs are decrypted with the
+//! decryption shares collected from them (see `Keyholders`), and neither the core nor any
+//! group of fewer than `threshold` keyholders can decrypt the blobs on its own.
+
+use num::{rational::Ratio, BigUint};
+use zksync_types::{
+    encrypted_tx::{EncryptedTransfer, EncryptedTxError},
+    tx::{EthSignData, TxEthSignature},
+    Token, Transfer, ZkSyncTx,
+};
+
+use crate::{FeeTokenPrices, Keyholders};
+
+/// Settings of the encrypted mempool.
+#[derive(Debug, Clone)]
+pub struct EncryptedMempool {
+    /// Keyholders of the sealing key the transfers are encrypted to.
+    pub keyholders: Keyholders,
+    /// Maximum amount of the blobs decrypted for one proposed block.
+    pub max_txs_per_block: usize,
+    /// Minimal fee of the transfer in USD.
+    pub min_fee: Ratio<BigUint>,
+    /// Value of the transfers in USD flagged for the operator, `None` if the travel rule is
+    /// disabled.
+    pub travel_rule_threshold_usd: Option<Ratio<BigUint>>,
+}
+
+impl EncryptedMempool {
+    /// Checks the decrypted transfer and returns it along with its Ethereum signature data.
+    /// `token` must be the token of the transfer.
+    pub(crate) fn check_transfer(
+        &self,
+        transfer: EncryptedTransfer,
+        token: &Token,
+        prices: &FeeTokenPrices,
+    ) -> Result<(ZkSyncTx, EthSignData), EncryptedTxError> {
+        transfer.verify_eth_signature(token)?;
+        if !self.is_fee_enough(&transfer.tx, prices) {
+            return Err(EncryptedTxError::FeeTooLow);
+        }
+
+        let message = transfer
+            .tx
+            .get_ethereum_sign_message(&token.symbol, token.decimals);
+        let eth_sign_data = EthSignData {
+            signature: TxEthSignature::EthereumSignature(transfer.eth_signature),
+            message: message.into_bytes(),
+        };
+        Ok((ZkSyncTx::Transfer(Box::new(transfer.tx)), eth_sign_data))
+    }
+
+    /// Fee can't be checked against the ticker when the blob is submitted, so the transfers
+    /// paying less than the minimal fee in USD are discarded. Fees in the tokens without the
+    /// known price are never enough.
+    fn is_fee_enough(&self, tx: &Transfer, prices: &FeeTokenPrices) -> bool {
+        prices
+            .get(&tx.token)
+            .map(|price| price * Ratio::from_integer(tx.fee.clone()) >= self.min_fee)
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use zksync_types::{tx::TimeRange, AccountId, Address, Nonce, TokenId};
+
+    use super::*;
+
+    #[test]
+    fn fee_check() {
+        let mempool = EncryptedMempool {
+            keyholders: Keyholders::new(
+                vec![(1, "http://127.0.0.1:3000".to_string())],
+                1,
+                "sample".to_string(),
+                Duration::from_secs(1),
+            )
+            .unwrap(),
+            max_txs_per_block: 10,
+            min_fee: Ratio::new(5u32.into(), 100u32.into()),
+            travel_rule_threshold_usd: None,
+        };
+        let mut prices = FeeTokenPrices::new();
+        prices.insert(TokenId(0), Ratio::new(1u32.into(), 1_000u32.into()));
+        let transfer = |token, fee: u32| {
+            Transfer::new(
+                AccountId(1),
+                Address::repeat_byte(1),
+                Address::repeat_byte(2),
+                TokenId(token),
+                100u32.into(),
+                fee.into(),
+                Nonce(0),
+                TimeRange::default(),
+                None,
+            )
+        };
+
+        assert!(mempool.is_fee_enough(&transfer(0, 50), &prices));
+        assert!(!mempool.is_fee_enough(&transfer(0, 49), &prices));
+        // Fee token without the price.
+        assert!(!mempool.is_fee_enough(&transfer(1, 1_000_000), &prices));
+    }
+}
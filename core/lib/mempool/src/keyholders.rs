@@ -0,0 +1,162 @@
+//! Client of the keyholders of the encrypted mempool.
+//!
+//! The sealing key is split between the keyholders, so the mempool requests the decryption shares
+//! of the blobs from every keyholder via its private core API (`POST /encrypted_mempool/shares`)
+//! right before the block formation. Blobs are only decrypted once the shares of `threshold`
+//! keyholders are collected, otherwise they're kept for the next block.
+//!
+//! Requests are authorized by the access tokens (JWT) signed with the secret shared by the
+//! keyholders.
+
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use anyhow::{bail, ensure};
+use futures::future::join_all;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde::Serialize;
+use serde_json::json;
+use zksync_types::encrypted_tx::Public;
+
+/// Lifetime of the access token of the decryption shares requests.
+const AUTH_TOKEN_LIFETIME: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize)]
+struct KeyholdersAuthToken {
+    /// Subject (whom auth token refers to).
+    sub: String,
+    /// Expiration time (as UTC timestamp).
+    exp: usize,
+}
+
+/// Client of the keyholders.
+#[derive(Debug, Clone)]
+pub struct Keyholders {
+    client: reqwest::Client,
+    /// Share indices of the keyholders and URLs of their private APIs.
+    keyholders: Vec<(u32, String)>,
+    threshold: usize,
+    secret_auth: String,
+}
+
+impl Keyholders {
+    /// Creates the client of the keyholders, any `threshold` of which can decrypt the blobs.
+    pub fn new(
+        keyholders: Vec<(u32, String)>,
+        threshold: usize,
+        secret_auth: String,
+        request_timeout: Duration,
+    ) -> anyhow::Result<Self> {
+        ensure!(
+            threshold > 0 && threshold <= keyholders.len(),
+            "Keyholders threshold must be in range 1..={}",
+            keyholders.len()
+        );
+        ensure!(
+            !secret_auth.is_empty(),
+            "Secret of the keyholders is not set"
+        );
+
+        let client = reqwest::Client::builder()
+            .timeout(request_timeout)
+            .build()?;
+        Ok(Self {
+            client,
+            keyholders,
+            threshold,
+            secret_auth,
+        })
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Creates the access token of the decryption shares requests.
+    fn auth_token(&self) -> anyhow::Result<String> {
+        let exp = UNIX_EPOCH.elapsed()? + AUTH_TOKEN_LIFETIME;
+        let token = encode(
+            &Header::default(),
+            &KeyholdersAuthToken {
+                sub: "keyholders".to_string(),
+                exp: exp.as_secs() as usize,
+            },
+            &EncodingKey::from_secret(self.secret_auth.as_ref()),
+        )?;
+        Ok(token)
+    }
+
+    /// Collects the decryption shares of the blobs with the given ephemeral keys. Returns the
+    /// shares of each blob as the pairs of the keyholder index and its share, or an error if
+    /// fewer than `threshold` keyholders have responded.
+    pub async fn collect_shares(
+        &self,
+        ephemeral_keys: &[Public],
+    ) -> anyhow::Result<Vec<Vec<(u32, Public)>>> {
+        let start = Instant::now();
+        let body = json!({ "ephemeral_keys": ephemeral_keys });
+        let token = self.auth_token()?;
+
+        let requests = self
+            .keyholders
+            .iter()
+            .map(|(_, url)| self.request_shares(url, &token, &body));
+        let responses = join_all(requests).await;
+
+        let mut shares = vec![Vec::new(); ephemeral_keys.len()];
+        let mut responded = 0;
+        for ((index, url), response) in self.keyholders.iter().zip(responses) {
+            match response {
+                Ok(keyholder_shares) if keyholder_shares.len() == ephemeral_keys.len() => {
+                    for (blob_shares, share) in shares.iter_mut().zip(keyholder_shares) {
+                        blob_shares.push((*index, share));
+                    }
+                    responded += 1;
+                }
+                Ok(keyholder_shares) => vlog::warn!(
+                    "Keyholder {} returned {} decryption shares for {} blobs",
+                    url,
+                    keyholder_shares.len(),
+                    ephemeral_keys.len()
+                ),
+                Err(err) => {
+                    vlog::warn!("Keyholder {} didn't return decryption shares: {}", url, err)
+                }
+            }
+        }
+
+        metrics::histogram!("mempool.keyholders.collect_shares", start.elapsed());
+        if responded < self.threshold {
+            metrics::increment_counter!("mempool.keyholders.quorum_not_reached");
+            bail!(
+                "Keyholders quorum is not reached: {} of {} keyholders responded",
+                responded,
+                self.threshold
+            );
+        }
+        Ok(shares)
+    }
+
+    async fn request_shares(
+        &self,
+        url: &str,
+        token: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<Vec<Public>> {
+        let response = self
+            .client
+            .post(format!(
+                "{}/encrypted_mempool/shares",
+                url.trim_end_matches('/')
+            ))
+            .bearer_auth(token)
+            .json(body)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!("Keyholder responded with {}: {}", status, body);
+        }
+        Ok(response.json().await?)
+    }
+}
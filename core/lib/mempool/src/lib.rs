@@ -21,13 +21,24 @@ use zksync_storage::ConnectionPool;
 // Local uses
 use crate::block_handler::MempoolBlocksHandler;
 pub use crate::block_handler::{GetBlockRequest, MempoolBlocksRequest, ProposedBlock};
+pub use crate::encrypted::EncryptedMempool;
+pub use crate::keyholders::Keyholders;
 use crate::mempool_transactions_queue::MempoolTransactionsQueue;
+pub use crate::ordering::{
+    FeePriorityOrdering, FeeTokenPrices, FifoOrdering, QosOrdering, RoundRobinOrdering, TxOrdering,
+};
+pub use crate::qos::{QosClass, QosWeights};
 use crate::state::MempoolState;
 pub use crate::transactions_handler::MempoolTransactionRequest;
 use crate::transactions_handler::MempoolTransactionsHandler;
 
 mod block_handler;
+mod encrypted;
+mod keyholders;
 mod mempool_transactions_queue;
+mod ordering;
+mod priority_ops_dedup;
+mod qos;
 mod state;
 mod transactions_handler;
 
@@ -58,6 +69,8 @@ pub fn run_mempool_block_handler(
     db_pool: ConnectionPool,
     block_requests: mpsc::Receiver<MempoolBlocksRequest>,
     block_chunk_sizes: Vec<usize>,
+    tx_ordering: Box<dyn TxOrdering>,
+    encrypted_mempool: Option<EncryptedMempool>,
 ) -> JoinHandle<()> {
     let mempool_state = MempoolState::new(db_pool);
     let max_block_size_chunks = *block_chunk_sizes
@@ -69,6 +82,8 @@ pub fn run_mempool_block_handler(
         mempool_state,
         requests: block_requests,
         max_block_size_chunks,
+        tx_ordering,
+        encrypted_mempool,
     };
 
     tokio::spawn(blocks_handler.run())
@@ -1,3 +1,4 @@
+use crate::ordering::{fee_tokens, FeeTokenPrices, TxOrdering};
 use crate::MempoolState;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, VecDeque};
@@ -63,10 +64,6 @@ impl MempoolTransactionsQueue {
         res
     }
 
-    fn pop_l2_transactions_front(&mut self) -> Option<SignedTxVariant> {
-        self.ready_l2_transactions.pop_front()
-    }
-
     fn pop_front_l1_transactions(&mut self) -> Option<PriorityOp> {
         self.l1_transactions.pop_front()
     }
@@ -132,19 +129,21 @@ impl MempoolTransactionsQueue {
         current_unprocessed_priority_op: u64,
         block_timestamp: u64,
         mempool_state: &MempoolState,
+        ordering: &dyn TxOrdering,
     ) -> Result<(Vec<SignedTxVariant>, Vec<PriorityOp>, usize), TxAddError> {
+        // Priority operations are selected first, so they can't be starved by L2 transactions.
         let (chunks_left, priority_ops) =
             self.select_l1_transactions(chunks, current_unprocessed_priority_op);
 
         let (chunks_left, executed_txs) = self
-            .select_l2_transactions(chunks_left, block_timestamp, mempool_state)
+            .select_l2_transactions(chunks_left, block_timestamp, mempool_state, ordering)
             .await?;
 
         Ok((executed_txs, priority_ops, chunks_left))
     }
 
     /// Returns: chunks left from max amount of chunks, ops selected
-    fn select_l1_transactions(
+    pub(crate) fn select_l1_transactions(
         &mut self,
         max_block_size_chunks: usize,
         current_unprocessed_l1_tx: u64,
@@ -174,18 +173,27 @@ impl MempoolTransactionsQueue {
         (max_block_size_chunks - used_chunks, result)
     }
 
-    /// Collect txs depending on the remaining chunks size
+    /// Collect txs depending on the remaining chunks size, in the order chosen by the ordering policy
     async fn select_l2_transactions(
         &mut self,
         mut chunks_left: usize,
         block_timestamp: u64,
         mempool_state: &MempoolState,
+        ordering: &dyn TxOrdering,
     ) -> Result<(usize, Vec<SignedTxVariant>), TxAddError> {
         self.prepare_new_ready_l2_transactions(block_timestamp);
 
-        let mut txs_for_commit = Vec::new();
+        let ready_txs: Vec<_> = self.ready_l2_transactions.drain(..).collect();
+        let prices = if ordering.uses_fee_prices() {
+            mempool_state
+                .load_fee_token_prices(&fee_tokens(&ready_txs))
+                .await?
+        } else {
+            FeeTokenPrices::new()
+        };
 
-        while let Some(tx) = self.pop_l2_transactions_front() {
+        let mut txs_for_commit = Vec::new();
+        for tx in ordering.order(ready_txs, &prices) {
             let chunks_for_tx = mempool_state.required_chunks(&tx).await?;
             if chunks_left >= chunks_for_tx {
                 txs_for_commit.push(tx);
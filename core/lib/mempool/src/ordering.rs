@@ -0,0 +1,281 @@
+//! Policies of the order in which the proposer drains the ready L2 transactions of the mempool.
+//!
+//! Whatever the policy is, the transactions of the same account are never reordered, since
+//! otherwise they would fail because of the nonce mismatch. Policies only decide how the
+//! transactions of the different accounts are interleaved.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use num::{rational::Ratio, BigUint, Zero};
+use zksync_types::{mempool::SignedTxVariant, AccountId, TokenId, TokenLike};
+
+use crate::qos::{QosScheduler, QosWeights};
+
+/// USD prices of the smallest units of the fee tokens.
+pub type FeeTokenPrices = HashMap<TokenId, Ratio<BigUint>>;
+
+/// Order in which the ready L2 transactions are included into the proposed block.
+pub trait TxOrdering: fmt::Debug + Send + Sync {
+    /// Name of the policy used as the label of its metrics.
+    fn name(&self) -> &'static str;
+
+    /// Whether the policy needs the prices of the fee tokens to order transactions.
+    fn uses_fee_prices(&self) -> bool {
+        false
+    }
+
+    /// Returns the transactions in the order they should be included into the block.
+    /// Transactions are given in the order they became ready in.
+    fn order(&self, txs: Vec<SignedTxVariant>, prices: &FeeTokenPrices) -> Vec<SignedTxVariant>;
+}
+
+/// Weighted round-robin over the QoS classes of transactions, see `QosScheduler`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QosOrdering(pub QosWeights);
+
+impl TxOrdering for QosOrdering {
+    fn name(&self) -> &'static str {
+        "qos"
+    }
+
+    fn order(&self, txs: Vec<SignedTxVariant>, _prices: &FeeTokenPrices) -> Vec<SignedTxVariant> {
+        let mut scheduler = QosScheduler::new(txs, self.0);
+        std::iter::from_fn(|| scheduler.pop()).collect()
+    }
+}
+
+/// Transactions are included in the order they were received by the server.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FifoOrdering;
+
+impl TxOrdering for FifoOrdering {
+    fn name(&self) -> &'static str {
+        "fifo"
+    }
+
+    fn order(&self, txs: Vec<SignedTxVariant>, _prices: &FeeTokenPrices) -> Vec<SignedTxVariant> {
+        sort_by_account_key(txs, |tx| {
+            tx.get_transactions().iter().map(|tx| tx.created_at).max()
+        })
+    }
+}
+
+/// Transactions paying the biggest fee in USD are included first, which lets the operator
+/// prioritize the paying users during the congestion. Fees in the tokens without the known
+/// price are considered zero.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FeePriorityOrdering;
+
+impl TxOrdering for FeePriorityOrdering {
+    fn name(&self) -> &'static str {
+        "fee_priority"
+    }
+
+    fn uses_fee_prices(&self) -> bool {
+        true
+    }
+
+    fn order(&self, txs: Vec<SignedTxVariant>, prices: &FeeTokenPrices) -> Vec<SignedTxVariant> {
+        sort_by_account_key(txs, |tx| std::cmp::Reverse(fee_value(tx, prices)))
+    }
+}
+
+/// Accounts take turns: every account gets one transaction included per round, so a single
+/// account with many queued transactions can't postpone the transactions of the others.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RoundRobinOrdering;
+
+impl TxOrdering for RoundRobinOrdering {
+    fn name(&self) -> &'static str {
+        "round_robin"
+    }
+
+    fn order(&self, txs: Vec<SignedTxVariant>, _prices: &FeeTokenPrices) -> Vec<SignedTxVariant> {
+        let mut account_rounds: HashMap<AccountId, usize> = HashMap::new();
+        let mut txs: Vec<_> = txs
+            .into_iter()
+            .map(|tx| {
+                let accounts = accounts_of(&tx);
+                // Batch takes a turn of every account it contains.
+                let round = accounts
+                    .iter()
+                    .filter_map(|account_id| account_rounds.get(account_id))
+                    .map(|round| round + 1)
+                    .max()
+                    .unwrap_or(0);
+                for account_id in accounts {
+                    account_rounds.insert(account_id, round);
+                }
+                (round, tx)
+            })
+            .collect();
+
+        txs.sort_by_key(|(round, _)| *round);
+        txs.into_iter().map(|(_, tx)| tx).collect()
+    }
+}
+
+/// Fee tokens of the transactions, used to load their prices.
+pub(crate) fn fee_tokens(txs: &[SignedTxVariant]) -> Vec<TokenId> {
+    let mut tokens: Vec<_> = txs
+        .iter()
+        .flat_map(|tx| tx.get_transactions())
+        .filter_map(|tx| match tx.tx.get_fee_info() {
+            Some((_, TokenLike::Id(token_id), _, _)) => Some(token_id),
+            _ => None,
+        })
+        .collect();
+    tokens.sort_unstable();
+    tokens.dedup();
+    tokens
+}
+
+/// Total fee of the transaction or batch in USD.
+fn fee_value(tx: &SignedTxVariant, prices: &FeeTokenPrices) -> Ratio<BigUint> {
+    tx.get_transactions()
+        .iter()
+        .filter_map(|tx| match tx.tx.get_fee_info() {
+            Some((_, TokenLike::Id(token_id), _, fee)) => prices
+                .get(&token_id)
+                .map(|price| price * Ratio::from_integer(fee)),
+            _ => None,
+        })
+        .fold(Ratio::zero(), |total, fee| total + fee)
+}
+
+fn accounts_of(tx: &SignedTxVariant) -> Vec<AccountId> {
+    tx.get_transactions()
+        .iter()
+        .filter_map(|tx| tx.tx.account_id().ok())
+        .collect()
+}
+
+/// Sorts transactions by the key in the ascending order.
+///
+/// A transaction never gets a smaller key than the earlier transactions of the same accounts,
+/// so the stable sort doesn't reorder them.
+fn sort_by_account_key<K, F>(txs: Vec<SignedTxVariant>, key: F) -> Vec<SignedTxVariant>
+where
+    K: Ord + Clone,
+    F: Fn(&SignedTxVariant) -> K,
+{
+    let mut account_keys: HashMap<AccountId, K> = HashMap::new();
+    let mut txs: Vec<_> = txs
+        .into_iter()
+        .map(|tx| {
+            let accounts = accounts_of(&tx);
+            let key = accounts
+                .iter()
+                .filter_map(|account_id| account_keys.get(account_id))
+                .cloned()
+                .fold(key(&tx), std::cmp::max);
+            for account_id in accounts {
+                account_keys.insert(account_id, key.clone());
+            }
+            (key, tx)
+        })
+        .collect();
+
+    txs.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+    txs.into_iter().map(|(_, tx)| tx).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use zksync_types::{tx::Transfer, Address, Nonce, SignedZkSyncTx, ZkSyncTx};
+
+    use super::*;
+
+    fn transfer(account_id: u32, nonce: u32, token: u32, fee: u32, age: i64) -> SignedTxVariant {
+        let transfer = Transfer::new(
+            AccountId(account_id),
+            Address::random(),
+            Address::random(),
+            TokenId(token),
+            500u32.into(),
+            fee.into(),
+            Nonce(nonce),
+            Default::default(),
+            None,
+        );
+
+        SignedTxVariant::Tx(SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at: Utc::now() - Duration::seconds(age),
+        })
+    }
+
+    fn nonces(txs: Vec<SignedTxVariant>) -> Vec<u32> {
+        txs.into_iter()
+            .map(|tx| match tx {
+                SignedTxVariant::Tx(tx) => *tx.tx.nonce(),
+                SignedTxVariant::Batch(_) => unreachable!(),
+            })
+            .collect()
+    }
+
+    /// Checks that the oldest transactions go first unless it would reorder an account.
+    #[test]
+    fn fifo() {
+        // Every account except the first one has a single transaction, so nonces are used as
+        // identifiers.
+        let txs = vec![
+            transfer(1, 0, 0, 1, 10),
+            transfer(1, 1, 0, 1, 50),
+            transfer(2, 2, 0, 1, 30),
+            transfer(3, 3, 0, 1, 40),
+        ];
+
+        assert_eq!(
+            nonces(FifoOrdering.order(txs, &FeeTokenPrices::new())),
+            vec![3, 2, 0, 1]
+        );
+    }
+
+    /// Checks that fees are compared in USD, and the accounts are not reordered.
+    #[test]
+    fn fee_priority() {
+        let mut prices = FeeTokenPrices::new();
+        prices.insert(TokenId(0), Ratio::from_integer(10u32.into()));
+        prices.insert(TokenId(1), Ratio::from_integer(1u32.into()));
+        let txs = vec![
+            transfer(1, 0, 1, 50, 0),
+            transfer(2, 1, 0, 2, 0),
+            transfer(2, 2, 0, 100, 0),
+            transfer(3, 3, 0, 8, 0),
+            // Fee token without the price.
+            transfer(4, 4, 2, 1_000, 0),
+        ];
+
+        assert_eq!(
+            nonces(FeePriorityOrdering.order(txs, &prices)),
+            vec![3, 0, 1, 2, 4]
+        );
+        assert_eq!(
+            fee_tokens(&[transfer(1, 0, 2, 1, 0), transfer(2, 0, 1, 1, 0)]),
+            vec![TokenId(1), TokenId(2)]
+        );
+    }
+
+    /// Checks that accounts take turns.
+    #[test]
+    fn round_robin() {
+        let txs = vec![
+            transfer(1, 0, 0, 1, 0),
+            transfer(1, 1, 0, 1, 0),
+            transfer(1, 2, 0, 1, 0),
+            transfer(2, 10, 0, 1, 0),
+            transfer(2, 11, 0, 1, 0),
+            transfer(3, 20, 0, 1, 0),
+        ];
+
+        assert_eq!(
+            nonces(RoundRobinOrdering.order(txs, &FeeTokenPrices::new())),
+            vec![0, 10, 20, 1, 11, 2]
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Deduplication of the priority operations received from the Ethereum watcher.
+//!
+//! The watcher re-sends the operations of the overlapping block ranges, after restarts and
+//! after reverting its block range, so the same serial id may arrive many times. Serial ids
+//! of the executed operations and the hashes of the operations stored in mempool are persistent,
+//! so the duplicates are detected regardless of the watcher state.
+
+use std::collections::HashMap;
+
+use zksync_types::{PriorityOp, SerialId, H256};
+
+/// Stored state of the priority operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoredPriorityOp {
+    pub eth_hash: H256,
+    pub confirmed: bool,
+}
+
+/// Numbers of the skipped duplicates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SkippedDuplicates {
+    /// Operations that were already executed.
+    pub executed: usize,
+    /// Operations that are already stored in mempool with the same or stronger confirmation status.
+    pub stored: usize,
+}
+
+/// Removes the operations that were already executed or stored.
+///
+/// The operation stored in mempool is only updated if it becomes confirmed or its Ethereum
+/// transaction has changed (i.e. there was a reorg on L1). A confirmed operation is never
+/// downgraded to unconfirmed.
+pub fn sift_duplicate_priority_ops(
+    ops: Vec<PriorityOp>,
+    confirmed: bool,
+    last_executed_serial_id: Option<SerialId>,
+    stored: &HashMap<SerialId, StoredPriorityOp>,
+) -> (Vec<PriorityOp>, SkippedDuplicates) {
+    let mut skipped = SkippedDuplicates::default();
+    let mut new_ops: Vec<PriorityOp> = Vec::with_capacity(ops.len());
+    for op in ops {
+        if matches!(last_executed_serial_id, Some(serial_id) if op.serial_id <= serial_id) {
+            skipped.executed += 1;
+            continue;
+        }
+        let is_stored = stored.get(&op.serial_id).map_or(false, |stored| {
+            stored.eth_hash == op.eth_hash && (stored.confirmed || !confirmed)
+        });
+        // The same operation may also be repeated within the request.
+        let is_repeated = new_ops
+            .iter()
+            .any(|new_op| new_op.serial_id == op.serial_id && new_op.eth_hash == op.eth_hash);
+        if is_stored || is_repeated {
+            skipped.stored += 1;
+            continue;
+        }
+        new_ops.push(op);
+    }
+    (new_ops, skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{Address, Deposit, TokenId, ZkSyncPriorityOp};
+
+    fn priority_op(serial_id: SerialId, eth_hash: H256) -> PriorityOp {
+        PriorityOp {
+            serial_id,
+            data: ZkSyncPriorityOp::Deposit(Deposit {
+                from: Address::zero(),
+                token: TokenId(0),
+                amount: 100u32.into(),
+                to: Address::zero(),
+            }),
+            deadline_block: 100,
+            eth_hash,
+            eth_block: 10,
+            eth_block_index: Some(1),
+        }
+    }
+
+    #[test]
+    fn duplicates_are_skipped() {
+        let hash = H256::repeat_byte(1);
+        let reorged_hash = H256::repeat_byte(2);
+        let mut stored = HashMap::new();
+        stored.insert(
+            3,
+            StoredPriorityOp {
+                eth_hash: hash,
+                confirmed: true,
+            },
+        );
+        stored.insert(
+            4,
+            StoredPriorityOp {
+                eth_hash: hash,
+                confirmed: false,
+            },
+        );
+        stored.insert(
+            5,
+            StoredPriorityOp {
+                eth_hash: hash,
+                confirmed: false,
+            },
+        );
+        let ops = vec![
+            priority_op(1, hash),
+            priority_op(3, hash),
+            priority_op(4, hash),
+            priority_op(5, reorged_hash),
+            priority_op(6, hash),
+            priority_op(6, hash),
+        ];
+
+        // Unconfirmed operations don't update the stored ones with the same hash.
+        let (new_ops, skipped) = sift_duplicate_priority_ops(ops.clone(), false, Some(2), &stored);
+        let serial_ids: Vec<_> = new_ops.iter().map(|op| op.serial_id).collect();
+        assert_eq!(serial_ids, vec![5, 6]);
+        assert_eq!(
+            skipped,
+            SkippedDuplicates {
+                executed: 1,
+                stored: 3
+            }
+        );
+
+        // Confirmation of the stored unconfirmed operation is not a duplicate.
+        let (new_ops, skipped) = sift_duplicate_priority_ops(ops, true, Some(2), &stored);
+        let serial_ids: Vec<_> = new_ops.iter().map(|op| op.serial_id).collect();
+        assert_eq!(serial_ids, vec![4, 5, 6]);
+        assert_eq!(
+            skipped,
+            SkippedDuplicates {
+                executed: 1,
+                stored: 2
+            }
+        );
+    }
+}
@@ -0,0 +1,337 @@
+//! Quality of service classes for the transactions proposed by mempool.
+//!
+//! Priority operations always go first in the proposed block, so L1-originated operations
+//! can't be starved by the L2 load. L2 transactions are split into classes and scheduled
+//! using a weighted round-robin, so that e.g. a flood of zero-fee transactions can't
+//! postpone forced exits or the fee-paying transactions indefinitely.
+
+use std::collections::{HashMap, VecDeque};
+
+use num::Zero;
+use zksync_types::{mempool::SignedTxVariant, AccountId};
+
+/// Class of an operation, in the order of decreasing priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum QosClass {
+    /// L1-originated operations. These are not scheduled and always included first.
+    PriorityOp,
+    ForcedExit,
+    FeePaying,
+    /// Transactions without fee, e.g. ones whitelisted for subsidies.
+    ZeroFee,
+}
+
+/// Classes of L2 transactions in the scheduling order.
+const L2_CLASSES: [QosClass; 3] = [QosClass::ForcedExit, QosClass::FeePaying, QosClass::ZeroFee];
+
+impl QosClass {
+    /// Determines the class of an L2 transaction.
+    /// Batch belongs to the most privileged class of its transactions.
+    pub fn of_tx(tx: &SignedTxVariant) -> Self {
+        tx.get_transactions()
+            .iter()
+            .map(|tx| {
+                if matches!(tx.tx, zksync_types::ZkSyncTx::ForcedExit(_)) {
+                    QosClass::ForcedExit
+                } else if tx
+                    .tx
+                    .get_fee_info()
+                    .map(|(_, _, _, fee)| fee.is_zero())
+                    .unwrap_or(true)
+                {
+                    QosClass::ZeroFee
+                } else {
+                    QosClass::FeePaying
+                }
+            })
+            .min()
+            .unwrap_or(QosClass::ZeroFee)
+    }
+
+    fn index(self) -> usize {
+        match self {
+            QosClass::PriorityOp => unreachable!("Priority operations are not scheduled"),
+            QosClass::ForcedExit => 0,
+            QosClass::FeePaying => 1,
+            QosClass::ZeroFee => 2,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            QosClass::PriorityOp => "priority_op",
+            QosClass::ForcedExit => "forced_exit",
+            QosClass::FeePaying => "fee_paying",
+            QosClass::ZeroFee => "zero_fee",
+        }
+    }
+}
+
+/// Amount of transactions taken from each class per one scheduling round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QosWeights {
+    pub forced_exit: usize,
+    pub fee_paying: usize,
+    pub zero_fee: usize,
+}
+
+impl Default for QosWeights {
+    fn default() -> Self {
+        Self {
+            forced_exit: 8,
+            fee_paying: 4,
+            zero_fee: 1,
+        }
+    }
+}
+
+impl QosWeights {
+    fn get(&self, class: QosClass) -> usize {
+        // Zero weight would starve the class completely.
+        let weight = match class {
+            QosClass::PriorityOp => unreachable!("Priority operations are not scheduled"),
+            QosClass::ForcedExit => self.forced_exit,
+            QosClass::FeePaying => self.fee_paying,
+            QosClass::ZeroFee => self.zero_fee,
+        };
+        std::cmp::max(weight, 1)
+    }
+}
+
+/// Transaction waiting in the class queue.
+#[derive(Debug)]
+struct QueuedTx {
+    /// Position of the transaction in the order it became ready in.
+    seq_no: usize,
+    accounts: Vec<AccountId>,
+    tx: SignedTxVariant,
+}
+
+/// Weighted round-robin scheduler over the ready L2 transactions.
+#[derive(Debug)]
+pub(crate) struct QosScheduler {
+    queues: [VecDeque<QueuedTx>; 3],
+    /// Sequence numbers of the queued transactions of every account, earliest first.
+    account_txs: HashMap<AccountId, VecDeque<usize>>,
+    weights: QosWeights,
+    /// Index of the class currently being served.
+    current: usize,
+    /// Amount of transactions taken from the current class during this round.
+    served: usize,
+}
+
+impl QosScheduler {
+    /// Splits the transactions into classes, preserving their relative order.
+    ///
+    /// A transaction never gets a more privileged class than the earlier transactions of the
+    /// same accounts, so it doesn't wait in the class queue it can't be taken from.
+    pub(crate) fn new(txs: impl IntoIterator<Item = SignedTxVariant>, weights: QosWeights) -> Self {
+        let mut queues: [VecDeque<QueuedTx>; 3] = Default::default();
+        let mut account_txs: HashMap<AccountId, VecDeque<usize>> = HashMap::new();
+        let mut account_classes: HashMap<AccountId, QosClass> = HashMap::new();
+
+        for (seq_no, tx) in txs.into_iter().enumerate() {
+            let mut accounts: Vec<AccountId> = tx
+                .get_transactions()
+                .iter()
+                .filter_map(|tx| tx.tx.account_id().ok())
+                .collect();
+            accounts.sort_unstable();
+            accounts.dedup();
+
+            let class = accounts
+                .iter()
+                .filter_map(|account_id| account_classes.get(account_id))
+                .copied()
+                .fold(QosClass::of_tx(&tx), std::cmp::max);
+            for &account_id in &accounts {
+                account_classes.insert(account_id, class);
+                account_txs.entry(account_id).or_default().push_back(seq_no);
+            }
+
+            queues[class.index()].push_back(QueuedTx {
+                seq_no,
+                accounts,
+                tx,
+            });
+        }
+
+        for class in L2_CLASSES.iter() {
+            metrics::gauge!(
+                "mempool.qos.ready_txs",
+                queues[class.index()].len() as f64,
+                "class" => class.as_str()
+            );
+        }
+
+        Self {
+            queues,
+            account_txs,
+            weights,
+            current: 0,
+            served: 0,
+        }
+    }
+
+    /// Returns the next transaction to be included into the block.
+    ///
+    /// Transactions of the same account must not overtake each other, since otherwise they will
+    /// fail because of the nonce mismatch. The earlier transaction may still wait in the queue of
+    /// the more privileged class, so the class is served by its first transaction whose
+    /// accounts have no earlier transactions queued.
+    pub(crate) fn pop(&mut self) -> Option<SignedTxVariant> {
+        // One extra step is needed to return to the class we've started from.
+        // The earliest queued transaction can always be taken, so none is found only once
+        // the queues are empty.
+        for _ in 0..=L2_CLASSES.len() {
+            let class = L2_CLASSES[self.current];
+            if self.served < self.weights.get(class) {
+                if let Some(tx) = self.pop_ready(self.current) {
+                    self.served += 1;
+                    return Some(tx);
+                }
+            }
+            self.current = (self.current + 1) % L2_CLASSES.len();
+            self.served = 0;
+        }
+        None
+    }
+
+    fn pop_ready(&mut self, queue: usize) -> Option<SignedTxVariant> {
+        let account_txs = &self.account_txs;
+        let position = self.queues[queue].iter().position(|queued| {
+            queued.accounts.iter().all(|account_id| {
+                account_txs
+                    .get(account_id)
+                    .and_then(|txs| txs.front())
+                    .map_or(true, |&seq_no| seq_no == queued.seq_no)
+            })
+        })?;
+        let queued = self.queues[queue]
+            .remove(position)
+            .expect("position is in the queue");
+
+        for account_id in &queued.accounts {
+            if let Some(txs) = self.account_txs.get_mut(account_id) {
+                txs.pop_front();
+            }
+        }
+        Some(queued.tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use zksync_types::{
+        tx::{ForcedExit, Transfer},
+        Address, Nonce, SignedZkSyncTx, TokenId, ZkSyncTx,
+    };
+
+    use super::*;
+
+    fn transfer(account_id: u32, nonce: u32, fee: u32) -> SignedTxVariant {
+        let transfer = Transfer::new(
+            AccountId(account_id),
+            Address::random(),
+            Address::random(),
+            TokenId(0),
+            500u32.into(),
+            fee.into(),
+            Nonce(nonce),
+            Default::default(),
+            None,
+        );
+
+        SignedTxVariant::Tx(SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+        })
+    }
+
+    fn forced_exit(account_id: u32, nonce: u32) -> SignedTxVariant {
+        let forced_exit = ForcedExit::new(
+            AccountId(account_id),
+            Address::random(),
+            TokenId(0),
+            10u32.into(),
+            Nonce(nonce),
+            Default::default(),
+            None,
+        );
+
+        SignedTxVariant::Tx(SignedZkSyncTx {
+            tx: ZkSyncTx::ForcedExit(Box::new(forced_exit)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+        })
+    }
+
+    fn nonces(scheduler: &mut QosScheduler) -> Vec<u32> {
+        std::iter::from_fn(|| scheduler.pop())
+            .map(|tx| match tx {
+                SignedTxVariant::Tx(tx) => *tx.tx.nonce(),
+                SignedTxVariant::Batch(_) => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn classify() {
+        assert_eq!(QosClass::of_tx(&forced_exit(1, 0)), QosClass::ForcedExit);
+        assert_eq!(QosClass::of_tx(&transfer(1, 0, 10)), QosClass::FeePaying);
+        assert_eq!(QosClass::of_tx(&transfer(1, 0, 0)), QosClass::ZeroFee);
+    }
+
+    /// Checks that classes are served according to their weights and no class is starved.
+    #[test]
+    fn weighted_scheduling() {
+        let weights = QosWeights {
+            forced_exit: 2,
+            fee_paying: 1,
+            zero_fee: 1,
+        };
+        // Every transaction belongs to a different account, so nonces are used as identifiers.
+        let txs = vec![
+            transfer(1, 1, 0),
+            transfer(2, 2, 0),
+            transfer(3, 3, 10),
+            transfer(4, 4, 10),
+            forced_exit(5, 5),
+            forced_exit(6, 6),
+            forced_exit(7, 7),
+        ];
+        let mut scheduler = QosScheduler::new(txs, weights);
+
+        assert_eq!(nonces(&mut scheduler), vec![5, 6, 3, 1, 7, 4, 2]);
+    }
+
+    /// Checks that transactions of the same account are never reordered.
+    #[test]
+    fn account_order_is_preserved() {
+        let txs = vec![transfer(1, 0, 0), forced_exit(1, 1), transfer(2, 5, 10)];
+        let mut scheduler = QosScheduler::new(txs, QosWeights::default());
+
+        // Forced exit is downgraded to the zero-fee class of the previous transaction.
+        assert_eq!(nonces(&mut scheduler), vec![5, 0, 1]);
+    }
+
+    /// Checks that the transaction of the less privileged class waits for the earlier
+    /// transaction of the same account queued behind the backlog of the more privileged class.
+    #[test]
+    fn account_order_is_preserved_across_classes() {
+        let mut txs: Vec<_> = (10..18).map(|nonce| forced_exit(nonce, nonce)).collect();
+        txs.push(forced_exit(1, 0));
+        txs.push(transfer(1, 1, 0));
+        txs.push(transfer(2, 30, 0));
+        let mut scheduler = QosScheduler::new(txs, QosWeights::default());
+
+        // The turn of the zero-fee class goes to the transaction of the other account.
+        assert_eq!(
+            nonces(&mut scheduler),
+            vec![10, 11, 12, 13, 14, 15, 16, 17, 30, 0, 1]
+        );
+    }
+}
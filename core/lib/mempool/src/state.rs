@@ -1,14 +1,19 @@
 use std::collections::HashSet;
 use std::time::Instant;
 
-use zksync_storage::{ConnectionPool, StorageProcessor};
+use chrono::{DateTime, Utc};
+use num::{rational::Ratio, BigUint};
+use zksync_storage::{
+    travel_rule::records::TravelRuleRecord, ConnectionPool, QueryResult, StorageProcessor,
+};
 use zksync_types::{
+    encrypted_tx::{decrypt_transfer_with_shares, ephemeral_key, EncryptedTransfer},
     mempool::{SignedTxVariant, SignedTxsBatch},
     tx::{error::TxAddError, TxHash},
-    Address, TransferOp, TransferToNewOp, ZkSyncTx,
+    Address, SignedZkSyncTx, TokenId, TokenLike, TransferOp, TransferToNewOp, ZkSyncTx,
 };
 
-use crate::MempoolTransactionsQueue;
+use crate::{EncryptedMempool, FeeTokenPrices, MempoolTransactionsQueue};
 
 #[derive(Debug, Clone)]
 pub(crate) struct MempoolState {
@@ -98,6 +103,239 @@ impl MempoolState {
         }
     }
 
+    /// Loads the USD prices of the smallest units of the given tokens.
+    /// Tokens without the known price are skipped.
+    pub async fn load_fee_token_prices(
+        &self,
+        tokens: &[TokenId],
+    ) -> Result<FeeTokenPrices, TxAddError> {
+        let start = Instant::now();
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        let mut prices = FeeTokenPrices::new();
+        for &token_id in tokens {
+            let token = storage
+                .tokens_schema()
+                .get_token(TokenLike::Id(token_id))
+                .await
+                .map_err(|_| TxAddError::DbError)?;
+            let price = storage
+                .tokens_schema()
+                .get_historical_ticker_price(token_id)
+                .await
+                .map_err(|_| TxAddError::DbError)?;
+            if let (Some(token), Some(price)) = (token, price) {
+                let unit = BigUint::from(10u32).pow(u32::from(token.decimals));
+                prices.insert(token_id, price.usd_price / unit);
+            }
+        }
+
+        metrics::histogram!("mempool_state.load_fee_token_prices", start.elapsed());
+        Ok(prices)
+    }
+
+    /// Decrypts the oldest blobs of the encrypted mempool and moves the accepted transfers to the
+    /// mempool, the rest are discarded. Transfers are hidden from the public API until executed.
+    ///
+    /// Each transfer is checked in its own savepoint, so the one which can't be checked due to
+    /// the database error is discarded without affecting the rest. If the blobs can't be taken,
+    /// the quorum of the keyholders isn't reached or the transfers can't be committed, the blobs
+    /// are kept for the next block.
+    pub async fn reveal_encrypted_txs(
+        &self,
+        settings: &EncryptedMempool,
+    ) -> Result<(), TxAddError> {
+        let start = Instant::now();
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        let blobs = transaction
+            .encrypted_mempool_schema()
+            .take_txs(settings.max_txs_per_block)
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        if blobs.is_empty() {
+            return Ok(());
+        }
+
+        // Blobs without the ephemeral key can't be decrypted, so the keyholders aren't asked for
+        // their shares.
+        let total = blobs.len();
+        let blobs: Vec<_> = blobs
+            .into_iter()
+            .filter_map(|blob| match ephemeral_key(&blob.blob) {
+                Ok(ephemeral_key) => Some((blob, ephemeral_key)),
+                Err(err) => {
+                    vlog::debug!("Encrypted transaction {} is discarded: {}", blob.id, err);
+                    None
+                }
+            })
+            .collect();
+        let ephemeral_keys: Vec<_> = blobs.iter().map(|(_, key)| *key).collect();
+        let shares = if ephemeral_keys.is_empty() {
+            Vec::new()
+        } else {
+            match settings.keyholders.collect_shares(&ephemeral_keys).await {
+                Ok(shares) => shares,
+                Err(err) => {
+                    // The transaction is rolled back, so the blobs are kept for the next block.
+                    vlog::warn!("Encrypted transactions are not revealed: {}", err);
+                    return Ok(());
+                }
+            }
+        };
+
+        let threshold = settings.keyholders.threshold();
+        let transfers: Vec<_> = blobs
+            .into_iter()
+            .zip(shares)
+            .filter_map(|((blob, _), shares)| {
+                match decrypt_transfer_with_shares(&blob.blob, &shares, threshold) {
+                    Ok(transfer) => Some((transfer, blob.created_at)),
+                    Err(err) => {
+                        vlog::debug!("Encrypted transaction {} is discarded: {}", blob.id, err);
+                        None
+                    }
+                }
+            })
+            .collect();
+        let mut tokens: Vec<_> = transfers
+            .iter()
+            .map(|(transfer, _)| transfer.tx.token)
+            .collect();
+        tokens.sort_unstable();
+        tokens.dedup();
+        let prices = self.load_fee_token_prices(&tokens).await?;
+
+        let mut revealed = 0;
+        for (transfer, created_at) in transfers {
+            let (account_id, nonce) = (transfer.tx.account_id, transfer.tx.nonce);
+            let mut savepoint = transaction
+                .start_transaction()
+                .await
+                .map_err(|_| TxAddError::DbError)?;
+            let result = Self::accept_encrypted_transfer(
+                &mut savepoint,
+                settings,
+                transfer,
+                created_at,
+                &prices,
+            )
+            .await;
+            match result {
+                Ok(Ok(())) => {
+                    savepoint.commit().await.map_err(|_| TxAddError::DbError)?;
+                    revealed += 1;
+                }
+                Ok(Err(reason)) => vlog::debug!(
+                    "Encrypted transfer of the account {} with nonce {} is discarded: {}",
+                    *account_id,
+                    *nonce,
+                    reason
+                ),
+                Err(err) => {
+                    metrics::increment_counter!("mempool.encrypted_txs.db_errors");
+                    vlog::warn!(
+                        "Encrypted transfer of the account {} with nonce {} is discarded, \
+                         it can't be checked: {}",
+                        *account_id,
+                        *nonce,
+                        err
+                    );
+                }
+            }
+        }
+        transaction
+            .commit()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        metrics::counter!("mempool.encrypted_txs", revealed as u64, "result" => "revealed");
+        metrics::counter!("mempool.encrypted_txs", (total - revealed) as u64, "result" => "discarded");
+        metrics::histogram!("mempool_state.reveal_encrypted_txs", start.elapsed());
+        Ok(())
+    }
+
+    /// Applies the checks of the API to the decrypted transfer and stores the accepted one in the
+    /// mempool. The outer error is a database error, the inner one is the reason of the rejection.
+    async fn accept_encrypted_transfer(
+        storage: &mut StorageProcessor<'_>,
+        settings: &EncryptedMempool,
+        transfer: EncryptedTransfer,
+        created_at: DateTime<Utc>,
+        prices: &FeeTokenPrices,
+    ) -> QueryResult<Result<(), String>> {
+        let token = match storage
+            .tokens_schema()
+            .get_token(TokenLike::Id(transfer.tx.token))
+            .await?
+        {
+            Some(token) => token,
+            None => return Ok(Err("Token is not found".to_string())),
+        };
+        let nonce = storage
+            .chain()
+            .account_schema()
+            .estimate_nonce(transfer.tx.account_id)
+            .await?
+            .unwrap_or_default();
+        if transfer.tx.nonce < nonce {
+            return Ok(Err(TxAddError::NonceMismatch.to_string()));
+        }
+        // Transfers of the frozen accounts are rejected by the API as well.
+        if let Some(freeze) = storage
+            .account_freezes_schema()
+            .active_freeze(transfer.tx.account_id)
+            .await?
+        {
+            return Ok(Err(format!("Account is frozen: {}", freeze.reason)));
+        }
+        let amount_usd = prices
+            .get(&transfer.tx.token)
+            .map(|price| price * Ratio::from_integer(transfer.tx.amount.clone()));
+        let (tx, eth_sign_data) = match settings.check_transfer(transfer, &token, prices) {
+            Ok(checked) => checked,
+            Err(err) => return Ok(Err(err.to_string())),
+        };
+
+        let tx = SignedZkSyncTx {
+            tx,
+            eth_sign_data: Some(eth_sign_data),
+            created_at,
+        };
+        let tx_hash = tx.hash();
+        storage.chain().mempool_schema().insert_tx(&tx).await?;
+        storage
+            .private_txs_schema()
+            .mark_private_txs(None, &[tx_hash])
+            .await?;
+        // The metadata can't be attached to the encrypted transfers, so the large ones are
+        // flagged the same way as the ones submitted via API.
+        if let ZkSyncTx::Transfer(transfer) = &tx.tx {
+            if let Some(record) = TravelRuleRecord::for_transfer(
+                tx_hash,
+                transfer,
+                amount_usd,
+                settings.travel_rule_threshold_usd.as_ref(),
+                None,
+            ) {
+                metrics::increment_counter!("mempool.encrypted_txs.travel_rule_flagged");
+                storage.travel_rule_schema().save_record(record).await?;
+            }
+        }
+        Ok(Ok(()))
+    }
+
     pub async fn collect_garbage(&self) {
         let mut storage = self.db_pool.access_storage().await.expect("Db error");
         // Remove any possible duplicates of already executed transactions
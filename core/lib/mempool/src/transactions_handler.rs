@@ -1,14 +1,19 @@
 use futures::channel::{mpsc, oneshot};
 use futures::StreamExt;
 
-use zksync_storage::ConnectionPool;
+use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::{
+    fee_sponsor::sponsorship_key,
+    gas_counter::{CommitCost, VerifyCost},
     mempool::SignedTxsBatch,
     tx::{error::TxAddError, TxEthSignature},
-    PriorityOp, SignedZkSyncTx,
+    PriorityOp, SerialId, SignedZkSyncTx, ZkSyncTx, H256,
 };
 
-use crate::state::MempoolState;
+use crate::{
+    priority_ops_dedup::{sift_duplicate_priority_ops, StoredPriorityOp},
+    state::MempoolState,
+};
 
 #[derive(Debug)]
 pub enum MempoolTransactionRequest {
@@ -32,6 +37,15 @@ pub enum MempoolTransactionRequest {
         Vec<TxEthSignature>,
         oneshot::Sender<Result<(), TxAddError>>,
     ),
+    /// Add a new batch submitted by the client of the private channel with the given ID.
+    /// Invariants are the same as in `NewTxsBatch` variant, but the transactions are hidden
+    /// from the public API until they are executed.
+    NewPrivateTxsBatch(
+        i64,
+        Vec<SignedZkSyncTx>,
+        Vec<TxEthSignature>,
+        oneshot::Sender<Result<(), TxAddError>>,
+    ),
 }
 
 pub(crate) struct MempoolTransactionsHandler {
@@ -63,7 +77,12 @@ impl MempoolTransactionsHandler {
             return Err(TxAddError::NonceMismatch);
         }
 
-        storage
+        let mut transaction = storage.start_transaction().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        Self::charge_sponsor(&mut transaction, &tx.tx).await?;
+        transaction
             .chain()
             .mempool_schema()
             .insert_tx(&tx)
@@ -72,6 +91,10 @@ impl MempoolTransactionsHandler {
                 vlog::error!("Mempool storage access error: {}", err);
                 TxAddError::DbError
             })?;
+        transaction.commit().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
 
         let labels = vec![
             ("stage", "mempool".to_string()),
@@ -83,11 +106,57 @@ impl MempoolTransactionsHandler {
         Ok(())
     }
 
+    /// Charges the gas of the zero-fee transfer from the daily budget of its sponsor.
+    /// Transfers of the senders which aren't sponsors are not affected.
+    async fn charge_sponsor(
+        storage: &mut StorageProcessor<'_>,
+        tx: &ZkSyncTx,
+    ) -> Result<(), TxAddError> {
+        let ((address, token), to) = match (sponsorship_key(tx), tx) {
+            (Some(key), ZkSyncTx::Transfer(transfer)) => (key, transfer.to),
+            _ => return Ok(()),
+        };
+
+        let is_sponsor = storage
+            .fee_sponsors_schema()
+            .load_sponsor(address, token)
+            .await
+            .map_err(|_| TxAddError::DbError)?
+            .is_some();
+        if !is_sponsor {
+            return Ok(());
+        }
+
+        let recipient_exists = storage
+            .chain()
+            .account_schema()
+            .does_account_exist(to)
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        let gas = if recipient_exists {
+            CommitCost::TRANSFER_COST + VerifyCost::TRANSFER_COST
+        } else {
+            CommitCost::TRANSFER_TO_NEW_COST + VerifyCost::TRANSFER_TO_NEW_COST
+        };
+
+        let charged = storage
+            .fee_sponsors_schema()
+            .charge_gas(address, token, gas)
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        if !charged {
+            metrics::increment_counter!("mempool.fee_sponsors.budget_exceeded");
+            return Err(TxAddError::SponsorGasBudgetExceeded);
+        }
+        metrics::counter!("mempool.fee_sponsors.gas_charged", gas);
+        Ok(())
+    }
+
     /// Add priority operations to the mempool. For a better UX, we save unconfirmed transactions
     /// to the database. And we will move them to the real queue when they are confirmed.
     async fn add_priority_ops(
         &mut self,
-        mut ops: Vec<PriorityOp>,
+        ops: Vec<PriorityOp>,
         confirmed: bool,
     ) -> Result<(), TxAddError> {
         let mut storage = self.db_pool.access_storage().await.map_err(|err| {
@@ -101,9 +170,40 @@ impl MempoolTransactionsHandler {
             .await
             .map_err(|_| TxAddError::DbError)?;
 
-        if let Some(serial_id) = last_processed_priority_op {
-            ops.retain(|op| op.serial_id > serial_id)
-        }
+        let serial_ids: Vec<_> = ops.iter().map(|op| op.serial_id).collect();
+        let stored = storage
+            .chain()
+            .mempool_schema()
+            .load_priority_ops_hashes(&serial_ids)
+            .await
+            .map_err(|err| {
+                vlog::error!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?
+            .into_iter()
+            .map(|op| {
+                (
+                    op.serial_id as SerialId,
+                    StoredPriorityOp {
+                        eth_hash: H256::from_slice(&op.eth_hash),
+                        confirmed: op.confirmed,
+                    },
+                )
+            })
+            .collect();
+
+        let (ops, skipped) =
+            sift_duplicate_priority_ops(ops, confirmed, last_processed_priority_op, &stored);
+        metrics::counter!(
+            "mempool.priority_ops.skipped_duplicates",
+            skipped.executed as u64,
+            "reason" => "executed"
+        );
+        metrics::counter!(
+            "mempool.priority_ops.skipped_duplicates",
+            skipped.stored as u64,
+            "reason" => "stored"
+        );
 
         // Nothing to insert
         if ops.is_empty() {
@@ -137,6 +237,7 @@ impl MempoolTransactionsHandler {
         &mut self,
         txs: Vec<SignedZkSyncTx>,
         eth_signatures: Vec<TxEthSignature>,
+        private_client: Option<i64>,
     ) -> Result<(), TxAddError> {
         let batch = SignedTxsBatch {
             txs: txs.clone(),
@@ -144,6 +245,11 @@ impl MempoolTransactionsHandler {
             eth_signatures: eth_signatures.clone(),
         };
 
+        // The size is checked first, since it takes a connection of its own.
+        if self.mempool_state.chunks_for_batch(&batch).await? > self.max_block_size_chunks {
+            return Err(TxAddError::BatchTooBig);
+        }
+
         let mut storage = self.db_pool.access_storage().await.map_err(|err| {
             vlog::error!("Mempool storage access error: {}", err);
             TxAddError::DbError
@@ -166,10 +272,6 @@ impl MempoolTransactionsHandler {
             }
         }
 
-        if self.mempool_state.chunks_for_batch(&batch).await? > self.max_block_size_chunks {
-            return Err(TxAddError::BatchTooBig);
-        }
-
         for tx in &batch.txs {
             let labels = vec![
                 ("stage", "mempool".to_string()),
@@ -180,7 +282,11 @@ impl MempoolTransactionsHandler {
             metrics::histogram!("process_tx", tx.elapsed(), &labels);
         }
 
-        storage
+        let mut transaction = storage.start_transaction().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        transaction
             .chain()
             .mempool_schema()
             .insert_batch(&batch.txs, eth_signatures)
@@ -189,6 +295,22 @@ impl MempoolTransactionsHandler {
                 vlog::warn!("Mempool storage access error: {}", err);
                 TxAddError::DbError
             })?;
+        // Batch must not be visible even for a moment, so it's marked in the same transaction.
+        if let Some(client_id) = private_client {
+            let tx_hashes: Vec<_> = batch.txs.iter().map(|tx| tx.hash()).collect();
+            transaction
+                .private_txs_schema()
+                .mark_private_txs(Some(client_id), &tx_hashes)
+                .await
+                .map_err(|err| {
+                    vlog::warn!("Mempool storage access error: {}", err);
+                    TxAddError::DbError
+                })?;
+        }
+        transaction.commit().await.map_err(|err| {
+            vlog::warn!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
 
         Ok(())
     }
@@ -202,7 +324,16 @@ impl MempoolTransactionsHandler {
                     resp.send(tx_add_result).unwrap_or_default();
                 }
                 MempoolTransactionRequest::NewTxsBatch(txs, eth_signatures, resp) => {
-                    let tx_add_result = self.add_batch(txs, eth_signatures).await;
+                    let tx_add_result = self.add_batch(txs, eth_signatures, None).await;
+                    resp.send(tx_add_result).unwrap_or_default();
+                }
+                MempoolTransactionRequest::NewPrivateTxsBatch(
+                    client_id,
+                    txs,
+                    eth_signatures,
+                    resp,
+                ) => {
+                    let tx_add_result = self.add_batch(txs, eth_signatures, Some(client_id)).await;
                     resp.send(tx_add_result).unwrap_or_default();
                 }
                 MempoolTransactionRequest::NewPriorityOps(ops, confirmed, resp) => {
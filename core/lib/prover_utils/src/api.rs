@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use zksync_circuit::serialization::ProverData;
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
-use zksync_types::BlockNumber;
+use zksync_types::{prover::ProofSystemVersion, BlockNumber};
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ProverInputRequest {
@@ -39,6 +39,9 @@ pub struct ProverOutputRequest {
     pub first_block: BlockNumber,
     pub last_block: BlockNumber,
     pub data: JobResultData,
+    /// Proof system the proof is created with, missing for the older provers.
+    #[serde(default)]
+    pub proof_system: ProofSystemVersion,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -30,6 +30,7 @@ pub mod api;
 pub mod exit_proof;
 pub mod fs_utils;
 pub mod network_utils;
+pub mod payload;
 
 pub const SETUP_MIN_POW2: u32 = 20;
 pub const SETUP_MAX_POW2: u32 = 26;
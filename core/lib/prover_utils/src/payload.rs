@@ -0,0 +1,215 @@
+//! Encoding of the payloads exchanged over the prover API.
+//!
+//! Witnesses and proofs are serialized as JSON and may be compressed, which matters for the
+//! provers running outside of the server network. The compression is passed in the
+//! `X-Payload-Compression` header, and the SHA-256 hash of the uncompressed JSON in the
+//! `X-Content-Hash` header, so the receiver checks the payload after decompressing it.
+//! Payloads without the compression header are plain JSON, as sent by the older provers.
+
+// Built-in deps
+use std::io::{Read, Write};
+use std::str::FromStr;
+// External deps
+use flate2::{read::GzDecoder, write::GzEncoder};
+use parity_crypto::digest::sha256;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+// Workspace deps
+use zksync_types::H256;
+
+pub const PAYLOAD_COMPRESSION_HEADER: &str = "X-Payload-Compression";
+pub const CONTENT_HASH_HEADER: &str = "X-Content-Hash";
+
+/// Zstd level providing most of the gain at a small fraction of the maximum level time.
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadCompression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl PayloadCompression {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+        }
+    }
+
+    /// Parses the value of the compression header, missing header means no compression.
+    pub fn from_header(value: Option<&str>) -> Result<Self, PayloadError> {
+        value.map_or(Ok(Self::None), str::parse)
+    }
+
+    fn compress(self, data: Vec<u8>) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(data),
+            Self::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(&data)?;
+                encoder.finish()
+            }
+            Self::Zstd => zstd::stream::encode_all(data.as_slice(), ZSTD_LEVEL),
+        }
+    }
+
+    fn decompress(self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(data.to_vec()),
+            Self::Gzip => {
+                let mut decompressed = Vec::new();
+                GzDecoder::new(data).read_to_end(&mut decompressed)?;
+                Ok(decompressed)
+            }
+            Self::Zstd => zstd::stream::decode_all(data),
+        }
+    }
+}
+
+impl Default for PayloadCompression {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl FromStr for PayloadCompression {
+    type Err = PayloadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "gzip" => Ok(Self::Gzip),
+            "zstd" => Ok(Self::Zstd),
+            _ => Err(PayloadError::UnknownCompression(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PayloadError {
+    #[error("Unknown payload compression: {0}")]
+    UnknownCompression(String),
+    #[error("Malformed content hash: {0}")]
+    MalformedHash(String),
+    #[error("Failed to decompress the payload: {0}")]
+    Decompression(std::io::Error),
+    #[error("Content hash mismatch, expected {expected:?}, got {actual:?}")]
+    HashMismatch { expected: H256, actual: H256 },
+    #[error("Malformed payload: {0}")]
+    Json(serde_json::Error),
+}
+
+/// Payload ready to be sent as the HTTP body.
+#[derive(Debug, Clone)]
+pub struct EncodedPayload {
+    pub body: Vec<u8>,
+    pub compression: PayloadCompression,
+    pub content_hash: H256,
+}
+
+impl EncodedPayload {
+    pub fn encode<T: Serialize>(
+        value: &T,
+        compression: PayloadCompression,
+    ) -> anyhow::Result<Self> {
+        let json = serde_json::to_vec(value)?;
+        let content_hash = content_hash(&json);
+        Ok(Self {
+            body: compression.compress(json)?,
+            compression,
+            content_hash,
+        })
+    }
+
+    /// Returns the headers describing the payload.
+    pub fn headers(&self) -> [(&'static str, String); 2] {
+        [
+            (
+                PAYLOAD_COMPRESSION_HEADER,
+                self.compression.as_str().to_string(),
+            ),
+            (CONTENT_HASH_HEADER, format!("{:?}", self.content_hash)),
+        ]
+    }
+}
+
+/// Decodes the received payload, checking its content hash if it was sent.
+/// Returns the value along with the actual content hash.
+pub fn decode_payload<T: DeserializeOwned>(
+    body: &[u8],
+    compression: PayloadCompression,
+    content_hash_header: Option<&str>,
+) -> Result<(T, H256), PayloadError> {
+    let expected = content_hash_header
+        .map(|value| {
+            H256::from_str(value.trim_start_matches("0x"))
+                .map_err(|_| PayloadError::MalformedHash(value.to_string()))
+        })
+        .transpose()?;
+    let json = compression
+        .decompress(body)
+        .map_err(PayloadError::Decompression)?;
+
+    let actual = content_hash(&json);
+    if let Some(expected) = expected {
+        if expected != actual {
+            return Err(PayloadError::HashMismatch { expected, actual });
+        }
+    }
+    let value = serde_json::from_slice(&json).map_err(PayloadError::Json)?;
+    Ok((value, actual))
+}
+
+/// SHA-256 hash of the uncompressed JSON payload.
+pub fn content_hash(json: &[u8]) -> H256 {
+    H256::from_slice(&sha256(json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::WorkingOn;
+
+    /// Checks that the payloads survive the round trip with every compression,
+    /// and the corrupted ones are rejected.
+    #[test]
+    fn payload_round_trip() {
+        let value = WorkingOn {
+            prover_name: "prover".repeat(100),
+            job_id: 42,
+        };
+        for compression in [
+            PayloadCompression::None,
+            PayloadCompression::Gzip,
+            PayloadCompression::Zstd,
+        ] {
+            let payload = EncodedPayload::encode(&value, compression).unwrap();
+            if compression != PayloadCompression::None {
+                assert!(payload.body.len() < 600);
+            }
+            let [(_, compression_header), (_, hash_header)] = payload.headers();
+            let compression = PayloadCompression::from_header(Some(&compression_header)).unwrap();
+
+            let (decoded, content_hash): (WorkingOn, _) =
+                decode_payload(&payload.body, compression, Some(&hash_header)).unwrap();
+            assert_eq!(decoded.prover_name, value.prover_name);
+            assert_eq!(content_hash, payload.content_hash);
+
+            let other_hash = format!("{:?}", H256::repeat_byte(0x11));
+            assert!(matches!(
+                decode_payload::<WorkingOn>(&payload.body, compression, Some(&other_hash)),
+                Err(PayloadError::HashMismatch { .. })
+            ));
+        }
+
+        assert_eq!(
+            PayloadCompression::from_header(None).unwrap(),
+            PayloadCompression::None
+        );
+        assert!(PayloadCompression::from_header(Some("brotli")).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+//! Caches shared between the API replicas.
+//!
+//! Every API process keeps its own in-memory caches, which get inconsistent across the replicas.
+//! With the `redis` cache backend the cached entries are also stored in Redis, and the core
+//! publishes the number of every sealed block to the invalidation channel.
+//!
+//! Entries are grouped by the last sealed block known to the cache (the generation of the cache).
+//! Once a new block is published, every replica drops the local entries of the previous
+//! generation and starts filling the shared entries of the new one, while the outdated shared
+//! entries expire on their own.
+
+// Built-in uses
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+// External uses
+use futures::StreamExt;
+use redis::{aio::ConnectionManager, AsyncCommands, RedisResult};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::{sync::OnceCell, time};
+// Workspace uses
+use zksync_config::configs::api::{CacheBackend, CommonApiConfig};
+use zksync_types::BlockNumber;
+
+/// Key storing the number of the last sealed block.
+const GENERATION_KEY: &str = "zksync:cache:block";
+/// Channel the numbers of the sealed blocks are published to.
+const INVALIDATION_CHANNEL: &str = "zksync:cache:invalidate";
+/// Interval of resubscribing to the invalidation channel after the connection failure.
+const RESUBSCRIBE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Cache stored in Redis and shared between the processes.
+///
+/// The connection is established on the first access, so the cache can be created outside
+/// of the async context. Errors of the shared cache are never propagated to the callers:
+/// they are logged and treated as cache misses.
+#[derive(Clone)]
+pub struct SharedCache {
+    client: redis::Client,
+    connection: Arc<OnceCell<ConnectionManager>>,
+    generation: Arc<AtomicU32>,
+    ttl: Duration,
+}
+
+impl fmt::Debug for SharedCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedCache")
+            .field("generation", &self.generation())
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+impl SharedCache {
+    /// Creates the shared cache if the `redis` backend is selected in the config.
+    pub fn from_config(config: &CommonApiConfig) -> anyhow::Result<Option<Self>> {
+        match config.cache_backend {
+            CacheBackend::Local => Ok(None),
+            CacheBackend::Redis => Ok(Some(Self {
+                client: redis::Client::open(config.redis_url.as_str())?,
+                connection: Default::default(),
+                generation: Default::default(),
+                ttl: config.shared_cache_ttl(),
+            })),
+        }
+    }
+
+    /// Returns the last sealed block known to the cache.
+    /// Entries cached before this block was sealed are outdated.
+    pub fn generation(&self) -> u32 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Loads the entry from the namespace of the current generation.
+    pub async fn get<V: DeserializeOwned>(&self, namespace: &str, key: &str) -> Option<V> {
+        match self.load(namespace, key).await {
+            Ok(value) => value.and_then(|value| serde_json::from_str(&value).ok()),
+            Err(err) => {
+                vlog::warn!("Failed to load the entry from the shared cache: {}", err);
+                None
+            }
+        }
+    }
+
+    /// Stores the entry in the namespace of the current generation.
+    pub async fn insert<V: Serialize>(&self, namespace: &str, key: &str, value: &V) {
+        let value = serde_json::to_string(value).expect("cache entries are serializable");
+        if let Err(err) = self.store(namespace, key, value).await {
+            vlog::warn!("Failed to store the entry in the shared cache: {}", err);
+        }
+    }
+
+    /// Publishes the number of the sealed block, so all the replicas drop the outdated entries.
+    pub async fn publish_block_sealed(&self, block_number: BlockNumber) -> anyhow::Result<()> {
+        let mut connection = self.connection().await?;
+        connection
+            .set::<_, _, ()>(GENERATION_KEY, *block_number)
+            .await?;
+        connection
+            .publish::<_, _, ()>(INVALIDATION_CHANNEL, *block_number)
+            .await?;
+        Ok(())
+    }
+
+    fn namespace_key(&self, namespace: &str) -> String {
+        format!("zksync:cache:{}:{}", self.generation(), namespace)
+    }
+
+    async fn load(&self, namespace: &str, key: &str) -> RedisResult<Option<String>> {
+        let mut connection = self.connection().await?;
+        connection.hget(self.namespace_key(namespace), key).await
+    }
+
+    async fn store(&self, namespace: &str, key: &str, value: String) -> RedisResult<()> {
+        let namespace_key = self.namespace_key(namespace);
+        let mut connection = self.connection().await?;
+        connection
+            .hset::<_, _, _, ()>(&namespace_key, key, value)
+            .await?;
+        connection
+            .expire(&namespace_key, self.ttl.as_secs() as usize)
+            .await
+    }
+
+    async fn connection(&self) -> RedisResult<ConnectionManager> {
+        let connection = self
+            .connection
+            .get_or_try_init(|| async {
+                let mut connection = self.client.get_tokio_connection_manager().await?;
+                let generation: Option<u32> = connection.get(GENERATION_KEY).await?;
+                self.generation
+                    .store(generation.unwrap_or_default(), Ordering::SeqCst);
+                tokio::spawn(listen_invalidations(
+                    self.client.clone(),
+                    self.generation.clone(),
+                ));
+                RedisResult::Ok(connection)
+            })
+            .await?;
+        Ok(connection.clone())
+    }
+}
+
+async fn listen_invalidations(client: redis::Client, generation: Arc<AtomicU32>) {
+    loop {
+        if let Err(err) = subscribe(&client, &generation).await {
+            vlog::warn!("Shared cache invalidation channel failed: {}", err);
+        }
+        time::sleep(RESUBSCRIBE_INTERVAL).await;
+    }
+}
+
+async fn subscribe(client: &redis::Client, generation: &AtomicU32) -> RedisResult<()> {
+    let mut pubsub = client.get_async_connection().await?.into_pubsub();
+    pubsub.subscribe(INVALIDATION_CHANNEL).await?;
+    let mut messages = pubsub.on_message();
+    while let Some(message) = messages.next().await {
+        // Blocks are published by the single core in order, and the generation goes back
+        // on the blocks revert as well.
+        let block_number: u32 = message.get_payload()?;
+        generation.store(block_number, Ordering::SeqCst);
+    }
+    Ok(())
+}
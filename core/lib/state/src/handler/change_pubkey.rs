@@ -29,6 +29,12 @@ impl TxHandler<ChangePubKey> for ZkSyncState {
             tx.fee_token <= max_processable_token(),
             ChangePubKeyOpError::InvalidFeeTokenId
         );
+        if let Some(ChangePubKeyEthAuthData::CREATE2(create2_data)) = &tx.eth_auth_data {
+            invariant!(
+                create2_data.get_address(&tx.new_pk_hash) == tx.account,
+                ChangePubKeyOpError::Create2AddressMismatch
+            );
+        }
         invariant!(
             tx.is_eth_auth_data_valid(),
             ChangePubKeyOpError::InvalidAuthData
@@ -1,6 +1,6 @@
 use num::BigUint;
 use zksync_crypto::params::{self, max_account_id};
-use zksync_types::{AccountUpdate, AccountUpdates, Close, CloseOp, TokenId};
+use zksync_types::{AccountUpdate, AccountUpdates, Close, CloseOp, PubKeyHash};
 
 use crate::{
     handler::{error::CloseOpError, TxHandler},
@@ -32,11 +32,8 @@ impl TxHandler<Close> for ZkSyncState {
         let mut updates = Vec::new();
         let account = self.get_account(op.account_id).unwrap();
 
-        for token in 0..params::total_tokens() {
-            invariant!(
-                account.get_balance(TokenId(token as u32)) == BigUint::from(0u32),
-                CloseOpError::AccountNotEmpty(token)
-            );
+        if let Some(token) = account.get_nonzero_balances().keys().min() {
+            return Err(CloseOpError::AccountNotEmpty(**token as usize));
         }
 
         invariant!(op.tx.nonce == account.nonce, CloseOpError::NonceMismatch);
@@ -59,3 +56,28 @@ impl TxHandler<Close> for ZkSyncState {
         Ok((Some(fee), updates))
     }
 }
+
+impl ZkSyncState {
+    /// Removes the empty account from the tree, so its leaf can be taken by a new account.
+    ///
+    /// Unlike the disabled `Close` transactions of the users, reclaims are initiated by the operator
+    /// and are not signed. Only the accounts which never set the signing key can be reclaimed, which
+    /// are the ones the forced exits are applied to. The account must have no balances.
+    pub fn reclaim_account(&mut self, tx: Close) -> Result<OpSuccess, CloseOpError> {
+        let (account_id, account) = self
+            .get_account_by_address(&tx.account)
+            .ok_or(CloseOpError::AccountNotFound)?;
+        invariant!(
+            account.pub_key_hash == PubKeyHash::default(),
+            CloseOpError::AccountHasSigningKey
+        );
+
+        let op = CloseOp { tx, account_id };
+        let (fee, updates) = <Self as TxHandler<Close>>::apply_op(self, &op)?;
+        Ok(OpSuccess {
+            fee,
+            updates,
+            executed_op: op.into(),
+        })
+    }
+}
@@ -10,6 +10,8 @@ pub enum ChangePubKeyOpError {
     InvalidAccountAddress,
     #[error("ChangePubKey Ethereum auth data is incorrect")]
     InvalidAuthData,
+    #[error("ChangePubKey CREATE2 address doesn't match the account address")]
+    Create2AddressMismatch,
     #[error("ChangePubKey zkSync signature is incorrect")]
     InvalidZksyncSignature,
     #[error("ChangePubKey account id is incorrect")]
@@ -32,6 +34,10 @@ pub enum CloseOpError {
     AccountNotEmpty(usize),
     #[error("Nonce mismatch")]
     NonceMismatch,
+    #[error("Account does not exist")]
+    AccountNotFound,
+    #[error("Account has the signing key set")]
+    AccountHasSigningKey,
 }
 
 #[derive(Clone, Debug, Error, PartialEq)]
@@ -78,6 +84,8 @@ pub enum TransferOpError {
     FromAccountNotFound,
     #[error("Account is locked")]
     FromAccountLocked,
+    #[error("Account is frozen")]
+    FromAccountFrozen,
     #[error("Transfer account id is incorrect")]
     TransferAccountIncorrect,
     #[error("Transfer signature is incorrect")]
@@ -179,6 +187,8 @@ pub enum SwapOpError {
     AccountNotFound,
     #[error("Account is locked")]
     AccountLocked,
+    #[error("Account is frozen")]
+    AccountFrozen,
     #[error("Swap signature is incorrect")]
     SwapInvalidSignature,
     #[error("Order signature is incorrect")]
@@ -201,4 +211,6 @@ pub enum SwapOpError {
     AmountsNotCompatible,
     #[error("Self-swap is not allowed")]
     SelfSwap,
+    #[error("NFT has to be swapped as a whole")]
+    NFTPartialFill,
 }
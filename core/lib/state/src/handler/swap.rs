@@ -108,6 +108,10 @@ impl ZkSyncState {
             order.verify_signature() == Some(account.pub_key_hash),
             SwapOpError::OrderInvalidSignature
         );
+        invariant!(
+            !self.is_frozen(order.account_id),
+            SwapOpError::AccountFrozen
+        );
         Ok(())
     }
 
@@ -160,6 +164,15 @@ impl ZkSyncState {
             swap.orders.0.token_sell != swap.orders.1.token_sell,
             SwapOpError::SwapSameToken
         );
+        // NFT can't be partially filled, the whole token has to be transferred.
+        invariant!(
+            !swap.orders.0.sells_nft() || swap.amounts.0 == BigUint::from(1u32),
+            SwapOpError::NFTPartialFill
+        );
+        invariant!(
+            !swap.orders.1.sells_nft() || swap.amounts.1 == BigUint::from(1u32),
+            SwapOpError::NFTPartialFill
+        );
         invariant!(
             swap.orders.0.amount.is_zero() || swap.orders.0.amount == swap.amounts.0,
             SwapOpError::AmountsNotMatched
@@ -46,6 +46,7 @@ impl TxHandler<Transfer> for ZkSyncState {
             from == tx.account_id,
             TransferOpError::TransferAccountIncorrect
         );
+        invariant!(!self.is_frozen(from), TransferOpError::FromAccountFrozen);
 
         let outcome = if let Some((to, _)) = self.get_account_by_address(&tx.to) {
             let transfer_op = TransferOp { tx, from, to };
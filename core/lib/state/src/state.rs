@@ -1,12 +1,12 @@
 use num::BigUint;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use zksync_crypto::{merkle_tree::TreeMemoryUsage, params, params::NFT_STORAGE_ACCOUNT_ID, Fr};
 use zksync_types::{
     helpers::reverse_updates,
     operations::{TransferOp, TransferToNewOp, ZkSyncOp},
-    Account, AccountId, AccountMap, AccountTree, AccountUpdate, AccountUpdates, Address,
-    SignedZkSyncTx, TokenId, ZkSyncPriorityOp, ZkSyncTx, NFT,
+    Account, AccountFreeze, AccountId, AccountMap, AccountTree, AccountUpdate, AccountUpdates,
+    Address, SignedZkSyncTx, TokenId, ZkSyncPriorityOp, ZkSyncTx, NFT,
 };
 
 use crate::{
@@ -31,7 +31,16 @@ pub struct ZkSyncState {
 
     pub nfts: HashMap<TokenId, NFT>,
 
+    /// Accounts frozen by the operator, they can't send funds to other accounts.
+    /// Freezes are not a part of the rollup state and are set by the state keeper.
+    pub frozen_accounts: HashMap<AccountId, AccountFreeze>,
+
     next_free_id: AccountId,
+
+    /// Leaves freed by reclaiming the empty accounts. New accounts take the smallest of them
+    /// before growing the tree. Other gaps of the tree are never reused, so that the ids of
+    /// the accounts are assigned the same way they always were.
+    free_ids: BTreeSet<AccountId>,
 }
 
 #[derive(Debug, Clone)]
@@ -70,21 +79,15 @@ impl ZkSyncState {
             balance_tree,
             account_id_by_address: HashMap::new(),
             next_free_id: AccountId(0),
+            free_ids: BTreeSet::new(),
             nfts: HashMap::new(),
+            frozen_accounts: HashMap::new(),
         }
     }
 
     pub fn from_acc_map(accounts: AccountMap) -> Self {
         let mut empty = Self::empty();
 
-        let mut next_free_id = 0;
-        for account in &accounts {
-            if account.0 != &NFT_STORAGE_ACCOUNT_ID {
-                next_free_id = std::cmp::max(next_free_id, **account.0 + 1);
-            }
-        }
-        empty.next_free_id = AccountId(next_free_id as u32);
-
         for (id, account) in accounts {
             empty.insert_account(id, account);
         }
@@ -107,7 +110,9 @@ impl ZkSyncState {
             balance_tree,
             account_id_by_address,
             next_free_id: AccountId(next_free_id as u32),
+            free_ids: BTreeSet::new(),
             nfts,
+            frozen_accounts: HashMap::new(),
         }
     }
 
@@ -348,7 +353,25 @@ impl ZkSyncState {
     }
 
     pub(crate) fn get_free_account_id(&self) -> AccountId {
-        self.next_free_id
+        self.free_account_ids().next().unwrap()
+    }
+
+    /// Returns the ids the new accounts will get, in order.
+    pub(crate) fn free_account_ids(&self) -> impl Iterator<Item = AccountId> + '_ {
+        self.free_ids
+            .iter()
+            .copied()
+            .chain((*self.next_free_id..).map(AccountId))
+    }
+
+    /// Returns the amount of the account tree leaves not available for the new accounts,
+    /// the NFT storage account excluded. Gaps of the restored tree are counted as occupied.
+    pub fn occupied_leaves(&self) -> usize {
+        *self.next_free_id as usize - self.free_ids.len()
+    }
+
+    pub fn is_frozen(&self, account_id: AccountId) -> bool {
+        self.frozen_accounts.contains_key(&account_id)
     }
 
     pub fn collect_fee(&mut self, fees: &[CollectedFee], fee_account: AccountId) -> AccountUpdates {
@@ -407,16 +430,20 @@ impl ZkSyncState {
         if id != NFT_STORAGE_ACCOUNT_ID && id >= self.next_free_id {
             self.next_free_id = id + 1;
         }
+        self.free_ids.remove(&id);
     }
 
-    #[allow(dead_code)]
     pub(crate) fn remove_account(&mut self, id: AccountId) {
-        assert_eq!(*id, *self.next_free_id - 1);
-
         if let Some(account) = self.get_account(id) {
             self.account_id_by_address.remove(&account.address);
             self.balance_tree.remove(*id);
-            *self.next_free_id -= 1;
+            if id != NFT_STORAGE_ACCOUNT_ID {
+                self.free_ids.insert(id);
+            }
+            // Free leaves at the end of the tree are not kept in the set.
+            while *self.next_free_id > 0 && self.free_ids.remove(&(self.next_free_id - 1)) {
+                *self.next_free_id -= 1;
+            }
         }
     }
 
@@ -1068,6 +1095,8 @@ mod tests {
 
         let state = ZkSyncState::new(balance_tree, account_id_by_address, HashMap::new());
         assert_eq!(*state.next_free_id, 10);
+        assert_eq!(state.get_free_account_id(), AccountId(10));
+        assert_eq!(state.occupied_leaves(), 10);
     }
 
     /// Checks if insert_account panics if account has id greater that next_free_id.
@@ -1098,8 +1127,7 @@ mod tests {
         );
     }
 
-    /// Checks if remove_account panics if account is not last.
-    #[should_panic(expected = "assertion failed: `(left == right)")]
+    /// Checks if the leaves of the removed accounts are reused by the new accounts.
     #[test]
     fn remove_not_last_account() {
         let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
@@ -1109,20 +1137,31 @@ mod tests {
         }
         let mut plasma_state = ZkSyncState::from_acc_map(AccountMap::default());
 
-        plasma_state.insert_account(
-            AccountId(0),
-            Account::default_with_address(&random_addresses[0]),
+        for (id, address) in random_addresses.iter().take(3).enumerate() {
+            plasma_state
+                .insert_account(AccountId(id as u32), Account::default_with_address(address));
+        }
+
+        plasma_state.remove_account(AccountId(1));
+        assert_eq!(*plasma_state.next_free_id, 3);
+        assert_eq!(plasma_state.occupied_leaves(), 2);
+        assert_eq!(plasma_state.get_free_account_id(), AccountId(1));
+        assert_eq!(
+            plasma_state.free_account_ids().take(3).collect::<Vec<_>>(),
+            vec![AccountId(1), AccountId(3), AccountId(4)]
         );
+
         plasma_state.insert_account(
             AccountId(1),
-            Account::default_with_address(&random_addresses[1]),
-        );
-        plasma_state.insert_account(
-            AccountId(2),
-            Account::default_with_address(&random_addresses[2]),
+            Account::default_with_address(&random_addresses[3]),
         );
+        assert_eq!(plasma_state.get_free_account_id(), AccountId(3));
 
+        // Free leaves at the end of the tree are dropped together with the last account.
         plasma_state.remove_account(AccountId(1));
+        plasma_state.remove_account(AccountId(2));
+        assert_eq!(*plasma_state.next_free_id, 1);
+        assert!(plasma_state.free_ids.is_empty());
     }
 
     /// Checks if from_acc_map works with unsorted accounts.
@@ -1148,5 +1187,6 @@ mod tests {
         );
         let plasma_state = ZkSyncState::from_acc_map(account_map);
         assert_eq!(*plasma_state.next_free_id, 6);
+        assert!(plasma_state.free_ids.is_empty());
     }
 }
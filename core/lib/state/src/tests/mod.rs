@@ -4,6 +4,7 @@ mod timestamp;
 
 use crate::state::ZkSyncState;
 use num::BigUint;
+use vlog::sentry::types::Utc;
 use web3::types::H256;
 use zksync_crypto::{
     priv_key_from_fs,
@@ -11,7 +12,8 @@ use zksync_crypto::{
     PrivateKey,
 };
 use zksync_types::{
-    tx::PackedEthSignature, Account, AccountId, AccountUpdate, PubKeyHash, SignedZkSyncTx, TokenId,
+    tx::{ChangePubKeyCREATE2Data, PackedEthSignature},
+    Account, AccountFreeze, AccountId, AccountUpdate, PubKeyHash, SignedZkSyncTx, TokenId,
     ZkSyncPriorityOp, ZkSyncTx, NFT,
 };
 
@@ -47,6 +49,15 @@ impl PlasmaTestBuilder {
         self.block_timestamp = block_timestamp;
     }
 
+    pub fn freeze_account(&mut self, account_id: AccountId) {
+        let freeze = AccountFreeze {
+            account_id,
+            reason: "compliance hold".into(),
+            frozen_at: Utc::now(),
+        };
+        self.state.frozen_accounts.insert(account_id, freeze);
+    }
+
     pub fn mint_nft(
         &mut self,
         token_id: TokenId,
@@ -87,6 +98,23 @@ impl PlasmaTestBuilder {
         (account_id, account, sk)
     }
 
+    /// Adds the locked account deployed with `CREATE2`, its address is derived
+    /// from the provided data and the public key hash of the generated private key.
+    pub fn add_create2_account(
+        &mut self,
+        create2_data: &ChangePubKeyCREATE2Data,
+    ) -> (AccountId, Account, PrivateKey) {
+        let account_id = self.state.get_free_account_id();
+
+        let sk = priv_key_from_fs(self.rng.gen());
+        let address = create2_data.get_address(&PubKeyHash::from_privkey(&sk));
+
+        let account = Account::default_with_address(&address);
+        self.state.insert_account(account_id, account.clone());
+
+        (account_id, account, sk)
+    }
+
     pub fn set_balance<B: Into<BigUint>>(
         &mut self,
         account_id: AccountId,
@@ -1,10 +1,18 @@
 use crate::tests::{AccountState::*, PlasmaTestBuilder};
 use zksync_types::{
     account::{AccountUpdate, PubKeyHash},
-    tx::ChangePubKey,
-    AccountId, TokenId,
+    tx::{ChangePubKey, ChangePubKeyCREATE2Data, ChangePubKeyEthAuthData},
+    AccountId, Address, TokenId, H256,
 };
 
+fn create2_data() -> ChangePubKeyCREATE2Data {
+    ChangePubKeyCREATE2Data {
+        creator_address: Address::repeat_byte(0x11),
+        salt_arg: H256::repeat_byte(0x22),
+        code_hash: H256::repeat_byte(0x33),
+    }
+}
+
 /// Check ChangePubKey operation on new account
 #[test]
 fn success() {
@@ -102,3 +110,77 @@ fn invalid_account_id() {
         "ChangePubKey account id is incorrect",
     );
 }
+
+/// Check ChangePubKey authorized by the CREATE2 data
+#[test]
+fn create2_success() {
+    let mut tb = PlasmaTestBuilder::new();
+    let token_id = TokenId(1);
+    let balance = 10u32;
+    let (account_id, account, sk) = tb.add_create2_account(&create2_data());
+    tb.set_balance(account_id, token_id, balance);
+    let old_pub_key_hash = account.pub_key_hash;
+    let new_pub_key_hash = PubKeyHash::from_privkey(&sk);
+
+    let change_pub_key = ChangePubKey::new_create2_signed(
+        account_id,
+        token_id,
+        balance.into(),
+        Default::default(),
+        create2_data(),
+        &sk,
+    )
+    .expect("Failed to sign ChangePubkey");
+    assert_eq!(change_pub_key.account, account.address);
+
+    tb.test_tx_success(
+        change_pub_key.into(),
+        &[
+            (
+                account_id,
+                AccountUpdate::ChangePubKeyHash {
+                    old_nonce: account.nonce,
+                    new_nonce: account.nonce + 1,
+                    old_pub_key_hash,
+                    new_pub_key_hash,
+                },
+            ),
+            (
+                account_id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: account.nonce + 1,
+                    new_nonce: account.nonce + 1,
+                    balance_update: (token_id, balance.into(), 0u32.into()),
+                },
+            ),
+        ],
+    )
+}
+
+/// Check that ChangePubKey fails if the CREATE2 data
+/// does not derive the account address
+#[test]
+fn create2_address_mismatch() {
+    let mut tb = PlasmaTestBuilder::new();
+    let (account_id, _, sk) = tb.add_create2_account(&create2_data());
+
+    let mut change_pub_key = ChangePubKey::new_create2_signed(
+        account_id,
+        TokenId(0),
+        0u32.into(),
+        Default::default(),
+        create2_data(),
+        &sk,
+    )
+    .expect("Failed to sign ChangePubkey");
+    change_pub_key.eth_auth_data =
+        Some(ChangePubKeyEthAuthData::CREATE2(ChangePubKeyCREATE2Data {
+            salt_arg: H256::repeat_byte(0x44),
+            ..create2_data()
+        }));
+
+    tb.test_tx_fail(
+        change_pub_key.into(),
+        "ChangePubKey CREATE2 address doesn't match the account address",
+    );
+}
@@ -1,5 +1,11 @@
-use crate::tests::{AccountState::*, PlasmaTestBuilder};
-use zksync_types::tx::{Close, TxSignature};
+use crate::{
+    handler::error::CloseOpError,
+    tests::{AccountState::*, PlasmaTestBuilder},
+};
+use zksync_types::{
+    tx::{Close, TxSignature},
+    Account, AccountUpdate, TokenId,
+};
 
 /// Checks that Close operations fails
 /// because it is disabled
@@ -17,3 +23,68 @@ fn expected_fail() {
 
     tb.test_tx_fail(close.into(), "Close operations are disabled");
 }
+
+fn reclaim_tx(account: &Account) -> Close {
+    Close {
+        account: account.address,
+        nonce: account.nonce,
+        signature: TxSignature::default(),
+        time_range: Default::default(),
+    }
+}
+
+/// Checks that the empty account is removed by the operator
+/// and its leaf is taken by the next new account.
+#[test]
+fn reclaim_success() {
+    let mut tb = PlasmaTestBuilder::new();
+
+    tb.add_account(Unlocked);
+    let (account_id, account, _) = tb.add_account(Locked);
+    tb.add_account(Locked);
+
+    let op_success = tb.state.reclaim_account(reclaim_tx(&account)).unwrap();
+    assert_eq!(
+        op_success.updates,
+        vec![(
+            account_id,
+            AccountUpdate::Delete {
+                address: account.address,
+                nonce: account.nonce,
+            }
+        )]
+    );
+    assert!(tb.state.get_account(account_id).is_none());
+    assert_eq!(tb.state.occupied_leaves(), 2);
+
+    let (new_account_id, _, _) = tb.add_account(Locked);
+    assert_eq!(new_account_id, account_id);
+}
+
+/// Checks that the accounts with the balances or the signing key can't be reclaimed.
+#[test]
+fn reclaim_fail() {
+    let mut tb = PlasmaTestBuilder::new();
+
+    let (account_id, account, _) = tb.add_account(Locked);
+    tb.set_balance(account_id, TokenId(2), 1u32);
+    assert_eq!(
+        tb.state.reclaim_account(reclaim_tx(&account)).unwrap_err(),
+        CloseOpError::AccountNotEmpty(2)
+    );
+
+    let (_, unlocked_account, _) = tb.add_account(Unlocked);
+    assert_eq!(
+        tb.state
+            .reclaim_account(reclaim_tx(&unlocked_account))
+            .unwrap_err(),
+        CloseOpError::AccountHasSigningKey
+    );
+
+    let (_, mut account, _) = tb.add_account(Locked);
+    account.nonce = account.nonce + 1;
+    assert_eq!(
+        tb.state.reclaim_account(reclaim_tx(&account)).unwrap_err(),
+        CloseOpError::NonceMismatch
+    );
+}
@@ -1,7 +1,7 @@
 use crate::tests::{AccountState::*, PlasmaTestBuilder};
 use num::{BigUint, Zero};
-use zksync_crypto::PrivateKey;
-use zksync_types::{Account, AccountId, AccountUpdate, Order, Swap, TokenId};
+use zksync_crypto::{params::MIN_NFT_TOKEN_ID, PrivateKey};
+use zksync_types::{tx::TxSignature, Account, AccountId, AccountUpdate, Order, Swap, TokenId};
 use TestResult::*;
 
 type TestAccount = (AccountId, Account, PrivateKey);
@@ -255,6 +255,36 @@ fn self_swap() {
     test_swap.test(tb, Failure("Self-swap is not allowed"));
 }
 
+/// One of the swapping accounts is frozen, should fail
+#[test]
+fn frozen_account() {
+    let mut tb = PlasmaTestBuilder::new();
+
+    let test_swap = TestSwap {
+        accounts: (0, 1),
+        recipients: (2, 3),
+        submitter: 4,
+        tokens: (18, 19),
+        fee_token: 0,
+        amounts: (50, 100),
+        fee: 25,
+        balances: (100, 200, 50),
+        first_price: (1, 2),
+        second_price: (2, 1),
+        is_limit_order: (false, false),
+        test_accounts: vec![
+            tb.add_account(Unlocked),
+            tb.add_account(Unlocked),
+            tb.add_account(Locked),
+            tb.add_account(Unlocked),
+            tb.add_account(Unlocked),
+        ],
+    };
+    tb.freeze_account(test_swap.test_accounts[1].0);
+
+    test_swap.test(tb, Failure("Account is frozen"));
+}
+
 /// Accounts try to swap using same tokens, should fail
 #[test]
 fn equal_tokens() {
@@ -512,3 +542,96 @@ fn limit_orders() {
         },
     );
 }
+
+/// NFT is sold for a fungible token to the limit order, should succeed
+#[test]
+fn nft_swap() {
+    let mut tb = PlasmaTestBuilder::new();
+
+    let test_swap = TestSwap {
+        accounts: (0, 1),
+        recipients: (2, 3),
+        submitter: 4,
+        tokens: (MIN_NFT_TOKEN_ID, 19),
+        fee_token: 0,
+        amounts: (1, 100),
+        fee: 25,
+        balances: (1, 200, 50),
+        first_price: (1, 100),
+        second_price: (100, 1),
+        is_limit_order: (false, true),
+        test_accounts: vec![
+            tb.add_account(Unlocked),
+            tb.add_account(Unlocked),
+            tb.add_account(Locked),
+            tb.add_account(Unlocked),
+            tb.add_account(Unlocked),
+        ],
+    };
+
+    test_swap.test(
+        tb,
+        Success {
+            nonce_changes: vec![(0, 1), (0, 0), (0, 0), (0, 0), (0, 1)],
+            balance_changes: vec![(1, 0), (0, 1), (200, 100), (0, 100), (50, 25)],
+        },
+    );
+}
+
+/// The order selling an NFT bypasses the correctness check and has zero amount,
+/// the swap doesn't transfer the NFT as a whole, should fail
+#[test]
+fn nft_partial_fill() {
+    let mut tb = PlasmaTestBuilder::new();
+    let (seller_id, seller, seller_sk) = tb.add_account(Unlocked);
+    let (buyer_id, buyer, buyer_sk) = tb.add_account(Unlocked);
+    let (submitter_id, submitter, submitter_sk) = tb.add_account(Unlocked);
+    let nft = TokenId(MIN_NFT_TOKEN_ID);
+    let token = TokenId(19);
+
+    tb.set_balance(seller_id, nft, 1u32);
+    tb.set_balance(buyer_id, token, 200u32);
+    tb.set_balance(submitter_id, TokenId(0), 50u32);
+
+    let mut order_0 = Order::new_signed(
+        seller_id,
+        seller.address,
+        seller.nonce,
+        nft,
+        token,
+        (BigUint::from(1u32), BigUint::from(100u32)),
+        BigUint::from(1u32),
+        Default::default(),
+        &seller_sk,
+    )
+    .expect("order creation failed");
+    order_0.amount = BigUint::zero();
+    order_0.signature = TxSignature::sign_musig(&seller_sk, &order_0.get_bytes());
+
+    let order_1 = Order::new_signed(
+        buyer_id,
+        buyer.address,
+        buyer.nonce,
+        token,
+        nft,
+        (BigUint::from(100u32), BigUint::from(1u32)),
+        BigUint::zero(),
+        Default::default(),
+        &buyer_sk,
+    )
+    .expect("order creation failed");
+
+    let mut swap = Swap::new(
+        submitter_id,
+        submitter.address,
+        submitter.nonce,
+        (order_0, order_1),
+        (BigUint::zero(), BigUint::zero()),
+        BigUint::from(25u32),
+        TokenId(0),
+        None,
+    );
+    swap.signature = TxSignature::sign_musig(&submitter_sk, &swap.get_sign_bytes());
+
+    tb.test_tx_fail(swap.into(), "NFT has to be swapped as a whole");
+}
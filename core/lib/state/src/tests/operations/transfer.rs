@@ -2,7 +2,9 @@ use crate::tests::{AccountState::*, PlasmaTestBuilder};
 use num::{BigUint, Zero};
 use vlog::sentry::types::Utc;
 use web3::types::H160;
-use zksync_types::{AccountId, AccountUpdate, Nonce, SignedZkSyncTx, TokenId, Transfer, ZkSyncTx};
+use zksync_types::{
+    AccountId, AccountUpdate, Nonce, SignedZkSyncTx, TokenId, Transfer, Withdraw, ZkSyncTx,
+};
 
 /// Check Transfer operation to existing account
 #[test]
@@ -269,6 +271,51 @@ fn invalid_account_id() {
     tb.test_tx_fail(transfer.into(), "Transfer account id is incorrect")
 }
 
+/// Check that the frozen account can't transfer funds, but can still withdraw them
+#[test]
+fn frozen_account() {
+    let token_id = TokenId(0);
+    let amount = BigUint::from(100u32);
+    let fee = BigUint::from(10u32);
+
+    let mut tb = PlasmaTestBuilder::new();
+
+    let (account_id, account, sk) = tb.add_account(Unlocked);
+    let (_, to_account, _) = tb.add_account(Locked);
+    tb.set_balance(account_id, token_id, &amount + &fee);
+    tb.freeze_account(account_id);
+
+    let transfer = Transfer::new_signed(
+        account_id,
+        account.address,
+        to_account.address,
+        token_id,
+        amount.clone(),
+        fee.clone(),
+        account.nonce,
+        Default::default(),
+        &sk,
+    )
+    .unwrap();
+    tb.test_tx_fail(transfer.into(), "Account is frozen");
+
+    let withdraw = Withdraw::new_signed(
+        account_id,
+        account.address,
+        account.address,
+        token_id,
+        amount,
+        fee,
+        account.nonce,
+        Default::default(),
+        &sk,
+    )
+    .unwrap();
+    tb.state
+        .execute_tx(withdraw.into(), 0)
+        .expect("frozen account should be able to withdraw");
+}
+
 #[test]
 fn execute_txs_batch_success_transfers() {
     let token_id = TokenId(0);
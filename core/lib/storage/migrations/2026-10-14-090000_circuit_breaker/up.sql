@@ -0,0 +1,7 @@
+CREATE TABLE circuit_breaker_trips (
+    id BIGSERIAL PRIMARY KEY,
+    source TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    tripped_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    resolved_at TIMESTAMP WITH TIME ZONE
+);
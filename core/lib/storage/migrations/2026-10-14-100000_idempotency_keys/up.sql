@@ -0,0 +1,8 @@
+CREATE TABLE tx_idempotency_keys (
+    account_id BIGINT NOT NULL,
+    idempotency_key TEXT NOT NULL,
+    tx_hash BYTEA NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (account_id, idempotency_key)
+);
+CREATE INDEX IF NOT EXISTS tx_idempotency_keys_created_at_idx ON tx_idempotency_keys (created_at);
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS network_stats_tvl;
+DROP TABLE IF EXISTS network_stats_daily_fees;
+DROP TABLE IF EXISTS network_stats_daily_txs;
+DROP TABLE IF EXISTS network_stats_daily;
@@ -0,0 +1,23 @@
+-- Summary tables filled by the background statistics aggregator.
+CREATE TABLE network_stats_daily (
+    day DATE PRIMARY KEY,
+    active_accounts BIGINT NOT NULL,
+    avg_finality_secs DOUBLE PRECISION
+);
+CREATE TABLE network_stats_daily_txs (
+    day DATE NOT NULL,
+    tx_type TEXT NOT NULL,
+    tx_count BIGINT NOT NULL,
+    PRIMARY KEY (day, tx_type)
+);
+CREATE TABLE network_stats_daily_fees (
+    day DATE NOT NULL,
+    token_id INTEGER NOT NULL,
+    amount NUMERIC NOT NULL,
+    PRIMARY KEY (day, token_id)
+);
+CREATE TABLE network_stats_tvl (
+    token_id INTEGER PRIMARY KEY,
+    amount NUMERIC NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS aggregated_operation_costs;
+DROP TABLE IF EXISTS block_fees;
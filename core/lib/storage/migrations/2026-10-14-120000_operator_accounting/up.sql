@@ -0,0 +1,15 @@
+-- Fees collected by the operator in every block.
+CREATE TABLE block_fees (
+    block_number BIGINT NOT NULL REFERENCES blocks (number) ON DELETE CASCADE,
+    token_id INTEGER NOT NULL,
+    amount NUMERIC NOT NULL,
+    PRIMARY KEY (block_number, token_id)
+);
+-- L1 gas spent for the confirmed aggregated operations.
+CREATE TABLE aggregated_operation_costs (
+    op_id BIGINT PRIMARY KEY REFERENCES aggregate_operations (id) ON DELETE CASCADE,
+    tx_hash BYTEA NOT NULL,
+    gas_used NUMERIC NOT NULL,
+    gas_price NUMERIC NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
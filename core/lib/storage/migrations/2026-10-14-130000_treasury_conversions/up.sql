@@ -0,0 +1,16 @@
+-- Audit records of the conversions of the collected fees made by the treasury task.
+CREATE TABLE treasury_conversions (
+    id BIGSERIAL PRIMARY KEY,
+    token_id INTEGER NOT NULL,
+    amount NUMERIC NOT NULL,
+    target_token_id INTEGER NOT NULL,
+    expected_amount NUMERIC NOT NULL,
+    route TEXT NOT NULL,
+    tx_hash BYTEA NOT NULL,
+    status TEXT NOT NULL,
+    fail_reason TEXT,
+    details JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX treasury_conversions_status_idx ON treasury_conversions (status);
@@ -0,0 +1,13 @@
+-- Onchain `ChangePubKey` transactions waiting for the auth fact to be set on L1.
+CREATE TABLE onchain_auth_txs (
+    tx_hash BYTEA PRIMARY KEY,
+    account_address BYTEA NOT NULL,
+    nonce BIGINT NOT NULL,
+    new_pk_hash BYTEA NOT NULL,
+    tx JSONB NOT NULL,
+    status TEXT NOT NULL,
+    fail_reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX onchain_auth_txs_status_idx ON onchain_auth_txs (status);
@@ -0,0 +1,13 @@
+-- Deposits that can't be credited by the network and have to be refunded on L1.
+CREATE TABLE refundable_deposits (
+    serial_id BIGINT PRIMARY KEY,
+    eth_hash BYTEA NOT NULL,
+    from_address BYTEA NOT NULL,
+    to_address BYTEA NOT NULL,
+    token_id INTEGER NOT NULL,
+    amount NUMERIC NOT NULL,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX refundable_deposits_from_address_idx ON refundable_deposits (from_address);
+CREATE INDEX refundable_deposits_to_address_idx ON refundable_deposits (to_address);
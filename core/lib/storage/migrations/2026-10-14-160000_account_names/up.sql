@@ -0,0 +1,8 @@
+-- Human-readable names of the accounts, the name is active once its fee transaction is executed.
+CREATE TABLE account_names (
+    name TEXT PRIMARY KEY,
+    address BYTEA NOT NULL,
+    fee_tx_hash BYTEA NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX account_names_address_idx ON account_names (address);
@@ -0,0 +1,12 @@
+-- Compliance holds of the accounts. Records are never deleted, so the table is also
+-- the audit trail of the freezes: the freeze is active until `unfrozen_at` is set.
+CREATE TABLE account_freezes (
+    id BIGSERIAL PRIMARY KEY,
+    account_id BIGINT NOT NULL,
+    reason TEXT NOT NULL,
+    frozen_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    unfreeze_reason TEXT,
+    unfrozen_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE UNIQUE INDEX account_freezes_active_idx ON account_freezes (account_id) WHERE unfrozen_at IS NULL;
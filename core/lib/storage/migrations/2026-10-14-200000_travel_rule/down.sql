@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS travel_rule_records_to_address_idx;
+DROP INDEX IF EXISTS travel_rule_records_from_address_idx;
+DROP TABLE IF EXISTS travel_rule_records;
@@ -0,0 +1,17 @@
+-- Transfers subject to the travel rule. The metadata is encrypted by the sender for the
+-- recipient, so the server stores it as is. Transfers above the threshold submitted without
+-- the metadata are stored with the `NULL` metadata and are reviewed by the operator.
+CREATE TABLE travel_rule_records (
+    tx_hash BYTEA PRIMARY KEY,
+    from_address BYTEA NOT NULL,
+    to_address BYTEA NOT NULL,
+    token_id INTEGER NOT NULL,
+    amount NUMERIC NOT NULL,
+    amount_usd_scaled BIGINT,
+    encrypted_metadata BYTEA,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX travel_rule_records_flagged_idx ON travel_rule_records (created_at) WHERE encrypted_metadata IS NULL;
+CREATE INDEX travel_rule_records_from_address_idx ON travel_rule_records (from_address, created_at);
+CREATE INDEX travel_rule_records_to_address_idx ON travel_rule_records (to_address, created_at);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS fee_sponsor_gas_usage;
+DROP TABLE IF EXISTS fee_sponsors;
@@ -0,0 +1,17 @@
+-- Addresses allowed to send the zero-fee transfers of the token within the daily gas budget.
+CREATE TABLE fee_sponsors (
+    address BYTEA NOT NULL,
+    token_id INTEGER NOT NULL,
+    daily_gas_budget BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (address, token_id)
+);
+
+-- L1 gas taken by the sponsored transfers admitted to the mempool, per UTC day.
+CREATE TABLE fee_sponsor_gas_usage (
+    address BYTEA NOT NULL,
+    token_id INTEGER NOT NULL,
+    day DATE NOT NULL,
+    gas_used BIGINT NOT NULL,
+    PRIMARY KEY (address, token_id, day)
+);
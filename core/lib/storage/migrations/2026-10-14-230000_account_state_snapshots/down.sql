@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS account_pubkey_updates_account_block_idx;
+DROP INDEX IF EXISTS account_creates_account_block_idx;
+DROP INDEX IF EXISTS account_balance_updates_account_block_idx;
+DROP TABLE IF EXISTS account_state_snapshots;
@@ -0,0 +1,18 @@
+-- States of the accounts at the finalized blocks, so the historical state is restored
+-- by applying only the updates made after the nearest snapshot.
+CREATE TABLE account_state_snapshots (
+    account_id BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    -- NULL if the account doesn't exist at the block.
+    account JSONB,
+    -- Last block not after the snapshot one in which the account was updated.
+    last_update_block BIGINT NOT NULL,
+    PRIMARY KEY (account_id, block_number)
+);
+
+CREATE INDEX account_balance_updates_account_block_idx
+    ON account_balance_updates (account_id, block_number);
+CREATE INDEX account_creates_account_block_idx
+    ON account_creates (account_id, block_number);
+CREATE INDEX account_pubkey_updates_account_block_idx
+    ON account_pubkey_updates (account_id, block_number);
@@ -0,0 +1,9 @@
+-- Content hashes of the results published by the provers, verified on receipt.
+-- `compression` is the encoding the payload was transferred with.
+CREATE TABLE prover_job_results (
+    job_id INT PRIMARY KEY REFERENCES prover_job_queue (id) ON DELETE CASCADE,
+    content_hash BYTEA NOT NULL,
+    compression TEXT NOT NULL,
+    payload_size BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
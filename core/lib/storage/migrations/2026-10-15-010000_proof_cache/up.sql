@@ -0,0 +1,7 @@
+-- Single block proofs by the block commitment, kept on reverts so the re-applied blocks
+-- are not proven again.
+CREATE TABLE proof_cache (
+    block_commitment BYTEA PRIMARY KEY,
+    proof JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1,3 @@
+ALTER TABLE proof_cache DROP COLUMN IF EXISTS proof_system;
+ALTER TABLE aggregated_proofs DROP COLUMN IF EXISTS proof_system;
+ALTER TABLE proofs DROP COLUMN IF EXISTS proof_system;
@@ -0,0 +1,4 @@
+-- Proof system the proofs are created with, see `ProofSystemVersion`.
+ALTER TABLE proofs ADD COLUMN proof_system TEXT NOT NULL DEFAULT 'plonk_v1';
+ALTER TABLE aggregated_proofs ADD COLUMN proof_system TEXT NOT NULL DEFAULT 'plonk_v1';
+ALTER TABLE proof_cache ADD COLUMN proof_system TEXT NOT NULL DEFAULT 'plonk_v1';
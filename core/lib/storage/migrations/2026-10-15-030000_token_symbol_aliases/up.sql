@@ -0,0 +1,7 @@
+-- Aliases of the token symbols, resolving the symbol lookups before the symbols of the tokens.
+-- Aliases are stored in lowercase, as the lookups are case-insensitive.
+CREATE TABLE token_symbol_aliases (
+    alias TEXT PRIMARY KEY,
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON DELETE CASCADE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS nft_creator_serial_id_idx;
+DROP INDEX IF EXISTS balances_nft_owner_idx;
@@ -0,0 +1,4 @@
+-- Balances equal to one, which the NFT balances are, indexed by the token, so the owners of NFTs
+-- are found without scanning the balances.
+CREATE INDEX IF NOT EXISTS balances_nft_owner_idx ON balances (coin_id, account_id) WHERE balance = 1;
+CREATE INDEX IF NOT EXISTS nft_creator_serial_id_idx ON nft (creator_account_id, serial_id);
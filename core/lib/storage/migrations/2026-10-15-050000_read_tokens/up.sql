@@ -0,0 +1,13 @@
+-- Read tokens issued for the grants signed by the owners of the accounts. Only the hashes of the
+-- tokens are stored. The grant can't be used to issue another token, as the grantee and the
+-- expiration time are unique for the account.
+CREATE TABLE read_tokens (
+    id BIGSERIAL PRIMARY KEY,
+    account_address BYTEA NOT NULL,
+    token_hash BYTEA NOT NULL UNIQUE,
+    grantee TEXT NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    revoked_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    UNIQUE (account_address, grantee, expires_at)
+);
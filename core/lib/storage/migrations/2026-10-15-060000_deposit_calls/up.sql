@@ -0,0 +1,11 @@
+-- L2 calls of the deposits made via the deposit router, submitted once the deposit is executed.
+CREATE TABLE deposit_calls (
+    serial_id BIGINT PRIMARY KEY,
+    owner_address BYTEA NOT NULL,
+    call BYTEA NOT NULL,
+    eth_block BIGINT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX deposit_calls_status_idx ON deposit_calls (status);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS weth_unwraps;
@@ -0,0 +1,17 @@
+-- Withdrawals delivered as native ETH by the WETH unwrapper once their blocks are executed.
+-- The signed unwrapping transaction is stored along with the operator nonce reserved for it,
+-- and is resent until it's mined.
+CREATE TABLE weth_unwraps (
+    tx_hash BYTEA PRIMARY KEY,
+    block_number BIGINT NOT NULL,
+    recipient BYTEA NOT NULL,
+    amount NUMERIC NOT NULL,
+    eth_tx_hash BYTEA,
+    eth_nonce BIGINT,
+    raw_tx BYTEA,
+    confirmed BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX weth_unwraps_block_number_idx ON weth_unwraps (block_number) WHERE eth_tx_hash IS NULL;
+CREATE INDEX weth_unwraps_unconfirmed_idx ON weth_unwraps (eth_nonce)
+    WHERE eth_tx_hash IS NOT NULL AND NOT confirmed;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS timelocked_withdrawals;
+DROP TABLE IF EXISTS withdrawal_timelocks;
@@ -0,0 +1,24 @@
+-- Per-account timelocks delaying the withdrawals above the threshold.
+CREATE TABLE withdrawal_timelocks (
+    account_id BIGINT PRIMARY KEY,
+    threshold_usd BIGINT NOT NULL,
+    delay_blocks BIGINT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- Withdrawals held until the timelock of the account expires, `notified` is set once the
+-- notification hooks are called for the current status.
+CREATE TABLE timelocked_withdrawals (
+    tx_hash BYTEA PRIMARY KEY,
+    account_id BIGINT NOT NULL,
+    tx JSONB NOT NULL,
+    release_block BIGINT NOT NULL,
+    status TEXT NOT NULL,
+    fail_reason TEXT,
+    notified BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX timelocked_withdrawals_account_id_idx ON timelocked_withdrawals (account_id);
+CREATE INDEX timelocked_withdrawals_status_idx ON timelocked_withdrawals (status, release_block);
+CREATE INDEX timelocked_withdrawals_notified_idx ON timelocked_withdrawals (notified) WHERE notified = false;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS watch_addresses;
+DROP TABLE IF EXISTS watch_lists;
@@ -0,0 +1,16 @@
+-- Lists of the addresses watched on behalf of the exchanges, the incoming transfers and deposits
+-- of the blocks after `last_block` are not reported to the webhook yet.
+CREATE TABLE watch_lists (
+    id BIGSERIAL PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    webhook_url TEXT NOT NULL,
+    last_block BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE TABLE watch_addresses (
+    watch_list_id BIGINT NOT NULL REFERENCES watch_lists (id) ON DELETE CASCADE,
+    address BYTEA NOT NULL,
+    PRIMARY KEY (watch_list_id, address)
+);
+CREATE INDEX watch_addresses_address_idx ON watch_addresses USING hash (address);
@@ -0,0 +1,9 @@
+-- L1 transactions which delivered the funds of the withdrawals, recorded once their blocks
+-- are executed.
+CREATE TABLE withdrawal_eth_txs (
+    tx_hash BYTEA PRIMARY KEY,
+    block_number BIGINT NOT NULL,
+    eth_tx_hash BYTEA NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX withdrawal_eth_txs_block_number_idx ON withdrawal_eth_txs (block_number);
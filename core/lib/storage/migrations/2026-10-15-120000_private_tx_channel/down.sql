@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS private_mempool_txs;
+DROP TABLE IF EXISTS private_tx_clients;
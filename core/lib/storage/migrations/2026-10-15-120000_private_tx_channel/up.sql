@@ -0,0 +1,17 @@
+-- Clients of the private transactions channel, e.g. large traders. Only the hashes of their bearer
+-- tokens are stored.
+CREATE TABLE private_tx_clients (
+    id BIGSERIAL PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    token_hash BYTEA NOT NULL UNIQUE,
+    max_batches_per_minute INTEGER NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- Transactions of the mempool submitted via the private channel, hidden from the public API until
+-- they are executed. Rows are kept when the client is removed, so its queued transactions stay hidden.
+CREATE TABLE private_mempool_txs (
+    tx_hash TEXT PRIMARY KEY,
+    client_id BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
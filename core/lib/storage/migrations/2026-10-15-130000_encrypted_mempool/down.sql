@@ -0,0 +1,4 @@
+DELETE FROM private_mempool_txs WHERE client_id IS NULL;
+ALTER TABLE private_mempool_txs ALTER COLUMN client_id SET NOT NULL;
+
+DROP TABLE IF EXISTS encrypted_mempool_txs;
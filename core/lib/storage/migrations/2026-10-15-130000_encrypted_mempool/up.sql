@@ -0,0 +1,11 @@
+-- Transfers encrypted to the sealing key of the operator, waiting for the block formation.
+-- Rows are removed once the mempool decrypts them, whether the transfer turns out correct or not.
+CREATE TABLE encrypted_mempool_txs (
+    id BIGSERIAL PRIMARY KEY,
+    blob BYTEA NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- Decrypted transfers are hidden from the public API the same way as the private ones, but have
+-- no client.
+ALTER TABLE private_mempool_txs ALTER COLUMN client_id DROP NOT NULL;
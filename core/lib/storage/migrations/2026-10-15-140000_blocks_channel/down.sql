@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS notify_blocks_events ON events;
+DROP TRIGGER IF EXISTS notify_blocks_sealed ON blocks;
+DROP FUNCTION IF EXISTS notify_blocks_channel;
@@ -0,0 +1,18 @@
+-- Notifies the API servers about the new sealed blocks and the block events (committed, finalized
+-- and reverted blocks), so the cached responses are invalidated without polling the blocks.
+CREATE OR REPLACE FUNCTION notify_blocks_channel() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM (
+        SELECT pg_notify('blocks_channel', TG_TABLE_NAME)
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER notify_blocks_sealed
+AFTER INSERT ON blocks
+FOR EACH ROW EXECUTE PROCEDURE notify_blocks_channel();
+
+CREATE TRIGGER notify_blocks_events
+AFTER INSERT ON events
+FOR EACH ROW WHEN (NEW.event_type = 'Block') EXECUTE PROCEDURE notify_blocks_channel();
@@ -0,0 +1,104 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{AccountFreeze, AccountId};
+// Local imports
+use self::records::StorageAccountFreeze;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the compliance holds of the accounts.
+///
+/// Freezes are made by the operator via the private API and are picked up by the state keeper,
+/// which rejects the transfers from the frozen accounts. The records are kept after the account
+/// is unfrozen, so the history of the freezes can be audited.
+#[derive(Debug)]
+pub struct AccountFreezesSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> AccountFreezesSchema<'a, 'c> {
+    /// Freezes the account. Returns `false` if the account is already frozen.
+    pub async fn freeze(&mut self, account_id: AccountId, reason: &str) -> QueryResult<bool> {
+        let start = Instant::now();
+        let inserted = sqlx::query!(
+            "INSERT INTO account_freezes (account_id, reason) VALUES ($1, $2)
+            ON CONFLICT (account_id) WHERE unfrozen_at IS NULL DO NOTHING",
+            i64::from(*account_id),
+            reason
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.account_freezes.freeze", start.elapsed());
+        Ok(inserted == 1)
+    }
+
+    /// Unfreezes the account. Returns `false` if the account is not frozen.
+    pub async fn unfreeze(&mut self, account_id: AccountId, reason: &str) -> QueryResult<bool> {
+        let start = Instant::now();
+        let updated = sqlx::query!(
+            "UPDATE account_freezes SET unfrozen_at = now(), unfreeze_reason = $2
+            WHERE account_id = $1 AND unfrozen_at IS NULL",
+            i64::from(*account_id),
+            reason
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.account_freezes.unfreeze", start.elapsed());
+        Ok(updated == 1)
+    }
+
+    /// Loads the active freeze of the account, if any.
+    pub async fn active_freeze(
+        &mut self,
+        account_id: AccountId,
+    ) -> QueryResult<Option<AccountFreeze>> {
+        let start = Instant::now();
+        let freeze = sqlx::query_as!(
+            StorageAccountFreeze,
+            "SELECT * FROM account_freezes WHERE account_id = $1 AND unfrozen_at IS NULL",
+            i64::from(*account_id)
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.account_freezes.active_freeze", start.elapsed());
+        Ok(freeze.map(Into::into))
+    }
+
+    /// Loads the active freezes of all the accounts.
+    pub async fn load_active_freezes(&mut self) -> QueryResult<Vec<AccountFreeze>> {
+        let start = Instant::now();
+        let freezes = sqlx::query_as!(
+            StorageAccountFreeze,
+            "SELECT * FROM account_freezes WHERE unfrozen_at IS NULL ORDER BY id"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.account_freezes.load_active_freezes", start.elapsed());
+        Ok(freezes.into_iter().map(Into::into).collect())
+    }
+
+    /// Loads all the freezes of the account, including the lifted ones, starting from the latest.
+    pub async fn load_history(
+        &mut self,
+        account_id: AccountId,
+    ) -> QueryResult<Vec<StorageAccountFreeze>> {
+        let start = Instant::now();
+        let freezes = sqlx::query_as!(
+            StorageAccountFreeze,
+            "SELECT * FROM account_freezes WHERE account_id = $1 ORDER BY id DESC",
+            i64::from(*account_id)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.account_freezes.load_history", start.elapsed());
+        Ok(freezes)
+    }
+}
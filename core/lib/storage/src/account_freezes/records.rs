@@ -0,0 +1,27 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+use zksync_types::{AccountFreeze, AccountId};
+// Local imports
+
+/// Freeze of the account made by the operator. The freeze is active until `unfrozen_at` is set.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageAccountFreeze {
+    pub id: i64,
+    pub account_id: i64,
+    pub reason: String,
+    pub frozen_at: DateTime<Utc>,
+    pub unfreeze_reason: Option<String>,
+    pub unfrozen_at: Option<DateTime<Utc>>,
+}
+
+impl From<StorageAccountFreeze> for AccountFreeze {
+    fn from(val: StorageAccountFreeze) -> Self {
+        Self {
+            account_id: AccountId(val.account_id as u32),
+            reason: val.reason,
+            frozen_at: val.frozen_at,
+        }
+    }
+}
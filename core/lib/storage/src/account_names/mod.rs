@@ -0,0 +1,91 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{tx::TxHash, Address};
+// Local imports
+use self::records::StorageAccountName;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the human-readable names of the accounts.
+///
+/// The registration fee is paid by a transaction submitted with the registration, the name
+/// resolves to the address once that transaction is executed. The name of the failed
+/// registration becomes available again.
+#[derive(Debug)]
+pub struct AccountNamesSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> AccountNamesSchema<'a, 'c> {
+    /// Reserves the name for the address. Returns `false` if the name is already registered
+    /// and the fee transaction of that registration has not failed.
+    pub async fn reserve_account_name(
+        &mut self,
+        name: &str,
+        address: Address,
+        fee_tx_hash: TxHash,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO account_names (name, address, fee_tx_hash)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (name) DO UPDATE
+            SET address = $2, fee_tx_hash = $3, created_at = now()
+            WHERE EXISTS (
+                SELECT 1 FROM executed_transactions
+                WHERE executed_transactions.tx_hash = account_names.fee_tx_hash
+                    AND executed_transactions.success = false
+            )
+            "#,
+            name,
+            address.as_bytes(),
+            fee_tx_hash.as_ref()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.account_names.reserve_account_name", start.elapsed());
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Loads the registration of the name along with the result of its fee transaction.
+    pub async fn load_account_name(
+        &mut self,
+        name: &str,
+    ) -> QueryResult<Option<StorageAccountName>> {
+        let start = Instant::now();
+        let record = sqlx::query_as!(
+            StorageAccountName,
+            r#"
+            SELECT account_names.name, account_names.address, account_names.fee_tx_hash,
+                account_names.created_at, executed_transactions.success AS "fee_tx_success?"
+            FROM account_names
+            LEFT JOIN executed_transactions
+                ON executed_transactions.tx_hash = account_names.fee_tx_hash
+            WHERE account_names.name = $1
+            "#,
+            name
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.account_names.load_account_name", start.elapsed());
+        Ok(record)
+    }
+
+    /// Returns the address the name resolves to, the name is resolved only if its fee
+    /// transaction is executed successfully.
+    pub async fn resolve_account_name(&mut self, name: &str) -> QueryResult<Option<Address>> {
+        let start = Instant::now();
+        let address = self
+            .load_account_name(name)
+            .await?
+            .filter(|record| record.fee_tx_success == Some(true))
+            .map(|record| Address::from_slice(&record.address));
+
+        metrics::histogram!("sql.account_names.resolve_account_name", start.elapsed());
+        Ok(address)
+    }
+}
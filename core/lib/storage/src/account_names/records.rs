@@ -0,0 +1,14 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// Name registered for the account.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageAccountName {
+    pub name: String,
+    pub address: Vec<u8>,
+    pub fee_tx_hash: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+    /// Result of the fee transaction, `None` if it's not executed yet.
+    pub fee_tx_success: Option<bool>,
+}
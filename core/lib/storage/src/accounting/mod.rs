@@ -0,0 +1,210 @@
+// Built-in deps
+use std::str::FromStr;
+use std::time::Instant;
+// External imports
+use chrono::NaiveDate;
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{BlockNumber, H256, U256};
+// Local imports
+use self::records::{BlockProfitability, DailyProfitability, StorageBlockFee};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the operator revenue and cost accounting.
+///
+/// The revenue of the block is the fees collected by its transactions, the cost of the block
+/// is its share of the L1 gas spent for the aggregated operations containing it.
+#[derive(Debug)]
+pub struct AccountingSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> AccountingSchema<'a, 'c> {
+    /// Records the fees collected by the successful transactions of the block.
+    /// Transactions of the block must be stored before calling this method.
+    pub async fn record_block_fees(&mut self, block_number: BlockNumber) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO block_fees (block_number, token_id, amount)
+            SELECT $1, fees.token_id, SUM(fees.amount) FROM (
+                SELECT COALESCE(tx->>'feeToken', tx->>'token')::integer AS token_id,
+                    (tx->>'fee')::numeric AS amount
+                FROM executed_transactions
+                WHERE block_number = $1 AND success = true AND tx->>'fee' IS NOT NULL
+            ) fees
+            WHERE fees.token_id IS NOT NULL
+            GROUP BY fees.token_id
+            ON CONFLICT (block_number, token_id) DO NOTHING
+            "#,
+            i64::from(*block_number)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.accounting.record_block_fees", start.elapsed());
+        Ok(())
+    }
+
+    /// Records the L1 gas spent by the transaction that confirmed the aggregated operation.
+    pub async fn record_operation_cost(
+        &mut self,
+        op_id: i64,
+        tx_hash: H256,
+        gas_used: U256,
+        gas_price: U256,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let gas_used = BigDecimal::from_str(&gas_used.to_string())?;
+        let gas_price = BigDecimal::from_str(&gas_price.to_string())?;
+        sqlx::query!(
+            r#"
+            INSERT INTO aggregated_operation_costs (op_id, tx_hash, gas_used, gas_price)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (op_id) DO NOTHING
+            "#,
+            op_id,
+            tx_hash.as_bytes(),
+            gas_used,
+            gas_price
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.accounting.record_operation_cost", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the fees collected in the blocks of the range.
+    pub async fn load_block_fees(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<Vec<StorageBlockFee>> {
+        let start = Instant::now();
+        let fees = sqlx::query_as!(
+            StorageBlockFee,
+            r#"
+            SELECT * FROM block_fees
+            WHERE block_number BETWEEN $1 AND $2
+            ORDER BY block_number, token_id
+            "#,
+            i64::from(*from_block),
+            i64::from(*to_block)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.accounting.load_block_fees", start.elapsed());
+        Ok(fees)
+    }
+
+    /// Computes the profitability of every block of the range.
+    pub async fn load_blocks_profitability(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<Vec<BlockProfitability>> {
+        let start = Instant::now();
+        // The gas of the aggregated operation is split evenly between its blocks.
+        let profitability = sqlx::query_as!(
+            BlockProfitability,
+            r#"
+            WITH revenue AS (
+                SELECT block_fees.block_number,
+                    SUM(block_fees.amount * ticker_price.usd_price / power(10::numeric, tokens.decimals)) AS revenue_usd
+                FROM block_fees
+                    INNER JOIN tokens ON tokens.id = block_fees.token_id
+                    INNER JOIN ticker_price ON ticker_price.token_id = block_fees.token_id
+                WHERE block_fees.block_number BETWEEN $1 AND $2
+                GROUP BY block_fees.block_number
+            ),
+            costs AS (
+                SELECT blocks.number AS block_number,
+                    SUM(aggregated_operation_costs.gas_used * aggregated_operation_costs.gas_price
+                        / (aggregate_operations.to_block - aggregate_operations.from_block + 1)) AS cost_wei
+                FROM blocks
+                    INNER JOIN aggregate_operations
+                        ON blocks.number BETWEEN aggregate_operations.from_block AND aggregate_operations.to_block
+                    INNER JOIN aggregated_operation_costs ON aggregated_operation_costs.op_id = aggregate_operations.id
+                WHERE blocks.number BETWEEN $1 AND $2
+                GROUP BY blocks.number
+            )
+            SELECT
+                blocks.number AS "block_number!",
+                COALESCE(revenue.revenue_usd, 0) AS "revenue_usd!",
+                COALESCE(costs.cost_wei, 0) AS "l1_cost_wei!",
+                COALESCE(costs.cost_wei, 0) * COALESCE((SELECT usd_price FROM ticker_price WHERE token_id = 0), 0)
+                    / power(10::numeric, 18) AS "l1_cost_usd!"
+            FROM blocks
+                LEFT JOIN revenue ON revenue.block_number = blocks.number
+                LEFT JOIN costs ON costs.block_number = blocks.number
+            WHERE blocks.number BETWEEN $1 AND $2
+            ORDER BY blocks.number
+            "#,
+            i64::from(*from_block),
+            i64::from(*to_block)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.accounting.load_blocks_profitability", start.elapsed());
+        Ok(profitability)
+    }
+
+    /// Computes the profitability of the blocks created during the days starting from `from_day`,
+    /// the latest days go first.
+    pub async fn load_daily_profitability(
+        &mut self,
+        from_day: NaiveDate,
+    ) -> QueryResult<Vec<DailyProfitability>> {
+        let start = Instant::now();
+        let profitability = sqlx::query_as!(
+            DailyProfitability,
+            r#"
+            WITH day_blocks AS (
+                SELECT number, (to_timestamp(timestamp) AT TIME ZONE 'UTC')::date AS day
+                FROM blocks
+                WHERE timestamp IS NOT NULL AND (to_timestamp(timestamp) AT TIME ZONE 'UTC')::date >= $1
+            ),
+            revenue AS (
+                SELECT block_fees.block_number,
+                    SUM(block_fees.amount * ticker_price.usd_price / power(10::numeric, tokens.decimals)) AS revenue_usd
+                FROM block_fees
+                    INNER JOIN day_blocks ON day_blocks.number = block_fees.block_number
+                    INNER JOIN tokens ON tokens.id = block_fees.token_id
+                    INNER JOIN ticker_price ON ticker_price.token_id = block_fees.token_id
+                GROUP BY block_fees.block_number
+            ),
+            costs AS (
+                SELECT day_blocks.number AS block_number,
+                    SUM(aggregated_operation_costs.gas_used * aggregated_operation_costs.gas_price
+                        / (aggregate_operations.to_block - aggregate_operations.from_block + 1)) AS cost_wei
+                FROM day_blocks
+                    INNER JOIN aggregate_operations
+                        ON day_blocks.number BETWEEN aggregate_operations.from_block AND aggregate_operations.to_block
+                    INNER JOIN aggregated_operation_costs ON aggregated_operation_costs.op_id = aggregate_operations.id
+                GROUP BY day_blocks.number
+            )
+            SELECT
+                day_blocks.day AS "day!",
+                COUNT(*) AS "blocks_count!",
+                COALESCE(SUM(revenue.revenue_usd), 0) AS "revenue_usd!",
+                COALESCE(SUM(costs.cost_wei), 0) AS "l1_cost_wei!",
+                COALESCE(SUM(costs.cost_wei), 0) * COALESCE((SELECT usd_price FROM ticker_price WHERE token_id = 0), 0)
+                    / power(10::numeric, 18) AS "l1_cost_usd!"
+            FROM day_blocks
+                LEFT JOIN revenue ON revenue.block_number = day_blocks.number
+                LEFT JOIN costs ON costs.block_number = day_blocks.number
+            GROUP BY day_blocks.day
+            ORDER BY day_blocks.day DESC
+            "#,
+            from_day
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.accounting.load_daily_profitability", start.elapsed());
+        Ok(profitability)
+    }
+}
@@ -0,0 +1,45 @@
+// External imports
+use chrono::NaiveDate;
+use sqlx::{types::BigDecimal, FromRow};
+
+/// Amount of fees in a certain token collected in the block.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageBlockFee {
+    pub block_number: i64,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+}
+
+/// Revenue and costs of the operator for the single block.
+///
+/// The values in USD are computed using the current token prices, the fees in tokens
+/// without known price are not taken into account.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct BlockProfitability {
+    pub block_number: i64,
+    pub revenue_usd: BigDecimal,
+    pub l1_cost_wei: BigDecimal,
+    pub l1_cost_usd: BigDecimal,
+}
+
+/// Revenue and costs of the operator for the blocks created during the day (in UTC).
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct DailyProfitability {
+    pub day: NaiveDate,
+    pub blocks_count: i64,
+    pub revenue_usd: BigDecimal,
+    pub l1_cost_wei: BigDecimal,
+    pub l1_cost_usd: BigDecimal,
+}
+
+impl BlockProfitability {
+    pub fn profit_usd(&self) -> BigDecimal {
+        &self.revenue_usd - &self.l1_cost_usd
+    }
+}
+
+impl DailyProfitability {
+    pub fn profit_usd(&self) -> BigDecimal {
+        &self.revenue_usd - &self.l1_cost_usd
+    }
+}
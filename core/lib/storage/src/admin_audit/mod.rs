@@ -0,0 +1,74 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+// Local imports
+use self::records::{NewAuditEntry, StorageAuditEntry};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the audit log of the admin actions.
+///
+/// The log is append-only: the table rejects updates and deletions, so the entries can't
+/// be altered even with the direct database access of the server.
+#[derive(Debug)]
+pub struct AdminAuditSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> AdminAuditSchema<'a, 'c> {
+    /// Appends the entry to the log, returning its ID. Should be called within the same database
+    /// transaction as the audited change, so that the change is never applied without the entry.
+    pub async fn record(&mut self, entry: &NewAuditEntry) -> QueryResult<i64> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            "INSERT INTO admin_audit_log (actor, action, target, before_value, after_value)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id",
+            entry.actor,
+            entry.action,
+            entry.target,
+            entry.before_value,
+            entry.after_value
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        metrics::histogram!("sql.admin_audit.record", start.elapsed());
+        Ok(id)
+    }
+
+    /// Loads the entries following the entry with `after_id` in the order they were recorded.
+    /// Entries may be narrowed down to the time range and the action.
+    pub async fn load_entries(
+        &mut self,
+        after_id: i64,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+        action: Option<&str>,
+        limit: u32,
+    ) -> QueryResult<Vec<StorageAuditEntry>> {
+        let start = Instant::now();
+        let entries = sqlx::query_as!(
+            StorageAuditEntry,
+            "SELECT * FROM admin_audit_log
+            WHERE id > $1
+                AND ($2::TIMESTAMPTZ IS NULL OR created_at >= $2)
+                AND ($3::TIMESTAMPTZ IS NULL OR created_at < $3)
+                AND ($4::TEXT IS NULL OR action = $4)
+            ORDER BY id
+            LIMIT $5",
+            after_id,
+            since,
+            until,
+            action,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.admin_audit.load_entries", start.elapsed());
+        Ok(entries)
+    }
+}
@@ -0,0 +1,30 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+/// Action made via the admin interfaces.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageAuditEntry {
+    pub id: i64,
+    /// Identity of the operator who made the action.
+    pub actor: String,
+    pub action: String,
+    /// Object affected by the action, e.g. the account ID.
+    pub target: Option<String>,
+    pub before_value: Option<Value>,
+    pub after_value: Option<Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Audit entry to be recorded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewAuditEntry {
+    pub actor: String,
+    pub action: String,
+    pub target: Option<String>,
+    pub before_value: Option<Value>,
+    pub after_value: Option<Value>,
+}
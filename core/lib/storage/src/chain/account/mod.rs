@@ -1,4 +1,5 @@
 // Built-in deps
+use std::collections::HashMap;
 use std::time::Instant;
 // External imports
 use num::{BigUint, Zero};
@@ -10,7 +11,7 @@ use zksync_types::{
 };
 // Local imports
 use self::records::*;
-use crate::chain::block::BlockSchema;
+use crate::chain::{block::BlockSchema, state::StateSchema};
 use crate::diff::StorageAccountDiff;
 use crate::{QueryResult, StorageProcessor};
 
@@ -19,10 +20,14 @@ pub mod restore_account;
 mod stored_state;
 
 pub(crate) use self::restore_account::restore_account;
-pub use self::stored_state::StoredAccountState;
+pub use self::stored_state::{BulkAccountState, StoredAccountState};
 use crate::tokens::records::StorageNFT;
 use num::bigint::ToBigInt;
 
+/// Amount of the updates applied to restore the historical state of the account, starting from
+/// which the restored state is stored as a snapshot.
+const ACCOUNT_SNAPSHOT_UPDATES: usize = 64;
+
 /// Account schema contains interfaces to interact with the stored
 /// ZKSync accounts.
 #[derive(Debug)]
@@ -216,6 +221,223 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         account_state
     }
 
+    /// Obtains both committed and verified states for the accounts of the given addresses.
+    /// The states are loaded with a fixed number of queries regardless of the number of
+    /// addresses and are returned in the order of the addresses.
+    pub async fn account_states_by_addresses(
+        &mut self,
+        addresses: &[Address],
+    ) -> QueryResult<Vec<BulkAccountState>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let raw_addresses: Vec<Vec<u8>> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let account_ids: HashMap<Address, AccountId> = sqlx::query!(
+            r#"
+                SELECT DISTINCT ON (address) address, account_id FROM account_creates
+                WHERE address = ANY($1) AND is_create = true
+                ORDER BY address, block_number DESC
+            "#,
+            &raw_addresses
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|record| {
+            (
+                Address::from_slice(&record.address),
+                AccountId(record.account_id as u32),
+            )
+        })
+        .collect();
+
+        let account_ids_list: Vec<AccountId> = account_ids.values().copied().collect();
+        let ids: Vec<i64> = account_ids_list.iter().map(|id| i64::from(**id)).collect();
+        let creator_ids: Vec<i32> = ids.iter().map(|id| *id as i32).collect();
+
+        // `accounts` table contains the verified states of the accounts.
+        let stored_accounts = sqlx::query_as!(
+            StorageAccount,
+            "SELECT * FROM accounts WHERE id = ANY($1)",
+            &ids
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let mut balances: HashMap<i64, Vec<StorageBalance>> = HashMap::new();
+        for balance in sqlx::query_as!(
+            StorageBalance,
+            "SELECT * FROM balances WHERE account_id = ANY($1)",
+            &ids
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        {
+            balances
+                .entry(balance.account_id)
+                .or_default()
+                .push(balance);
+        }
+        let nfts: Vec<StorageNFT> = sqlx::query_as!(
+            StorageNFT,
+            "
+                SELECT nft.*, tokens.symbol FROM nft
+                INNER JOIN tokens
+                ON tokens.id = nft.token_id
+                WHERE creator_account_id = ANY($1)
+            ",
+            &creator_ids
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let verified_blocks: HashMap<AccountId, BlockNumber> = stored_accounts
+            .iter()
+            .map(|account| {
+                (
+                    AccountId(account.id as u32),
+                    BlockNumber(account.last_block as u32),
+                )
+            })
+            .collect();
+        let mut verified: HashMap<AccountId, Account> = stored_accounts
+            .iter()
+            .map(|account| {
+                restore_account(account, balances.remove(&account.id).unwrap_or_default())
+            })
+            .collect();
+        for nft in nfts {
+            if let Some(account) = verified.get_mut(&AccountId(nft.creator_account_id as u32)) {
+                account
+                    .minted_nfts
+                    .insert(TokenId(nft.token_id as u32), nft.into());
+            }
+        }
+
+        // Apply the committed changes on top of the verified states.
+        let last_verified_block = BlockSchema(&mut transaction)
+            .get_last_verified_confirmed_block()
+            .await?;
+        let updates = StateSchema(&mut transaction)
+            .load_accounts_diff(
+                &account_ids_list,
+                last_verified_block,
+                BlockNumber(u32::MAX),
+            )
+            .await?;
+        let mut committed = verified.clone();
+        for (account_id, update) in updates {
+            let account = committed.remove(&account_id);
+            if let Some(account) = Account::apply_update(account, update) {
+                committed.insert(account_id, account);
+            }
+        }
+        let committed_blocks: HashMap<AccountId, BlockNumber> = sqlx::query!(
+            r#"
+                SELECT account_id, MAX(block_number) AS "block_number!" FROM (
+                    SELECT account_id, block_number FROM account_balance_updates
+                        WHERE account_id = ANY($1) AND block_number > $2
+                    UNION ALL
+                    SELECT account_id, block_number FROM account_creates
+                        WHERE account_id = ANY($1) AND block_number > $2
+                    UNION ALL
+                    SELECT account_id, block_number FROM account_pubkey_updates
+                        WHERE account_id = ANY($1) AND block_number > $2
+                ) updates
+                GROUP BY account_id
+            "#,
+            &ids,
+            i64::from(*last_verified_block)
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|record| {
+            (
+                AccountId(record.account_id as u32),
+                BlockNumber(record.block_number as u32),
+            )
+        })
+        .collect();
+
+        let account_types: HashMap<AccountId, DbAccountType> = sqlx::query_as!(
+            StorageAccountType,
+            r#"
+                SELECT account_id, account_type as "account_type!: DbAccountType"
+                FROM eth_account_types WHERE account_id = ANY($1)
+            "#,
+            &ids
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|record| (AccountId(record.account_id as u32), record.account_type))
+        .collect();
+        let no_2fa_hashes: HashMap<AccountId, PubKeyHash> = sqlx::query!(
+            "SELECT account_id, pub_key_hash FROM no_2fa_pub_key_hash WHERE account_id = ANY($1)",
+            &ids
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|record| {
+            (
+                AccountId(record.account_id as u32),
+                PubKeyHash::from_hex(&record.pub_key_hash).unwrap(),
+            )
+        })
+        .collect();
+
+        transaction.commit().await?;
+
+        let states = addresses
+            .iter()
+            .map(|address| match account_ids.get(address) {
+                Some(&account_id) => {
+                    let last_verified_block = verified_blocks
+                        .get(&account_id)
+                        .copied()
+                        .unwrap_or_default();
+                    BulkAccountState {
+                        state: StoredAccountState {
+                            committed: committed.get(&account_id).cloned().map(|a| (account_id, a)),
+                            verified: verified.get(&account_id).cloned().map(|a| (account_id, a)),
+                        },
+                        last_committed_block: committed_blocks
+                            .get(&account_id)
+                            .copied()
+                            .unwrap_or_default()
+                            .max(last_verified_block),
+                        last_verified_block,
+                        account_type: account_types.get(&account_id).map(|db_type| {
+                            EthAccountType::from_db(
+                                db_type,
+                                no_2fa_hashes.get(&account_id).copied(),
+                            )
+                        }),
+                    }
+                }
+                None => BulkAccountState {
+                    state: StoredAccountState {
+                        committed: None,
+                        verified: None,
+                    },
+                    last_committed_block: BlockNumber(0),
+                    last_verified_block: BlockNumber(0),
+                    account_type: None,
+                },
+            })
+            .collect();
+
+        metrics::histogram!(
+            "sql.chain.account.account_states_by_addresses",
+            start.elapsed()
+        );
+        Ok(states)
+    }
+
     /// Loads the last committed (e.g. just added but no necessarily verified) state for
     /// account given its ID.
     /// Returns both verified and committed states.
@@ -342,6 +564,129 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(account)
     }
 
+    /// Restores the state of the account after the given block, along with the last block
+    /// not after the given one in which the account was updated. The account is `None` if it
+    /// didn't exist at that block.
+    ///
+    /// The state is restored from the nearest snapshot by applying the updates made after it.
+    /// If the amount of such updates is significant and the block is finalized, the restored
+    /// state is stored as a new snapshot.
+    pub async fn account_state_at_block(
+        &mut self,
+        account_id: AccountId,
+        block_number: BlockNumber,
+    ) -> QueryResult<(BlockNumber, Option<Account>)> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let snapshot = sqlx::query!(
+            "
+                SELECT block_number, account, last_update_block FROM account_state_snapshots
+                WHERE account_id = $1 AND block_number <= $2
+                ORDER BY block_number DESC
+                LIMIT 1
+            ",
+            i64::from(*account_id),
+            i64::from(*block_number)
+        )
+        .fetch_optional(transaction.conn())
+        .await?;
+        let (snapshot_block, mut last_update_block, account) = match snapshot {
+            Some(snapshot) => {
+                let account: Option<Account> = snapshot
+                    .account
+                    .map(serde_json::from_value)
+                    .transpose()
+                    .expect("Invalid account state snapshot");
+                (
+                    BlockNumber(snapshot.block_number as u32),
+                    BlockNumber(snapshot.last_update_block as u32),
+                    account,
+                )
+            }
+            None => (BlockNumber(0), BlockNumber(0), None),
+        };
+
+        let updates = StateSchema(&mut transaction)
+            .load_accounts_diff(&[account_id], snapshot_block, block_number)
+            .await?;
+        if !updates.is_empty() {
+            let last_update = sqlx::query!(
+                r#"
+                    SELECT MAX(block_number) AS "block_number!" FROM (
+                        SELECT block_number FROM account_balance_updates
+                            WHERE account_id = $1 AND block_number > $2 AND block_number <= $3
+                        UNION ALL
+                        SELECT block_number FROM account_creates
+                            WHERE account_id = $1 AND block_number > $2 AND block_number <= $3
+                        UNION ALL
+                        SELECT block_number FROM account_pubkey_updates
+                            WHERE account_id = $1 AND block_number > $2 AND block_number <= $3
+                    ) updates
+                "#,
+                i64::from(*account_id),
+                i64::from(*snapshot_block),
+                i64::from(*block_number)
+            )
+            .fetch_one(transaction.conn())
+            .await?;
+            last_update_block = last_update_block.max(BlockNumber(last_update.block_number as u32));
+        }
+        let applied_updates = updates.len();
+        let account = updates
+            .into_iter()
+            .map(|(_, update)| update)
+            .fold(account, Account::apply_update);
+
+        if applied_updates >= ACCOUNT_SNAPSHOT_UPDATES
+            && BlockSchema(&mut transaction)
+                .is_block_finalized(block_number)
+                .await?
+        {
+            AccountSchema(&mut transaction)
+                .store_account_snapshot(
+                    account_id,
+                    block_number,
+                    last_update_block,
+                    account.as_ref(),
+                )
+                .await?;
+        }
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain.account.account_state_at_block", start.elapsed());
+        Ok((last_update_block, account))
+    }
+
+    /// Stores the state of the account after the finalized block.
+    pub(crate) async fn store_account_snapshot(
+        &mut self,
+        account_id: AccountId,
+        block_number: BlockNumber,
+        last_update_block: BlockNumber,
+        account: Option<&Account>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let account = account
+            .map(|account| serde_json::to_value(account).expect("Account state is serializable"));
+        sqlx::query!(
+            "
+                INSERT INTO account_state_snapshots (account_id, block_number, account, last_update_block)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (account_id, block_number) DO NOTHING
+            ",
+            i64::from(*account_id),
+            i64::from(*block_number),
+            account,
+            i64::from(*last_update_block)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.account.store_account_snapshot", start.elapsed());
+        Ok(())
+    }
+
     /// Obtains the last verified state of the account.
     pub async fn account_and_last_block(
         &mut self,
@@ -412,20 +757,22 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
     ) -> QueryResult<Option<AccountId>> {
         let start = Instant::now();
         // Find the account ID in `account_creates` table.
+        // The last record is a deletion if the account was reclaimed.
         let result = sqlx::query!(
             r#"
-                SELECT account_id FROM account_creates
-                WHERE address = $1 AND is_create = $2
-                ORDER BY block_number desc
+                SELECT account_id, is_create FROM account_creates
+                WHERE address = $1
+                ORDER BY block_number DESC, update_order_id DESC
                 LIMIT 1
             "#,
-            address.as_bytes(),
-            true
+            address.as_bytes()
         )
         .fetch_optional(self.0.conn())
         .await?;
 
-        let account_id = result.map(|record| AccountId(record.account_id as u32));
+        let account_id = result
+            .filter(|record| record.is_create)
+            .map(|record| AccountId(record.account_id as u32));
         metrics::histogram!("sql.chain.account.account_id_by_address", start.elapsed());
         Ok(account_id)
     }
@@ -436,14 +783,22 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
     ) -> QueryResult<Option<Address>> {
         let start = Instant::now();
         // Find the account address in `account_creates` table.
+        // Leaves of the reclaimed accounts may be taken by the new ones, so the last record is used.
         let result = sqlx::query!(
-            "SELECT address FROM account_creates WHERE account_id = $1",
+            r#"
+                SELECT address, is_create FROM account_creates
+                WHERE account_id = $1
+                ORDER BY block_number DESC, update_order_id DESC
+                LIMIT 1
+            "#,
             i64::from(*account_id)
         )
         .fetch_optional(self.0.conn())
         .await?;
 
-        let address = result.map(|record| Address::from_slice(&record.address));
+        let address = result
+            .filter(|record| record.is_create)
+            .map(|record| Address::from_slice(&record.address));
         metrics::histogram!("sql.chain.account.account_address_by_id", start.elapsed());
         Ok(address)
     }
@@ -1,7 +1,19 @@
-use zksync_types::{Account, AccountId};
+use super::records::EthAccountType;
+use zksync_types::{Account, AccountId, BlockNumber};
 
 #[derive(Debug, PartialEq)]
 pub struct StoredAccountState {
     pub committed: Option<(AccountId, Account)>,
     pub verified: Option<(AccountId, Account)>,
 }
+
+/// Account state loaded by the bulk query along with the data needed to display it.
+#[derive(Debug)]
+pub struct BulkAccountState {
+    pub state: StoredAccountState,
+    /// Last block with the update of the committed state.
+    pub last_committed_block: BlockNumber,
+    /// Last block with the update of the verified state.
+    pub last_verified_block: BlockNumber,
+    pub account_type: Option<EthAccountType>,
+}
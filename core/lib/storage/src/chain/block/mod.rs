@@ -12,7 +12,7 @@ use zksync_api_types::{
 use zksync_crypto::convert::FeConvert;
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
-    block::{Block, BlockMetadata, ExecutedOperations, IncompleteBlock, PendingBlock},
+    block::{Block, BlockMetadata, ExecutedOperations, IncompleteBlock, OpTypeStats, PendingBlock},
     event::block::BlockStatus,
     AccountId, BlockNumber, Fr, ZkSyncOp, H256, U256,
 };
@@ -24,8 +24,8 @@ use self::records::{
 use crate::{
     chain::operations::{
         records::{
-            NewExecutedPriorityOperation, NewExecutedTransaction, StoredExecutedPriorityOperation,
-            StoredExecutedTransaction,
+            NewExecutedOperation, NewExecutedPriorityOperation, NewExecutedTransaction,
+            StoredExecutedPriorityOperation, StoredExecutedTransaction,
         },
         OperationsSchema,
     },
@@ -53,6 +53,9 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
 
+        // Operations are prepared first, and then stored with the bulk inserts in the order
+        // of their execution.
+        let mut executed_operations = Vec::with_capacity(operations.len());
         for block_tx in operations.into_iter() {
             match block_tx {
                 ExecutedOperations::Tx(tx) => {
@@ -74,9 +77,14 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                             .account_type_by_id(tx.account_id)
                             .await?;
 
-                        let new_account_type = match (current_type, account_type) {
-                            // You can not change No2FA to Owned here
-                            (Some(EthAccountType::No2FA(hash)), EthAccountType::Owned) => {
+                        let new_account_type = match (current_type, account_type, tx.tx.no_2fa) {
+                            // The 2FA flag of the transaction is authorized by the Ethereum signature
+                            (_, EthAccountType::Owned, Some(true)) => {
+                                EthAccountType::No2FA(Some(tx.tx.new_pk_hash))
+                            }
+                            (_, EthAccountType::Owned, Some(false)) => EthAccountType::Owned,
+                            // You can not change No2FA to Owned here without the flag
+                            (Some(EthAccountType::No2FA(hash)), EthAccountType::Owned, None) => {
                                 EthAccountType::No2FA(hash)
                             }
                             _ => account_type,
@@ -95,30 +103,26 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                         &mut transaction,
                     )
                     .await?;
-                    transaction
-                        .chain()
-                        .operations_schema()
-                        .store_executed_tx(new_tx)
-                        .await?;
+                    executed_operations.push(NewExecutedOperation::Tx(new_tx));
                 }
                 ExecutedOperations::PriorityOp(prior_op) => {
-                    // Store the executed operation in the corresponding schema.
                     // For priority operation we should only store it in the Operations schema.
                     let new_priority_op = NewExecutedPriorityOperation::prepare_stored_priority_op(
                         *prior_op,
                         block_number,
                     );
-
-                    // Store the executed operation in the corresponding schema.
-                    transaction
-                        .chain()
-                        .operations_schema()
-                        .store_executed_priority_op(new_priority_op)
-                        .await?;
+                    executed_operations.push(NewExecutedOperation::PriorityOp(new_priority_op));
                 }
             }
         }
 
+        // Store the executed operations in the corresponding schema.
+        transaction
+            .chain()
+            .operations_schema()
+            .store_executed_operations(executed_operations)
+            .await?;
+
         transaction.commit().await?;
         metrics::histogram!("sql.chain.block.save_block_transactions", start.elapsed());
         Ok(())
@@ -209,6 +213,22 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         Ok(result)
     }
 
+    /// Returns the chunks, public data and estimated gas consumed by every type of the operations
+    /// in the block, `None` if the block doesn't exist yet.
+    pub async fn load_block_op_type_stats(
+        &mut self,
+        block: BlockNumber,
+    ) -> QueryResult<Option<Vec<OpTypeStats>>> {
+        let start = Instant::now();
+        let stats = self
+            .get_block(block)
+            .await?
+            .map(|block| block.op_type_stats());
+
+        metrics::histogram!("sql.chain.block.load_block_op_type_stats", start.elapsed());
+        Ok(stats)
+    }
+
     /// Same as `get_block_executed_ops`, but returns a vector of `ZkSyncOp` instead
     /// of `ExecutedOperations`.
     pub async fn get_block_operations(&mut self, block: BlockNumber) -> QueryResult<Vec<ZkSyncOp>> {
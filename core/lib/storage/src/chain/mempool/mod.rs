@@ -10,12 +10,15 @@ use zksync_api_types::v02::transaction::{
 use zksync_types::{
     block::IncompleteBlock,
     mempool::SignedTxVariant,
-    tx::{TxEthSignature, TxHash},
-    AccountId, Address, BlockNumber, ExecutedOperations, ExecutedPriorityOp, ExecutedTx,
+    tx::{ChangePubKey, TxEthSignature, TxHash},
+    AccountId, Address, BlockNumber, ExecutedOperations, ExecutedPriorityOp, ExecutedTx, Nonce,
     PriorityOp, SerialId, SignedZkSyncTx, ZkSyncPriorityOp, H256,
 };
 // Local imports
-use self::records::{MempoolPriorityOp, MempoolTx, QueuedBatchTx, RevertedBlock};
+use self::records::{
+    MempoolPriorityOp, MempoolPriorityOpHash, MempoolTx, OnchainAuthTx, QueuedBatchTx,
+    RevertedBlock,
+};
 use crate::{QueryResult, StorageProcessor};
 
 use crate::chain::operations::records::{
@@ -24,6 +27,13 @@ use crate::chain::operations::records::{
 
 pub mod records;
 
+/// Status of the onchain `ChangePubKey` transaction waiting for the auth fact.
+pub const ONCHAIN_AUTH_WAITING: &str = "waiting";
+/// Status of the onchain `ChangePubKey` transaction submitted after the auth fact was set.
+pub const ONCHAIN_AUTH_RELEASED: &str = "released";
+/// Status of the onchain `ChangePubKey` transaction rejected on submission.
+pub const ONCHAIN_AUTH_FAILED: &str = "failed";
+
 /// Schema for persisting transactions awaiting for the execution.
 ///
 /// This schema holds the transactions that are received by the `mempool` module, but not yet have
@@ -285,13 +295,24 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
     }
 
     pub async fn remove_txs(&mut self, txs: &[TxHash]) -> QueryResult<()> {
-        let start = Instant::now();
         let tx_hashes: Vec<_> = txs.iter().map(hex::encode).collect();
+        self.remove_encoded_txs(&tx_hashes).await
+    }
+
+    /// Removes the transactions with the given hex-encoded hashes.
+    pub(crate) async fn remove_encoded_txs(&mut self, tx_hashes: &[String]) -> QueryResult<()> {
+        let start = Instant::now();
 
         sqlx::query!(
             "DELETE FROM mempool_txs
             WHERE tx_hash = ANY($1)",
-            &tx_hashes
+            tx_hashes
+        )
+        .execute(self.0.conn())
+        .await?;
+        sqlx::query!(
+            "DELETE FROM private_mempool_txs WHERE tx_hash = ANY($1)",
+            tx_hashes
         )
         .execute(self.0.conn())
         .await?;
@@ -321,6 +342,186 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(contains)
     }
 
+    /// Stores the idempotency key passed by the account along with the submitted transaction.
+    /// If the key is already used, the stored transaction hash is left intact.
+    pub async fn store_idempotency_key(
+        &mut self,
+        account_id: AccountId,
+        idempotency_key: &str,
+        tx_hash: TxHash,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            "INSERT INTO tx_idempotency_keys (account_id, idempotency_key, tx_hash)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (account_id, idempotency_key) DO NOTHING",
+            i64::from(*account_id),
+            idempotency_key,
+            tx_hash.as_ref(),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "store_idempotency_key");
+        Ok(())
+    }
+
+    /// Returns the hash of the transaction submitted by the account with the given idempotency key.
+    pub async fn get_tx_by_idempotency_key(
+        &mut self,
+        account_id: AccountId,
+        idempotency_key: &str,
+    ) -> QueryResult<Option<TxHash>> {
+        let start = Instant::now();
+
+        let tx_hash = sqlx::query!(
+            "SELECT tx_hash FROM tx_idempotency_keys
+            WHERE account_id = $1 AND idempotency_key = $2",
+            i64::from(*account_id),
+            idempotency_key,
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| {
+            TxHash::from_slice(&record.tx_hash).expect("Incorrect tx hash in the database")
+        });
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_tx_by_idempotency_key");
+        Ok(tx_hash)
+    }
+
+    /// Removes the idempotency keys stored earlier than `max_age` ago.
+    pub async fn remove_old_idempotency_keys(
+        &mut self,
+        max_age: chrono::Duration,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        let offset = chrono::Utc::now() - max_age;
+        sqlx::query!(
+            "DELETE FROM tx_idempotency_keys WHERE created_at < $1",
+            offset
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "remove_old_idempotency_keys");
+        Ok(())
+    }
+
+    /// Stores the onchain `ChangePubKey` transaction to be submitted once its auth fact
+    /// is set on L1. Storing the same transaction again has no effect.
+    pub async fn enqueue_onchain_auth_tx(
+        &mut self,
+        tx_hash: TxHash,
+        tx: &ChangePubKey,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        let tx_json = serde_json::to_value(tx)?;
+        sqlx::query!(
+            "INSERT INTO onchain_auth_txs (tx_hash, account_address, nonce, new_pk_hash, tx, status)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (tx_hash) DO NOTHING",
+            tx_hash.as_ref(),
+            tx.account.as_bytes(),
+            i64::from(*tx.nonce),
+            &tx.new_pk_hash.data[..],
+            tx_json,
+            ONCHAIN_AUTH_WAITING,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "enqueue_onchain_auth_tx");
+        Ok(())
+    }
+
+    /// Loads the onchain `ChangePubKey` transactions which auth facts are not found yet.
+    pub async fn load_waiting_onchain_auth_txs(&mut self) -> QueryResult<Vec<OnchainAuthTx>> {
+        let start = Instant::now();
+
+        let txs = sqlx::query_as!(
+            OnchainAuthTx,
+            "SELECT * FROM onchain_auth_txs WHERE status = $1 ORDER BY created_at",
+            ONCHAIN_AUTH_WAITING,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "load_waiting_onchain_auth_txs");
+        Ok(txs)
+    }
+
+    /// Returns the queued onchain `ChangePubKey` transaction with the given hash.
+    pub async fn get_onchain_auth_tx(
+        &mut self,
+        tx_hash: TxHash,
+    ) -> QueryResult<Option<OnchainAuthTx>> {
+        let start = Instant::now();
+
+        let tx = sqlx::query_as!(
+            OnchainAuthTx,
+            "SELECT * FROM onchain_auth_txs WHERE tx_hash = $1",
+            tx_hash.as_ref(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_onchain_auth_tx");
+        Ok(tx)
+    }
+
+    /// Checks whether the account has the onchain `ChangePubKey` transaction with the given nonce
+    /// waiting for the auth fact.
+    pub async fn has_waiting_onchain_auth_tx(
+        &mut self,
+        address: Address,
+        nonce: Nonce,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+
+        let exists = sqlx::query!(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM onchain_auth_txs
+                WHERE account_address = $1 AND nonce = $2 AND status = $3
+            ) as "exists!""#,
+            address.as_bytes(),
+            i64::from(*nonce),
+            ONCHAIN_AUTH_WAITING,
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .exists;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "has_waiting_onchain_auth_tx");
+        Ok(exists)
+    }
+
+    /// Updates the status of the queued onchain `ChangePubKey` transaction.
+    pub async fn update_onchain_auth_tx_status(
+        &mut self,
+        tx_hash: TxHash,
+        status: &str,
+        fail_reason: Option<String>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            "UPDATE onchain_auth_txs SET status = $2, fail_reason = $3, updated_at = now()
+            WHERE tx_hash = $1",
+            tx_hash.as_ref(),
+            status,
+            fail_reason,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "update_onchain_auth_tx_status");
+        Ok(())
+    }
+
     /// Returns zkSync transaction with the given hash.
     pub async fn get_tx(&mut self, tx_hash: &[u8]) -> QueryResult<Option<SignedZkSyncTx>> {
         let start = Instant::now();
@@ -564,6 +765,26 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(ops.into_iter().map(|op| op.into()).collect())
     }
 
+    /// Loads the Ethereum transactions hashes and the confirmation status of the priority operations
+    /// with the given serial ids stored in mempool.
+    pub async fn load_priority_ops_hashes(
+        &mut self,
+        serial_ids: &[SerialId],
+    ) -> QueryResult<Vec<MempoolPriorityOpHash>> {
+        let start = Instant::now();
+        let serial_ids: Vec<_> = serial_ids.iter().map(|id| *id as i64).collect();
+        let hashes = sqlx::query_as!(
+            MempoolPriorityOpHash,
+            "SELECT serial_id, eth_hash, confirmed FROM mempool_priority_operations WHERE serial_id = ANY($1)",
+            &serial_ids
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "schema" => "mempool", "method" => "load_priority_ops_hashes");
+        Ok(hashes)
+    }
+
     pub async fn remove_priority_ops_from_mempool(&mut self, ids: &[u64]) -> QueryResult<()> {
         let ids: Vec<_> = ids.iter().map(|v| *v as i64).collect();
         sqlx::query!(
@@ -602,7 +823,9 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                 FROM mempool_txs
                 INNER JOIN txs_batches_hashes
                 ON txs_batches_hashes.batch_id = mempool_txs.batch_id
-                WHERE batch_hash = $1
+                WHERE batch_hash = $1 AND NOT EXISTS (
+                    SELECT 1 FROM private_mempool_txs WHERE private_mempool_txs.tx_hash = mempool_txs.tx_hash
+                )
                 ORDER BY id ASC
             "#,
             batch_hash.as_ref()
@@ -56,6 +56,21 @@ pub(crate) struct QueuedBatchTx {
     pub created_at: DateTime<Utc>,
 }
 
+/// Onchain `ChangePubKey` transaction waiting for the auth fact to be set on L1.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct OnchainAuthTx {
+    pub tx_hash: Vec<u8>,
+    pub account_address: Vec<u8>,
+    pub nonce: i64,
+    pub new_pk_hash: Vec<u8>,
+    pub tx: serde_json::Value,
+    /// One of `waiting`, `released` or `failed`.
+    pub status: String,
+    pub fail_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 #[derive(Debug, FromRow)]
 pub(crate) struct MempoolPriorityOp {
     pub serial_id: i64,
@@ -70,6 +85,14 @@ pub(crate) struct MempoolPriorityOp {
     pub deadline_block: i64,
 }
 
+/// Ethereum transaction and confirmation status of the priority operation stored in mempool.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct MempoolPriorityOpHash {
+    pub serial_id: i64,
+    pub eth_hash: Vec<u8>,
+    pub confirmed: bool,
+}
+
 impl From<MempoolPriorityOp> for PriorityOp {
     fn from(value: MempoolPriorityOp) -> Self {
         Self {
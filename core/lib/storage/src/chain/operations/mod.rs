@@ -1,7 +1,8 @@
 // Built-in deps
+use std::collections::HashMap;
 use std::time::Instant;
 // External imports
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 // Workspace imports
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
@@ -10,12 +11,14 @@ use zksync_types::{
 };
 // Local imports
 use self::records::{
-    NewExecutedPriorityOperation, NewExecutedTransaction, StoredAggregatedOperation,
-    StoredCompleteWithdrawalsTransaction, StoredExecutedPriorityOperation, StoredPendingWithdrawal,
+    NewExecutedOperation, NewExecutedPriorityOperation, NewExecutedTransaction,
+    StoredAggregatedOperation, StoredCompleteWithdrawalsTransaction,
+    StoredExecutedPriorityOperation, StoredPendingWithdrawal,
 };
 use crate::chain::operations::records::StoredExecutedTransaction;
 use crate::chain::operations_ext::OperationsExtSchema;
 use crate::ethereum::EthereumSchema;
+use crate::withdrawal_eth_txs::WithdrawalEthTxsSchema;
 use crate::{chain::mempool::MempoolSchema, QueryResult, StorageProcessor};
 
 pub mod records;
@@ -168,104 +171,198 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         &mut self,
         operation: NewExecutedTransaction,
     ) -> QueryResult<()> {
+        self.store_executed_txs(vec![operation]).await
+    }
+
+    /// Stores the executed transactions in the database, see `store_executed_operations`.
+    pub(crate) async fn store_executed_txs(
+        &mut self,
+        operations: Vec<NewExecutedTransaction>,
+    ) -> QueryResult<()> {
+        self.store_executed_operations(
+            operations
+                .into_iter()
+                .map(NewExecutedOperation::Tx)
+                .collect(),
+        )
+        .await
+    }
+
+    /// Stores the executed operations in the database with the bulk inserts.
+    ///
+    /// The operations must be passed in the order they were executed in: the sequence numbers
+    /// ordering the account history are reserved at once and assigned in this order, regardless
+    /// of the operation type and its success.
+    pub(crate) async fn store_executed_operations(
+        &mut self,
+        operations: Vec<NewExecutedOperation>,
+    ) -> QueryResult<()> {
+        if operations.is_empty() {
+            return Ok(());
+        }
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
 
+        let sequence_numbers = OperationsSchema(&mut transaction)
+            .reserve_sequence_numbers(operations.len())
+            .await?;
+        let mut txs = Vec::new();
+        let mut priority_ops = Vec::new();
+        for (operation, seq_no) in operations.into_iter().zip(sequence_numbers) {
+            match operation {
+                NewExecutedOperation::Tx(tx) => txs.push((tx, seq_no)),
+                NewExecutedOperation::PriorityOp(op) => priority_ops.push((op, seq_no)),
+            }
+        }
+        OperationsSchema(&mut transaction)
+            .insert_executed_txs(txs)
+            .await?;
+        OperationsSchema(&mut transaction)
+            .insert_executed_priority_ops(priority_ops)
+            .await?;
+
+        transaction.commit().await?;
+        metrics::histogram!(
+            "sql.chain.operations.store_executed_operations",
+            start.elapsed()
+        );
+        Ok(())
+    }
+
+    /// Takes the given number of values from the sequence of the executed operations,
+    /// in ascending order.
+    async fn reserve_sequence_numbers(&mut self, count: usize) -> QueryResult<Vec<i64>> {
+        let mut sequence_numbers: Vec<i64> = sqlx::query!(
+            r#"SELECT nextval('executed_operations_seq_number') AS "sequence_number!" FROM generate_series(1, $1)"#,
+            count as i64
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|row| row.sequence_number)
+        .collect();
+        sequence_numbers.sort_unstable();
+        Ok(sequence_numbers)
+    }
+
+    /// Inserts the executed transactions with their sequence numbers.
+    ///
+    /// Successful transactions replace the stored ones with the same hash, the failed ones
+    /// are skipped if the transaction is already stored.
+    async fn insert_executed_txs(
+        &mut self,
+        operations: Vec<(NewExecutedTransaction, i64)>,
+    ) -> QueryResult<()> {
+        if operations.is_empty() {
+            return Ok(());
+        }
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let tx_hashes: Vec<_> = operations
+            .iter()
+            .map(|(operation, _)| hex::encode(&operation.tx_hash))
+            .collect();
         MempoolSchema(&mut transaction)
-            .remove_tx(&operation.tx_hash)
+            .remove_encoded_txs(&tx_hashes)
             .await?;
 
-        let sequence_number: Option<i64> = if operation.success {
-            // If transaction succeed, it should replace the stored tx with the same hash.
-            // The situation when a duplicate tx is stored in the database may exist only if has
-            // failed previously.
-            // Possible scenario: user had no enough funds for transfer, then deposited some and
-            // sent the same transfer again.
-            sqlx::query!(
-                "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
-                ON CONFLICT (tx_hash)
-                DO UPDATE
-                SET block_number = $1, block_index = $2, tx = $3, operation = $4, tx_hash = $5, from_account = $6, to_account = $7, success = $8, fail_reason = $9, primary_account_address = $10, nonce = $11, created_at = $12, eth_sign_data = $13, batch_id = $14
-                RETURNING sequence_number
-                ",
-                operation.block_number,
-                operation.block_index,
-                operation.tx,
-                operation.operation,
-                operation.tx_hash,
-                operation.from_account,
-                operation.to_account,
-                operation.success,
-                operation.fail_reason,
-                operation.primary_account_address,
-                operation.nonce,
-                operation.created_at,
-                operation.eth_sign_data,
-                operation.batch_id,
-            )
-            .fetch_optional(transaction.conn())
-            .await?.map(|a| a.sequence_number).flatten()
-        } else {
-            // If transaction failed, we do nothing on conflict.
+        // If transaction succeed, it should replace the stored tx with the same hash, keeping
+        // its sequence number.
+        // The situation when a duplicate tx is stored in the database may exist only if has
+        // failed previously.
+        // Possible scenario: user had no enough funds for transfer, then deposited some and
+        // sent the same transfer again.
+        let (successful, failed): (Vec<_>, Vec<_>) = operations
+            .iter()
+            .partition(|(operation, _)| operation.success);
+
+        let columns = ExecutedTxColumns::new(&successful);
+        let mut sequence_numbers: HashMap<Vec<u8>, i64> = sqlx::query!(
+            "
+            INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id, sequence_number)
+            SELECT * FROM UNNEST ($1::bigint[], $2::integer[], $3::jsonb[], $4::jsonb[], $5::bytea[], $6::bytea[], $7::bytea[], $8::boolean[], $9::text[], $10::bytea[], $11::bigint[], $12::timestamptz[], $13::jsonb[], $14::bigint[], $15::bigint[])
+            ON CONFLICT (tx_hash)
+            DO UPDATE
+            SET block_number = EXCLUDED.block_number, block_index = EXCLUDED.block_index, tx = EXCLUDED.tx, operation = EXCLUDED.operation, from_account = EXCLUDED.from_account, to_account = EXCLUDED.to_account, success = EXCLUDED.success, fail_reason = EXCLUDED.fail_reason, primary_account_address = EXCLUDED.primary_account_address, nonce = EXCLUDED.nonce, created_at = EXCLUDED.created_at, eth_sign_data = EXCLUDED.eth_sign_data, batch_id = EXCLUDED.batch_id
+            RETURNING tx_hash, sequence_number
+            ",
+            &columns.block_number,
+            &columns.block_index as &[Option<i32>],
+            &columns.tx,
+            &columns.operation,
+            &columns.tx_hash,
+            &columns.from_account,
+            &columns.to_account as &[Option<Vec<u8>>],
+            &columns.success,
+            &columns.fail_reason as &[Option<String>],
+            &columns.primary_account_address,
+            &columns.nonce,
+            &columns.created_at,
+            &columns.eth_sign_data as &[Option<serde_json::Value>],
+            &columns.batch_id as &[Option<i64>],
+            &columns.sequence_number,
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .filter_map(|row| Some((row.tx_hash, row.sequence_number?)))
+        .collect();
+
+        // If transaction failed, we do nothing on conflict.
+        let columns = ExecutedTxColumns::new(&failed);
+        sequence_numbers.extend(
             sqlx::query!(
                 "
-                INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id, sequence_number)
+                SELECT * FROM UNNEST ($1::bigint[], $2::integer[], $3::jsonb[], $4::jsonb[], $5::bytea[], $6::bytea[], $7::bytea[], $8::boolean[], $9::text[], $10::bytea[], $11::bigint[], $12::timestamptz[], $13::jsonb[], $14::bigint[], $15::bigint[])
                 ON CONFLICT (tx_hash)
                 DO NOTHING
-                RETURNING sequence_number
+                RETURNING tx_hash, sequence_number
                 ",
-                operation.block_number,
-                operation.block_index,
-                operation.tx,
-                operation.operation,
-                operation.tx_hash,
-                operation.from_account,
-                operation.to_account,
-                operation.success,
-                operation.fail_reason,
-                operation.primary_account_address,
-                operation.nonce,
-                operation.created_at,
-                operation.eth_sign_data,
-                operation.batch_id,
+                &columns.block_number,
+                &columns.block_index as &[Option<i32>],
+                &columns.tx,
+                &columns.operation,
+                &columns.tx_hash,
+                &columns.from_account,
+                &columns.to_account as &[Option<Vec<u8>>],
+                &columns.success,
+                &columns.fail_reason as &[Option<String>],
+                &columns.primary_account_address,
+                &columns.nonce,
+                &columns.created_at,
+                &columns.eth_sign_data as &[Option<serde_json::Value>],
+                &columns.batch_id as &[Option<i64>],
+                &columns.sequence_number,
             )
-            .fetch_optional(transaction.conn())
-            .await?.map(|a| a.sequence_number).flatten()
-        };
-        if let Some(seq_no) = sequence_number {
-            let mut addresses = Vec::new();
-            let mut tokens = Vec::new();
-            for address in operation.affected_accounts {
-                for token in operation.used_tokens.iter() {
-                    addresses.push(address.clone());
-                    tokens.push(*token);
+            .fetch_all(transaction.conn())
+            .await?
+            .into_iter()
+            .filter_map(|row| Some((row.tx_hash, row.sequence_number?))),
+        );
+
+        let mut filters = TxFilterColumns::default();
+        for (operation, _) in &operations {
+            if let Some(&seq_no) = sequence_numbers.get(&operation.tx_hash) {
+                for address in &operation.affected_accounts {
+                    for token in &operation.used_tokens {
+                        filters.push(address, *token, &operation.tx_hash, seq_no);
+                    }
                 }
             }
-            sqlx::query!(
-                "
-                INSERT INTO tx_filters (address, token, tx_hash, sequence_number, is_priority)
-                SELECT u.address, u.token, $3, $4, false
-                    FROM UNNEST ($1::bytea[], $2::integer[])
-                    AS u(address, token)
-                ON CONFLICT ON CONSTRAINT tx_filters_pkey DO NOTHING
-                ",
-                &addresses,
-                &tokens,
-                &operation.tx_hash,
-                &seq_no
-            )
-            .execute(transaction.conn())
-            .await?;
         }
+        filters.store(&mut transaction, false).await?;
+
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.operations.store_executed_tx", start.elapsed());
-        // It's almost impossible situation, but it could be triggered in tests
-        let tx_duration = (Utc::now() - operation.created_at)
-            .to_std()
-            .unwrap_or_default();
-        metrics::histogram!("process_tx", tx_duration, "stage" => "execute");
+        metrics::histogram!("sql.chain.operations.store_executed_txs", start.elapsed());
+        for (operation, _) in &operations {
+            // It's almost impossible situation, but it could be triggered in tests
+            let tx_duration = (Utc::now() - operation.created_at)
+                .to_std()
+                .unwrap_or_default();
+            metrics::histogram!("process_tx", tx_duration, "stage" => "execute");
+        }
         Ok(())
     }
 
@@ -319,61 +416,115 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         &mut self,
         operation: NewExecutedPriorityOperation,
     ) -> QueryResult<()> {
+        self.store_executed_priority_ops(vec![operation]).await
+    }
+
+    /// Stores the executed priority operations in the database, see `store_executed_operations`.
+    pub(crate) async fn store_executed_priority_ops(
+        &mut self,
+        operations: Vec<NewExecutedPriorityOperation>,
+    ) -> QueryResult<()> {
+        self.store_executed_operations(
+            operations
+                .into_iter()
+                .map(NewExecutedOperation::PriorityOp)
+                .collect(),
+        )
+        .await
+    }
+
+    /// Inserts the executed priority operations with their sequence numbers, skipping
+    /// the already stored ones.
+    async fn insert_executed_priority_ops(
+        &mut self,
+        operations: Vec<(NewExecutedPriorityOperation, i64)>,
+    ) -> QueryResult<()> {
+        if operations.is_empty() {
+            return Ok(());
+        }
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
 
+        let serial_ids: Vec<_> = operations
+            .iter()
+            .map(|(operation, _)| operation.priority_op_serialid as u64)
+            .collect();
         MempoolSchema(&mut transaction)
-            .remove_priority_op_from_mempool(operation.priority_op_serialid)
+            .remove_priority_ops_from_mempool(&serial_ids)
             .await?;
 
-        let sequence_number: Option<i64>= sqlx::query!(
-            "INSERT INTO executed_priority_operations (block_number, block_index, operation, from_account, to_account,
-                priority_op_serialid, deadline_block, eth_hash, eth_block, created_at, eth_block_index, tx_hash)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        let mut block_number = Vec::with_capacity(operations.len());
+        let mut block_index = Vec::with_capacity(operations.len());
+        let mut operation_data = Vec::with_capacity(operations.len());
+        let mut from_account = Vec::with_capacity(operations.len());
+        let mut to_account = Vec::with_capacity(operations.len());
+        let mut priority_op_serialid = Vec::with_capacity(operations.len());
+        let mut deadline_block = Vec::with_capacity(operations.len());
+        let mut eth_hash = Vec::with_capacity(operations.len());
+        let mut eth_block = Vec::with_capacity(operations.len());
+        let mut created_at = Vec::with_capacity(operations.len());
+        let mut eth_block_index = Vec::with_capacity(operations.len());
+        let mut tx_hash = Vec::with_capacity(operations.len());
+        let mut sequence_number = Vec::with_capacity(operations.len());
+        for (operation, seq_no) in &operations {
+            block_number.push(operation.block_number);
+            block_index.push(operation.block_index);
+            operation_data.push(operation.operation.clone());
+            from_account.push(operation.from_account.clone());
+            to_account.push(operation.to_account.clone());
+            priority_op_serialid.push(operation.priority_op_serialid);
+            deadline_block.push(operation.deadline_block);
+            eth_hash.push(operation.eth_hash.clone());
+            eth_block.push(operation.eth_block);
+            created_at.push(operation.created_at);
+            eth_block_index.push(operation.eth_block_index);
+            tx_hash.push(operation.tx_hash.clone());
+            sequence_number.push(*seq_no);
+        }
+
+        let sequence_numbers: HashMap<i64, i64> = sqlx::query!(
+            "
+            INSERT INTO executed_priority_operations (block_number, block_index, operation, from_account, to_account,
+                priority_op_serialid, deadline_block, eth_hash, eth_block, created_at, eth_block_index, tx_hash, sequence_number)
+            SELECT * FROM UNNEST ($1::bigint[], $2::integer[], $3::jsonb[], $4::bytea[], $5::bytea[],
+                $6::bigint[], $7::bigint[], $8::bytea[], $9::bigint[], $10::timestamptz[], $11::bigint[], $12::bytea[], $13::bigint[])
             ON CONFLICT (priority_op_serialid)
             DO NOTHING
-            RETURNING sequence_number
+            RETURNING priority_op_serialid, sequence_number
             ",
-            operation.block_number,
-            operation.block_index,
-            operation.operation,
-            operation.from_account,
-            operation.to_account,
-            operation.priority_op_serialid,
-            operation.deadline_block,
-            operation.eth_hash,
-            operation.eth_block,
-            operation.created_at,
-            operation.eth_block_index,
-            operation.tx_hash,
+            &block_number,
+            &block_index,
+            &operation_data,
+            &from_account,
+            &to_account,
+            &priority_op_serialid,
+            &deadline_block,
+            &eth_hash,
+            &eth_block,
+            &created_at,
+            &eth_block_index as &[Option<i64>],
+            &tx_hash,
+            &sequence_number,
         )
-        .fetch_optional(transaction.conn())
-        .await?.map(|a| a.sequence_number).flatten();
-
-        let mut tokens = Vec::new();
-        tokens.resize(operation.affected_accounts.len(), operation.token);
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .filter_map(|row| Some((row.priority_op_serialid, row.sequence_number?)))
+        .collect();
 
-        if let Some(seq_no) = sequence_number {
-            sqlx::query!(
-                "
-                INSERT INTO tx_filters (address, token, tx_hash, sequence_number, is_priority)
-                SELECT u.address, u.token, $3, $4, true
-                    FROM UNNEST ($1::bytea[], $2::integer[])
-                    AS u(address, token)
-                ON CONFLICT ON CONSTRAINT tx_filters_pkey DO NOTHING
-                ",
-                &operation.affected_accounts,
-                &tokens,
-                &operation.tx_hash,
-                seq_no
-            )
-            .execute(transaction.conn())
-            .await?;
+        let mut filters = TxFilterColumns::default();
+        for (operation, _) in &operations {
+            if let Some(&seq_no) = sequence_numbers.get(&operation.priority_op_serialid) {
+                for address in &operation.affected_accounts {
+                    filters.push(address, operation.token, &operation.tx_hash, seq_no);
+                }
+            }
         }
+        filters.store(&mut transaction, true).await?;
 
         transaction.commit().await?;
         metrics::histogram!(
-            "sql.chain.operations.store_executed_priority_op",
+            "sql.chain.operations.store_executed_priority_ops",
             start.elapsed()
         );
         Ok(())
@@ -480,6 +631,18 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
     ) -> QueryResult<Option<H256>> {
         let start = Instant::now();
 
+        // Withdrawals of the executed blocks are recorded by the withdrawals tracker.
+        let recorded_eth_tx = WithdrawalEthTxsSchema(self.0)
+            .get_withdrawal_eth_tx(withdrawal_hash)
+            .await?;
+        if recorded_eth_tx.is_some() {
+            metrics::histogram!(
+                "sql.chain.operations.eth_tx_for_withdrawal",
+                start.elapsed()
+            );
+            return Ok(recorded_eth_tx);
+        }
+
         // For a long time, the operation `CompleteWithdrawals` was used to withdraw funds,
         // now it is used `ExecuteBlocks`, so we should check each of the possible options.
         let eth_withdraw_tx_for_execute_block = self
@@ -746,3 +909,90 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         Ok(())
     }
 }
+
+/// Columns of the executed transactions for the bulk insert.
+#[derive(Debug, Default)]
+struct ExecutedTxColumns {
+    block_number: Vec<i64>,
+    block_index: Vec<Option<i32>>,
+    tx: Vec<serde_json::Value>,
+    operation: Vec<serde_json::Value>,
+    tx_hash: Vec<Vec<u8>>,
+    from_account: Vec<Vec<u8>>,
+    to_account: Vec<Option<Vec<u8>>>,
+    success: Vec<bool>,
+    fail_reason: Vec<Option<String>>,
+    primary_account_address: Vec<Vec<u8>>,
+    nonce: Vec<i64>,
+    created_at: Vec<DateTime<Utc>>,
+    eth_sign_data: Vec<Option<serde_json::Value>>,
+    batch_id: Vec<Option<i64>>,
+    sequence_number: Vec<i64>,
+}
+
+impl ExecutedTxColumns {
+    fn new(operations: &[&(NewExecutedTransaction, i64)]) -> Self {
+        let mut columns = Self::default();
+        for (operation, seq_no) in operations {
+            columns.block_number.push(operation.block_number);
+            columns.block_index.push(operation.block_index);
+            columns.tx.push(operation.tx.clone());
+            columns.operation.push(operation.operation.clone());
+            columns.tx_hash.push(operation.tx_hash.clone());
+            columns.from_account.push(operation.from_account.clone());
+            columns.to_account.push(operation.to_account.clone());
+            columns.success.push(operation.success);
+            columns.fail_reason.push(operation.fail_reason.clone());
+            columns
+                .primary_account_address
+                .push(operation.primary_account_address.clone());
+            columns.nonce.push(operation.nonce);
+            columns.created_at.push(operation.created_at);
+            columns.eth_sign_data.push(operation.eth_sign_data.clone());
+            columns.batch_id.push(operation.batch_id);
+            columns.sequence_number.push(*seq_no);
+        }
+        columns
+    }
+}
+
+/// Rows of the `tx_filters` table for the bulk insert.
+#[derive(Debug, Default)]
+struct TxFilterColumns {
+    address: Vec<Vec<u8>>,
+    token: Vec<i32>,
+    tx_hash: Vec<Vec<u8>>,
+    sequence_number: Vec<i64>,
+}
+
+impl TxFilterColumns {
+    fn push(&mut self, address: &[u8], token: i32, tx_hash: &[u8], sequence_number: i64) {
+        self.address.push(address.to_vec());
+        self.token.push(token);
+        self.tx_hash.push(tx_hash.to_vec());
+        self.sequence_number.push(sequence_number);
+    }
+
+    async fn store(self, storage: &mut StorageProcessor<'_>, is_priority: bool) -> QueryResult<()> {
+        if self.address.is_empty() {
+            return Ok(());
+        }
+        sqlx::query!(
+            "
+            INSERT INTO tx_filters (address, token, tx_hash, sequence_number, is_priority)
+            SELECT u.address, u.token, u.tx_hash, u.sequence_number, $5
+                FROM UNNEST ($1::bytea[], $2::integer[], $3::bytea[], $4::bigint[])
+                AS u(address, token, tx_hash, sequence_number)
+            ON CONFLICT ON CONSTRAINT tx_filters_pkey DO NOTHING
+            ",
+            &self.address,
+            &self.token,
+            &self.tx_hash,
+            &self.sequence_number,
+            is_priority
+        )
+        .execute(storage.conn())
+        .await?;
+        Ok(())
+    }
+}
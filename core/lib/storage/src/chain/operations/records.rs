@@ -103,6 +103,13 @@ pub(crate) struct NewExecutedTransaction {
     pub used_tokens: Vec<i32>,
 }
 
+/// Executed operation of the block, either an L2 transaction or a priority operation.
+#[derive(Debug, Clone)]
+pub(crate) enum NewExecutedOperation {
+    Tx(NewExecutedTransaction),
+    PriorityOp(NewExecutedPriorityOperation),
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct StoredPendingWithdrawal {
     pub id: i64,
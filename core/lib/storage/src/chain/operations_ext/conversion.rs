@@ -16,6 +16,7 @@ impl StorageTxReceipt {
     pub(super) fn receipt_from_storage_receipt(
         receipt: StorageTxReceipt,
         is_block_finalized: Option<bool>,
+        eth_tx_hash: Option<H256>,
     ) -> Receipt {
         if receipt.block_number.is_some() {
             let status = if receipt.success.unwrap() {
@@ -44,6 +45,7 @@ impl StorageTxReceipt {
                         .block_number
                         .map(|number| BlockNumber(number as u32)),
                     fail_reason: receipt.fail_reason,
+                    eth_tx_hash,
                 })
             }
         } else {
@@ -52,6 +54,7 @@ impl StorageTxReceipt {
                 tx_hash: TxHash::from_slice(&receipt.tx_hash).unwrap(),
                 rollup_block: None,
                 fail_reason: None,
+                eth_tx_hash: None,
             })
         }
     }
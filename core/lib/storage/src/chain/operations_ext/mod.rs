@@ -126,6 +126,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         Null::bigint as priority_op_serialid
                     FROM mempool_txs
                     WHERE tx_hash = $2
+                        AND NOT EXISTS (SELECT 1 FROM private_mempool_txs WHERE tx_hash = $2)
                 ),
                 everything AS (
                     SELECT * FROM transaction
@@ -152,10 +153,20 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         let result = if let Some(receipt) = receipt {
             let is_block_finalized =
                 is_block_finalized(&mut transaction, receipt.block_number).await?;
+            // Only the withdrawals of the executed blocks have the L1 transactions.
+            let eth_tx_hash = if is_block_finalized == Some(true) && receipt.eth_block.is_none() {
+                transaction
+                    .withdrawal_eth_txs_schema()
+                    .get_withdrawal_eth_tx(&TxHash::from_slice(&receipt.tx_hash).unwrap())
+                    .await?
+            } else {
+                None
+            };
 
             Some(StorageTxReceipt::receipt_from_storage_receipt(
                 receipt,
                 is_block_finalized,
+                eth_tx_hash,
             ))
         } else {
             None
@@ -301,6 +312,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         eth_sign_data
                     FROM mempool_txs
                     WHERE tx_hash = $2
+                        AND NOT EXISTS (SELECT 1 FROM private_mempool_txs WHERE tx_hash = $2)
                 ),
                 everything AS (
                     SELECT * FROM transaction
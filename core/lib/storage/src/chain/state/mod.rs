@@ -54,10 +54,14 @@ impl<'a, 'c> StateSchema<'a, 'c> {
 
         // Simply go through the every account update, and update the corresponding table.
         // This may look scary, but every match arm is very simple by its nature.
+        // The most frequent updates are collected and stored with the bulk inserts afterwards.
         let update_order_ids =
             first_update_order_id..first_update_order_id + accounts_updated.len();
 
         let mut nonce_updates = HashMap::with_capacity(accounts_updated.len());
+        let mut account_creates = AccountCreateColumns::default();
+        let mut balance_updates = BalanceUpdateColumns::default();
+        let mut pubkey_updates = PubKeyUpdateColumns::default();
 
         for (update_order_id, (id, upd)) in update_order_ids.zip(accounts_updated.iter()) {
             vlog::debug!(
@@ -67,40 +71,17 @@ impl<'a, 'c> StateSchema<'a, 'c> {
             );
 
             match *upd {
-                AccountUpdate::Create { ref address, nonce } => {
+                AccountUpdate::Create { ref address, nonce }
+                | AccountUpdate::Delete { ref address, nonce } => {
                     let account_id = i64::from(**id);
-                    let is_create = true;
-                    let block_number = i64::from(*block_number);
-                    let address = address.as_bytes().to_vec();
                     let nonce = i64::from(*nonce);
-                    let update_order_id = update_order_id as i32;
-                    sqlx::query!(
-                        r#"
-                        INSERT INTO account_creates ( account_id, is_create, block_number, address, nonce, update_order_id )
-                        VALUES ( $1, $2, $3, $4, $5, $6 )
-                        "#,
-                        account_id, is_create, block_number, address, nonce, update_order_id
-                    )
-                    .execute(transaction.conn())
-                    .await?;
-                    nonce_updates.insert(account_id, nonce);
-                }
-                AccountUpdate::Delete { ref address, nonce } => {
-                    let account_id = i64::from(**id);
-                    let is_create = false;
-                    let block_number = i64::from(*block_number);
-                    let address = address.as_bytes().to_vec();
-                    let nonce = i64::from(*nonce);
-                    let update_order_id = update_order_id as i32;
-                    sqlx::query!(
-                        r#"
-                        INSERT INTO account_creates ( account_id, is_create, block_number, address, nonce, update_order_id )
-                        VALUES ( $1, $2, $3, $4, $5, $6 )
-                        "#,
-                        account_id, is_create, block_number, address, nonce, update_order_id
-                    )
-                    .execute(transaction.conn())
-                    .await?;
+                    account_creates.account_id.push(account_id);
+                    account_creates
+                        .is_create
+                        .push(matches!(upd, AccountUpdate::Create { .. }));
+                    account_creates.address.push(address.as_bytes().to_vec());
+                    account_creates.nonce.push(nonce);
+                    account_creates.update_order_id.push(update_order_id as i32);
                     nonce_updates.insert(account_id, nonce);
                 }
                 AccountUpdate::UpdateBalance {
@@ -109,30 +90,18 @@ impl<'a, 'c> StateSchema<'a, 'c> {
                     new_nonce,
                 } => {
                     let account_id = i64::from(**id);
-                    let block_number = i64::from(*block_number);
-                    let coin_id = *token as i32;
-                    let old_balance = BigDecimal::from(BigInt::from(old_balance.clone()));
-                    let new_balance = BigDecimal::from(BigInt::from(new_balance.clone()));
-                    let old_nonce = i64::from(*old_nonce);
                     let new_nonce = i64::from(*new_nonce);
-                    let update_order_id = update_order_id as i32;
-
-                    sqlx::query!(
-                        r#"
-                        INSERT INTO account_balance_updates ( account_id, block_number, coin_id, old_balance, new_balance, old_nonce, new_nonce, update_order_id )
-                        VALUES ( $1, $2, $3, $4, $5, $6, $7, $8 )
-                        "#,
-                        account_id,
-                        block_number,
-                        coin_id,
-                        old_balance,
-                        new_balance,
-                        old_nonce,
-                        new_nonce,
-                        update_order_id,
-                    )
-                    .execute(transaction.conn())
-                    .await?;
+                    balance_updates.account_id.push(account_id);
+                    balance_updates.coin_id.push(*token as i32);
+                    balance_updates
+                        .old_balance
+                        .push(BigDecimal::from(BigInt::from(old_balance.clone())));
+                    balance_updates
+                        .new_balance
+                        .push(BigDecimal::from(BigInt::from(new_balance.clone())));
+                    balance_updates.old_nonce.push(i64::from(*old_nonce));
+                    balance_updates.new_nonce.push(new_nonce);
+                    balance_updates.update_order_id.push(update_order_id as i32);
                     nonce_updates.insert(account_id, new_nonce);
                 }
                 AccountUpdate::ChangePubKeyHash {
@@ -141,22 +110,18 @@ impl<'a, 'c> StateSchema<'a, 'c> {
                     old_nonce,
                     new_nonce,
                 } => {
-                    let update_order_id = update_order_id as i32;
                     let account_id = i64::from(**id);
-                    let block_number = i64::from(*block_number);
-                    let old_pubkey_hash = old_pub_key_hash.data.to_vec();
-                    let new_pubkey_hash = new_pub_key_hash.data.to_vec();
-                    let old_nonce = i64::from(*old_nonce);
                     let new_nonce = i64::from(*new_nonce);
-                    sqlx::query!(
-                        r#"
-                        INSERT INTO account_pubkey_updates ( update_order_id, account_id, block_number, old_pubkey_hash, new_pubkey_hash, old_nonce, new_nonce )
-                        VALUES ( $1, $2, $3, $4, $5, $6, $7 )
-                        "#,
-                        update_order_id, account_id, block_number, old_pubkey_hash, new_pubkey_hash, old_nonce, new_nonce
-                    )
-                    .execute(transaction.conn())
-                    .await?;
+                    pubkey_updates.update_order_id.push(update_order_id as i32);
+                    pubkey_updates.account_id.push(account_id);
+                    pubkey_updates
+                        .old_pubkey_hash
+                        .push(old_pub_key_hash.data.to_vec());
+                    pubkey_updates
+                        .new_pubkey_hash
+                        .push(new_pub_key_hash.data.to_vec());
+                    pubkey_updates.old_nonce.push(i64::from(*old_nonce));
+                    pubkey_updates.new_nonce.push(new_nonce);
                     nonce_updates.insert(account_id, new_nonce);
                 }
                 AccountUpdate::MintNFT { ref token, nonce } => {
@@ -197,17 +162,78 @@ impl<'a, 'c> StateSchema<'a, 'c> {
             }
         }
 
-        // Update committed nonce
         let block_number = i64::from(*block_number);
-        for (account_id, nonce) in nonce_updates.iter() {
+        if !account_creates.account_id.is_empty() {
+            sqlx::query!(
+                r#"
+                INSERT INTO account_creates ( account_id, is_create, block_number, address, nonce, update_order_id )
+                SELECT u.account_id, u.is_create, $3, u.address, u.nonce, u.update_order_id
+                    FROM UNNEST ($1::bigint[], $2::boolean[], $4::bytea[], $5::bigint[], $6::integer[])
+                    AS u(account_id, is_create, address, nonce, update_order_id)
+                "#,
+                &account_creates.account_id,
+                &account_creates.is_create,
+                block_number,
+                &account_creates.address,
+                &account_creates.nonce,
+                &account_creates.update_order_id,
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        if !balance_updates.account_id.is_empty() {
+            sqlx::query!(
+                r#"
+                INSERT INTO account_balance_updates ( account_id, block_number, coin_id, old_balance, new_balance, old_nonce, new_nonce, update_order_id )
+                SELECT u.account_id, $2, u.coin_id, u.old_balance, u.new_balance, u.old_nonce, u.new_nonce, u.update_order_id
+                    FROM UNNEST ($1::bigint[], $3::integer[], $4::numeric[], $5::numeric[], $6::bigint[], $7::bigint[], $8::integer[])
+                    AS u(account_id, coin_id, old_balance, new_balance, old_nonce, new_nonce, update_order_id)
+                "#,
+                &balance_updates.account_id,
+                block_number,
+                &balance_updates.coin_id,
+                &balance_updates.old_balance,
+                &balance_updates.new_balance,
+                &balance_updates.old_nonce,
+                &balance_updates.new_nonce,
+                &balance_updates.update_order_id,
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        if !pubkey_updates.account_id.is_empty() {
+            sqlx::query!(
+                r#"
+                INSERT INTO account_pubkey_updates ( update_order_id, account_id, block_number, old_pubkey_hash, new_pubkey_hash, old_nonce, new_nonce )
+                SELECT u.update_order_id, u.account_id, $3, u.old_pubkey_hash, u.new_pubkey_hash, u.old_nonce, u.new_nonce
+                    FROM UNNEST ($1::integer[], $2::bigint[], $4::bytea[], $5::bytea[], $6::bigint[], $7::bigint[])
+                    AS u(update_order_id, account_id, old_pubkey_hash, new_pubkey_hash, old_nonce, new_nonce)
+                "#,
+                &pubkey_updates.update_order_id,
+                &pubkey_updates.account_id,
+                block_number,
+                &pubkey_updates.old_pubkey_hash,
+                &pubkey_updates.new_pubkey_hash,
+                &pubkey_updates.old_nonce,
+                &pubkey_updates.new_nonce,
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+
+        // Update committed nonce
+        if !nonce_updates.is_empty() {
+            let (account_ids, nonces): (Vec<i64>, Vec<i64>) = nonce_updates.into_iter().unzip();
             sqlx::query!(
-                "INSERT INTO committed_nonce (account_id, nonce, block_number) VALUES ($1, $2, $3) 
-                 ON CONFLICT (account_id) 
-                 DO UPDATE 
-                 SET nonce = $2, block_number = $3
+                "INSERT INTO committed_nonce (account_id, nonce, block_number)
+                 SELECT u.account_id, u.nonce, $3
+                    FROM UNNEST ($1::bigint[], $2::bigint[]) AS u(account_id, nonce)
+                 ON CONFLICT (account_id)
+                 DO UPDATE
+                 SET nonce = EXCLUDED.nonce, block_number = EXCLUDED.block_number
                  ",
-                account_id,
-                nonce,
+                &account_ids,
+                &nonces,
                 block_number
             )
             .execute(transaction.conn())
@@ -691,6 +717,89 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         result
     }
 
+    /// Loads the updates of the given accounts made in the blocks from the range
+    /// `(from_block, to_block]`, in the order they were applied.
+    pub async fn load_accounts_diff(
+        &mut self,
+        account_ids: &[AccountId],
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<AccountUpdates> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let account_ids: Vec<i64> = account_ids.iter().map(|id| i64::from(**id)).collect();
+        let (from_block, to_block) = (i64::from(*from_block), i64::from(*to_block));
+
+        let account_balance_diff = sqlx::query_as!(
+            StorageAccountUpdate,
+            "SELECT * FROM account_balance_updates
+            WHERE account_id = ANY($1) AND block_number > $2 AND block_number <= $3",
+            &account_ids,
+            from_block,
+            to_block,
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let account_creation_diff = sqlx::query_as!(
+            StorageAccountCreation,
+            "SELECT * FROM account_creates
+            WHERE account_id = ANY($1) AND block_number > $2 AND block_number <= $3",
+            &account_ids,
+            from_block,
+            to_block,
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let account_pubkey_diff = sqlx::query_as!(
+            StorageAccountPubkeyUpdate,
+            "SELECT * FROM account_pubkey_updates
+            WHERE account_id = ANY($1) AND block_number > $2 AND block_number <= $3",
+            &account_ids,
+            from_block,
+            to_block,
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let mint_nft_diffs = sqlx::query_as!(
+            StorageMintNFTUpdate,
+            "SELECT * FROM mint_nft_updates
+            WHERE creator_account_id = ANY($1) AND block_number > $2 AND block_number <= $3",
+            &account_ids,
+            from_block,
+            to_block,
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let mut account_diff = Vec::new();
+        account_diff.extend(
+            account_balance_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_creation_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_pubkey_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(mint_nft_diffs.into_iter().map(StorageAccountDiff::from));
+        account_diff.sort_by(StorageAccountDiff::cmp_order);
+
+        transaction.commit().await?;
+        metrics::histogram!("sql.chain.state.load_accounts_diff", start.elapsed());
+
+        Ok(account_diff.into_iter().map(|diff| diff.into()).collect())
+    }
+
     pub async fn get_mint_nft_update(&mut self, token_id: TokenId) -> QueryResult<Option<NFT>> {
         let start = Instant::now();
         let nft = sqlx::query_as!(
@@ -899,3 +1008,36 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         Ok(())
     }
 }
+
+/// Columns of the `account_creates` rows of the block for the bulk insert.
+#[derive(Debug, Default)]
+struct AccountCreateColumns {
+    account_id: Vec<i64>,
+    is_create: Vec<bool>,
+    address: Vec<Vec<u8>>,
+    nonce: Vec<i64>,
+    update_order_id: Vec<i32>,
+}
+
+/// Columns of the `account_balance_updates` rows of the block for the bulk insert.
+#[derive(Debug, Default)]
+struct BalanceUpdateColumns {
+    account_id: Vec<i64>,
+    coin_id: Vec<i32>,
+    old_balance: Vec<BigDecimal>,
+    new_balance: Vec<BigDecimal>,
+    old_nonce: Vec<i64>,
+    new_nonce: Vec<i64>,
+    update_order_id: Vec<i32>,
+}
+
+/// Columns of the `account_pubkey_updates` rows of the block for the bulk insert.
+#[derive(Debug, Default)]
+struct PubKeyUpdateColumns {
+    update_order_id: Vec<i32>,
+    account_id: Vec<i64>,
+    old_pubkey_hash: Vec<Vec<u8>>,
+    new_pubkey_hash: Vec<Vec<u8>>,
+    old_nonce: Vec<i64>,
+    new_nonce: Vec<i64>,
+}
@@ -0,0 +1,271 @@
+use std::cmp::max;
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_types::{BlockNumber, SequentialTxId, TokenId};
+// Local imports
+use self::records::{StorageDailyFee, StorageDailyStats, StorageDailyTxCount, StorageTokenTvl};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Auxiliary schema encapsulating the stats counting logic for the storage tables.
+#[derive(Debug)]
+pub struct StatsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> StatsSchema<'a, 'c> {
+    /// Returns the amount of blocks that don't have proofs yet.
+    pub async fn count_outstanding_proofs(&mut self, after_block: BlockNumber) -> QueryResult<u32> {
+        let start = Instant::now();
+        let count = sqlx::query!(
+            "SELECT COUNT(*) FROM executed_transactions WHERE block_number > $1",
+            i64::from(*after_block)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count
+        .unwrap_or(0);
+
+        metrics::histogram!("sql.chain.stats.count_outstanding_proofs", start.elapsed());
+        Ok(count as u32)
+    }
+
+    /// Count total transactions after tx_id, and return count and max tx_id.
+    /// It allows us to cache count of transactions and make these queries much faster
+    pub async fn count_total_transactions(
+        &mut self,
+        after_tx_id: SequentialTxId,
+    ) -> QueryResult<(u32, SequentialTxId)> {
+        let after_tx_id = *after_tx_id as i64;
+        let start = Instant::now();
+        let tx_res = sqlx::query!(
+            "SELECT COUNT(*), MAX(sequence_number) FROM executed_transactions 
+                 WHERE success = true AND sequence_number > $1",
+            after_tx_id
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        let prior_ops_res = sqlx::query!(
+            "SELECT COUNT(*), MAX(sequence_number) FROM executed_priority_operations WHERE sequence_number > $1",
+            after_tx_id
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.stats.count_total_transactions", start.elapsed());
+        Ok((
+            (tx_res.count.unwrap_or_default() + prior_ops_res.count.unwrap_or_default()) as u32,
+            SequentialTxId(max(
+                prior_ops_res.max.unwrap_or(after_tx_id),
+                tx_res.max.unwrap_or(after_tx_id),
+            ) as u64),
+        ))
+    }
+
+    /// Recomputes the aggregated statistics of the given day (in UTC) and stores them
+    /// into the summary tables. Previously aggregated values of the day are replaced,
+    /// so the method can be called repeatedly while the day is not over yet.
+    pub async fn aggregate_daily_stats(&mut self, day: NaiveDate) -> QueryResult<()> {
+        let start = Instant::now();
+        let from = DateTime::<Utc>::from_utc(day.and_hms(0, 0, 0), Utc);
+        let to = from + Duration::days(1);
+        let mut transaction = self.0.start_transaction().await?;
+
+        sqlx::query!("DELETE FROM network_stats_daily_txs WHERE day = $1", day)
+            .execute(transaction.conn())
+            .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO network_stats_daily_txs (day, tx_type, tx_count)
+            SELECT $1, txs.tx_type, COUNT(*) FROM (
+                SELECT tx->>'type' AS tx_type FROM executed_transactions
+                WHERE success = true AND created_at >= $2 AND created_at < $3
+                UNION ALL
+                SELECT operation->>'type' AS tx_type FROM executed_priority_operations
+                WHERE created_at >= $2 AND created_at < $3
+            ) txs
+            WHERE txs.tx_type IS NOT NULL
+            GROUP BY txs.tx_type
+            "#,
+            day,
+            from,
+            to
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        sqlx::query!("DELETE FROM network_stats_daily_fees WHERE day = $1", day)
+            .execute(transaction.conn())
+            .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO network_stats_daily_fees (day, token_id, amount)
+            SELECT $1, fees.token_id, SUM(fees.amount) FROM (
+                SELECT COALESCE(tx->>'feeToken', tx->>'token')::integer AS token_id,
+                    (tx->>'fee')::numeric AS amount
+                FROM executed_transactions
+                WHERE success = true AND created_at >= $2 AND created_at < $3
+                    AND tx->>'fee' IS NOT NULL
+            ) fees
+            WHERE fees.token_id IS NOT NULL
+            GROUP BY fees.token_id
+            "#,
+            day,
+            from,
+            to
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        // The block is considered final once the operation executing it is confirmed.
+        sqlx::query!(
+            r#"
+            INSERT INTO network_stats_daily (day, active_accounts, avg_finality_secs)
+            SELECT $1,
+                (
+                    SELECT COUNT(DISTINCT from_account) FROM executed_transactions
+                    WHERE success = true AND created_at >= $2 AND created_at < $3
+                ),
+                (
+                    SELECT AVG(EXTRACT(EPOCH FROM aggregate_operations.created_at - executed_transactions.created_at))::double precision
+                    FROM executed_transactions
+                        INNER JOIN execute_aggregated_blocks_binding
+                            ON execute_aggregated_blocks_binding.block_number = executed_transactions.block_number
+                        INNER JOIN aggregate_operations
+                            ON aggregate_operations.id = execute_aggregated_blocks_binding.op_id
+                    WHERE aggregate_operations.confirmed = true AND executed_transactions.success = true
+                        AND executed_transactions.created_at >= $2 AND executed_transactions.created_at < $3
+                )
+            ON CONFLICT (day) DO UPDATE
+            SET active_accounts = EXCLUDED.active_accounts, avg_finality_secs = EXCLUDED.avg_finality_secs
+            "#,
+            day,
+            from,
+            to
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+        metrics::histogram!("sql.chain.stats.aggregate_daily_stats", start.elapsed());
+        Ok(())
+    }
+
+    /// Recomputes the total value locked in the network for every fungible token.
+    pub async fn update_tvl(&mut self) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        sqlx::query!("DELETE FROM network_stats_tvl")
+            .execute(transaction.conn())
+            .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO network_stats_tvl (token_id, amount)
+            SELECT coin_id, SUM(balance) FROM balances
+            WHERE coin_id < $1
+            GROUP BY coin_id
+            "#,
+            MIN_NFT_TOKEN_ID as i32
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+        metrics::histogram!("sql.chain.stats.update_tvl", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the aggregated statistics of the days starting from `from_day`, the latest days go first.
+    pub async fn load_daily_stats(
+        &mut self,
+        from_day: NaiveDate,
+    ) -> QueryResult<Vec<StorageDailyStats>> {
+        let start = Instant::now();
+        let stats = sqlx::query_as!(
+            StorageDailyStats,
+            "SELECT * FROM network_stats_daily WHERE day >= $1 ORDER BY day DESC",
+            from_day
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.stats.load_daily_stats", start.elapsed());
+        Ok(stats)
+    }
+
+    /// Loads the numbers of transactions of every type executed during the days starting from `from_day`.
+    pub async fn load_daily_tx_counts(
+        &mut self,
+        from_day: NaiveDate,
+    ) -> QueryResult<Vec<StorageDailyTxCount>> {
+        let start = Instant::now();
+        let counts = sqlx::query_as!(
+            StorageDailyTxCount,
+            "SELECT * FROM network_stats_daily_txs WHERE day >= $1 ORDER BY day DESC, tx_type",
+            from_day
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.stats.load_daily_tx_counts", start.elapsed());
+        Ok(counts)
+    }
+
+    /// Loads the fees collected during the days starting from `from_day`.
+    pub async fn load_daily_fees(
+        &mut self,
+        from_day: NaiveDate,
+    ) -> QueryResult<Vec<StorageDailyFee>> {
+        let start = Instant::now();
+        let fees = sqlx::query_as!(
+            StorageDailyFee,
+            "SELECT * FROM network_stats_daily_fees WHERE day >= $1 ORDER BY day DESC, token_id",
+            from_day
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.stats.load_daily_fees", start.elapsed());
+        Ok(fees)
+    }
+
+    /// Loads the total amount of fees collected in every token over all the aggregated days.
+    pub async fn load_total_fees(&mut self) -> QueryResult<Vec<(TokenId, BigDecimal)>> {
+        let start = Instant::now();
+        let fees = sqlx::query!(
+            r#"
+            SELECT token_id, SUM(amount) AS "amount!" FROM network_stats_daily_fees
+            GROUP BY token_id
+            ORDER BY token_id
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|row| (TokenId(row.token_id as u32), row.amount))
+        .collect();
+
+        metrics::histogram!("sql.chain.stats.load_total_fees", start.elapsed());
+        Ok(fees)
+    }
+
+    /// Loads the last computed total value locked in the network for every token.
+    pub async fn load_tvl(&mut self) -> QueryResult<Vec<StorageTokenTvl>> {
+        let start = Instant::now();
+        let tvl = sqlx::query_as!(
+            StorageTokenTvl,
+            "SELECT * FROM network_stats_tvl ORDER BY token_id"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.stats.load_tvl", start.elapsed());
+        Ok(tvl)
+    }
+}
@@ -0,0 +1,35 @@
+// External imports
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{types::BigDecimal, FromRow};
+
+/// Aggregated statistics of the single day.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageDailyStats {
+    pub day: NaiveDate,
+    pub active_accounts: i64,
+    pub avg_finality_secs: Option<f64>,
+}
+
+/// Number of transactions of a certain type executed during the day.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageDailyTxCount {
+    pub day: NaiveDate,
+    pub tx_type: String,
+    pub tx_count: i64,
+}
+
+/// Amount of fees in a certain token collected during the day.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageDailyFee {
+    pub day: NaiveDate,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+}
+
+/// Total amount of the token locked in the network.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageTokenTvl {
+    pub token_id: i32,
+    pub amount: BigDecimal,
+    pub updated_at: DateTime<Utc>,
+}
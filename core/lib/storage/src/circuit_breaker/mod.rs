@@ -0,0 +1,62 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+// Local imports
+use self::records::CircuitBreakerTrip;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the circuit breaker shared by all the server components.
+///
+/// Any component that detects a divergence of the state (e.g. the witness generator or
+/// the prover server) trips the breaker, and the components producing and sending blocks
+/// halt until the trip is resolved by the operator.
+#[derive(Debug)]
+pub struct CircuitBreakerSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> CircuitBreakerSchema<'a, 'c> {
+    /// Trips the circuit breaker.
+    pub async fn trip(&mut self, source: &str, reason: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO circuit_breaker_trips (source, reason) VALUES ($1, $2)",
+            source,
+            reason
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.circuit_breaker.trip", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the earliest unresolved trip, if any.
+    pub async fn load_active_trip(&mut self) -> QueryResult<Option<CircuitBreakerTrip>> {
+        let start = Instant::now();
+        let trip = sqlx::query_as!(
+            CircuitBreakerTrip,
+            "SELECT * FROM circuit_breaker_trips WHERE resolved_at IS NULL ORDER BY id LIMIT 1"
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.circuit_breaker.load_active_trip", start.elapsed());
+        Ok(trip)
+    }
+
+    /// Resolves all the active trips. Returns the number of resolved trips.
+    pub async fn reset(&mut self) -> QueryResult<u64> {
+        let start = Instant::now();
+        let resolved = sqlx::query!(
+            "UPDATE circuit_breaker_trips SET resolved_at = now() WHERE resolved_at IS NULL"
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.circuit_breaker.reset", start.elapsed());
+        Ok(resolved)
+    }
+}
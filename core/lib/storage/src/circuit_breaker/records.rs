@@ -0,0 +1,16 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+/// Record of the circuit breaker being tripped. The trip is active until `resolved_at` is set.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct CircuitBreakerTrip {
+    pub id: i64,
+    /// Component that detected the divergence, e.g. `witness_generator`.
+    pub source: String,
+    pub reason: String,
+    pub tripped_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
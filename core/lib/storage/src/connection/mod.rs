@@ -1,11 +1,14 @@
 // Built-in deps
-use std::{fmt, time::Duration, time::Instant};
+use std::{fmt, sync::Arc, time::Duration, time::Instant};
 // External imports
 use async_trait::async_trait;
 use deadpool::managed::{Manager, PoolConfig, RecycleResult, Timeouts};
 use deadpool::Runtime;
 use sqlx::{Connection, Error as SqlxError, PgConnection};
-use tokio::time;
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    time,
+};
 // Local imports
 // use self::recoverable_connection::RecoverableConnection;
 use crate::{get_database_replica_url, get_database_url, StorageProcessor};
@@ -19,6 +22,10 @@ pub type PooledConnection = deadpool::managed::Object<DbPool>;
 
 pub const DB_CONNECTION_RETRIES: u32 = 3;
 
+/// Maximum time a component waits for its connection quota. Once elapsed, `access_storage` fails
+/// with `PoolTimedOut`, the quota is never exceeded.
+pub const QUOTA_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Clone)]
 pub struct DbPool {
     url: String,
@@ -47,14 +54,26 @@ impl Manager for DbPool {
     }
 }
 
+/// Limit of the connections simultaneously held by one component.
+#[derive(Debug)]
+struct ConnectionQuota {
+    component: &'static str,
+    permits: Arc<Semaphore>,
+    wait_timeout: Duration,
+}
+
 /// `ConnectionPool` is a wrapper over a `diesel`s `Pool`, encapsulating
 /// the fixed size pool of connection to the database.
 ///
 /// The size of the pool and the database URL are configured via environment
 /// variables `DATABASE_POOL_SIZE` and `DATABASE_URL` respectively.
+///
+/// The pool may be shared by the components with the connection quotas (see `with_quota`),
+/// so the bursts of one component can't take all the connections needed by the others.
 #[derive(Clone)]
 pub struct ConnectionPool {
     pool: Pool,
+    quota: Option<Arc<ConnectionQuota>>,
 }
 
 impl fmt::Debug for ConnectionPool {
@@ -73,7 +92,7 @@ impl ConnectionPool {
 
         let pool = DbPool::create(database_url, max_size as usize);
 
-        Self { pool }
+        Self { pool, quota: None }
     }
 
     /// Establishes a pool of the connections to the replica of database and
@@ -86,8 +105,41 @@ impl ConnectionPool {
 
         let pool = DbPool::create(database_url, max_size as usize);
 
-        Self { pool }
+        Self { pool, quota: None }
+    }
+
+    /// Returns the pool sharing the connections with this one, which lets the component hold
+    /// at most `max_connections` connections at once. Zero means no limit. Quota of this pool,
+    /// if any, doesn't apply to the returned one.
+    ///
+    /// The quota is not shared with the other pools, so every component should get its own one.
+    /// Waiting for the quota is limited by `QUOTA_WAIT_TIMEOUT`. The component must not call
+    /// `access_storage` while holding a connection, since it may hold its whole quota already.
+    pub fn with_quota(&self, component: &'static str, max_connections: usize) -> Self {
+        self.with_quota_timeout(component, max_connections, QUOTA_WAIT_TIMEOUT)
     }
+
+    fn with_quota_timeout(
+        &self,
+        component: &'static str,
+        max_connections: usize,
+        wait_timeout: Duration,
+    ) -> Self {
+        let quota = if max_connections > 0 {
+            Some(Arc::new(ConnectionQuota {
+                component,
+                permits: Arc::new(Semaphore::new(max_connections)),
+                wait_timeout,
+            }))
+        } else {
+            None
+        };
+        Self {
+            pool: self.pool.clone(),
+            quota,
+        }
+    }
+
     /// Creates a `StorageProcessor` entity over a recoverable connection.
     /// Upon a database outage connection will block the thread until
     /// it will be able to recover the connection (or, if connection cannot
@@ -98,10 +150,39 @@ impl ConnectionPool {
     /// database access is must-have (e.g. block committer).
     pub async fn access_storage(&self) -> Result<StorageProcessor<'_>, SqlxError> {
         let start = Instant::now();
+        let permit = self.acquire_quota_permit().await?;
         let connection = self.get_pooled_connection().await;
-        metrics::histogram!("sql.connection_acquire", start.elapsed());
 
-        Ok(StorageProcessor::from_pool(connection))
+        let component = self
+            .quota
+            .as_ref()
+            .map_or("shared", |quota| quota.component);
+        metrics::histogram!("sql.connection_acquire", start.elapsed(), "component" => component);
+        Ok(StorageProcessor::from_pool(connection).with_quota_permit(permit))
+    }
+
+    /// Waits until the component is allowed to take one more connection. Returns `None` if
+    /// there is no quota, and `PoolTimedOut` if the quota is still exhausted after the wait.
+    async fn acquire_quota_permit(&self) -> Result<Option<OwnedSemaphorePermit>, SqlxError> {
+        let quota = match &self.quota {
+            Some(quota) => quota,
+            None => return Ok(None),
+        };
+        let start = Instant::now();
+        let permit = time::timeout(quota.wait_timeout, quota.permits.clone().acquire_owned()).await;
+        metrics::histogram!("sql.connection_quota_wait", start.elapsed(), "component" => quota.component);
+        match permit {
+            Ok(permit) => Ok(Some(permit.expect("Quota semaphore is never closed"))),
+            Err(_) => {
+                vlog::warn!(
+                    "Connection quota of {} is exhausted for {:?}",
+                    quota.component,
+                    quota.wait_timeout
+                );
+                metrics::increment_counter!("sql.connection_quota_exceeded", "component" => quota.component);
+                Err(SqlxError::PoolTimedOut)
+            }
+        }
     }
 
     async fn get_pooled_connection(&self) -> PooledConnection {
@@ -126,3 +207,77 @@ impl ConnectionPool {
         self.pool.get().await.unwrap()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Creates the pool without connecting to the database, which is enough for the quota checks.
+    fn pool_with_quota(max_connections: usize, wait_timeout: Duration) -> ConnectionPool {
+        let pool = ConnectionPool {
+            pool: DbPool::create("postgres://localhost/test", 10),
+            quota: None,
+        };
+        pool.with_quota_timeout("test", max_connections, wait_timeout)
+    }
+
+    /// Checks that the component can't hold more permits than its quota, and that the dropped
+    /// permits are returned to the quota.
+    #[tokio::test]
+    async fn quota_permits_limit() {
+        let pool = pool_with_quota(2, Duration::from_secs(10));
+
+        let first = pool.acquire_quota_permit().await.unwrap();
+        let second = pool.acquire_quota_permit().await.unwrap();
+        assert!(first.is_some() && second.is_some());
+
+        let third = time::timeout(Duration::from_millis(50), pool.acquire_quota_permit()).await;
+        assert!(third.is_err(), "Permit was acquired beyond the quota");
+
+        drop(first);
+        let third = time::timeout(Duration::from_millis(50), pool.acquire_quota_permit()).await;
+        assert!(
+            matches!(third, Ok(Ok(Some(_)))),
+            "Dropped permit was not released"
+        );
+
+        // Quota of the other component is not affected.
+        let other = pool.with_quota_timeout("other", 1, Duration::from_secs(10));
+        assert!(other.acquire_quota_permit().await.unwrap().is_some());
+    }
+
+    /// Checks that waiting for the exhausted quota fails once timed out, instead of taking
+    /// the connection beyond the quota.
+    #[tokio::test]
+    async fn quota_wait_timeout() {
+        let pool = pool_with_quota(1, Duration::from_millis(50));
+
+        let _first = pool.acquire_quota_permit().await.unwrap();
+        let start = Instant::now();
+        assert!(matches!(
+            pool.acquire_quota_permit().await,
+            Err(SqlxError::PoolTimedOut)
+        ));
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    /// Checks that the quota permit is held by the storage processor and released with it.
+    #[cfg_attr(not(feature = "db_test"), ignore)]
+    #[tokio::test]
+    async fn quota_released_with_connection() -> anyhow::Result<()> {
+        let pool =
+            ConnectionPool::new(Some(2)).with_quota_timeout("test", 1, Duration::from_secs(10));
+
+        let storage = pool.access_storage().await?;
+        let second = time::timeout(Duration::from_millis(100), pool.access_storage()).await;
+        assert!(second.is_err(), "Connection was taken beyond the quota");
+
+        drop(storage);
+        let second = time::timeout(Duration::from_secs(5), pool.access_storage()).await;
+        assert!(
+            second.is_ok(),
+            "Quota permit was not released with the connection"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{DepositCallEvent, SerialId};
+// Local imports
+use self::records::StorageDepositCall;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// The deposit is not executed yet.
+pub const DEPOSIT_CALL_PENDING: &str = "pending";
+/// Transactions of the call are sent to the mempool.
+pub const DEPOSIT_CALL_SUBMITTED: &str = "submitted";
+/// Call is invalid or its transactions were rejected by the mempool.
+pub const DEPOSIT_CALL_REJECTED: &str = "rejected";
+
+/// Schema of the L2 calls carried by the deposits made via the deposit router.
+///
+/// The call is pending until the deposit is executed by the network, after that its transactions
+/// are submitted to the mempool once.
+#[derive(Debug)]
+pub struct DepositCallsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> DepositCallsSchema<'a, 'c> {
+    /// Records the call of the deposit, does nothing if it's already recorded.
+    pub async fn store_deposit_call(&mut self, event: &DepositCallEvent) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO deposit_calls (serial_id, owner_address, call, eth_block)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (serial_id) DO NOTHING
+            "#,
+            event.serial_id as i64,
+            event.owner.as_bytes(),
+            event.call,
+            event.eth_block as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.deposit_calls.store_deposit_call", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the pending calls of the deposits already executed by the network.
+    pub async fn load_executed_deposit_calls(&mut self) -> QueryResult<Vec<StorageDepositCall>> {
+        let start = Instant::now();
+        let calls = sqlx::query_as!(
+            StorageDepositCall,
+            r#"
+            SELECT * FROM deposit_calls
+            WHERE status = $1
+                AND serial_id IN (SELECT priority_op_serialid FROM executed_priority_operations)
+            ORDER BY serial_id
+            "#,
+            DEPOSIT_CALL_PENDING
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.deposit_calls.load_executed_deposit_calls",
+            start.elapsed()
+        );
+        Ok(calls)
+    }
+
+    /// Loads the call of the deposit with the given serial id.
+    pub async fn get_deposit_call(
+        &mut self,
+        serial_id: SerialId,
+    ) -> QueryResult<Option<StorageDepositCall>> {
+        let start = Instant::now();
+        let call = sqlx::query_as!(
+            StorageDepositCall,
+            "SELECT * FROM deposit_calls WHERE serial_id = $1",
+            serial_id as i64
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.deposit_calls.get_deposit_call", start.elapsed());
+        Ok(call)
+    }
+
+    /// Marks the call as processed with the given status, `error` is the reason of the rejection.
+    pub async fn set_deposit_call_status(
+        &mut self,
+        serial_id: SerialId,
+        status: &str,
+        error: Option<String>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE deposit_calls SET status = $2, error = $3 WHERE serial_id = $1",
+            serial_id as i64,
+            status,
+            error
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.deposit_calls.set_deposit_call_status", start.elapsed());
+        Ok(())
+    }
+}
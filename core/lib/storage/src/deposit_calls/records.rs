@@ -0,0 +1,15 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// L2 call of the deposit made via the deposit router.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageDepositCall {
+    pub serial_id: i64,
+    pub owner_address: Vec<u8>,
+    pub call: Vec<u8>,
+    pub eth_block: i64,
+    pub status: String,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
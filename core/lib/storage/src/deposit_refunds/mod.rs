@@ -0,0 +1,107 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use num::BigInt;
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{Address, PriorityOp};
+// Local imports
+use self::records::StorageRefundableDeposit;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// The deposit was not processed by the network before the deadline of the priority queue.
+pub const REFUND_REASON_EXPIRED: &str = "expired";
+
+/// Schema of the deposits that can't be credited by the network.
+///
+/// Funds of such deposits are returned to the recipient on L1 through the exodus mode:
+/// the deposit has to be canceled with `cancelOutstandingDepositsForExodusMode`, after that
+/// the funds can be taken out with `withdrawPendingBalance`.
+#[derive(Debug)]
+pub struct DepositRefundsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> DepositRefundsSchema<'a, 'c> {
+    /// Records the deposit as refundable, does nothing if it's already recorded.
+    pub async fn store_refundable_deposit(
+        &mut self,
+        op: &PriorityOp,
+        reason: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let deposit = op.data.try_get_deposit().ok_or_else(|| {
+            anyhow::anyhow!("Priority operation {} is not a deposit", op.serial_id)
+        })?;
+        let amount = BigDecimal::from(BigInt::from(deposit.amount));
+        sqlx::query!(
+            r#"
+            INSERT INTO refundable_deposits
+                (serial_id, eth_hash, from_address, to_address, token_id, amount, reason)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (serial_id) DO NOTHING
+            "#,
+            op.serial_id as i64,
+            op.eth_hash.as_bytes(),
+            deposit.from.as_bytes(),
+            deposit.to.as_bytes(),
+            *deposit.token as i32,
+            amount,
+            reason
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.deposit_refunds.store_refundable_deposit",
+            start.elapsed()
+        );
+        Ok(())
+    }
+
+    /// Loads the refundable deposits either sent from or to the address.
+    pub async fn load_refundable_deposits(
+        &mut self,
+        address: Address,
+    ) -> QueryResult<Vec<StorageRefundableDeposit>> {
+        let start = Instant::now();
+        let deposits = sqlx::query_as!(
+            StorageRefundableDeposit,
+            r#"
+            SELECT * FROM refundable_deposits
+            WHERE from_address = $1 OR to_address = $1
+            ORDER BY serial_id
+            "#,
+            address.as_bytes()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.deposit_refunds.load_refundable_deposits",
+            start.elapsed()
+        );
+        Ok(deposits)
+    }
+
+    /// Removes the records of the deposits that were eventually executed by the network,
+    /// returns the number of removed records.
+    pub async fn remove_executed_deposits(&mut self) -> QueryResult<u64> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            r#"
+            DELETE FROM refundable_deposits
+            WHERE serial_id IN (SELECT priority_op_serialid FROM executed_priority_operations)
+            "#
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!(
+            "sql.deposit_refunds.remove_executed_deposits",
+            start.elapsed()
+        );
+        Ok(removed)
+    }
+}
@@ -0,0 +1,16 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::{types::BigDecimal, FromRow};
+
+/// Deposit that can't be credited by the network and has to be refunded on L1.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageRefundableDeposit {
+    pub serial_id: i64,
+    pub eth_hash: Vec<u8>,
+    pub from_address: Vec<u8>,
+    pub to_address: Vec<u8>,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,69 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+// Local imports
+use self::records::StorageEncryptedTx;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the encrypted mempool.
+///
+/// Blobs are transfers encrypted to the sealing key of the operator. They are stored as is until
+/// the block formation, when the mempool takes and decrypts them.
+#[derive(Debug)]
+pub struct EncryptedMempoolSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> EncryptedMempoolSchema<'a, 'c> {
+    /// Stores the encrypted transfer, returns `None` if there are already `max_pending_txs` blobs
+    /// waiting for the block formation.
+    pub async fn insert_tx(
+        &mut self,
+        blob: &[u8],
+        max_pending_txs: usize,
+    ) -> QueryResult<Option<i64>> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO encrypted_mempool_txs (blob)
+            SELECT $1 WHERE (SELECT COUNT(*) FROM encrypted_mempool_txs) < $2
+            RETURNING id
+            "#,
+            blob,
+            max_pending_txs as i64
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| record.id);
+
+        metrics::histogram!("sql.encrypted_mempool.insert_tx", start.elapsed());
+        Ok(id)
+    }
+
+    /// Removes and returns at most `limit` oldest blobs. Must be called within the database
+    /// transaction storing the decrypted transfers, so the blobs are not lost.
+    pub async fn take_txs(&mut self, limit: usize) -> QueryResult<Vec<StorageEncryptedTx>> {
+        let start = Instant::now();
+        let mut txs = sqlx::query_as!(
+            StorageEncryptedTx,
+            r#"
+            DELETE FROM encrypted_mempool_txs
+            WHERE id IN (
+                SELECT id FROM encrypted_mempool_txs
+                ORDER BY id
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+            limit as i64
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+        txs.sort_by_key(|tx| tx.id);
+
+        metrics::histogram!("sql.encrypted_mempool.take_txs", start.elapsed());
+        Ok(txs)
+    }
+}
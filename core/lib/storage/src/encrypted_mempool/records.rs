@@ -0,0 +1,12 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageEncryptedTx {
+    pub id: i64,
+    pub blob: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,127 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{Address, FeeSponsor, TokenId};
+// Local imports
+use self::records::{StorageFeeSponsor, StorageFeeSponsorUsage};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the sponsors of the zero-fee transfers.
+///
+/// Sponsors are managed by the operator via the private API. The gas taken by the sponsored
+/// transfers is accounted per UTC day when the transfer is admitted to the mempool.
+#[derive(Debug)]
+pub struct FeeSponsorsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> FeeSponsorsSchema<'a, 'c> {
+    /// Adds the sponsor of the token transfers or updates its daily gas budget.
+    pub async fn store_sponsor(&mut self, sponsor: &FeeSponsor) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO fee_sponsors (address, token_id, daily_gas_budget) VALUES ($1, $2, $3)
+            ON CONFLICT (address, token_id) DO UPDATE SET daily_gas_budget = $3",
+            sponsor.address.as_bytes(),
+            *sponsor.token as i32,
+            sponsor.daily_gas_budget as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.fee_sponsors.store_sponsor", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the sponsor of the token transfers. Returns `false` if there is no such sponsor.
+    pub async fn remove_sponsor(&mut self, address: Address, token: TokenId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM fee_sponsors WHERE address = $1 AND token_id = $2",
+            address.as_bytes(),
+            *token as i32
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.fee_sponsors.remove_sponsor", start.elapsed());
+        Ok(removed == 1)
+    }
+
+    /// Loads the sponsor of the token transfers sent from the address, if any.
+    pub async fn load_sponsor(
+        &mut self,
+        address: Address,
+        token: TokenId,
+    ) -> QueryResult<Option<FeeSponsor>> {
+        let start = Instant::now();
+        let sponsor = sqlx::query_as!(
+            StorageFeeSponsor,
+            "SELECT * FROM fee_sponsors WHERE address = $1 AND token_id = $2",
+            address.as_bytes(),
+            *token as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.fee_sponsors.load_sponsor", start.elapsed());
+        Ok(sponsor.map(Into::into))
+    }
+
+    /// Loads all the sponsors along with the gas taken by their transfers today.
+    pub async fn load_sponsors_usage(&mut self) -> QueryResult<Vec<StorageFeeSponsorUsage>> {
+        let start = Instant::now();
+        let sponsors = sqlx::query_as!(
+            StorageFeeSponsorUsage,
+            r#"
+            SELECT fee_sponsors.address, fee_sponsors.token_id, fee_sponsors.daily_gas_budget,
+                COALESCE(fee_sponsor_gas_usage.gas_used, 0) AS "gas_used_today!"
+            FROM fee_sponsors
+            LEFT JOIN fee_sponsor_gas_usage
+                ON fee_sponsor_gas_usage.address = fee_sponsors.address
+                AND fee_sponsor_gas_usage.token_id = fee_sponsors.token_id
+                AND fee_sponsor_gas_usage.day = (now() AT TIME ZONE 'utc')::date
+            ORDER BY fee_sponsors.created_at
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.fee_sponsors.load_sponsors_usage", start.elapsed());
+        Ok(sponsors)
+    }
+
+    /// Charges the gas of the sponsored transfer from the budget of the current UTC day.
+    /// Returns `false` without charging anything if the budget would be exceeded.
+    pub async fn charge_gas(
+        &mut self,
+        address: Address,
+        token: TokenId,
+        gas: u64,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        // The check and the update are done in a single statement, so the concurrent transfers
+        // of the sponsor admitted by the different API replicas can't exceed the budget.
+        let charged = sqlx::query!(
+            "INSERT INTO fee_sponsor_gas_usage (address, token_id, day, gas_used)
+            SELECT address, token_id, (now() AT TIME ZONE 'utc')::date, $3 FROM fee_sponsors
+            WHERE address = $1 AND token_id = $2 AND daily_gas_budget >= $3
+            ON CONFLICT (address, token_id, day) DO UPDATE
+            SET gas_used = fee_sponsor_gas_usage.gas_used + EXCLUDED.gas_used
+            WHERE fee_sponsor_gas_usage.gas_used + EXCLUDED.gas_used <= (
+                SELECT daily_gas_budget FROM fee_sponsors WHERE address = $1 AND token_id = $2
+            )",
+            address.as_bytes(),
+            *token as i32,
+            gas as i64
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.fee_sponsors.charge_gas", start.elapsed());
+        Ok(charged == 1)
+    }
+}
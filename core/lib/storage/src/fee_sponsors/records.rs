@@ -0,0 +1,33 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+use zksync_types::{Address, FeeSponsor, TokenId};
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageFeeSponsor {
+    pub address: Vec<u8>,
+    pub token_id: i32,
+    pub daily_gas_budget: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Sponsor along with the gas taken by its transfers during the current UTC day.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageFeeSponsorUsage {
+    pub address: Vec<u8>,
+    pub token_id: i32,
+    pub daily_gas_budget: i64,
+    pub gas_used_today: i64,
+}
+
+impl From<StorageFeeSponsor> for FeeSponsor {
+    fn from(val: StorageFeeSponsor) -> Self {
+        Self {
+            address: Address::from_slice(&val.address),
+            token: TokenId(val.token_id as u32),
+            daily_gas_budget: val.daily_gas_budget as u64,
+        }
+    }
+}
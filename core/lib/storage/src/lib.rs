@@ -78,6 +78,7 @@
 use std::env;
 // External imports
 use sqlx::{postgres::Postgres, Connection, PgConnection, Transaction};
+use tokio::sync::OwnedSemaphorePermit;
 // Workspace imports
 use zksync_types::{ActionType, BlockNumber};
 // Local imports
@@ -87,20 +88,37 @@ use crate::connection::{holder::ConnectionHolder, PooledConnection};
 #[cfg(test)]
 mod tests;
 
+pub mod account_freezes;
+pub mod account_names;
+pub mod accounting;
+pub mod admin_audit;
 pub mod chain;
+pub mod circuit_breaker;
 pub mod config;
 pub mod connection;
 pub mod data_restore;
+pub mod deposit_calls;
+pub mod deposit_refunds;
 pub mod diff;
+pub mod encrypted_mempool;
 pub mod ethereum;
 pub mod event;
+pub mod fee_sponsors;
 pub mod forced_exit_requests;
 pub mod listener;
 pub mod misc;
+pub mod private_txs;
 pub mod prover;
+pub mod read_tokens;
 pub mod test_data;
 pub mod tokens;
+pub mod travel_rule;
+pub mod treasury;
 pub mod utils;
+pub mod watch_lists;
+pub mod weth_unwraps;
+pub mod withdrawal_eth_txs;
+pub mod withdrawal_timelocks;
 
 use forced_exit_requests::ForcedExitRequestsSchema;
 
@@ -130,6 +148,8 @@ pub fn get_database_url() -> String {
 pub struct StorageProcessor<'a> {
     conn: ConnectionHolder<'a>,
     in_transaction: bool,
+    /// Permit of the component quota, returned once the connection is released.
+    _quota_permit: Option<OwnedSemaphorePermit>,
 }
 
 #[derive(sqlx::Type, Debug, Clone, PartialEq, Eq)]
@@ -156,6 +176,7 @@ impl<'a> StorageProcessor<'a> {
         Ok(StorageProcessor {
             conn: ConnectionHolder::Direct(connection),
             in_transaction: false,
+            _quota_permit: None,
         })
     }
 
@@ -179,6 +200,7 @@ impl<'a> StorageProcessor<'a> {
         StorageProcessor {
             conn: ConnectionHolder::Transaction(conn),
             in_transaction: true,
+            _quota_permit: None,
         }
     }
 
@@ -198,14 +220,46 @@ impl<'a> StorageProcessor<'a> {
         Self {
             conn: ConnectionHolder::Pooled(conn),
             in_transaction: false,
+            _quota_permit: None,
         }
     }
 
+    /// Holds the quota permit until the connection is released.
+    pub(crate) fn with_quota_permit(mut self, permit: Option<OwnedSemaphorePermit>) -> Self {
+        self._quota_permit = permit;
+        self
+    }
+
+    /// Gains access to the `AccountFreezes` schema.
+    pub fn account_freezes_schema(&mut self) -> account_freezes::AccountFreezesSchema<'_, 'a> {
+        account_freezes::AccountFreezesSchema(self)
+    }
+
+    /// Gains access to the `AccountNames` schema.
+    pub fn account_names_schema(&mut self) -> account_names::AccountNamesSchema<'_, 'a> {
+        account_names::AccountNamesSchema(self)
+    }
+
+    /// Gains access to the `Accounting` schema.
+    pub fn accounting_schema(&mut self) -> accounting::AccountingSchema<'_, 'a> {
+        accounting::AccountingSchema(self)
+    }
+
+    /// Gains access to the `AdminAudit` schema.
+    pub fn admin_audit_schema(&mut self) -> admin_audit::AdminAuditSchema<'_, 'a> {
+        admin_audit::AdminAuditSchema(self)
+    }
+
     /// Gains access to the `Chain` schemas.
     pub fn chain(&mut self) -> chain::ChainIntermediator<'_, 'a> {
         chain::ChainIntermediator(self)
     }
 
+    /// Gains access to the `CircuitBreaker` schema.
+    pub fn circuit_breaker_schema(&mut self) -> circuit_breaker::CircuitBreakerSchema<'_, 'a> {
+        circuit_breaker::CircuitBreakerSchema(self)
+    }
+
     /// Gains access to the `Config` schema.
     pub fn config_schema(&mut self) -> config::ConfigSchema<'_, 'a> {
         config::ConfigSchema(self)
@@ -216,21 +270,87 @@ impl<'a> StorageProcessor<'a> {
         data_restore::DataRestoreSchema(self)
     }
 
+    /// Gains access to the `DepositCalls` schema.
+    pub fn deposit_calls_schema(&mut self) -> deposit_calls::DepositCallsSchema<'_, 'a> {
+        deposit_calls::DepositCallsSchema(self)
+    }
+
+    /// Gains access to the `DepositRefunds` schema.
+    pub fn deposit_refunds_schema(&mut self) -> deposit_refunds::DepositRefundsSchema<'_, 'a> {
+        deposit_refunds::DepositRefundsSchema(self)
+    }
+
+    /// Gains access to the `EncryptedMempool` schema.
+    pub fn encrypted_mempool_schema(
+        &mut self,
+    ) -> encrypted_mempool::EncryptedMempoolSchema<'_, 'a> {
+        encrypted_mempool::EncryptedMempoolSchema(self)
+    }
+
     /// Gains access to the `Ethereum` schema.
     pub fn ethereum_schema(&mut self) -> ethereum::EthereumSchema<'_, 'a> {
         ethereum::EthereumSchema(self)
     }
 
+    /// Gains access to the `FeeSponsors` schema.
+    pub fn fee_sponsors_schema(&mut self) -> fee_sponsors::FeeSponsorsSchema<'_, 'a> {
+        fee_sponsors::FeeSponsorsSchema(self)
+    }
+
+    /// Gains access to the `PrivateTxs` schema.
+    pub fn private_txs_schema(&mut self) -> private_txs::PrivateTxsSchema<'_, 'a> {
+        private_txs::PrivateTxsSchema(self)
+    }
+
     /// Gains access to the `Prover` schema.
     pub fn prover_schema(&mut self) -> prover::ProverSchema<'_, 'a> {
         prover::ProverSchema(self)
     }
 
+    /// Gains access to the `ReadTokens` schema.
+    pub fn read_tokens_schema(&mut self) -> read_tokens::ReadTokensSchema<'_, 'a> {
+        read_tokens::ReadTokensSchema(self)
+    }
+
     /// Gains access to the `Tokens` schema.
     pub fn tokens_schema(&mut self) -> tokens::TokensSchema<'_, 'a> {
         tokens::TokensSchema(self)
     }
 
+    /// Gains access to the `TravelRule` schema.
+    pub fn travel_rule_schema(&mut self) -> travel_rule::TravelRuleSchema<'_, 'a> {
+        travel_rule::TravelRuleSchema(self)
+    }
+
+    /// Gains access to the `Treasury` schema.
+    pub fn treasury_schema(&mut self) -> treasury::TreasurySchema<'_, 'a> {
+        treasury::TreasurySchema(self)
+    }
+
+    /// Gains access to the `WatchLists` schema.
+    pub fn watch_lists_schema(&mut self) -> watch_lists::WatchListsSchema<'_, 'a> {
+        watch_lists::WatchListsSchema(self)
+    }
+
+    /// Gains access to the `WethUnwraps` schema.
+    pub fn weth_unwraps_schema(&mut self) -> weth_unwraps::WethUnwrapsSchema<'_, 'a> {
+        weth_unwraps::WethUnwrapsSchema(self)
+    }
+
+    /// Gains access to the `WithdrawalEthTxs` schema.
+    pub fn withdrawal_eth_txs_schema(
+        &mut self,
+    ) -> withdrawal_eth_txs::WithdrawalEthTxsSchema<'_, 'a> {
+        withdrawal_eth_txs::WithdrawalEthTxsSchema(self)
+    }
+
+    /// Gains access to the `WithdrawalTimelocks` schema.
+    pub fn withdrawal_timelocks_schema(
+        &mut self,
+    ) -> withdrawal_timelocks::WithdrawalTimelocksSchema<'_, 'a> {
+        withdrawal_timelocks::WithdrawalTimelocksSchema(self)
+    }
+
     pub fn forced_exit_requests_schema(&mut self) -> ForcedExitRequestsSchema<'_, 'a> {
         ForcedExitRequestsSchema(self)
     }
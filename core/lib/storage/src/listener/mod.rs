@@ -9,6 +9,10 @@ use notification::StorageNotification;
 
 pub mod notification;
 
+/// Channel notified about the new sealed blocks and the block events, i.e. the committed,
+/// finalized and reverted blocks.
+pub const BLOCKS_CHANNEL: &str = "blocks_channel";
+
 /// A connection to the database that's capable of listening for notifications.
 /// In its current implementation uses PostgreSQL LISTEN/NOTIFY protocol.
 pub struct StorageListener {
@@ -10,6 +10,9 @@ use num::ToPrimitive;
 
 pub mod records;
 
+/// Key of the advisory lock held by the leader of the server instances.
+pub const LEADER_LOCK_KEY: i64 = 0x7a6b_5359_4e43_0001;
+
 /// MiscSchema should be used for various features not directly related to the main zkSync functionality
 /// Please, use this schema if your functionality needs 1-3 methods. Otherwise, it should have a dedicated schema
 #[derive(Debug)]
@@ -64,4 +67,52 @@ impl<'a, 'c> MiscSchema<'a, 'c> {
         metrics::histogram!("sql.token.get_total_used_subsidy_for_type", start.elapsed());
         Ok(sum)
     }
+
+    /// Tries to take the session-level advisory lock, returning whether it's taken. The lock is held
+    /// until it's released or the connection is closed, the same connection may take it repeatedly.
+    pub async fn try_acquire_advisory_lock(&mut self, key: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+        let acquired = sqlx::query!(r#"SELECT pg_try_advisory_lock($1) AS "acquired!""#, key)
+            .fetch_one(self.0.conn())
+            .await?
+            .acquired;
+
+        metrics::histogram!("sql.misc.try_acquire_advisory_lock", start.elapsed());
+        Ok(acquired)
+    }
+
+    /// Checks whether the advisory lock with the given non-negative key is held by this connection.
+    /// Locks taken by a single `bigint` key have `objsubid = 1`, unlike the ones taken by two
+    /// `int` keys, which may have the same `classid` and `objid`.
+    pub async fn holds_advisory_lock(&mut self, key: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+        let held = sqlx::query!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM pg_locks
+                WHERE locktype = 'advisory' AND granted AND pid = pg_backend_pid()
+                    AND objsubid = 1 AND ((classid::bigint << 32) | objid::bigint) = $1
+            ) AS "held!"
+            "#,
+            key
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .held;
+
+        metrics::histogram!("sql.misc.holds_advisory_lock", start.elapsed());
+        Ok(held)
+    }
+
+    /// Releases the advisory lock taken by this connection, returning whether it was held.
+    pub async fn release_advisory_lock(&mut self, key: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+        let released = sqlx::query!(r#"SELECT pg_advisory_unlock($1) AS "released!""#, key)
+            .fetch_one(self.0.conn())
+            .await?
+            .released;
+
+        metrics::histogram!("sql.misc.release_advisory_lock", start.elapsed());
+        Ok(released)
+    }
 }
@@ -0,0 +1,132 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::tx::TxHash;
+// Local imports
+use self::records::StoragePrivateTxClient;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the private transactions channel.
+///
+/// Clients of the channel submit their batches with the bearer tokens identified by the hashes.
+/// Submitted transactions are stored in the mempool as usual, so the proposer picks them up at
+/// the block formation, but are marked private and hidden from the public API until executed.
+#[derive(Debug)]
+pub struct PrivateTxsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> PrivateTxsSchema<'a, 'c> {
+    /// Registers the client of the channel. Returns `None` if the name or the token is taken.
+    pub async fn create_client(
+        &mut self,
+        name: &str,
+        token_hash: &[u8],
+        max_batches_per_minute: u32,
+    ) -> QueryResult<Option<i64>> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO private_tx_clients (name, token_hash, max_batches_per_minute)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            RETURNING id
+            "#,
+            name,
+            token_hash,
+            max_batches_per_minute as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| record.id);
+
+        metrics::histogram!("sql.private_txs.create_client", start.elapsed());
+        Ok(id)
+    }
+
+    /// Removes the client, returns `false` if there is no client with such name.
+    /// Already submitted transactions of the client stay private.
+    pub async fn remove_client(&mut self, name: &str) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!("DELETE FROM private_tx_clients WHERE name = $1", name)
+            .execute(self.0.conn())
+            .await?
+            .rows_affected();
+
+        metrics::histogram!("sql.private_txs.remove_client", start.elapsed());
+        Ok(removed > 0)
+    }
+
+    /// Loads the client by the hash of its token.
+    pub async fn get_client_by_token(
+        &mut self,
+        token_hash: &[u8],
+    ) -> QueryResult<Option<StoragePrivateTxClient>> {
+        let start = Instant::now();
+        let client = sqlx::query_as!(
+            StoragePrivateTxClient,
+            "SELECT * FROM private_tx_clients WHERE token_hash = $1",
+            token_hash
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.private_txs.get_client_by_token", start.elapsed());
+        Ok(client)
+    }
+
+    /// Loads all the clients of the channel.
+    pub async fn load_clients(&mut self) -> QueryResult<Vec<StoragePrivateTxClient>> {
+        let start = Instant::now();
+        let clients = sqlx::query_as!(
+            StoragePrivateTxClient,
+            "SELECT * FROM private_tx_clients ORDER BY id"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.private_txs.load_clients", start.elapsed());
+        Ok(clients)
+    }
+
+    /// Marks the mempool transactions as submitted by the client via the private channel.
+    /// Transactions without the client are the decrypted ones of the encrypted mempool.
+    pub async fn mark_private_txs(
+        &mut self,
+        client_id: Option<i64>,
+        tx_hashes: &[TxHash],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = tx_hashes.iter().map(hex::encode).collect();
+        sqlx::query!(
+            r#"
+            INSERT INTO private_mempool_txs (tx_hash, client_id)
+            SELECT u.tx_hash, $1 FROM UNNEST ($2::text[]) AS u(tx_hash)
+            ON CONFLICT (tx_hash) DO NOTHING
+            "#,
+            client_id,
+            &tx_hashes
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.private_txs.mark_private_txs", start.elapsed());
+        Ok(())
+    }
+
+    /// Checks whether the mempool transaction was submitted via the private channel.
+    pub async fn is_private_tx(&mut self, tx_hash: TxHash) -> QueryResult<bool> {
+        let start = Instant::now();
+        let is_private = sqlx::query!(
+            r#"SELECT EXISTS(SELECT 1 FROM private_mempool_txs WHERE tx_hash = $1) AS "exists!""#,
+            hex::encode(tx_hash)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .exists;
+
+        metrics::histogram!("sql.private_txs.is_private_tx", start.elapsed());
+        Ok(is_private)
+    }
+}
@@ -0,0 +1,14 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoragePrivateTxClient {
+    pub id: i64,
+    pub name: String,
+    pub token_hash: Vec<u8>,
+    pub max_batches_per_minute: i32,
+    pub created_at: DateTime<Utc>,
+}
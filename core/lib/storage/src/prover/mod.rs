@@ -3,16 +3,18 @@ use std::time::Instant;
 // External imports
 use anyhow::format_err;
 // Workspace imports
-use zksync_types::BlockNumber;
+use zksync_types::{BlockNumber, H256};
 // Local imports
-use self::records::{StorageProverJobQueue, StoredAggregatedProof, StoredProof};
+use self::records::{ProverBacklog, StorageProverJobQueue, StoredAggregatedProof, StoredProof};
 use crate::chain::operations::OperationsSchema;
 use crate::prover::records::StorageBlockWitness;
 use crate::{QueryResult, StorageProcessor};
-use chrono::{TimeZone, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
 use zksync_types::aggregated_operations::AggregatedActionType;
-use zksync_types::prover::{ProverJob, ProverJobStatus, ProverJobType};
+use zksync_types::prover::{
+    ProofSystemVersion, ProverJob, ProverJobStatus, ProverJobType, SINGLE_PROOF_JOB_PRIORITY,
+};
 
 pub mod records;
 
@@ -22,6 +24,37 @@ pub mod records;
 pub struct ProverSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
 impl<'a, 'c> ProverSchema<'a, 'c> {
+    /// Returns the amounts of the prover jobs by their state, the finished ones are counted
+    /// starting from `done_since`.
+    pub async fn prover_backlog(
+        &mut self,
+        done_since: DateTime<Utc>,
+    ) -> QueryResult<ProverBacklog> {
+        let start = Instant::now();
+        let record = sqlx::query!(
+            r#"SELECT
+                COUNT(*) FILTER (WHERE job_status = $1) AS "idle_jobs!",
+                COUNT(*) FILTER (WHERE job_status = $2) AS "in_progress_jobs!",
+                COUNT(*) FILTER (WHERE job_status = $3 AND updated_at >= $4) AS "done_jobs!",
+                COUNT(DISTINCT updated_by) FILTER (WHERE job_status = $2) AS "active_provers!"
+            FROM prover_job_queue"#,
+            ProverJobStatus::Idle.to_number(),
+            ProverJobStatus::InProgress.to_number(),
+            ProverJobStatus::Done.to_number(),
+            done_since
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "prover_backlog");
+        Ok(ProverBacklog {
+            idle_jobs: record.idle_jobs as u32,
+            in_progress_jobs: record.in_progress_jobs as u32,
+            done_jobs: record.done_jobs as u32,
+            active_provers: record.active_provers as u32,
+        })
+    }
+
     /// Returns the amount of blocks which await for proof, but have
     /// no assigned prover run.
     pub async fn pending_jobs_count(&mut self) -> QueryResult<u32> {
@@ -176,6 +209,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         job_id: i32,
         block_number: BlockNumber,
         proof: &SingleProof,
+        proof_system: ProofSystemVersion,
     ) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -196,10 +230,11 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         }
 
         sqlx::query!(
-            "INSERT INTO proofs (block_number, proof)
-            VALUES ($1, $2)",
+            "INSERT INTO proofs (block_number, proof, proof_system)
+            VALUES ($1, $2, $3)",
             i64::from(*block_number),
-            serde_json::to_value(proof).unwrap()
+            serde_json::to_value(proof).unwrap(),
+            proof_system.as_str()
         )
         .execute(transaction.conn())
         .await?;
@@ -246,6 +281,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         first_block: BlockNumber,
         last_block: BlockNumber,
         proof: &AggregatedProof,
+        proof_system: ProofSystemVersion,
     ) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -266,11 +302,12 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         }
 
         sqlx::query!(
-            "INSERT INTO aggregated_proofs (first_block, last_block, proof)
-            VALUES ($1, $2, $3)",
+            "INSERT INTO aggregated_proofs (first_block, last_block, proof, proof_system)
+            VALUES ($1, $2, $3, $4)",
             i64::from(*first_block),
             i64::from(*last_block),
-            serde_json::to_value(proof).unwrap()
+            serde_json::to_value(proof).unwrap(),
+            proof_system.as_str()
         )
         .execute(transaction.conn())
         .await?;
@@ -303,12 +340,12 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(proof)
     }
 
-    /// Gets the stored proof for a block.
+    /// Gets the stored aggregated proof for blocks along with the proof system it's created with.
     pub async fn load_aggregated_proof(
         &mut self,
         first_block: BlockNumber,
         last_block: BlockNumber,
-    ) -> QueryResult<Option<AggregatedProof>> {
+    ) -> QueryResult<Option<(AggregatedProof, ProofSystemVersion)>> {
         let start = Instant::now();
         let proof = sqlx::query_as!(
             StoredAggregatedProof,
@@ -318,12 +355,145 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?
-        .map(|stored| serde_json::from_value(stored.proof).unwrap());
+        .map(|stored| -> QueryResult<_> {
+            let proof_system = stored.proof_system.parse()?;
+            Ok((serde_json::from_value(stored.proof).unwrap(), proof_system))
+        })
+        .transpose()?;
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "load_aggregated_proof");
         Ok(proof)
     }
 
+    /// Caches the single block proof by the block commitment, so the block with the same
+    /// commitment is not proven again.
+    pub async fn store_cached_proof(
+        &mut self,
+        block_commitment: H256,
+        proof: &SingleProof,
+        proof_system: ProofSystemVersion,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO proof_cache (block_commitment, proof, proof_system)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (block_commitment) DO NOTHING",
+            block_commitment.as_bytes(),
+            serde_json::to_value(proof).unwrap(),
+            proof_system.as_str()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "store_cached_proof");
+        Ok(())
+    }
+
+    /// Gets the cached proof of the block with the given commitment along with the proof system
+    /// it's created with.
+    pub async fn load_cached_proof(
+        &mut self,
+        block_commitment: H256,
+    ) -> QueryResult<Option<(SingleProof, ProofSystemVersion)>> {
+        let start = Instant::now();
+        let proof = sqlx::query!(
+            "SELECT proof, proof_system FROM proof_cache WHERE block_commitment = $1",
+            block_commitment.as_bytes()
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| -> QueryResult<_> {
+            let proof_system = record.proof_system.parse()?;
+            Ok((serde_json::from_value(record.proof).unwrap(), proof_system))
+        })
+        .transpose()?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_cached_proof");
+        Ok(proof)
+    }
+
+    /// Stores the cached proof for a block along with the finished job, so the block
+    /// is not queued for proving.
+    pub async fn store_proof_from_cache(
+        &mut self,
+        block_number: BlockNumber,
+        proof: &SingleProof,
+        proof_system: ProofSystemVersion,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        sqlx::query!(
+            "INSERT INTO prover_job_queue (job_status, job_priority, job_type, updated_by, first_block, last_block, job_data)
+            VALUES ($1, $2, $3, 'server_proof_cache', $4, $4, 'null'::jsonb)",
+            ProverJobStatus::Done.to_number(),
+            SINGLE_PROOF_JOB_PRIORITY,
+            ProverJobType::SingleProof.to_string(),
+            i64::from(*block_number)
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO proofs (block_number, proof, proof_system)
+            VALUES ($1, $2, $3)",
+            i64::from(*block_number),
+            serde_json::to_value(proof).unwrap(),
+            proof_system.as_str()
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction
+            .prover_schema()
+            .set_block_processing_metrics(block_number, block_number, "single_proof".to_string())
+            .await?;
+        transaction.commit().await?;
+        metrics::histogram!("sql", start.elapsed(), "prover" => "store_proof_from_cache");
+        Ok(())
+    }
+
+    /// Records the content hash of the result published for the job, along with the compression
+    /// and the size of the received payload.
+    pub async fn store_job_result_hash(
+        &mut self,
+        job_id: i32,
+        content_hash: H256,
+        compression: &str,
+        payload_size: usize,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO prover_job_results (job_id, content_hash, compression, payload_size)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (job_id) DO UPDATE
+            SET (content_hash, compression, payload_size, created_at) = ($2, $3, $4, now())",
+            job_id,
+            content_hash.as_bytes(),
+            compression,
+            payload_size as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "store_job_result_hash");
+        Ok(())
+    }
+
+    /// Gets the content hash of the result published for the job.
+    pub async fn load_job_result_hash(&mut self, job_id: i32) -> QueryResult<Option<H256>> {
+        let start = Instant::now();
+        let content_hash = sqlx::query!(
+            "SELECT content_hash FROM prover_job_results WHERE job_id = $1",
+            job_id
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| H256::from_slice(&record.content_hash));
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_job_result_hash");
+        Ok(content_hash)
+    }
+
     /// Stores witness for a block
     pub async fn store_witness(
         &mut self,
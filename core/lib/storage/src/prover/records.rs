@@ -25,6 +25,7 @@ pub struct StoredProof {
     pub block_number: i64,
     pub proof: serde_json::Value,
     pub created_at: DateTime<Utc>,
+    pub proof_system: String,
 }
 
 #[derive(Debug, FromRow)]
@@ -33,6 +34,7 @@ pub struct StoredAggregatedProof {
     pub last_block: i64,
     pub proof: serde_json::Value,
     pub created_at: DateTime<Utc>,
+    pub proof_system: String,
 }
 
 // Every time before a prover worker starts generating the proof, a prover run is recorded for monitoring purposes
@@ -56,6 +58,17 @@ pub struct StorageBlockWitness {
     pub witness: String,
 }
 
+/// Amounts of the prover jobs by their state, used to scale the provers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProverBacklog {
+    pub idle_jobs: u32,
+    pub in_progress_jobs: u32,
+    /// Jobs finished since the requested moment.
+    pub done_jobs: u32,
+    /// Provers working on the jobs in progress.
+    pub active_provers: u32,
+}
+
 #[derive(Debug, FromRow)]
 pub struct StorageProverJobQueue {
     pub id: i32,
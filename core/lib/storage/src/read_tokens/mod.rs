@@ -0,0 +1,90 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+use self::records::StorageReadToken;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the read tokens delegating the access to the private data of the accounts.
+///
+/// Tokens are issued for the grants signed by the owners of the accounts and are identified
+/// by their hashes. Expired and revoked tokens are kept, so the grants can't be reused.
+#[derive(Debug)]
+pub struct ReadTokensSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> ReadTokensSchema<'a, 'c> {
+    /// Stores the token issued for the grant. Returns `None` if a token is already issued for
+    /// the same grant.
+    pub async fn store_token(
+        &mut self,
+        address: Address,
+        token_hash: &[u8],
+        grantee: &str,
+        expires_at: DateTime<Utc>,
+    ) -> QueryResult<Option<i64>> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO read_tokens (account_address, token_hash, grantee, expires_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (account_address, grantee, expires_at) DO NOTHING
+            RETURNING id
+            "#,
+            address.as_bytes(),
+            token_hash,
+            grantee,
+            expires_at
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| record.id);
+
+        metrics::histogram!("sql.read_tokens.store_token", start.elapsed());
+        Ok(id)
+    }
+
+    /// Loads the token by its hash, unless it's expired or revoked.
+    pub async fn get_active_token(
+        &mut self,
+        token_hash: &[u8],
+    ) -> QueryResult<Option<StorageReadToken>> {
+        let start = Instant::now();
+        let token = sqlx::query_as!(
+            StorageReadToken,
+            r#"
+            SELECT * FROM read_tokens
+            WHERE token_hash = $1 AND revoked_at IS NULL AND expires_at > now()
+            "#,
+            token_hash
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.read_tokens.get_active_token", start.elapsed());
+        Ok(token)
+    }
+
+    /// Revokes the token issued for the account. Returns `false` if there is no such token
+    /// or it's already revoked.
+    pub async fn revoke_token(&mut self, id: i64, address: Address) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            r#"
+            UPDATE read_tokens SET revoked_at = now()
+            WHERE id = $1 AND account_address = $2 AND revoked_at IS NULL
+            "#,
+            id,
+            address.as_bytes()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.read_tokens.revoke_token", start.elapsed());
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,16 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageReadToken {
+    pub id: i64,
+    pub account_address: Vec<u8>,
+    pub token_hash: Vec<u8>,
+    pub grantee: String,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
@@ -262,6 +262,7 @@ pub fn gen_unique_aggregated_operation_with_txs(
             AggregatedOperation::PublishProofBlocksOnchain(BlocksProofOperation {
                 blocks: vec![block],
                 proof: Default::default(),
+                proof_system: Default::default(),
             })
         }
         AggregatedActionType::ExecuteBlocks => {
@@ -0,0 +1,42 @@
+// Workspace imports
+use zksync_types::AccountId;
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the account stays frozen until it's unfrozen, and the history is kept.
+#[db_test]
+async fn freeze_and_unfreeze(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let account_id = AccountId(3);
+    let mut schema = storage.account_freezes_schema();
+    assert!(schema.active_freeze(account_id).await?.is_none());
+    assert!(!schema.unfreeze(account_id, "no hold").await?);
+
+    assert!(schema.freeze(account_id, "case #1").await?);
+    // The account can't be frozen twice.
+    assert!(!schema.freeze(account_id, "case #2").await?);
+    schema.freeze(AccountId(4), "case #3").await?;
+
+    let freeze = schema.active_freeze(account_id).await?.unwrap();
+    assert_eq!(freeze.account_id, account_id);
+    assert_eq!(freeze.reason, "case #1");
+    assert_eq!(schema.load_active_freezes().await?.len(), 2);
+
+    assert!(schema.unfreeze(account_id, "case #1 is closed").await?);
+    assert!(schema.active_freeze(account_id).await?.is_none());
+    assert_eq!(schema.load_active_freezes().await?.len(), 1);
+
+    // The account may be frozen again, the lifted freeze stays in the history.
+    assert!(schema.freeze(account_id, "case #4").await?);
+    let history = schema.load_history(account_id).await?;
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].reason, "case #4");
+    assert!(history[0].unfrozen_at.is_none());
+    assert_eq!(
+        history[1].unfreeze_reason.as_deref(),
+        Some("case #1 is closed")
+    );
+    assert!(history[1].unfrozen_at.is_some());
+
+    Ok(())
+}
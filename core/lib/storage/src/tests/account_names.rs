@@ -0,0 +1,101 @@
+// Workspace imports
+use zksync_types::{tx::TxHash, Address};
+// Local imports
+use crate::chain::operations::records::NewExecutedTransaction;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn fee_tx(tx_hash: TxHash, success: bool) -> NewExecutedTransaction {
+    NewExecutedTransaction {
+        block_number: 1,
+        tx_hash: tx_hash.as_ref().to_vec(),
+        tx: Default::default(),
+        operation: Default::default(),
+        from_account: Default::default(),
+        to_account: None,
+        success,
+        fail_reason: None,
+        block_index: None,
+        primary_account_address: Default::default(),
+        nonce: Default::default(),
+        created_at: chrono::Utc::now(),
+        eth_sign_data: None,
+        batch_id: None,
+        affected_accounts: Vec::new(),
+        used_tokens: Vec::new(),
+    }
+}
+
+/// Checks that the name is resolved only after its fee transaction is executed and
+/// can be taken again if the fee transaction fails.
+#[db_test]
+async fn account_names(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let first_address = Address::repeat_byte(0x01);
+    let second_address = Address::repeat_byte(0x02);
+    let first_hash = TxHash::from_slice(&[1; 32]).unwrap();
+    let second_hash = TxHash::from_slice(&[2; 32]).unwrap();
+    let third_hash = TxHash::from_slice(&[3; 32]).unwrap();
+
+    assert!(
+        storage
+            .account_names_schema()
+            .reserve_account_name("alice", first_address, first_hash)
+            .await?
+    );
+    // The fee transaction is not executed yet, so the name is pending.
+    assert!(
+        !storage
+            .account_names_schema()
+            .reserve_account_name("alice", second_address, second_hash)
+            .await?
+    );
+    let record = storage
+        .account_names_schema()
+        .load_account_name("alice")
+        .await?
+        .unwrap();
+    assert_eq!(record.fee_tx_success, None);
+    assert!(storage
+        .account_names_schema()
+        .resolve_account_name("alice")
+        .await?
+        .is_none());
+
+    // Once the fee transaction fails, the name can be registered by someone else.
+    storage
+        .chain()
+        .operations_schema()
+        .store_executed_tx(fee_tx(first_hash, false))
+        .await?;
+    assert!(
+        storage
+            .account_names_schema()
+            .reserve_account_name("alice", second_address, second_hash)
+            .await?
+    );
+    storage
+        .chain()
+        .operations_schema()
+        .store_executed_tx(fee_tx(second_hash, true))
+        .await?;
+    assert_eq!(
+        storage
+            .account_names_schema()
+            .resolve_account_name("alice")
+            .await?,
+        Some(second_address)
+    );
+    assert!(
+        !storage
+            .account_names_schema()
+            .reserve_account_name("alice", first_address, third_hash)
+            .await?
+    );
+    assert!(storage
+        .account_names_schema()
+        .load_account_name("bob")
+        .await?
+        .is_none());
+
+    Ok(())
+}
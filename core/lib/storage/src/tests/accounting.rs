@@ -0,0 +1,81 @@
+// Built-in imports
+use std::str::FromStr;
+// External imports
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{
+    aggregated_operations::{AggregatedActionType, AggregatedOperation},
+    BlockNumber, H256, U256,
+};
+// Local imports
+use crate::test_data::{gen_sample_block, gen_unique_aggregated_operation};
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the L1 cost of the aggregated operation is split between its blocks.
+#[db_test]
+async fn operation_costs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    for block_number in 1..=2 {
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(gen_sample_block(
+                BlockNumber(block_number),
+                100,
+                Default::default(),
+            ))
+            .await?;
+        storage
+            .accounting_schema()
+            .record_block_fees(BlockNumber(block_number))
+            .await?;
+    }
+    let mut operation =
+        gen_unique_aggregated_operation(BlockNumber(1), AggregatedActionType::CommitBlocks, 100);
+    if let AggregatedOperation::CommitBlocks(commit) = &mut operation {
+        commit
+            .blocks
+            .push(gen_sample_block(BlockNumber(2), 100, Default::default()));
+    }
+    storage
+        .chain()
+        .operations_schema()
+        .store_aggregated_action(operation)
+        .await?;
+    let (op_id, _) = storage
+        .chain()
+        .operations_schema()
+        .get_aggregated_op_that_affects_block(AggregatedActionType::CommitBlocks, BlockNumber(2))
+        .await?
+        .expect("Operation is not stored");
+
+    // 100000 gas for 10 gwei.
+    let gas_price = U256::from(10_000_000_000u64);
+    storage
+        .accounting_schema()
+        .record_operation_cost(op_id, H256::random(), U256::from(100_000), gas_price)
+        .await?;
+    // The cost is recorded only once.
+    storage
+        .accounting_schema()
+        .record_operation_cost(op_id, H256::random(), U256::from(200_000), gas_price)
+        .await?;
+
+    let blocks = storage
+        .accounting_schema()
+        .load_blocks_profitability(BlockNumber(1), BlockNumber(2))
+        .await?;
+    assert_eq!(blocks.len(), 2);
+    let expected_cost = BigDecimal::from_str("500000000000000").unwrap();
+    for block in &blocks {
+        assert_eq!(block.l1_cost_wei, expected_cost);
+        assert_eq!(block.revenue_usd, BigDecimal::from(0));
+    }
+    assert!(storage
+        .accounting_schema()
+        .load_block_fees(BlockNumber(1), BlockNumber(2))
+        .await?
+        .is_empty());
+
+    Ok(())
+}
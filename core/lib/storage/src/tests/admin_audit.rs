@@ -0,0 +1,59 @@
+// External imports
+use serde_json::json;
+// Workspace imports
+// Local imports
+use crate::admin_audit::records::NewAuditEntry;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn entry(action: &str, target: &str) -> NewAuditEntry {
+    NewAuditEntry {
+        actor: "alice".to_string(),
+        action: action.to_string(),
+        target: Some(target.to_string()),
+        before_value: None,
+        after_value: Some(json!({ "reason": "case #1" })),
+    }
+}
+
+/// Checks that the entries are loaded in order and filtered, and can't be altered.
+#[db_test]
+async fn audit_log(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let first_id = storage
+        .admin_audit_schema()
+        .record(&entry("account_freeze", "3"))
+        .await?;
+    storage
+        .admin_audit_schema()
+        .record(&entry("fee_sponsor_store", "0x01"))
+        .await?;
+    storage
+        .admin_audit_schema()
+        .record(&entry("account_freeze", "4"))
+        .await?;
+
+    let entries = storage
+        .admin_audit_schema()
+        .load_entries(0, None, None, None, 10)
+        .await?;
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].id, first_id);
+    assert_eq!(entries[0].actor, "alice");
+    assert_eq!(entries[0].after_value, Some(json!({ "reason": "case #1" })));
+
+    let entries = storage
+        .admin_audit_schema()
+        .load_entries(first_id, None, None, Some("account_freeze"), 10)
+        .await?;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].target.as_deref(), Some("4"));
+
+    // The log is append-only.
+    let mut transaction = storage.start_transaction().await?;
+    let result = sqlx::query("DELETE FROM admin_audit_log")
+        .execute(transaction.conn())
+        .await;
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -217,6 +217,83 @@ async fn stored_accounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Checks that the states loaded in bulk match the states of the separate accounts.
+#[db_test]
+async fn bulk_account_states(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let mut rng = create_rng();
+
+    let block_size = 100;
+    let (accounts_block, updates_block) = apply_random_updates(AccountMap::default(), &mut rng);
+    BlockSchema(&mut storage)
+        .save_full_block(gen_sample_block(
+            BlockNumber(1),
+            block_size,
+            Default::default(),
+        ))
+        .await?;
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(1), &updates_block, 0)
+        .await?;
+
+    let mut addresses: Vec<Address> = accounts_block
+        .values()
+        .map(|account| account.address)
+        .collect();
+    // Unknown address is returned without states.
+    addresses.push(Address::repeat_byte(0xab));
+
+    let mut expected = Vec::new();
+    for address in &addresses {
+        expected.push(
+            AccountSchema(&mut storage)
+                .account_state_by_address(*address)
+                .await?,
+        );
+    }
+    let states = AccountSchema(&mut storage)
+        .account_states_by_addresses(&addresses)
+        .await?;
+    assert!(states.last().unwrap().state.committed.is_none());
+    assert!(states[0].state.committed.is_some());
+    assert!(states.iter().all(|state| state.state.verified.is_none()));
+    assert!(states[..states.len() - 1]
+        .iter()
+        .all(|state| *state.last_committed_block == 1 && *state.last_verified_block == 0));
+    let states: Vec<_> = states.into_iter().map(|state| state.state).collect();
+    assert_eq!(states, expected);
+
+    // Verify the block, now the accounts have both states.
+    OperationsSchema(&mut storage)
+        .store_aggregated_action(gen_unique_aggregated_operation(
+            BlockNumber(1),
+            AggregatedActionType::ExecuteBlocks,
+            block_size,
+        ))
+        .await?;
+    StateSchema(&mut storage)
+        .apply_state_update(BlockNumber(1))
+        .await?;
+
+    let mut expected = Vec::new();
+    for address in &addresses {
+        expected.push(
+            AccountSchema(&mut storage)
+                .account_state_by_address(*address)
+                .await?,
+        );
+    }
+    let states = AccountSchema(&mut storage)
+        .account_states_by_addresses(&addresses)
+        .await?;
+    assert!(states[0].state.verified.is_some());
+    assert_eq!(*states[0].last_verified_block, 1);
+    let states: Vec<_> = states.into_iter().map(|state| state.state).collect();
+    assert_eq!(states, expected);
+
+    Ok(())
+}
+
 #[db_test]
 async fn test_get_balance(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     let _lock = ACCOUNT_MUTEX.lock().await;
@@ -534,3 +611,50 @@ async fn test_get_nft_owner(mut storage: StorageProcessor<'_>) -> QueryResult<()
 
     Ok(())
 }
+
+/// Checks that the historical states of the accounts are restored from the updates,
+/// starting from the nearest snapshot.
+#[db_test]
+async fn account_state_at_block(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+
+    let mut states = vec![AccountMap::default()];
+    for block_number in 1..=3 {
+        let (accounts_map, updates) =
+            apply_random_updates(states.last().cloned().unwrap(), &mut rng);
+        StateSchema(&mut storage)
+            .commit_state_update(BlockNumber(block_number), &updates, 0)
+            .await?;
+        states.push(accounts_map);
+    }
+
+    for (block_number, accounts_map) in states.iter().enumerate().skip(1) {
+        for (&account_id, account) in accounts_map {
+            let (_, restored) = AccountSchema(&mut storage)
+                .account_state_at_block(account_id, BlockNumber(block_number as u32))
+                .await?;
+            assert_eq!(restored.as_ref(), Some(account));
+        }
+    }
+    // Account doesn't exist before the first block.
+    let account_id = *states[1].keys().next().unwrap();
+    let (last_update_block, restored) = AccountSchema(&mut storage)
+        .account_state_at_block(account_id, BlockNumber(0))
+        .await?;
+    assert_eq!((last_update_block, restored), (BlockNumber(0), None));
+
+    // The nearest snapshot is used as a starting point.
+    AccountSchema(&mut storage)
+        .store_account_snapshot(account_id, BlockNumber(2), BlockNumber(2), None)
+        .await?;
+    let (last_update_block, restored) = AccountSchema(&mut storage)
+        .account_state_at_block(account_id, BlockNumber(2))
+        .await?;
+    assert_eq!((last_update_block, restored), (BlockNumber(2), None));
+    let (_, restored) = AccountSchema(&mut storage)
+        .account_state_at_block(account_id, BlockNumber(1))
+        .await?;
+    assert_eq!(restored.as_ref(), states[1].get(&account_id));
+
+    Ok(())
+}
@@ -1393,3 +1393,75 @@ async fn test_incomplete_block_logic(mut storage: StorageProcessor<'_>) -> Query
 
     Ok(())
 }
+
+/// Checks that the 2FA flag of the committed `ChangePubKey` transactions updates the account type.
+#[db_test]
+async fn change_pubkey_no_2fa_flag(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    use crate::chain::account::records::EthAccountType;
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::{operations::ChangePubKeyOp, ExecutedOperations, ExecutedTx, ZkSyncOp};
+
+    let account_id = AccountId(0xbabe);
+    let account = ZkSyncAccount::rand();
+    account.set_account_id(Some(account_id));
+
+    let change_pubkey = |no_2fa: Option<bool>| {
+        let mut tx = account.sign_change_pubkey_tx(
+            None,
+            true,
+            TokenId(0),
+            Default::default(),
+            ChangePubKeyType::ECDSA,
+            Default::default(),
+        );
+        tx.no_2fa = no_2fa;
+        let op = ZkSyncOp::ChangePubKeyOffchain(Box::new(ChangePubKeyOp { tx, account_id }));
+        ExecutedOperations::Tx(Box::new(ExecutedTx {
+            signed_tx: op.try_get_tx().unwrap().into(),
+            success: true,
+            op: Some(op),
+            fail_reason: None,
+            block_index: None,
+            created_at: chrono::Utc::now(),
+            batch_id: None,
+        }))
+    };
+    let pub_key_hash = account.pubkey_hash;
+
+    // 2FA is disabled for the new key on request.
+    BlockSchema(&mut storage)
+        .save_block_transactions(BlockNumber(1), vec![change_pubkey(Some(true))])
+        .await?;
+    let account_type = storage
+        .chain()
+        .account_schema()
+        .account_type_by_id(account_id)
+        .await?;
+    assert!(
+        matches!(account_type, Some(EthAccountType::No2FA(Some(hash))) if hash == pub_key_hash)
+    );
+
+    // The policy is kept by the transactions without the flag.
+    BlockSchema(&mut storage)
+        .save_block_transactions(BlockNumber(2), vec![change_pubkey(None)])
+        .await?;
+    let account_type = storage
+        .chain()
+        .account_schema()
+        .account_type_by_id(account_id)
+        .await?;
+    assert!(matches!(account_type, Some(EthAccountType::No2FA(Some(_)))));
+
+    // 2FA is enabled back.
+    BlockSchema(&mut storage)
+        .save_block_transactions(BlockNumber(3), vec![change_pubkey(Some(false))])
+        .await?;
+    let account_type = storage
+        .chain()
+        .account_schema()
+        .account_type_by_id(account_id)
+        .await?;
+    assert!(matches!(account_type, Some(EthAccountType::Owned)));
+
+    Ok(())
+}
@@ -6,7 +6,7 @@ use zksync_types::{
     block::{Block, ExecutedOperations},
     mempool::SignedTxVariant,
     priority_ops::FullExit,
-    tx::{ChangePubKey, Transfer, TxHash, Withdraw},
+    tx::{ChangePubKey, ChangePubKeyEthAuthData, Transfer, TxHash, Withdraw},
     AccountId, Address, BlockNumber, ExecutedPriorityOp, ExecutedTx, FullExitOp, Nonce, PriorityOp,
     SignedZkSyncTx, TokenId, ZkSyncOp, ZkSyncPriorityOp, ZkSyncTx, H256,
 };
@@ -15,7 +15,7 @@ use crate::test_data::gen_eth_sign_data;
 use crate::tests::db_test;
 use crate::{
     chain::{
-        mempool::MempoolSchema,
+        mempool::{MempoolSchema, ONCHAIN_AUTH_RELEASED, ONCHAIN_AUTH_WAITING},
         operations::{
             records::{NewExecutedPriorityOperation, NewExecutedTransaction},
             OperationsSchema,
@@ -258,6 +258,62 @@ async fn remove_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Checks that idempotency keys are stored per account and the first stored hash is kept.
+#[db_test]
+async fn idempotency_keys(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = zksync_txs();
+    let (first_hash, second_hash) = (txs[0].hash(), txs[1].hash());
+
+    assert!(MempoolSchema(&mut storage)
+        .get_tx_by_idempotency_key(AccountId(1), "key")
+        .await?
+        .is_none());
+
+    MempoolSchema(&mut storage)
+        .store_idempotency_key(AccountId(1), "key", first_hash)
+        .await?;
+    // The key is already used, so the stored hash doesn't change.
+    MempoolSchema(&mut storage)
+        .store_idempotency_key(AccountId(1), "key", second_hash)
+        .await?;
+    // The same key of another account is independent.
+    MempoolSchema(&mut storage)
+        .store_idempotency_key(AccountId(2), "key", second_hash)
+        .await?;
+
+    assert_eq!(
+        MempoolSchema(&mut storage)
+            .get_tx_by_idempotency_key(AccountId(1), "key")
+            .await?,
+        Some(first_hash)
+    );
+    assert_eq!(
+        MempoolSchema(&mut storage)
+            .get_tx_by_idempotency_key(AccountId(2), "key")
+            .await?,
+        Some(second_hash)
+    );
+
+    // Keys are not removed until they're old enough.
+    MempoolSchema(&mut storage)
+        .remove_old_idempotency_keys(chrono::Duration::hours(1))
+        .await?;
+    assert!(MempoolSchema(&mut storage)
+        .get_tx_by_idempotency_key(AccountId(1), "key")
+        .await?
+        .is_some());
+
+    MempoolSchema(&mut storage)
+        .remove_old_idempotency_keys(chrono::Duration::zero())
+        .await?;
+    assert!(MempoolSchema(&mut storage)
+        .get_tx_by_idempotency_key(AccountId(1), "key")
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
 /// Checks that already committed txs are removed by `collect_garbage` method.
 #[db_test]
 async fn collect_garbage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -512,3 +568,110 @@ async fn test_return_executed_txs_to_mempool(mut storage: StorageProcessor<'_>)
     assert_eq!(block_tx.variance_name(), "FullExit");
     Ok(())
 }
+
+/// Checks the lifecycle of the queued onchain `ChangePubKey` transactions.
+#[db_test]
+async fn onchain_auth_queue(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let change_pubkey = ChangePubKey::new(
+        AccountId(123),
+        Address::random(),
+        Default::default(),
+        TokenId(0),
+        Default::default(),
+        Nonce(13),
+        Default::default(),
+        None,
+        Some(ChangePubKeyEthAuthData::Onchain),
+    );
+    let tx_hash = ZkSyncTx::ChangePubKey(Box::new(change_pubkey.clone())).hash();
+
+    MempoolSchema(&mut storage)
+        .enqueue_onchain_auth_tx(tx_hash, &change_pubkey)
+        .await?;
+    // Queueing the same transaction again has no effect.
+    MempoolSchema(&mut storage)
+        .enqueue_onchain_auth_tx(tx_hash, &change_pubkey)
+        .await?;
+
+    let waiting = MempoolSchema(&mut storage)
+        .load_waiting_onchain_auth_txs()
+        .await?;
+    assert_eq!(waiting.len(), 1);
+    assert_eq!(waiting[0].tx_hash, tx_hash.as_ref().to_vec());
+    assert_eq!(waiting[0].nonce, 13);
+    assert_eq!(waiting[0].status, ONCHAIN_AUTH_WAITING);
+    let stored: ChangePubKey = serde_json::from_value(waiting[0].tx.clone())?;
+    assert_eq!(stored.account, change_pubkey.account);
+    assert!(
+        MempoolSchema(&mut storage)
+            .has_waiting_onchain_auth_tx(change_pubkey.account, Nonce(13))
+            .await?
+    );
+    assert!(
+        !MempoolSchema(&mut storage)
+            .has_waiting_onchain_auth_tx(change_pubkey.account, Nonce(14))
+            .await?
+    );
+
+    MempoolSchema(&mut storage)
+        .update_onchain_auth_tx_status(tx_hash, ONCHAIN_AUTH_RELEASED, None)
+        .await?;
+    assert!(
+        !MempoolSchema(&mut storage)
+            .has_waiting_onchain_auth_tx(change_pubkey.account, Nonce(13))
+            .await?
+    );
+    assert!(MempoolSchema(&mut storage)
+        .load_waiting_onchain_auth_txs()
+        .await?
+        .is_empty());
+    let released = MempoolSchema(&mut storage)
+        .get_onchain_auth_tx(tx_hash)
+        .await?
+        .expect("Queued transaction is not found");
+    assert_eq!(released.status, ONCHAIN_AUTH_RELEASED);
+
+    assert!(MempoolSchema(&mut storage)
+        .get_onchain_auth_tx(TxHash::default())
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+/// Checks that the hashes of the stored priority operations are loaded along with their status.
+#[db_test]
+async fn priority_ops_hashes(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let priority_op = |serial_id: u64, eth_hash: H256| PriorityOp {
+        serial_id,
+        data: ZkSyncPriorityOp::FullExit(FullExit {
+            account_id: AccountId(1),
+            eth_address: Address::random(),
+            token: TokenId(0),
+            is_legacy: false,
+        }),
+        deadline_block: 100,
+        eth_hash,
+        eth_block: 10,
+        eth_block_index: Some(1),
+    };
+
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&[priority_op(1, H256::repeat_byte(1))], true)
+        .await?;
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&[priority_op(2, H256::repeat_byte(2))], false)
+        .await?;
+
+    let mut hashes = MempoolSchema(&mut storage)
+        .load_priority_ops_hashes(&[1, 2, 3])
+        .await?;
+    hashes.sort_by_key(|op| op.serial_id);
+    assert_eq!(hashes.len(), 2);
+    assert_eq!(hashes[0].eth_hash, H256::repeat_byte(1).as_bytes().to_vec());
+    assert!(hashes[0].confirmed);
+    assert_eq!(hashes[1].serial_id, 2);
+    assert!(!hashes[1].confirmed);
+
+    Ok(())
+}
@@ -2,8 +2,9 @@ mod accounts;
 mod block;
 mod mempool;
 mod operations;
-mod operations_ext;
+pub mod operations_ext;
 mod state;
+mod stats;
 mod tree_cache;
 
 pub use block::apply_random_updates;
@@ -3,7 +3,7 @@ use chrono::{Duration, Utc};
 // Workspace imports
 use zksync_types::{
     aggregated_operations::AggregatedActionType, Address, BlockNumber, Deposit, SequentialTxId,
-    ZkSyncPriorityOp, H256,
+    TxHash, ZkSyncPriorityOp, H256,
 };
 // Local imports
 use crate::chain::mempool::MempoolSchema;
@@ -11,7 +11,7 @@ use crate::{
     chain::{
         block::BlockSchema,
         operations::{
-            records::{NewExecutedPriorityOperation, NewExecutedTransaction},
+            records::{NewExecutedOperation, NewExecutedPriorityOperation, NewExecutedTransaction},
             OperationsSchema,
         },
     },
@@ -293,6 +293,148 @@ async fn transaction_resent(mut storage: StorageProcessor<'_>) -> QueryResult<()
     Ok(())
 }
 
+/// Checks that the transactions stored with one bulk insert are handled the same way
+/// as the ones stored one by one.
+#[db_test]
+async fn executed_operations_bulk(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const BLOCK_NUMBER: i64 = 1;
+
+    let stored_tx = NewExecutedTransaction {
+        block_number: BLOCK_NUMBER,
+        tx_hash: vec![0x12, 0xAD, 0xBE, 0xEF],
+        tx: Default::default(),
+        operation: Default::default(),
+        from_account: Default::default(),
+        to_account: None,
+        success: false,
+        fail_reason: Some("Nonce mismatch".to_string()),
+        block_index: None,
+        primary_account_address: Default::default(),
+        nonce: Default::default(),
+        created_at: chrono::Utc::now(),
+        eth_sign_data: None,
+        batch_id: None,
+        affected_accounts: vec![Address::repeat_byte(0x11).as_bytes().to_vec()],
+        used_tokens: vec![0],
+    };
+    let resent_tx = NewExecutedTransaction {
+        success: true,
+        fail_reason: None,
+        block_index: Some(0),
+        ..stored_tx.clone()
+    };
+    let other_tx = NewExecutedTransaction {
+        tx_hash: vec![0x34, 0xAD, 0xBE, 0xEF],
+        block_index: Some(1),
+        batch_id: Some(1),
+        ..resent_tx.clone()
+    };
+
+    // The failed tx is stored first, and is replaced by the successful one from the bulk.
+    OperationsSchema(&mut storage)
+        .store_executed_tx(stored_tx.clone())
+        .await?;
+    OperationsSchema(&mut storage)
+        .store_executed_txs(vec![resent_tx.clone(), other_tx.clone(), stored_tx.clone()])
+        .await?;
+
+    for tx in [&resent_tx, &other_tx] {
+        let loaded_tx = OperationsSchema(&mut storage)
+            .get_executed_operation(tx.tx_hash.as_ref())
+            .await?
+            .unwrap();
+        assert!(loaded_tx.success);
+        assert_eq!(loaded_tx.block_index, tx.block_index);
+        assert_eq!(loaded_tx.batch_id, tx.batch_id);
+    }
+    let block_txs = BlockSchema(&mut storage)
+        .get_block_transactions(BlockNumber(BLOCK_NUMBER as u32))
+        .await?;
+    assert_eq!(block_txs.len(), 2);
+
+    Ok(())
+}
+
+/// Checks that the operations of one block get their sequence numbers in the execution order,
+/// regardless of their type and success.
+#[db_test]
+async fn executed_operations_sequence_numbers(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    const BLOCK_NUMBER: i64 = 1;
+
+    let tx = |hash_byte: u8, block_index: Option<i32>| {
+        NewExecutedOperation::Tx(NewExecutedTransaction {
+            block_number: BLOCK_NUMBER,
+            tx_hash: vec![hash_byte; 32],
+            tx: Default::default(),
+            operation: Default::default(),
+            from_account: Default::default(),
+            to_account: None,
+            success: block_index.is_some(),
+            fail_reason: block_index.map_or(Some("Nonce mismatch".to_string()), |_| None),
+            block_index,
+            primary_account_address: Default::default(),
+            nonce: Default::default(),
+            created_at: chrono::Utc::now(),
+            eth_sign_data: None,
+            batch_id: None,
+            affected_accounts: Vec::new(),
+            used_tokens: Vec::new(),
+        })
+    };
+    let deposit = |hash_byte: u8, block_index: i32, serial_id: i64| {
+        NewExecutedOperation::PriorityOp(NewExecutedPriorityOperation {
+            block_number: BLOCK_NUMBER,
+            block_index,
+            operation: Default::default(),
+            from_account: Default::default(),
+            to_account: Default::default(),
+            priority_op_serialid: serial_id,
+            deadline_block: 100,
+            eth_hash: vec![hash_byte; 32],
+            eth_block: 10,
+            created_at: chrono::Utc::now(),
+            tx_hash: vec![hash_byte; 32],
+            eth_block_index: Some(serial_id),
+            affected_accounts: Vec::new(),
+            token: 0,
+        })
+    };
+
+    // Deposits, failed and successful transactions interleaved in the execution order.
+    let hashes = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+    let operations = vec![
+        deposit(hashes[0], 0, 0),
+        tx(hashes[1], None),
+        tx(hashes[2], Some(1)),
+        deposit(hashes[3], 2, 1),
+        tx(hashes[4], Some(3)),
+        tx(hashes[5], None),
+    ];
+    OperationsSchema(&mut storage)
+        .store_executed_operations(operations)
+        .await?;
+
+    let mut sequence_numbers = Vec::new();
+    for hash_byte in hashes.iter() {
+        let sequence_number = storage
+            .chain()
+            .operations_ext_schema()
+            .get_tx_sequence_number(TxHash::from_slice(&[*hash_byte; 32]).unwrap())
+            .await?
+            .expect("Operation must have a sequence number");
+        sequence_numbers.push(sequence_number);
+    }
+    assert!(
+        sequence_numbers.windows(2).all(|pair| pair[0] < pair[1]),
+        "Sequence numbers do not follow the execution order: {:?}",
+        sequence_numbers
+    );
+
+    Ok(())
+}
+
 /// Checks that rejected transactions are removed correctly depending on the given age limit.
 #[db_test]
 async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -220,6 +220,45 @@ async fn state_diff(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Checks that the diff of the accounts matches the corresponding part of the state diff.
+#[db_test]
+async fn accounts_diff(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+
+    let mut accounts_map = AccountMap::default();
+    for block_number in 1..=3 {
+        let (new_accounts_map, updates) = apply_random_updates(accounts_map, &mut rng);
+        accounts_map = new_accounts_map;
+        StateSchema(&mut storage)
+            .commit_state_update(BlockNumber(block_number), &updates, 0)
+            .await?;
+    }
+
+    let account_ids: Vec<AccountId> = accounts_map.keys().copied().step_by(2).collect();
+    let (_, state_diff) = StateSchema(&mut storage)
+        .load_state_diff(BlockNumber(1), Some(BlockNumber(3)))
+        .await?
+        .expect("Can't load the diff");
+    let expected_diff: Vec<_> = state_diff
+        .into_iter()
+        .filter(|(id, _)| account_ids.contains(id))
+        .collect();
+
+    let accounts_diff = StateSchema(&mut storage)
+        .load_accounts_diff(&account_ids, BlockNumber(1), BlockNumber(3))
+        .await?;
+    assert!(!accounts_diff.is_empty());
+    assert_eq!(accounts_diff, expected_diff);
+
+    // There are no updates in the future blocks.
+    let accounts_diff = StateSchema(&mut storage)
+        .load_accounts_diff(&account_ids, BlockNumber(3), BlockNumber(5))
+        .await?;
+    assert!(accounts_diff.is_empty());
+
+    Ok(())
+}
+
 /// Checks if account updates are removed correctly.
 #[db_test]
 async fn test_remove_account_updates(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -0,0 +1,118 @@
+// External imports
+use chrono::Utc;
+use num::{BigUint, Zero};
+// Workspace imports
+use zksync_types::{AccountId, AccountUpdate, Address, BlockNumber, Nonce, TokenId};
+// Local imports
+use crate::tests::{db_test, ACCOUNT_MUTEX};
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the total value locked is computed for every token from the applied balances.
+#[db_test]
+async fn tvl_aggregation(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let updates = vec![
+        (
+            AccountId(1),
+            AccountUpdate::Create {
+                address: Address::random(),
+                nonce: Nonce(0),
+            },
+        ),
+        (
+            AccountId(2),
+            AccountUpdate::Create {
+                address: Address::random(),
+                nonce: Nonce(0),
+            },
+        ),
+        (
+            AccountId(1),
+            AccountUpdate::UpdateBalance {
+                old_nonce: Nonce(0),
+                new_nonce: Nonce(0),
+                balance_update: (TokenId(0), BigUint::zero(), BigUint::from(100u32)),
+            },
+        ),
+        (
+            AccountId(2),
+            AccountUpdate::UpdateBalance {
+                old_nonce: Nonce(0),
+                new_nonce: Nonce(0),
+                balance_update: (TokenId(0), BigUint::zero(), BigUint::from(50u32)),
+            },
+        ),
+        (
+            AccountId(2),
+            AccountUpdate::UpdateBalance {
+                old_nonce: Nonce(0),
+                new_nonce: Nonce(0),
+                balance_update: (TokenId(1), BigUint::zero(), BigUint::from(7u32)),
+            },
+        ),
+    ];
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(BlockNumber(1), &updates, 0)
+        .await?;
+    storage
+        .chain()
+        .state_schema()
+        .apply_state_update(BlockNumber(1))
+        .await?;
+
+    storage.chain().stats_schema().update_tvl().await?;
+    // The update is idempotent.
+    storage.chain().stats_schema().update_tvl().await?;
+
+    let tvl: Vec<_> = storage
+        .chain()
+        .stats_schema()
+        .load_tvl()
+        .await?
+        .into_iter()
+        .map(|tvl| (tvl.token_id, tvl.amount.to_string()))
+        .collect();
+    assert_eq!(tvl, vec![(0, "150".to_string()), (1, "7".to_string())]);
+
+    Ok(())
+}
+
+/// Checks that the daily statistics are stored once per day and can be recomputed.
+#[db_test]
+async fn daily_stats_aggregation(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let today = Utc::today().naive_utc();
+
+    storage
+        .chain()
+        .stats_schema()
+        .aggregate_daily_stats(today)
+        .await?;
+    storage
+        .chain()
+        .stats_schema()
+        .aggregate_daily_stats(today)
+        .await?;
+
+    let stats = storage
+        .chain()
+        .stats_schema()
+        .load_daily_stats(today)
+        .await?;
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].day, today);
+    assert_eq!(stats[0].active_accounts, 0);
+    assert_eq!(stats[0].avg_finality_secs, None);
+
+    let tx_counts = storage
+        .chain()
+        .stats_schema()
+        .load_daily_tx_counts(today)
+        .await?;
+    assert!(tx_counts.is_empty());
+    let fees = storage.chain().stats_schema().load_total_fees().await?;
+    assert!(fees.is_empty());
+
+    Ok(())
+}
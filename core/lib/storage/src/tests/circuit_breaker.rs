@@ -0,0 +1,31 @@
+// Workspace imports
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the circuit breaker stays tripped until it's reset.
+#[db_test]
+async fn trip_and_reset(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut schema = storage.circuit_breaker_schema();
+    assert!(schema.load_active_trip().await?.is_none());
+    assert_eq!(schema.reset().await?, 0);
+
+    schema
+        .trip("witness_generator", "root hash mismatch")
+        .await?;
+    schema.trip("admin", "manual").await?;
+    // The earliest trip is reported.
+    let trip = schema.load_active_trip().await?.unwrap();
+    assert_eq!(trip.source, "witness_generator");
+    assert_eq!(trip.reason, "root hash mismatch");
+    assert!(trip.resolved_at.is_none());
+
+    assert_eq!(schema.reset().await?, 2);
+    assert!(schema.load_active_trip().await?.is_none());
+
+    // The breaker may be tripped again after the reset.
+    schema.trip("admin", "manual").await?;
+    assert_eq!(schema.load_active_trip().await?.unwrap().source, "admin");
+
+    Ok(())
+}
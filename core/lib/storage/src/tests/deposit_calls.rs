@@ -0,0 +1,103 @@
+// External imports
+// Workspace imports
+use zksync_types::{Address, DepositCallEvent, H256};
+// Local imports
+use crate::chain::operations::records::NewExecutedPriorityOperation;
+use crate::deposit_calls::{DEPOSIT_CALL_PENDING, DEPOSIT_CALL_REJECTED, DEPOSIT_CALL_SUBMITTED};
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn deposit_call(serial_id: u64) -> DepositCallEvent {
+    DepositCallEvent {
+        serial_id,
+        owner: Address::repeat_byte(0x01),
+        call: b"[]".to_vec(),
+        eth_block: 1,
+    }
+}
+
+fn executed_deposit(serial_id: u64) -> NewExecutedPriorityOperation {
+    NewExecutedPriorityOperation {
+        block_number: 1,
+        block_index: serial_id as i32,
+        operation: Default::default(),
+        from_account: Address::repeat_byte(0x01).as_bytes().to_vec(),
+        to_account: Address::repeat_byte(0x01).as_bytes().to_vec(),
+        priority_op_serialid: serial_id as i64,
+        deadline_block: 10,
+        eth_hash: H256::from_low_u64_be(serial_id).as_bytes().to_vec(),
+        eth_block: 1,
+        created_at: chrono::Utc::now(),
+        tx_hash: Default::default(),
+        eth_block_index: Some(1),
+        affected_accounts: Default::default(),
+        token: Default::default(),
+    }
+}
+
+/// Checks that the deposit calls are loaded once their deposits are executed,
+/// and only until they're processed.
+#[db_test]
+async fn deposit_calls(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    for serial_id in 1..=3 {
+        storage
+            .deposit_calls_schema()
+            .store_deposit_call(&deposit_call(serial_id))
+            .await?;
+    }
+    // Storing the same call twice is allowed.
+    storage
+        .deposit_calls_schema()
+        .store_deposit_call(&deposit_call(1))
+        .await?;
+    assert!(storage
+        .deposit_calls_schema()
+        .load_executed_deposit_calls()
+        .await?
+        .is_empty());
+
+    for serial_id in 1..=2 {
+        storage
+            .chain()
+            .operations_schema()
+            .store_executed_priority_op(executed_deposit(serial_id))
+            .await?;
+    }
+    let calls = storage
+        .deposit_calls_schema()
+        .load_executed_deposit_calls()
+        .await?;
+    let serial_ids: Vec<_> = calls.iter().map(|call| call.serial_id).collect();
+    assert_eq!(serial_ids, vec![1, 2]);
+    assert_eq!(calls[0].status, DEPOSIT_CALL_PENDING);
+    assert_eq!(calls[0].call, b"[]".to_vec());
+
+    storage
+        .deposit_calls_schema()
+        .set_deposit_call_status(1, DEPOSIT_CALL_SUBMITTED, None)
+        .await?;
+    storage
+        .deposit_calls_schema()
+        .set_deposit_call_status(2, DEPOSIT_CALL_REJECTED, Some("Invalid call".to_string()))
+        .await?;
+    assert!(storage
+        .deposit_calls_schema()
+        .load_executed_deposit_calls()
+        .await?
+        .is_empty());
+
+    let rejected = storage
+        .deposit_calls_schema()
+        .get_deposit_call(2)
+        .await?
+        .unwrap();
+    assert_eq!(rejected.status, DEPOSIT_CALL_REJECTED);
+    assert_eq!(rejected.error.as_deref(), Some("Invalid call"));
+    assert!(storage
+        .deposit_calls_schema()
+        .get_deposit_call(4)
+        .await?
+        .is_none());
+
+    Ok(())
+}
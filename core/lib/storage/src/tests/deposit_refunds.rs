@@ -0,0 +1,97 @@
+// External imports
+use num::BigUint;
+// Workspace imports
+use zksync_types::{Address, Deposit, PriorityOp, TokenId, ZkSyncPriorityOp, H256};
+// Local imports
+use crate::chain::operations::records::NewExecutedPriorityOperation;
+use crate::deposit_refunds::REFUND_REASON_EXPIRED;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn deposit(serial_id: u64, from: Address, to: Address) -> PriorityOp {
+    PriorityOp {
+        serial_id,
+        data: ZkSyncPriorityOp::Deposit(Deposit {
+            from,
+            token: TokenId(0),
+            amount: BigUint::from(100500u32),
+            to,
+        }),
+        deadline_block: 10,
+        eth_hash: H256::from_low_u64_be(serial_id),
+        eth_block: 1,
+        eth_block_index: Some(1),
+    }
+}
+
+/// Checks that the refundable deposits are stored, loaded by the address
+/// and removed once executed.
+#[db_test]
+async fn refundable_deposits(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let sender = Address::repeat_byte(0x01);
+    let recipient = Address::repeat_byte(0x02);
+
+    storage
+        .deposit_refunds_schema()
+        .store_refundable_deposit(&deposit(1, sender, recipient), REFUND_REASON_EXPIRED)
+        .await?;
+    storage
+        .deposit_refunds_schema()
+        .store_refundable_deposit(&deposit(2, recipient, recipient), REFUND_REASON_EXPIRED)
+        .await?;
+    // Storing the same deposit twice is allowed.
+    storage
+        .deposit_refunds_schema()
+        .store_refundable_deposit(&deposit(1, sender, recipient), REFUND_REASON_EXPIRED)
+        .await?;
+
+    let sent = storage
+        .deposit_refunds_schema()
+        .load_refundable_deposits(sender)
+        .await?;
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].serial_id, 1);
+    assert_eq!(sent[0].reason, REFUND_REASON_EXPIRED);
+
+    let received = storage
+        .deposit_refunds_schema()
+        .load_refundable_deposits(recipient)
+        .await?;
+    assert_eq!(received.len(), 2);
+
+    // The first deposit is eventually executed by the network.
+    storage
+        .chain()
+        .operations_schema()
+        .store_executed_priority_op(NewExecutedPriorityOperation {
+            block_number: 1,
+            block_index: 1,
+            operation: Default::default(),
+            from_account: sender.as_bytes().to_vec(),
+            to_account: recipient.as_bytes().to_vec(),
+            priority_op_serialid: 1,
+            deadline_block: 10,
+            eth_hash: H256::from_low_u64_be(1).as_bytes().to_vec(),
+            eth_block: 1,
+            created_at: chrono::Utc::now(),
+            tx_hash: Default::default(),
+            eth_block_index: Some(1),
+            affected_accounts: Default::default(),
+            token: Default::default(),
+        })
+        .await?;
+    let removed = storage
+        .deposit_refunds_schema()
+        .remove_executed_deposits()
+        .await?;
+    assert_eq!(removed, 1);
+
+    let received = storage
+        .deposit_refunds_schema()
+        .load_refundable_deposits(recipient)
+        .await?;
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].serial_id, 2);
+
+    Ok(())
+}
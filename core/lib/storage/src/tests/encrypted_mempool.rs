@@ -0,0 +1,33 @@
+// External imports
+// Workspace imports
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the amount of the pending blobs is limited, and the oldest ones are taken first.
+#[db_test]
+async fn encrypted_mempool_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut schema = storage.encrypted_mempool_schema();
+
+    let first = schema.insert_tx(b"first", 2).await?.unwrap();
+    let second = schema.insert_tx(b"second", 2).await?.unwrap();
+    assert!(second > first);
+    assert!(schema.insert_tx(b"third", 2).await?.is_none());
+
+    let txs = schema.take_txs(1).await?;
+    assert_eq!(txs.len(), 1);
+    assert_eq!(txs[0].id, first);
+    assert_eq!(txs[0].blob, b"first");
+
+    schema.insert_tx(b"third", 2).await?.unwrap();
+    let blobs: Vec<_> = schema
+        .take_txs(10)
+        .await?
+        .into_iter()
+        .map(|tx| tx.blob)
+        .collect();
+    assert_eq!(blobs, vec![b"second".to_vec(), b"third".to_vec()]);
+    assert!(schema.take_txs(10).await?.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+// Workspace imports
+use zksync_types::{Address, FeeSponsor, TokenId};
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the gas of the sponsored transfers is charged within the daily budget only.
+#[db_test]
+async fn sponsor_gas_budget(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::repeat_byte(0x11);
+    let token = TokenId(1);
+    let mut schema = storage.fee_sponsors_schema();
+    assert!(schema.load_sponsor(address, token).await?.is_none());
+    // Transfers of the unknown sponsor are never charged.
+    assert!(!schema.charge_gas(address, token, 100).await?);
+
+    let mut sponsor = FeeSponsor {
+        address,
+        token,
+        daily_gas_budget: 1000,
+    };
+    schema.store_sponsor(&sponsor).await?;
+    assert_eq!(
+        schema.load_sponsor(address, token).await?,
+        Some(sponsor.clone())
+    );
+    assert!(schema.load_sponsor(address, TokenId(2)).await?.is_none());
+
+    assert!(!schema.charge_gas(address, token, 1001).await?);
+    assert!(schema.charge_gas(address, token, 600).await?);
+    assert!(schema.charge_gas(address, token, 400).await?);
+    assert!(!schema.charge_gas(address, token, 1).await?);
+
+    let usage = schema.load_sponsors_usage().await?;
+    assert_eq!(usage.len(), 1);
+    assert_eq!(usage[0].gas_used_today, 1000);
+
+    // Raising the budget allows to spend the rest of it today.
+    sponsor.daily_gas_budget = 1500;
+    schema.store_sponsor(&sponsor).await?;
+    assert!(schema.charge_gas(address, token, 500).await?);
+    assert!(!schema.charge_gas(address, token, 1).await?);
+
+    assert!(schema.remove_sponsor(address, token).await?);
+    assert!(!schema.remove_sponsor(address, token).await?);
+    assert!(schema.load_sponsors_usage().await?.is_empty());
+
+    Ok(())
+}
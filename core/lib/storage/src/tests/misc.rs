@@ -2,7 +2,7 @@ use sqlx::types::BigDecimal;
 use zksync_types::TokenId;
 
 use crate::tests::db_test;
-use crate::{misc::records::Subsidy, misc::MiscSchema};
+use crate::{misc::records::Subsidy, misc::MiscSchema, misc::LEADER_LOCK_KEY};
 use crate::{QueryResult, StorageProcessor};
 
 fn get_subsidy(name: String, value: u64) -> Subsidy {
@@ -54,3 +54,62 @@ async fn stored_subsidy(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
 
     Ok(())
 }
+
+/// Checks taking and releasing the advisory lock.
+#[db_test]
+async fn advisory_lock(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(
+        !MiscSchema(&mut storage)
+            .holds_advisory_lock(LEADER_LOCK_KEY)
+            .await?
+    );
+    assert!(
+        MiscSchema(&mut storage)
+            .try_acquire_advisory_lock(LEADER_LOCK_KEY)
+            .await?
+    );
+    assert!(
+        MiscSchema(&mut storage)
+            .holds_advisory_lock(LEADER_LOCK_KEY)
+            .await?
+    );
+    assert!(
+        !MiscSchema(&mut storage)
+            .holds_advisory_lock(LEADER_LOCK_KEY + 1)
+            .await?
+    );
+
+    assert!(
+        MiscSchema(&mut storage)
+            .release_advisory_lock(LEADER_LOCK_KEY)
+            .await?
+    );
+    assert!(
+        !MiscSchema(&mut storage)
+            .holds_advisory_lock(LEADER_LOCK_KEY)
+            .await?
+    );
+    assert!(
+        !MiscSchema(&mut storage)
+            .release_advisory_lock(LEADER_LOCK_KEY)
+            .await?
+    );
+
+    // The lock taken by two `int` keys with the same bits is a different lock.
+    sqlx::query("SELECT pg_advisory_lock($1, $2)")
+        .bind((LEADER_LOCK_KEY >> 32) as i32)
+        .bind(LEADER_LOCK_KEY as i32)
+        .execute(storage.conn())
+        .await?;
+    assert!(
+        !MiscSchema(&mut storage)
+            .holds_advisory_lock(LEADER_LOCK_KEY)
+            .await?
+    );
+    sqlx::query("SELECT pg_advisory_unlock($1, $2)")
+        .bind((LEADER_LOCK_KEY >> 32) as i32)
+        .bind(LEADER_LOCK_KEY as i32)
+        .execute(storage.conn())
+        .await?;
+    Ok(())
+}
@@ -23,15 +23,32 @@ use tokio::sync::Mutex;
 // Workspace imports
 use zksync_crypto::rand::{SeedableRng, XorShiftRng};
 
+mod account_freezes;
+mod account_names;
+mod accounting;
+mod admin_audit;
 pub(crate) mod chain;
+mod circuit_breaker;
 mod config;
 mod data_restore;
+mod deposit_calls;
+mod deposit_refunds;
+mod encrypted_mempool;
 mod ethereum;
 mod event;
+mod fee_sponsors;
 mod forced_exit_requests;
 mod misc;
+mod private_txs;
 mod prover;
+mod read_tokens;
 mod tokens;
+mod travel_rule;
+mod treasury;
+mod watch_lists;
+mod weth_unwraps;
+mod withdrawal_eth_txs;
+mod withdrawal_timelocks;
 
 pub use db_test_macro::test as db_test;
 
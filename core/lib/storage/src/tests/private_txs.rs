@@ -0,0 +1,135 @@
+// External imports
+use chrono::Utc;
+// Workspace imports
+use zksync_types::{
+    tx::{Transfer, TxHash},
+    AccountId, Address, Nonce, SignedZkSyncTx, TokenId, ZkSyncTx,
+};
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn transfer(account_id: u32) -> SignedZkSyncTx {
+    let transfer = Transfer::new(
+        AccountId(account_id),
+        Address::random(),
+        Address::random(),
+        TokenId(0),
+        100u32.into(),
+        10u32.into(),
+        Nonce(0),
+        Default::default(),
+        None,
+    );
+    SignedZkSyncTx {
+        tx: ZkSyncTx::Transfer(Box::new(transfer)),
+        eth_sign_data: None,
+        created_at: Utc::now(),
+    }
+}
+
+/// Checks that the name and the token of the client are unique.
+#[db_test]
+async fn private_tx_clients(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut schema = storage.private_txs_schema();
+
+    let id = schema.create_client("Trader", &[1; 32], 10).await?.unwrap();
+    assert!(schema
+        .create_client("Trader", &[2; 32], 10)
+        .await?
+        .is_none());
+    assert!(schema.create_client("Other", &[1; 32], 10).await?.is_none());
+
+    let client = schema.get_client_by_token(&[1; 32]).await?.unwrap();
+    assert_eq!(client.id, id);
+    assert_eq!(client.name, "Trader");
+    assert_eq!(client.max_batches_per_minute, 10);
+    assert!(schema.get_client_by_token(&[2; 32]).await?.is_none());
+    assert_eq!(schema.load_clients().await?, vec![client]);
+
+    assert!(schema.remove_client("Trader").await?);
+    assert!(!schema.remove_client("Trader").await?);
+    assert!(schema.get_client_by_token(&[1; 32]).await?.is_none());
+
+    Ok(())
+}
+
+/// Checks that the private transactions are proposed as usual, but are hidden from the API
+/// queries until they are removed from the mempool.
+#[db_test]
+async fn private_txs_visibility(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = vec![transfer(1), transfer(2)];
+    let tx_hashes: Vec<TxHash> = txs.iter().map(|tx| tx.hash()).collect();
+    let batch_hash = TxHash::batch_hash(&tx_hashes);
+    let public_tx = transfer(3);
+
+    storage
+        .chain()
+        .mempool_schema()
+        .insert_batch(&txs, Vec::new())
+        .await?;
+    storage
+        .chain()
+        .mempool_schema()
+        .insert_tx(&public_tx)
+        .await?;
+    storage
+        .private_txs_schema()
+        .mark_private_txs(Some(1), &tx_hashes)
+        .await?;
+
+    assert_eq!(
+        storage.chain().mempool_schema().load_txs(&[]).await?.len(),
+        2
+    );
+    assert!(
+        storage
+            .private_txs_schema()
+            .is_private_tx(tx_hashes[0])
+            .await?
+    );
+    assert!(
+        !storage
+            .private_txs_schema()
+            .is_private_tx(public_tx.hash())
+            .await?
+    );
+    assert!(storage
+        .chain()
+        .operations_ext_schema()
+        .tx_data_api_v02(tx_hashes[0].as_ref())
+        .await?
+        .is_none());
+    assert!(storage
+        .chain()
+        .operations_ext_schema()
+        .tx_receipt_api_v02(tx_hashes[1].as_ref())
+        .await?
+        .is_none());
+    assert!(storage
+        .chain()
+        .mempool_schema()
+        .get_queued_batch_info(batch_hash)
+        .await?
+        .is_none());
+    assert!(storage
+        .chain()
+        .operations_ext_schema()
+        .tx_data_api_v02(public_tx.hash().as_ref())
+        .await?
+        .is_some());
+
+    storage
+        .chain()
+        .mempool_schema()
+        .remove_txs(&tx_hashes)
+        .await?;
+    assert!(
+        !storage
+            .private_txs_schema()
+            .is_private_tx(tx_hashes[0])
+            .await?
+    );
+
+    Ok(())
+}
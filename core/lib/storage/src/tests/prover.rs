@@ -1,16 +1,20 @@
 // External imports
 use anyhow::format_err;
+use chrono::Utc;
 use once_cell::sync::Lazy;
 use tokio::sync::Mutex;
 // Workspace imports
 use zksync_types::{
-    prover::{ProverJob, ProverJobType},
-    BlockNumber,
+    prover::{ProofSystemVersion, ProverJob, ProverJobType},
+    BlockNumber, H256,
 };
 // Local imports
 use crate::test_data::{gen_sample_block, get_sample_aggregated_proof, get_sample_single_proof};
 use crate::tests::db_test;
-use crate::{prover::ProverSchema, QueryResult, StorageProcessor};
+use crate::{
+    prover::{records::ProverBacklog, ProverSchema},
+    QueryResult, StorageProcessor,
+};
 
 static MUTEX: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
@@ -55,10 +59,16 @@ async fn test_store_proof(storage: &mut StorageProcessor<'_>) -> QueryResult<()>
     let aggregated_proof = get_sample_aggregated_proof();
 
     let stored_proof = ProverSchema(storage)
-        .store_proof(1, BlockNumber(1), &proof)
+        .store_proof(1, BlockNumber(1), &proof, ProofSystemVersion::PlonkV1)
         .await;
     let stored_aggregated_proof = ProverSchema(storage)
-        .store_aggregated_proof(1, BlockNumber(1), BlockNumber(1), &aggregated_proof)
+        .store_aggregated_proof(
+            1,
+            BlockNumber(1),
+            BlockNumber(1),
+            &aggregated_proof,
+            ProofSystemVersion::PlonkV1,
+        )
         .await;
 
     assert!(stored_proof
@@ -102,7 +112,12 @@ async fn test_store_proof(storage: &mut StorageProcessor<'_>) -> QueryResult<()>
 
     // Store proofs.
     let stored_proof = ProverSchema(storage)
-        .store_proof(stored_job_id, BlockNumber(1), &proof)
+        .store_proof(
+            stored_job_id,
+            BlockNumber(1),
+            &proof,
+            ProofSystemVersion::PlonkV1,
+        )
         .await;
     let stored_aggregated_proof = ProverSchema(storage)
         .store_aggregated_proof(
@@ -110,6 +125,7 @@ async fn test_store_proof(storage: &mut StorageProcessor<'_>) -> QueryResult<()>
             BlockNumber(1),
             BlockNumber(1),
             &aggregated_proof,
+            ProofSystemVersion::PlonkV1,
         )
         .await;
 
@@ -123,7 +139,27 @@ async fn test_store_proof(storage: &mut StorageProcessor<'_>) -> QueryResult<()>
         .await?;
 
     assert!(loaded_proof.is_some());
-    assert!(loaded_aggregated_proof.is_some());
+    assert_eq!(
+        loaded_aggregated_proof.map(|(_, proof_system)| proof_system),
+        Some(ProofSystemVersion::PlonkV1)
+    );
+
+    // Content hash of the result is recorded once, the repeated publication replaces it.
+    assert!(ProverSchema(storage)
+        .load_job_result_hash(stored_job_id)
+        .await?
+        .is_none());
+    for content_hash in [H256::repeat_byte(0x01), H256::repeat_byte(0x02)] {
+        ProverSchema(storage)
+            .store_job_result_hash(stored_job_id, content_hash, "zstd", 100)
+            .await?;
+        assert_eq!(
+            ProverSchema(storage)
+                .load_job_result_hash(stored_job_id)
+                .await?,
+            Some(content_hash)
+        );
+    }
 
     Ok(())
 }
@@ -198,10 +234,22 @@ async fn pending_jobs_count(storage: &mut StorageProcessor<'_>) -> QueryResult<(
             third_job.job_id,
             third_job.first_block,
             &get_sample_single_proof(),
+            ProofSystemVersion::PlonkV1,
         )
         .await?;
     let jobs_count = ProverSchema(storage).pending_jobs_count().await?;
     assert_eq!(jobs_count, 2);
+    // Two proofs were stored by `test_store_proof`.
+    let recently = Utc::now() - chrono::Duration::hours(1);
+    assert_eq!(
+        ProverSchema(storage).prover_backlog(recently).await?,
+        ProverBacklog {
+            idle_jobs: 0,
+            in_progress_jobs: 2,
+            done_jobs: 3,
+            active_provers: 1,
+        }
+    );
 
     ProverSchema(storage)
         .record_prover_stop("test_prover")
@@ -209,6 +257,9 @@ async fn pending_jobs_count(storage: &mut StorageProcessor<'_>) -> QueryResult<(
 
     let jobs_count = ProverSchema(storage).pending_jobs_count().await?;
     assert_eq!(jobs_count, 2);
+    let backlog = ProverSchema(storage).prover_backlog(Utc::now()).await?;
+    assert_eq!((backlog.idle_jobs, backlog.done_jobs), (2, 0));
+    assert_eq!(backlog.active_provers, 0);
 
     Ok(())
 }
@@ -334,7 +385,12 @@ async fn test_remove_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()
             .await?;
         let job_id = get_idle_job_from_queue(&mut storage).await?.job_id;
         ProverSchema(&mut storage)
-            .store_proof(job_id, BlockNumber(block_number), &proof)
+            .store_proof(
+                job_id,
+                BlockNumber(block_number),
+                &proof,
+                ProofSystemVersion::PlonkV1,
+            )
             .await?;
     }
 
@@ -367,7 +423,13 @@ async fn test_remove_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()
         .await?;
     let job_id = get_idle_job_from_queue(&mut storage).await?.job_id;
     ProverSchema(&mut storage)
-        .store_aggregated_proof(job_id, BlockNumber(1), BlockNumber(2), &aggregated_proof)
+        .store_aggregated_proof(
+            job_id,
+            BlockNumber(1),
+            BlockNumber(2),
+            &aggregated_proof,
+            ProofSystemVersion::PlonkV1,
+        )
         .await?;
 
     ProverSchema(&mut storage)
@@ -381,7 +443,13 @@ async fn test_remove_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()
         .await?;
     let job_id = get_idle_job_from_queue(&mut storage).await?.job_id;
     ProverSchema(&mut storage)
-        .store_aggregated_proof(job_id, BlockNumber(3), BlockNumber(5), &aggregated_proof)
+        .store_aggregated_proof(
+            job_id,
+            BlockNumber(3),
+            BlockNumber(5),
+            &aggregated_proof,
+            ProofSystemVersion::PlonkV1,
+        )
         .await?;
 
     // Remove aggregated proofs for blocks with numbers greater than 3. It means that proof for 3-5 blocks should be deleted.
@@ -441,3 +509,51 @@ async fn test_remove_prover_jobs(mut storage: StorageProcessor<'_>) -> QueryResu
 
     Ok(())
 }
+
+/// Checks that the cached proofs outlive the reverts and are stored for the re-applied blocks.
+#[db_test]
+async fn test_proof_cache(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    let proof = get_sample_single_proof();
+    let commitment = H256::repeat_byte(0x11);
+    assert!(ProverSchema(&mut storage)
+        .load_cached_proof(commitment)
+        .await?
+        .is_none());
+
+    ProverSchema(&mut storage)
+        .store_cached_proof(commitment, &proof, ProofSystemVersion::PlonkV1)
+        .await?;
+    // Repeated caching is a no-op.
+    ProverSchema(&mut storage)
+        .store_cached_proof(commitment, &proof, ProofSystemVersion::PlonkV1)
+        .await?;
+    ProverSchema(&mut storage)
+        .remove_proofs(BlockNumber(0))
+        .await?;
+    let (cached_proof, proof_system) = ProverSchema(&mut storage)
+        .load_cached_proof(commitment)
+        .await?
+        .expect("Proof is not cached");
+    assert_eq!(proof_system, ProofSystemVersion::PlonkV1);
+
+    // Block is marked as proven without the job to be taken by the provers.
+    ProverSchema(&mut storage)
+        .store_proof_from_cache(BlockNumber(1), &cached_proof, proof_system)
+        .await?;
+    assert!(ProverSchema(&mut storage)
+        .load_proof(BlockNumber(1))
+        .await?
+        .is_some());
+    assert_eq!(
+        ProverSchema(&mut storage)
+            .get_last_block_prover_job_queue(ProverJobType::SingleProof)
+            .await?,
+        BlockNumber(1)
+    );
+    assert_eq!(ProverSchema(&mut storage).pending_jobs_count().await?, 0);
+
+    Ok(())
+}
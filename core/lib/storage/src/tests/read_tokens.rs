@@ -0,0 +1,44 @@
+// External imports
+use chrono::{Duration, SubsecRound, Utc};
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the grant can be used once, and the revoked and expired tokens are not active.
+#[db_test]
+async fn read_tokens(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::repeat_byte(1);
+    let expires_at = Utc::now().trunc_subsecs(0) + Duration::days(30);
+    let mut schema = storage.read_tokens_schema();
+
+    let id = schema
+        .store_token(address, &[1; 32], "Auditor", expires_at)
+        .await?
+        .unwrap();
+    // The same grant can't be used to issue another token.
+    assert!(schema
+        .store_token(address, &[2; 32], "Auditor", expires_at)
+        .await?
+        .is_none());
+    let token = schema.get_active_token(&[1; 32]).await?.unwrap();
+    assert_eq!(token.id, id);
+    assert_eq!(token.account_address, address.as_bytes());
+    assert_eq!(token.expires_at, expires_at);
+    assert!(schema.get_active_token(&[2; 32]).await?.is_none());
+
+    // Only the owner of the token can revoke it.
+    assert!(!schema.revoke_token(id, Address::repeat_byte(2)).await?);
+    assert!(schema.revoke_token(id, address).await?);
+    assert!(!schema.revoke_token(id, address).await?);
+    assert!(schema.get_active_token(&[1; 32]).await?.is_none());
+
+    schema
+        .store_token(address, &[3; 32], "Auditor", Utc::now() - Duration::days(1))
+        .await?
+        .unwrap();
+    assert!(schema.get_active_token(&[3; 32]).await?.is_none());
+
+    Ok(())
+}
@@ -4,10 +4,12 @@ use std::str::FromStr;
 use chrono::Utc;
 use num::{rational::Ratio, BigUint};
 // Workspace imports
+use zksync_api_types::v02::pagination::{PaginationDirection, PaginationQuery};
 use zksync_test_account::ZkSyncAccount;
 use zksync_types::{
-    tokens::TokenMarketVolume, AccountId, Address, BlockNumber, ExecutedOperations, ExecutedTx,
-    Token, TokenId, TokenKind, TokenLike, TokenPrice, WithdrawNFTOp, ZkSyncOp, H256,
+    tokens::TokenMarketVolume, AccountId, AccountUpdate, Address, BlockNumber, ExecutedOperations,
+    ExecutedTx, Nonce, Token, TokenId, TokenKind, TokenLike, TokenPrice, WithdrawNFTOp, ZkSyncOp,
+    H256, NFT,
 };
 use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 // Local imports
@@ -340,5 +342,229 @@ async fn test_nfts_with_factories(mut storage: StorageProcessor<'_>) -> QueryRes
     assert_eq!(nft.current_factory, new_factory_address);
     assert_eq!(nft.withdrawn_factory.unwrap(), default_factory_address);
 
+    let factory = storage
+        .tokens_schema()
+        .load_nft_factory(AccountId(creator_account_id as u32))
+        .await?
+        .unwrap();
+    assert_eq!(factory.factory_address(), new_factory_address);
+    assert!(storage
+        .tokens_schema()
+        .load_nft_factory(AccountId(creator_account_id as u32 + 1))
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+/// Checks the resolution of the symbols shared by several tokens.
+#[db_test]
+async fn token_symbol_collisions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let usdc = Token::new(
+        TokenId(1),
+        Address::from_low_u64_be(1),
+        "USDC",
+        6,
+        TokenKind::ERC20,
+    );
+    let bridged_usdc = Token::new(
+        TokenId(2),
+        Address::from_low_u64_be(2),
+        "usdc",
+        6,
+        TokenKind::ERC20,
+    );
+    storage
+        .tokens_schema()
+        .store_or_update_token(usdc.clone())
+        .await?;
+    let symbol = TokenLike::Symbol("USDC".to_string());
+    assert_eq!(
+        storage.tokens_schema().get_token(symbol.clone()).await?,
+        Some(usdc.clone())
+    );
+
+    // Shared symbol resolves to none of the tokens, unless qualified with the ID.
+    storage
+        .tokens_schema()
+        .store_or_update_token(bridged_usdc.clone())
+        .await?;
+    assert_eq!(
+        storage.tokens_schema().get_token(symbol.clone()).await?,
+        None
+    );
+    assert_eq!(
+        storage.tokens_schema().get_tokens_by_symbol("Usdc").await?,
+        vec![usdc.clone(), bridged_usdc.clone()]
+    );
+    assert_eq!(
+        storage
+            .tokens_schema()
+            .get_token(TokenLike::Symbol(bridged_usdc.qualified_symbol()))
+            .await?,
+        Some(bridged_usdc.clone())
+    );
+    assert_eq!(
+        storage
+            .tokens_schema()
+            .get_token(TokenLike::Symbol("ETH-2".to_string()))
+            .await?,
+        None
+    );
+
+    // Aliases select the canonical token and name the other one.
+    storage
+        .tokens_schema()
+        .store_symbol_alias("USDC", usdc.id)
+        .await?;
+    storage
+        .tokens_schema()
+        .store_symbol_alias("USDC.e", bridged_usdc.id)
+        .await?;
+    assert_eq!(
+        storage.tokens_schema().get_token(symbol.clone()).await?,
+        Some(usdc.clone())
+    );
+    assert_eq!(
+        storage
+            .tokens_schema()
+            .get_token(TokenLike::Symbol("usdc.E".to_string()))
+            .await?,
+        Some(bridged_usdc.clone())
+    );
+    let aliases = storage.tokens_schema().load_symbol_aliases().await?;
+    assert_eq!(
+        aliases
+            .iter()
+            .map(|alias| (alias.alias.as_str(), alias.token_id))
+            .collect::<Vec<_>>(),
+        vec![("usdc", usdc.id), ("usdc.e", bridged_usdc.id)]
+    );
+
+    assert!(storage.tokens_schema().remove_symbol_alias("USDC").await?);
+    assert!(!storage.tokens_schema().remove_symbol_alias("USDC").await?);
+    assert_eq!(storage.tokens_schema().get_token(symbol).await?, None);
+
+    Ok(())
+}
+
+/// Checks the pages of the NFTs owned by the account and minted by the creator.
+#[db_test]
+async fn nft_ownership_pages(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (creator_id, owner_id) = (AccountId(1), AccountId(2));
+    let creator_address = Address::random();
+    let mut updates = vec![
+        (
+            creator_id,
+            AccountUpdate::Create {
+                address: creator_address,
+                nonce: Nonce(0),
+            },
+        ),
+        (
+            owner_id,
+            AccountUpdate::Create {
+                address: Address::random(),
+                nonce: Nonce(0),
+            },
+        ),
+    ];
+    let nft_ids: Vec<_> = (0..3).map(|i| TokenId(MIN_NFT_TOKEN_ID + i)).collect();
+    for (serial_id, &token_id) in nft_ids.iter().enumerate() {
+        let nft = NFT::new(
+            token_id,
+            serial_id as u32,
+            creator_id,
+            creator_address,
+            Address::random(),
+            None,
+            H256::random(),
+        );
+        updates.push((
+            creator_id,
+            AccountUpdate::MintNFT {
+                token: nft,
+                nonce: Nonce(serial_id as u32),
+            },
+        ));
+    }
+    // The owner gets the first and the last NFTs, the creator keeps the second one.
+    for (i, &token_id) in nft_ids.iter().enumerate() {
+        let account_id = if i == 1 { creator_id } else { owner_id };
+        updates.push((
+            account_id,
+            AccountUpdate::UpdateBalance {
+                old_nonce: Nonce(0),
+                new_nonce: Nonce(0),
+                balance_update: (token_id, BigUint::from(0u32), BigUint::from(1u32)),
+            },
+        ));
+    }
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(BlockNumber(1), &updates, 0)
+        .await?;
+    storage
+        .chain()
+        .state_schema()
+        .apply_state_update(BlockNumber(1))
+        .await?;
+
+    let page = |from, limit, direction| PaginationQuery {
+        from,
+        limit,
+        direction,
+    };
+    let owned = storage
+        .tokens_schema()
+        .load_owned_nfts_page(owner_id, &page(TokenId(0), 10, PaginationDirection::Newer))
+        .await?;
+    let owned_ids: Vec<_> = owned.iter().map(|nft| nft.id).collect();
+    assert_eq!(owned_ids, vec![nft_ids[0], nft_ids[2]]);
+    let owned = storage
+        .tokens_schema()
+        .load_owned_nfts_page(owner_id, &page(nft_ids[1], 10, PaginationDirection::Older))
+        .await?;
+    assert_eq!(owned.len(), 1);
+    assert_eq!(owned[0].id, nft_ids[0]);
+    assert_eq!(
+        storage
+            .tokens_schema()
+            .get_owned_nfts_stats(owner_id)
+            .await?,
+        (2, Some(nft_ids[2]))
+    );
+    assert_eq!(
+        storage
+            .tokens_schema()
+            .get_owned_nfts_stats(AccountId(3))
+            .await?,
+        (0, None)
+    );
+
+    let minted = storage
+        .tokens_schema()
+        .load_minted_nfts_page(creator_id, &page(2, 2, PaginationDirection::Older))
+        .await?;
+    let serial_ids: Vec<_> = minted.iter().map(|nft| nft.serial_id).collect();
+    assert_eq!(serial_ids, vec![2, 1]);
+    assert_eq!(minted[0].id, nft_ids[2]);
+    assert_eq!(minted[0].creator_address, creator_address);
+    assert_eq!(
+        storage
+            .tokens_schema()
+            .get_minted_nfts_count(creator_id)
+            .await?,
+        3
+    );
+    assert_eq!(
+        storage
+            .tokens_schema()
+            .get_minted_nfts_count(owner_id)
+            .await?,
+        0
+    );
+
     Ok(())
 }
@@ -0,0 +1,110 @@
+// External imports
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{tx::TxHash, Address, TokenId};
+// Local imports
+use crate::tests::db_test;
+use crate::travel_rule::records::{StorageTravelRuleRecord, TravelRuleRecord};
+use crate::{QueryResult, StorageProcessor};
+
+fn record(hash_byte: u8, encrypted_metadata: Option<Vec<u8>>) -> TravelRuleRecord {
+    TravelRuleRecord {
+        tx_hash: TxHash::from_slice(&[hash_byte; 32]).unwrap(),
+        from: Address::repeat_byte(1),
+        to: Address::repeat_byte(2),
+        token_id: TokenId(0),
+        amount: BigDecimal::from(1_000_000),
+        amount_usd_scaled: Some(5_000_000_000),
+        encrypted_metadata,
+    }
+}
+
+/// Checks that the records are never overwritten, and only the records without the metadata are flagged.
+#[db_test]
+async fn travel_rule_records(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut schema = storage.travel_rule_schema();
+    let tx_hash = TxHash::from_slice(&[1; 32]).unwrap();
+    assert!(schema.get_record(tx_hash).await?.is_none());
+
+    assert!(schema.save_record(record(1, Some(vec![0xab; 16]))).await?);
+    // The metadata can't be replaced.
+    assert!(!schema.save_record(record(1, Some(vec![0xcd; 16]))).await?);
+    let stored = schema.get_record(tx_hash).await?.unwrap();
+    assert_eq!(stored.encrypted_metadata, Some(vec![0xab; 16]));
+    assert_eq!(stored.from_address, Address::repeat_byte(1).as_bytes());
+    assert_eq!(stored.amount_usd_scaled, Some(5_000_000_000));
+
+    schema.save_record(record(2, None)).await?;
+    schema.save_record(record(3, None)).await?;
+    let flagged = schema.load_flagged(10).await?;
+    assert_eq!(flagged.len(), 2);
+    assert!(flagged
+        .iter()
+        .all(|record| record.encrypted_metadata.is_none()));
+    assert_eq!(schema.load_flagged(1).await?.len(), 1);
+
+    Ok(())
+}
+
+/// Checks the pages of the transfers of the address, the records without the metadata are skipped.
+#[db_test]
+async fn travel_rule_account_records(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut schema = storage.travel_rule_schema();
+    for hash_byte in 1..=3 {
+        schema
+            .save_record(record(hash_byte, Some(vec![hash_byte; 16])))
+            .await?;
+    }
+    schema.save_record(record(4, None)).await?;
+    let mut other = record(5, Some(vec![5; 16]));
+    other.from = Address::repeat_byte(3);
+    other.to = Address::repeat_byte(4);
+    schema.save_record(other).await?;
+
+    let tx_hashes = |records: Vec<StorageTravelRuleRecord>| -> Vec<u8> {
+        records.iter().map(|record| record.tx_hash[0]).collect()
+    };
+    let records = schema
+        .load_account_records(Address::repeat_byte(2), None, 10)
+        .await?;
+    // Records are stored within the same transaction, so they are ordered by the hash.
+    assert_eq!(tx_hashes(records), vec![3, 2, 1]);
+    let records = schema
+        .load_account_records(
+            Address::repeat_byte(1),
+            Some(TxHash::from_slice(&[3; 32]).unwrap()),
+            1,
+        )
+        .await?;
+    assert_eq!(tx_hashes(records), vec![2]);
+    assert!(schema
+        .load_account_records(Address::repeat_byte(5), None, 10)
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+/// Checks that the records of the given transfers are loaded, except the ones without the metadata.
+#[db_test]
+async fn travel_rule_metadata_records(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut schema = storage.travel_rule_schema();
+    schema.save_record(record(1, Some(vec![1; 16]))).await?;
+    schema.save_record(record(2, None)).await?;
+    schema.save_record(record(3, Some(vec![3; 16]))).await?;
+
+    let tx_hashes: Vec<_> = (1..=4)
+        .map(|hash_byte| TxHash::from_slice(&[hash_byte; 32]).unwrap())
+        .collect();
+    let mut records: Vec<_> = schema
+        .load_metadata_records(&tx_hashes)
+        .await?
+        .into_iter()
+        .map(|record| record.tx_hash[0])
+        .collect();
+    records.sort_unstable();
+    assert_eq!(records, vec![1, 3]);
+    assert!(schema.load_metadata_records(&[]).await?.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+// External imports
+use sqlx::types::BigDecimal;
+// Local imports
+use crate::tests::db_test;
+use crate::treasury::{
+    records::NewTreasuryConversion, CONVERSION_COMMITTED, CONVERSION_FAILED, CONVERSION_SENT,
+};
+use crate::{QueryResult, StorageProcessor};
+
+fn conversion(token_id: i32) -> NewTreasuryConversion {
+    NewTreasuryConversion {
+        token_id,
+        amount: BigDecimal::from(1000),
+        target_token_id: 0,
+        expected_amount: BigDecimal::from(990),
+        route: "swap".to_string(),
+        tx_hash: vec![token_id as u8; 32],
+        details: serde_json::json!({ "slippage": 1.0 }),
+    }
+}
+
+/// Checks the lifecycle of the conversion audit records.
+#[db_test]
+async fn conversions_lifecycle(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let first = storage
+        .treasury_schema()
+        .store_conversion(conversion(1))
+        .await?;
+    let second = storage
+        .treasury_schema()
+        .store_conversion(conversion(2))
+        .await?;
+
+    let pending = storage.treasury_schema().load_pending_conversions().await?;
+    assert_eq!(pending.len(), 2);
+    assert!(pending
+        .iter()
+        .all(|record| record.status == CONVERSION_SENT));
+
+    storage
+        .treasury_schema()
+        .update_conversion_status(first, CONVERSION_COMMITTED, None)
+        .await?;
+    storage
+        .treasury_schema()
+        .update_conversion_status(
+            second,
+            CONVERSION_FAILED,
+            Some("Nonce mismatch".to_string()),
+        )
+        .await?;
+    assert!(storage
+        .treasury_schema()
+        .load_pending_conversions()
+        .await?
+        .is_empty());
+
+    // The newest conversions go first.
+    let conversions = storage.treasury_schema().load_conversions(10).await?;
+    assert_eq!(conversions.len(), 2);
+    assert_eq!(conversions[0].id, second);
+    assert_eq!(
+        conversions[0].fail_reason.as_deref(),
+        Some("Nonce mismatch")
+    );
+    assert_eq!(conversions[1].id, first);
+    assert_eq!(conversions[1].status, CONVERSION_COMMITTED);
+    assert_eq!(conversions[1].details, conversion(1).details);
+
+    assert_eq!(
+        storage.treasury_schema().load_conversions(1).await?.len(),
+        1
+    );
+
+    Ok(())
+}
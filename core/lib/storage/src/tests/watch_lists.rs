@@ -0,0 +1,143 @@
+// External imports
+use serde_json::json;
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{Address, BlockNumber, H256};
+// Local imports
+use crate::chain::operations::{
+    records::{NewExecutedPriorityOperation, NewExecutedTransaction},
+    OperationsSchema,
+};
+use crate::tests::db_test;
+use crate::watch_lists::{WATCHED_DEPOSIT, WATCHED_TRANSFER};
+use crate::{QueryResult, StorageProcessor};
+
+fn executed_transfer(
+    block_number: i64,
+    hash: u64,
+    to: Address,
+    success: bool,
+) -> NewExecutedTransaction {
+    NewExecutedTransaction {
+        block_number,
+        tx_hash: H256::from_low_u64_be(hash).as_bytes().to_vec(),
+        tx: json!({ "type": "Transfer", "token": 1, "amount": "100" }),
+        operation: Default::default(),
+        from_account: Address::repeat_byte(0x01).as_bytes().to_vec(),
+        to_account: Some(to.as_bytes().to_vec()),
+        success,
+        fail_reason: None,
+        block_index: None,
+        primary_account_address: Default::default(),
+        nonce: Default::default(),
+        created_at: chrono::Utc::now(),
+        eth_sign_data: None,
+        batch_id: None,
+        affected_accounts: Vec::new(),
+        used_tokens: Vec::new(),
+    }
+}
+
+fn executed_deposit(
+    block_number: i64,
+    serial_id: u64,
+    to: Address,
+) -> NewExecutedPriorityOperation {
+    NewExecutedPriorityOperation {
+        block_number,
+        block_index: serial_id as i32,
+        operation: json!({ "type": "Deposit", "priority_op": { "token": 2, "amount": "200" } }),
+        from_account: Address::repeat_byte(0x01).as_bytes().to_vec(),
+        to_account: to.as_bytes().to_vec(),
+        priority_op_serialid: serial_id as i64,
+        deadline_block: 100,
+        eth_hash: H256::from_low_u64_be(serial_id).as_bytes().to_vec(),
+        eth_block: 1,
+        created_at: chrono::Utc::now(),
+        tx_hash: H256::from_low_u64_be(100 + serial_id).as_bytes().to_vec(),
+        eth_block_index: Some(1),
+        affected_accounts: Default::default(),
+        token: 2,
+    }
+}
+
+/// Checks that the addresses are added to and removed from the watch list in bulk.
+#[db_test]
+async fn watch_addresses(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut schema = storage.watch_lists_schema();
+    let id = schema
+        .create_watch_list("exchange", "http://localhost/hook", BlockNumber(0))
+        .await?;
+    assert_eq!(schema.get_watch_list("exchange").await?.unwrap().id, id);
+    assert!(schema.get_watch_list("unknown").await?.is_none());
+
+    let addresses: Vec<_> = (0..1000).map(Address::from_low_u64_be).collect();
+    assert_eq!(schema.add_watch_addresses(id, &addresses).await?, 1000);
+    // Already watched addresses are skipped.
+    assert_eq!(schema.add_watch_addresses(id, &addresses[..10]).await?, 0);
+    assert_eq!(schema.count_watch_addresses(id).await?, 1000);
+
+    assert_eq!(
+        schema.remove_watch_addresses(id, &addresses[..100]).await?,
+        100
+    );
+    assert_eq!(schema.count_watch_addresses(id).await?, 900);
+
+    schema.remove_watch_list(id).await?;
+    assert!(schema.load_watch_lists().await?.is_empty());
+    assert_eq!(schema.count_watch_addresses(id).await?, 0);
+
+    Ok(())
+}
+
+/// Checks that only the successful incoming transfers and deposits of the watched addresses
+/// within the block range are loaded.
+#[db_test]
+async fn watched_operations(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (watched, other) = (Address::repeat_byte(0x02), Address::repeat_byte(0x03));
+    let mut operations = OperationsSchema(&mut storage);
+    operations
+        .store_executed_tx(executed_transfer(1, 1, watched, true))
+        .await?;
+    operations
+        .store_executed_tx(executed_transfer(1, 2, watched, false))
+        .await?;
+    operations
+        .store_executed_tx(executed_transfer(2, 3, other, true))
+        .await?;
+    operations
+        .store_executed_tx(executed_transfer(3, 4, watched, true))
+        .await?;
+    operations
+        .store_executed_priority_op(executed_deposit(2, 1, watched))
+        .await?;
+
+    let mut schema = storage.watch_lists_schema();
+    let id = schema
+        .create_watch_list("exchange", "http://localhost/hook", BlockNumber(0))
+        .await?;
+    schema.add_watch_addresses(id, &[watched]).await?;
+
+    let loaded = schema
+        .load_watched_operations(id, BlockNumber(0), BlockNumber(2))
+        .await?;
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[0].kind, WATCHED_TRANSFER);
+    assert_eq!(loaded[0].block_number, 1);
+    assert_eq!(loaded[0].token_id, 1);
+    assert_eq!(loaded[0].amount, BigDecimal::from(100));
+    assert_eq!(loaded[1].kind, WATCHED_DEPOSIT);
+    assert_eq!(loaded[1].token_id, 2);
+    assert_eq!(loaded[1].amount, BigDecimal::from(200));
+
+    let loaded = schema
+        .load_watched_operations(id, BlockNumber(2), BlockNumber(3))
+        .await?;
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].block_number, 3);
+
+    schema.set_last_block(id, BlockNumber(3)).await?;
+    assert_eq!(schema.load_watch_lists().await?[0].last_block, 3);
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+// External imports
+use num::BigUint;
+// Workspace imports
+use zksync_types::{
+    tx::TxHash, AccountId, Address, BlockNumber, Nonce, TokenId, Withdraw, ZkSyncTx, H256,
+};
+// Local imports
+use crate::chain::operations::records::NewExecutedTransaction;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn withdraw_tx(
+    block_number: i64,
+    nonce: u32,
+    unwrap_weth: bool,
+    success: bool,
+) -> (TxHash, NewExecutedTransaction) {
+    let mut withdraw = Withdraw::new(
+        AccountId(1),
+        Address::repeat_byte(0x01),
+        Address::repeat_byte(0x02),
+        TokenId(1),
+        BigUint::from(100u32),
+        BigUint::from(0u32),
+        Nonce(nonce),
+        Default::default(),
+        None,
+    );
+    withdraw.unwrap_weth = unwrap_weth;
+    let tx = ZkSyncTx::from(withdraw);
+    let tx_hash = tx.hash();
+    let executed = NewExecutedTransaction {
+        block_number,
+        tx_hash: tx_hash.as_ref().to_vec(),
+        tx: serde_json::to_value(&tx).unwrap(),
+        operation: Default::default(),
+        from_account: Address::repeat_byte(0x01).as_bytes().to_vec(),
+        to_account: Some(Address::repeat_byte(0x02).as_bytes().to_vec()),
+        success,
+        fail_reason: None,
+        block_index: None,
+        primary_account_address: Default::default(),
+        nonce: Default::default(),
+        created_at: chrono::Utc::now(),
+        eth_sign_data: None,
+        batch_id: None,
+        affected_accounts: Vec::new(),
+        used_tokens: Vec::new(),
+    };
+    (tx_hash, executed)
+}
+
+/// Checks that only the successful flagged withdrawals of the given blocks are recorded,
+/// and that they're pending until signed and unconfirmed until mined.
+#[db_test]
+async fn weth_unwraps(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (unwrapped, tx) = withdraw_tx(1, 0, true, true);
+    storage
+        .chain()
+        .operations_schema()
+        .store_executed_tx(tx)
+        .await?;
+    let (later, tx) = withdraw_tx(2, 1, true, true);
+    storage
+        .chain()
+        .operations_schema()
+        .store_executed_tx(tx)
+        .await?;
+    for (block_number, nonce, unwrap_weth, success) in [(1, 2, false, true), (1, 3, true, false)] {
+        let (_, tx) = withdraw_tx(block_number, nonce, unwrap_weth, success);
+        storage
+            .chain()
+            .operations_schema()
+            .store_executed_tx(tx)
+            .await?;
+    }
+
+    let mut schema = storage.weth_unwraps_schema();
+    assert_eq!(
+        schema
+            .store_executed_unwraps(BlockNumber(0), BlockNumber(1))
+            .await?,
+        1
+    );
+    // Already recorded withdrawals are skipped.
+    assert_eq!(
+        schema
+            .store_executed_unwraps(BlockNumber(0), BlockNumber(2))
+            .await?,
+        1
+    );
+    let pending = schema.load_pending_unwraps().await?;
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].tx_hash, unwrapped.as_ref());
+    assert_eq!(pending[0].recipient, Address::repeat_byte(0x01).as_bytes());
+    assert_eq!(pending[0].amount, 100u32.into());
+
+    // The signed transaction is unconfirmed until mined.
+    let eth_tx_hash = H256::repeat_byte(0x03);
+    schema
+        .save_unwrap_tx(&unwrapped, 5, eth_tx_hash, &[0xab; 8])
+        .await?;
+    let pending = schema.load_pending_unwraps().await?;
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].tx_hash, later.as_ref());
+    let unconfirmed = schema.load_unconfirmed_unwraps().await?;
+    assert_eq!(unconfirmed.len(), 1);
+    assert_eq!(unconfirmed[0].tx_hash, unwrapped.as_ref());
+    assert_eq!(
+        unconfirmed[0].eth_tx_hash,
+        Some(eth_tx_hash.as_bytes().to_vec())
+    );
+    assert_eq!(unconfirmed[0].eth_nonce, Some(5));
+    assert_eq!(unconfirmed[0].raw_tx, Some(vec![0xab; 8]));
+
+    schema.confirm_unwrap(&unwrapped).await?;
+    assert!(schema.load_unconfirmed_unwraps().await?.is_empty());
+    assert!(schema.get_unwrap(&unwrapped).await?.unwrap().confirmed);
+
+    Ok(())
+}
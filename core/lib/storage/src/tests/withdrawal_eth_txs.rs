@@ -0,0 +1,89 @@
+// External imports
+// Workspace imports
+use zksync_api_types::v02::transaction::Receipt;
+use zksync_types::BlockNumber;
+// Local imports
+use crate::tests::{
+    chain::operations_ext::{
+        commit_block, commit_schema_data, setup::TransactionsHistoryTestSetup, verify_block,
+    },
+    db_test,
+};
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the withdrawals are recorded once their blocks are executed, and that their
+/// receipts contain the L1 transactions.
+#[db_test]
+async fn withdrawal_eth_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+    commit_block(&mut storage, BlockNumber(1)).await?;
+    let (transfer_hash, withdraw_hash, withdraw_nft_hash) = (
+        setup.get_tx_hash(0, 2),
+        setup.get_tx_hash(0, 5),
+        setup.get_tx_hash(0, 7),
+    );
+
+    // Withdrawals of the committed blocks don't have the L1 transactions yet.
+    let mut schema = storage.withdrawal_eth_txs_schema();
+    assert_eq!(
+        schema
+            .store_executed_withdrawals(BlockNumber(0), BlockNumber(1))
+            .await?,
+        0
+    );
+    assert_eq!(schema.get_last_recorded_block().await?, BlockNumber(0));
+
+    verify_block(&mut storage, BlockNumber(1)).await?;
+    let execute_tx_hash = storage
+        .ethereum_schema()
+        .aggregated_op_final_hash(BlockNumber(1))
+        .await?
+        .unwrap();
+    let mut schema = storage.withdrawal_eth_txs_schema();
+    assert_eq!(
+        schema
+            .store_executed_withdrawals(BlockNumber(0), BlockNumber(1))
+            .await?,
+        2
+    );
+    // Recording the same blocks again has no effect.
+    assert_eq!(
+        schema
+            .store_executed_withdrawals(BlockNumber(0), BlockNumber(1))
+            .await?,
+        0
+    );
+    assert_eq!(schema.get_last_recorded_block().await?, BlockNumber(1));
+    assert_eq!(
+        schema.get_withdrawal_eth_tx(&withdraw_hash).await?,
+        Some(execute_tx_hash)
+    );
+    assert_eq!(
+        schema.get_withdrawal_eth_tx(&withdraw_nft_hash).await?,
+        Some(execute_tx_hash)
+    );
+    assert_eq!(schema.get_withdrawal_eth_tx(&transfer_hash).await?, None);
+
+    let receipt = storage
+        .chain()
+        .operations_ext_schema()
+        .tx_receipt_api_v02(withdraw_hash.as_ref())
+        .await?;
+    match receipt.unwrap() {
+        Receipt::L2(receipt) => assert_eq!(receipt.eth_tx_hash, Some(execute_tx_hash)),
+        Receipt::L1(_) => panic!("Should be L2 receipt"),
+    }
+    let receipt = storage
+        .chain()
+        .operations_ext_schema()
+        .tx_receipt_api_v02(transfer_hash.as_ref())
+        .await?;
+    match receipt.unwrap() {
+        Receipt::L2(receipt) => assert_eq!(receipt.eth_tx_hash, None),
+        Receipt::L1(_) => panic!("Should be L2 receipt"),
+    }
+
+    Ok(())
+}
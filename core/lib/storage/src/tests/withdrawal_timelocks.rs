@@ -0,0 +1,157 @@
+// External imports
+use num::BigUint;
+// Workspace imports
+use zksync_types::{
+    tx::{SignedZkSyncTx, TxHash},
+    AccountId, Address, BlockNumber, Nonce, TokenId, Withdraw, ZkSyncTx,
+};
+// Local imports
+use crate::tests::db_test;
+use crate::withdrawal_timelocks::{
+    WITHDRAWAL_CANCELLED, WITHDRAWAL_PENDING, WITHDRAWAL_REJECTED, WITHDRAWAL_RELEASED,
+};
+use crate::{QueryResult, StorageProcessor};
+
+fn withdraw_tx(nonce: u32) -> SignedZkSyncTx {
+    let withdraw = Withdraw::new(
+        AccountId(1),
+        Address::repeat_byte(0x01),
+        Address::repeat_byte(0x02),
+        TokenId(0),
+        BigUint::from(100u32),
+        BigUint::from(0u32),
+        Nonce(nonce),
+        Default::default(),
+        None,
+    );
+    ZkSyncTx::from(withdraw).into()
+}
+
+/// Checks that the timelock of the account can be set, replaced and removed.
+#[db_test]
+async fn withdrawal_timelocks(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut schema = storage.withdrawal_timelocks_schema();
+    assert!(schema.get_timelock(AccountId(1)).await?.is_none());
+
+    schema.set_timelock(AccountId(1), 1000, 10).await?;
+    schema.set_timelock(AccountId(1), 500, 20).await?;
+    let timelock = schema.get_timelock(AccountId(1)).await?.unwrap();
+    assert_eq!(timelock.threshold_usd, 500);
+    assert_eq!(timelock.delay_blocks, 20);
+    assert!(schema.get_timelock(AccountId(2)).await?.is_none());
+
+    schema.remove_timelock(AccountId(1)).await?;
+    assert!(schema.get_timelock(AccountId(1)).await?.is_none());
+
+    Ok(())
+}
+
+/// Checks that the held withdrawals are released once their blocks are created, can only be
+/// cancelled while pending, and that every status change is reported once.
+#[db_test]
+async fn pending_withdrawals(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (first, second) = (withdraw_tx(0), withdraw_tx(1));
+    let mut schema = storage.withdrawal_timelocks_schema();
+    schema
+        .store_pending_withdrawal(AccountId(1), &first, BlockNumber(5))
+        .await?;
+    schema
+        .store_pending_withdrawal(AccountId(1), &second, BlockNumber(10))
+        .await?;
+    // Storing the same withdrawal again has no effect.
+    schema
+        .store_pending_withdrawal(AccountId(1), &first, BlockNumber(20))
+        .await?;
+
+    assert!(schema
+        .load_releasable_withdrawals(BlockNumber(4))
+        .await?
+        .is_empty());
+    let releasable = schema.load_releasable_withdrawals(BlockNumber(5)).await?;
+    assert_eq!(releasable.len(), 1);
+    assert_eq!(releasable[0].tx_hash, first.hash().as_ref());
+    let stored: SignedZkSyncTx = serde_json::from_value(releasable[0].tx.clone())?;
+    assert_eq!(stored.hash(), first.hash());
+
+    let unnotified = schema.load_unnotified_withdrawals().await?;
+    assert_eq!(unnotified.len(), 2);
+    for withdrawal in unnotified {
+        assert_eq!(withdrawal.status, WITHDRAWAL_PENDING);
+        let tx_hash = TxHash::from_slice(&withdrawal.tx_hash).unwrap();
+        schema
+            .mark_withdrawal_notified(&tx_hash, &withdrawal.status)
+            .await?;
+    }
+    assert!(schema.load_unnotified_withdrawals().await?.is_empty());
+
+    assert!(
+        schema
+            .update_withdrawal_status(&first.hash(), WITHDRAWAL_PENDING, WITHDRAWAL_RELEASED, None)
+            .await?
+    );
+    assert!(
+        schema
+            .update_withdrawal_status(
+                &second.hash(),
+                WITHDRAWAL_PENDING,
+                WITHDRAWAL_CANCELLED,
+                None
+            )
+            .await?
+    );
+    // Withdrawals which are not pending anymore can't be cancelled.
+    assert!(
+        !schema
+            .update_withdrawal_status(
+                &first.hash(),
+                WITHDRAWAL_PENDING,
+                WITHDRAWAL_CANCELLED,
+                None
+            )
+            .await?
+    );
+    // Released withdrawals can still be rejected by the mempool.
+    assert!(
+        schema
+            .update_withdrawal_status(
+                &first.hash(),
+                WITHDRAWAL_RELEASED,
+                WITHDRAWAL_REJECTED,
+                Some("Nonce mismatch".to_string())
+            )
+            .await?
+    );
+    assert!(schema
+        .load_releasable_withdrawals(BlockNumber(10))
+        .await?
+        .is_empty());
+    assert_eq!(schema.load_unnotified_withdrawals().await?.len(), 2);
+
+    assert_eq!(
+        schema.load_account_withdrawals(AccountId(1)).await?.len(),
+        2
+    );
+    assert!(schema
+        .load_account_withdrawals(AccountId(2))
+        .await?
+        .is_empty());
+    assert_eq!(
+        schema
+            .get_pending_withdrawal(&second.hash())
+            .await?
+            .unwrap()
+            .status,
+        WITHDRAWAL_CANCELLED
+    );
+    assert_eq!(
+        schema
+            .get_pending_withdrawal(&first.hash())
+            .await?
+            .unwrap()
+            .fail_reason
+            .as_deref(),
+        Some("Nonce mismatch")
+    );
+
+    Ok(())
+}
@@ -13,11 +13,14 @@ use zksync_api_types::v02::{
 use zksync_types::{AccountId, Address, Token, TokenId, TokenLike, TokenPrice, NFT};
 use zksync_utils::ratio_to_big_decimal;
 // Local imports
-use self::records::{DBMarketVolume, DbTickerPrice, DbToken, StorageApiNFT, StorageNFT, TokenKind};
+use self::records::{
+    DBMarketVolume, DbTickerPrice, DbToken, StorageApiNFT, StorageNFT, StorageNFTFactory,
+    StorageTokenSymbolAlias, TokenKind,
+};
 
 use crate::utils::address_to_stored_string;
 use crate::{QueryResult, StorageProcessor};
-use zksync_types::tokens::TokenMarketVolume;
+use zksync_types::tokens::{TokenMarketVolume, TokenSymbolAlias};
 
 pub mod records;
 
@@ -38,7 +41,8 @@ pub enum StoreTokenError {
 }
 
 impl<'a, 'c> TokensSchema<'a, 'c> {
-    /// Persists the new token in the database.
+    /// Persists the new token in the database. The token is rejected if its ID, address or
+    /// symbol (case-insensitive) is already taken.
     pub async fn store_token(&mut self, token: Token) -> Result<(), StoreTokenError> {
         let start = Instant::now();
 
@@ -46,7 +50,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             DbToken,
             r#"
             SELECT id, address, decimals, kind as "kind: _", symbol FROM tokens
-            WHERE id = $1 OR address = $2 OR symbol = $3
+            WHERE id = $1 OR address = $2 OR lower(symbol) = lower($3)
             LIMIT 1
             "#,
             *token.id as i32,
@@ -401,24 +405,163 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(db_token.map(|t| t.into()))
     }
 
-    /// Given the numeric token ID, symbol or address, returns token.
-    pub async fn get_token(&mut self, token_like: TokenLike) -> QueryResult<Option<Token>> {
+    /// Loads the page of the finalized NFTs owned by the account, ordered by the token ID.
+    pub async fn load_owned_nfts_page(
+        &mut self,
+        owner_id: AccountId,
+        query: &PaginationQuery<TokenId>,
+    ) -> QueryResult<Vec<NFT>> {
         let start = Instant::now();
+        let nfts = match query.direction {
+            PaginationDirection::Newer => {
+                sqlx::query_as!(
+                    StorageNFT,
+                    r#"
+                    SELECT nft.*, tokens.symbol FROM balances
+                    INNER JOIN nft
+                        ON nft.token_id = balances.coin_id
+                    INNER JOIN tokens
+                        ON tokens.id = nft.token_id
+                    WHERE balances.account_id = $1 AND balances.balance = 1
+                        AND nft.token_id >= $2
+                    ORDER BY nft.token_id ASC
+                    LIMIT $3
+                    "#,
+                    i64::from(*owner_id),
+                    *query.from as i32,
+                    i64::from(query.limit)
+                )
+                .fetch_all(self.0.conn())
+                .await?
+            }
+            PaginationDirection::Older => {
+                sqlx::query_as!(
+                    StorageNFT,
+                    r#"
+                    SELECT nft.*, tokens.symbol FROM balances
+                    INNER JOIN nft
+                        ON nft.token_id = balances.coin_id
+                    INNER JOIN tokens
+                        ON tokens.id = nft.token_id
+                    WHERE balances.account_id = $1 AND balances.balance = 1
+                        AND nft.token_id <= $2
+                    ORDER BY nft.token_id DESC
+                    LIMIT $3
+                    "#,
+                    i64::from(*owner_id),
+                    *query.from as i32,
+                    i64::from(query.limit)
+                )
+                .fetch_all(self.0.conn())
+                .await?
+            }
+        };
 
-        let db_token = match token_like {
-            TokenLike::Id(token_id) => {
+        metrics::histogram!("sql.token.load_owned_nfts_page", start.elapsed());
+        Ok(nfts.into_iter().map(NFT::from).collect())
+    }
+
+    /// Returns the number of the finalized NFTs owned by the account and the greatest ID of them.
+    pub async fn get_owned_nfts_stats(
+        &mut self,
+        owner_id: AccountId,
+    ) -> QueryResult<(u32, Option<TokenId>)> {
+        let start = Instant::now();
+        let record = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as "count!", MAX(nft.token_id) as max_token_id FROM balances
+            INNER JOIN nft
+                ON nft.token_id = balances.coin_id
+            WHERE balances.account_id = $1 AND balances.balance = 1
+            "#,
+            i64::from(*owner_id)
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.get_owned_nfts_stats", start.elapsed());
+        Ok((
+            record.count as u32,
+            record.max_token_id.map(|id| TokenId(id as u32)),
+        ))
+    }
+
+    /// Loads the page of the finalized NFTs minted by the creator, ordered by the serial ID.
+    pub async fn load_minted_nfts_page(
+        &mut self,
+        creator_id: AccountId,
+        query: &PaginationQuery<u32>,
+    ) -> QueryResult<Vec<NFT>> {
+        let start = Instant::now();
+        let nfts = match query.direction {
+            PaginationDirection::Newer => {
                 sqlx::query_as!(
-                    DbToken,
+                    StorageNFT,
                     r#"
-                    SELECT id, address, decimals, kind as "kind: _", symbol FROM tokens
-                    WHERE id = $1
-                    LIMIT 1
+                    SELECT nft.*, tokens.symbol FROM nft
+                    INNER JOIN tokens
+                        ON tokens.id = nft.token_id
+                    WHERE nft.creator_account_id = $1 AND nft.serial_id >= $2
+                    ORDER BY nft.serial_id ASC
+                    LIMIT $3
                     "#,
-                    *token_id as i32
+                    *creator_id as i32,
+                    query.from as i32,
+                    i64::from(query.limit)
                 )
-                .fetch_optional(self.0.conn())
+                .fetch_all(self.0.conn())
                 .await?
             }
+            PaginationDirection::Older => {
+                sqlx::query_as!(
+                    StorageNFT,
+                    r#"
+                    SELECT nft.*, tokens.symbol FROM nft
+                    INNER JOIN tokens
+                        ON tokens.id = nft.token_id
+                    WHERE nft.creator_account_id = $1 AND nft.serial_id <= $2
+                    ORDER BY nft.serial_id DESC
+                    LIMIT $3
+                    "#,
+                    *creator_id as i32,
+                    query.from as i32,
+                    i64::from(query.limit)
+                )
+                .fetch_all(self.0.conn())
+                .await?
+            }
+        };
+
+        metrics::histogram!("sql.token.load_minted_nfts_page", start.elapsed());
+        Ok(nfts.into_iter().map(NFT::from).collect())
+    }
+
+    /// Returns the number of the finalized NFTs minted by the creator. Serial IDs of the NFTs
+    /// are assigned sequentially, so the last minted one has the serial ID one less than the number.
+    pub async fn get_minted_nfts_count(&mut self, creator_id: AccountId) -> QueryResult<u32> {
+        let start = Instant::now();
+        let count = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!" FROM nft WHERE creator_account_id = $1"#,
+            *creator_id as i32
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        metrics::histogram!("sql.token.get_minted_nfts_count", start.elapsed());
+        Ok(count as u32)
+    }
+
+    /// Given the numeric token ID, symbol or address, returns token.
+    ///
+    /// Symbol is resolved by its alias first, then by the symbol of the only token having it.
+    /// Symbol shared by several tokens resolves to none of them, unless it's qualified with
+    /// the token ID.
+    pub async fn get_token(&mut self, token_like: TokenLike) -> QueryResult<Option<Token>> {
+        let start = Instant::now();
+
+        let db_token = match token_like {
+            TokenLike::Id(token_id) => self.get_token_by_id(token_id).await?,
             TokenLike::Address(token_address) => {
                 sqlx::query_as!(
                     DbToken,
@@ -432,24 +575,34 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
                 .fetch_optional(self.0.conn())
                 .await?
             }
-            TokenLike::Symbol(token_symbol) => {
+            TokenLike::Symbol(ref token_symbol) => {
                 // Note: for address and symbol queries we use `lower(...) = lower(...)` syntax, which means
                 // that we need to do a full scan over the table in order to achieve case-insensitive search.
                 // Luckily, we
                 // 1) don't have too much tokens.
                 // 2) most tokens requests will be handled by `TokenDbCache` anyway,
                 // so it shouldn't be a problem.
-                sqlx::query_as!(
+                let db_token = sqlx::query_as!(
                     DbToken,
                     r#"
                     SELECT id, address, decimals, kind as "kind: _", symbol FROM tokens
-                    WHERE lower(symbol) = lower($1)
-                    LIMIT 1
+                    WHERE id = COALESCE(
+                        (SELECT token_id FROM token_symbol_aliases WHERE alias = lower($1)),
+                        (SELECT MIN(id) FROM tokens WHERE lower(symbol) = lower($1) HAVING COUNT(*) = 1)
+                    )
                     "#,
                     token_symbol
                 )
                 .fetch_optional(self.0.conn())
-                .await?
+                .await?;
+
+                match (db_token, token_like.qualified_symbol()) {
+                    (None, Some((symbol, token_id))) => self
+                        .get_token_by_id(token_id)
+                        .await?
+                        .filter(|token| token.symbol.eq_ignore_ascii_case(symbol)),
+                    (db_token, _) => db_token,
+                }
             }
         };
 
@@ -457,6 +610,88 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(db_token.map(|t| t.into()))
     }
 
+    async fn get_token_by_id(&mut self, token_id: TokenId) -> QueryResult<Option<DbToken>> {
+        let db_token = sqlx::query_as!(
+            DbToken,
+            r#"
+            SELECT id, address, decimals, kind as "kind: _", symbol FROM tokens
+            WHERE id = $1
+            LIMIT 1
+            "#,
+            *token_id as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+        Ok(db_token)
+    }
+
+    /// Loads the tokens having the given symbol, case-insensitive, ordered by ID.
+    pub async fn get_tokens_by_symbol(&mut self, symbol: &str) -> QueryResult<Vec<Token>> {
+        let start = Instant::now();
+        let tokens = sqlx::query_as!(
+            DbToken,
+            r#"
+            SELECT id, address, decimals, kind as "kind: _", symbol FROM tokens
+            WHERE lower(symbol) = lower($1)
+            ORDER BY id
+            "#,
+            symbol
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.get_tokens_by_symbol", start.elapsed());
+        Ok(tokens.into_iter().map(Into::into).collect())
+    }
+
+    /// Stores the alias of the token symbol, replacing the previous target of the alias.
+    pub async fn store_symbol_alias(&mut self, alias: &str, token_id: TokenId) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO token_symbol_aliases (alias, token_id)
+            VALUES ($1, $2)
+            ON CONFLICT (alias)
+            DO UPDATE SET token_id = $2, created_at = now()
+            "#,
+            alias.to_lowercase(),
+            *token_id as i32
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.store_symbol_alias", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the alias of the token symbol. Returns `false` if there is no such alias.
+    pub async fn remove_symbol_alias(&mut self, alias: &str) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "DELETE FROM token_symbol_aliases WHERE alias = $1",
+            alias.to_lowercase()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.remove_symbol_alias", start.elapsed());
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Loads all the aliases of the token symbols.
+    pub async fn load_symbol_aliases(&mut self) -> QueryResult<Vec<TokenSymbolAlias>> {
+        let start = Instant::now();
+        let aliases = sqlx::query_as!(
+            StorageTokenSymbolAlias,
+            "SELECT * FROM token_symbol_aliases ORDER BY alias"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_symbol_aliases", start.elapsed());
+        Ok(aliases.into_iter().map(Into::into).collect())
+    }
+
     pub async fn get_token_market_volume(
         &mut self,
         token_id: TokenId,
@@ -582,4 +817,25 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         metrics::histogram!("sql.token.store_nft_factory", start.elapsed());
         Ok(())
     }
+
+    /// Loads the factory registered by the creator, if any.
+    pub async fn load_nft_factory(
+        &mut self,
+        creator_id: AccountId,
+    ) -> QueryResult<Option<StorageNFTFactory>> {
+        let start = Instant::now();
+        let factory = sqlx::query_as!(
+            StorageNFTFactory,
+            r#"
+            SELECT * FROM nft_factory
+            WHERE creator_id = $1
+            "#,
+            creator_id.0 as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_nft_factory", start.elapsed());
+        Ok(factory)
+    }
 }
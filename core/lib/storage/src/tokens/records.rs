@@ -9,7 +9,7 @@ use crate::utils::{address_to_stored_string, stored_str_address_to_address};
 use chrono::{DateTime, Utc};
 use zksync_api_types::v02::token::ApiNFT;
 use zksync_types::{
-    tokens::{TokenMarketVolume, TokenPrice},
+    tokens::{TokenMarketVolume, TokenPrice, TokenSymbolAlias},
     AccountId, Address, Token, TokenId, H256, NFT,
 };
 use zksync_utils::big_decimal_to_ratio;
@@ -152,6 +152,25 @@ impl From<StorageApiNFT> for ApiNFT {
     }
 }
 
+/// Factory registered by the creator for withdrawing their NFTs.
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageNFTFactory {
+    pub creator_id: i32,
+    pub factory_address: String,
+    pub creator_address: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StorageNFTFactory {
+    pub fn creator_address(&self) -> Address {
+        stored_str_address_to_address(&self.creator_address)
+    }
+
+    pub fn factory_address(&self) -> Address {
+        stored_str_address_to_address(&self.factory_address)
+    }
+}
+
 #[derive(Debug, Clone, FromRow)]
 pub struct DBMarketVolume {
     pub token_id: i32,
@@ -168,3 +187,20 @@ impl From<DBMarketVolume> for TokenMarketVolume {
         }
     }
 }
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageTokenSymbolAlias {
+    pub alias: String,
+    pub token_id: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StorageTokenSymbolAlias> for TokenSymbolAlias {
+    fn from(val: StorageTokenSymbolAlias) -> Self {
+        Self {
+            alias: val.alias,
+            token_id: TokenId(val.token_id as u32),
+            created_at: val.created_at,
+        }
+    }
+}
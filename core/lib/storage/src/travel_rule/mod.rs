@@ -0,0 +1,135 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{tx::TxHash, Address};
+// Local imports
+use self::records::{StorageTravelRuleRecord, TravelRuleRecord};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the transfers subject to the travel rule.
+///
+/// The sender attaches the metadata encrypted for the recipient to the large transfers, the
+/// metadata is available to both parties of the transfer. Transfers above the threshold
+/// submitted without the metadata are flagged for the operator.
+#[derive(Debug)]
+pub struct TravelRuleSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> TravelRuleSchema<'a, 'c> {
+    /// Stores the record of the transfer. Returns `false` if the transfer is already recorded,
+    /// the stored record is never overwritten.
+    pub async fn save_record(&mut self, record: TravelRuleRecord) -> QueryResult<bool> {
+        let start = Instant::now();
+        let inserted = sqlx::query!(
+            "INSERT INTO travel_rule_records
+            (tx_hash, from_address, to_address, token_id, amount, amount_usd_scaled, encrypted_metadata)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (tx_hash) DO NOTHING",
+            record.tx_hash.as_ref(),
+            record.from.as_bytes(),
+            record.to.as_bytes(),
+            *record.token_id as i32,
+            record.amount,
+            record.amount_usd_scaled.map(|amount| amount as i64),
+            record.encrypted_metadata
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.travel_rule.save_record", start.elapsed());
+        Ok(inserted == 1)
+    }
+
+    /// Loads the record of the transfer.
+    pub async fn get_record(
+        &mut self,
+        tx_hash: TxHash,
+    ) -> QueryResult<Option<StorageTravelRuleRecord>> {
+        let start = Instant::now();
+        let record = sqlx::query_as!(
+            StorageTravelRuleRecord,
+            "SELECT * FROM travel_rule_records WHERE tx_hash = $1",
+            tx_hash.as_ref()
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.travel_rule.get_record", start.elapsed());
+        Ok(record)
+    }
+
+    /// Loads the transfers of the address having the metadata, from the latest to the earliest.
+    /// The page starts after the transfer `before`, or from the latest transfer if it's `None`.
+    pub async fn load_account_records(
+        &mut self,
+        address: Address,
+        before: Option<TxHash>,
+        limit: u32,
+    ) -> QueryResult<Vec<StorageTravelRuleRecord>> {
+        let start = Instant::now();
+        let records = sqlx::query_as!(
+            StorageTravelRuleRecord,
+            r#"
+            SELECT * FROM travel_rule_records
+            WHERE (from_address = $1 OR to_address = $1) AND encrypted_metadata IS NOT NULL
+                AND (
+                    $2::bytea IS NULL OR (created_at, tx_hash) < (
+                        SELECT created_at, tx_hash FROM travel_rule_records WHERE tx_hash = $2
+                    )
+                )
+            ORDER BY created_at DESC, tx_hash DESC
+            LIMIT $3
+            "#,
+            address.as_bytes(),
+            before.as_ref().map(|tx_hash| tx_hash.as_ref()),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.travel_rule.load_account_records", start.elapsed());
+        Ok(records)
+    }
+
+    /// Loads the records of the given transfers having the metadata.
+    pub async fn load_metadata_records(
+        &mut self,
+        tx_hashes: &[TxHash],
+    ) -> QueryResult<Vec<StorageTravelRuleRecord>> {
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = tx_hashes
+            .iter()
+            .map(|tx_hash| tx_hash.as_ref().to_vec())
+            .collect();
+        let records = sqlx::query_as!(
+            StorageTravelRuleRecord,
+            "SELECT * FROM travel_rule_records
+            WHERE tx_hash = ANY($1) AND encrypted_metadata IS NOT NULL",
+            &tx_hashes
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.travel_rule.load_metadata_records", start.elapsed());
+        Ok(records)
+    }
+
+    /// Loads the latest transfers above the threshold submitted without the metadata.
+    pub async fn load_flagged(&mut self, limit: u32) -> QueryResult<Vec<StorageTravelRuleRecord>> {
+        let start = Instant::now();
+        let records = sqlx::query_as!(
+            StorageTravelRuleRecord,
+            "SELECT * FROM travel_rule_records WHERE encrypted_metadata IS NULL
+            ORDER BY created_at DESC LIMIT $1",
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.travel_rule.load_flagged", start.elapsed());
+        Ok(records)
+    }
+}
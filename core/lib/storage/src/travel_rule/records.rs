@@ -0,0 +1,75 @@
+// External imports
+use chrono::{DateTime, Utc};
+use num::{rational::Ratio, BigUint};
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_types::{tx::TxHash, Address, TokenId, Transfer};
+use zksync_utils::{biguint_to_big_decimal, ratio_to_scaled_u64};
+// Local imports
+
+/// Transfer subject to the travel rule, stored once the transfer is accepted by the mempool.
+pub struct TravelRuleRecord {
+    pub tx_hash: TxHash,
+    pub from: Address,
+    pub to: Address,
+    pub token_id: TokenId,
+    pub amount: BigDecimal,
+    /// Value of the transfer in USD scaled by 10^6, if the token price is known.
+    pub amount_usd_scaled: Option<u64>,
+    /// Metadata encrypted for the recipient, `None` for the non-compliant transfers.
+    pub encrypted_metadata: Option<Vec<u8>>,
+}
+
+impl TravelRuleRecord {
+    /// Returns the record of the transfer accepted by the mempool, `None` if the transfer is not
+    /// subject to the travel rule. Only the fungible token transfers are recorded: the ones with the
+    /// metadata, and the ones above the threshold without it, which are flagged for the operator.
+    /// Transfers of the tokens without the known price are never flagged.
+    pub fn for_transfer(
+        tx_hash: TxHash,
+        transfer: &Transfer,
+        amount_usd: Option<Ratio<BigUint>>,
+        threshold_usd: Option<&Ratio<BigUint>>,
+        metadata: Option<Vec<u8>>,
+    ) -> Option<Self> {
+        if transfer.token.0 >= MIN_NFT_TOKEN_ID {
+            return None;
+        }
+        if metadata.is_none() {
+            let exceeds_threshold = matches!(
+                (&amount_usd, threshold_usd),
+                (Some(amount_usd), Some(threshold_usd)) if amount_usd >= threshold_usd
+            );
+            if !exceeds_threshold {
+                return None;
+            }
+        }
+        Some(Self {
+            tx_hash,
+            from: transfer.from,
+            to: transfer.to,
+            token_id: transfer.token,
+            amount: biguint_to_big_decimal(transfer.amount.clone()),
+            amount_usd_scaled: amount_usd.map(ratio_to_scaled_u64),
+            encrypted_metadata: metadata,
+        })
+    }
+
+    /// Whether the transfer is recorded without the metadata, i.e. flagged for the operator.
+    pub fn is_flagged(&self) -> bool {
+        self.encrypted_metadata.is_none()
+    }
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageTravelRuleRecord {
+    pub tx_hash: Vec<u8>,
+    pub from_address: Vec<u8>,
+    pub to_address: Vec<u8>,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+    pub amount_usd_scaled: Option<i64>,
+    pub encrypted_metadata: Option<Vec<u8>>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,118 @@
+// Built-in deps
+use std::time::Instant;
+// Local imports
+use self::records::{NewTreasuryConversion, StorageTreasuryConversion};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Status of the conversion which transaction is not executed in a sealed block yet.
+pub const CONVERSION_SENT: &str = "sent";
+/// Status of the conversion which transaction is included into the sealed block.
+pub const CONVERSION_COMMITTED: &str = "committed";
+/// Status of the conversion which transaction has failed.
+pub const CONVERSION_FAILED: &str = "failed";
+
+/// Schema of the audit records of the fee token conversions made by the treasury task.
+#[derive(Debug)]
+pub struct TreasurySchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> TreasurySchema<'a, 'c> {
+    /// Stores the conversion before its transaction is sent to the mempool, returns its id.
+    pub async fn store_conversion(
+        &mut self,
+        conversion: NewTreasuryConversion,
+    ) -> QueryResult<i64> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO treasury_conversions
+                (token_id, amount, target_token_id, expected_amount, route, tx_hash, status, details)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id
+            "#,
+            conversion.token_id,
+            conversion.amount,
+            conversion.target_token_id,
+            conversion.expected_amount,
+            conversion.route,
+            conversion.tx_hash,
+            CONVERSION_SENT,
+            conversion.details
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        metrics::histogram!("sql.treasury.store_conversion", start.elapsed());
+        Ok(id)
+    }
+
+    /// Updates the status of the conversion once its transaction is executed.
+    pub async fn update_conversion_status(
+        &mut self,
+        id: i64,
+        status: &str,
+        fail_reason: Option<String>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            UPDATE treasury_conversions
+            SET status = $2, fail_reason = $3, updated_at = now()
+            WHERE id = $1
+            "#,
+            id,
+            status,
+            fail_reason
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.treasury.update_conversion_status", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the conversions which transactions are not executed yet.
+    pub async fn load_pending_conversions(
+        &mut self,
+    ) -> QueryResult<Vec<StorageTreasuryConversion>> {
+        let start = Instant::now();
+        let conversions = sqlx::query_as!(
+            StorageTreasuryConversion,
+            r#"
+            SELECT * FROM treasury_conversions
+            WHERE status = $1
+            ORDER BY id
+            "#,
+            CONVERSION_SENT
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.treasury.load_pending_conversions", start.elapsed());
+        Ok(conversions)
+    }
+
+    /// Loads the latest conversions, the newest go first.
+    pub async fn load_conversions(
+        &mut self,
+        limit: u32,
+    ) -> QueryResult<Vec<StorageTreasuryConversion>> {
+        let start = Instant::now();
+        let conversions = sqlx::query_as!(
+            StorageTreasuryConversion,
+            r#"
+            SELECT * FROM treasury_conversions
+            ORDER BY id DESC
+            LIMIT $1
+            "#,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.treasury.load_conversions", start.elapsed());
+        Ok(conversions)
+    }
+}
@@ -0,0 +1,35 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::{types::BigDecimal, FromRow};
+
+/// Audit record of the conversion of the collected fees made by the treasury task.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageTreasuryConversion {
+    pub id: i64,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+    pub target_token_id: i32,
+    pub expected_amount: BigDecimal,
+    /// Either `swap` or `l1`.
+    pub route: String,
+    pub tx_hash: Vec<u8>,
+    /// One of `sent`, `committed` or `failed`.
+    pub status: String,
+    pub fail_reason: Option<String>,
+    /// Route-specific information, e.g. the counterparty order of the swap.
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Information about the conversion sent to the network.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewTreasuryConversion {
+    pub token_id: i32,
+    pub amount: BigDecimal,
+    pub target_token_id: i32,
+    pub expected_amount: BigDecimal,
+    pub route: String,
+    pub tx_hash: Vec<u8>,
+    pub details: serde_json::Value,
+}
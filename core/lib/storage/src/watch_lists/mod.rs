@@ -0,0 +1,217 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{Address, BlockNumber};
+// Local imports
+use self::records::{StorageWatchList, StorageWatchedOperation};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Kind of the watched operation transferring the funds within L2.
+pub const WATCHED_TRANSFER: &str = "transfer";
+/// Kind of the watched operation depositing the funds from L1.
+pub const WATCHED_DEPOSIT: &str = "deposit";
+
+/// Schema of the address watch lists.
+///
+/// Exchanges register their deposit addresses in bulk, and the incoming transfers and deposits
+/// of all the addresses of the list are reported to its webhook block by block, so no
+/// subscription per address is needed.
+#[derive(Debug)]
+pub struct WatchListsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> WatchListsSchema<'a, 'c> {
+    /// Creates the watch list reporting the operations of the blocks after `last_block`,
+    /// returns its ID.
+    pub async fn create_watch_list(
+        &mut self,
+        name: &str,
+        webhook_url: &str,
+        last_block: BlockNumber,
+    ) -> QueryResult<i64> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO watch_lists (name, webhook_url, last_block)
+            VALUES ($1, $2, $3)
+            RETURNING id
+            "#,
+            name,
+            webhook_url,
+            i64::from(*last_block)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        metrics::histogram!("sql.watch_lists.create_watch_list", start.elapsed());
+        Ok(id)
+    }
+
+    /// Removes the watch list along with its addresses.
+    pub async fn remove_watch_list(&mut self, id: i64) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!("DELETE FROM watch_lists WHERE id = $1", id)
+            .execute(self.0.conn())
+            .await?;
+
+        metrics::histogram!("sql.watch_lists.remove_watch_list", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the watch list by its name.
+    pub async fn get_watch_list(&mut self, name: &str) -> QueryResult<Option<StorageWatchList>> {
+        let start = Instant::now();
+        let watch_list = sqlx::query_as!(
+            StorageWatchList,
+            "SELECT * FROM watch_lists WHERE name = $1",
+            name
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.watch_lists.get_watch_list", start.elapsed());
+        Ok(watch_list)
+    }
+
+    /// Loads all the watch lists.
+    pub async fn load_watch_lists(&mut self) -> QueryResult<Vec<StorageWatchList>> {
+        let start = Instant::now();
+        let watch_lists =
+            sqlx::query_as!(StorageWatchList, "SELECT * FROM watch_lists ORDER BY id")
+                .fetch_all(self.0.conn())
+                .await?;
+
+        metrics::histogram!("sql.watch_lists.load_watch_lists", start.elapsed());
+        Ok(watch_lists)
+    }
+
+    /// Adds the addresses to the watch list with a single query, returns the number of the
+    /// addresses which weren't watched yet.
+    pub async fn add_watch_addresses(
+        &mut self,
+        id: i64,
+        addresses: &[Address],
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let added = sqlx::query!(
+            r#"
+            INSERT INTO watch_addresses (watch_list_id, address)
+            SELECT $1, u.address FROM UNNEST ($2::bytea[]) AS u(address)
+            ON CONFLICT (watch_list_id, address) DO NOTHING
+            "#,
+            id,
+            &addresses
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.watch_lists.add_watch_addresses", start.elapsed());
+        Ok(added)
+    }
+
+    /// Removes the addresses from the watch list, returns the number of the removed addresses.
+    pub async fn remove_watch_addresses(
+        &mut self,
+        id: i64,
+        addresses: &[Address],
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let removed = sqlx::query!(
+            "DELETE FROM watch_addresses WHERE watch_list_id = $1 AND address = ANY($2::bytea[])",
+            id,
+            &addresses
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.watch_lists.remove_watch_addresses", start.elapsed());
+        Ok(removed)
+    }
+
+    /// Returns the number of the addresses of the watch list.
+    pub async fn count_watch_addresses(&mut self, id: i64) -> QueryResult<u64> {
+        let start = Instant::now();
+        let count = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!" FROM watch_addresses WHERE watch_list_id = $1"#,
+            id
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        metrics::histogram!("sql.watch_lists.count_watch_addresses", start.elapsed());
+        Ok(count as u64)
+    }
+
+    /// Loads the successful transfers and deposits to the addresses of the watch list made in
+    /// the blocks of the `(from_block, to_block]` range.
+    pub async fn load_watched_operations(
+        &mut self,
+        id: i64,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<Vec<StorageWatchedOperation>> {
+        let start = Instant::now();
+        let operations = sqlx::query_as!(
+            StorageWatchedOperation,
+            r#"
+            SELECT $4::text AS "kind!", tx_hash AS "tx_hash!", block_number AS "block_number!",
+                from_account AS "from_account!", to_account AS "to_account!",
+                (tx->>'token')::integer AS "token_id!", (tx->>'amount')::numeric AS "amount!"
+            FROM executed_transactions
+            INNER JOIN watch_addresses ON watch_addresses.address = executed_transactions.to_account
+            WHERE watch_addresses.watch_list_id = $1
+                AND block_number > $2 AND block_number <= $3
+                AND success = true AND tx->>'type' = 'Transfer'
+            UNION ALL
+            SELECT $5::text, tx_hash, block_number, from_account, to_account,
+                (operation->'priority_op'->>'token')::integer,
+                (operation->'priority_op'->>'amount')::numeric
+            FROM executed_priority_operations
+            INNER JOIN watch_addresses ON watch_addresses.address = executed_priority_operations.to_account
+            WHERE watch_addresses.watch_list_id = $1
+                AND block_number > $2 AND block_number <= $3
+                AND operation->>'type' = 'Deposit'
+            ORDER BY 3
+            "#,
+            id,
+            i64::from(*from_block),
+            i64::from(*to_block),
+            WATCHED_TRANSFER,
+            WATCHED_DEPOSIT
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.watch_lists.load_watched_operations", start.elapsed());
+        Ok(operations)
+    }
+
+    /// Marks the operations of the blocks up to the given one as reported.
+    pub async fn set_last_block(&mut self, id: i64, last_block: BlockNumber) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE watch_lists SET last_block = $2 WHERE id = $1",
+            id,
+            i64::from(*last_block)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.watch_lists.set_last_block", start.elapsed());
+        Ok(())
+    }
+}
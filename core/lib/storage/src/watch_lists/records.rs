@@ -0,0 +1,27 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::{types::BigDecimal, FromRow};
+
+/// List of the addresses which incoming transfers and deposits are reported to the webhook.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageWatchList {
+    pub id: i64,
+    pub name: String,
+    pub webhook_url: String,
+    /// Last block which operations are reported.
+    pub last_block: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Incoming transfer or deposit of the watched address.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageWatchedOperation {
+    /// Either `transfer` or `deposit`.
+    pub kind: String,
+    pub tx_hash: Vec<u8>,
+    pub block_number: i64,
+    pub from_account: Vec<u8>,
+    pub to_account: Vec<u8>,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+}
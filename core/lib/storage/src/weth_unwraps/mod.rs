@@ -0,0 +1,140 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{tx::TxHash, BlockNumber, H256};
+// Local imports
+use self::records::StorageWethUnwrap;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema of the withdrawals delivered as native ETH.
+///
+/// Withdrawals with the `unwrapWeth` flag are recorded once their blocks are executed on L1, and
+/// stay pending until the server signs the unwrapping transaction. The signed transaction is
+/// stored and stays unconfirmed until it's mined.
+#[derive(Debug)]
+pub struct WethUnwrapsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> WethUnwrapsSchema<'a, 'c> {
+    /// Records the successful withdrawals with the `unwrapWeth` flag of the blocks in the
+    /// `(from_block, to_block]` range, returns the number of the new records.
+    pub async fn store_executed_unwraps(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let stored = sqlx::query!(
+            r#"
+            INSERT INTO weth_unwraps (tx_hash, block_number, recipient, amount)
+            SELECT tx_hash, block_number, from_account, (tx->>'amount')::numeric
+            FROM executed_transactions
+            WHERE block_number > $1 AND block_number <= $2
+                AND tx->>'type' = 'Withdraw' AND (tx->>'unwrapWeth')::boolean = true
+                AND success = true
+            ON CONFLICT (tx_hash) DO NOTHING
+            "#,
+            i64::from(*from_block),
+            i64::from(*to_block)
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.weth_unwraps.store_executed_unwraps", start.elapsed());
+        Ok(stored)
+    }
+
+    /// Loads the withdrawals which unwrapping transactions weren't signed yet.
+    pub async fn load_pending_unwraps(&mut self) -> QueryResult<Vec<StorageWethUnwrap>> {
+        let start = Instant::now();
+        let unwraps = sqlx::query_as!(
+            StorageWethUnwrap,
+            r#"
+            SELECT * FROM weth_unwraps
+            WHERE eth_tx_hash IS NULL
+            ORDER BY block_number, tx_hash
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.weth_unwraps.load_pending_unwraps", start.elapsed());
+        Ok(unwraps)
+    }
+
+    /// Loads the unwrapping of the withdrawal with the given hash.
+    pub async fn get_unwrap(&mut self, tx_hash: &TxHash) -> QueryResult<Option<StorageWethUnwrap>> {
+        let start = Instant::now();
+        let unwrap = sqlx::query_as!(
+            StorageWethUnwrap,
+            "SELECT * FROM weth_unwraps WHERE tx_hash = $1",
+            tx_hash.as_ref()
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.weth_unwraps.get_unwrap", start.elapsed());
+        Ok(unwrap)
+    }
+
+    /// Loads the withdrawals which unwrapping transactions are signed but not mined yet,
+    /// in the order of their nonces.
+    pub async fn load_unconfirmed_unwraps(&mut self) -> QueryResult<Vec<StorageWethUnwrap>> {
+        let start = Instant::now();
+        let unwraps = sqlx::query_as!(
+            StorageWethUnwrap,
+            r#"
+            SELECT * FROM weth_unwraps
+            WHERE eth_tx_hash IS NOT NULL AND NOT confirmed
+            ORDER BY eth_nonce
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.weth_unwraps.load_unconfirmed_unwraps", start.elapsed());
+        Ok(unwraps)
+    }
+
+    /// Stores the signed L1 transaction unwrapping the withdrawal, along with the operator nonce
+    /// reserved for it.
+    pub async fn save_unwrap_tx(
+        &mut self,
+        tx_hash: &TxHash,
+        eth_nonce: i64,
+        eth_tx_hash: H256,
+        raw_tx: &[u8],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE weth_unwraps SET eth_tx_hash = $2, eth_nonce = $3, raw_tx = $4
+            WHERE tx_hash = $1",
+            tx_hash.as_ref(),
+            eth_tx_hash.as_bytes(),
+            eth_nonce,
+            raw_tx
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.weth_unwraps.save_unwrap_tx", start.elapsed());
+        Ok(())
+    }
+
+    /// Marks the unwrapping transaction of the withdrawal as mined.
+    pub async fn confirm_unwrap(&mut self, tx_hash: &TxHash) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE weth_unwraps SET confirmed = true WHERE tx_hash = $1",
+            tx_hash.as_ref()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.weth_unwraps.confirm_unwrap", start.elapsed());
+        Ok(())
+    }
+}
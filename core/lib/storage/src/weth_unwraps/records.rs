@@ -0,0 +1,19 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::{types::BigDecimal, FromRow};
+
+/// Withdrawal delivered as native ETH by the WETH unwrapper.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageWethUnwrap {
+    pub tx_hash: Vec<u8>,
+    pub block_number: i64,
+    pub recipient: Vec<u8>,
+    pub amount: BigDecimal,
+    pub eth_tx_hash: Option<Vec<u8>>,
+    /// Operator nonce reserved for the unwrapping transaction.
+    pub eth_nonce: Option<i64>,
+    /// Signed unwrapping transaction, resent until it's mined.
+    pub raw_tx: Option<Vec<u8>>,
+    pub confirmed: bool,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,103 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{aggregated_operations::AggregatedActionType, tx::TxHash, BlockNumber, H256};
+// Local imports
+use crate::{QueryResult, StorageProcessor};
+
+/// Schema of the L1 transactions which delivered the funds of the withdrawals.
+///
+/// Successful withdrawals are recorded along with the hash of the `ExecuteBlocks` transaction
+/// once their blocks are executed on L1, so the receipt of the withdrawal can point to its L1
+/// transaction without searching the aggregated operations.
+#[derive(Debug)]
+pub struct WithdrawalEthTxsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> WithdrawalEthTxsSchema<'a, 'c> {
+    /// Records the L1 transactions of the successful withdrawals of the blocks in the
+    /// `(from_block, to_block]` range executed on L1, returns the number of the new records.
+    pub async fn store_executed_withdrawals(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let stored = sqlx::query!(
+            r#"
+            INSERT INTO withdrawal_eth_txs (tx_hash, block_number, eth_tx_hash)
+            SELECT executed_transactions.tx_hash, executed_transactions.block_number,
+                eth_operations.final_hash
+            FROM executed_transactions
+            INNER JOIN aggregate_operations
+                ON executed_transactions.block_number
+                    BETWEEN aggregate_operations.from_block AND aggregate_operations.to_block
+            INNER JOIN eth_aggregated_ops_binding
+                ON eth_aggregated_ops_binding.op_id = aggregate_operations.id
+            INNER JOIN eth_operations ON eth_operations.id = eth_aggregated_ops_binding.eth_op_id
+            WHERE executed_transactions.block_number > $1
+                AND executed_transactions.block_number <= $2
+                AND executed_transactions.success = true
+                AND executed_transactions.tx->>'type' IN ('Withdraw', 'ForcedExit', 'WithdrawNFT')
+                AND aggregate_operations.action_type = $3
+                AND eth_operations.confirmed = true AND eth_operations.final_hash IS NOT NULL
+            ON CONFLICT (tx_hash) DO NOTHING
+            "#,
+            i64::from(*from_block),
+            i64::from(*to_block),
+            AggregatedActionType::ExecuteBlocks.to_string()
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!(
+            "sql.withdrawal_eth_txs.store_executed_withdrawals",
+            start.elapsed()
+        );
+        Ok(stored)
+    }
+
+    /// Returns the last block which withdrawals are recorded.
+    pub async fn get_last_recorded_block(&mut self) -> QueryResult<BlockNumber> {
+        let start = Instant::now();
+        let last_block =
+            sqlx::query!(r#"SELECT MAX(block_number) as "max" FROM withdrawal_eth_txs"#)
+                .fetch_one(self.0.conn())
+                .await?
+                .max
+                .unwrap_or(0);
+
+        metrics::histogram!(
+            "sql.withdrawal_eth_txs.get_last_recorded_block",
+            start.elapsed()
+        );
+        Ok(BlockNumber(last_block as u32))
+    }
+
+    /// Returns the hash of the L1 transaction which delivered the funds of the withdrawal.
+    /// Withdrawals with the `unwrapWeth` flag are delivered by the unwrapping transaction
+    /// once it's sent.
+    pub async fn get_withdrawal_eth_tx(&mut self, tx_hash: &TxHash) -> QueryResult<Option<H256>> {
+        let start = Instant::now();
+        let eth_tx_hash = sqlx::query!(
+            r#"
+            SELECT COALESCE(weth_unwraps.eth_tx_hash, withdrawal_eth_txs.eth_tx_hash)
+                AS "eth_tx_hash!"
+            FROM withdrawal_eth_txs
+            LEFT JOIN weth_unwraps ON weth_unwraps.tx_hash = withdrawal_eth_txs.tx_hash
+            WHERE withdrawal_eth_txs.tx_hash = $1
+            "#,
+            tx_hash.as_ref()
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| H256::from_slice(&record.eth_tx_hash));
+
+        metrics::histogram!(
+            "sql.withdrawal_eth_txs.get_withdrawal_eth_tx",
+            start.elapsed()
+        );
+        Ok(eth_tx_hash)
+    }
+}
@@ -0,0 +1,273 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{
+    tx::{SignedZkSyncTx, TxHash},
+    AccountId, BlockNumber,
+};
+// Local imports
+use self::records::{StoragePendingWithdrawal, StorageWithdrawalTimelock};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Status of the withdrawal waiting for the timelock to expire.
+pub const WITHDRAWAL_PENDING: &str = "pending";
+/// Status of the withdrawal sent to the mempool after the timelock expired.
+pub const WITHDRAWAL_RELEASED: &str = "released";
+/// Status of the withdrawal cancelled by the owner of the account.
+pub const WITHDRAWAL_CANCELLED: &str = "cancelled";
+/// Status of the withdrawal rejected by the mempool on release.
+pub const WITHDRAWAL_REJECTED: &str = "rejected";
+
+/// Schema of the withdrawal timelocks.
+///
+/// Accounts with the timelock have their withdrawals above the threshold held for the given
+/// number of blocks, during which the owner can cancel them. Every change of the status of the
+/// held withdrawal has to be reported by the notification hooks, which is tracked by the
+/// `notified` flag.
+#[derive(Debug)]
+pub struct WithdrawalTimelocksSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> WithdrawalTimelocksSchema<'a, 'c> {
+    /// Sets the timelock of the account, replacing the previous one.
+    pub async fn set_timelock(
+        &mut self,
+        account_id: AccountId,
+        threshold_usd: u64,
+        delay_blocks: u32,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO withdrawal_timelocks (account_id, threshold_usd, delay_blocks)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (account_id) DO UPDATE
+            SET threshold_usd = $2, delay_blocks = $3, updated_at = now()
+            "#,
+            i64::from(*account_id),
+            threshold_usd as i64,
+            i64::from(delay_blocks)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.withdrawal_timelocks.set_timelock", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the timelock of the account. Already held withdrawals stay pending.
+    pub async fn remove_timelock(&mut self, account_id: AccountId) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "DELETE FROM withdrawal_timelocks WHERE account_id = $1",
+            i64::from(*account_id)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.withdrawal_timelocks.remove_timelock", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the timelock of the account.
+    pub async fn get_timelock(
+        &mut self,
+        account_id: AccountId,
+    ) -> QueryResult<Option<StorageWithdrawalTimelock>> {
+        let start = Instant::now();
+        let timelock = sqlx::query_as!(
+            StorageWithdrawalTimelock,
+            "SELECT * FROM withdrawal_timelocks WHERE account_id = $1",
+            i64::from(*account_id)
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.withdrawal_timelocks.get_timelock", start.elapsed());
+        Ok(timelock)
+    }
+
+    /// Holds the verified withdrawal until the given block is created.
+    /// Storing the same withdrawal again has no effect.
+    pub async fn store_pending_withdrawal(
+        &mut self,
+        account_id: AccountId,
+        tx: &SignedZkSyncTx,
+        release_block: BlockNumber,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let tx_json = serde_json::to_value(tx)?;
+        sqlx::query!(
+            r#"
+            INSERT INTO timelocked_withdrawals (tx_hash, account_id, tx, release_block, status)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (tx_hash) DO NOTHING
+            "#,
+            tx.hash().as_ref(),
+            i64::from(*account_id),
+            tx_json,
+            i64::from(*release_block),
+            WITHDRAWAL_PENDING
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.withdrawal_timelocks.store_pending_withdrawal",
+            start.elapsed()
+        );
+        Ok(())
+    }
+
+    /// Loads the pending withdrawals which timelocks expire by the given block.
+    pub async fn load_releasable_withdrawals(
+        &mut self,
+        last_block: BlockNumber,
+    ) -> QueryResult<Vec<StoragePendingWithdrawal>> {
+        let start = Instant::now();
+        let withdrawals = sqlx::query_as!(
+            StoragePendingWithdrawal,
+            r#"
+            SELECT * FROM timelocked_withdrawals
+            WHERE status = $1 AND release_block <= $2
+            ORDER BY release_block, created_at
+            "#,
+            WITHDRAWAL_PENDING,
+            i64::from(*last_block)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.withdrawal_timelocks.load_releasable_withdrawals",
+            start.elapsed()
+        );
+        Ok(withdrawals)
+    }
+
+    /// Loads the held withdrawal with the given hash.
+    pub async fn get_pending_withdrawal(
+        &mut self,
+        tx_hash: &TxHash,
+    ) -> QueryResult<Option<StoragePendingWithdrawal>> {
+        let start = Instant::now();
+        let withdrawal = sqlx::query_as!(
+            StoragePendingWithdrawal,
+            "SELECT * FROM timelocked_withdrawals WHERE tx_hash = $1",
+            tx_hash.as_ref()
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.withdrawal_timelocks.get_pending_withdrawal",
+            start.elapsed()
+        );
+        Ok(withdrawal)
+    }
+
+    /// Loads the held withdrawals of the account, the latest ones first.
+    pub async fn load_account_withdrawals(
+        &mut self,
+        account_id: AccountId,
+    ) -> QueryResult<Vec<StoragePendingWithdrawal>> {
+        let start = Instant::now();
+        let withdrawals = sqlx::query_as!(
+            StoragePendingWithdrawal,
+            r#"
+            SELECT * FROM timelocked_withdrawals
+            WHERE account_id = $1
+            ORDER BY created_at DESC
+            "#,
+            i64::from(*account_id)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.withdrawal_timelocks.load_account_withdrawals",
+            start.elapsed()
+        );
+        Ok(withdrawals)
+    }
+
+    /// Updates the status of the held withdrawal, returns `false` if the withdrawal doesn't have
+    /// the expected status anymore, e.g. it was cancelled or released concurrently.
+    pub async fn update_withdrawal_status(
+        &mut self,
+        tx_hash: &TxHash,
+        current_status: &str,
+        new_status: &str,
+        fail_reason: Option<String>,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let updated = sqlx::query!(
+            r#"
+            UPDATE timelocked_withdrawals
+            SET status = $3, fail_reason = $4, notified = false, updated_at = now()
+            WHERE tx_hash = $1 AND status = $2
+            "#,
+            tx_hash.as_ref(),
+            current_status,
+            new_status,
+            fail_reason
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!(
+            "sql.withdrawal_timelocks.update_withdrawal_status",
+            start.elapsed()
+        );
+        Ok(updated > 0)
+    }
+
+    /// Loads the held withdrawals which last status change is not reported yet.
+    pub async fn load_unnotified_withdrawals(
+        &mut self,
+    ) -> QueryResult<Vec<StoragePendingWithdrawal>> {
+        let start = Instant::now();
+        let withdrawals = sqlx::query_as!(
+            StoragePendingWithdrawal,
+            r#"
+            SELECT * FROM timelocked_withdrawals
+            WHERE notified = false
+            ORDER BY updated_at
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.withdrawal_timelocks.load_unnotified_withdrawals",
+            start.elapsed()
+        );
+        Ok(withdrawals)
+    }
+
+    /// Marks the given status of the withdrawal as reported. If the status has changed
+    /// since it was loaded, the new one is still reported.
+    pub async fn mark_withdrawal_notified(
+        &mut self,
+        tx_hash: &TxHash,
+        status: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE timelocked_withdrawals SET notified = true WHERE tx_hash = $1 AND status = $2",
+            tx_hash.as_ref(),
+            status
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.withdrawal_timelocks.mark_withdrawal_notified",
+            start.elapsed()
+        );
+        Ok(())
+    }
+}
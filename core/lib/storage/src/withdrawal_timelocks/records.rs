@@ -0,0 +1,30 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// Timelock of the account delaying the withdrawals above the threshold.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageWithdrawalTimelock {
+    pub account_id: i64,
+    /// Minimum USD value of the delayed withdrawals.
+    pub threshold_usd: i64,
+    pub delay_blocks: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Withdrawal held until the timelock of the account expires.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoragePendingWithdrawal {
+    pub tx_hash: Vec<u8>,
+    pub account_id: i64,
+    /// Serialized `SignedZkSyncTx` with the already verified signatures.
+    pub tx: serde_json::Value,
+    /// Withdrawal is sent to the mempool once this block is created.
+    pub release_block: i64,
+    /// One of `pending`, `released`, `cancelled` or `rejected`.
+    pub status: String,
+    pub fail_reason: Option<String>,
+    pub notified: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
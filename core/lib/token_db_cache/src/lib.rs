@@ -3,15 +3,22 @@ use std::{collections::HashMap, sync::Arc};
 
 use tokio::sync::RwLock;
 
+use zksync_shared_cache::SharedCache;
 use zksync_storage::StorageProcessor;
 use zksync_types::tokens::TokenMarketVolume;
 use zksync_types::{Token, TokenId, TokenLike, NFT};
 
+/// Namespace of the tokens in the shared cache.
+const SHARED_CACHE_NAMESPACE: &str = "tokens";
+
 #[derive(Debug, Clone, Default)]
 pub struct TokenDBCache {
-    cache: Arc<RwLock<HashMap<TokenLike, (Token, Instant)>>>,
+    /// Cached tokens along with the time and the shared cache generation of their update.
+    cache: Arc<RwLock<HashMap<TokenLike, (Token, Instant, u32)>>>,
     nft_tokens: Arc<RwLock<HashMap<TokenId, NFT>>>,
     token_invalidate_cache: Duration,
+    /// Cache shared with the other processes, which is consulted before the database.
+    shared: Option<SharedCache>,
 }
 
 impl TokenDBCache {
@@ -22,6 +29,29 @@ impl TokenDBCache {
         }
     }
 
+    /// Shares the cached tokens with the other processes. The local entries are dropped on
+    /// every change of the shared cache generation, so all the processes see the same tokens.
+    pub fn with_shared_cache(mut self, shared: Option<SharedCache>) -> Self {
+        self.shared = shared;
+        self
+    }
+
+    fn generation(&self) -> u32 {
+        self.shared
+            .as_ref()
+            .map(SharedCache::generation)
+            .unwrap_or_default()
+    }
+
+    async fn get_local(&self, token_query: &TokenLike) -> Option<Token> {
+        let cache = self.cache.read().await;
+        let (token, update_time, generation) = cache.get(token_query)?;
+        if update_time.elapsed() < self.token_invalidate_cache && *generation == self.generation() {
+            return Some(token.clone());
+        }
+        None
+    }
+
     /// Version of `get_token` that only attempts to find the token in the cache.
     /// This method should be used in places that don't require the DB connection itself,
     /// so taking a connection from the pool is avoided.
@@ -35,13 +65,7 @@ impl TokenDBCache {
     ) -> Option<Token> {
         let token_query = token_query.into();
         // Just return token from cache.
-        if let Some((token, update_time)) = self.cache.read().await.get(&token_query.to_lowercase())
-        {
-            if update_time.elapsed() < self.token_invalidate_cache {
-                return Some(token.clone());
-            }
-        }
-        None
+        self.get_local(&token_query.to_lowercase()).await
     }
 
     /// Performs case-insensitive token search.
@@ -51,26 +75,40 @@ impl TokenDBCache {
         token_query: impl Into<TokenLike>,
     ) -> anyhow::Result<Option<Token>> {
         let token_query = token_query.into();
+        let cache_key = token_query.to_lowercase();
         // Just return token from cache.
-        if let Some((token, update_time)) = self.cache.read().await.get(&token_query.to_lowercase())
-        {
-            if update_time.elapsed() < self.token_invalidate_cache {
-                return Ok(Some(token.clone()));
-            }
+        if let Some(token) = self.get_local(&cache_key).await {
+            return Ok(Some(token));
         }
-        // Tries to fetch token from the underlying database.
-        let token = {
-            storage
-                .tokens_schema()
-                .get_token(token_query.clone())
-                .await?
+        // Tries to fetch token from the shared cache.
+        let shared_key = format!("{:?}", cache_key);
+        let generation = self.generation();
+        let shared_token = match &self.shared {
+            Some(shared) => shared.get(SHARED_CACHE_NAMESPACE, &shared_key).await,
+            None => None,
+        };
+        let token = match shared_token {
+            Some(token) => Some(token),
+            None => {
+                // Tries to fetch token from the underlying database.
+                let token = storage
+                    .tokens_schema()
+                    .get_token(token_query.clone())
+                    .await?;
+                if let (Some(shared), Some(token)) = (&self.shared, &token) {
+                    shared
+                        .insert(SHARED_CACHE_NAMESPACE, &shared_key, token)
+                        .await;
+                }
+                token
+            }
         };
         // Stores received token into the local cache.
         if let Some(token) = &token {
             self.cache
                 .write()
                 .await
-                .insert(token_query.to_lowercase(), (token.clone(), Instant::now()));
+                .insert(cache_key, (token.clone(), Instant::now(), generation));
         }
 
         Ok(token)
@@ -110,16 +148,63 @@ impl TokenDBCache {
         Ok(None)
     }
 
+    /// Fills the cache with all the tokens. Symbols shared by several tokens are only cached
+    /// for the tokens they are aliased to.
     pub async fn fill_token_cache(&mut self, storage: &mut StorageProcessor<'_>) {
         let tokens = Self::get_all_tokens(storage).await.unwrap();
+        let aliases = storage.tokens_schema().load_symbol_aliases().await.unwrap();
+        let mut symbol_counts = HashMap::new();
+        for token in &tokens {
+            *symbol_counts
+                .entry(token.symbol.to_lowercase())
+                .or_insert(0usize) += 1;
+        }
+
+        let generation = self.generation();
         let mut cache = self.cache.write().await;
+        let mut tokens_by_id = HashMap::new();
         for token in tokens {
             let symbol = TokenLike::Symbol(token.symbol.clone());
             let token_id = TokenLike::Id(token.id);
             let address = TokenLike::Address(token.address);
-            cache.insert(symbol.to_lowercase(), (token.clone(), Instant::now()));
-            cache.insert(token_id.to_lowercase(), (token.clone(), Instant::now()));
-            cache.insert(address.to_lowercase(), (token.clone(), Instant::now()));
+            let entry = (token.clone(), Instant::now(), generation);
+            if symbol_counts[&token.symbol.to_lowercase()] == 1 {
+                cache.insert(symbol.to_lowercase(), entry.clone());
+            } else {
+                let qualified = TokenLike::Symbol(token.qualified_symbol());
+                cache.insert(qualified.to_lowercase(), entry.clone());
+            }
+            cache.insert(token_id.to_lowercase(), entry.clone());
+            cache.insert(address.to_lowercase(), entry);
+            tokens_by_id.insert(token.id, token);
+        }
+        for alias in aliases {
+            if let Some(token) = tokens_by_id.get(&alias.token_id) {
+                let entry = (token.clone(), Instant::now(), generation);
+                cache.insert(TokenLike::Symbol(alias.alias), entry);
+            }
+        }
+    }
+
+    /// Returns the tokens sharing the queried symbol, if the query is a symbol that can't be
+    /// resolved to a single token. Allows to suggest the unambiguous queries to the users.
+    pub async fn symbol_collisions(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        token_query: &TokenLike,
+    ) -> anyhow::Result<Vec<Token>> {
+        let symbol = match token_query {
+            TokenLike::Symbol(symbol) => symbol,
+            _ => return Ok(Vec::new()),
+        };
+        if self.get_token(storage, token_query).await?.is_some() {
+            return Ok(Vec::new());
+        }
+        let tokens = storage.tokens_schema().get_tokens_by_symbol(symbol).await?;
+        if tokens.len() > 1 {
+            Ok(tokens)
+        } else {
+            Ok(Vec::new())
         }
     }
 
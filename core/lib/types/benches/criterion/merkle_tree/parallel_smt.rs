@@ -169,6 +169,41 @@ fn smt_root_hash_cached(b: &mut Bencher<'_>, size: u32) {
     );
 }
 
+/// Measures the time of recalculating the root hash of a hashed SMT after applying
+/// the updates of a sealed block.
+///
+/// Only the paths of the updated accounts are rehashed, so the bench is expected to
+/// get much better results than `smt_root_hash` for the tree of the same size.
+fn smt_root_hash_block_updates(b: &mut Bencher<'_>, size: u32, updates: u32) {
+    let depth = zksync_crypto::params::account_tree_depth();
+
+    // Create a tree, fill it with some accounts and calculate its root hash.
+    let mut tree = RealSMT::new(depth);
+    for (id, account) in (0..size).map(gen_account).enumerate() {
+        let id = id as u32;
+        tree.insert(id, account.clone());
+    }
+    tree.root_hash();
+
+    // Updated accounts are spread over the tree, like the accounts touched by a block.
+    let step = (size / updates).max(1);
+    let setup = || {
+        let mut tree = tree.clone();
+        for id in (0..size).step_by(step as usize).take(updates as usize) {
+            tree.insert(id, gen_account(id + size));
+        }
+        tree
+    };
+
+    b.iter_batched_ref(
+        setup,
+        |tree| {
+            let _hash = black_box(tree.root_hash());
+        },
+        BatchSize::LargeInput,
+    );
+}
+
 /// Measures the time to `drop` a tree with calculated cache.
 fn smt_drop(b: &mut Bencher<'_>, size: u32) {
     let depth = zksync_crypto::params::account_tree_depth();
@@ -217,6 +252,19 @@ pub fn bench_merkle_tree(c: &mut Criterion) {
         c.bench_function(&bench_name, |b| smt_root_hash_cached(b, *tree_size));
     }
 
+    for &(tree_size, updates) in &[(20_000, 100), (20_000, 1000), (20_000, 10_000)] {
+        let bench_name = format!(
+            "Parallel SMT root hash (after block) / size {}, updates {}",
+            tree_size, updates
+        );
+        c.bench_function(&bench_name, |b| {
+            smt_root_hash_block_updates(b, tree_size, updates)
+        });
+    }
+    c.bench_function("Parallel SMT root hash / size 20000", |b| {
+        smt_root_hash(b, 20_000)
+    });
+
     // Drop benchmarks.
     for tree_size in &[10, 100, 1000, 10_000] {
         let bench_name = format!("Parallel SMT drop / size {}", tree_size);
@@ -1,8 +1,8 @@
 //! Benchmarks for the Parallel Sparse Merkle Tree.
 
 use criterion::{black_box, BatchSize, Bencher, Criterion, Throughput};
-use zksync_crypto::merkle_tree::{hasher::Hasher, RescueHasher};
-use zksync_crypto::Engine;
+use zksync_crypto::merkle_tree::{hasher::Hasher, set_hash_backend, HashBackend, RescueHasher};
+use zksync_crypto::{ff::PrimeField, Engine, Fr};
 
 const SMALL_INPUT_SIZE: usize = 16; // 16 bits / 2 bytes
 const BIG_INPUT_SIZE: usize = zksync_crypto::params::MAX_CIRCUIT_MSG_HASH_BITS; // Biggest supported size.
@@ -48,6 +48,20 @@ fn rescue_big(b: &mut Bencher<'_>) {
     );
 }
 
+/// Measures the time of merging two tree nodes with the given hash backend.
+/// The AVX2 backend is measured only if it is enabled and supported.
+fn rescue_compress(b: &mut Bencher<'_>, backend: HashBackend) {
+    let hasher = RescueHasher::<Engine>::default();
+    let lhs = Fr::from_str("1").unwrap();
+    let rhs = Fr::from_str("2").unwrap();
+    set_hash_backend(backend);
+
+    b.iter(|| {
+        let _ = hasher.compress(black_box(&lhs), black_box(&rhs), 0);
+    });
+    set_hash_backend(HashBackend::Reference);
+}
+
 pub fn bench_rescue_hasher(c: &mut Criterion) {
     let mut small_input_group = c.benchmark_group("Small input");
     small_input_group.throughput(Throughput::Bytes((SMALL_INPUT_SIZE / 8) as u64));
@@ -58,4 +72,13 @@ pub fn bench_rescue_hasher(c: &mut Criterion) {
     big_input_group.throughput(Throughput::Bytes((BIG_INPUT_SIZE / 8) as u64));
     big_input_group.bench_function("Rescue Hasher", rescue_big);
     big_input_group.finish();
+
+    let mut compress_group = c.benchmark_group("Compress");
+    for backend in [HashBackend::Reference, HashBackend::Avx2] {
+        if backend.is_supported() {
+            let bench_name = format!("Rescue Hasher ({:?} backend)", backend);
+            compress_group.bench_function(&bench_name, |b| rescue_compress(b, backend));
+        }
+    }
+    compress_group.finish();
 }
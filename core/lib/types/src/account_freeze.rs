@@ -0,0 +1,21 @@
+//! Compliance holds of the accounts.
+//!
+//! The operator may freeze an account, e.g. on the request of a regulator. A frozen account can't
+//! send funds to other accounts of the network, but its exits (withdrawals, forced exits and full
+//! exits) are never blocked, so the funds can't be locked in the rollup. Freezes are not a part
+//! of the rollup state and are enforced by the state keeper only.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::AccountId;
+
+/// Active freeze of the account.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountFreeze {
+    pub account_id: AccountId,
+    /// Reason provided by the operator, e.g. the reference of the compliance case.
+    pub reason: String,
+    pub frozen_at: DateTime<Utc>,
+}
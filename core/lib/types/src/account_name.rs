@@ -0,0 +1,88 @@
+//! Human-readable names of the accounts.
+//!
+//! The name is registered by the owner of the address, the registration fee is paid by the
+//! transfer of the owner to themselves submitted along with the registration. Names are
+//! unique and resolve to the address once the fee transfer is executed.
+
+use thiserror::Error;
+
+use crate::{tx::TxHash, Address};
+
+/// Minimum number of characters in the account name.
+pub const MIN_ACCOUNT_NAME_LEN: usize = 3;
+/// Maximum number of characters in the account name.
+pub const MAX_ACCOUNT_NAME_LEN: usize = 32;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AccountNameError {
+    #[error(
+        "Name must be from {} to {} characters long",
+        MIN_ACCOUNT_NAME_LEN,
+        MAX_ACCOUNT_NAME_LEN
+    )]
+    WrongLength,
+    #[error("Name can contain only lowercase latin letters, digits and hyphens")]
+    InvalidCharacter,
+    #[error("Name can't start or end with a hyphen")]
+    InvalidHyphen,
+}
+
+/// Checks that the name can be registered.
+pub fn validate_account_name(name: &str) -> Result<(), AccountNameError> {
+    if name.len() < MIN_ACCOUNT_NAME_LEN || name.len() > MAX_ACCOUNT_NAME_LEN {
+        return Err(AccountNameError::WrongLength);
+    }
+    if !name
+        .bytes()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'-')
+    {
+        return Err(AccountNameError::InvalidCharacter);
+    }
+    if name.starts_with('-') || name.ends_with('-') {
+        return Err(AccountNameError::InvalidHyphen);
+    }
+    Ok(())
+}
+
+/// Returns the message the owner of the address signs to register the name.
+/// The message includes the hash of the fee transaction, so the fee can't be used
+/// for another registration.
+pub fn register_account_name_message(name: &str, address: Address, fee_tx_hash: TxHash) -> String {
+    format!(
+        "Register zkSync name: {}\nAddress: {:?}\nFee transaction: {}",
+        name,
+        address,
+        hex::encode(fee_tx_hash.as_ref())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_name_validation() {
+        assert_eq!(validate_account_name("alice"), Ok(()));
+        assert_eq!(validate_account_name("alice-42"), Ok(()));
+        assert_eq!(
+            validate_account_name("al"),
+            Err(AccountNameError::WrongLength)
+        );
+        assert_eq!(
+            validate_account_name(&"a".repeat(MAX_ACCOUNT_NAME_LEN + 1)),
+            Err(AccountNameError::WrongLength)
+        );
+        assert_eq!(
+            validate_account_name("Alice"),
+            Err(AccountNameError::InvalidCharacter)
+        );
+        assert_eq!(
+            validate_account_name("alice.eth"),
+            Err(AccountNameError::InvalidCharacter)
+        );
+        assert_eq!(
+            validate_account_name("-alice"),
+            Err(AccountNameError::InvalidHyphen)
+        );
+    }
+}
@@ -1,7 +1,9 @@
 use crate::block::Block;
+use crate::prover::ProofSystemVersion;
 use ethabi::Token;
+use parity_crypto::Keccak256;
 use serde::{Deserialize, Serialize};
-use zksync_basic_types::{BlockNumber, U256};
+use zksync_basic_types::{BlockNumber, H256, U256};
 use zksync_crypto::proof::EncodedAggregatedProof;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +29,13 @@ pub fn stored_block_info(block: &Block) -> Token {
     ])
 }
 
+/// Returns the hash of the block info as it's stored on the contract in `storedBlockHashes`.
+pub fn stored_block_info_hash(block: &Block) -> H256 {
+    ethabi::encode(&[stored_block_info(block)])
+        .keccak256()
+        .into()
+}
+
 impl BlocksCommitOperation {
     pub fn get_eth_tx_args(&self) -> Vec<Token> {
         let stored_block_info = stored_block_info(&self.last_committed_block);
@@ -78,6 +87,9 @@ pub struct BlocksCreateProofOperation {
 pub struct BlocksProofOperation {
     pub blocks: Vec<Block>,
     pub proof: EncodedAggregatedProof,
+    /// Proof system the proof is created with, determines the verifier it's sent to.
+    #[serde(default)]
+    pub proof_system: ProofSystemVersion,
 }
 
 impl BlocksProofOperation {
@@ -175,6 +187,20 @@ impl std::str::FromStr for AggregatedActionType {
     }
 }
 
+/// Returns the message that operators committee members sign to approve the action
+/// over the blocks: `keccak256(action_type || (block_number || block_commitment)...)`.
+///
+/// Every member builds the message from the blocks in its own storage,
+/// so approvals only match if members agree on the content of the blocks.
+pub fn committee_approval_message(action_type: AggregatedActionType, blocks: &[Block]) -> H256 {
+    let mut bytes = action_type.to_string().into_bytes();
+    for block in blocks {
+        bytes.extend_from_slice(&block.block_number.to_be_bytes());
+        bytes.extend_from_slice(block.block_commitment.as_bytes());
+    }
+    bytes.keccak256().into()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AggregatedOperation {
     CommitBlocks(BlocksCommitOperation),
@@ -209,6 +235,20 @@ impl AggregatedOperation {
         }
     }
 
+    pub fn get_blocks(&self) -> &[Block] {
+        match self {
+            AggregatedOperation::CommitBlocks(op) => &op.blocks,
+            AggregatedOperation::CreateProofBlocks(op) => &op.blocks,
+            AggregatedOperation::PublishProofBlocksOnchain(op) => &op.blocks,
+            AggregatedOperation::ExecuteBlocks(op) => &op.blocks,
+        }
+    }
+
+    /// Returns the message that operators committee members sign to approve the operation.
+    pub fn committee_approval_message(&self) -> H256 {
+        committee_approval_message(self.get_action_type(), self.get_blocks())
+    }
+
     pub fn is_commit(&self) -> bool {
         matches!(self.get_action_type(), AggregatedActionType::CommitBlocks)
     }
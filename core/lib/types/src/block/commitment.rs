@@ -0,0 +1,202 @@
+//! Block commitment, the value the smart contract verifies the block proof against.
+//!
+//! The commitment binds the block header to its public data, so anyone having the public data
+//! of the block (e.g. served by the API or taken from the L1 calldata) can recompute it and
+//! compare with the commitment stored in the contract. Functions of this module are pure and
+//! don't depend on the server state.
+
+use parity_crypto::digest::sha256;
+use thiserror::Error;
+use zksync_basic_types::{AccountId, BlockNumber, H256, U256};
+use zksync_crypto::params::CHUNK_BYTES;
+
+use crate::operations::{
+    ChangePubKeyOp, DepositOp, ForcedExitOp, FullExitOp, WithdrawNFTOp, WithdrawOp, ZkSyncOp,
+};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum PubdataError {
+    #[error("Public data length {0} is not a multiple of the chunk size")]
+    UnalignedLength(usize),
+    #[error("Unknown operation type {op_code:#04x} at offset {offset}")]
+    UnknownOperation { op_code: u8, offset: usize },
+    #[error("Operation at offset {0} exceeds the public data")]
+    TruncatedOperation(usize),
+}
+
+/// Calculates the block commitment.
+///
+/// Values are hashed with SHA-256 one by one, every step hashing the result of the previous one
+/// concatenated with the next value:
+///
+/// 1. block number and fee account ID, both as 32-byte big-endian integers;
+/// 2. state root hash before the block;
+/// 3. state root hash after the block;
+/// 4. block timestamp as a 32-byte big-endian integer;
+/// 5. public data of the block followed by the onchain operations commitment.
+///
+/// State root hashes are the ones encoded for the smart contract, see `Block::encode_fr_for_eth`.
+pub fn block_commitment(
+    block_number: BlockNumber,
+    fee_account: AccountId,
+    old_state_hash: H256,
+    new_state_hash: H256,
+    timestamp: u64,
+    onchain_op_commitment: &[u8],
+    public_data: &[u8],
+) -> H256 {
+    let mut hash_arg = vec![0u8; 64];
+    U256::from(*block_number).to_big_endian(&mut hash_arg[0..32]);
+    U256::from(*fee_account).to_big_endian(&mut hash_arg[32..]);
+    hash_arg = sha256(&hash_arg).to_vec();
+
+    hash_arg.extend_from_slice(old_state_hash.as_bytes());
+    hash_arg = sha256(&hash_arg).to_vec();
+
+    hash_arg.extend_from_slice(new_state_hash.as_bytes());
+    hash_arg = sha256(&hash_arg).to_vec();
+
+    hash_arg.resize(64, 0u8);
+    U256::from(timestamp).to_big_endian(&mut hash_arg[32..]);
+    hash_arg = sha256(&hash_arg).to_vec();
+
+    hash_arg.extend_from_slice(public_data);
+    hash_arg.extend_from_slice(onchain_op_commitment);
+    H256::from_slice(&sha256(&hash_arg))
+}
+
+/// Restores the onchain operations commitment from the public data of the block.
+///
+/// The commitment holds a byte per chunk of the public data, which is `0x01` for the first
+/// chunk of the operations processed by the smart contract and `0x00` otherwise.
+pub fn onchain_op_commitment(public_data: &[u8]) -> Result<Vec<u8>, PubdataError> {
+    if public_data.len() % CHUNK_BYTES != 0 {
+        return Err(PubdataError::UnalignedLength(public_data.len()));
+    }
+
+    let mut commitment = vec![0u8; public_data.len() / CHUNK_BYTES];
+    let mut offset = 0;
+    while offset < public_data.len() {
+        let op_code = public_data[offset];
+        let length = ZkSyncOp::public_data_length(&public_data[offset..])
+            .map_err(|_| PubdataError::UnknownOperation { op_code, offset })?;
+        if offset + length > public_data.len() {
+            return Err(PubdataError::TruncatedOperation(offset));
+        }
+        if is_onchain_op_code(op_code) {
+            commitment[offset / CHUNK_BYTES] = 0x01;
+        }
+        offset += length;
+    }
+    Ok(commitment)
+}
+
+/// Calculates the block commitment from the public data only, restoring the onchain operations
+/// commitment from it.
+pub fn block_commitment_from_public_data(
+    block_number: BlockNumber,
+    fee_account: AccountId,
+    old_state_hash: H256,
+    new_state_hash: H256,
+    timestamp: u64,
+    public_data: &[u8],
+) -> Result<H256, PubdataError> {
+    let onchain_op_commitment = onchain_op_commitment(public_data)?;
+    Ok(block_commitment(
+        block_number,
+        fee_account,
+        old_state_hash,
+        new_state_hash,
+        timestamp,
+        &onchain_op_commitment,
+        public_data,
+    ))
+}
+
+/// Must be kept in sync with `ZkSyncOp::is_onchain_operation`.
+fn is_onchain_op_code(op_code: u8) -> bool {
+    matches!(
+        op_code,
+        DepositOp::OP_CODE
+            | WithdrawOp::OP_CODE
+            | WithdrawNFTOp::OP_CODE
+            | FullExitOp::OP_CODE
+            | ChangePubKeyOp::OP_CODE
+            | ForcedExitOp::OP_CODE
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::TransferOp;
+
+    /// Test vectors for the implementations of the commitment in other languages.
+    #[test]
+    fn commitment_test_vectors() {
+        // Empty block padded with the noops.
+        let public_data = vec![0u8; 10 * CHUNK_BYTES];
+        assert_eq!(onchain_op_commitment(&public_data).unwrap(), vec![0u8; 10]);
+        let commitment = block_commitment_from_public_data(
+            BlockNumber(1),
+            AccountId(0),
+            H256::zero(),
+            H256::repeat_byte(0x11),
+            1_600_000_000,
+            &public_data,
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(commitment),
+            "5c7445f8a523f5130f5518f9c05387a2e051df9d3dd8a664c6587527ce268615"
+        );
+
+        // Deposit followed by the transfer and two noops.
+        let mut public_data = vec![DepositOp::OP_CODE];
+        public_data.resize(DepositOp::CHUNKS * CHUNK_BYTES, 0xaa);
+        public_data.push(TransferOp::OP_CODE);
+        public_data.resize((DepositOp::CHUNKS + TransferOp::CHUNKS) * CHUNK_BYTES, 0xbb);
+        public_data.resize(10 * CHUNK_BYTES, 0x00);
+        let mut expected_onchain_op_commitment = vec![0u8; 10];
+        expected_onchain_op_commitment[0] = 0x01;
+        assert_eq!(
+            onchain_op_commitment(&public_data).unwrap(),
+            expected_onchain_op_commitment
+        );
+        let commitment = block_commitment_from_public_data(
+            BlockNumber(42),
+            AccountId(7),
+            H256::repeat_byte(0x01),
+            H256::repeat_byte(0x02),
+            1_650_000_000,
+            &public_data,
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(commitment),
+            "f3466b514c5cfa85c9a4324e97bfcbd8210111b54ace027db00b9e08006081fa"
+        );
+    }
+
+    #[test]
+    fn malformed_public_data() {
+        assert_eq!(
+            onchain_op_commitment(&[0u8; CHUNK_BYTES + 1]),
+            Err(PubdataError::UnalignedLength(CHUNK_BYTES + 1))
+        );
+        let mut public_data = vec![0u8; 2 * CHUNK_BYTES];
+        public_data[CHUNK_BYTES] = 0xff;
+        assert_eq!(
+            onchain_op_commitment(&public_data),
+            Err(PubdataError::UnknownOperation {
+                op_code: 0xff,
+                offset: CHUNK_BYTES
+            })
+        );
+        public_data[CHUNK_BYTES] = DepositOp::OP_CODE;
+        assert_eq!(
+            onchain_op_commitment(&public_data),
+            Err(PubdataError::TruncatedOperation(CHUNK_BYTES))
+        );
+    }
+}
@@ -91,7 +91,8 @@ impl IncompleteBlock {
         block
     }
 
-    fn chunks_used(&self) -> usize {
+    /// Returns the amount of chunks used by the executed operations.
+    pub fn chunks_used(&self) -> usize {
         self.block_transactions
             .iter()
             .filter_map(ExecutedOperations::get_executed_op)
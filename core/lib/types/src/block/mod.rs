@@ -1,10 +1,13 @@
 //! zkSync network block definition.
 
 use super::{AccountId, BlockNumber, Fr, PriorityOp, ZkSyncOp};
-use crate::{tx::error::CloseOperationsDisabled, SignedZkSyncTx, TokenId};
+use crate::{
+    gas_counter::{CalldataCost, CommitCost, VerifyCost},
+    tx::error::CloseOperationsDisabled,
+    SignedZkSyncTx, TokenId,
+};
 use chrono::Utc;
 use chrono::{DateTime, TimeZone};
-use parity_crypto::digest::sha256;
 use parity_crypto::Keccak256;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -13,6 +16,7 @@ use zksync_crypto::franklin_crypto::bellman::pairing::ff::{PrimeField, PrimeFiel
 use zksync_crypto::params::{CHUNK_BIT_WIDTH, CHUNK_BYTES};
 use zksync_crypto::serialization::FrSerde;
 
+pub mod commitment;
 mod incomplete_block;
 
 pub use incomplete_block::IncompleteBlock;
@@ -252,7 +256,7 @@ impl Block {
             new_root_hash,
             block_commitment: H256::default(),
         };
-        block.block_commitment = Block::get_commitment(
+        block.block_commitment = commitment::block_commitment(
             block.block_number,
             block.fee_account,
             previous_block_root_hash,
@@ -296,7 +300,7 @@ impl Block {
             timestamp,
         };
         block.block_chunks_size = block.smallest_block_size(available_block_chunks_sizes);
-        block.block_commitment = Block::get_commitment(
+        block.block_commitment = commitment::block_commitment(
             block_number,
             fee_account,
             previous_block_root_hash,
@@ -446,35 +450,6 @@ impl Block {
         res
     }
 
-    fn get_commitment(
-        block_number: BlockNumber,
-        fee_account: AccountId,
-        old_state_hash: H256,
-        new_state_hash: H256,
-        timestamp: u64,
-        onchain_op_commitment: &[u8],
-        public_data: &[u8],
-    ) -> H256 {
-        let mut hash_arg = vec![0u8; 64];
-        U256::from(*block_number).to_big_endian(&mut hash_arg[0..32]);
-        U256::from(*fee_account).to_big_endian(&mut hash_arg[32..]);
-        hash_arg = sha256(&hash_arg).to_vec();
-
-        hash_arg.extend_from_slice(old_state_hash.as_bytes());
-        hash_arg = sha256(&hash_arg).to_vec();
-
-        hash_arg.extend_from_slice(new_state_hash.as_bytes());
-        hash_arg = sha256(&hash_arg).to_vec();
-
-        hash_arg.resize(64, 0u8);
-        U256::from(timestamp).to_big_endian(&mut hash_arg[32..]);
-        hash_arg = sha256(&hash_arg).to_vec();
-
-        hash_arg.extend_from_slice(public_data);
-        hash_arg.extend_from_slice(onchain_op_commitment);
-        H256::from_slice(&sha256(&hash_arg))
-    }
-
     pub fn processable_ops_pubdata(&self) -> Vec<Vec<u8>> {
         self.block_transactions
             .iter()
@@ -489,6 +464,50 @@ impl Block {
             .collect()
     }
 
+    /// Returns the chunks, public data and estimated gas consumed by every type of the
+    /// operations executed in the block, in order of the first operation of each type.
+    /// The chunks left unused are accounted as the noops.
+    pub fn op_type_stats(&self) -> Vec<OpTypeStats> {
+        let mut stats: Vec<OpTypeStats> = Vec::new();
+        let mut chunks_used = 0;
+        for op in self
+            .block_transactions
+            .iter()
+            .filter_map(ExecutedOperations::get_executed_op)
+        {
+            let op_type = op.op_name();
+            let index = match stats.iter().position(|stats| stats.op_type == op_type) {
+                Some(index) => index,
+                None => {
+                    stats.push(OpTypeStats::new(op_type));
+                    stats.len() - 1
+                }
+            };
+            let public_data = op.public_data();
+            let op_stats = &mut stats[index];
+            op_stats.count += 1;
+            op_stats.chunks += op.chunks() as u64;
+            op_stats.pubdata_bytes += public_data.len() as u64;
+            op_stats.commit_gas += CommitCost::op_cost(op).as_u64();
+            op_stats.verify_gas += VerifyCost::op_cost(op).as_u64();
+            op_stats.pubdata_gas += CalldataCost::bytes_cost(&public_data);
+            chunks_used += op.chunks();
+        }
+
+        let padding_chunks = self.block_chunks_size.saturating_sub(chunks_used);
+        if padding_chunks > 0 {
+            let padding_bytes = padding_chunks * CHUNK_BYTES;
+            stats.push(OpTypeStats {
+                count: padding_chunks as u32,
+                chunks: padding_chunks as u64,
+                pubdata_bytes: padding_bytes as u64,
+                pubdata_gas: padding_bytes as u64 * CalldataCost::ZERO_BYTE_COST,
+                ..OpTypeStats::new("Noop")
+            });
+        }
+        stats
+    }
+
     pub fn timestamp_utc(&self) -> DateTime<Utc> {
         Utc.timestamp(self.timestamp as i64, 0)
     }
@@ -517,6 +536,41 @@ pub fn smallest_block_size_for_chunks(
     );
 }
 
+/// Resources consumed by the operations of the same type in the block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpTypeStats {
+    /// Name of the operation type, e.g. `Transfer`.
+    pub op_type: String,
+    pub count: u32,
+    pub chunks: u64,
+    pub pubdata_bytes: u64,
+    /// Estimated gas of processing the operations in the `commitBlocks` call.
+    pub commit_gas: u64,
+    /// Estimated gas of processing the operations in the `executeBlocks` call.
+    pub verify_gas: u64,
+    /// Gas of the public data of the operations passed in the `commitBlocks` calldata.
+    pub pubdata_gas: u64,
+}
+
+impl OpTypeStats {
+    fn new(op_type: &str) -> Self {
+        Self {
+            op_type: op_type.to_string(),
+            count: 0,
+            chunks: 0,
+            pubdata_bytes: 0,
+            commit_gas: 0,
+            verify_gas: 0,
+            pubdata_gas: 0,
+        }
+    }
+
+    /// Total estimated L1 gas consumed by the operations.
+    pub fn estimated_gas(&self) -> u64 {
+        self.commit_gas + self.verify_gas + self.pubdata_gas
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OnchainOperationsBlockInfo {
     pub public_data_offset: u32,
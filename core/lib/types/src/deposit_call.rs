@@ -0,0 +1,188 @@
+//! Deposits carrying the L2 call, made via the `DepositRouter` contract.
+//!
+//! The router deposits the funds to the sender's own account and emits the `DepositCall` event with
+//! the serial id of the deposit. The call is a JSON-encoded list of the L2 transactions of the
+//! sender, signed in advance, which the server submits once the deposit is executed. The deposit
+//! transaction on L1 already authorizes the call, so the transactions don't need the Ethereum
+//! signatures.
+
+use std::convert::TryFrom;
+
+use ethabi::{decode, ParamType};
+use thiserror::Error;
+
+use zksync_basic_types::{Log, H256};
+
+use crate::{tx::TransactionError, Address, SerialId, ZkSyncTx};
+
+/// Maximum number of the transactions in the call.
+pub const MAX_CALL_TXS: usize = 4;
+
+#[derive(Debug, Error)]
+#[allow(clippy::large_enum_variant)]
+pub enum DepositCallEventParseError {
+    #[error("Cannot parse log for Deposit Call Event {0:?}")]
+    ParseLogError(Log),
+    #[error("Cannot parse log for Deposit Call Event {0:?}")]
+    ParseError(ethabi::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum DepositCallError {
+    #[error("Call is not a list of the transactions: {0}")]
+    InvalidEncoding(String),
+    #[error("Call must contain from 1 to {} transactions", MAX_CALL_TXS)]
+    InvalidLength,
+    #[error("Transaction {0} is not allowed in the call, only transfers, swaps and public key changes are")]
+    UnsupportedTx(usize),
+    #[error("Transaction {0} is not made by the depositor")]
+    WrongAccount(usize),
+    #[error("Transaction {0} is incorrect: {1}")]
+    IncorrectTx(usize, TransactionError),
+}
+
+#[derive(Clone, Debug)]
+pub struct DepositCallEvent {
+    /// Serial id of the deposit priority operation.
+    pub serial_id: SerialId,
+    /// Sender of the deposit, the funds are deposited to it on L2.
+    pub owner: Address,
+    pub call: Vec<u8>,
+    pub eth_block: u64,
+}
+
+impl DepositCallEvent {
+    /// Signature of the `DepositCall(uint64 indexed serialId, address indexed owner, bytes call)` event.
+    pub fn topic() -> H256 {
+        ethabi::long_signature(
+            "DepositCall",
+            &[ParamType::Uint(64), ParamType::Address, ParamType::Bytes],
+        )
+    }
+
+    /// Decodes the transactions of the call and checks they can be executed on behalf of the owner.
+    pub fn decode_call(&self) -> Result<Vec<ZkSyncTx>, DepositCallError> {
+        let mut txs: Vec<ZkSyncTx> = serde_json::from_slice(&self.call)
+            .map_err(|err| DepositCallError::InvalidEncoding(err.to_string()))?;
+        if txs.is_empty() || txs.len() > MAX_CALL_TXS {
+            return Err(DepositCallError::InvalidLength);
+        }
+        for (index, tx) in txs.iter_mut().enumerate() {
+            if !matches!(
+                tx,
+                ZkSyncTx::Transfer(_) | ZkSyncTx::ChangePubKey(_) | ZkSyncTx::Swap(_)
+            ) {
+                return Err(DepositCallError::UnsupportedTx(index));
+            }
+            // Swap is made by both of its parties, the owner must be the submitter.
+            if tx.account() != self.owner {
+                return Err(DepositCallError::WrongAccount(index));
+            }
+            tx.check_correctness()
+                .map_err(|err| DepositCallError::IncorrectTx(index, err))?;
+        }
+        Ok(txs)
+    }
+}
+
+impl TryFrom<Log> for DepositCallEvent {
+    type Error = DepositCallEventParseError;
+
+    fn try_from(event: Log) -> Result<Self, Self::Error> {
+        let eth_block = match event.block_number {
+            Some(block_number) => block_number.as_u64(),
+            None => return Err(DepositCallEventParseError::ParseLogError(event)),
+        };
+        if event.topics.len() != 3 {
+            return Err(DepositCallEventParseError::ParseLogError(event));
+        }
+
+        let mut decoded_event = decode(
+            &[
+                ParamType::Bytes, // call
+            ],
+            &event.data.0,
+        )
+        .map_err(DepositCallEventParseError::ParseError)?;
+        let serial_id = event.topics[1].to_low_u64_be();
+        let owner = Address::from_slice(&event.topics[2].as_fixed_bytes()[12..]);
+        let call = decoded_event.remove(0).into_bytes().unwrap();
+        Ok(Self {
+            serial_id,
+            owner,
+            call,
+            eth_block,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tx::Withdraw, AccountId, Nonce, TokenId};
+
+    fn event(call: Vec<u8>) -> DepositCallEvent {
+        DepositCallEvent {
+            serial_id: 1,
+            owner: Address::repeat_byte(1),
+            call,
+            eth_block: 10,
+        }
+    }
+
+    #[test]
+    fn deposit_call_parsing() {
+        let call = b"[]".to_vec();
+        let log = Log {
+            address: Address::zero(),
+            topics: vec![
+                DepositCallEvent::topic(),
+                H256::from_low_u64_be(7),
+                H256::from(Address::repeat_byte(1)),
+            ],
+            data: ethabi::encode(&[ethabi::Token::Bytes(call.clone())]).into(),
+            block_hash: None,
+            block_number: Some(10u64.into()),
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+        let event = DepositCallEvent::try_from(log).unwrap();
+        assert_eq!(event.serial_id, 7);
+        assert_eq!(event.owner, Address::repeat_byte(1));
+        assert_eq!(event.call, call);
+        assert_eq!(event.eth_block, 10);
+    }
+
+    #[test]
+    fn deposit_call_validation() {
+        assert!(matches!(
+            event(b"not a json".to_vec()).decode_call(),
+            Err(DepositCallError::InvalidEncoding(_))
+        ));
+        assert!(matches!(
+            event(b"[]".to_vec()).decode_call(),
+            Err(DepositCallError::InvalidLength)
+        ));
+
+        let withdraw = Withdraw::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(1),
+            TokenId(0),
+            100u32.into(),
+            0u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        );
+        let call = serde_json::to_vec(&[ZkSyncTx::from(withdraw)]).unwrap();
+        assert!(matches!(
+            event(call).decode_call(),
+            Err(DepositCallError::UnsupportedTx(0))
+        ));
+    }
+}
@@ -0,0 +1,445 @@
+//! Encrypted mempool, an experimental option limiting the front-running of the pending transfers.
+//!
+//! Users encrypt their signed transfers to the sealing key of the operator, so the pending
+//! transfers are seen neither by the API servers nor by the readers of the database. Blobs are
+//! decrypted by the mempool only at the block formation, and the transfers are proposed right
+//! away. Blobs which can't be decrypted or contain the incorrect transfers are discarded.
+//!
+//! The sealing key is never held by a single party. It's split with the Shamir's scheme between
+//! the keyholders (see `split_sealing_key`), any `threshold` of them can decrypt the blob together.
+//! The blob is an ECIES ciphertext, which key is derived from the point `s * R`, where `s` is the
+//! sealing key and `R` is the ephemeral public key stored in the blob. Each keyholder multiplies
+//! `R` by its share of the key (see `decryption_share`), and the mempool interpolates `s * R` from
+//! the decryption shares without learning `s` (see `decrypt_transfer_with_shares`).
+
+use num::{BigUint, One, Zero};
+use parity_crypto::publickey::{
+    ec_math_utils::{public_add, public_mul_secret},
+    ecies, Generator, KeyPair, Random, Secret,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use zksync_basic_types::H256;
+
+use crate::tokens::Token;
+use crate::tx::{PackedEthSignature, Transfer};
+
+pub use parity_crypto::publickey::Public;
+
+/// Maximum size of the encrypted transfer in bytes.
+pub const MAX_ENCRYPTED_TX_SIZE: usize = 2048;
+
+/// Data authenticated along with the encrypted transfer, so the blobs encrypted to the same key
+/// for other purposes are not accepted.
+const ENCRYPTED_TX_AUTH_DATA: &[u8] = b"zkSync encrypted transfer";
+
+/// Order of the secp256k1 group, the key shares are the values of a polynomial over its field.
+const CURVE_ORDER: &[u8] = b"fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+
+/// Maximum amount of the share combinations tried to decrypt one blob, so the incorrect shares of
+/// a few keyholders don't stall the block formation.
+const MAX_SHARE_COMBINATIONS: usize = 64;
+
+/// Transfer along with its Ethereum signature, serialized as JSON before the encryption.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedTransfer {
+    pub tx: Transfer,
+    /// Signature of the text message of the transfer by its sender. It's required even if the
+    /// sender has disabled the 2FA.
+    pub eth_signature: PackedEthSignature,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum EncryptedTxError {
+    #[error("Sealing key is incorrect")]
+    InvalidSealingKey,
+    #[error("Threshold must be in range 1..={0}")]
+    InvalidThreshold(usize),
+    #[error("Blob doesn't contain the ephemeral key")]
+    InvalidEphemeralKey,
+    #[error("Not enough decryption shares: {0} of {1}")]
+    NotEnoughShares(usize, usize),
+    #[error("Blob can't be decrypted with the sealing key")]
+    Undecryptable,
+    #[error("Blob doesn't contain the transfer")]
+    IncorrectPayload,
+    #[error("Transfer is incorrect: {0}")]
+    IncorrectTx(String),
+    #[error("Transfer is not signed by its sender")]
+    IncorrectEthSignature,
+    #[error("Transfer doesn't pay the minimal fee")]
+    FeeTooLow,
+}
+
+/// Returns the public key the users encrypt their transfers to.
+pub fn sealing_public_key(sealing_key: &H256) -> Result<Public, EncryptedTxError> {
+    KeyPair::from_secret((*sealing_key).into())
+        .map(|key_pair| *key_pair.public())
+        .map_err(|_| EncryptedTxError::InvalidSealingKey)
+}
+
+/// Encrypts the signed transfer to the sealing public key.
+pub fn encrypt_transfer(
+    sealing_public_key: &Public,
+    transfer: &EncryptedTransfer,
+) -> Result<Vec<u8>, EncryptedTxError> {
+    let payload = serde_json::to_vec(transfer).expect("failed to encode the transfer");
+    ecies::encrypt(sealing_public_key, ENCRYPTED_TX_AUTH_DATA, &payload)
+        .map_err(|_| EncryptedTxError::InvalidSealingKey)
+}
+
+/// Decrypts the blob and checks the correctness of the transfer including its zkSync signature.
+/// The Ethereum signature is checked separately, since it requires the token of the transfer.
+pub fn decrypt_transfer(
+    sealing_key: &H256,
+    blob: &[u8],
+) -> Result<EncryptedTransfer, EncryptedTxError> {
+    let payload = ecies::decrypt(&(*sealing_key).into(), ENCRYPTED_TX_AUTH_DATA, blob)
+        .map_err(|_| EncryptedTxError::Undecryptable)?;
+    parse_transfer(&payload)
+}
+
+fn parse_transfer(payload: &[u8]) -> Result<EncryptedTransfer, EncryptedTxError> {
+    let mut transfer: EncryptedTransfer =
+        serde_json::from_slice(payload).map_err(|_| EncryptedTxError::IncorrectPayload)?;
+    transfer
+        .tx
+        .check_correctness()
+        .map_err(|err| EncryptedTxError::IncorrectTx(err.to_string()))?;
+    Ok(transfer)
+}
+
+/// Share of the sealing key held by a keyholder.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SealingKeyShare {
+    /// Non-zero index of the keyholder, required to combine its decryption shares.
+    pub index: u32,
+    pub key: H256,
+}
+
+/// Splits the sealing key between `keyholders` parties, so that any `threshold` of them can
+/// decrypt the blobs together, while fewer learn nothing about the key.
+pub fn split_sealing_key(
+    sealing_key: &H256,
+    threshold: usize,
+    keyholders: usize,
+) -> Result<Vec<SealingKeyShare>, EncryptedTxError> {
+    sealing_public_key(sealing_key)?;
+    if threshold == 0 || threshold > keyholders {
+        return Err(EncryptedTxError::InvalidThreshold(keyholders));
+    }
+
+    let order = curve_order();
+    // Polynomial of the degree `threshold - 1` with the sealing key as the free coefficient.
+    let mut coefficients = vec![BigUint::from_bytes_be(sealing_key.as_bytes())];
+    for _ in 1..threshold {
+        let coefficient = Random.generate();
+        coefficients.push(BigUint::from_bytes_be(coefficient.secret().as_bytes()));
+    }
+
+    let shares = (1..=keyholders as u32)
+        .map(|index| {
+            let key = coefficients
+                .iter()
+                .rev()
+                .fold(BigUint::zero(), |acc, coefficient| {
+                    (acc * index + coefficient) % &order
+                });
+            SealingKeyShare {
+                index,
+                key: scalar_to_h256(&key),
+            }
+        })
+        .collect();
+    Ok(shares)
+}
+
+/// Returns the ephemeral public key of the blob, which the keyholders compute their decryption
+/// shares for.
+pub fn ephemeral_key(blob: &[u8]) -> Result<Public, EncryptedTxError> {
+    // ECIES ciphertext starts with the uncompressed ephemeral key: `0x04 || x || y`.
+    match blob.get(0..65) {
+        Some(key) if key[0] == 0x04 => Ok(Public::from_slice(&key[1..])),
+        _ => Err(EncryptedTxError::InvalidEphemeralKey),
+    }
+}
+
+/// Computes the decryption share of the keyholder for the blob with the given ephemeral key.
+pub fn decryption_share(
+    key_share: &H256,
+    ephemeral_key: &Public,
+) -> Result<Public, EncryptedTxError> {
+    let mut share = *ephemeral_key;
+    public_mul_secret(&mut share, &Secret::from(*key_share))
+        .map_err(|_| EncryptedTxError::InvalidEphemeralKey)?;
+    Ok(share)
+}
+
+/// Decrypts the blob with the decryption shares of the keyholders, given as the pairs of the
+/// keyholder index and its share. Shares are combined by `threshold`, until a combination
+/// decrypts the blob, so the blob is decrypted as long as `threshold` of the shares are correct.
+pub fn decrypt_transfer_with_shares(
+    blob: &[u8],
+    shares: &[(u32, Public)],
+    threshold: usize,
+) -> Result<EncryptedTransfer, EncryptedTxError> {
+    ephemeral_key(blob)?;
+    if threshold == 0 || shares.len() < threshold {
+        return Err(EncryptedTxError::NotEnoughShares(shares.len(), threshold));
+    }
+
+    let order = curve_order();
+    // The blob is decrypted with the combined point `s * R` substituted for the ephemeral key
+    // and the unit secret, since the ECIES key only depends on the point `secret * R`.
+    let unit_secret = Secret::from(H256::from_low_u64_be(1));
+    let mut blob = blob.to_vec();
+    for combination in combinations(shares.len(), threshold).take(MAX_SHARE_COMBINATIONS) {
+        let shares: Vec<_> = combination.iter().map(|&i| shares[i]).collect();
+        let point = match combine_shares(&shares, &order) {
+            Some(point) => point,
+            None => continue,
+        };
+        blob[1..65].copy_from_slice(point.as_bytes());
+        if let Ok(payload) = ecies::decrypt(&unit_secret, ENCRYPTED_TX_AUTH_DATA, &blob) {
+            return parse_transfer(&payload);
+        }
+    }
+    Err(EncryptedTxError::Undecryptable)
+}
+
+/// Interpolates the point `s * R` from the decryption shares with the Lagrange coefficients at
+/// zero. Returns `None` if the shares are malformed, e.g. have the repeated indices.
+fn combine_shares(shares: &[(u32, Public)], order: &BigUint) -> Option<Public> {
+    let mut combined: Option<Public> = None;
+    for (i, (index, share)) in shares.iter().enumerate() {
+        let index = BigUint::from(*index) % order;
+        let mut numerator = BigUint::one();
+        let mut denominator = BigUint::one();
+        for (j, (other, _)) in shares.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let other = BigUint::from(*other) % order;
+            numerator = numerator * &other % order;
+            denominator = denominator * ((order + &other - &index) % order) % order;
+        }
+        if index.is_zero() || denominator.is_zero() {
+            return None;
+        }
+        // Denominator is inverted with the Fermat's little theorem, since the order is prime.
+        let inverse = denominator.modpow(&(order - BigUint::from(2u32)), order);
+        let coefficient = numerator * inverse % order;
+
+        let mut point = *share;
+        public_mul_secret(&mut point, &Secret::from(scalar_to_h256(&coefficient))).ok()?;
+        combined = match combined {
+            Some(mut combined) => {
+                public_add(&mut combined, &point).ok()?;
+                Some(combined)
+            }
+            None => Some(point),
+        };
+    }
+    combined
+}
+
+/// Iterates over the `k`-element combinations of the indices `0..n` in the lexicographic order.
+fn combinations(n: usize, k: usize) -> impl Iterator<Item = Vec<usize>> {
+    let mut next = if k <= n {
+        Some((0..k).collect::<Vec<_>>())
+    } else {
+        None
+    };
+    std::iter::from_fn(move || {
+        let current = next.take()?;
+        let mut following = current.clone();
+        if let Some(i) = (0..k).rev().find(|&i| following[i] < n - k + i) {
+            following[i] += 1;
+            for j in i + 1..k {
+                following[j] = following[j - 1] + 1;
+            }
+            next = Some(following);
+        }
+        Some(current)
+    })
+}
+
+fn curve_order() -> BigUint {
+    BigUint::parse_bytes(CURVE_ORDER, 16).expect("invalid curve order")
+}
+
+fn scalar_to_h256(scalar: &BigUint) -> H256 {
+    let bytes = scalar.to_bytes_be();
+    let mut result = H256::zero();
+    result.as_bytes_mut()[32 - bytes.len()..].copy_from_slice(&bytes);
+    result
+}
+
+impl EncryptedTransfer {
+    /// Checks that the transfer is signed by its sender, `token` must be the token of the transfer.
+    pub fn verify_eth_signature(&self, token: &Token) -> Result<(), EncryptedTxError> {
+        let message = self
+            .tx
+            .get_ethereum_sign_message(&token.symbol, token.decimals);
+        match self
+            .eth_signature
+            .signature_recover_signer(message.as_bytes())
+        {
+            Ok(signer) if signer == self.tx.from => Ok(()),
+            _ => Err(EncryptedTxError::IncorrectEthSignature),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_crypto::{
+        franklin_crypto::eddsa::PrivateKey,
+        rand::{Rng, SeedableRng, XorShiftRng},
+    };
+
+    use super::*;
+    use crate::{tx::TimeRange, AccountId, Address, Nonce, TokenId, TokenKind};
+
+    fn signed_transfer(eth_key: &H256, token: &Token) -> EncryptedTransfer {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let tx = Transfer::new_signed(
+            AccountId(1),
+            PackedEthSignature::address_from_private_key(eth_key).unwrap(),
+            Address::repeat_byte(2),
+            token.id,
+            1_000u32.into(),
+            10u32.into(),
+            Nonce(0),
+            TimeRange::default(),
+            &PrivateKey(rng.gen()),
+        )
+        .unwrap();
+        let message = tx.get_ethereum_sign_message(&token.symbol, token.decimals);
+        EncryptedTransfer {
+            tx,
+            eth_signature: PackedEthSignature::sign(eth_key, message.as_bytes()).unwrap(),
+        }
+    }
+
+    /// Checks that the transfer is decrypted by the sealing key only.
+    #[test]
+    fn encrypted_transfer_roundtrip() {
+        let sealing_key = H256::repeat_byte(7);
+        let token = Token::new(TokenId(0), Address::zero(), "ETH", 18, TokenKind::ERC20);
+        let transfer = signed_transfer(&H256::repeat_byte(1), &token);
+
+        let blob = encrypt_transfer(&sealing_public_key(&sealing_key).unwrap(), &transfer).unwrap();
+        let decrypted = decrypt_transfer(&sealing_key, &blob).unwrap();
+        assert_eq!(decrypted.tx.hash(), transfer.tx.hash());
+        assert_eq!(decrypted.verify_eth_signature(&token), Ok(()));
+
+        assert_eq!(
+            decrypt_transfer(&H256::repeat_byte(8), &blob).unwrap_err(),
+            EncryptedTxError::Undecryptable
+        );
+        assert_eq!(
+            decrypt_transfer(&sealing_key, b"garbage").unwrap_err(),
+            EncryptedTxError::Undecryptable
+        );
+
+        let blob = ecies::encrypt(
+            &sealing_public_key(&sealing_key).unwrap(),
+            ENCRYPTED_TX_AUTH_DATA,
+            b"{}",
+        )
+        .unwrap();
+        assert_eq!(
+            decrypt_transfer(&sealing_key, &blob).unwrap_err(),
+            EncryptedTxError::IncorrectPayload
+        );
+    }
+
+    /// Checks that the blob is decrypted by any `threshold` of the keyholders, but not by fewer.
+    #[test]
+    fn threshold_decryption() {
+        let sealing_key = H256::repeat_byte(7);
+        let token = Token::new(TokenId(0), Address::zero(), "ETH", 18, TokenKind::ERC20);
+        let transfer = signed_transfer(&H256::repeat_byte(1), &token);
+        let blob = encrypt_transfer(&sealing_public_key(&sealing_key).unwrap(), &transfer).unwrap();
+
+        let key_shares = split_sealing_key(&sealing_key, 3, 5).unwrap();
+        assert_eq!(key_shares.len(), 5);
+        let blob_key = ephemeral_key(&blob).unwrap();
+        let shares: Vec<_> = key_shares
+            .iter()
+            .map(|share| {
+                let point = decryption_share(&share.key, &blob_key).unwrap();
+                (share.index, point)
+            })
+            .collect();
+
+        for combination in combinations(shares.len(), 3) {
+            let shares: Vec<_> = combination.iter().map(|&i| shares[i]).collect();
+            let decrypted = decrypt_transfer_with_shares(&blob, &shares, 3).unwrap();
+            assert_eq!(decrypted.tx.hash(), transfer.tx.hash());
+        }
+        assert_eq!(
+            decrypt_transfer_with_shares(&blob, &shares[..2], 3).unwrap_err(),
+            EncryptedTxError::NotEnoughShares(2, 3)
+        );
+        // Shares of two keyholders can't be used as if the threshold was 2.
+        assert_eq!(
+            decrypt_transfer_with_shares(&blob, &shares[..2], 2).unwrap_err(),
+            EncryptedTxError::Undecryptable
+        );
+
+        // Incorrect shares are skipped as long as `threshold` of the shares are correct.
+        let mut shares = shares;
+        shares[0].1 = shares[1].1;
+        shares[2].1 = shares[1].1;
+        let decrypted = decrypt_transfer_with_shares(&blob, &shares, 3).unwrap();
+        assert_eq!(decrypted.tx.hash(), transfer.tx.hash());
+        assert_eq!(
+            decrypt_transfer_with_shares(&blob, &shares[..4], 3).unwrap_err(),
+            EncryptedTxError::Undecryptable
+        );
+
+        assert_eq!(
+            split_sealing_key(&sealing_key, 4, 3).unwrap_err(),
+            EncryptedTxError::InvalidThreshold(3)
+        );
+        assert_eq!(
+            ephemeral_key(b"garbage").unwrap_err(),
+            EncryptedTxError::InvalidEphemeralKey
+        );
+    }
+
+    #[test]
+    fn share_combinations() {
+        let combinations: Vec<_> = combinations(4, 2).collect();
+        assert_eq!(
+            combinations,
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3]
+            ]
+        );
+        assert_eq!(combinations(3, 3).count(), 1);
+        assert_eq!(combinations(2, 3).count(), 0);
+    }
+
+    /// Checks that the transfer must be signed by its sender.
+    #[test]
+    fn encrypted_transfer_eth_signature() {
+        let token = Token::new(TokenId(0), Address::zero(), "ETH", 18, TokenKind::ERC20);
+        let mut transfer = signed_transfer(&H256::repeat_byte(1), &token);
+        let other = signed_transfer(&H256::repeat_byte(2), &token);
+
+        transfer.eth_signature = other.eth_signature;
+        assert_eq!(
+            transfer.verify_eth_signature(&token),
+            Err(EncryptedTxError::IncorrectEthSignature)
+        );
+    }
+}
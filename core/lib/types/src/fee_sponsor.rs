@@ -0,0 +1,32 @@
+//! Sponsors of the zero-fee transfers.
+//!
+//! The operator may whitelist an address (e.g. a treasury of a project) to send the transfers of
+//! a token without paying the fee. The L1 gas spent on such transfers is charged from the daily
+//! budget of the sponsor when the transfer is admitted to the mempool, and the transfers exceeding
+//! the budget are rejected. Sponsorship only applies to the single transfers, the transactions
+//! of a batch pay the fee as usual.
+
+use num::Zero;
+use serde::{Deserialize, Serialize};
+
+use crate::{tx::ZkSyncTx, Address, TokenId};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeSponsor {
+    pub address: Address,
+    pub token: TokenId,
+    /// Amount of the L1 gas the sponsored transfers may take per UTC day.
+    pub daily_gas_budget: u64,
+}
+
+/// Returns the sender and the token of the transfer if it may be sponsored,
+/// i.e. it's a transfer paying no fee.
+pub fn sponsorship_key(tx: &ZkSyncTx) -> Option<(Address, TokenId)> {
+    match tx {
+        ZkSyncTx::Transfer(transfer) if transfer.fee.is_zero() => {
+            Some((transfer.from, transfer.token))
+        }
+        _ => None,
+    }
+}
@@ -40,6 +40,8 @@ impl CommitCost {
     pub const FORCED_EXIT_COST: u64 = Self::WITHDRAW_COST; // TODO: Verify value (ZKS-109).
     pub const MINT_TOKEN_COST: u64 = 920;
     pub const SWAP_COST: u64 = 710;
+    // TODO: estimate once the operations are supported by the contract.
+    pub const CLOSE_COST: u64 = Self::TRANSFER_COST;
 
     pub fn base_cost() -> U256 {
         U256::from(Self::BASE_COST)
@@ -75,7 +77,7 @@ impl CommitCost {
             ZkSyncOp::ForcedExit(_) => Self::FORCED_EXIT_COST,
             ZkSyncOp::Swap(_) => Self::SWAP_COST,
             ZkSyncOp::MintNFTOp(_) => Self::MINT_TOKEN_COST,
-            ZkSyncOp::Close(_) => unreachable!("Close operations are disabled"),
+            ZkSyncOp::Close(_) => Self::CLOSE_COST,
             ZkSyncOp::WithdrawNFT(_) => Self::WITHDRAW_NFT_COST,
         };
 
@@ -97,6 +99,7 @@ impl VerifyCost {
     pub const CHANGE_PUBKEY_COST: u64 = 0;
     pub const TRANSFER_COST: u64 = 0;
     pub const TRANSFER_TO_NEW_COST: u64 = 0;
+    pub const CLOSE_COST: u64 = 0;
     pub const SWAP_COST: u64 = 0;
     pub const FULL_EXIT_COST: u64 = 30_000;
     pub const WITHDRAW_COST: u64 = 30_000;
@@ -122,7 +125,7 @@ impl VerifyCost {
             ZkSyncOp::ForcedExit(_) => Self::FORCED_EXIT_COST,
             ZkSyncOp::MintNFTOp(_) => Self::MINT_NFT_COST,
             ZkSyncOp::Swap(_) => Self::SWAP_COST,
-            ZkSyncOp::Close(_) => unreachable!("Close operations are disabled"),
+            ZkSyncOp::Close(_) => Self::CLOSE_COST,
             ZkSyncOp::WithdrawNFT(_) => Self::WITHDRAW_NFT_COST,
         };
 
@@ -130,6 +133,27 @@ impl VerifyCost {
     }
 }
 
+/// Cost of the Ethereum transaction calldata, as defined in EIP-2028.
+#[derive(Debug)]
+pub struct CalldataCost;
+
+impl CalldataCost {
+    pub const ZERO_BYTE_COST: u64 = 4;
+    pub const NON_ZERO_BYTE_COST: u64 = 16;
+
+    pub fn bytes_cost(data: &[u8]) -> u64 {
+        data.iter()
+            .map(|&byte| {
+                if byte == 0 {
+                    Self::ZERO_BYTE_COST
+                } else {
+                    Self::NON_ZERO_BYTE_COST
+                }
+            })
+            .sum()
+    }
+}
+
 /// `GasCounter` is an entity capable of counting the estimated gas cost of an
 /// upcoming transaction. It watches for the total gas cost of either commit
 /// or withdraw operation to not exceed the reasonable gas limit amount.
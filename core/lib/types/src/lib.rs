@@ -37,11 +37,16 @@
 //! [`Account`]: ./account/struct.Account.html
 
 pub mod account;
+pub mod account_freeze;
+pub mod account_name;
 pub mod aggregated_operations;
 pub mod block;
+pub mod deposit_call;
+pub mod encrypted_tx;
 pub mod ethereum;
 pub mod event;
 pub mod fee;
+pub mod fee_sponsor;
 pub mod forced_exit_requests;
 pub mod gas_counter;
 pub mod helpers;
@@ -49,9 +54,12 @@ pub mod mempool;
 pub mod network;
 pub mod operations;
 pub mod priority_ops;
+pub mod private_tx;
 pub mod prover;
+pub mod read_token;
 pub mod register_factory;
 pub mod tokens;
+pub mod travel_rule;
 pub mod tx;
 mod utils;
 
@@ -59,8 +67,11 @@ mod utils;
 mod tests;
 
 pub use self::account::{Account, AccountUpdate, PubKeyHash};
+pub use self::account_freeze::AccountFreeze;
 pub use self::block::{ExecutedOperations, ExecutedPriorityOp, ExecutedTx};
+pub use self::deposit_call::DepositCallEvent;
 pub use self::fee::{BatchFee, Fee, OutputFeeType, TotalFee};
+pub use self::fee_sponsor::FeeSponsor;
 pub use self::operations::{
     ChangePubKeyOp, DepositOp, ForcedExitOp, FullExitOp, MintNFTOp, SwapOp, TransferOp,
     TransferToNewOp, WithdrawNFTOp, WithdrawOp, ZkSyncOp,
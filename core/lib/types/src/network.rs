@@ -5,7 +5,7 @@
 use std::{fmt, str::FromStr};
 
 // External uses
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 // Workspace uses
 
@@ -13,8 +13,9 @@ use serde::{Deserialize, Serialize};
 
 /// Network to be used for a zkSync client.
 ///
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
-#[serde(rename_all = "camelCase")]
+/// Well-known networks are represented by their names, any other EVM chain is represented
+/// by its chain ID, e.g. `1337` for a private chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Network {
     /// Ethereum Mainnet.
     Mainnet,
@@ -24,12 +25,16 @@ pub enum Network {
     Goerli,
     /// Ethereum Ropsten testnet.
     Ropsten,
+    /// Ethereum Sepolia testnet.
+    Sepolia,
     /// Self-hosted Ethereum & zkSync networks.
     Localhost,
     /// Unknown network type.
     Unknown,
     /// Test network for testkit purposes
     Test,
+    /// Arbitrary EVM network identified by its chain ID.
+    Custom(u64),
 }
 
 impl FromStr for Network {
@@ -41,9 +46,14 @@ impl FromStr for Network {
             "rinkeby" => Self::Rinkeby,
             "ropsten" => Self::Ropsten,
             "goerli" => Self::Goerli,
+            "sepolia" => Self::Sepolia,
             "localhost" => Self::Localhost,
+            "unknown" => Self::Unknown,
             "test" => Self::Test,
-            another => return Err(another.to_owned()),
+            another => match another.parse::<u64>() {
+                Ok(chain_id) => Self::from_chain_id(chain_id),
+                Err(_) => return Err(another.to_owned()),
+            },
         })
     }
 }
@@ -56,8 +66,10 @@ impl fmt::Display for Network {
             Self::Ropsten => write!(f, "ropsten"),
             Self::Localhost => write!(f, "localhost"),
             Self::Goerli => write!(f, "goerli"),
+            Self::Sepolia => write!(f, "sepolia"),
             Self::Unknown => write!(f, "unknown"),
             Self::Test => write!(f, "test"),
+            Self::Custom(chain_id) => write!(f, "{}", chain_id),
         }
     }
 }
@@ -71,8 +83,72 @@ impl Network {
             Network::Rinkeby => 4,
             Network::Goerli => 5,
             Network::Localhost => 9,
+            Network::Sepolia => 11155111,
+            Network::Custom(chain_id) => chain_id,
             Network::Unknown => panic!("Unknown chain ID"),
             Network::Test => panic!("Test chain ID"),
         }
     }
+
+    /// Returns the network with the given chain ID on the Ethereum side.
+    /// Chain IDs of the networks unknown to zkSync are represented as `Network::Custom`.
+    pub fn from_chain_id(chain_id: u64) -> Self {
+        match chain_id {
+            1 => Network::Mainnet,
+            3 => Network::Ropsten,
+            4 => Network::Rinkeby,
+            5 => Network::Goerli,
+            9 => Network::Localhost,
+            11155111 => Network::Sepolia,
+            chain_id => Network::Custom(chain_id),
+        }
+    }
+}
+
+// The network is serialized as a plain string, so that custom chains can be set in the
+// configuration the same way as the well-known ones.
+impl Serialize for Network {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Network {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let network = String::deserialize(deserializer)?;
+        network
+            .parse()
+            .map_err(|network| de::Error::custom(format!("unknown network: {}", network)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_from_str() {
+        assert_eq!("sepolia".parse::<Network>(), Ok(Network::Sepolia));
+        assert_eq!("5".parse::<Network>(), Ok(Network::Goerli));
+        assert_eq!("1337".parse::<Network>(), Ok(Network::Custom(1337)));
+        assert!("kovan".parse::<Network>().is_err());
+
+        assert_eq!(Network::Custom(1337).chain_id(), 1337);
+        assert_eq!(
+            Network::Custom(1337).to_string().parse::<Network>(),
+            Ok(Network::Custom(1337))
+        );
+    }
+
+    #[test]
+    fn network_serde() {
+        let network: Network = serde_json::from_str("\"mainnet\"").unwrap();
+        assert_eq!(network, Network::Mainnet);
+        let network: Network = serde_json::from_str("\"1337\"").unwrap();
+        assert_eq!(network, Network::Custom(1337));
+        assert_eq!(
+            serde_json::to_string(&Network::Custom(1337)).unwrap(),
+            "\"1337\""
+        );
+    }
 }
@@ -31,6 +31,11 @@ pub use self::{
 };
 use crate::operations::error::{PublicDataDecodeError, UnexpectedOperationType};
 
+/// Version of the public data encoding produced by `ZkSyncOp::public_data`, equal to the version
+/// of the smart contract that introduced it. Public data of the previous versions is decoded
+/// by `ZkSyncOp::from_legacy_public_data`.
+pub const PUBDATA_VERSION: u32 = 6;
+
 /// zkSync network operation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -72,6 +77,24 @@ impl ZkSyncOp {
             ZkSyncOp::WithdrawNFT(_) => WithdrawNFTOp::CHUNKS,
         }
     }
+    /// Returns the name of the operation type, same as its serialized `type` tag.
+    pub fn op_name(&self) -> &'static str {
+        match self {
+            ZkSyncOp::Noop(_) => "Noop",
+            ZkSyncOp::Deposit(_) => "Deposit",
+            ZkSyncOp::TransferToNew(_) => "TransferToNew",
+            ZkSyncOp::Withdraw(_) => "Withdraw",
+            ZkSyncOp::Close(_) => "Close",
+            ZkSyncOp::Transfer(_) => "Transfer",
+            ZkSyncOp::FullExit(_) => "FullExit",
+            ZkSyncOp::ChangePubKeyOffchain(_) => "ChangePubKeyOffchain",
+            ZkSyncOp::ForcedExit(_) => "ForcedExit",
+            ZkSyncOp::Swap(_) => "Swap",
+            ZkSyncOp::MintNFTOp(_) => "MintNFTOp",
+            ZkSyncOp::WithdrawNFT(_) => "WithdrawNFT",
+        }
+    }
+
     /// Get information about amounts in operation
     pub fn get_amount_info(&self) -> Option<Vec<(TokenId, BigUint)>> {
         match self {
@@ -0,0 +1,15 @@
+//! Private transactions channel for the large traders.
+//!
+//! Clients of the channel are registered by the operator and submit their batches with the bearer
+//! tokens. Batches pass the same checks as the public ones and are stored in the mempool, so the
+//! proposer picks them up at the block formation, but the public API doesn't show them until they
+//! are executed. Only the hashes of the tokens are stored, so the leaked database doesn't give
+//! access to the channel.
+
+/// Maximum number of characters in the name of the client.
+pub const MAX_PRIVATE_TX_CLIENT_NAME_LEN: usize = 64;
+
+/// Returns the hash of the bearer token of the client under which it's stored.
+pub fn private_tx_token_hash(token: &str) -> [u8; 32] {
+    tiny_keccak::keccak256(token.as_bytes())
+}
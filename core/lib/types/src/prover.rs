@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
 use thiserror::Error;
 use zksync_basic_types::BlockNumber;
 
@@ -72,3 +74,48 @@ impl ToString for ProverJobType {
 #[derive(Debug, Error, PartialEq)]
 #[error("Incorrect ProverJobStatus number: {0}")]
 pub struct IncorrectProverJobStatus(pub i32);
+
+/// Proof system the block proofs are created with. Proofs are tagged with it in storage, so the
+/// experimental proof systems may be developed side-by-side with the one used in production, and
+/// their proofs are sent to the matching verifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofSystemVersion {
+    /// PLONK with the recursive aggregation of the block proofs, verified by the main contract.
+    PlonkV1,
+}
+
+impl ProofSystemVersion {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PlonkV1 => "plonk_v1",
+        }
+    }
+}
+
+impl Default for ProofSystemVersion {
+    fn default() -> Self {
+        Self::PlonkV1
+    }
+}
+
+impl fmt::Display for ProofSystemVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ProofSystemVersion {
+    type Err = IncorrectProofSystemVersion;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plonk_v1" => Ok(Self::PlonkV1),
+            _ => Err(IncorrectProofSystemVersion(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("Incorrect proof system version: {0}")]
+pub struct IncorrectProofSystemVersion(pub String);
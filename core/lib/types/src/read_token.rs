@@ -0,0 +1,105 @@
+//! Read tokens delegating the access to the private data of the account.
+//!
+//! The owner of the address signs the grant message naming the third party, e.g. an accountant
+//! or an auditor, and the expiration time. The server issues the bearer token for the grant, which
+//! gives access to the full history of the account along with the data otherwise available only to
+//! the owner, such as the travel rule metadata. The token can't be used to submit transactions, and
+//! is revoked by the owner at any time.
+
+use chrono::{DateTime, Duration, Utc};
+use thiserror::Error;
+
+/// Maximum number of characters in the name of the grantee.
+pub const MAX_READ_TOKEN_GRANTEE_LEN: usize = 64;
+/// Maximum lifetime of the read token in days.
+pub const MAX_READ_TOKEN_LIFETIME_DAYS: i64 = 366;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ReadTokenGrantError {
+    #[error(
+        "Grantee must be from 1 to {} characters long",
+        MAX_READ_TOKEN_GRANTEE_LEN
+    )]
+    WrongGranteeLength,
+    #[error("Grantee can't contain the control characters")]
+    InvalidGranteeCharacter,
+    #[error("Token must expire in the future")]
+    Expired,
+    #[error("Token can't live longer than {} days", MAX_READ_TOKEN_LIFETIME_DAYS)]
+    TooLongLifetime,
+}
+
+/// Checks the grant before the token is issued.
+pub fn validate_read_token_grant(
+    grantee: &str,
+    expires_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<(), ReadTokenGrantError> {
+    if grantee.is_empty() || grantee.chars().count() > MAX_READ_TOKEN_GRANTEE_LEN {
+        return Err(ReadTokenGrantError::WrongGranteeLength);
+    }
+    if grantee.chars().any(char::is_control) {
+        return Err(ReadTokenGrantError::InvalidGranteeCharacter);
+    }
+    if expires_at <= now {
+        return Err(ReadTokenGrantError::Expired);
+    }
+    if expires_at - now > Duration::days(MAX_READ_TOKEN_LIFETIME_DAYS) {
+        return Err(ReadTokenGrantError::TooLongLifetime);
+    }
+    Ok(())
+}
+
+/// Returns the message the owner of the address signs to grant the read access.
+/// The expiration time is included in the message in seconds.
+pub fn read_token_grant_message(grantee: &str, expires_at: DateTime<Utc>) -> String {
+    format!(
+        "Grant zkSync read access\nGrantee: {}\nExpires at: {}",
+        grantee,
+        expires_at.timestamp()
+    )
+}
+
+/// Returns the message the owner of the address signs to revoke the read token.
+pub fn read_token_revoke_message(token_id: i64) -> String {
+    format!("Revoke zkSync read access\nToken: {}", token_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_token_grant_validation() {
+        let now = Utc::now();
+        let expires_at = now + Duration::days(30);
+        assert_eq!(
+            validate_read_token_grant("Auditor", expires_at, now),
+            Ok(())
+        );
+        assert_eq!(
+            validate_read_token_grant("", expires_at, now),
+            Err(ReadTokenGrantError::WrongGranteeLength)
+        );
+        assert_eq!(
+            validate_read_token_grant(&"a".repeat(MAX_READ_TOKEN_GRANTEE_LEN + 1), expires_at, now),
+            Err(ReadTokenGrantError::WrongGranteeLength)
+        );
+        assert_eq!(
+            validate_read_token_grant("Auditor\nExpires at: 0", expires_at, now),
+            Err(ReadTokenGrantError::InvalidGranteeCharacter)
+        );
+        assert_eq!(
+            validate_read_token_grant("Auditor", now, now),
+            Err(ReadTokenGrantError::Expired)
+        );
+        assert_eq!(
+            validate_read_token_grant(
+                "Auditor",
+                now + Duration::days(MAX_READ_TOKEN_LIFETIME_DAYS + 1),
+                now
+            ),
+            Err(ReadTokenGrantError::TooLongLifetime)
+        );
+    }
+}
@@ -5,7 +5,7 @@ use thiserror::Error;
 
 use zksync_basic_types::Log;
 
-use crate::Address;
+use crate::{AccountId, Address};
 
 #[derive(Debug, Error)]
 #[allow(clippy::large_enum_variant)]
@@ -18,6 +18,7 @@ pub enum RegisterNFTFactoryEventParseError {
 
 #[derive(Clone, Debug)]
 pub struct RegisterNFTFactoryEvent {
+    pub creator_id: AccountId,
     pub factory_address: Address,
     pub creator_address: Address,
     pub eth_block: u64,
@@ -39,12 +40,44 @@ impl TryFrom<Log> for RegisterNFTFactoryEvent {
             &event.data.0,
         )
         .map_err(RegisterNFTFactoryEventParseError::ParseError)?;
+        let creator_id = AccountId(event.topics[1].to_low_u64_be() as u32);
         let creator_address = Address::from_slice(&event.topics[2].as_fixed_bytes()[12..]);
         let factory_address = decoded_event.remove(0).into_address().unwrap();
         Ok(Self {
+            creator_id,
             factory_address,
             creator_address,
             eth_block,
         })
     }
 }
+
+/// Returns the message the creator signs to register the factory, it's verified
+/// by the `registerNFTFactoryCreator` method of the Governance contract.
+pub fn register_nft_factory_message(
+    creator_id: AccountId,
+    creator_address: Address,
+    factory_address: Address,
+) -> Vec<u8> {
+    format!(
+        "\nCreator's account ID in zkSync: {}\nCreator: {}\nFactory: {}",
+        hex::encode(creator_id.0.to_be_bytes()),
+        hex::encode(creator_address),
+        hex::encode(factory_address)
+    )
+    .into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_nft_factory_message_length() {
+        let message =
+            register_nft_factory_message(AccountId(258), Address::repeat_byte(1), Address::zero());
+        // The length is hardcoded into the prefix of the signed message in the contract.
+        assert_eq!(message.len(), 141);
+        assert!(message.starts_with(b"\nCreator's account ID in zkSync: 00000102\n"));
+    }
+}
@@ -3,7 +3,8 @@ use zksync_crypto::ff::Field;
 use zksync_crypto::Fr;
 
 use super::utils::*;
-use crate::block::Block;
+use crate::block::{commitment, Block};
+use crate::gas_counter::{CalldataCost, CommitCost};
 
 /// Checks that we cannot create a block with invalid block sizes provided.
 #[test]
@@ -129,6 +130,57 @@ fn test_get_eth_witness_data() {
     assert!(block.get_eth_witness_data().0.is_empty());
 }
 
+/// Checks that the operations are grouped by type, and the spare chunks are accounted as noops.
+#[test]
+fn test_op_type_stats() {
+    let block = Block::new(
+        BlockNumber(0),
+        Fr::one(),
+        AccountId(0),
+        vec![
+            create_withdraw_tx(),
+            create_change_pubkey_tx(),
+            create_withdraw_tx(),
+        ],
+        (0, 0),
+        100,
+        1_000_000.into(),
+        1_500_000.into(),
+        H256::default(),
+        0,
+    );
+
+    let stats = block.op_type_stats();
+    let op_types: Vec<_> = stats.iter().map(|stats| stats.op_type.as_str()).collect();
+    assert_eq!(op_types, vec!["Withdraw", "ChangePubKeyOffchain", "Noop"]);
+
+    let withdraw = block.block_transactions[0].get_executed_op().unwrap();
+    assert_eq!(stats[0].count, 2);
+    assert_eq!(stats[0].chunks, 2 * withdraw.chunks() as u64);
+    assert_eq!(
+        stats[0].pubdata_bytes,
+        2 * withdraw.public_data().len() as u64
+    );
+    assert_eq!(
+        stats[0].commit_gas,
+        2 * CommitCost::op_cost(withdraw).as_u64()
+    );
+    assert_eq!(
+        stats[0].estimated_gas(),
+        stats[0].commit_gas + stats[0].verify_gas + stats[0].pubdata_gas
+    );
+
+    let chunks: u64 = stats.iter().map(|stats| stats.chunks).sum();
+    assert_eq!(chunks, block.block_chunks_size as u64);
+    let pubdata_bytes: u64 = stats.iter().map(|stats| stats.pubdata_bytes).sum();
+    assert_eq!(pubdata_bytes, block.get_eth_public_data().len() as u64);
+    let pubdata_gas: u64 = stats.iter().map(|stats| stats.pubdata_gas).sum();
+    assert_eq!(
+        pubdata_gas,
+        CalldataCost::bytes_cost(&block.get_eth_public_data())
+    );
+}
+
 #[test]
 fn test_get_withdrawals_data() {
     let operations = vec![
@@ -166,3 +218,42 @@ fn test_get_withdrawals_data() {
     // No more corresponding operations left.
     assert!(block.get_withdrawals_data().is_empty());
 }
+
+/// Checks that the commitment restored from the public data matches the one of the block.
+#[test]
+fn test_commitment_from_public_data() {
+    let operations = vec![
+        create_change_pubkey_tx(),
+        create_full_exit_op(),
+        create_withdraw_tx(),
+    ];
+    let previous_root_hash = H256::repeat_byte(0x01);
+    let block = Block::new_from_available_block_sizes(
+        BlockNumber(5),
+        Fr::one(),
+        AccountId(3),
+        operations,
+        (0, 1),
+        &[50, 100],
+        1_000_000.into(),
+        1_500_000.into(),
+        previous_root_hash,
+        1_600_000_000,
+    );
+
+    let public_data = block.get_eth_public_data();
+    assert_eq!(
+        commitment::onchain_op_commitment(&public_data).unwrap(),
+        block.get_onchain_op_commitment()
+    );
+    let restored = commitment::block_commitment_from_public_data(
+        block.block_number,
+        block.fee_account,
+        previous_root_hash,
+        block.get_eth_encoded_root(),
+        block.timestamp,
+        &public_data,
+    )
+    .unwrap();
+    assert_eq!(restored, block.block_commitment);
+}
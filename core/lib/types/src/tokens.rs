@@ -97,6 +97,17 @@ impl TokenLike {
         }
     }
 
+    /// Splits the symbol qualified with the token ID, e.g. "USDC-5", into the symbol and the ID.
+    pub fn qualified_symbol(&self) -> Option<(&str, TokenId)> {
+        match self {
+            Self::Symbol(symbol) => {
+                let (symbol, id) = symbol.rsplit_once('-')?;
+                Some((symbol, TokenId(u32::from_str(id).ok()?)))
+            }
+            _ => None,
+        }
+    }
+
     /// Makes request case-insensitive (lowercase).
     /// Used to compare queries against keys in the cache.
     pub fn to_lowercase(&self) -> Self {
@@ -158,6 +169,22 @@ impl Token {
             is_nft: true,
         }
     }
+
+    /// Symbol qualified with the token ID, e.g. "USDC-5". It resolves to the token even if
+    /// the symbol is shared with other tokens.
+    pub fn qualified_symbol(&self) -> String {
+        format!("{}-{}", self.symbol, self.id)
+    }
+}
+
+/// Alias of the token symbol. Symbol lookups are resolved by the aliases first, so an alias
+/// equal to the symbol shared by several tokens selects the canonical one of them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenSymbolAlias {
+    /// Alias in lowercase, as the symbol lookups are case-insensitive.
+    pub alias: String,
+    pub token_id: TokenId,
+    pub created_at: DateTime<Utc>,
 }
 
 /// ERC-20 standard token.
@@ -406,4 +433,18 @@ mod tests {
             TokenLike::Symbol("eth".into())
         );
     }
+
+    #[test]
+    fn token_like_qualified_symbol() {
+        let token = Token::new(TokenId(5), Address::zero(), "USDC-E", 6, TokenKind::ERC20);
+        assert_eq!(token.qualified_symbol(), "USDC-E-5");
+        assert_eq!(
+            TokenLike::Symbol(token.qualified_symbol()).qualified_symbol(),
+            Some(("USDC-E", TokenId(5)))
+        );
+
+        assert_eq!(TokenLike::Symbol("USDC-E".into()).qualified_symbol(), None);
+        assert_eq!(TokenLike::Symbol("USDC".into()).qualified_symbol(), None);
+        assert_eq!(TokenLike::Id(TokenId(5)).qualified_symbol(), None);
+    }
 }
@@ -0,0 +1,19 @@
+//! Travel rule metadata of the large transfers.
+//!
+//! The sender of the transfer above the threshold set by the operator attaches the information
+//! about the originator and the beneficiary required by the travel rule. The metadata is
+//! encrypted by the sender for the recipient, so the server stores it as an opaque blob and
+//! hands it out to the parties of the transfer only.
+
+use crate::tx::TxHash;
+
+/// Maximum size of the encrypted metadata in bytes.
+pub const MAX_TRAVEL_RULE_METADATA_LEN: usize = 4096;
+
+/// Returns the message a party of the transfer signs to access its travel rule metadata.
+pub fn travel_rule_metadata_message(tx_hash: TxHash) -> String {
+    format!(
+        "Access zkSync travel rule metadata\nTransaction: {}",
+        hex::encode(tx_hash.as_ref())
+    )
+}
@@ -14,8 +14,9 @@ use zksync_utils::{format_units, BigUintSerdeAsRadix10Str};
 
 use super::{PackedEthSignature, TimeRange, TxSignature, VerifiedSignatureCache};
 use crate::tx::error::{
-    FEE_AMOUNT_IS_NOT_PACKABLE, INVALID_AUTH_DATA, WRONG_ACCOUNT_ID, WRONG_FEE_ERROR,
-    WRONG_SIGNATURE, WRONG_TIME_RANGE, WRONG_TOKEN_FOR_PAYING_FEE,
+    CREATE2_ADDRESS_MISMATCH, CREATE2_NONZERO_NONCE, CREATE2_NO_2FA, FEE_AMOUNT_IS_NOT_PACKABLE,
+    INVALID_AUTH_DATA, WRONG_ACCOUNT_ID, WRONG_FEE_ERROR, WRONG_SIGNATURE, WRONG_TIME_RANGE,
+    WRONG_TOKEN_FOR_PAYING_FEE,
 };
 use crate::{
     account::PubKeyHash,
@@ -151,6 +152,12 @@ pub struct ChangePubKey {
     pub eth_signature: Option<PackedEthSignature>,
     /// Data needed to check if Ethereum address authorized ChangePubKey operation
     pub eth_auth_data: Option<ChangePubKeyEthAuthData>,
+    /// Changes the 2FA policy of the account along with the key: `true` disables the Ethereum
+    /// signatures for the transactions signed with the new key, `false` enables 2FA back.
+    /// The flag isn't a part of the L2 message, so it has to be authorized by the Ethereum
+    /// signature of the `get_ethereum_sign_message` message.
+    #[serde(default, rename = "no2FA", skip_serializing_if = "Option::is_none")]
+    pub no_2fa: Option<bool>,
     /// Time range when the transaction is valid
     /// This fields must be Option<...> because of backward compatibility with first version of ZkSync
     #[serde(flatten)]
@@ -179,7 +186,6 @@ impl ChangePubKey {
         signature: Option<TxSignature>,
         eth_signature: Option<PackedEthSignature>,
     ) -> Self {
-        // TODO: support CREATE2 (ZKS-452)
         let eth_auth_data = Some(
             eth_signature
                 .map(|eth_signature| {
@@ -201,6 +207,7 @@ impl ChangePubKey {
             signature: signature.clone().unwrap_or_default(),
             eth_signature: None,
             eth_auth_data,
+            no_2fa: None,
             cached_signer: VerifiedSignatureCache::NotCached,
             time_range: Some(time_range),
         };
@@ -240,6 +247,39 @@ impl ChangePubKey {
         Ok(tx)
     }
 
+    /// Creates a signed transaction for the account deployed with `CREATE2`
+    /// and checks the transaction correctness.
+    ///
+    /// The account address is derived from the CREATE2 data and the new public key hash,
+    /// the nonce is always zero since the account is only authorized through CREATE2
+    /// before its first transaction.
+    pub fn new_create2_signed(
+        account_id: AccountId,
+        fee_token: TokenId,
+        fee: BigUint,
+        time_range: TimeRange,
+        create2_data: ChangePubKeyCREATE2Data,
+        private_key: &PrivateKey,
+    ) -> Result<Self, TransactionError> {
+        let new_pk_hash = PubKeyHash::from_privkey(private_key);
+        let account = create2_data.get_address(&new_pk_hash);
+        let mut tx = Self::new(
+            account_id,
+            account,
+            new_pk_hash,
+            fee_token,
+            fee,
+            Nonce(0),
+            time_range,
+            None,
+            None,
+        );
+        tx.eth_auth_data = Some(ChangePubKeyEthAuthData::CREATE2(create2_data));
+        tx.signature = TxSignature::sign_musig(private_key, &tx.get_bytes());
+        tx.check_correctness()?;
+        Ok(tx)
+    }
+
     /// Restores the `PubKeyHash` from the transaction signature.
     pub fn verify_signature(&self) -> Option<(PubKeyHash, TxVersion)> {
         if let VerifiedSignatureCache::Cached(cached_signer) = &self.cached_signer {
@@ -410,8 +450,9 @@ impl ChangePubKey {
     ///
     /// Set signing key: {pubKeyHash}
     /// [Fee: {fee} {token}]
+    /// [2FA: disabled|enabled]
     ///
-    /// Note that the second line is optional.
+    /// Note that the last two lines are optional.
     pub fn get_ethereum_sign_message_part(&self, token_symbol: &str, decimals: u8) -> String {
         let mut message = format!(
             "Set signing key: {}",
@@ -427,9 +468,25 @@ impl ChangePubKey {
                 .as_str(),
             );
         }
+        match self.no_2fa {
+            Some(true) => message.push_str("\n2FA: disabled"),
+            Some(false) => message.push_str("\n2FA: enabled"),
+            None => {}
+        }
         message
     }
 
+    /// Gets message that should be signed by Ethereum keys of the account to change its 2FA policy.
+    /// The key itself is authorized by `eth_auth_data`, so the message is only required if the
+    /// `no_2fa` flag is set.
+    pub fn get_ethereum_sign_message(&self, token_symbol: &str, decimals: u8) -> Option<String> {
+        self.no_2fa?;
+        let mut message = self.get_ethereum_sign_message_part(token_symbol, decimals);
+        message.push('\n');
+        message.push_str(format!("Nonce: {}", self.nonce).as_str());
+        Some(message)
+    }
+
     pub fn get_change_pubkey_fee_type(&self) -> ChangePubKeyFeeTypeArg {
         if let Some(auth_data) = &self.eth_auth_data {
             ChangePubKeyFeeTypeArg::ContractsV4Version(auth_data.get_fee_type())
@@ -453,11 +510,24 @@ impl ChangePubKey {
     /// Verifies the transaction correctness:
     ///
     /// - Ethereum signature (if set) must correspond to the account address.
+    /// - CREATE2 data (if set) must derive the account address and the nonce must be zero.
     /// - zkSync signature must correspond to the `new_pk_hash` field of the transaction.
     /// - `account_id` field must be within supported range.
     /// - `fee_token` field must be within supported range.
     /// - `fee` field must represent a packable value.
     pub fn check_correctness(&mut self) -> Result<(), TransactionError> {
+        if let Some(ChangePubKeyEthAuthData::CREATE2(create2_data)) = &self.eth_auth_data {
+            if create2_data.get_address(&self.new_pk_hash) != self.account {
+                return Err(TransactionError::Create2AddressMismatch);
+            }
+            if self.nonce != Nonce(0) {
+                return Err(TransactionError::Create2NonZeroNonce);
+            }
+            // CREATE2 accounts have no Ethereum key, so there is no 2FA to change.
+            if self.no_2fa.is_some() {
+                return Err(TransactionError::Create2No2FA);
+            }
+        }
         if !self.is_eth_auth_data_valid() {
             return Err(TransactionError::InvalidAuthData);
         }
@@ -498,6 +568,9 @@ impl ChangePubKey {
 #[derive(Error, Debug, Copy, Clone, Serialize, Deserialize)]
 pub enum TransactionError {
     InvalidAuthData,
+    Create2AddressMismatch,
+    Create2NonZeroNonce,
+    Create2No2FA,
     WrongFee,
     FeeNotPackable,
     WrongAccountId,
@@ -516,6 +589,9 @@ impl Display for TransactionError {
             TransactionError::WrongSignature => WRONG_SIGNATURE,
             TransactionError::WrongFeeToken => WRONG_TOKEN_FOR_PAYING_FEE,
             TransactionError::InvalidAuthData => INVALID_AUTH_DATA,
+            TransactionError::Create2AddressMismatch => CREATE2_ADDRESS_MISMATCH,
+            TransactionError::Create2NonZeroNonce => CREATE2_NONZERO_NONCE,
+            TransactionError::Create2No2FA => CREATE2_NO_2FA,
         };
         write!(f, "{}", error)
     }
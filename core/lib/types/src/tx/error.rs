@@ -57,6 +57,9 @@ pub enum TxAddError {
 
     #[error("Too many Ethereum signatures provided")]
     EthSignaturesLimitExceeded,
+
+    #[error("Daily gas budget of the fee sponsor is exhausted")]
+    SponsorGasBudgetExceeded,
 }
 
 #[derive(Error, Debug, Copy, Clone, Serialize, Deserialize)]
@@ -92,3 +95,7 @@ pub const WRONG_TOKEN_FOR_PAYING_FEE: &str = "Specified token is not supported f
 pub const WRONG_SIGNATURE: &str = "L2 signature is incorrect";
 pub const WRONG_TO_ADDRESS: &str = "Transfer for specified address is not supported";
 pub const INVALID_AUTH_DATA: &str = "Specified auth data is incorrect";
+pub const CREATE2_ADDRESS_MISMATCH: &str =
+    "Account address doesn't match the address derived from the CREATE2 data";
+pub const CREATE2_NONZERO_NONCE: &str = "CREATE2 auth data can only be used with zero nonce";
+pub const CREATE2_NO_2FA: &str = "2FA policy can't be changed for CREATE2 accounts";
@@ -17,6 +17,7 @@ use crate::tx::error::{
 };
 use crate::tx::version::TxVersion;
 use crate::tx::TimeRange;
+use crate::tx::{Eip712Struct, Eip712Value};
 use crate::{account::PubKeyHash, Engine};
 use crate::{
     helpers::{is_fee_amount_packable, pack_fee_amount},
@@ -199,6 +200,20 @@ impl ForcedExit {
         message
     }
 
+    /// Gets EIP-712 typed structure which may be signed by Ethereum keys of the account for
+    /// 2-Factor authentication instead of the text message.
+    pub fn get_eip712_struct(&self, token_symbol: &str) -> Eip712Struct {
+        Eip712Struct::new("ForcedExit")
+            .field(
+                "initiatorAccountId",
+                Eip712Value::Uint32(*self.initiator_account_id),
+            )
+            .field("target", Eip712Value::Address(self.target))
+            .field("token", Eip712Value::String(token_symbol.to_owned()))
+            .field("fee", Eip712Value::Uint256(self.fee.clone()))
+            .field("nonce", Eip712Value::Uint32(*self.nonce))
+    }
+
     /// Helper method to remove cache and test transaction behavior without the signature cache.
     #[doc(hidden)]
     pub fn wipe_signer_cache(&mut self) {
@@ -38,6 +38,7 @@ pub use self::{
 // Re-export primitives associated with transactions.
 pub use self::primitives::{
     eip1271_signature::EIP1271Signature,
+    eip712::{eip712_signing_message, Eip712Domain, Eip712Struct, Eip712Value},
     eth_batch_sign_data::EthBatchSignData,
     eth_batch_signature::EthBatchSignatures,
     eth_signature::{TxEthSignature, TxEthSignatureVariant},
@@ -0,0 +1,238 @@
+//! Support of the EIP-712 typed structured data used as an alternative to the text 2FA messages.
+//!
+//! Text messages are signed according to EIP-191 with the version byte `0x45` (`personal_sign`),
+//! while the typed data uses the version byte `0x01`. Hardware wallets are able to display the
+//! fields of the typed data, so users can see what exactly they are signing.
+//!
+//! The scheme is chosen per transaction: the signature may be prefixed with the version byte
+//! of the signed message (see `TxEthSignature`), signatures without it are text ones.
+
+use num::BigUint;
+use parity_crypto::Keccak256;
+use zksync_basic_types::{Address, H256};
+
+/// EIP-191 version byte of the structured data.
+pub const EIP712_VERSION_BYTE: u8 = 0x01;
+/// EIP-191 version byte of the text messages (`personal_sign`).
+pub const PERSONAL_SIGN_VERSION_BYTE: u8 = 0x45;
+
+/// Name of the zkSync signing domain.
+pub const EIP712_DOMAIN_NAME: &str = "zkSync";
+/// Version of the zkSync signing domain.
+pub const EIP712_DOMAIN_VERSION: &str = "1";
+
+/// Value of a single field of the typed structure.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Eip712Value {
+    Address(Address),
+    Uint32(u32),
+    Uint256(BigUint),
+    String(String),
+}
+
+impl Eip712Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Address(_) => "address",
+            Self::Uint32(_) => "uint32",
+            Self::Uint256(_) => "uint256",
+            Self::String(_) => "string",
+        }
+    }
+
+    /// Encodes the value as a 32-byte word according to the `encodeData` rules.
+    fn encode(&self) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        match self {
+            Self::Address(address) => word[12..].copy_from_slice(address.as_bytes()),
+            Self::Uint32(value) => word[28..].copy_from_slice(&value.to_be_bytes()),
+            Self::Uint256(value) => {
+                // Amounts in zkSync never exceed 128 bits, so only the lowest bytes are meaningful.
+                let bytes = value.to_bytes_be();
+                let bytes = &bytes[bytes.len().saturating_sub(32)..];
+                word[32 - bytes.len()..].copy_from_slice(bytes);
+            }
+            Self::String(value) => word = value.as_bytes().keccak256(),
+        }
+        word
+    }
+}
+
+/// Typed structure to be hashed according to EIP-712.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Eip712Struct {
+    name: &'static str,
+    fields: Vec<(&'static str, Eip712Value)>,
+}
+
+impl Eip712Struct {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn field(mut self, name: &'static str, value: Eip712Value) -> Self {
+        self.fields.push((name, value));
+        self
+    }
+
+    /// Returns the type definition, e.g. `Transfer(address to,uint32 nonce)`.
+    pub fn encode_type(&self) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|(name, value)| format!("{} {}", value.type_name(), name))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{}({})", self.name, fields)
+    }
+
+    /// Calculates `hashStruct` of the structure.
+    pub fn hash(&self) -> H256 {
+        let mut bytes = Vec::with_capacity(32 * (self.fields.len() + 1));
+        bytes.extend_from_slice(&self.encode_type().as_bytes().keccak256());
+        for (_, value) in &self.fields {
+            bytes.extend_from_slice(&value.encode());
+        }
+        bytes.keccak256().into()
+    }
+}
+
+/// zkSync signing domain, which binds the signatures to the L1 chain and the zkSync contract
+/// deployed there, so they can't be replayed on the other networks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Eip712Domain {
+    pub chain_id: u64,
+    pub verifying_contract: Address,
+}
+
+impl Eip712Domain {
+    pub fn new(chain_id: u64, verifying_contract: Address) -> Self {
+        Self {
+            chain_id,
+            verifying_contract,
+        }
+    }
+
+    /// Returns the `EIP712Domain` structure.
+    pub fn to_struct(&self) -> Eip712Struct {
+        Eip712Struct::new("EIP712Domain")
+            .field("name", Eip712Value::String(EIP712_DOMAIN_NAME.to_owned()))
+            .field(
+                "version",
+                Eip712Value::String(EIP712_DOMAIN_VERSION.to_owned()),
+            )
+            .field("chainId", Eip712Value::Uint256(self.chain_id.into()))
+            .field(
+                "verifyingContract",
+                Eip712Value::Address(self.verifying_contract),
+            )
+    }
+
+    /// Calculates `domainSeparator`.
+    pub fn separator(&self) -> H256 {
+        self.to_struct().hash()
+    }
+}
+
+/// Returns the message to be signed for the typed structure:
+/// `0x19 || 0x01 || domainSeparator || hashStruct(message)`.
+///
+/// Unlike text messages, the keccak256 hash of this message is signed without any prefixes.
+pub fn eip712_signing_message(domain: &Eip712Domain, message: &Eip712Struct) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(66);
+    bytes.push(0x19);
+    bytes.push(EIP712_VERSION_BYTE);
+    bytes.extend_from_slice(domain.separator().as_bytes());
+    bytes.extend_from_slice(message.hash().as_bytes());
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    /// Checks the hashing against the `Mail` example from the EIP-712 specification.
+    #[test]
+    fn eip712_specification_example() {
+        let person = |name: &str, wallet: &str| {
+            (
+                name.to_owned(),
+                Address::from_str(wallet).expect("Correct address"),
+            )
+        };
+        let from = person("Cow", "CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826");
+        let to = person("Bob", "bBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB");
+
+        let person_struct = |(name, wallet): (String, Address)| {
+            Eip712Struct::new("Person")
+                .field("name", Eip712Value::String(name))
+                .field("wallet", Eip712Value::Address(wallet))
+        };
+        assert_eq!(
+            person_struct(from.clone()).encode_type(),
+            "Person(string name,address wallet)"
+        );
+        assert_eq!(
+            person_struct(from).hash(),
+            H256::from_str("fc71e5fa27ff56c350aa531bc129ebdf613b772b6604664f5d8dbe21b85eb0c8")
+                .unwrap()
+        );
+        assert_eq!(
+            person_struct(to).hash(),
+            H256::from_str("cd54f074a4af31b4411ff6a60c9719dbd559c221c8ac3492d9d872b041d703d1")
+                .unwrap()
+        );
+    }
+
+    /// Checks the domain hashing against the example from the EIP-712 specification.
+    #[test]
+    fn eip712_specification_domain() {
+        let verifying_contract =
+            Address::from_str("CcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC").expect("Correct address");
+        let domain = Eip712Struct::new("EIP712Domain")
+            .field("name", Eip712Value::String("Ether Mail".to_owned()))
+            .field("version", Eip712Value::String("1".to_owned()))
+            .field("chainId", Eip712Value::Uint256(1u32.into()))
+            .field(
+                "verifyingContract",
+                Eip712Value::Address(verifying_contract),
+            );
+        assert_eq!(
+            domain.hash(),
+            H256::from_str("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+                .unwrap()
+        );
+
+        let zksync_domain = Eip712Domain::new(1, verifying_contract).to_struct();
+        assert_eq!(
+            zksync_domain.encode_type(),
+            "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+        );
+    }
+
+    /// Checks that signing message has the correct layout.
+    #[test]
+    fn signing_message_layout() {
+        let contract = Address::repeat_byte(0x11);
+        let domain = Eip712Domain::new(1, contract);
+        let message = Eip712Struct::new("Test").field("value", Eip712Value::Uint32(1));
+        let signing_message = eip712_signing_message(&domain, &message);
+
+        assert_eq!(signing_message.len(), 66);
+        assert_eq!(&signing_message[..2], &[0x19, EIP712_VERSION_BYTE]);
+        assert_eq!(&signing_message[2..34], domain.separator().as_bytes());
+        assert_eq!(&signing_message[34..], message.hash().as_bytes());
+        assert_ne!(
+            signing_message,
+            eip712_signing_message(&Eip712Domain::new(4, contract), &message)
+        );
+        assert_ne!(
+            signing_message,
+            eip712_signing_message(&Eip712Domain::new(1, Address::repeat_byte(0x22)), &message)
+        );
+    }
+}
@@ -1,17 +1,88 @@
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
+use zksync_utils::ZeroPrefixHexSerde;
 
-use crate::tx::{EIP1271Signature, PackedEthSignature};
+use crate::tx::{
+    eip712::{EIP712_VERSION_BYTE, PERSONAL_SIGN_VERSION_BYTE},
+    EIP1271Signature, PackedEthSignature,
+};
 
 /// Representation of the signature secured by L1.
 /// May be either a signature generated via Ethereum private key
 /// corresponding to the account address,
 /// or on-chain signature via EIP-1271.
+///
+/// Both `EthereumSignature` and `EIP712Signature` are serialized as the `EthereumSignature` type.
+/// The signature may be prefixed with the EIP-191 version byte of the signed message: `0x45` for
+/// the text message and `0x01` for the EIP-712 typed data. Signatures without the prefix are the
+/// text ones, as they were before the typed data support.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(tag = "type", content = "signature")]
+#[serde(try_from = "RawTxEthSignature", into = "RawTxEthSignature")]
 pub enum TxEthSignature {
     EthereumSignature(PackedEthSignature),
     EIP1271Signature(EIP1271Signature),
+    /// Signature of the EIP-712 typed data generated via Ethereum private key.
+    /// Signed message is the typed structure instead of the text message.
+    EIP712Signature(PackedEthSignature),
+}
+
+impl TxEthSignature {
+    /// Returns `true` if the signed message is expected to be an EIP-712 typed data.
+    pub fn is_typed_data(&self) -> bool {
+        matches!(self, Self::EIP712Signature(_))
+    }
+}
+
+/// Serialized form of `TxEthSignature`.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", content = "signature")]
+enum RawTxEthSignature {
+    EthereumSignature(#[serde(with = "ZeroPrefixHexSerde")] Vec<u8>),
+    EIP1271Signature(EIP1271Signature),
+}
+
+impl TryFrom<RawTxEthSignature> for TxEthSignature {
+    type Error = String;
+
+    fn try_from(raw: RawTxEthSignature) -> Result<Self, Self::Error> {
+        let bytes = match raw {
+            RawTxEthSignature::EthereumSignature(bytes) => bytes,
+            RawTxEthSignature::EIP1271Signature(signature) => {
+                return Ok(Self::EIP1271Signature(signature))
+            }
+        };
+        let parse = |bytes: &[u8]| {
+            PackedEthSignature::deserialize_packed(bytes).map_err(|err| err.to_string())
+        };
+        match bytes.len() {
+            66 => match bytes[0] {
+                PERSONAL_SIGN_VERSION_BYTE => Ok(Self::EthereumSignature(parse(&bytes[1..])?)),
+                EIP712_VERSION_BYTE => Ok(Self::EIP712Signature(parse(&bytes[1..])?)),
+                version => Err(format!(
+                    "Unsupported version byte of the signature: {:#04x}",
+                    version
+                )),
+            },
+            _ => Ok(Self::EthereumSignature(parse(&bytes)?)),
+        }
+    }
+}
+
+impl From<TxEthSignature> for RawTxEthSignature {
+    fn from(signature: TxEthSignature) -> Self {
+        match signature {
+            TxEthSignature::EthereumSignature(signature) => {
+                Self::EthereumSignature(signature.serialize_packed().to_vec())
+            }
+            TxEthSignature::EIP712Signature(signature) => {
+                let mut bytes = vec![EIP712_VERSION_BYTE];
+                bytes.extend_from_slice(&signature.serialize_packed());
+                Self::EthereumSignature(bytes)
+            }
+            TxEthSignature::EIP1271Signature(signature) => Self::EIP1271Signature(signature),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -64,6 +135,12 @@ impl Display for TxEthSignature {
             Self::EthereumSignature(sign) => {
                 write!(f, "0x{}", hex::encode(sign.serialize_packed()))
             }
+            Self::EIP712Signature(sign) => write!(
+                f,
+                "0x{:02x}{}",
+                EIP712_VERSION_BYTE,
+                hex::encode(sign.serialize_packed())
+            ),
             Self::EIP1271Signature(sign) => write!(f, "0x{}", hex::encode(sign.0.clone())),
         }
     }
@@ -86,6 +86,28 @@ impl PackedEthSignature {
         Ok(public_to_address(&public_key))
     }
 
+    /// Signs the EIP-712 typed data message, which is expected to be already encoded
+    /// (see `eip712_signing_message`). Unlike `sign`, no prefix is added to the message.
+    pub fn sign_typed_data(
+        private_key: &H256,
+        typed_data_message: &[u8],
+    ) -> Result<PackedEthSignature, PackedETHSignatureError> {
+        let secret_key = (*private_key).into();
+        let signed_bytes: H256 = typed_data_message.keccak256().into();
+        let signature = sign(&secret_key, &signed_bytes)?;
+        Ok(PackedEthSignature(signature))
+    }
+
+    /// Checks signature of the EIP-712 typed data message and returns ethereum address of the signer.
+    pub fn typed_data_recover_signer(
+        &self,
+        typed_data_message: &[u8],
+    ) -> Result<Address, PackedETHSignatureError> {
+        let signed_bytes: H256 = typed_data_message.keccak256().into();
+        let public_key = recover(&self.0, &signed_bytes)?;
+        Ok(public_to_address(&public_key))
+    }
+
     /// Get Ethereum address from private key.
     pub fn address_from_private_key(
         private_key: &H256,
@@ -7,7 +7,8 @@ use zksync_basic_types::Address;
 use zksync_crypto::{
     franklin_crypto::eddsa::PrivateKey,
     params::{
-        max_account_id, max_processable_token, max_token_id, CURRENT_TX_VERSION, PRICE_BIT_WIDTH,
+        max_account_id, max_processable_token, max_token_id, CURRENT_TX_VERSION, MIN_NFT_TOKEN_ID,
+        PRICE_BIT_WIDTH,
     },
     primitives::rescue_hash_orders,
 };
@@ -90,6 +91,17 @@ impl Order {
         out
     }
 
+    /// Whether the order sells an NFT. Such an order can't be partially filled,
+    /// so it has to sell exactly one token.
+    pub fn sells_nft(&self) -> bool {
+        self.token_sell.0 >= MIN_NFT_TOKEN_ID
+    }
+
+    /// Whether the order buys an NFT.
+    pub fn buys_nft(&self) -> bool {
+        self.token_buy.0 >= MIN_NFT_TOKEN_ID
+    }
+
     pub fn verify_signature(&self) -> Option<PubKeyHash> {
         self.signature
             .verify_musig(&self.get_bytes())
@@ -172,6 +184,9 @@ impl Order {
         if self.token_sell > max_token_id() {
             return Err(OrderError::WrongSellToken);
         }
+        if self.sells_nft() && self.amount != BigUint::from(1u32) {
+            return Err(OrderError::WrongNFTAmount);
+        }
         if !self.time_range.check_correctness() {
             return Err(OrderError::WrongTimeRange);
         }
@@ -194,6 +209,8 @@ pub enum OrderError {
     WrongSellToken,
     #[error("Specified time interval is not valid for the current time")]
     WrongTimeRange,
+    #[error("Order selling an NFT has to sell exactly one token")]
+    WrongNFTAmount,
 }
 
 impl Swap {
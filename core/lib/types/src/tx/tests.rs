@@ -16,7 +16,7 @@ use zksync_crypto::{
 use super::*;
 use crate::{
     helpers::{pack_fee_amount, pack_token_amount},
-    AccountId, Engine, Nonce, PubKeyHash, TokenId, H256,
+    AccountId, Engine, Nonce, PubKeyHash, Token, TokenId, TokenKind, H256,
 };
 
 fn gen_pk_and_msg() -> (PrivateKey<Engine>, Vec<Vec<u8>>) {
@@ -537,6 +537,86 @@ fn test_ethereum_signature_sign() {
     }
 }
 
+/// Checks that EIP-712 typed data signature of a transaction can be verified
+/// and is not interchangeable with the text message signature.
+#[test]
+fn test_eip712_signature_sign_verify() {
+    let private_key = H256::random();
+    let address = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+    let token = Token::new(TokenId(0), Address::zero(), "ETH", 18, TokenKind::ERC20);
+
+    let transfer = ZkSyncTx::Transfer(Box::new(Transfer::new(
+        AccountId(1),
+        address,
+        Address::random(),
+        token.id,
+        1000u32.into(),
+        10u32.into(),
+        Nonce(0),
+        Default::default(),
+        None,
+    )));
+    let contract = Address::random();
+    let message = transfer
+        .get_eip712_signing_message(&token, &Eip712Domain::new(9, contract))
+        .expect("Transfer supports typed data");
+    assert_ne!(
+        transfer.get_eip712_signing_message(&token, &Eip712Domain::new(1, contract)),
+        Some(message.clone())
+    );
+
+    let signature = PackedEthSignature::sign_typed_data(&private_key, &message).unwrap();
+    assert_eq!(
+        signature.typed_data_recover_signer(&message).unwrap(),
+        address
+    );
+    assert_ne!(
+        signature.signature_recover_signer(&message).unwrap(),
+        address
+    );
+
+    let eth_signature = TxEthSignature::EIP712Signature(signature);
+    assert!(eth_signature.is_typed_data());
+    let json = serde_json::to_string(&eth_signature).unwrap();
+    assert_eq!(
+        serde_json::from_str::<TxEthSignature>(&json).unwrap(),
+        eth_signature
+    );
+}
+
+/// Checks that the signature scheme is negotiated by the version byte of the signature.
+#[test]
+fn test_eth_signature_version_byte() {
+    let private_key = H256::random();
+    let signature = PackedEthSignature::sign(&private_key, b"message").unwrap();
+    let packed = hex::encode(signature.serialize_packed());
+    let parse = |hex_signature: String| {
+        serde_json::from_value::<TxEthSignature>(serde_json::json!({
+            "type": "EthereumSignature",
+            "signature": hex_signature,
+        }))
+    };
+
+    // Signatures without the version byte are the text ones.
+    let text_signature = TxEthSignature::EthereumSignature(signature.clone());
+    assert_eq!(parse(format!("0x{}", packed)).unwrap(), text_signature);
+    assert_eq!(parse(format!("0x45{}", packed)).unwrap(), text_signature);
+    assert_eq!(
+        serde_json::to_value(&text_signature).unwrap()["signature"],
+        format!("0x{}", packed)
+    );
+
+    let typed_signature = TxEthSignature::EIP712Signature(signature);
+    assert_eq!(parse(format!("0x01{}", packed)).unwrap(), typed_signature);
+    let json = serde_json::to_value(&typed_signature).unwrap();
+    assert_eq!(json["type"], "EthereumSignature");
+    assert_eq!(json["signature"], format!("0x01{}", packed));
+    assert_eq!(typed_signature.to_string(), format!("0x01{}", packed));
+
+    assert!(parse(format!("0x02{}", packed)).is_err());
+    assert!(parse(format!("0x0145{}", packed)).is_err());
+}
+
 /// Checks that we are able to decode old entries from the database.
 #[test]
 fn eth_sign_data_compatibility() {
@@ -585,3 +665,142 @@ fn test_check_signature() {
 
     assert_eq!(hex::encode(signature), "4e3298ac8cc13868dbbc94ad6fb41085ffe05b3c2eee22f88b05e69b7a5126aea723d7a3e7282ef5a32d9479c9c8dde52b3e3c462dd445dcd8158ebb6edb6000");
 }
+
+#[test]
+fn test_change_pub_key_create2_correctness() {
+    let (pk, _) = gen_pk_and_msg();
+    let create2_data = ChangePubKeyCREATE2Data {
+        creator_address: Address::repeat_byte(0x11),
+        salt_arg: H256::repeat_byte(0x22),
+        code_hash: H256::repeat_byte(0x33),
+    };
+
+    let mut tx = ChangePubKey::new_create2_signed(
+        AccountId(1),
+        TokenId(0),
+        BigUint::from(0u32),
+        Default::default(),
+        create2_data.clone(),
+        &pk,
+    )
+    .expect("failed to sign CREATE2 ChangePubKey");
+    assert_eq!(tx.nonce, Nonce(0));
+    assert_eq!(
+        tx.account,
+        create2_data.get_address(&PubKeyHash::from_privkey(&pk))
+    );
+    assert!(tx.is_eth_auth_data_valid());
+
+    // The account address must be derived from the auth data.
+    let mut wrong_account = tx.clone();
+    wrong_account.account = Address::repeat_byte(0x44);
+    assert!(matches!(
+        wrong_account.check_correctness(),
+        Err(change_pubkey::TransactionError::Create2AddressMismatch)
+    ));
+
+    // CREATE2 account can only be authorized before its first transaction.
+    tx.nonce = Nonce(1);
+    assert!(matches!(
+        tx.check_correctness(),
+        Err(change_pubkey::TransactionError::Create2NonZeroNonce)
+    ));
+}
+
+#[test]
+fn test_change_pub_key_no_2fa_flag() {
+    let (pk, _) = gen_pk_and_msg();
+    let pub_key_hash = PubKeyHash::from_privkey(&pk);
+    let mut tx = ChangePubKey::new_signed(
+        AccountId(1),
+        Address::repeat_byte(0x11),
+        pub_key_hash,
+        TokenId(0),
+        BigUint::from(0u32),
+        Nonce(3),
+        Default::default(),
+        None,
+        &pk,
+    )
+    .expect("failed to sign ChangePubKey");
+
+    // The message is only required to change the 2FA policy.
+    assert_eq!(tx.get_ethereum_sign_message("ETH", 18), None);
+    let json = serde_json::to_value(&tx).unwrap();
+    assert!(json.get("no2FA").is_none());
+
+    tx.no_2fa = Some(true);
+    assert_eq!(
+        tx.get_ethereum_sign_message("ETH", 18).unwrap(),
+        format!(
+            "Set signing key: {}\n2FA: disabled\nNonce: 3",
+            hex::encode(&pub_key_hash.data)
+        )
+    );
+    let json = serde_json::to_value(&tx).unwrap();
+    assert_eq!(json["no2FA"], true);
+    let deserialized: ChangePubKey = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized.no_2fa, Some(true));
+
+    tx.no_2fa = Some(false);
+    assert!(tx
+        .get_ethereum_sign_message_part("ETH", 18)
+        .ends_with("\n2FA: enabled"));
+
+    // CREATE2 accounts have no 2FA.
+    let mut create2_tx = ChangePubKey::new_create2_signed(
+        AccountId(1),
+        TokenId(0),
+        BigUint::from(0u32),
+        Default::default(),
+        ChangePubKeyCREATE2Data {
+            creator_address: Address::repeat_byte(0x11),
+            salt_arg: H256::repeat_byte(0x22),
+            code_hash: H256::repeat_byte(0x33),
+        },
+        &pk,
+    )
+    .expect("failed to sign CREATE2 ChangePubKey");
+    create2_tx.no_2fa = Some(true);
+    assert!(matches!(
+        create2_tx.check_correctness(),
+        Err(change_pubkey::TransactionError::Create2No2FA)
+    ));
+}
+
+#[test]
+fn test_nft_order_correctness() {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let key = gen_pk_and_msg().0;
+    let nft = TokenId(zksync_crypto::params::MIN_NFT_TOKEN_ID);
+    let token = gen_token_id(&mut rng);
+    let mut nft_order = |token_sell, token_buy, amount: u64| {
+        Order::new_signed(
+            AccountId(1),
+            Address::from(rng.gen::<[u8; 20]>()),
+            Nonce(0),
+            token_sell,
+            token_buy,
+            (BigUint::from(1u8), BigUint::from(100u8)),
+            BigUint::from(amount),
+            Default::default(),
+            &key,
+        )
+    };
+
+    let order = nft_order(nft, token, 1).expect("NFT sale order is correct");
+    assert!(order.sells_nft());
+    assert!(!order.buys_nft());
+    // The order selling an NFT can't be a limit order.
+    assert!(matches!(
+        nft_order(nft, token, 0),
+        Err(swap::OrderError::WrongNFTAmount)
+    ));
+    assert!(matches!(
+        nft_order(nft, token, 2),
+        Err(swap::OrderError::WrongNFTAmount)
+    ));
+    // The order buying an NFT may be a limit order.
+    let order = nft_order(token, nft, 0).expect("NFT purchase order is correct");
+    assert!(order.buys_nft());
+}
@@ -19,7 +19,7 @@ use crate::{
     helpers::{
         is_fee_amount_packable, is_token_amount_packable, pack_fee_amount, pack_token_amount,
     },
-    tx::TimeRange,
+    tx::{Eip712Struct, Eip712Value, TimeRange},
     AccountId, Nonce, TokenId,
 };
 
@@ -211,6 +211,19 @@ impl Transfer {
         message
     }
 
+    /// Gets EIP-712 typed structure which may be signed by Ethereum keys of the account for
+    /// 2-Factor authentication instead of the text message.
+    pub fn get_eip712_struct(&self, token_symbol: &str) -> Eip712Struct {
+        Eip712Struct::new("Transfer")
+            .field("accountId", Eip712Value::Uint32(*self.account_id))
+            .field("from", Eip712Value::Address(self.from))
+            .field("to", Eip712Value::Address(self.to))
+            .field("token", Eip712Value::String(token_symbol.to_owned()))
+            .field("amount", Eip712Value::Uint256(self.amount.clone()))
+            .field("fee", Eip712Value::Uint256(self.fee.clone()))
+            .field("nonce", Eip712Value::Uint32(*self.nonce))
+    }
+
     /// Returns an old-format message that should be signed by Ethereum account key.
     /// Needed for backwards compatibility.
     pub fn get_old_ethereum_sign_message(&self, token_symbol: &str, decimals: u8) -> String {
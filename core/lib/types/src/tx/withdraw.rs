@@ -25,6 +25,7 @@ use crate::tx::error::{
     WRONG_FEE_ERROR, WRONG_SIGNATURE, WRONG_TIME_RANGE, WRONG_TOKEN, WRONG_TOKEN_FOR_PAYING_FEE,
 };
 use crate::tx::version::TxVersion;
+use crate::tx::{Eip712Struct, Eip712Value};
 
 /// `Withdraw` transaction performs a withdrawal of funds from zkSync account to L1 account.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +57,11 @@ pub struct Withdraw {
     /// rejected.
     #[serde(default)]
     pub fast: bool,
+    /// Optional setting asking the server to deliver the withdrawn WETH as native ETH.
+    /// The funds have to be withdrawn to the WETH unwrapper contract, which sends the ETH
+    /// to the `from` address once the block is executed.
+    #[serde(default)]
+    pub unwrap_weth: bool,
     /// Time range when the transaction is valid
     /// This fields must be Option<...> because of backward compatibility with first version of ZkSync
     #[serde(flatten)]
@@ -93,6 +99,7 @@ impl Withdraw {
             signature: signature.clone().unwrap_or_default(),
             cached_signer: VerifiedSignatureCache::NotCached,
             fast: false,
+            unwrap_weth: false,
             time_range: Some(time_range),
         };
         if signature.is_some() {
@@ -212,6 +219,19 @@ impl Withdraw {
         message
     }
 
+    /// Gets EIP-712 typed structure which may be signed by Ethereum keys of the account for
+    /// 2-Factor authentication instead of the text message.
+    pub fn get_eip712_struct(&self, token_symbol: &str) -> Eip712Struct {
+        Eip712Struct::new("Withdraw")
+            .field("accountId", Eip712Value::Uint32(*self.account_id))
+            .field("from", Eip712Value::Address(self.from))
+            .field("to", Eip712Value::Address(self.to))
+            .field("token", Eip712Value::String(token_symbol.to_owned()))
+            .field("amount", Eip712Value::Uint256(self.amount.clone()))
+            .field("fee", Eip712Value::Uint256(self.fee.clone()))
+            .field("nonce", Eip712Value::Uint32(*self.nonce))
+    }
+
     /// Returns an old-format message that should be signed by Ethereum account key.
     /// Needed for backwards compatibility.
     pub fn get_old_ethereum_sign_message(&self, token_symbol: &str, decimals: u8) -> String {
@@ -10,9 +10,10 @@ use zksync_crypto::params::ETH_TOKEN_ID;
 use crate::{
     operations::{ChangePubKeyOp, MintNFTOp},
     tx::{
+        eip712_signing_message,
         error::{CloseOperationsDisabled, TransactionError},
-        ChangePubKey, Close, ForcedExit, MintNFT, Swap, TimeRange, Transfer, TxEthSignature,
-        TxHash, TxSignature, Withdraw, WithdrawNFT,
+        ChangePubKey, Close, Eip712Domain, ForcedExit, MintNFT, Swap, TimeRange, Transfer,
+        TxEthSignature, TxHash, TxSignature, Withdraw, WithdrawNFT,
     },
     utils::deserialize_eth_message,
     CloseOp, ForcedExitOp, Nonce, SwapOp, Token, TokenId, TokenLike, TransferOp, TxFeeTypes,
@@ -272,10 +273,13 @@ impl ZkSyncTx {
 
     /// Returns a message that user has to sign to send the transaction.
     /// If the transaction doesn't need a message signature, returns `None`.
-    /// `ChangePubKey` message is handled separately since its Ethereum signature
-    /// is passed to the contract.
+    /// `ChangePubKey` key is authorized separately since its Ethereum signature
+    /// is passed to the contract, the message only covers its 2FA flag.
     pub fn get_ethereum_sign_message(&self, token: Token) -> Option<String> {
         match self {
+            ZkSyncTx::ChangePubKey(tx) => {
+                tx.get_ethereum_sign_message(&token.symbol, token.decimals)
+            }
             ZkSyncTx::Transfer(tx) => {
                 Some(tx.get_ethereum_sign_message(&token.symbol, token.decimals))
             }
@@ -296,6 +300,23 @@ impl ZkSyncTx {
         }
     }
 
+    /// Returns an EIP-712 typed data message that user may sign instead of the text message
+    /// to send the transaction, signed in the specified domain.
+    /// If the transaction doesn't support typed data signatures, returns `None`.
+    pub fn get_eip712_signing_message(
+        &self,
+        token: &Token,
+        domain: &Eip712Domain,
+    ) -> Option<Vec<u8>> {
+        let message = match self {
+            ZkSyncTx::Transfer(tx) => tx.get_eip712_struct(&token.symbol),
+            ZkSyncTx::Withdraw(tx) => tx.get_eip712_struct(&token.symbol),
+            ZkSyncTx::ForcedExit(tx) => tx.get_eip712_struct(&token.symbol),
+            _ => return None,
+        };
+        Some(eip712_signing_message(domain, &message))
+    }
+
     /// Returns a message that user has to sign to send the transaction in the old format.
     /// If the transaction doesn't need a message signature, returns `None`.
     /// Needed for backwards compatibility.
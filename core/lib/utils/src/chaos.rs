@@ -0,0 +1,213 @@
+//! Fault injection points of the server actors, used to rehearse the failure handling in staging.
+//!
+//! Each point is named after the actor it's placed in, and applies the faults configured for it
+//! with the given probabilities: a random delay of the processing, dropping of the received
+//! message, or a simulated error (of the database or the Ethereum node) which is handled by the
+//! actor the same way as the real one. Points only apply the kinds of faults the actor can
+//! survive, e.g. the committer never drops the blocks it's requested to save.
+//!
+//! Faults are only injected by the builds with the `chaos` feature. Otherwise the injection points
+//! are no-ops, and the faults can't be configured.
+
+// Built-in deps
+use std::collections::HashMap;
+// External uses
+use anyhow::ensure;
+use serde::{Deserialize, Serialize};
+
+/// Actor the faults are injected into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultPoint {
+    /// Delays, drops and errors of the Ethereum watcher requests and polls.
+    EthWatch,
+    /// Delays of the commit requests and errors of the aggregated operations creation.
+    Committer,
+    /// Delays of the iterations and errors of the commitment steps of the operations.
+    EthSender,
+}
+
+/// Faults injected at the point.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FaultConfig {
+    /// Probability of delaying the processing.
+    pub delay_probability: f64,
+    /// Maximum delay in milliseconds, the actual one is uniformly distributed up to it.
+    pub max_delay_ms: u64,
+    /// Probability of dropping the received message.
+    pub drop_probability: f64,
+    /// Probability of the simulated error.
+    pub error_probability: f64,
+}
+
+impl FaultConfig {
+    fn validate(&self) -> anyhow::Result<()> {
+        for probability in &[
+            self.delay_probability,
+            self.drop_probability,
+            self.error_probability,
+        ] {
+            ensure!(
+                (0.0..=1.0).contains(probability),
+                "Fault probability must be within [0, 1], got {}",
+                probability
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Whether the faults can be injected by this build.
+pub fn is_enabled() -> bool {
+    cfg!(feature = "chaos")
+}
+
+/// Sets the faults injected at the point, replacing the previous ones.
+pub fn set_faults(point: FaultPoint, faults: FaultConfig) -> anyhow::Result<()> {
+    ensure!(
+        is_enabled(),
+        "Fault injection is not compiled in, the server must be built with the `chaos` feature"
+    );
+    faults.validate()?;
+    registry::set(point, faults);
+    Ok(())
+}
+
+/// Stops injecting the faults at every point.
+pub fn clear_faults() {
+    registry::clear();
+}
+
+/// Returns the faults injected at each point.
+pub fn configured_faults() -> HashMap<FaultPoint, FaultConfig> {
+    registry::all()
+}
+
+/// Delays the processing, if the delay is drawn for the point.
+pub async fn inject_delay(point: FaultPoint) {
+    if let Some(faults) = registry::get(point) {
+        if registry::draw(faults.delay_probability) {
+            let delay_ms = registry::uniform(faults.max_delay_ms);
+            vlog::warn!("Chaos: delaying {:?} by {}ms", point, delay_ms);
+            tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+        }
+    }
+}
+
+/// Checks whether the received message must be dropped.
+pub fn inject_drop(point: FaultPoint) -> bool {
+    let dropped = registry::get(point)
+        .map(|faults| registry::draw(faults.drop_probability))
+        .unwrap_or(false);
+    if dropped {
+        vlog::warn!("Chaos: dropping the message received by {:?}", point);
+    }
+    dropped
+}
+
+/// Returns the simulated error, if it's drawn for the point.
+pub fn inject_error(point: FaultPoint) -> anyhow::Result<()> {
+    if let Some(faults) = registry::get(point) {
+        ensure!(
+            !registry::draw(faults.error_probability),
+            "Chaos: simulated error of {:?}",
+            point
+        );
+    }
+    Ok(())
+}
+
+#[cfg(feature = "chaos")]
+mod registry {
+    use super::{FaultConfig, FaultPoint};
+    use once_cell::sync::Lazy;
+    use rand::Rng;
+    use std::{collections::HashMap, sync::RwLock};
+
+    static FAULTS: Lazy<RwLock<HashMap<FaultPoint, FaultConfig>>> = Lazy::new(Default::default);
+
+    pub fn set(point: FaultPoint, faults: FaultConfig) {
+        FAULTS.write().unwrap().insert(point, faults);
+    }
+
+    pub fn clear() {
+        FAULTS.write().unwrap().clear();
+    }
+
+    pub fn all() -> HashMap<FaultPoint, FaultConfig> {
+        FAULTS.read().unwrap().clone()
+    }
+
+    pub fn get(point: FaultPoint) -> Option<FaultConfig> {
+        FAULTS.read().unwrap().get(&point).copied()
+    }
+
+    pub fn draw(probability: f64) -> bool {
+        probability > 0.0 && rand::thread_rng().gen_bool(probability)
+    }
+
+    pub fn uniform(max: u64) -> u64 {
+        rand::thread_rng().gen_range(0..=max)
+    }
+}
+
+#[cfg(not(feature = "chaos"))]
+mod registry {
+    use super::{FaultConfig, FaultPoint};
+    use std::collections::HashMap;
+
+    pub fn set(_point: FaultPoint, _faults: FaultConfig) {}
+
+    pub fn clear() {}
+
+    pub fn all() -> HashMap<FaultPoint, FaultConfig> {
+        HashMap::new()
+    }
+
+    pub fn get(_point: FaultPoint) -> Option<FaultConfig> {
+        None
+    }
+
+    pub fn draw(_probability: f64) -> bool {
+        false
+    }
+
+    pub fn uniform(_max: u64) -> u64 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks the faults injection, whether or not it's compiled in.
+    #[tokio::test]
+    async fn faults_injection() {
+        let invalid = FaultConfig {
+            error_probability: 1.5,
+            ..Default::default()
+        };
+        assert!(set_faults(FaultPoint::EthWatch, invalid).is_err());
+
+        let faults = FaultConfig {
+            delay_probability: 1.0,
+            max_delay_ms: 1,
+            drop_probability: 1.0,
+            error_probability: 1.0,
+        };
+        assert_eq!(
+            set_faults(FaultPoint::EthWatch, faults).is_ok(),
+            is_enabled()
+        );
+        inject_delay(FaultPoint::EthWatch).await;
+        assert_eq!(inject_drop(FaultPoint::EthWatch), is_enabled());
+        assert_eq!(inject_error(FaultPoint::EthWatch).is_err(), is_enabled());
+        assert!(inject_error(FaultPoint::Committer).is_ok());
+
+        clear_faults();
+        assert!(configured_faults().is_empty());
+        assert!(!inject_drop(FaultPoint::EthWatch));
+    }
+}
@@ -1,11 +1,13 @@
 //! Various helpers used in the zkSync stack.
 
+pub mod chaos;
 mod convert;
 mod env_tools;
 mod format;
 mod macros;
 pub mod panic_notify;
 mod serde_wrappers;
+pub mod shutdown;
 mod string;
 
 pub use convert::*;
@@ -0,0 +1,152 @@
+//! Coordinated shutdown of the server actors.
+//!
+//! Once the shutdown is requested, the actors holding the [`ShutdownSignal`] stop accepting new
+//! work, and the ones registered as participants finish their in-flight work and report the
+//! completion. The server waits for the participants for a bounded time before exiting.
+
+// Built-in deps
+use std::time::Duration;
+// External uses
+use futures::future;
+use tokio::{
+    sync::{oneshot, watch},
+    time::{self, Instant},
+};
+
+/// Shared flag raised once the shutdown is requested.
+#[derive(Debug, Clone)]
+pub struct ShutdownSignal(watch::Receiver<bool>);
+
+impl Default for ShutdownSignal {
+    fn default() -> Self {
+        Self::never()
+    }
+}
+
+impl ShutdownSignal {
+    /// Creates the signal which is never raised.
+    pub fn never() -> Self {
+        let (_, receiver) = watch::channel(false);
+        Self(receiver)
+    }
+
+    pub fn is_requested(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    /// Resolves once the shutdown is requested.
+    pub async fn requested(&mut self) {
+        while !self.is_requested() {
+            if self.0.changed().await.is_err() {
+                // Coordinator is dropped without requesting the shutdown.
+                future::pending::<()>().await;
+            }
+        }
+    }
+}
+
+/// Actor which has to finish its work before the server exits.
+#[derive(Debug)]
+pub struct ShutdownParticipant {
+    signal: ShutdownSignal,
+    completion: oneshot::Sender<()>,
+}
+
+impl ShutdownParticipant {
+    pub fn is_requested(&self) -> bool {
+        self.signal.is_requested()
+    }
+
+    /// Resolves once the shutdown is requested.
+    pub async fn requested(&mut self) {
+        self.signal.requested().await
+    }
+
+    /// Reports that the actor has finished its work and may be stopped.
+    pub fn complete(self) {
+        self.completion.send(()).unwrap_or_default();
+    }
+}
+
+/// Coordinator of the shutdown, owned by the server.
+#[derive(Debug)]
+pub struct Shutdown {
+    sender: watch::Sender<bool>,
+    signal: ShutdownSignal,
+    participants: Vec<(&'static str, oneshot::Receiver<()>)>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        let (sender, receiver) = watch::channel(false);
+        Self {
+            sender,
+            signal: ShutdownSignal(receiver),
+            participants: Vec::new(),
+        }
+    }
+
+    pub fn signal(&self) -> ShutdownSignal {
+        self.signal.clone()
+    }
+
+    /// Registers the actor the server has to wait for on the shutdown.
+    pub fn participant(&mut self, name: &'static str) -> ShutdownParticipant {
+        let (completion, receiver) = oneshot::channel();
+        self.participants.push((name, receiver));
+        ShutdownParticipant {
+            signal: self.signal(),
+            completion,
+        }
+    }
+
+    /// Requests the shutdown and waits for the participants to complete, at most for `timeout`.
+    /// Returns the names of the participants which haven't completed in time, including the ones
+    /// dropped without completing, e.g. because of a panic.
+    pub async fn run(self, timeout: Duration) -> Vec<&'static str> {
+        self.sender.send(true).unwrap_or_default();
+
+        let deadline = Instant::now() + timeout;
+        let mut incomplete = Vec::new();
+        for (name, completion) in self.participants {
+            if !matches!(time::timeout_at(deadline, completion).await, Ok(Ok(()))) {
+                incomplete.push(name);
+            }
+        }
+        incomplete
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn shutdown_participants() {
+        let mut shutdown = Shutdown::new();
+        let mut signal = shutdown.signal();
+        let mut completing = shutdown.participant("completing");
+        let stuck = shutdown.participant("stuck");
+        let dropped = shutdown.participant("dropped");
+        assert!(!signal.is_requested());
+        assert!(!ShutdownSignal::never().is_requested());
+
+        let task = tokio::spawn(async move {
+            completing.requested().await;
+            completing.complete();
+        });
+        drop(dropped);
+
+        let incomplete = shutdown.run(Duration::from_millis(100)).await;
+        assert_eq!(incomplete, vec!["stuck", "dropped"]);
+        assert!(stuck.is_requested());
+        signal.requested().await;
+        task.await.unwrap();
+    }
+}
@@ -0,0 +1,89 @@
+//! Embedded Ethereum node for the end-to-end tests.
+//!
+//! The node is an `anvil` process (from Foundry) listening on a free local port, mining a block
+//! per transaction, with the test accounts derived from the test mnemonic and funded. The process
+//! is killed once the node is dropped, so each test gets a fresh chain.
+
+use std::net::TcpListener;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use web3::{transports::Http, Web3};
+use zksync_config::test_config::EthConfig;
+
+/// Time the node is given to start accepting the requests.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+const STARTUP_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Amount of the test accounts funded by the node, same as the amount printed by `zk run test-accounts`.
+const TEST_ACCOUNTS: usize = 10;
+/// Balance of each test account, in ether.
+const TEST_ACCOUNT_BALANCE: u64 = 1_000_000;
+
+#[derive(Debug)]
+pub struct AnvilNode {
+    process: Child,
+    url: String,
+}
+
+impl AnvilNode {
+    /// Spawns the node and waits until it starts accepting the requests.
+    pub async fn spawn(chain_id: u64) -> Self {
+        let port = free_port();
+        let process = Command::new("anvil")
+            .args(&[
+                "--host",
+                "127.0.0.1",
+                "--port",
+                &port.to_string(),
+                "--chain-id",
+                &chain_id.to_string(),
+                "--mnemonic",
+                &EthConfig::load().test_mnemonic,
+                "--accounts",
+                &TEST_ACCOUNTS.to_string(),
+                "--balance",
+                &TEST_ACCOUNT_BALANCE.to_string(),
+            ])
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .unwrap_or_else(|err| panic!("failed to spawn anvil, is it installed? {}", err));
+        let node = Self {
+            process,
+            url: format!("http://127.0.0.1:{}", port),
+        };
+        node.wait_for_startup().await;
+        node
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    async fn wait_for_startup(&self) {
+        let web3 = Web3::new(Http::new(&self.url).expect("http transport start"));
+        let started_at = Instant::now();
+        while web3.eth().block_number().await.is_err() {
+            assert!(
+                started_at.elapsed() < STARTUP_TIMEOUT,
+                "anvil hasn't started in {:?}",
+                STARTUP_TIMEOUT
+            );
+            tokio::time::sleep(STARTUP_POLL_INTERVAL).await;
+        }
+    }
+}
+
+impl Drop for AnvilNode {
+    fn drop(&mut self) {
+        self.process.kill().unwrap_or_default();
+        self.process.wait().ok();
+    }
+}
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("failed to find a free port")
+        .port()
+}
@@ -236,6 +236,7 @@ async fn main() {
         let proof_op = BlocksProofOperation {
             blocks: vec![block],
             proof: aggreagated_proof.serialize_aggregated_proof(),
+            proof_system: Default::default(),
         };
         test_setup
             .execute_verify_commitments(proof_op)
@@ -311,6 +312,7 @@ async fn main() {
         let proof_op = BlocksProofOperation {
             blocks,
             proof: aggregated_proof.serialize_aggregated_proof(),
+            proof_system: Default::default(),
         };
         let tx_receipt = test_setup
             .execute_verify_commitments(proof_op)
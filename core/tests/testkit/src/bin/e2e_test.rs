@@ -0,0 +1,81 @@
+//! Whole-pipeline regression test against the embedded Anvil node: the funds are deposited,
+//! transferred and withdrawn, and the resulting L1 and L2 balances are checked.
+
+use num::BigUint;
+use zksync_testkit::{
+    eth_account::parse_ether,
+    harness::TestHarness,
+    scenarios::{perform_basic_operations, BlockProcessing},
+    ETHAccountId, Token, ZKSyncAccountId,
+};
+use zksync_types::TokenId;
+
+#[tokio::main]
+async fn main() {
+    let mut harness = TestHarness::start().await;
+    let deposit_amount = parse_ether("1.0").unwrap();
+
+    perform_basic_operations(
+        TokenId(1),
+        &mut harness,
+        deposit_amount.clone(),
+        BlockProcessing::CommitAndVerify,
+    )
+    .await;
+    harness.assert_blocks_executed(4).await;
+
+    // Round trip of the funds: the recipient of the withdrawal doesn't send any L1 transactions,
+    // so its balance is known exactly.
+    let token = TokenId(0);
+    let amount = &deposit_amount / BigUint::from(2u32);
+    let recipient_balance = harness.get_eth_balance(ETHAccountId(2), token).await;
+
+    harness.start_block();
+    harness
+        .deposit(
+            ETHAccountId(1),
+            ZKSyncAccountId(3),
+            Token(token),
+            deposit_amount.clone(),
+        )
+        .await;
+    harness
+        .execute_commit_and_verify_block()
+        .await
+        .expect("deposit block execution failed");
+    harness
+        .assert_l2_balance(ZKSyncAccountId(3), token, &deposit_amount)
+        .await;
+
+    harness.start_block();
+    harness
+        .change_pubkey_with_tx(ZKSyncAccountId(3), Token(token), 0u32.into())
+        .await;
+    harness
+        .withdraw(
+            ZKSyncAccountId(3),
+            ETHAccountId(2),
+            Token(token),
+            amount.clone(),
+            0u32.into(),
+        )
+        .await;
+    let withdraw_block = harness
+        .execute_commit_and_verify_block()
+        .await
+        .expect("withdrawal block execution failed");
+    harness.assert_blocks_executed(6).await;
+    harness
+        .assert_l2_balance(ZKSyncAccountId(3), token, &(&deposit_amount - &amount))
+        .await;
+
+    harness
+        .assert_l1_balance(ETHAccountId(2), token, &(recipient_balance + &amount))
+        .await;
+    println!(
+        "End-to-end test success, last block: {}",
+        withdraw_block.block.block_number
+    );
+
+    harness.stop();
+}
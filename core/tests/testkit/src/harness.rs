@@ -0,0 +1,166 @@
+//! End-to-end test harness running the block pipeline against an embedded Ethereum node.
+//!
+//! The harness spawns the Anvil node, deploys the contracts for the genesis state and runs the
+//! state keeper in-process. Tests use the `TestSetup` methods to deposit, transfer and withdraw
+//! funds, commit and verify the blocks on L1 with the dummy proofs, and assert on the L1 state
+//! with the harness helpers. `anvil` and `zk` must be available, and the contracts built.
+
+use std::ops::{Deref, DerefMut};
+use std::thread::JoinHandle;
+
+use futures::channel::oneshot;
+use num::BigUint;
+use web3::transports::Http;
+use zksync_test_account::ZkSyncETHAccountData;
+use zksync_types::{Nonce, TokenId};
+
+use crate::{
+    anvil::AnvilNode,
+    eth_account::EthereumAccount,
+    external_commands::{deploy_contracts, get_test_accounts, Contracts},
+    zksync_account::ZkSyncAccount,
+};
+
+use super::*;
+
+pub struct TestHarness {
+    pub setup: TestSetup,
+    pub contracts: Contracts,
+    pub config: TestkitConfig,
+    state_keeper: Option<(JoinHandle<()>, oneshot::Sender<()>)>,
+    // Dropped last, so the node outlives the rest of the harness.
+    _node: AnvilNode,
+}
+
+impl TestHarness {
+    /// Spawns the node, deploys the contracts and starts the state keeper with the genesis state.
+    pub async fn start() -> Self {
+        let mut config = TestkitConfig::from_env();
+        let node = AnvilNode::spawn(config.chain_id).await;
+        // Contracts are deployed by the `zk` scripts, which take the node URL from the environment.
+        std::env::set_var("ETH_CLIENT_WEB3_URL", node.url());
+        config.web3_url = node.url().to_string();
+
+        let fee_account = ZkSyncAccount::rand();
+        let (sk_thread_handle, stop_state_keeper_sender, sk_channels) =
+            spawn_state_keeper(&fee_account.address, genesis_state(&fee_account.address));
+        let initial_root = genesis_state(&fee_account.address).state.root_hash();
+        let contracts = deploy_contracts(false, initial_root);
+
+        let transport = Http::new(&config.web3_url).expect("http transport start");
+        let eth_account = |private_key, address| {
+            EthereumAccount::new(
+                private_key,
+                address,
+                transport.clone(),
+                contracts.contract,
+                config.chain_id,
+                config.gas_price_factor,
+            )
+        };
+        let (test_accounts_info, commit_account_info) = get_test_accounts();
+        let commit_account =
+            eth_account(commit_account_info.private_key, commit_account_info.address);
+        let eth_accounts: Vec<_> = test_accounts_info
+            .into_iter()
+            .map(|info| eth_account(info.private_key, info.address))
+            .collect();
+
+        let mut zksync_accounts = vec![fee_account];
+        zksync_accounts.extend(eth_accounts.iter().map(|eth_account| {
+            ZkSyncAccount::new(
+                ZkSyncAccount::rand().private_key,
+                Nonce(0),
+                eth_account.address,
+                ZkSyncETHAccountData::EOA {
+                    eth_private_key: eth_account.private_key,
+                },
+            )
+        }));
+        let accounts = AccountSet {
+            eth_accounts,
+            zksync_accounts,
+            fee_account_id: ZKSyncAccountId(0),
+        };
+
+        let setup = TestSetup::new(
+            sk_channels,
+            accounts,
+            &contracts,
+            commit_account,
+            initial_root,
+            None,
+        );
+        Self {
+            setup,
+            contracts,
+            config,
+            state_keeper: Some((sk_thread_handle, stop_state_keeper_sender)),
+            _node: node,
+        }
+    }
+
+    /// Checks the L1 balance of the account, including the balance pending for the withdrawal.
+    pub async fn assert_l1_balance(
+        &self,
+        account: ETHAccountId,
+        token: TokenId,
+        expected: &BigUint,
+    ) {
+        let balance = self.setup.get_eth_balance(account, token).await;
+        assert_eq!(
+            &balance, expected,
+            "L1 balance of {:?} in the token {} doesn't match",
+            account, token
+        );
+    }
+
+    /// Checks the L2 balance of the account in the state of the last block.
+    pub async fn assert_l2_balance(
+        &self,
+        account: ZKSyncAccountId,
+        token: TokenId,
+        expected: &BigUint,
+    ) {
+        let balance = self
+            .setup
+            .get_zksync_account_committed_state(account)
+            .await
+            .map(|(_, account)| account.get_balance(token))
+            .unwrap_or_default();
+        assert_eq!(
+            &balance, expected,
+            "L2 balance of {:?} in the token {} doesn't match",
+            account, token
+        );
+    }
+
+    /// Checks that the blocks up to the given one are committed and executed on L1.
+    pub async fn assert_blocks_executed(&self, blocks: u64) {
+        let committed = self.setup.total_blocks_committed().await.unwrap();
+        let verified = self.setup.total_blocks_verified().await.unwrap();
+        assert_eq!((committed, verified), (blocks, blocks));
+    }
+
+    /// Stops the state keeper and the node.
+    pub fn stop(mut self) {
+        if let Some((thread_handle, stop_sender)) = self.state_keeper.take() {
+            stop_sender.send(()).expect("sk stop send");
+            thread_handle.join().expect("sk thread join");
+        }
+    }
+}
+
+impl Deref for TestHarness {
+    type Target = TestSetup;
+
+    fn deref(&self) -> &TestSetup {
+        &self.setup
+    }
+}
+
+impl DerefMut for TestHarness {
+    fn deref_mut(&mut self) -> &mut TestSetup {
+        &mut self.setup
+    }
+}
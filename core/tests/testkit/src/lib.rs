@@ -13,9 +13,11 @@ use zksync_crypto::params::{
 pub use zksync_test_account as zksync_account;
 
 pub mod account_set;
+pub mod anvil;
 pub mod data_restore;
 pub mod eth_account;
 pub mod external_commands;
+pub mod harness;
 pub mod scenarios;
 pub mod state_keeper_utils;
 pub mod test_setup;
@@ -1049,6 +1049,7 @@ impl TestSetup {
         let block_proof_op = BlocksProofOperation {
             blocks: blocks.to_vec(),
             proof,
+            proof_system: Default::default(),
         };
         self.commit_account
             .verify_block(&block_proof_op)
@@ -1104,6 +1105,7 @@ impl TestSetup {
         let block_proof_op = BlocksProofOperation {
             blocks: vec![new_block.clone()],
             proof,
+            proof_system: Default::default(),
         };
         let verify_result = self
             .commit_account
@@ -1,10 +1,10 @@
 import { Command } from 'commander';
 import * as utils from './utils';
 
+// The specification is generated from the API types, so it can't drift from the server.
 export async function buildDocs() {
-    await utils.spawn('api_docs');
-    await utils.spawn('api_docs compile');
-    await utils.spawn('api_docs generate-docs');
+    const output = `${process.env.ZKSYNC_HOME}/infrastructure/api-docs/openapi.json`;
+    await utils.spawn(`cargo run --release --bin openapi -- --output ${output}`);
 }
 
-export const command = new Command('api-docs').description('build api v0.2 documentation').action(buildDocs);
+export const command = new Command('api-docs').description('generate OpenAPI specification of api v0.3').action(buildDocs);
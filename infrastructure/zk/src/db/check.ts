@@ -0,0 +1,89 @@
+import { Command } from 'commander';
+import * as utils from '../utils';
+import * as env from '../env';
+import fs from 'fs';
+
+const MIGRATIONS_DIR = 'core/lib/storage/migrations';
+
+// Diesel stores the version of the migration as the digits of its directory name prefix,
+// e.g. `2021-01-01-120000_name` is recorded as `20210101120000`.
+function migrationVersion(dir: string) {
+    return dir.split('_')[0].replace(/-/g, '');
+}
+
+function localMigrations() {
+    const migrations = new Map<string, string>();
+    for (const dir of fs.readdirSync(MIGRATIONS_DIR).sort()) {
+        if (fs.existsSync(`${MIGRATIONS_DIR}/${dir}/up.sql`)) {
+            migrations.set(migrationVersion(dir), dir);
+        }
+    }
+    return migrations;
+}
+
+async function appliedMigrations() {
+    const { stdout } = await utils.exec(
+        `psql "${process.env.DATABASE_URL}" -At -c 'SELECT version FROM __diesel_schema_migrations ORDER BY version'`
+    );
+    return stdout.split('\n').filter((version) => version.length > 0);
+}
+
+// Applies the migrations within a transaction which is then rolled back, so the database is not changed.
+// With `ON_ERROR_STOP` psql exits on the first failed statement, which aborts the transaction as well.
+export async function dryRun(dirs: string[]) {
+    const files = dirs.map((dir) => `-f ${MIGRATIONS_DIR}/${dir}/up.sql`).join(' ');
+    await utils.exec(`psql "${process.env.DATABASE_URL}" -q -v ON_ERROR_STOP=1 -c 'BEGIN' ${files} -c 'ROLLBACK'`);
+}
+
+// Checks that the live database schema matches the migrations of the repository and the compile-time
+// sqlx metadata, so the drift is reported before the deployment rather than by the failing queries.
+export async function check(options: { dryRun: boolean }) {
+    // force read env
+    env.reload();
+
+    const local = localMigrations();
+    const applied = await appliedMigrations();
+    const pending = [...local.keys()].filter((version) => !applied.includes(version));
+    const unknown = applied.filter((version) => !local.has(version));
+    let drift = false;
+
+    if (unknown.length > 0) {
+        drift = true;
+        console.log(`Migrations applied to the database but missing in the repository: ${unknown.join(', ')}`);
+    }
+    if (pending.length > 0) {
+        const dirs = pending.map((version) => local.get(version) as string);
+        console.log(`Pending migrations:\n${dirs.map((dir) => `  ${dir}`).join('\n')}`);
+        if (options.dryRun) {
+            try {
+                await dryRun(dirs);
+                console.log('Pending migrations can be applied');
+            } catch (err) {
+                drift = true;
+                console.log(`Pending migrations fail to apply: ${err.stderr || err.message}`);
+            }
+        }
+        // The sqlx metadata describes the schema with all the migrations applied.
+        console.log('Skipping the sqlx metadata check until the pending migrations are applied');
+    } else {
+        console.log('All the migrations are applied');
+        process.chdir('core/lib/storage');
+        try {
+            await utils.exec('cargo sqlx prepare --check -- --tests');
+            console.log('sqlx metadata matches the database schema');
+        } catch (err) {
+            drift = true;
+            console.log(`sqlx metadata doesn't match the database schema: ${err.stderr || err.message}`);
+        }
+        process.chdir(process.env.ZKSYNC_HOME as string);
+    }
+
+    if (drift) {
+        throw new Error('Database schema drift is detected');
+    }
+}
+
+export const command = new Command('check')
+    .description('check the database schema against the migrations and the sqlx metadata')
+    .option('--dry-run', 'apply the pending migrations within the rolled back transaction')
+    .action(check);
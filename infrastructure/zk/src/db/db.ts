@@ -3,10 +3,11 @@ import * as utils from '../utils';
 import * as env from '../env';
 import fs from 'fs';
 
+import * as check from './check';
 import * as insert from './insert';
 import * as update from './update';
 
-export { insert, update };
+export { check, insert, update };
 
 const SQL = () => `psql "${process.env.DATABASE_URL}" -c`;
 
@@ -75,7 +76,8 @@ export async function wait(tries: number = 4) {
 export const command = new Command('db')
     .description('database management')
     .addCommand(update.command)
-    .addCommand(insert.command);
+    .addCommand(insert.command)
+    .addCommand(check.command);
 
 command.command('drop').description('drop the database').action(drop);
 command.command('migrate').description('run migrations').action(migrate);
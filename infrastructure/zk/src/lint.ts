@@ -37,6 +37,8 @@ async function clippy() {
     process.env.SQLX_OFFLINE = 'true';
     process.chdir(process.env.ZKSYNC_HOME as string);
     await utils.spawn('cargo clippy  --all --tests --benches -- -D warnings -A clippy::upper-case-acronyms');
+    // The signing primitives are also built without the prover parts for the browser wallets.
+    await utils.spawn('cargo clippy -p zksync_crypto --no-default-features -- -D warnings');
     delete process.env.SQLX_OFFLINE;
 
     process.chdir('sdk/zksync-crypto');
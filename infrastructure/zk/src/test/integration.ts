@@ -103,10 +103,9 @@ export async function all() {
 }
 
 export async function apiDocs() {
+    // Checks that the OpenAPI specification can be generated successfully.
+    await utils.spawn('cargo run --release --bin openapi > /dev/null');
     await utils.spawn('api_docs');
-    // Checks that documentation can be built successfully.
-    await utils.spawn('api_docs compile');
-    await utils.spawn('api_docs generate-docs');
     // Checks that response structures of endpoints match structures defined in the documentation.
     await utils.spawn('api_docs compile --test');
     await utils.spawn('api_docs test');
@@ -127,7 +126,9 @@ export async function withdrawalHelpers() {
 export async function testkit(command: string, timeout: number) {
     let containerID = '';
     const prevUrls = process.env.ETH_CLIENT_WEB3_URL?.split(',')[0];
-    if (process.env.ZKSYNC_ENV == 'dev' && process.env.CI != '1') {
+    // The end-to-end test spawns its own Anvil node.
+    const ownNode = command == 'e2e_test';
+    if (process.env.ZKSYNC_ENV == 'dev' && process.env.CI != '1' && !ownNode) {
         const { stdout } = await utils.exec('docker run --rm -d -p 7545:8545 matterlabs/geth:latest fast');
         containerID = stdout;
         process.env.ETH_CLIENT_WEB3_URL = 'http://localhost:7545';
@@ -150,7 +151,7 @@ export async function testkit(command: string, timeout: number) {
     // but be careful! this is not called upon explicit termination
     // e.g. on SIGINT or process.exit()
     process.on('beforeExit', async (code) => {
-        if (process.env.ZKSYNC_ENV == 'dev' && process.env.CI != '1') {
+        if (process.env.ZKSYNC_ENV == 'dev' && process.env.CI != '1' && !ownNode) {
             try {
                 // probably should be replaced with child_process.execSync in future
                 // to change the hook to program.on('exit', ...)
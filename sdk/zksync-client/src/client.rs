@@ -0,0 +1,203 @@
+//! Typed wrapper over the REST API v0.2 client.
+
+// Built-in uses
+use std::time::{Duration, Instant};
+// External uses
+use serde::de::DeserializeOwned;
+// Workspace uses
+use zksync_api_client::rest::client::Client as RestClient;
+use zksync_api_types::{
+    v02::{
+        account::AccountState,
+        fee::{ApiFee, ApiTxFeeTypes, TxInBatchFeeRequest},
+        token::ApiToken,
+        transaction::{Receipt, SubmitBatchResponse, TxData, TxWaitLevel},
+        Response,
+    },
+    TxWithSignature,
+};
+use zksync_types::{
+    tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash},
+    Address, TokenLike, ZkSyncTx,
+};
+// Local uses
+use crate::error::{ClientError, Result};
+
+/// Parameters of waiting for the transaction.
+#[derive(Debug, Clone, Copy)]
+pub struct WaitOptions {
+    /// Status the transaction is awaited for.
+    pub level: TxWaitLevel,
+    /// Time after which the waiting fails with the `OperationTimeout` error.
+    pub timeout: Duration,
+    /// Interval between the requests of the transaction status.
+    pub poll_interval: Duration,
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        Self {
+            level: TxWaitLevel::Committed,
+            timeout: Duration::from_secs(60),
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Extracts the result of the API call, failing if the server returned an error.
+fn decode<T: DeserializeOwned>(response: Response) -> Result<T> {
+    if let Some(error) = response.error {
+        return Err(ClientError::Api(error));
+    }
+    let result = response.result.unwrap_or(serde_json::Value::Null);
+    Ok(serde_json::from_value(result)?)
+}
+
+/// Client of the zkSync REST API returning the models shared with the server.
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: RestClient,
+}
+
+impl Client {
+    /// Creates the client of the server with the given URL, e.g. `https://api.zksync.io`.
+    pub fn new(url: String) -> Self {
+        Self {
+            inner: RestClient::new(url),
+        }
+    }
+
+    /// Returns the untyped client, e.g. to call the endpoints not covered by this one.
+    pub fn inner(&self) -> &RestClient {
+        &self.inner
+    }
+
+    /// Loads the committed and finalized states of the account.
+    pub async fn account(&self, address: Address) -> Result<AccountState> {
+        let response = self
+            .inner
+            .account_full_info(&format!("{:?}", address))
+            .await?;
+        decode(response)
+    }
+
+    /// Loads the token by its ID, address or symbol.
+    pub async fn token(&self, token: &TokenLike) -> Result<ApiToken> {
+        let response = self.inner.token_by_id(token).await?;
+        decode(response)
+    }
+
+    /// Requests the fee of the transaction paid in the given token.
+    pub async fn tx_fee(
+        &self,
+        tx_type: ApiTxFeeTypes,
+        address: Address,
+        token: TokenLike,
+    ) -> Result<ApiFee> {
+        let response = self.inner.get_txs_fee(tx_type, address, token).await?;
+        decode(response)
+    }
+
+    /// Requests the fee of the batch paid in the given token.
+    pub async fn batch_fee(
+        &self,
+        transactions: Vec<TxInBatchFeeRequest>,
+        token: TokenLike,
+    ) -> Result<ApiFee> {
+        let response = self.inner.get_batch_fee(transactions, token).await?;
+        decode(response)
+    }
+
+    /// Submits the signed transaction, returns its hash.
+    pub async fn submit_tx(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+    ) -> Result<TxHash> {
+        let response = self.inner.submit_tx(tx, signature).await?;
+        decode(response)
+    }
+
+    /// Submits the signed batch of the transactions.
+    pub async fn submit_batch(
+        &self,
+        txs: Vec<TxWithSignature>,
+        signature: Option<EthBatchSignatures>,
+    ) -> Result<SubmitBatchResponse> {
+        let response = self.inner.submit_batch(txs, signature).await?;
+        decode(response)
+    }
+
+    /// Returns the receipt of the transaction, or `None` if the server doesn't know it.
+    pub async fn tx_receipt(&self, tx_hash: TxHash) -> Result<Option<Receipt>> {
+        let response = self.inner.tx_status(tx_hash).await?;
+        decode(response)
+    }
+
+    /// Returns the transaction along with its signature.
+    pub async fn tx_data(&self, tx_hash: TxHash) -> Result<Option<TxData>> {
+        let response = self.inner.tx_data(tx_hash).await?;
+        decode(response)
+    }
+
+    /// Polls the status of the transaction until it reaches the awaited level or gets
+    /// rejected, returns the latest receipt.
+    pub async fn wait_for_tx(&self, tx_hash: TxHash, options: WaitOptions) -> Result<Receipt> {
+        let start = Instant::now();
+        let mut timer = tokio::time::interval(options.poll_interval);
+        loop {
+            timer.tick().await;
+            if let Some(receipt) = self.tx_receipt(tx_hash).await? {
+                if options.level.is_reached(receipt.status()) {
+                    return Ok(receipt);
+                }
+            }
+            if start.elapsed() >= options.timeout {
+                return Err(ClientError::OperationTimeout);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn response(value: serde_json::Value) -> Response {
+        let mut response = json!({
+            "request": {
+                "network": "localhost",
+                "apiVersion": "v02",
+                "resource": "/api/v0.2/transactions",
+                "args": {},
+                "timestamp": "2021-01-01T00:00:00Z",
+            },
+            "status": "success",
+            "error": null,
+            "result": null,
+        });
+        response
+            .as_object_mut()
+            .unwrap()
+            .extend(value.as_object().unwrap().clone());
+        serde_json::from_value(response).unwrap()
+    }
+
+    #[test]
+    fn decode_result() {
+        let tx_hash = TxHash::default();
+        let decoded: TxHash = decode(response(json!({ "result": tx_hash }))).unwrap();
+        assert_eq!(decoded, tx_hash);
+
+        let decoded: Option<Receipt> = decode(response(json!({}))).unwrap();
+        assert!(decoded.is_none());
+
+        let error = decode::<TxHash>(response(json!({
+            "status": "error",
+            "error": { "title": "Nonce mismatch" },
+        })))
+        .unwrap_err();
+        assert!(matches!(error, ClientError::Api(_)));
+    }
+}
@@ -0,0 +1,23 @@
+// External uses
+use thiserror::Error;
+// Workspace uses
+use zksync_api_client::rest::client::ClientError as TransportError;
+use zksync_eth_signer::error::SignerError;
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("Request failed: {0}")]
+    Transport(#[from] TransportError),
+    #[error("API error: {0}")]
+    Api(serde_json::Value),
+    #[error("Unable to decode server response: {0}")]
+    MalformedResponse(#[from] serde_json::Error),
+    #[error("Signing error: {0}")]
+    Signing(#[from] SignerError),
+    #[error("Account is not found")]
+    AccountNotFound,
+    #[error("Transaction hasn't reached the awaited status within the timeout")]
+    OperationTimeout,
+}
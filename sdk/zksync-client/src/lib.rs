@@ -0,0 +1,44 @@
+//! Typed async client of the zkSync REST API.
+//!
+//! The client speaks the API v0.2 in terms of the models shared with the server, so the requests
+//! and responses can't drift from what the server actually accepts and returns. On top of it, the
+//! [`Wallet`] builds the transactions, signs them with the zkSync key of the account along with
+//! the Ethereum 2FA signature, requests the fees and polls the statuses of the submitted
+//! transactions.
+//!
+//! ```no_run
+//! use zksync::{WalletCredentials, Network};
+//! use zksync_client::{Client, Wallet};
+//! use zksync_eth_signer::PrivateKeySigner;
+//! use zksync_types::{Address, H256};
+//!
+//! # async fn example(eth_private_key: H256) -> Result<(), Box<dyn std::error::Error>> {
+//! let eth_signer = PrivateKeySigner::new(eth_private_key);
+//! let address: Address = "0x2d5bd7a3f9a1a6bd0aa2fd6c7e3c2b9a4dd286e1".parse()?;
+//! let credentials =
+//!     WalletCredentials::from_eth_signer(address, eth_signer, Network::Rinkeby).await?;
+//!
+//! let client = Client::new("https://rinkeby-api.zksync.io".to_string());
+//! let wallet = Wallet::new(client, credentials).await?;
+//! let tx_hash = wallet
+//!     .transfer(address, &"ETH".into(), 1_000u32.into())
+//!     .await?;
+//! let _receipt = wallet.client().wait_for_tx(tx_hash, Default::default()).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! JSON-RPC API is served by the `RpcProvider` of the `zksync` crate.
+
+pub mod client;
+pub mod error;
+pub mod wallet;
+
+pub use crate::{
+    client::{Client, WaitOptions},
+    error::ClientError,
+    wallet::Wallet,
+};
+
+pub use zksync_api_types;
+pub use zksync_types;
@@ -0,0 +1,122 @@
+//! Account of the zkSync network building and signing its transactions.
+
+// External uses
+use num::BigUint;
+// Workspace uses
+use zksync::{signer::Signer, WalletCredentials};
+use zksync_api_types::v02::fee::ApiTxFeeTypes;
+use zksync_eth_signer::EthereumSigner;
+use zksync_types::{
+    tx::{PackedEthSignature, TimeRange, TxEthSignature, TxEthSignatureVariant, TxHash},
+    Address, Nonce, Token, TokenLike, ZkSyncTx,
+};
+// Local uses
+use crate::{
+    client::Client,
+    error::{ClientError, Result},
+};
+
+fn eth_signature(signature: Option<PackedEthSignature>) -> TxEthSignatureVariant {
+    TxEthSignatureVariant::Single(signature.map(TxEthSignature::EthereumSignature))
+}
+
+/// Wallet sending the transactions of the account via the REST API.
+///
+/// The fees and nonces of the transactions are requested from the server, so the wallet
+/// doesn't keep any state besides the keys of the account.
+#[derive(Debug)]
+pub struct Wallet<S: EthereumSigner> {
+    client: Client,
+    signer: Signer<S>,
+}
+
+impl<S: EthereumSigner> Wallet<S> {
+    /// Creates the wallet of the account with the given credentials. The account may not exist
+    /// yet, but it can't sign the transactions until it receives the funds.
+    pub async fn new(client: Client, credentials: WalletCredentials<S>) -> Result<Self> {
+        let mut signer = Signer::with_credentials(credentials);
+        let account = client.account(signer.address).await?;
+        signer.set_account_id(account.committed.map(|account| account.account_id));
+        Ok(Self { client, signer })
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn signer(&self) -> &Signer<S> {
+        &self.signer
+    }
+
+    pub fn address(&self) -> Address {
+        self.signer.address
+    }
+
+    /// Returns the nonce of the next transaction of the account.
+    pub async fn nonce(&self) -> Result<Nonce> {
+        let account = self.client.account(self.address()).await?;
+        account
+            .committed
+            .map(|account| account.nonce)
+            .ok_or(ClientError::AccountNotFound)
+    }
+
+    async fn token(&self, token: &TokenLike) -> Result<Token> {
+        let token = self.client.token(token).await?;
+        Ok(Token::new(
+            token.id,
+            token.address,
+            &token.symbol,
+            token.decimals,
+            Default::default(),
+        ))
+    }
+
+    /// Transfers the funds to the given address, paying the fee in the same token.
+    pub async fn transfer(
+        &self,
+        to: Address,
+        token: &TokenLike,
+        amount: BigUint,
+    ) -> Result<TxHash> {
+        let token = self.token(token).await?;
+        let fee = self
+            .client
+            .tx_fee(ApiTxFeeTypes::Transfer, to, token.id.into())
+            .await?
+            .total_fee;
+        let nonce = self.nonce().await?;
+
+        let (transfer, signature) = self
+            .signer
+            .sign_transfer(token, amount, fee, to, nonce, TimeRange::default())
+            .await?;
+        self.client
+            .submit_tx(ZkSyncTx::from(transfer), eth_signature(signature))
+            .await
+    }
+
+    /// Withdraws the funds to the given Ethereum address, paying the fee in the same token.
+    pub async fn withdraw(
+        &self,
+        to: Address,
+        token: &TokenLike,
+        amount: BigUint,
+    ) -> Result<TxHash> {
+        let token = self.token(token).await?;
+        let fee = self
+            .client
+            .tx_fee(ApiTxFeeTypes::Withdraw, to, token.id.into())
+            .await?
+            .total_fee;
+        let nonce = self.nonce().await?;
+
+        let (withdraw, signature) = self
+            .signer
+            .sign_withdraw(token, amount, fee, to, nonce, TimeRange::default())
+            .await?;
+        self.client
+            .submit_tx(ZkSyncTx::from(withdraw), eth_signature(signature))
+            .await
+    }
+}
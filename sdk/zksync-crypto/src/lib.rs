@@ -44,6 +44,7 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 pub fn zksync_crypto_init() {
     JUBJUB_PARAMS.with(|_| {});
     RESCUE_PARAMS.with(|_| {});
+    let _ = &*crypto_lib::params::RESCUE_HASHER;
     set_panic_hook();
 }
 
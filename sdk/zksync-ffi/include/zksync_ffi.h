@@ -0,0 +1,77 @@
+/*
+ * C bindings for the zkSync transaction signing, see `src/lib.rs` for the details.
+ *
+ * Transactions and orders are passed as NUL-terminated JSON strings in the format accepted by
+ * the API. Strings returned by the library must be released with `zksync_string_free`.
+ */
+
+#ifndef ZKSYNC_FFI_H
+#define ZKSYNC_FFI_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define ZKSYNC_PRIVATE_KEY_LEN 32
+#define ZKSYNC_PUBKEY_HASH_LEN 20
+#define ZKSYNC_TX_HASH_LEN 32
+#define ZKSYNC_SIGNATURE_LEN 96
+
+typedef enum {
+    ZKSYNC_OK = 0,
+    ZKSYNC_NULL_POINTER = 1,
+    ZKSYNC_SEED_TOO_SHORT = 2,
+    ZKSYNC_INVALID_PRIVATE_KEY = 3,
+    ZKSYNC_INVALID_STRING = 4,
+    ZKSYNC_INVALID_TX = 5,
+    ZKSYNC_MESSAGE_TOO_LONG = 6,
+    ZKSYNC_BUFFER_TOO_SMALL = 7,
+    ZKSYNC_NO_ETH_MESSAGE = 8,
+    ZKSYNC_PANIC = 9,
+} ZksyncStatus;
+
+/* Derives the private key from the seed of at least 32 bytes. */
+ZksyncStatus zksync_private_key_from_seed(const uint8_t *seed, size_t seed_len,
+                                          uint8_t out_private_key[ZKSYNC_PRIVATE_KEY_LEN]);
+
+/* Computes the public key hash set as the signing key of the account. */
+ZksyncStatus zksync_pubkey_hash(const uint8_t private_key[ZKSYNC_PRIVATE_KEY_LEN],
+                                uint8_t out_pubkey_hash[ZKSYNC_PUBKEY_HASH_LEN]);
+
+/* Signs the message, writing the packed public key followed by the packed signature. */
+ZksyncStatus zksync_sign_musig(const uint8_t private_key[ZKSYNC_PRIVATE_KEY_LEN],
+                               const uint8_t *msg, size_t msg_len,
+                               uint8_t out_signature[ZKSYNC_SIGNATURE_LEN]);
+
+/*
+ * Writes the message signed for the transaction. `inout_len` is the length of the buffer on
+ * input and the length of the message on output, even if `ZKSYNC_BUFFER_TOO_SMALL` is returned.
+ */
+ZksyncStatus zksync_tx_message(const char *tx_json, uint8_t *out_message, size_t *inout_len);
+
+/* Computes the hash of the transaction. */
+ZksyncStatus zksync_tx_hash(const char *tx_json, uint8_t out_hash[ZKSYNC_TX_HASH_LEN]);
+
+/* Returns the message to sign with the Ethereum key to authorize the transaction. */
+ZksyncStatus zksync_tx_eth_message(const char *tx_json, const char *token_symbol,
+                                   uint8_t token_decimals, char **out_message);
+
+/* Returns the JSON of the transaction with the signature set. */
+ZksyncStatus zksync_sign_tx(const uint8_t private_key[ZKSYNC_PRIVATE_KEY_LEN],
+                            const char *tx_json, char **out_tx_json);
+
+/* Returns the JSON of the swap order with the signature set. */
+ZksyncStatus zksync_sign_order(const uint8_t private_key[ZKSYNC_PRIVATE_KEY_LEN],
+                               const char *order_json, char **out_order_json);
+
+/* Releases the string returned by the library, does nothing for null. */
+void zksync_string_free(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ZKSYNC_FFI_H */
@@ -0,0 +1,376 @@
+//! C bindings for the zkSync transaction signing.
+//!
+//! Mobile wallets link this library instead of reimplementing the byte layouts of the
+//! transactions, so the messages they sign are produced by the same code the server uses to
+//! verify the signatures. The C declarations are in `include/zksync_ffi.h`.
+//!
+//! Transactions are passed as NUL-terminated JSON strings in the format accepted by the API.
+//! Every function returns `ZksyncStatus` and writes its result to the buffers provided by the
+//! caller, except for the strings, which are allocated by the library and must be released with
+//! `zksync_string_free`.
+
+// Built-in deps
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    panic, slice,
+};
+// External uses
+use sha2::{Digest, Sha256};
+// Workspace uses
+use zksync_crypto::{
+    bellman::{pairing::ff::PrimeField, PrimeFieldRepr},
+    franklin_crypto::alt_babyjubjub::fs::FsRepr,
+    params::PAD_MSG_BEFORE_HASH_BITS_LEN,
+    priv_key_from_fs, Fs, PrivateKey,
+};
+use zksync_types::{tx::TxSignature, Address, Order, PubKeyHash, Token, TokenKind, ZkSyncTx};
+
+#[cfg(test)]
+mod tests;
+
+/// Length of the private key in bytes.
+pub const ZKSYNC_PRIVATE_KEY_LEN: usize = 32;
+/// Length of the public key hash in bytes.
+pub const ZKSYNC_PUBKEY_HASH_LEN: usize = 20;
+/// Length of the transaction hash in bytes.
+pub const ZKSYNC_TX_HASH_LEN: usize = 32;
+/// Length of the packed public key followed by the packed signature in bytes.
+pub const ZKSYNC_SIGNATURE_LEN: usize = 96;
+
+/// Result of the call, `Ok` unless the call failed.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZksyncStatus {
+    Ok = 0,
+    /// One of the required pointers is null.
+    NullPointer = 1,
+    /// Seed is shorter than 32 bytes.
+    SeedTooShort = 2,
+    /// Bytes don't represent the private key.
+    InvalidPrivateKey = 3,
+    /// String is not valid UTF-8.
+    InvalidString = 4,
+    /// JSON doesn't represent the transaction or the order.
+    InvalidTx = 5,
+    /// Message is longer than the signed messages can be.
+    MessageTooLong = 6,
+    /// Output buffer is too small, the required length is written instead.
+    BufferTooSmall = 7,
+    /// Transaction has no Ethereum message to sign.
+    NoEthMessage = 8,
+    /// Library has panicked, which is a bug.
+    Panic = 9,
+}
+
+type FfiResult<T = ()> = Result<T, ZksyncStatus>;
+
+/// Runs the body of the exported function, so the panics never unwind across the FFI boundary.
+fn guard(body: impl FnOnce() -> FfiResult + panic::UnwindSafe) -> ZksyncStatus {
+    match panic::catch_unwind(body) {
+        Ok(Ok(())) => ZksyncStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => ZksyncStatus::Panic,
+    }
+}
+
+unsafe fn input<'a>(data: *const u8, len: usize) -> FfiResult<&'a [u8]> {
+    if data.is_null() {
+        return Err(ZksyncStatus::NullPointer);
+    }
+    Ok(slice::from_raw_parts(data, len))
+}
+
+unsafe fn write_output(out: *mut u8, value: &[u8]) -> FfiResult {
+    if out.is_null() {
+        return Err(ZksyncStatus::NullPointer);
+    }
+    slice::from_raw_parts_mut(out, value.len()).copy_from_slice(value);
+    Ok(())
+}
+
+unsafe fn read_str<'a>(value: *const c_char) -> FfiResult<&'a str> {
+    if value.is_null() {
+        return Err(ZksyncStatus::NullPointer);
+    }
+    CStr::from_ptr(value)
+        .to_str()
+        .map_err(|_| ZksyncStatus::InvalidString)
+}
+
+unsafe fn write_string(out: *mut *mut c_char, value: String) -> FfiResult {
+    if out.is_null() {
+        return Err(ZksyncStatus::NullPointer);
+    }
+    let value = CString::new(value).map_err(|_| ZksyncStatus::InvalidString)?;
+    *out = value.into_raw();
+    Ok(())
+}
+
+unsafe fn read_tx(tx_json: *const c_char) -> FfiResult<ZkSyncTx> {
+    serde_json::from_str(read_str(tx_json)?).map_err(|_| ZksyncStatus::InvalidTx)
+}
+
+unsafe fn read_private_key(private_key: *const u8) -> FfiResult<PrivateKey> {
+    let mut fs_repr = FsRepr::default();
+    fs_repr
+        .read_be(input(private_key, ZKSYNC_PRIVATE_KEY_LEN)?)
+        .map_err(|_| ZksyncStatus::InvalidPrivateKey)?;
+    Fs::from_repr(fs_repr)
+        .map(priv_key_from_fs)
+        .map_err(|_| ZksyncStatus::InvalidPrivateKey)
+}
+
+/// Derives the private key the same way as `zksync.js` and the Rust SDK: the seed is hashed
+/// with `sha256` twice, and the hashing is repeated until the output is a valid private key.
+fn private_key_from_seed(seed: &[u8]) -> FfiResult<Vec<u8>> {
+    if seed.len() < 32 {
+        return Err(ZksyncStatus::SeedTooShort);
+    }
+
+    let sha256_bytes = |input: &[u8]| {
+        let mut hasher = Sha256::new();
+        hasher.input(input);
+        hasher.result().to_vec()
+    };
+
+    let mut effective_seed = sha256_bytes(seed);
+    loop {
+        let raw_priv_key = sha256_bytes(&effective_seed);
+        let mut fs_repr = FsRepr::default();
+        fs_repr
+            .read_be(&raw_priv_key[..])
+            .expect("failed to read raw_priv_key");
+        if Fs::from_repr(fs_repr).is_ok() {
+            return Ok(raw_priv_key);
+        }
+        effective_seed = raw_priv_key;
+    }
+}
+
+fn sign_musig(private_key: &PrivateKey, msg: &[u8]) -> FfiResult<TxSignature> {
+    if msg.len() * 8 > PAD_MSG_BEFORE_HASH_BITS_LEN {
+        return Err(ZksyncStatus::MessageTooLong);
+    }
+    Ok(TxSignature::sign_musig(private_key, msg))
+}
+
+/// Returns the message signed by the owner of the account. The swaps commit to the hash of the
+/// orders instead of the orders themselves, since they're too long to be signed.
+fn tx_message(tx: &ZkSyncTx) -> Vec<u8> {
+    match tx {
+        ZkSyncTx::Swap(tx) => tx.get_sign_bytes(),
+        tx => tx.get_bytes(),
+    }
+}
+
+fn set_signature(tx: &mut ZkSyncTx, signature: TxSignature) {
+    match tx {
+        ZkSyncTx::Transfer(tx) => tx.signature = signature,
+        ZkSyncTx::Withdraw(tx) => tx.signature = signature,
+        ZkSyncTx::Close(tx) => tx.signature = signature,
+        ZkSyncTx::ChangePubKey(tx) => tx.signature = signature,
+        ZkSyncTx::ForcedExit(tx) => tx.signature = signature,
+        ZkSyncTx::MintNFT(tx) => tx.signature = signature,
+        ZkSyncTx::Swap(tx) => tx.signature = signature,
+        ZkSyncTx::WithdrawNFT(tx) => tx.signature = signature,
+    }
+}
+
+/// Derives the private key from the seed, e.g. the Ethereum signature of the fixed message,
+/// and writes its 32 bytes to `out_private_key`.
+///
+/// # Safety
+///
+/// `seed` must point to `seed_len` readable bytes, `out_private_key` to 32 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn zksync_private_key_from_seed(
+    seed: *const u8,
+    seed_len: usize,
+    out_private_key: *mut u8,
+) -> ZksyncStatus {
+    guard(|| {
+        let private_key = private_key_from_seed(input(seed, seed_len)?)?;
+        write_output(out_private_key, &private_key)
+    })
+}
+
+/// Writes the 20 bytes of the public key hash of the private key to `out_pubkey_hash`,
+/// which is set as the signing key of the account by the `ChangePubKey` transaction.
+///
+/// # Safety
+///
+/// `private_key` must point to 32 readable bytes, `out_pubkey_hash` to 20 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn zksync_pubkey_hash(
+    private_key: *const u8,
+    out_pubkey_hash: *mut u8,
+) -> ZksyncStatus {
+    guard(|| {
+        let private_key = read_private_key(private_key)?;
+        write_output(
+            out_pubkey_hash,
+            &PubKeyHash::from_privkey(&private_key).data,
+        )
+    })
+}
+
+/// Signs the message with the musig Schnorr signature and writes the 32 bytes of the packed
+/// public key followed by the 64 bytes of the packed signature to `out_signature`, the same
+/// layout as the signatures of `zksync.js`.
+///
+/// # Safety
+///
+/// `private_key` must point to 32 readable bytes, `msg` to `msg_len` readable bytes and
+/// `out_signature` to 96 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn zksync_sign_musig(
+    private_key: *const u8,
+    msg: *const u8,
+    msg_len: usize,
+    out_signature: *mut u8,
+) -> ZksyncStatus {
+    guard(|| {
+        let private_key = read_private_key(private_key)?;
+        let signature = sign_musig(&private_key, input(msg, msg_len)?)?;
+        let mut packed = signature
+            .pub_key
+            .serialize_packed()
+            .expect("failed to pack the public key");
+        packed.extend(
+            signature
+                .signature
+                .serialize_packed()
+                .expect("failed to pack the signature"),
+        );
+        write_output(out_signature, &packed)
+    })
+}
+
+/// Writes the message signed by `zksync_sign_tx` for the transaction to `out_message`.
+/// On input `inout_len` is the length of the buffer, on output the length of the message.
+/// If the buffer is too small, nothing but the length is written.
+///
+/// # Safety
+///
+/// `tx_json` must be a NUL-terminated string, `out_message` must point to `*inout_len`
+/// writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn zksync_tx_message(
+    tx_json: *const c_char,
+    out_message: *mut u8,
+    inout_len: *mut usize,
+) -> ZksyncStatus {
+    guard(|| {
+        if inout_len.is_null() {
+            return Err(ZksyncStatus::NullPointer);
+        }
+        let message = tx_message(&read_tx(tx_json)?);
+        let capacity = *inout_len;
+        *inout_len = message.len();
+        if capacity < message.len() {
+            return Err(ZksyncStatus::BufferTooSmall);
+        }
+        write_output(out_message, &message)
+    })
+}
+
+/// Writes the 32 bytes of the transaction hash to `out_hash`.
+///
+/// # Safety
+///
+/// `tx_json` must be a NUL-terminated string, `out_hash` must point to 32 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn zksync_tx_hash(tx_json: *const c_char, out_hash: *mut u8) -> ZksyncStatus {
+    guard(|| {
+        let tx = read_tx(tx_json)?;
+        write_output(out_hash, tx.hash().as_ref())
+    })
+}
+
+/// Returns the message the owner of the account signs with the Ethereum key to authorize the
+/// transaction, given the symbol and the decimals of the transferred token.
+/// The message must be released with `zksync_string_free`.
+///
+/// # Safety
+///
+/// `tx_json` and `token_symbol` must be NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn zksync_tx_eth_message(
+    tx_json: *const c_char,
+    token_symbol: *const c_char,
+    token_decimals: u8,
+    out_message: *mut *mut c_char,
+) -> ZksyncStatus {
+    guard(|| {
+        let tx = read_tx(tx_json)?;
+        let token = Token::new(
+            tx.token_id(),
+            Address::zero(),
+            read_str(token_symbol)?,
+            token_decimals,
+            TokenKind::ERC20,
+        );
+        let message = tx
+            .get_ethereum_sign_message(token)
+            .ok_or(ZksyncStatus::NoEthMessage)?;
+        write_string(out_message, message)
+    })
+}
+
+/// Signs the transaction and returns its JSON with the signature set, ready to be submitted
+/// along with the Ethereum signature if one is required.
+/// The JSON must be released with `zksync_string_free`.
+///
+/// # Safety
+///
+/// `private_key` must point to 32 readable bytes, `tx_json` must be a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn zksync_sign_tx(
+    private_key: *const u8,
+    tx_json: *const c_char,
+    out_tx_json: *mut *mut c_char,
+) -> ZksyncStatus {
+    guard(|| {
+        let private_key = read_private_key(private_key)?;
+        let mut tx = read_tx(tx_json)?;
+        let signature = sign_musig(&private_key, &tx_message(&tx))?;
+        set_signature(&mut tx, signature);
+        let tx_json = serde_json::to_string(&tx).expect("failed to serialize the tx");
+        write_string(out_tx_json, tx_json)
+    })
+}
+
+/// Signs the order of the swap and returns its JSON with the signature set.
+/// The JSON must be released with `zksync_string_free`.
+///
+/// # Safety
+///
+/// `private_key` must point to 32 readable bytes, `order_json` must be a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn zksync_sign_order(
+    private_key: *const u8,
+    order_json: *const c_char,
+    out_order_json: *mut *mut c_char,
+) -> ZksyncStatus {
+    guard(|| {
+        let private_key = read_private_key(private_key)?;
+        let mut order: Order =
+            serde_json::from_str(read_str(order_json)?).map_err(|_| ZksyncStatus::InvalidTx)?;
+        order.signature = sign_musig(&private_key, &order.get_bytes())?;
+        let order_json = serde_json::to_string(&order).expect("failed to serialize the order");
+        write_string(out_order_json, order_json)
+    })
+}
+
+/// Releases the string returned by the library.
+///
+/// # Safety
+///
+/// `value` must be returned by the library and not released yet, or be null.
+#[no_mangle]
+pub unsafe extern "C" fn zksync_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
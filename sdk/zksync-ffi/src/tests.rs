@@ -0,0 +1,145 @@
+use std::{ffi::CString, ptr};
+
+use zksync_types::{
+    tx::{TimeRange, TxSignature},
+    AccountId, Address, Nonce, PubKeyHash, TokenId, Transfer, ZkSyncTx,
+};
+
+use super::*;
+
+fn private_key() -> ([u8; ZKSYNC_PRIVATE_KEY_LEN], PrivateKey) {
+    let seed = [1u8; 32];
+    let mut bytes = [0u8; ZKSYNC_PRIVATE_KEY_LEN];
+    let status =
+        unsafe { zksync_private_key_from_seed(seed.as_ptr(), seed.len(), bytes.as_mut_ptr()) };
+    assert_eq!(status, ZksyncStatus::Ok);
+    let private_key = unsafe { read_private_key(bytes.as_ptr()) }.unwrap();
+    (bytes, private_key)
+}
+
+fn transfer(private_key: &PrivateKey) -> Transfer {
+    Transfer::new_signed(
+        AccountId(1),
+        Address::repeat_byte(0x01),
+        Address::repeat_byte(0x02),
+        TokenId(0),
+        100u32.into(),
+        10u32.into(),
+        Nonce(3),
+        TimeRange::default(),
+        private_key,
+    )
+    .unwrap()
+}
+
+unsafe fn take_string(value: *mut c_char) -> String {
+    let string = CStr::from_ptr(value).to_str().unwrap().to_owned();
+    zksync_string_free(value);
+    string
+}
+
+/// Checks that the signed transaction is the same as the one signed by the server code.
+#[test]
+fn sign_tx() {
+    let (key_bytes, private_key) = private_key();
+    let expected = transfer(&private_key);
+    let mut unsigned = expected.clone();
+    unsigned.signature = TxSignature::default();
+    let tx_json = serde_json::to_string(&ZkSyncTx::from(unsigned)).unwrap();
+    let tx_json = CString::new(tx_json).unwrap();
+
+    let mut signed_json = ptr::null_mut();
+    let status = unsafe { zksync_sign_tx(key_bytes.as_ptr(), tx_json.as_ptr(), &mut signed_json) };
+    assert_eq!(status, ZksyncStatus::Ok);
+    let signed: ZkSyncTx = serde_json::from_str(&unsafe { take_string(signed_json) }).unwrap();
+    assert_eq!(
+        signed.signature().signature.serialize_packed().unwrap(),
+        expected.signature.signature.serialize_packed().unwrap()
+    );
+
+    let mut pubkey_hash = [0u8; ZKSYNC_PUBKEY_HASH_LEN];
+    let status = unsafe { zksync_pubkey_hash(key_bytes.as_ptr(), pubkey_hash.as_mut_ptr()) };
+    assert_eq!(status, ZksyncStatus::Ok);
+    match signed {
+        ZkSyncTx::Transfer(tx) => assert_eq!(
+            tx.verify_signature().map(|(pubkey_hash, _)| pubkey_hash),
+            Some(PubKeyHash { data: pubkey_hash })
+        ),
+        tx => panic!("Unexpected tx: {:?}", tx),
+    }
+}
+
+/// Checks that the message of the transaction is written only if it fits into the buffer.
+#[test]
+fn tx_message() {
+    let (_, private_key) = private_key();
+    let tx = transfer(&private_key);
+    let tx_json =
+        CString::new(serde_json::to_string(&ZkSyncTx::from(tx.clone())).unwrap()).unwrap();
+
+    let mut message = vec![0u8; 8];
+    let mut len = message.len();
+    let status = unsafe { zksync_tx_message(tx_json.as_ptr(), message.as_mut_ptr(), &mut len) };
+    assert_eq!(status, ZksyncStatus::BufferTooSmall);
+    assert_eq!(len, tx.get_bytes().len());
+
+    message.resize(len, 0);
+    let status = unsafe { zksync_tx_message(tx_json.as_ptr(), message.as_mut_ptr(), &mut len) };
+    assert_eq!(status, ZksyncStatus::Ok);
+    assert_eq!(message, tx.get_bytes());
+
+    let mut hash = [0u8; ZKSYNC_TX_HASH_LEN];
+    let status = unsafe { zksync_tx_hash(tx_json.as_ptr(), hash.as_mut_ptr()) };
+    assert_eq!(status, ZksyncStatus::Ok);
+    assert_eq!(&hash[..], ZkSyncTx::from(tx).hash().as_ref());
+}
+
+/// Checks that the signature of the raw message is verified by the server code.
+#[test]
+fn sign_musig() {
+    let (key_bytes, _) = private_key();
+    let msg = b"message";
+    let mut signature = [0u8; ZKSYNC_SIGNATURE_LEN];
+    let status = unsafe {
+        zksync_sign_musig(
+            key_bytes.as_ptr(),
+            msg.as_ptr(),
+            msg.len(),
+            signature.as_mut_ptr(),
+        )
+    };
+    assert_eq!(status, ZksyncStatus::Ok);
+    let signature = TxSignature::deserialize_from_packed_bytes(&signature).unwrap();
+    assert!(signature.verify_musig(msg).is_some());
+
+    let long_msg = [0u8; 100];
+    let status = unsafe {
+        zksync_sign_musig(
+            key_bytes.as_ptr(),
+            long_msg.as_ptr(),
+            long_msg.len(),
+            signature.as_mut_ptr(),
+        )
+    };
+    assert_eq!(status, ZksyncStatus::MessageTooLong);
+}
+
+/// Checks that the invalid arguments are reported instead of panicking.
+#[test]
+fn invalid_arguments() {
+    let (key_bytes, _) = private_key();
+    let mut out = ptr::null_mut();
+    let tx_json = CString::new("{}").unwrap();
+    let status = unsafe { zksync_sign_tx(key_bytes.as_ptr(), tx_json.as_ptr(), &mut out) };
+    assert_eq!(status, ZksyncStatus::InvalidTx);
+    let status = unsafe { zksync_sign_tx(ptr::null(), tx_json.as_ptr(), &mut out) };
+    assert_eq!(status, ZksyncStatus::NullPointer);
+    assert!(out.is_null());
+
+    let short_seed = [1u8; 16];
+    let mut key = [0u8; ZKSYNC_PRIVATE_KEY_LEN];
+    let status = unsafe {
+        zksync_private_key_from_seed(short_seed.as_ptr(), short_seed.len(), key.as_mut_ptr())
+    };
+    assert_eq!(status, ZksyncStatus::SeedTooShort);
+}
@@ -9,7 +9,7 @@ use web3::contract::Options;
 use web3::transports::Http;
 use web3::types::{TransactionReceipt, H160, H256, U256};
 
-use zksync_eth_client::ETHDirectClient;
+use zksync_eth_client::{ens, ETHDirectClient};
 use zksync_eth_signer::EthereumSigner;
 use zksync_types::{AccountId, Address, PriorityOp, PriorityOpId, TokenId, TokenLike};
 
@@ -148,6 +148,43 @@ impl<S: EthereumSigner> EthereumProvider<S> {
         Ok(res)
     }
 
+    /// Returns the address the ENS name points to, `None` if the name is not resolved.
+    /// The resolved address should be confirmed by the user before sending funds to it.
+    pub async fn resolve_ens_name(&self, name: &str) -> Result<Option<Address>, ClientError> {
+        let node = ens::namehash(&name.to_lowercase());
+        let resolver: Address = self
+            .eth_client
+            .call_contract_function(
+                "resolver",
+                node,
+                None,
+                Options::default(),
+                None,
+                ens::ens_registry_address(),
+                ens::ens_registry_contract(),
+            )
+            .await
+            .map_err(|err| ClientError::NetworkError(err.to_string()))?;
+        if resolver.is_zero() {
+            return Ok(None);
+        }
+
+        let address: Address = self
+            .eth_client
+            .call_contract_function(
+                "addr",
+                node,
+                None,
+                Options::default(),
+                None,
+                resolver,
+                ens::ens_resolver_contract(),
+            )
+            .await
+            .map_err(|err| ClientError::NetworkError(err.to_string()))?;
+        Ok(Some(address).filter(|address| !address.is_zero()))
+    }
+
     /// Returns the pending nonce for the Ethereum account.
     pub async fn nonce(&self) -> Result<U256, ClientError> {
         self.client()
@@ -119,6 +119,9 @@ impl<S: EthereumSigner> Signer<S> {
                 TxEthSignature::EIP1271Signature(..) => Err(SignerError::CustomError(
                     "Can't sign ChangePubKey message with EIP1271 signer".to_string(),
                 )),
+                TxEthSignature::EIP712Signature(..) => Err(SignerError::CustomError(
+                    "Can't sign ChangePubKey message with EIP712 signature".to_string(),
+                )),
             }?;
 
             ChangePubKeyEthAuthData::ECDSA(ChangePubKeyECDSAData {
@@ -505,6 +505,7 @@ export interface ApiL2TxReceipt {
     rollupBlock?: number;
     status: L2TxStatus;
     failReason?: string;
+    ethTxHash?: string;
 }
 
 export type ApiTxReceipt = ApiL1TxReceipt | ApiL2TxReceipt;